@@ -5,18 +5,23 @@ pragma solidity ^0.8.24;
 import {AccessControl} from "@openzeppelin/contracts/access/AccessControl.sol";
 import {ERC20} from "@openzeppelin/contracts/token/ERC20/ERC20.sol";
 import {ERC20Burnable} from "@openzeppelin/contracts/token/ERC20/extensions/ERC20Burnable.sol";
+import {ERC20Permit} from "@openzeppelin/contracts/token/ERC20/extensions/ERC20Permit.sol";
 import {Pausable} from "@openzeppelin/contracts/utils/Pausable.sol";
 
 /// @title RelationalEuro
 /// @notice Managed EUR stablecoin profile for initial deployment and demos.
-/// @dev Non-upgradeable v1 token with role-based minting and pausing.
-contract RelationalEuro is ERC20, ERC20Burnable, Pausable, AccessControl {
+/// @dev Non-upgradeable v1 token with role-based minting and pausing. Supports
+///      EIP-2612 permits so wallets can approve spenders with a signature.
+contract RelationalEuro is ERC20, ERC20Burnable, ERC20Permit, Pausable, AccessControl {
     bytes32 public constant MINTER_ROLE = keccak256("MINTER_ROLE");
     bytes32 public constant PAUSER_ROLE = keccak256("PAUSER_ROLE");
 
     error ZeroAddressRoleHolder();
 
-    constructor(address admin, address minter, address pauser) ERC20("Relational Euro", "rEUR") {
+    constructor(address admin, address minter, address pauser)
+        ERC20("Relational Euro", "rEUR")
+        ERC20Permit("Relational Euro")
+    {
         if (admin == address(0) || minter == address(0) || pauser == address(0)) {
             revert ZeroAddressRoleHolder();
         }
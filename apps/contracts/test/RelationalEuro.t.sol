@@ -82,4 +82,31 @@ contract RelationalEuroTest is Test {
         assertEq(token.totalSupply(), 7_500_000);
         assertEq(token.balanceOf(user), 7_500_000);
     }
+
+    function testPermitGrantsAllowanceWithoutApprove() external {
+        (address owner, uint256 ownerKey) = makeAddrAndKey("owner");
+        uint256 deadline = block.timestamp + 1 hours;
+        bytes32 structHash = keccak256(
+            abi.encode(
+                keccak256(
+                    "Permit(address owner,address spender,uint256 value,uint256 nonce,uint256 deadline)"
+                ),
+                owner,
+                recipient,
+                2_000_000,
+                token.nonces(owner),
+                deadline
+            )
+        );
+        (uint8 v, bytes32 r, bytes32 s) = vm.sign(
+            ownerKey, keccak256(abi.encodePacked("\x19\x01", token.DOMAIN_SEPARATOR(), structHash))
+        );
+
+        token.permit(owner, recipient, 2_000_000, deadline, v, r, s);
+        assertEq(token.allowance(owner, recipient), 2_000_000);
+        assertEq(token.nonces(owner), 1);
+
+        vm.expectRevert();
+        token.permit(owner, recipient, 2_000_000, deadline, v, r, s);
+    }
 }
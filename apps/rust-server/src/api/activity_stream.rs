@@ -0,0 +1,442 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Live feeds of the caller's activity.
+//!
+//! `GET /v1/stream/activity` (Server-Sent Events) and `GET /v1/ws`
+//! (WebSocket) push an event whenever one of the caller's transactions
+//! settles, a deposit to one of their wallets is indexed, or one of their
+//! fiat requests changes status. Both are fed by the domain event bus
+//! ([`crate::events`]), which [`TxDatabase`](crate::storage::TxDatabase) and
+//! [`FiatRequestRepository`] publish to on every write, so a client only
+//! sees events from the instance it is connected to. Events carry identifiers only; clients fetch details
+//! from the regular endpoints.
+
+use std::convert::Infallible;
+
+use axum::{
+    extract::{
+        ws::{Message, WebSocket, WebSocketUpgrade},
+        Query, State,
+    },
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        Response,
+    },
+};
+use serde::{Deserialize, Serialize};
+use tokio::sync::{broadcast::error::RecvError, mpsc};
+use tokio_stream::{wrappers::ReceiverStream, Stream, StreamExt};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::{
+    auth::{Auth, AuthenticatedUser},
+    error::ApiError,
+    events::DomainEvent,
+    state::AppState,
+    storage::{
+        tx_database::TxStatusChange, FiatDirection, FiatRequestRepository, FiatRequestStatus,
+        FiatStatusChange, OwnershipEnforcer, TokenType, TxStatus, WalletRepository,
+    },
+};
+
+/// Events buffered for a slow client before the stream waits on it.
+const EVENT_BUFFER: usize = 64;
+
+/// One event on the activity stream. The SSE event name is `kind`.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ActivityEvent {
+    /// A transaction sent from one of the caller's wallets was confirmed.
+    TxConfirmed { wallet_id: String, tx_hash: String },
+    /// A transaction sent from one of the caller's wallets failed.
+    TxFailed { wallet_id: String, tx_hash: String },
+    /// An incoming transfer to one of the caller's wallets was indexed.
+    DepositIndexed {
+        wallet_id: String,
+        tx_hash: String,
+        amount: String,
+        token: TokenType,
+    },
+    /// A fiat request was created or changed status.
+    FiatUpdated {
+        request_id: String,
+        wallet_id: String,
+        direction: FiatDirection,
+        status: FiatRequestStatus,
+    },
+    /// Events were dropped because the client fell behind; refetch state.
+    Resync,
+}
+
+impl ActivityEvent {
+    fn kind(&self) -> &'static str {
+        match self {
+            Self::TxConfirmed { .. } => "tx_confirmed",
+            Self::TxFailed { .. } => "tx_failed",
+            Self::DepositIndexed { .. } => "deposit_indexed",
+            Self::FiatUpdated { .. } => "fiat_updated",
+            Self::Resync => "resync",
+        }
+    }
+
+    /// Wallet the event concerns; `None` for [`ActivityEvent::Resync`].
+    fn wallet_id(&self) -> Option<&str> {
+        match self {
+            Self::TxConfirmed { wallet_id, .. }
+            | Self::TxFailed { wallet_id, .. }
+            | Self::DepositIndexed { wallet_id, .. }
+            | Self::FiatUpdated { wallet_id, .. } => Some(wallet_id),
+            Self::Resync => None,
+        }
+    }
+
+    fn to_sse(&self) -> Event {
+        Event::default()
+            .event(self.kind())
+            .data(serde_json::to_string(self).unwrap_or_default())
+    }
+}
+
+/// Activity events for `user` caused by a transaction write.
+///
+/// The sender's owner hears about the outcome and the recipient's owner
+/// about the deposit; pending writes produce nothing.
+pub(crate) fn tx_activity(
+    state: &AppState,
+    user: &AuthenticatedUser,
+    change: &TxStatusChange,
+) -> Vec<ActivityEvent> {
+    let Some(tx_db) = state.tx_db.as_ref() else {
+        return Vec::new();
+    };
+    if change.status == TxStatus::Pending {
+        return Vec::new();
+    }
+    let Ok(Some(tx)) = tx_db.get_transaction(&change.tx_hash) else {
+        return Vec::new();
+    };
+
+    let wallets = WalletRepository::new(state.storage());
+    let owned_wallet = |address: &str| {
+        tx_db
+            .get_wallet_id_for_address(address)
+            .ok()
+            .flatten()
+            .filter(|wallet_id| {
+                wallets
+                    .get(wallet_id)
+                    .is_ok_and(|wallet| wallet.is_owned_by(user))
+            })
+    };
+
+    let mut events = Vec::new();
+    if let Some(wallet_id) = owned_wallet(&tx.from) {
+        let tx_hash = tx.tx_hash.clone();
+        events.push(if change.status == TxStatus::Confirmed {
+            ActivityEvent::TxConfirmed { wallet_id, tx_hash }
+        } else {
+            ActivityEvent::TxFailed { wallet_id, tx_hash }
+        });
+    }
+    if change.status == TxStatus::Confirmed {
+        if let Some(wallet_id) = owned_wallet(&tx.to) {
+            events.push(ActivityEvent::DepositIndexed {
+                wallet_id,
+                tx_hash: tx.tx_hash,
+                amount: tx.amount,
+                token: tx.token,
+            });
+        }
+    }
+    events
+}
+
+/// Activity event for `user` caused by a fiat request write, if it is theirs.
+pub(crate) fn fiat_activity(
+    state: &AppState,
+    user: &AuthenticatedUser,
+    change: &FiatStatusChange,
+) -> Option<ActivityEvent> {
+    let record = FiatRequestRepository::new(state.storage())
+        .get(&change.request_id)
+        .ok()?;
+    if !record.is_owned_by(user) {
+        return None;
+    }
+    Some(ActivityEvent::FiatUpdated {
+        request_id: record.request_id,
+        wallet_id: record.wallet_id,
+        direction: record.direction,
+        status: change.status,
+    })
+}
+
+/// Start feeding `user`'s activity into a channel. The feed stops once
+/// the receiver is dropped.
+fn activity_feed(state: AppState, user: AuthenticatedUser) -> mpsc::Receiver<ActivityEvent> {
+    let mut domain_events = state.events.subscribe();
+    let (sender, receiver) = mpsc::channel(EVENT_BUFFER);
+
+    tokio::spawn(async move {
+        loop {
+            let events = tokio::select! {
+                _ = sender.closed() => break,
+                event = domain_events.recv() => match event {
+                    Ok(DomainEvent::TxStatusChanged(change)) => tx_activity(&state, &user, &change),
+                    Ok(DomainEvent::FiatStatusChanged(change)) => {
+                        fiat_activity(&state, &user, &change).into_iter().collect()
+                    }
+                    Ok(_) => continue,
+                    Err(RecvError::Lagged(_)) => vec![ActivityEvent::Resync],
+                    Err(RecvError::Closed) => break,
+                },
+            };
+            for event in events {
+                if sender.send(event).await.is_err() {
+                    return;
+                }
+            }
+        }
+    });
+    receiver
+}
+
+/// Stream the caller's activity as Server-Sent Events.
+///
+/// Alternative to WebSockets for web clients behind proxies that block
+/// upgrades. Each event's name is its `kind`; a `resync` event means some
+/// events were dropped and the client should refetch. The stream sends a
+/// keep-alive comment while idle.
+#[utoipa::path(
+    get,
+    path = "/v1/stream/activity",
+    tag = "Activity",
+    security(("bearer" = [])),
+    responses(
+        (status = 200, description = "`text/event-stream` of activity events", body = ActivityEvent, content_type = "text/event-stream"),
+        (status = 401, description = "Unauthorized")
+    )
+)]
+pub async fn stream_activity(
+    Auth(user): Auth,
+    State(state): State<AppState>,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, ApiError> {
+    let receiver = activity_feed(state, user);
+    let stream = ReceiverStream::new(receiver).map(|event| Ok(event.to_sse()));
+    Ok(Sse::new(stream).keep_alive(KeepAlive::default()))
+}
+
+/// Query parameters of the activity WebSocket.
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct ActivitySocketQuery {
+    /// Only push events for this wallet (must be the caller's).
+    pub wallet_id: Option<String>,
+}
+
+/// Subscribe to the caller's activity over a WebSocket.
+///
+/// Replaces polling a transaction's status after a send. Each text message
+/// is one JSON [`ActivityEvent`]; a `resync` event means some events were
+/// dropped and the client should refetch. Messages from the client are
+/// ignored apart from close frames.
+#[utoipa::path(
+    get,
+    path = "/v1/ws",
+    tag = "Activity",
+    params(ActivitySocketQuery),
+    security(("bearer" = [])),
+    responses(
+        (status = 101, description = "Switched to a WebSocket carrying activity events", body = ActivityEvent),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - not wallet owner"),
+        (status = 404, description = "Wallet not found")
+    )
+)]
+pub async fn activity_socket(
+    Auth(user): Auth,
+    State(state): State<AppState>,
+    Query(query): Query<ActivitySocketQuery>,
+    upgrade: WebSocketUpgrade,
+) -> Result<Response, ApiError> {
+    if let Some(wallet_id) = &query.wallet_id {
+        let wallet = WalletRepository::new(state.storage()).get(wallet_id)?;
+        if !wallet.is_owned_by(&user) {
+            return Err(
+                ApiError::forbidden("You do not own this wallet").with_code("wallet_not_owned")
+            );
+        }
+    }
+    let events = activity_feed(state, user);
+    Ok(upgrade.on_upgrade(move |socket| push_activity(socket, events, query.wallet_id)))
+}
+
+/// Forward activity events to the socket until either side closes it.
+async fn push_activity(
+    mut socket: WebSocket,
+    mut events: mpsc::Receiver<ActivityEvent>,
+    wallet_id: Option<String>,
+) {
+    loop {
+        tokio::select! {
+            event = events.recv() => {
+                let Some(event) = event else { break };
+                if !concerns_wallet(&event, wallet_id.as_deref()) {
+                    continue;
+                }
+                let text = serde_json::to_string(&event).unwrap_or_default();
+                if socket.send(Message::Text(text.into())).await.is_err() {
+                    break;
+                }
+            }
+            incoming = socket.recv() => match incoming {
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => {}
+            },
+        }
+    }
+}
+
+/// Whether a socket filtered to `wallet_id` should receive `event`.
+fn concerns_wallet(event: &ActivityEvent, wallet_id: Option<&str>) -> bool {
+    match (wallet_id, event.wallet_id()) {
+        (Some(wanted), Some(actual)) => wanted == actual,
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::Role;
+    use crate::storage::{StoredFiatRequest, StoredTransaction, WalletMetadata, WalletStatus};
+    use chrono::Utc;
+
+    fn user(user_id: &str) -> AuthenticatedUser {
+        AuthenticatedUser {
+            user_id: user_id.to_string(),
+            role: Role::Client,
+            session_id: None,
+            issuer: "https://test.clerk.dev".to_string(),
+            expires_at: Utc::now().timestamp() + 3600,
+            tenant_id: None,
+        }
+    }
+
+    fn wallet_meta(wallet_id: &str, owner_user_id: &str, public_address: &str) -> WalletMetadata {
+        WalletMetadata {
+            wallet_id: wallet_id.to_string(),
+            owner_user_id: owner_user_id.to_string(),
+            public_address: public_address.to_string(),
+            created_at: Utc::now(),
+            status: WalletStatus::Active,
+            label: None,
+            email_lookup_key: None,
+            email_sha256: None,
+            account_type: Default::default(),
+            smart_account: None,
+            lock: None,
+            deleted_at: None,
+            purged_at: None,
+            funded_at: None,
+            tenant_id: None,
+        }
+    }
+
+    #[test]
+    fn transactions_notify_sender_and_recipient_owners() {
+        let state = AppState::default();
+        let tx_db = state.tx_db.as_ref().unwrap();
+        let wallets = WalletRepository::new(state.storage());
+        let sender = "0x7777777777777777777777777777777777777777";
+        let recipient = "0x8888888888888888888888888888888888888888";
+        for (wallet_id, owner, address) in [("w-a", "user-a", sender), ("w-b", "user-b", recipient)]
+        {
+            wallets
+                .create(&wallet_meta(wallet_id, owner, address), b"test-key")
+                .unwrap();
+            tx_db.register_address(address, wallet_id).unwrap();
+        }
+        let tx = StoredTransaction::new_pending(
+            "0xact1".to_string(),
+            "w-a".to_string(),
+            Some("w-b".to_string()),
+            sender.to_string(),
+            recipient.to_string(),
+            "2.5".to_string(),
+            TokenType::Native,
+            "fuji".to_string(),
+            "https://testnet.snowtrace.io/tx/0xact1".to_string(),
+        );
+        tx_db.upsert_transaction(&tx, &[]).unwrap();
+
+        let pending = TxStatusChange {
+            tx_hash: "0xact1".to_string(),
+            status: TxStatus::Pending,
+        };
+        assert!(tx_activity(&state, &user("user-a"), &pending).is_empty());
+
+        tx_db
+            .update_status("0xact1", TxStatus::Confirmed, Some(1), Some(21_000))
+            .unwrap();
+        let confirmed = TxStatusChange {
+            tx_hash: "0xact1".to_string(),
+            status: TxStatus::Confirmed,
+        };
+        assert_eq!(
+            tx_activity(&state, &user("user-a"), &confirmed),
+            vec![ActivityEvent::TxConfirmed {
+                wallet_id: "w-a".to_string(),
+                tx_hash: "0xact1".to_string(),
+            }]
+        );
+        assert_eq!(
+            tx_activity(&state, &user("user-b"), &confirmed),
+            vec![ActivityEvent::DepositIndexed {
+                wallet_id: "w-b".to_string(),
+                tx_hash: "0xact1".to_string(),
+                amount: "2.5".to_string(),
+                token: TokenType::Native,
+            }]
+        );
+        assert!(tx_activity(&state, &user("user-c"), &confirmed).is_empty());
+    }
+
+    #[test]
+    fn fiat_updates_reach_only_the_owner() {
+        let state = AppState::default();
+        let request = StoredFiatRequest::new_queued(
+            "fiat-act-1".to_string(),
+            "w-a".to_string(),
+            "user-a".to_string(),
+            FiatDirection::OnRamp,
+            "10.00".to_string(),
+            "truelayer_sandbox".to_string(),
+            None,
+        );
+        FiatRequestRepository::new(state.storage())
+            .create(&request)
+            .unwrap();
+        let change = FiatStatusChange {
+            request_id: "fiat-act-1".to_string(),
+            status: FiatRequestStatus::Queued,
+        };
+
+        let event = fiat_activity(&state, &user("user-a"), &change).unwrap();
+        assert_eq!(event.kind(), "fiat_updated");
+        assert!(fiat_activity(&state, &user("user-b"), &change).is_none());
+    }
+
+    #[test]
+    fn wallet_filtered_sockets_keep_resyncs() {
+        let confirmed = ActivityEvent::TxConfirmed {
+            wallet_id: "w-a".to_string(),
+            tx_hash: "0xact2".to_string(),
+        };
+        assert!(concerns_wallet(&confirmed, None));
+        assert!(concerns_wallet(&confirmed, Some("w-a")));
+        assert!(!concerns_wallet(&confirmed, Some("w-b")));
+        assert!(concerns_wallet(&ActivityEvent::Resync, Some("w-b")));
+    }
+}
@@ -0,0 +1,127 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Checksummed addresses in API responses.
+//!
+//! Addresses are stored in the case they arrived in: lowercase from the
+//! indexer and RPC logs, checksummed from configuration, whatever the client
+//! sent for recipients. The [`checksum_addresses`] layer rewrites every JSON
+//! string that is an address (`0x` and 40 hex digits) to its EIP-55 form, so
+//! clients see one format and can compare addresses byte for byte.
+//! Transaction hashes and other hex values have other lengths and are left
+//! alone.
+
+use axum::{
+    body::{to_bytes, Body},
+    extract::Request,
+    http::header,
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use serde_json::Value;
+
+use crate::blockchain::{address::is_address, checksum_address};
+
+/// Checksum every address string in `value`. Returns whether any changed.
+fn checksum_values(value: &mut Value) -> bool {
+    match value {
+        Value::String(s) if is_address(s) => {
+            let checksummed = checksum_address(s);
+            let changed = checksummed != *s;
+            *s = checksummed;
+            changed
+        }
+        Value::Object(map) => map
+            .values_mut()
+            .fold(false, |changed, v| checksum_values(v) | changed),
+        Value::Array(items) => items
+            .iter_mut()
+            .fold(false, |changed, v| checksum_values(v) | changed),
+        _ => false,
+    }
+}
+
+/// Layer checksumming the addresses in JSON responses.
+pub async fn checksum_addresses(request: Request, next: Next) -> Response {
+    let response = next.run(request).await;
+    let is_json = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|ct| ct.starts_with("application/json"));
+    if !is_json {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let bytes = match to_bytes(body, usize::MAX).await {
+        Ok(bytes) => bytes,
+        Err(error) => {
+            tracing::warn!(error = %error, "Failed to buffer response for address formatting");
+            return axum::http::StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+    if !bytes.windows(2).any(|w| w == b"0x") {
+        return Response::from_parts(parts, Body::from(bytes));
+    }
+    let Ok(mut value) = serde_json::from_slice::<Value>(&bytes) else {
+        return Response::from_parts(parts, Body::from(bytes));
+    };
+    if !checksum_values(&mut value) {
+        return Response::from_parts(parts, Body::from(bytes));
+    }
+    let formatted = serde_json::to_vec(&value).unwrap_or_else(|_| bytes.to_vec());
+    parts.headers.remove(header::CONTENT_LENGTH);
+    Response::from_parts(parts, Body::from(formatted))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{routing::get, Json, Router};
+    use serde_json::json;
+    use tower::ServiceExt;
+
+    #[test]
+    fn only_address_strings_are_rewritten() {
+        let tx_hash = format!("0x{}", "ab".repeat(32));
+        let mut value = json!({
+            "from": "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed",
+            "tx_hash": tx_hash,
+            "items": [{ "token": "native" }, { "token": "0xfb6916095ca1df60bb79ce92ce3ea74c37c5d359" }],
+            "amount": "1.5",
+        });
+        assert!(checksum_values(&mut value));
+        assert_eq!(value["from"], "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed");
+        assert_eq!(
+            value["items"][1]["token"],
+            "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359"
+        );
+        assert_eq!(value["tx_hash"], tx_hash);
+        assert_eq!(value["items"][0]["token"], "native");
+        assert!(!checksum_values(&mut value));
+    }
+
+    #[tokio::test]
+    async fn json_responses_are_checksummed() {
+        let app = Router::new()
+            .route(
+                "/",
+                get(|| async {
+                    Json(json!({ "address": "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed" }))
+                }),
+            )
+            .layer(axum::middleware::from_fn(checksum_addresses));
+        let response = app
+            .oneshot(Request::builder().uri("/").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(
+            body["address"],
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"
+        );
+    }
+}
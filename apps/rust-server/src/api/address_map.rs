@@ -0,0 +1,248 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Inspecting and repairing the indexer's address→wallet map.
+//!
+//! `GET /v1/admin/indexer/address-map` lists the mappings and where they
+//! differ from encrypted storage (see [`crate::indexer::address_map`]);
+//! `PUT` repairs them all or sets and removes single entries. Edits are
+//! audit-logged with the previous and new owner of each address.
+
+use std::collections::BTreeMap;
+
+use axum::{extract::State, Json};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::{
+    api::admin::require_platform_admin,
+    auth::AdminOnly,
+    blockchain::{address::is_address, address_key},
+    error::{ApiError, StorageContext},
+    indexer::address_map::{
+        expected_address_map, find_issues, AddressMapIssue, AddressMapIssueKind,
+    },
+    state::AppState,
+    storage::{
+        repository::watch_only::tracking_id, AuditEvent, AuditEventType, AuditRepository,
+        TxDatabase, WalletRepository,
+    },
+};
+
+/// Most entries one edit may change.
+const MAX_EDITS: usize = 500;
+
+/// One mapping.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct AddressMapEntry {
+    pub address: String,
+    /// Wallet ID, fiat service wallet ID, or the tracking ID of a
+    /// watch-only address.
+    pub wallet_id: String,
+}
+
+/// The address map and its differences from encrypted storage.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AddressMapResponse {
+    /// Ordered by address.
+    pub entries: Vec<AddressMapEntry>,
+    pub total: usize,
+    pub issues: Vec<AddressMapIssue>,
+    pub issue_count: usize,
+}
+
+/// One entry to set or remove.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct AddressMapEdit {
+    pub address: String,
+    /// New owner; `null` removes the mapping.
+    #[serde(default)]
+    pub wallet_id: Option<String>,
+}
+
+/// Request body for editing the address map.
+#[derive(Debug, Clone, Default, Deserialize, ToSchema)]
+pub struct UpdateAddressMapRequest {
+    /// Apply encrypted storage's view to every listed issue first: register
+    /// missing and mismatched addresses, remove stale ones.
+    #[serde(default)]
+    pub repair: bool,
+    /// Entries to set or remove, applied after `repair`.
+    #[serde(default)]
+    pub entries: Vec<AddressMapEdit>,
+}
+
+fn tx_db(state: &AppState) -> Result<&TxDatabase, ApiError> {
+    state
+        .tx_db
+        .as_deref()
+        .ok_or_else(|| ApiError::service_unavailable("Transaction database is not available"))
+}
+
+fn db_error(e: impl std::fmt::Display) -> ApiError {
+    ApiError::internal(format!("Transaction database error: {e}"))
+}
+
+fn load_map(state: &AppState, db: &TxDatabase) -> Result<AddressMapResponse, ApiError> {
+    let mapped = db.list_address_map().map_err(db_error)?;
+    let expected =
+        expected_address_map(state.storage()).context("Failed to load wallet addresses")?;
+    let issues = find_issues(&mapped, &expected);
+    let entries: Vec<AddressMapEntry> = mapped
+        .into_iter()
+        .map(|(address, wallet_id)| AddressMapEntry { address, wallet_id })
+        .collect();
+    Ok(AddressMapResponse {
+        total: entries.len(),
+        entries,
+        issue_count: issues.len(),
+        issues,
+    })
+}
+
+/// The changes an edit request makes: address → new owner (`None` removes).
+/// Explicit entries override repairs of the same address.
+fn planned_changes(
+    state: &AppState,
+    request: &UpdateAddressMapRequest,
+    issues: &[AddressMapIssue],
+    expected: &BTreeMap<String, String>,
+) -> Result<BTreeMap<String, Option<String>>, ApiError> {
+    if request.entries.len() > MAX_EDITS {
+        return Err(ApiError::bad_request(format!(
+            "At most {MAX_EDITS} entries can be edited at once"
+        )));
+    }
+    let mut changes = BTreeMap::new();
+    if request.repair {
+        for issue in issues {
+            let owner = match issue.kind {
+                AddressMapIssueKind::Stale => None,
+                _ => issue.expected_wallet_id.clone(),
+            };
+            changes.insert(issue.address.clone(), owner);
+        }
+    }
+    let wallets = WalletRepository::new(state.storage());
+    for edit in &request.entries {
+        let address = edit.address.trim();
+        if !is_address(address) {
+            return Err(ApiError::bad_request(format!(
+                "Invalid address: {}",
+                edit.address
+            )));
+        }
+        let address = address_key(address);
+        let owner = edit
+            .wallet_id
+            .as_deref()
+            .map(str::trim)
+            .filter(|id| !id.is_empty());
+        // Owners must exist, so a typo cannot hide an address from the
+        // indexer.
+        if let Some(owner) = owner {
+            let known = expected.values().any(|id| id == owner)
+                || owner == tracking_id(&address)
+                || wallets.get(owner).is_ok();
+            if !known {
+                return Err(ApiError::bad_request(format!(
+                    "Unknown wallet ID for {address}: {owner}"
+                )));
+            }
+        }
+        changes.insert(address, owner.map(str::to_string));
+    }
+    Ok(changes)
+}
+
+/// Inspect the address→wallet map (platform admin only).
+///
+/// Lists every mapping and where it differs from wallet, fiat service
+/// wallet and watch-only records in encrypted storage.
+#[utoipa::path(
+    get,
+    path = "/v1/admin/indexer/address-map",
+    tag = "Admin",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Address map and issues", body = AddressMapResponse),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not authorized (platform admin required)"),
+        (status = 503, description = "Transaction database unavailable")
+    )
+)]
+pub async fn get_address_map(
+    AdminOnly(admin): AdminOnly,
+    State(state): State<AppState>,
+) -> Result<Json<AddressMapResponse>, ApiError> {
+    require_platform_admin(&admin)?;
+    let db = tx_db(&state)?;
+    Ok(Json(load_map(&state, db)?))
+}
+
+/// Repair or edit the address→wallet map (platform admin only).
+///
+/// With `repair`, every listed issue is resolved in favour of encrypted
+/// storage; `entries` then set or remove single mappings. Returns the map
+/// after the edit.
+#[utoipa::path(
+    put,
+    path = "/v1/admin/indexer/address-map",
+    tag = "Admin",
+    request_body = UpdateAddressMapRequest,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Address map after the edit", body = AddressMapResponse),
+        (status = 400, description = "Invalid address, unknown wallet ID or too many entries"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not authorized (platform admin required)"),
+        (status = 503, description = "Transaction database unavailable")
+    )
+)]
+pub async fn update_address_map(
+    AdminOnly(admin): AdminOnly,
+    State(state): State<AppState>,
+    Json(request): Json<UpdateAddressMapRequest>,
+) -> Result<Json<AddressMapResponse>, ApiError> {
+    require_platform_admin(&admin)?;
+    let db = tx_db(&state)?;
+    let mapped = db.list_address_map().map_err(db_error)?;
+    let expected =
+        expected_address_map(state.storage()).context("Failed to load wallet addresses")?;
+    let issues = find_issues(&mapped, &expected);
+    let changes = planned_changes(&state, &request, &issues, &expected)?;
+
+    let previous: BTreeMap<String, String> = mapped.into_iter().collect();
+    let mut applied = Vec::new();
+    for (address, owner) in &changes {
+        let before = previous.get(address);
+        if before == owner.as_ref() {
+            continue;
+        }
+        let result = match owner {
+            Some(wallet_id) => db.register_address(address, wallet_id),
+            None => db.remove_wallet_address(address),
+        };
+        result.map_err(db_error)?;
+        applied.push(serde_json::json!({
+            "address": address,
+            "from": before,
+            "to": owner,
+        }));
+    }
+
+    if !applied.is_empty() {
+        let event = AuditEvent::new(AuditEventType::AdminAccess)
+            .with_user(&admin.user_id)
+            .with_resource("tx_database", "address_wallet_map")
+            .with_details(serde_json::json!({
+                "action": "address_map_edit",
+                "repair": request.repair,
+                "changes": applied,
+            }));
+        let _ = AuditRepository::new(state.storage()).log(&event);
+    }
+
+    Ok(Json(load_map(&state, db)?))
+}
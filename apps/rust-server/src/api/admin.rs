@@ -10,23 +10,35 @@
 //! - Audit log queries
 //! - Operational tooling
 
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use alloy::primitives::Address;
 use axum::{
     extract::{Path, Query, State},
     http::StatusCode,
     Json,
 };
-use chrono::{NaiveDate, Utc};
+use chrono::{Duration, NaiveDate, NaiveTime, Utc};
 use serde::{Deserialize, Serialize};
 use utoipa::{IntoParams, ToSchema};
 
 use crate::{
+    api::admin_activity::log_admin_read,
     audit_log,
-    auth::AdminOnly,
-    error::ApiError,
+    auth::{jwks::JwksStats, AdminOnly, AuthenticatedUser},
+    blockchain::avax_fuji,
+    error::{ApiError, StorageContext},
+    indexer::{
+        self,
+        rebuild::{RebuildOptions, RebuildReport},
+        IndexerError,
+    },
     state::AppState,
     storage::{
-        AuditEvent, AuditEventType, AuditRepository, BookmarkRepository, WalletRepository,
-        WalletStatus,
+        tx_database::TxDbError, AuditEvent, AuditEventType, AuditQuery, AuditRepository,
+        BookmarkRepository, EmailIndexRepository, StoredBookmark, WalletMetadata, WalletRepository,
+        WalletResponse, WalletStatus,
     },
 };
 
@@ -66,6 +78,9 @@ pub struct AdminWalletItem {
     pub status: WalletStatus,
     /// When the wallet was created.
     pub created_at: String,
+    /// Tenant of the wallet; absent for the default tenant.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tenant_id: Option<String>,
 }
 
 /// Response for admin wallet list.
@@ -94,19 +109,24 @@ pub struct AuditQueryParams {
     pub resource_id: Option<String>,
     /// Maximum number of results (default 100).
     pub limit: Option<usize>,
-    /// Offset for pagination.
+    /// Matches to skip (after `cursor`, if given).
     pub offset: Option<usize>,
+    /// `next_cursor` from the previous page.
+    pub cursor: Option<String>,
 }
 
 /// Response for audit log queries.
 #[derive(Debug, Serialize, ToSchema)]
 pub struct AuditLogResponse {
-    /// Audit events matching the query.
+    /// Audit events matching the query, oldest first.
     pub events: Vec<AuditEvent>,
-    /// Total count (before limit/offset).
+    /// Total count (before cursor/limit/offset).
     pub total: usize,
     /// Whether there are more results.
     pub has_more: bool,
+    /// Cursor for the next page; absent on the last page.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
 }
 
 /// Admin user summary.
@@ -138,6 +158,9 @@ pub struct DetailedHealthResponse {
     pub storage: StorageHealth,
     /// Auth configuration status.
     pub auth_configured: bool,
+    /// JWKS refresh counters, when JWT verification is configured.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub jwks: Option<JwksStats>,
     /// Server version.
     pub version: String,
     /// Build timestamp.
@@ -215,8 +238,7 @@ pub async fn get_system_stats(
     let storage = state.storage();
 
     // Count wallets by status
-    let wallet_repo = WalletRepository::new(storage);
-    let all_wallets = wallet_repo.list_all_wallets().unwrap_or_default();
+    let all_wallets = visible_wallets(storage, &user);
     let active_wallets = all_wallets
         .iter()
         .filter(|w| w.status == WalletStatus::Active)
@@ -231,8 +253,7 @@ pub async fn get_system_stats(
         .count();
 
     // Count bookmarks
-    let bookmark_repo = BookmarkRepository::new(storage);
-    let total_bookmarks = bookmark_repo.list_all().unwrap_or_default().len();
+    let total_bookmarks = visible_bookmarks(storage, &user, &all_wallets).len();
 
     // Audit log
     audit_log!(&storage, AuditEventType::AdminAccess, &user);
@@ -248,9 +269,52 @@ pub async fn get_system_stats(
     }))
 }
 
+/// Reject tenant admins from endpoints that span every tenant.
+pub(crate) fn require_platform_admin(admin: &AuthenticatedUser) -> Result<(), ApiError> {
+    if admin.tenant_id.is_some() {
+        return Err(
+            ApiError::forbidden("Only platform admins may use this endpoint")
+                .with_code("platform_admin_required"),
+        );
+    }
+    Ok(())
+}
+
+/// Wallets in the admin's view: all of them, or their tenant's.
+fn visible_wallets(
+    storage: &crate::storage::EncryptedStorage,
+    admin: &AuthenticatedUser,
+) -> Vec<WalletMetadata> {
+    WalletRepository::new(storage)
+        .list_all_wallets()
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|w| admin.sees_tenant(w.tenant_id.as_deref()))
+        .collect()
+}
+
+/// Bookmarks in the admin's view: those of `wallets` for tenant admins.
+fn visible_bookmarks(
+    storage: &crate::storage::EncryptedStorage,
+    admin: &AuthenticatedUser,
+    wallets: &[WalletMetadata],
+) -> Vec<StoredBookmark> {
+    let bookmarks = BookmarkRepository::new(storage)
+        .list_all()
+        .unwrap_or_default();
+    if admin.tenant_id.is_none() {
+        return bookmarks;
+    }
+    bookmarks
+        .into_iter()
+        .filter(|b| wallets.iter().any(|w| w.wallet_id == b.wallet_id))
+        .collect()
+}
+
 /// List all wallets (admin view).
 ///
-/// Returns all wallets across all users. Admin only.
+/// Returns all wallets across all users, or those of the admin's tenant.
+/// Admin only.
 #[utoipa::path(
     get,
     path = "/v1/admin/wallets",
@@ -267,9 +331,7 @@ pub async fn list_all_wallets(
     State(state): State<AppState>,
 ) -> Result<Json<AdminWalletListResponse>, ApiError> {
     let storage = state.storage();
-    let wallet_repo = WalletRepository::new(storage);
-
-    let wallets = wallet_repo.list_all_wallets().unwrap_or_default();
+    let wallets = visible_wallets(storage, &user);
     let items: Vec<AdminWalletItem> = wallets
         .into_iter()
         .map(|w| AdminWalletItem {
@@ -278,13 +340,20 @@ pub async fn list_all_wallets(
             public_address: w.public_address,
             status: w.status,
             created_at: w.created_at.to_rfc3339(),
+            tenant_id: w.tenant_id,
         })
         .collect();
 
     let total = items.len();
 
-    // Audit log
-    audit_log!(&storage, AuditEventType::AdminAccess, &user);
+    log_admin_read(
+        storage,
+        &user,
+        "wallets",
+        "*",
+        &["wallet_id", "owner_user_id", "public_address", "status"],
+        total,
+    );
 
     Ok(Json(AdminWalletListResponse {
         wallets: items,
@@ -313,11 +382,8 @@ pub async fn list_all_users(
     let storage = state.storage();
 
     // Collect all user IDs from various sources
-    let wallet_repo = WalletRepository::new(storage);
-    let bookmark_repo = BookmarkRepository::new(storage);
-
-    let wallets = wallet_repo.list_all_wallets().unwrap_or_default();
-    let bookmarks = bookmark_repo.list_all().unwrap_or_default();
+    let wallets = visible_wallets(storage, &user);
+    let bookmarks = visible_bookmarks(storage, &user, &wallets);
 
     // Build user map
     let mut user_map: std::collections::HashMap<String, AdminUserSummary> =
@@ -348,8 +414,14 @@ pub async fn list_all_users(
     let users: Vec<AdminUserSummary> = user_map.into_values().collect();
     let total = users.len();
 
-    // Audit log
-    audit_log!(&storage, AuditEventType::AdminAccess, &user);
+    log_admin_read(
+        storage,
+        &user,
+        "users",
+        "*",
+        &["user_id", "wallet_count", "bookmark_count"],
+        total,
+    );
 
     Ok(Json(AdminUserListResponse { users, total }))
 }
@@ -357,7 +429,10 @@ pub async fn list_all_users(
 /// Query audit logs.
 ///
 /// Search and filter audit log entries. Supports date range, user ID,
-/// event type, and resource filtering. Admin only.
+/// event type, and resource filtering, with cursor pagination. Queries run
+/// against the audit index, which is first caught up with the daily
+/// archive files of the range. Platform admins only, since audit events
+/// are not partitioned by tenant.
 #[utoipa::path(
     get,
     path = "/v1/admin/audit/events",
@@ -368,7 +443,7 @@ pub async fn list_all_users(
         (status = 200, description = "Audit events", body = AuditLogResponse),
         (status = 400, description = "Invalid query parameters"),
         (status = 401, description = "Not authenticated"),
-        (status = 403, description = "Not authorized (admin required)")
+        (status = 403, description = "Not authorized (platform admin required)")
     )
 )]
 pub async fn query_audit_logs(
@@ -376,62 +451,74 @@ pub async fn query_audit_logs(
     Query(params): Query<AuditQueryParams>,
     State(state): State<AppState>,
 ) -> Result<Json<AuditLogResponse>, ApiError> {
+    require_platform_admin(&admin_user)?;
     let storage = state.storage();
-    let audit_repo = AuditRepository::new(storage);
+    let tx_db = state
+        .tx_db
+        .as_ref()
+        .ok_or_else(|| ApiError::service_unavailable("Transaction database is not available"))?;
 
     // Default date range: today only
-    let today = Utc::now().format("%Y-%m-%d").to_string();
-    let start_date = params.start_date.as_deref().unwrap_or(&today);
-    let end_date = params.end_date.as_deref().unwrap_or(&today);
-
-    // Validate dates
-    NaiveDate::parse_from_str(start_date, "%Y-%m-%d")
-        .map_err(|_| ApiError::bad_request("Invalid start_date format. Use YYYY-MM-DD."))?;
-    NaiveDate::parse_from_str(end_date, "%Y-%m-%d")
-        .map_err(|_| ApiError::bad_request("Invalid end_date format. Use YYYY-MM-DD."))?;
-
-    // Fetch events
-    let mut events = audit_repo
-        .read_events_range(start_date, end_date)
-        .unwrap_or_default();
-
-    // Apply filters
-    if let Some(user_id) = &params.user_id {
-        events.retain(|e| e.user_id.as_deref() == Some(user_id.as_str()));
-    }
-
-    if let Some(event_type) = &params.event_type {
-        events.retain(|e| {
-            let type_str = serde_json::to_string(&e.event_type)
-                .unwrap_or_default()
-                .trim_matches('"')
-                .to_string();
-            type_str == *event_type
-        });
-    }
-
-    if let Some(resource_type) = &params.resource_type {
-        events.retain(|e| e.resource_type.as_deref() == Some(resource_type.as_str()));
-    }
-
-    if let Some(resource_id) = &params.resource_id {
-        events.retain(|e| e.resource_id.as_deref() == Some(resource_id.as_str()));
+    let today = Utc::now().date_naive();
+    let parse_date = |value: Option<&str>, message: &str| match value {
+        Some(value) => {
+            NaiveDate::parse_from_str(value, "%Y-%m-%d").map_err(|_| ApiError::bad_request(message))
+        }
+        None => Ok(today),
+    };
+    let start_date = parse_date(
+        params.start_date.as_deref(),
+        "Invalid start_date format. Use YYYY-MM-DD.",
+    )?;
+    let end_date = parse_date(
+        params.end_date.as_deref(),
+        "Invalid end_date format. Use YYYY-MM-DD.",
+    )?;
+    if start_date > end_date {
+        return Err(ApiError::bad_request(
+            "start_date must not be after end_date",
+        ));
     }
 
-    let total = events.len();
-    let limit = params.limit.unwrap_or(100).min(1000); // Max 1000
-    let offset = params.offset.unwrap_or(0);
-
-    let has_more = offset + limit < total;
-    let events: Vec<AuditEvent> = events.into_iter().skip(offset).take(limit).collect();
-
-    // Log the admin access
-    audit_log!(&storage, AuditEventType::AdminAccess, &admin_user);
+    AuditRepository::new(storage)
+        .sync_index(tx_db, start_date, end_date, Utc::now())
+        .context("Failed to index audit events")?;
+
+    let query = AuditQuery {
+        from: start_date.and_time(NaiveTime::MIN).and_utc(),
+        to: (end_date + Duration::days(1))
+            .and_time(NaiveTime::MIN)
+            .and_utc(),
+        user_id: params.user_id,
+        event_type: params.event_type,
+        resource_type: params.resource_type,
+        resource_id: params.resource_id,
+        cursor: params.cursor,
+        offset: params.offset.unwrap_or(0),
+        limit: params.limit.unwrap_or(100).min(1000), // Max 1000
+    };
+    let page = tx_db.query_audit_events(&query).map_err(|e| match e {
+        TxDbError::InvalidCursor(_) => {
+            ApiError::bad_request("Invalid pagination cursor").with_code("invalid_cursor")
+        }
+        e => ApiError::internal(format!("Failed to query audit events: {e}")),
+    })?;
+    let events = page.events;
+
+    log_admin_read(
+        storage,
+        &admin_user,
+        "audit_events",
+        "*",
+        &["user_id", "resource_id", "ip_address", "details"],
+        events.len(),
+    );
 
     Ok(Json(AuditLogResponse {
         events,
-        total,
-        has_more,
+        total: page.total,
+        has_more: page.next_cursor.is_some(),
+        next_cursor: page.next_cursor,
     }))
 }
 
@@ -447,13 +534,14 @@ pub async fn query_audit_logs(
     responses(
         (status = 200, description = "Detailed health status", body = DetailedHealthResponse),
         (status = 401, description = "Not authenticated"),
-        (status = 403, description = "Not authorized (admin required)")
+        (status = 403, description = "Not authorized (platform admin required)")
     )
 )]
 pub async fn get_detailed_health(
-    AdminOnly(_user): AdminOnly,
+    AdminOnly(user): AdminOnly,
     State(state): State<AppState>,
 ) -> Result<Json<DetailedHealthResponse>, ApiError> {
+    require_platform_admin(&user)?;
     let storage = state.storage();
     let data_dir = storage.paths().root().to_string_lossy().to_string();
 
@@ -485,6 +573,11 @@ pub async fn get_detailed_health(
             total_files,
         },
         auth_configured,
+        jwks: state
+            .auth_config()
+            .jwks
+            .as_ref()
+            .map(|j| j.stats_snapshot()),
         version: env!("CARGO_PKG_VERSION").to_string(),
         build_time: option_env!("BUILD_TIME").unwrap_or("unknown").to_string(),
     }))
@@ -516,12 +609,14 @@ pub async fn suspend_wallet(
 
     let mut wallet = wallet_repo
         .get(&wallet_id)
-        .map_err(|_| ApiError::not_found(format!("Wallet {} not found", wallet_id)))?;
+        .ok()
+        .filter(|w| user.sees_tenant(w.tenant_id.as_deref()))
+        .ok_or_else(|| ApiError::not_found(format!("Wallet {} not found", wallet_id)))?;
 
     wallet.status = WalletStatus::Suspended;
     wallet_repo
         .update(&wallet)
-        .map_err(|e| ApiError::internal(format!("Failed to suspend wallet: {}", e)))?;
+        .context("Failed to suspend wallet")?;
 
     // Audit log
     let audit_repo = AuditRepository::new(storage);
@@ -559,12 +654,20 @@ pub async fn activate_wallet(
 
     let mut wallet = wallet_repo
         .get(&wallet_id)
-        .map_err(|_| ApiError::not_found(format!("Wallet {} not found", wallet_id)))?;
+        .ok()
+        .filter(|w| user.sees_tenant(w.tenant_id.as_deref()))
+        .ok_or_else(|| ApiError::not_found(format!("Wallet {} not found", wallet_id)))?;
+    if wallet.status == WalletStatus::Deleted {
+        return Err(
+            ApiError::conflict("Deleted wallets must be restored, not activated")
+                .with_code("wallet_deleted"),
+        );
+    }
 
     wallet.status = WalletStatus::Active;
     wallet_repo
         .update(&wallet)
-        .map_err(|e| ApiError::internal(format!("Failed to activate wallet: {}", e)))?;
+        .context("Failed to activate wallet")?;
 
     // Audit log
     let audit_repo = AuditRepository::new(storage);
@@ -577,6 +680,226 @@ pub async fn activate_wallet(
     Ok(StatusCode::OK)
 }
 
+/// Restore a soft-deleted wallet (admin action).
+///
+/// Makes the wallet active again and re-registers its address, owner,
+/// email lookup and discovery token. Only possible until the retention
+/// period ends and its key material is purged.
+#[utoipa::path(
+    post,
+    path = "/v1/admin/wallets/{wallet_id}/restore",
+    tag = "Admin",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Wallet restored", body = WalletResponse),
+        (status = 404, description = "Wallet not found"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not authorized (admin required)"),
+        (status = 409, description = "Wallet not deleted, already purged, or its owner or email has another wallet")
+    )
+)]
+pub async fn restore_wallet(
+    AdminOnly(user): AdminOnly,
+    Path(wallet_id): Path<String>,
+    State(state): State<AppState>,
+) -> Result<Json<WalletResponse>, ApiError> {
+    let storage = state.storage();
+    let wallet_repo = WalletRepository::new(storage);
+
+    let wallet = wallet_repo
+        .get(&wallet_id)
+        .ok()
+        .filter(|w| user.sees_tenant(w.tenant_id.as_deref()))
+        .ok_or_else(|| ApiError::not_found(format!("Wallet {} not found", wallet_id)))?;
+    if wallet.status != WalletStatus::Deleted {
+        return Err(ApiError::conflict("Wallet is not deleted").with_code("wallet_not_deleted"));
+    }
+    if wallet.purged_at.is_some() {
+        return Err(
+            ApiError::conflict("Wallet key material was purged after retention")
+                .with_code("wallet_purged"),
+        );
+    }
+
+    // Owners hold one wallet; deleting this one let them create another.
+    if let Some(db) = state.tx_db.as_ref() {
+        let current = db
+            .get_user_wallet(&wallet.owner_user_id)
+            .map_err(|e| ApiError::internal(format!("User lookup failed: {e}")))?;
+        if let Some(other) = current.filter(|id| *id != wallet_id) {
+            let live = wallet_repo
+                .get(&other)
+                .is_ok_and(|w| w.status != WalletStatus::Deleted);
+            if live {
+                return Err(ApiError::conflict("The owner already has another wallet")
+                    .with_code("owner_has_wallet"));
+            }
+        }
+        if let Some(ref lookup_key) = wallet.email_lookup_key {
+            let taken = EmailIndexRepository::new(db.clone())
+                .lookup(lookup_key)
+                .map_err(|e| ApiError::internal(format!("Email lookup failed: {e}")))?
+                .is_some_and(|entry| entry.wallet_id != wallet_id);
+            if taken {
+                return Err(
+                    ApiError::conflict("The wallet's email belongs to another wallet")
+                        .with_code("email_has_wallet"),
+                );
+            }
+        }
+    }
+
+    let restored = wallet_repo
+        .restore(&wallet_id)
+        .context("Failed to restore wallet")?;
+
+    // Undo the index cleanup `delete_wallet` did.
+    if let Some(db) = state.tx_db.as_ref() {
+        db.register_address(&restored.public_address, &wallet_id)
+            .map_err(|e| ApiError::internal(format!("Failed to register wallet address: {e}")))?;
+        db.register_user_wallet(&restored.owner_user_id, &wallet_id)
+            .map_err(|e| {
+                ApiError::internal(format!("Failed to register user→wallet mapping: {e}"))
+            })?;
+        if let Some(ref lookup_key) = restored.email_lookup_key {
+            EmailIndexRepository::new(db.clone())
+                .register(lookup_key, &wallet_id, &restored.public_address)
+                .map_err(|e| ApiError::internal(format!("Failed to register email lookup: {e}")))?;
+        }
+    }
+    if let Some(ref sha256_hex) = restored.email_sha256 {
+        if let Ok(sha256_bytes) = alloy::hex::decode(sha256_hex) {
+            if let Ok(token) = state.voprf_server.compute_local_token(&sha256_bytes) {
+                let token_hex = alloy::hex::encode(&token);
+                if let Err(e) = state
+                    .voprf_store
+                    .register(&token_hex, &restored.public_address)
+                {
+                    tracing::warn!(error = %e, wallet_id = %wallet_id, "Failed to register VOPRF token");
+                }
+            }
+        }
+    }
+    state.cache_bus.invalidate_address(&restored.public_address);
+
+    let event = AuditEvent::new(AuditEventType::WalletRestored)
+        .with_user(&user.user_id)
+        .with_resource("wallet", &wallet_id)
+        .with_details(serde_json::json!({
+            "owner_user_id": restored.owner_user_id,
+            "deleted_at": wallet.deleted_at,
+        }));
+    let _ = AuditRepository::new(storage).log(&event);
+
+    Ok(Json(WalletResponse::from(restored)))
+}
+
+// ============================================================================
+// Transaction Database Rebuild
+// ============================================================================
+
+/// Only one rebuild runs at a time.
+static REBUILD_RUNNING: AtomicBool = AtomicBool::new(false);
+
+/// Request to replay chain history into the transaction database.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct RebuildTxDatabaseRequest {
+    /// First block to replay.
+    pub from_block: u64,
+    /// Last block to replay (default: chain head). At most 200,000 blocks
+    /// are replayed per call.
+    #[serde(default)]
+    pub to_block: Option<u64>,
+    /// Token contracts to replay (default: the token registry).
+    #[serde(default)]
+    pub token_contracts: Option<Vec<String>>,
+    /// Restore missing records into the live database. Without it the
+    /// rebuild only verifies.
+    #[serde(default)]
+    pub apply: bool,
+}
+
+/// Rebuild the transaction database from chain data (admin action).
+///
+/// Replays ERC-20 transfers of known addresses over a block range into a
+/// scratch database and compares them with the live one. With `apply`,
+/// re-registers wallet addresses and lookups and restores missing or
+/// still-pending records. Use after the redb file was lost or corrupted.
+#[utoipa::path(
+    post,
+    path = "/v1/admin/tx-database/rebuild",
+    tag = "Admin",
+    request_body = RebuildTxDatabaseRequest,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Rebuild report", body = RebuildReport),
+        (status = 400, description = "Invalid block range or token address"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not authorized (platform admin required)"),
+        (status = 409, description = "A rebuild is already running"),
+        (status = 503, description = "Transaction database or RPC unavailable")
+    )
+)]
+pub async fn rebuild_tx_database(
+    AdminOnly(user): AdminOnly,
+    State(state): State<AppState>,
+    Json(request): Json<RebuildTxDatabaseRequest>,
+) -> Result<Json<RebuildReport>, ApiError> {
+    require_platform_admin(&user)?;
+    let tx_db = state
+        .tx_db
+        .clone()
+        .ok_or_else(|| ApiError::service_unavailable("Transaction database is not available"))?;
+    let token_contracts = match request.token_contracts {
+        Some(addresses) => addresses
+            .iter()
+            .map(|a| {
+                Address::from_str(a.trim())
+                    .map_err(|e| ApiError::bad_request(format!("Invalid token contract: {e}")))
+            })
+            .collect::<Result<Vec<_>, _>>()?,
+        None => indexer::fuji_token_contracts(),
+    };
+    if REBUILD_RUNNING.swap(true, Ordering::SeqCst) {
+        return Err(ApiError::conflict("A rebuild is already running"));
+    }
+    let result = indexer::rebuild::rebuild(
+        state.storage(),
+        &tx_db,
+        Some(&state.cache_bus),
+        avax_fuji(),
+        RebuildOptions {
+            from_block: request.from_block,
+            to_block: request.to_block,
+            token_contracts,
+            apply: request.apply,
+        },
+    )
+    .await;
+    REBUILD_RUNNING.store(false, Ordering::SeqCst);
+    let report = result.map_err(|e| match e {
+        IndexerError::InvalidRange(message) => ApiError::bad_request(message),
+        IndexerError::Rpc(message) => ApiError::service_unavailable(message),
+        IndexerError::Db(e) => ApiError::internal(format!("Transaction database error: {e}")),
+    })?;
+
+    let event = AuditEvent::new(AuditEventType::AdminAccess)
+        .with_user(&user.user_id)
+        .with_resource("tx_database", "tx.redb")
+        .with_details(serde_json::json!({
+            "action": "tx_database_rebuild",
+            "from_block": report.from_block,
+            "to_block": report.to_block,
+            "applied": report.applied,
+            "restored": report.restored,
+            "missing_from_database": report.missing_from_database_count,
+            "missing_on_chain": report.missing_on_chain_count,
+        }));
+    let _ = AuditRepository::new(state.storage()).log(&event);
+
+    Ok(Json(report))
+}
+
 // ============================================================================
 // Helper Functions
 // ============================================================================
@@ -634,6 +957,7 @@ mod tests {
             public_address: "0x123...".to_string(),
             status: WalletStatus::Active,
             created_at: "2026-01-28T12:00:00Z".to_string(),
+            tenant_id: None,
         };
 
         let json = serde_json::to_string(&item).unwrap();
@@ -663,6 +987,79 @@ mod tests {
         let path = std::path::Path::new("/nonexistent/path");
         assert_eq!(count_files_recursive(path), 0);
     }
+
+    fn admin(tenant_id: Option<&str>) -> AdminOnly {
+        AdminOnly(AuthenticatedUser {
+            user_id: "admin_1".to_string(),
+            role: crate::auth::Role::Admin,
+            session_id: None,
+            issuer: "https://test.clerk.dev".to_string(),
+            expires_at: Utc::now().timestamp() + 3600,
+            tenant_id: tenant_id.map(str::to_string),
+        })
+    }
+
+    #[tokio::test]
+    async fn tenant_admins_only_see_their_tenant() {
+        let state = AppState::default();
+        let repo = WalletRepository::new(state.storage());
+        for (wallet_id, tenant_id) in [
+            ("w_a", Some("org_a")),
+            ("w_b", Some("org_b")),
+            ("w_0", None),
+        ] {
+            repo.create(
+                &WalletMetadata {
+                    wallet_id: wallet_id.to_string(),
+                    owner_user_id: format!("user_{wallet_id}"),
+                    public_address: format!("0x{wallet_id}"),
+                    created_at: Utc::now(),
+                    status: WalletStatus::Active,
+                    label: None,
+                    email_lookup_key: None,
+                    email_sha256: None,
+                    account_type: Default::default(),
+                    smart_account: None,
+                    lock: None,
+                    deleted_at: None,
+                    purged_at: None,
+                    funded_at: None,
+                    tenant_id: tenant_id.map(str::to_string),
+                },
+                b"test_key",
+            )
+            .unwrap();
+        }
+
+        let Json(all) = list_all_wallets(admin(None), State(state.clone()))
+            .await
+            .unwrap();
+        assert_eq!(all.total, 3);
+        let Json(scoped) = list_all_wallets(admin(Some("org_a")), State(state.clone()))
+            .await
+            .unwrap();
+        assert_eq!(scoped.total, 1);
+        assert_eq!(scoped.wallets[0].tenant_id.as_deref(), Some("org_a"));
+
+        let err = suspend_wallet(
+            admin(Some("org_a")),
+            Path("w_b".to_string()),
+            State(state.clone()),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::NOT_FOUND);
+        assert_eq!(repo.get("w_b").unwrap().status, WalletStatus::Active);
+
+        let err = query_audit_logs(
+            admin(Some("org_a")),
+            Query(serde_json::from_str::<AuditQueryParams>("{}").unwrap()),
+            State(state),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::FORBIDDEN);
+    }
 }
 
 // ============================================================================
@@ -715,9 +1112,10 @@ pub struct PeerInfoResponse {
 ///
 /// Returns this node's VOPRF public key and discovery status.
 pub async fn get_self_node_info(
-    AdminOnly(_user): AdminOnly,
+    AdminOnly(user): AdminOnly,
     State(state): State<AppState>,
 ) -> Result<Json<SelfNodeInfoResponse>, ApiError> {
+    require_platform_admin(&user)?;
     let peer_count = state.peer_registry.peers().len();
     let ratls_available = crate::discovery::ffi::is_ratls_available();
 
@@ -732,13 +1130,19 @@ pub async fn get_self_node_info(
 ///
 /// List all configured discovery peers.
 pub async fn list_peers(
-    AdminOnly(_user): AdminOnly,
+    AdminOnly(user): AdminOnly,
     State(state): State<AppState>,
 ) -> Result<Json<Vec<PeerInfoResponse>>, ApiError> {
+    require_platform_admin(&user)?;
     let peers = state.peer_registry.list_peers();
-    let response: Vec<PeerInfoResponse> = peers
-        .into_iter()
-        .map(|p| PeerInfoResponse {
+    let response: Vec<PeerInfoResponse> = peers.into_iter().map(Into::into).collect();
+
+    Ok(Json(response))
+}
+
+impl From<crate::discovery::PeerConfig> for PeerInfoResponse {
+    fn from(p: crate::discovery::PeerConfig) -> Self {
+        Self {
             node_id: p.node_id,
             url: p.url,
             voprf_public_key: p.voprf_public_key,
@@ -746,10 +1150,30 @@ pub async fn list_peers(
             mrsigner: p.attestation_policy.mrsigner.map(alloy::hex::encode),
             min_isv_svn: p.attestation_policy.min_isv_svn,
             isv_prod_id: p.attestation_policy.isv_prod_id,
-        })
-        .collect();
+        }
+    }
+}
 
-    Ok(Json(response))
+/// A peer's configuration as recorded in `ConfigChanged` audit events.
+fn peer_snapshot(state: &AppState, node_id: &str) -> Option<serde_json::Value> {
+    state
+        .peer_registry
+        .list_peers()
+        .into_iter()
+        .find(|p| p.node_id == node_id)
+        .and_then(|p| serde_json::to_value(PeerInfoResponse::from(p)).ok())
+}
+
+fn log_peer_change(
+    state: &AppState,
+    user: &AuthenticatedUser,
+    node_id: &str,
+    old: Option<serde_json::Value>,
+) {
+    let event = AuditEvent::config_changed(old, peer_snapshot(state, node_id))
+        .with_user(&user.user_id)
+        .with_resource("peer", node_id);
+    let _ = AuditRepository::new(state.storage()).log(&event);
 }
 
 /// POST /v1/admin/peers
@@ -761,6 +1185,7 @@ pub async fn add_peer(
     State(state): State<AppState>,
     Json(body): Json<AddPeerRequest>,
 ) -> Result<(StatusCode, Json<serde_json::Value>), ApiError> {
+    require_platform_admin(&user)?;
     validate_node_id(&body.node_id)?;
     let policy = parse_attestation_policy(&body)?;
 
@@ -776,13 +1201,7 @@ pub async fn add_peer(
         .add_peer(config)
         .map_err(|e| ApiError::bad_request(format!("Failed to add peer: {e}")))?;
 
-    audit_log!(
-        state.storage(),
-        AuditEventType::ConfigChanged,
-        &user,
-        "peer",
-        &body.node_id
-    );
+    log_peer_change(&state, &user, &body.node_id, None);
 
     Ok((
         StatusCode::CREATED,
@@ -801,18 +1220,14 @@ pub async fn remove_peer(
     State(state): State<AppState>,
     Path(node_id): Path<String>,
 ) -> Result<Json<serde_json::Value>, ApiError> {
+    require_platform_admin(&user)?;
+    let old = peer_snapshot(&state, &node_id);
     state
         .peer_registry
         .remove_peer(&node_id)
         .map_err(|e| ApiError::not_found(format!("Peer not found: {e}")))?;
 
-    audit_log!(
-        state.storage(),
-        AuditEventType::ConfigChanged,
-        &user,
-        "peer",
-        &node_id
-    );
+    log_peer_change(&state, &user, &node_id, old);
 
     Ok(Json(serde_json::json!({
         "message": "Peer removed successfully",
@@ -829,6 +1244,7 @@ pub async fn update_peer(
     Path(node_id): Path<String>,
     Json(body): Json<AddPeerRequest>,
 ) -> Result<Json<serde_json::Value>, ApiError> {
+    require_platform_admin(&user)?;
     if body.node_id != node_id {
         return Err(ApiError::bad_request(
             "node_id in path must match node_id in body",
@@ -845,18 +1261,13 @@ pub async fn update_peer(
         attestation_policy: policy,
     };
 
+    let old = peer_snapshot(&state, &node_id);
     state
         .peer_registry
         .update_peer(config)
         .map_err(|e| ApiError::bad_request(format!("Failed to update peer: {e}")))?;
 
-    audit_log!(
-        state.storage(),
-        AuditEventType::ConfigChanged,
-        &user,
-        "peer",
-        &node_id
-    );
+    log_peer_change(&state, &user, &node_id, old);
 
     Ok(Json(serde_json::json!({
         "message": "Peer updated successfully",
@@ -980,14 +1391,15 @@ pub struct RaTlsTestResponse {
     responses(
         (status = 200, description = "Diagnostic completed (check `ok` field)", body = RaTlsTestResponse),
         (status = 401, description = "Unauthorized"),
-        (status = 403, description = "Not authorized (admin required)"),
+        (status = 403, description = "Not authorized (platform admin required)"),
     ),
     security(("bearer_auth" = [])),
     tag = "Admin"
 )]
 pub async fn test_self_ratls(
-    AdminOnly(_user): AdminOnly,
+    AdminOnly(user): AdminOnly,
 ) -> Result<Json<RaTlsTestResponse>, ApiError> {
+    require_platform_admin(&user)?;
     let mut steps: Vec<DiagnosticStep> = Vec::new();
     let target = "self".to_string();
 
@@ -1221,17 +1633,18 @@ pub async fn test_self_ratls(
     responses(
         (status = 200, description = "Diagnostic completed (check `ok` field)", body = RaTlsTestResponse),
         (status = 401, description = "Unauthorized"),
-        (status = 403, description = "Not authorized (admin required)"),
+        (status = 403, description = "Not authorized (platform admin required)"),
         (status = 404, description = "Peer not found"),
     ),
     security(("bearer_auth" = [])),
     tag = "Admin"
 )]
 pub async fn test_peer_ratls(
-    AdminOnly(_user): AdminOnly,
+    AdminOnly(user): AdminOnly,
     State(state): State<AppState>,
     Path(node_id): Path<String>,
 ) -> Result<Json<RaTlsTestResponse>, ApiError> {
+    require_platform_admin(&user)?;
     let target = format!("peer:{node_id}");
     let mut steps: Vec<DiagnosticStep> = Vec::new();
 
@@ -0,0 +1,373 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Admin access logs.
+//!
+//! Every admin read of sensitive data (user and wallet listings, audit
+//! events, name reviews with beneficiary names, escrow parties, key ceremony
+//! approvals, the dormant wallet report) is recorded as its own
+//! `admin_data_read` event naming the fields returned. `GET
+//! /v1/admin/audit/admin-activity` lists those events together with other
+//! admin actions, per admin, for periodic access reviews.
+
+use std::collections::BTreeMap;
+
+use axum::{
+    extract::{Query, State},
+    Json,
+};
+use chrono::{Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::{
+    api::admin::require_platform_admin,
+    auth::{AdminOnly, AuthenticatedUser},
+    error::ApiError,
+    state::AppState,
+    storage::{AuditEvent, AuditEventType, AuditRepository, EncryptedStorage},
+};
+
+/// Default review window.
+const DEFAULT_DAYS: i64 = 30;
+/// Longest range one query may read.
+const MAX_DAYS: i64 = 366;
+
+/// Event types that count as admin activity.
+const ADMIN_EVENT_TYPES: &[AuditEventType] = &[
+    AuditEventType::AdminDataRead,
+    AuditEventType::AdminAccess,
+    AuditEventType::AdminBootstrapped,
+    AuditEventType::AdminBootstrapRevoked,
+    AuditEventType::ConfigChanged,
+    AuditEventType::WebhookKeyRotated,
+    AuditEventType::ApiKeyCreated,
+    AuditEventType::ApiKeyRevoked,
+    AuditEventType::FiatNameReviewDecided,
+    AuditEventType::ReserveKeyCeremony,
+    AuditEventType::WalletKeyRotated,
+    AuditEventType::WalletRestored,
+];
+
+/// Record that `admin` read `fields` of `records` sensitive records.
+///
+/// `resource_id` names a single record, or `*` for a listing.
+pub(crate) fn log_admin_read(
+    storage: &EncryptedStorage,
+    admin: &AuthenticatedUser,
+    resource_type: &str,
+    resource_id: &str,
+    fields: &[&str],
+    records: usize,
+) {
+    let event = AuditEvent::new(AuditEventType::AdminDataRead)
+        .with_user(&admin.user_id)
+        .with_resource(resource_type, resource_id)
+        .with_details(serde_json::json!({
+            "fields": fields,
+            "records": records,
+        }));
+    let _ = AuditRepository::new(storage).log(&event);
+}
+
+/// Query parameters for the admin activity log.
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct AdminActivityParams {
+    /// Only this admin's activity.
+    pub admin_user_id: Option<String>,
+    /// Start date (YYYY-MM-DD, default 30 days before `end_date`).
+    pub start_date: Option<String>,
+    /// End date (YYYY-MM-DD, default today).
+    pub end_date: Option<String>,
+    /// Only sensitive data reads.
+    #[serde(default)]
+    pub reads_only: bool,
+    /// Maximum number of events (default 100, max 1000).
+    pub limit: Option<usize>,
+    /// Offset for pagination.
+    pub offset: Option<usize>,
+}
+
+/// One admin's activity in the range.
+#[derive(Debug, Serialize, ToSchema, PartialEq, Eq)]
+pub struct AdminActivitySummary {
+    pub admin_user_id: String,
+    /// Events of any admin activity type.
+    pub events: usize,
+    /// Sensitive data reads.
+    pub data_reads: usize,
+    /// Sensitive data reads per resource type.
+    pub reads_by_resource: BTreeMap<String, usize>,
+    /// Every field read across those reads.
+    pub fields_read: Vec<String>,
+}
+
+/// Admin activity log.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AdminActivityResponse {
+    pub start_date: String,
+    pub end_date: String,
+    /// Per admin, over all matching events.
+    pub admins: Vec<AdminActivitySummary>,
+    /// Matching events, newest first.
+    pub events: Vec<AuditEvent>,
+    /// Total count (before limit/offset).
+    pub total: usize,
+    pub has_more: bool,
+}
+
+fn summarize(events: &[AuditEvent]) -> Vec<AdminActivitySummary> {
+    let mut by_admin: BTreeMap<&str, AdminActivitySummary> = BTreeMap::new();
+    for event in events {
+        let Some(admin) = event.user_id.as_deref() else {
+            continue;
+        };
+        let summary = by_admin
+            .entry(admin)
+            .or_insert_with(|| AdminActivitySummary {
+                admin_user_id: admin.to_string(),
+                events: 0,
+                data_reads: 0,
+                reads_by_resource: BTreeMap::new(),
+                fields_read: Vec::new(),
+            });
+        summary.events += 1;
+        if event.event_type != AuditEventType::AdminDataRead {
+            continue;
+        }
+        summary.data_reads += 1;
+        *summary
+            .reads_by_resource
+            .entry(event.resource_type.clone().unwrap_or_default())
+            .or_insert(0) += 1;
+        let fields = event
+            .details
+            .as_ref()
+            .and_then(|d| d["fields"].as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(|f| f.as_str());
+        for field in fields {
+            if !summary.fields_read.iter().any(|f| f == field) {
+                summary.fields_read.push(field.to_string());
+            }
+        }
+    }
+    by_admin
+        .into_values()
+        .map(|mut summary| {
+            summary.fields_read.sort();
+            summary
+        })
+        .collect()
+}
+
+/// Admin activity for access reviews (admin only).
+///
+/// Lists sensitive data reads and other admin actions, optionally for one
+/// admin, with a per-admin summary of what was read. Querying this log is
+/// itself recorded.
+#[utoipa::path(
+    get,
+    path = "/v1/admin/audit/admin-activity",
+    tag = "Admin",
+    params(AdminActivityParams),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Admin activity", body = AdminActivityResponse),
+        (status = 400, description = "Invalid date range"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not authorized (platform admin required)")
+    )
+)]
+pub async fn get_admin_activity(
+    AdminOnly(admin): AdminOnly,
+    Query(params): Query<AdminActivityParams>,
+    State(state): State<AppState>,
+) -> Result<Json<AdminActivityResponse>, ApiError> {
+    require_platform_admin(&admin)?;
+    let parse = |value: &str, name: &str| {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d")
+            .map_err(|_| ApiError::bad_request(format!("Invalid {name} format. Use YYYY-MM-DD.")))
+    };
+    let end = match &params.end_date {
+        Some(value) => parse(value, "end_date")?,
+        None => Utc::now().date_naive(),
+    };
+    let start = match &params.start_date {
+        Some(value) => parse(value, "start_date")?,
+        None => end - Duration::days(DEFAULT_DAYS),
+    };
+    if start > end || (end - start).num_days() >= MAX_DAYS {
+        return Err(ApiError::bad_request(format!(
+            "start_date must be on or before end_date, at most {MAX_DAYS} days apart"
+        )));
+    }
+    let start_date = start.format("%Y-%m-%d").to_string();
+    let end_date = end.format("%Y-%m-%d").to_string();
+
+    let storage = state.storage();
+    let mut events: Vec<AuditEvent> = AuditRepository::new(storage)
+        .read_events_range(&start_date, &end_date)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|e| {
+            if params.reads_only {
+                e.event_type == AuditEventType::AdminDataRead
+            } else {
+                ADMIN_EVENT_TYPES.contains(&e.event_type)
+            }
+        })
+        .filter(|e| {
+            params
+                .admin_user_id
+                .as_deref()
+                .is_none_or(|id| e.user_id.as_deref() == Some(id))
+        })
+        .collect();
+    events.reverse();
+
+    let admins = summarize(&events);
+    let total = events.len();
+    let limit = params.limit.unwrap_or(100).min(1000);
+    let offset = params.offset.unwrap_or(0);
+    let has_more = offset + limit < total;
+    let events: Vec<_> = events.into_iter().skip(offset).take(limit).collect();
+
+    log_admin_read(
+        storage,
+        &admin,
+        "admin_activity",
+        params.admin_user_id.as_deref().unwrap_or("*"),
+        &["user_id", "resource_id", "details"],
+        events.len(),
+    );
+
+    Ok(Json(AdminActivityResponse {
+        start_date,
+        end_date,
+        admins,
+        events,
+        total,
+        has_more,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::Role;
+
+    fn admin(user_id: &str) -> AuthenticatedUser {
+        AuthenticatedUser {
+            user_id: user_id.to_string(),
+            role: Role::Admin,
+            session_id: None,
+            issuer: "test".to_string(),
+            expires_at: 0,
+            tenant_id: None,
+        }
+    }
+
+    #[test]
+    fn summary_collects_reads_per_admin() {
+        let read = |admin: &str, resource: &str, fields: &[&str]| {
+            AuditEvent::new(AuditEventType::AdminDataRead)
+                .with_user(admin)
+                .with_resource(resource, "*")
+                .with_details(serde_json::json!({ "fields": fields, "records": 1 }))
+        };
+        let events = vec![
+            read("admin_a", "wallets", &["owner_user_id", "public_address"]),
+            read("admin_a", "users", &["user_id"]),
+            read("admin_a", "wallets", &["public_address"]),
+            AuditEvent::new(AuditEventType::ConfigChanged).with_user("admin_b"),
+        ];
+        let summary = summarize(&events);
+        assert_eq!(summary.len(), 2);
+        assert_eq!(summary[0].admin_user_id, "admin_a");
+        assert_eq!(summary[0].data_reads, 3);
+        assert_eq!(summary[0].reads_by_resource["wallets"], 2);
+        assert_eq!(
+            summary[0].fields_read,
+            vec!["owner_user_id", "public_address", "user_id"]
+        );
+        assert_eq!(summary[1].events, 1);
+        assert_eq!(summary[1].data_reads, 0);
+    }
+
+    #[tokio::test]
+    async fn activity_is_filtered_by_admin() {
+        let state = AppState::default();
+        let storage = state.storage();
+        log_admin_read(storage, &admin("admin_a"), "users", "*", &["user_id"], 3);
+        log_admin_read(
+            storage,
+            &admin("admin_b"),
+            "wallets",
+            "*",
+            &["owner_user_id"],
+            1,
+        );
+        let _ = AuditRepository::new(storage)
+            .log(&AuditEvent::new(AuditEventType::WalletCreated).with_user("user_1"));
+
+        let Json(response) = get_admin_activity(
+            AdminOnly(admin("admin_c")),
+            Query(AdminActivityParams {
+                admin_user_id: Some("admin_a".to_string()),
+                start_date: None,
+                end_date: None,
+                reads_only: false,
+                limit: None,
+                offset: None,
+            }),
+            State(state.clone()),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.total, 1);
+        assert_eq!(response.events[0].resource_type.as_deref(), Some("users"));
+        assert_eq!(response.admins.len(), 1);
+
+        let Json(all) = get_admin_activity(
+            AdminOnly(admin("admin_c")),
+            Query(AdminActivityParams {
+                admin_user_id: None,
+                start_date: None,
+                end_date: None,
+                reads_only: true,
+                limit: None,
+                offset: None,
+            }),
+            State(state),
+        )
+        .await
+        .unwrap();
+        // The first query was recorded as admin_c's read.
+        assert_eq!(all.total, 3);
+        assert_eq!(all.events[0].user_id.as_deref(), Some("admin_c"));
+    }
+
+    #[tokio::test]
+    async fn tenant_admins_cannot_review_admin_activity() {
+        let mut tenant_admin = admin("admin_acme");
+        tenant_admin.tenant_id = Some("org_acme".to_string());
+        let err = get_admin_activity(
+            AdminOnly(tenant_admin),
+            Query(AdminActivityParams {
+                admin_user_id: None,
+                start_date: None,
+                end_date: None,
+                reads_only: false,
+                limit: None,
+                offset: None,
+            }),
+            State(AppState::default()),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.code, Some("platform_admin_required"));
+    }
+}
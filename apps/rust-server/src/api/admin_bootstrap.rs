@@ -0,0 +1,315 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Cold-start designation of the first admin.
+//!
+//! A fresh deployment has no admin until someone sets the role in Clerk.
+//! `POST /v1/admin/bootstrap` lets the operator designate one instead: any
+//! authenticated user presenting the setup token armed at startup (sealed
+//! `ADMIN_BOOTSTRAP_TOKEN`, or generated and shown once on the enclave's
+//! console) before it expires becomes admin. It succeeds once; the token is
+//! discarded afterwards and the ceremony is recorded as an
+//! `admin_bootstrapped` audit event. `DELETE /v1/admin/bootstrap` revokes
+//! the bootstrap admin's grant, or an unused token, for good.
+
+use std::sync::Mutex;
+
+use axum::{extract::State, Json};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::{
+    api::admin::require_platform_admin,
+    auth::{AdminOnly, Auth},
+    error::{ApiError, StorageContext},
+    state::AppState,
+    storage::{
+        repository::admin_bootstrap::setup_token_sha256, AdminBootstrapRepository, AuditEvent,
+        AuditEventType, AuditRepository, SetupTokenSource,
+    },
+};
+
+/// Serializes bootstrap attempts so only one can claim the token.
+static BOOTSTRAP_LOCK: Mutex<()> = Mutex::new(());
+
+/// Request to designate the initial admin.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct AdminBootstrapRequest {
+    /// Setup token from sealed configuration or the enclave log.
+    pub setup_token: String,
+}
+
+/// The designated initial admin.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct AdminBootstrapResponse {
+    pub admin_user_id: String,
+    pub completed_at: DateTime<Utc>,
+    pub token_source: SetupTokenSource,
+}
+
+/// Result of revoking the admin bootstrap.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct AdminBootstrapRevocation {
+    /// The bootstrap admin whose grant was removed; their JWT role applies
+    /// again.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub admin_user_id: Option<String>,
+    /// Whether an unused setup token was discarded.
+    pub token_discarded: bool,
+    pub revoked_at: DateTime<Utc>,
+}
+
+/// Designate the caller as the initial admin.
+///
+/// Requires the one-time setup token. The caller is treated as admin from
+/// the next request on, whatever role their JWT carries.
+#[utoipa::path(
+    post,
+    path = "/v1/admin/bootstrap",
+    tag = "Admin",
+    request_body = AdminBootstrapRequest,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Caller designated as admin", body = AdminBootstrapResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Invalid setup token"),
+        (status = 409, description = "An admin was already designated, the bootstrap was revoked, or no valid token is armed")
+    )
+)]
+pub async fn bootstrap_admin(
+    Auth(user): Auth,
+    State(state): State<AppState>,
+    Json(request): Json<AdminBootstrapRequest>,
+) -> Result<Json<AdminBootstrapResponse>, ApiError> {
+    let storage = state.storage();
+    let repo = AdminBootstrapRepository::new(storage);
+    let _guard = BOOTSTRAP_LOCK
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+    let now = Utc::now();
+    let record = repo.get().context("Failed to load bootstrap state")?;
+    if record.as_ref().is_some_and(|r| r.revoked_at.is_some()) {
+        return Err(
+            ApiError::conflict("The admin bootstrap was revoked").with_code("bootstrap_revoked")
+        );
+    }
+    if record.as_ref().is_some_and(|r| r.admin_user_id.is_some()) {
+        return Err(
+            ApiError::conflict("An admin was already designated").with_code("bootstrap_completed")
+        );
+    }
+    let Some(mut record) = record.filter(|r| r.token_sha256.is_some()) else {
+        return Err(ApiError::conflict("No setup token is armed").with_code("bootstrap_not_armed"));
+    };
+    if !record.token_valid(now) {
+        return Err(ApiError::conflict(
+            "The setup token has expired; restart the server to arm a new one",
+        )
+        .with_code("setup_token_expired"));
+    }
+
+    if record.token_sha256.as_deref() != Some(setup_token_sha256(&request.setup_token).as_str()) {
+        let event = AuditEvent::new(AuditEventType::PermissionDenied)
+            .with_user(&user.user_id)
+            .with_resource("system", "admin_bootstrap")
+            .failed("Invalid setup token");
+        let _ = AuditRepository::new(storage).log(&event);
+        return Err(ApiError::forbidden("Invalid setup token").with_code("invalid_setup_token"));
+    }
+
+    let token_source = record.token_source.unwrap_or(SetupTokenSource::Generated);
+    record.admin_user_id = Some(user.user_id.clone());
+    record.completed_at = Some(now);
+    record.token_sha256 = None;
+    record.expires_at = None;
+    repo.save(&record)
+        .context("Failed to record bootstrap admin")?;
+
+    let event = AuditEvent::new(AuditEventType::AdminBootstrapped)
+        .with_user(&user.user_id)
+        .with_resource("system", "admin_bootstrap")
+        .with_details(serde_json::json!({
+            "token_source": token_source,
+            "token_armed_at": record.armed_at,
+            "previous_role": user.role.to_string(),
+        }));
+    let _ = AuditRepository::new(storage).log(&event);
+    tracing::warn!(user_id = %user.user_id, "Initial admin designated via setup token");
+
+    Ok(Json(AdminBootstrapResponse {
+        admin_user_id: user.user_id,
+        completed_at: now,
+        token_source,
+    }))
+}
+
+/// Revoke the admin bootstrap (admin action).
+///
+/// Removes the bootstrap admin's grant, or discards a token nobody used,
+/// and stops arming new tokens. Further admins are assigned in Clerk.
+#[utoipa::path(
+    delete,
+    path = "/v1/admin/bootstrap",
+    tag = "Admin",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Bootstrap revoked", body = AdminBootstrapRevocation),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Not authorized (platform admin required)"),
+        (status = 409, description = "The bootstrap was already revoked")
+    )
+)]
+pub async fn revoke_admin_bootstrap(
+    AdminOnly(admin): AdminOnly,
+    State(state): State<AppState>,
+) -> Result<Json<AdminBootstrapRevocation>, ApiError> {
+    require_platform_admin(&admin)?;
+    let storage = state.storage();
+    let _guard = BOOTSTRAP_LOCK
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+    let now = Utc::now();
+    let revoked = AdminBootstrapRepository::new(storage)
+        .revoke(&admin.user_id, now)
+        .context("Failed to revoke admin bootstrap")?
+        .ok_or_else(|| {
+            ApiError::conflict("The admin bootstrap was already revoked")
+                .with_code("bootstrap_revoked")
+        })?;
+
+    let event = AuditEvent::new(AuditEventType::AdminBootstrapRevoked)
+        .with_user(&admin.user_id)
+        .with_resource("system", "admin_bootstrap")
+        .with_details(serde_json::json!({
+            "admin_user_id": revoked.admin_user_id,
+            "token_discarded": revoked.token_discarded,
+        }));
+    let _ = AuditRepository::new(storage).log(&event);
+    tracing::warn!(user_id = %admin.user_id, "Admin bootstrap revoked");
+
+    Ok(Json(AdminBootstrapRevocation {
+        admin_user_id: revoked.admin_user_id,
+        token_discarded: revoked.token_discarded,
+        revoked_at: now,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::{AuthenticatedUser, Role};
+    use crate::storage::{repository::admin_bootstrap::SETUP_TOKEN_TTL, BootstrapArming};
+    use chrono::TimeDelta;
+
+    fn client(user_id: &str) -> Auth {
+        Auth(AuthenticatedUser {
+            user_id: user_id.to_string(),
+            role: Role::Client,
+            session_id: None,
+            issuer: "https://test.clerk.dev".to_string(),
+            expires_at: Utc::now().timestamp() + 3600,
+            tenant_id: None,
+        })
+    }
+
+    fn request(token: &str) -> Json<AdminBootstrapRequest> {
+        Json(AdminBootstrapRequest {
+            setup_token: token.to_string(),
+        })
+    }
+
+    #[tokio::test]
+    async fn setup_token_designates_one_admin() {
+        let state = AppState::default();
+        let err = bootstrap_admin(client("user_a"), State(state.clone()), request("x"))
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, axum::http::StatusCode::CONFLICT);
+
+        let repo = AdminBootstrapRepository::new(state.storage());
+        assert!(matches!(
+            repo.arm(Some("sealed-token"), Utc::now()).unwrap(),
+            BootstrapArming::Sealed { .. }
+        ));
+
+        let err = bootstrap_admin(client("user_a"), State(state.clone()), request("wrong"))
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, axum::http::StatusCode::FORBIDDEN);
+        assert!(repo.admin_user_id().unwrap().is_none());
+
+        let Json(response) = bootstrap_admin(
+            client("user_a"),
+            State(state.clone()),
+            request("sealed-token"),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.admin_user_id, "user_a");
+        assert_eq!(response.token_source, SetupTokenSource::Sealed);
+        assert_eq!(repo.admin_user_id().unwrap().as_deref(), Some("user_a"));
+
+        let err = bootstrap_admin(
+            client("user_b"),
+            State(state.clone()),
+            request("sealed-token"),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, axum::http::StatusCode::CONFLICT);
+        assert!(repo.get().unwrap().unwrap().token_sha256.is_none());
+    }
+
+    #[tokio::test]
+    async fn expired_tokens_are_refused_and_revocation_removes_the_grant() {
+        let state = AppState::default();
+        let repo = AdminBootstrapRepository::new(state.storage());
+        let BootstrapArming::Generated { token, .. } = repo
+            .arm(None, Utc::now() - SETUP_TOKEN_TTL - TimeDelta::minutes(1))
+            .unwrap()
+        else {
+            panic!("expected a generated token");
+        };
+        let err = bootstrap_admin(client("user_a"), State(state.clone()), request(&token))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code, Some("setup_token_expired"));
+
+        let BootstrapArming::Generated { token, .. } = repo.arm(None, Utc::now()).unwrap() else {
+            panic!("expected a new token after expiry");
+        };
+        let _ = bootstrap_admin(client("user_a"), State(state.clone()), request(&token))
+            .await
+            .unwrap();
+
+        let Auth(mut tenant_admin) = client("admin_t");
+        tenant_admin.role = Role::Admin;
+        tenant_admin.tenant_id = Some("tenant_a".to_string());
+        let err = revoke_admin_bootstrap(AdminOnly(tenant_admin), State(state.clone()))
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, axum::http::StatusCode::FORBIDDEN);
+
+        let Auth(mut admin) = client("admin_b");
+        admin.role = Role::Admin;
+        let Json(revoked) = revoke_admin_bootstrap(AdminOnly(admin.clone()), State(state.clone()))
+            .await
+            .unwrap();
+        assert_eq!(revoked.admin_user_id.as_deref(), Some("user_a"));
+        assert!(!revoked.token_discarded);
+        assert!(repo.admin_user_id().unwrap().is_none());
+
+        let err = revoke_admin_bootstrap(AdminOnly(admin), State(state.clone()))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code, Some("bootstrap_revoked"));
+        let err = bootstrap_admin(client("user_c"), State(state), request(&token))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code, Some("bootstrap_revoked"));
+    }
+}
@@ -0,0 +1,430 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Storage integrity report.
+//!
+//! `GET /v1/admin/integrity` cross-checks the storage subsystems against
+//! each other and lists every discrepancy by check:
+//!
+//! - `wallet_keys`: every wallet's metadata reads, and its private key
+//!   reads, parses and controls the wallet's address (for smart accounts,
+//!   the owner address);
+//! - `transaction_index`: every wallet index entry in the transaction
+//!   database resolves to a transaction record;
+//! - `fiat_request_wallets`: every fiat request's wallet exists;
+//! - `mirrored_transactions`: a record shared by two internal wallets is
+//!   indexed for both sides and its counterparty exists; a replaced
+//!   transaction and its replacement link to each other and are not both
+//!   confirmed.
+//!
+//! The report only reads; repairs are left to operators.
+
+use std::collections::{HashMap, HashSet};
+
+use axum::{extract::State, Json};
+use chrono::Utc;
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::{
+    api::admin::require_platform_admin,
+    auth::AdminOnly,
+    blockchain::{same_address, signing::signer_from_pem},
+    error::{ApiError, StorageContext},
+    state::AppState,
+    storage::{
+        FiatRequestRepository, StoredFiatRequest, StoredTransaction, TxStatus, WalletRepository,
+    },
+};
+
+/// A cross-check run by the integrity report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum IntegrityCheck {
+    WalletKeys,
+    TransactionIndex,
+    FiatRequestWallets,
+    MirroredTransactions,
+}
+
+/// One inconsistency found by a check.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct IntegrityDiscrepancy {
+    /// Wallet ID, transaction hash or fiat request ID.
+    pub subject: String,
+    pub detail: String,
+}
+
+/// Outcome of one check.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct IntegrityCheckResult {
+    pub check: IntegrityCheck,
+    /// Records examined.
+    pub checked: usize,
+    pub discrepancies: Vec<IntegrityDiscrepancy>,
+}
+
+impl IntegrityCheckResult {
+    fn new(check: IntegrityCheck) -> Self {
+        Self {
+            check,
+            checked: 0,
+            discrepancies: Vec::new(),
+        }
+    }
+
+    fn flag(&mut self, subject: &str, detail: String) {
+        self.discrepancies.push(IntegrityDiscrepancy {
+            subject: subject.to_string(),
+            detail,
+        });
+    }
+}
+
+/// Storage integrity report.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct IntegrityReportResponse {
+    pub generated_at: String,
+    pub checks: Vec<IntegrityCheckResult>,
+    pub total_discrepancies: usize,
+}
+
+/// Every wallet's metadata and key read, and the key controls the wallet.
+fn check_wallet_keys(wallets: &WalletRepository) -> Result<IntegrityCheckResult, ApiError> {
+    let mut result = IntegrityCheckResult::new(IntegrityCheck::WalletKeys);
+    for wallet_id in wallets.list_all_ids().context("Failed to list wallets")? {
+        result.checked += 1;
+        let meta = match wallets.get(&wallet_id) {
+            Ok(meta) => meta,
+            Err(e) => {
+                result.flag(&wallet_id, format!("metadata unreadable: {e}"));
+                continue;
+            }
+        };
+        let pem = match wallets.read_private_key(&wallet_id) {
+            Ok(pem) => pem,
+            Err(e) => {
+                result.flag(&wallet_id, format!("private key unreadable: {e}"));
+                continue;
+            }
+        };
+        let signer = match signer_from_pem(&pem) {
+            Ok(signer) => signer,
+            Err(e) => {
+                result.flag(&wallet_id, format!("private key does not parse: {e}"));
+                continue;
+            }
+        };
+        let expected = meta
+            .smart_account
+            .as_ref()
+            .map_or(&meta.public_address, |info| &info.owner_address);
+        let actual = signer.address().to_string();
+        if !same_address(&actual, expected) {
+            result.flag(
+                &wallet_id,
+                format!("private key controls {actual}, expected {expected}"),
+            );
+        }
+    }
+    Ok(result)
+}
+
+/// Every index entry resolves to a transaction record.
+fn check_transaction_index(
+    entries: &[(String, String, String)],
+    transactions: &HashMap<&str, &StoredTransaction>,
+) -> IntegrityCheckResult {
+    let mut result = IntegrityCheckResult::new(IntegrityCheck::TransactionIndex);
+    for (address, tx_hash, direction) in entries {
+        result.checked += 1;
+        if !transactions.contains_key(tx_hash.as_str()) {
+            result.flag(
+                tx_hash,
+                format!("indexed as {direction} for {address} but no transaction record exists"),
+            );
+        }
+    }
+    result
+}
+
+/// Every fiat request's wallet exists.
+fn check_fiat_request_wallets(
+    requests: &[StoredFiatRequest],
+    wallet_exists: impl Fn(&str) -> bool,
+) -> IntegrityCheckResult {
+    let mut result = IntegrityCheckResult::new(IntegrityCheck::FiatRequestWallets);
+    for request in requests {
+        result.checked += 1;
+        if !wallet_exists(&request.wallet_id) {
+            result.flag(
+                &request.request_id,
+                format!("wallet {} does not exist", request.wallet_id),
+            );
+        }
+    }
+    result
+}
+
+/// Shared and linked transaction records agree with each other.
+fn check_mirrored_transactions(
+    entries: &[(String, String, String)],
+    transactions: &HashMap<&str, &StoredTransaction>,
+    wallet_exists: impl Fn(&str) -> bool,
+) -> IntegrityCheckResult {
+    let mut result = IntegrityCheckResult::new(IntegrityCheck::MirroredTransactions);
+    let indexed: HashSet<(&str, &str, &str)> = entries
+        .iter()
+        .map(|(address, tx_hash, direction)| {
+            (address.as_str(), tx_hash.as_str(), direction.as_str())
+        })
+        .collect();
+
+    let mut hashes: Vec<_> = transactions.keys().copied().collect();
+    hashes.sort_unstable();
+    for hash in hashes {
+        let tx = transactions[hash];
+        if let Some(counterparty) = &tx.counterparty_wallet_id {
+            result.checked += 1;
+            if !wallet_exists(counterparty) {
+                result.flag(
+                    hash,
+                    format!("counterparty wallet {counterparty} does not exist"),
+                );
+            }
+            for (address, direction) in [(&tx.from, "sent"), (&tx.to, "received")] {
+                if !indexed.contains(&(address.to_lowercase().as_str(), hash, direction)) {
+                    result.flag(hash, format!("not indexed as {direction} for {address}"));
+                }
+            }
+        }
+        if let Some(replacement) = &tx.replaced_by {
+            result.checked += 1;
+            match transactions.get(replacement.as_str()) {
+                None => result.flag(hash, format!("replacement {replacement} has no record")),
+                Some(next) if next.replaces.as_deref() != Some(hash) => result.flag(
+                    hash,
+                    format!("replacement {replacement} does not link back"),
+                ),
+                Some(next)
+                    if tx.status == TxStatus::Confirmed && next.status == TxStatus::Confirmed =>
+                {
+                    result.flag(
+                        hash,
+                        format!("confirmed together with its replacement {replacement}"),
+                    )
+                }
+                Some(_) => {}
+            }
+        }
+    }
+    result
+}
+
+/// Cross-check storage subsystems (platform admin only).
+///
+/// Reads every wallet, index entry, transaction and fiat request and
+/// reports inconsistencies between them by check. Nothing is modified.
+#[utoipa::path(
+    get,
+    path = "/v1/admin/integrity",
+    tag = "Admin",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Integrity report", body = IntegrityReportResponse),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not authorized (platform admin required)")
+    )
+)]
+pub async fn get_integrity_report(
+    AdminOnly(admin): AdminOnly,
+    State(state): State<AppState>,
+) -> Result<Json<IntegrityReportResponse>, ApiError> {
+    require_platform_admin(&admin)?;
+    let storage = state.storage();
+    let wallets = WalletRepository::new(storage);
+
+    let (entries, transactions) = match &state.tx_db {
+        Some(tx_db) => (
+            tx_db
+                .list_index_entries()
+                .map_err(|e| ApiError::internal(format!("Failed to list index entries: {e}")))?,
+            tx_db
+                .list_all_transactions()
+                .map_err(|e| ApiError::internal(format!("Failed to list transactions: {e}")))?,
+        ),
+        None => (Vec::new(), Vec::new()),
+    };
+    let by_hash: HashMap<&str, &StoredTransaction> = transactions
+        .iter()
+        .map(|tx| (tx.tx_hash.as_str(), tx))
+        .collect();
+    let requests = FiatRequestRepository::new(storage)
+        .list_all()
+        .context("Failed to list fiat requests")?;
+
+    let checks = vec![
+        check_wallet_keys(&wallets)?,
+        check_transaction_index(&entries, &by_hash),
+        check_fiat_request_wallets(&requests, |id| wallets.exists(id)),
+        check_mirrored_transactions(&entries, &by_hash, |id| wallets.exists(id)),
+    ];
+    for check in &checks {
+        if !check.discrepancies.is_empty() {
+            tracing::warn!(
+                check = ?check.check,
+                discrepancies = check.discrepancies.len(),
+                "Storage integrity check found discrepancies"
+            );
+        }
+    }
+
+    Ok(Json(IntegrityReportResponse {
+        generated_at: Utc::now().to_rfc3339(),
+        total_discrepancies: checks.iter().map(|c| c.discrepancies.len()).sum(),
+        checks,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::{repository::service_wallet::generate_secp256k1_keypair, TokenType};
+    use crate::storage::{FiatDirection, WalletMetadata, WalletStatus};
+
+    fn tx(hash: &str, counterparty: Option<&str>) -> StoredTransaction {
+        StoredTransaction::new_pending(
+            hash.to_string(),
+            "w-sender".to_string(),
+            counterparty.map(str::to_string),
+            "0xAAAA".to_string(),
+            "0xBBBB".to_string(),
+            "1.0".to_string(),
+            TokenType::Native,
+            "fuji".to_string(),
+            String::new(),
+        )
+    }
+
+    fn entry(address: &str, hash: &str, direction: &str) -> (String, String, String) {
+        (address.to_string(), hash.to_string(), direction.to_string())
+    }
+
+    #[test]
+    fn wallet_keys_must_read_parse_and_match_the_address() {
+        let state = AppState::default();
+        let wallets = WalletRepository::new(state.storage());
+        let meta = |id: &str, address: &str| WalletMetadata {
+            wallet_id: id.to_string(),
+            owner_user_id: "u-1".to_string(),
+            public_address: address.to_string(),
+            created_at: Utc::now(),
+            status: WalletStatus::Active,
+            label: None,
+            email_lookup_key: None,
+            email_sha256: None,
+            account_type: Default::default(),
+            smart_account: None,
+            lock: None,
+            deleted_at: None,
+            purged_at: None,
+            funded_at: None,
+            tenant_id: None,
+        };
+        let (pem, address) = generate_secp256k1_keypair().unwrap();
+        wallets
+            .create(&meta("w-good", &address), pem.as_bytes())
+            .unwrap();
+        wallets
+            .create(
+                &meta("w-moved", "0x1111111111111111111111111111111111111111"),
+                pem.as_bytes(),
+            )
+            .unwrap();
+        wallets
+            .create(&meta("w-garbled", &address), b"not a key")
+            .unwrap();
+
+        let result = check_wallet_keys(&wallets).unwrap();
+        assert_eq!(result.checked, 3);
+        let mut flagged: Vec<_> = result
+            .discrepancies
+            .iter()
+            .map(|d| d.subject.as_str())
+            .collect();
+        flagged.sort_unstable();
+        assert_eq!(flagged, ["w-garbled", "w-moved"]);
+    }
+
+    #[test]
+    fn dangling_index_entries_and_missing_fiat_wallets_are_flagged() {
+        let kept = tx("0xkept", None);
+        let by_hash = HashMap::from([("0xkept", &kept)]);
+        let entries = [
+            entry("0xaaaa", "0xkept", "sent"),
+            entry("0xaaaa", "0xgone", "sent"),
+        ];
+        let index = check_transaction_index(&entries, &by_hash);
+        assert_eq!(index.checked, 2);
+        assert_eq!(index.discrepancies.len(), 1);
+        assert_eq!(index.discrepancies[0].subject, "0xgone");
+
+        let request = |id: &str, wallet: &str| {
+            StoredFiatRequest::new_queued(
+                id.to_string(),
+                wallet.to_string(),
+                "u-1".to_string(),
+                FiatDirection::OnRamp,
+                "10.00".to_string(),
+                "truelayer_sandbox".to_string(),
+                None,
+            )
+        };
+        let fiat = check_fiat_request_wallets(
+            &[request("fr-1", "w-live"), request("fr-2", "w-gone")],
+            |id| id == "w-live",
+        );
+        assert_eq!(fiat.checked, 2);
+        assert_eq!(fiat.discrepancies[0].subject, "fr-2");
+    }
+
+    #[test]
+    fn mirrored_and_replaced_records_must_agree() {
+        let mirrored = tx("0xmirror", Some("w-receiver"));
+        let half = tx("0xhalf", Some("w-gone"));
+        let mut replaced = tx("0xold", None);
+        replaced.replaced_by = Some("0xnew".to_string());
+        replaced.status = TxStatus::Confirmed;
+        let mut replacement = tx("0xnew", None);
+        replacement.replaces = Some("0xold".to_string());
+        replacement.status = TxStatus::Confirmed;
+        let by_hash = HashMap::from([
+            ("0xmirror", &mirrored),
+            ("0xhalf", &half),
+            ("0xold", &replaced),
+            ("0xnew", &replacement),
+        ]);
+        let entries = [
+            entry("0xaaaa", "0xmirror", "sent"),
+            entry("0xbbbb", "0xmirror", "received"),
+            entry("0xaaaa", "0xhalf", "sent"),
+        ];
+
+        let result = check_mirrored_transactions(&entries, &by_hash, |id| id == "w-receiver");
+        assert_eq!(result.checked, 3);
+        let details: Vec<_> = result
+            .discrepancies
+            .iter()
+            .map(|d| (d.subject.as_str(), d.detail.as_str()))
+            .collect();
+        assert_eq!(
+            details,
+            [
+                ("0xhalf", "counterparty wallet w-gone does not exist"),
+                ("0xhalf", "not indexed as received for 0xBBBB"),
+                ("0xold", "confirmed together with its replacement 0xnew"),
+            ]
+        );
+    }
+}
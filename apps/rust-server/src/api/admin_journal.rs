@@ -0,0 +1,141 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Accounting journal.
+//!
+//! `GET /v1/admin/journal/entries` lists the double-entry journal kept by
+//! the [journal poster](crate::journal) and `GET
+//! /v1/admin/journal/trial-balance` sums it per asset and account, flagging
+//! entries or assets whose debits and credits differ. Finance reads these
+//! instead of reconstructing movements from raw transaction lists.
+
+use axum::{
+    extract::{Query, State},
+    Json,
+};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::{
+    api::{admin::require_platform_admin, admin_activity::log_admin_read},
+    auth::AdminOnly,
+    error::{ApiError, StorageContext},
+    state::AppState,
+    storage::{JournalEntry, JournalRepository, JournalSource, TrialBalance},
+};
+
+const DEFAULT_LIMIT: usize = 100;
+const MAX_LIMIT: usize = 1000;
+
+/// Query parameters for listing journal entries.
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct JournalEntriesParams {
+    /// Only entries posting to this account key, e.g. `wallet:{wallet_id}`,
+    /// `reserve`, `fees`, `escrow:{escrow_id}`, `network_fees` or
+    /// `external`.
+    pub account: Option<String>,
+    /// Only entries derived from this kind of record.
+    pub source: Option<JournalSource>,
+    /// Maximum number of entries (default 100, max 1000).
+    pub limit: Option<usize>,
+}
+
+/// Journal entries, newest first.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct JournalEntriesResponse {
+    pub entries: Vec<JournalEntry>,
+    /// Entries matching the filters, before `limit`.
+    pub total: usize,
+}
+
+/// Trial balance of the whole journal.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TrialBalanceResponse {
+    pub generated_at: String,
+    #[serde(flatten)]
+    pub trial_balance: TrialBalance,
+}
+
+/// List journal entries.
+///
+/// Platform admins only.
+#[utoipa::path(
+    get,
+    path = "/v1/admin/journal/entries",
+    tag = "Admin",
+    security(("bearer_auth" = [])),
+    params(JournalEntriesParams),
+    responses(
+        (status = 200, description = "Journal entries", body = JournalEntriesResponse),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not authorized (platform admin required)")
+    )
+)]
+pub async fn list_journal_entries(
+    AdminOnly(admin): AdminOnly,
+    State(state): State<AppState>,
+    Query(params): Query<JournalEntriesParams>,
+) -> Result<Json<JournalEntriesResponse>, ApiError> {
+    require_platform_admin(&admin)?;
+    let storage = state.storage();
+    let limit = params.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+
+    let mut entries: Vec<JournalEntry> = JournalRepository::new(storage)
+        .list()
+        .context("Failed to read journal")?
+        .into_iter()
+        .filter(|e| params.source.is_none_or(|source| e.source == source))
+        .filter(|e| params.account.as_deref().is_none_or(|key| e.touches(key)))
+        .collect();
+    entries.reverse();
+    let total = entries.len();
+    entries.truncate(limit);
+
+    log_admin_read(
+        storage,
+        &admin,
+        "journal",
+        params.account.as_deref().unwrap_or("all"),
+        &["postings"],
+        entries.len(),
+    );
+    Ok(Json(JournalEntriesResponse { entries, total }))
+}
+
+/// Journal trial balance.
+///
+/// Sums debits and credits per asset and account. `balanced` is false when
+/// any entry, or any asset's totals, do not balance. Platform admins only.
+#[utoipa::path(
+    get,
+    path = "/v1/admin/journal/trial-balance",
+    tag = "Admin",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Trial balance", body = TrialBalanceResponse),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not authorized (platform admin required)")
+    )
+)]
+pub async fn get_trial_balance(
+    AdminOnly(admin): AdminOnly,
+    State(state): State<AppState>,
+) -> Result<Json<TrialBalanceResponse>, ApiError> {
+    require_platform_admin(&admin)?;
+    let entries = JournalRepository::new(state.storage())
+        .list()
+        .context("Failed to read journal")?;
+    let trial_balance = TrialBalance::compute(&entries);
+    if !trial_balance.balanced {
+        tracing::warn!(
+            unbalanced_entries = trial_balance.unbalanced_entries.len(),
+            "Journal trial balance does not balance"
+        );
+    }
+    Ok(Json(TrialBalanceResponse {
+        generated_at: Utc::now().to_rfc3339(),
+        trial_balance,
+    }))
+}
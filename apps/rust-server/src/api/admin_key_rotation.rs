@@ -0,0 +1,516 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Admin-triggered key rotation for user wallets.
+//!
+//! `POST /v1/admin/wallets/{wallet_id}/rotate-key` moves an EOA wallet to a
+//! fresh key generated in the enclave:
+//!
+//! 1. The new key is staged next to the wallet's current one. A rotation
+//!    that fails part-way keeps it, and the next attempt reuses it, so
+//!    funds already swept stay reachable.
+//! 2. On every registered network, listed ERC-20 balances and indexed NFTs
+//!    are sent from the old address to the new one, then the remaining
+//!    native balance less gas once the token sweeps are mined.
+//! 3. The old key is archived (encrypted, under its address), the staged
+//!    key becomes current, and the wallet's metadata, address map, email
+//!    lookup and discovery token move to the new address.
+//!
+//! Sends from the wallet are refused while the rotation runs, and sends
+//! already past their checks are waited for before the sweep starts.
+//!
+//! Smart-account wallets are refused: their address belongs to the account
+//! contract, and changing its owner needs an on-chain call of its own.
+//! Transfers to the old address after the rotation are no longer indexed;
+//! the archived key can still move them.
+
+use std::{
+    collections::HashSet,
+    sync::{Mutex, OnceLock},
+    time::Duration,
+};
+
+use alloy::primitives::U256;
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::{
+    auth::AdminOnly,
+    blockchain::{
+        client::AvaxClientError, networks, nft::transfer_calldata, signing::signer_from_pem,
+        tokens, wallet_from_pem, AvaxClient, TxBuilder,
+    },
+    error::{ApiError, StorageContext},
+    state::AppState,
+    storage::{
+        repository::service_wallet::generate_secp256k1_keypair, AuditEvent, AuditEventType,
+        AuditRepository, EmailIndexRepository, TxDatabase, WalletAccountType, WalletMetadata,
+        WalletRepository, WalletStatus,
+    },
+};
+
+/// How long token sweeps may take to be mined before the native sweep.
+const SWEEP_RECEIPT_TIMEOUT: Duration = Duration::from_secs(60);
+/// Interval between receipt checks.
+const SWEEP_RECEIPT_POLL: Duration = Duration::from_secs(2);
+
+/// Wallets with a rotation in progress.
+static ROTATING: OnceLock<Mutex<HashSet<String>>> = OnceLock::new();
+
+/// Marks a wallet as rotating until dropped.
+struct RotationGuard(String);
+
+impl RotationGuard {
+    fn acquire(wallet_id: &str) -> Option<Self> {
+        let mut rotating = ROTATING
+            .get_or_init(Default::default)
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        rotating
+            .insert(wallet_id.to_string())
+            .then(|| Self(wallet_id.to_string()))
+    }
+}
+
+/// Whether a rotation of `wallet_id` is in progress.
+pub(crate) fn is_rotating(wallet_id: &str) -> bool {
+    ROTATING.get().is_some_and(|rotating| {
+        rotating
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .contains(wallet_id)
+    })
+}
+
+impl Drop for RotationGuard {
+    fn drop(&mut self) {
+        if let Some(rotating) = ROTATING.get() {
+            rotating
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .remove(&self.0);
+        }
+    }
+}
+
+// =============================================================================
+// Response Types
+// =============================================================================
+
+/// One balance moved to the new address.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct SweptAsset {
+    pub network: String,
+    /// `native`, or the token / NFT contract address.
+    pub asset: String,
+    /// NFT token ID, decimal.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token_id: Option<String>,
+    /// Amount in the asset's smallest unit.
+    pub amount: String,
+    pub tx_hash: String,
+}
+
+/// Result of a key rotation.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct KeyRotationResponse {
+    pub wallet_id: String,
+    pub old_address: String,
+    pub new_address: String,
+    /// Transfers that moved the old address's balances.
+    pub swept: Vec<SweptAsset>,
+    /// Addresses of every key the wallet was rotated away from.
+    pub archived_addresses: Vec<String>,
+}
+
+// =============================================================================
+// Sweep
+// =============================================================================
+
+fn sweep_error(network: &str, e: AvaxClientError) -> ApiError {
+    ApiError::service_unavailable(format!(
+        "Sweep on {network} failed: {e}. The new key is kept; retry the rotation."
+    ))
+    .with_code("key_rotation_sweep_failed")
+}
+
+/// Wait until every transaction in `tx_hashes` is mined successfully.
+async fn wait_for_sweeps(
+    client: &AvaxClient,
+    network: &str,
+    tx_hashes: &[String],
+) -> Result<(), ApiError> {
+    let deadline = tokio::time::Instant::now() + SWEEP_RECEIPT_TIMEOUT;
+    for tx_hash in tx_hashes {
+        loop {
+            let receipt = client
+                .get_transaction_receipt_status(tx_hash)
+                .await
+                .map_err(|e| sweep_error(network, e))?;
+            match receipt {
+                Some(receipt) if receipt.success => break,
+                Some(_) => {
+                    return Err(sweep_error(
+                        network,
+                        AvaxClientError::TransactionFailed(format!("{tx_hash} reverted")),
+                    ))
+                }
+                None if tokio::time::Instant::now() >= deadline => {
+                    return Err(sweep_error(
+                        network,
+                        AvaxClientError::TransactionFailed(format!("{tx_hash} not mined yet")),
+                    ))
+                }
+                None => tokio::time::sleep(SWEEP_RECEIPT_POLL).await,
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Move everything the old key holds on every registered network to
+/// `new_address`.
+async fn sweep_balances(
+    state: &AppState,
+    tx_db: &TxDatabase,
+    wallet: &WalletMetadata,
+    old_key: &[u8],
+    new_address: &str,
+) -> Result<Vec<SweptAsset>, ApiError> {
+    let old_address = &wallet.public_address;
+    let holdings = tx_db
+        .list_nft_holdings(&wallet.wallet_id)
+        .map_err(|e| ApiError::internal(format!("Failed to read NFTs: {e}")))?;
+    let mut swept = Vec::new();
+
+    for registered in networks().all() {
+        let network = &registered.config;
+        let client = state
+            .chain_client(network)
+            .await
+            .map_err(|e| sweep_error(network.id, e))?;
+        let signer = wallet_from_pem(old_key)
+            .map_err(|e| ApiError::internal(format!("Failed to create signer: {e}")))?;
+        let builder = TxBuilder::new(network.clone(), signer)
+            .await
+            .map_err(|e| sweep_error(network.id, e))?
+            .with_nonce_manager(state.nonce_manager.clone());
+
+        let mut pending = Vec::new();
+        for token in tokens().on_network(network.id) {
+            let balance = client
+                .get_token_balance(old_address, &token.address)
+                .await
+                .map_err(|e| sweep_error(network.id, e))?;
+            let amount = U256::from_str_radix(&balance.balance_raw, 10).unwrap_or_default();
+            if amount.is_zero() {
+                continue;
+            }
+            let sent = builder
+                .send_token(new_address, &token.address, amount, None, None)
+                .await
+                .map_err(|e| sweep_error(network.id, e))?;
+            pending.push(sent.tx_hash.clone());
+            swept.push(SweptAsset {
+                network: network.id.to_string(),
+                asset: token.address.to_lowercase(),
+                token_id: None,
+                amount: amount.to_string(),
+                tx_hash: sent.tx_hash,
+            });
+        }
+
+        for holding in holdings.iter().filter(|h| h.network == network.id) {
+            let Ok(token_id) = U256::from_str_radix(&holding.token_id, 10) else {
+                continue;
+            };
+            let held = client
+                .get_nft_balance(holding.standard, &holding.contract, old_address, token_id)
+                .await
+                .map_err(|e| sweep_error(network.id, e))?;
+            if held.is_zero() {
+                continue;
+            }
+            let (Ok(from), Ok(to)) = (old_address.parse(), new_address.parse()) else {
+                continue;
+            };
+            let calldata = transfer_calldata(holding.standard, from, to, token_id, held);
+            let sent = builder
+                .send_contract_call(&holding.contract, calldata, None, None, None)
+                .await
+                .map_err(|e| sweep_error(network.id, e))?;
+            pending.push(sent.tx_hash.clone());
+            swept.push(SweptAsset {
+                network: network.id.to_string(),
+                asset: holding.contract.clone(),
+                token_id: Some(holding.token_id.clone()),
+                amount: held.to_string(),
+                tx_hash: sent.tx_hash,
+            });
+        }
+
+        // Token sweeps pay gas from the native balance; sweep it last.
+        wait_for_sweeps(&client, network.id, &pending).await?;
+        if let Some((sent, amount)) = builder
+            .sweep_native(new_address)
+            .await
+            .map_err(|e| sweep_error(network.id, e))?
+        {
+            swept.push(SweptAsset {
+                network: network.id.to_string(),
+                asset: "native".to_string(),
+                token_id: None,
+                amount: amount.to_string(),
+                tx_hash: sent.tx_hash,
+            });
+        }
+    }
+
+    Ok(swept)
+}
+
+// =============================================================================
+// Handler
+// =============================================================================
+
+/// Rotate a wallet's key (admin action).
+///
+/// Generates a new key, sweeps the old address's balances to it and
+/// archives the old key. Safe to retry after a failure.
+#[utoipa::path(
+    post,
+    path = "/v1/admin/wallets/{wallet_id}/rotate-key",
+    tag = "Admin",
+    params(("wallet_id" = String, Path, description = "Wallet ID")),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Key rotated", body = KeyRotationResponse),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not authorized (admin required)"),
+        (status = 404, description = "Wallet not found or deleted"),
+        (status = 409, description = "A rotation of this wallet is already running"),
+        (status = 422, description = "Smart-account wallet"),
+        (status = 503, description = "A sweep failed; the new key is kept for a retry")
+    )
+)]
+pub async fn rotate_wallet_key(
+    AdminOnly(user): AdminOnly,
+    Path(wallet_id): Path<String>,
+    State(state): State<AppState>,
+) -> Result<Json<KeyRotationResponse>, ApiError> {
+    let storage = state.storage();
+    let repo = WalletRepository::new(storage);
+
+    let mut wallet = repo
+        .get(&wallet_id)
+        .ok()
+        .filter(|w| user.sees_tenant(w.tenant_id.as_deref()))
+        .filter(|w| w.status != WalletStatus::Deleted)
+        .ok_or_else(|| ApiError::not_found(format!("Wallet {} not found", wallet_id)))?;
+    if wallet.account_type == WalletAccountType::SmartAccount {
+        return Err(ApiError::unprocessable(
+            "Smart-account wallets cannot be rotated; change the account owner instead",
+        )
+        .with_code("smart_account_key_rotation"));
+    }
+    let _guard = RotationGuard::acquire(&wallet_id).ok_or_else(|| {
+        ApiError::conflict("A key rotation of this wallet is already running")
+            .with_code("key_rotation_in_progress")
+    })?;
+    // New sends now see the rotation; wait out the ones already checked.
+    state
+        .spending_reservations
+        .wait_idle(&wallet.owner_user_id)
+        .await;
+    let tx_db = state
+        .tx_db
+        .as_ref()
+        .expect("transaction database must be configured");
+
+    let new_key = match repo
+        .rotation_key(&wallet_id)
+        .context("Failed to read staged key")?
+    {
+        Some(staged) => staged,
+        None => {
+            let (pem, _) = generate_secp256k1_keypair()
+                .map_err(|e| ApiError::internal(format!("Key generation failed: {}", e)))?;
+            repo.stage_rotation_key(&wallet_id, pem.as_bytes())
+                .context("Failed to stage new key")?;
+            pem.into_bytes()
+        }
+    };
+    let new_address = signer_from_pem(&new_key)
+        .map_err(|e| ApiError::internal(format!("Staged key is unusable: {e}")))?
+        .address()
+        .to_string()
+        .to_lowercase();
+    let old_key = repo
+        .read_private_key(&wallet_id)
+        .map_err(|e| ApiError::internal(format!("Failed to read private key: {e}")))?;
+
+    let swept = sweep_balances(&state, tx_db, &wallet, &old_key, &new_address).await?;
+
+    let old_address = wallet.public_address.clone();
+    wallet.public_address = new_address.clone();
+    repo.complete_rotation(&wallet, &old_address)
+        .context("Failed to store rotated key")?;
+
+    // Point the indexes at the new address.
+    let _ = tx_db.remove_wallet_address(&old_address);
+    tx_db
+        .register_address(&new_address, &wallet_id)
+        .map_err(|e| ApiError::internal(format!("Failed to register new address: {e}")))?;
+    if let Some(ref lookup_key) = wallet.email_lookup_key {
+        let email_repo = EmailIndexRepository::new(tx_db.clone());
+        if let Err(e) = email_repo.register(lookup_key, &wallet_id, &new_address) {
+            tracing::warn!(error = %e, wallet_id = %wallet_id, "Failed to move email lookup");
+        }
+    }
+    if let Some(ref sha256_hex) = wallet.email_sha256 {
+        if let Ok(sha256_bytes) = alloy::hex::decode(sha256_hex) {
+            if let Ok(token) = state.voprf_server.compute_local_token(&sha256_bytes) {
+                let token_hex = alloy::hex::encode(&token);
+                if let Err(e) = state.voprf_store.register(&token_hex, &new_address) {
+                    tracing::warn!(error = %e, wallet_id = %wallet_id, "Failed to move VOPRF token");
+                }
+            }
+        }
+    }
+    state.cache_bus.invalidate_address(&old_address);
+    state.cache_bus.invalidate_address(&new_address);
+
+    let event = AuditEvent::new(AuditEventType::WalletKeyRotated)
+        .with_user(&user.user_id)
+        .with_resource(&wallet_id, "wallet")
+        .with_details(serde_json::json!({
+            "old_address": old_address,
+            "new_address": new_address,
+            "swept": swept.iter().map(|s| &s.tx_hash).collect::<Vec<_>>(),
+        }));
+    let _ = AuditRepository::new(storage).log(&event);
+
+    let archived_addresses = repo
+        .archived_key_addresses(&wallet_id)
+        .context("Failed to list archived keys")?;
+    Ok(Json(KeyRotationResponse {
+        wallet_id,
+        old_address,
+        new_address,
+        swept,
+        archived_addresses,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::{AuthenticatedUser, Role};
+    use crate::storage::SmartAccountInfo;
+    use axum::http::StatusCode;
+    use chrono::Utc;
+
+    fn admin() -> AdminOnly {
+        AdminOnly(AuthenticatedUser {
+            user_id: "admin_1".to_string(),
+            role: Role::Admin,
+            session_id: None,
+            issuer: "https://test.clerk.dev".to_string(),
+            expires_at: Utc::now().timestamp() + 3600,
+            tenant_id: None,
+        })
+    }
+
+    fn wallet(wallet_id: &str, account_type: WalletAccountType) -> WalletMetadata {
+        WalletMetadata {
+            wallet_id: wallet_id.to_string(),
+            owner_user_id: "user-a".to_string(),
+            public_address: "0x1111111111111111111111111111111111111111".to_string(),
+            created_at: Utc::now(),
+            status: WalletStatus::Active,
+            label: None,
+            email_lookup_key: None,
+            email_sha256: None,
+            account_type,
+            smart_account: None,
+            lock: None,
+            deleted_at: None,
+            purged_at: None,
+            funded_at: None,
+            tenant_id: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn rotation_is_refused_before_touching_keys() {
+        let state = AppState::default();
+        let repo = WalletRepository::new(state.storage());
+        let mut smart = wallet("w_smart", WalletAccountType::SmartAccount);
+        smart.smart_account = Some(SmartAccountInfo {
+            owner_address: "0x2222222222222222222222222222222222222222".to_string(),
+            entry_point: "0x0000000071727De22E5E9d8BAf0edAc6f37da032".to_string(),
+            factory: "0x3333333333333333333333333333333333333333".to_string(),
+            deployed: false,
+            deployed_at: None,
+        });
+        repo.create(&smart, b"key").unwrap();
+        let err = rotate_wallet_key(admin(), Path("w_smart".to_string()), State(state.clone()))
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert!(repo.rotation_key("w_smart").unwrap().is_none());
+
+        let err = rotate_wallet_key(admin(), Path("w_missing".to_string()), State(state.clone()))
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, StatusCode::NOT_FOUND);
+
+        repo.create(&wallet("w_busy", WalletAccountType::Eoa), b"key")
+            .unwrap();
+        let _running = RotationGuard::acquire("w_busy").unwrap();
+        let err = rotate_wallet_key(admin(), Path("w_busy".to_string()), State(state.clone()))
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, StatusCode::CONFLICT);
+        assert!(repo.rotation_key("w_busy").unwrap().is_none());
+        let busy = repo.get("w_busy").unwrap();
+        let err = crate::api::wallets::ensure_unlocked(&busy).unwrap_err();
+        assert_eq!(err.code, Some("key_rotation_in_progress"));
+    }
+
+    #[tokio::test]
+    async fn rotation_waits_for_unlimited_signing_paths() {
+        let state = AppState::default();
+        let signer = wallet("w_signing", WalletAccountType::Eoa);
+        WalletRepository::new(state.storage())
+            .create(&signer, b"key")
+            .unwrap();
+
+        // An NFT transfer or replacement holds off the rotation until it
+        // has broadcast.
+        let signing = crate::api::limits::hold_for_signing(&state, &signer)
+            .await
+            .unwrap();
+        let idle = {
+            let state = state.clone();
+            tokio::spawn(async move { state.spending_reservations.wait_idle("user-a").await })
+        };
+        tokio::task::yield_now().await;
+        assert!(!idle.is_finished());
+        drop(signing);
+        idle.await.unwrap();
+
+        // Once the rotation has started, those paths are refused.
+        let _running = RotationGuard::acquire("w_signing").unwrap();
+        let err = crate::api::limits::hold_for_signing(&state, &signer)
+            .await
+            .err()
+            .unwrap();
+        assert_eq!(err.code, Some("key_rotation_in_progress"));
+    }
+}
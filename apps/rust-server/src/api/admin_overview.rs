@@ -0,0 +1,596 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Admin dashboard overview.
+//!
+//! `GET /v1/admin/overview` gathers the signals operators check first into
+//! one response: pending fiat work, stuck transactions, indexer lag, reserve
+//! balances, worker heartbeats and recent failures. Anything that needs the
+//! chain is fetched with a short timeout; if the RPC is down, those sections
+//! carry an `error` and the rest of the overview is still returned.
+
+use std::collections::BTreeMap;
+use std::env;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use alloy::primitives::U256;
+use axum::{extract::State, Json};
+use chrono::{DateTime, TimeDelta, Utc};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::{
+    api::{admin::require_platform_admin, fiat::resolve_reur_contract_address},
+    auth::AdminOnly,
+    blockchain::{avax_fuji, parse_amount, AvaxClient},
+    canary::{self, CanaryOutcome, CanaryRun},
+    error::ApiError,
+    indexer,
+    state::AppState,
+    storage::{
+        AuditEvent, AuditRepository, ClawbackStatus, FiatRequestRepository, FiatRequestStatus,
+        FiatServiceWalletRepository, ReserveSendQueueRepository, ReserveSendStatus,
+        StoredFiatRequest, StoredReserveSendJob,
+    },
+    worker_health::{self, Worker},
+};
+
+/// Minimum reserve rEUR balance before the overview raises an alert.
+const FIAT_RESERVE_MIN_REUR_ENV: &str = "FIAT_RESERVE_MIN_REUR";
+const DEFAULT_RESERVE_MIN_REUR: &str = "1000.00";
+/// Minimum reserve AVAX balance (gas) before the overview raises an alert.
+const FIAT_RESERVE_MIN_AVAX_ENV: &str = "FIAT_RESERVE_MIN_AVAX";
+const DEFAULT_RESERVE_MIN_AVAX: &str = "0.5";
+/// Pending transactions older than this are reported as stuck.
+const STUCK_TX_AFTER_MINUTES: i64 = 10;
+/// Stuck transactions listed individually; the count covers all of them.
+const STUCK_TX_LISTED: usize = 10;
+/// Indexer lag, in blocks, above which the overview raises an alert.
+pub(crate) const INDEXER_LAG_ALERT_BLOCKS: u64 = 100;
+/// Timeout for each chain read.
+const CHAIN_READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Overall state of the deployment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum OverviewStatus {
+    /// Nothing needs attention.
+    Ok,
+    /// At least one entry in `alerts`.
+    Attention,
+}
+
+/// Pending fiat work.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FiatOverview {
+    /// Non-terminal requests.
+    pub pending_total: usize,
+    /// Non-terminal requests per status.
+    pub pending_by_status: BTreeMap<String, usize>,
+    /// Creation time of the oldest non-terminal request.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub oldest_pending_at: Option<String>,
+    /// Requests that failed in the last 24 hours.
+    pub failed_last_24h: usize,
+    /// Chargebacks whose rEUR has not been recovered yet.
+    pub open_chargebacks: usize,
+    /// Reserve sends queued or in flight.
+    pub reserve_queue_unfinished: usize,
+    /// Reserve sends interrupted by a restart, awaiting an admin.
+    pub reserve_queue_interrupted: usize,
+}
+
+/// A transaction still pending past the stuck threshold.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct StuckTransaction {
+    pub tx_hash: String,
+    pub wallet_id: String,
+    pub created_at: String,
+    pub age_minutes: i64,
+}
+
+/// Transactions pending for longer than `threshold_minutes`.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct StuckTransactionsOverview {
+    pub threshold_minutes: i64,
+    pub count: usize,
+    /// Oldest stuck transactions, oldest first.
+    pub oldest: Vec<StuckTransaction>,
+}
+
+/// Event indexer progress.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct IndexerOverview {
+    pub network: String,
+    /// Last block the indexer has processed (0 before the first run).
+    pub last_indexed_block: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub head_block: Option<u64>,
+    /// Blocks between the chain head and the indexer checkpoint.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lag_blocks: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Reserve wallet balances against their alert thresholds.
+#[derive(Debug, Default, Serialize, ToSchema)]
+pub struct ReserveOverview {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub public_address: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reur_balance: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub avax_balance: Option<String>,
+    pub min_reur: String,
+    pub min_avax: String,
+    pub reur_low: bool,
+    pub avax_low: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Liveness of a background worker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum WorkerStatus {
+    Healthy,
+    /// No heartbeat within the stale threshold.
+    Stale,
+    /// Never reported a heartbeat since the server started.
+    NotStarted,
+}
+
+/// Heartbeat of one background worker.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct WorkerOverview {
+    pub worker: Worker,
+    pub status: WorkerStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_heartbeat_at: Option<String>,
+}
+
+/// Failed operations recorded in the audit log over the last 24 hours.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ErrorOverview {
+    pub total: usize,
+    pub by_event_type: BTreeMap<String, usize>,
+}
+
+/// Admin dashboard overview.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AdminOverviewResponse {
+    pub generated_at: String,
+    pub status: OverviewStatus,
+    /// Human-readable findings that need attention.
+    pub alerts: Vec<String>,
+    pub fiat: FiatOverview,
+    pub stuck_transactions: StuckTransactionsOverview,
+    pub indexer: IndexerOverview,
+    pub reserve: ReserveOverview,
+    pub workers: Vec<WorkerOverview>,
+    pub errors_last_24h: ErrorOverview,
+    /// Latest canary transfer; absent when the canary is off or has not run.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub canary: Option<CanaryRun>,
+}
+
+fn snake_case_name<T: Serialize>(value: &T) -> String {
+    serde_json::to_value(value)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+fn summarize_fiat(
+    requests: &[StoredFiatRequest],
+    jobs: &[StoredReserveSendJob],
+    now: DateTime<Utc>,
+) -> FiatOverview {
+    let day_ago = now - TimeDelta::hours(24);
+    let pending: Vec<_> = requests
+        .iter()
+        .filter(|r| {
+            !matches!(
+                r.status,
+                FiatRequestStatus::Completed | FiatRequestStatus::Failed
+            )
+        })
+        .collect();
+    let mut pending_by_status = BTreeMap::new();
+    for request in &pending {
+        *pending_by_status
+            .entry(snake_case_name(&request.status))
+            .or_insert(0) += 1;
+    }
+    FiatOverview {
+        pending_total: pending.len(),
+        pending_by_status,
+        oldest_pending_at: pending
+            .iter()
+            .map(|r| r.created_at)
+            .min()
+            .map(|ts| ts.to_rfc3339()),
+        failed_last_24h: requests
+            .iter()
+            .filter(|r| r.status == FiatRequestStatus::Failed && r.updated_at >= day_ago)
+            .count(),
+        open_chargebacks: requests
+            .iter()
+            .filter_map(|r| r.chargeback.as_ref())
+            .filter(|c| c.clawback_status == ClawbackStatus::Pending)
+            .count(),
+        reserve_queue_unfinished: jobs.iter().filter(|j| !j.status.is_finished()).count(),
+        reserve_queue_interrupted: jobs
+            .iter()
+            .filter(|j| j.status == ReserveSendStatus::Interrupted)
+            .count(),
+    }
+}
+
+fn summarize_errors(events: &[AuditEvent], now: DateTime<Utc>) -> ErrorOverview {
+    let day_ago = now - TimeDelta::hours(24);
+    let mut by_event_type = BTreeMap::new();
+    for event in events
+        .iter()
+        .filter(|e| !e.success && e.timestamp >= day_ago)
+    {
+        *by_event_type
+            .entry(snake_case_name(&event.event_type))
+            .or_insert(0) += 1;
+    }
+    ErrorOverview {
+        total: by_event_type.values().sum(),
+        by_event_type,
+    }
+}
+
+fn worker_overview(worker: Worker, now: DateTime<Utc>) -> WorkerOverview {
+    let last = worker_health::last_heartbeat(worker);
+    let status = match last {
+        None => WorkerStatus::NotStarted,
+        Some(at) if now - at > TimeDelta::seconds(worker.stale_after_secs()) => WorkerStatus::Stale,
+        Some(_) => WorkerStatus::Healthy,
+    };
+    WorkerOverview {
+        worker,
+        status,
+        last_heartbeat_at: last.map(|ts| ts.to_rfc3339()),
+    }
+}
+
+/// Run a chain read with [`CHAIN_READ_TIMEOUT`], flattening both failures
+/// into a message.
+pub(crate) async fn chain_read<T, E: std::fmt::Display>(
+    read: impl std::future::Future<Output = Result<T, E>>,
+) -> Result<T, String> {
+    match tokio::time::timeout(CHAIN_READ_TIMEOUT, read).await {
+        Ok(Ok(value)) => Ok(value),
+        Ok(Err(e)) => Err(e.to_string()),
+        Err(_) => Err("Chain RPC timed out".to_string()),
+    }
+}
+
+async fn chain_client(state: &AppState) -> Result<Arc<AvaxClient>, String> {
+    match &state.avax_client {
+        Some(client) => Ok(Arc::clone(client)),
+        None => chain_read(AvaxClient::fuji()).await.map(Arc::new),
+    }
+}
+
+fn below_threshold(balance_raw: &str, threshold: &str, decimals: u8) -> bool {
+    let balance = U256::from_str(balance_raw).unwrap_or(U256::ZERO);
+    parse_amount(threshold, decimals).is_ok_and(|min| balance < min)
+}
+
+async fn reserve_overview(
+    state: &AppState,
+    client: &Result<Arc<AvaxClient>, String>,
+) -> ReserveOverview {
+    let mut overview = ReserveOverview {
+        min_reur: env::var(FIAT_RESERVE_MIN_REUR_ENV)
+            .unwrap_or_else(|_| DEFAULT_RESERVE_MIN_REUR.to_string()),
+        min_avax: env::var(FIAT_RESERVE_MIN_AVAX_ENV)
+            .unwrap_or_else(|_| DEFAULT_RESERVE_MIN_AVAX.to_string()),
+        ..Default::default()
+    };
+    let wallet = match FiatServiceWalletRepository::new(state.storage()).get() {
+        Ok(wallet) => wallet,
+        Err(_) => {
+            overview.error = Some("Reserve wallet is not initialized".to_string());
+            return overview;
+        }
+    };
+    overview.public_address = Some(wallet.public_address.clone());
+    let client = match client {
+        Ok(client) => client,
+        Err(e) => {
+            overview.error = Some(e.clone());
+            return overview;
+        }
+    };
+    let contract = match resolve_reur_contract_address() {
+        Ok(contract) => contract,
+        Err(e) => {
+            overview.error = Some(e.message);
+            return overview;
+        }
+    };
+
+    match chain_read(client.get_token_balance(&wallet.public_address, &contract)).await {
+        Ok(balance) => {
+            overview.reur_low =
+                below_threshold(&balance.balance_raw, &overview.min_reur, balance.decimals);
+            overview.reur_balance = Some(balance.balance_formatted);
+        }
+        Err(e) => overview.error = Some(e),
+    }
+    match chain_read(client.get_native_balance(&wallet.public_address)).await {
+        Ok(balance) => {
+            overview.avax_low =
+                below_threshold(&balance.balance_raw, &overview.min_avax, balance.decimals);
+            overview.avax_balance = Some(balance.balance_formatted);
+        }
+        Err(e) => overview.error = Some(e),
+    }
+    overview
+}
+
+async fn indexer_overview(
+    state: &AppState,
+    client: &Result<Arc<AvaxClient>, String>,
+) -> IndexerOverview {
+    let network = avax_fuji();
+    let key = indexer::checkpoint_key(&network);
+    let mut overview = IndexerOverview {
+        network: key.clone(),
+        last_indexed_block: 0,
+        head_block: None,
+        lag_blocks: None,
+        error: None,
+    };
+    match state
+        .tx_db
+        .as_ref()
+        .map(|db| db.get_last_indexed_block(&key))
+    {
+        Some(Ok(block)) => overview.last_indexed_block = block,
+        Some(Err(e)) => overview.error = Some(e.to_string()),
+        None => overview.error = Some("Transaction database not configured".to_string()),
+    }
+    match client {
+        Ok(client) => match chain_read(client.get_block_number()).await {
+            Ok(head) => {
+                overview.head_block = Some(head);
+                overview.lag_blocks = Some(head.saturating_sub(overview.last_indexed_block));
+            }
+            Err(e) => overview.error = Some(e),
+        },
+        Err(e) => overview.error = Some(e.clone()),
+    }
+    overview
+}
+
+fn collect_alerts(overview: &AdminOverviewResponse) -> Vec<String> {
+    let mut alerts = Vec::new();
+    if overview.stuck_transactions.count > 0 {
+        alerts.push(format!(
+            "{} transaction(s) pending for over {} minutes",
+            overview.stuck_transactions.count, STUCK_TX_AFTER_MINUTES
+        ));
+    }
+    if overview.fiat.reserve_queue_interrupted > 0 {
+        alerts.push(format!(
+            "{} interrupted reserve send(s) need resolving",
+            overview.fiat.reserve_queue_interrupted
+        ));
+    }
+    if overview.fiat.open_chargebacks > 0 {
+        alerts.push(format!(
+            "{} chargeback(s) awaiting clawback",
+            overview.fiat.open_chargebacks
+        ));
+    }
+    if let Some(lag) = overview
+        .indexer
+        .lag_blocks
+        .filter(|lag| *lag > INDEXER_LAG_ALERT_BLOCKS)
+    {
+        alerts.push(format!("Indexer is {lag} blocks behind the chain head"));
+    }
+    if overview.reserve.reur_low {
+        alerts.push(format!(
+            "Reserve rEUR balance is below {}",
+            overview.reserve.min_reur
+        ));
+    }
+    if overview.reserve.avax_low {
+        alerts.push(format!(
+            "Reserve AVAX balance is below {}",
+            overview.reserve.min_avax
+        ));
+    }
+    for worker in &overview.workers {
+        if worker.status == WorkerStatus::Stale {
+            alerts.push(format!(
+                "{} has not reported a heartbeat in {} seconds",
+                snake_case_name(&worker.worker),
+                worker.worker.stale_after_secs()
+            ));
+        }
+    }
+    if let Some(run) = &overview.canary {
+        let detail = run.detail.as_deref().unwrap_or("no detail");
+        match run.outcome {
+            CanaryOutcome::Healthy => {}
+            CanaryOutcome::Degraded => alerts.push(format!("Canary transfer degraded: {detail}")),
+            CanaryOutcome::Failed => alerts.push(format!("Canary transfer failed: {detail}")),
+        }
+    }
+    alerts
+}
+
+/// Dashboard overview of operational signals.
+///
+/// Chain-dependent sections report an `error` instead of failing the
+/// request when the RPC is unavailable. Admin only.
+#[utoipa::path(
+    get,
+    path = "/v1/admin/overview",
+    tag = "Admin",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Operational overview", body = AdminOverviewResponse),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not authorized (platform admin required)")
+    )
+)]
+pub async fn get_admin_overview(
+    AdminOnly(admin): AdminOnly,
+    State(state): State<AppState>,
+) -> Result<Json<AdminOverviewResponse>, ApiError> {
+    require_platform_admin(&admin)?;
+    let storage = state.storage();
+    let now = Utc::now();
+
+    let requests = FiatRequestRepository::new(storage)
+        .list_all()
+        .map_err(|e| ApiError::internal(format!("Failed to list fiat requests: {e}")))?;
+    let queue = ReserveSendQueueRepository::new(storage)
+        .load()
+        .map_err(|e| ApiError::internal(format!("Failed to load reserve queue: {e}")))?;
+    let fiat = summarize_fiat(&requests, &queue.jobs, now);
+
+    let stuck = match &state.tx_db {
+        Some(tx_db) => tx_db
+            .list_pending_before(now - TimeDelta::minutes(STUCK_TX_AFTER_MINUTES))
+            .map_err(|e| ApiError::internal(format!("Failed to scan transactions: {e}")))?,
+        None => Vec::new(),
+    };
+    let stuck_transactions = StuckTransactionsOverview {
+        threshold_minutes: STUCK_TX_AFTER_MINUTES,
+        count: stuck.len(),
+        oldest: stuck
+            .into_iter()
+            .take(STUCK_TX_LISTED)
+            .map(|tx| StuckTransaction {
+                age_minutes: (now - tx.created_at).num_minutes(),
+                created_at: tx.created_at.to_rfc3339(),
+                tx_hash: tx.tx_hash,
+                wallet_id: tx.wallet_id,
+            })
+            .collect(),
+    };
+
+    let yesterday = (now - TimeDelta::days(1)).format("%Y-%m-%d").to_string();
+    let today = now.format("%Y-%m-%d").to_string();
+    let events = AuditRepository::new(storage)
+        .read_events_range(&yesterday, &today)
+        .unwrap_or_default();
+
+    let client = chain_client(&state).await;
+    let (indexer, reserve) = tokio::join!(
+        indexer_overview(&state, &client),
+        reserve_overview(&state, &client)
+    );
+
+    let mut overview = AdminOverviewResponse {
+        generated_at: now.to_rfc3339(),
+        status: OverviewStatus::Ok,
+        alerts: Vec::new(),
+        fiat,
+        stuck_transactions,
+        indexer,
+        reserve,
+        workers: Worker::ALL
+            .iter()
+            .map(|worker| worker_overview(*worker, now))
+            .collect(),
+        errors_last_24h: summarize_errors(&events, now),
+        canary: canary::latest_run(),
+    };
+    overview.alerts = collect_alerts(&overview);
+    if !overview.alerts.is_empty() {
+        overview.status = OverviewStatus::Attention;
+    }
+    Ok(Json(overview))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::repository::reserve_queue::StoredReserveSendQueue;
+    use crate::storage::{AuditEventType, FiatDirection, ReserveSendKind};
+
+    fn request(id: &str, status: FiatRequestStatus, age_hours: i64) -> StoredFiatRequest {
+        let mut record = StoredFiatRequest::new_queued(
+            id.to_string(),
+            "w".to_string(),
+            "u".to_string(),
+            FiatDirection::OnRamp,
+            "10.00".to_string(),
+            "truelayer_sandbox".to_string(),
+            None,
+        );
+        record.status = status;
+        record.created_at = Utc::now() - TimeDelta::hours(age_hours);
+        record.updated_at = record.created_at;
+        record
+    }
+
+    #[test]
+    fn fiat_summary_counts_pending_and_recent_failures() {
+        let now = Utc::now();
+        let requests = vec![
+            request("a", FiatRequestStatus::SettlementPending, 3),
+            request("b", FiatRequestStatus::SettlementPending, 1),
+            request("c", FiatRequestStatus::AwaitingProvider, 2),
+            request("d", FiatRequestStatus::Failed, 2),
+            request("e", FiatRequestStatus::Failed, 48),
+            request("f", FiatRequestStatus::Completed, 1),
+        ];
+        let mut queue = StoredReserveSendQueue::default();
+        queue.enqueue(ReserveSendKind::Mint, &[], "0xabc", "5.00");
+        queue.jobs[0].status = ReserveSendStatus::Interrupted;
+
+        let summary = summarize_fiat(&requests, &queue.jobs, now);
+        assert_eq!(summary.pending_total, 3);
+        assert_eq!(summary.pending_by_status["settlement_pending"], 2);
+        assert_eq!(summary.pending_by_status["awaiting_provider"], 1);
+        assert_eq!(
+            summary.oldest_pending_at,
+            Some(requests[0].created_at.to_rfc3339())
+        );
+        assert_eq!(summary.failed_last_24h, 1);
+        assert_eq!(summary.reserve_queue_unfinished, 1);
+        assert_eq!(summary.reserve_queue_interrupted, 1);
+    }
+
+    #[test]
+    fn error_summary_groups_recent_failures_by_type() {
+        let now = Utc::now();
+        let mut old = AuditEvent::new(AuditEventType::AuthFailure).failed("expired");
+        old.timestamp = now - TimeDelta::hours(30);
+        let events = vec![
+            AuditEvent::new(AuditEventType::AuthFailure).failed("bad token"),
+            AuditEvent::new(AuditEventType::AuthFailure).failed("bad token"),
+            AuditEvent::new(AuditEventType::PermissionDenied).failed("not owner"),
+            AuditEvent::new(AuditEventType::WalletCreated),
+            old,
+        ];
+        let summary = summarize_errors(&events, now);
+        assert_eq!(summary.total, 3);
+        assert_eq!(summary.by_event_type["auth_failure"], 2);
+        assert_eq!(summary.by_event_type["permission_denied"], 1);
+    }
+
+    #[test]
+    fn balances_are_compared_in_minor_units() {
+        assert!(below_threshold("999999999", "1000.00", 6));
+        assert!(!below_threshold("1000000000", "1000.00", 6));
+        assert!(!below_threshold("1", "not-a-number", 6));
+    }
+}
@@ -0,0 +1,902 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Admin reports.
+//!
+//! `GET /v1/admin/reports/dormant` lists wallets that still hold funds but
+//! have seen no activity for a number of days, for dormancy outreach and
+//! unclaimed-asset processing. A wallet's last activity is the latest of
+//! its creation, its transactions (sent or received between internal
+//! wallets), its fiat requests and its owner's last sign-in.
+//!
+//! `GET /v1/admin/wallets/{wallet_id}/heatmap` summarizes one wallet's
+//! sends, receipts and fiat operations per day, so fraud analysts can spot
+//! bursts without exporting its raw history.
+//!
+//! `GET /v1/admin/reports/proof-of-reserve` sums every custodial wallet's
+//! on-chain balance per token and compares it with the server's ledger: the
+//! net confirmed transfers into those wallets. The report is signed like a
+//! [transaction proof](crate::api::tx_proofs), with the enclave's published
+//! Ed25519 key, so an auditor can check it was produced inside the TEE and
+//! re-read the listed addresses' balances at the stated block.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use alloy::primitives::{I256, U256};
+
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use chrono::{DateTime, NaiveDate, TimeDelta, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::{
+    api::{
+        admin::require_platform_admin, admin_activity::log_admin_read,
+        balance::fetch_address_balance, categories::wallet_history, tx_proofs::sign_statement,
+    },
+    auth::AdminOnly,
+    blockchain::{
+        address_key, avax_fuji, checksum_address, format_amount, parse_amount, TokenBalance,
+        WalletBalanceResponse,
+    },
+    error::{ApiError, StorageContext},
+    providers::pricing::{value_at_tx_time, PRICED_SYMBOLS},
+    state::AppState,
+    storage::{
+        FiatRequestRepository, PriceHistories, PriceHistoryRepository, SessionLogRepository,
+        StoredFiatRequest, StoredTransaction, TokenType, TxStatus, WalletMetadata,
+        WalletRepository, WalletStatus,
+    },
+};
+
+/// Inactivity period used when `inactive_days` is omitted.
+const DEFAULT_INACTIVE_DAYS: u32 = 365;
+/// Longest inactivity period accepted (10 years).
+const MAX_INACTIVE_DAYS: u32 = 3650;
+
+/// Query parameters for the dormant wallet report.
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct DormantReportParams {
+    /// Days without activity before a wallet counts as dormant (default 365).
+    pub inactive_days: Option<u32>,
+}
+
+/// How the platform can reach a dormant wallet's owner.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct OwnerContactState {
+    /// The owner registered an email address with the wallet.
+    pub email_registered: bool,
+    /// Owner's most recent sign-in, if any was recorded.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_seen_at: Option<String>,
+}
+
+/// A wallet with funds and no recent activity.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DormantWallet {
+    pub wallet_id: String,
+    pub owner_user_id: String,
+    pub public_address: String,
+    pub status: WalletStatus,
+    pub created_at: String,
+    pub last_activity_at: String,
+    pub inactive_days: i64,
+    /// Native AVAX balance.
+    pub native_balance: TokenBalance,
+    /// Non-zero token balances (rEUR).
+    pub token_balances: Vec<TokenBalance>,
+    pub owner_contact: OwnerContactState,
+}
+
+/// Dormant wallet report, longest inactive first.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DormantReportResponse {
+    pub generated_at: String,
+    pub inactive_days: u32,
+    /// Wallets inactive for the period whose balances were checked.
+    pub inactive_checked: usize,
+    pub wallets: Vec<DormantWallet>,
+    pub total: usize,
+}
+
+fn bump(latest: &mut HashMap<String, DateTime<Utc>>, wallet_id: &str, at: DateTime<Utc>) {
+    latest
+        .entry(wallet_id.to_string())
+        .and_modify(|current| *current = (*current).max(at))
+        .or_insert(at);
+}
+
+/// Latest transaction or fiat activity per wallet ID.
+fn last_activity_by_wallet(
+    transactions: &[StoredTransaction],
+    requests: &[StoredFiatRequest],
+) -> HashMap<String, DateTime<Utc>> {
+    let mut latest = HashMap::new();
+    for tx in transactions {
+        bump(&mut latest, &tx.wallet_id, tx.created_at);
+        if let Some(counterparty) = &tx.counterparty_wallet_id {
+            bump(&mut latest, counterparty, tx.created_at);
+        }
+    }
+    for request in requests {
+        bump(&mut latest, &request.wallet_id, request.updated_at);
+    }
+    latest
+}
+
+/// Wallets whose last activity is before `cutoff`, with that time.
+fn inactive_wallets(
+    wallets: Vec<WalletMetadata>,
+    activity: &HashMap<String, DateTime<Utc>>,
+    last_seen: &HashMap<String, Option<DateTime<Utc>>>,
+    cutoff: DateTime<Utc>,
+) -> Vec<(WalletMetadata, DateTime<Utc>)> {
+    wallets
+        .into_iter()
+        .filter(|w| w.status != WalletStatus::Deleted)
+        .filter_map(|wallet| {
+            let last = [
+                Some(wallet.created_at),
+                activity.get(&wallet.wallet_id).copied(),
+                last_seen.get(&wallet.owner_user_id).copied().flatten(),
+            ]
+            .into_iter()
+            .flatten()
+            .max()?;
+            (last < cutoff).then_some((wallet, last))
+        })
+        .collect()
+}
+
+fn is_nonzero(balance: &TokenBalance) -> bool {
+    !balance.balance_raw.trim_start_matches('0').is_empty()
+}
+
+fn has_funds(balance: &WalletBalanceResponse) -> bool {
+    std::iter::once(&balance.native_balance)
+        .chain(&balance.token_balances)
+        .any(is_nonzero)
+}
+
+/// Dormant wallets: funds held, no activity for `inactive_days`.
+///
+/// Balances are read from the chain for every inactive wallet, so the
+/// report fails with 503 if the RPC is unavailable. Admin only.
+#[utoipa::path(
+    get,
+    path = "/v1/admin/reports/dormant",
+    tag = "Admin",
+    params(DormantReportParams),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Dormant wallets", body = DormantReportResponse),
+        (status = 400, description = "Invalid inactive_days"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not authorized (platform admin required)"),
+        (status = 503, description = "Chain RPC unavailable")
+    )
+)]
+pub async fn get_dormant_report(
+    AdminOnly(admin): AdminOnly,
+    Query(params): Query<DormantReportParams>,
+    State(state): State<AppState>,
+) -> Result<Json<DormantReportResponse>, ApiError> {
+    require_platform_admin(&admin)?;
+    let inactive_days = params.inactive_days.unwrap_or(DEFAULT_INACTIVE_DAYS);
+    if inactive_days == 0 || inactive_days > MAX_INACTIVE_DAYS {
+        return Err(ApiError::bad_request(format!(
+            "inactive_days must be between 1 and {MAX_INACTIVE_DAYS}"
+        )));
+    }
+    let storage = state.storage();
+    let now = Utc::now();
+    let cutoff = now - TimeDelta::days(i64::from(inactive_days));
+
+    let wallets = WalletRepository::new(storage)
+        .list_all_wallets()
+        .context("Failed to list wallets")?;
+    let transactions = match &state.tx_db {
+        Some(tx_db) => tx_db
+            .list_all_transactions()
+            .map_err(|e| ApiError::internal(format!("Failed to list transactions: {e}")))?,
+        None => Vec::new(),
+    };
+    let requests = FiatRequestRepository::new(storage)
+        .list_all()
+        .context("Failed to list fiat requests")?;
+    let activity = last_activity_by_wallet(&transactions, &requests);
+
+    let sessions = SessionLogRepository::new(storage);
+    let mut last_seen = HashMap::new();
+    for wallet in &wallets {
+        last_seen
+            .entry(wallet.owner_user_id.clone())
+            .or_insert_with(|| {
+                sessions
+                    .get(&wallet.owner_user_id)
+                    .ok()
+                    .and_then(|log| log.sessions.iter().map(|s| s.last_seen).max())
+            });
+    }
+
+    let inactive = inactive_wallets(wallets, &activity, &last_seen, cutoff);
+    let inactive_checked = inactive.len();
+    let mut dormant = Vec::new();
+    let network = avax_fuji();
+    for (wallet, last_activity) in inactive {
+        let balance = fetch_address_balance(&state, &network, &wallet.public_address, None).await?;
+        if !has_funds(&balance) {
+            continue;
+        }
+        dormant.push(DormantWallet {
+            owner_contact: OwnerContactState {
+                email_registered: wallet.email_lookup_key.is_some(),
+                last_seen_at: last_seen
+                    .get(&wallet.owner_user_id)
+                    .copied()
+                    .flatten()
+                    .map(|ts| ts.to_rfc3339()),
+            },
+            inactive_days: (now - last_activity).num_days(),
+            last_activity_at: last_activity.to_rfc3339(),
+            created_at: wallet.created_at.to_rfc3339(),
+            wallet_id: wallet.wallet_id,
+            owner_user_id: wallet.owner_user_id,
+            public_address: wallet.public_address,
+            status: wallet.status,
+            native_balance: balance.native_balance,
+            token_balances: balance
+                .token_balances
+                .into_iter()
+                .filter(is_nonzero)
+                .collect(),
+        });
+    }
+    dormant.sort_by(|a, b| b.inactive_days.cmp(&a.inactive_days));
+
+    log_admin_read(
+        storage,
+        &admin,
+        "wallets",
+        "*",
+        &[
+            "wallet_id",
+            "owner_user_id",
+            "public_address",
+            "balances",
+            "email_registered",
+            "last_seen_at",
+        ],
+        dormant.len(),
+    );
+
+    Ok(Json(DormantReportResponse {
+        generated_at: now.to_rfc3339(),
+        inactive_days,
+        inactive_checked,
+        total: dormant.len(),
+        wallets: dormant,
+    }))
+}
+
+/// Days covered when `days` is omitted.
+const DEFAULT_HEATMAP_DAYS: u32 = 90;
+/// Longest period accepted.
+const MAX_HEATMAP_DAYS: u32 = 366;
+
+/// Query parameters for the wallet heatmap.
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct HeatmapParams {
+    /// Days to cover, ending today (default 90, at most 366).
+    pub days: Option<u32>,
+}
+
+/// One day of a wallet's activity. Volumes are in EUR at each day's
+/// price; transfers without a price are counted but not valued.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, ToSchema)]
+pub struct HeatmapDay {
+    /// UTC date (`YYYY-MM-DD`).
+    pub date: String,
+    pub sends: u32,
+    pub sent_eur: f64,
+    pub receipts: u32,
+    pub received_eur: f64,
+    /// On-ramp and off-ramp requests created that day.
+    pub fiat_operations: u32,
+    pub fiat_eur: f64,
+    /// Transfers with no EUR price.
+    pub unpriced: u32,
+}
+
+/// Per-day activity of a wallet, oldest day first, one entry per day.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct WalletHeatmapResponse {
+    pub wallet_id: String,
+    pub generated_at: String,
+    pub days: u32,
+    pub heatmap: Vec<HeatmapDay>,
+}
+
+/// Bucket transfers and fiat requests into the `days` days ending `today`.
+/// Failed transfers are left out.
+fn daily_activity(
+    history: &[(StoredTransaction, String)],
+    requests: &[StoredFiatRequest],
+    prices: &PriceHistories,
+    today: NaiveDate,
+    days: u32,
+) -> Vec<HeatmapDay> {
+    let first = today - TimeDelta::days(i64::from(days) - 1);
+    let mut buckets: BTreeMap<NaiveDate, HeatmapDay> = first
+        .iter_days()
+        .take_while(|day| *day <= today)
+        .map(|day| {
+            let bucket = HeatmapDay {
+                date: day.to_string(),
+                ..Default::default()
+            };
+            (day, bucket)
+        })
+        .collect();
+
+    for (tx, direction) in history {
+        if tx.status == TxStatus::Failed {
+            continue;
+        }
+        let Some(day) = buckets.get_mut(&tx.created_at.date_naive()) else {
+            continue;
+        };
+        let value = value_at_tx_time(prices, tx);
+        if value.is_none() {
+            day.unpriced += 1;
+        }
+        if direction == "sent" {
+            day.sends += 1;
+            day.sent_eur += value.unwrap_or_default();
+        } else {
+            day.receipts += 1;
+            day.received_eur += value.unwrap_or_default();
+        }
+    }
+    for request in requests {
+        if let Some(day) = buckets.get_mut(&request.created_at.date_naive()) {
+            day.fiat_operations += 1;
+            day.fiat_eur += request.amount_eur.parse::<f64>().unwrap_or_default();
+        }
+    }
+    buckets.into_values().collect()
+}
+
+/// Per-day activity heatmap of a wallet, for fraud review.
+///
+/// Counts and EUR volumes of sends, receipts and fiat operations for each
+/// of the last `days` days, including days without activity. Admin only.
+#[utoipa::path(
+    get,
+    path = "/v1/admin/wallets/{wallet_id}/heatmap",
+    tag = "Admin",
+    params(
+        ("wallet_id" = String, Path, description = "Wallet ID"),
+        HeatmapParams
+    ),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Daily activity", body = WalletHeatmapResponse),
+        (status = 400, description = "Invalid days"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not authorized (admin required)"),
+        (status = 404, description = "Wallet not found")
+    )
+)]
+pub async fn get_wallet_heatmap(
+    AdminOnly(admin): AdminOnly,
+    Path(wallet_id): Path<String>,
+    Query(params): Query<HeatmapParams>,
+    State(state): State<AppState>,
+) -> Result<Json<WalletHeatmapResponse>, ApiError> {
+    let days = params.days.unwrap_or(DEFAULT_HEATMAP_DAYS);
+    if days == 0 || days > MAX_HEATMAP_DAYS {
+        return Err(ApiError::bad_request(format!(
+            "days must be between 1 and {MAX_HEATMAP_DAYS}"
+        )));
+    }
+    let storage = state.storage();
+    let wallet = WalletRepository::new(storage)
+        .get(&wallet_id)
+        .ok()
+        .filter(|w| admin.sees_tenant(w.tenant_id.as_deref()))
+        .ok_or_else(|| ApiError::not_found(format!("Wallet {} not found", wallet_id)))?;
+
+    let history = match &state.tx_db {
+        Some(tx_db) => wallet_history(tx_db, &wallet.public_address)
+            .map_err(|e| ApiError::internal(format!("Failed to list transactions: {e}")))?,
+        None => Vec::new(),
+    };
+    let requests: Vec<StoredFiatRequest> = FiatRequestRepository::new(storage)
+        .list_by_owner(&wallet.owner_user_id)
+        .context("Failed to list fiat requests")?
+        .into_iter()
+        .filter(|request| request.wallet_id == wallet.wallet_id)
+        .collect();
+    let prices = PriceHistoryRepository::new(storage)
+        .get_many(&PRICED_SYMBOLS)
+        .unwrap_or_else(|e| {
+            tracing::warn!(error = %e, "Failed to load price history");
+            PriceHistories::new()
+        });
+
+    let now = Utc::now();
+    let heatmap = daily_activity(&history, &requests, &prices, now.date_naive(), days);
+
+    log_admin_read(
+        storage,
+        &admin,
+        "wallets",
+        &wallet.wallet_id,
+        &["transactions", "fiat_requests"],
+        history.len() + requests.len(),
+    );
+
+    Ok(Json(WalletHeatmapResponse {
+        wallet_id: wallet.wallet_id,
+        generated_at: now.to_rfc3339(),
+        days,
+        heatmap,
+    }))
+}
+
+/// `type` of a proof-of-reserve statement.
+pub const RESERVE_STATEMENT_TYPE: &str = "relational.proof_of_reserve.v1";
+
+/// One token's totals across all custodial wallets. Amounts are decimal
+/// strings; `*_raw` ones are in the token's smallest unit.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct ReserveTokenTotal {
+    pub symbol: String,
+    /// `None` for the native token.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contract_address: Option<String>,
+    pub decimals: u8,
+    /// Sum of the wallets' on-chain balances.
+    pub on_chain_raw: String,
+    pub on_chain: String,
+    /// Net confirmed transfers into the wallets recorded by the server; may
+    /// be negative. Native totals leave out the gas the wallets paid.
+    pub ledger_raw: String,
+    pub ledger: String,
+    /// `on_chain_raw - ledger_raw`.
+    pub difference_raw: String,
+    /// Whether the two totals agree.
+    pub balanced: bool,
+}
+
+/// Facts the server attests to about the custodial wallets' reserves.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ReserveStatement {
+    /// Always `relational.proof_of_reserve.v1`.
+    #[serde(rename = "type")]
+    pub statement_type: String,
+    pub chain_id: u64,
+    pub network: String,
+    /// Balances were read at or after this block.
+    pub block_number: u64,
+    /// Every custodial wallet address covered, sorted.
+    pub addresses: Vec<String>,
+    /// Native token first, then contracts by address.
+    pub tokens: Vec<ReserveTokenTotal>,
+    /// Unix seconds.
+    pub issued_at: i64,
+}
+
+/// Proof-of-reserve report signed inside the enclave.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ProofOfReserveResponse {
+    pub statement: ReserveStatement,
+    /// `statement` exactly as signed (canonical JSON).
+    pub signed_statement: String,
+    /// `t=<unix>,kid=<key id>,v1=<signature>` over `"{t}.{signed_statement}"`.
+    pub signature: String,
+}
+
+/// Token of a balance or transfer: `None` for the native token, otherwise
+/// the contract's address key.
+type TokenKey = Option<String>;
+
+fn token_key(token: &TokenType) -> TokenKey {
+    match token {
+        TokenType::Native => None,
+        TokenType::Erc20(contract) => Some(address_key(contract)),
+    }
+}
+
+/// Net confirmed transfers into `addresses` on `network`, for the tokens in
+/// `decimals`. Transfers between two of the addresses cancel out.
+fn ledger_totals(
+    transactions: &[StoredTransaction],
+    addresses: &HashSet<String>,
+    network: &str,
+    decimals: &HashMap<TokenKey, u8>,
+) -> HashMap<TokenKey, I256> {
+    let mut seen = HashSet::new();
+    let mut totals = HashMap::new();
+    for tx in transactions {
+        if tx.status != TxStatus::Confirmed
+            || tx.network != network
+            || !seen.insert(tx.tx_hash.to_ascii_lowercase())
+        {
+            continue;
+        }
+        let key = token_key(&tx.token);
+        let Some(&token_decimals) = decimals.get(&key) else {
+            continue;
+        };
+        let Some(amount) = parse_amount(&tx.amount, token_decimals)
+            .ok()
+            .and_then(|amount| I256::try_from(amount).ok())
+        else {
+            tracing::warn!(
+                tx_hash = %tx.tx_hash,
+                amount = %tx.amount,
+                "Unreadable transfer amount left out of the reserve ledger"
+            );
+            continue;
+        };
+        let total = totals.entry(key).or_insert(I256::ZERO);
+        if addresses.contains(&address_key(&tx.to)) {
+            *total = total.saturating_add(amount);
+        }
+        if addresses.contains(&address_key(&tx.from)) {
+            *total = total.saturating_sub(amount);
+        }
+    }
+    totals
+}
+
+fn format_signed_amount(value: I256, decimals: u8) -> String {
+    let formatted = format_amount(value.unsigned_abs(), decimals);
+    if value.is_negative() {
+        format!("-{formatted}")
+    } else {
+        formatted
+    }
+}
+
+/// Compare summed on-chain balances with the ledger, one entry per token.
+fn reserve_totals(
+    on_chain: BTreeMap<TokenKey, (TokenBalance, U256)>,
+    ledger: &HashMap<TokenKey, I256>,
+) -> Vec<ReserveTokenTotal> {
+    on_chain
+        .into_iter()
+        .map(|(key, (token, total))| {
+            let ledger = ledger.get(&key).copied().unwrap_or(I256::ZERO);
+            let difference = I256::try_from(total)
+                .unwrap_or(I256::MAX)
+                .saturating_sub(ledger);
+            ReserveTokenTotal {
+                on_chain_raw: total.to_string(),
+                on_chain: format_amount(total, token.decimals),
+                ledger_raw: ledger.to_string(),
+                ledger: format_signed_amount(ledger, token.decimals),
+                difference_raw: difference.to_string(),
+                balanced: difference.is_zero(),
+                symbol: token.symbol,
+                contract_address: token.contract_address,
+                decimals: token.decimals,
+            }
+        })
+        .collect()
+}
+
+/// Signed proof of reserve.
+///
+/// Sums the on-chain balances of every custodial wallet, including
+/// suspended and deleted ones, per token and compares them with the
+/// server's ledger of confirmed transfers. The statement is signed with the
+/// key published at `GET /v1/webhooks/signing-key`. Balances are read from
+/// the chain, so the report fails with 503 if the RPC is unavailable.
+/// Platform admins only.
+#[utoipa::path(
+    get,
+    path = "/v1/admin/reports/proof-of-reserve",
+    tag = "Admin",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Signed proof of reserve", body = ProofOfReserveResponse),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not authorized (platform admin required)"),
+        (status = 503, description = "Chain RPC or signing key unavailable")
+    )
+)]
+pub async fn get_proof_of_reserve(
+    AdminOnly(admin): AdminOnly,
+    State(state): State<AppState>,
+) -> Result<Json<ProofOfReserveResponse>, ApiError> {
+    require_platform_admin(&admin)?;
+    let storage = state.storage();
+    let network = avax_fuji();
+
+    let wallets = WalletRepository::new(storage)
+        .list_all_wallets()
+        .context("Failed to list wallets")?;
+    let block_number = state
+        .chain_client(&network)
+        .await
+        .map_err(|e| ApiError::service_unavailable(format!("Failed to connect: {e}")))?
+        .get_block_number()
+        .await
+        .map_err(|e| ApiError::service_unavailable(format!("Failed to read block: {e}")))?;
+
+    let mut on_chain: BTreeMap<TokenKey, (TokenBalance, U256)> = BTreeMap::new();
+    let mut addresses = BTreeMap::new();
+    for wallet in &wallets {
+        let key = address_key(&wallet.public_address);
+        if addresses.contains_key(&key) {
+            continue;
+        }
+        let balance = fetch_address_balance(&state, &network, &wallet.public_address, None).await?;
+        for token in std::iter::once(balance.native_balance).chain(balance.token_balances) {
+            let raw = U256::from_str_radix(&token.balance_raw, 10).map_err(|_| {
+                ApiError::internal(format!(
+                    "Unreadable {} balance of {}",
+                    token.symbol, wallet.public_address
+                ))
+            })?;
+            let entry = on_chain
+                .entry(token.contract_address.as_deref().map(address_key))
+                .or_insert_with(|| (token, U256::ZERO));
+            entry.1 = entry.1.saturating_add(raw);
+        }
+        addresses.insert(key, checksum_address(&wallet.public_address));
+    }
+
+    let transactions = match &state.tx_db {
+        Some(tx_db) => tx_db
+            .list_all_transactions()
+            .map_err(|e| ApiError::internal(format!("Failed to list transactions: {e}")))?,
+        None => Vec::new(),
+    };
+    let decimals = on_chain
+        .iter()
+        .map(|(key, (token, _))| (key.clone(), token.decimals))
+        .collect();
+    let address_keys = addresses.keys().cloned().collect();
+    let ledger = ledger_totals(&transactions, &address_keys, network.id, &decimals);
+
+    let statement = ReserveStatement {
+        statement_type: RESERVE_STATEMENT_TYPE.to_string(),
+        chain_id: network.chain_id,
+        network: network.id.to_string(),
+        block_number,
+        addresses: addresses.into_values().collect(),
+        tokens: reserve_totals(on_chain, &ledger),
+        issued_at: Utc::now().timestamp(),
+    };
+    let (signed_statement, signature) =
+        sign_statement(&state, &statement, statement.issued_at).await?;
+
+    log_admin_read(
+        storage,
+        &admin,
+        "wallets",
+        "*",
+        &["public_address", "balances"],
+        statement.addresses.len(),
+    );
+
+    Ok(Json(ProofOfReserveResponse {
+        statement,
+        signed_statement,
+        signature,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::FiatDirection;
+
+    fn wallet(id: &str, owner: &str, age_days: i64) -> WalletMetadata {
+        WalletMetadata {
+            wallet_id: id.to_string(),
+            owner_user_id: owner.to_string(),
+            public_address: format!("0x{id}"),
+            created_at: Utc::now() - TimeDelta::days(age_days),
+            status: WalletStatus::Active,
+            label: None,
+            email_lookup_key: None,
+            email_sha256: None,
+            account_type: Default::default(),
+            smart_account: None,
+            lock: None,
+            deleted_at: None,
+            purged_at: None,
+            funded_at: None,
+            tenant_id: None,
+        }
+    }
+
+    fn tx(wallet_id: &str, counterparty: Option<&str>, age_days: i64) -> StoredTransaction {
+        let mut tx = StoredTransaction::new_pending(
+            format!("0x{wallet_id}{age_days}"),
+            wallet_id.to_string(),
+            counterparty.map(str::to_string),
+            "0xfrom".to_string(),
+            "0xto".to_string(),
+            "1.0".to_string(),
+            TokenType::Native,
+            "fuji".to_string(),
+            String::new(),
+        );
+        tx.status = TxStatus::Confirmed;
+        tx.created_at = Utc::now() - TimeDelta::days(age_days);
+        tx
+    }
+
+    #[test]
+    fn recent_transactions_fiat_and_sign_ins_keep_wallets_active() {
+        let now = Utc::now();
+        let mut onramp = StoredFiatRequest::new_queued(
+            "fr-1".to_string(),
+            "w-fiat".to_string(),
+            "u-fiat".to_string(),
+            FiatDirection::OnRamp,
+            "10.00".to_string(),
+            "truelayer_sandbox".to_string(),
+            None,
+        );
+        onramp.updated_at = now - TimeDelta::days(5);
+        let old_tx = tx("w-old", None, 500);
+        let activity = last_activity_by_wallet(
+            &[tx("w-sender", Some("w-receiver"), 10), old_tx.clone()],
+            &[onramp],
+        );
+
+        let mut deleted = wallet("w-deleted", "u-deleted", 900);
+        deleted.status = WalletStatus::Deleted;
+        let wallets = vec![
+            wallet("w-sender", "u-sender", 900),
+            wallet("w-receiver", "u-receiver", 900),
+            wallet("w-old", "u-old", 900),
+            wallet("w-fiat", "u-fiat", 900),
+            wallet("w-seen", "u-seen", 900),
+            wallet("w-new", "u-new", 3),
+            deleted,
+        ];
+        let last_seen = HashMap::from([
+            ("u-seen".to_string(), Some(now - TimeDelta::days(1))),
+            ("u-old".to_string(), None),
+        ]);
+
+        let inactive = inactive_wallets(wallets, &activity, &last_seen, now - TimeDelta::days(365));
+        assert_eq!(inactive.len(), 1);
+        assert_eq!(inactive[0].0.wallet_id, "w-old");
+        assert_eq!(inactive[0].1, old_tx.created_at);
+    }
+
+    #[test]
+    fn heatmap_buckets_activity_by_day() {
+        let today = Utc::now().date_naive();
+        let sent = tx("w-1", None, 0);
+        let received = tx("w-1", None, 2);
+        let mut failed = tx("w-1", None, 0);
+        failed.status = TxStatus::Failed;
+        let too_old = tx("w-1", None, 30);
+        let history = vec![
+            (sent, "sent".to_string()),
+            (received, "received".to_string()),
+            (failed, "sent".to_string()),
+            (too_old, "received".to_string()),
+        ];
+        let mut offramp = StoredFiatRequest::new_queued(
+            "fr-2".to_string(),
+            "w-1".to_string(),
+            "u-1".to_string(),
+            FiatDirection::OffRamp,
+            "25.50".to_string(),
+            "truelayer_sandbox".to_string(),
+            None,
+        );
+        offramp.created_at = Utc::now() - TimeDelta::days(2);
+
+        let heatmap = daily_activity(&history, &[offramp], &PriceHistories::new(), today, 7);
+        assert_eq!(heatmap.len(), 7);
+        assert_eq!(heatmap[6].date, today.to_string());
+        assert_eq!((heatmap[6].sends, heatmap[6].receipts), (1, 0));
+        assert_eq!(heatmap[6].unpriced, 1);
+        assert_eq!(heatmap[4].receipts, 1);
+        assert_eq!(heatmap[4].fiat_operations, 1);
+        assert_eq!(heatmap[4].fiat_eur, 25.5);
+        let total: u32 = heatmap.iter().map(|d| d.sends + d.receipts).sum();
+        assert_eq!(total, 2);
+    }
+
+    #[test]
+    fn reserve_ledger_nets_external_transfers_per_token() {
+        let transfer = |hash: &str, from: &str, to: &str, amount: &str, token: TokenType| {
+            let mut tx = StoredTransaction::new_pending(
+                hash.to_string(),
+                "w-1".to_string(),
+                None,
+                from.to_string(),
+                to.to_string(),
+                amount.to_string(),
+                token,
+                "fuji".to_string(),
+                String::new(),
+            );
+            tx.status = TxStatus::Confirmed;
+            tx
+        };
+        let reur = "0x00000000000000000000000000000000000000Ee";
+        let mut pending = transfer("0x5", "0xext", "0xa", "9", TokenType::Native);
+        pending.status = TxStatus::Pending;
+        let mut elsewhere = transfer("0x6", "0xext", "0xa", "9", TokenType::Native);
+        elsewhere.network = "mainnet".to_string();
+        let transactions = vec![
+            transfer("0x1", "0xEXT", "0xA", "3", TokenType::Native),
+            transfer("0x1", "0xEXT", "0xA", "3", TokenType::Native),
+            transfer("0x2", "0xa", "0xext", "1.5", TokenType::Native),
+            transfer("0x3", "0xa", "0xb", "1", TokenType::Native),
+            transfer(
+                "0x4",
+                "0xa",
+                "0xext",
+                "2.25",
+                TokenType::Erc20(reur.to_string()),
+            ),
+            transfer(
+                "0x7",
+                "0xext",
+                "0xa",
+                "4",
+                TokenType::Erc20("0xother".to_string()),
+            ),
+            pending,
+            elsewhere,
+        ];
+        let addresses = HashSet::from(["0xa".to_string(), "0xb".to_string()]);
+        let decimals = HashMap::from([(None, 18), (Some(address_key(reur)), 6)]);
+
+        let ledger = ledger_totals(&transactions, &addresses, "fuji", &decimals);
+        assert_eq!(ledger.len(), 2);
+        assert_eq!(format_signed_amount(ledger[&None], 18), "1.5");
+        assert_eq!(
+            format_signed_amount(ledger[&Some(address_key(reur))], 6),
+            "-2.25"
+        );
+
+        let balance = |symbol: &str, contract: Option<&str>, decimals: u8| TokenBalance {
+            symbol: symbol.to_string(),
+            name: symbol.to_string(),
+            balance_raw: "0".to_string(),
+            balance_formatted: "0".to_string(),
+            decimals,
+            contract_address: contract.map(str::to_string),
+        };
+        let on_chain = BTreeMap::from([
+            (
+                None,
+                (
+                    balance("AVAX", None, 18),
+                    U256::from(1_500_000_000_000_000_000u128),
+                ),
+            ),
+            (
+                Some(address_key(reur)),
+                (balance("rEUR", Some(reur), 6), U256::from(1_000_000u64)),
+            ),
+        ]);
+        let totals = reserve_totals(on_chain, &ledger);
+        assert_eq!(totals[0].symbol, "AVAX");
+        assert!(totals[0].balanced);
+        assert_eq!(totals[0].on_chain, "1.5");
+        assert_eq!(totals[1].ledger_raw, "-2250000");
+        assert_eq!(totals[1].difference_raw, "3250000");
+        assert!(!totals[1].balanced);
+    }
+}
@@ -0,0 +1,668 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! User-configurable wallet alerts.
+//!
+//! A wallet owner sets rules at `PUT /v1/wallets/{wallet_id}/alerts`: a
+//! balance below a threshold, an incoming transfer above a threshold, or any
+//! outgoing transfer. The fiat poller evaluates them about once a minute and
+//! records raised alerts on the wallet's rules, where clients read them as
+//! notifications.
+//!
+//! A low balance alerts once when it falls below the threshold and again
+//! only after it recovered. Transfer alerts cover confirmed transfers
+//! indexed after the rules were last saved, and their message is the
+//! transfer's [description](crate::api::tx_descriptions) when a template
+//! matches.
+
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+use utoipa::ToSchema;
+
+use crate::{
+    api::{
+        fiat::resolve_reur_contract_address,
+        tx_descriptions::{TxDescriber, TxFacts},
+    },
+    auth::{Auth, AuthenticatedUser},
+    blockchain::{address_key, AvaxClient},
+    error::{ApiError, StorageContext},
+    providers::pricing::{token_symbol, PRICED_SYMBOLS},
+    state::AppState,
+    storage::{
+        AlertEvent, AlertEventKind, AlertRepository, AlertRule, AlertRuleKind, EncryptedStorage,
+        OwnershipEnforcer, StorageError, StoredTransaction, StoredWalletAlerts, TxDatabase,
+        TxStatus, WalletMetadata, WalletRepository, WalletStatus,
+    },
+};
+
+/// Rules a wallet may have.
+const MAX_RULES: usize = 10;
+
+/// Most recent transactions checked for transfer alerts on each run.
+const RECENT_TRANSFERS: usize = 50;
+
+/// Request body for replacing a wallet's alert rules.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct WalletAlertsRequest {
+    /// The new rules; an empty list turns alerts off.
+    pub rules: Vec<AlertRule>,
+}
+
+/// A wallet's alert rules and recent alerts.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct WalletAlertsResponse {
+    pub wallet_id: String,
+    pub rules: Vec<AlertRule>,
+    /// Recent alerts, newest first.
+    pub events: Vec<AlertEvent>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+fn to_response(wallet_id: String, alerts: Option<&StoredWalletAlerts>) -> WalletAlertsResponse {
+    WalletAlertsResponse {
+        wallet_id,
+        rules: alerts.map(|a| a.rules.clone()).unwrap_or_default(),
+        events: alerts
+            .map(|a| a.events.iter().rev().cloned().collect())
+            .unwrap_or_default(),
+        updated_at: alerts.map(|a| a.updated_at),
+    }
+}
+
+fn load_owned_wallet(
+    state: &AppState,
+    user: &AuthenticatedUser,
+    wallet_id: &str,
+) -> Result<WalletMetadata, ApiError> {
+    let wallet = WalletRepository::new(state.storage()).get(wallet_id)?;
+    if !wallet.is_owned_by(user) {
+        return Err(ApiError::forbidden("You do not own this wallet").with_code("wallet_not_owned"));
+    }
+    if wallet.status == WalletStatus::Deleted {
+        return Err(ApiError::not_found("Wallet not found").with_code("wallet_not_found"));
+    }
+    Ok(wallet)
+}
+
+fn load_alerts(state: &AppState, wallet_id: &str) -> Result<Option<StoredWalletAlerts>, ApiError> {
+    match AlertRepository::new(state.storage()).get(wallet_id) {
+        Ok(alerts) => Ok(Some(alerts)),
+        Err(StorageError::NotFound(_)) => Ok(None),
+        Err(e) => Err(ApiError::internal(format!("Failed to load alerts: {e}"))),
+    }
+}
+
+/// Check and normalize rules: token symbols are canonicalized and unused
+/// fields dropped.
+fn validate_rules(rules: Vec<AlertRule>) -> Result<Vec<AlertRule>, ApiError> {
+    if rules.len() > MAX_RULES {
+        return Err(ApiError::bad_request(format!(
+            "At most {MAX_RULES} alert rules are allowed"
+        )));
+    }
+    let mut normalized: Vec<AlertRule> = Vec::with_capacity(rules.len());
+    for rule in rules {
+        let rule = match rule.kind {
+            AlertRuleKind::AnyOutgoing => AlertRule {
+                kind: rule.kind,
+                token: None,
+                threshold: None,
+            },
+            AlertRuleKind::LowBalance | AlertRuleKind::LargeIncoming => {
+                let token = rule
+                    .token
+                    .as_deref()
+                    .and_then(|t| PRICED_SYMBOLS.iter().find(|s| s.eq_ignore_ascii_case(t)))
+                    .ok_or_else(|| ApiError::bad_request("token must be AVAX or rEUR"))?;
+                let threshold = rule
+                    .threshold
+                    .as_deref()
+                    .map(str::trim)
+                    .filter(|t| t.parse::<f64>().is_ok_and(|v| v.is_finite() && v > 0.0))
+                    .ok_or_else(|| ApiError::bad_request("threshold must be a positive amount"))?;
+                AlertRule {
+                    kind: rule.kind,
+                    token: Some(token.to_string()),
+                    threshold: Some(threshold.to_string()),
+                }
+            }
+        };
+        if normalized
+            .iter()
+            .any(|r| r.kind == rule.kind && r.token == rule.token)
+        {
+            return Err(ApiError::bad_request("Duplicate alert rule"));
+        }
+        normalized.push(rule);
+    }
+    Ok(normalized)
+}
+
+/// Replace a wallet's alert rules.
+#[utoipa::path(
+    put,
+    path = "/v1/wallets/{wallet_id}/alerts",
+    tag = "Wallets",
+    params(("wallet_id" = String, Path, description = "Wallet ID")),
+    request_body = WalletAlertsRequest,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Alert rules saved", body = WalletAlertsResponse),
+        (status = 400, description = "Invalid rule"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+        (status = 404, description = "Wallet not found")
+    )
+)]
+pub async fn put_wallet_alerts(
+    Auth(user): Auth,
+    State(state): State<AppState>,
+    Path(wallet_id): Path<String>,
+    Json(request): Json<WalletAlertsRequest>,
+) -> Result<Json<WalletAlertsResponse>, ApiError> {
+    load_owned_wallet(&state, &user, &wallet_id)?;
+    let rules = validate_rules(request.rules)?;
+
+    let now = Utc::now();
+    let existing = load_alerts(&state, &wallet_id)?;
+    let low_tokens = existing
+        .as_ref()
+        .map(|a| {
+            a.low_tokens
+                .iter()
+                .filter(|token| {
+                    rules.iter().any(|r| {
+                        r.kind == AlertRuleKind::LowBalance && r.token.as_ref() == Some(*token)
+                    })
+                })
+                .cloned()
+                .collect()
+        })
+        .unwrap_or_default();
+    let alerts = StoredWalletAlerts {
+        wallet_id: wallet_id.clone(),
+        owner_user_id: user.user_id.clone(),
+        rules,
+        armed_at: now,
+        low_tokens,
+        seen_tx: existing
+            .as_ref()
+            .map(|a| a.seen_tx.clone())
+            .unwrap_or_default(),
+        events: existing
+            .as_ref()
+            .map(|a| a.events.clone())
+            .unwrap_or_default(),
+        created_at: existing.as_ref().map(|a| a.created_at).unwrap_or(now),
+        updated_at: now,
+    };
+    AlertRepository::new(state.storage())
+        .save(&alerts)
+        .context("Failed to store alerts")?;
+
+    Ok(Json(to_response(wallet_id, Some(&alerts))))
+}
+
+/// Get a wallet's alert rules and recent alerts.
+#[utoipa::path(
+    get,
+    path = "/v1/wallets/{wallet_id}/alerts",
+    tag = "Wallets",
+    params(("wallet_id" = String, Path, description = "Wallet ID")),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Alert rules", body = WalletAlertsResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+        (status = 404, description = "Wallet not found")
+    )
+)]
+pub async fn get_wallet_alerts(
+    Auth(user): Auth,
+    State(state): State<AppState>,
+    Path(wallet_id): Path<String>,
+) -> Result<Json<WalletAlertsResponse>, ApiError> {
+    load_owned_wallet(&state, &user, &wallet_id)?;
+    let alerts = load_alerts(&state, &wallet_id)?;
+    Ok(Json(to_response(wallet_id, alerts.as_ref())))
+}
+
+fn parse_amount(amount: &str) -> Option<f64> {
+    amount.trim().parse::<f64>().ok()
+}
+
+/// Raise transfer alerts for a page of the wallet's history (newest first).
+/// `describe` may replace the default message of a transfer. Returns the
+/// number of alerts raised and whether `alerts` changed.
+fn evaluate_transfers(
+    alerts: &mut StoredWalletAlerts,
+    history: &[(StoredTransaction, String)],
+    now: DateTime<Utc>,
+    describe: impl Fn(&StoredTransaction, &str) -> Option<String>,
+) -> (usize, bool) {
+    let any_outgoing = alerts
+        .rules
+        .iter()
+        .any(|r| r.kind == AlertRuleKind::AnyOutgoing);
+    let mut raised = 0;
+    let mut changed = false;
+    for (tx, direction) in history.iter().rev() {
+        if tx.status != TxStatus::Confirmed
+            || tx.created_at < alerts.armed_at
+            || alerts.has_seen(&tx.tx_hash)
+        {
+            continue;
+        }
+        alerts.mark_seen(&tx.tx_hash);
+        changed = true;
+
+        let symbol = token_symbol(&tx.token).unwrap_or("tokens");
+        if direction == "sent" {
+            if any_outgoing {
+                let message = describe(tx, direction)
+                    .unwrap_or_else(|| format!("Sent {} {symbol} to {}", tx.amount, tx.to));
+                alerts.record_event(
+                    AlertEventKind::OutgoingTransfer,
+                    Some(tx.tx_hash.clone()),
+                    message,
+                    now,
+                );
+                raised += 1;
+            }
+            continue;
+        }
+        let amount = parse_amount(&tx.amount).unwrap_or(0.0);
+        let large = alerts.rules.iter().any(|r| {
+            r.kind == AlertRuleKind::LargeIncoming
+                && r.token.as_deref() == Some(symbol)
+                && r.threshold
+                    .as_deref()
+                    .and_then(parse_amount)
+                    .is_some_and(|threshold| amount > threshold)
+        });
+        if large {
+            let message = describe(tx, direction)
+                .unwrap_or_else(|| format!("Received {} {symbol} from {}", tx.amount, tx.from));
+            alerts.record_event(
+                AlertEventKind::LargeIncoming,
+                Some(tx.tx_hash.clone()),
+                message,
+                now,
+            );
+            raised += 1;
+        }
+    }
+    (raised, changed)
+}
+
+/// Apply a balance reading to a low-balance rule. Returns the number of
+/// alerts raised and whether `alerts` changed.
+fn evaluate_balance(
+    alerts: &mut StoredWalletAlerts,
+    token: &str,
+    threshold: &str,
+    balance: &str,
+    now: DateTime<Utc>,
+) -> (usize, bool) {
+    let (Some(value), Some(limit)) = (parse_amount(balance), parse_amount(threshold)) else {
+        return (0, false);
+    };
+    let was_low = alerts.low_tokens.iter().any(|t| t == token);
+    if value < limit && !was_low {
+        alerts.low_tokens.push(token.to_string());
+        alerts.record_event(
+            AlertEventKind::LowBalance,
+            None,
+            format!("{token} balance is {balance}, below {threshold}"),
+            now,
+        );
+        (1, true)
+    } else if value >= limit && was_low {
+        alerts.low_tokens.retain(|t| t != token);
+        (0, true)
+    } else {
+        (0, false)
+    }
+}
+
+/// Formatted balance of `token` for an address.
+async fn read_balance(
+    client: &AvaxClient,
+    address: &str,
+    token: &str,
+    reur: Option<&str>,
+) -> Option<String> {
+    let balance = match (token, reur) {
+        ("AVAX", _) => client.get_native_balance(address).await,
+        ("rEUR", Some(reur)) => client.get_token_balance(address, reur).await,
+        _ => return None,
+    };
+    match balance {
+        Ok(balance) => Some(balance.balance_formatted),
+        Err(e) => {
+            warn!(%address, token, error = %e, "Alerts: failed to read balance");
+            None
+        }
+    }
+}
+
+/// Evaluate every wallet's alert rules. Returns the number of alerts raised.
+pub(crate) async fn run_alerts(storage: &Arc<EncryptedStorage>, tx_db: &TxDatabase) -> usize {
+    let repo = AlertRepository::new(storage);
+    let all = match repo.list_all() {
+        Ok(all) => all,
+        Err(e) => {
+            warn!(error = %e, "Alerts: failed to list rules");
+            return 0;
+        }
+    };
+    let reur = resolve_reur_contract_address().ok();
+    let describer = TxDescriber::load(storage);
+    let mut client: Option<AvaxClient> = None;
+    let mut raised = 0;
+
+    for mut alerts in all.into_iter().filter(|a| !a.rules.is_empty()) {
+        let Ok(wallet) = WalletRepository::new(storage).get(&alerts.wallet_id) else {
+            continue;
+        };
+        if wallet.status == WalletStatus::Deleted {
+            continue;
+        }
+        let now = Utc::now();
+        let mut changed = false;
+
+        if alerts.watches_transfers() {
+            match tx_db.list_by_wallet(&address_key(&wallet.public_address), None, RECENT_TRANSFERS)
+            {
+                Ok((page, _)) => {
+                    let (count, updated) =
+                        evaluate_transfers(&mut alerts, &page, now, |tx, dir| {
+                            describer.describe(&TxFacts::of_stored(tx, dir, None))
+                        });
+                    raised += count;
+                    changed |= updated;
+                }
+                Err(e) => {
+                    warn!(wallet_id = %alerts.wallet_id, error = %e, "Alerts: failed to list transactions")
+                }
+            }
+        }
+
+        let low_rules: Vec<AlertRule> = alerts
+            .rules
+            .iter()
+            .filter(|r| r.kind == AlertRuleKind::LowBalance)
+            .cloned()
+            .collect();
+        for rule in low_rules {
+            let (Some(token), Some(threshold)) = (rule.token, rule.threshold) else {
+                continue;
+            };
+            if client.is_none() {
+                match AvaxClient::fuji().await {
+                    Ok(c) => client = Some(c),
+                    Err(e) => {
+                        warn!(error = %e, "Alerts: failed to connect to chain");
+                        break;
+                    }
+                }
+            }
+            let Some(chain) = client.as_ref() else {
+                break;
+            };
+            let Some(balance) =
+                read_balance(chain, &wallet.public_address, &token, reur.as_deref()).await
+            else {
+                continue;
+            };
+            let (count, updated) = evaluate_balance(&mut alerts, &token, &threshold, &balance, now);
+            raised += count;
+            changed |= updated;
+        }
+
+        if changed {
+            alerts.updated_at = now;
+            if let Err(e) = repo.save(&alerts) {
+                warn!(wallet_id = %alerts.wallet_id, error = %e, "Alerts: failed to save rules");
+            }
+        }
+    }
+
+    raised
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::{AuthenticatedUser, Role};
+    use crate::storage::TokenType;
+    use axum::http::StatusCode;
+    use chrono::TimeDelta;
+
+    const ME: &str = "0x1111111111111111111111111111111111111111";
+    const OTHER: &str = "0x2222222222222222222222222222222222222222";
+
+    fn user(user_id: &str) -> Auth {
+        Auth(AuthenticatedUser {
+            user_id: user_id.to_string(),
+            role: Role::Client,
+            session_id: None,
+            issuer: "https://test.clerk.dev".into(),
+            expires_at: Utc::now().timestamp() + 3600,
+            tenant_id: None,
+        })
+    }
+
+    fn rule(kind: AlertRuleKind, token: Option<&str>, threshold: Option<&str>) -> AlertRule {
+        AlertRule {
+            kind,
+            token: token.map(str::to_string),
+            threshold: threshold.map(str::to_string),
+        }
+    }
+
+    fn alerts(rules: Vec<AlertRule>, armed_at: DateTime<Utc>) -> StoredWalletAlerts {
+        StoredWalletAlerts {
+            wallet_id: "wallet-1".to_string(),
+            owner_user_id: "user-1".to_string(),
+            rules,
+            armed_at,
+            low_tokens: Vec::new(),
+            seen_tx: Vec::new(),
+            events: Vec::new(),
+            created_at: armed_at,
+            updated_at: armed_at,
+        }
+    }
+
+    fn transfer(
+        hash: &str,
+        direction: &str,
+        amount: &str,
+        at: DateTime<Utc>,
+    ) -> (StoredTransaction, String) {
+        let (from, to) = if direction == "sent" {
+            (ME, OTHER)
+        } else {
+            (OTHER, ME)
+        };
+        let mut tx = StoredTransaction::new_pending(
+            hash.to_string(),
+            "wallet-1".to_string(),
+            None,
+            from.to_string(),
+            to.to_string(),
+            amount.to_string(),
+            TokenType::Native,
+            "fuji".to_string(),
+            String::new(),
+        );
+        tx.status = TxStatus::Confirmed;
+        tx.created_at = at;
+        (tx, direction.to_string())
+    }
+
+    #[test]
+    fn rules_are_validated_and_normalized() {
+        let rules = validate_rules(vec![
+            rule(AlertRuleKind::LowBalance, Some("reur"), Some(" 25 ")),
+            rule(AlertRuleKind::AnyOutgoing, Some("AVAX"), Some("1")),
+        ])
+        .unwrap();
+        assert_eq!(rules[0].token.as_deref(), Some("rEUR"));
+        assert_eq!(rules[0].threshold.as_deref(), Some("25"));
+        assert_eq!(rules[1].token, None);
+
+        for invalid in [
+            vec![rule(AlertRuleKind::LowBalance, Some("USDC"), Some("1"))],
+            vec![rule(AlertRuleKind::LargeIncoming, Some("AVAX"), Some("-1"))],
+            vec![rule(AlertRuleKind::LargeIncoming, Some("AVAX"), None)],
+            vec![
+                rule(AlertRuleKind::AnyOutgoing, None, None),
+                rule(AlertRuleKind::AnyOutgoing, None, None),
+            ],
+        ] {
+            assert!(validate_rules(invalid).is_err());
+        }
+    }
+
+    #[test]
+    fn transfer_alerts_fire_once_for_new_confirmed_transfers() {
+        let now = Utc::now();
+        let mut alerts = alerts(
+            vec![
+                rule(AlertRuleKind::LargeIncoming, Some("AVAX"), Some("10")),
+                rule(AlertRuleKind::AnyOutgoing, None, None),
+            ],
+            now - TimeDelta::hours(1),
+        );
+        let mut pending = transfer("0xd", "sent", "1", now);
+        pending.0.status = TxStatus::Pending;
+        let history = vec![
+            pending,
+            transfer("0xc", "received", "50", now),
+            transfer("0xb", "received", "5", now),
+            transfer("0xa", "sent", "2", now),
+            transfer("0x0", "sent", "2", now - TimeDelta::hours(2)),
+        ];
+
+        let no_description = |_: &StoredTransaction, _: &str| None;
+        assert_eq!(
+            evaluate_transfers(&mut alerts, &history, now, no_description),
+            (2, true)
+        );
+        assert_eq!(alerts.events[0].kind, AlertEventKind::OutgoingTransfer);
+        assert_eq!(alerts.events[1].kind, AlertEventKind::LargeIncoming);
+        assert_eq!(alerts.events[1].tx_hash.as_deref(), Some("0xc"));
+        assert_eq!(
+            evaluate_transfers(&mut alerts, &history, now, no_description),
+            (0, false)
+        );
+    }
+
+    #[test]
+    fn low_balance_alerts_again_only_after_recovery() {
+        let now = Utc::now();
+        let mut alerts = alerts(Vec::new(), now);
+        assert_eq!(
+            evaluate_balance(&mut alerts, "rEUR", "20", "15.5", now),
+            (1, true)
+        );
+        assert_eq!(
+            evaluate_balance(&mut alerts, "rEUR", "20", "10", now),
+            (0, false)
+        );
+        assert_eq!(
+            evaluate_balance(&mut alerts, "rEUR", "20", "30", now),
+            (0, true)
+        );
+        assert_eq!(
+            evaluate_balance(&mut alerts, "rEUR", "20", "5", now),
+            (1, true)
+        );
+        assert_eq!(alerts.events.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn put_keeps_history_and_rejects_foreign_wallets() {
+        let state = AppState::default();
+        let now = Utc::now();
+        WalletRepository::new(state.storage())
+            .create(
+                &WalletMetadata {
+                    wallet_id: "wallet-1".to_string(),
+                    owner_user_id: "user-1".to_string(),
+                    public_address: ME.to_string(),
+                    created_at: now,
+                    status: WalletStatus::Active,
+                    label: None,
+                    email_lookup_key: None,
+                    email_sha256: None,
+                    account_type: Default::default(),
+                    smart_account: None,
+                    lock: None,
+                    deleted_at: None,
+                    purged_at: None,
+                    funded_at: None,
+                    tenant_id: None,
+                },
+                b"test_key",
+            )
+            .unwrap();
+
+        let Json(empty) = get_wallet_alerts(
+            user("user-1"),
+            State(state.clone()),
+            Path("wallet-1".into()),
+        )
+        .await
+        .unwrap();
+        assert!(empty.rules.is_empty());
+
+        let body = || WalletAlertsRequest {
+            rules: vec![rule(AlertRuleKind::AnyOutgoing, None, None)],
+        };
+        let _ = put_wallet_alerts(
+            user("user-1"),
+            State(state.clone()),
+            Path("wallet-1".into()),
+            Json(body()),
+        )
+        .await
+        .unwrap();
+        let repo = AlertRepository::new(state.storage());
+        let mut stored = repo.get("wallet-1").unwrap();
+        stored.record_event(AlertEventKind::OutgoingTransfer, None, "x".into(), now);
+        repo.save(&stored).unwrap();
+
+        let Json(updated) = put_wallet_alerts(
+            user("user-1"),
+            State(state.clone()),
+            Path("wallet-1".into()),
+            Json(body()),
+        )
+        .await
+        .unwrap();
+        assert_eq!(updated.events.len(), 1);
+
+        let err = put_wallet_alerts(
+            user("user-2"),
+            State(state),
+            Path("wallet-1".into()),
+            Json(body()),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::FORBIDDEN);
+    }
+}
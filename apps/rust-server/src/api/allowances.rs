@@ -0,0 +1,420 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! ERC-20 allowances.
+//!
+//! `approve` broadcasts an on-chain `approve(spender, amount)` from a
+//! custodial wallet, the way DEX routers and other contracts expect to be
+//! authorized before they pull tokens. An `amount` of `"0"` revokes an
+//! allowance; `"unlimited"` approves `2^256 - 1`. Smart-account wallets
+//! submit the approval as a UserOperation.
+//!
+//! `allowances` reads the current allowance of one or more spenders from
+//! the token contract. Nothing is indexed locally, so the caller names the
+//! spenders to check.
+//!
+//! For approvals that cost no gas, see [`crate::api::permits`].
+
+use alloy::primitives::U256;
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::{
+    api::{
+        claims::active_wallet,
+        limits,
+        permits::parse_address,
+        transactions::{
+            send_error, send_network, send_user_operation, sending_wallet, SendTransactionResponse,
+        },
+    },
+    auth::Auth,
+    blockchain::{
+        erc20::{approve_calldata, is_unlimited_allowance},
+        format_amount, parse_amount, resolve_network,
+        smart_account::Call,
+        wallet_from_pem, TxBuilder,
+    },
+    error::ApiError,
+    state::AppState,
+    storage::{AuditEvent, AuditEventType, AuditRepository, WalletAccountType, WalletRepository},
+};
+
+/// Most spenders checked by one allowances request.
+const MAX_SPENDERS: usize = 20;
+
+// =============================================================================
+// Request/Response Types
+// =============================================================================
+
+/// Request to approve a spender for an ERC-20 token.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct ApproveRequest {
+    /// ERC-20 contract address.
+    pub token: String,
+    /// Contract or address allowed to spend the tokens.
+    pub spender: String,
+    /// Allowance in human-readable units (e.g. "25.00"), `"0"` to revoke,
+    /// or `"unlimited"`.
+    pub amount: String,
+    /// Network ID from the server's network registry.
+    #[serde(default = "default_fuji")]
+    pub network: String,
+    /// Optional gas limit override
+    #[serde(default)]
+    pub gas_limit: Option<String>,
+    /// Optional max priority fee per gas override (in wei)
+    #[serde(default)]
+    pub max_priority_fee_per_gas: Option<String>,
+}
+
+fn default_fuji() -> String {
+    "fuji".to_string()
+}
+
+/// Query parameters for reading allowances.
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct AllowancesQuery {
+    /// ERC-20 contract address.
+    pub token: String,
+    /// Spender addresses to check (comma-separated, at most 20).
+    pub spenders: String,
+    /// Network to query, by registry ID.
+    #[param(default = "fuji")]
+    pub network: Option<String>,
+}
+
+/// Current allowance of one spender.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct Allowance {
+    pub spender: String,
+    /// Allowance in the token's smallest unit.
+    pub amount_raw: String,
+    /// Allowance in human-readable units.
+    pub amount: String,
+    /// Whether the allowance is effectively unlimited.
+    pub unlimited: bool,
+}
+
+/// Allowances a wallet has granted for a token.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct AllowancesResponse {
+    pub wallet_id: String,
+    /// Address that holds the tokens.
+    pub owner: String,
+    pub token: String,
+    pub network: String,
+    pub decimals: u8,
+    pub allowances: Vec<Allowance>,
+}
+
+// =============================================================================
+// Helpers
+// =============================================================================
+
+/// Parse an allowance amount: human-readable units or `"unlimited"`.
+pub(crate) fn parse_allowance_amount(amount: &str, decimals: u8) -> Result<U256, ApiError> {
+    if amount.eq_ignore_ascii_case("unlimited") {
+        return Ok(U256::MAX);
+    }
+    parse_amount(amount, decimals)
+        .map_err(|e| ApiError::bad_request(format!("Invalid amount: {e}")))
+}
+
+fn parse_spenders(raw: &str) -> Result<Vec<String>, ApiError> {
+    let spenders: Vec<String> = raw
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .collect();
+    if spenders.is_empty() || spenders.len() > MAX_SPENDERS {
+        return Err(ApiError::bad_request(format!(
+            "`spenders` must list between 1 and {MAX_SPENDERS} addresses"
+        )));
+    }
+    for spender in &spenders {
+        parse_address(spender, "spenders")?;
+    }
+    Ok(spenders)
+}
+
+// =============================================================================
+// Handlers
+// =============================================================================
+
+/// Approve a spender for an ERC-20 token.
+///
+/// Broadcasts `approve(spender, amount)` from the wallet. The new allowance
+/// replaces any previous one.
+#[utoipa::path(
+    post,
+    path = "/v1/wallets/{wallet_id}/approve",
+    tag = "Allowances",
+    params(("wallet_id" = String, Path, description = "Wallet ID")),
+    request_body = ApproveRequest,
+    security(("bearer" = [])),
+    responses(
+        (status = 200, description = "Approval submitted", body = SendTransactionResponse),
+        (status = 400, description = "Invalid request"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - not wallet owner, or suspended"),
+        (status = 404, description = "Wallet not found"),
+        (status = 422, description = "Insufficient gas balance, spending limit exceeded, or the ID is a watch-only address"),
+        (status = 503, description = "Blockchain network unavailable")
+    )
+)]
+pub async fn approve_token(
+    Auth(user): Auth,
+    State(state): State<AppState>,
+    Path(wallet_id): Path<String>,
+    Json(request): Json<ApproveRequest>,
+) -> Result<Json<SendTransactionResponse>, ApiError> {
+    let storage = state.storage();
+    let wallet = sending_wallet(storage, &user, &wallet_id)?;
+    parse_address(&request.token, "token")?;
+    parse_address(&request.spender, "spender")?;
+    let network = send_network(&wallet, &request.network)?;
+    let gas_limit = request
+        .gas_limit
+        .as_deref()
+        .map(str::parse::<u64>)
+        .transpose()
+        .map_err(|_| ApiError::bad_request("Invalid gas_limit"))?;
+    let max_priority_fee = request
+        .max_priority_fee_per_gas
+        .as_deref()
+        .map(str::parse::<u128>)
+        .transpose()
+        .map_err(|_| ApiError::bad_request("Invalid max_priority_fee_per_gas"))?;
+
+    let client = state
+        .chain_client(&network)
+        .await
+        .map_err(|e| ApiError::service_unavailable(format!("Failed to connect: {e}")))?;
+    let decimals = client
+        .get_token_decimals(&request.token)
+        .await
+        .map_err(|e| ApiError::service_unavailable(format!("Failed to read token: {e}")))?;
+    let amount = parse_allowance_amount(&request.amount, decimals)?;
+    let _reservation = limits::reserve_spending(
+        &state,
+        &wallet,
+        &[(&request.token, &request.amount)],
+        network.id,
+    )
+    .await?;
+
+    let result = if wallet.account_type == WalletAccountType::SmartAccount {
+        let call = Call::approve(&request.token, &request.spender, amount)
+            .map_err(|e| ApiError::bad_request(e.to_string()))?;
+        send_user_operation(storage, &wallet, &[call], gas_limit, max_priority_fee).await?
+    } else {
+        let calldata = approve_calldata(&request.spender, amount)
+            .map_err(|e| ApiError::bad_request(e.to_string()))?;
+        let key = WalletRepository::new(storage)
+            .read_private_key(&wallet_id)
+            .map_err(|e| ApiError::internal(format!("Failed to read private key: {e}")))?;
+        let eth_wallet = wallet_from_pem(&key)
+            .map_err(|e| ApiError::internal(format!("Failed to create signer: {e}")))?;
+        TxBuilder::new(network.clone(), eth_wallet)
+            .await
+            .map_err(|e| ApiError::service_unavailable(format!("Failed to connect: {e}")))?
+            .with_nonce_manager(state.nonce_manager.clone())
+            .send_contract_call(&request.token, calldata, None, gas_limit, max_priority_fee)
+            .await
+            .map_err(send_error)?
+    };
+
+    let event = AuditEvent::new(AuditEventType::TransactionBroadcast)
+        .with_user(&user.user_id)
+        .with_resource(&wallet_id, "wallet")
+        .with_details(serde_json::json!({
+            "tx_hash": result.tx_hash,
+            "kind": "approval",
+            "token": request.token,
+            "spender": request.spender,
+            "amount": amount.to_string(),
+            "network": network.id,
+        }));
+    let _ = AuditRepository::new(storage).log(&event);
+
+    Ok(Json(SendTransactionResponse {
+        tx_hash: result.tx_hash,
+        status: "pending".to_string(),
+        explorer_url: result.explorer_url,
+    }))
+}
+
+/// Read token allowances granted by a wallet.
+///
+/// Returns the current on-chain allowance of each listed spender.
+#[utoipa::path(
+    get,
+    path = "/v1/wallets/{wallet_id}/allowances",
+    tag = "Allowances",
+    params(
+        ("wallet_id" = String, Path, description = "Wallet ID"),
+        AllowancesQuery
+    ),
+    security(("bearer" = [])),
+    responses(
+        (status = 200, description = "Allowances read", body = AllowancesResponse),
+        (status = 400, description = "Invalid request"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - not wallet owner, or suspended"),
+        (status = 404, description = "Wallet not found"),
+        (status = 503, description = "Blockchain network unavailable")
+    )
+)]
+pub async fn get_allowances(
+    Auth(user): Auth,
+    State(state): State<AppState>,
+    Path(wallet_id): Path<String>,
+    Query(query): Query<AllowancesQuery>,
+) -> Result<Json<AllowancesResponse>, ApiError> {
+    let wallet = active_wallet(state.storage(), &user, &wallet_id)?;
+    parse_address(&query.token, "token")?;
+    let spenders = parse_spenders(&query.spenders)?;
+    let network = resolve_network(query.network.as_deref()).map_err(ApiError::bad_request)?;
+
+    let client = state.chain_client(&network).await.map_err(|e| {
+        ApiError::service_unavailable(format!("Failed to connect to blockchain: {e}"))
+    })?;
+    let decimals = client
+        .get_token_decimals(&query.token)
+        .await
+        .map_err(|e| ApiError::service_unavailable(format!("Failed to read token: {e}")))?;
+
+    let mut allowances = Vec::with_capacity(spenders.len());
+    for spender in spenders {
+        let amount = client
+            .get_token_allowance(&query.token, &wallet.public_address, &spender)
+            .await
+            .map_err(|e| ApiError::service_unavailable(format!("Failed to read allowance: {e}")))?;
+        allowances.push(Allowance {
+            spender,
+            amount_raw: amount.to_string(),
+            amount: format_amount(amount, decimals),
+            unlimited: is_unlimited_allowance(amount),
+        });
+    }
+
+    Ok(Json(AllowancesResponse {
+        wallet_id,
+        owner: wallet.public_address,
+        token: query.token,
+        network: network.id.to_string(),
+        decimals,
+        allowances,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::{AuthenticatedUser, Role};
+    use crate::storage::{WalletMetadata, WalletStatus};
+    use chrono::Utc;
+
+    fn auth(user_id: &str) -> Auth {
+        Auth(AuthenticatedUser {
+            user_id: user_id.to_string(),
+            role: Role::Client,
+            session_id: None,
+            issuer: "https://test.clerk.dev".to_string(),
+            expires_at: Utc::now().timestamp() + 3600,
+            tenant_id: None,
+        })
+    }
+
+    #[test]
+    fn allowance_amounts_accept_unlimited() {
+        assert_eq!(parse_allowance_amount("Unlimited", 6).unwrap(), U256::MAX);
+        assert_eq!(
+            parse_allowance_amount("1.5", 6).unwrap(),
+            U256::from(1_500_000u64)
+        );
+        assert_eq!(parse_allowance_amount("0", 6).unwrap(), U256::ZERO);
+        assert!(parse_allowance_amount("lots", 6).is_err());
+    }
+
+    #[tokio::test]
+    async fn invalid_spenders_are_rejected_before_reaching_the_chain() {
+        let state = AppState::default();
+        let wallet = WalletMetadata {
+            wallet_id: "w1".to_string(),
+            owner_user_id: "user-a".to_string(),
+            public_address: "0x1111111111111111111111111111111111111111".to_string(),
+            created_at: Utc::now(),
+            status: WalletStatus::Active,
+            label: None,
+            email_lookup_key: None,
+            email_sha256: None,
+            account_type: WalletAccountType::Eoa,
+            smart_account: None,
+            lock: None,
+            deleted_at: None,
+            purged_at: None,
+            funded_at: None,
+            tenant_id: None,
+        };
+        WalletRepository::new(state.storage())
+            .create(&wallet, b"test_key")
+            .unwrap();
+        let token = "0x5425890298aed601595a70AB815c96711a31Bc65";
+
+        let err = approve_token(
+            auth("user-a"),
+            State(state.clone()),
+            Path("w1".to_string()),
+            Json(ApproveRequest {
+                token: token.to_string(),
+                spender: "0x0000000000000000000000000000000000000000".to_string(),
+                amount: "unlimited".to_string(),
+                network: default_fuji(),
+                gas_limit: None,
+                max_priority_fee_per_gas: None,
+            }),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, axum::http::StatusCode::BAD_REQUEST);
+
+        let too_many = vec!["0x2222222222222222222222222222222222222222"; MAX_SPENDERS + 1];
+        for spenders in [String::from(" , "), too_many.join(",")] {
+            let err = get_allowances(
+                auth("user-a"),
+                State(state.clone()),
+                Path("w1".to_string()),
+                Query(AllowancesQuery {
+                    token: token.to_string(),
+                    spenders,
+                    network: None,
+                }),
+            )
+            .await
+            .unwrap_err();
+            assert_eq!(err.status, axum::http::StatusCode::BAD_REQUEST);
+        }
+
+        let err = get_allowances(
+            auth("user-b"),
+            State(state),
+            Path("w1".to_string()),
+            Query(AllowancesQuery {
+                token: token.to_string(),
+                spenders: "0x2222222222222222222222222222222222222222".to_string(),
+                network: None,
+            }),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, axum::http::StatusCode::FORBIDDEN);
+    }
+}
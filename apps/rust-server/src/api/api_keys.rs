@@ -0,0 +1,340 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Machine-to-machine API keys.
+//!
+//! Platform admins mint and revoke keys under `/v1/admin/api-keys` for
+//! integrations that cannot obtain a Clerk JWT (cron jobs, internal
+//! services). Clients send the key in the `X-Api-Key` header; the
+//! [`Auth`](crate::auth::Auth) extractor then acts as the key's principal
+//! with its role, and rejects requests outside the key's scopes. The
+//! plaintext key is returned once at creation; only its SHA-256 is stored.
+//!
+//! Signed admin routes still need a request signature, so a key can only
+//! call them if `ADMIN_SIGNING_KEYS` lists a key for its principal.
+
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::{
+    api::admin::require_platform_admin,
+    auth::{
+        extractor::API_KEY_ISSUER, tenant::is_valid_tenant_id, AdminOnly, AuthenticatedUser, Role,
+    },
+    error::{ApiError, StorageContext},
+    state::AppState,
+    storage::{
+        repository::api_keys::{api_key_sha256, generate_api_key, parse_scope, API_KEY_PREFIX},
+        ApiKeyRepository, AuditEvent, AuditEventType, AuditRepository, StorageError, StoredApiKey,
+    },
+};
+
+/// Longest key name.
+const MAX_NAME_LEN: usize = 64;
+/// Most scopes one key may carry.
+const MAX_SCOPES: usize = 32;
+/// Longest key lifetime.
+const MAX_EXPIRES_IN_DAYS: u32 = 365;
+
+/// Request body for minting an API key.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct CreateApiKeyRequest {
+    /// Label for operators, e.g. the integration using the key.
+    pub name: String,
+    /// Role the key acts with.
+    pub role: Role,
+    /// `{area}:{access}` entries. `area` is the first path segment under
+    /// `/v1` (`wallets`, `admin`, ...) or `*`; `access` is `read` (GET and
+    /// HEAD) or `write` (any method).
+    pub scopes: Vec<String>,
+    /// Tenant the key acts in; omitted for the default tenant.
+    #[serde(default)]
+    pub tenant_id: Option<String>,
+    /// Days until the key expires (1-365); omitted for no expiry.
+    #[serde(default)]
+    pub expires_in_days: Option<u32>,
+}
+
+/// An API key, without its secret.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ApiKeySummary {
+    pub key_id: String,
+    pub name: String,
+    /// Leading part of the key, to recognise it in client configuration.
+    pub prefix: String,
+    pub role: Role,
+    pub scopes: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tenant_id: Option<String>,
+    pub created_by: String,
+    pub created_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_used_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+impl From<StoredApiKey> for ApiKeySummary {
+    fn from(key: StoredApiKey) -> Self {
+        Self {
+            prefix: format!("{API_KEY_PREFIX}{}", key.key_id),
+            key_id: key.key_id,
+            name: key.name,
+            role: key.role,
+            scopes: key.scopes,
+            tenant_id: key.tenant_id,
+            created_by: key.created_by,
+            created_at: key.created_at,
+            expires_at: key.expires_at,
+            last_used_at: key.last_used_at,
+            revoked_at: key.revoked_at,
+        }
+    }
+}
+
+/// A freshly minted API key.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct CreateApiKeyResponse {
+    /// The full key. It is not stored and cannot be shown again.
+    pub api_key: String,
+    pub key: ApiKeySummary,
+}
+
+/// All API keys.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ApiKeyListResponse {
+    /// Keys, newest first, including revoked ones.
+    pub keys: Vec<ApiKeySummary>,
+    pub total: usize,
+}
+
+/// Keys are managed by people: a key cannot mint or revoke keys.
+fn require_human_admin(admin: &AuthenticatedUser) -> Result<(), ApiError> {
+    require_platform_admin(admin)?;
+    if admin.issuer == API_KEY_ISSUER {
+        return Err(
+            ApiError::forbidden("API keys cannot manage API keys").with_code("api_key_not_allowed")
+        );
+    }
+    Ok(())
+}
+
+/// Trimmed, deduplicated scopes; rejects unknown ones.
+fn normalize_scopes(scopes: &[String]) -> Result<Vec<String>, ApiError> {
+    let mut scopes: Vec<String> = scopes.iter().map(|s| s.trim().to_string()).collect();
+    scopes.sort();
+    scopes.dedup();
+    if scopes.is_empty() || scopes.len() > MAX_SCOPES {
+        return Err(ApiError::bad_request(format!(
+            "scopes must list 1-{MAX_SCOPES} entries"
+        )));
+    }
+    if let Some(invalid) = scopes.iter().find(|s| parse_scope(s).is_none()) {
+        return Err(ApiError::bad_request(format!(
+            "Invalid scope `{invalid}`: expected `<area>:read` or `<area>:write`"
+        )));
+    }
+    Ok(scopes)
+}
+
+fn log_key_event(
+    state: &AppState,
+    event_type: AuditEventType,
+    admin: &AuthenticatedUser,
+    key: &StoredApiKey,
+) {
+    let event = AuditEvent::new(event_type)
+        .with_user(&admin.user_id)
+        .with_resource("api_key", &key.key_id)
+        .with_details(serde_json::json!({
+            "name": key.name,
+            "role": key.role,
+            "scopes": key.scopes,
+            "tenant_id": key.tenant_id,
+            "expires_at": key.expires_at,
+        }));
+    let _ = AuditRepository::new(state.storage()).log(&event);
+}
+
+/// List API keys (platform admin only).
+#[utoipa::path(
+    get,
+    path = "/v1/admin/api-keys",
+    tag = "Admin",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "API keys", body = ApiKeyListResponse),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not authorized (platform admin required)")
+    )
+)]
+pub async fn list_api_keys(
+    AdminOnly(admin): AdminOnly,
+    State(state): State<AppState>,
+) -> Result<Json<ApiKeyListResponse>, ApiError> {
+    require_human_admin(&admin)?;
+    let keys: Vec<ApiKeySummary> = ApiKeyRepository::new(state.storage())
+        .list_all()
+        .context("Failed to list API keys")?
+        .into_iter()
+        .map(ApiKeySummary::from)
+        .collect();
+    Ok(Json(ApiKeyListResponse {
+        total: keys.len(),
+        keys,
+    }))
+}
+
+/// Mint an API key (platform admin only).
+///
+/// The response carries the full key; store it right away, it cannot be
+/// retrieved again.
+#[utoipa::path(
+    post,
+    path = "/v1/admin/api-keys",
+    tag = "Admin",
+    request_body = CreateApiKeyRequest,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Key minted", body = CreateApiKeyResponse),
+        (status = 400, description = "Invalid name, scopes, tenant or expiry"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not authorized (platform admin required)")
+    )
+)]
+pub async fn create_api_key(
+    AdminOnly(admin): AdminOnly,
+    State(state): State<AppState>,
+    Json(body): Json<CreateApiKeyRequest>,
+) -> Result<Json<CreateApiKeyResponse>, ApiError> {
+    require_human_admin(&admin)?;
+    let name = body.name.trim().to_string();
+    if name.is_empty() || name.chars().count() > MAX_NAME_LEN {
+        return Err(ApiError::bad_request(format!(
+            "name must be 1-{MAX_NAME_LEN} characters"
+        )));
+    }
+    let scopes = normalize_scopes(&body.scopes)?;
+    let tenant_id = body
+        .tenant_id
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty());
+    if let Some(invalid) = tenant_id.as_ref().filter(|t| !is_valid_tenant_id(t)) {
+        return Err(ApiError::bad_request(format!(
+            "Invalid tenant ID: {invalid}"
+        )));
+    }
+    if body
+        .expires_in_days
+        .is_some_and(|days| days == 0 || days > MAX_EXPIRES_IN_DAYS)
+    {
+        return Err(ApiError::bad_request(format!(
+            "expires_in_days must be between 1 and {MAX_EXPIRES_IN_DAYS}"
+        )));
+    }
+
+    let now = Utc::now();
+    let (key_id, api_key) = generate_api_key();
+    let key = StoredApiKey {
+        key_id,
+        name,
+        key_sha256: api_key_sha256(&api_key),
+        role: body.role,
+        scopes,
+        tenant_id,
+        created_by: admin.user_id.clone(),
+        created_at: now,
+        expires_at: body
+            .expires_in_days
+            .map(|days| now + Duration::days(i64::from(days))),
+        last_used_at: None,
+        revoked_at: None,
+    };
+    ApiKeyRepository::new(state.storage())
+        .save(&key)
+        .context("Failed to save API key")?;
+
+    log_key_event(&state, AuditEventType::ApiKeyCreated, &admin, &key);
+    Ok(Json(CreateApiKeyResponse {
+        api_key,
+        key: key.into(),
+    }))
+}
+
+/// Revoke an API key (platform admin only). Takes effect on the next request.
+#[utoipa::path(
+    delete,
+    path = "/v1/admin/api-keys/{key_id}",
+    tag = "Admin",
+    params(("key_id" = String, Path, description = "API key ID")),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Key revoked", body = ApiKeySummary),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not authorized (platform admin required)"),
+        (status = 404, description = "API key not found")
+    )
+)]
+pub async fn revoke_api_key(
+    AdminOnly(admin): AdminOnly,
+    State(state): State<AppState>,
+    Path(key_id): Path<String>,
+) -> Result<Json<ApiKeySummary>, ApiError> {
+    require_human_admin(&admin)?;
+    if !key_id.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(ApiError::not_found("API key not found"));
+    }
+    let key = match ApiKeyRepository::new(state.storage()).revoke(&key_id, Utc::now()) {
+        Ok(key) => key,
+        Err(StorageError::NotFound(_)) => return Err(ApiError::not_found("API key not found")),
+        Err(e) => return Err(ApiError::internal(format!("Failed to revoke API key: {e}"))),
+    };
+
+    log_key_event(&state, AuditEventType::ApiKeyRevoked, &admin, &key);
+    Ok(Json(key.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::StatusCode;
+
+    fn admin(issuer: &str, tenant_id: Option<&str>) -> AuthenticatedUser {
+        AuthenticatedUser {
+            user_id: "admin_1".to_string(),
+            role: Role::Admin,
+            session_id: None,
+            issuer: issuer.to_string(),
+            expires_at: 0,
+            tenant_id: tenant_id.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn only_platform_admins_with_a_session_manage_keys() {
+        assert!(require_human_admin(&admin("https://clerk.test", None)).is_ok());
+        let err = require_human_admin(&admin("https://clerk.test", Some("org_1"))).unwrap_err();
+        assert_eq!(err.status, StatusCode::FORBIDDEN);
+        let err = require_human_admin(&admin(API_KEY_ISSUER, None)).unwrap_err();
+        assert_eq!(err.status, StatusCode::FORBIDDEN);
+    }
+
+    #[test]
+    fn scopes_are_normalized_and_validated() {
+        let scopes = |s: &[&str]| s.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            normalize_scopes(&scopes(&[" wallets:read", "admin:write", "wallets:read"])).unwrap(),
+            scopes(&["admin:write", "wallets:read"])
+        );
+        assert!(normalize_scopes(&[]).is_err());
+        assert!(normalize_scopes(&scopes(&["wallets:all"])).is_err());
+    }
+}
@@ -0,0 +1,650 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Standing-order style auto top-up.
+//!
+//! A wallet owner pairs an authorized mandate with a rule: whenever the
+//! wallet's rEUR balance drops below a threshold, the fiat poller creates a
+//! mandate on-ramp for a fixed amount, at most `max_per_month` times per
+//! calendar month. Each run is recorded on the rule so clients can show it
+//! as a notification.
+
+use std::str::FromStr;
+use std::sync::Arc;
+
+use alloy::primitives::U256;
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+use utoipa::ToSchema;
+
+use crate::{
+    api::{
+        fiat::{
+            format_minor_eur, parse_amount_to_minor, parse_amount_to_token_minor_u256,
+            resolve_reur_contract_address,
+        },
+        fiat_mandates::execute_mandate_onramp,
+    },
+    audit_log,
+    auth::{Auth, AuthenticatedUser},
+    blockchain::AvaxClient,
+    error::{ApiError, StorageContext},
+    providers::clerk::ClerkClient,
+    state::AppState,
+    storage::{
+        AuditEventType, AutoTopUpEvent, AutoTopUpEventKind, AutoTopUpRepository, EncryptedStorage,
+        FiatMandateRepository, FiatMandateStatus, FiatRequestRepository, FiatRequestStatus,
+        OwnershipEnforcer, StorageError, StoredAutoTopUp, WalletMetadata, WalletRepository,
+        WalletStatus,
+    },
+};
+
+/// Upper bound for `max_per_month`.
+const MAX_TOPUPS_PER_MONTH: u32 = 31;
+
+/// Request body for configuring a wallet's auto top-up.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct AutoTopUpRequest {
+    /// Authorized mandate for this wallet that pays for top-ups.
+    pub mandate_id: String,
+    /// Top up when the rEUR balance is below this EUR amount.
+    pub threshold_eur: String,
+    /// EUR amount of each top-up.
+    pub topup_eur: String,
+    /// Top-ups allowed per calendar month (UTC), 1-31.
+    pub max_per_month: u32,
+    /// Set to `false` to pause the rule without deleting it.
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// Auto top-up rule returned to clients.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct AutoTopUpResponse {
+    pub wallet_id: String,
+    pub mandate_id: String,
+    pub threshold_eur: String,
+    pub topup_eur: String,
+    pub max_per_month: u32,
+    pub enabled: bool,
+    /// Top-ups already made this calendar month.
+    pub triggered_this_month: u32,
+    /// Recent activity, newest first.
+    pub events: Vec<AutoTopUpEvent>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Response for removing a rule.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct DeleteAutoTopUpResponse {
+    pub wallet_id: String,
+    pub deleted: bool,
+}
+
+fn to_response(rule: &StoredAutoTopUp) -> AutoTopUpResponse {
+    AutoTopUpResponse {
+        wallet_id: rule.wallet_id.clone(),
+        mandate_id: rule.mandate_id.clone(),
+        threshold_eur: rule.threshold_eur.clone(),
+        topup_eur: rule.topup_eur.clone(),
+        max_per_month: rule.max_per_month,
+        enabled: rule.enabled,
+        triggered_this_month: rule.triggered_in_month(Utc::now()),
+        events: rule.events.iter().rev().cloned().collect(),
+        created_at: rule.created_at,
+        updated_at: rule.updated_at,
+    }
+}
+
+fn load_owned_wallet(
+    state: &AppState,
+    user: &AuthenticatedUser,
+    wallet_id: &str,
+) -> Result<WalletMetadata, ApiError> {
+    let wallet = WalletRepository::new(state.storage()).get(wallet_id)?;
+    if !wallet.is_owned_by(user) {
+        return Err(ApiError::forbidden("You do not own this wallet").with_code("wallet_not_owned"));
+    }
+    if wallet.status == WalletStatus::Deleted {
+        return Err(ApiError::not_found("Wallet not found").with_code("wallet_not_found"));
+    }
+    Ok(wallet)
+}
+
+fn load_rule(state: &AppState, wallet_id: &str) -> Result<StoredAutoTopUp, ApiError> {
+    AutoTopUpRepository::new(state.storage())
+        .get(wallet_id)
+        .map_err(|e| match e {
+            StorageError::NotFound(_) => ApiError::not_found("No auto top-up configured"),
+            _ => ApiError::internal(format!("Failed to load auto top-up: {e}")),
+        })
+}
+
+/// Configure or replace a wallet's auto top-up.
+#[utoipa::path(
+    put,
+    path = "/v1/wallets/{wallet_id}/auto-topup",
+    tag = "Fiat",
+    params(("wallet_id" = String, Path, description = "Wallet ID")),
+    request_body = AutoTopUpRequest,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Auto top-up saved", body = AutoTopUpResponse),
+        (status = 400, description = "Bad request"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+        (status = 404, description = "Wallet or mandate not found"),
+        (status = 409, description = "Mandate is not authorized for this wallet"),
+        (status = 422, description = "Top-up amount exceeds the mandate's single-payment limit")
+    )
+)]
+pub async fn put_auto_topup(
+    Auth(user): Auth,
+    State(state): State<AppState>,
+    Path(wallet_id): Path<String>,
+    Json(request): Json<AutoTopUpRequest>,
+) -> Result<Json<AutoTopUpResponse>, ApiError> {
+    let wallet = load_owned_wallet(&state, &user, &wallet_id)?;
+    if wallet.status != WalletStatus::Active {
+        return Err(ApiError::forbidden(
+            "Wallet must be active for fiat requests",
+        ));
+    }
+
+    if !(1..=MAX_TOPUPS_PER_MONTH).contains(&request.max_per_month) {
+        return Err(ApiError::bad_request(format!(
+            "max_per_month must be between 1 and {MAX_TOPUPS_PER_MONTH}"
+        )));
+    }
+    let (_, threshold_minor) = parse_amount_to_minor(&request.threshold_eur)
+        .map_err(|_| ApiError::bad_request("threshold_eur must be a positive EUR amount"))?;
+    let (_, topup_minor) = parse_amount_to_minor(&request.topup_eur)
+        .map_err(|_| ApiError::bad_request("topup_eur must be a positive EUR amount"))?;
+
+    let storage = state.storage();
+    let mandate = FiatMandateRepository::new(storage)
+        .get(&request.mandate_id)
+        .map_err(|_| ApiError::not_found("Mandate not found"))?;
+    if mandate.owner_user_id != user.user_id {
+        return Err(ApiError::forbidden(
+            "You do not have permission to access this mandate",
+        ));
+    }
+    if mandate.wallet_id != wallet_id {
+        return Err(ApiError::conflict("Mandate belongs to a different wallet"));
+    }
+    if mandate.status != FiatMandateStatus::Authorized {
+        return Err(ApiError::conflict("Mandate is not authorized"));
+    }
+    if topup_minor > mandate.max_single_payment_minor {
+        return Err(ApiError::unprocessable(format!(
+            "topup_eur exceeds the mandate's single-payment limit of {} EUR",
+            format_minor_eur(mandate.max_single_payment_minor)
+        )));
+    }
+
+    let repo = AutoTopUpRepository::new(storage);
+    let now = Utc::now();
+    let existing = repo.get(&wallet_id).ok();
+    let rule = StoredAutoTopUp {
+        wallet_id: wallet_id.clone(),
+        owner_user_id: user.user_id.clone(),
+        mandate_id: request.mandate_id,
+        threshold_eur: format_minor_eur(threshold_minor),
+        topup_eur: format_minor_eur(topup_minor),
+        max_per_month: request.max_per_month,
+        enabled: request.enabled,
+        events: existing
+            .as_ref()
+            .map(|r| r.events.clone())
+            .unwrap_or_default(),
+        created_at: existing.as_ref().map(|r| r.created_at).unwrap_or(now),
+        updated_at: now,
+    };
+    repo.save(&rule)
+        .map_err(|e| ApiError::internal(format!("Failed to store auto top-up: {e}")))?;
+
+    audit_log!(
+        storage,
+        AuditEventType::FiatAutoTopUpConfigured,
+        &user,
+        "wallet",
+        &wallet_id
+    );
+
+    Ok(Json(to_response(&rule)))
+}
+
+/// Get a wallet's auto top-up and its recent activity.
+#[utoipa::path(
+    get,
+    path = "/v1/wallets/{wallet_id}/auto-topup",
+    tag = "Fiat",
+    params(("wallet_id" = String, Path, description = "Wallet ID")),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Auto top-up rule", body = AutoTopUpResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+        (status = 404, description = "Wallet not found or no rule configured")
+    )
+)]
+pub async fn get_auto_topup(
+    Auth(user): Auth,
+    State(state): State<AppState>,
+    Path(wallet_id): Path<String>,
+) -> Result<Json<AutoTopUpResponse>, ApiError> {
+    load_owned_wallet(&state, &user, &wallet_id)?;
+    let rule = load_rule(&state, &wallet_id)?;
+    Ok(Json(to_response(&rule)))
+}
+
+/// Remove a wallet's auto top-up.
+#[utoipa::path(
+    delete,
+    path = "/v1/wallets/{wallet_id}/auto-topup",
+    tag = "Fiat",
+    params(("wallet_id" = String, Path, description = "Wallet ID")),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Auto top-up removed", body = DeleteAutoTopUpResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+        (status = 404, description = "Wallet not found or no rule configured")
+    )
+)]
+pub async fn delete_auto_topup(
+    Auth(user): Auth,
+    State(state): State<AppState>,
+    Path(wallet_id): Path<String>,
+) -> Result<(StatusCode, Json<DeleteAutoTopUpResponse>), ApiError> {
+    load_owned_wallet(&state, &user, &wallet_id)?;
+    load_rule(&state, &wallet_id)?;
+    let storage = state.storage();
+    AutoTopUpRepository::new(storage)
+        .delete(&wallet_id)
+        .context("Failed to remove auto top-up")?;
+
+    audit_log!(
+        storage,
+        AuditEventType::FiatAutoTopUpRemoved,
+        &user,
+        "wallet",
+        &wallet_id
+    );
+
+    Ok((
+        StatusCode::OK,
+        Json(DeleteAutoTopUpResponse {
+            wallet_id,
+            deleted: true,
+        }),
+    ))
+}
+
+/// Outcome of the checks that run before a rule's balance is read.
+#[derive(Debug, PartialEq, Eq)]
+enum Precheck {
+    /// The previous top-up has not finished yet.
+    PreviousPending,
+    /// The mandate can no longer be used; the rule is paused.
+    MandateUnusable,
+    /// Read the balance.
+    Ready,
+}
+
+fn precheck(
+    last_request_status: Option<FiatRequestStatus>,
+    mandate_status: Option<FiatMandateStatus>,
+) -> Precheck {
+    let previous_done = matches!(
+        last_request_status,
+        None | Some(FiatRequestStatus::Completed | FiatRequestStatus::Failed)
+    );
+    if !previous_done {
+        return Precheck::PreviousPending;
+    }
+    if mandate_status != Some(FiatMandateStatus::Authorized) {
+        return Precheck::MandateUnusable;
+    }
+    Precheck::Ready
+}
+
+/// Evaluate every enabled auto top-up rule and create on-ramps for wallets
+/// below their threshold. Returns the number of on-ramps created.
+pub(crate) async fn run_auto_topups(
+    storage: &Arc<EncryptedStorage>,
+    clerk: Option<&ClerkClient>,
+) -> usize {
+    let repo = AutoTopUpRepository::new(storage);
+    let rules = match repo.list_all() {
+        Ok(rules) => rules,
+        Err(e) => {
+            warn!(error = %e, "Auto top-up: failed to list rules");
+            return 0;
+        }
+    };
+    let rules: Vec<StoredAutoTopUp> = rules.into_iter().filter(|r| r.enabled).collect();
+    if rules.is_empty() {
+        return 0;
+    }
+
+    let reur = match resolve_reur_contract_address() {
+        Ok(address) => address,
+        Err(e) => {
+            warn!(error = %e.message, "Auto top-up: rEUR contract not configured");
+            return 0;
+        }
+    };
+    let mut client: Option<AvaxClient> = None;
+    let mut created = 0;
+
+    for mut rule in rules {
+        let last_status = rule.last_request_id().and_then(|id| {
+            FiatRequestRepository::new(storage)
+                .get(id)
+                .ok()
+                .map(|r| r.status)
+        });
+        let mandate = FiatMandateRepository::new(storage)
+            .get(&rule.mandate_id)
+            .ok();
+        let now = Utc::now();
+
+        match precheck(last_status, mandate.as_ref().map(|m| m.status)) {
+            Precheck::PreviousPending => continue,
+            Precheck::MandateUnusable => {
+                rule.enabled = false;
+                rule.updated_at = now;
+                rule.record_event(
+                    AutoTopUpEventKind::Failed,
+                    None,
+                    "Auto top-up paused: the mandate is no longer authorized".to_string(),
+                    now,
+                );
+                if let Err(e) = repo.save(&rule) {
+                    warn!(wallet_id = %rule.wallet_id, error = %e, "Auto top-up: failed to save rule");
+                }
+                continue;
+            }
+            Precheck::Ready => {}
+        }
+        let Some(mandate) = mandate else { continue };
+
+        let Ok(wallet) = WalletRepository::new(storage).get(&rule.wallet_id) else {
+            continue;
+        };
+        if wallet.status != WalletStatus::Active {
+            continue;
+        }
+
+        if client.is_none() {
+            match AvaxClient::fuji().await {
+                Ok(c) => client = Some(c),
+                Err(e) => {
+                    warn!(error = %e, "Auto top-up: failed to connect to chain");
+                    return created;
+                }
+            }
+        }
+        let Some(chain) = client.as_ref() else {
+            return created;
+        };
+        let balance = match chain.get_token_balance(&wallet.public_address, &reur).await {
+            Ok(balance) => balance,
+            Err(e) => {
+                warn!(wallet_id = %rule.wallet_id, error = %e, "Auto top-up: failed to read balance");
+                continue;
+            }
+        };
+        let (Ok(balance_raw), Ok(threshold_raw)) = (
+            U256::from_str(&balance.balance_raw),
+            parse_amount_to_token_minor_u256(&rule.threshold_eur),
+        ) else {
+            continue;
+        };
+        if balance_raw >= threshold_raw {
+            continue;
+        }
+
+        if rule.triggered_in_month(now) >= rule.max_per_month {
+            rule.record_event(
+                AutoTopUpEventKind::MonthlyCapReached,
+                None,
+                format!(
+                    "Balance is below {} EUR but this month's {} top-ups are used",
+                    rule.threshold_eur, rule.max_per_month
+                ),
+                now,
+            );
+        } else {
+            match execute_mandate_onramp(
+                storage,
+                clerk,
+                &mandate,
+                &rule.topup_eur,
+                Some("Auto top-up".to_string()),
+            )
+            .await
+            {
+                Ok(record) => {
+                    info!(
+                        wallet_id = %rule.wallet_id,
+                        request_id = %record.request_id,
+                        amount_eur = %record.amount_eur,
+                        "Auto top-up: created on-ramp"
+                    );
+                    rule.record_event(
+                        AutoTopUpEventKind::Triggered,
+                        Some(record.request_id),
+                        format!(
+                            "Topping up {} EUR: balance fell below {} EUR",
+                            rule.topup_eur, rule.threshold_eur
+                        ),
+                        now,
+                    );
+                    created += 1;
+                }
+                Err(e) => {
+                    warn!(wallet_id = %rule.wallet_id, error = %e.message, "Auto top-up: on-ramp failed");
+                    rule.record_event(AutoTopUpEventKind::Failed, None, e.message, now);
+                }
+            }
+        }
+        rule.updated_at = now;
+        if let Err(e) = repo.save(&rule) {
+            warn!(wallet_id = %rule.wallet_id, error = %e, "Auto top-up: failed to save rule");
+        }
+    }
+
+    created
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::{AuthenticatedUser, Role};
+    use crate::storage::StoredFiatMandate;
+
+    fn user(user_id: &str) -> Auth {
+        Auth(AuthenticatedUser {
+            user_id: user_id.to_string(),
+            role: Role::Client,
+            session_id: None,
+            issuer: "https://test.clerk.dev".into(),
+            expires_at: Utc::now().timestamp() + 3600,
+            tenant_id: None,
+        })
+    }
+
+    fn seed(state: &AppState, mandate_status: FiatMandateStatus) {
+        let now = Utc::now();
+        WalletRepository::new(state.storage())
+            .create(
+                &WalletMetadata {
+                    wallet_id: "wallet-1".to_string(),
+                    owner_user_id: "user-1".to_string(),
+                    public_address: "0x0000000000000000000000000000000000000001".to_string(),
+                    created_at: now,
+                    status: WalletStatus::Active,
+                    label: None,
+                    email_lookup_key: None,
+                    email_sha256: None,
+                    account_type: Default::default(),
+                    smart_account: None,
+                    lock: None,
+                    deleted_at: None,
+                    purged_at: None,
+                    funded_at: None,
+                    tenant_id: None,
+                },
+                b"test_key",
+            )
+            .unwrap();
+        FiatMandateRepository::new(state.storage())
+            .create(&StoredFiatMandate {
+                mandate_id: "m-1".to_string(),
+                owner_user_id: "user-1".to_string(),
+                wallet_id: "wallet-1".to_string(),
+                provider: "truelayer_sandbox".to_string(),
+                provider_mandate_id: Some("tl-m-1".to_string()),
+                provider_action_url: None,
+                status: mandate_status,
+                max_single_payment_minor: 10_000,
+                max_monthly_minor: 50_000,
+                failure_reason: None,
+                created_at: now,
+                updated_at: now,
+                revoked_at: None,
+            })
+            .unwrap();
+    }
+
+    fn body(topup_eur: &str) -> AutoTopUpRequest {
+        AutoTopUpRequest {
+            mandate_id: "m-1".to_string(),
+            threshold_eur: "20".to_string(),
+            topup_eur: topup_eur.to_string(),
+            max_per_month: 2,
+            enabled: true,
+        }
+    }
+
+    #[test]
+    fn precheck_waits_for_previous_topup_and_authorized_mandate() {
+        assert_eq!(
+            precheck(
+                Some(FiatRequestStatus::AwaitingProvider),
+                Some(FiatMandateStatus::Authorized)
+            ),
+            Precheck::PreviousPending
+        );
+        assert_eq!(
+            precheck(
+                Some(FiatRequestStatus::Completed),
+                Some(FiatMandateStatus::Revoked)
+            ),
+            Precheck::MandateUnusable
+        );
+        assert_eq!(precheck(None, None), Precheck::MandateUnusable);
+        assert_eq!(
+            precheck(
+                Some(FiatRequestStatus::Failed),
+                Some(FiatMandateStatus::Authorized)
+            ),
+            Precheck::Ready
+        );
+    }
+
+    #[tokio::test]
+    async fn put_normalizes_amounts_and_keeps_history() {
+        let state = AppState::default();
+        seed(&state, FiatMandateStatus::Authorized);
+
+        let Json(saved) = put_auto_topup(
+            user("user-1"),
+            State(state.clone()),
+            Path("wallet-1".into()),
+            Json(body("50")),
+        )
+        .await
+        .unwrap();
+        assert_eq!(saved.threshold_eur, "20.00");
+        assert_eq!(saved.topup_eur, "50.00");
+
+        let repo = AutoTopUpRepository::new(state.storage());
+        let mut stored = repo.get("wallet-1").unwrap();
+        stored.record_event(
+            AutoTopUpEventKind::Triggered,
+            Some("req-1".into()),
+            "x".into(),
+            Utc::now(),
+        );
+        repo.save(&stored).unwrap();
+
+        let Json(updated) = put_auto_topup(
+            user("user-1"),
+            State(state.clone()),
+            Path("wallet-1".into()),
+            Json(body("60")),
+        )
+        .await
+        .unwrap();
+        assert_eq!(updated.triggered_this_month, 1);
+        assert_eq!(updated.created_at, saved.created_at);
+    }
+
+    #[tokio::test]
+    async fn put_rejects_unusable_mandates_and_foreign_wallets() {
+        let state = AppState::default();
+        seed(&state, FiatMandateStatus::AuthorizationRequired);
+
+        let err = put_auto_topup(
+            user("user-1"),
+            State(state.clone()),
+            Path("wallet-1".into()),
+            Json(body("50")),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::CONFLICT);
+
+        let err = put_auto_topup(
+            user("user-2"),
+            State(state.clone()),
+            Path("wallet-1".into()),
+            Json(body("50")),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn put_rejects_topup_above_single_payment_limit() {
+        let state = AppState::default();
+        seed(&state, FiatMandateStatus::Authorized);
+
+        let err = put_auto_topup(
+            user("user-1"),
+            State(state),
+            Path("wallet-1".into()),
+            Json(body("150")),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::UNPROCESSABLE_ENTITY);
+    }
+}
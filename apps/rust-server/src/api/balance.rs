@@ -3,26 +3,33 @@
 // Copyright (C) 2026 Relational Network
 
 //! Blockchain balance query endpoints.
+//!
+//! Besides the current balance, `GET /v1/wallets/{wallet_id}/balance/at`
+//! reads a wallet's balance as of a past block or time, for dispute
+//! resolution and statement checks (see [`crate::blockchain::history`]).
 
 use axum::{
     extract::{Path, Query, State},
     Json,
 };
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use utoipa::{IntoParams, ToSchema};
 
 use crate::{
-    auth::Auth,
-    blockchain::{ensure_fuji_network, AvaxClient, WalletBalanceResponse, REUR_TOKEN},
+    auth::{Auth, AuthenticatedUser},
+    blockchain::{
+        client::AvaxClientError, history, resolve_network, NetworkConfig, WalletBalanceResponse,
+    },
     error::ApiError,
     state::AppState,
-    storage::{WalletRepository, WalletStatus},
+    storage::{OwnershipEnforcer, WalletMetadata, WalletRepository, WalletStatus},
 };
 
 /// Query parameters for balance request.
 #[derive(Debug, Deserialize, IntoParams)]
 pub struct BalanceQuery {
-    /// Network to query. Only "fuji" is supported.
+    /// Network to query, by registry ID (see `EVM_NETWORKS`).
     #[param(default = "fuji")]
     pub network: Option<String>,
     /// Additional token contract addresses to query (comma-separated)
@@ -39,6 +46,58 @@ pub struct BalanceResponse {
     pub balance: WalletBalanceResponse,
 }
 
+/// Query parameters for a balance as of a past block.
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct HistoricalBalanceQuery {
+    /// Network to query, by registry ID (see `EVM_NETWORKS`).
+    #[param(default = "fuji")]
+    pub network: Option<String>,
+    /// Block number to read the balance at.
+    pub block: Option<u64>,
+    /// Time to read the balance at (RFC 3339); resolves to the last block
+    /// mined at or before it. Give either `block` or `timestamp`.
+    pub timestamp: Option<DateTime<Utc>>,
+}
+
+/// Balance as of a past block.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct HistoricalBalanceResponse {
+    /// Wallet ID
+    pub wallet_id: String,
+    /// Block the balance was read at
+    pub block_number: u64,
+    /// When that block was mined
+    pub block_timestamp: DateTime<Utc>,
+    /// Balance information
+    #[serde(flatten)]
+    pub balance: WalletBalanceResponse,
+}
+
+/// Load a wallet the caller owns and may query.
+fn queryable_wallet(
+    state: &AppState,
+    user: &AuthenticatedUser,
+    wallet_id: &str,
+) -> Result<WalletMetadata, ApiError> {
+    let wallet = WalletRepository::new(state.storage()).get(wallet_id)?;
+
+    // Verify ownership
+    if !wallet.is_owned_by(user) {
+        return Err(ApiError::forbidden("You do not own this wallet").with_code("wallet_not_owned"));
+    }
+
+    // Check wallet status
+    if wallet.status == WalletStatus::Deleted {
+        return Err(ApiError::not_found("Wallet has been deleted").with_code("wallet_deleted"));
+    }
+
+    if wallet.status == WalletStatus::Suspended {
+        return Err(ApiError::forbidden("Wallet is suspended").with_code("wallet_suspended"));
+    }
+
+    Ok(wallet)
+}
+
 /// Get the balance of a wallet on the Avalanche C-Chain.
 ///
 /// Returns native AVAX balance and any configured ERC-20 token balances.
@@ -65,60 +124,158 @@ pub async fn get_wallet_balance(
     Path(wallet_id): Path<String>,
     Query(query): Query<BalanceQuery>,
 ) -> Result<Json<BalanceResponse>, ApiError> {
-    // Get wallet from storage
-    let storage = state.storage();
-    let wallet_repo = WalletRepository::new(storage);
-    let wallet = wallet_repo.get(&wallet_id).map_err(|e| match e {
-        crate::storage::StorageError::NotFound(_) => ApiError::not_found("Wallet not found"),
-        _ => ApiError::internal(format!("Failed to access storage: {}", e)),
-    })?;
+    let wallet = queryable_wallet(&state, &user, &wallet_id)?;
+    let network = resolve_network(query.network.as_deref()).map_err(ApiError::bad_request)?;
 
-    // Verify ownership
-    if wallet.owner_user_id != user.user_id {
-        return Err(ApiError::forbidden("You do not own this wallet"));
-    }
+    let balance = fetch_address_balance(
+        &state,
+        &network,
+        &wallet.public_address,
+        query.tokens.as_deref(),
+    )
+    .await?;
 
-    // Check wallet status
-    if wallet.status == WalletStatus::Deleted {
-        return Err(ApiError::not_found("Wallet has been deleted"));
-    }
+    Ok(Json(BalanceResponse {
+        wallet_id: wallet.wallet_id,
+        balance,
+    }))
+}
 
-    if wallet.status == WalletStatus::Suspended {
-        return Err(ApiError::forbidden("Wallet is suspended"));
-    }
+fn historical_state_error(e: AvaxClientError) -> ApiError {
+    ApiError::service_unavailable(format!("Failed to read historical state: {}", e))
+        .with_code("historical_state_unavailable")
+}
+
+/// Get the balance of a wallet as of a past block or time.
+///
+/// Covers the native token and the tokens listed on the network at the time
+/// of the request. Reading old blocks needs an archive RPC; when the node
+/// has pruned the state the request fails with `503` and code
+/// `historical_state_unavailable`. Balances at final blocks are cached.
+#[utoipa::path(
+    get,
+    path = "/v1/wallets/{wallet_id}/balance/at",
+    tag = "Wallets",
+    params(
+        ("wallet_id" = String, Path, description = "Wallet ID"),
+        HistoricalBalanceQuery
+    ),
+    security(("bearer" = [])),
+    responses(
+        (status = 200, description = "Balance at the block", body = HistoricalBalanceResponse),
+        (status = 400, description = "Neither or both of block and timestamp, a future block or time, or a time before the first block"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - not wallet owner"),
+        (status = 404, description = "Wallet not found"),
+        (status = 503, description = "Blockchain network or historical state unavailable")
+    )
+)]
+pub async fn get_wallet_balance_at(
+    Auth(user): Auth,
+    State(state): State<AppState>,
+    Path(wallet_id): Path<String>,
+    Query(query): Query<HistoricalBalanceQuery>,
+) -> Result<Json<HistoricalBalanceResponse>, ApiError> {
+    let wallet = queryable_wallet(&state, &user, &wallet_id)?;
+    let network = resolve_network(query.network.as_deref()).map_err(ApiError::bad_request)?;
 
-    ensure_fuji_network(query.network.as_deref()).map_err(ApiError::bad_request)?;
-
-    // Use shared client from AppState (connection pool reuse), fall back to per-request
-    let owned_client;
-    let client = if let Some(ref shared) = state.avax_client {
-        shared.as_ref()
-    } else {
-        owned_client = AvaxClient::fuji().await.map_err(|e| {
-            ApiError::service_unavailable(format!("Failed to connect to blockchain: {}", e))
-        })?;
-        &owned_client
+    let client = state.chain_client(&network).await.map_err(|e| {
+        ApiError::service_unavailable(format!("Failed to connect to blockchain: {}", e))
+    })?;
+    let head = client.get_block_number().await.map_err(|e| {
+        ApiError::service_unavailable(format!("Failed to read the latest block: {}", e))
+    })?;
+    let block = match (query.block, query.timestamp) {
+        (Some(block), None) if block <= head => block,
+        (Some(block), None) => {
+            return Err(ApiError::bad_request(format!(
+                "Block {block} is after the latest block {head}"
+            )))
+        }
+        (None, Some(timestamp)) => {
+            if timestamp > Utc::now() {
+                return Err(ApiError::bad_request("timestamp is in the future"));
+            }
+            let seconds = u64::try_from(timestamp.timestamp()).unwrap_or(0);
+            history::last_block_at(seconds, head, |number| {
+                history::block_timestamp(&client, network.id, number, head)
+            })
+            .await
+            .map_err(historical_state_error)?
+            .ok_or_else(|| ApiError::bad_request("timestamp is before the first block"))?
+        }
+        _ => {
+            return Err(ApiError::bad_request(
+                "Give exactly one of block or timestamp",
+            ))
+        }
     };
 
-    // Build list of token addresses to query
-    let mut token_addresses: Vec<&str> = Vec::new();
+    let block_time = history::block_timestamp(&client, network.id, block, head)
+        .await
+        .map_err(historical_state_error)?;
+    let listed: Vec<String> = crate::blockchain::tokens()
+        .on_network(network.id)
+        .into_iter()
+        .map(|token| token.address)
+        .collect();
+    let token_addresses: Vec<&str> = listed.iter().map(String::as_str).collect();
+    let balance = history::balances_at(
+        &client,
+        network.id,
+        &wallet.public_address,
+        &token_addresses,
+        block,
+        head,
+    )
+    .await
+    .map_err(historical_state_error)?;
 
-    // Add known tokens for Fuji.
-    if let Some(addr) = REUR_TOKEN.fuji_address {
-        token_addresses.push(addr);
-    }
+    Ok(Json(HistoricalBalanceResponse {
+        wallet_id: wallet.wallet_id,
+        block_number: block,
+        block_timestamp: i64::try_from(block_time)
+            .ok()
+            .and_then(|s| DateTime::from_timestamp(s, 0))
+            .unwrap_or_default(),
+        balance,
+    }))
+}
+
+/// Query native, listed token and any extra token balances for an address
+/// on `network`. Listed tokens are those in the
+/// [token registry](crate::blockchain::tokens) for the network.
+///
+/// `tokens` is the comma-separated list from [`BalanceQuery::tokens`].
+/// Shared with the watch-only balance endpoint.
+pub(crate) async fn fetch_address_balance(
+    state: &AppState,
+    network: &NetworkConfig,
+    address: &str,
+    tokens: Option<&str>,
+) -> Result<WalletBalanceResponse, ApiError> {
+    // Shared client on Fuji (connection pool reuse), per-request elsewhere
+    let client = state.chain_client(network).await.map_err(|e| {
+        ApiError::service_unavailable(format!("Failed to connect to blockchain: {}", e))
+    })?;
+
+    // Tokens listed on this network in the token registry
+    let listed: Vec<String> = crate::blockchain::tokens()
+        .on_network(network.id)
+        .into_iter()
+        .map(|token| token.address)
+        .collect();
+    let token_addresses: Vec<&str> = listed.iter().map(String::as_str).collect();
 
     // Add any custom token addresses from query
     // Note: We can't easily add runtime strings to a Vec<&str>, so we'll handle this separately
-    let custom_tokens: Vec<String> = query
-        .tokens
-        .as_deref()
+    let custom_tokens: Vec<String> = tokens
         .map(|t| t.split(',').map(|s| s.trim().to_string()).collect())
         .unwrap_or_default();
 
     // Query balances
     let balance = client
-        .get_wallet_balances(&wallet.public_address, &token_addresses)
+        .get_wallet_balances(address, &token_addresses)
         .await
         .map_err(|e| ApiError::service_unavailable(format!("Failed to query balance: {}", e)))?;
 
@@ -126,10 +283,7 @@ pub async fn get_wallet_balance(
     let mut final_balance = balance;
     for token_addr in custom_tokens {
         if token_addr.starts_with("0x") {
-            match client
-                .get_token_balance(&wallet.public_address, &token_addr)
-                .await
-            {
+            match client.get_token_balance(address, &token_addr).await {
                 Ok(token_balance) => {
                     final_balance.token_balances.push(token_balance);
                 }
@@ -141,10 +295,7 @@ pub async fn get_wallet_balance(
         }
     }
 
-    Ok(Json(BalanceResponse {
-        wallet_id: wallet.wallet_id,
-        balance: final_balance,
-    }))
+    Ok(final_balance)
 }
 
 #[cfg(test)]
@@ -160,4 +311,67 @@ mod tests {
         assert!(query.network.is_none());
         assert!(query.tokens.is_none());
     }
+
+    #[tokio::test]
+    async fn historical_balance_checks_ownership_and_network() {
+        let state = AppState::default();
+        WalletRepository::new(state.storage())
+            .create(
+                &WalletMetadata {
+                    wallet_id: "wallet-1".to_string(),
+                    owner_user_id: "user-1".to_string(),
+                    public_address: "0x0000000000000000000000000000000000000001".to_string(),
+                    created_at: Utc::now(),
+                    status: WalletStatus::Active,
+                    label: None,
+                    email_lookup_key: None,
+                    email_sha256: None,
+                    account_type: Default::default(),
+                    smart_account: None,
+                    lock: None,
+                    deleted_at: None,
+                    purged_at: None,
+                    funded_at: None,
+                    tenant_id: None,
+                },
+                b"test_key",
+            )
+            .unwrap();
+        let user = |user_id: &str| {
+            Auth(AuthenticatedUser {
+                user_id: user_id.to_string(),
+                role: crate::auth::Role::Client,
+                session_id: None,
+                issuer: "https://test.clerk.dev".to_string(),
+                expires_at: Utc::now().timestamp() + 3600,
+                tenant_id: None,
+            })
+        };
+        let query = |network: Option<&str>| {
+            Query(HistoricalBalanceQuery {
+                network: network.map(str::to_string),
+                block: Some(1),
+                timestamp: None,
+            })
+        };
+
+        let err = get_wallet_balance_at(
+            user("user-2"),
+            State(state.clone()),
+            Path("wallet-1".to_string()),
+            query(None),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, axum::http::StatusCode::FORBIDDEN);
+        let err = get_wallet_balance_at(
+            user("user-1"),
+            State(state),
+            Path("wallet-1".to_string()),
+            query(Some("mainnet")),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, axum::http::StatusCode::BAD_REQUEST);
+    }
 }
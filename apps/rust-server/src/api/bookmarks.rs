@@ -12,9 +12,10 @@ use serde::Deserialize;
 use utoipa::IntoParams;
 
 use crate::{
+    api::usage::{check_quota, QuotaKind, StorageQuotas},
     audit_log,
     auth::Auth,
-    error::ApiError,
+    error::{ApiError, StorageContext},
     models::{Bookmark, CreateBookmarkRequest, WalletAddress},
     providers::email,
     state::AppState,
@@ -109,7 +110,8 @@ pub async fn list_bookmarks(
     responses(
         (status = 201, body = Bookmark),
         (status = 401, description = "Unauthorized"),
-        (status = 403, description = "Forbidden - not your wallet")
+        (status = 403, description = "Forbidden - not your wallet"),
+        (status = 422, description = "Storage quota exceeded")
     )
 )]
 pub async fn create_bookmark(
@@ -186,6 +188,15 @@ pub async fn create_bookmark(
         created_at: Utc::now(),
     };
 
+    let bytes = serde_json::to_vec(&stored).map_or(0, |b| b.len() as u64);
+    check_quota(
+        storage,
+        &user.user_id,
+        QuotaKind::Bookmarks,
+        bytes,
+        &StorageQuotas::from_env(),
+    )?;
+
     let repo = BookmarkRepository::new(storage);
     repo.create(&stored)
         .map_err(|e| ApiError::internal(format!("Failed to create bookmark: {}", e)))?;
@@ -256,7 +267,7 @@ pub async fn delete_bookmark(
 
     // Delete bookmark
     repo.delete(&bookmark_id)
-        .map_err(|e| ApiError::internal(format!("Failed to delete bookmark: {}", e)))?;
+        .context("Failed to delete bookmark")?;
 
     // Audit log
     audit_log!(
@@ -291,6 +302,7 @@ mod tests {
             session_id: None,
             issuer: "test".to_string(),
             expires_at: 0,
+            tenant_id: None,
         };
 
         (temp, state, user)
@@ -307,6 +319,13 @@ mod tests {
             label: None,
             email_lookup_key: None,
             email_sha256: None,
+            account_type: Default::default(),
+            smart_account: None,
+            lock: None,
+            deleted_at: None,
+            purged_at: None,
+            funded_at: None,
+            tenant_id: None,
         };
         let repo = WalletRepository::new(storage);
         repo.create(&metadata, b"test_key").unwrap();
@@ -0,0 +1,881 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Cross-chain USDC transfers between Avalanche and one remote chain.
+//!
+//! Transfers use CCTP burn-and-mint (see [`crate::blockchain::bridge`]):
+//!
+//! ```text
+//!  initiate ──▶ burning ──receipt──▶ attesting ──attestation──▶ minting ──receipt──▶ completed
+//!                  │                                               │
+//!                  └──────────────── reverted ────────────────────┴──▶ failed
+//! ```
+//!
+//! Initiating approves the token messenger if needed and broadcasts the
+//! burn. There is no background worker: fetching a transfer moves it along
+//! as far as the chains and the attestation service allow, and submits the
+//! mint from the same wallet key on the destination chain. The wallet
+//! therefore needs native gas on both chains.
+//!
+//! Both legs live on one record; the Avalanche leg also appears in the
+//! wallet's transaction history.
+
+use std::str::FromStr;
+use std::sync::Mutex;
+
+use alloy::{
+    primitives::{Address, U256},
+    sol_types::SolCall,
+};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::claims::{active_wallet, record_transfer};
+use super::wallets::ensure_unlocked;
+use crate::{
+    api::limits,
+    auth::{Auth, AuthenticatedUser},
+    blockchain::{
+        bridge::{
+            encode_deposit_for_burn, encode_receive_message, BridgeConfig, ChainEndpoint,
+            USDC_DECIMALS,
+        },
+        client::AvaxClientError,
+        erc20::IERC20,
+        format_amount, parse_amount,
+        transactions::SendResult,
+        wallet_from_pem, AvaxClient, TxBuilder,
+    },
+    error::{ApiError, StorageContext},
+    providers::cctp::AttestationClient,
+    state::AppState,
+    storage::{
+        AuditEvent, AuditEventType, AuditRepository, BridgeDirection, BridgeRepository,
+        BridgeStatus, EncryptedStorage, StoredBridgeTransfer, TokenType, WalletAccountType,
+        WalletMetadata, WalletRepository,
+    },
+};
+
+/// Gas limit for a burn sent right behind its `approve`, which cannot be
+/// estimated until the approval is mined.
+const BURN_GAS_LIMIT: u64 = 250_000;
+/// Rough time from burn to mint when leaving Avalanche, which finalizes in
+/// seconds.
+const AVALANCHE_ESTIMATE_SECS: u64 = 60;
+/// Rough time from burn to mint when leaving the remote chain; CCTP waits
+/// for hard finality, which takes about 15 minutes on Ethereum.
+const REMOTE_ESTIMATE_SECS: u64 = 1200;
+/// A mint claimed this long ago without a transaction hash is assumed lost
+/// (e.g. the process restarted while sending) and is retried.
+const MINT_CLAIM_TIMEOUT_SECS: i64 = 300;
+
+/// Serializes status transitions.
+static BRIDGE_LOCK: Mutex<()> = Mutex::new(());
+
+// =============================================================================
+// Request/Response Types
+// =============================================================================
+
+/// Request to price a bridge transfer.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct BridgeQuoteRequest {
+    /// USDC amount in human-readable units (e.g. "25.00").
+    pub amount: String,
+    /// `outbound` (Avalanche to the remote chain, default) or `inbound`.
+    #[serde(default)]
+    pub direction: BridgeDirection,
+}
+
+/// Price and preconditions of a bridge transfer.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct BridgeQuoteResponse {
+    pub direction: BridgeDirection,
+    pub source_chain: String,
+    pub destination_chain: String,
+    pub amount: String,
+    /// Amount minted on the destination chain. CCTP charges no fee, so this
+    /// equals `amount`.
+    pub receive_amount: String,
+    pub fee: String,
+    /// USDC balance of the wallet on the source chain.
+    pub source_balance: String,
+    /// Whether initiating will send an `approve` before the burn.
+    pub approval_required: bool,
+    /// Rough seconds until the funds arrive.
+    pub estimated_seconds: u64,
+}
+
+/// Request to start a bridge transfer.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct InitiateBridgeRequest {
+    /// USDC amount in human-readable units.
+    pub amount: String,
+    #[serde(default)]
+    pub direction: BridgeDirection,
+    /// Address to mint to; defaults to the wallet's own address, which is
+    /// the same on both chains.
+    #[serde(default)]
+    pub recipient: Option<String>,
+}
+
+/// A bridge transfer and both of its legs.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct BridgeTransferResponse {
+    pub transfer_id: String,
+    pub wallet_id: String,
+    pub direction: BridgeDirection,
+    pub status: BridgeStatus,
+    pub source_chain: String,
+    pub destination_chain: String,
+    pub amount: String,
+    pub sender: String,
+    pub recipient: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub approval_tx_hash: Option<String>,
+    pub burn_tx_hash: String,
+    pub burn_explorer_url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mint_tx_hash: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mint_explorer_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+/// A user's bridge transfers, newest first.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct BridgeTransferListResponse {
+    pub transfers: Vec<BridgeTransferResponse>,
+}
+
+fn to_response(transfer: &StoredBridgeTransfer) -> BridgeTransferResponse {
+    BridgeTransferResponse {
+        transfer_id: transfer.transfer_id.clone(),
+        wallet_id: transfer.wallet_id.clone(),
+        direction: transfer.direction,
+        status: transfer.status,
+        source_chain: transfer.source_chain.clone(),
+        destination_chain: transfer.destination_chain.clone(),
+        amount: transfer.amount.clone(),
+        sender: transfer.sender.clone(),
+        recipient: transfer.recipient.clone(),
+        approval_tx_hash: transfer.approval_tx_hash.clone(),
+        burn_tx_hash: transfer.burn_tx_hash.clone(),
+        burn_explorer_url: transfer.burn_explorer_url.clone(),
+        mint_tx_hash: transfer.mint_tx_hash.clone(),
+        mint_explorer_url: transfer.mint_explorer_url.clone(),
+        last_error: transfer.last_error.clone(),
+        created_at: transfer.created_at,
+        updated_at: transfer.updated_at,
+        completed_at: transfer.completed_at,
+    }
+}
+
+// =============================================================================
+// Helpers
+// =============================================================================
+
+fn bridge_config() -> Result<&'static BridgeConfig, ApiError> {
+    BridgeConfig::get().ok_or_else(|| ApiError::service_unavailable("Bridging is not configured"))
+}
+
+/// The caller's active, unlocked EOA wallet. Smart accounts do not exist on the
+/// remote chain, so they cannot receive the mint.
+fn bridge_wallet(
+    storage: &EncryptedStorage,
+    user: &AuthenticatedUser,
+    wallet_id: &str,
+) -> Result<WalletMetadata, ApiError> {
+    let wallet = active_wallet(storage, user, wallet_id)?;
+    ensure_unlocked(&wallet)?;
+    if wallet.account_type == WalletAccountType::SmartAccount {
+        return Err(ApiError::unprocessable(
+            "Smart-account wallets cannot bridge; use an EOA wallet",
+        ));
+    }
+    Ok(wallet)
+}
+
+fn parse_usdc(amount: &str) -> Result<U256, ApiError> {
+    let raw = parse_amount(amount.trim(), USDC_DECIMALS)
+        .map_err(|e| ApiError::bad_request(format!("Invalid amount: {e}")))?;
+    if raw.is_zero() {
+        return Err(
+            ApiError::bad_request("Amount must be greater than zero").with_code("invalid_amount")
+        );
+    }
+    Ok(raw)
+}
+
+async fn connect(endpoint: &ChainEndpoint) -> Result<AvaxClient, ApiError> {
+    AvaxClient::new(endpoint.network.clone())
+        .await
+        .map_err(|e| ApiError::service_unavailable(format!("Failed to connect: {e}")))
+}
+
+fn rpc_error(e: AvaxClientError) -> ApiError {
+    ApiError::service_unavailable(format!("Chain query failed: {e}"))
+}
+
+/// USDC balance and token-messenger allowance of `owner` on `endpoint`.
+async fn source_funds(
+    client: &AvaxClient,
+    endpoint: &ChainEndpoint,
+    owner: &str,
+) -> Result<(U256, U256), ApiError> {
+    let usdc = endpoint.usdc.to_string();
+    let balance = client
+        .get_token_balance(owner, &usdc)
+        .await
+        .map_err(rpc_error)?;
+    let balance = U256::from_str(&balance.balance_raw)
+        .map_err(|e| ApiError::internal(format!("Invalid balance: {e}")))?;
+    let allowance = client
+        .get_token_allowance(&usdc, owner, &endpoint.token_messenger.to_string())
+        .await
+        .map_err(rpc_error)?;
+    Ok((balance, allowance))
+}
+
+async fn wallet_builder(
+    state: &AppState,
+    wallet_id: &str,
+    endpoint: &ChainEndpoint,
+) -> Result<TxBuilder, ApiError> {
+    let key = WalletRepository::new(state.storage())
+        .read_private_key(wallet_id)
+        .map_err(|e| ApiError::internal(format!("Failed to read private key: {e}")))?;
+    let signer = wallet_from_pem(&key)
+        .map_err(|e| ApiError::internal(format!("Failed to create signer: {e}")))?;
+    Ok(TxBuilder::new(endpoint.network.clone(), signer)
+        .await
+        .map_err(|e| ApiError::service_unavailable(format!("Failed to connect: {e}")))?
+        .with_nonce_manager(state.nonce_manager.clone()))
+}
+
+fn send_error(e: AvaxClientError) -> ApiError {
+    if e.to_string().contains("insufficient funds") {
+        ApiError::unprocessable("Insufficient gas balance for transaction")
+            .with_code("insufficient_gas")
+    } else {
+        ApiError::service_unavailable(format!("Transaction failed: {e}"))
+    }
+}
+
+fn estimated_seconds(direction: BridgeDirection) -> u64 {
+    match direction {
+        BridgeDirection::Outbound => AVALANCHE_ESTIMATE_SECS,
+        BridgeDirection::Inbound => REMOTE_ESTIMATE_SECS,
+    }
+}
+
+/// Apply `change` if the transfer is still in `from`. Returns the updated
+/// record, or `None` when another request moved it first.
+fn transition(
+    storage: &EncryptedStorage,
+    transfer_id: &str,
+    from: BridgeStatus,
+    change: impl FnOnce(&mut StoredBridgeTransfer),
+) -> Result<Option<StoredBridgeTransfer>, ApiError> {
+    let _guard = BRIDGE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let repo = BridgeRepository::new(storage);
+    let mut transfer = repo
+        .get(transfer_id)
+        .context("Failed to read bridge transfer")?;
+    if transfer.status != from {
+        return Ok(None);
+    }
+    change(&mut transfer);
+    transfer.updated_at = Utc::now();
+    repo.update(&transfer)
+        .context("Failed to update bridge transfer")?;
+    Ok(Some(transfer))
+}
+
+fn reload(storage: &EncryptedStorage, transfer_id: &str) -> Result<StoredBridgeTransfer, ApiError> {
+    BridgeRepository::new(storage)
+        .get(transfer_id)
+        .context("Failed to read bridge transfer")
+}
+
+fn log_event(
+    storage: &EncryptedStorage,
+    event_type: AuditEventType,
+    transfer: &StoredBridgeTransfer,
+) {
+    let event = AuditEvent::new(event_type)
+        .with_user(&transfer.user_id)
+        .with_resource("bridge_transfer", &transfer.transfer_id)
+        .with_details(serde_json::json!({
+            "wallet_id": transfer.wallet_id,
+            "direction": transfer.direction,
+            "amount": transfer.amount,
+            "recipient": transfer.recipient,
+            "burn_tx_hash": transfer.burn_tx_hash,
+            "mint_tx_hash": transfer.mint_tx_hash,
+            "last_error": transfer.last_error,
+        }));
+    let _ = AuditRepository::new(storage).log(&event);
+}
+
+fn fail(
+    storage: &EncryptedStorage,
+    transfer: &StoredBridgeTransfer,
+    from: BridgeStatus,
+    reason: &str,
+) -> Result<StoredBridgeTransfer, ApiError> {
+    match transition(storage, &transfer.transfer_id, from, |t| {
+        t.status = BridgeStatus::Failed;
+        t.last_error = Some(reason.to_string());
+    })? {
+        Some(failed) => {
+            log_event(storage, AuditEventType::BridgeFailed, &failed);
+            Ok(failed)
+        }
+        None => reload(storage, &transfer.transfer_id),
+    }
+}
+
+/// Move a transfer as far along as the chains and Circle allow.
+async fn advance(
+    state: &AppState,
+    config: &BridgeConfig,
+    mut transfer: StoredBridgeTransfer,
+) -> Result<StoredBridgeTransfer, ApiError> {
+    let storage = state.storage();
+    let (source, destination) = config.route(transfer.direction == BridgeDirection::Outbound);
+
+    if transfer.status == BridgeStatus::Burning {
+        let receipt = connect(source)
+            .await?
+            .get_transaction_receipt_status(&transfer.burn_tx_hash)
+            .await
+            .map_err(rpc_error)?;
+        match receipt {
+            None => return Ok(transfer),
+            Some(receipt) if !receipt.success => {
+                return fail(
+                    storage,
+                    &transfer,
+                    BridgeStatus::Burning,
+                    "Burn transaction reverted",
+                );
+            }
+            Some(_) => {
+                transfer =
+                    match transition(storage, &transfer.transfer_id, BridgeStatus::Burning, |t| {
+                        t.status = BridgeStatus::Attesting;
+                    })? {
+                        Some(updated) => updated,
+                        None => reload(storage, &transfer.transfer_id)?,
+                    };
+            }
+        }
+    }
+
+    if transfer.status == BridgeStatus::Minting
+        && transfer.mint_tx_hash.is_none()
+        && Utc::now() - transfer.updated_at > Duration::seconds(MINT_CLAIM_TIMEOUT_SECS)
+    {
+        if let Some(updated) =
+            transition(storage, &transfer.transfer_id, BridgeStatus::Minting, |t| {
+                if t.mint_tx_hash.is_none() {
+                    t.status = BridgeStatus::Attesting;
+                }
+            })?
+        {
+            transfer = updated;
+        }
+    }
+
+    if transfer.status == BridgeStatus::Attesting {
+        let attestation = AttestationClient::from_env()
+            .map_err(|e| ApiError::internal(e.to_string()))?
+            .fetch(source.domain, &transfer.burn_tx_hash)
+            .await
+            .map_err(|e| ApiError::service_unavailable(e.to_string()))?;
+        let Some(attestation) = attestation else {
+            return Ok(transfer);
+        };
+        let calldata = encode_receive_message(&attestation.message, &attestation.attestation)
+            .map_err(|e| ApiError::internal(e.to_string()))?;
+        // Claim the mint so concurrent requests do not submit it twice.
+        let Some(claimed) = transition(
+            storage,
+            &transfer.transfer_id,
+            BridgeStatus::Attesting,
+            |t| {
+                t.status = BridgeStatus::Minting;
+                t.message = Some(attestation.message.clone());
+                t.attestation = Some(attestation.attestation.clone());
+                t.last_error = None;
+            },
+        )?
+        else {
+            return reload(storage, &transfer.transfer_id);
+        };
+
+        let sent = match wallet_builder(state, &claimed.wallet_id, destination).await {
+            Ok(builder) => builder
+                .send_contract_call(
+                    &destination.message_transmitter.to_string(),
+                    calldata,
+                    None,
+                    None,
+                    None,
+                )
+                .await
+                .map_err(send_error),
+            Err(e) => Err(e),
+        };
+        let result = match sent {
+            Ok(result) => result,
+            Err(e) => {
+                transition(storage, &claimed.transfer_id, BridgeStatus::Minting, |t| {
+                    t.status = BridgeStatus::Attesting;
+                    t.last_error = Some(e.message.clone());
+                })?;
+                return Err(e);
+            }
+        };
+        transfer = transition(storage, &claimed.transfer_id, BridgeStatus::Minting, |t| {
+            t.mint_tx_hash = Some(result.tx_hash.clone());
+            t.mint_explorer_url = Some(result.explorer_url.clone());
+        })?
+        .unwrap_or(claimed);
+        if transfer.direction == BridgeDirection::Inbound {
+            record_avalanche_leg(state, config, &transfer, &result);
+        }
+    }
+
+    if transfer.status == BridgeStatus::Minting {
+        let Some(mint_tx_hash) = transfer.mint_tx_hash.clone() else {
+            return Ok(transfer);
+        };
+        let receipt = connect(destination)
+            .await?
+            .get_transaction_receipt_status(&mint_tx_hash)
+            .await
+            .map_err(rpc_error)?;
+        match receipt {
+            None => {}
+            Some(receipt) if !receipt.success => {
+                return fail(
+                    storage,
+                    &transfer,
+                    BridgeStatus::Minting,
+                    "Mint transaction reverted",
+                );
+            }
+            Some(_) => {
+                if let Some(completed) =
+                    transition(storage, &transfer.transfer_id, BridgeStatus::Minting, |t| {
+                        t.status = BridgeStatus::Completed;
+                        t.completed_at = Some(Utc::now());
+                    })?
+                {
+                    log_event(storage, AuditEventType::BridgeCompleted, &completed);
+                    transfer = completed;
+                }
+            }
+        }
+    }
+
+    Ok(transfer)
+}
+
+/// Add the Avalanche leg (the burn when leaving, the mint when arriving)
+/// to the wallet's transaction history.
+fn record_avalanche_leg(
+    state: &AppState,
+    config: &BridgeConfig,
+    transfer: &StoredBridgeTransfer,
+    result: &SendResult,
+) {
+    let Some(tx_db) = state.tx_db.as_deref() else {
+        return;
+    };
+    let usdc = TokenType::Erc20(config.avalanche.usdc.to_string());
+    let (direction, from, to) = match transfer.direction {
+        BridgeDirection::Outbound => (
+            "sent",
+            transfer.sender.clone(),
+            config.avalanche.token_messenger.to_string(),
+        ),
+        BridgeDirection::Inbound => (
+            "received",
+            config.avalanche.message_transmitter.to_string(),
+            transfer.recipient.clone(),
+        ),
+    };
+    record_transfer(
+        tx_db,
+        state.tx_cache.as_deref(),
+        result,
+        &transfer.wallet_id,
+        direction,
+        &from,
+        &to,
+        &transfer.amount,
+        usdc,
+    );
+}
+
+// =============================================================================
+// Handlers
+// =============================================================================
+
+/// Quote a bridge transfer.
+///
+/// Reports the amount received, the wallet's USDC balance on the source
+/// chain, and whether an `approve` will be sent first.
+#[utoipa::path(
+    post,
+    path = "/v1/wallets/{wallet_id}/bridge/quote",
+    tag = "Bridge",
+    params(("wallet_id" = String, Path, description = "Wallet ID")),
+    request_body = BridgeQuoteRequest,
+    security(("bearer" = [])),
+    responses(
+        (status = 200, description = "Quote", body = BridgeQuoteResponse),
+        (status = 400, description = "Invalid amount"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - not wallet owner, or suspended"),
+        (status = 404, description = "Wallet not found"),
+        (status = 422, description = "Smart-account wallet"),
+        (status = 503, description = "Bridging not configured, or chain unavailable")
+    )
+)]
+pub async fn quote_bridge(
+    Auth(user): Auth,
+    State(state): State<AppState>,
+    Path(wallet_id): Path<String>,
+    Json(request): Json<BridgeQuoteRequest>,
+) -> Result<Json<BridgeQuoteResponse>, ApiError> {
+    let wallet = bridge_wallet(state.storage(), &user, &wallet_id)?;
+    let raw_amount = parse_usdc(&request.amount)?;
+    let config = bridge_config()?;
+    let (source, destination) = config.route(request.direction == BridgeDirection::Outbound);
+
+    let client = connect(source).await?;
+    let (balance, allowance) = source_funds(&client, source, &wallet.public_address).await?;
+    let amount = format_amount(raw_amount, USDC_DECIMALS);
+
+    Ok(Json(BridgeQuoteResponse {
+        direction: request.direction,
+        source_chain: source.network.name.to_string(),
+        destination_chain: destination.network.name.to_string(),
+        receive_amount: amount.clone(),
+        amount,
+        fee: "0".to_string(),
+        source_balance: format_amount(balance, USDC_DECIMALS),
+        approval_required: allowance < raw_amount,
+        estimated_seconds: estimated_seconds(request.direction),
+    }))
+}
+
+/// Start a bridge transfer.
+///
+/// Approves the token messenger if needed and burns the USDC on the source
+/// chain. Poll the transfer to have it attested and minted.
+#[utoipa::path(
+    post,
+    path = "/v1/wallets/{wallet_id}/bridge/transfers",
+    tag = "Bridge",
+    params(("wallet_id" = String, Path, description = "Wallet ID")),
+    request_body = InitiateBridgeRequest,
+    security(("bearer" = [])),
+    responses(
+        (status = 201, description = "Burn submitted", body = BridgeTransferResponse),
+        (status = 400, description = "Invalid amount or recipient"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - not wallet owner, or suspended"),
+        (status = 404, description = "Wallet not found"),
+        (status = 422, description = "Smart-account wallet, insufficient USDC or gas, or spending limit exceeded"),
+        (status = 503, description = "Bridging not configured, or chain unavailable")
+    )
+)]
+pub async fn initiate_bridge(
+    Auth(user): Auth,
+    State(state): State<AppState>,
+    Path(wallet_id): Path<String>,
+    Json(request): Json<InitiateBridgeRequest>,
+) -> Result<(StatusCode, Json<BridgeTransferResponse>), ApiError> {
+    let storage = state.storage();
+    let wallet = bridge_wallet(storage, &user, &wallet_id)?;
+    let amount = parse_usdc(&request.amount)?;
+    let recipient = match request.recipient.as_deref().map(str::trim) {
+        Some(raw) => Address::from_str(raw)
+            .map_err(|e| ApiError::bad_request(format!("Invalid recipient: {e}")))?,
+        None => Address::from_str(&wallet.public_address)
+            .map_err(|e| ApiError::internal(format!("Invalid wallet address: {e}")))?,
+    };
+    if recipient == Address::ZERO {
+        return Err(ApiError::bad_request(
+            "Recipient cannot be the zero address",
+        ));
+    }
+    let config = bridge_config()?;
+    let (source, destination) = config.route(request.direction == BridgeDirection::Outbound);
+
+    let client = connect(source).await?;
+    let (balance, allowance) = source_funds(&client, source, &wallet.public_address).await?;
+    if balance < amount {
+        return Err(ApiError::unprocessable(format!(
+            "Insufficient USDC on {}",
+            source.network.name
+        )));
+    }
+
+    let usdc = source.usdc.to_string();
+    let reservation = limits::reserve_spending(
+        &state,
+        &wallet,
+        &[(&usdc, &format_amount(amount, USDC_DECIMALS))],
+        source.network.id,
+    )
+    .await?;
+    let builder = wallet_builder(&state, &wallet_id, source).await?;
+    let approval = if allowance < amount {
+        let calldata = IERC20::approveCall {
+            spender: source.token_messenger,
+            amount,
+        }
+        .abi_encode();
+        Some(
+            builder
+                .send_contract_call(&usdc, calldata, None, None, None)
+                .await
+                .map_err(send_error)?,
+        )
+    } else {
+        None
+    };
+    let calldata = encode_deposit_for_burn(amount, destination.domain, recipient, source.usdc);
+    let burn = builder
+        .send_contract_call(
+            &source.token_messenger.to_string(),
+            calldata,
+            None,
+            approval.as_ref().map(|_| BURN_GAS_LIMIT),
+            None,
+        )
+        .await
+        .map_err(send_error)?;
+    reservation.commit(&burn.tx_hash);
+
+    let now = Utc::now();
+    let transfer = StoredBridgeTransfer {
+        transfer_id: Uuid::new_v4().to_string(),
+        user_id: user.user_id.clone(),
+        wallet_id: wallet_id.clone(),
+        direction: request.direction,
+        source_chain: source.network.name.to_string(),
+        destination_chain: destination.network.name.to_string(),
+        source_domain: source.domain,
+        destination_domain: destination.domain,
+        amount: format_amount(amount, USDC_DECIMALS),
+        amount_raw: amount.to_string(),
+        sender: wallet.public_address.clone(),
+        recipient: recipient.to_string(),
+        status: BridgeStatus::Burning,
+        approval_tx_hash: approval.map(|result| result.tx_hash),
+        burn_tx_hash: burn.tx_hash.clone(),
+        burn_explorer_url: burn.explorer_url.clone(),
+        message: None,
+        attestation: None,
+        mint_tx_hash: None,
+        mint_explorer_url: None,
+        last_error: None,
+        created_at: now,
+        updated_at: now,
+        completed_at: None,
+    };
+    BridgeRepository::new(storage)
+        .create(&transfer)
+        .map_err(|e| ApiError::internal(format!("Failed to store bridge transfer: {e}")))?;
+    if transfer.direction == BridgeDirection::Outbound {
+        record_avalanche_leg(&state, config, &transfer, &burn);
+    }
+    log_event(storage, AuditEventType::BridgeInitiated, &transfer);
+
+    Ok((StatusCode::CREATED, Json(to_response(&transfer))))
+}
+
+/// List the caller's bridge transfers.
+#[utoipa::path(
+    get,
+    path = "/v1/bridge/transfers",
+    tag = "Bridge",
+    security(("bearer" = [])),
+    responses(
+        (status = 200, description = "Bridge transfers, newest first", body = BridgeTransferListResponse),
+        (status = 401, description = "Unauthorized")
+    )
+)]
+pub async fn list_bridge_transfers(
+    Auth(user): Auth,
+    State(state): State<AppState>,
+) -> Result<Json<BridgeTransferListResponse>, ApiError> {
+    let transfers = BridgeRepository::new(state.storage())
+        .list_for_user(&user.user_id)
+        .map_err(|e| ApiError::internal(format!("Failed to list bridge transfers: {e}")))?;
+    Ok(Json(BridgeTransferListResponse {
+        transfers: transfers.iter().map(to_response).collect(),
+    }))
+}
+
+/// Get a bridge transfer, advancing it first.
+///
+/// Checks the burn receipt, fetches the attestation, submits the mint and
+/// checks its receipt, as far as each is ready.
+#[utoipa::path(
+    get,
+    path = "/v1/bridge/transfers/{transfer_id}",
+    tag = "Bridge",
+    params(("transfer_id" = String, Path, description = "Bridge transfer ID")),
+    security(("bearer" = [])),
+    responses(
+        (status = 200, description = "Bridge transfer", body = BridgeTransferResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Transfer not found"),
+        (status = 422, description = "Insufficient gas on the destination chain to mint"),
+        (status = 503, description = "Chain or attestation service unavailable")
+    )
+)]
+pub async fn get_bridge_transfer(
+    Auth(user): Auth,
+    State(state): State<AppState>,
+    Path(transfer_id): Path<String>,
+) -> Result<Json<BridgeTransferResponse>, ApiError> {
+    let transfer = BridgeRepository::new(state.storage()).get(&transfer_id)?;
+    if transfer.user_id != user.user_id {
+        return Err(ApiError::not_found("Bridge transfer not found"));
+    }
+    if transfer.status.is_final() {
+        return Ok(Json(to_response(&transfer)));
+    }
+    let Some(config) = BridgeConfig::get() else {
+        return Ok(Json(to_response(&transfer)));
+    };
+    let transfer = advance(&state, config, transfer).await?;
+    Ok(Json(to_response(&transfer)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::{AuthenticatedUser, Role};
+    use crate::storage::WalletStatus;
+
+    fn auth(user_id: &str) -> Auth {
+        Auth(AuthenticatedUser {
+            user_id: user_id.to_string(),
+            role: Role::Client,
+            session_id: None,
+            issuer: "https://test.clerk.dev".to_string(),
+            expires_at: Utc::now().timestamp() + 3600,
+            tenant_id: None,
+        })
+    }
+
+    #[tokio::test]
+    async fn transfers_are_private_and_smart_accounts_cannot_bridge() {
+        let state = AppState::default();
+        let storage = state.storage();
+        WalletRepository::new(storage)
+            .create(
+                &WalletMetadata {
+                    wallet_id: "w-smart".to_string(),
+                    owner_user_id: "user-a".to_string(),
+                    public_address: "0x1111111111111111111111111111111111111111".to_string(),
+                    created_at: Utc::now(),
+                    status: WalletStatus::Active,
+                    label: None,
+                    email_lookup_key: None,
+                    email_sha256: None,
+                    account_type: WalletAccountType::SmartAccount,
+                    smart_account: None,
+                    lock: None,
+                    deleted_at: None,
+                    purged_at: None,
+                    funded_at: None,
+                    tenant_id: None,
+                },
+                b"test_key",
+            )
+            .unwrap();
+        let err = initiate_bridge(
+            auth("user-a"),
+            State(state.clone()),
+            Path("w-smart".to_string()),
+            Json(InitiateBridgeRequest {
+                amount: "1".to_string(),
+                direction: BridgeDirection::Outbound,
+                recipient: None,
+            }),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::UNPROCESSABLE_ENTITY);
+
+        let now = Utc::now();
+        BridgeRepository::new(storage)
+            .create(&StoredBridgeTransfer {
+                transfer_id: "br-1".to_string(),
+                user_id: "user-a".to_string(),
+                wallet_id: "w-eoa".to_string(),
+                direction: BridgeDirection::Outbound,
+                source_chain: "Avalanche Fuji Testnet".to_string(),
+                destination_chain: "Ethereum Sepolia".to_string(),
+                source_domain: 1,
+                destination_domain: 0,
+                amount: "1".to_string(),
+                amount_raw: "1000000".to_string(),
+                sender: "0x1111111111111111111111111111111111111111".to_string(),
+                recipient: "0x1111111111111111111111111111111111111111".to_string(),
+                status: BridgeStatus::Completed,
+                approval_tx_hash: None,
+                burn_tx_hash: "0xburn".to_string(),
+                burn_explorer_url: "https://testnet.snowtrace.io/tx/0xburn".to_string(),
+                message: Some("0x01".to_string()),
+                attestation: Some("0x02".to_string()),
+                mint_tx_hash: Some("0xmint".to_string()),
+                mint_explorer_url: None,
+                last_error: None,
+                created_at: now,
+                updated_at: now,
+                completed_at: Some(now),
+            })
+            .unwrap();
+
+        let err = get_bridge_transfer(auth("user-b"), State(state.clone()), Path("br-1".into()))
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, StatusCode::NOT_FOUND);
+
+        let Json(own) =
+            get_bridge_transfer(auth("user-a"), State(state.clone()), Path("br-1".into()))
+                .await
+                .unwrap();
+        assert_eq!(own.status, BridgeStatus::Completed);
+        assert_eq!(own.mint_tx_hash.as_deref(), Some("0xmint"));
+
+        let Json(list) = list_bridge_transfers(auth("user-b"), State(state))
+            .await
+            .unwrap();
+        assert!(list.transfers.is_empty());
+    }
+}
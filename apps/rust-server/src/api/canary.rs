@@ -0,0 +1,67 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Results of the [`canary`](crate::canary) transfer worker.
+
+use axum::Json;
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::{
+    api::admin::require_platform_admin,
+    auth::AdminOnly,
+    canary::{self, CanaryConfig, CanaryOutcome, CanaryRun},
+    error::ApiError,
+};
+
+/// Recent canary runs and their thresholds.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CanaryReportResponse {
+    /// Whether `CANARY_WALLET_IDS` is configured.
+    pub enabled: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interval_secs: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confirm_alert_secs: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub index_alert_secs: Option<u64>,
+    pub healthy: usize,
+    pub degraded: usize,
+    pub failed: usize,
+    /// Newest first, since the server started.
+    pub runs: Vec<CanaryRun>,
+}
+
+/// Recent canary transfer results (admin only).
+///
+/// Runs are kept in memory, up to one day at the default interval.
+#[utoipa::path(
+    get,
+    path = "/v1/admin/canary",
+    tag = "Admin",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Canary results", body = CanaryReportResponse),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not authorized (platform admin required)")
+    )
+)]
+pub async fn get_canary_report(
+    AdminOnly(admin): AdminOnly,
+) -> Result<Json<CanaryReportResponse>, ApiError> {
+    require_platform_admin(&admin)?;
+    let config = CanaryConfig::from_env();
+    let runs = canary::recent_runs();
+    let count = |outcome| runs.iter().filter(|r| r.outcome == outcome).count();
+    Ok(Json(CanaryReportResponse {
+        enabled: config.is_some(),
+        interval_secs: config.as_ref().map(|c| c.interval_secs),
+        confirm_alert_secs: config.as_ref().map(|c| c.confirm_alert_secs),
+        index_alert_secs: config.as_ref().map(|c| c.index_alert_secs),
+        healthy: count(CanaryOutcome::Healthy),
+        degraded: count(CanaryOutcome::Degraded),
+        failed: count(CanaryOutcome::Failed),
+        runs,
+    }))
+}
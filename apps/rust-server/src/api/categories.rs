@@ -0,0 +1,832 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Transaction categories and budgets.
+//!
+//! Every wallet transaction gets an automatic [`TxCategory`], derived from
+//! the wallet's history each time it is read:
+//!
+//! - `fiat_settlement`: to or from the fiat reserve wallet
+//! - `internal`: between the user's own wallets
+//! - `fee`: sent to an address listed in `TX_FEE_ADDRESSES` (e.g. a token
+//!   paymaster)
+//! - `swap`: one of a sent and a received transfer of different tokens with
+//!   the same counterparty in the same block
+//! - `recurring`: same direction, counterparty, token and amount in at least
+//!   [`RECURRING_MIN_MONTHS`] calendar months
+//! - `transfer`: anything else
+//!
+//! Users can also create their own categories, with an optional monthly EUR
+//! budget, and assign transactions to them. Both appear in transaction lists
+//! and the tax report; `GET /v1/categories/spending` totals a month's
+//! outgoing value per category against its budget.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use chrono::{Datelike, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::{
+    api::transactions::TransactionSummary,
+    auth::{Auth, AuthenticatedUser},
+    blockchain::address_key,
+    error::{ApiError, StorageContext},
+    providers::pricing::{value_at_tx_time, PRICED_SYMBOLS},
+    state::AppState,
+    storage::{
+        tx_database::TxDbResult, CategoryRepository, EncryptedStorage, FiatServiceWalletRepository,
+        InsightsRepository, OwnershipEnforcer, PriceHistories, PriceHistoryRepository,
+        StoredTransaction, TokenType, TxDatabase, TxStatus, UserCategory, WalletMetadata,
+        WalletRepository, WalletStatus,
+    },
+};
+
+/// Calendar months a repeated transfer must appear in to count as recurring.
+pub const RECURRING_MIN_MONTHS: usize = 3;
+
+/// Categories a single user may create.
+const MAX_CATEGORIES_PER_USER: usize = 50;
+
+/// Longest accepted category name.
+const MAX_NAME_LEN: usize = 40;
+
+/// Page size when loading a wallet's history.
+const HISTORY_PAGE: usize = 500;
+
+/// Automatic transaction category.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TxCategory {
+    Transfer,
+    FiatSettlement,
+    Fee,
+    Swap,
+    Recurring,
+    Internal,
+}
+
+impl TxCategory {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TxCategory::Transfer => "transfer",
+            TxCategory::FiatSettlement => "fiat_settlement",
+            TxCategory::Fee => "fee",
+            TxCategory::Swap => "swap",
+            TxCategory::Recurring => "recurring",
+            TxCategory::Internal => "internal",
+        }
+    }
+}
+
+/// Addresses the automatic rules compare counterparties against, all
+/// lowercase.
+#[derive(Debug, Default)]
+pub(crate) struct CategoryContext {
+    pub reserve_address: Option<String>,
+    pub own_addresses: HashSet<String>,
+    pub fee_addresses: HashSet<String>,
+}
+
+impl CategoryContext {
+    /// Context for `user_id`'s wallets.
+    pub(crate) fn load(storage: &EncryptedStorage, user_id: &str) -> Result<Self, ApiError> {
+        let wallets = WalletRepository::new(storage)
+            .list_all_wallets()
+            .map_err(|e| ApiError::internal(format!("Failed to list wallets: {e}")))?;
+        Ok(Self::from_wallets(storage, &wallets, user_id))
+    }
+
+    /// Context for `user_id` from an already loaded wallet list.
+    pub(crate) fn from_wallets(
+        storage: &EncryptedStorage,
+        wallets: &[WalletMetadata],
+        user_id: &str,
+    ) -> Self {
+        let own_addresses = wallets
+            .iter()
+            .filter(|w| w.owner_user_id == user_id)
+            .map(|w| address_key(&w.public_address))
+            .collect();
+        let fee_addresses = std::env::var("TX_FEE_ADDRESSES")
+            .unwrap_or_default()
+            .split(',')
+            .map(address_key)
+            .filter(|a| !a.is_empty())
+            .collect();
+        Self {
+            reserve_address: FiatServiceWalletRepository::new(storage)
+                .get()
+                .ok()
+                .map(|reserve| address_key(&reserve.public_address)),
+            own_addresses,
+            fee_addresses,
+        }
+    }
+}
+
+/// A wallet's whole indexed history, newest first, with the direction of
+/// each transaction.
+pub(crate) fn load_history(
+    state: &AppState,
+    address: &str,
+) -> Result<Vec<(StoredTransaction, String)>, ApiError> {
+    let tx_db = state
+        .tx_db
+        .as_ref()
+        .expect("transaction database must be configured");
+    wallet_history(tx_db, address)
+        .map_err(|e| ApiError::internal(format!("Failed to list transactions: {e}")))
+}
+
+/// [`load_history`] without the app state, for background workers.
+pub(crate) fn wallet_history(
+    tx_db: &TxDatabase,
+    address: &str,
+) -> TxDbResult<Vec<(StoredTransaction, String)>> {
+    let address = address_key(address);
+    let mut history = Vec::new();
+    let mut cursor = None;
+    loop {
+        let (page, next) = tx_db.list_by_wallet(&address, cursor.as_deref(), HISTORY_PAGE)?;
+        history.extend(page);
+        match next {
+            Some(next) => cursor = Some(next),
+            None => break,
+        }
+    }
+    Ok(history)
+}
+
+fn counterparty(tx: &StoredTransaction, direction: &str) -> String {
+    if direction == "sent" {
+        address_key(&tx.to)
+    } else {
+        address_key(&tx.from)
+    }
+}
+
+fn token_key(token: &TokenType) -> String {
+    match token {
+        TokenType::Native => "native".to_string(),
+        TokenType::Erc20(address) => address_key(address),
+    }
+}
+
+/// Transfers of one block with one counterparty: direction, token, hash.
+type BlockTransfers<'a> = HashMap<(u64, String), Vec<(&'a str, String, &'a str)>>;
+/// Months in which the same transfer (direction, counterparty, token,
+/// amount) was seen.
+type ActiveMonths<'a> = HashMap<(&'a str, String, String, &'a str), HashSet<(i32, u32)>>;
+
+/// Automatic category of every transaction in a wallet's history, keyed by
+/// lowercase hash.
+pub(crate) fn categorize(
+    history: &[(StoredTransaction, String)],
+    context: &CategoryContext,
+) -> HashMap<String, TxCategory> {
+    let mut by_block: BlockTransfers<'_> = HashMap::new();
+    let mut months: ActiveMonths<'_> = HashMap::new();
+    for (tx, direction) in history {
+        let other = counterparty(tx, direction);
+        if let Some(block) = tx.block_number {
+            by_block.entry((block, other.clone())).or_default().push((
+                direction.as_str(),
+                token_key(&tx.token),
+                &tx.tx_hash,
+            ));
+        }
+        months
+            .entry((
+                direction.as_str(),
+                other,
+                token_key(&tx.token),
+                tx.amount.as_str(),
+            ))
+            .or_default()
+            .insert((tx.created_at.year(), tx.created_at.month()));
+    }
+
+    let mut swaps = HashSet::new();
+    for legs in by_block.values() {
+        let is_swap = legs.iter().any(|(dir, token, _)| {
+            *dir == "sent"
+                && legs
+                    .iter()
+                    .any(|(other_dir, other_token, _)| *other_dir != "sent" && other_token != token)
+        });
+        if is_swap {
+            swaps.extend(legs.iter().map(|(_, _, hash)| hash.to_lowercase()));
+        }
+    }
+
+    history
+        .iter()
+        .map(|(tx, direction)| {
+            let hash = tx.tx_hash.to_lowercase();
+            let other = counterparty(tx, direction);
+            let category = if context.reserve_address.as_deref() == Some(other.as_str()) {
+                TxCategory::FiatSettlement
+            } else if context.own_addresses.contains(&other) {
+                TxCategory::Internal
+            } else if direction == "sent" && context.fee_addresses.contains(&other) {
+                TxCategory::Fee
+            } else if swaps.contains(&hash) {
+                TxCategory::Swap
+            } else if months
+                .get(&(
+                    direction.as_str(),
+                    other,
+                    token_key(&tx.token),
+                    tx.amount.as_str(),
+                ))
+                .is_some_and(|m| m.len() >= RECURRING_MIN_MONTHS)
+            {
+                TxCategory::Recurring
+            } else {
+                TxCategory::Transfer
+            };
+            (hash, category)
+        })
+        .collect()
+}
+
+/// Fill in the automatic and user categories of a wallet's transactions.
+pub(crate) fn annotate(
+    state: &AppState,
+    user_id: &str,
+    address: &str,
+    transactions: &mut [TransactionSummary],
+) -> Result<(), ApiError> {
+    let storage = state.storage();
+    let context = CategoryContext::load(storage, user_id)?;
+    let categories = categorize(&load_history(state, address)?, &context);
+    let user_categories = CategoryRepository::new(storage)
+        .get(user_id)
+        .context("Failed to load categories")?;
+    for tx in transactions {
+        tx.category = categories.get(&tx.tx_hash.to_lowercase()).copied();
+        tx.user_category_id = user_categories
+            .category_of(&tx.tx_hash)
+            .map(|c| c.category_id.clone());
+    }
+    Ok(())
+}
+
+// =============================================================================
+// User categories
+// =============================================================================
+
+/// Request body for creating or updating a category.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct CategoryRequest {
+    pub name: String,
+    /// Monthly budget in EUR (e.g. "250.00"). Omit for no budget.
+    #[serde(default)]
+    pub monthly_budget_eur: Option<String>,
+}
+
+/// Validate a request and return the trimmed name and normalized budget.
+fn validate_request(
+    request: &CategoryRequest,
+    existing: &[UserCategory],
+    category_id: Option<&str>,
+) -> Result<(String, Option<String>), ApiError> {
+    let name = request.name.trim();
+    if name.is_empty() || name.chars().count() > MAX_NAME_LEN {
+        return Err(ApiError::bad_request(format!(
+            "name must be 1 to {MAX_NAME_LEN} characters"
+        )));
+    }
+    if existing
+        .iter()
+        .any(|c| Some(c.category_id.as_str()) != category_id && c.name.eq_ignore_ascii_case(name))
+    {
+        return Err(ApiError::conflict(format!(
+            "A category named '{name}' already exists"
+        )));
+    }
+    let budget = match request.monthly_budget_eur.as_deref().map(str::trim) {
+        None => None,
+        Some(value) => match value.parse::<f64>() {
+            Ok(amount) if amount > 0.0 && amount.is_finite() => Some(format!("{amount:.2}")),
+            _ => {
+                return Err(ApiError::bad_request(
+                    "monthly_budget_eur must be a positive EUR amount",
+                ))
+            }
+        },
+    };
+    Ok((name.to_string(), budget))
+}
+
+/// List your categories.
+#[utoipa::path(
+    get,
+    path = "/v1/categories",
+    tag = "Categories",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, body = [UserCategory]),
+        (status = 401, description = "Unauthorized")
+    )
+)]
+pub async fn list_categories(
+    Auth(user): Auth,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<UserCategory>>, ApiError> {
+    let stored = CategoryRepository::new(state.storage())
+        .get(&user.user_id)
+        .context("Failed to load categories")?;
+    Ok(Json(stored.categories))
+}
+
+/// Create a category.
+#[utoipa::path(
+    post,
+    path = "/v1/categories",
+    tag = "Categories",
+    request_body = CategoryRequest,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 201, body = UserCategory),
+        (status = 400, description = "Invalid name or budget"),
+        (status = 401, description = "Unauthorized"),
+        (status = 409, description = "Name already used"),
+        (status = 422, description = "Category limit reached")
+    )
+)]
+pub async fn create_category(
+    Auth(user): Auth,
+    State(state): State<AppState>,
+    Json(request): Json<CategoryRequest>,
+) -> Result<(StatusCode, Json<UserCategory>), ApiError> {
+    let repo = CategoryRepository::new(state.storage());
+    let mut stored = repo
+        .get(&user.user_id)
+        .context("Failed to load categories")?;
+    let (name, monthly_budget_eur) = validate_request(&request, &stored.categories, None)?;
+    if stored.categories.len() >= MAX_CATEGORIES_PER_USER {
+        return Err(ApiError::unprocessable(format!(
+            "At most {MAX_CATEGORIES_PER_USER} categories per user"
+        )));
+    }
+    let category = UserCategory {
+        category_id: uuid::Uuid::new_v4().to_string(),
+        name,
+        monthly_budget_eur,
+        created_at: Utc::now(),
+    };
+    stored.categories.push(category.clone());
+    repo.save(&user.user_id, &stored)
+        .context("Failed to save categories")?;
+    Ok((StatusCode::CREATED, Json(category)))
+}
+
+/// Rename a category or change its budget.
+#[utoipa::path(
+    put,
+    path = "/v1/categories/{category_id}",
+    tag = "Categories",
+    params(("category_id" = String, Path, description = "Category ID")),
+    request_body = CategoryRequest,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, body = UserCategory),
+        (status = 400, description = "Invalid name or budget"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Category not found"),
+        (status = 409, description = "Name already used")
+    )
+)]
+pub async fn update_category(
+    Auth(user): Auth,
+    State(state): State<AppState>,
+    Path(category_id): Path<String>,
+    Json(request): Json<CategoryRequest>,
+) -> Result<Json<UserCategory>, ApiError> {
+    let repo = CategoryRepository::new(state.storage());
+    let mut stored = repo
+        .get(&user.user_id)
+        .context("Failed to load categories")?;
+    let (name, monthly_budget_eur) =
+        validate_request(&request, &stored.categories, Some(&category_id))?;
+    let category = stored
+        .categories
+        .iter_mut()
+        .find(|c| c.category_id == category_id)
+        .ok_or_else(|| ApiError::not_found("Category not found"))?;
+    category.name = name;
+    category.monthly_budget_eur = monthly_budget_eur;
+    let updated = category.clone();
+    repo.save(&user.user_id, &stored)
+        .context("Failed to save categories")?;
+    Ok(Json(updated))
+}
+
+/// Delete a category. Its transactions become uncategorized.
+#[utoipa::path(
+    delete,
+    path = "/v1/categories/{category_id}",
+    tag = "Categories",
+    params(("category_id" = String, Path, description = "Category ID")),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 204, description = "Deleted"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Category not found")
+    )
+)]
+pub async fn delete_category(
+    Auth(user): Auth,
+    State(state): State<AppState>,
+    Path(category_id): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    let repo = CategoryRepository::new(state.storage());
+    let mut stored = repo
+        .get(&user.user_id)
+        .context("Failed to load categories")?;
+    if !stored.remove(&category_id) {
+        return Err(ApiError::not_found("Category not found"));
+    }
+    repo.save(&user.user_id, &stored)
+        .context("Failed to save categories")?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Request body for assigning a transaction to a category.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct AssignCategoryRequest {
+    /// Category to assign; `null` clears the assignment.
+    pub category_id: Option<String>,
+}
+
+/// A transaction's categories.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct TransactionCategoryResponse {
+    pub tx_hash: String,
+    pub category: TxCategory,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_category_id: Option<String>,
+}
+
+pub(crate) fn owned_wallet(
+    storage: &EncryptedStorage,
+    user: &AuthenticatedUser,
+    wallet_id: &str,
+) -> Result<WalletMetadata, ApiError> {
+    let wallet = WalletRepository::new(storage).get(wallet_id)?;
+    if !wallet.is_owned_by(user) {
+        return Err(ApiError::forbidden("You do not own this wallet").with_code("wallet_not_owned"));
+    }
+    Ok(wallet)
+}
+
+/// Assign a wallet transaction to one of your categories.
+#[utoipa::path(
+    put,
+    path = "/v1/wallets/{wallet_id}/transactions/{tx_hash}/category",
+    tag = "Categories",
+    params(
+        ("wallet_id" = String, Path, description = "Wallet ID"),
+        ("tx_hash" = String, Path, description = "Transaction hash")
+    ),
+    request_body = AssignCategoryRequest,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, body = TransactionCategoryResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - not wallet owner"),
+        (status = 404, description = "Wallet, transaction or category not found")
+    )
+)]
+pub async fn assign_transaction_category(
+    Auth(user): Auth,
+    State(state): State<AppState>,
+    Path((wallet_id, tx_hash)): Path<(String, String)>,
+    Json(request): Json<AssignCategoryRequest>,
+) -> Result<Json<TransactionCategoryResponse>, ApiError> {
+    let storage = state.storage();
+    let wallet = owned_wallet(storage, &user, &wallet_id)?;
+    let history = load_history(&state, &wallet.public_address)?;
+    let tx_hash = tx_hash.to_lowercase();
+    let Some((tx, _)) = history
+        .iter()
+        .find(|(tx, _)| tx.tx_hash.to_lowercase() == tx_hash)
+    else {
+        return Err(ApiError::not_found("Transaction not found in this wallet"));
+    };
+
+    let repo = CategoryRepository::new(storage);
+    let mut stored = repo
+        .get(&user.user_id)
+        .context("Failed to load categories")?;
+    match &request.category_id {
+        Some(category_id) => {
+            if stored.get(category_id).is_none() {
+                return Err(ApiError::not_found("Category not found"));
+            }
+            stored
+                .assignments
+                .insert(tx_hash.clone(), category_id.clone());
+        }
+        None => {
+            stored.assignments.remove(&tx_hash);
+        }
+    }
+    repo.save(&user.user_id, &stored)
+        .context("Failed to save categories")?;
+    // The month's per-category totals are stale now; the aggregator
+    // recomputes them on its next run.
+    InsightsRepository::new(storage)
+        .invalidate_month(
+            &wallet.wallet_id,
+            &tx.created_at.format("%Y-%m").to_string(),
+        )
+        .context("Failed to update insights")?;
+
+    let context = CategoryContext::load(storage, &user.user_id)?;
+    let category = categorize(&history, &context)
+        .get(&tx_hash)
+        .copied()
+        .unwrap_or(TxCategory::Transfer);
+    Ok(Json(TransactionCategoryResponse {
+        tx_hash,
+        category,
+        user_category_id: request.category_id,
+    }))
+}
+
+// =============================================================================
+// Spending
+// =============================================================================
+
+/// Query parameters for the spending summary.
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct SpendingQuery {
+    /// Month as `YYYY-MM` (UTC, default: current month).
+    pub month: Option<String>,
+}
+
+/// Spending in one user category.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct CategorySpending {
+    pub category_id: String,
+    pub name: String,
+    pub spent_eur: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub budget_eur: Option<String>,
+    /// Budget left; negative when overspent.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remaining_eur: Option<String>,
+    pub over_budget: bool,
+}
+
+/// Response for `GET /v1/categories/spending`.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct SpendingResponse {
+    pub month: String,
+    pub currency: String,
+    /// Per user category, in category order.
+    pub categories: Vec<CategorySpending>,
+    /// Spending not assigned to a user category.
+    pub uncategorized_eur: String,
+    /// Spending per automatic category, including assigned transactions.
+    pub by_category: BTreeMap<String, String>,
+    /// Outgoing transactions without a recorded price, left out of the totals.
+    pub unpriced: usize,
+}
+
+pub(crate) fn eur(value: f64) -> String {
+    format!("{value:.2}")
+}
+
+/// First day of a `YYYY-MM` month query, defaulting to the current month.
+pub(crate) fn parse_month(month: Option<&str>) -> Result<NaiveDate, ApiError> {
+    match month {
+        Some(month) => NaiveDate::parse_from_str(&format!("{month}-01"), "%Y-%m-%d")
+            .map_err(|_| ApiError::bad_request("Invalid month format. Use YYYY-MM.")),
+        None => Ok(Utc::now().date_naive().with_day(1).unwrap_or_default()),
+    }
+}
+
+/// A month's outgoing value per category (all your wallets).
+///
+/// Counts confirmed sends, valued at the recorded EUR price of their day.
+/// Transfers between your own wallets are not spending and are left out.
+#[utoipa::path(
+    get,
+    path = "/v1/categories/spending",
+    tag = "Categories",
+    params(SpendingQuery),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, body = SpendingResponse),
+        (status = 400, description = "Invalid month"),
+        (status = 401, description = "Unauthorized")
+    )
+)]
+pub async fn get_spending(
+    Auth(user): Auth,
+    State(state): State<AppState>,
+    Query(query): Query<SpendingQuery>,
+) -> Result<Json<SpendingResponse>, ApiError> {
+    let month = parse_month(query.month.as_deref())?;
+    let in_month = |tx: &StoredTransaction| {
+        tx.created_at.year() == month.year() && tx.created_at.month() == month.month()
+    };
+
+    let storage = state.storage();
+    let context = CategoryContext::load(storage, &user.user_id)?;
+    let user_categories = CategoryRepository::new(storage)
+        .get(&user.user_id)
+        .context("Failed to load categories")?;
+    let prices = PriceHistoryRepository::new(storage)
+        .get_many(&PRICED_SYMBOLS)
+        .unwrap_or_else(|e| {
+            tracing::warn!(error = %e, "Failed to load price history");
+            PriceHistories::new()
+        });
+    let wallets: Vec<WalletMetadata> = WalletRepository::new(storage)
+        .list_all_wallets()
+        .map_err(|e| ApiError::internal(format!("Failed to list wallets: {e}")))?
+        .into_iter()
+        .filter(|w| w.is_owned_by(&user) && w.status != WalletStatus::Deleted)
+        .collect();
+
+    let mut spent: HashMap<String, f64> = HashMap::new();
+    let mut by_category: BTreeMap<String, f64> = BTreeMap::new();
+    let mut uncategorized = 0.0;
+    let mut unpriced = 0;
+    for wallet in &wallets {
+        let history = load_history(&state, &wallet.public_address)?;
+        let categories = categorize(&history, &context);
+        for (tx, direction) in &history {
+            if direction != "sent" || tx.status != TxStatus::Confirmed || !in_month(tx) {
+                continue;
+            }
+            let category = categories
+                .get(&tx.tx_hash.to_lowercase())
+                .copied()
+                .unwrap_or(TxCategory::Transfer);
+            if category == TxCategory::Internal {
+                continue;
+            }
+            let Some(value) = value_at_tx_time(&prices, tx) else {
+                unpriced += 1;
+                continue;
+            };
+            *by_category
+                .entry(category.as_str().to_string())
+                .or_default() += value;
+            match user_categories.category_of(&tx.tx_hash) {
+                Some(c) => *spent.entry(c.category_id.clone()).or_default() += value,
+                None => uncategorized += value,
+            }
+        }
+    }
+
+    let categories = user_categories
+        .categories
+        .iter()
+        .map(|c| {
+            let spent_eur = spent.get(&c.category_id).copied().unwrap_or(0.0);
+            let budget = c
+                .monthly_budget_eur
+                .as_deref()
+                .and_then(|b| b.parse::<f64>().ok());
+            CategorySpending {
+                category_id: c.category_id.clone(),
+                name: c.name.clone(),
+                spent_eur: eur(spent_eur),
+                budget_eur: c.monthly_budget_eur.clone(),
+                remaining_eur: budget.map(|b| eur(b - spent_eur)),
+                over_budget: budget.is_some_and(|b| spent_eur > b),
+            }
+        })
+        .collect();
+
+    Ok(Json(SpendingResponse {
+        month: month.format("%Y-%m").to_string(),
+        currency: "EUR".to_string(),
+        categories,
+        uncategorized_eur: eur(uncategorized),
+        by_category: by_category
+            .into_iter()
+            .map(|(category, value)| (category, eur(value)))
+            .collect(),
+        unpriced,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{DateTime, TimeZone};
+
+    const ME: &str = "0x1111111111111111111111111111111111111111";
+    const RESERVE: &str = "0x2222222222222222222222222222222222222222";
+    const SAVINGS: &str = "0x3333333333333333333333333333333333333333";
+    const LANDLORD: &str = "0x4444444444444444444444444444444444444444";
+    const DEX: &str = "0x5555555555555555555555555555555555555555";
+    const REUR: &str = "0x6666666666666666666666666666666666666666";
+    const FEES: &str = "0x7777777777777777777777777777777777777777";
+
+    fn tx(
+        hash: &str,
+        direction: &str,
+        other: &str,
+        amount: &str,
+        token: TokenType,
+        block: u64,
+        at: DateTime<Utc>,
+    ) -> (StoredTransaction, String) {
+        let (from, to) = if direction == "sent" {
+            (ME, other)
+        } else {
+            (other, ME)
+        };
+        let mut tx = StoredTransaction::new_pending(
+            hash.to_string(),
+            "wallet-1".to_string(),
+            None,
+            from.to_string(),
+            to.to_string(),
+            amount.to_string(),
+            token,
+            "fuji".to_string(),
+            String::new(),
+        );
+        tx.status = TxStatus::Confirmed;
+        tx.block_number = Some(block);
+        tx.created_at = at;
+        (tx, direction.to_string())
+    }
+
+    #[test]
+    fn rules_pick_the_most_specific_category() {
+        let day = |m, d| Utc.with_ymd_and_hms(2026, m, d, 12, 0, 0).unwrap();
+        let reur = || TokenType::Erc20(REUR.to_string());
+        let history = vec![
+            tx("0xa1", "received", RESERVE, "100", reur(), 1, day(1, 5)),
+            tx("0xa2", "sent", SAVINGS, "10", reur(), 2, day(1, 6)),
+            tx("0xa3", "sent", DEX, "5", reur(), 3, day(1, 7)),
+            tx(
+                "0xa4",
+                "received",
+                DEX,
+                "0.2",
+                TokenType::Native,
+                3,
+                day(1, 7),
+            ),
+            tx("0xb1", "sent", LANDLORD, "700", reur(), 4, day(1, 1)),
+            tx("0xb2", "sent", LANDLORD, "700", reur(), 5, day(2, 1)),
+            tx("0xb3", "sent", LANDLORD, "700", reur(), 6, day(3, 1)),
+            tx("0xc1", "sent", LANDLORD, "50", reur(), 7, day(3, 2)),
+            tx("0xc2", "sent", FEES, "1", reur(), 8, day(3, 3)),
+        ];
+        let context = CategoryContext {
+            reserve_address: Some(RESERVE.to_string()),
+            own_addresses: [ME, SAVINGS].map(str::to_string).into(),
+            fee_addresses: [FEES.to_string()].into(),
+        };
+        let categories = categorize(&history, &context);
+        assert_eq!(categories["0xa1"], TxCategory::FiatSettlement);
+        assert_eq!(categories["0xa2"], TxCategory::Internal);
+        assert_eq!(categories["0xa3"], TxCategory::Swap);
+        assert_eq!(categories["0xa4"], TxCategory::Swap);
+        assert_eq!(categories["0xb1"], TxCategory::Recurring);
+        assert_eq!(categories["0xb3"], TxCategory::Recurring);
+        assert_eq!(categories["0xc1"], TxCategory::Transfer);
+        assert_eq!(categories["0xc2"], TxCategory::Fee);
+    }
+
+    #[test]
+    fn names_must_be_unique_and_budgets_positive() {
+        let existing = vec![UserCategory {
+            category_id: "cat-1".into(),
+            name: "Groceries".into(),
+            monthly_budget_eur: None,
+            created_at: Utc::now(),
+        }];
+        let request = |name: &str, budget: Option<&str>| CategoryRequest {
+            name: name.into(),
+            monthly_budget_eur: budget.map(str::to_string),
+        };
+        assert!(validate_request(&request("groceries", None), &existing, None).is_err());
+        assert!(validate_request(&request("Groceries", None), &existing, Some("cat-1")).is_ok());
+        assert!(validate_request(&request("Rent", Some("-5")), &existing, None).is_err());
+        assert!(validate_request(&request(" ", None), &existing, None).is_err());
+        assert_eq!(
+            validate_request(&request(" Rent ", Some("700")), &existing, None).unwrap(),
+            ("Rent".to_string(), Some("700.00".to_string()))
+        );
+    }
+}
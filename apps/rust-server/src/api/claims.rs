@@ -0,0 +1,1124 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Claimable transfers: send to an email address or a link.
+//!
+//! The sender's funds move into a fresh escrow sub-wallet together with a
+//! small AVAX stipend that pays the payout's gas, and the sender gets a
+//! claim link carrying an HMAC-signed token to pass on. The recipient opens
+//! the link, signs up, and claims into one of their wallets. A transfer
+//! bound to an email hash can only be claimed by the user whose wallet is
+//! registered for that email.
+//!
+//! Unclaimed transfers go back to the sender once they expire, either
+//! through the [`ClaimExpiryWorker`](crate::claim_expiry::ClaimExpiryWorker)
+//! or the sender's reclaim call. Whatever is left of the stipend stays in
+//! the escrow.
+
+use std::{
+    env,
+    sync::{Arc, Mutex},
+};
+
+use alloy::primitives::U256;
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use base64ct::{Base64UrlUnpadded, Encoding};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    api::{limits, transactions::send_from_wallet, wallets::ensure_unlocked},
+    auth::{Auth, AuthenticatedUser},
+    blockchain::{
+        avax_fuji, parse_amount, same_address, tokens,
+        transactions::{NonceManager, SendResult},
+        wallet_from_pem, TxBuilder, AVAX_FUJI, NETWORK_FUJI, REUR_TOKEN,
+    },
+    error::{ApiError, StorageContext},
+    providers::email,
+    secret_signer::{SecretKey, SecretSigner},
+    state::AppState,
+    storage::{
+        AuditEvent, AuditEventType, AuditRepository, ClaimStatus, EmailIndexRepository,
+        EncryptedStorage, EscrowRepository, OwnershipEnforcer, StoredClaim, StoredTransaction,
+        TokenType, TxCache, TxDatabase, WalletMetadata, WalletRepository, WalletStatus,
+    },
+};
+
+/// AVAX sent to each escrow alongside the funds to pay for the payout.
+pub const ESCROW_GAS_STIPEND_AVAX: &str = "0.005";
+/// Lifetime of a claim link when the sender does not choose one (7 days).
+const DEFAULT_CLAIM_TTL_HOURS: u32 = 168;
+/// Longest lifetime a sender may choose (30 days).
+const MAX_CLAIM_TTL_HOURS: u32 = 720;
+/// Unsettled claimable transfers a user may have at once.
+const MAX_PENDING_CLAIMS: usize = 20;
+const MAX_NOTE_CHARS: usize = 140;
+/// Page the recipient opens; the token is appended as `?token=`.
+const CLAIM_LINK_BASE_URL_ENV: &str = "CLAIM_LINK_BASE_URL";
+const DEFAULT_CLAIM_LINK_BASE_URL: &str = "http://localhost:3000/claim";
+
+/// Serializes claim status transitions.
+static CLAIM_LOCK: Mutex<()> = Mutex::new(());
+
+// =============================================================================
+// Request/Response Types
+// =============================================================================
+
+/// Request to create a claimable transfer.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct CreateClaimRequest {
+    /// Amount in human-readable units (e.g. "25.00").
+    pub amount: String,
+    /// `native` for AVAX or the rEUR contract address.
+    #[serde(default = "default_native")]
+    pub token: String,
+    /// SHA-256 hash of the recipient's email. Restricts claiming to the user
+    /// whose wallet is registered for it.
+    #[serde(default)]
+    pub to_email_hash: Option<String>,
+    /// Hours until the sender can take the funds back (default 168, max 720).
+    #[serde(default)]
+    pub expires_in_hours: Option<u32>,
+    /// Message shown to the recipient (max 140 characters).
+    #[serde(default)]
+    pub note: Option<String>,
+}
+
+fn default_native() -> String {
+    "native".to_string()
+}
+
+/// A claimable transfer as seen by its sender.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ClaimResponse {
+    pub claim_id: String,
+    pub status: ClaimStatus,
+    pub token: String,
+    pub amount: String,
+    /// Escrow sub-wallet holding the funds.
+    pub escrow_address: String,
+    /// Whether only the owner of a given email can claim.
+    pub email_bound: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    pub funding_tx_hashes: Vec<String>,
+    /// Whether the gas stipend reached the escrow; retried in the background.
+    pub gas_funded: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payout_tx_hash: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+    pub expires_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    /// Link to hand to the recipient. Only returned on creation.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub claim_url: Option<String>,
+}
+
+impl From<&StoredClaim> for ClaimResponse {
+    fn from(claim: &StoredClaim) -> Self {
+        Self {
+            claim_id: claim.claim_id.clone(),
+            status: claim.status,
+            token: claim.token.clone(),
+            amount: claim.amount.clone(),
+            escrow_address: claim.escrow_address.clone(),
+            email_bound: claim.recipient_email_hash.is_some(),
+            note: claim.note.clone(),
+            funding_tx_hashes: claim.funding_tx_hashes.clone(),
+            gas_funded: claim.gas_funded,
+            payout_tx_hash: claim.payout_tx_hash.clone(),
+            last_error: claim.last_error.clone(),
+            expires_at: claim.expires_at,
+            created_at: claim.created_at,
+            claim_url: None,
+        }
+    }
+}
+
+/// The sender's claimable transfers.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ClaimListResponse {
+    pub claims: Vec<ClaimResponse>,
+}
+
+/// What a recipient sees when opening a claim link.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ClaimPreviewResponse {
+    pub status: ClaimStatus,
+    pub token: String,
+    pub amount: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    /// Whether the transfer is reserved for the owner of a specific email.
+    pub email_bound: bool,
+    pub expires_at: DateTime<Utc>,
+    /// Whether the link can still be claimed.
+    pub claimable: bool,
+}
+
+/// Request to claim a transfer.
+#[derive(Debug, Clone, Default, Deserialize, ToSchema)]
+pub struct RedeemClaimRequest {
+    /// Wallet to receive the funds. Ignored for email-bound transfers, which
+    /// pay the wallet registered for the email.
+    #[serde(default)]
+    pub wallet_id: Option<String>,
+}
+
+// =============================================================================
+// Helpers
+// =============================================================================
+
+/// Tokens escrow sub-wallets hold: AVAX and rEUR.
+pub(crate) fn parse_escrow_token(token: &str) -> Result<TokenType, ApiError> {
+    let reur = tokens().by_symbol(NETWORK_FUJI, REUR_TOKEN.symbol);
+    if token == "native" {
+        Ok(TokenType::Native)
+    } else if let Some(reur) = reur.filter(|reur| same_address(token, &reur.address)) {
+        Ok(TokenType::Erc20(reur.address))
+    } else {
+        Err(ApiError::bad_request(
+            "Escrow transfers support AVAX (`native`) and rEUR only",
+        ))
+    }
+}
+
+pub(crate) fn token_decimals(token: &TokenType) -> u8 {
+    tokens().decimals(&AVAX_FUJI, &token_label(token))
+}
+
+/// How a token is stored on escrow records: `native` or the contract address.
+pub(crate) fn token_label(token: &TokenType) -> String {
+    match token {
+        TokenType::Native => "native".to_string(),
+        TokenType::Erc20(contract) => contract.clone(),
+    }
+}
+
+/// Token for a claim link: `{claim_id}.{base64url(hmac(claim_id))}`.
+async fn sign_token(signer: &SecretSigner, claim_id: &str) -> Result<String, ApiError> {
+    let tag = signer
+        .mac(SecretKey::ClaimLink, claim_id.as_bytes())
+        .await?;
+    Ok(format!(
+        "{claim_id}.{}",
+        Base64UrlUnpadded::encode_string(&tag)
+    ))
+}
+
+/// Check a link token's signature and return its claim ID.
+async fn verify_token(signer: &SecretSigner, token: &str) -> Result<String, ApiError> {
+    let invalid = || ApiError::not_found("Claim link not found");
+    let (claim_id, tag) = token.split_once('.').ok_or_else(invalid)?;
+    let tag = Base64UrlUnpadded::decode_vec(tag).map_err(|_| invalid())?;
+    if !signer
+        .verify_mac(SecretKey::ClaimLink, claim_id.as_bytes(), &tag)
+        .await?
+    {
+        return Err(invalid());
+    }
+    Ok(claim_id.to_string())
+}
+
+fn claim_url(token: &str) -> String {
+    let base = env::var(CLAIM_LINK_BASE_URL_ENV)
+        .ok()
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+        .unwrap_or_else(|| DEFAULT_CLAIM_LINK_BASE_URL.to_string());
+    format!("{base}?token={token}")
+}
+
+fn load_claim(storage: &EncryptedStorage, claim_id: &str) -> Result<StoredClaim, ApiError> {
+    EscrowRepository::new(storage)
+        .get_claim(claim_id)
+        .context("Failed to load claim")
+}
+
+/// An owned, active wallet of `user_id`.
+pub(crate) fn active_wallet(
+    storage: &EncryptedStorage,
+    user: &AuthenticatedUser,
+    wallet_id: &str,
+) -> Result<WalletMetadata, ApiError> {
+    let wallet = WalletRepository::new(storage).get(wallet_id)?;
+    if !wallet.is_owned_by(user) {
+        return Err(ApiError::forbidden("You do not own this wallet").with_code("wallet_not_owned"));
+    }
+    match wallet.status {
+        WalletStatus::Deleted => {
+            Err(ApiError::not_found("Wallet has been deleted").with_code("wallet_deleted"))
+        }
+        WalletStatus::Suspended => {
+            Err(ApiError::forbidden("Wallet is suspended").with_code("wallet_suspended"))
+        }
+        _ => Ok(wallet),
+    }
+}
+
+/// Sign and broadcast a transfer from an escrow sub-wallet key.
+async fn send_transfer(
+    private_key_pem: &[u8],
+    to: &str,
+    token: &TokenType,
+    amount: U256,
+) -> Result<SendResult, ApiError> {
+    let signer = wallet_from_pem(private_key_pem)
+        .map_err(|e| ApiError::internal(format!("Failed to create signer: {e}")))?;
+    let builder = TxBuilder::new(avax_fuji(), signer)
+        .await
+        .map_err(|e| ApiError::service_unavailable(format!("Failed to connect: {e}")))?;
+    match token {
+        TokenType::Native => builder.send_native(to, amount, None, None).await,
+        TokenType::Erc20(contract) => builder.send_token(to, contract, amount, None, None).await,
+    }
+    .map_err(|e| {
+        if e.to_string().contains("insufficient funds") {
+            ApiError::unprocessable("Insufficient balance for transaction")
+                .with_code("insufficient_balance")
+        } else {
+            ApiError::service_unavailable(format!("Transaction failed: {e}"))
+        }
+    })
+}
+
+/// Record a transfer in one wallet's history.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn record_transfer(
+    tx_db: &TxDatabase,
+    tx_cache: Option<&TxCache>,
+    result: &SendResult,
+    wallet_id: &str,
+    direction: &str,
+    from: &str,
+    to: &str,
+    amount: &str,
+    token: TokenType,
+) {
+    let tx = StoredTransaction::new_pending(
+        result.tx_hash.clone(),
+        wallet_id.to_string(),
+        None,
+        from.to_string(),
+        to.to_string(),
+        amount.to_string(),
+        token,
+        "fuji".to_string(),
+        result.explorer_url.clone(),
+    );
+    let address = if direction == "sent" { from } else { to };
+    if let Err(e) = tx_db.upsert_transaction(&tx, &[(address.to_string(), direction)]) {
+        tracing::warn!(error = %e, tx_hash = %result.tx_hash, "Failed to store transfer");
+    }
+    if let Some(tx_cache) = tx_cache {
+        tx_cache.invalidate(address);
+    }
+}
+
+/// Funds moved into a new escrow sub-wallet.
+pub(crate) struct EscrowFunding {
+    pub escrow_address: String,
+    pub tx_hashes: Vec<String>,
+    /// False when the separate stipend transfer for a token escrow failed.
+    pub gas_funded: bool,
+    pub last_error: Option<String>,
+}
+
+/// Create the escrow sub-wallet `escrow_id` and fund it from `wallet` with
+/// `amount` plus the gas stipend.
+///
+/// Native transfers carry the stipend in the same transaction; token
+/// transfers send it separately, and a failed stipend is reported through
+/// `gas_funded` rather than as an error. If the main transfer fails the
+/// sub-wallet is discarded.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn fund_escrow(
+    storage: &EncryptedStorage,
+    nonces: &Arc<NonceManager>,
+    tx_db: &TxDatabase,
+    tx_cache: Option<&TxCache>,
+    wallet: &WalletMetadata,
+    escrow_id: &str,
+    token: &TokenType,
+    amount: U256,
+) -> Result<EscrowFunding, ApiError> {
+    let repo = EscrowRepository::new(storage);
+    let escrow_address = repo
+        .create_key(escrow_id)
+        .map_err(|e| ApiError::internal(format!("Failed to create escrow wallet: {e}")))?;
+
+    let stipend = parse_amount(ESCROW_GAS_STIPEND_AVAX, 18).expect("valid stipend");
+    let funding_amount = match token {
+        TokenType::Native => amount + stipend,
+        TokenType::Erc20(_) => amount,
+    };
+    let funding = match send_from_wallet(
+        storage,
+        nonces,
+        wallet,
+        &avax_fuji(),
+        &escrow_address,
+        token,
+        funding_amount,
+        None,
+        None,
+    )
+    .await
+    {
+        Ok(result) => result,
+        Err(e) => {
+            let _ = repo.delete_key(escrow_id);
+            return Err(e);
+        }
+    };
+    record_transfer(
+        tx_db,
+        tx_cache,
+        &funding,
+        &wallet.wallet_id,
+        "sent",
+        &wallet.public_address,
+        &escrow_address,
+        &crate::blockchain::format_amount(funding_amount, token_decimals(token)),
+        token.clone(),
+    );
+
+    let mut funded = EscrowFunding {
+        escrow_address,
+        tx_hashes: vec![funding.tx_hash],
+        gas_funded: true,
+        last_error: None,
+    };
+    if matches!(token, TokenType::Erc20(_)) {
+        match send_stipend(
+            storage,
+            nonces,
+            tx_db,
+            tx_cache,
+            &wallet.wallet_id,
+            &wallet.public_address,
+            &funded.escrow_address,
+        )
+        .await
+        {
+            Ok(tx_hash) => funded.tx_hashes.push(tx_hash),
+            Err(e) => {
+                funded.gas_funded = false;
+                funded.last_error = Some(format!("Gas stipend transfer failed: {}", e.message));
+            }
+        }
+    }
+    Ok(funded)
+}
+
+/// Send the gas stipend from a user's wallet to an escrow sub-wallet and
+/// return the transaction hash.
+pub(crate) async fn send_stipend(
+    storage: &EncryptedStorage,
+    nonces: &Arc<NonceManager>,
+    tx_db: &TxDatabase,
+    tx_cache: Option<&TxCache>,
+    wallet_id: &str,
+    from: &str,
+    escrow_address: &str,
+) -> Result<String, ApiError> {
+    let wallet = WalletRepository::new(storage)
+        .get(wallet_id)
+        .context("Failed to load wallet")?;
+    let stipend = parse_amount(ESCROW_GAS_STIPEND_AVAX, 18).expect("valid stipend");
+    let result = send_from_wallet(
+        storage,
+        nonces,
+        &wallet,
+        &avax_fuji(),
+        escrow_address,
+        &TokenType::Native,
+        stipend,
+        None,
+        None,
+    )
+    .await?;
+    record_transfer(
+        tx_db,
+        tx_cache,
+        &result,
+        wallet_id,
+        "sent",
+        from,
+        escrow_address,
+        ESCROW_GAS_STIPEND_AVAX,
+        TokenType::Native,
+    );
+    Ok(result.tx_hash)
+}
+
+/// Retry the gas stipend for a claim whose stipend transfer failed.
+pub(crate) async fn fund_gas(
+    storage: &EncryptedStorage,
+    nonces: &Arc<NonceManager>,
+    tx_db: &TxDatabase,
+    tx_cache: Option<&TxCache>,
+    claim: &mut StoredClaim,
+) -> Result<(), ApiError> {
+    match send_stipend(
+        storage,
+        nonces,
+        tx_db,
+        tx_cache,
+        &claim.sender_wallet_id,
+        &claim.sender_address,
+        &claim.escrow_address,
+    )
+    .await
+    {
+        Ok(tx_hash) => {
+            claim.funding_tx_hashes.push(tx_hash);
+            claim.gas_funded = true;
+            claim.last_error = None;
+            Ok(())
+        }
+        Err(e) => {
+            claim.last_error = Some(format!("Gas stipend transfer failed: {}", e.message));
+            Err(e)
+        }
+    }
+}
+
+/// Send the full escrowed amount from sub-wallet `escrow_id` to a user's
+/// wallet and record it in that wallet's history.
+///
+/// An escrow without enough balance maps to a conflict: its funding has not
+/// confirmed yet.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn pay_out(
+    storage: &EncryptedStorage,
+    tx_db: &TxDatabase,
+    tx_cache: Option<&TxCache>,
+    escrow_id: &str,
+    escrow_address: &str,
+    token: &str,
+    amount: &str,
+    to_wallet_id: &str,
+    to_address: &str,
+) -> Result<String, ApiError> {
+    let token = parse_escrow_token(token)?;
+    let value = parse_amount(amount, token_decimals(&token))
+        .map_err(|e| ApiError::internal(format!("Stored escrow amount is invalid: {e}")))?;
+    let key = EscrowRepository::new(storage)
+        .read_key(escrow_id)
+        .map_err(|e| ApiError::internal(format!("Failed to read escrow key: {e}")))?;
+    let result = send_transfer(&key, to_address, &token, value)
+        .await
+        .map_err(|e| {
+            if e.status == StatusCode::UNPROCESSABLE_ENTITY {
+                ApiError::conflict("The escrow funding is not confirmed yet; try again shortly")
+            } else {
+                e
+            }
+        })?;
+    record_transfer(
+        tx_db,
+        tx_cache,
+        &result,
+        to_wallet_id,
+        "received",
+        escrow_address,
+        to_address,
+        amount,
+        token,
+    );
+    Ok(result.tx_hash)
+}
+
+/// Pay a pending claim out of its escrow to `to_address`.
+///
+/// Moves the claim to `Settling` first so a concurrent claim and reclaim
+/// cannot both send. On failure the claim returns to `Pending`.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn settle_claim(
+    storage: &EncryptedStorage,
+    tx_db: &TxDatabase,
+    tx_cache: Option<&TxCache>,
+    claim_id: &str,
+    to_wallet_id: &str,
+    to_address: &str,
+    outcome: ClaimStatus,
+    claimed_by: Option<&str>,
+) -> Result<StoredClaim, ApiError> {
+    let repo = EscrowRepository::new(storage);
+    let mut claim = {
+        let _guard = CLAIM_LOCK
+            .lock()
+            .map_err(|_| ApiError::internal("Claim lock poisoned"))?;
+        let mut claim = load_claim(storage, claim_id)?;
+        match claim.status {
+            ClaimStatus::Pending => {}
+            ClaimStatus::Settling => {
+                return Err(ApiError::conflict("This transfer is being settled"))
+            }
+            ClaimStatus::Claimed => return Err(ApiError::conflict("Already claimed")),
+            ClaimStatus::Reclaimed => return Err(ApiError::conflict("Returned to the sender")),
+        }
+        if !claim.gas_funded {
+            return Err(ApiError::conflict(
+                "The escrow is still being funded; try again shortly",
+            ));
+        }
+        claim.status = ClaimStatus::Settling;
+        claim.updated_at = Utc::now();
+        repo.update_claim(&claim)
+            .map_err(|e| ApiError::internal(format!("Failed to update claim: {e}")))?;
+        claim
+    };
+
+    let sent = pay_out(
+        storage,
+        tx_db,
+        tx_cache,
+        &claim.claim_id,
+        &claim.escrow_address,
+        &claim.token,
+        &claim.amount,
+        to_wallet_id,
+        to_address,
+    )
+    .await;
+
+    claim.updated_at = Utc::now();
+    let tx_hash = match sent {
+        Ok(tx_hash) => tx_hash,
+        Err(e) => {
+            claim.status = ClaimStatus::Pending;
+            claim.last_error = Some(e.message.clone());
+            let _ = repo.update_claim(&claim);
+            return Err(e);
+        }
+    };
+
+    claim.status = outcome;
+    claim.claimed_by_user_id = claimed_by.map(str::to_string);
+    claim.payout_wallet_id = Some(to_wallet_id.to_string());
+    claim.payout_tx_hash = Some(tx_hash);
+    claim.last_error = None;
+    repo.update_claim(&claim)
+        .map_err(|e| ApiError::internal(format!("Failed to update claim: {e}")))?;
+    Ok(claim)
+}
+
+// =============================================================================
+// Handlers
+// =============================================================================
+
+/// Send funds to an email address or a link.
+///
+/// Moves the amount and a gas stipend into an escrow sub-wallet and returns
+/// the claim link for the recipient.
+#[utoipa::path(
+    post,
+    path = "/v1/wallets/{wallet_id}/claims",
+    tag = "Claims",
+    params(("wallet_id" = String, Path, description = "Sending wallet ID")),
+    request_body = CreateClaimRequest,
+    security(("bearer" = [])),
+    responses(
+        (status = 201, description = "Escrow funded; claim link created", body = ClaimResponse),
+        (status = 400, description = "Invalid amount, token, email hash, expiry or note"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - not wallet owner or wallet suspended"),
+        (status = 404, description = "Wallet not found"),
+        (status = 422, description = "Insufficient balance, spending limit exceeded, or too many unclaimed transfers"),
+        (status = 503, description = "Blockchain network unavailable")
+    )
+)]
+pub async fn create_claim(
+    Auth(user): Auth,
+    State(state): State<AppState>,
+    Path(wallet_id): Path<String>,
+    Json(request): Json<CreateClaimRequest>,
+) -> Result<(StatusCode, Json<ClaimResponse>), ApiError> {
+    let storage = state.storage();
+    let wallet = active_wallet(storage, &user, &wallet_id)?;
+    ensure_unlocked(&wallet)?;
+
+    let token = parse_escrow_token(&request.token)?;
+    let amount = parse_amount(&request.amount, token_decimals(&token))
+        .map_err(|e| ApiError::bad_request(format!("Invalid amount: {e}")))?;
+    if amount.is_zero() {
+        return Err(
+            ApiError::bad_request("Amount must be greater than zero").with_code("invalid_amount")
+        );
+    }
+    if let Some(hash) = &request.to_email_hash {
+        if !email::validate_email_hash(hash) {
+            return Err(ApiError::bad_request(
+                "to_email_hash must be 64 lowercase hex characters",
+            ));
+        }
+    }
+    let ttl_hours = request.expires_in_hours.unwrap_or(DEFAULT_CLAIM_TTL_HOURS);
+    if !(1..=MAX_CLAIM_TTL_HOURS).contains(&ttl_hours) {
+        return Err(ApiError::bad_request(format!(
+            "expires_in_hours must be between 1 and {MAX_CLAIM_TTL_HOURS}"
+        )));
+    }
+    let note = request
+        .note
+        .map(|n| n.trim().to_string())
+        .filter(|n| !n.is_empty());
+    if note
+        .as_ref()
+        .is_some_and(|n| n.chars().count() > MAX_NOTE_CHARS)
+    {
+        return Err(ApiError::bad_request(format!(
+            "note must be at most {MAX_NOTE_CHARS} characters"
+        )));
+    }
+
+    let repo = EscrowRepository::new(storage);
+    let unsettled = repo
+        .list_claims_by_sender(&user.user_id)
+        .map_err(|e| ApiError::internal(format!("Failed to list claims: {e}")))?
+        .iter()
+        .filter(|c| matches!(c.status, ClaimStatus::Pending | ClaimStatus::Settling))
+        .count();
+    if unsettled >= MAX_PENDING_CLAIMS {
+        return Err(ApiError::unprocessable(format!(
+            "At most {MAX_PENDING_CLAIMS} unclaimed transfers can be open at once"
+        )));
+    }
+
+    let tx_db = state
+        .tx_db
+        .as_ref()
+        .expect("transaction database must be configured");
+    let tx_cache = state.tx_cache.as_deref();
+    let claim_id = Uuid::new_v4().to_string();
+    let reservation = limits::reserve_spending(
+        &state,
+        &wallet,
+        &[(&token_label(&token), request.amount.trim())],
+        NETWORK_FUJI,
+    )
+    .await?;
+    let funding = fund_escrow(
+        storage,
+        &state.nonce_manager,
+        tx_db,
+        tx_cache,
+        &wallet,
+        &claim_id,
+        &token,
+        amount,
+    )
+    .await?;
+    reservation.commit(&funding.tx_hashes[0]);
+
+    let now = Utc::now();
+    let claim = StoredClaim {
+        claim_id: claim_id.clone(),
+        sender_user_id: user.user_id.clone(),
+        sender_wallet_id: wallet_id.clone(),
+        sender_address: wallet.public_address.clone(),
+        escrow_address: funding.escrow_address,
+        token: token_label(&token),
+        amount: request.amount.trim().to_string(),
+        recipient_email_hash: request.to_email_hash,
+        note,
+        funding_tx_hashes: funding.tx_hashes,
+        gas_funded: funding.gas_funded,
+        status: ClaimStatus::Pending,
+        expires_at: now + Duration::hours(i64::from(ttl_hours)),
+        claimed_by_user_id: None,
+        payout_wallet_id: None,
+        payout_tx_hash: None,
+        // A failed stipend is retried by the expiry worker.
+        last_error: funding.last_error,
+        created_at: now,
+        updated_at: now,
+    };
+    repo.create_claim(&claim)
+        .map_err(|e| ApiError::internal(format!("Failed to store claim: {e}")))?;
+
+    let event = AuditEvent::new(AuditEventType::ClaimCreated)
+        .with_user(&user.user_id)
+        .with_resource("claim", &claim_id)
+        .with_details(serde_json::json!({
+            "wallet_id": wallet_id,
+            "amount": claim.amount,
+            "token": claim.token,
+            "email_bound": claim.recipient_email_hash.is_some(),
+            "expires_at": claim.expires_at,
+        }));
+    let _ = AuditRepository::new(storage).log(&event);
+
+    let mut response = ClaimResponse::from(&claim);
+    response.claim_url = Some(claim_url(
+        &sign_token(&state.secret_signer, &claim_id).await?,
+    ));
+    Ok((StatusCode::CREATED, Json(response)))
+}
+
+/// List the caller's claimable transfers, newest first.
+#[utoipa::path(
+    get,
+    path = "/v1/claims",
+    tag = "Claims",
+    security(("bearer" = [])),
+    responses(
+        (status = 200, description = "Claimable transfers sent by the caller", body = ClaimListResponse),
+        (status = 401, description = "Unauthorized")
+    )
+)]
+pub async fn list_claims(
+    Auth(user): Auth,
+    State(state): State<AppState>,
+) -> Result<Json<ClaimListResponse>, ApiError> {
+    let claims = EscrowRepository::new(state.storage())
+        .list_claims_by_sender(&user.user_id)
+        .map_err(|e| ApiError::internal(format!("Failed to list claims: {e}")))?;
+    Ok(Json(ClaimListResponse {
+        claims: claims.iter().map(ClaimResponse::from).collect(),
+    }))
+}
+
+/// Show what a claim link holds.
+///
+/// No authentication required, so recipients can see the transfer before
+/// signing up.
+#[utoipa::path(
+    get,
+    path = "/v1/claim-links/{token}",
+    tag = "Claims",
+    params(("token" = String, Path, description = "Token from the claim link")),
+    responses(
+        (status = 200, description = "Claim details", body = ClaimPreviewResponse),
+        (status = 404, description = "Unknown or tampered link")
+    )
+)]
+pub async fn preview_claim(
+    State(state): State<AppState>,
+    Path(token): Path<String>,
+) -> Result<Json<ClaimPreviewResponse>, ApiError> {
+    let storage = state.storage();
+    let claim_id = verify_token(&state.secret_signer, &token).await?;
+    let claim = load_claim(storage, &claim_id)?;
+    Ok(Json(ClaimPreviewResponse {
+        status: claim.status,
+        claimable: claim.status == ClaimStatus::Pending && claim.expires_at > Utc::now(),
+        token: claim.token,
+        amount: claim.amount,
+        note: claim.note,
+        email_bound: claim.recipient_email_hash.is_some(),
+        expires_at: claim.expires_at,
+    }))
+}
+
+/// Claim a transfer into one of the caller's wallets.
+#[utoipa::path(
+    post,
+    path = "/v1/claim-links/{token}/claim",
+    tag = "Claims",
+    params(("token" = String, Path, description = "Token from the claim link")),
+    request_body = RedeemClaimRequest,
+    security(("bearer" = [])),
+    responses(
+        (status = 200, description = "Funds sent to the caller's wallet", body = ClaimResponse),
+        (status = 400, description = "No wallet given, or the caller is the sender"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Reserved for another recipient, or not the caller's wallet"),
+        (status = 404, description = "Unknown link or wallet"),
+        (status = 409, description = "Expired, already settled, or escrow funding not confirmed yet"),
+        (status = 503, description = "Blockchain network unavailable")
+    )
+)]
+pub async fn redeem_claim(
+    Auth(user): Auth,
+    State(state): State<AppState>,
+    Path(token): Path<String>,
+    Json(request): Json<RedeemClaimRequest>,
+) -> Result<Json<ClaimResponse>, ApiError> {
+    let storage = state.storage();
+    let claim_id = verify_token(&state.secret_signer, &token).await?;
+    let claim = load_claim(storage, &claim_id)?;
+    if claim.sender_user_id == user.user_id {
+        return Err(ApiError::bad_request(
+            "You sent this transfer; reclaim it after it expires instead",
+        ));
+    }
+    if claim.status == ClaimStatus::Pending && claim.expires_at <= Utc::now() {
+        return Err(ApiError::conflict("This claim link has expired"));
+    }
+
+    let tx_db = state
+        .tx_db
+        .as_ref()
+        .expect("transaction database must be configured");
+    let wallet = match &claim.recipient_email_hash {
+        Some(hash) => {
+            let lookup_key = email::hmac_lookup_key(&state.email_hmac_key, hash);
+            let entry = EmailIndexRepository::new(tx_db.clone())
+                .lookup(&lookup_key)
+                .map_err(|e| ApiError::internal(format!("Email lookup failed: {e}")))?;
+            let reserved =
+                || ApiError::forbidden("This transfer is reserved for another recipient");
+            let entry = entry.ok_or_else(reserved)?;
+            let wallet = active_wallet(storage, &user, &entry.wallet_id).map_err(|e| {
+                if e.status == StatusCode::FORBIDDEN {
+                    reserved()
+                } else {
+                    e
+                }
+            })?;
+            if request
+                .wallet_id
+                .as_ref()
+                .is_some_and(|id| id != &wallet.wallet_id)
+            {
+                return Err(ApiError::bad_request(
+                    "Email-bound transfers are paid to the wallet registered for the email",
+                ));
+            }
+            wallet
+        }
+        None => {
+            let wallet_id = request
+                .wallet_id
+                .as_deref()
+                .ok_or_else(|| ApiError::bad_request("wallet_id is required"))?;
+            active_wallet(storage, &user, wallet_id)?
+        }
+    };
+
+    let claim = settle_claim(
+        storage,
+        tx_db,
+        state.tx_cache.as_deref(),
+        &claim_id,
+        &wallet.wallet_id,
+        &wallet.public_address,
+        ClaimStatus::Claimed,
+        Some(&user.user_id),
+    )
+    .await?;
+
+    let event = AuditEvent::new(AuditEventType::ClaimRedeemed)
+        .with_user(&user.user_id)
+        .with_resource("claim", &claim_id)
+        .with_details(serde_json::json!({
+            "wallet_id": wallet.wallet_id,
+            "tx_hash": claim.payout_tx_hash,
+        }));
+    let _ = AuditRepository::new(storage).log(&event);
+
+    Ok(Json(ClaimResponse::from(&claim)))
+}
+
+/// Take back an expired, unclaimed transfer.
+///
+/// The expiry worker does this automatically; this call lets the sender do
+/// it without waiting.
+#[utoipa::path(
+    post,
+    path = "/v1/claims/{claim_id}/reclaim",
+    tag = "Claims",
+    params(("claim_id" = String, Path, description = "Claim ID")),
+    security(("bearer" = [])),
+    responses(
+        (status = 200, description = "Funds returned to the sending wallet", body = ClaimResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Not the sender"),
+        (status = 404, description = "Claim not found"),
+        (status = 409, description = "Not expired yet, or already settled"),
+        (status = 503, description = "Blockchain network unavailable")
+    )
+)]
+pub async fn reclaim_claim(
+    Auth(user): Auth,
+    State(state): State<AppState>,
+    Path(claim_id): Path<String>,
+) -> Result<Json<ClaimResponse>, ApiError> {
+    let storage = state.storage();
+    let claim = load_claim(storage, &claim_id)?;
+    if claim.sender_user_id != user.user_id {
+        return Err(ApiError::forbidden("You did not send this transfer"));
+    }
+    if claim.status == ClaimStatus::Pending && claim.expires_at > Utc::now() {
+        return Err(ApiError::conflict(
+            "The claim link is still valid; it can be reclaimed once it expires",
+        ));
+    }
+
+    let tx_db = state
+        .tx_db
+        .as_ref()
+        .expect("transaction database must be configured");
+    let claim = settle_claim(
+        storage,
+        tx_db,
+        state.tx_cache.as_deref(),
+        &claim_id,
+        &claim.sender_wallet_id,
+        &claim.sender_address,
+        ClaimStatus::Reclaimed,
+        None,
+    )
+    .await?;
+
+    let event = AuditEvent::new(AuditEventType::ClaimReclaimed)
+        .with_user(&user.user_id)
+        .with_resource("claim", &claim_id)
+        .with_details(serde_json::json!({ "tx_hash": claim.payout_tx_hash }));
+    let _ = AuditRepository::new(storage).log(&event);
+
+    Ok(Json(ClaimResponse::from(&claim)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::{AuthenticatedUser, Role};
+
+    fn auth(user_id: &str) -> Auth {
+        Auth(AuthenticatedUser {
+            user_id: user_id.to_string(),
+            role: Role::Client,
+            session_id: None,
+            issuer: "https://test.clerk.dev".to_string(),
+            expires_at: Utc::now().timestamp() + 3600,
+            tenant_id: None,
+        })
+    }
+
+    fn stored_claim(state: &AppState, expires_at: DateTime<Utc>) -> StoredClaim {
+        let now = Utc::now();
+        let claim = StoredClaim {
+            claim_id: Uuid::new_v4().to_string(),
+            sender_user_id: "sender".to_string(),
+            sender_wallet_id: "wallet-s".to_string(),
+            sender_address: "0x1111111111111111111111111111111111111111".to_string(),
+            escrow_address: "0x2222222222222222222222222222222222222222".to_string(),
+            token: "native".to_string(),
+            amount: "1.5".to_string(),
+            recipient_email_hash: Some("ab".repeat(32)),
+            note: Some("Dinner".to_string()),
+            funding_tx_hashes: vec!["0xfund".to_string()],
+            gas_funded: true,
+            status: ClaimStatus::Pending,
+            expires_at,
+            claimed_by_user_id: None,
+            payout_wallet_id: None,
+            payout_tx_hash: None,
+            last_error: None,
+            created_at: now,
+            updated_at: now,
+        };
+        EscrowRepository::new(state.storage())
+            .create_claim(&claim)
+            .unwrap();
+        claim
+    }
+
+    async fn link_token(state: &AppState, claim: &StoredClaim) -> String {
+        sign_token(&state.secret_signer, &claim.claim_id)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn link_tokens_reject_tampering() {
+        let signer = &AppState::default().secret_signer;
+        let token = sign_token(signer, "claim-1").await.unwrap();
+        assert_eq!(verify_token(signer, &token).await.unwrap(), "claim-1");
+        let forged = token.replacen("claim-1", "claim-2", 1);
+        assert!(verify_token(signer, &forged).await.is_err());
+        let other = &AppState::default().secret_signer;
+        assert!(verify_token(other, &token).await.is_err());
+    }
+
+    #[test]
+    fn only_avax_and_reur_are_supported() {
+        assert!(matches!(
+            parse_escrow_token("native"),
+            Ok(TokenType::Native)
+        ));
+        assert!(matches!(
+            parse_escrow_token(&REUR_TOKEN.fuji_address.unwrap().to_lowercase()),
+            Ok(TokenType::Erc20(_))
+        ));
+        let err = parse_escrow_token("0x0000000000000000000000000000000000000001").unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn links_preview_publicly_and_guard_who_can_claim() {
+        let state = AppState::default();
+        let claim = stored_claim(&state, Utc::now() + Duration::hours(1));
+        let token = link_token(&state, &claim).await;
+
+        let Json(preview) = preview_claim(State(state.clone()), Path(token.clone()))
+            .await
+            .unwrap();
+        assert!(preview.claimable && preview.email_bound);
+        assert_eq!(preview.amount, "1.5");
+
+        let own = redeem_claim(
+            auth("sender"),
+            State(state.clone()),
+            Path(token.clone()),
+            Json(RedeemClaimRequest::default()),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(own.status, StatusCode::BAD_REQUEST);
+
+        // Nobody has registered the email yet.
+        let other = redeem_claim(
+            auth("someone-else"),
+            State(state.clone()),
+            Path(token),
+            Json(RedeemClaimRequest::default()),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(other.status, StatusCode::FORBIDDEN);
+
+        let early = reclaim_claim(
+            auth("sender"),
+            State(state.clone()),
+            Path(claim.claim_id.clone()),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(early.status, StatusCode::CONFLICT);
+    }
+
+    #[tokio::test]
+    async fn expired_links_cannot_be_claimed() {
+        let state = AppState::default();
+        let claim = stored_claim(&state, Utc::now() - Duration::minutes(1));
+        let token = link_token(&state, &claim).await;
+
+        let Json(preview) = preview_claim(State(state.clone()), Path(token.clone()))
+            .await
+            .unwrap();
+        assert!(!preview.claimable);
+        let err = redeem_claim(
+            auth("recipient"),
+            State(state),
+            Path(token),
+            Json(RedeemClaimRequest::default()),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::CONFLICT);
+    }
+}
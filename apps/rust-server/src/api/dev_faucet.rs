@@ -0,0 +1,345 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Fuji test-funds faucet for developers and testers.
+//!
+//! `POST /v1/dev/faucet` sends test AVAX from the fiat reserve wallet to one
+//! of the caller's wallets and mints test rEUR to it, so flows can be tried
+//! without external faucets. Both sends go through the reserve send queue
+//! like every other reserve-signed transaction (see
+//! [`crate::api::reserve_queue`]); the rEUR mint needs the reserve wallet to
+//! hold `MINTER_ROLE`.
+//!
+//! Each user gets at most `FAUCET_DAILY_DRIPS` drips per UTC day. The module
+//! is only compiled with the `dev` feature.
+
+use axum::{extract::State, Json};
+use serde::{Deserialize, Serialize};
+use utoipa::{OpenApi, ToSchema};
+
+use crate::{
+    api::{
+        claims::active_wallet,
+        fiat::{
+            ensure_minter_role, ensure_service_wallet, mint_error, resolve_reur_contract_address,
+        },
+        reserve_queue,
+    },
+    auth::Auth,
+    blockchain::{
+        avax_fuji, minter::encode_mint_call, parse_amount, wallet_from_pem, AvaxClient, TxBuilder,
+    },
+    error::ApiError,
+    state::AppState,
+    storage::{
+        AuditEvent, AuditEventType, AuditRepository, CacheResource, FaucetRepository,
+        FiatServiceWalletRepository, ReserveSendKind, StoredTransaction, TokenType,
+    },
+};
+
+const DEFAULT_AVAX_AMOUNT: &str = "0.05";
+const DEFAULT_REUR_AMOUNT: &str = "100";
+const DEFAULT_DAILY_DRIPS: u32 = 3;
+const REUR_DECIMALS: u8 = 6;
+
+/// Held while a drip is checked against the daily cap and sent, so two
+/// concurrent requests cannot both take the last drip.
+static DRIP_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+/// Faucet amounts and cap.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FaucetConfig {
+    /// AVAX sent per drip; `"0"` sends none.
+    pub avax_amount: String,
+    /// rEUR minted per drip; `"0"` mints none.
+    pub reur_amount: String,
+    /// Drips per user per UTC day.
+    pub daily_drips: u32,
+}
+
+impl FaucetConfig {
+    /// Read `FAUCET_AVAX_AMOUNT`, `FAUCET_REUR_AMOUNT` and
+    /// `FAUCET_DAILY_DRIPS`.
+    pub fn from_env() -> Result<Self, String> {
+        Self::from_lookup(|name| std::env::var(name).ok())
+    }
+
+    pub fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Result<Self, String> {
+        let amount = |name: &str, default: &str, decimals: u8| {
+            let value = lookup(name).map_or_else(|| default.to_string(), |v| v.trim().to_string());
+            parse_amount(&value, decimals).map_err(|e| format!("{name}: {e}"))?;
+            Ok::<_, String>(value)
+        };
+        let daily_drips = match lookup("FAUCET_DAILY_DRIPS") {
+            Some(v) => v
+                .trim()
+                .parse()
+                .map_err(|e| format!("FAUCET_DAILY_DRIPS: {e}"))?,
+            None => DEFAULT_DAILY_DRIPS,
+        };
+        Ok(Self {
+            avax_amount: amount("FAUCET_AVAX_AMOUNT", DEFAULT_AVAX_AMOUNT, 18)?,
+            reur_amount: amount("FAUCET_REUR_AMOUNT", DEFAULT_REUR_AMOUNT, REUR_DECIMALS)?,
+            daily_drips,
+        })
+    }
+}
+
+/// Request for test funds.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct FaucetRequest {
+    /// Wallet to fund; must belong to the caller.
+    pub wallet_id: String,
+}
+
+/// One faucet transfer.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct FaucetTransfer {
+    /// `"native"` or the rEUR contract address.
+    pub token: String,
+    pub amount: String,
+    pub tx_hash: String,
+    pub explorer_url: String,
+}
+
+/// Test funds sent to a wallet.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct FaucetResponse {
+    pub wallet_id: String,
+    pub address: String,
+    pub transfers: Vec<FaucetTransfer>,
+    /// Drips left for the caller today (UTC).
+    pub drips_remaining_today: u32,
+}
+
+/// Store a faucet transfer in the recipient's history.
+fn record_transfer(
+    state: &AppState,
+    wallet_id: &str,
+    from: &str,
+    to: &str,
+    token: TokenType,
+    transfer: &FaucetTransfer,
+) {
+    let Some(tx_db) = &state.tx_db else {
+        return;
+    };
+    let tx = StoredTransaction::new_pending(
+        transfer.tx_hash.clone(),
+        wallet_id.to_string(),
+        None,
+        from.to_string(),
+        to.to_string(),
+        transfer.amount.clone(),
+        token,
+        avax_fuji().id.to_string(),
+        transfer.explorer_url.clone(),
+    );
+    if let Err(e) = tx_db.upsert_transaction(&tx, &[(to.to_string(), "received")]) {
+        tracing::warn!(error = %e, "Failed to store faucet transaction");
+    }
+    state
+        .cache_bus
+        .invalidate(to, CacheResource::TransactionHistory);
+}
+
+/// Send test AVAX and mint test rEUR to a wallet.
+///
+/// Development builds only. Funds come from the fiat reserve wallet on
+/// Fuji; each user may drip `FAUCET_DAILY_DRIPS` times per UTC day.
+#[utoipa::path(
+    post,
+    path = "/v1/dev/faucet",
+    tag = "Development",
+    request_body = FaucetRequest,
+    security(("bearer" = [])),
+    responses(
+        (status = 200, description = "Test funds sent", body = FaucetResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - not wallet owner"),
+        (status = 404, description = "Wallet not found"),
+        (status = 409, description = "Reserve wallet lacks MINTER_ROLE"),
+        (status = 429, description = "Daily faucet cap reached"),
+        (status = 503, description = "Reserve wallet not initialized or chain unavailable")
+    )
+)]
+pub async fn request_faucet(
+    Auth(user): Auth,
+    State(state): State<AppState>,
+    Json(request): Json<FaucetRequest>,
+) -> Result<Json<FaucetResponse>, ApiError> {
+    let config = FaucetConfig::from_env().map_err(ApiError::service_unavailable)?;
+    let storage = state.storage();
+    let wallet = active_wallet(storage, &user, &request.wallet_id)?;
+
+    let _drip_guard = DRIP_LOCK.lock().await;
+    let faucet = FaucetRepository::new(storage);
+    let today = chrono::Utc::now().date_naive();
+    let used = faucet.get(&user.user_id)?.map_or(0, |u| u.drips_on(today));
+    if used >= config.daily_drips {
+        return Err(ApiError::too_many_requests(format!(
+            "Faucet limit of {} drips per day reached",
+            config.daily_drips
+        ))
+        .with_code("faucet_limit_reached"));
+    }
+
+    let service_wallet = ensure_service_wallet(storage)?;
+    let service_address = service_wallet.public_address.clone();
+    let to = wallet.public_address.clone();
+    let avax_wei = parse_amount(&config.avax_amount, 18)
+        .map_err(|e| ApiError::internal(format!("Invalid faucet amount: {e}")))?;
+    let reur_minor = parse_amount(&config.reur_amount, REUR_DECIMALS)
+        .map_err(|e| ApiError::internal(format!("Invalid faucet amount: {e}")))?;
+
+    let reserve_builder = move || async move {
+        let key = FiatServiceWalletRepository::new(storage)
+            .read_private_key()
+            .map_err(|e| ApiError::internal(format!("Failed to read service wallet key: {e}")))?;
+        let eth_wallet = wallet_from_pem(&key).map_err(|e| {
+            ApiError::internal(format!("Failed to load service wallet signer: {e}"))
+        })?;
+        TxBuilder::new(avax_fuji(), eth_wallet)
+            .await
+            .map_err(|e| ApiError::service_unavailable(format!("Failed to connect to chain: {e}")))
+    };
+
+    let mut transfers = Vec::new();
+    if !avax_wei.is_zero() {
+        let sent = reserve_queue::submit(
+            storage,
+            ReserveSendKind::FaucetDrip,
+            &[],
+            &to,
+            &config.avax_amount,
+            || async {
+                reserve_builder()
+                    .await?
+                    .send_native(&to, avax_wei, None, None)
+                    .await
+                    .map_err(|e| ApiError::service_unavailable(format!("Faucet send failed: {e}")))
+            },
+        )
+        .await?;
+        let transfer = FaucetTransfer {
+            token: "native".to_string(),
+            amount: config.avax_amount.clone(),
+            tx_hash: sent.tx_hash,
+            explorer_url: sent.explorer_url,
+        };
+        record_transfer(
+            &state,
+            &wallet.wallet_id,
+            &service_address,
+            &to,
+            TokenType::Native,
+            &transfer,
+        );
+        transfers.push(transfer);
+    }
+
+    if !reur_minor.is_zero() {
+        let contract = resolve_reur_contract_address()?;
+        let client = AvaxClient::fuji().await.map_err(|e| {
+            ApiError::service_unavailable(format!("Failed to connect to chain: {e}"))
+        })?;
+        ensure_minter_role(&client, &contract, &service_address).await?;
+        let calldata = encode_mint_call(&to, reur_minor)
+            .map_err(|e| ApiError::internal(format!("Failed to encode mint call: {e}")))?;
+        let sent = reserve_queue::submit(
+            storage,
+            ReserveSendKind::FaucetMint,
+            &[],
+            &to,
+            &config.reur_amount,
+            || async {
+                reserve_builder()
+                    .await?
+                    .send_contract_call(&contract, calldata, None, None, None)
+                    .await
+                    .map_err(|e| mint_error(e, &service_address, &contract))
+            },
+        )
+        .await?;
+        let transfer = FaucetTransfer {
+            token: contract.clone(),
+            amount: config.reur_amount.clone(),
+            tx_hash: sent.tx_hash,
+            explorer_url: sent.explorer_url,
+        };
+        record_transfer(
+            &state,
+            &wallet.wallet_id,
+            &service_address,
+            &to,
+            TokenType::Erc20(contract),
+            &transfer,
+        );
+        transfers.push(transfer);
+    }
+
+    let drips = faucet.record_drip(&user.user_id)?;
+    let event = AuditEvent::new(AuditEventType::TransactionBroadcast)
+        .with_user(&user.user_id)
+        .with_resource("wallet", &wallet.wallet_id)
+        .with_details(serde_json::json!({
+            "source": "dev_faucet",
+            "to": to,
+            "tx_hashes": transfers.iter().map(|t| t.tx_hash.as_str()).collect::<Vec<_>>(),
+            "avax_amount": config.avax_amount,
+            "reur_amount": config.reur_amount,
+        }));
+    let _ = AuditRepository::new(storage).log(&event);
+
+    Ok(Json(FaucetResponse {
+        wallet_id: wallet.wallet_id,
+        address: to,
+        transfers,
+        drips_remaining_today: config.daily_drips.saturating_sub(drips),
+    }))
+}
+
+/// OpenAPI document of the development endpoints, merged into the main
+/// document in `dev` builds.
+#[derive(OpenApi)]
+#[openapi(
+    paths(request_faucet),
+    components(schemas(FaucetRequest, FaucetResponse, FaucetTransfer)),
+    tags((name = "Development", description = "Development-only helpers (dev builds)"))
+)]
+pub struct FaucetApiDoc;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn config_defaults_and_validation() {
+        let lookup = |vars: &'static [(&'static str, &'static str)]| {
+            move |name: &str| {
+                vars.iter()
+                    .find(|(key, _)| *key == name)
+                    .map(|(_, v)| v.to_string())
+            }
+        };
+        assert_eq!(
+            FaucetConfig::from_lookup(lookup(&[])),
+            Ok(FaucetConfig {
+                avax_amount: DEFAULT_AVAX_AMOUNT.to_string(),
+                reur_amount: DEFAULT_REUR_AMOUNT.to_string(),
+                daily_drips: DEFAULT_DAILY_DRIPS,
+            })
+        );
+        let config = FaucetConfig::from_lookup(lookup(&[
+            ("FAUCET_REUR_AMOUNT", "0"),
+            ("FAUCET_DAILY_DRIPS", "10"),
+        ]))
+        .unwrap();
+        assert_eq!(config.reur_amount, "0");
+        assert_eq!(config.daily_drips, 10);
+
+        assert!(FaucetConfig::from_lookup(lookup(&[("FAUCET_AVAX_AMOUNT", "lots")])).is_err());
+        assert!(FaucetConfig::from_lookup(lookup(&[("FAUCET_DAILY_DRIPS", "-1")])).is_err());
+    }
+}
@@ -0,0 +1,1183 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Escrowed conditional payments between users.
+//!
+//! The payer moves funds into an escrow sub-wallet (see [`super::claims`] for
+//! how sub-wallets are funded) and names a payee. From there:
+//!
+//! ```text
+//!            request-release          dispute
+//!  funded ──────────────────▶ release_requested ──────▶ disputed
+//!    │  │                        │        │                │  │
+//!    │  └─── approve (payer) ────┼────────┴── approve ─────┘  │
+//!    │                           ▼                            │
+//!    │                       released ◀── resolve (arbiter) ──┤
+//!    │                                                        │
+//!    └── refund (payee any time, payer after expiry) ──▶ refunded
+//! ```
+//!
+//! Timeouts keep a payment from being stuck: if the payee never requests
+//! release before `expires_at` the payment is refunded, and if the payer
+//! neither approves nor disputes a release request before its
+//! `review_deadline` it is released. Disputes wait for an admin arbiter.
+//!
+//! Every transition is kept in the payment's history and written to the
+//! audit log.
+
+use std::sync::Mutex;
+
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use super::claims::{
+    active_wallet, fund_escrow, parse_escrow_token, pay_out, token_decimals, token_label,
+};
+use crate::{
+    api::{admin_activity::log_admin_read, limits, wallets::ensure_unlocked},
+    auth::{AdminOnly, Auth, AuthenticatedUser},
+    blockchain::{parse_amount, NETWORK_FUJI},
+    error::{ApiError, StorageContext},
+    providers::email,
+    state::AppState,
+    storage::{
+        AuditEvent, AuditEventType, AuditRepository, EmailIndexRepository, EncryptedStorage,
+        EscrowActor, EscrowPaymentStatus, EscrowRepository, EscrowTransition, StorageError,
+        StoredEscrowPayment, TxCache, TxDatabase, WalletMetadata, WalletRepository, WalletStatus,
+    },
+};
+
+/// Time the payee has to request release when the payer does not set one
+/// (14 days).
+const DEFAULT_ESCROW_TTL_HOURS: u32 = 336;
+/// Longest time a payer may give the payee (90 days).
+const MAX_ESCROW_TTL_HOURS: u32 = 2160;
+/// Time the payer has to answer a release request before it is released.
+pub const REVIEW_WINDOW_HOURS: i64 = 72;
+/// Unsettled escrows a user may have open as payer.
+const MAX_OPEN_ESCROWS: usize = 20;
+const MAX_NOTE_CHARS: usize = 280;
+
+/// Serializes status transitions.
+static ESCROW_LOCK: Mutex<()> = Mutex::new(());
+
+// =============================================================================
+// Request/Response Types
+// =============================================================================
+
+/// Request to open an escrowed payment.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct CreateEscrowRequest {
+    /// Payee's wallet. Set exactly one of `payee_wallet_id` and
+    /// `payee_email_hash`.
+    #[serde(default)]
+    pub payee_wallet_id: Option<String>,
+    /// SHA-256 hash of the payee's email; pays the wallet registered for it.
+    #[serde(default)]
+    pub payee_email_hash: Option<String>,
+    /// Amount in human-readable units (e.g. "250.00").
+    pub amount: String,
+    /// `native` for AVAX or the rEUR contract address.
+    #[serde(default = "default_native")]
+    pub token: String,
+    /// What the payment is for (max 280 characters).
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Hours the payee has to request release (default 336, max 2160).
+    #[serde(default)]
+    pub expires_in_hours: Option<u32>,
+}
+
+fn default_native() -> String {
+    "native".to_string()
+}
+
+/// Optional reason attached to a party's action.
+#[derive(Debug, Clone, Default, Deserialize, ToSchema)]
+pub struct EscrowActionRequest {
+    /// Shown in the payment's history (max 280 characters).
+    #[serde(default)]
+    pub note: Option<String>,
+}
+
+/// An arbiter's decision on a contested payment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum EscrowOutcome {
+    Release,
+    Refund,
+}
+
+/// Request to resolve a release request or dispute (admin only).
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct ResolveEscrowRequest {
+    pub outcome: EscrowOutcome,
+    /// Reason for the decision (required, max 280 characters).
+    pub note: String,
+}
+
+/// Filter for the admin escrow list.
+#[derive(Debug, Default, Deserialize, IntoParams)]
+pub struct AdminEscrowQuery {
+    /// Only payments in this status (e.g. `disputed`).
+    pub status: Option<EscrowPaymentStatus>,
+}
+
+/// An escrowed payment.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct EscrowResponse {
+    pub escrow_id: String,
+    /// The caller's side (`payer` or `payee`); omitted for admins.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub role: Option<EscrowActor>,
+    pub status: EscrowPaymentStatus,
+    pub payer_address: String,
+    pub payee_address: String,
+    pub token: String,
+    pub amount: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Escrow sub-wallet holding the funds.
+    pub escrow_address: String,
+    pub funding_tx_hashes: Vec<String>,
+    /// Whether the gas stipend reached the escrow; retried in the background.
+    pub gas_funded: bool,
+    /// The payee must request release before this.
+    pub expires_at: DateTime<Utc>,
+    /// The payer must answer the release request before this.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub review_deadline: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub settlement_tx_hash: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+    pub history: Vec<EscrowTransition>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A list of escrowed payments, newest first.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct EscrowListResponse {
+    pub escrows: Vec<EscrowResponse>,
+}
+
+/// Build the response for `viewer`; `None` is an admin and sees user IDs
+/// in the history.
+fn to_response(payment: &StoredEscrowPayment, viewer: Option<&str>) -> EscrowResponse {
+    let role = viewer.map(|user_id| {
+        if payment.payer_user_id == user_id {
+            EscrowActor::Payer
+        } else {
+            EscrowActor::Payee
+        }
+    });
+    let mut history = payment.history.clone();
+    if viewer.is_some() {
+        for entry in &mut history {
+            entry.user_id = None;
+        }
+    }
+    EscrowResponse {
+        escrow_id: payment.escrow_id.clone(),
+        role,
+        status: payment.status,
+        payer_address: payment.payer_address.clone(),
+        payee_address: payment.payee_address.clone(),
+        token: payment.token.clone(),
+        amount: payment.amount.clone(),
+        description: payment.description.clone(),
+        escrow_address: payment.escrow_address.clone(),
+        funding_tx_hashes: payment.funding_tx_hashes.clone(),
+        gas_funded: payment.gas_funded,
+        expires_at: payment.expires_at,
+        review_deadline: payment.review_deadline,
+        settlement_tx_hash: payment.settlement_tx_hash.clone(),
+        last_error: payment.last_error.clone(),
+        history,
+        created_at: payment.created_at,
+        updated_at: payment.updated_at,
+    }
+}
+
+// =============================================================================
+// State Machine
+// =============================================================================
+
+/// Something a party, the arbiter or a timeout does to a payment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum EscrowAction {
+    RequestRelease,
+    Dispute,
+    Release,
+    Refund,
+}
+
+/// Status `action` by `actor` leads to, or why it is not allowed.
+fn next_status(
+    payment: &StoredEscrowPayment,
+    actor: EscrowActor,
+    action: EscrowAction,
+    now: DateTime<Utc>,
+) -> Result<EscrowPaymentStatus, ApiError> {
+    use EscrowActor::*;
+    use EscrowPaymentStatus::*;
+
+    match payment.status {
+        Settling => return Err(ApiError::conflict("A payout is in progress")),
+        Released => return Err(ApiError::conflict("Already released to the payee")),
+        Refunded => return Err(ApiError::conflict("Already refunded to the payer")),
+        Funded | ReleaseRequested | Disputed => {}
+    }
+    let expired = now >= payment.expires_at;
+    let review_over = payment.review_deadline.is_some_and(|at| now >= at);
+
+    match (action, actor) {
+        (EscrowAction::RequestRelease, Payee) => match payment.status {
+            Funded if expired => Err(ApiError::conflict(
+                "The escrow expired before release was requested",
+            )),
+            Funded => Ok(ReleaseRequested),
+            _ => Err(ApiError::conflict("Release was already requested")),
+        },
+        (EscrowAction::RequestRelease, _) => {
+            Err(ApiError::forbidden("Only the payee can request release"))
+        }
+
+        (EscrowAction::Dispute, Payer) => match payment.status {
+            ReleaseRequested => Ok(Disputed),
+            Funded => Err(ApiError::conflict("There is no release request to dispute")),
+            _ => Err(ApiError::conflict("Already disputed")),
+        },
+        (EscrowAction::Dispute, _) => Err(ApiError::forbidden("Only the payer can dispute")),
+
+        (EscrowAction::Release, Payer) => Ok(Released),
+        (EscrowAction::Release, Arbiter) if payment.status != Funded => Ok(Released),
+        (EscrowAction::Release, System) if payment.status == ReleaseRequested && review_over => {
+            Ok(Released)
+        }
+        (EscrowAction::Release, Payee) => {
+            Err(ApiError::forbidden("The payee requests release instead"))
+        }
+        (EscrowAction::Release, _) => Err(ApiError::conflict(
+            "Nothing to decide: release has not been requested",
+        )),
+
+        (EscrowAction::Refund, Payee) => Ok(Refunded),
+        (EscrowAction::Refund, Payer | System) if payment.status == Funded && expired => {
+            Ok(Refunded)
+        }
+        (EscrowAction::Refund, Payer) if payment.status == Funded => Err(ApiError::conflict(
+            "The payee can still request release; refunds open once the escrow expires",
+        )),
+        (EscrowAction::Refund, Payer) => Err(ApiError::conflict(
+            "Release was requested; approve it or dispute it",
+        )),
+        (EscrowAction::Refund, Arbiter) if payment.status != Funded => Ok(Refunded),
+        (EscrowAction::Refund, _) => Err(ApiError::conflict(
+            "Nothing to decide: release has not been requested",
+        )),
+    }
+}
+
+fn audit_event_type(status: EscrowPaymentStatus) -> AuditEventType {
+    match status {
+        EscrowPaymentStatus::ReleaseRequested => AuditEventType::EscrowReleaseRequested,
+        EscrowPaymentStatus::Disputed => AuditEventType::EscrowDisputed,
+        EscrowPaymentStatus::Refunded => AuditEventType::EscrowRefunded,
+        _ => AuditEventType::EscrowReleased,
+    }
+}
+
+fn load_payment(
+    storage: &EncryptedStorage,
+    escrow_id: &str,
+) -> Result<StoredEscrowPayment, ApiError> {
+    EscrowRepository::new(storage)
+        .get_payment(escrow_id)
+        .context("Failed to load escrow")
+}
+
+fn validate_note(note: Option<String>) -> Result<Option<String>, ApiError> {
+    let note = note.map(|n| n.trim().to_string()).filter(|n| !n.is_empty());
+    if note
+        .as_ref()
+        .is_some_and(|n| n.chars().count() > MAX_NOTE_CHARS)
+    {
+        return Err(ApiError::bad_request(format!(
+            "note must be at most {MAX_NOTE_CHARS} characters"
+        )));
+    }
+    Ok(note)
+}
+
+/// Apply `action` to a payment and log it.
+///
+/// Release and refund pay out of the escrow: the payment is `Settling`
+/// while the transfer is broadcast, and returns to its previous status with
+/// `last_error` set if the transfer fails.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn apply_action(
+    storage: &EncryptedStorage,
+    tx_db: &TxDatabase,
+    tx_cache: Option<&TxCache>,
+    escrow_id: &str,
+    actor: EscrowActor,
+    user_id: Option<&str>,
+    action: EscrowAction,
+    note: Option<String>,
+) -> Result<StoredEscrowPayment, ApiError> {
+    let repo = EscrowRepository::new(storage);
+    let now = Utc::now();
+    let (mut payment, next, previous) = {
+        let _guard = ESCROW_LOCK
+            .lock()
+            .map_err(|_| ApiError::internal("Escrow lock poisoned"))?;
+        let mut payment = load_payment(storage, escrow_id)?;
+        let next = next_status(&payment, actor, action, now)?;
+        let previous = payment.status;
+        if next.is_final() {
+            if !payment.gas_funded {
+                return Err(ApiError::conflict(
+                    "The escrow is still being funded; try again shortly",
+                ));
+            }
+            payment.status = EscrowPaymentStatus::Settling;
+            payment.updated_at = now;
+        } else {
+            if next == EscrowPaymentStatus::ReleaseRequested {
+                payment.review_deadline = Some(now + Duration::hours(REVIEW_WINDOW_HOURS));
+            }
+            payment.transition(next, actor, user_id, note.clone(), now);
+        }
+        repo.update_payment(&payment)
+            .map_err(|e| ApiError::internal(format!("Failed to update escrow: {e}")))?;
+        (payment, next, previous)
+    };
+
+    if next.is_final() {
+        let (to_wallet_id, to_address) = match next {
+            EscrowPaymentStatus::Released => (&payment.payee_wallet_id, &payment.payee_address),
+            _ => (&payment.payer_wallet_id, &payment.payer_address),
+        };
+        let sent = pay_out(
+            storage,
+            tx_db,
+            tx_cache,
+            &payment.escrow_id,
+            &payment.escrow_address,
+            &payment.token,
+            &payment.amount,
+            to_wallet_id,
+            to_address,
+        )
+        .await;
+        match sent {
+            Ok(tx_hash) => {
+                payment.settlement_tx_hash = Some(tx_hash);
+                payment.last_error = None;
+                payment.transition(next, actor, user_id, note.clone(), Utc::now());
+            }
+            Err(e) => {
+                payment.status = previous;
+                payment.last_error = Some(e.message.clone());
+                payment.updated_at = Utc::now();
+                let _ = repo.update_payment(&payment);
+                return Err(e);
+            }
+        }
+        repo.update_payment(&payment)
+            .map_err(|e| ApiError::internal(format!("Failed to update escrow: {e}")))?;
+    }
+
+    let mut event = AuditEvent::new(audit_event_type(next))
+        .with_resource("escrow", escrow_id)
+        .with_details(serde_json::json!({
+            "actor": actor,
+            "note": note,
+            "tx_hash": payment.settlement_tx_hash,
+        }));
+    if let Some(user_id) = user_id {
+        event = event.with_user(user_id);
+    }
+    let _ = AuditRepository::new(storage).log(&event);
+
+    Ok(payment)
+}
+
+/// The payee's active wallet, by ID or by registered email.
+fn resolve_payee(
+    state: &AppState,
+    request: &CreateEscrowRequest,
+) -> Result<WalletMetadata, ApiError> {
+    let wallet_id = match (&request.payee_wallet_id, &request.payee_email_hash) {
+        (Some(wallet_id), None) => wallet_id.clone(),
+        (None, Some(hash)) => {
+            if !email::validate_email_hash(hash) {
+                return Err(ApiError::bad_request(
+                    "payee_email_hash must be 64 lowercase hex characters",
+                ));
+            }
+            let tx_db = state
+                .tx_db
+                .as_ref()
+                .expect("transaction database must be configured");
+            let lookup_key = email::hmac_lookup_key(&state.email_hmac_key, hash);
+            EmailIndexRepository::new(tx_db.clone())
+                .lookup(&lookup_key)
+                .map_err(|e| ApiError::internal(format!("Email lookup failed: {e}")))?
+                .ok_or_else(|| ApiError::not_found("No wallet is registered for this email"))?
+                .wallet_id
+        }
+        _ => {
+            return Err(ApiError::bad_request(
+                "Set exactly one of payee_wallet_id and payee_email_hash",
+            ))
+        }
+    };
+    let wallet = WalletRepository::new(state.storage())
+        .get(&wallet_id)
+        .map_err(|e| match e {
+            StorageError::NotFound(_) => ApiError::not_found("Payee wallet not found"),
+            other => other.into(),
+        })?;
+    if wallet.status != WalletStatus::Active {
+        return Err(ApiError::unprocessable("Payee wallet is not active"));
+    }
+    Ok(wallet)
+}
+
+/// Load a payment the caller is a party to.
+fn party_payment(
+    storage: &EncryptedStorage,
+    user_id: &str,
+    escrow_id: &str,
+) -> Result<(StoredEscrowPayment, EscrowActor), ApiError> {
+    let payment = load_payment(storage, escrow_id)?;
+    if payment.payer_user_id == user_id {
+        Ok((payment, EscrowActor::Payer))
+    } else if payment.payee_user_id == user_id {
+        Ok((payment, EscrowActor::Payee))
+    } else {
+        Err(ApiError::forbidden("You are not a party to this escrow"))
+    }
+}
+
+/// Apply a party's action and return the caller's view of the result.
+async fn party_action(
+    state: AppState,
+    user_id: &str,
+    escrow_id: &str,
+    action: EscrowAction,
+    request: EscrowActionRequest,
+) -> Result<Json<EscrowResponse>, ApiError> {
+    let note = validate_note(request.note)?;
+    let storage = state.storage();
+    let (_, actor) = party_payment(storage, user_id, escrow_id)?;
+    let tx_db = state
+        .tx_db
+        .as_ref()
+        .expect("transaction database must be configured");
+    let payment = apply_action(
+        storage,
+        tx_db,
+        state.tx_cache.as_deref(),
+        escrow_id,
+        actor,
+        Some(user_id),
+        action,
+        note,
+    )
+    .await?;
+    Ok(Json(to_response(&payment, Some(user_id))))
+}
+
+// =============================================================================
+// Handlers
+// =============================================================================
+
+/// Open an escrowed payment to another user.
+///
+/// Moves the amount and a gas stipend from the payer's wallet into an
+/// escrow sub-wallet.
+#[utoipa::path(
+    post,
+    path = "/v1/wallets/{wallet_id}/escrows",
+    tag = "Escrow",
+    params(("wallet_id" = String, Path, description = "Payer's wallet ID")),
+    request_body = CreateEscrowRequest,
+    security(("bearer" = [])),
+    responses(
+        (status = 201, description = "Escrow funded", body = EscrowResponse),
+        (status = 400, description = "Invalid payee, amount, token, expiry or description"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - not wallet owner or wallet suspended"),
+        (status = 404, description = "Wallet or payee not found"),
+        (status = 422, description = "Insufficient balance, spending limit exceeded, inactive payee, or too many open escrows"),
+        (status = 503, description = "Blockchain network unavailable")
+    )
+)]
+pub async fn create_escrow(
+    Auth(user): Auth,
+    State(state): State<AppState>,
+    Path(wallet_id): Path<String>,
+    Json(request): Json<CreateEscrowRequest>,
+) -> Result<(StatusCode, Json<EscrowResponse>), ApiError> {
+    let storage = state.storage();
+    let wallet = active_wallet(storage, &user, &wallet_id)?;
+    ensure_unlocked(&wallet)?;
+    let payee = resolve_payee(&state, &request)?;
+    if payee.owner_user_id == user.user_id {
+        return Err(ApiError::bad_request(
+            "Escrow is between two users; use a transfer between your own wallets instead",
+        ));
+    }
+
+    let token = parse_escrow_token(&request.token)?;
+    let amount = parse_amount(&request.amount, token_decimals(&token))
+        .map_err(|e| ApiError::bad_request(format!("Invalid amount: {e}")))?;
+    if amount.is_zero() {
+        return Err(
+            ApiError::bad_request("Amount must be greater than zero").with_code("invalid_amount")
+        );
+    }
+    let ttl_hours = request.expires_in_hours.unwrap_or(DEFAULT_ESCROW_TTL_HOURS);
+    if !(1..=MAX_ESCROW_TTL_HOURS).contains(&ttl_hours) {
+        return Err(ApiError::bad_request(format!(
+            "expires_in_hours must be between 1 and {MAX_ESCROW_TTL_HOURS}"
+        )));
+    }
+    let description = validate_note(request.description)?;
+
+    let repo = EscrowRepository::new(storage);
+    let open = repo
+        .list_payments_for_user(&user.user_id)
+        .map_err(|e| ApiError::internal(format!("Failed to list escrows: {e}")))?
+        .iter()
+        .filter(|p| p.payer_user_id == user.user_id && !p.status.is_final())
+        .count();
+    if open >= MAX_OPEN_ESCROWS {
+        return Err(ApiError::unprocessable(format!(
+            "At most {MAX_OPEN_ESCROWS} escrows can be open at once"
+        )));
+    }
+
+    let tx_db = state
+        .tx_db
+        .as_ref()
+        .expect("transaction database must be configured");
+    let tx_cache = state.tx_cache.as_deref();
+    let escrow_id = Uuid::new_v4().to_string();
+    let reservation = limits::reserve_spending(
+        &state,
+        &wallet,
+        &[(&token_label(&token), request.amount.trim())],
+        NETWORK_FUJI,
+    )
+    .await?;
+    let funding = fund_escrow(
+        storage,
+        &state.nonce_manager,
+        tx_db,
+        tx_cache,
+        &wallet,
+        &escrow_id,
+        &token,
+        amount,
+    )
+    .await?;
+    reservation.commit(&funding.tx_hashes[0]);
+
+    let now = Utc::now();
+    let mut payment = StoredEscrowPayment {
+        escrow_id: escrow_id.clone(),
+        payer_user_id: user.user_id.clone(),
+        payer_wallet_id: wallet.wallet_id.clone(),
+        payer_address: wallet.public_address.clone(),
+        payee_user_id: payee.owner_user_id.clone(),
+        payee_wallet_id: payee.wallet_id.clone(),
+        payee_address: payee.public_address.clone(),
+        escrow_address: funding.escrow_address,
+        token: token_label(&token),
+        amount: request.amount.trim().to_string(),
+        description,
+        funding_tx_hashes: funding.tx_hashes,
+        gas_funded: funding.gas_funded,
+        status: EscrowPaymentStatus::Funded,
+        expires_at: now + Duration::hours(i64::from(ttl_hours)),
+        review_deadline: None,
+        settlement_tx_hash: None,
+        // A failed stipend is retried by the expiry worker.
+        last_error: funding.last_error,
+        history: Vec::new(),
+        created_at: now,
+        updated_at: now,
+    };
+    payment.transition(
+        EscrowPaymentStatus::Funded,
+        EscrowActor::Payer,
+        Some(&user.user_id),
+        None,
+        now,
+    );
+    repo.create_payment(&payment)
+        .map_err(|e| ApiError::internal(format!("Failed to store escrow: {e}")))?;
+
+    let event = AuditEvent::new(AuditEventType::EscrowCreated)
+        .with_user(&user.user_id)
+        .with_resource("escrow", &escrow_id)
+        .with_details(serde_json::json!({
+            "wallet_id": wallet.wallet_id,
+            "payee_user_id": payment.payee_user_id,
+            "amount": payment.amount,
+            "token": payment.token,
+            "expires_at": payment.expires_at,
+        }));
+    let _ = AuditRepository::new(storage).log(&event);
+
+    Ok((
+        StatusCode::CREATED,
+        Json(to_response(&payment, Some(&user.user_id))),
+    ))
+}
+
+/// List escrowed payments where the caller is payer or payee.
+#[utoipa::path(
+    get,
+    path = "/v1/escrows",
+    tag = "Escrow",
+    security(("bearer" = [])),
+    responses(
+        (status = 200, description = "The caller's escrows", body = EscrowListResponse),
+        (status = 401, description = "Unauthorized")
+    )
+)]
+pub async fn list_escrows(
+    Auth(user): Auth,
+    State(state): State<AppState>,
+) -> Result<Json<EscrowListResponse>, ApiError> {
+    let payments = EscrowRepository::new(state.storage())
+        .list_payments_for_user(&user.user_id)
+        .map_err(|e| ApiError::internal(format!("Failed to list escrows: {e}")))?;
+    Ok(Json(EscrowListResponse {
+        escrows: payments
+            .iter()
+            .map(|p| to_response(p, Some(&user.user_id)))
+            .collect(),
+    }))
+}
+
+/// Get an escrowed payment.
+#[utoipa::path(
+    get,
+    path = "/v1/escrows/{escrow_id}",
+    tag = "Escrow",
+    params(("escrow_id" = String, Path, description = "Escrow ID")),
+    security(("bearer" = [])),
+    responses(
+        (status = 200, description = "Escrow details", body = EscrowResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Not a party to this escrow"),
+        (status = 404, description = "Escrow not found")
+    )
+)]
+pub async fn get_escrow(
+    Auth(user): Auth,
+    State(state): State<AppState>,
+    Path(escrow_id): Path<String>,
+) -> Result<Json<EscrowResponse>, ApiError> {
+    let (payment, _) = party_payment(state.storage(), &user.user_id, &escrow_id)?;
+    Ok(Json(to_response(&payment, Some(&user.user_id))))
+}
+
+/// Ask the payer to release the funds (payee).
+///
+/// The payer then has 72 hours to approve or dispute before the funds are
+/// released.
+#[utoipa::path(
+    post,
+    path = "/v1/escrows/{escrow_id}/request-release",
+    tag = "Escrow",
+    params(("escrow_id" = String, Path, description = "Escrow ID")),
+    request_body = EscrowActionRequest,
+    security(("bearer" = [])),
+    responses(
+        (status = 200, description = "Release requested", body = EscrowResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Not the payee"),
+        (status = 404, description = "Escrow not found"),
+        (status = 409, description = "Expired, already requested, or settled")
+    )
+)]
+pub async fn request_release(
+    Auth(user): Auth,
+    State(state): State<AppState>,
+    Path(escrow_id): Path<String>,
+    Json(request): Json<EscrowActionRequest>,
+) -> Result<Json<EscrowResponse>, ApiError> {
+    party_action(
+        state,
+        &user.user_id,
+        &escrow_id,
+        EscrowAction::RequestRelease,
+        request,
+    )
+    .await
+}
+
+/// Release the funds to the payee (payer).
+#[utoipa::path(
+    post,
+    path = "/v1/escrows/{escrow_id}/approve",
+    tag = "Escrow",
+    params(("escrow_id" = String, Path, description = "Escrow ID")),
+    request_body = EscrowActionRequest,
+    security(("bearer" = [])),
+    responses(
+        (status = 200, description = "Funds released to the payee", body = EscrowResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Not the payer"),
+        (status = 404, description = "Escrow not found"),
+        (status = 409, description = "Already settled, or escrow funding not confirmed yet"),
+        (status = 503, description = "Blockchain network unavailable")
+    )
+)]
+pub async fn approve_release(
+    Auth(user): Auth,
+    State(state): State<AppState>,
+    Path(escrow_id): Path<String>,
+    Json(request): Json<EscrowActionRequest>,
+) -> Result<Json<EscrowResponse>, ApiError> {
+    party_action(
+        state,
+        &user.user_id,
+        &escrow_id,
+        EscrowAction::Release,
+        request,
+    )
+    .await
+}
+
+/// Contest a release request (payer).
+///
+/// The payment then waits for an admin arbiter.
+#[utoipa::path(
+    post,
+    path = "/v1/escrows/{escrow_id}/dispute",
+    tag = "Escrow",
+    params(("escrow_id" = String, Path, description = "Escrow ID")),
+    request_body = EscrowActionRequest,
+    security(("bearer" = [])),
+    responses(
+        (status = 200, description = "Disputed", body = EscrowResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Not the payer"),
+        (status = 404, description = "Escrow not found"),
+        (status = 409, description = "No release request to dispute")
+    )
+)]
+pub async fn dispute_release(
+    Auth(user): Auth,
+    State(state): State<AppState>,
+    Path(escrow_id): Path<String>,
+    Json(request): Json<EscrowActionRequest>,
+) -> Result<Json<EscrowResponse>, ApiError> {
+    party_action(
+        state,
+        &user.user_id,
+        &escrow_id,
+        EscrowAction::Dispute,
+        request,
+    )
+    .await
+}
+
+/// Return the funds to the payer.
+///
+/// The payee can refund at any time; the payer only once the escrow has
+/// expired without a release request.
+#[utoipa::path(
+    post,
+    path = "/v1/escrows/{escrow_id}/refund",
+    tag = "Escrow",
+    params(("escrow_id" = String, Path, description = "Escrow ID")),
+    request_body = EscrowActionRequest,
+    security(("bearer" = [])),
+    responses(
+        (status = 200, description = "Funds returned to the payer", body = EscrowResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Not a party to this escrow"),
+        (status = 404, description = "Escrow not found"),
+        (status = 409, description = "Not refundable by the caller yet, or already settled"),
+        (status = 503, description = "Blockchain network unavailable")
+    )
+)]
+pub async fn refund_escrow(
+    Auth(user): Auth,
+    State(state): State<AppState>,
+    Path(escrow_id): Path<String>,
+    Json(request): Json<EscrowActionRequest>,
+) -> Result<Json<EscrowResponse>, ApiError> {
+    party_action(
+        state,
+        &user.user_id,
+        &escrow_id,
+        EscrowAction::Refund,
+        request,
+    )
+    .await
+}
+
+/// Whether the payment's payer wallet is in the admin's tenant.
+fn admin_sees_payment(
+    storage: &EncryptedStorage,
+    admin: &AuthenticatedUser,
+    payment: &StoredEscrowPayment,
+) -> bool {
+    if admin.tenant_id.is_none() {
+        return true;
+    }
+    WalletRepository::new(storage)
+        .get(&payment.payer_wallet_id)
+        .is_ok_and(|w| admin.sees_tenant(w.tenant_id.as_deref()))
+}
+
+/// List escrowed payments: all of them, or those paid from the admin's
+/// tenant (admin only).
+#[utoipa::path(
+    get,
+    path = "/v1/admin/escrows",
+    tag = "Admin",
+    params(AdminEscrowQuery),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Escrows, newest first", body = EscrowListResponse),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not authorized (admin required)")
+    )
+)]
+pub async fn admin_list_escrows(
+    AdminOnly(admin): AdminOnly,
+    State(state): State<AppState>,
+    Query(query): Query<AdminEscrowQuery>,
+) -> Result<Json<EscrowListResponse>, ApiError> {
+    let mut payments = EscrowRepository::new(state.storage())
+        .list_payments()
+        .map_err(|e| ApiError::internal(format!("Failed to list escrows: {e}")))?;
+    payments.retain(|p| {
+        query.status.is_none_or(|status| p.status == status)
+            && admin_sees_payment(state.storage(), &admin, p)
+    });
+    payments.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    log_admin_read(
+        state.storage(),
+        &admin,
+        "escrows",
+        "*",
+        &["payer_address", "payee_address", "amount", "description"],
+        payments.len(),
+    );
+    Ok(Json(EscrowListResponse {
+        escrows: payments.iter().map(|p| to_response(p, None)).collect(),
+    }))
+}
+
+/// Decide a release request or dispute (admin only).
+#[utoipa::path(
+    post,
+    path = "/v1/admin/escrows/{escrow_id}/resolve",
+    tag = "Admin",
+    params(("escrow_id" = String, Path, description = "Escrow ID")),
+    request_body = ResolveEscrowRequest,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Funds released or refunded", body = EscrowResponse),
+        (status = 400, description = "Missing or overlong note"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not authorized (admin required)"),
+        (status = 404, description = "Escrow not found"),
+        (status = 409, description = "No release request or dispute to decide"),
+        (status = 503, description = "Blockchain network unavailable")
+    )
+)]
+pub async fn resolve_escrow(
+    AdminOnly(admin): AdminOnly,
+    State(state): State<AppState>,
+    Path(escrow_id): Path<String>,
+    Json(request): Json<ResolveEscrowRequest>,
+) -> Result<Json<EscrowResponse>, ApiError> {
+    let note = validate_note(Some(request.note))?
+        .ok_or_else(|| ApiError::bad_request("note is required"))?;
+    if EscrowRepository::new(state.storage())
+        .get_payment(&escrow_id)
+        .is_ok_and(|p| !admin_sees_payment(state.storage(), &admin, &p))
+    {
+        return Err(ApiError::not_found("Escrow not found"));
+    }
+    let action = match request.outcome {
+        EscrowOutcome::Release => EscrowAction::Release,
+        EscrowOutcome::Refund => EscrowAction::Refund,
+    };
+    let tx_db = state
+        .tx_db
+        .as_ref()
+        .expect("transaction database must be configured");
+    let payment = apply_action(
+        state.storage(),
+        tx_db,
+        state.tx_cache.as_deref(),
+        &escrow_id,
+        EscrowActor::Arbiter,
+        Some(&admin.user_id),
+        action,
+        Some(note),
+    )
+    .await?;
+    Ok(Json(to_response(&payment, None)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::Role;
+
+    fn user(user_id: &str, role: Role) -> AuthenticatedUser {
+        AuthenticatedUser {
+            user_id: user_id.to_string(),
+            role,
+            session_id: None,
+            issuer: "https://test.clerk.dev".to_string(),
+            expires_at: Utc::now().timestamp() + 3600,
+            tenant_id: None,
+        }
+    }
+
+    fn payment(status: EscrowPaymentStatus, expires_at: DateTime<Utc>) -> StoredEscrowPayment {
+        let now = Utc::now();
+        StoredEscrowPayment {
+            escrow_id: Uuid::new_v4().to_string(),
+            payer_user_id: "payer".to_string(),
+            payer_wallet_id: "wallet-payer".to_string(),
+            payer_address: "0x1111111111111111111111111111111111111111".to_string(),
+            payee_user_id: "payee".to_string(),
+            payee_wallet_id: "wallet-payee".to_string(),
+            payee_address: "0x3333333333333333333333333333333333333333".to_string(),
+            escrow_address: "0x2222222222222222222222222222222222222222".to_string(),
+            token: "native".to_string(),
+            amount: "2".to_string(),
+            description: Some("Logo design".to_string()),
+            funding_tx_hashes: vec!["0xfund".to_string()],
+            gas_funded: true,
+            status,
+            expires_at,
+            review_deadline: None,
+            settlement_tx_hash: None,
+            last_error: None,
+            history: Vec::new(),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[test]
+    fn state_machine_enforces_roles_and_timeouts() {
+        use EscrowAction::*;
+        use EscrowActor::*;
+        use EscrowPaymentStatus::*;
+
+        let now = Utc::now();
+        let open = payment(Funded, now + Duration::days(1));
+        assert_eq!(
+            next_status(&open, Payee, RequestRelease, now).unwrap(),
+            ReleaseRequested
+        );
+        assert_eq!(next_status(&open, Payer, Release, now).unwrap(), Released);
+        assert_eq!(next_status(&open, Payee, Refund, now).unwrap(), Refunded);
+        assert_eq!(
+            next_status(&open, Payer, RequestRelease, now)
+                .unwrap_err()
+                .status,
+            StatusCode::FORBIDDEN
+        );
+        assert_eq!(
+            next_status(&open, Payer, Refund, now).unwrap_err().status,
+            StatusCode::CONFLICT
+        );
+        assert!(next_status(&open, Arbiter, Release, now).is_err());
+
+        let expired = payment(Funded, now - Duration::minutes(1));
+        assert!(next_status(&expired, Payee, RequestRelease, now).is_err());
+        assert_eq!(next_status(&expired, Payer, Refund, now).unwrap(), Refunded);
+        assert_eq!(
+            next_status(&expired, System, Refund, now).unwrap(),
+            Refunded
+        );
+
+        let mut requested = payment(ReleaseRequested, now - Duration::minutes(1));
+        requested.review_deadline = Some(now + Duration::hours(1));
+        assert_eq!(
+            next_status(&requested, Payer, Dispute, now).unwrap(),
+            Disputed
+        );
+        assert!(next_status(&requested, Payer, Refund, now).is_err());
+        assert!(next_status(&requested, System, Release, now).is_err());
+        let later = now + Duration::hours(2);
+        assert_eq!(
+            next_status(&requested, System, Release, later).unwrap(),
+            Released
+        );
+
+        let disputed = payment(Disputed, now + Duration::days(1));
+        assert_eq!(
+            next_status(&disputed, Arbiter, Refund, now).unwrap(),
+            Refunded
+        );
+        assert_eq!(
+            next_status(&disputed, Arbiter, Release, now).unwrap(),
+            Released
+        );
+        assert!(next_status(&disputed, Payer, Dispute, now).is_err());
+
+        let settled = payment(Released, now + Duration::days(1));
+        assert!(next_status(&settled, Payee, Refund, now).is_err());
+    }
+
+    #[tokio::test]
+    async fn parties_move_a_payment_to_arbitration() {
+        let state = AppState::default();
+        let stored = payment(EscrowPaymentStatus::Funded, Utc::now() + Duration::days(1));
+        let escrow_id = stored.escrow_id.clone();
+        EscrowRepository::new(state.storage())
+            .create_payment(&stored)
+            .unwrap();
+
+        let err = get_escrow(
+            Auth(user("stranger", Role::Client)),
+            State(state.clone()),
+            Path(escrow_id.clone()),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::FORBIDDEN);
+
+        let Json(requested) = request_release(
+            Auth(user("payee", Role::Client)),
+            State(state.clone()),
+            Path(escrow_id.clone()),
+            Json(EscrowActionRequest {
+                note: Some("Delivered the final files".to_string()),
+            }),
+        )
+        .await
+        .unwrap();
+        assert_eq!(requested.status, EscrowPaymentStatus::ReleaseRequested);
+        assert_eq!(requested.role, Some(EscrowActor::Payee));
+        assert!(requested.review_deadline.is_some());
+        assert!(requested.history.iter().all(|h| h.user_id.is_none()));
+
+        let err = refund_escrow(
+            Auth(user("payer", Role::Client)),
+            State(state.clone()),
+            Path(escrow_id.clone()),
+            Json(EscrowActionRequest::default()),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::CONFLICT);
+
+        let Json(disputed) = dispute_release(
+            Auth(user("payer", Role::Client)),
+            State(state.clone()),
+            Path(escrow_id.clone()),
+            Json(EscrowActionRequest {
+                note: Some("Files are missing".to_string()),
+            }),
+        )
+        .await
+        .unwrap();
+        assert_eq!(disputed.status, EscrowPaymentStatus::Disputed);
+
+        let Json(listed) = admin_list_escrows(
+            AdminOnly(user("admin-1", Role::Admin)),
+            State(state.clone()),
+            Query(AdminEscrowQuery {
+                status: Some(EscrowPaymentStatus::Disputed),
+            }),
+        )
+        .await
+        .unwrap();
+        assert_eq!(listed.escrows.len(), 1);
+        assert_eq!(listed.escrows[0].history.len(), 2);
+        assert_eq!(
+            listed.escrows[0].history[1].user_id.as_deref(),
+            Some("payer")
+        );
+
+        let err = resolve_escrow(
+            AdminOnly(user("admin-1", Role::Admin)),
+            State(state),
+            Path(escrow_id),
+            Json(ResolveEscrowRequest {
+                outcome: EscrowOutcome::Refund,
+                note: "  ".to_string(),
+            }),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn tenant_admins_only_see_their_tenants_escrows() {
+        let state = AppState::default();
+        let stored = payment(
+            EscrowPaymentStatus::Disputed,
+            Utc::now() + Duration::days(1),
+        );
+        let escrow_id = stored.escrow_id.clone();
+        EscrowRepository::new(state.storage())
+            .create_payment(&stored)
+            .unwrap();
+        let mut tenant_admin = user("admin-acme", Role::Admin);
+        tenant_admin.tenant_id = Some("org_acme".to_string());
+
+        let Json(listed) = admin_list_escrows(
+            AdminOnly(tenant_admin.clone()),
+            State(state.clone()),
+            Query(AdminEscrowQuery { status: None }),
+        )
+        .await
+        .unwrap();
+        assert!(listed.escrows.is_empty());
+
+        let err = resolve_escrow(
+            AdminOnly(tenant_admin),
+            State(state.clone()),
+            Path(escrow_id),
+            Json(ResolveEscrowRequest {
+                outcome: EscrowOutcome::Refund,
+                note: "Refund the payer".to_string(),
+            }),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::NOT_FOUND);
+
+        let Json(listed) = admin_list_escrows(
+            AdminOnly(user("admin-1", Role::Admin)),
+            State(state),
+            Query(AdminEscrowQuery { status: None }),
+        )
+        .await
+        .unwrap();
+        assert_eq!(listed.escrows.len(), 1);
+    }
+}
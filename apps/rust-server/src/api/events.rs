@@ -0,0 +1,477 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Machine-readable catalog of the events the service emits.
+//!
+//! `GET /v1/events/catalog` is public. It lists every event type per
+//! channel (audit log, user notifications, outbound webhooks) with a JSON
+//! Schema (draft 2020-12) for its payload. [`CATALOG_VERSION`] changes only
+//! when an existing event is renamed or removed or a payload field is removed
+//! or changes type; new events and new optional fields keep the version.
+
+use axum::Json;
+use serde::Serialize;
+use serde_json::{json, Value};
+use utoipa::ToSchema;
+
+use crate::storage::{AlertEventKind, AuditEventType, AutoTopUpEventKind, WebhookEventType};
+
+/// Version of the catalog contract.
+pub const CATALOG_VERSION: u32 = 1;
+
+const JSON_SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// One event type and the schema of its payload.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CatalogEvent {
+    /// Event name, as it appears in the payload.
+    pub name: String,
+    pub description: String,
+    /// JSON Schema of the payload.
+    #[schema(value_type = Object)]
+    pub schema: Value,
+}
+
+/// A channel the service emits events on.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct EventChannel {
+    /// `audit`, `notification` or `webhook`.
+    pub channel: String,
+    /// Where the events can be read.
+    pub delivery: String,
+    /// Payload field naming the event type.
+    pub discriminator: String,
+    pub events: Vec<CatalogEvent>,
+}
+
+/// Response for `GET /v1/events/catalog`.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct EventCatalogResponse {
+    pub version: u32,
+    pub channels: Vec<EventChannel>,
+}
+
+/// Serialized name of a snake_case enum variant.
+fn wire_name<T: Serialize>(value: &T) -> String {
+    serde_json::to_value(value)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+fn nullable_string() -> Value {
+    json!({ "type": ["string", "null"] })
+}
+
+/// Schema of an [`AuditEvent`](crate::storage::AuditEvent) of type `name`.
+fn audit_schema(name: &str) -> Value {
+    json!({
+        "$schema": JSON_SCHEMA_DIALECT,
+        "title": name,
+        "type": "object",
+        "properties": {
+            "event_id": { "type": "string" },
+            "timestamp": { "type": "string", "format": "date-time" },
+            "event_type": { "const": name },
+            "user_id": nullable_string(),
+            "resource_id": nullable_string(),
+            "resource_type": nullable_string(),
+            "ip_address": nullable_string(),
+            "details": { "description": "Event-specific JSON, or null" },
+            "success": { "type": "boolean" },
+            "error": nullable_string(),
+        },
+        "required": [
+            "event_id", "timestamp", "event_type", "user_id", "resource_id",
+            "resource_type", "ip_address", "details", "success", "error",
+        ],
+    })
+}
+
+/// Schema of an [`AutoTopUpEvent`](crate::storage::AutoTopUpEvent) of
+/// `kind`.
+fn auto_topup_schema(kind: AutoTopUpEventKind) -> Value {
+    let name = wire_name(&kind);
+    let mut schema = json!({
+        "$schema": JSON_SCHEMA_DIALECT,
+        "title": name,
+        "type": "object",
+        "properties": {
+            "at": { "type": "string", "format": "date-time" },
+            "kind": { "const": name },
+            "message": { "type": "string" },
+        },
+        "required": ["at", "kind", "message"],
+    });
+    if kind == AutoTopUpEventKind::Triggered {
+        schema["properties"]["request_id"] = json!({
+            "type": "string",
+            "description": "On-ramp created by the top-up",
+        });
+        schema["required"] = json!(["at", "kind", "message", "request_id"]);
+    }
+    schema
+}
+
+/// Schema of an [`AlertEvent`](crate::storage::AlertEvent) of `kind`.
+fn alert_schema(kind: AlertEventKind) -> Value {
+    let name = wire_name(&kind);
+    let mut schema = json!({
+        "$schema": JSON_SCHEMA_DIALECT,
+        "title": name,
+        "type": "object",
+        "properties": {
+            "at": { "type": "string", "format": "date-time" },
+            "kind": { "const": name },
+            "message": { "type": "string" },
+        },
+        "required": ["at", "kind", "message"],
+    });
+    if kind != AlertEventKind::LowBalance {
+        schema["properties"]["tx_hash"] = json!({
+            "type": "string",
+            "description": "Transfer that raised the alert",
+        });
+        schema["required"] = json!(["at", "kind", "message", "tx_hash"]);
+    }
+    schema
+}
+
+/// Events of the [`ActivityEvent`](super::activity_stream::ActivityEvent)
+/// stream.
+fn activity_events() -> Vec<CatalogEvent> {
+    let string = || json!({ "type": "string" });
+    let tx_fields = || vec![("wallet_id", string()), ("tx_hash", string())];
+    let kinds = [
+        (
+            "tx_confirmed",
+            "A transaction sent from one of the caller's wallets was confirmed",
+            tx_fields(),
+        ),
+        (
+            "tx_failed",
+            "A transaction sent from one of the caller's wallets failed",
+            tx_fields(),
+        ),
+        (
+            "deposit_indexed",
+            "An incoming transfer to one of the caller's wallets was indexed",
+            vec![
+                ("wallet_id", string()),
+                ("tx_hash", string()),
+                ("amount", string()),
+                (
+                    "token",
+                    json!({ "description": "`native`, or `{\"erc20\": contract}`" }),
+                ),
+            ],
+        ),
+        (
+            "fiat_updated",
+            "A fiat request was created or changed status",
+            vec![
+                ("request_id", string()),
+                ("wallet_id", string()),
+                ("direction", string()),
+                ("status", string()),
+            ],
+        ),
+        (
+            "resync",
+            "Events were dropped because the client fell behind; refetch state",
+            Vec::new(),
+        ),
+    ];
+
+    kinds
+        .into_iter()
+        .map(|(name, description, fields)| {
+            let mut properties = serde_json::Map::new();
+            properties.insert("kind".to_string(), json!({ "const": name }));
+            let mut required = vec!["kind"];
+            for (field, schema) in fields {
+                properties.insert(field.to_string(), schema);
+                required.push(field);
+            }
+            CatalogEvent {
+                name: name.to_string(),
+                description: description.to_string(),
+                schema: json!({
+                    "$schema": JSON_SCHEMA_DIALECT,
+                    "title": name,
+                    "type": "object",
+                    "properties": properties,
+                    "required": required,
+                }),
+            }
+        })
+        .collect()
+}
+
+/// Schema of a webhook subscription delivery of `event_type` (see
+/// [`crate::webhook_dispatcher`]).
+fn webhook_schema(event_type: WebhookEventType) -> Value {
+    let string = || json!({ "type": "string" });
+    let token = || json!({ "description": "`native`, or `{\"erc20\": contract}`" });
+    let data: Vec<(&str, Value)> = match event_type {
+        WebhookEventType::TransactionConfirmed => vec![
+            ("wallet_id", string()),
+            ("tx_hash", string()),
+            ("to", string()),
+            ("amount", string()),
+            ("token", token()),
+            ("network", string()),
+        ],
+        WebhookEventType::DepositIndexed => vec![
+            ("wallet_id", string()),
+            ("tx_hash", string()),
+            ("from", string()),
+            ("amount", string()),
+            ("token", token()),
+            ("network", string()),
+            ("block_number", json!({ "type": ["integer", "null"] })),
+            (
+                "confirmations",
+                json!({
+                    "type": ["integer", "null"],
+                    "description": "Blocks on top of the transfer's block, counting it; null when the chain head could not be read"
+                }),
+            ),
+        ],
+        WebhookEventType::FiatCompleted => vec![
+            ("request_id", string()),
+            ("wallet_id", string()),
+            ("direction", string()),
+            ("amount_eur", string()),
+        ],
+    };
+    let required: Vec<&str> = data.iter().map(|(field, _)| *field).collect();
+    let properties: serde_json::Map<String, Value> = data
+        .into_iter()
+        .map(|(field, schema)| (field.to_string(), schema))
+        .collect();
+    let name = event_type.name();
+    json!({
+        "$schema": JSON_SCHEMA_DIALECT,
+        "title": name,
+        "type": "object",
+        "properties": {
+            "id": { "type": "string", "description": "Delivery ID, the same on retries" },
+            "event": { "const": name },
+            "created_at": { "type": "string", "format": "date-time" },
+            "data": {
+                "type": "object",
+                "properties": properties,
+                "required": required,
+            },
+        },
+        "required": ["id", "event", "created_at", "data"],
+    })
+}
+
+/// Build the catalog.
+pub fn catalog() -> EventCatalogResponse {
+    let audit = AuditEventType::ALL
+        .iter()
+        .map(|event_type| {
+            let name = wire_name(event_type);
+            CatalogEvent {
+                description: event_type.description().to_string(),
+                schema: audit_schema(&name),
+                name,
+            }
+        })
+        .collect();
+    let notifications = AutoTopUpEventKind::ALL
+        .iter()
+        .map(|&kind| CatalogEvent {
+            name: wire_name(&kind),
+            description: kind.description().to_string(),
+            schema: auto_topup_schema(kind),
+        })
+        .chain(AlertEventKind::ALL.iter().map(|&kind| CatalogEvent {
+            name: wire_name(&kind),
+            description: kind.description().to_string(),
+            schema: alert_schema(kind),
+        }))
+        .collect();
+
+    EventCatalogResponse {
+        version: CATALOG_VERSION,
+        channels: vec![
+            EventChannel {
+                channel: "audit".to_string(),
+                delivery: "GET /v1/admin/audit/events".to_string(),
+                discriminator: "event_type".to_string(),
+                events: audit,
+            },
+            EventChannel {
+                channel: "notification".to_string(),
+                delivery: "GET /v1/wallets/{wallet_id}/auto-topup and \
+                           GET /v1/wallets/{wallet_id}/alerts (events)"
+                    .to_string(),
+                discriminator: "kind".to_string(),
+                events: notifications,
+            },
+            EventChannel {
+                channel: "activity".to_string(),
+                delivery: "GET /v1/stream/activity (Server-Sent Events)".to_string(),
+                discriminator: "kind".to_string(),
+                events: activity_events(),
+            },
+            // Signed as described by GET /v1/webhooks/signing-key and with
+            // the subscription's secret.
+            EventChannel {
+                channel: "webhook".to_string(),
+                delivery: "POST to the endpoints registered with POST /v1/webhooks".to_string(),
+                discriminator: "event".to_string(),
+                events: WebhookEventType::ALL
+                    .iter()
+                    .map(|&event_type| CatalogEvent {
+                        name: event_type.name().to_string(),
+                        description: event_type.description().to_string(),
+                        schema: webhook_schema(event_type),
+                    })
+                    .collect(),
+            },
+        ],
+    }
+}
+
+/// Catalog of emitted events (public).
+///
+/// Lists every event type per channel with a JSON Schema for its payload,
+/// so integrators can build against a versioned contract.
+#[utoipa::path(
+    get,
+    path = "/v1/events/catalog",
+    tag = "Events",
+    responses(
+        (status = 200, description = "Event catalog", body = EventCatalogResponse)
+    )
+)]
+pub async fn get_event_catalog() -> Json<EventCatalogResponse> {
+    Json(catalog())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn every_event_is_listed_once_with_a_matching_schema() {
+        let catalog = catalog();
+        for channel in &catalog.channels {
+            let names: HashSet<_> = channel.events.iter().map(|e| e.name.as_str()).collect();
+            assert_eq!(names.len(), channel.events.len(), "{}", channel.channel);
+            for event in &channel.events {
+                assert!(!event.name.is_empty());
+                assert_eq!(
+                    event.schema["properties"][&channel.discriminator]["const"],
+                    event.name
+                );
+            }
+        }
+        assert_eq!(catalog.channels[0].events.len(), AuditEventType::ALL.len());
+    }
+
+    #[test]
+    fn activity_schemas_require_every_serialized_field() {
+        use crate::api::activity_stream::ActivityEvent;
+        use crate::storage::TokenType;
+
+        let deposit = ActivityEvent::DepositIndexed {
+            wallet_id: "w".to_string(),
+            tx_hash: "0x1".to_string(),
+            amount: "1".to_string(),
+            token: TokenType::Native,
+        };
+        let serialized = serde_json::to_value(&deposit).unwrap();
+        let schema = activity_events()
+            .into_iter()
+            .find(|event| event.name == "deposit_indexed")
+            .unwrap()
+            .schema;
+        let required: HashSet<_> = schema["required"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|f| f.as_str().unwrap())
+            .collect();
+        let fields: HashSet<_> = serialized
+            .as_object()
+            .unwrap()
+            .keys()
+            .map(String::as_str)
+            .collect();
+        assert_eq!(required, fields);
+    }
+
+    #[test]
+    fn webhook_schemas_require_every_serialized_field() {
+        use crate::webhook_dispatcher::{new_delivery, WebhookEvent};
+
+        let subscription = crate::storage::StoredWebhookSubscription {
+            subscription_id: "whs_1".to_string(),
+            owner_user_id: "user-1".to_string(),
+            tenant_id: None,
+            url: "https://hooks.example.com/".to_string(),
+            secret: "s3cret-s3cret-s3cret".to_string(),
+            events: Vec::new(),
+            wallet_id: None,
+            created_at: chrono::Utc::now(),
+        };
+        let event = WebhookEvent {
+            owner_user_id: "user-1".to_string(),
+            tenant_id: None,
+            wallet_id: Some("w".to_string()),
+            event_type: WebhookEventType::FiatCompleted,
+            data: json!({
+                "request_id": "req-1",
+                "wallet_id": "w",
+                "direction": "on_ramp",
+                "amount_eur": "10.00",
+            }),
+        };
+        let payload = new_delivery(&subscription, &event, chrono::Utc::now()).payload;
+        let schema = webhook_schema(WebhookEventType::FiatCompleted);
+        let keys = |value: &Value| -> HashSet<String> {
+            value.as_object().unwrap().keys().cloned().collect()
+        };
+        let required = |value: &Value| -> HashSet<String> {
+            value
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|f| f.as_str().unwrap().to_string())
+                .collect()
+        };
+        assert_eq!(required(&schema["required"]), keys(&payload));
+        assert_eq!(
+            required(&schema["properties"]["data"]["required"]),
+            keys(&payload["data"])
+        );
+    }
+
+    #[test]
+    fn audit_schema_requires_every_serialized_field() {
+        let event = crate::storage::AuditEvent::new(AuditEventType::WalletCreated);
+        let serialized = serde_json::to_value(&event).unwrap();
+        let schema = audit_schema("wallet_created");
+        let required: HashSet<_> = schema["required"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|f| f.as_str().unwrap())
+            .collect();
+        let fields: HashSet<_> = serialized
+            .as_object()
+            .unwrap()
+            .keys()
+            .map(String::as_str)
+            .collect();
+        assert_eq!(required, fields);
+    }
+}
@@ -0,0 +1,361 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Feature flags.
+//!
+//! Admins create and change flags under `/v1/admin/feature-flags`; handlers
+//! gate new capabilities on them with [`AppState::feature_enabled`] (see
+//! `response_shaping` for experimental response fields), and clients read
+//! the flags that are on for them from `GET /v1/users/me/features` to
+//! decide what to show.
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::{
+    api::admin::require_platform_admin,
+    auth::{tenant::is_valid_tenant_id, AdminOnly, Auth},
+    error::{ApiError, StorageContext},
+    state::AppState,
+    storage::{AuditEvent, AuditRepository, FeatureFlagRepository, StoredFeatureFlag},
+};
+
+/// Longest flag key.
+const MAX_KEY_LEN: usize = 64;
+/// Most users a flag's allow-list may name.
+const MAX_ALLOWED_USERS: usize = 1000;
+
+/// Request body for creating or replacing a feature flag.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct UpsertFeatureFlagRequest {
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Master switch. A disabled flag is off for everyone.
+    pub enabled: bool,
+    /// Share of users (0-100) the flag is on for.
+    #[serde(default)]
+    pub rollout_percent: u8,
+    /// Users the flag is always on for while enabled.
+    #[serde(default)]
+    pub allowed_users: Vec<String>,
+    /// Tenants the flag is limited to; empty for all tenants.
+    #[serde(default)]
+    pub tenants: Vec<String>,
+}
+
+/// All feature flags.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FeatureFlagListResponse {
+    pub flags: Vec<StoredFeatureFlag>,
+    pub total: usize,
+}
+
+/// Feature flags that are on for the caller.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MyFeaturesResponse {
+    /// Enabled flag keys, sorted.
+    pub features: Vec<String>,
+}
+
+/// Keys are lowercase ASCII letters, digits, `_`, `-` and `.`, so they are
+/// safe as file names.
+fn validate_key(key: &str) -> Result<(), ApiError> {
+    let valid = !key.is_empty()
+        && key.len() <= MAX_KEY_LEN
+        && key
+            .bytes()
+            .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b"_-.".contains(&b))
+        && !key.starts_with('.');
+    if !valid {
+        return Err(ApiError::bad_request(format!(
+            "Flag key must be 1-{MAX_KEY_LEN} characters of a-z, 0-9, '_', '-' or '.'"
+        )));
+    }
+    Ok(())
+}
+
+fn log_flag_change(
+    state: &AppState,
+    admin_user_id: &str,
+    key: &str,
+    old: Option<&StoredFeatureFlag>,
+    new: Option<&StoredFeatureFlag>,
+) {
+    let event = AuditEvent::config_changed(
+        old.and_then(|f| serde_json::to_value(f).ok()),
+        new.and_then(|f| serde_json::to_value(f).ok()),
+    )
+    .with_user(admin_user_id)
+    .with_resource("feature_flag", key);
+    let _ = AuditRepository::new(state.storage()).log(&event);
+}
+
+/// List all feature flags (admin only).
+#[utoipa::path(
+    get,
+    path = "/v1/admin/feature-flags",
+    tag = "Admin",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Feature flags", body = FeatureFlagListResponse),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not authorized (admin required)")
+    )
+)]
+pub async fn list_feature_flags(
+    AdminOnly(_admin): AdminOnly,
+    State(state): State<AppState>,
+) -> Result<Json<FeatureFlagListResponse>, ApiError> {
+    let flags = FeatureFlagRepository::new(state.storage())
+        .list_all()
+        .context("Failed to list feature flags")?;
+    Ok(Json(FeatureFlagListResponse {
+        total: flags.len(),
+        flags,
+    }))
+}
+
+/// Create or replace a feature flag (admin only).
+///
+/// Takes effect on the next request; no redeploy is needed. Flags are shared
+/// by all tenants, so tenant admins cannot change them.
+#[utoipa::path(
+    put,
+    path = "/v1/admin/feature-flags/{key}",
+    tag = "Admin",
+    params(("key" = String, Path, description = "Flag key")),
+    request_body = UpsertFeatureFlagRequest,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Flag saved", body = StoredFeatureFlag),
+        (status = 400, description = "Invalid key, percentage, allow-list or tenant"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not authorized (platform admin required)")
+    )
+)]
+pub async fn put_feature_flag(
+    AdminOnly(admin): AdminOnly,
+    State(state): State<AppState>,
+    Path(key): Path<String>,
+    Json(body): Json<UpsertFeatureFlagRequest>,
+) -> Result<Json<StoredFeatureFlag>, ApiError> {
+    require_platform_admin(&admin)?;
+    validate_key(&key)?;
+    if body.rollout_percent > 100 {
+        return Err(ApiError::bad_request(
+            "rollout_percent must be between 0 and 100",
+        ));
+    }
+    let mut allowed_users: Vec<String> = body
+        .allowed_users
+        .iter()
+        .map(|u| u.trim().to_string())
+        .filter(|u| !u.is_empty())
+        .collect();
+    allowed_users.sort();
+    allowed_users.dedup();
+    if allowed_users.len() > MAX_ALLOWED_USERS {
+        return Err(ApiError::bad_request(format!(
+            "allowed_users may list at most {MAX_ALLOWED_USERS} users"
+        )));
+    }
+
+    let mut tenants: Vec<String> = body.tenants.iter().map(|t| t.trim().to_string()).collect();
+    tenants.sort();
+    tenants.dedup();
+    if let Some(invalid) = tenants.iter().find(|t| !is_valid_tenant_id(t)) {
+        return Err(ApiError::bad_request(format!(
+            "Invalid tenant ID: {invalid}"
+        )));
+    }
+
+    let repo = FeatureFlagRepository::new(state.storage());
+    let old = repo.get(&key).ok();
+    let now = Utc::now();
+    let flag = StoredFeatureFlag {
+        key: key.clone(),
+        description: body
+            .description
+            .map(|d| d.trim().to_string())
+            .filter(|d| !d.is_empty()),
+        enabled: body.enabled,
+        rollout_percent: body.rollout_percent,
+        allowed_users,
+        tenants,
+        created_at: old.as_ref().map_or(now, |f| f.created_at),
+        updated_at: now,
+        updated_by: admin.user_id.clone(),
+    };
+    repo.save(&flag).context("Failed to save feature flag")?;
+
+    log_flag_change(&state, &admin.user_id, &key, old.as_ref(), Some(&flag));
+    Ok(Json(flag))
+}
+
+/// Delete a feature flag (admin only). Gated capabilities are off for
+/// everyone once their flag is gone.
+#[utoipa::path(
+    delete,
+    path = "/v1/admin/feature-flags/{key}",
+    tag = "Admin",
+    params(("key" = String, Path, description = "Flag key")),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 204, description = "Flag deleted"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not authorized (platform admin required)"),
+        (status = 404, description = "Flag not found")
+    )
+)]
+pub async fn delete_feature_flag(
+    AdminOnly(admin): AdminOnly,
+    State(state): State<AppState>,
+    Path(key): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    require_platform_admin(&admin)?;
+    validate_key(&key)?;
+    let repo = FeatureFlagRepository::new(state.storage());
+    let old = repo
+        .get(&key)
+        .map_err(|_| ApiError::not_found("Feature flag not found"))?;
+    repo.delete(&key).context("Failed to delete feature flag")?;
+
+    log_flag_change(&state, &admin.user_id, &key, Some(&old), None);
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Feature flags that are on for the current user.
+#[utoipa::path(
+    get,
+    path = "/v1/users/me/features",
+    tag = "Users",
+    security(("bearer" = [])),
+    responses(
+        (status = 200, description = "Enabled features", body = MyFeaturesResponse),
+        (status = 401, description = "Not authenticated")
+    )
+)]
+pub async fn list_my_features(
+    Auth(user): Auth,
+    State(state): State<AppState>,
+) -> Result<Json<MyFeaturesResponse>, ApiError> {
+    let features = FeatureFlagRepository::new(state.storage())
+        .list_all()
+        .context("Failed to list feature flags")?
+        .into_iter()
+        .filter(|flag| flag.is_enabled_for(&user.user_id, user.tenant_id.as_deref()))
+        .map(|flag| flag.key)
+        .collect();
+    Ok(Json(MyFeaturesResponse { features }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::{AuthenticatedUser, Role};
+
+    fn user(user_id: &str, role: Role) -> AuthenticatedUser {
+        AuthenticatedUser {
+            user_id: user_id.to_string(),
+            role,
+            session_id: None,
+            issuer: "https://test.clerk.dev".into(),
+            expires_at: Utc::now().timestamp() + 3600,
+            tenant_id: None,
+        }
+    }
+
+    fn body(
+        enabled: bool,
+        rollout_percent: u8,
+        allowed_users: &[&str],
+    ) -> UpsertFeatureFlagRequest {
+        UpsertFeatureFlagRequest {
+            description: Some("Token swaps".to_string()),
+            enabled,
+            rollout_percent,
+            allowed_users: allowed_users.iter().map(|u| u.to_string()).collect(),
+            tenants: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn keys_must_be_safe_file_names() {
+        for key in ["swaps", "staking.v2", "fiat-card_payouts"] {
+            assert!(validate_key(key).is_ok(), "{key}");
+        }
+        for key in ["", "Swaps", "../wallets", ".hidden", "a b", &"x".repeat(65)] {
+            assert!(validate_key(key).is_err(), "{key}");
+        }
+    }
+
+    #[tokio::test]
+    async fn admins_roll_out_flags_that_users_then_see() {
+        let state = AppState::default();
+        let admin = user("admin_1", Role::Admin);
+
+        let Json(flag) = put_feature_flag(
+            AdminOnly(admin.clone()),
+            State(state.clone()),
+            Path("swaps".to_string()),
+            Json(body(true, 0, &["user_beta", " user_beta "])),
+        )
+        .await
+        .unwrap();
+        assert_eq!(flag.allowed_users, ["user_beta"]);
+        let created_at = flag.created_at;
+
+        let Json(mine) =
+            list_my_features(Auth(user("user_beta", Role::Client)), State(state.clone()))
+                .await
+                .unwrap();
+        assert_eq!(mine.features, ["swaps"]);
+        let Json(mine) =
+            list_my_features(Auth(user("user_other", Role::Client)), State(state.clone()))
+                .await
+                .unwrap();
+        assert!(mine.features.is_empty());
+
+        let Json(flag) = put_feature_flag(
+            AdminOnly(admin.clone()),
+            State(state.clone()),
+            Path("swaps".to_string()),
+            Json(body(true, 100, &[])),
+        )
+        .await
+        .unwrap();
+        assert_eq!(flag.created_at, created_at);
+        assert!(state.feature_enabled("swaps", "user_other", None));
+
+        let err = put_feature_flag(
+            AdminOnly(admin.clone()),
+            State(state.clone()),
+            Path("swaps".to_string()),
+            Json(body(true, 101, &[])),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+
+        let status = delete_feature_flag(
+            AdminOnly(admin.clone()),
+            State(state.clone()),
+            Path("swaps".to_string()),
+        )
+        .await
+        .unwrap();
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        assert!(!state.feature_enabled("swaps", "user_other", None));
+        let err = delete_feature_flag(AdminOnly(admin), State(state), Path("swaps".to_string()))
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, StatusCode::NOT_FOUND);
+    }
+}
@@ -10,40 +10,58 @@ use std::{
     sync::{Arc, Mutex, OnceLock},
 };
 
-use alloy::primitives::U256;
+use alloy::primitives::{
+    utils::{format_ether, parse_ether},
+    U256,
+};
 use axum::{
     extract::{Path, Query, State},
     http::{HeaderMap, StatusCode},
     Json,
 };
-use chrono::{TimeDelta, Utc};
+use chrono::{DateTime, Datelike, NaiveDate, TimeDelta, Utc, Weekday};
 use serde::{Deserialize, Serialize};
 use tracing::{info, warn};
 use utoipa::{IntoParams, ToSchema};
 
 use crate::{
-    audit_log,
+    api::{
+        admin::require_platform_admin,
+        fiat_beneficiaries, fiat_destination_kyc, fiat_name_check,
+        fiat_return::{self, FiatReturnClient},
+        iban, reserve_queue,
+        wallets::ensure_unlocked,
+    },
     auth::{AdminOnly, Auth},
     blockchain::{
-        avax_fuji, ensure_fuji_network, parse_amount, wallet_from_pem, AvaxClient, TxBuilder,
+        address_key, avax_fuji, disperse::encode_disperse_token_call, ensure_fuji_network,
+        minter::encode_mint_call, parse_amount, same_address, tokens, wallet_from_pem, AvaxClient,
+        TxBuilder, NETWORK_FUJI, REUR_TOKEN,
     },
-    error::ApiError,
-    providers::truelayer::{
-        CreateOffRampRequest, CreateOnRampRequest, ProviderExecutionStatus, TrueLayerClient,
-        TrueLayerError,
+    error::{ApiError, StorageContext},
+    providers::{
+        card::CardPaymentClient,
+        fiat::{FiatProvider, FiatProviderError, OnRampProvider, CARD_PROVIDER_ID},
+        truelayer::{
+            CreateOffRampRequest, CreateOnRampRequest, ProviderExecutionStatus, TrueLayerClient,
+            TrueLayerError,
+        },
     },
     state::AppState,
     storage::{
-        AuditEventType, FiatDirection, FiatRequestRepository, FiatRequestStatus,
-        FiatServiceWalletMetadata, FiatServiceWalletRepository, StoredFiatRequest,
-        StoredTransaction, TokenType, TxCache, TxDatabase, TxStatus, WalletRepository,
+        AuditEvent, AuditEventType, AuditRepository, ClawbackStatus, DestinationKycCheck,
+        FeeSchedule, FiatActor, FiatChargeback, FiatDirection, FiatRequestRepository,
+        FiatRequestStatus, FiatServiceWalletMetadata, FiatServiceWalletRepository, GasSpendEntry,
+        OwnershipEnforcer, ProviderCredentials, ReserveGasLedgerRepository, ReserveKeySource,
+        ReserveSendKind, ReserveSendStatus, StorageError, StoredFiatRequest, StoredTransaction,
+        TenantConfigRepository, TokenType, TxCache, TxDatabase, TxStatus, WalletRepository,
         WalletStatus,
     },
 };
 
-const DEFAULT_PROVIDER: &str = "truelayer_sandbox";
-const SUPPORTED_PROVIDER_IDS: [&str; 1] = [DEFAULT_PROVIDER];
-const REUR_DECIMALS: u8 = 6;
+const DEFAULT_PROVIDER: &str = crate::providers::fiat::TRUELAYER_PROVIDER_ID;
+/// Supported providers, in the order used when a request does not pick one.
+const SUPPORTED_PROVIDER_IDS: [&str; 2] = [DEFAULT_PROVIDER, CARD_PROVIDER_ID];
 const REUR_CONTRACT_ENV: &str = "REUR_CONTRACT_ADDRESS_FUJI";
 
 const FIAT_MIN_CONFIRMATIONS_ENV: &str = "FIAT_MIN_CONFIRMATIONS";
@@ -57,12 +75,31 @@ const MAX_SETTLEMENT_ATTEMPTS: u32 = 10;
 const SETTLEMENT_RETRY_BASE_SECS: i64 = 15;
 /// Maximum delay cap between settlement retries (seconds).
 const SETTLEMENT_RETRY_MAX_SECS: i64 = 300;
+/// Daily cap on reserve-wallet settlement gas, as a decimal AVAX amount.
+const FIAT_RESERVE_DAILY_GAS_BUDGET_ENV: &str = "FIAT_RESERVE_DAILY_GAS_BUDGET_AVAX";
+/// How long after settlement the poller keeps looking for the transfer
+/// receipt to record its gas cost.
+const GAS_ACCOUNTING_WINDOW_HOURS: i64 = 24;
+/// Widest date range served by the reconciliation report.
+const RECONCILIATION_MAX_DAYS: i64 = 31;
+/// Disperse contract used to batch small on-ramp settlements. Unset disables
+/// batching.
+const FIAT_DISPERSE_CONTRACT_ENV: &str = "FIAT_DISPERSE_CONTRACT_ADDRESS_FUJI";
+/// On-ramps at or below this EUR amount are eligible for batched settlement.
+const FIAT_SETTLEMENT_BATCH_MAX_EUR_ENV: &str = "FIAT_SETTLEMENT_BATCH_MAX_EUR";
+const DEFAULT_SETTLEMENT_BATCH_MAX_EUR: &str = "50.00";
+/// How long a batch-eligible on-ramp waits for others before settling alone.
+const SETTLEMENT_BATCH_WINDOW_SECS: i64 = 60;
+/// Most recipients in one disperse transaction.
+const MAX_SETTLEMENT_BATCH_SIZE: usize = 20;
 /// TrueLayer sandbox JWKS URL for webhook signature verification.
 const TRUELAYER_SANDBOX_JWKS_URL: &str = "https://webhooks.truelayer-sandbox.com/.well-known/jwks";
-const ACTIVE_FIAT_STATUSES: [FiatRequestStatus; 5] = [
+const ACTIVE_FIAT_STATUSES: [FiatRequestStatus; 7] = [
     FiatRequestStatus::Queued,
     FiatRequestStatus::AwaitingProvider,
     FiatRequestStatus::AwaitingUserDeposit,
+    FiatRequestStatus::ReviewRequired,
+    FiatRequestStatus::KycRequired,
     FiatRequestStatus::SettlementPending,
     FiatRequestStatus::ProviderPending,
 ];
@@ -83,12 +120,24 @@ pub struct CreateFiatRequest {
     /// Optional free-form note.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub note: Option<String>,
-    /// Beneficiary account holder name (required for off-ramp).
+    /// Saved beneficiary to pay out to (off-ramp only). Replaces the
+    /// inline beneficiary fields.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub beneficiary_id: Option<String>,
+    /// Beneficiary account holder name (off-ramp without `beneficiary_id`).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub beneficiary_account_holder_name: Option<String>,
-    /// Beneficiary IBAN (required for off-ramp).
+    /// Beneficiary IBAN (off-ramp without `beneficiary_id`).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub beneficiary_iban: Option<String>,
+    /// Client to return to after the hosted payment page (on-ramp only,
+    /// `web` default).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub return_client: Option<FiatReturnClient>,
+    /// Explicit return URI (on-ramp only). Must be allowlisted for the
+    /// deployment; defaults to the client's configured URI.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub return_uri: Option<String>,
 }
 
 /// Fiat request response returned to clients.
@@ -102,6 +151,9 @@ pub struct FiatRequestResponse {
     pub direction: FiatDirection,
     /// Amount in EUR.
     pub amount_eur: String,
+    /// Tenant fee withheld from the amount, in EUR.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fee_eur: Option<String>,
     /// Provider identifier.
     pub provider: String,
     /// Current status.
@@ -141,12 +193,81 @@ pub struct FiatRequestResponse {
     /// Optional last chain sync time.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub last_chain_sync_at: Option<String>,
+    /// Hashes of the on-chain transactions that settled this request.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub settlement_tx_ids: Vec<String>,
+    /// Card chargeback raised against this on-ramp, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chargeback: Option<FiatChargeback>,
+    /// Saved beneficiary the off-ramp pays out to, if one was used.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub beneficiary_id: Option<String>,
+    /// Destination wallet KYC check, for large on-ramps.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub destination_kyc: Option<DestinationKycCheck>,
+    /// Payment scheme of the off-ramp payout, once the provider reports it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scheme_id: Option<String>,
+    /// When the off-ramp payout was executed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub executed_at: Option<String>,
+    /// When the payout should reach the beneficiary's bank account.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expected_arrival: Option<PayoutArrivalEstimate>,
+    /// Progress milestones reached so far, oldest first.
+    pub timeline: Vec<FiatTimelineEntry>,
     /// Creation time.
     pub created_at: String,
     /// Last update time.
     pub updated_at: String,
 }
 
+/// Expected arrival of an off-ramp payout, based on its payment scheme.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PayoutArrivalEstimate {
+    /// Whether the scheme credits the beneficiary within seconds of
+    /// execution (e.g. SEPA Instant).
+    pub instant: bool,
+    /// Latest expected arrival (RFC 3339).
+    pub expected_by: String,
+    /// The payout was executed over an instant scheme, so the funds are
+    /// already available to the beneficiary.
+    pub funds_available: bool,
+}
+
+/// User-facing milestone of a fiat request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum FiatTimelineStep {
+    /// Request accepted.
+    Created,
+    /// On-ramp payment authorized at the provider, or off-ramp payout
+    /// accepted by the provider.
+    ProviderAuthorized,
+    /// Off-ramp rEUR deposit seen on-chain.
+    DepositDetected,
+    /// Funds delivered: rEUR transferred (on-ramp) or payout executed
+    /// (off-ramp).
+    Settled,
+    /// Request finished successfully.
+    Completed,
+    /// Request failed.
+    Failed,
+    /// The card payment was disputed; delivered rEUR is recovered.
+    ChargedBack,
+}
+
+/// A reached milestone with the time it was reached.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct FiatTimelineEntry {
+    pub step: FiatTimelineStep,
+    /// RFC 3339 timestamp.
+    pub at: String,
+    /// Transaction hash or failure reason, when relevant.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
 /// List response for fiat requests.
 #[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct FiatRequestListResponse {
@@ -202,6 +323,8 @@ pub struct FiatServiceWalletStatusResponse {
     pub public_address: String,
     /// Whether wallet was present/bootstrapped.
     pub bootstrapped: bool,
+    /// Provenance of the reserve key (enclave-generated or key ceremony).
+    pub key_source: ReserveKeySource,
     /// Fuji-only network value.
     pub chain_network: String,
     /// Configured rEUR contract.
@@ -214,6 +337,65 @@ pub struct FiatServiceWalletStatusResponse {
     pub reur_balance_raw: String,
 }
 
+/// Admin request to mint rEUR into the reserve wallet.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct FiatReserveTopUpRequest {
+    /// EUR amount to mint (e.g. "1000.00").
+    pub amount_eur: String,
+}
+
+/// Reserve top-up result.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct FiatReserveTopUpResponse {
+    /// Mint transaction hash.
+    pub tx_hash: String,
+    /// Block explorer URL.
+    pub explorer_url: String,
+    /// Reserve wallet that received the minted rEUR.
+    pub minted_to: String,
+    /// Normalized EUR amount minted.
+    pub amount_eur: String,
+}
+
+/// Query params for the reconciliation report (inclusive UTC dates).
+#[derive(Debug, Clone, Deserialize, IntoParams)]
+pub struct FiatReconciliationQuery {
+    /// First day (`YYYY-MM-DD`). Defaults to six days before `to`.
+    pub from: Option<String>,
+    /// Last day (`YYYY-MM-DD`). Defaults to today.
+    pub to: Option<String>,
+}
+
+/// Reserve-wallet gas spend for one UTC day.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct FiatReconciliationDay {
+    /// UTC date (`YYYY-MM-DD`).
+    pub date: String,
+    /// Total gas spend in wei.
+    pub gas_spent_wei: String,
+    /// Total gas spend in AVAX.
+    pub gas_spent_avax: String,
+    /// Whether the day's spend reached the configured budget.
+    pub budget_exhausted: bool,
+    /// Per-request settlement gas entries.
+    pub entries: Vec<GasSpendEntry>,
+}
+
+/// Reserve reconciliation report.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct FiatReconciliationReport {
+    pub from: String,
+    pub to: String,
+    /// Configured daily gas budget in AVAX, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub daily_gas_budget_avax: Option<String>,
+    /// Gas spend across the whole range in AVAX.
+    pub total_gas_spent_avax: String,
+    pub days: Vec<FiatReconciliationDay>,
+    /// Completed on-ramps whose settlement gas has not been recorded yet.
+    pub unaccounted_settlements: Vec<String>,
+}
+
 /// Manual sync response.
 #[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct FiatSyncResponse {
@@ -257,7 +439,7 @@ pub struct TrueLayerWebhookPayload {
     data: Option<serde_json::Value>,
 }
 
-fn parse_amount_to_minor(amount: &str) -> Result<(String, u64), ApiError> {
+pub(crate) fn parse_amount_to_minor(amount: &str) -> Result<(String, u64), ApiError> {
     let trimmed = amount.trim();
     if trimmed.is_empty() {
         return Err(ApiError::bad_request(
@@ -318,8 +500,17 @@ fn parse_amount_to_minor(amount: &str) -> Result<(String, u64), ApiError> {
     Ok((normalized, minor))
 }
 
-fn parse_amount_to_token_minor_u256(amount: &str) -> Result<U256, ApiError> {
-    parse_amount(amount, REUR_DECIMALS)
+/// Decimals of the settlement token, as listed in the token registry.
+/// Falls back to rEUR's when the contract is not configured or listed.
+pub(crate) fn settlement_decimals() -> u8 {
+    resolve_reur_contract_address()
+        .ok()
+        .and_then(|contract| tokens().get(NETWORK_FUJI, &contract))
+        .map_or(REUR_TOKEN.decimals, |token| token.decimals)
+}
+
+pub(crate) fn parse_amount_to_token_minor_u256(amount: &str) -> Result<U256, ApiError> {
+    parse_amount(amount, settlement_decimals())
         .map_err(|e| ApiError::bad_request(format!("invalid amount_eur for token settlement: {e}")))
 }
 
@@ -330,21 +521,32 @@ fn u256_to_u64(value: U256) -> Result<u64, ApiError> {
         .map_err(|_| ApiError::bad_request("amount_eur is too large for settlement"))
 }
 
-fn provider_summaries() -> Vec<FiatProviderSummary> {
-    let enabled = TrueLayerClient::is_configured();
+fn provider_summaries(credentials: &ProviderCredentials) -> Vec<FiatProviderSummary> {
+    let enabled = TrueLayerClient::is_configured_for(credentials.truelayer.as_ref());
     let supports_on_ramp = enabled;
     let supports_off_ramp = enabled;
-    vec![FiatProviderSummary {
-        provider_id: DEFAULT_PROVIDER.to_string(),
-        display_name: "TrueLayer Sandbox".to_string(),
-        sandbox: true,
-        enabled,
-        supports_on_ramp,
-        supports_off_ramp,
-    }]
+    let card_enabled = CardPaymentClient::is_configured_for(credentials.card.as_ref());
+    vec![
+        FiatProviderSummary {
+            provider_id: DEFAULT_PROVIDER.to_string(),
+            display_name: "TrueLayer Sandbox".to_string(),
+            sandbox: true,
+            enabled,
+            supports_on_ramp,
+            supports_off_ramp,
+        },
+        FiatProviderSummary {
+            provider_id: CARD_PROVIDER_ID.to_string(),
+            display_name: "Card (sandbox)".to_string(),
+            sandbox: true,
+            enabled: card_enabled,
+            supports_on_ramp: card_enabled,
+            supports_off_ramp: false,
+        },
+    ]
 }
 
-fn resolve_provider_id(raw_provider: Option<String>) -> Result<String, ApiError> {
+pub(crate) fn resolve_provider_id(raw_provider: Option<String>) -> Result<String, ApiError> {
     let provider = raw_provider
         .as_deref()
         .map(str::trim)
@@ -362,21 +564,65 @@ fn resolve_provider_id(raw_provider: Option<String>) -> Result<String, ApiError>
     }
 }
 
-fn ensure_provider_enabled(provider: &str, _direction: FiatDirection) -> Result<(), ApiError> {
-    if provider != DEFAULT_PROVIDER {
-        return Err(ApiError::bad_request("Unsupported provider"));
+fn ensure_provider_enabled(
+    provider: &str,
+    direction: FiatDirection,
+    credentials: &ProviderCredentials,
+) -> Result<(), ApiError> {
+    match provider {
+        DEFAULT_PROVIDER => {
+            if !TrueLayerClient::is_configured_for(credentials.truelayer.as_ref()) {
+                return Err(ApiError::service_unavailable(
+                    "TrueLayer sandbox is not configured. Set TRUELAYER_* environment variables.",
+                ));
+            }
+        }
+        CARD_PROVIDER_ID => {
+            if direction == FiatDirection::OffRamp {
+                return Err(ApiError::bad_request(
+                    "Card payments only support on-ramp requests",
+                ));
+            }
+            if !CardPaymentClient::is_configured_for(credentials.card.as_ref()) {
+                return Err(ApiError::service_unavailable(
+                    "Card payments are not configured. Set CARD_PSP_SECRET_KEY.",
+                ));
+            }
+        }
+        _ => return Err(ApiError::bad_request("Unsupported provider")),
     }
 
-    if !TrueLayerClient::is_configured() {
-        return Err(ApiError::service_unavailable(
-            "TrueLayer sandbox is not configured. Set TRUELAYER_* environment variables.",
-        ));
+    Ok(())
+}
+
+/// Pick the provider for a new request: the requested one if given,
+/// otherwise the first supported provider enabled for the direction.
+fn select_provider(
+    raw_provider: Option<String>,
+    direction: FiatDirection,
+    credentials: &ProviderCredentials,
+) -> Result<String, ApiError> {
+    let requested = raw_provider.filter(|value| !value.trim().is_empty());
+    if requested.is_some() {
+        let provider = resolve_provider_id(requested)?;
+        ensure_provider_enabled(&provider, direction, credentials)?;
+        return Ok(provider);
     }
 
-    Ok(())
+    SUPPORTED_PROVIDER_IDS
+        .iter()
+        .find(|id| ensure_provider_enabled(id, direction, credentials).is_ok())
+        .map(|id| id.to_string())
+        .ok_or_else(|| {
+            ensure_provider_enabled(DEFAULT_PROVIDER, direction, credentials)
+                .err()
+                .unwrap_or_else(|| ApiError::service_unavailable("No fiat provider is configured"))
+        })
 }
 
-fn normalize_offramp_account_holder_name(raw: Option<String>) -> Result<String, ApiError> {
+pub(crate) fn normalize_offramp_account_holder_name(
+    raw: Option<String>,
+) -> Result<String, ApiError> {
     let name = raw
         .as_deref()
         .map(str::trim)
@@ -395,7 +641,7 @@ fn normalize_offramp_account_holder_name(raw: Option<String>) -> Result<String,
     Ok(name)
 }
 
-fn normalize_offramp_iban(raw: Option<String>) -> Result<String, ApiError> {
+pub(crate) fn normalize_offramp_iban(raw: Option<String>) -> Result<String, ApiError> {
     let compact = raw
         .as_deref()
         .map(|value| {
@@ -422,11 +668,13 @@ fn normalize_offramp_iban(raw: Option<String>) -> Result<String, ApiError> {
             "beneficiary_iban must contain only letters and numbers",
         ));
     }
+    iban::validate_iban(&compact)
+        .map_err(|e| ApiError::bad_request(format!("beneficiary_iban is invalid: {e}")))?;
 
     Ok(compact)
 }
 
-fn map_provider_error(error: TrueLayerError) -> ApiError {
+pub(crate) fn map_provider_error(error: TrueLayerError) -> ApiError {
     match error {
         TrueLayerError::MissingConfig(message) => ApiError::service_unavailable(format!(
             "TrueLayer sandbox configuration error: {message}"
@@ -443,7 +691,17 @@ fn map_provider_error(error: TrueLayerError) -> ApiError {
     }
 }
 
-fn map_onramp_provider_status(status: ProviderExecutionStatus) -> FiatRequestStatus {
+pub(crate) fn map_fiat_provider_error(error: FiatProviderError) -> ApiError {
+    match error {
+        FiatProviderError::TrueLayer(error) => map_provider_error(error),
+        FiatProviderError::Card(error) => ApiError::service_unavailable(error.to_string()),
+        FiatProviderError::Unknown(provider) => {
+            ApiError::bad_request(format!("Unsupported provider `{provider}`"))
+        }
+    }
+}
+
+pub(crate) fn map_onramp_provider_status(status: ProviderExecutionStatus) -> FiatRequestStatus {
     match status {
         ProviderExecutionStatus::Completed => FiatRequestStatus::SettlementPending,
         ProviderExecutionStatus::Failed => FiatRequestStatus::Failed,
@@ -459,6 +717,76 @@ fn map_offramp_provider_status(status: ProviderExecutionStatus) -> FiatRequestSt
     }
 }
 
+/// Record the payout scheme and execution time reported by the provider.
+/// Sandbox payouts report `unknown` until routed; that never replaces a
+/// scheme already known.
+fn note_payout_execution(
+    record: &mut StoredFiatRequest,
+    scheme_id: Option<&str>,
+    executed_at: Option<DateTime<Utc>>,
+) {
+    if let Some(scheme_id) = scheme_id.filter(|s| !s.is_empty() && *s != "unknown") {
+        record.scheme_id = Some(scheme_id.to_string());
+    }
+    if record.status == FiatRequestStatus::Completed && record.executed_at.is_none() {
+        // Providers omit the timestamp on some responses; the sync that
+        // observed execution is the next best bound.
+        record.executed_at = Some(executed_at.unwrap_or_else(Utc::now));
+    }
+}
+
+/// Worst-case time from payout execution to the beneficiary's account.
+enum SchemeArrival {
+    /// Credited within the given time of execution.
+    Instant(TimeDelta),
+    /// Credited by the end of the next business day.
+    NextBusinessDay,
+}
+
+fn scheme_arrival(scheme_id: &str) -> Option<SchemeArrival> {
+    match scheme_id {
+        "sepa_credit_transfer_instant" => Some(SchemeArrival::Instant(TimeDelta::seconds(10))),
+        "faster_payments_service" => Some(SchemeArrival::Instant(TimeDelta::hours(2))),
+        // Sandbox payouts move between TrueLayer accounts.
+        "internal_transfer" => Some(SchemeArrival::Instant(TimeDelta::zero())),
+        "sepa_credit_transfer" => Some(SchemeArrival::NextBusinessDay),
+        _ => None,
+    }
+}
+
+/// End of the business day following `from` (UTC, weekends skipped).
+fn next_business_day(from: DateTime<Utc>) -> DateTime<Utc> {
+    let mut day = from.date_naive();
+    loop {
+        day = day.succ_opt().unwrap_or(day);
+        if !matches!(day.weekday(), Weekday::Sat | Weekday::Sun) {
+            break;
+        }
+    }
+    day.and_hms_opt(23, 59, 59)
+        .map(|ts| ts.and_utc())
+        .unwrap_or(from)
+}
+
+fn estimate_payout_arrival(record: &StoredFiatRequest) -> Option<PayoutArrivalEstimate> {
+    if record.direction != FiatDirection::OffRamp || record.status == FiatRequestStatus::Failed {
+        return None;
+    }
+    let arrival = scheme_arrival(record.scheme_id.as_deref()?)?;
+    // Before execution the payout can still be queued at the provider, so
+    // estimate from now rather than from when it was accepted.
+    let from = record.executed_at.unwrap_or_else(Utc::now);
+    let (instant, expected_by) = match arrival {
+        SchemeArrival::Instant(window) => (true, from + window),
+        SchemeArrival::NextBusinessDay => (false, next_business_day(from)),
+    };
+    Some(PayoutArrivalEstimate {
+        instant,
+        expected_by: expected_by.to_rfc3339(),
+        funds_available: instant && record.executed_at.is_some(),
+    })
+}
+
 fn map_webhook_status(raw: &str) -> ProviderExecutionStatus {
     let normalized = raw.trim().to_ascii_lowercase();
     if normalized.contains("fail")
@@ -480,7 +808,7 @@ fn map_webhook_status(raw: &str) -> ProviderExecutionStatus {
     }
 }
 
-fn resolve_reur_contract_address() -> Result<String, ApiError> {
+pub(crate) fn resolve_reur_contract_address() -> Result<String, ApiError> {
     let value = env::var(REUR_CONTRACT_ENV)
         .ok()
         .map(|v| v.trim().to_string())
@@ -503,6 +831,30 @@ fn resolve_reur_contract_address() -> Result<String, ApiError> {
     Ok(value)
 }
 
+fn resolve_disperse_contract_address() -> Option<String> {
+    let value = env::var(FIAT_DISPERSE_CONTRACT_ENV)
+        .ok()
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())?;
+    if !value.starts_with("0x")
+        || value.len() != 42
+        || !value[2..].chars().all(|c| c.is_ascii_hexdigit())
+    {
+        warn!("{FIAT_DISPERSE_CONTRACT_ENV} is not a valid EVM address; batching disabled");
+        return None;
+    }
+    Some(value)
+}
+
+fn settlement_batch_max_minor() -> u64 {
+    env::var(FIAT_SETTLEMENT_BATCH_MAX_EUR_ENV)
+        .ok()
+        .and_then(|v| parse_amount_to_minor(&v).ok())
+        .or_else(|| parse_amount_to_minor(DEFAULT_SETTLEMENT_BATCH_MAX_EUR).ok())
+        .map(|(_, minor)| minor)
+        .unwrap_or(0)
+}
+
 fn fiat_min_confirmations() -> u64 {
     env::var(FIAT_MIN_CONFIRMATIONS_ENV)
         .ok()
@@ -511,12 +863,13 @@ fn fiat_min_confirmations() -> u64 {
         .max(1)
 }
 
-fn to_response(record: &StoredFiatRequest) -> FiatRequestResponse {
+pub(crate) fn to_response(record: &StoredFiatRequest) -> FiatRequestResponse {
     FiatRequestResponse {
         request_id: record.request_id.clone(),
         wallet_id: record.wallet_id.clone(),
         direction: record.direction,
         amount_eur: record.amount_eur.clone(),
+        fee_eur: record.fee_eur.clone(),
         provider: record.provider.clone(),
         status: record.status,
         chain_network: record.chain_network.clone(),
@@ -531,11 +884,90 @@ fn to_response(record: &StoredFiatRequest) -> FiatRequestResponse {
         failure_reason: record.failure_reason.clone(),
         last_provider_sync_at: record.last_provider_sync_at.map(|ts| ts.to_rfc3339()),
         last_chain_sync_at: record.last_chain_sync_at.map(|ts| ts.to_rfc3339()),
+        settlement_tx_ids: record.settlement_tx_ids.clone(),
+        chargeback: record.chargeback.clone(),
+        beneficiary_id: record.beneficiary_id.clone(),
+        destination_kyc: record.destination_kyc.clone(),
+        scheme_id: record.scheme_id.clone(),
+        executed_at: record.executed_at.map(|ts| ts.to_rfc3339()),
+        expected_arrival: estimate_payout_arrival(record),
+        timeline: build_timeline(record),
         created_at: record.created_at.to_rfc3339(),
         updated_at: record.updated_at.to_rfc3339(),
     }
 }
 
+/// Derive user-facing milestones from the request's transition log.
+///
+/// Records persisted before the log existed only report creation and the
+/// milestone implied by their current status, dated `updated_at`.
+fn build_timeline(record: &StoredFiatRequest) -> Vec<FiatTimelineEntry> {
+    let mut timeline = vec![FiatTimelineEntry {
+        step: FiatTimelineStep::Created,
+        at: record.created_at.to_rfc3339(),
+        detail: None,
+    }];
+    let mut reach = |step: FiatTimelineStep, at: DateTime<Utc>, detail: Option<String>| {
+        // Status can bounce (e.g. a failed batch falls back to individual
+        // settlement); only the first time a milestone is reached counts.
+        if !timeline.iter().any(|entry| entry.step == step) {
+            timeline.push(FiatTimelineEntry {
+                step,
+                at: at.to_rfc3339(),
+                detail,
+            });
+        }
+    };
+
+    let transitions: Vec<_> = if record.transitions.is_empty() {
+        vec![(
+            record.status,
+            record.updated_at,
+            record.failure_reason.clone(),
+        )]
+    } else {
+        record
+            .transitions
+            .iter()
+            .map(|t| (t.status, t.at, t.detail.clone()))
+            .collect()
+    };
+
+    for (status, at, detail) in transitions {
+        match (record.direction, status) {
+            (FiatDirection::OnRamp, FiatRequestStatus::SettlementPending)
+            | (FiatDirection::OffRamp, FiatRequestStatus::ProviderPending) => {
+                reach(FiatTimelineStep::ProviderAuthorized, at, None);
+            }
+            (FiatDirection::OffRamp, FiatRequestStatus::SettlementPending) => {
+                reach(
+                    FiatTimelineStep::DepositDetected,
+                    at,
+                    record.deposit_tx_hash.clone(),
+                );
+            }
+            (direction, FiatRequestStatus::Completed) => {
+                let settlement_tx = match direction {
+                    FiatDirection::OnRamp => record.reserve_transfer_tx_hash.clone(),
+                    FiatDirection::OffRamp => None,
+                };
+                reach(FiatTimelineStep::Settled, at, settlement_tx);
+                reach(FiatTimelineStep::Completed, at, None);
+            }
+            (_, FiatRequestStatus::Failed) => reach(FiatTimelineStep::Failed, at, detail),
+            _ => {}
+        }
+    }
+    if let Some(chargeback) = &record.chargeback {
+        reach(
+            FiatTimelineStep::ChargedBack,
+            chargeback.received_at,
+            chargeback.clawback_tx_hash.clone(),
+        );
+    }
+    timeline
+}
+
 /// Cached JWKS bytes fetched from TrueLayer.
 static CACHED_JWKS: OnceLock<Vec<u8>> = OnceLock::new();
 /// In-process guard against concurrent syncs for the same request.
@@ -666,12 +1098,16 @@ fn extract_webhook_status(payload: &TrueLayerWebhookPayload) -> Option<ProviderE
         })
 }
 
-fn ensure_service_wallet(
+pub(crate) fn ensure_service_wallet(
     storage: &Arc<crate::storage::EncryptedStorage>,
 ) -> Result<FiatServiceWalletMetadata, ApiError> {
     let repo = FiatServiceWalletRepository::new(storage);
-    repo.get()
-        .map_err(|e| ApiError::internal(format!("Failed to load service wallet: {e}")))
+    repo.get().map_err(|e| match e {
+        StorageError::NotFound(_) => ApiError::service_unavailable(
+            "Fiat reserve wallet is not initialized; complete the key ceremony first",
+        ),
+        other => ApiError::internal(format!("Failed to load service wallet: {other}")),
+    })
 }
 
 /// Returns `true` when the error message indicates the sending account
@@ -702,135 +1138,603 @@ fn should_skip_settlement_retry(record: &StoredFiatRequest) -> bool {
     Utc::now() - record.updated_at < cooldown
 }
 
-async fn send_reserve_transfer(
-    storage: &Arc<crate::storage::EncryptedStorage>,
-    to: &str,
-    amount_eur: &str,
-) -> Result<crate::blockchain::transactions::SendResult, ApiError> {
-    let contract = resolve_reur_contract_address()?;
-    let service_repo = FiatServiceWalletRepository::new(storage);
-    let service_wallet = ensure_service_wallet(storage)?;
-
-    let private_key_pem = service_repo
-        .read_private_key()
-        .map_err(|e| ApiError::internal(format!("Failed to read service wallet key: {e}")))?;
+/// Parse the configured daily gas budget into wei. Unset or invalid means
+/// no budget.
+fn resolve_daily_gas_budget_wei() -> Option<u128> {
+    let raw = env::var(FIAT_RESERVE_DAILY_GAS_BUDGET_ENV).ok()?;
+    match parse_ether(raw.trim()).map(u128::try_from) {
+        Ok(Ok(wei)) => Some(wei),
+        _ => {
+            warn!(value = %raw, "Ignoring invalid {FIAT_RESERVE_DAILY_GAS_BUDGET_ENV}");
+            None
+        }
+    }
+}
 
-    let eth_wallet = wallet_from_pem(&private_key_pem)
-        .map_err(|e| ApiError::internal(format!("Failed to load service wallet signer: {e}")))?;
+fn utc_day(at: DateTime<Utc>) -> String {
+    at.format("%Y-%m-%d").to_string()
+}
 
-    let amount_minor = parse_amount_to_token_minor_u256(amount_eur)?;
-    let tx_builder = TxBuilder::new(avax_fuji(), eth_wallet)
-        .await
-        .map_err(|e| ApiError::service_unavailable(format!("Failed to connect to chain: {e}")))?;
+fn format_avax(wei: u128) -> String {
+    format_ether(U256::from(wei))
+}
 
-    tx_builder
-        .send_token(to, &contract, amount_minor, None, None)
-        .await
-        .map_err(|e| ApiError::service_unavailable(format!("Reserve transfer failed: {e}")))
-        .inspect(|_result| {
-            let _ = service_wallet;
-        })
+/// Returns `(spent, budget)` in wei when today's settlement gas has reached
+/// the configured budget.
+fn daily_gas_budget_exhausted(
+    storage: &crate::storage::EncryptedStorage,
+    budget_wei: Option<u128>,
+) -> Option<(u128, u128)> {
+    let budget = budget_wei?;
+    let spent = match ReserveGasLedgerRepository::new(storage).spent_on(&utc_day(Utc::now())) {
+        Ok(spent) => spent,
+        Err(error) => {
+            warn!(error = %error, "Failed to read reserve gas ledger; enforcing budget");
+            return Some((budget, budget));
+        }
+    };
+    (spent >= budget).then_some((spent, budget))
 }
 
-fn list_wallet_transactions(
-    tx_db: &TxDatabase,
-    wallet_address: &str,
-) -> Result<Vec<StoredTransaction>, ApiError> {
-    let mut cursor = None;
-    let mut transactions = Vec::new();
+/// Whether a completed on-ramp still needs its settlement gas recorded.
+fn needs_gas_accounting(record: &StoredFiatRequest, now: DateTime<Utc>) -> bool {
+    record.direction == FiatDirection::OnRamp
+        && record.status == FiatRequestStatus::Completed
+        && record.reserve_transfer_tx_hash.is_some()
+        && record.reserve_gas_spent_wei.is_none()
+        && record
+            .last_chain_sync_at
+            .map(|at| now - at < TimeDelta::hours(GAS_ACCOUNTING_WINDOW_HOURS))
+            .unwrap_or(false)
+}
 
-    loop {
-        let (page, next_cursor) = tx_db
-            .list_by_wallet(wallet_address, cursor.as_deref(), 200)
-            .map_err(|e| ApiError::internal(format!("Failed to list wallet transactions: {e}")))?;
-        let page_len = page.len();
-        transactions.extend(page.into_iter().map(|(tx, _direction)| tx));
+/// Record the reserve wallet's gas cost for a settled on-ramp once the
+/// transfer receipt is available. Leaves the record untouched otherwise so
+/// the next poll retries.
+async fn account_settlement_gas(
+    storage: &crate::storage::EncryptedStorage,
+    record: &mut StoredFiatRequest,
+) {
+    let Some(tx_hash) = record.reserve_transfer_tx_hash.clone() else {
+        return;
+    };
+    let receipt = match AvaxClient::new(avax_fuji()).await {
+        Ok(client) => client.get_transaction_receipt_status(&tx_hash).await,
+        Err(error) => Err(error),
+    };
+    let receipt = match receipt {
+        Ok(Some(receipt)) => receipt,
+        Ok(None) => return,
+        Err(error) => {
+            warn!(
+                request_id = %record.request_id,
+                tx_hash = %tx_hash,
+                error = %error,
+                "Failed to fetch settlement receipt for gas accounting"
+            );
+            return;
+        }
+    };
+    if !receipt.success {
+        warn!(
+            request_id = %record.request_id,
+            tx_hash = %tx_hash,
+            "Settlement transfer reverted on-chain"
+        );
+    }
 
-        if next_cursor.is_none() || page_len < 200 {
-            break;
+    let mut entry = GasSpendEntry::new(
+        &record.request_id,
+        &tx_hash,
+        receipt.gas_used,
+        receipt.effective_gas_price,
+    );
+    entry.batch_size = record.settlement_batch_size;
+    // Batch members share the transaction's fee evenly.
+    let share = entry.cost() / u128::from(record.settlement_batch_size.unwrap_or(1).max(1));
+    let cost_wei = share.to_string();
+    match ReserveGasLedgerRepository::new(storage).record(&utc_day(Utc::now()), entry) {
+        Ok(daily_total) => {
+            info!(
+                request_id = %record.request_id,
+                tx_hash = %tx_hash,
+                gas_used = receipt.gas_used,
+                cost_avax = %format_avax(share),
+                daily_total_avax = %format_avax(daily_total),
+                "Recorded reserve settlement gas"
+            );
+            record.reserve_gas_spent_wei = Some(cost_wei);
+            record.updated_at = Utc::now();
+        }
+        Err(error) => {
+            warn!(
+                request_id = %record.request_id,
+                error = %error,
+                "Failed to record reserve settlement gas"
+            );
         }
-        cursor = next_cursor;
     }
+}
 
-    Ok(transactions)
+/// Whether an on-ramp may be settled as part of a disperse batch: small,
+/// awaiting its first settlement attempt, and not yet paid.
+fn is_batch_candidate(record: &StoredFiatRequest, max_minor: u64) -> bool {
+    record.direction == FiatDirection::OnRamp
+        && record.status == FiatRequestStatus::SettlementPending
+        && record.reserve_transfer_tx_hash.is_none()
+        && record.settlement_attempts == 0
+        && record.chargeback.is_none()
+        && !fiat_destination_kyc::blocks_settlement(record)
+        && parse_amount_to_minor(&record.amount_eur)
+            .map(|(_, minor)| minor <= max_minor)
+            .unwrap_or(false)
 }
 
-async fn detect_confirmed_deposit(
-    storage: &Arc<crate::storage::EncryptedStorage>,
-    tx_db: &TxDatabase,
-    record: &StoredFiatRequest,
-) -> Result<Option<String>, ApiError> {
-    let reur_contract = resolve_reur_contract_address()?.to_ascii_lowercase();
-    let service_wallet = record
-        .service_wallet_address
-        .as_deref()
-        .ok_or_else(|| ApiError::internal("Missing service_wallet_address on fiat request"))?
-        .to_ascii_lowercase();
+/// Whether individual settlement should hold off so the batch sweep can pick
+/// the request up. After the batch window it settles alone.
+fn waiting_for_batch(record: &StoredFiatRequest, max_minor: u64, now: DateTime<Utc>) -> bool {
+    is_batch_candidate(record, max_minor)
+        && now - record.updated_at < TimeDelta::seconds(SETTLEMENT_BATCH_WINDOW_SECS)
+}
 
-    let expected_amount = parse_amount_to_token_minor_u256(&record.amount_eur)?;
-    let min_confirmations = fiat_min_confirmations();
+pub(crate) fn format_minor_eur(minor: u64) -> String {
+    format!("{}.{:02}", minor / 100, minor % 100)
+}
 
-    // Only consider transactions submitted AFTER this fiat request was
-    // created.  Without this guard, old deposits from previous requests
-    // (with the same amount) would be matched, causing the payout to
-    // fire before the user has made their deposit for THIS request.
-    let request_created = record.created_at;
-    let wallet = WalletRepository::new(storage)
-        .get(&record.wallet_id)
-        .map_err(|e| ApiError::internal(format!("Failed to load wallet metadata: {e}")))?;
-    let candidate_txs = list_wallet_transactions(tx_db, &wallet.public_address)?;
+/// Amount settled to the user: the requested amount less the tenant fee.
+pub(crate) fn net_amount_eur(record: &StoredFiatRequest) -> Result<String, ApiError> {
+    let Some(fee_eur) = record.fee_eur.as_deref() else {
+        return Ok(record.amount_eur.clone());
+    };
+    let (_, gross_minor) = parse_amount_to_minor(&record.amount_eur)?;
+    let (_, fee_minor) = parse_amount_to_minor(fee_eur)?;
+    Ok(format_minor_eur(gross_minor.saturating_sub(fee_minor)))
+}
 
-    let chain = AvaxClient::fuji()
-        .await
-        .map_err(|e| ApiError::service_unavailable(format!("Failed to connect to chain: {e}")))?;
-    let current_block = chain
-        .get_block_number()
-        .await
-        .map_err(|e| ApiError::service_unavailable(format!("Failed to read block number: {e}")))?;
+/// Fiat provider credentials of a tenant, empty when it uses the
+/// deployment's.
+pub(crate) fn tenant_provider_credentials(
+    storage: &crate::storage::EncryptedStorage,
+    tenant_id: Option<&str>,
+) -> ProviderCredentials {
+    TenantConfigRepository::new(storage)
+        .for_tenant(tenant_id)
+        .map(|config| config.provider_credentials)
+        .unwrap_or_default()
+}
 
-    for tx in candidate_txs {
-        // Skip transactions that pre-date this fiat request.
-        if tx.created_at < request_created {
-            continue;
+/// Settle small pending on-ramps for different wallets in one disperse
+/// transaction. Returns the number of requests settled.
+///
+/// Runs only when a disperse contract is configured and the reserve wallet
+/// has approved it for the batch total; otherwise the requests settle
+/// individually once their batch window passes. A failed batch sends every
+/// member back to individual settlement.
+pub(crate) async fn settle_onramp_batch(
+    storage: &Arc<crate::storage::EncryptedStorage>,
+    tx_db: &TxDatabase,
+    tx_cache: Option<&TxCache>,
+) -> usize {
+    let Some(disperse) = resolve_disperse_contract_address() else {
+        return 0;
+    };
+    let max_minor = settlement_batch_max_minor();
+    let repo = FiatRequestRepository::new(storage);
+    let mut candidates: Vec<StoredFiatRequest> = match repo.list_all() {
+        Ok(all) => all
+            .into_iter()
+            .filter(|r| is_batch_candidate(r, max_minor))
+            .collect(),
+        Err(error) => {
+            warn!(error = %error, "Batch settlement: failed to list fiat requests");
+            return 0;
         }
+    };
+    if candidates.len() < 2 {
+        return 0;
+    }
+    candidates.sort_by_key(|r| r.created_at);
 
-        let token_addr = match &tx.token {
-            TokenType::Native => continue,
-            TokenType::Erc20(addr) => addr,
+    let wallet_repo = WalletRepository::new(storage);
+    let mut destinations = HashSet::new();
+    let mut sync_guards = Vec::new();
+    let mut batch: Vec<(StoredFiatRequest, String)> = Vec::new();
+    for candidate in candidates {
+        if batch.len() >= MAX_SETTLEMENT_BATCH_SIZE {
+            break;
+        }
+        let Some(guard) = FiatSyncInFlightGuard::try_acquire(&candidate.request_id) else {
+            continue;
         };
-
-        if token_addr.to_ascii_lowercase() != reur_contract {
+        // Re-read under the guard in case a sync finished in between.
+        let Ok(record) = repo.get(&candidate.request_id) else {
             continue;
-        }
-        if tx.to.to_ascii_lowercase() != service_wallet {
+        };
+        if !is_batch_candidate(&record, max_minor)
+            || reserve_queue::latest_settlement_job(storage, &record.request_id).is_some()
+        {
             continue;
         }
-
-        let tx_amount = match parse_amount(&tx.amount, REUR_DECIMALS) {
-            Ok(value) => value,
-            Err(_) => continue,
+        let Ok(wallet) = wallet_repo.get(&record.wallet_id) else {
+            continue;
         };
-        if tx_amount != expected_amount {
+        if !destinations.insert(address_key(&wallet.public_address)) {
             continue;
         }
+        sync_guards.push(guard);
+        batch.push((record, wallet.public_address));
+    }
+    if batch.len() < 2 {
+        return 0;
+    }
 
-        match tx.status {
-            TxStatus::Confirmed => {
-                let confirmations = tx
-                    .block_number
-                    .map(|b| current_block.saturating_sub(b) + 1)
-                    .unwrap_or(0);
-                if confirmations >= min_confirmations {
-                    return Ok(Some(tx.tx_hash));
-                }
-            }
-            TxStatus::Pending => {
-                let receipt = chain
-                    .get_transaction_receipt_status(&tx.tx_hash)
-                    .await
-                    .map_err(|e| {
-                        ApiError::service_unavailable(format!("Failed to read receipt: {e}"))
-                    })?;
+    if let Some((spent, budget)) =
+        daily_gas_budget_exhausted(storage, resolve_daily_gas_budget_wei())
+    {
+        warn!(
+            spent_avax = %format_avax(spent),
+            budget_avax = %format_avax(budget),
+            "Batch settlement deferred: reserve wallet daily gas budget exhausted"
+        );
+        return 0;
+    }
+
+    let prepared = async {
+        let contract = resolve_reur_contract_address()?;
+        let service_wallet = ensure_service_wallet(storage)?;
+        let mut payouts = Vec::with_capacity(batch.len());
+        let mut total = U256::ZERO;
+        let mut total_minor = 0u64;
+        for (record, destination) in &batch {
+            let net_amount_eur = net_amount_eur(record)?;
+            let amount = parse_amount_to_token_minor_u256(&net_amount_eur)?;
+            total += amount;
+            total_minor += parse_amount_to_minor(&net_amount_eur)?.1;
+            payouts.push((destination.clone(), amount));
+        }
+
+        let client = AvaxClient::fuji().await.map_err(|e| {
+            ApiError::service_unavailable(format!("Failed to connect to chain: {e}"))
+        })?;
+        let allowance = client
+            .get_token_allowance(&contract, &service_wallet.public_address, &disperse)
+            .await
+            .map_err(|e| {
+                ApiError::service_unavailable(format!("Failed to read disperse allowance: {e}"))
+            })?;
+        if allowance < total {
+            return Err(ApiError::conflict(format!(
+                "Reserve wallet {} has not approved disperse contract {disperse} for {} rEUR",
+                service_wallet.public_address,
+                format_minor_eur(total_minor)
+            )));
+        }
+
+        let calldata = encode_disperse_token_call(&contract, &payouts)
+            .map_err(|e| ApiError::internal(format!("Failed to encode disperse call: {e}")))?;
+        Ok::<_, ApiError>((calldata, format_minor_eur(total_minor)))
+    };
+    let (calldata, total_eur) = match prepared.await {
+        Ok(prepared) => prepared,
+        Err(error) => {
+            warn!(error = %error.message, "Batch settlement skipped");
+            return 0;
+        }
+    };
+
+    let request_ids: Vec<String> = batch.iter().map(|(r, _)| r.request_id.clone()).collect();
+    for (record, _) in &mut batch {
+        record.settlement_attempts += 1;
+    }
+    info!(
+        count = batch.len(),
+        total_eur = %total_eur,
+        "Attempting batched on-ramp settlement via disperse contract"
+    );
+
+    let result = reserve_queue::submit(
+        storage,
+        ReserveSendKind::BatchSettlement,
+        &request_ids,
+        &disperse,
+        &total_eur,
+        || async {
+            let private_key_pem = FiatServiceWalletRepository::new(storage)
+                .read_private_key()
+                .map_err(|e| {
+                    ApiError::internal(format!("Failed to read service wallet key: {e}"))
+                })?;
+            let eth_wallet = wallet_from_pem(&private_key_pem).map_err(|e| {
+                ApiError::internal(format!("Failed to load service wallet signer: {e}"))
+            })?;
+            let tx_builder = TxBuilder::new(avax_fuji(), eth_wallet).await.map_err(|e| {
+                ApiError::service_unavailable(format!("Failed to connect to chain: {e}"))
+            })?;
+            tx_builder
+                .send_contract_call(&disperse, calldata, None, None, None)
+                .await
+                .map_err(|e| {
+                    ApiError::service_unavailable(format!("Batched settlement failed: {e}"))
+                })
+        },
+    )
+    .await;
+
+    let size = batch.len();
+    for (mut record, destination) in batch {
+        match &result {
+            Ok(sent) => {
+                record.settlement_batch_size = Some(size as u32);
+                complete_settlement(
+                    &mut record,
+                    &sent.tx_hash,
+                    &sent.explorer_url,
+                    &destination,
+                    tx_db,
+                    tx_cache,
+                );
+            }
+            Err(error) => {
+                if is_insufficient_funds_error(&error.message) {
+                    record.settlement_attempts = record.settlement_attempts.saturating_sub(1);
+                }
+                record.failure_reason = Some(format!(
+                    "{}. Settlement will retry individually.",
+                    error.message
+                ));
+                record.updated_at = Utc::now();
+            }
+        }
+        if let Err(e) = repo.update(&mut record) {
+            warn!(
+                request_id = %record.request_id,
+                error = %e,
+                "Failed to persist batched settlement outcome"
+            );
+        }
+    }
+
+    match result {
+        Ok(_) => size,
+        Err(error) => {
+            warn!(error = %error.message, "Batched on-ramp settlement failed");
+            0
+        }
+    }
+}
+
+async fn send_reserve_transfer(
+    storage: &Arc<crate::storage::EncryptedStorage>,
+    request_id: &str,
+    to: &str,
+    amount_eur: &str,
+) -> Result<crate::blockchain::transactions::SendResult, ApiError> {
+    let contract = resolve_reur_contract_address()?;
+    let service_repo = FiatServiceWalletRepository::new(storage);
+    ensure_service_wallet(storage)?;
+    let amount_minor = parse_amount_to_token_minor_u256(amount_eur)?;
+
+    reserve_queue::submit(
+        storage,
+        ReserveSendKind::Settlement,
+        &[request_id.to_string()],
+        to,
+        amount_eur,
+        || async {
+            let private_key_pem = service_repo.read_private_key().map_err(|e| {
+                ApiError::internal(format!("Failed to read service wallet key: {e}"))
+            })?;
+            let eth_wallet = wallet_from_pem(&private_key_pem).map_err(|e| {
+                ApiError::internal(format!("Failed to load service wallet signer: {e}"))
+            })?;
+            let tx_builder = TxBuilder::new(avax_fuji(), eth_wallet).await.map_err(|e| {
+                ApiError::service_unavailable(format!("Failed to connect to chain: {e}"))
+            })?;
+            tx_builder
+                .send_token(to, &contract, amount_minor, None, None)
+                .await
+                .map_err(|e| ApiError::service_unavailable(format!("Reserve transfer failed: {e}")))
+        },
+    )
+    .await
+}
+
+/// Selector of OpenZeppelin's `AccessControlUnauthorizedAccount(address,bytes32)`.
+const ACCESS_CONTROL_UNAUTHORIZED_SELECTOR: &str = "0xe2517d3f";
+
+fn missing_minter_role_error(service_address: &str, contract: &str) -> ApiError {
+    ApiError::conflict(format!(
+        "Reserve wallet {service_address} does not hold MINTER_ROLE on rEUR contract {contract}; \
+         grant it with grantRole(MINTER_ROLE, {service_address}) before topping up"
+    ))
+}
+
+/// Map a failed reserve mint to an API error.
+pub(crate) fn mint_error(
+    e: crate::blockchain::client::AvaxClientError,
+    service_address: &str,
+    contract: &str,
+) -> ApiError {
+    let msg = e.to_string();
+    // The role can be revoked between the check and the send.
+    if msg.contains(ACCESS_CONTROL_UNAUTHORIZED_SELECTOR)
+        || msg.contains("AccessControlUnauthorizedAccount")
+    {
+        missing_minter_role_error(service_address, contract)
+    } else {
+        ApiError::service_unavailable(format!("Reserve mint failed: {e}"))
+    }
+}
+
+/// Verify on-chain that the reserve wallet may mint before sending a mint.
+pub(crate) async fn ensure_minter_role(
+    client: &AvaxClient,
+    contract: &str,
+    service_address: &str,
+) -> Result<(), ApiError> {
+    let has_role = client
+        .has_minter_role(contract, service_address)
+        .await
+        .map_err(|e| {
+            ApiError::service_unavailable(format!("Failed to check rEUR minter role: {e}"))
+        })?;
+    if has_role {
+        Ok(())
+    } else {
+        Err(missing_minter_role_error(service_address, contract))
+    }
+}
+
+/// Mint rEUR into the reserve wallet. The reserve wallet must be the minter.
+async fn topup_fiat_reserve(
+    storage: &Arc<crate::storage::EncryptedStorage>,
+    amount_eur: &str,
+) -> Result<FiatReserveTopUpResponse, ApiError> {
+    let (normalized_amount, _) = parse_amount_to_minor(amount_eur)?;
+    let contract = resolve_reur_contract_address()?;
+    let service_wallet = ensure_service_wallet(storage)?;
+
+    let client = AvaxClient::fuji()
+        .await
+        .map_err(|e| ApiError::service_unavailable(format!("Failed to connect to chain: {e}")))?;
+    ensure_minter_role(&client, &contract, &service_wallet.public_address).await?;
+
+    let amount_minor = parse_amount_to_token_minor_u256(&normalized_amount)?;
+    let calldata = encode_mint_call(&service_wallet.public_address, amount_minor)
+        .map_err(|e| ApiError::internal(format!("Failed to encode mint call: {e}")))?;
+
+    let result = reserve_queue::submit(
+        storage,
+        ReserveSendKind::Mint,
+        &[],
+        &service_wallet.public_address,
+        &normalized_amount,
+        || async {
+            let private_key_pem = FiatServiceWalletRepository::new(storage)
+                .read_private_key()
+                .map_err(|e| {
+                    ApiError::internal(format!("Failed to read service wallet key: {e}"))
+                })?;
+            let eth_wallet = wallet_from_pem(&private_key_pem).map_err(|e| {
+                ApiError::internal(format!("Failed to load service wallet signer: {e}"))
+            })?;
+            let tx_builder = TxBuilder::new(avax_fuji(), eth_wallet).await.map_err(|e| {
+                ApiError::service_unavailable(format!("Failed to connect to chain: {e}"))
+            })?;
+            tx_builder
+                .send_contract_call(&contract, calldata, None, None, None)
+                .await
+                .map_err(|e| mint_error(e, &service_wallet.public_address, &contract))
+        },
+    )
+    .await?;
+
+    Ok(FiatReserveTopUpResponse {
+        tx_hash: result.tx_hash,
+        explorer_url: result.explorer_url,
+        minted_to: service_wallet.public_address,
+        amount_eur: normalized_amount,
+    })
+}
+
+fn list_wallet_transactions(
+    tx_db: &TxDatabase,
+    wallet_address: &str,
+) -> Result<Vec<StoredTransaction>, ApiError> {
+    let mut cursor = None;
+    let mut transactions = Vec::new();
+
+    loop {
+        let (page, next_cursor) = tx_db
+            .list_by_wallet(wallet_address, cursor.as_deref(), 200)
+            .map_err(|e| ApiError::internal(format!("Failed to list wallet transactions: {e}")))?;
+        let page_len = page.len();
+        transactions.extend(page.into_iter().map(|(tx, _direction)| tx));
+
+        if next_cursor.is_none() || page_len < 200 {
+            break;
+        }
+        cursor = next_cursor;
+    }
+
+    Ok(transactions)
+}
+
+async fn detect_confirmed_deposit(
+    storage: &Arc<crate::storage::EncryptedStorage>,
+    tx_db: &TxDatabase,
+    record: &StoredFiatRequest,
+) -> Result<Option<String>, ApiError> {
+    let reur_contract = resolve_reur_contract_address()?;
+    let service_wallet = record
+        .service_wallet_address
+        .as_deref()
+        .ok_or_else(|| ApiError::internal("Missing service_wallet_address on fiat request"))?;
+
+    let expected_amount = parse_amount_to_token_minor_u256(&record.amount_eur)?;
+    let min_confirmations = fiat_min_confirmations();
+
+    // Only consider transactions submitted AFTER this fiat request was
+    // created.  Without this guard, old deposits from previous requests
+    // (with the same amount) would be matched, causing the payout to
+    // fire before the user has made their deposit for THIS request.
+    let request_created = record.created_at;
+    let wallet = WalletRepository::new(storage)
+        .get(&record.wallet_id)
+        .context("Failed to load wallet metadata")?;
+    let candidate_txs = list_wallet_transactions(tx_db, &wallet.public_address)?;
+
+    let chain = AvaxClient::fuji()
+        .await
+        .map_err(|e| ApiError::service_unavailable(format!("Failed to connect to chain: {e}")))?;
+    let current_block = chain
+        .get_block_number()
+        .await
+        .map_err(|e| ApiError::service_unavailable(format!("Failed to read block number: {e}")))?;
+
+    for tx in candidate_txs {
+        // Skip transactions that pre-date this fiat request.
+        if tx.created_at < request_created {
+            continue;
+        }
+
+        let token_addr = match &tx.token {
+            TokenType::Native => continue,
+            TokenType::Erc20(addr) => addr,
+        };
+
+        if !same_address(token_addr, &reur_contract) {
+            continue;
+        }
+        if !same_address(&tx.to, service_wallet) {
+            continue;
+        }
+
+        let tx_amount = match parse_amount(&tx.amount, settlement_decimals()) {
+            Ok(value) => value,
+            Err(_) => continue,
+        };
+        if tx_amount != expected_amount {
+            continue;
+        }
+
+        match tx.status {
+            TxStatus::Confirmed => {
+                let confirmations = tx
+                    .block_number
+                    .map(|b| current_block.saturating_sub(b) + 1)
+                    .unwrap_or(0);
+                if confirmations >= min_confirmations {
+                    return Ok(Some(tx.tx_hash));
+                }
+            }
+            TxStatus::Pending => {
+                let receipt = chain
+                    .get_transaction_receipt_status(&tx.tx_hash)
+                    .await
+                    .map_err(|e| {
+                        ApiError::service_unavailable(format!("Failed to read receipt: {e}"))
+                    })?;
 
                 if let Some(receipt) = receipt {
                     let _ = tx_db.update_status(
@@ -859,27 +1763,91 @@ async fn detect_confirmed_deposit(
     Ok(None)
 }
 
+/// Mark an on-ramp settled by `tx_hash` and record the incoming transfer in
+/// the destination wallet's history.
+fn complete_settlement(
+    record: &mut StoredFiatRequest,
+    tx_hash: &str,
+    explorer_url: &str,
+    destination_address: &str,
+    tx_db: &TxDatabase,
+    tx_cache: Option<&TxCache>,
+) {
+    record.reserve_transfer_tx_hash = Some(tx_hash.to_string());
+    record.link_settlement_tx(tx_hash);
+    record.last_chain_sync_at = Some(Utc::now());
+    record.transition(FiatRequestStatus::Completed, FiatActor::Poller);
+    record.failure_reason = None;
+    record.updated_at = Utc::now();
+
+    info!(
+        request_id = %record.request_id,
+        tx_hash = %tx_hash,
+        "On-ramp settlement transfer succeeded"
+    );
+
+    // Record the incoming rEUR transfer in the user's transaction history.
+    let reur_contract = resolve_reur_contract_address().unwrap_or_default();
+    let service_addr = record.service_wallet_address.clone().unwrap_or_default();
+    let tx_record = StoredTransaction::new_pending(
+        tx_hash.to_string(),
+        record.wallet_id.clone(),
+        None,
+        service_addr,
+        destination_address.to_string(),
+        net_amount_eur(record).unwrap_or_else(|_| record.amount_eur.clone()),
+        TokenType::Erc20(reur_contract),
+        record.chain_network.clone(),
+        explorer_url.to_string(),
+    );
+    // The transfer already succeeded on-chain — mark confirmed.
+    let mut tx_record = tx_record;
+    tx_record.status = TxStatus::Confirmed;
+    tx_record.related_fiat_request_id = Some(record.request_id.clone());
+    let directions = vec![(destination_address.to_string(), "received")];
+    if let Err(e) = tx_db.upsert_transaction(&tx_record, &directions) {
+        warn!(
+            request_id = %record.request_id,
+            tx_hash = %tx_hash,
+            "failed to store on-ramp settlement transaction record in tx db: {e}"
+        );
+    } else if let Some(tx_cache) = tx_cache {
+        tx_cache.invalidate(destination_address);
+    }
+}
+
 async fn sync_onramp_request(
     storage: &Arc<crate::storage::EncryptedStorage>,
     tx_db: &TxDatabase,
     tx_cache: Option<&TxCache>,
     record: &mut StoredFiatRequest,
 ) {
+    if needs_gas_accounting(record, Utc::now()) {
+        account_settlement_gas(storage, record).await;
+        return;
+    }
+
     if matches!(
         record.status,
         FiatRequestStatus::Queued | FiatRequestStatus::AwaitingProvider
     ) {
         if let Some(provider_reference) = record.provider_reference.as_deref() {
-            if TrueLayerClient::is_configured() {
-                match TrueLayerClient::from_env() {
+            let credentials = tenant_provider_credentials(storage, record.tenant_id.as_deref());
+            if OnRampProvider::is_configured_for(&record.provider, &credentials) {
+                match OnRampProvider::for_tenant(&record.provider, &credentials) {
                     Ok(client) => match client.fetch_onramp_status(provider_reference).await {
                         Ok(status) => {
-                            record.status = map_onramp_provider_status(status);
                             record.last_provider_sync_at = Some(Utc::now());
                             record.updated_at = Utc::now();
                             if matches!(status, ProviderExecutionStatus::Failed) {
-                                record.failure_reason = Some(
-                                    "Provider reported failure for on-ramp request".to_string(),
+                                record.fail(
+                                    "Provider reported failure for on-ramp request",
+                                    FiatActor::Poller,
+                                );
+                            } else {
+                                record.transition(
+                                    map_onramp_provider_status(status),
+                                    FiatActor::Poller,
                                 );
                             }
                         }
@@ -904,6 +1872,10 @@ async fn sync_onramp_request(
         }
     }
 
+    if fiat_destination_kyc::hold_for_kyc(record) {
+        return;
+    }
+
     if record.status == FiatRequestStatus::SettlementPending
         && record.reserve_transfer_tx_hash.is_none()
     {
@@ -913,76 +1885,120 @@ async fn sync_onramp_request(
             return;
         }
 
+        if resolve_disperse_contract_address().is_some()
+            && waiting_for_batch(record, settlement_batch_max_minor(), Utc::now())
+        {
+            return;
+        }
+
         let wallet_repo = WalletRepository::new(storage);
         let destination_wallet = match wallet_repo.get(&record.wallet_id) {
             Ok(wallet) => wallet,
             Err(error) => {
-                record.status = FiatRequestStatus::Failed;
-                record.failure_reason = Some(format!(
-                    "Unable to load destination wallet for settlement: {error}"
-                ));
+                record.fail(
+                    format!("Unable to load destination wallet for settlement: {error}"),
+                    FiatActor::Poller,
+                );
                 record.updated_at = Utc::now();
                 return;
             }
         };
 
+        // A previous send for this request may have outlived the process
+        // that made it; never send again while that is unresolved.
+        if let Some(job) = reserve_queue::latest_settlement_job(storage, &record.request_id) {
+            match (job.status, job.tx_hash) {
+                (ReserveSendStatus::Submitted, Some(tx_hash)) => {
+                    if job.kind == ReserveSendKind::BatchSettlement {
+                        record.settlement_batch_size = Some(job.request_ids.len() as u32);
+                    }
+                    let explorer_url = format!("{}/tx/{}", avax_fuji().explorer_url, tx_hash);
+                    complete_settlement(
+                        record,
+                        &tx_hash,
+                        &explorer_url,
+                        &destination_wallet.public_address,
+                        tx_db,
+                        tx_cache,
+                    );
+                    return;
+                }
+                (ReserveSendStatus::Interrupted, _) => {
+                    record.failure_reason = Some(format!(
+                        "Reserve send {} was interrupted; an admin must resolve it \
+                         before settlement can retry.",
+                        job.job_id
+                    ));
+                    return;
+                }
+                (ReserveSendStatus::Queued | ReserveSendStatus::Sending, _) => return,
+                _ => {}
+            }
+        }
+
+        // A payment disputed before any rEUR left the reserve is simply
+        // not settled.
+        if let Some(chargeback) = record.chargeback.as_mut() {
+            chargeback.clawback_status = ClawbackStatus::NotRequired;
+            chargeback.resolved_at = Some(Utc::now());
+            record.fail("Card payment was charged back", FiatActor::Poller);
+            record.updated_at = Utc::now();
+            return;
+        }
+
+        // Gas budget exhaustion is not the request's fault, so like a funding
+        // shortfall it does not count as an attempt.
+        if let Some((spent, budget)) =
+            daily_gas_budget_exhausted(storage, resolve_daily_gas_budget_wei())
+        {
+            warn!(
+                request_id = %record.request_id,
+                spent_avax = %format_avax(spent),
+                budget_avax = %format_avax(budget),
+                "Reserve wallet daily gas budget exhausted — settlement deferred"
+            );
+            record.failure_reason = Some(format!(
+                "Reserve wallet daily gas budget exhausted ({} of {} AVAX). \
+                 Settlement will resume after 00:00 UTC.",
+                format_avax(spent),
+                format_avax(budget)
+            ));
+            return;
+        }
+
+        let settlement_amount_eur = match net_amount_eur(record) {
+            Ok(amount) => amount,
+            Err(error) => {
+                record.fail(error.message, FiatActor::Poller);
+                return;
+            }
+        };
+
         record.settlement_attempts += 1;
         info!(
             request_id = %record.request_id,
             attempt = record.settlement_attempts,
             destination = %destination_wallet.public_address,
-            amount_eur = %record.amount_eur,
+            amount_eur = %settlement_amount_eur,
             "Attempting on-ramp settlement transfer from service wallet"
         );
 
         match send_reserve_transfer(
             storage,
+            &record.request_id,
             &destination_wallet.public_address,
-            &record.amount_eur,
+            &settlement_amount_eur,
         )
         .await
         {
-            Ok(result) => {
-                record.reserve_transfer_tx_hash = Some(result.tx_hash.clone());
-                record.last_chain_sync_at = Some(Utc::now());
-                record.status = FiatRequestStatus::Completed;
-                record.failure_reason = None;
-                record.updated_at = Utc::now();
-
-                info!(
-                    request_id = %record.request_id,
-                    tx_hash = %result.tx_hash,
-                    "On-ramp settlement transfer succeeded"
-                );
-
-                // Record the incoming rEUR transfer in the user's transaction history.
-                let reur_contract = resolve_reur_contract_address().unwrap_or_default();
-                let service_addr = record.service_wallet_address.clone().unwrap_or_default();
-                let tx_record = StoredTransaction::new_pending(
-                    result.tx_hash.clone(),
-                    record.wallet_id.clone(),
-                    None,
-                    service_addr,
-                    destination_wallet.public_address.clone(),
-                    record.amount_eur.clone(),
-                    TokenType::Erc20(reur_contract),
-                    record.chain_network.clone(),
-                    result.explorer_url.clone(),
-                );
-                // The transfer already succeeded on-chain — mark confirmed.
-                let mut tx_record = tx_record;
-                tx_record.status = TxStatus::Confirmed;
-                let directions = vec![(destination_wallet.public_address.clone(), "received")];
-                if let Err(e) = tx_db.upsert_transaction(&tx_record, &directions) {
-                    warn!(
-                        request_id = %record.request_id,
-                        tx_hash = %result.tx_hash,
-                        "failed to store on-ramp settlement transaction record in tx db: {e}"
-                    );
-                } else if let Some(tx_cache) = tx_cache {
-                    tx_cache.invalidate(&destination_wallet.public_address);
-                }
-            }
+            Ok(result) => complete_settlement(
+                record,
+                &result.tx_hash,
+                &result.explorer_url,
+                &destination_wallet.public_address,
+                tx_db,
+                tx_cache,
+            ),
             Err(error) => {
                 let is_funding_issue = is_insufficient_funds_error(&error.message);
 
@@ -1013,11 +2029,13 @@ async fn sync_onramp_request(
                     );
 
                     if record.settlement_attempts >= MAX_SETTLEMENT_ATTEMPTS {
-                        record.status = FiatRequestStatus::Failed;
-                        record.failure_reason = Some(format!(
-                            "Settlement failed after {} attempts: {}",
-                            record.settlement_attempts, error.message
-                        ));
+                        record.fail(
+                            format!(
+                                "Settlement failed after {} attempts: {}",
+                                record.settlement_attempts, error.message
+                            ),
+                            FiatActor::Poller,
+                        );
                     } else {
                         // Stay in SettlementPending — the poller will retry.
                         record.failure_reason = Some(format!(
@@ -1040,6 +2058,14 @@ async fn sync_offramp_request(
     if record.status == FiatRequestStatus::AwaitingUserDeposit {
         match detect_confirmed_deposit(storage, tx_db, record).await {
             Ok(Some(tx_hash)) => {
+                if let Err(e) = tx_db.set_related_fiat_request(&tx_hash, &record.request_id) {
+                    warn!(
+                        request_id = %record.request_id,
+                        tx_hash = %tx_hash,
+                        "failed to link off-ramp deposit transaction: {e}"
+                    );
+                }
+                record.link_settlement_tx(&tx_hash);
                 record.deposit_tx_hash = Some(tx_hash);
                 record.last_chain_sync_at = Some(Utc::now());
                 record.updated_at = Utc::now();
@@ -1053,14 +2079,16 @@ async fn sync_offramp_request(
                         provider_reference = ?record.provider_reference,
                         "Off-ramp payout already created; skipping re-creation"
                     );
-                    record.status = FiatRequestStatus::ProviderPending;
+                    record.transition(FiatRequestStatus::ProviderPending, FiatActor::Poller);
                     return;
                 }
 
-                if !TrueLayerClient::is_configured() {
-                    record.status = FiatRequestStatus::Failed;
-                    record.failure_reason =
-                        Some("TrueLayer is not configured for off-ramp payout".to_string());
+                let credentials = tenant_provider_credentials(storage, record.tenant_id.as_deref());
+                if !TrueLayerClient::is_configured_for(credentials.truelayer.as_ref()) {
+                    record.fail(
+                        "TrueLayer is not configured for off-ramp payout",
+                        FiatActor::Poller,
+                    );
                     return;
                 }
 
@@ -1068,35 +2096,38 @@ async fn sync_offramp_request(
                     match record.beneficiary_account_holder_name.as_deref() {
                         Some(value) => value,
                         None => {
-                            record.status = FiatRequestStatus::Failed;
-                            record.failure_reason =
-                                Some("Missing beneficiary account holder name".to_string());
+                            record
+                                .fail("Missing beneficiary account holder name", FiatActor::Poller);
                             return;
                         }
                     };
                 let beneficiary_iban = match record.beneficiary_iban.as_deref() {
                     Some(value) => value,
                     None => {
-                        record.status = FiatRequestStatus::Failed;
-                        record.failure_reason = Some("Missing beneficiary IBAN".to_string());
+                        record.fail("Missing beneficiary IBAN", FiatActor::Poller);
                         return;
                     }
                 };
 
-                let amount_provider_minor = match parse_amount_to_minor(&record.amount_eur) {
+                let payout_amount_eur = match net_amount_eur(record) {
+                    Ok(amount) => amount,
+                    Err(error) => {
+                        record.fail(error.message, FiatActor::Poller);
+                        return;
+                    }
+                };
+                let amount_provider_minor = match parse_amount_to_minor(&payout_amount_eur) {
                     Ok((_, minor)) => minor,
                     Err(error) => {
-                        record.status = FiatRequestStatus::Failed;
-                        record.failure_reason = Some(error.message.clone());
+                        record.fail(error.message.clone(), FiatActor::Poller);
                         return;
                     }
                 };
 
-                let client = match TrueLayerClient::from_env() {
+                let client = match TrueLayerClient::for_tenant(credentials.truelayer.as_ref()) {
                     Ok(client) => client,
                     Err(error) => {
-                        record.status = FiatRequestStatus::Failed;
-                        record.failure_reason = Some(map_provider_error(error).message);
+                        record.fail(map_provider_error(error).message, FiatActor::Poller);
                         return;
                     }
                 };
@@ -1107,7 +2138,7 @@ async fn sync_offramp_request(
                         wallet_id: &record.wallet_id,
                         user_id: &record.owner_user_id,
                         amount_in_minor: amount_provider_minor,
-                        amount_eur: &record.amount_eur,
+                        amount_eur: &payout_amount_eur,
                         beneficiary_account_holder_name,
                         beneficiary_iban,
                         note: record.note.as_deref(),
@@ -1117,7 +2148,10 @@ async fn sync_offramp_request(
                     Ok(execution) => {
                         record.provider_reference = Some(execution.provider_reference);
                         record.provider_action_url = execution.provider_action_url;
-                        record.status = map_offramp_provider_status(execution.status);
+                        record.transition(
+                            map_offramp_provider_status(execution.status),
+                            FiatActor::Poller,
+                        );
                         record.last_provider_sync_at = Some(Utc::now());
                         record.updated_at = Utc::now();
                         if matches!(execution.status, ProviderExecutionStatus::Failed) {
@@ -1129,8 +2163,7 @@ async fn sync_offramp_request(
                     }
                     Err(error) => {
                         let mapped = map_provider_error(error);
-                        record.status = FiatRequestStatus::Failed;
-                        record.failure_reason = Some(mapped.message);
+                        record.fail(mapped.message, FiatActor::Poller);
                         record.updated_at = Utc::now();
                     }
                 }
@@ -1150,14 +2183,15 @@ async fn sync_offramp_request(
         record.status,
         FiatRequestStatus::ProviderPending | FiatRequestStatus::AwaitingProvider
     ) {
-        let Some(provider_reference) = record.provider_reference.as_deref() else {
+        let Some(provider_reference) = record.provider_reference.clone() else {
             return;
         };
-        if !TrueLayerClient::is_configured() {
+        let credentials = tenant_provider_credentials(storage, record.tenant_id.as_deref());
+        if !TrueLayerClient::is_configured_for(credentials.truelayer.as_ref()) {
             return;
         }
 
-        let client = match TrueLayerClient::from_env() {
+        let client = match TrueLayerClient::for_tenant(credentials.truelayer.as_ref()) {
             Ok(client) => client,
             Err(error) => {
                 warn!(
@@ -1169,11 +2203,16 @@ async fn sync_offramp_request(
             }
         };
 
-        match client.fetch_offramp_status(provider_reference).await {
+        match client.fetch_offramp_status(&provider_reference).await {
             Ok(details) => {
-                record.status = map_offramp_provider_status(details.status);
+                record.transition(
+                    map_offramp_provider_status(details.status),
+                    FiatActor::Poller,
+                );
                 record.last_provider_sync_at = Some(Utc::now());
                 record.updated_at = Utc::now();
+                note_payout_execution(record, details.scheme_id.as_deref(), details.executed_at);
+                fiat_beneficiaries::note_completed_payout(storage, record);
                 match details.status {
                     ProviderExecutionStatus::Failed => {
                         record.failure_reason = Some(details.failure_reason.unwrap_or_else(|| {
@@ -1217,8 +2256,7 @@ async fn sync_request_internal(
     record: &mut StoredFiatRequest,
 ) {
     if let Err(error) = ensure_fuji_network(Some(record.chain_network.as_str())) {
-        record.status = FiatRequestStatus::Failed;
-        record.failure_reason = Some(error);
+        record.fail(error, FiatActor::Poller);
         record.updated_at = Utc::now();
         return;
     }
@@ -1243,14 +2281,14 @@ pub(crate) async fn sync_and_persist_request(
             request_id = %request_id,
             "Fiat request sync skipped; request is already being synced"
         );
-        return repo
-            .get(request_id)
-            .map_err(|_| ApiError::not_found("Fiat request not found"));
+        return repo.get(request_id).map_err(|_| {
+            ApiError::not_found("Fiat request not found").with_code("fiat_request_not_found")
+        });
     };
 
-    let mut record = repo
-        .get(request_id)
-        .map_err(|_| ApiError::not_found("Fiat request not found"))?;
+    let mut record = repo.get(request_id).map_err(|_| {
+        ApiError::not_found("Fiat request not found").with_code("fiat_request_not_found")
+    })?;
 
     sync_request_internal(storage, tx_db, tx_cache, &mut record).await;
 
@@ -1258,13 +2296,7 @@ pub(crate) async fn sync_and_persist_request(
     // While we were calling the provider API (~200ms), the webhook handler may
     // have already set this record to Failed/Completed. Honour that.
     if let Ok(current) = repo.get(request_id) {
-        if matches!(
-            current.status,
-            FiatRequestStatus::Completed | FiatRequestStatus::Failed
-        ) && !matches!(
-            record.status,
-            FiatRequestStatus::Completed | FiatRequestStatus::Failed
-        ) {
+        if current.status.is_final() && !record.status.is_final() {
             info!(
                 request_id = %request_id,
                 stored_status = ?current.status,
@@ -1275,13 +2307,14 @@ pub(crate) async fn sync_and_persist_request(
         }
     }
 
-    repo.update(&record)
-        .map_err(|e| ApiError::internal(format!("Failed to persist fiat request sync: {e}")))?;
+    repo.update(&mut record)
+        .context("Failed to persist fiat request sync")?;
 
     Ok(record)
 }
 
-/// Return request IDs of all fiat requests in a syncable (non-terminal) status.
+/// Return request IDs of all fiat requests in a syncable (non-terminal) status,
+/// plus recently settled on-ramps whose gas has not been recorded yet.
 pub(crate) fn list_pending_request_ids(
     storage: &Arc<crate::storage::EncryptedStorage>,
 ) -> Vec<String> {
@@ -1309,7 +2342,11 @@ pub(crate) fn list_pending_request_ids(
                 })
                 .unwrap_or(true),
             FiatRequestStatus::AwaitingUserDeposit | FiatRequestStatus::SettlementPending => true,
-            FiatRequestStatus::Completed | FiatRequestStatus::Failed => false,
+            FiatRequestStatus::Completed => needs_gas_accounting(r, now),
+            // Waits for an admin or the user, not for the provider or the chain.
+            FiatRequestStatus::ReviewRequired
+            | FiatRequestStatus::KycRequired
+            | FiatRequestStatus::Failed => false,
         })
         .map(|r| r.request_id)
         .collect()
@@ -1326,13 +2363,124 @@ pub(crate) fn list_pending_request_ids(
         (status = 401, description = "Unauthorized")
     )
 )]
-pub async fn list_fiat_providers(Auth(_user): Auth) -> Json<FiatProviderListResponse> {
+pub async fn list_fiat_providers(
+    Auth(user): Auth,
+    State(state): State<AppState>,
+) -> Json<FiatProviderListResponse> {
+    let credentials = tenant_provider_credentials(state.storage(), user.tenant_id.as_deref());
     Json(FiatProviderListResponse {
         default_provider: DEFAULT_PROVIDER.to_string(),
-        providers: provider_summaries(),
+        providers: provider_summaries(&credentials),
     })
 }
 
+/// Validate the common fields of a new fiat request and build its record.
+///
+/// Returns the queued record and the amount in provider minor units.
+pub(crate) fn new_request_record(
+    storage: &Arc<crate::storage::EncryptedStorage>,
+    user_id: &str,
+    wallet_id: String,
+    direction: FiatDirection,
+    amount_eur: &str,
+    provider: Option<String>,
+    note: Option<String>,
+) -> Result<(StoredFiatRequest, u64), ApiError> {
+    let (normalized_amount, amount_in_minor_provider) = parse_amount_to_minor(amount_eur)?;
+
+    // Ensure settlement prerequisites are available.
+    let _ = resolve_reur_contract_address()?;
+    let service_wallet = ensure_service_wallet(storage)?;
+
+    let wallet_repo = WalletRepository::new(storage);
+    let wallet = wallet_repo
+        .get(&wallet_id)
+        .map_err(|_| ApiError::not_found("Wallet not found").with_code("wallet_not_found"))?;
+
+    if wallet.owner_user_id != user_id {
+        return Err(ApiError::forbidden("You do not own this wallet").with_code("wallet_not_owned"));
+    }
+    if wallet.status != WalletStatus::Active {
+        return Err(ApiError::forbidden(
+            "Wallet must be active for fiat requests",
+        ));
+    }
+    if direction == FiatDirection::OffRamp {
+        ensure_unlocked(&wallet)?;
+    }
+
+    let tenant = TenantConfigRepository::new(storage).for_tenant(wallet.tenant_id.as_deref());
+    let credentials = tenant
+        .as_ref()
+        .map(|config| config.provider_credentials.clone())
+        .unwrap_or_default();
+    let provider = select_provider(provider, direction, &credentials)?;
+
+    let note = note.and_then(|value| {
+        let trimmed = value.trim().to_string();
+        if trimmed.is_empty() {
+            None
+        } else {
+            Some(trimmed)
+        }
+    });
+
+    let request_id = uuid::Uuid::new_v4().to_string();
+    let mut record = StoredFiatRequest::new_queued(
+        request_id,
+        wallet_id,
+        user_id.to_string(),
+        direction,
+        normalized_amount,
+        provider,
+        note,
+    );
+    record.tenant_id = wallet.tenant_id.clone();
+    if let Some(tenant) = &tenant {
+        let bps = match direction {
+            FiatDirection::OnRamp => tenant.fees.on_ramp_bps,
+            FiatDirection::OffRamp => tenant.fees.off_ramp_bps,
+        };
+        let fee_minor = FeeSchedule::fee_minor(bps, amount_in_minor_provider);
+        if fee_minor > 0 {
+            record.fee_eur = Some(format_minor_eur(fee_minor));
+        }
+    }
+    // On-ramps mint the amount net of the fee; off-ramps expect the full
+    // deposit and pay out the net amount.
+    let expected_amount_eur = match direction {
+        FiatDirection::OnRamp => net_amount_eur(&record)?,
+        FiatDirection::OffRamp => record.amount_eur.clone(),
+    };
+    record.chain_network = "fuji".to_string();
+    record.expected_amount_minor = Some(u256_to_u64(parse_amount_to_token_minor_u256(
+        &expected_amount_eur,
+    )?)?);
+    record.service_wallet_address = Some(service_wallet.public_address.clone());
+    Ok((record, amount_in_minor_provider))
+}
+
+/// Store a newly initiated fiat request and audit it against its owner.
+pub(crate) fn persist_new_request(
+    storage: &Arc<crate::storage::EncryptedStorage>,
+    record: &mut StoredFiatRequest,
+) -> Result<(), ApiError> {
+    record.note_transition();
+    let repo = FiatRequestRepository::new(storage);
+    repo.create(record)
+        .map_err(|e| ApiError::internal(format!("Failed to store fiat request: {e}")))?;
+
+    let audit_event = match record.direction {
+        FiatDirection::OnRamp => AuditEventType::FiatOnRampRequested,
+        FiatDirection::OffRamp => AuditEventType::FiatOffRampRequested,
+    };
+    let event = AuditEvent::new(audit_event)
+        .with_user(&record.owner_user_id)
+        .with_resource("fiat_request", &record.request_id);
+    let _ = AuditRepository::new(storage).log(&event);
+    Ok(())
+}
+
 async fn create_request(
     Auth(user): Auth,
     State(state): State<AppState>,
@@ -1346,86 +2494,91 @@ async fn create_request(
         amount_eur,
         provider,
         note,
+        beneficiary_id,
         beneficiary_account_holder_name,
         beneficiary_iban,
+        return_client,
+        return_uri,
     } = request;
 
-    let (normalized_amount, amount_in_minor_provider) = parse_amount_to_minor(&amount_eur)?;
-    let expected_amount_token_minor =
-        u256_to_u64(parse_amount_to_token_minor_u256(&normalized_amount)?)?;
     let storage = state.storage();
-
-    // Ensure settlement prerequisites are available.
-    let _ = resolve_reur_contract_address()?;
-    let service_wallet = ensure_service_wallet(storage)?;
-
-    let wallet_repo = WalletRepository::new(storage);
-    let wallet = wallet_repo
-        .get(&wallet_id)
-        .map_err(|_| ApiError::not_found("Wallet not found"))?;
-
-    if wallet.owner_user_id != user.user_id {
-        return Err(ApiError::forbidden("You do not own this wallet"));
-    }
-    if wallet.status != WalletStatus::Active {
-        return Err(ApiError::forbidden(
-            "Wallet must be active for fiat requests",
-        ));
+    let (mut record, amount_in_minor_provider) = new_request_record(
+        storage,
+        &user.user_id,
+        wallet_id,
+        direction,
+        &amount_eur,
+        provider,
+        note,
+    )?;
+    if !record.is_owned_by(&user) {
+        return Err(ApiError::forbidden("You do not own this wallet").with_code("wallet_not_owned"));
     }
 
-    let provider = resolve_provider_id(provider)?;
-    ensure_provider_enabled(&provider, direction)?;
-
-    let note = note.and_then(|value| {
-        let trimmed = value.trim().to_string();
-        if trimmed.is_empty() {
-            None
-        } else {
-            Some(trimmed)
-        }
-    });
-
-    let beneficiary = if direction == FiatDirection::OffRamp {
-        Some((
-            normalize_offramp_account_holder_name(beneficiary_account_holder_name)?,
-            normalize_offramp_iban(beneficiary_iban)?,
-        ))
+    let return_uri = if direction == FiatDirection::OnRamp {
+        Some(fiat_return::resolve_return_uri(
+            return_client.unwrap_or_default(),
+            return_uri.as_deref(),
+        )?)
     } else {
         None
     };
 
-    let request_id = uuid::Uuid::new_v4().to_string();
-    let mut record = StoredFiatRequest::new_queued(
-        request_id,
-        wallet_id,
-        user.user_id.clone(),
-        direction,
-        normalized_amount.clone(),
-        provider.clone(),
-        note.clone(),
-    );
-    record.chain_network = "fuji".to_string();
-    record.expected_amount_minor = Some(expected_amount_token_minor);
-    record.service_wallet_address = Some(service_wallet.public_address.clone());
-
-    if let Some((beneficiary_name, beneficiary_iban)) = beneficiary {
-        record.beneficiary_account_holder_name = Some(beneficiary_name);
-        record.beneficiary_iban = Some(beneficiary_iban);
+    if direction == FiatDirection::OffRamp {
+        if let Some(beneficiary_id) = beneficiary_id {
+            if beneficiary_account_holder_name.is_some() || beneficiary_iban.is_some() {
+                return Err(ApiError::bad_request(
+                    "beneficiary_id cannot be combined with inline beneficiary details",
+                ));
+            }
+            let beneficiary = fiat_beneficiaries::payout_beneficiary(
+                storage,
+                &user.user_id,
+                &beneficiary_id,
+                amount_in_minor_provider,
+            )?;
+            record.beneficiary_id = Some(beneficiary.beneficiary_id);
+            record.beneficiary_account_holder_name = Some(beneficiary.account_holder_name);
+            record.beneficiary_iban = Some(beneficiary.iban);
+        } else {
+            record.beneficiary_account_holder_name = Some(normalize_offramp_account_holder_name(
+                beneficiary_account_holder_name,
+            )?);
+            record.beneficiary_iban = Some(normalize_offramp_iban(beneficiary_iban)?);
+        }
     }
 
     if direction == FiatDirection::OnRamp {
-        let client = TrueLayerClient::from_env().map_err(map_provider_error)?;
+        // Before the provider call, so a failed lookup leaves no payment
+        // behind.
+        fiat_destination_kyc::check_onramp_destination(
+            state.clerk_client.as_ref(),
+            storage,
+            &mut record,
+            amount_in_minor_provider,
+        )
+        .await?;
+    }
+
+    if let Some(return_uri) = return_uri {
+        let return_uri =
+            fiat_return::signed_return_uri(&state.secret_signer, return_uri, &record.request_id)
+                .await?;
+        let credentials = tenant_provider_credentials(storage, record.tenant_id.as_deref());
+        let client = OnRampProvider::for_tenant(&record.provider, &credentials)
+            .map_err(map_fiat_provider_error)?;
         let execution = client
             .create_onramp(CreateOnRampRequest {
                 request_id: &record.request_id,
                 wallet_id: &record.wallet_id,
                 user_id: &record.owner_user_id,
                 amount_in_minor: amount_in_minor_provider,
-                amount_eur: &normalized_amount,
-                note: note.as_deref(),
+                amount_eur: &record.amount_eur,
+                note: record.note.as_deref(),
+                return_uri: &return_uri,
             })
             .await
-            .map_err(map_provider_error)?;
+            .map_err(map_fiat_provider_error)?;
 
         record.provider_reference = Some(execution.provider_reference);
         record.provider_action_url = execution.provider_action_url;
@@ -1439,21 +2592,14 @@ async fn create_request(
         record.updated_at = Utc::now();
     }
 
-    let repo = FiatRequestRepository::new(storage);
-    repo.create(&record)
-        .map_err(|e| ApiError::internal(format!("Failed to store fiat request: {e}")))?;
+    if direction == FiatDirection::OffRamp {
+        fiat_name_check::check_offramp_name(&state, &mut record, amount_in_minor_provider).await?;
+    }
 
-    let audit_event = match direction {
-        FiatDirection::OnRamp => AuditEventType::FiatOnRampRequested,
-        FiatDirection::OffRamp => AuditEventType::FiatOffRampRequested,
-    };
-    audit_log!(
-        storage,
-        audit_event,
-        &user,
-        "fiat_request",
-        &record.request_id
-    );
+    persist_new_request(storage, &mut record)?;
+    if record.status == FiatRequestStatus::ReviewRequired {
+        fiat_name_check::audit_review_required(storage, &record);
+    }
 
     Ok((StatusCode::CREATED, Json(to_response(&record))))
 }
@@ -1532,7 +2678,7 @@ pub async fn list_fiat_requests(
     };
     let limit = query.limit.map(|value| value.min(200));
     let requests = repo
-        .list_filtered_for_owner(&user.user_id, query.wallet_id.as_deref(), statuses, limit)
+        .list_filtered_for_user(&user, query.wallet_id.as_deref(), statuses, limit)
         .map_err(|e| ApiError::internal(format!("Failed to list fiat requests: {e}")))?;
 
     // Serve cached status — the background FiatPoller handles provider syncing
@@ -1569,11 +2715,11 @@ pub async fn get_fiat_request(
 ) -> Result<Json<FiatRequestResponse>, ApiError> {
     let storage = state.storage();
     let repo = FiatRequestRepository::new(storage);
-    let record = repo
-        .get(&request_id)
-        .map_err(|_| ApiError::not_found("Fiat request not found"))?;
+    let record = repo.get(&request_id).map_err(|_| {
+        ApiError::not_found("Fiat request not found").with_code("fiat_request_not_found")
+    })?;
 
-    if record.owner_user_id != user.user_id {
+    if !record.is_owned_by(&user) {
         return Err(ApiError::forbidden(
             "You do not have permission to access this fiat request",
         ));
@@ -1639,7 +2785,8 @@ pub async fn truelayer_webhook(
         }
     }
 
-    // ── Prevent backward status transitions ──
+    // ── Apply the provider status, never leaving a final state or moving
+    // back from settlement ──
     if let Some(new_status) = extract_webhook_status(&payload) {
         let new_mapped = if record.direction == FiatDirection::OnRamp {
             map_onramp_provider_status(new_status)
@@ -1647,32 +2794,19 @@ pub async fn truelayer_webhook(
             map_offramp_provider_status(new_status)
         };
 
-        let is_terminal = matches!(
-            record.status,
-            FiatRequestStatus::Completed | FiatRequestStatus::Failed
-        );
-
-        // Prevent backward transitions — don't downgrade from
-        // SettlementPending to a less-advanced state.
-        let is_backward = matches!(record.status, FiatRequestStatus::SettlementPending)
-            && matches!(
-                new_mapped,
-                FiatRequestStatus::AwaitingProvider | FiatRequestStatus::Queued
-            );
-
-        if !is_terminal && !is_backward {
-            record.status = new_mapped;
-            if matches!(new_status, ProviderExecutionStatus::Failed) {
-                // Use failure_reason from TrueLayer webhook when available,
-                // fall back to generic message.
-                record.failure_reason = Some(
-                    payload
-                        .failure_reason
-                        .clone()
-                        .unwrap_or_else(|| "Webhook reported provider failure".to_string()),
-                );
-            }
-        }
+        let applied = if matches!(new_status, ProviderExecutionStatus::Failed) {
+            // Use failure_reason from TrueLayer webhook when available,
+            // fall back to generic message.
+            record.fail(
+                payload
+                    .failure_reason
+                    .clone()
+                    .unwrap_or_else(|| "Webhook reported provider failure".to_string()),
+                FiatActor::Provider,
+            )
+        } else {
+            record.transition(new_mapped, FiatActor::Provider)
+        };
 
         info!(
             request_id = %record.request_id,
@@ -1681,11 +2815,21 @@ pub async fn truelayer_webhook(
             mapped_status = ?new_mapped,
             scheme_id = ?payload.scheme_id,
             failure_reason = ?payload.failure_reason,
-            is_terminal,
+            applied,
             "Webhook status transition"
         );
     }
 
+    if record.direction == FiatDirection::OffRamp {
+        let executed_at = payload
+            .executed_at
+            .as_deref()
+            .and_then(|raw| DateTime::parse_from_rfc3339(raw).ok())
+            .map(|ts| ts.with_timezone(&Utc));
+        note_payout_execution(record, payload.scheme_id.as_deref(), executed_at);
+        fiat_beneficiaries::note_completed_payout(storage, record);
+    }
+
     if let Some(event_id) = payload.event_id.clone() {
         record.provider_event_id = Some(event_id);
     }
@@ -1695,53 +2839,59 @@ pub async fn truelayer_webhook(
 
     // Persist the webhook update immediately so the record is up-to-date.
     repo.update(record)
-        .map_err(|e| ApiError::internal(format!("Failed to persist webhook update: {e}")))?;
+        .context("Failed to persist webhook update")?;
 
     // If the request just transitioned to SettlementPending, fire off settlement
     // immediately in a background task instead of waiting for the next poller tick.
     if record.status == FiatRequestStatus::SettlementPending
         && record.reserve_transfer_tx_hash.is_none()
     {
-        let storage = Arc::clone(storage);
-        let tx_db = state
-            .tx_db
-            .clone()
-            .ok_or_else(|| ApiError::internal("transaction database must be configured"))?;
-        let request_id = record.request_id.clone();
-        tokio::spawn(async move {
-            info!(
-                request_id = %request_id,
-                "Webhook: triggering immediate settlement"
-            );
-            match sync_and_persist_request(
-                &storage,
-                tx_db.as_ref(),
-                state.tx_cache.as_deref(),
-                &request_id,
-            )
-            .await
-            {
-                Ok(r) => {
-                    info!(
-                        request_id = %r.request_id,
-                        status = ?r.status,
-                        "Webhook: immediate settlement completed"
-                    );
-                }
-                Err(e) => {
-                    warn!(
-                        request_id = %request_id,
-                        error = %e.message,
-                        "Webhook: immediate settlement failed (poller will retry)"
-                    );
-                }
-            }
-        });
+        spawn_immediate_settlement(&state, &record.request_id)?;
     }
 
     Ok(StatusCode::ACCEPTED)
 }
 
+/// Settle a request that a webhook just moved to `SettlementPending` in a
+/// background task instead of waiting for the next poller tick.
+pub(crate) fn spawn_immediate_settlement(
+    state: &AppState,
+    request_id: &str,
+) -> Result<(), ApiError> {
+    let storage = Arc::clone(state.storage());
+    let tx_db = state
+        .tx_db
+        .clone()
+        .ok_or_else(|| ApiError::internal("transaction database must be configured"))?;
+    let tx_cache = state.tx_cache.clone();
+    let request_id = request_id.to_string();
+    tokio::spawn(async move {
+        info!(
+            request_id = %request_id,
+            "Webhook: triggering immediate settlement"
+        );
+        match sync_and_persist_request(&storage, tx_db.as_ref(), tx_cache.as_deref(), &request_id)
+            .await
+        {
+            Ok(r) => {
+                info!(
+                    request_id = %r.request_id,
+                    status = ?r.status,
+                    "Webhook: immediate settlement completed"
+                );
+            }
+            Err(e) => {
+                warn!(
+                    request_id = %request_id,
+                    error = %e.message,
+                    "Webhook: immediate settlement failed (poller will retry)"
+                );
+            }
+        }
+    });
+    Ok(())
+}
+
 /// Get fiat reserve service-wallet status.
 #[utoipa::path(
     get,
@@ -1751,13 +2901,14 @@ pub async fn truelayer_webhook(
     responses(
         (status = 200, description = "Fiat reserve wallet status", body = FiatServiceWalletStatusResponse),
         (status = 401, description = "Unauthorized"),
-        (status = 403, description = "Forbidden")
+        (status = 403, description = "Not authorized (platform admin required)")
     )
 )]
 pub async fn get_fiat_service_wallet(
-    AdminOnly(_admin): AdminOnly,
+    AdminOnly(admin): AdminOnly,
     State(state): State<AppState>,
 ) -> Result<Json<FiatServiceWalletStatusResponse>, ApiError> {
+    require_platform_admin(&admin)?;
     let storage = state.storage();
     let service_wallet = ensure_service_wallet(storage)?;
     let contract_address = resolve_reur_contract_address()?;
@@ -1780,6 +2931,7 @@ pub async fn get_fiat_service_wallet(
         wallet_id: service_wallet.wallet_id,
         public_address: service_wallet.public_address,
         bootstrapped: true,
+        key_source: service_wallet.key_source,
         chain_network: "fuji".to_string(),
         reur_contract_address: contract_address,
         avax_balance: native.balance_formatted,
@@ -1788,6 +2940,134 @@ pub async fn get_fiat_service_wallet(
     }))
 }
 
+/// Mint rEUR into the fiat reserve wallet.
+///
+/// Checks on-chain that the reserve wallet holds `MINTER_ROLE` before sending
+/// the mint, so a misconfigured contract fails with an actionable error.
+#[utoipa::path(
+    post,
+    path = "/v1/admin/fiat/reserve/topup",
+    tag = "Admin",
+    request_body = FiatReserveTopUpRequest,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Mint submitted", body = FiatReserveTopUpResponse),
+        (status = 400, description = "Invalid amount"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Not authorized (platform admin required)"),
+        (status = 409, description = "Reserve wallet lacks MINTER_ROLE"),
+        (status = 503, description = "Chain or reserve wallet unavailable")
+    )
+)]
+pub async fn topup_fiat_reserve_admin(
+    AdminOnly(admin): AdminOnly,
+    State(state): State<AppState>,
+    Json(request): Json<FiatReserveTopUpRequest>,
+) -> Result<Json<FiatReserveTopUpResponse>, ApiError> {
+    require_platform_admin(&admin)?;
+    let storage = state.storage();
+    let response = topup_fiat_reserve(storage, &request.amount_eur).await?;
+
+    let event = AuditEvent::new(AuditEventType::AdminAccess)
+        .with_user(&admin.user_id)
+        .with_resource("fiat_service_wallet", &response.minted_to)
+        .with_details(serde_json::json!({
+            "action": "reserve_topup",
+            "amount_eur": response.amount_eur,
+            "tx_hash": response.tx_hash,
+        }));
+    let _ = AuditRepository::new(storage).log(&event);
+
+    Ok(Json(response))
+}
+
+fn parse_report_date(value: &str, field: &str) -> Result<NaiveDate, ApiError> {
+    NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d")
+        .map_err(|_| ApiError::bad_request(format!("{field} must be a YYYY-MM-DD date")))
+}
+
+/// Reserve reconciliation report.
+///
+/// Shows the AVAX gas the reserve wallet spent on settlements per UTC day and
+/// per fiat request, against the configured daily gas budget.
+#[utoipa::path(
+    get,
+    path = "/v1/admin/fiat/reconciliation",
+    tag = "Admin",
+    params(FiatReconciliationQuery),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Reconciliation report", body = FiatReconciliationReport),
+        (status = 400, description = "Invalid date range"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Not authorized (platform admin required)")
+    )
+)]
+pub async fn get_fiat_reconciliation_report(
+    AdminOnly(admin): AdminOnly,
+    State(state): State<AppState>,
+    Query(query): Query<FiatReconciliationQuery>,
+) -> Result<Json<FiatReconciliationReport>, ApiError> {
+    require_platform_admin(&admin)?;
+    let storage = state.storage();
+    let to = match query.to.as_deref() {
+        Some(value) => parse_report_date(value, "to")?,
+        None => Utc::now().date_naive(),
+    };
+    let from = match query.from.as_deref() {
+        Some(value) => parse_report_date(value, "from")?,
+        None => to - TimeDelta::days(6),
+    };
+    if from > to {
+        return Err(ApiError::bad_request("from must not be after to"));
+    }
+    if (to - from).num_days() >= RECONCILIATION_MAX_DAYS {
+        return Err(ApiError::bad_request(format!(
+            "Date range must not exceed {RECONCILIATION_MAX_DAYS} days"
+        )));
+    }
+
+    let budget = resolve_daily_gas_budget_wei();
+    let ledger = ReserveGasLedgerRepository::new(storage);
+    let mut days = Vec::new();
+    let mut total = 0u128;
+    for date in from.iter_days().take_while(|date| *date <= to) {
+        let day = ledger
+            .get_day(&date.format("%Y-%m-%d").to_string())
+            .context("Failed to read gas ledger")?;
+        let spent = day.total_wei();
+        total = total.saturating_add(spent);
+        days.push(FiatReconciliationDay {
+            date: day.date,
+            gas_spent_wei: spent.to_string(),
+            gas_spent_avax: format_avax(spent),
+            budget_exhausted: budget.is_some_and(|budget| spent >= budget),
+            entries: day.entries,
+        });
+    }
+
+    let unaccounted_settlements = FiatRequestRepository::new(storage)
+        .list_all()
+        .map_err(|e| ApiError::internal(format!("Failed to list fiat requests: {e}")))?
+        .into_iter()
+        .filter(|r| {
+            r.direction == FiatDirection::OnRamp
+                && r.reserve_transfer_tx_hash.is_some()
+                && r.reserve_gas_spent_wei.is_none()
+        })
+        .map(|r| r.request_id)
+        .collect();
+
+    Ok(Json(FiatReconciliationReport {
+        from: from.format("%Y-%m-%d").to_string(),
+        to: to.format("%Y-%m-%d").to_string(),
+        daily_gas_budget_avax: budget.map(format_avax),
+        total_gas_spent_avax: format_avax(total),
+        days,
+        unaccounted_settlements,
+    }))
+}
+
 /// Manual fiat request sync.
 #[utoipa::path(
     post,
@@ -1805,10 +3085,16 @@ pub async fn get_fiat_service_wallet(
     )
 )]
 pub async fn sync_fiat_request_admin(
-    AdminOnly(_admin): AdminOnly,
+    AdminOnly(admin): AdminOnly,
     State(state): State<AppState>,
     Path(request_id): Path<String>,
 ) -> Result<Json<FiatSyncResponse>, ApiError> {
+    if FiatRequestRepository::new(state.storage())
+        .get(&request_id)
+        .is_ok_and(|r| !admin.sees_tenant(r.tenant_id.as_deref()))
+    {
+        return Err(ApiError::not_found("Fiat request not found"));
+    }
     let tx_db = state
         .tx_db
         .as_ref()
@@ -1829,6 +3115,22 @@ pub async fn sync_fiat_request_admin(
 mod tests {
     use super::*;
 
+    #[test]
+    fn missing_minter_role_error_names_wallet_and_contract() {
+        let error = missing_minter_role_error(
+            "0x1111111111111111111111111111111111111111",
+            "0x2222222222222222222222222222222222222222",
+        );
+        assert_eq!(error.status, StatusCode::CONFLICT);
+        assert!(error.message.contains("MINTER_ROLE"));
+        assert!(error
+            .message
+            .contains("0x1111111111111111111111111111111111111111"));
+        assert!(error
+            .message
+            .contains("0x2222222222222222222222222222222222222222"));
+    }
+
     #[test]
     fn resolve_provider_defaults_to_truelayer_sandbox() {
         let provider = resolve_provider_id(None).expect("default provider should resolve");
@@ -1875,6 +3177,14 @@ mod tests {
         assert_eq!(iban, "GB79CLRB04066800102649");
     }
 
+    #[test]
+    fn normalize_offramp_iban_rejects_bad_check_digits() {
+        let error = normalize_offramp_iban(Some("GB79 CLRB 0406 6800 1026 48".to_string()))
+            .expect_err("mistyped IBAN should fail");
+        assert_eq!(error.status, StatusCode::BAD_REQUEST);
+        assert!(error.message.contains("check digits"));
+    }
+
     #[test]
     fn webhook_status_mapping_is_reasonable() {
         assert_eq!(
@@ -1920,12 +3230,16 @@ mod tests {
             request_id: "test".to_string(),
             wallet_id: "w".to_string(),
             owner_user_id: "u".to_string(),
+            tenant_id: None,
             direction: FiatDirection::OnRamp,
             amount_eur: "10.00".to_string(),
+            fee_eur: None,
             provider: "truelayer_sandbox".to_string(),
             note: None,
             beneficiary_account_holder_name: None,
             beneficiary_iban: None,
+            scheme_id: None,
+            executed_at: None,
             provider_reference: None,
             provider_action_url: None,
             status: FiatRequestStatus::SettlementPending,
@@ -1934,11 +3248,20 @@ mod tests {
             expected_amount_minor: None,
             deposit_tx_hash: None,
             reserve_transfer_tx_hash: None,
+            reserve_gas_spent_wei: None,
+            settlement_batch_size: None,
+            mandate_id: None,
+            beneficiary_id: None,
+            name_check: None,
+            destination_kyc: None,
+            chargeback: None,
             provider_event_id: None,
             last_provider_sync_at: None,
             last_chain_sync_at: None,
             settlement_attempts: 0,
+            settlement_tx_ids: Vec::new(),
             failure_reason: None,
+            transitions: Vec::new(),
             created_at: Utc::now(),
             updated_at: Utc::now(),
         };
@@ -1951,12 +3274,16 @@ mod tests {
             request_id: "test".to_string(),
             wallet_id: "w".to_string(),
             owner_user_id: "u".to_string(),
+            tenant_id: None,
             direction: FiatDirection::OnRamp,
             amount_eur: "10.00".to_string(),
+            fee_eur: None,
             provider: "truelayer_sandbox".to_string(),
             note: None,
             beneficiary_account_holder_name: None,
             beneficiary_iban: None,
+            scheme_id: None,
+            executed_at: None,
             provider_reference: None,
             provider_action_url: None,
             status: FiatRequestStatus::SettlementPending,
@@ -1965,11 +3292,20 @@ mod tests {
             expected_amount_minor: None,
             deposit_tx_hash: None,
             reserve_transfer_tx_hash: None,
+            reserve_gas_spent_wei: None,
+            settlement_batch_size: None,
+            mandate_id: None,
+            beneficiary_id: None,
+            name_check: None,
+            destination_kyc: None,
+            chargeback: None,
             provider_event_id: None,
             last_provider_sync_at: None,
             last_chain_sync_at: None,
             settlement_attempts: 1,
+            settlement_tx_ids: Vec::new(),
             failure_reason: None,
+            transitions: Vec::new(),
             created_at: Utc::now(),
             updated_at: Utc::now(), // just now
         };
@@ -1980,4 +3316,322 @@ mod tests {
         record.updated_at = Utc::now() - TimeDelta::try_seconds(20).unwrap();
         assert!(!should_skip_settlement_retry(&record));
     }
+
+    fn timeline_steps(record: &StoredFiatRequest) -> Vec<FiatTimelineStep> {
+        build_timeline(record).iter().map(|e| e.step).collect()
+    }
+
+    #[test]
+    fn timeline_follows_onramp_transitions() {
+        let mut record = StoredFiatRequest::new_queued(
+            "req-timeline".to_string(),
+            "w".to_string(),
+            "u".to_string(),
+            FiatDirection::OnRamp,
+            "10.00".to_string(),
+            "truelayer_sandbox".to_string(),
+            None,
+        );
+        for status in [
+            FiatRequestStatus::AwaitingProvider,
+            FiatRequestStatus::SettlementPending,
+            FiatRequestStatus::Completed,
+        ] {
+            record.status = status;
+            record.note_transition();
+        }
+        record.reserve_transfer_tx_hash = Some("0xabc".to_string());
+
+        assert_eq!(
+            timeline_steps(&record),
+            vec![
+                FiatTimelineStep::Created,
+                FiatTimelineStep::ProviderAuthorized,
+                FiatTimelineStep::Settled,
+                FiatTimelineStep::Completed,
+            ]
+        );
+        assert_eq!(build_timeline(&record)[2].detail.as_deref(), Some("0xabc"));
+    }
+
+    #[test]
+    fn timeline_reports_offramp_deposit_and_failure() {
+        let mut record = StoredFiatRequest::new_queued(
+            "req-timeline".to_string(),
+            "w".to_string(),
+            "u".to_string(),
+            FiatDirection::OffRamp,
+            "10.00".to_string(),
+            "truelayer_sandbox".to_string(),
+            None,
+        );
+        record.deposit_tx_hash = Some("0xdep".to_string());
+        record.status = FiatRequestStatus::SettlementPending;
+        record.note_transition();
+        record.status = FiatRequestStatus::Failed;
+        record.failure_reason = Some("payout rejected".to_string());
+        record.note_transition();
+
+        let timeline = build_timeline(&record);
+        assert_eq!(
+            timeline_steps(&record),
+            vec![
+                FiatTimelineStep::Created,
+                FiatTimelineStep::DepositDetected,
+                FiatTimelineStep::Failed,
+            ]
+        );
+        assert_eq!(timeline[1].detail.as_deref(), Some("0xdep"));
+        assert_eq!(timeline[2].detail.as_deref(), Some("payout rejected"));
+    }
+
+    #[test]
+    fn timeline_falls_back_to_status_for_records_without_log() {
+        let mut record = settled_onramp();
+        record.transitions.clear();
+        assert_eq!(
+            timeline_steps(&record),
+            vec![
+                FiatTimelineStep::Created,
+                FiatTimelineStep::Settled,
+                FiatTimelineStep::Completed,
+            ]
+        );
+
+        record.status = FiatRequestStatus::AwaitingProvider;
+        assert_eq!(timeline_steps(&record), vec![FiatTimelineStep::Created]);
+    }
+
+    fn settled_onramp() -> StoredFiatRequest {
+        let mut record = StoredFiatRequest::new_queued(
+            "req-gas".to_string(),
+            "w".to_string(),
+            "u".to_string(),
+            FiatDirection::OnRamp,
+            "10.00".to_string(),
+            "truelayer_sandbox".to_string(),
+            None,
+        );
+        record.status = FiatRequestStatus::Completed;
+        record.reserve_transfer_tx_hash = Some("0xabc".to_string());
+        record.last_chain_sync_at = Some(Utc::now());
+        record
+    }
+
+    #[test]
+    fn settlement_links_request_and_transaction() {
+        let dir = tempfile::tempdir().unwrap();
+        let tx_db = TxDatabase::open(&dir.path().join("tx.redb")).unwrap();
+        let mut record = settled_onramp();
+        record.status = FiatRequestStatus::SettlementPending;
+        let destination = "0x2222222222222222222222222222222222222222";
+
+        complete_settlement(
+            &mut record,
+            "0xdef",
+            "https://explorer",
+            destination,
+            &tx_db,
+            None,
+        );
+        complete_settlement(
+            &mut record,
+            "0xdef",
+            "https://explorer",
+            destination,
+            &tx_db,
+            None,
+        );
+
+        assert_eq!(record.settlement_tx_ids, ["0xdef"]);
+        assert_eq!(to_response(&record).settlement_tx_ids, ["0xdef"]);
+        let stored = tx_db.get_transaction("0xdef").unwrap().unwrap();
+        assert_eq!(stored.related_fiat_request_id.as_deref(), Some("req-gas"));
+    }
+
+    #[test]
+    fn instant_payouts_report_funds_available_once_executed() {
+        let mut record = settled_onramp();
+        record.direction = FiatDirection::OffRamp;
+        record.status = FiatRequestStatus::ProviderPending;
+        assert!(estimate_payout_arrival(&record).is_none());
+
+        note_payout_execution(&mut record, Some("sepa_credit_transfer_instant"), None);
+        let pending = estimate_payout_arrival(&record).unwrap();
+        assert!(pending.instant && !pending.funds_available);
+        assert!(record.executed_at.is_none());
+
+        let executed_at = Utc::now() - TimeDelta::seconds(3);
+        record.status = FiatRequestStatus::Completed;
+        note_payout_execution(&mut record, Some("unknown"), Some(executed_at));
+        assert_eq!(
+            record.scheme_id.as_deref(),
+            Some("sepa_credit_transfer_instant")
+        );
+        let done = estimate_payout_arrival(&record).unwrap();
+        assert!(done.funds_available);
+        assert_eq!(
+            done.expected_by,
+            (executed_at + TimeDelta::seconds(10)).to_rfc3339()
+        );
+    }
+
+    #[test]
+    fn regular_sepa_arrives_by_the_next_business_day() {
+        let friday = DateTime::parse_from_rfc3339("2026-10-16T15:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        assert_eq!(
+            next_business_day(friday).to_rfc3339(),
+            "2026-10-19T23:59:59+00:00"
+        );
+
+        let mut record = settled_onramp();
+        record.direction = FiatDirection::OffRamp;
+        record.scheme_id = Some("sepa_credit_transfer".to_string());
+        record.executed_at = Some(friday);
+        let estimate = estimate_payout_arrival(&record).unwrap();
+        assert!(!estimate.instant && !estimate.funds_available);
+    }
+
+    #[test]
+    fn gas_accounting_tracks_recent_unaccounted_settlements_only() {
+        let now = Utc::now();
+        let mut record = settled_onramp();
+        assert!(needs_gas_accounting(&record, now));
+
+        record.reserve_gas_spent_wei = Some("1".to_string());
+        assert!(!needs_gas_accounting(&record, now));
+
+        record.reserve_gas_spent_wei = None;
+        record.last_chain_sync_at = Some(now - TimeDelta::hours(GAS_ACCOUNTING_WINDOW_HOURS + 1));
+        assert!(!needs_gas_accounting(&record, now));
+
+        let mut offramp = settled_onramp();
+        offramp.direction = FiatDirection::OffRamp;
+        assert!(!needs_gas_accounting(&offramp, now));
+    }
+
+    #[test]
+    fn daily_gas_budget_blocks_once_spend_reaches_budget() {
+        let state = AppState::default();
+        let storage = state.storage();
+        assert_eq!(daily_gas_budget_exhausted(storage, None), None);
+        assert_eq!(daily_gas_budget_exhausted(storage, Some(1_000)), None);
+
+        ReserveGasLedgerRepository::new(storage)
+            .record(
+                &utc_day(Utc::now()),
+                GasSpendEntry::new("req-1", "0xaa", 100, 10),
+            )
+            .unwrap();
+        assert_eq!(
+            daily_gas_budget_exhausted(storage, Some(1_000)),
+            Some((1_000, 1_000))
+        );
+        assert_eq!(daily_gas_budget_exhausted(storage, Some(1_001)), None);
+    }
+
+    #[tokio::test]
+    async fn reconciliation_report_sums_days_and_lists_unaccounted() {
+        let state = AppState::default();
+        let storage = state.storage();
+        let ledger = ReserveGasLedgerRepository::new(storage);
+        ledger
+            .record(
+                "2026-03-01",
+                GasSpendEntry::new("req-1", "0xaa", 21_000, 1_000_000_000),
+            )
+            .unwrap();
+        ledger
+            .record(
+                "2026-03-02",
+                GasSpendEntry::new("req-2", "0xbb", 21_000, 1_000_000_000),
+            )
+            .unwrap();
+        FiatRequestRepository::new(storage)
+            .create(&settled_onramp())
+            .unwrap();
+
+        let admin = AdminOnly(crate::auth::AuthenticatedUser {
+            user_id: "admin-1".to_string(),
+            role: crate::auth::Role::Admin,
+            session_id: None,
+            issuer: "https://test.clerk.dev".to_string(),
+            expires_at: Utc::now().timestamp() + 3600,
+            tenant_id: None,
+        });
+        let Json(report) = get_fiat_reconciliation_report(
+            admin,
+            State(state.clone()),
+            Query(FiatReconciliationQuery {
+                from: Some("2026-03-01".to_string()),
+                to: Some("2026-03-03".to_string()),
+            }),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(report.days.len(), 3);
+        assert_eq!(report.days[0].gas_spent_wei, "21000000000000");
+        assert!(report.days[2].entries.is_empty());
+        assert_eq!(report.total_gas_spent_avax, format_avax(42_000_000_000_000));
+        assert_eq!(report.unaccounted_settlements, vec!["req-gas".to_string()]);
+    }
+
+    #[test]
+    fn reconciliation_rejects_bad_dates() {
+        assert!(parse_report_date("2026-13-01", "from").is_err());
+        assert_eq!(
+            parse_report_date("2026-03-01", "from").unwrap(),
+            NaiveDate::from_ymd_opt(2026, 3, 1).unwrap()
+        );
+    }
+
+    #[test]
+    fn batch_candidates_are_small_first_attempt_settlements() {
+        let mut record = settled_onramp();
+        record.status = FiatRequestStatus::SettlementPending;
+        record.reserve_transfer_tx_hash = None;
+        assert!(is_batch_candidate(&record, 5_000));
+        assert!(!is_batch_candidate(&record, 999));
+
+        record.settlement_attempts = 1;
+        assert!(!is_batch_candidate(&record, 5_000));
+    }
+
+    #[test]
+    fn batch_window_expires_into_individual_settlement() {
+        let mut record = settled_onramp();
+        record.status = FiatRequestStatus::SettlementPending;
+        record.reserve_transfer_tx_hash = None;
+        let now = Utc::now();
+        record.updated_at = now;
+        assert!(waiting_for_batch(&record, 5_000, now));
+
+        record.updated_at = now - TimeDelta::seconds(SETTLEMENT_BATCH_WINDOW_SECS + 1);
+        assert!(!waiting_for_batch(&record, 5_000, now));
+    }
+
+    #[test]
+    fn format_minor_eur_pads_cents() {
+        assert_eq!(format_minor_eur(2505), "25.05");
+        assert_eq!(format_minor_eur(7), "0.07");
+    }
+
+    #[test]
+    fn net_amount_withholds_the_tenant_fee() {
+        let mut record = StoredFiatRequest::new_queued(
+            "req".to_string(),
+            "w".to_string(),
+            "u".to_string(),
+            FiatDirection::OnRamp,
+            "10.00".to_string(),
+            "truelayer_sandbox".to_string(),
+            None,
+        );
+        assert_eq!(net_amount_eur(&record).unwrap(), "10.00");
+        record.fee_eur = Some("0.15".to_string());
+        assert_eq!(net_amount_eur(&record).unwrap(), "9.85");
+    }
 }
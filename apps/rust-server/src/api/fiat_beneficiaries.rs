@@ -0,0 +1,641 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Saved beneficiaries for off-ramp payouts.
+//!
+//! Users save the bank accounts they pay out to once and then reference
+//! them by `beneficiary_id` when creating off-ramps. Each beneficiary can
+//! carry its own per-payout and per-month limits. A newly added
+//! beneficiary is cooling for [`BENEFICIARY_COOLING_HOURS`]: during that
+//! time it only receives payouts up to [`COOLING_MAX_PAYOUT_MINOR`], so an
+//! attacker who adds their own account cannot drain a wallet to it at once.
+//!
+//! A beneficiary becomes `verified` once a payout to it completes.
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use chrono::{DateTime, Datelike, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::{
+    api::{
+        fiat::{
+            format_minor_eur, normalize_offramp_account_holder_name, normalize_offramp_iban,
+            parse_amount_to_minor,
+        },
+        soft_quotas::{self, next_month_start, QuotaUsage},
+        usage::{check_quota, QuotaKind, StorageQuotas},
+    },
+    audit_log,
+    auth::Auth,
+    error::{ApiError, StorageContext},
+    state::AppState,
+    storage::{
+        AuditEvent, AuditEventType, AuditRepository, EncryptedStorage, FiatBeneficiaryRepository,
+        FiatBeneficiaryStatus, FiatDirection, FiatRequestRepository, FiatRequestStatus,
+        StoredFiatBeneficiary, StoredFiatRequest,
+    },
+};
+
+/// How long a newly added beneficiary is limited to small payouts.
+pub const BENEFICIARY_COOLING_HOURS: i64 = 24;
+
+/// Largest payout to a cooling beneficiary, in euro cents (100 EUR).
+pub const COOLING_MAX_PAYOUT_MINOR: u64 = 10_000;
+
+/// Longest label a user may give a beneficiary.
+const MAX_LABEL_LEN: usize = 64;
+
+/// Request body for saving a beneficiary.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct CreateFiatBeneficiaryRequest {
+    /// Name of the account holder.
+    pub account_holder_name: String,
+    /// IBAN of the account; spaces are ignored.
+    pub iban: String,
+    /// Optional display name (e.g. "Savings").
+    #[serde(default)]
+    pub label: Option<String>,
+    /// Largest single payout in EUR (e.g. "500.00"); unlimited if omitted.
+    #[serde(default)]
+    pub max_single_payout_eur: Option<String>,
+    /// Largest payout total per calendar month (UTC) in EUR; unlimited if
+    /// omitted.
+    #[serde(default)]
+    pub max_monthly_eur: Option<String>,
+}
+
+/// Beneficiary returned to clients.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct FiatBeneficiaryResponse {
+    pub beneficiary_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    pub account_holder_name: String,
+    pub iban: String,
+    pub status: FiatBeneficiaryStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_single_payout_eur: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_monthly_eur: Option<String>,
+    /// Payouts to the beneficiary this calendar month, excluding failed
+    /// ones.
+    pub used_this_month_eur: String,
+    /// Until when payouts are capped at the cooling limit; absent once the
+    /// cooling period is over.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cooling_until: Option<String>,
+    pub created_at: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verified_at: Option<String>,
+}
+
+/// List response for beneficiaries.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct FiatBeneficiaryListResponse {
+    pub beneficiaries: Vec<FiatBeneficiaryResponse>,
+    pub total: usize,
+}
+
+fn cooling_until(beneficiary: &StoredFiatBeneficiary) -> DateTime<Utc> {
+    beneficiary.created_at + Duration::hours(BENEFICIARY_COOLING_HOURS)
+}
+
+fn to_beneficiary_response(
+    beneficiary: &StoredFiatBeneficiary,
+    used_minor: u64,
+    now: DateTime<Utc>,
+) -> FiatBeneficiaryResponse {
+    let until = cooling_until(beneficiary);
+    FiatBeneficiaryResponse {
+        beneficiary_id: beneficiary.beneficiary_id.clone(),
+        label: beneficiary.label.clone(),
+        account_holder_name: beneficiary.account_holder_name.clone(),
+        iban: beneficiary.iban.clone(),
+        status: beneficiary.status,
+        max_single_payout_eur: beneficiary.max_single_payout_minor.map(format_minor_eur),
+        max_monthly_eur: beneficiary.max_monthly_minor.map(format_minor_eur),
+        used_this_month_eur: format_minor_eur(used_minor),
+        cooling_until: (now < until).then(|| until.to_rfc3339()),
+        created_at: beneficiary.created_at.to_rfc3339(),
+        verified_at: beneficiary.verified_at.map(|ts| ts.to_rfc3339()),
+    }
+}
+
+/// Euro cents paid out to `beneficiary_id` in the calendar month of `now`.
+fn beneficiary_usage_minor(
+    requests: &[StoredFiatRequest],
+    beneficiary_id: &str,
+    now: DateTime<Utc>,
+) -> u64 {
+    requests
+        .iter()
+        .filter(|r| r.direction == FiatDirection::OffRamp)
+        .filter(|r| r.beneficiary_id.as_deref() == Some(beneficiary_id))
+        .filter(|r| r.status != FiatRequestStatus::Failed)
+        .filter(|r| r.created_at.year() == now.year() && r.created_at.month() == now.month())
+        .filter_map(|r| parse_amount_to_minor(&r.amount_eur).ok())
+        .fold(0u64, |acc, (_, minor)| acc.saturating_add(minor))
+}
+
+fn check_beneficiary_limits(
+    beneficiary: &StoredFiatBeneficiary,
+    used_minor: u64,
+    amount_minor: u64,
+    now: DateTime<Utc>,
+) -> Result<(), ApiError> {
+    let until = cooling_until(beneficiary);
+    if now < until && amount_minor > COOLING_MAX_PAYOUT_MINOR {
+        return Err(ApiError::unprocessable(format!(
+            "Beneficiary was added recently; payouts above {} EUR are allowed from {}",
+            format_minor_eur(COOLING_MAX_PAYOUT_MINOR),
+            until.to_rfc3339()
+        )));
+    }
+    if let Some(max) = beneficiary.max_single_payout_minor {
+        if amount_minor > max {
+            return Err(ApiError::unprocessable(format!(
+                "Amount exceeds the beneficiary's single-payout limit of {} EUR",
+                format_minor_eur(max)
+            )));
+        }
+    }
+    if let Some(max) = beneficiary.max_monthly_minor {
+        if used_minor.saturating_add(amount_minor) > max {
+            return Err(ApiError::unprocessable(format!(
+                "Amount exceeds the beneficiary's monthly limit: {} of {} EUR used",
+                format_minor_eur(used_minor),
+                format_minor_eur(max)
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn parse_limit(value: Option<String>, field: &str) -> Result<Option<u64>, ApiError> {
+    value
+        .map(|value| {
+            parse_amount_to_minor(&value)
+                .map(|(_, minor)| minor)
+                .map_err(|_| {
+                    ApiError::bad_request(format!("{field} must be a positive EUR amount"))
+                })
+        })
+        .transpose()
+}
+
+fn owner_requests(
+    storage: &EncryptedStorage,
+    owner_user_id: &str,
+) -> Result<Vec<StoredFiatRequest>, ApiError> {
+    FiatRequestRepository::new(storage)
+        .list_filtered_for_owner(owner_user_id, None, None, None)
+        .context("Failed to list fiat requests")
+}
+
+fn load_owned_beneficiary(
+    storage: &EncryptedStorage,
+    user_id: &str,
+    beneficiary_id: &str,
+) -> Result<StoredFiatBeneficiary, ApiError> {
+    let beneficiary = FiatBeneficiaryRepository::new(storage)
+        .get(beneficiary_id)
+        .ok()
+        .filter(|b| b.removed_at.is_none())
+        .ok_or_else(|| ApiError::not_found("Beneficiary not found"))?;
+    if beneficiary.owner_user_id != user_id {
+        return Err(ApiError::forbidden(
+            "You do not have permission to access this beneficiary",
+        ));
+    }
+    Ok(beneficiary)
+}
+
+/// Load the user's beneficiary for an off-ramp of `amount_minor` euro cents,
+/// after checking its cooling period and limits.
+pub(crate) fn payout_beneficiary(
+    storage: &EncryptedStorage,
+    user_id: &str,
+    beneficiary_id: &str,
+    amount_minor: u64,
+) -> Result<StoredFiatBeneficiary, ApiError> {
+    let beneficiary = load_owned_beneficiary(storage, user_id, beneficiary_id)?;
+    let now = Utc::now();
+    let used = beneficiary_usage_minor(
+        &owner_requests(storage, user_id)?,
+        &beneficiary.beneficiary_id,
+        now,
+    );
+    check_beneficiary_limits(&beneficiary, used, amount_minor, now)?;
+    if let Some(max) = beneficiary.max_monthly_minor {
+        let used = used.saturating_add(amount_minor);
+        soft_quotas::record(QuotaUsage {
+            policy: "beneficiary_monthly",
+            limit: max,
+            used,
+            resets_at: Some(next_month_start(now)),
+            message: format!(
+                "{} of the beneficiary's {} EUR monthly limit used",
+                format_minor_eur(used),
+                format_minor_eur(max)
+            ),
+        });
+    }
+    Ok(beneficiary)
+}
+
+/// Mark the beneficiary of a completed off-ramp as verified.
+pub(crate) fn note_completed_payout(storage: &EncryptedStorage, record: &StoredFiatRequest) {
+    if record.direction != FiatDirection::OffRamp || record.status != FiatRequestStatus::Completed {
+        return;
+    }
+    let Some(beneficiary_id) = record.beneficiary_id.as_deref() else {
+        return;
+    };
+    let repo = FiatBeneficiaryRepository::new(storage);
+    let Ok(mut beneficiary) = repo.get(beneficiary_id) else {
+        return;
+    };
+    if beneficiary.status == FiatBeneficiaryStatus::Verified {
+        return;
+    }
+    let now = Utc::now();
+    beneficiary.status = FiatBeneficiaryStatus::Verified;
+    beneficiary.verified_at = Some(now);
+    beneficiary.updated_at = now;
+    if let Err(e) = repo.update(&beneficiary) {
+        tracing::warn!(
+            beneficiary_id = %beneficiary_id,
+            error = %e,
+            "Failed to mark beneficiary verified"
+        );
+    }
+}
+
+/// Save a beneficiary for off-ramp payouts.
+#[utoipa::path(
+    post,
+    path = "/v1/fiat/beneficiaries",
+    tag = "Fiat",
+    request_body = CreateFiatBeneficiaryRequest,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 201, description = "Beneficiary saved", body = FiatBeneficiaryResponse),
+        (status = 400, description = "Bad request"),
+        (status = 401, description = "Unauthorized"),
+        (status = 409, description = "IBAN already saved"),
+        (status = 422, description = "Storage quota exceeded")
+    )
+)]
+pub async fn create_fiat_beneficiary(
+    Auth(user): Auth,
+    State(state): State<AppState>,
+    Json(request): Json<CreateFiatBeneficiaryRequest>,
+) -> Result<(StatusCode, Json<FiatBeneficiaryResponse>), ApiError> {
+    let account_holder_name =
+        normalize_offramp_account_holder_name(Some(request.account_holder_name))?;
+    let iban = normalize_offramp_iban(Some(request.iban))?;
+    let label = request
+        .label
+        .map(|label| label.trim().to_string())
+        .filter(|label| !label.is_empty());
+    if label
+        .as_ref()
+        .is_some_and(|l| l.chars().count() > MAX_LABEL_LEN)
+    {
+        return Err(ApiError::bad_request(format!(
+            "label must be at most {MAX_LABEL_LEN} characters"
+        )));
+    }
+    let max_single_payout_minor =
+        parse_limit(request.max_single_payout_eur, "max_single_payout_eur")?;
+    let max_monthly_minor = parse_limit(request.max_monthly_eur, "max_monthly_eur")?;
+    if let (Some(single), Some(monthly)) = (max_single_payout_minor, max_monthly_minor) {
+        if single > monthly {
+            return Err(ApiError::bad_request(
+                "max_single_payout_eur cannot exceed max_monthly_eur",
+            ));
+        }
+    }
+
+    let storage = state.storage();
+    let repo = FiatBeneficiaryRepository::new(storage);
+    if repo
+        .list_by_owner(&user.user_id)
+        .context("Failed to list beneficiaries")?
+        .iter()
+        .any(|b| b.iban == iban)
+    {
+        return Err(ApiError::conflict(
+            "A beneficiary with this IBAN is already saved",
+        ));
+    }
+
+    let now = Utc::now();
+    let beneficiary = StoredFiatBeneficiary {
+        beneficiary_id: uuid::Uuid::new_v4().to_string(),
+        owner_user_id: user.user_id.clone(),
+        label,
+        account_holder_name,
+        iban,
+        status: FiatBeneficiaryStatus::Unverified,
+        max_single_payout_minor,
+        max_monthly_minor,
+        created_at: now,
+        updated_at: now,
+        verified_at: None,
+        removed_at: None,
+    };
+    let bytes = serde_json::to_vec(&beneficiary).map_or(0, |b| b.len() as u64);
+    check_quota(
+        storage,
+        &user.user_id,
+        QuotaKind::FiatBeneficiaries,
+        bytes,
+        &StorageQuotas::from_env(),
+    )?;
+    repo.create(&beneficiary)
+        .context("Failed to store beneficiary")?;
+
+    audit_log!(
+        storage,
+        AuditEventType::FiatBeneficiaryAdded,
+        &user,
+        "fiat_beneficiary",
+        &beneficiary.beneficiary_id
+    );
+
+    Ok((
+        StatusCode::CREATED,
+        Json(to_beneficiary_response(&beneficiary, 0, now)),
+    ))
+}
+
+/// List the current user's beneficiaries.
+#[utoipa::path(
+    get,
+    path = "/v1/fiat/beneficiaries",
+    tag = "Fiat",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Beneficiaries listed", body = FiatBeneficiaryListResponse),
+        (status = 401, description = "Unauthorized")
+    )
+)]
+pub async fn list_fiat_beneficiaries(
+    Auth(user): Auth,
+    State(state): State<AppState>,
+) -> Result<Json<FiatBeneficiaryListResponse>, ApiError> {
+    let storage = state.storage();
+    let beneficiaries = FiatBeneficiaryRepository::new(storage)
+        .list_by_owner(&user.user_id)
+        .context("Failed to list beneficiaries")?;
+    let requests = owner_requests(storage, &user.user_id)?;
+    let now = Utc::now();
+
+    let beneficiaries: Vec<_> = beneficiaries
+        .iter()
+        .map(|b| {
+            let used = beneficiary_usage_minor(&requests, &b.beneficiary_id, now);
+            to_beneficiary_response(b, used, now)
+        })
+        .collect();
+    Ok(Json(FiatBeneficiaryListResponse {
+        total: beneficiaries.len(),
+        beneficiaries,
+    }))
+}
+
+/// Get a beneficiary.
+#[utoipa::path(
+    get,
+    path = "/v1/fiat/beneficiaries/{beneficiary_id}",
+    tag = "Fiat",
+    params(("beneficiary_id" = String, Path, description = "Beneficiary ID")),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Beneficiary details", body = FiatBeneficiaryResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+        (status = 404, description = "Not found")
+    )
+)]
+pub async fn get_fiat_beneficiary(
+    Auth(user): Auth,
+    State(state): State<AppState>,
+    Path(beneficiary_id): Path<String>,
+) -> Result<Json<FiatBeneficiaryResponse>, ApiError> {
+    let storage = state.storage();
+    let beneficiary = load_owned_beneficiary(storage, &user.user_id, &beneficiary_id)?;
+    let now = Utc::now();
+    let used = beneficiary_usage_minor(
+        &owner_requests(storage, &user.user_id)?,
+        &beneficiary.beneficiary_id,
+        now,
+    );
+    Ok(Json(to_beneficiary_response(&beneficiary, used, now)))
+}
+
+/// Remove a beneficiary. Off-ramps already paying out to it are unaffected.
+#[utoipa::path(
+    delete,
+    path = "/v1/fiat/beneficiaries/{beneficiary_id}",
+    tag = "Fiat",
+    params(("beneficiary_id" = String, Path, description = "Beneficiary ID")),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 204, description = "Beneficiary removed"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+        (status = 404, description = "Not found")
+    )
+)]
+pub async fn delete_fiat_beneficiary(
+    Auth(user): Auth,
+    State(state): State<AppState>,
+    Path(beneficiary_id): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    let storage = state.storage();
+    let mut beneficiary = load_owned_beneficiary(storage, &user.user_id, &beneficiary_id)?;
+
+    let now = Utc::now();
+    beneficiary.removed_at = Some(now);
+    beneficiary.updated_at = now;
+    FiatBeneficiaryRepository::new(storage)
+        .update(&beneficiary)
+        .context("Failed to remove beneficiary")?;
+
+    let event = AuditEvent::new(AuditEventType::FiatBeneficiaryRemoved)
+        .with_user(&user.user_id)
+        .with_resource("fiat_beneficiary", &beneficiary_id);
+    let _ = AuditRepository::new(storage).log(&event);
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::{AuthenticatedUser, Role};
+
+    fn user(user_id: &str) -> Auth {
+        Auth(AuthenticatedUser {
+            user_id: user_id.to_string(),
+            role: Role::Client,
+            session_id: None,
+            issuer: "https://test.clerk.dev".into(),
+            expires_at: Utc::now().timestamp() + 3600,
+            tenant_id: None,
+        })
+    }
+
+    fn beneficiary(created_at: DateTime<Utc>) -> StoredFiatBeneficiary {
+        StoredFiatBeneficiary {
+            beneficiary_id: "b-1".to_string(),
+            owner_user_id: "user-1".to_string(),
+            label: None,
+            account_holder_name: "Jane Doe".to_string(),
+            iban: "GB33BUKB20201555555555".to_string(),
+            status: FiatBeneficiaryStatus::Unverified,
+            max_single_payout_minor: Some(50_000),
+            max_monthly_minor: Some(80_000),
+            created_at,
+            updated_at: created_at,
+            verified_at: None,
+            removed_at: None,
+        }
+    }
+
+    fn payout(amount: &str, status: FiatRequestStatus) -> StoredFiatRequest {
+        let mut record = StoredFiatRequest::new_queued(
+            uuid::Uuid::new_v4().to_string(),
+            "w-1".to_string(),
+            "user-1".to_string(),
+            FiatDirection::OffRamp,
+            amount.to_string(),
+            "truelayer_sandbox".to_string(),
+            None,
+        );
+        record.beneficiary_id = Some("b-1".to_string());
+        record.status = status;
+        record
+    }
+
+    fn create_request(iban: &str) -> CreateFiatBeneficiaryRequest {
+        CreateFiatBeneficiaryRequest {
+            account_holder_name: "Jane Doe".to_string(),
+            iban: iban.to_string(),
+            label: Some(" Savings ".to_string()),
+            max_single_payout_eur: None,
+            max_monthly_eur: None,
+        }
+    }
+
+    #[test]
+    fn usage_counts_this_months_non_failed_payouts() {
+        let now = Utc::now();
+        let mut last_month = payout("40.00", FiatRequestStatus::Completed);
+        last_month.created_at = now - Duration::days(40);
+        let requests = vec![
+            payout("10.50", FiatRequestStatus::Completed),
+            payout("20.00", FiatRequestStatus::ProviderPending),
+            payout("99.00", FiatRequestStatus::Failed),
+            last_month,
+        ];
+        assert_eq!(beneficiary_usage_minor(&requests, "b-1", now), 3_050);
+    }
+
+    #[test]
+    fn cooling_period_caps_payouts_before_the_limits_apply() {
+        let now = Utc::now();
+        let fresh = beneficiary(now - Duration::hours(1));
+        assert!(check_beneficiary_limits(&fresh, 0, COOLING_MAX_PAYOUT_MINOR, now).is_ok());
+        assert_eq!(
+            check_beneficiary_limits(&fresh, 0, COOLING_MAX_PAYOUT_MINOR + 1, now)
+                .unwrap_err()
+                .status,
+            StatusCode::UNPROCESSABLE_ENTITY
+        );
+
+        let settled = beneficiary(now - Duration::hours(BENEFICIARY_COOLING_HOURS));
+        assert!(check_beneficiary_limits(&settled, 0, 50_000, now).is_ok());
+        assert!(check_beneficiary_limits(&settled, 0, 50_001, now).is_err());
+        assert!(check_beneficiary_limits(&settled, 30_000, 50_000, now).is_ok());
+        assert!(check_beneficiary_limits(&settled, 30_001, 50_000, now).is_err());
+    }
+
+    #[tokio::test]
+    async fn create_list_and_remove() {
+        let state = AppState::default();
+        let (status, Json(created)) = create_fiat_beneficiary(
+            user("user-1"),
+            State(state.clone()),
+            Json(create_request("gb33 bukb 2020 1555 5555 55")),
+        )
+        .await
+        .unwrap();
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(created.iban, "GB33BUKB20201555555555");
+        assert_eq!(created.label.as_deref(), Some("Savings"));
+        assert_eq!(created.status, FiatBeneficiaryStatus::Unverified);
+        assert!(created.cooling_until.is_some());
+
+        let err = create_fiat_beneficiary(
+            user("user-1"),
+            State(state.clone()),
+            Json(create_request("GB33BUKB20201555555555")),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::CONFLICT);
+
+        let id = created.beneficiary_id;
+        let err = get_fiat_beneficiary(user("user-2"), State(state.clone()), Path(id.clone()))
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, StatusCode::FORBIDDEN);
+
+        let status =
+            delete_fiat_beneficiary(user("user-1"), State(state.clone()), Path(id.clone()))
+                .await
+                .unwrap();
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        let Json(listed) = list_fiat_beneficiaries(user("user-1"), State(state.clone()))
+            .await
+            .unwrap();
+        assert_eq!(listed.total, 0);
+        let err = payout_beneficiary(state.storage(), "user-1", &id, 100).unwrap_err();
+        assert_eq!(err.status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn completed_payout_verifies_beneficiary() {
+        let state = AppState::default();
+        FiatBeneficiaryRepository::new(state.storage())
+            .create(&beneficiary(Utc::now()))
+            .unwrap();
+
+        note_completed_payout(
+            state.storage(),
+            &payout("5.00", FiatRequestStatus::ProviderPending),
+        );
+        let repo = FiatBeneficiaryRepository::new(state.storage());
+        assert_eq!(
+            repo.get("b-1").unwrap().status,
+            FiatBeneficiaryStatus::Unverified
+        );
+
+        note_completed_payout(
+            state.storage(),
+            &payout("5.00", FiatRequestStatus::Completed),
+        );
+        let verified = repo.get("b-1").unwrap();
+        assert_eq!(verified.status, FiatBeneficiaryStatus::Verified);
+        assert!(verified.verified_at.is_some());
+    }
+}
@@ -0,0 +1,513 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Card on-ramp webhooks and chargeback recovery.
+//!
+//! Checkout results update the on-ramp like TrueLayer webhooks do. A dispute
+//! (chargeback) marks the on-ramp: if no rEUR was delivered yet, settlement
+//! is cancelled; otherwise the poller moves the delivered rEUR from the
+//! user's wallet back to the reserve wallet, recovering as much as the
+//! wallet still holds.
+
+use std::str::FromStr;
+use std::sync::Arc;
+
+use alloy::primitives::U256;
+use axum::{
+    extract::State,
+    http::{HeaderMap, StatusCode},
+};
+use chrono::{TimeDelta, Utc};
+use tracing::{info, warn};
+
+use crate::{
+    api::fiat::{
+        map_onramp_provider_status, net_amount_eur, parse_amount_to_token_minor_u256,
+        resolve_reur_contract_address, settlement_decimals, spawn_immediate_settlement,
+        tenant_provider_credentials,
+    },
+    api::transactions::send_from_wallet,
+    blockchain::{avax_fuji, format_amount, transactions::NonceManager, AvaxClient},
+    error::{ApiError, StorageContext},
+    providers::{
+        card::{self, CardPaymentClient, CardWebhookAction, CardWebhookEvent},
+        fiat::{ProviderExecutionStatus, CARD_PROVIDER_ID},
+    },
+    state::AppState,
+    storage::{
+        AuditEvent, AuditEventType, AuditRepository, ClawbackStatus, EncryptedStorage, FiatActor,
+        FiatChargeback, FiatDirection, FiatRequestRepository, FiatRequestStatus, StoredFiatRequest,
+        StoredTransaction, TenantConfigRepository, TokenType, TxCache, TxDatabase, TxStatus,
+        WalletRepository,
+    },
+};
+
+/// Recovery transfers tried before the chargeback is left to an admin.
+const MAX_CLAWBACK_ATTEMPTS: u32 = 5;
+/// Delay between recovery attempts, multiplied by the attempt count. The
+/// usual cause of failure is a wallet without AVAX for gas.
+const CLAWBACK_RETRY_SECS: i64 = 60;
+
+/// Apply a checkout status to an on-ramp, never leaving a terminal state or
+/// moving back from settlement.
+fn apply_checkout_status(record: &mut StoredFiatRequest, status: ProviderExecutionStatus) {
+    if status == ProviderExecutionStatus::Failed {
+        record.fail(
+            "Card payment failed or the checkout expired",
+            FiatActor::Provider,
+        );
+    } else {
+        record.transition(map_onramp_provider_status(status), FiatActor::Provider);
+    }
+}
+
+/// Record a dispute against an on-ramp. Returns `false` if it was already
+/// recorded.
+fn record_chargeback(
+    record: &mut StoredFiatRequest,
+    dispute_id: &str,
+    reason: Option<String>,
+) -> bool {
+    if record.chargeback.is_some() {
+        return false;
+    }
+    let now = Utc::now();
+    // Settled, or possibly settling: the sync decides once any in-flight
+    // reserve send resolves.
+    let delivered = matches!(
+        record.status,
+        FiatRequestStatus::Completed | FiatRequestStatus::SettlementPending
+    );
+    record.chargeback = Some(FiatChargeback {
+        dispute_id: dispute_id.to_string(),
+        reason,
+        received_at: now,
+        clawback_status: if delivered {
+            ClawbackStatus::Pending
+        } else {
+            ClawbackStatus::NotRequired
+        },
+        clawback_attempts: 0,
+        clawed_back_eur: None,
+        clawback_tx_hash: None,
+        detail: None,
+        resolved_at: if delivered { None } else { Some(now) },
+    });
+    if !delivered && record.status != FiatRequestStatus::Failed {
+        record.fail("Card payment was charged back", FiatActor::Provider);
+    }
+    record.updated_at = now;
+    true
+}
+
+fn find_by_reference(
+    storage: &EncryptedStorage,
+    provider_reference: &str,
+) -> Result<Option<StoredFiatRequest>, ApiError> {
+    let requests = FiatRequestRepository::new(storage)
+        .list_all()
+        .map_err(|e| ApiError::internal(format!("Failed to list fiat requests: {e}")))?;
+    Ok(requests.into_iter().find(|record| {
+        record.provider == CARD_PROVIDER_ID
+            && record.provider_reference.as_deref() == Some(provider_reference)
+    }))
+}
+
+/// Card PSP webhook (checkout results and disputes).
+#[utoipa::path(
+    post,
+    path = "/v1/fiat/providers/card/webhook",
+    tag = "Fiat",
+    request_body(content = String, content_type = "application/json", description = "PSP event, signed in the `Stripe-Signature` header"),
+    responses(
+        (status = 202, description = "Webhook accepted"),
+        (status = 400, description = "Invalid payload"),
+        (status = 403, description = "Signature verification failed"),
+        (status = 503, description = "Card webhooks not configured")
+    )
+)]
+pub async fn card_webhook(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    body: axum::body::Bytes,
+) -> Result<StatusCode, ApiError> {
+    // The deployment's account and tenants with their own card account each
+    // sign with their own secret; the one that verifies names the account.
+    let mut secrets: Vec<(Option<String>, String)> = card::webhook_secret()
+        .map(|secret| (None, secret))
+        .into_iter()
+        .collect();
+    let tenants = TenantConfigRepository::new(state.storage())
+        .list_all()
+        .unwrap_or_default();
+    secrets.extend(tenants.into_iter().filter_map(|config| {
+        let secret = config.provider_credentials.card?.webhook_secret?;
+        Some((Some(config.tenant_id), secret))
+    }));
+    if secrets.is_empty() {
+        return Err(ApiError::service_unavailable(
+            "Card webhooks are not configured. Set CARD_PSP_WEBHOOK_SECRET.",
+        ));
+    }
+    let signature = headers
+        .get(card::WEBHOOK_SIGNATURE_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .ok_or_else(|| ApiError::forbidden("Missing Stripe-Signature header"))?;
+    let now = Utc::now().timestamp();
+    let mut failure = None;
+    let mut account = None;
+    for (tenant_id, secret) in secrets {
+        match card::verify_webhook_signature(&secret, signature, &body, now) {
+            Ok(()) => {
+                account = Some(tenant_id);
+                break;
+            }
+            Err(e) => failure = Some(e.to_string()),
+        }
+    }
+    let Some(tenant_id) = account else {
+        return Err(ApiError::forbidden(failure.unwrap_or_default()));
+    };
+
+    let event: CardWebhookEvent = serde_json::from_slice(&body)
+        .map_err(|e| ApiError::bad_request(format!("Invalid webhook payload: {e}")))?;
+
+    let storage = state.storage();
+    let repo = FiatRequestRepository::new(storage);
+    match card::classify_webhook_event(&event) {
+        CardWebhookAction::Checkout { session_id, status } => {
+            let Some(mut record) = find_by_reference(storage, &session_id)? else {
+                return Ok(StatusCode::ACCEPTED);
+            };
+            if record.provider_event_id.as_deref() == Some(event.id.as_str()) {
+                return Ok(StatusCode::ACCEPTED);
+            }
+            apply_checkout_status(&mut record, status);
+            record.provider_event_id = Some(event.id.clone());
+            record.last_provider_sync_at = Some(Utc::now());
+            record.updated_at = Utc::now();
+            repo.update(&mut record)
+                .context("Failed to persist webhook update")?;
+            info!(
+                request_id = %record.request_id,
+                event_type = %event.event_type,
+                status = ?record.status,
+                "Card webhook status transition"
+            );
+
+            if record.status == FiatRequestStatus::SettlementPending
+                && record.reserve_transfer_tx_hash.is_none()
+            {
+                spawn_immediate_settlement(&state, &record.request_id)?;
+            }
+        }
+        CardWebhookAction::Dispute {
+            dispute_id,
+            payment_intent_id,
+            reason,
+        } => {
+            let credentials = tenant_provider_credentials(storage, tenant_id.as_deref());
+            let client = CardPaymentClient::for_tenant(credentials.card.as_ref())
+                .map_err(|e| ApiError::service_unavailable(e.to_string()))?;
+            let Some(request_id) = client
+                .fetch_payment_request_id(&payment_intent_id)
+                .await
+                .map_err(|e| ApiError::service_unavailable(e.to_string()))?
+            else {
+                warn!(payment_intent_id = %payment_intent_id, "Dispute for unknown payment");
+                return Ok(StatusCode::ACCEPTED);
+            };
+            let Ok(mut record) = repo.get(&request_id) else {
+                return Ok(StatusCode::ACCEPTED);
+            };
+            if record.direction != FiatDirection::OnRamp
+                || !record_chargeback(&mut record, &dispute_id, reason)
+            {
+                return Ok(StatusCode::ACCEPTED);
+            }
+            repo.update(&mut record)
+                .context("Failed to persist chargeback")?;
+
+            let event = AuditEvent::new(AuditEventType::FiatChargebackReceived)
+                .with_user(&record.owner_user_id)
+                .with_resource("fiat_request", &record.request_id)
+                .with_details(serde_json::json!({ "dispute_id": dispute_id }));
+            let _ = AuditRepository::new(storage).log(&event);
+            warn!(
+                request_id = %record.request_id,
+                dispute_id = %dispute_id,
+                "Card payment charged back"
+            );
+        }
+        CardWebhookAction::Ignored => {}
+    }
+
+    Ok(StatusCode::ACCEPTED)
+}
+
+fn clawback_due(record: &StoredFiatRequest, now: chrono::DateTime<Utc>) -> bool {
+    let Some(chargeback) = &record.chargeback else {
+        return false;
+    };
+    chargeback.clawback_status == ClawbackStatus::Pending
+        && record.status == FiatRequestStatus::Completed
+        && (chargeback.clawback_attempts == 0
+            || now - record.updated_at
+                >= TimeDelta::seconds(CLAWBACK_RETRY_SECS * chargeback.clawback_attempts as i64))
+}
+
+/// Move up to the settled amount from the user's wallet back to the reserve
+/// wallet. Returns the transfer and the amount moved, or `None` if the
+/// wallet holds no rEUR.
+async fn transfer_back(
+    storage: &EncryptedStorage,
+    nonces: &Arc<NonceManager>,
+    record: &StoredFiatRequest,
+) -> Result<Option<(crate::blockchain::transactions::SendResult, U256, U256)>, ApiError> {
+    let contract = resolve_reur_contract_address()?;
+    let reserve = record
+        .service_wallet_address
+        .clone()
+        .ok_or_else(|| ApiError::internal("Request has no reserve wallet address"))?;
+    let wallet_repo = WalletRepository::new(storage);
+    let wallet = wallet_repo
+        .get(&record.wallet_id)
+        .context("Failed to load wallet")?;
+    // Only the amount net of the tenant fee was delivered.
+    let delivered_eur = net_amount_eur(record)?;
+    let owed = parse_amount_to_token_minor_u256(&delivered_eur)?;
+
+    let client = AvaxClient::fuji()
+        .await
+        .map_err(|e| ApiError::service_unavailable(format!("Failed to connect to chain: {e}")))?;
+    let balance = client
+        .get_token_balance(&wallet.public_address, &contract)
+        .await
+        .map_err(|e| ApiError::service_unavailable(format!("Failed to read balance: {e}")))?;
+    let balance = U256::from_str(&balance.balance_raw).unwrap_or(U256::ZERO);
+    let amount = owed.min(balance);
+    if amount.is_zero() {
+        return Ok(None);
+    }
+
+    let sent = send_from_wallet(
+        storage,
+        nonces,
+        &wallet,
+        &avax_fuji(),
+        &reserve,
+        &TokenType::Erc20(contract),
+        amount,
+        None,
+        None,
+    )
+    .await
+    .map_err(|e| ApiError {
+        message: format!("Recovery transfer failed: {}", e.message),
+        ..e
+    })?;
+    Ok(Some((sent, amount, owed)))
+}
+
+/// Attempt one recovery transfer and update the chargeback accordingly.
+async fn claw_back(
+    storage: &EncryptedStorage,
+    nonces: &Arc<NonceManager>,
+    tx_db: &TxDatabase,
+    tx_cache: Option<&TxCache>,
+    record: &mut StoredFiatRequest,
+) {
+    let result = transfer_back(storage, nonces, record).await;
+    let now = Utc::now();
+    let user_address = WalletRepository::new(storage)
+        .get(&record.wallet_id)
+        .map(|w| w.public_address)
+        .unwrap_or_default();
+    let Some(chargeback) = record.chargeback.as_mut() else {
+        return;
+    };
+    chargeback.clawback_attempts += 1;
+
+    match result {
+        Ok(Some((sent, amount, owed))) => {
+            let recovered_eur = format_amount(amount, settlement_decimals());
+            chargeback.clawback_status = if amount == owed {
+                ClawbackStatus::Recovered
+            } else {
+                ClawbackStatus::Partial
+            };
+            let owed_eur = format_amount(owed, settlement_decimals());
+            chargeback.detail = (amount != owed)
+                .then(|| format!("Wallet held only {recovered_eur} of {owed_eur} rEUR"));
+            chargeback.clawed_back_eur = Some(recovered_eur.clone());
+            chargeback.clawback_tx_hash = Some(sent.tx_hash.clone());
+            chargeback.resolved_at = Some(now);
+
+            let reur_contract = resolve_reur_contract_address().unwrap_or_default();
+            let reserve = record.service_wallet_address.clone().unwrap_or_default();
+            let tx_record = StoredTransaction::new_pending(
+                sent.tx_hash.clone(),
+                record.wallet_id.clone(),
+                None,
+                user_address.clone(),
+                reserve,
+                recovered_eur,
+                TokenType::Erc20(reur_contract),
+                record.chain_network.clone(),
+                sent.explorer_url,
+            );
+            let mut tx_record = tx_record;
+            tx_record.status = TxStatus::Confirmed;
+            tx_record.related_fiat_request_id = Some(record.request_id.clone());
+            let directions = vec![(user_address.clone(), "sent")];
+            if let Err(e) = tx_db.upsert_transaction(&tx_record, &directions) {
+                warn!(request_id = %record.request_id, "failed to store clawback transaction: {e}");
+            } else if let Some(tx_cache) = tx_cache {
+                tx_cache.invalidate(&user_address);
+            }
+
+            let event = AuditEvent::new(AuditEventType::FiatClawback)
+                .with_user(&record.owner_user_id)
+                .with_resource("fiat_request", &record.request_id)
+                .with_details(serde_json::json!({
+                    "tx_hash": sent.tx_hash,
+                    "amount_eur": chargeback.clawed_back_eur,
+                }));
+            let _ = AuditRepository::new(storage).log(&event);
+            info!(
+                request_id = %record.request_id,
+                tx_hash = %sent.tx_hash,
+                status = ?chargeback.clawback_status,
+                "Chargeback clawback sent"
+            );
+            record.link_settlement_tx(&sent.tx_hash);
+        }
+        Ok(None) => {
+            chargeback.clawback_status = ClawbackStatus::Failed;
+            chargeback.detail = Some("Wallet holds no rEUR to recover".to_string());
+            chargeback.resolved_at = Some(now);
+        }
+        Err(error) => {
+            warn!(
+                request_id = %record.request_id,
+                attempt = chargeback.clawback_attempts,
+                error = %error.message,
+                "Chargeback clawback failed"
+            );
+            chargeback.detail = Some(error.message);
+            if chargeback.clawback_attempts >= MAX_CLAWBACK_ATTEMPTS {
+                chargeback.clawback_status = ClawbackStatus::Failed;
+                chargeback.resolved_at = Some(now);
+            }
+        }
+    }
+    record.updated_at = now;
+}
+
+/// Run due chargeback recoveries. Returns the number attempted.
+pub(crate) async fn run_pending_clawbacks(
+    storage: &Arc<EncryptedStorage>,
+    nonces: &Arc<NonceManager>,
+    tx_db: &TxDatabase,
+    tx_cache: Option<&TxCache>,
+) -> usize {
+    let repo = FiatRequestRepository::new(storage);
+    let now = Utc::now();
+    let due: Vec<StoredFiatRequest> = match repo.list_all() {
+        Ok(requests) => requests
+            .into_iter()
+            .filter(|r| clawback_due(r, now))
+            .collect(),
+        Err(e) => {
+            warn!(error = %e, "Clawback: failed to list fiat requests");
+            return 0;
+        }
+    };
+
+    for mut record in due.iter().cloned() {
+        claw_back(storage, nonces, tx_db, tx_cache, &mut record).await;
+        if let Err(e) = repo.update(&mut record) {
+            warn!(request_id = %record.request_id, error = %e, "Clawback: failed to persist");
+        }
+    }
+    due.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn onramp(status: FiatRequestStatus) -> StoredFiatRequest {
+        let mut record = StoredFiatRequest::new_queued(
+            "req-1".to_string(),
+            "wallet-1".to_string(),
+            "user-1".to_string(),
+            FiatDirection::OnRamp,
+            "25.00".to_string(),
+            CARD_PROVIDER_ID.to_string(),
+            None,
+        );
+        record.status = status;
+        record
+    }
+
+    #[test]
+    fn checkout_updates_never_leave_terminal_or_settling_states() {
+        let mut record = onramp(FiatRequestStatus::AwaitingProvider);
+        apply_checkout_status(&mut record, ProviderExecutionStatus::Completed);
+        assert_eq!(record.status, FiatRequestStatus::SettlementPending);
+
+        apply_checkout_status(&mut record, ProviderExecutionStatus::Pending);
+        assert_eq!(record.status, FiatRequestStatus::SettlementPending);
+
+        let mut done = onramp(FiatRequestStatus::Completed);
+        apply_checkout_status(&mut done, ProviderExecutionStatus::Failed);
+        assert_eq!(done.status, FiatRequestStatus::Completed);
+    }
+
+    #[test]
+    fn chargeback_before_payment_fails_the_request() {
+        let mut record = onramp(FiatRequestStatus::AwaitingProvider);
+        assert!(record_chargeback(&mut record, "dp_1", None));
+        assert_eq!(record.status, FiatRequestStatus::Failed);
+        let chargeback = record.chargeback.as_ref().unwrap();
+        assert_eq!(chargeback.clawback_status, ClawbackStatus::NotRequired);
+        assert!(chargeback.resolved_at.is_some());
+
+        assert!(!record_chargeback(&mut record, "dp_1", None));
+    }
+
+    #[test]
+    fn chargeback_after_settlement_schedules_clawback() {
+        let mut record = onramp(FiatRequestStatus::Completed);
+        record.updated_at = Utc::now() - TimeDelta::seconds(5);
+        assert!(record_chargeback(
+            &mut record,
+            "dp_1",
+            Some("fraudulent".into())
+        ));
+        assert_eq!(record.status, FiatRequestStatus::Completed);
+        assert!(clawback_due(&record, Utc::now()));
+
+        let chargeback = record.chargeback.as_mut().unwrap();
+        chargeback.clawback_attempts = 1;
+        record.updated_at = Utc::now();
+        assert!(!clawback_due(&record, Utc::now()));
+        assert!(clawback_due(
+            &record,
+            Utc::now() + TimeDelta::seconds(CLAWBACK_RETRY_SECS)
+        ));
+
+        let mut settling = onramp(FiatRequestStatus::SettlementPending);
+        record_chargeback(&mut settling, "dp_2", None);
+        assert_eq!(
+            settling.chargeback.unwrap().clawback_status,
+            ClawbackStatus::Pending
+        );
+        assert!(!clawback_due(
+            &onramp(FiatRequestStatus::SettlementPending),
+            Utc::now()
+        ));
+    }
+}
@@ -0,0 +1,299 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Destination wallet KYC for large on-ramps.
+//!
+//! On-ramps of at least `FIAT_ONRAMP_KYC_THRESHOLD_EUR` (default 1000.00)
+//! only settle into a wallet whose owner has reached KYC tier
+//! `FIAT_ONRAMP_KYC_TIER` (default 2). The tier is checked when the on-ramp
+//! is created and recorded on the request. If it is too low, the payment
+//! still goes ahead, but settlement stops in `kyc_required` until the owner
+//! completes verification and asks for a re-check. Each outcome is written
+//! to the request's transition log.
+
+use std::env;
+
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use chrono::Utc;
+
+use crate::{
+    api::fiat::{parse_amount_to_minor, to_response, FiatRequestResponse},
+    auth::Auth,
+    error::{ApiError, StorageContext},
+    providers::clerk::ClerkClient,
+    state::AppState,
+    storage::{
+        DestinationKycCheck, EncryptedStorage, FiatActor, FiatDirection, FiatRequestRepository,
+        FiatRequestStatus, OwnershipEnforcer, StoredFiatRequest, WalletRepository,
+    },
+};
+
+const FIAT_ONRAMP_KYC_THRESHOLD_EUR_ENV: &str = "FIAT_ONRAMP_KYC_THRESHOLD_EUR";
+const DEFAULT_ONRAMP_KYC_THRESHOLD_EUR: &str = "1000.00";
+const FIAT_ONRAMP_KYC_TIER_ENV: &str = "FIAT_ONRAMP_KYC_TIER";
+const DEFAULT_ONRAMP_KYC_TIER: u8 = 2;
+
+fn kyc_threshold_minor() -> u64 {
+    env::var(FIAT_ONRAMP_KYC_THRESHOLD_EUR_ENV)
+        .ok()
+        .and_then(|v| parse_amount_to_minor(&v).ok())
+        .or_else(|| parse_amount_to_minor(DEFAULT_ONRAMP_KYC_THRESHOLD_EUR).ok())
+        .map(|(_, minor)| minor)
+        .unwrap_or(0)
+}
+
+fn required_tier() -> u8 {
+    env::var(FIAT_ONRAMP_KYC_TIER_ENV)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_ONRAMP_KYC_TIER)
+}
+
+/// Look up the destination wallet's owner and their KYC tier. Without a
+/// Clerk client nobody is verified.
+async fn check_destination(
+    clerk: Option<&ClerkClient>,
+    storage: &EncryptedStorage,
+    wallet_id: &str,
+) -> Result<DestinationKycCheck, ApiError> {
+    let wallet = WalletRepository::new(storage)
+        .get(wallet_id)
+        .map_err(|_| ApiError::not_found("Wallet not found").with_code("wallet_not_found"))?;
+    let tier = match clerk {
+        Some(clerk) => clerk
+            .get_kyc_tier(&wallet.owner_user_id)
+            .await
+            .map_err(|e| ApiError::service_unavailable(format!("Failed to fetch KYC tier: {e}")))?,
+        None => 0,
+    };
+    let required_tier = required_tier();
+    Ok(DestinationKycCheck {
+        wallet_owner_user_id: wallet.owner_user_id,
+        tier,
+        required_tier,
+        passed: tier >= required_tier,
+        checked_at: Utc::now(),
+    })
+}
+
+/// Record the destination KYC check on a new on-ramp when the amount
+/// reaches the check threshold.
+pub(crate) async fn check_onramp_destination(
+    clerk: Option<&ClerkClient>,
+    storage: &EncryptedStorage,
+    record: &mut StoredFiatRequest,
+    amount_minor: u64,
+) -> Result<(), ApiError> {
+    if amount_minor < kyc_threshold_minor() {
+        return Ok(());
+    }
+    record.destination_kyc = Some(check_destination(clerk, storage, &record.wallet_id).await?);
+    Ok(())
+}
+
+/// Whether the on-ramp's destination failed its KYC check.
+pub(crate) fn blocks_settlement(record: &StoredFiatRequest) -> bool {
+    record.destination_kyc.as_ref().is_some_and(|c| !c.passed)
+}
+
+/// Stop a paid on-ramp before settlement if its destination failed the KYC
+/// check. Returns `true` if the request is (now) held.
+pub(crate) fn hold_for_kyc(record: &mut StoredFiatRequest) -> bool {
+    if record.status == FiatRequestStatus::KycRequired {
+        return true;
+    }
+    if record.status != FiatRequestStatus::SettlementPending
+        || record.reserve_transfer_tx_hash.is_some()
+        || !blocks_settlement(record)
+    {
+        return false;
+    }
+    record.transition(FiatRequestStatus::KycRequired, FiatActor::Poller)
+}
+
+/// Re-check the destination wallet owner's KYC tier.
+///
+/// Call after completing verification. If the owner now has the required
+/// tier, a held on-ramp moves back to `settlement_pending` and is settled
+/// by the next poller sweep.
+#[utoipa::path(
+    post,
+    path = "/v1/fiat/requests/{request_id}/kyc-check",
+    tag = "Fiat",
+    params(
+        ("request_id" = String, Path, description = "Fiat request ID")
+    ),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "KYC re-checked", body = FiatRequestResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+        (status = 404, description = "Not found"),
+        (status = 409, description = "Request does not need a KYC check"),
+        (status = 503, description = "Identity provider unavailable")
+    )
+)]
+pub async fn recheck_destination_kyc(
+    Auth(user): Auth,
+    State(state): State<AppState>,
+    Path(request_id): Path<String>,
+) -> Result<Json<FiatRequestResponse>, ApiError> {
+    let storage = state.storage();
+    let repo = FiatRequestRepository::new(storage);
+    let mut record = repo.get(&request_id).map_err(|_| {
+        ApiError::not_found("Fiat request not found").with_code("fiat_request_not_found")
+    })?;
+    if !record.is_owned_by(&user) {
+        return Err(ApiError::forbidden(
+            "You do not have permission to access this fiat request",
+        ));
+    }
+    let waiting = matches!(
+        record.status,
+        FiatRequestStatus::Queued
+            | FiatRequestStatus::AwaitingProvider
+            | FiatRequestStatus::KycRequired
+    );
+    if record.direction != FiatDirection::OnRamp || !waiting || !blocks_settlement(&record) {
+        return Err(ApiError::conflict("Fiat request does not need a KYC check"));
+    }
+
+    let check = check_destination(state.clerk_client.as_ref(), storage, &record.wallet_id).await?;
+    let passed = check.passed;
+    record.destination_kyc = Some(check);
+    if passed && record.status == FiatRequestStatus::KycRequired {
+        record.transition(FiatRequestStatus::SettlementPending, FiatActor::User);
+    }
+    record.updated_at = Utc::now();
+    repo.update(&mut record)
+        .context("Failed to store KYC check")?;
+
+    Ok(Json(to_response(&record)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        auth::{AuthenticatedUser, Role},
+        storage::{WalletMetadata, WalletStatus},
+    };
+    use axum::http::StatusCode;
+
+    fn user(user_id: &str) -> Auth {
+        Auth(AuthenticatedUser {
+            user_id: user_id.to_string(),
+            role: Role::Client,
+            session_id: None,
+            issuer: "https://test.clerk.dev".into(),
+            expires_at: Utc::now().timestamp() + 3600,
+            tenant_id: None,
+        })
+    }
+
+    fn onramp(status: FiatRequestStatus, passed: Option<bool>) -> StoredFiatRequest {
+        let mut record = StoredFiatRequest::new_queued(
+            "fr-1".to_string(),
+            "w-1".to_string(),
+            "user-1".to_string(),
+            FiatDirection::OnRamp,
+            "2500.00".to_string(),
+            "truelayer_sandbox".to_string(),
+            None,
+        );
+        record.status = status;
+        record.destination_kyc = passed.map(|passed| DestinationKycCheck {
+            wallet_owner_user_id: "user-1".to_string(),
+            tier: if passed { 2 } else { 1 },
+            required_tier: 2,
+            passed,
+            checked_at: Utc::now(),
+        });
+        record
+    }
+
+    #[test]
+    fn only_failed_checks_hold_settlement() {
+        let mut unchecked = onramp(FiatRequestStatus::SettlementPending, None);
+        assert!(!hold_for_kyc(&mut unchecked));
+        let mut passed = onramp(FiatRequestStatus::SettlementPending, Some(true));
+        assert!(!hold_for_kyc(&mut passed));
+        assert_eq!(passed.status, FiatRequestStatus::SettlementPending);
+
+        let mut failed = onramp(FiatRequestStatus::SettlementPending, Some(false));
+        assert!(hold_for_kyc(&mut failed));
+        assert_eq!(failed.status, FiatRequestStatus::KycRequired);
+        failed.note_transition();
+        assert_eq!(
+            failed.transitions.last().unwrap().detail.as_deref(),
+            Some("Destination KYC not passed: tier 1 of 2 required")
+        );
+
+        // Not paid yet: nothing to hold.
+        let mut unpaid = onramp(FiatRequestStatus::AwaitingProvider, Some(false));
+        assert!(!hold_for_kyc(&mut unpaid));
+        assert_eq!(unpaid.status, FiatRequestStatus::AwaitingProvider);
+    }
+
+    #[tokio::test]
+    async fn recheck_keeps_unverified_owner_on_hold() {
+        let state = AppState::default();
+        WalletRepository::new(state.storage())
+            .create(
+                &WalletMetadata {
+                    wallet_id: "w-1".to_string(),
+                    owner_user_id: "user-1".to_string(),
+                    public_address: "0x0000000000000000000000000000000000000001".to_string(),
+                    created_at: Utc::now(),
+                    status: WalletStatus::Active,
+                    label: None,
+                    email_lookup_key: None,
+                    email_sha256: None,
+                    account_type: Default::default(),
+                    smart_account: None,
+                    lock: None,
+                    deleted_at: None,
+                    purged_at: None,
+                    funded_at: None,
+                    tenant_id: None,
+                },
+                b"test_key",
+            )
+            .unwrap();
+        let repo = FiatRequestRepository::new(state.storage());
+        repo.create(&onramp(FiatRequestStatus::KycRequired, Some(false)))
+            .unwrap();
+
+        let err = recheck_destination_kyc(
+            user("user-2"),
+            State(state.clone()),
+            Path("fr-1".to_string()),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::FORBIDDEN);
+
+        // Without an identity provider the owner has no tier.
+        let Json(response) = recheck_destination_kyc(
+            user("user-1"),
+            State(state.clone()),
+            Path("fr-1".to_string()),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.status, FiatRequestStatus::KycRequired);
+        assert_eq!(response.destination_kyc.unwrap().tier, 0);
+
+        let mut passed = onramp(FiatRequestStatus::SettlementPending, Some(true));
+        passed.request_id = "fr-2".to_string();
+        repo.create(&passed).unwrap();
+        let err = recheck_destination_kyc(user("user-1"), State(state), Path("fr-2".to_string()))
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, StatusCode::CONFLICT);
+    }
+}
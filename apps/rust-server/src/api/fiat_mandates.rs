@@ -0,0 +1,721 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Linked bank accounts for repeat on-ramps.
+//!
+//! A user creates a variable-recurring-payment mandate once and authorizes
+//! it on the provider's hosted page. Later on-ramps are then paid from the
+//! mandate directly, within the per-payment and per-month limits chosen at
+//! creation. Those limits are also sent to the provider as mandate
+//! constraints, so the bank rejects anything that slips past the local check
+//! (e.g. two concurrent on-ramps near the monthly cap).
+
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use chrono::{DateTime, Datelike, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+use utoipa::ToSchema;
+
+use crate::{
+    api::{
+        fiat::{
+            format_minor_eur, map_onramp_provider_status, map_provider_error, new_request_record,
+            parse_amount_to_minor, persist_new_request, resolve_provider_id,
+            tenant_provider_credentials, to_response, FiatRequestResponse,
+        },
+        fiat_destination_kyc,
+        fiat_return::{self, FiatReturnClient},
+        soft_quotas::{self, next_month_start, QuotaUsage},
+        usage::{check_quota, QuotaKind, StorageQuotas},
+    },
+    audit_log,
+    auth::Auth,
+    error::{ApiError, StorageContext},
+    providers::{
+        clerk::ClerkClient,
+        truelayer::{
+            CreateMandatePaymentRequest, CreateMandateRequest, ProviderExecutionStatus,
+            ProviderMandateStatus, TrueLayerClient,
+        },
+    },
+    state::AppState,
+    storage::{
+        AuditEventType, EncryptedStorage, FiatDirection, FiatMandateRepository, FiatMandateStatus,
+        FiatRequestRepository, FiatRequestStatus, OwnershipEnforcer, StoredFiatMandate,
+        StoredFiatRequest, WalletRepository, WalletStatus,
+    },
+};
+
+/// Request body for linking a bank account.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct CreateFiatMandateRequest {
+    /// Wallet credited by on-ramps funded through the mandate.
+    pub wallet_id: String,
+    /// Largest single on-ramp in EUR (e.g. "250.00").
+    pub max_single_payment_eur: String,
+    /// Largest on-ramp total per calendar month (UTC) in EUR.
+    pub max_monthly_eur: String,
+    /// Client to return to after authorizing (`web` default).
+    #[serde(default)]
+    pub return_client: Option<FiatReturnClient>,
+    /// Explicit, allowlisted return URI.
+    #[serde(default)]
+    pub return_uri: Option<String>,
+}
+
+/// Request body for an on-ramp paid from a mandate.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct MandateOnRampRequest {
+    /// Amount in EUR decimal string (e.g. "25.50").
+    pub amount_eur: String,
+    /// Optional free-form note.
+    #[serde(default)]
+    pub note: Option<String>,
+}
+
+/// Mandate returned to clients.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct FiatMandateResponse {
+    pub mandate_id: String,
+    pub wallet_id: String,
+    pub provider: String,
+    pub status: FiatMandateStatus,
+    /// Hosted page where the user authorizes the mandate.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provider_action_url: Option<String>,
+    pub max_single_payment_eur: String,
+    pub max_monthly_eur: String,
+    /// On-ramps charged to the mandate this calendar month, excluding
+    /// failed ones.
+    pub used_this_month_eur: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub failure_reason: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub revoked_at: Option<String>,
+}
+
+/// List response for mandates.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct FiatMandateListResponse {
+    pub mandates: Vec<FiatMandateResponse>,
+    pub total: usize,
+}
+
+fn to_mandate_response(mandate: &StoredFiatMandate, used_minor: u64) -> FiatMandateResponse {
+    FiatMandateResponse {
+        mandate_id: mandate.mandate_id.clone(),
+        wallet_id: mandate.wallet_id.clone(),
+        provider: mandate.provider.clone(),
+        status: mandate.status,
+        provider_action_url: mandate.provider_action_url.clone(),
+        max_single_payment_eur: format_minor_eur(mandate.max_single_payment_minor),
+        max_monthly_eur: format_minor_eur(mandate.max_monthly_minor),
+        used_this_month_eur: format_minor_eur(used_minor),
+        failure_reason: mandate.failure_reason.clone(),
+        created_at: mandate.created_at.to_rfc3339(),
+        updated_at: mandate.updated_at.to_rfc3339(),
+        revoked_at: mandate.revoked_at.map(|ts| ts.to_rfc3339()),
+    }
+}
+
+fn map_mandate_status(status: ProviderMandateStatus) -> FiatMandateStatus {
+    match status {
+        ProviderMandateStatus::AuthorizationRequired => FiatMandateStatus::AuthorizationRequired,
+        ProviderMandateStatus::Authorized => FiatMandateStatus::Authorized,
+        ProviderMandateStatus::Revoked => FiatMandateStatus::Revoked,
+        ProviderMandateStatus::Failed => FiatMandateStatus::Failed,
+    }
+}
+
+/// Euro cents charged to `mandate_id` in the calendar month of `now`.
+fn mandate_usage_minor(
+    requests: &[StoredFiatRequest],
+    mandate_id: &str,
+    now: DateTime<Utc>,
+) -> u64 {
+    requests
+        .iter()
+        .filter(|r| r.mandate_id.as_deref() == Some(mandate_id))
+        .filter(|r| r.status != FiatRequestStatus::Failed)
+        .filter(|r| r.created_at.year() == now.year() && r.created_at.month() == now.month())
+        .filter_map(|r| parse_amount_to_minor(&r.amount_eur).ok())
+        .fold(0u64, |acc, (_, minor)| acc.saturating_add(minor))
+}
+
+fn check_mandate_limits(
+    mandate: &StoredFiatMandate,
+    used_minor: u64,
+    amount_minor: u64,
+) -> Result<(), ApiError> {
+    if amount_minor > mandate.max_single_payment_minor {
+        return Err(ApiError::unprocessable(format!(
+            "Amount exceeds the mandate's single-payment limit of {} EUR",
+            format_minor_eur(mandate.max_single_payment_minor)
+        )));
+    }
+    if used_minor.saturating_add(amount_minor) > mandate.max_monthly_minor {
+        return Err(ApiError::unprocessable(format!(
+            "Amount exceeds the mandate's monthly limit: {} of {} EUR used",
+            format_minor_eur(used_minor),
+            format_minor_eur(mandate.max_monthly_minor)
+        )));
+    }
+    Ok(())
+}
+
+fn parse_limit(value: &str, field: &str) -> Result<u64, ApiError> {
+    parse_amount_to_minor(value)
+        .map(|(_, minor)| minor)
+        .map_err(|_| ApiError::bad_request(format!("{field} must be a positive EUR amount")))
+}
+
+fn usage_for_owner(
+    state: &AppState,
+    owner_user_id: &str,
+) -> Result<Vec<StoredFiatRequest>, ApiError> {
+    FiatRequestRepository::new(state.storage())
+        .list_filtered_for_owner(owner_user_id, None, None, None)
+        .map_err(|e| ApiError::internal(format!("Failed to list fiat requests: {e}")))
+}
+
+fn load_owned_mandate(
+    state: &AppState,
+    user_id: &str,
+    mandate_id: &str,
+) -> Result<StoredFiatMandate, ApiError> {
+    let mandate = FiatMandateRepository::new(state.storage())
+        .get(mandate_id)
+        .map_err(|_| ApiError::not_found("Mandate not found"))?;
+    if mandate.owner_user_id != user_id {
+        return Err(ApiError::forbidden(
+            "You do not have permission to access this mandate",
+        ));
+    }
+    Ok(mandate)
+}
+
+/// Link a bank account for repeat on-ramps.
+#[utoipa::path(
+    post,
+    path = "/v1/fiat/mandates",
+    tag = "Fiat",
+    request_body = CreateFiatMandateRequest,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 201, description = "Mandate created; user must authorize it at provider_action_url", body = FiatMandateResponse),
+        (status = 400, description = "Bad request"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+        (status = 404, description = "Wallet not found"),
+        (status = 422, description = "Storage quota exceeded"),
+        (status = 503, description = "Provider unavailable")
+    )
+)]
+pub async fn create_fiat_mandate(
+    Auth(user): Auth,
+    State(state): State<AppState>,
+    Json(request): Json<CreateFiatMandateRequest>,
+) -> Result<(StatusCode, Json<FiatMandateResponse>), ApiError> {
+    let max_single_payment_minor =
+        parse_limit(&request.max_single_payment_eur, "max_single_payment_eur")?;
+    let max_monthly_minor = parse_limit(&request.max_monthly_eur, "max_monthly_eur")?;
+    if max_single_payment_minor > max_monthly_minor {
+        return Err(ApiError::bad_request(
+            "max_single_payment_eur cannot exceed max_monthly_eur",
+        ));
+    }
+
+    let storage = state.storage();
+    let wallet = WalletRepository::new(storage)
+        .get(&request.wallet_id)
+        .map_err(|_| ApiError::not_found("Wallet not found").with_code("wallet_not_found"))?;
+    if !wallet.is_owned_by(&user) {
+        return Err(ApiError::forbidden("You do not own this wallet").with_code("wallet_not_owned"));
+    }
+    if wallet.status != WalletStatus::Active {
+        return Err(ApiError::forbidden(
+            "Wallet must be active for fiat requests",
+        ));
+    }
+
+    // Checked before the provider creates the mandate; records are small
+    // and fixed-size, so only an existing overrun can fail the size check.
+    check_quota(
+        storage,
+        &user.user_id,
+        QuotaKind::FiatMandates,
+        0,
+        &StorageQuotas::from_env(),
+    )?;
+
+    let provider = resolve_provider_id(None)?;
+    let return_uri = fiat_return::resolve_return_uri(
+        request.return_client.unwrap_or_default(),
+        request.return_uri.as_deref(),
+    )?;
+
+    let mandate_id = uuid::Uuid::new_v4().to_string();
+    let credentials = tenant_provider_credentials(storage, wallet.tenant_id.as_deref());
+    let client =
+        TrueLayerClient::for_tenant(credentials.truelayer.as_ref()).map_err(map_provider_error)?;
+    let created = client
+        .create_mandate(CreateMandateRequest {
+            mandate_id: &mandate_id,
+            wallet_id: &request.wallet_id,
+            user_id: &user.user_id,
+            max_single_payment_minor,
+            max_monthly_minor,
+            return_uri: return_uri.as_str(),
+        })
+        .await
+        .map_err(map_provider_error)?;
+
+    let now = Utc::now();
+    let mandate = StoredFiatMandate {
+        mandate_id,
+        owner_user_id: user.user_id.clone(),
+        wallet_id: request.wallet_id,
+        provider,
+        provider_mandate_id: Some(created.provider_mandate_id),
+        provider_action_url: created.provider_action_url,
+        status: map_mandate_status(created.status),
+        max_single_payment_minor,
+        max_monthly_minor,
+        failure_reason: None,
+        created_at: now,
+        updated_at: now,
+        revoked_at: None,
+    };
+    FiatMandateRepository::new(storage)
+        .create(&mandate)
+        .map_err(|e| ApiError::internal(format!("Failed to store mandate: {e}")))?;
+
+    audit_log!(
+        storage,
+        AuditEventType::FiatMandateCreated,
+        &user,
+        "fiat_mandate",
+        &mandate.mandate_id
+    );
+
+    Ok((StatusCode::CREATED, Json(to_mandate_response(&mandate, 0))))
+}
+
+/// List the current user's mandates.
+#[utoipa::path(
+    get,
+    path = "/v1/fiat/mandates",
+    tag = "Fiat",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Mandates listed", body = FiatMandateListResponse),
+        (status = 401, description = "Unauthorized")
+    )
+)]
+pub async fn list_fiat_mandates(
+    Auth(user): Auth,
+    State(state): State<AppState>,
+) -> Result<Json<FiatMandateListResponse>, ApiError> {
+    let mandates = FiatMandateRepository::new(state.storage())
+        .list_by_owner(&user.user_id)
+        .map_err(|e| ApiError::internal(format!("Failed to list mandates: {e}")))?;
+    let requests = usage_for_owner(&state, &user.user_id)?;
+    let now = Utc::now();
+
+    let mandates: Vec<_> = mandates
+        .iter()
+        .map(|m| to_mandate_response(m, mandate_usage_minor(&requests, &m.mandate_id, now)))
+        .collect();
+    Ok(Json(FiatMandateListResponse {
+        total: mandates.len(),
+        mandates,
+    }))
+}
+
+/// Get a mandate. Pending authorizations are refreshed from the provider.
+#[utoipa::path(
+    get,
+    path = "/v1/fiat/mandates/{mandate_id}",
+    tag = "Fiat",
+    params(("mandate_id" = String, Path, description = "Mandate ID")),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Mandate details", body = FiatMandateResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+        (status = 404, description = "Not found")
+    )
+)]
+pub async fn get_fiat_mandate(
+    Auth(user): Auth,
+    State(state): State<AppState>,
+    Path(mandate_id): Path<String>,
+) -> Result<Json<FiatMandateResponse>, ApiError> {
+    let mut mandate = load_owned_mandate(&state, &user.user_id, &mandate_id)?;
+
+    if mandate.status == FiatMandateStatus::AuthorizationRequired {
+        let credentials = tenant_provider_credentials(state.storage(), user.tenant_id.as_deref());
+        if let (Some(provider_id), true) = (
+            mandate.provider_mandate_id.clone(),
+            TrueLayerClient::is_configured_for(credentials.truelayer.as_ref()),
+        ) {
+            let refreshed = match TrueLayerClient::for_tenant(credentials.truelayer.as_ref()) {
+                Ok(client) => client.fetch_mandate_status(&provider_id).await,
+                Err(e) => Err(e),
+            };
+            match refreshed {
+                Ok(status) if map_mandate_status(status) != mandate.status => {
+                    mandate.status = map_mandate_status(status);
+                    mandate.updated_at = Utc::now();
+                    if mandate.status == FiatMandateStatus::Failed {
+                        mandate.failure_reason = Some("Mandate authorization failed".to_string());
+                    }
+                    FiatMandateRepository::new(state.storage())
+                        .update(&mandate)
+                        .context("Failed to store mandate")?;
+                }
+                Ok(_) => {}
+                Err(e) => warn!(
+                    mandate_id = %mandate.mandate_id,
+                    error = %e,
+                    "Failed to refresh mandate status; serving cached status"
+                ),
+            }
+        }
+    }
+
+    let requests = usage_for_owner(&state, &user.user_id)?;
+    let used = mandate_usage_minor(&requests, &mandate.mandate_id, Utc::now());
+    Ok(Json(to_mandate_response(&mandate, used)))
+}
+
+/// Revoke a mandate at the provider and locally.
+#[utoipa::path(
+    post,
+    path = "/v1/fiat/mandates/{mandate_id}/revoke",
+    tag = "Fiat",
+    params(("mandate_id" = String, Path, description = "Mandate ID")),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Mandate revoked", body = FiatMandateResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+        (status = 404, description = "Not found"),
+        (status = 503, description = "Provider unavailable")
+    )
+)]
+pub async fn revoke_fiat_mandate(
+    Auth(user): Auth,
+    State(state): State<AppState>,
+    Path(mandate_id): Path<String>,
+) -> Result<Json<FiatMandateResponse>, ApiError> {
+    let mut mandate = load_owned_mandate(&state, &user.user_id, &mandate_id)?;
+    let requests = usage_for_owner(&state, &user.user_id)?;
+    let used = mandate_usage_minor(&requests, &mandate.mandate_id, Utc::now());
+    if mandate.status == FiatMandateStatus::Revoked {
+        return Ok(Json(to_mandate_response(&mandate, used)));
+    }
+
+    if let Some(provider_id) = mandate.provider_mandate_id.as_deref() {
+        let credentials = tenant_provider_credentials(state.storage(), user.tenant_id.as_deref());
+        let client = TrueLayerClient::for_tenant(credentials.truelayer.as_ref())
+            .map_err(map_provider_error)?;
+        client
+            .revoke_mandate(provider_id)
+            .await
+            .map_err(map_provider_error)?;
+    }
+
+    let now = Utc::now();
+    mandate.status = FiatMandateStatus::Revoked;
+    mandate.revoked_at = Some(now);
+    mandate.updated_at = now;
+    FiatMandateRepository::new(state.storage())
+        .update(&mandate)
+        .context("Failed to store mandate")?;
+
+    audit_log!(
+        state.storage(),
+        AuditEventType::FiatMandateRevoked,
+        &user,
+        "fiat_mandate",
+        &mandate.mandate_id
+    );
+
+    Ok(Json(to_mandate_response(&mandate, used)))
+}
+
+/// Create an on-ramp paid from an authorized mandate, without the hosted
+/// payment flow.
+#[utoipa::path(
+    post,
+    path = "/v1/fiat/mandates/{mandate_id}/onramp",
+    tag = "Fiat",
+    params(("mandate_id" = String, Path, description = "Mandate ID")),
+    request_body = MandateOnRampRequest,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 201, description = "Fiat on-ramp request created", body = FiatRequestResponse),
+        (status = 400, description = "Bad request"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+        (status = 404, description = "Not found"),
+        (status = 409, description = "Mandate is not authorized"),
+        (status = 422, description = "Mandate limit exceeded"),
+        (status = 503, description = "Provider unavailable")
+    )
+)]
+pub async fn create_mandate_onramp(
+    Auth(user): Auth,
+    State(state): State<AppState>,
+    Path(mandate_id): Path<String>,
+    Json(request): Json<MandateOnRampRequest>,
+) -> Result<(StatusCode, Json<FiatRequestResponse>), ApiError> {
+    let mandate = load_owned_mandate(&state, &user.user_id, &mandate_id)?;
+    let record = execute_mandate_onramp(
+        state.storage(),
+        state.clerk_client.as_ref(),
+        &mandate,
+        &request.amount_eur,
+        request.note,
+    )
+    .await?;
+    Ok((StatusCode::CREATED, Json(to_response(&record))))
+}
+
+/// Create and store an on-ramp for the mandate's owner, paid from the
+/// mandate, after checking its status and limits.
+pub(crate) async fn execute_mandate_onramp(
+    storage: &Arc<EncryptedStorage>,
+    clerk: Option<&ClerkClient>,
+    mandate: &StoredFiatMandate,
+    amount_eur: &str,
+    note: Option<String>,
+) -> Result<StoredFiatRequest, ApiError> {
+    if mandate.status != FiatMandateStatus::Authorized {
+        return Err(ApiError::conflict("Mandate is not authorized"));
+    }
+    let provider_mandate_id = mandate
+        .provider_mandate_id
+        .clone()
+        .ok_or_else(|| ApiError::conflict("Mandate is not authorized"))?;
+
+    let (_, amount_minor) = parse_amount_to_minor(amount_eur)?;
+    let requests = FiatRequestRepository::new(storage)
+        .list_filtered_for_owner(&mandate.owner_user_id, None, None, None)
+        .map_err(|e| ApiError::internal(format!("Failed to list fiat requests: {e}")))?;
+    let now = Utc::now();
+    let used = mandate_usage_minor(&requests, &mandate.mandate_id, now);
+    check_mandate_limits(mandate, used, amount_minor)?;
+    let used = used.saturating_add(amount_minor);
+    soft_quotas::record(QuotaUsage {
+        policy: "mandate_monthly",
+        limit: mandate.max_monthly_minor,
+        used,
+        resets_at: Some(next_month_start(now)),
+        message: format!(
+            "{} of the mandate's {} EUR monthly limit used",
+            format_minor_eur(used),
+            format_minor_eur(mandate.max_monthly_minor)
+        ),
+    });
+
+    let (mut record, amount_in_minor_provider) = new_request_record(
+        storage,
+        &mandate.owner_user_id,
+        mandate.wallet_id.clone(),
+        FiatDirection::OnRamp,
+        amount_eur,
+        Some(mandate.provider.clone()),
+        note,
+    )?;
+    record.mandate_id = Some(mandate.mandate_id.clone());
+    fiat_destination_kyc::check_onramp_destination(
+        clerk,
+        storage,
+        &mut record,
+        amount_in_minor_provider,
+    )
+    .await?;
+
+    let credentials = tenant_provider_credentials(storage, record.tenant_id.as_deref());
+    let client =
+        TrueLayerClient::for_tenant(credentials.truelayer.as_ref()).map_err(map_provider_error)?;
+    let execution = client
+        .create_mandate_payment(CreateMandatePaymentRequest {
+            request_id: &record.request_id,
+            wallet_id: &record.wallet_id,
+            user_id: &record.owner_user_id,
+            provider_mandate_id: &provider_mandate_id,
+            amount_in_minor: amount_in_minor_provider,
+            amount_eur: &record.amount_eur,
+        })
+        .await
+        .map_err(map_provider_error)?;
+
+    record.provider_reference = Some(execution.provider_reference);
+    record.status = map_onramp_provider_status(execution.status);
+    record.updated_at = Utc::now();
+    if matches!(execution.status, ProviderExecutionStatus::Failed) {
+        record.failure_reason = Some("Provider rejected the mandate payment".to_string());
+    }
+
+    persist_new_request(storage, &mut record)?;
+    Ok(record)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::{AuthenticatedUser, Role};
+    use chrono::TimeDelta;
+
+    fn user(user_id: &str) -> Auth {
+        Auth(AuthenticatedUser {
+            user_id: user_id.to_string(),
+            role: Role::Client,
+            session_id: None,
+            issuer: "https://test.clerk.dev".into(),
+            expires_at: Utc::now().timestamp() + 3600,
+            tenant_id: None,
+        })
+    }
+
+    fn mandate(status: FiatMandateStatus) -> StoredFiatMandate {
+        let now = Utc::now();
+        StoredFiatMandate {
+            mandate_id: "m-1".to_string(),
+            owner_user_id: "user-1".to_string(),
+            wallet_id: "w-1".to_string(),
+            provider: "truelayer_sandbox".to_string(),
+            provider_mandate_id: None,
+            provider_action_url: None,
+            status,
+            max_single_payment_minor: 10_000,
+            max_monthly_minor: 25_000,
+            failure_reason: None,
+            created_at: now,
+            updated_at: now,
+            revoked_at: None,
+        }
+    }
+
+    fn mandate_onramp(amount: &str, status: FiatRequestStatus) -> StoredFiatRequest {
+        let mut record = StoredFiatRequest::new_queued(
+            uuid::Uuid::new_v4().to_string(),
+            "w-1".to_string(),
+            "user-1".to_string(),
+            FiatDirection::OnRamp,
+            amount.to_string(),
+            "truelayer_sandbox".to_string(),
+            None,
+        );
+        record.mandate_id = Some("m-1".to_string());
+        record.status = status;
+        record
+    }
+
+    #[test]
+    fn usage_counts_this_months_non_failed_mandate_payments() {
+        let now = Utc::now();
+        let mut last_month = mandate_onramp("40.00", FiatRequestStatus::Completed);
+        last_month.created_at = now - TimeDelta::days(40);
+        let mut other_mandate = mandate_onramp("5.00", FiatRequestStatus::Completed);
+        other_mandate.mandate_id = Some("m-2".to_string());
+        let requests = vec![
+            mandate_onramp("10.50", FiatRequestStatus::Completed),
+            mandate_onramp("20.00", FiatRequestStatus::SettlementPending),
+            mandate_onramp("99.00", FiatRequestStatus::Failed),
+            last_month,
+            other_mandate,
+        ];
+        assert_eq!(mandate_usage_minor(&requests, "m-1", now), 3_050);
+    }
+
+    #[test]
+    fn limits_cover_single_payment_and_monthly_total() {
+        let m = mandate(FiatMandateStatus::Authorized);
+        assert!(check_mandate_limits(&m, 0, 10_000).is_ok());
+        assert_eq!(
+            check_mandate_limits(&m, 0, 10_001).unwrap_err().status,
+            StatusCode::UNPROCESSABLE_ENTITY
+        );
+        assert!(check_mandate_limits(&m, 15_000, 10_000).is_ok());
+        assert!(check_mandate_limits(&m, 15_001, 10_000).is_err());
+    }
+
+    #[tokio::test]
+    async fn create_rejects_single_limit_above_monthly_limit() {
+        let state = AppState::default();
+        let err = create_fiat_mandate(
+            user("user-1"),
+            State(state),
+            Json(CreateFiatMandateRequest {
+                wallet_id: "w-1".to_string(),
+                max_single_payment_eur: "500".to_string(),
+                max_monthly_eur: "100".to_string(),
+                return_client: None,
+                return_uri: None,
+            }),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn revoke_is_owner_only_and_idempotent() {
+        let state = AppState::default();
+        FiatMandateRepository::new(state.storage())
+            .create(&mandate(FiatMandateStatus::Authorized))
+            .unwrap();
+
+        let err = revoke_fiat_mandate(user("user-2"), State(state.clone()), Path("m-1".into()))
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, StatusCode::FORBIDDEN);
+
+        let Json(revoked) =
+            revoke_fiat_mandate(user("user-1"), State(state.clone()), Path("m-1".into()))
+                .await
+                .unwrap();
+        assert_eq!(revoked.status, FiatMandateStatus::Revoked);
+        assert!(revoked.revoked_at.is_some());
+
+        let Json(again) =
+            revoke_fiat_mandate(user("user-1"), State(state.clone()), Path("m-1".into()))
+                .await
+                .unwrap();
+        assert_eq!(again.revoked_at, revoked.revoked_at);
+    }
+
+    #[tokio::test]
+    async fn onramp_requires_authorized_mandate() {
+        let state = AppState::default();
+        FiatMandateRepository::new(state.storage())
+            .create(&mandate(FiatMandateStatus::Revoked))
+            .unwrap();
+
+        let err = create_mandate_onramp(
+            user("user-1"),
+            State(state),
+            Path("m-1".into()),
+            Json(MandateOnRampRequest {
+                amount_eur: "10.00".to_string(),
+                note: None,
+            }),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::CONFLICT);
+    }
+}
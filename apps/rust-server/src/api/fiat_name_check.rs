@@ -0,0 +1,514 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Beneficiary name checks for large off-ramps.
+//!
+//! Paying out to a bank account in someone else's name is a common sign of
+//! a money mule. Off-ramps of at least `FIAT_NAME_CHECK_THRESHOLD_EUR`
+//! (default 1000.00) compare the beneficiary account holder name with the
+//! owner's KYC-verified name. A close match lets the off-ramp proceed.
+//! Otherwise, or when the user has no verified name, the request waits in
+//! `review_required` until an admin approves or rejects it. The comparison
+//! and the decision are kept on the request.
+
+use std::env;
+
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use unicode_normalization::{char::is_combining_mark, UnicodeNormalization};
+use utoipa::ToSchema;
+
+use crate::{
+    api::{
+        admin_activity::log_admin_read,
+        fiat::{parse_amount_to_minor, to_response, FiatRequestResponse},
+    },
+    auth::AdminOnly,
+    error::{ApiError, StorageContext},
+    state::AppState,
+    storage::{
+        AuditEvent, AuditEventType, AuditRepository, BeneficiaryNameCheck, EncryptedStorage,
+        FiatActor, FiatRequestRepository, FiatRequestStatus, NameReview, NameReviewDecision,
+        StoredFiatRequest,
+    },
+};
+
+const FIAT_NAME_CHECK_THRESHOLD_EUR_ENV: &str = "FIAT_NAME_CHECK_THRESHOLD_EUR";
+const DEFAULT_NAME_CHECK_THRESHOLD_EUR: &str = "1000.00";
+
+/// Similarity at or above which two names are considered the same person.
+pub const NAME_MATCH_THRESHOLD: f64 = 0.85;
+
+/// Request body for an admin name-review decision.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct NameReviewRequest {
+    pub decision: NameReviewDecision,
+    /// Why the admin decided this way.
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+/// Off-ramp waiting for a name review.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct NameReviewItem {
+    pub request: FiatRequestResponse,
+    pub owner_user_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub beneficiary_account_holder_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name_check: Option<BeneficiaryNameCheck>,
+}
+
+/// Off-ramps waiting for a name review, oldest first.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct NameReviewListResponse {
+    pub reviews: Vec<NameReviewItem>,
+    pub total: usize,
+}
+
+fn name_check_threshold_minor() -> u64 {
+    env::var(FIAT_NAME_CHECK_THRESHOLD_EUR_ENV)
+        .ok()
+        .and_then(|v| parse_amount_to_minor(&v).ok())
+        .or_else(|| parse_amount_to_minor(DEFAULT_NAME_CHECK_THRESHOLD_EUR).ok())
+        .map(|(_, minor)| minor)
+        .unwrap_or(0)
+}
+
+/// Lower-case words of a name with accents and punctuation removed, sorted
+/// and without repeats.
+fn name_tokens(name: &str) -> Vec<String> {
+    let folded: String = name
+        .nfkd()
+        .filter(|c| !is_combining_mark(*c))
+        .flat_map(char::to_lowercase)
+        .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+        .collect();
+    let mut tokens: Vec<String> = folded.split_whitespace().map(str::to_string).collect();
+    tokens.sort();
+    tokens.dedup();
+    tokens
+}
+
+/// Edit-distance similarity of two strings, from 0 to 1.
+fn ratio(a: &str, b: &str) -> f64 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let longest = a.len().max(b.len());
+    if longest == 0 {
+        return 0.0;
+    }
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.iter().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    1.0 - previous[b.len()] as f64 / longest as f64
+}
+
+/// Shared words followed by a name's remaining words.
+fn with_rest(common: &[&str], tokens: &[String]) -> String {
+    common
+        .iter()
+        .copied()
+        .chain(
+            tokens
+                .iter()
+                .map(String::as_str)
+                .filter(|t| !common.contains(t)),
+        )
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Similarity of two personal names, from 0 to 1.
+///
+/// Word order, case, accents and punctuation are ignored. A name whose
+/// words (at least two) all appear in the other scores 1, so a missing
+/// middle name still matches.
+pub(crate) fn name_similarity(a: &str, b: &str) -> f64 {
+    let a = name_tokens(a);
+    let b = name_tokens(b);
+    let common: Vec<&str> = a
+        .iter()
+        .filter(|t| b.contains(t))
+        .map(String::as_str)
+        .collect();
+    let shared = common.join(" ");
+    let full_a = with_rest(&common, &a);
+    let full_b = with_rest(&common, &b);
+
+    let mut score = ratio(&full_a, &full_b);
+    if common.len() >= 2 {
+        score = score
+            .max(ratio(&shared, &full_a))
+            .max(ratio(&shared, &full_b));
+    }
+    score
+}
+
+/// Compare an off-ramp's beneficiary with the owner's verified name when the
+/// amount reaches the check threshold, holding mismatches for review.
+pub(crate) async fn check_offramp_name(
+    state: &AppState,
+    record: &mut StoredFiatRequest,
+    amount_minor: u64,
+) -> Result<(), ApiError> {
+    if amount_minor < name_check_threshold_minor() {
+        return Ok(());
+    }
+    let verified_name = match &state.clerk_client {
+        Some(clerk) => clerk
+            .get_verified_name(&record.owner_user_id)
+            .await
+            .map_err(|e| {
+                ApiError::service_unavailable(format!("Failed to fetch verified name: {e}"))
+            })?,
+        None => None,
+    };
+    let beneficiary = record
+        .beneficiary_account_holder_name
+        .as_deref()
+        .unwrap_or_default();
+    let score = verified_name
+        .as_deref()
+        .map(|name| name_similarity(name, beneficiary))
+        .unwrap_or(0.0);
+    let matched = score >= NAME_MATCH_THRESHOLD;
+
+    record.name_check = Some(BeneficiaryNameCheck {
+        verified_name,
+        score,
+        matched,
+        checked_at: Utc::now(),
+        review: None,
+    });
+    if !matched {
+        record.transition(FiatRequestStatus::ReviewRequired, FiatActor::User);
+    }
+    Ok(())
+}
+
+/// Record that an off-ramp was held for name review.
+pub(crate) fn audit_review_required(storage: &EncryptedStorage, record: &StoredFiatRequest) {
+    let event = AuditEvent::new(AuditEventType::FiatNameReviewRequired)
+        .with_user(&record.owner_user_id)
+        .with_resource("fiat_request", &record.request_id)
+        .with_details(serde_json::json!({
+            "score": record.name_check.as_ref().map(|c| c.score),
+            "has_verified_name": record
+                .name_check
+                .as_ref()
+                .is_some_and(|c| c.verified_name.is_some()),
+        }));
+    let _ = AuditRepository::new(storage).log(&event);
+}
+
+/// List off-ramps waiting for a name review.
+#[utoipa::path(
+    get,
+    path = "/v1/admin/fiat/name-reviews",
+    tag = "Admin",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Off-ramps waiting for review", body = NameReviewListResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden")
+    )
+)]
+pub async fn list_name_reviews(
+    AdminOnly(admin): AdminOnly,
+    State(state): State<AppState>,
+) -> Result<Json<NameReviewListResponse>, ApiError> {
+    let mut pending: Vec<_> = FiatRequestRepository::new(state.storage())
+        .list_all()
+        .context("Failed to list fiat requests")?
+        .into_iter()
+        .filter(|r| {
+            r.status == FiatRequestStatus::ReviewRequired
+                && admin.sees_tenant(r.tenant_id.as_deref())
+        })
+        .collect();
+    pending.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+
+    let reviews: Vec<_> = pending
+        .iter()
+        .map(|record| NameReviewItem {
+            request: to_response(record),
+            owner_user_id: record.owner_user_id.clone(),
+            beneficiary_account_holder_name: record.beneficiary_account_holder_name.clone(),
+            name_check: record.name_check.clone(),
+        })
+        .collect();
+    log_admin_read(
+        state.storage(),
+        &admin,
+        "fiat_requests",
+        "*",
+        &[
+            "owner_user_id",
+            "beneficiary_account_holder_name",
+            "name_check",
+        ],
+        reviews.len(),
+    );
+    Ok(Json(NameReviewListResponse {
+        total: reviews.len(),
+        reviews,
+    }))
+}
+
+/// Approve or reject an off-ramp held for name review.
+///
+/// Approval lets the off-ramp wait for the user's deposit as usual;
+/// rejection fails it.
+#[utoipa::path(
+    post,
+    path = "/v1/admin/fiat/requests/{request_id}/name-review",
+    tag = "Admin",
+    params(
+        ("request_id" = String, Path, description = "Fiat request ID")
+    ),
+    request_body = NameReviewRequest,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Decision recorded", body = FiatRequestResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden"),
+        (status = 404, description = "Not found"),
+        (status = 409, description = "Request is not waiting for review")
+    )
+)]
+pub async fn decide_name_review(
+    AdminOnly(admin): AdminOnly,
+    State(state): State<AppState>,
+    Path(request_id): Path<String>,
+    Json(request): Json<NameReviewRequest>,
+) -> Result<Json<FiatRequestResponse>, ApiError> {
+    let storage = state.storage();
+    let repo = FiatRequestRepository::new(storage);
+    let mut record = repo
+        .get(&request_id)
+        .ok()
+        .filter(|r| admin.sees_tenant(r.tenant_id.as_deref()))
+        .ok_or_else(|| {
+            ApiError::not_found("Fiat request not found").with_code("fiat_request_not_found")
+        })?;
+    let next = match request.decision {
+        NameReviewDecision::Approved => FiatRequestStatus::AwaitingUserDeposit,
+        NameReviewDecision::Rejected => FiatRequestStatus::Failed,
+    };
+    if record.status != FiatRequestStatus::ReviewRequired
+        || !FiatRequestStatus::can_transition(record.status, next, FiatActor::Admin)
+    {
+        return Err(ApiError::conflict(
+            "Fiat request is not waiting for a name review",
+        ));
+    }
+
+    let now = Utc::now();
+    let reason = request
+        .reason
+        .map(|r| r.trim().to_string())
+        .filter(|r| !r.is_empty());
+    if let Some(check) = record.name_check.as_mut() {
+        check.review = Some(NameReview {
+            decision: request.decision,
+            reviewed_by: admin.user_id.clone(),
+            reason: reason.clone(),
+            decided_at: now,
+        });
+    }
+    record.transition(next, FiatActor::Admin);
+    if next == FiatRequestStatus::Failed {
+        record.failure_reason =
+            Some("Beneficiary name does not match the verified account holder".to_string());
+    }
+    record.updated_at = now;
+    repo.update(&mut record)
+        .context("Failed to store name review")?;
+
+    let event = AuditEvent::new(AuditEventType::FiatNameReviewDecided)
+        .with_user(&admin.user_id)
+        .with_resource("fiat_request", &request_id)
+        .with_details(serde_json::json!({
+            "decision": request.decision,
+            "reason": reason,
+            "owner_user_id": record.owner_user_id,
+        }));
+    let _ = AuditRepository::new(storage).log(&event);
+
+    Ok(Json(to_response(&record)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        auth::{AuthenticatedUser, Role},
+        storage::FiatDirection,
+    };
+    use axum::http::StatusCode;
+
+    fn admin() -> AdminOnly {
+        AdminOnly(AuthenticatedUser {
+            user_id: "admin-1".to_string(),
+            role: Role::Admin,
+            session_id: None,
+            issuer: "https://test.clerk.dev".into(),
+            expires_at: Utc::now().timestamp() + 3600,
+            tenant_id: None,
+        })
+    }
+
+    fn held_offramp(state: &AppState) -> StoredFiatRequest {
+        let mut record = StoredFiatRequest::new_queued(
+            "fr-1".to_string(),
+            "w-1".to_string(),
+            "user-1".to_string(),
+            FiatDirection::OffRamp,
+            "1500.00".to_string(),
+            "truelayer_sandbox".to_string(),
+            None,
+        );
+        record.beneficiary_account_holder_name = Some("Someone Else".to_string());
+        record.name_check = Some(BeneficiaryNameCheck {
+            verified_name: Some("Jane Doe".to_string()),
+            score: 0.2,
+            matched: false,
+            checked_at: Utc::now(),
+            review: None,
+        });
+        record.status = FiatRequestStatus::ReviewRequired;
+        FiatRequestRepository::new(state.storage())
+            .create(&record)
+            .unwrap();
+        record
+    }
+
+    #[test]
+    fn similar_names_match_and_different_people_do_not() {
+        for (a, b) in [
+            ("Jane Doe", "jane doe"),
+            ("Jane Doe", "DOE, Jane"),
+            ("José Müller", "Jose Muller"),
+            ("Jane Mary Doe", "Jane Doe"),
+            ("Jonathan Smith", "Jonathon Smith"),
+        ] {
+            let score = name_similarity(a, b);
+            assert!(score >= NAME_MATCH_THRESHOLD, "{a} / {b}: {score}");
+        }
+        for (a, b) in [
+            ("Jane Doe", "John Smith"),
+            ("Jane Doe", "Jane Smith"),
+            ("Jane Doe", "Doe"),
+            ("Jane Doe", ""),
+        ] {
+            let score = name_similarity(a, b);
+            assert!(score < NAME_MATCH_THRESHOLD, "{a} / {b}: {score}");
+        }
+    }
+
+    #[tokio::test]
+    async fn unverified_user_above_threshold_is_held_for_review() {
+        let state = AppState::default();
+        let mut record = StoredFiatRequest::new_queued(
+            "fr-2".to_string(),
+            "w-1".to_string(),
+            "user-1".to_string(),
+            FiatDirection::OffRamp,
+            "10.00".to_string(),
+            "truelayer_sandbox".to_string(),
+            None,
+        );
+        record.status = FiatRequestStatus::AwaitingUserDeposit;
+        record.beneficiary_account_holder_name = Some("Jane Doe".to_string());
+
+        check_offramp_name(&state, &mut record, 1_000)
+            .await
+            .unwrap();
+        assert!(record.name_check.is_none());
+        assert_eq!(record.status, FiatRequestStatus::AwaitingUserDeposit);
+
+        check_offramp_name(&state, &mut record, name_check_threshold_minor())
+            .await
+            .unwrap();
+        let check = record.name_check.as_ref().unwrap();
+        assert!(!check.matched);
+        assert!(check.verified_name.is_none());
+        assert_eq!(record.status, FiatRequestStatus::ReviewRequired);
+    }
+
+    #[tokio::test]
+    async fn approval_releases_and_rejection_fails_the_offramp() {
+        let state = AppState::default();
+        held_offramp(&state);
+
+        let Json(listed) = list_name_reviews(admin(), State(state.clone()))
+            .await
+            .unwrap();
+        assert_eq!(listed.total, 1);
+        assert_eq!(
+            listed.reviews[0].beneficiary_account_holder_name.as_deref(),
+            Some("Someone Else")
+        );
+
+        let Json(approved) = decide_name_review(
+            admin(),
+            State(state.clone()),
+            Path("fr-1".to_string()),
+            Json(NameReviewRequest {
+                decision: NameReviewDecision::Approved,
+                reason: Some("Joint account, documents checked".to_string()),
+            }),
+        )
+        .await
+        .unwrap();
+        assert_eq!(approved.status, FiatRequestStatus::AwaitingUserDeposit);
+        let stored = FiatRequestRepository::new(state.storage())
+            .get("fr-1")
+            .unwrap();
+        let review = stored.name_check.unwrap().review.unwrap();
+        assert_eq!(review.decision, NameReviewDecision::Approved);
+        assert_eq!(review.reviewed_by, "admin-1");
+
+        // A decided request cannot be decided again.
+        let err = decide_name_review(
+            admin(),
+            State(state.clone()),
+            Path("fr-1".to_string()),
+            Json(NameReviewRequest {
+                decision: NameReviewDecision::Rejected,
+                reason: None,
+            }),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::CONFLICT);
+
+        let state = AppState::default();
+        held_offramp(&state);
+        let Json(rejected) = decide_name_review(
+            admin(),
+            State(state),
+            Path("fr-1".to_string()),
+            Json(NameReviewRequest {
+                decision: NameReviewDecision::Rejected,
+                reason: None,
+            }),
+        )
+        .await
+        .unwrap();
+        assert_eq!(rejected.status, FiatRequestStatus::Failed);
+        assert!(rejected.failure_reason.is_some());
+    }
+}
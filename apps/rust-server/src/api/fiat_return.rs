@@ -0,0 +1,371 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Hosted-payment return URIs.
+//!
+//! After authorizing an on-ramp on the provider's hosted page, the user is
+//! sent back to a return URI. Each deployment configures one URI per client
+//! (the web app and the mobile deep link) plus an allowlist of further URIs
+//! callers may request explicitly. Every return URI carries a `state`
+//! parameter, HMAC-signed through the [secret signer](crate::secret_signer),
+//! that the frontend hands back
+//! to `GET /v1/fiat/return` to confirm the redirect belongs to a request it
+//! owns.
+//!
+//! Card payments return here after 3-D Secure authentication. Their status
+//! is refreshed from the provider on return, so the frontend sees the
+//! outcome without waiting for the webhook.
+
+use std::env;
+
+use axum::extract::{Query, State};
+use axum::Json;
+use base64ct::{Base64UrlUnpadded, Encoding};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use url::Url;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::{
+    api::fiat::{sync_and_persist_request, to_response, FiatRequestResponse},
+    auth::Auth,
+    canonical_json,
+    error::ApiError,
+    providers::fiat::CARD_PROVIDER_ID,
+    secret_signer::{SecretKey, SecretSigner},
+    state::AppState,
+    storage::{FiatRequestRepository, FiatRequestStatus, OwnershipEnforcer},
+};
+
+/// Return URI for the web app.
+const FIAT_RETURN_URI_WEB_ENV: &str = "FIAT_RETURN_URI_WEB";
+/// Deep link for the mobile app. Unset disables mobile returns.
+const FIAT_RETURN_URI_MOBILE_ENV: &str = "FIAT_RETURN_URI_MOBILE";
+/// Comma-separated URIs (or path prefixes) callers may request explicitly.
+const FIAT_RETURN_URI_ALLOWLIST_ENV: &str = "FIAT_RETURN_URI_ALLOWLIST";
+const DEFAULT_WEB_RETURN_URI: &str = "http://localhost:3000/callback";
+/// How long a signed state stays valid. Covers slow bank authorizations.
+const RETURN_STATE_TTL_SECS: i64 = 24 * 3600;
+
+/// Client the user returns to after the hosted payment page.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum FiatReturnClient {
+    /// Browser app (`FIAT_RETURN_URI_WEB`).
+    #[default]
+    Web,
+    /// Mobile app deep link (`FIAT_RETURN_URI_MOBILE`).
+    Mobile,
+}
+
+/// Query for verifying a return.
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct FiatReturnQuery {
+    /// `state` parameter from the return URI.
+    pub state: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ReturnState {
+    /// Fiat request ID.
+    rid: String,
+    /// Expiry (unix seconds).
+    exp: i64,
+}
+
+fn env_uri(name: &str) -> Option<String> {
+    env::var(name)
+        .ok()
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+fn client_default_uri(client: FiatReturnClient) -> Option<String> {
+    match client {
+        FiatReturnClient::Web => {
+            Some(env_uri(FIAT_RETURN_URI_WEB_ENV).unwrap_or(DEFAULT_WEB_RETURN_URI.to_string()))
+        }
+        FiatReturnClient::Mobile => env_uri(FIAT_RETURN_URI_MOBILE_ENV),
+    }
+}
+
+/// Allowed return URIs: the configured client defaults plus the allowlist.
+fn allowlist() -> Vec<String> {
+    let mut allowed: Vec<String> = [FiatReturnClient::Web, FiatReturnClient::Mobile]
+        .into_iter()
+        .filter_map(client_default_uri)
+        .collect();
+    if let Some(extra) = env_uri(FIAT_RETURN_URI_ALLOWLIST_ENV) {
+        allowed.extend(
+            extra
+                .split(',')
+                .map(str::trim)
+                .filter(|entry| !entry.is_empty())
+                .map(str::to_string),
+        );
+    }
+    allowed
+}
+
+/// Whether `candidate` is `entry` or lies under its path.
+///
+/// Scheme, host and port must match exactly; the path must equal the entry's
+/// or continue it at a `/` boundary, so `/callback` does not admit
+/// `/callback-evil`.
+fn uri_allowed_by(candidate: &Url, entry: &str) -> bool {
+    let Ok(entry) = Url::parse(entry) else {
+        return false;
+    };
+    if candidate.scheme() != entry.scheme()
+        || candidate.host_str() != entry.host_str()
+        || candidate.port_or_known_default() != entry.port_or_known_default()
+    {
+        return false;
+    }
+    let prefix = entry.path().trim_end_matches('/');
+    match candidate.path().strip_prefix(prefix) {
+        Some(rest) => rest.is_empty() || rest.starts_with('/'),
+        None => false,
+    }
+}
+
+/// Pick the return URI for a request: an explicitly requested one if it is
+/// allowlisted, otherwise the client's configured default.
+pub(crate) fn resolve_return_uri(
+    client: FiatReturnClient,
+    requested: Option<&str>,
+) -> Result<Url, ApiError> {
+    let raw = match requested.map(str::trim).filter(|v| !v.is_empty()) {
+        Some(uri) => uri.to_string(),
+        None => client_default_uri(client).ok_or_else(|| {
+            ApiError::bad_request(format!(
+                "No return URI is configured for {client:?} clients"
+            ))
+        })?,
+    };
+    let uri = Url::parse(&raw).map_err(|_| ApiError::bad_request("Invalid return_uri"))?;
+    if matches!(uri.scheme(), "javascript" | "data" | "file") {
+        return Err(ApiError::bad_request("Invalid return_uri scheme"));
+    }
+    if uri.query_pairs().any(|(key, _)| key == "state") {
+        return Err(ApiError::bad_request(
+            "return_uri must not carry a state parameter",
+        ));
+    }
+    if !allowlist().iter().any(|entry| uri_allowed_by(&uri, entry)) {
+        return Err(ApiError::bad_request("return_uri is not allowlisted"));
+    }
+    Ok(uri)
+}
+
+/// Sign a state for `request_id`: `base64url(json).base64url(hmac)`.
+async fn sign_state(signer: &SecretSigner, request_id: &str, now: i64) -> Result<String, ApiError> {
+    let claims = ReturnState {
+        rid: request_id.to_string(),
+        exp: now + RETURN_STATE_TTL_SECS,
+    };
+    let json = canonical_json::to_vec(&claims).expect("state claims serialize");
+    let payload = Base64UrlUnpadded::encode_string(&json);
+    let tag = signer
+        .mac(SecretKey::FiatReturnState, payload.as_bytes())
+        .await?;
+    Ok(format!(
+        "{payload}.{}",
+        Base64UrlUnpadded::encode_string(&tag)
+    ))
+}
+
+/// Check a state's signature and expiry and return its request ID.
+async fn verify_state(signer: &SecretSigner, state: &str, now: i64) -> Result<String, ApiError> {
+    let invalid = || ApiError::bad_request("Invalid return state");
+    let (payload, tag) = state.split_once('.').ok_or_else(invalid)?;
+    let tag = Base64UrlUnpadded::decode_vec(tag).map_err(|_| invalid())?;
+    if !signer
+        .verify_mac(SecretKey::FiatReturnState, payload.as_bytes(), &tag)
+        .await?
+    {
+        return Err(invalid());
+    }
+    let json = Base64UrlUnpadded::decode_vec(payload).map_err(|_| invalid())?;
+    let claims: ReturnState = serde_json::from_slice(&json).map_err(|_| invalid())?;
+    if claims.exp < now {
+        return Err(ApiError::bad_request("Return state expired"));
+    }
+    Ok(claims.rid)
+}
+
+/// Return URI for `request_id` with its signed `state` appended.
+pub(crate) async fn signed_return_uri(
+    signer: &SecretSigner,
+    mut uri: Url,
+    request_id: &str,
+) -> Result<String, ApiError> {
+    let state = sign_state(signer, request_id, Utc::now().timestamp()).await?;
+    uri.query_pairs_mut().append_pair("state", &state);
+    Ok(uri.to_string())
+}
+
+/// Verify a hosted-payment return and fetch the request it belongs to.
+#[utoipa::path(
+    get,
+    path = "/v1/fiat/return",
+    tag = "Fiat",
+    params(FiatReturnQuery),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "State verified; current request details", body = FiatRequestResponse),
+        (status = 400, description = "Invalid or expired state"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Request belongs to another user"),
+        (status = 404, description = "Request not found")
+    )
+)]
+pub async fn verify_fiat_return(
+    Auth(user): Auth,
+    State(state): State<AppState>,
+    Query(query): Query<FiatReturnQuery>,
+) -> Result<Json<FiatRequestResponse>, ApiError> {
+    let storage = state.storage();
+    let request_id =
+        verify_state(&state.secret_signer, &query.state, Utc::now().timestamp()).await?;
+
+    let record = FiatRequestRepository::new(storage)
+        .get(&request_id)
+        .map_err(|_| {
+            ApiError::not_found("Fiat request not found").with_code("fiat_request_not_found")
+        })?;
+    if !record.is_owned_by(&user) {
+        return Err(ApiError::forbidden(
+            "You do not have permission to access this fiat request",
+        ));
+    }
+
+    let awaiting_card_payment = record.provider == CARD_PROVIDER_ID
+        && matches!(
+            record.status,
+            FiatRequestStatus::Queued | FiatRequestStatus::AwaitingProvider
+        );
+    if let (true, Some(tx_db)) = (awaiting_card_payment, state.tx_db.as_ref()) {
+        if let Ok(refreshed) =
+            sync_and_persist_request(storage, tx_db, state.tx_cache.as_deref(), &request_id).await
+        {
+            return Ok(Json(to_response(&refreshed)));
+        }
+    }
+    Ok(Json(to_response(&record)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::{AuthenticatedUser, Role};
+    use crate::storage::{FiatDirection, StoredFiatRequest};
+
+    fn user(user_id: &str) -> Auth {
+        Auth(AuthenticatedUser {
+            user_id: user_id.to_string(),
+            role: Role::Client,
+            session_id: None,
+            issuer: "https://test.clerk.dev".into(),
+            expires_at: Utc::now().timestamp() + 3600,
+            tenant_id: None,
+        })
+    }
+
+    #[test]
+    fn allowlist_matches_on_path_boundaries() {
+        let entry = "https://wallet.example.com/fiat/return";
+        let check = |uri: &str| uri_allowed_by(&Url::parse(uri).unwrap(), entry);
+        assert!(check("https://wallet.example.com/fiat/return"));
+        assert!(check("https://wallet.example.com/fiat/return/onramp?x=1"));
+        assert!(!check("https://wallet.example.com/fiat/returned"));
+        assert!(!check("http://wallet.example.com/fiat/return"));
+        assert!(!check("https://wallet.example.com.evil.io/fiat/return"));
+
+        let deep_link = Url::parse("relationalwallet://fiat/return").unwrap();
+        assert!(uri_allowed_by(&deep_link, "relationalwallet://fiat"));
+    }
+
+    #[test]
+    fn default_web_uri_is_allowed_and_foreign_uris_are_not() {
+        let uri = resolve_return_uri(FiatReturnClient::Web, None).unwrap();
+        assert_eq!(uri.as_str(), DEFAULT_WEB_RETURN_URI);
+        assert!(resolve_return_uri(FiatReturnClient::Web, Some("https://evil.io/cb")).is_err());
+        assert!(resolve_return_uri(
+            FiatReturnClient::Web,
+            Some("http://localhost:3000/callback?state=forged")
+        )
+        .is_err());
+    }
+
+    #[tokio::test]
+    async fn state_round_trips_and_rejects_tampering_and_expiry() {
+        let signer = &AppState::default().secret_signer;
+        let now = 1_700_000_000;
+        let state = sign_state(signer, "req-1", now).await.unwrap();
+        assert_eq!(verify_state(signer, &state, now).await.unwrap(), "req-1");
+
+        let (payload, tag) = state.split_once('.').unwrap();
+        let forged_payload = Base64UrlUnpadded::encode_string(br#"{"rid":"req-2","exp":1}"#);
+        let forged = format!("{forged_payload}.{tag}");
+        assert!(verify_state(signer, &forged, now).await.is_err());
+        let other = &AppState::default().secret_signer;
+        assert!(verify_state(other, &state, now).await.is_err());
+        assert!(verify_state(signer, payload, now).await.is_err());
+
+        let err = verify_state(signer, &state, now + RETURN_STATE_TTL_SECS + 1)
+            .await
+            .unwrap_err();
+        assert_eq!(err.message, "Return state expired");
+    }
+
+    #[tokio::test]
+    async fn verify_returns_request_only_to_its_owner() {
+        let state = AppState::default();
+        let storage = state.storage();
+        let record = StoredFiatRequest::new_queued(
+            "req-return".to_string(),
+            "w".to_string(),
+            "user-1".to_string(),
+            FiatDirection::OnRamp,
+            "10.00".to_string(),
+            "truelayer_sandbox".to_string(),
+            None,
+        );
+        FiatRequestRepository::new(storage).create(&record).unwrap();
+
+        let uri = resolve_return_uri(FiatReturnClient::Web, None).unwrap();
+        let signed = signed_return_uri(&state.secret_signer, uri, "req-return")
+            .await
+            .unwrap();
+        let signed = Url::parse(&signed).unwrap();
+        let returned_state = signed
+            .query_pairs()
+            .find(|(key, _)| key == "state")
+            .map(|(_, value)| value.into_owned())
+            .unwrap();
+
+        let Json(response) = verify_fiat_return(
+            user("user-1"),
+            State(state.clone()),
+            Query(FiatReturnQuery {
+                state: returned_state.clone(),
+            }),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.request_id, "req-return");
+
+        let err = verify_fiat_return(
+            user("user-2"),
+            State(state.clone()),
+            Query(FiatReturnQuery {
+                state: returned_state,
+            }),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, axum::http::StatusCode::FORBIDDEN);
+    }
+}
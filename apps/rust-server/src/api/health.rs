@@ -3,12 +3,24 @@
 // Copyright (C) 2026 Relational Network
 
 use axum::{extract::State, http::StatusCode, Json};
+use chrono::{DateTime, TimeDelta, Utc};
 use serde::Serialize;
 use std::path::Path;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
 use utoipa::ToSchema;
 
+use crate::api::admin_overview::{chain_read, INDEXER_LAG_ALERT_BLOCKS};
+use crate::blockchain::avax_fuji;
 use crate::config::DATA_DIR_ENV;
+use crate::indexer;
+use crate::providers::truelayer::TrueLayerClient;
 use crate::state::AppState;
+use crate::worker_health::{self, Worker};
+
+/// How long a computed status page is served before checking again, so
+/// public traffic never reaches the chain or the fiat provider directly.
+const STATUS_CACHE_TTL: Duration = Duration::from_secs(30);
 
 /// Health check response with individual component status.
 #[derive(Debug, Serialize, ToSchema)]
@@ -35,6 +47,112 @@ pub struct HealthResponse {
     pub status: String,
 }
 
+/// Coarse state of a component on the public status page.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ComponentStatus {
+    Ok,
+    Degraded,
+}
+
+impl ComponentStatus {
+    fn from_ok(ok: bool) -> Self {
+        if ok {
+            Self::Ok
+        } else {
+            Self::Degraded
+        }
+    }
+}
+
+/// Components reported on the public status page.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct StatusComponents {
+    /// The API itself.
+    pub api: ComponentStatus,
+    /// Blockchain RPC connectivity.
+    pub chain: ComponentStatus,
+    /// Fiat provider reachability; omitted when fiat is not configured.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fiat: Option<ComponentStatus>,
+    /// Whether transaction history is up to date with the chain.
+    pub indexer: ComponentStatus,
+}
+
+/// Redacted service status for a public status page.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct StatusPageResponse {
+    /// `degraded` if any component is.
+    pub status: ComponentStatus,
+    pub components: StatusComponents,
+    /// When the components were last checked (RFC 3339).
+    pub checked_at: String,
+}
+
+static STATUS_CACHE: Mutex<Option<(Instant, StatusPageResponse)>> = Mutex::new(None);
+
+/// Whether the indexer is fresh: its heartbeat is recent and, when the
+/// chain head is known, it is no more than [`INDEXER_LAG_ALERT_BLOCKS`]
+/// behind.
+fn indexer_fresh(
+    last_heartbeat: Option<DateTime<Utc>>,
+    last_indexed_block: Option<u64>,
+    head_block: Option<u64>,
+    now: DateTime<Utc>,
+) -> bool {
+    let beating = last_heartbeat
+        .is_some_and(|at| now - at <= TimeDelta::seconds(Worker::EventIndexer.stale_after_secs()));
+    let caught_up = match (last_indexed_block, head_block) {
+        (Some(indexed), Some(head)) => head.saturating_sub(indexed) <= INDEXER_LAG_ALERT_BLOCKS,
+        _ => false,
+    };
+    beating && caught_up
+}
+
+async fn fiat_status() -> Option<ComponentStatus> {
+    if !TrueLayerClient::is_configured() {
+        return None;
+    }
+    let reachable = match TrueLayerClient::from_env() {
+        Ok(client) => chain_read(client.check_reachable()).await.is_ok(),
+        Err(_) => false,
+    };
+    Some(ComponentStatus::from_ok(reachable))
+}
+
+async fn check_status(state: &AppState) -> StatusPageResponse {
+    let network = avax_fuji();
+    let head_block = match state.chain_client(&network).await {
+        Ok(client) => chain_read(client.get_block_number()).await.ok(),
+        Err(_) => None,
+    };
+    let last_indexed_block = state.tx_db.as_ref().and_then(|db| {
+        db.get_last_indexed_block(&indexer::checkpoint_key(&network))
+            .ok()
+    });
+    let now = Utc::now();
+    let components = StatusComponents {
+        api: ComponentStatus::Ok,
+        chain: ComponentStatus::from_ok(head_block.is_some()),
+        fiat: fiat_status().await,
+        indexer: ComponentStatus::from_ok(indexer_fresh(
+            worker_health::last_heartbeat(Worker::EventIndexer),
+            last_indexed_block,
+            head_block,
+            now,
+        )),
+    };
+    let all_ok = [components.api, components.chain, components.indexer]
+        .into_iter()
+        .chain(components.fiat)
+        .all(|c| c == ComponentStatus::Ok);
+    StatusPageResponse {
+        status: ComponentStatus::from_ok(all_ok),
+        components,
+        checked_at: now.to_rfc3339(),
+    }
+}
+
 /// Check if the data directory exists and is accessible.
 fn check_data_dir() -> Option<String> {
     if let Ok(dir) = std::env::var(DATA_DIR_ENV) {
@@ -116,3 +234,50 @@ pub async fn liveness() -> Json<HealthResponse> {
 pub async fn readiness(state: State<AppState>) -> (StatusCode, Json<ReadyResponse>) {
     health(state).await
 }
+
+/// Public status page.
+///
+/// Reports each component as `ok` or `degraded` without details, for
+/// display on a public status page. Results are cached for 30 seconds and
+/// requests are rate limited per client IP.
+#[utoipa::path(
+    get,
+    path = "/status",
+    tag = "Health",
+    responses(
+        (status = 200, description = "Service status", body = StatusPageResponse),
+        (status = 429, description = "Rate limited")
+    )
+)]
+pub async fn status(State(state): State<AppState>) -> Json<StatusPageResponse> {
+    let cached = STATUS_CACHE
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .as_ref()
+        .filter(|(at, _)| at.elapsed() < STATUS_CACHE_TTL)
+        .map(|(_, response)| response.clone());
+    if let Some(response) = cached {
+        return Json(response);
+    }
+    let response = check_status(&state).await;
+    *STATUS_CACHE.lock().unwrap_or_else(|e| e.into_inner()) =
+        Some((Instant::now(), response.clone()));
+    Json(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn indexer_is_fresh_only_when_beating_and_caught_up() {
+        let now = Utc::now();
+        let recent = Some(now - TimeDelta::seconds(10));
+        assert!(indexer_fresh(recent, Some(1_000), Some(1_050), now));
+        assert!(!indexer_fresh(recent, Some(1_000), Some(2_000), now));
+        assert!(!indexer_fresh(recent, Some(1_000), None, now));
+        assert!(!indexer_fresh(None, Some(1_000), Some(1_000), now));
+        let stale = Some(now - TimeDelta::hours(1));
+        assert!(!indexer_fresh(stale, Some(1_000), Some(1_000), now));
+    }
+}
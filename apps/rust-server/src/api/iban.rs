@@ -0,0 +1,249 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! IBAN validation for off-ramp payouts.
+//!
+//! Payouts go out over SEPA, so an IBAN is accepted only if its country
+//! code is known, its length matches that country's format, its mod-97
+//! check digits are correct (ISO 13616), and the country is reachable over
+//! SEPA. Catching these at request time means a typo fails immediately
+//! instead of after the user has burned their rEUR.
+
+use std::fmt;
+
+/// IBAN length per country, from the SWIFT IBAN registry.
+const IBAN_LENGTHS: &[(&str, usize)] = &[
+    ("AD", 24),
+    ("AE", 23),
+    ("AL", 28),
+    ("AT", 20),
+    ("AZ", 28),
+    ("BA", 20),
+    ("BE", 16),
+    ("BG", 22),
+    ("BH", 22),
+    ("BI", 27),
+    ("BR", 29),
+    ("BY", 28),
+    ("CH", 21),
+    ("CR", 22),
+    ("CY", 28),
+    ("CZ", 24),
+    ("DE", 22),
+    ("DJ", 27),
+    ("DK", 18),
+    ("DO", 28),
+    ("EE", 20),
+    ("EG", 29),
+    ("ES", 24),
+    ("FI", 18),
+    ("FK", 18),
+    ("FO", 18),
+    ("FR", 27),
+    ("GB", 22),
+    ("GE", 22),
+    ("GI", 23),
+    ("GL", 18),
+    ("GR", 27),
+    ("GT", 28),
+    ("HR", 21),
+    ("HU", 28),
+    ("IE", 22),
+    ("IL", 23),
+    ("IQ", 23),
+    ("IS", 26),
+    ("IT", 27),
+    ("JO", 30),
+    ("KW", 30),
+    ("KZ", 20),
+    ("LB", 28),
+    ("LC", 32),
+    ("LI", 21),
+    ("LT", 20),
+    ("LU", 20),
+    ("LV", 21),
+    ("LY", 25),
+    ("MC", 27),
+    ("MD", 24),
+    ("ME", 22),
+    ("MK", 19),
+    ("MN", 20),
+    ("MR", 27),
+    ("MT", 31),
+    ("MU", 30),
+    ("NI", 28),
+    ("NL", 18),
+    ("NO", 15),
+    ("OM", 23),
+    ("PK", 24),
+    ("PL", 28),
+    ("PS", 29),
+    ("PT", 25),
+    ("QA", 29),
+    ("RO", 24),
+    ("RS", 22),
+    ("RU", 33),
+    ("SA", 24),
+    ("SC", 31),
+    ("SD", 18),
+    ("SE", 24),
+    ("SI", 19),
+    ("SK", 24),
+    ("SM", 27),
+    ("SO", 23),
+    ("ST", 25),
+    ("SV", 28),
+    ("TL", 23),
+    ("TN", 24),
+    ("TR", 26),
+    ("UA", 29),
+    ("VA", 22),
+    ("VG", 24),
+    ("XK", 20),
+    ("YE", 30),
+];
+
+/// Countries in the SEPA schemes' geographical scope that issue their own
+/// IBANs. Territories using another country's prefix (e.g. French overseas
+/// departments, Jersey) are covered by that prefix.
+const SEPA_COUNTRIES: &[&str] = &[
+    "AD", "AL", "AT", "BE", "BG", "CH", "CY", "CZ", "DE", "DK", "EE", "ES", "FI", "FR", "GB", "GI",
+    "GR", "HR", "HU", "IE", "IS", "IT", "LI", "LT", "LU", "LV", "MC", "MD", "ME", "MK", "MT", "NL",
+    "NO", "PL", "PT", "RO", "RS", "SE", "SI", "SK", "SM", "VA",
+];
+
+/// Why an IBAN was rejected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum IbanError {
+    /// The first two characters are not a country with an IBAN format.
+    UnknownCountry(String),
+    /// The length does not match the country's format.
+    Length {
+        country: String,
+        expected: usize,
+        actual: usize,
+    },
+    /// The check digits do not match the rest of the IBAN.
+    Checksum,
+    /// The country cannot be paid over SEPA.
+    NotSepa(String),
+}
+
+impl fmt::Display for IbanError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownCountry(country) => write!(f, "unknown IBAN country code '{country}'"),
+            Self::Length {
+                country,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "IBANs from {country} have {expected} characters, got {actual}"
+            ),
+            Self::Checksum => write!(f, "check digits do not match; please re-check the IBAN"),
+            Self::NotSepa(country) => {
+                write!(
+                    f,
+                    "payouts to {country} accounts are not supported (not in SEPA)"
+                )
+            }
+        }
+    }
+}
+
+/// Remainder of the IBAN, rearranged with its first four characters moved
+/// to the end and letters expanded to 10..=35, modulo 97.
+fn mod97(iban: &str) -> u32 {
+    iban.chars()
+        .skip(4)
+        .chain(iban.chars().take(4))
+        .fold(0u32, |rem, c| match c.to_digit(36) {
+            Some(v) if v < 10 => (rem * 10 + v) % 97,
+            Some(v) => (rem * 100 + v) % 97,
+            None => rem,
+        })
+}
+
+/// Validate a compact, upper-case, alphanumeric IBAN.
+pub(crate) fn validate_iban(iban: &str) -> Result<(), IbanError> {
+    let country = iban.get(..2).unwrap_or(iban);
+    let expected = IBAN_LENGTHS
+        .iter()
+        .find(|(code, _)| *code == country)
+        .map(|(_, len)| *len)
+        .ok_or_else(|| IbanError::UnknownCountry(country.to_string()))?;
+    if iban.len() != expected {
+        return Err(IbanError::Length {
+            country: country.to_string(),
+            expected,
+            actual: iban.len(),
+        });
+    }
+    if !iban[2..4].bytes().all(|b| b.is_ascii_digit()) || mod97(iban) != 1 {
+        return Err(IbanError::Checksum);
+    }
+    if !SEPA_COUNTRIES.contains(&country) {
+        return Err(IbanError::NotSepa(country.to_string()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_valid_sepa_ibans() {
+        for iban in [
+            "DE89370400440532013000",
+            "GB33BUKB20201555555555",
+            "FR1420041010050500013M02606",
+            "NO9386011117947",
+            "CH9300762011623852957",
+        ] {
+            assert_eq!(validate_iban(iban), Ok(()), "{iban}");
+        }
+    }
+
+    #[test]
+    fn rejects_a_single_changed_digit() {
+        assert_eq!(
+            validate_iban("DE89370400440532013001"),
+            Err(IbanError::Checksum)
+        );
+        assert_eq!(
+            validate_iban("DE88370400440532013000"),
+            Err(IbanError::Checksum)
+        );
+    }
+
+    #[test]
+    fn rejects_wrong_length_and_unknown_country() {
+        assert_eq!(
+            validate_iban("DE8937040044053201300"),
+            Err(IbanError::Length {
+                country: "DE".to_string(),
+                expected: 22,
+                actual: 21,
+            })
+        );
+        assert_eq!(
+            validate_iban("ZZ89370400440532013000"),
+            Err(IbanError::UnknownCountry("ZZ".to_string()))
+        );
+    }
+
+    #[test]
+    fn rejects_valid_ibans_outside_sepa() {
+        assert_eq!(
+            validate_iban("TR330006100519786457841326"),
+            Err(IbanError::NotSepa("TR".to_string()))
+        );
+        assert_eq!(
+            validate_iban("BR1800360305000010009795493C1"),
+            Err(IbanError::NotSepa("BR".to_string()))
+        );
+    }
+}
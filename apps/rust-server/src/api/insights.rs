@@ -0,0 +1,339 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Monthly spending insights per wallet.
+//!
+//! `GET /v1/wallets/{wallet_id}/insights` serves the aggregates the
+//! [insights aggregator](crate::insights) keeps up to date; nothing is
+//! computed from the transaction history on request. User category budgets
+//! are read live, so budget and name changes show up immediately.
+
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::{
+    api::categories::{eur, owned_wallet, parse_month, CategorySpending},
+    auth::Auth,
+    error::{ApiError, StorageContext},
+    insights::{previous_month, volume},
+    state::AppState,
+    storage::{CategoryRepository, InsightsRepository, MonthlyInsights, StoredUserCategories},
+};
+
+/// Counterparties listed in a response.
+const TOP_COUNTERPARTIES: usize = 5;
+
+/// Query parameters for wallet insights.
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct InsightsQuery {
+    /// Month as `YYYY-MM` (UTC, default: current month).
+    pub month: Option<String>,
+}
+
+/// Spending in one automatic category, compared to the prior month.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct CategoryInsight {
+    /// Automatic category (`transfer`, `fee`, ...).
+    pub category: String,
+    pub spent_eur: String,
+    pub previous_eur: String,
+    /// Change against the prior month in percent; absent when the prior
+    /// month had no spending in this category.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub change_pct: Option<f64>,
+}
+
+/// Value exchanged with one counterparty.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct CounterpartyInsight {
+    pub address: String,
+    pub sent_eur: String,
+    pub received_eur: String,
+    pub transactions: u32,
+}
+
+/// Totals of the prior month.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct MonthComparison {
+    pub month: String,
+    pub spent_eur: String,
+    pub received_eur: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spent_change_pct: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub received_change_pct: Option<f64>,
+}
+
+/// Response for `GET /v1/wallets/{wallet_id}/insights`.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct WalletInsightsResponse {
+    pub wallet_id: String,
+    pub month: String,
+    pub currency: String,
+    /// When this month was last aggregated; absent for months the
+    /// aggregator has not covered (yet).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub computed_at: Option<DateTime<Utc>>,
+    /// Last aggregator run over this wallet.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub aggregated_at: Option<DateTime<Utc>>,
+    pub spent_eur: String,
+    pub received_eur: String,
+    /// Confirmed transactions, including transfers between your wallets.
+    pub transactions: u32,
+    /// Transactions without a recorded price, left out of the totals.
+    pub unpriced: u32,
+    /// Spending per automatic category.
+    pub categories: Vec<CategoryInsight>,
+    /// Spending per user category, with budgets.
+    pub budgets: Vec<CategorySpending>,
+    /// Spending not assigned to a user category.
+    pub uncategorized_eur: String,
+    /// Largest counterparties by volume.
+    pub top_counterparties: Vec<CounterpartyInsight>,
+    pub previous_month: MonthComparison,
+}
+
+/// Percentage change from `previous` to `current`, one decimal.
+fn change_pct(current: f64, previous: f64) -> Option<f64> {
+    (previous > 0.0).then(|| ((current - previous) / previous * 1000.0).round() / 10.0)
+}
+
+fn build_response(
+    wallet_id: String,
+    month: String,
+    aggregated_at: Option<DateTime<Utc>>,
+    current: Option<&MonthlyInsights>,
+    previous: (String, Option<&MonthlyInsights>),
+    user_categories: &StoredUserCategories,
+) -> WalletInsightsResponse {
+    let empty = MonthlyInsights::default();
+    let (previous_key, previous) = previous;
+    let computed_at = current.map(|m| m.computed_at);
+    let current = current.unwrap_or(&empty);
+    let previous = previous.unwrap_or(&empty);
+
+    let mut names: Vec<&String> = current
+        .by_category
+        .keys()
+        .chain(previous.by_category.keys())
+        .collect();
+    names.sort();
+    names.dedup();
+    let categories = names
+        .into_iter()
+        .map(|name| {
+            let spent = current.by_category.get(name).copied().unwrap_or(0.0);
+            let before = previous.by_category.get(name).copied().unwrap_or(0.0);
+            CategoryInsight {
+                category: name.clone(),
+                spent_eur: eur(spent),
+                previous_eur: eur(before),
+                change_pct: change_pct(spent, before),
+            }
+        })
+        .collect();
+
+    // Spending assigned to categories deleted since counts as uncategorized.
+    let mut uncategorized = current.uncategorized_eur;
+    for (category_id, value) in &current.by_user_category {
+        if user_categories.get(category_id).is_none() {
+            uncategorized += value;
+        }
+    }
+    let budgets = user_categories
+        .categories
+        .iter()
+        .map(|c| {
+            let spent = current
+                .by_user_category
+                .get(&c.category_id)
+                .copied()
+                .unwrap_or(0.0);
+            let budget = c
+                .monthly_budget_eur
+                .as_deref()
+                .and_then(|b| b.parse::<f64>().ok());
+            CategorySpending {
+                category_id: c.category_id.clone(),
+                name: c.name.clone(),
+                spent_eur: eur(spent),
+                budget_eur: c.monthly_budget_eur.clone(),
+                remaining_eur: budget.map(|b| eur(b - spent)),
+                over_budget: budget.is_some_and(|b| spent > b),
+            }
+        })
+        .collect();
+
+    let mut counterparties: Vec<_> = current.counterparties.iter().collect();
+    counterparties.sort_by(|a, b| volume(b.1).total_cmp(&volume(a.1)));
+    let top_counterparties = counterparties
+        .into_iter()
+        .take(TOP_COUNTERPARTIES)
+        .map(|(address, totals)| CounterpartyInsight {
+            address: address.clone(),
+            sent_eur: eur(totals.sent_eur),
+            received_eur: eur(totals.received_eur),
+            transactions: totals.transactions,
+        })
+        .collect();
+
+    WalletInsightsResponse {
+        wallet_id,
+        month,
+        currency: "EUR".to_string(),
+        computed_at,
+        aggregated_at,
+        spent_eur: eur(current.spent_eur),
+        received_eur: eur(current.received_eur),
+        transactions: current.transactions,
+        unpriced: current.unpriced,
+        categories,
+        budgets,
+        uncategorized_eur: eur(uncategorized),
+        top_counterparties,
+        previous_month: MonthComparison {
+            month: previous_key,
+            spent_eur: eur(previous.spent_eur),
+            received_eur: eur(previous.received_eur),
+            spent_change_pct: change_pct(current.spent_eur, previous.spent_eur),
+            received_change_pct: change_pct(current.received_eur, previous.received_eur),
+        },
+    }
+}
+
+/// Monthly spending insights for a wallet.
+///
+/// Spending per category, budgets, top counterparties and a comparison to
+/// the prior month, valued in EUR at the recorded price of each
+/// transaction's day. Served from aggregates refreshed in the background
+/// every 15 minutes; `computed_at` tells how current the month is.
+#[utoipa::path(
+    get,
+    path = "/v1/wallets/{wallet_id}/insights",
+    tag = "Categories",
+    params(
+        ("wallet_id" = String, Path, description = "Wallet ID"),
+        InsightsQuery
+    ),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, body = WalletInsightsResponse),
+        (status = 400, description = "Invalid month"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Not your wallet"),
+        (status = 404, description = "Wallet not found")
+    )
+)]
+pub async fn get_wallet_insights(
+    Auth(user): Auth,
+    State(state): State<AppState>,
+    Path(wallet_id): Path<String>,
+    Query(query): Query<InsightsQuery>,
+) -> Result<Json<WalletInsightsResponse>, ApiError> {
+    let month = parse_month(query.month.as_deref())?
+        .format("%Y-%m")
+        .to_string();
+    let storage = state.storage();
+    let wallet = owned_wallet(storage, &user, &wallet_id)?;
+    let stored = InsightsRepository::new(storage)
+        .get(&wallet.wallet_id)
+        .context("Failed to load insights")?;
+    let user_categories = CategoryRepository::new(storage)
+        .get(&user.user_id)
+        .context("Failed to load categories")?;
+
+    let previous = previous_month(&month).unwrap_or_default();
+    Ok(Json(build_response(
+        wallet.wallet_id,
+        month.clone(),
+        stored.updated_at,
+        stored.months.get(&month),
+        (previous.clone(), stored.months.get(&previous)),
+        &user_categories,
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::{CounterpartyTotals, UserCategory};
+
+    #[test]
+    fn response_compares_months_and_folds_deleted_categories() {
+        let current = MonthlyInsights {
+            spent_eur: 150.0,
+            received_eur: 20.0,
+            by_category: [("transfer".to_string(), 150.0)].into(),
+            by_user_category: [("rent".to_string(), 100.0), ("deleted".to_string(), 30.0)].into(),
+            uncategorized_eur: 20.0,
+            counterparties: (0..8)
+                .map(|i| {
+                    (
+                        format!("0x{i:040}"),
+                        CounterpartyTotals {
+                            sent_eur: i as f64,
+                            received_eur: 0.0,
+                            transactions: 1,
+                        },
+                    )
+                })
+                .collect(),
+            transactions: 9,
+            unpriced: 0,
+            computed_at: Utc::now(),
+        };
+        let previous = MonthlyInsights {
+            spent_eur: 100.0,
+            by_category: [("fee".to_string(), 10.0), ("transfer".to_string(), 90.0)].into(),
+            ..Default::default()
+        };
+        let mut user_categories = StoredUserCategories::default();
+        user_categories.categories.push(UserCategory {
+            category_id: "rent".to_string(),
+            name: "Rent".to_string(),
+            monthly_budget_eur: Some("80".to_string()),
+            created_at: Utc::now(),
+        });
+
+        let response = build_response(
+            "wallet-1".to_string(),
+            "2026-03".to_string(),
+            None,
+            Some(&current),
+            ("2026-02".to_string(), Some(&previous)),
+            &user_categories,
+        );
+        assert_eq!(response.spent_eur, "150.00");
+        assert_eq!(response.previous_month.spent_change_pct, Some(50.0));
+        assert_eq!(response.previous_month.received_change_pct, None);
+        assert_eq!(response.categories.len(), 2);
+        assert_eq!(response.categories[0].category, "fee");
+        assert_eq!(response.categories[0].change_pct, Some(-100.0));
+        assert_eq!(response.categories[1].change_pct, Some(66.7));
+        assert_eq!(response.uncategorized_eur, "50.00");
+        assert_eq!(response.budgets.len(), 1);
+        assert!(response.budgets[0].over_budget);
+        assert_eq!(response.budgets[0].remaining_eur.as_deref(), Some("-20.00"));
+        assert_eq!(response.top_counterparties.len(), TOP_COUNTERPARTIES);
+        assert_eq!(response.top_counterparties[0].sent_eur, "7.00");
+
+        let missing = build_response(
+            "wallet-1".to_string(),
+            "2026-03".to_string(),
+            None,
+            None,
+            ("2026-02".to_string(), None),
+            &user_categories,
+        );
+        assert!(missing.computed_at.is_none());
+        assert_eq!(missing.spent_eur, "0.00");
+        assert!(missing.categories.is_empty());
+    }
+}
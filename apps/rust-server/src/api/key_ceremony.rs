@@ -0,0 +1,669 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Admin key-ceremony endpoints for the fiat reserve wallet.
+//!
+//! Flow:
+//! 1. `POST   /v1/admin/fiat/service-wallet/ceremony` — open a ceremony with a threshold.
+//! 2. `POST   /v1/admin/fiat/service-wallet/ceremony/shares` — each operator submits one share;
+//!    a second share from the same operator is refused, so no one admin can meet the threshold.
+//! 3. `GET    /v1/admin/fiat/service-wallet/ceremony` — once the threshold is met, shows the
+//!    derived address so operators can compare it with their offline record.
+//! 4. `POST   /v1/admin/fiat/service-wallet/ceremony/activate` — writes the service wallet
+//!    after the caller echoes the expected address.
+//!
+//! `DELETE /v1/admin/fiat/service-wallet/ceremony` aborts and discards collected shares.
+//! Share values are never returned by any endpoint.
+
+use axum::{extract::State, http::StatusCode, Json};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::{
+    api::{admin::require_platform_admin, admin_activity::log_admin_read},
+    auth::AdminOnly,
+    blockchain::same_address,
+    error::{ApiError, StorageContext},
+    state::AppState,
+    storage::{
+        repository::key_ceremony::{combine_shares, decode_share_value, StoredKeyShare},
+        repository::service_wallet::address_from_secret,
+        AuditEvent, AuditEventType, AuditRepository, EncryptedStorage, FiatServiceWalletRepository,
+        KeyCeremonyRepository, KeyCeremonyStatus, StorageError, StoredKeyCeremony,
+    },
+};
+
+/// Smallest threshold accepted; a 1-of-n split offers no dual control.
+const MIN_THRESHOLD: u8 = 2;
+
+/// Request to open a key ceremony.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct StartKeyCeremonyRequest {
+    /// Number of shares required to reconstruct the reserve key (>= 2).
+    pub threshold: u8,
+}
+
+/// A single operator share.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct SubmitKeyShareRequest {
+    /// Share index (x-coordinate, 1..=255).
+    pub index: u8,
+    /// Hex-encoded 32-byte share value (optional `0x` prefix).
+    pub share: String,
+}
+
+/// Confirmation that the derived address matches the operators' record.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct ActivateKeyCeremonyRequest {
+    /// Address the operators expect the reconstructed key to control.
+    pub expected_address: String,
+}
+
+/// Share provenance entry (never includes the share value).
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct KeyShareSummary {
+    /// Share index.
+    pub index: u8,
+    /// Admin who submitted the share.
+    pub submitted_by: String,
+    /// Submission timestamp.
+    pub submitted_at: DateTime<Utc>,
+}
+
+/// Key ceremony status.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct KeyCeremonyResponse {
+    pub ceremony_id: String,
+    pub status: KeyCeremonyStatus,
+    /// Shares required.
+    pub threshold: u8,
+    /// Shares received so far.
+    pub shares_received: usize,
+    /// Who submitted which share index.
+    pub shares: Vec<KeyShareSummary>,
+    /// Address derived from the reconstructed key (once the threshold is met).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub derived_address: Option<String>,
+    pub created_by: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub activated_by: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub activated_at: Option<DateTime<Utc>>,
+}
+
+impl From<&StoredKeyCeremony> for KeyCeremonyResponse {
+    fn from(record: &StoredKeyCeremony) -> Self {
+        Self {
+            ceremony_id: record.ceremony_id.clone(),
+            status: record.status,
+            threshold: record.threshold,
+            shares_received: record.shares.len(),
+            shares: record
+                .shares
+                .iter()
+                .map(|share| KeyShareSummary {
+                    index: share.index,
+                    submitted_by: share.submitted_by.clone(),
+                    submitted_at: share.submitted_at,
+                })
+                .collect(),
+            derived_address: record.derived_address.clone(),
+            created_by: record.created_by.clone(),
+            created_at: record.created_at,
+            updated_at: record.updated_at,
+            activated_by: record.activated_by.clone(),
+            activated_at: record.activated_at,
+        }
+    }
+}
+
+fn load_ceremony(storage: &EncryptedStorage) -> Result<StoredKeyCeremony, ApiError> {
+    KeyCeremonyRepository::new(storage)
+        .get()
+        .map_err(|e| match e {
+            StorageError::NotFound(_) => ApiError::not_found("No key ceremony in progress"),
+            other => ApiError::internal(format!("Failed to load key ceremony: {other}")),
+        })
+}
+
+fn save_ceremony(storage: &EncryptedStorage, record: &StoredKeyCeremony) -> Result<(), ApiError> {
+    KeyCeremonyRepository::new(storage)
+        .save(record)
+        .map_err(|e| ApiError::internal(format!("Failed to persist key ceremony: {e}")))
+}
+
+fn log_ceremony_event(
+    storage: &EncryptedStorage,
+    admin_id: &str,
+    ceremony_id: &str,
+    details: serde_json::Value,
+) {
+    let event = AuditEvent::new(AuditEventType::ReserveKeyCeremony)
+        .with_user(admin_id)
+        .with_resource("key_ceremony", ceremony_id)
+        .with_details(details);
+    let _ = AuditRepository::new(storage).log(&event);
+}
+
+/// Open a reserve-wallet key ceremony.
+#[utoipa::path(
+    post,
+    path = "/v1/admin/fiat/service-wallet/ceremony",
+    tag = "Admin",
+    request_body = StartKeyCeremonyRequest,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 201, description = "Ceremony opened", body = KeyCeremonyResponse),
+        (status = 400, description = "Invalid threshold"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Not authorized (platform admin required)"),
+        (status = 409, description = "Reserve wallet already initialized or ceremony in progress")
+    )
+)]
+pub async fn start_key_ceremony(
+    AdminOnly(admin): AdminOnly,
+    State(state): State<AppState>,
+    Json(request): Json<StartKeyCeremonyRequest>,
+) -> Result<(StatusCode, Json<KeyCeremonyResponse>), ApiError> {
+    require_platform_admin(&admin)?;
+    let storage = state.storage();
+
+    if request.threshold < MIN_THRESHOLD {
+        return Err(ApiError::bad_request(format!(
+            "threshold must be at least {MIN_THRESHOLD}"
+        )));
+    }
+    if FiatServiceWalletRepository::new(storage).exists() {
+        return Err(ApiError::conflict(
+            "Fiat reserve wallet is already initialized",
+        ));
+    }
+    if let Ok(existing) = KeyCeremonyRepository::new(storage).get() {
+        if existing.status != KeyCeremonyStatus::Activated {
+            return Err(ApiError::conflict(format!(
+                "Key ceremony {} is already in progress",
+                existing.ceremony_id
+            )));
+        }
+    }
+
+    let record = StoredKeyCeremony::new(request.threshold, &admin.user_id);
+    save_ceremony(storage, &record)?;
+
+    log_ceremony_event(
+        storage,
+        &admin.user_id,
+        &record.ceremony_id,
+        serde_json::json!({"action": "start", "threshold": record.threshold}),
+    );
+
+    Ok((
+        StatusCode::CREATED,
+        Json(KeyCeremonyResponse::from(&record)),
+    ))
+}
+
+/// Get the current key ceremony status, including the derived address once
+/// enough shares have been submitted.
+#[utoipa::path(
+    get,
+    path = "/v1/admin/fiat/service-wallet/ceremony",
+    tag = "Admin",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Ceremony status", body = KeyCeremonyResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Not authorized (platform admin required)"),
+        (status = 404, description = "No ceremony in progress")
+    )
+)]
+pub async fn get_key_ceremony(
+    AdminOnly(admin): AdminOnly,
+    State(state): State<AppState>,
+) -> Result<Json<KeyCeremonyResponse>, ApiError> {
+    require_platform_admin(&admin)?;
+    let record = load_ceremony(state.storage())?;
+    log_admin_read(
+        state.storage(),
+        &admin,
+        "key_ceremony",
+        &record.ceremony_id,
+        &["shares", "created_by", "activated_by", "derived_address"],
+        1,
+    );
+    Ok(Json(KeyCeremonyResponse::from(&record)))
+}
+
+/// Submit one Shamir share to the open ceremony.
+///
+/// When the threshold is reached the secret is reconstructed in memory to
+/// derive the address; the secret itself is not persisted until activation.
+#[utoipa::path(
+    post,
+    path = "/v1/admin/fiat/service-wallet/ceremony/shares",
+    tag = "Admin",
+    request_body = SubmitKeyShareRequest,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Share accepted", body = KeyCeremonyResponse),
+        (status = 400, description = "Malformed share"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Not authorized (platform admin required)"),
+        (status = 404, description = "No ceremony in progress"),
+        (status = 409, description = "Duplicate index, second share from the same operator, or threshold already reached"),
+        (status = 422, description = "Shares do not reconstruct a valid secp256k1 key")
+    )
+)]
+pub async fn submit_key_share(
+    AdminOnly(admin): AdminOnly,
+    State(state): State<AppState>,
+    Json(request): Json<SubmitKeyShareRequest>,
+) -> Result<Json<KeyCeremonyResponse>, ApiError> {
+    require_platform_admin(&admin)?;
+    let storage = state.storage();
+    let mut record = load_ceremony(storage)?;
+
+    if record.status != KeyCeremonyStatus::Collecting {
+        return Err(ApiError::conflict(
+            "Key ceremony is no longer collecting shares",
+        ));
+    }
+    if request.index == 0 {
+        return Err(ApiError::bad_request(
+            "share index must be between 1 and 255",
+        ));
+    }
+    if record
+        .shares
+        .iter()
+        .any(|s| s.submitted_by == admin.user_id)
+    {
+        return Err(ApiError::conflict(
+            "You already submitted a share; each share must come from a different operator",
+        ));
+    }
+    if record.shares.iter().any(|s| s.index == request.index) {
+        return Err(ApiError::conflict(format!(
+            "Share index {} was already submitted",
+            request.index
+        )));
+    }
+    let value = decode_share_value(&request.share).map_err(ApiError::bad_request)?;
+
+    record.shares.push(StoredKeyShare {
+        index: request.index,
+        value: alloy::hex::encode(value),
+        submitted_by: admin.user_id.clone(),
+        submitted_at: Utc::now(),
+    });
+
+    if record.shares.len() >= record.threshold as usize {
+        let shares = record
+            .decoded_shares()
+            .map_err(|e| ApiError::internal(format!("Corrupt share record: {e}")))?;
+        let secret = combine_shares(&shares).map_err(ApiError::bad_request)?;
+        let address = address_from_secret(&secret).map_err(|e| {
+            ApiError::unprocessable(format!(
+                "Submitted shares do not reconstruct a usable key ({e}); abort and retry"
+            ))
+        })?;
+        record.derived_address = Some(address);
+        record.status = KeyCeremonyStatus::AwaitingActivation;
+    }
+    record.updated_at = Utc::now();
+    save_ceremony(storage, &record)?;
+
+    log_ceremony_event(
+        storage,
+        &admin.user_id,
+        &record.ceremony_id,
+        serde_json::json!({
+            "action": "share_submitted",
+            "index": request.index,
+            "shares_received": record.shares.len(),
+            "derived_address": record.derived_address,
+        }),
+    );
+
+    Ok(Json(KeyCeremonyResponse::from(&record)))
+}
+
+/// Activate the reserve wallet from the reconstructed key.
+///
+/// The caller must echo the address obtained out-of-band; activation fails if
+/// it differs from the derived address.
+#[utoipa::path(
+    post,
+    path = "/v1/admin/fiat/service-wallet/ceremony/activate",
+    tag = "Admin",
+    request_body = ActivateKeyCeremonyRequest,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Reserve wallet activated", body = KeyCeremonyResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Not authorized (platform admin required)"),
+        (status = 404, description = "No ceremony in progress"),
+        (status = 409, description = "Threshold not reached or reserve wallet already exists"),
+        (status = 422, description = "Expected address does not match")
+    )
+)]
+pub async fn activate_key_ceremony(
+    AdminOnly(admin): AdminOnly,
+    State(state): State<AppState>,
+    Json(request): Json<ActivateKeyCeremonyRequest>,
+) -> Result<Json<KeyCeremonyResponse>, ApiError> {
+    require_platform_admin(&admin)?;
+    let storage = state.storage();
+    let mut record = load_ceremony(storage)?;
+
+    if record.status != KeyCeremonyStatus::AwaitingActivation {
+        return Err(ApiError::conflict(
+            "Key ceremony is not awaiting activation",
+        ));
+    }
+
+    let shares = record
+        .decoded_shares()
+        .map_err(|e| ApiError::internal(format!("Corrupt share record: {e}")))?;
+    let secret = combine_shares(&shares).map_err(ApiError::internal)?;
+    let derived = address_from_secret(&secret).map_err(ApiError::internal)?;
+
+    if !same_address(&derived, &request.expected_address) {
+        log_ceremony_event(
+            storage,
+            &admin.user_id,
+            &record.ceremony_id,
+            serde_json::json!({
+                "action": "activation_rejected",
+                "expected_address": request.expected_address,
+                "derived_address": derived,
+            }),
+        );
+        return Err(ApiError::unprocessable(
+            "Expected address does not match the derived reserve address",
+        ));
+    }
+
+    let metadata = FiatServiceWalletRepository::new(storage)
+        .import_from_ceremony(&secret, &record.ceremony_id)
+        .map_err(|e| match e {
+            StorageError::AlreadyExists(_) => {
+                ApiError::conflict("Fiat reserve wallet is already initialized")
+            }
+            other => ApiError::internal(format!("Failed to store reserve wallet: {other}")),
+        })?;
+
+    let now = Utc::now();
+    record.wipe_share_values();
+    record.status = KeyCeremonyStatus::Activated;
+    record.activated_by = Some(admin.user_id.clone());
+    record.activated_at = Some(now);
+    record.updated_at = now;
+    save_ceremony(storage, &record)?;
+
+    log_ceremony_event(
+        storage,
+        &admin.user_id,
+        &record.ceremony_id,
+        serde_json::json!({
+            "action": "activate",
+            "public_address": metadata.public_address,
+            "share_indexes": record.shares.iter().map(|s| s.index).collect::<Vec<_>>(),
+        }),
+    );
+
+    Ok(Json(KeyCeremonyResponse::from(&record)))
+}
+
+/// Abort the open ceremony and discard all collected shares.
+#[utoipa::path(
+    delete,
+    path = "/v1/admin/fiat/service-wallet/ceremony",
+    tag = "Admin",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 204, description = "Ceremony aborted"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Not authorized (platform admin required)"),
+        (status = 404, description = "No ceremony in progress"),
+        (status = 409, description = "Ceremony already activated")
+    )
+)]
+pub async fn abort_key_ceremony(
+    AdminOnly(admin): AdminOnly,
+    State(state): State<AppState>,
+) -> Result<StatusCode, ApiError> {
+    require_platform_admin(&admin)?;
+    let storage = state.storage();
+    let record = load_ceremony(storage)?;
+
+    if record.status == KeyCeremonyStatus::Activated {
+        return Err(ApiError::conflict(
+            "Key ceremony already activated; the record is kept for provenance",
+        ));
+    }
+
+    KeyCeremonyRepository::new(storage)
+        .delete()
+        .context("Failed to abort key ceremony")?;
+
+    log_ceremony_event(
+        storage,
+        &admin.user_id,
+        &record.ceremony_id,
+        serde_json::json!({"action": "abort", "shares_discarded": record.shares.len()}),
+    );
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::{AuthenticatedUser, Role};
+    use crate::storage::repository::key_ceremony::{split_secret, SECRET_LEN};
+
+    fn admin(user_id: &str) -> AdminOnly {
+        AdminOnly(AuthenticatedUser {
+            user_id: user_id.to_string(),
+            role: Role::Admin,
+            session_id: None,
+            issuer: "https://test.clerk.dev".to_string(),
+            expires_at: Utc::now().timestamp() + 3600,
+            tenant_id: None,
+        })
+    }
+
+    fn share_request(index: u8, value: &[u8; SECRET_LEN]) -> Json<SubmitKeyShareRequest> {
+        Json(SubmitKeyShareRequest {
+            index,
+            share: alloy::hex::encode(value),
+        })
+    }
+
+    #[tokio::test]
+    async fn ceremony_reconstructs_and_activates_reserve_wallet() {
+        let state = AppState::default();
+        let secret = [0x5au8; SECRET_LEN];
+        let expected = address_from_secret(&secret).unwrap();
+        let shares = split_secret(&secret, 2, 3);
+
+        let (status, opened) = start_key_ceremony(
+            admin("admin-1"),
+            State(state.clone()),
+            Json(StartKeyCeremonyRequest { threshold: 2 }),
+        )
+        .await
+        .unwrap();
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(opened.status, KeyCeremonyStatus::Collecting);
+
+        let after_one = submit_key_share(
+            admin("admin-1"),
+            State(state.clone()),
+            share_request(shares[2].0, &shares[2].1),
+        )
+        .await
+        .unwrap();
+        assert!(after_one.derived_address.is_none());
+
+        let after_two = submit_key_share(
+            admin("admin-2"),
+            State(state.clone()),
+            share_request(shares[0].0, &shares[0].1),
+        )
+        .await
+        .unwrap();
+        assert_eq!(after_two.status, KeyCeremonyStatus::AwaitingActivation);
+        assert_eq!(
+            after_two.derived_address.as_deref(),
+            Some(expected.as_str())
+        );
+
+        let wrong = activate_key_ceremony(
+            admin("admin-1"),
+            State(state.clone()),
+            Json(ActivateKeyCeremonyRequest {
+                expected_address: "0x0000000000000000000000000000000000000001".to_string(),
+            }),
+        )
+        .await;
+        assert_eq!(wrong.unwrap_err().status, StatusCode::UNPROCESSABLE_ENTITY);
+
+        let activated = activate_key_ceremony(
+            admin("admin-1"),
+            State(state.clone()),
+            Json(ActivateKeyCeremonyRequest {
+                expected_address: expected.to_uppercase().replace("0X", "0x"),
+            }),
+        )
+        .await
+        .unwrap();
+        assert_eq!(activated.status, KeyCeremonyStatus::Activated);
+
+        let wallet = FiatServiceWalletRepository::new(state.storage())
+            .get()
+            .unwrap();
+        assert_eq!(wallet.public_address, expected);
+        assert_eq!(
+            wallet.ceremony_id.as_deref(),
+            Some(activated.ceremony_id.as_str())
+        );
+
+        let stored = KeyCeremonyRepository::new(state.storage()).get().unwrap();
+        assert!(stored.shares.iter().all(|s| s.value.is_empty()));
+    }
+
+    #[tokio::test]
+    async fn start_is_rejected_when_reserve_wallet_exists() {
+        let state = AppState::default();
+        FiatServiceWalletRepository::new(state.storage())
+            .bootstrap()
+            .unwrap();
+
+        let err = start_key_ceremony(
+            admin("admin-1"),
+            State(state),
+            Json(StartKeyCeremonyRequest { threshold: 2 }),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::CONFLICT);
+    }
+
+    #[tokio::test]
+    async fn one_operator_cannot_submit_two_shares() {
+        let state = AppState::default();
+        let secret = [0x5au8; SECRET_LEN];
+        let shares = split_secret(&secret, 2, 3);
+        let _ = start_key_ceremony(
+            admin("admin-1"),
+            State(state.clone()),
+            Json(StartKeyCeremonyRequest { threshold: 2 }),
+        )
+        .await
+        .unwrap();
+
+        let _ = submit_key_share(
+            admin("admin-1"),
+            State(state.clone()),
+            share_request(shares[0].0, &shares[0].1),
+        )
+        .await
+        .unwrap();
+        let err = submit_key_share(
+            admin("admin-1"),
+            State(state.clone()),
+            share_request(shares[1].0, &shares[1].1),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::CONFLICT);
+
+        let record = KeyCeremonyRepository::new(state.storage()).get().unwrap();
+        assert_eq!(record.shares.len(), 1);
+        assert_eq!(record.status, KeyCeremonyStatus::Collecting);
+    }
+
+    #[tokio::test]
+    async fn duplicate_share_index_is_rejected_and_abort_discards() {
+        let state = AppState::default();
+        let _ = start_key_ceremony(
+            admin("admin-1"),
+            State(state.clone()),
+            Json(StartKeyCeremonyRequest { threshold: 3 }),
+        )
+        .await
+        .unwrap();
+
+        let share = [7u8; SECRET_LEN];
+        let _ = submit_key_share(
+            admin("admin-1"),
+            State(state.clone()),
+            share_request(1, &share),
+        )
+        .await
+        .unwrap();
+        let err = submit_key_share(
+            admin("admin-2"),
+            State(state.clone()),
+            share_request(1, &share),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::CONFLICT);
+
+        assert_eq!(
+            abort_key_ceremony(admin("admin-1"), State(state.clone()))
+                .await
+                .unwrap(),
+            StatusCode::NO_CONTENT
+        );
+        let err = get_key_ceremony(admin("admin-1"), State(state))
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn tenant_admins_cannot_run_a_ceremony() {
+        let state = AppState::default();
+        let mut tenant_admin = admin("tenant-admin");
+        tenant_admin.0.tenant_id = Some("tenant_a".to_string());
+
+        let err = start_key_ceremony(
+            tenant_admin,
+            State(state.clone()),
+            Json(StartKeyCeremonyRequest { threshold: 2 }),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::FORBIDDEN);
+        assert_eq!(err.code, Some("platform_admin_required"));
+        assert!(KeyCeremonyRepository::new(state.storage()).get().is_err());
+    }
+}
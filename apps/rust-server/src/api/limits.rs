@@ -0,0 +1,1148 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Spending limits.
+//!
+//! Admins cap what a user, across all their wallets, or a single wallet may
+//! send per UTC day and per calendar month under `/v1/admin/limits`. Sends
+//! are valued in EUR: rEUR at par and AVAX, on any network whose native coin
+//! it is, at the latest recorded daily price. Sends of unpriced tokens on
+//! Fuji are not counted, and neither are NFTs. Sends on other networks that
+//! cannot be valued fail with 422 `spending_limit_unvalued` while the user or
+//! wallet has limits. A send that would take the user or wallet past a limit
+//! fails with 422 `spending_limit_exceeded`, carrying the exhausted allowance
+//! in `details`. Owners read their allowances from
+//! `GET /v1/wallets/{wallet_id}/limits`.
+//!
+//! Every user-initiated send (transfers, batches, claims, escrow funding
+//! and bridging) takes a [`SpendingReservation`] before it is signed. The
+//! reservation holds a lock per owner from the check until the send is
+//! broadcast, so concurrent sends from the owner's wallets cannot together
+//! exceed a limit, and keeps counting the send until the transaction index
+//! has it. Approvals and permits are checked but not counted: they may not
+//! grant more than the limits still allow. Signing paths that are not
+//! limited (NFT transfers, replacements) hold an empty reservation, so a key
+//! rotation waits for every send that has already read the key.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::OwnedMutexGuard;
+use utoipa::ToSchema;
+
+use crate::{
+    api::{
+        admin::require_platform_admin,
+        fiat::{format_minor_eur, parse_amount_to_minor},
+        wallets::ensure_unlocked,
+    },
+    auth::{AdminOnly, Auth, AuthenticatedUser},
+    blockchain::{address_key, networks, NETWORK_FUJI},
+    error::{ApiError, StorageContext},
+    events::DomainEvent,
+    providers::pricing::{token_symbol, PRICED_SYMBOLS},
+    state::AppState,
+    storage::{
+        AuditEvent, AuditRepository, OwnershipEnforcer, PriceHistories, PriceHistoryRepository,
+        SpendingLimitRepository, StoredSpendingLimits, TokenType, TxDatabase, TxStatus,
+        WalletMetadata, WalletRepository, WalletStatus,
+    },
+};
+
+/// Transactions read per history page while summing spending.
+const HISTORY_PAGE: usize = 200;
+
+/// Request body for setting spending limits. Omit a period to leave it
+/// uncapped.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct SpendingLimitsRequest {
+    /// Most that may be sent per UTC day, in EUR.
+    #[serde(default)]
+    pub daily_eur: Option<String>,
+    /// Most that may be sent per calendar month (UTC), in EUR.
+    #[serde(default)]
+    pub monthly_eur: Option<String>,
+}
+
+/// Whom a limit applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum LimitScope {
+    /// All wallets of the owner.
+    User,
+    /// This wallet only.
+    Wallet,
+}
+
+/// Period a limit resets after.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum LimitPeriod {
+    Daily,
+    Monthly,
+}
+
+/// One limit and how much of it is left.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct SpendingAllowance {
+    pub scope: LimitScope,
+    pub period: LimitPeriod,
+    pub limit_eur: String,
+    /// EUR value sent so far this period.
+    pub spent_eur: String,
+    pub remaining_eur: String,
+    /// Start of the next period.
+    pub resets_at: DateTime<Utc>,
+}
+
+/// Allowances that apply to a wallet.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SpendingAllowanceResponse {
+    pub wallet_id: String,
+    /// Empty when no limit applies.
+    pub allowances: Vec<SpendingAllowance>,
+}
+
+/// `details` of a `spending_limit_exceeded` error.
+#[derive(Debug, Serialize)]
+struct LimitExceeded<'a> {
+    #[serde(flatten)]
+    allowance: &'a SpendingAllowance,
+    /// EUR value of the refused send.
+    requested_eur: String,
+}
+
+/// A limit with amounts in cents.
+struct Allowance {
+    scope: LimitScope,
+    period: LimitPeriod,
+    limit: u64,
+    spent: u64,
+    resets_at: DateTime<Utc>,
+}
+
+impl Allowance {
+    fn remaining(&self) -> u64 {
+        self.limit.saturating_sub(self.spent)
+    }
+
+    fn to_response(&self) -> SpendingAllowance {
+        SpendingAllowance {
+            scope: self.scope,
+            period: self.period,
+            limit_eur: format_minor_eur(self.limit),
+            spent_eur: format_minor_eur(self.spent),
+            remaining_eur: format_minor_eur(self.remaining()),
+            resets_at: self.resets_at,
+        }
+    }
+}
+
+/// A broadcast send that may not be in the transaction index yet.
+#[derive(Debug, Clone)]
+struct InFlightSpend {
+    wallet_id: String,
+    tx_hash: String,
+    /// EUR value in cents.
+    value: u64,
+    at: DateTime<Utc>,
+}
+
+/// Sends being checked against spending limits, per owner.
+#[derive(Debug, Default)]
+pub struct SpendingReservations {
+    owners: Mutex<HashMap<String, Arc<tokio::sync::Mutex<Vec<InFlightSpend>>>>>,
+}
+
+impl SpendingReservations {
+    /// Wait for exclusive use of `owner_user_id`'s limits.
+    async fn lock(&self, owner_user_id: &str) -> OwnedMutexGuard<Vec<InFlightSpend>> {
+        let lock = self
+            .owners
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .entry(owner_user_id.to_string())
+            .or_default()
+            .clone();
+        lock.lock_owned().await
+    }
+
+    /// Wait until no send of `owner_user_id` is between its check and its
+    /// broadcast.
+    pub(crate) async fn wait_idle(&self, owner_user_id: &str) {
+        drop(self.lock(owner_user_id).await);
+    }
+}
+
+/// A send that passed the spending limits.
+///
+/// Holds the owner's lock until dropped. [`commit`](Self::commit) it once
+/// the send is broadcast; dropping it uncommitted releases the amount.
+pub(crate) struct SpendingReservation {
+    in_flight: OwnedMutexGuard<Vec<InFlightSpend>>,
+    wallet_id: String,
+    value: u64,
+}
+
+impl SpendingReservation {
+    /// Count the reserved amount until `tx_hash` is indexed.
+    pub(crate) fn commit(mut self, tx_hash: &str) {
+        if self.value == 0 {
+            return;
+        }
+        let spend = InFlightSpend {
+            wallet_id: self.wallet_id.clone(),
+            tx_hash: tx_hash.to_string(),
+            value: self.value,
+            at: Utc::now(),
+        };
+        self.in_flight.push(spend);
+    }
+}
+
+fn midnight(date: NaiveDate) -> DateTime<Utc> {
+    Utc.from_utc_datetime(&date.and_hms_opt(0, 0, 0).expect("midnight exists"))
+}
+
+/// Start of the period containing `now` and of the next one.
+fn period_bounds(period: LimitPeriod, now: DateTime<Utc>) -> (DateTime<Utc>, DateTime<Utc>) {
+    let today = now.date_naive();
+    match period {
+        LimitPeriod::Daily => (midnight(today), midnight(today) + Duration::days(1)),
+        LimitPeriod::Monthly => {
+            let first = today.with_day(1).expect("day 1 exists");
+            let next = if first.month() == 12 {
+                NaiveDate::from_ymd_opt(first.year() + 1, 1, 1)
+            } else {
+                NaiveDate::from_ymd_opt(first.year(), first.month() + 1, 1)
+            }
+            .expect("first of month exists");
+            (midnight(first), midnight(next))
+        }
+    }
+}
+
+/// EUR value in cents of `amount` of `token`, at the latest price recorded
+/// on or before `date`. `None` for unpriced tokens, for tokens on networks
+/// other than Fuji and for native coins of unregistered networks.
+fn eur_minor(
+    prices: &PriceHistories,
+    token: &TokenType,
+    amount: &str,
+    network: &str,
+    date: NaiveDate,
+) -> Option<u64> {
+    let symbol = match token {
+        TokenType::Native => {
+            let native = networks().get(network)?.native_symbol;
+            PRICED_SYMBOLS.into_iter().find(|s| *s == native)?
+        }
+        TokenType::Erc20(_) if network.eq_ignore_ascii_case(NETWORK_FUJI) => token_symbol(token)?,
+        TokenType::Erc20(_) => return None,
+    };
+    let price = if symbol == "rEUR" {
+        1.0
+    } else {
+        prices.get(symbol)?.price_on_or_before(date)?
+    };
+    let value = amount.trim().parse::<f64>().ok()? * price;
+    (value.is_finite() && value >= 0.0).then(|| (value * 100.0).round() as u64)
+}
+
+/// Cents sent from `address` since `day_start` and since `month_start`.
+/// Failed transactions do not count.
+fn spent_minor(
+    tx_db: &TxDatabase,
+    prices: &PriceHistories,
+    address: &str,
+    day_start: DateTime<Utc>,
+    month_start: DateTime<Utc>,
+) -> Result<(u64, u64), ApiError> {
+    let address = address_key(address);
+    let (mut day, mut month) = (0u64, 0u64);
+    let mut cursor = None;
+    loop {
+        let (page, next) = tx_db
+            .list_by_wallet(&address, cursor.as_deref(), HISTORY_PAGE)
+            .map_err(|e| ApiError::internal(format!("Failed to read transactions: {e}")))?;
+        for (tx, direction) in &page {
+            // Newest first: nothing further back is in either period.
+            if tx.created_at < month_start {
+                return Ok((day, month));
+            }
+            if direction != "sent" || tx.status == TxStatus::Failed {
+                continue;
+            }
+            let Some(value) = eur_minor(
+                prices,
+                &tx.token,
+                &tx.amount,
+                &tx.network,
+                tx.created_at.date_naive(),
+            ) else {
+                continue;
+            };
+            month += value;
+            if tx.created_at >= day_start {
+                day += value;
+            }
+        }
+        match next {
+            Some(next) => cursor = Some(next),
+            None => return Ok((day, month)),
+        }
+    }
+}
+
+/// Cents of `in_flight` sends since `day_start` and since `month_start`,
+/// limited to `wallet_id` when given.
+fn in_flight_minor(
+    in_flight: &[InFlightSpend],
+    wallet_id: Option<&str>,
+    day_start: DateTime<Utc>,
+    month_start: DateTime<Utc>,
+) -> (u64, u64) {
+    in_flight
+        .iter()
+        .filter(|s| wallet_id.is_none_or(|id| s.wallet_id == id) && s.at >= month_start)
+        .fold((0, 0), |(day, month), s| {
+            let day = if s.at >= day_start {
+                day + s.value
+            } else {
+                day
+            };
+            (day, month + s.value)
+        })
+}
+
+fn push_allowances(
+    out: &mut Vec<Allowance>,
+    scope: LimitScope,
+    limits: &StoredSpendingLimits,
+    (day_spent, month_spent): (u64, u64),
+    now: DateTime<Utc>,
+) {
+    for (period, limit, spent) in [
+        (LimitPeriod::Daily, &limits.daily_eur, day_spent),
+        (LimitPeriod::Monthly, &limits.monthly_eur, month_spent),
+    ] {
+        let Some(limit) = limit
+            .as_deref()
+            .and_then(|l| parse_amount_to_minor(l).ok())
+            .map(|(_, minor)| minor)
+        else {
+            continue;
+        };
+        out.push(Allowance {
+            scope,
+            period,
+            limit,
+            spent,
+            resets_at: period_bounds(period, now).1,
+        });
+    }
+}
+
+/// Limits that apply to sends from `wallet`, with what is left of each.
+/// `in_flight` are the owner's broadcast sends not yet indexed.
+fn allowances(
+    state: &AppState,
+    wallet: &WalletMetadata,
+    prices: &PriceHistories,
+    now: DateTime<Utc>,
+    in_flight: &[InFlightSpend],
+) -> Result<Vec<Allowance>, ApiError> {
+    let storage = state.storage();
+    let repo = SpendingLimitRepository::new(storage);
+    let user_limits = repo
+        .get_user(&wallet.owner_user_id)
+        .context("Failed to load spending limits")?;
+    let wallet_limits = repo
+        .get_wallet(&wallet.wallet_id)
+        .context("Failed to load spending limits")?;
+    if user_limits.is_none() && wallet_limits.is_none() {
+        return Ok(Vec::new());
+    }
+
+    let tx_db = state
+        .tx_db
+        .as_ref()
+        .expect("transaction database must be configured");
+    let (day_start, _) = period_bounds(LimitPeriod::Daily, now);
+    let (month_start, _) = period_bounds(LimitPeriod::Monthly, now);
+
+    let mut out = Vec::new();
+    let wallet_spent = spent_minor(
+        tx_db,
+        prices,
+        &wallet.public_address,
+        day_start,
+        month_start,
+    )?;
+    let (wallet_day, wallet_month) =
+        in_flight_minor(in_flight, Some(&wallet.wallet_id), day_start, month_start);
+    if let Some(limits) = &user_limits {
+        let mut spent = (0, 0);
+        for owned in WalletRepository::new(storage)
+            .list_by_owner(&wallet.owner_user_id)
+            .context("Failed to list wallets")?
+        {
+            let (day, month) = if owned.wallet_id == wallet.wallet_id {
+                wallet_spent
+            } else {
+                spent_minor(tx_db, prices, &owned.public_address, day_start, month_start)?
+            };
+            spent = (spent.0 + day, spent.1 + month);
+        }
+        let (day, month) = in_flight_minor(in_flight, None, day_start, month_start);
+        spent = (spent.0 + day, spent.1 + month);
+        push_allowances(&mut out, LimitScope::User, limits, spent, now);
+    }
+    if let Some(limits) = &wallet_limits {
+        let spent = (wallet_spent.0 + wallet_day, wallet_spent.1 + wallet_month);
+        push_allowances(&mut out, LimitScope::Wallet, limits, spent, now);
+    }
+    Ok(out)
+}
+
+fn load_prices(state: &AppState) -> PriceHistories {
+    PriceHistoryRepository::new(state.storage())
+        .get_many(&PRICED_SYMBOLS)
+        .unwrap_or_else(|e| {
+            tracing::warn!(error = %e, "Failed to load price history");
+            PriceHistories::new()
+        })
+}
+
+fn check_allowances(allowances: &[Allowance], requested: u64) -> Result<(), ApiError> {
+    let Some(exhausted) = allowances.iter().find(|a| requested > a.remaining()) else {
+        return Ok(());
+    };
+    let allowance = exhausted.to_response();
+    let period = match exhausted.period {
+        LimitPeriod::Daily => "Daily",
+        LimitPeriod::Monthly => "Monthly",
+    };
+    Err(ApiError::unprocessable(format!(
+        "{period} spending limit of {} EUR would be exceeded; {} EUR remaining",
+        allowance.limit_eur, allowance.remaining_eur
+    ))
+    .with_code("spending_limit_exceeded")
+    .with_details(LimitExceeded {
+        allowance: &allowance,
+        requested_eur: format_minor_eur(requested),
+    }))
+}
+
+/// EUR value in cents of `(token, amount)` pairs sent on `network`, or
+/// `None` if a send on a network other than Fuji cannot be valued. An
+/// `"unlimited"` approval of a valued token is worth more than any limit.
+fn requested_minor(
+    prices: &PriceHistories,
+    sends: &[(&str, &str)],
+    network: &str,
+    date: NaiveDate,
+) -> Option<u64> {
+    let mut total = 0u64;
+    for (token, amount) in sends {
+        let token = if *token == "native" {
+            TokenType::Native
+        } else {
+            TokenType::Erc20(token.to_string())
+        };
+        let value = if amount.trim().eq_ignore_ascii_case("unlimited") {
+            eur_minor(prices, &token, "0", network, date).map(|_| u64::MAX)
+        } else {
+            eur_minor(prices, &token, amount, network, date)
+        };
+        match value {
+            Some(value) => total = total.saturating_add(value),
+            None if network.eq_ignore_ascii_case(NETWORK_FUJI) => {}
+            None => return None,
+        }
+    }
+    Some(total)
+}
+
+/// Whether the owner of `wallet` or the wallet itself has spending limits.
+fn has_limits(state: &AppState, wallet: &WalletMetadata) -> Result<bool, ApiError> {
+    let repo = SpendingLimitRepository::new(state.storage());
+    Ok(repo
+        .get_user(&wallet.owner_user_id)
+        .context("Failed to load spending limits")?
+        .is_some()
+        || repo
+            .get_wallet(&wallet.wallet_id)
+            .context("Failed to load spending limits")?
+            .is_some())
+}
+
+/// Check sends of `(token, amount)` pairs from `wallet` against the
+/// spending limits and reserve them. Unpriced tokens on Fuji never exceed
+/// a limit; sends on other networks that cannot be valued are refused
+/// while limits apply.
+///
+/// Hold the reservation until the send is broadcast, then commit it.
+pub(crate) async fn reserve_spending(
+    state: &AppState,
+    wallet: &WalletMetadata,
+    sends: &[(&str, &str)],
+    network: &str,
+) -> Result<SpendingReservation, ApiError> {
+    let mut in_flight = state
+        .spending_reservations
+        .lock(&wallet.owner_user_id)
+        .await;
+    // Checked again under the lock: a key rotation may have started.
+    ensure_unlocked(wallet)?;
+    let now = Utc::now();
+    let prices = load_prices(state);
+    let requested = requested_minor(&prices, sends, network, now.date_naive());
+    let reservation = |in_flight| SpendingReservation {
+        in_flight,
+        wallet_id: wallet.wallet_id.clone(),
+        value: requested.unwrap_or(0),
+    };
+    let Some(requested) = requested.filter(|r| *r > 0) else {
+        if requested.is_none() && has_limits(state, wallet)? {
+            let e = ApiError::unprocessable(format!(
+                "Sends on network `{network}` cannot be valued against spending limits"
+            ))
+            .with_code("spending_limit_unvalued");
+            state.events.publish(DomainEvent::PolicyViolation {
+                user_id: Some(wallet.owner_user_id.clone()),
+                policy: "spending_limit",
+                detail: e.message.clone(),
+            });
+            return Err(e);
+        }
+        return Ok(reservation(in_flight));
+    };
+
+    // Indexed sends are counted from the index; older ones count for nothing.
+    let tx_db = state
+        .tx_db
+        .as_ref()
+        .expect("transaction database must be configured");
+    let (month_start, _) = period_bounds(LimitPeriod::Monthly, now);
+    in_flight.retain(|s| {
+        s.at >= month_start && !matches!(tx_db.get_transaction(&s.tx_hash), Ok(Some(_)))
+    });
+
+    let result = check_allowances(
+        &allowances(state, wallet, &prices, now, &in_flight)?,
+        requested,
+    );
+    if let Err(e) = result {
+        state.events.publish(DomainEvent::PolicyViolation {
+            user_id: Some(wallet.owner_user_id.clone()),
+            policy: "spending_limit",
+            detail: e.message.clone(),
+        });
+        return Err(e);
+    }
+    Ok(reservation(in_flight))
+}
+
+/// Hold off a key rotation of `wallet` until the returned reservation is
+/// dropped, for signing paths that are not limited.
+pub(crate) async fn hold_for_signing(
+    state: &AppState,
+    wallet: &WalletMetadata,
+) -> Result<SpendingReservation, ApiError> {
+    reserve_spending(state, wallet, &[], NETWORK_FUJI).await
+}
+
+/// Refuse sends that would exceed a spending limit, without reserving them.
+pub(crate) async fn check_spending_limits(
+    state: &AppState,
+    wallet: &WalletMetadata,
+    sends: &[(&str, &str)],
+    network: &str,
+) -> Result<(), ApiError> {
+    reserve_spending(state, wallet, sends, network)
+        .await
+        .map(drop)
+}
+
+/// Normalize a limit to two decimals, or refuse it.
+fn parse_limit(field: &str, value: Option<&str>) -> Result<Option<String>, ApiError> {
+    let Some(value) = value.map(str::trim).filter(|v| !v.is_empty()) else {
+        return Ok(None);
+    };
+    match parse_amount_to_minor(value) {
+        Ok((normalized, minor)) if minor > 0 => Ok(Some(normalized)),
+        _ => Err(ApiError::bad_request(format!(
+            "{field} must be a positive EUR amount with at most two decimals"
+        ))),
+    }
+}
+
+fn limits_from_request(
+    admin: &AuthenticatedUser,
+    body: &SpendingLimitsRequest,
+) -> Result<StoredSpendingLimits, ApiError> {
+    let daily_eur = parse_limit("daily_eur", body.daily_eur.as_deref())?;
+    let monthly_eur = parse_limit("monthly_eur", body.monthly_eur.as_deref())?;
+    if daily_eur.is_none() && monthly_eur.is_none() {
+        return Err(ApiError::bad_request(
+            "Set daily_eur, monthly_eur or both; delete the limits to remove them",
+        ));
+    }
+    Ok(StoredSpendingLimits {
+        daily_eur,
+        monthly_eur,
+        updated_at: Utc::now(),
+        updated_by: admin.user_id.clone(),
+    })
+}
+
+fn log_limits_change(
+    state: &AppState,
+    admin_user_id: &str,
+    resource_type: &str,
+    resource_id: &str,
+    old: Option<&StoredSpendingLimits>,
+    new: Option<&StoredSpendingLimits>,
+) {
+    let event = AuditEvent::config_changed(
+        old.and_then(|l| serde_json::to_value(l).ok()),
+        new.and_then(|l| serde_json::to_value(l).ok()),
+    )
+    .with_user(admin_user_id)
+    .with_resource(resource_type, resource_id);
+    let _ = AuditRepository::new(state.storage()).log(&event);
+}
+
+/// A wallet the admin's tenant may see.
+fn admin_wallet(
+    state: &AppState,
+    admin: &AuthenticatedUser,
+    wallet_id: &str,
+) -> Result<WalletMetadata, ApiError> {
+    WalletRepository::new(state.storage())
+        .get(wallet_id)
+        .ok()
+        .filter(|w| admin.sees_tenant(w.tenant_id.as_deref()))
+        .ok_or_else(|| ApiError::not_found(format!("Wallet {wallet_id} not found")))
+}
+
+/// Get a user's spending limits (platform admin only).
+#[utoipa::path(
+    get,
+    path = "/v1/admin/limits/users/{user_id}",
+    tag = "Admin",
+    params(("user_id" = String, Path, description = "User ID")),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Limits", body = StoredSpendingLimits),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not authorized (platform admin required)"),
+        (status = 404, description = "No limits set")
+    )
+)]
+pub async fn get_user_limits(
+    AdminOnly(admin): AdminOnly,
+    State(state): State<AppState>,
+    Path(user_id): Path<String>,
+) -> Result<Json<StoredSpendingLimits>, ApiError> {
+    require_platform_admin(&admin)?;
+    SpendingLimitRepository::new(state.storage())
+        .get_user(&user_id)
+        .context("Failed to load spending limits")?
+        .map(Json)
+        .ok_or_else(|| ApiError::not_found("No spending limits set"))
+}
+
+/// Set a user's spending limits across all their wallets (platform admin
+/// only).
+#[utoipa::path(
+    put,
+    path = "/v1/admin/limits/users/{user_id}",
+    tag = "Admin",
+    params(("user_id" = String, Path, description = "User ID")),
+    request_body = SpendingLimitsRequest,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Limits saved", body = StoredSpendingLimits),
+        (status = 400, description = "Invalid amount"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not authorized (platform admin required)")
+    )
+)]
+pub async fn put_user_limits(
+    AdminOnly(admin): AdminOnly,
+    State(state): State<AppState>,
+    Path(user_id): Path<String>,
+    Json(body): Json<SpendingLimitsRequest>,
+) -> Result<Json<StoredSpendingLimits>, ApiError> {
+    require_platform_admin(&admin)?;
+    let limits = limits_from_request(&admin, &body)?;
+    let repo = SpendingLimitRepository::new(state.storage());
+    let old = repo.get_user(&user_id).ok().flatten();
+    repo.set_user(&user_id, &limits)
+        .context("Failed to save spending limits")?;
+    log_limits_change(
+        &state,
+        &admin.user_id,
+        "user_spending_limits",
+        &user_id,
+        old.as_ref(),
+        Some(&limits),
+    );
+    Ok(Json(limits))
+}
+
+/// Remove a user's spending limits (platform admin only).
+#[utoipa::path(
+    delete,
+    path = "/v1/admin/limits/users/{user_id}",
+    tag = "Admin",
+    params(("user_id" = String, Path, description = "User ID")),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 204, description = "Limits removed"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not authorized (platform admin required)"),
+        (status = 404, description = "No limits set")
+    )
+)]
+pub async fn delete_user_limits(
+    AdminOnly(admin): AdminOnly,
+    State(state): State<AppState>,
+    Path(user_id): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    require_platform_admin(&admin)?;
+    let repo = SpendingLimitRepository::new(state.storage());
+    let old = repo
+        .get_user(&user_id)
+        .context("Failed to load spending limits")?
+        .ok_or_else(|| ApiError::not_found("No spending limits set"))?;
+    repo.delete_user(&user_id)
+        .context("Failed to delete spending limits")?;
+    log_limits_change(
+        &state,
+        &admin.user_id,
+        "user_spending_limits",
+        &user_id,
+        Some(&old),
+        None,
+    );
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Get a wallet's spending limits (admin only).
+#[utoipa::path(
+    get,
+    path = "/v1/admin/limits/wallets/{wallet_id}",
+    tag = "Admin",
+    params(("wallet_id" = String, Path, description = "Wallet ID")),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Limits", body = StoredSpendingLimits),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not authorized (admin required)"),
+        (status = 404, description = "Wallet not found or no limits set")
+    )
+)]
+pub async fn get_wallet_limits(
+    AdminOnly(admin): AdminOnly,
+    State(state): State<AppState>,
+    Path(wallet_id): Path<String>,
+) -> Result<Json<StoredSpendingLimits>, ApiError> {
+    admin_wallet(&state, &admin, &wallet_id)?;
+    SpendingLimitRepository::new(state.storage())
+        .get_wallet(&wallet_id)
+        .context("Failed to load spending limits")?
+        .map(Json)
+        .ok_or_else(|| ApiError::not_found("No spending limits set"))
+}
+
+/// Set a wallet's spending limits (admin only).
+#[utoipa::path(
+    put,
+    path = "/v1/admin/limits/wallets/{wallet_id}",
+    tag = "Admin",
+    params(("wallet_id" = String, Path, description = "Wallet ID")),
+    request_body = SpendingLimitsRequest,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Limits saved", body = StoredSpendingLimits),
+        (status = 400, description = "Invalid amount"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not authorized (admin required)"),
+        (status = 404, description = "Wallet not found")
+    )
+)]
+pub async fn put_wallet_limits(
+    AdminOnly(admin): AdminOnly,
+    State(state): State<AppState>,
+    Path(wallet_id): Path<String>,
+    Json(body): Json<SpendingLimitsRequest>,
+) -> Result<Json<StoredSpendingLimits>, ApiError> {
+    admin_wallet(&state, &admin, &wallet_id)?;
+    let limits = limits_from_request(&admin, &body)?;
+    let repo = SpendingLimitRepository::new(state.storage());
+    let old = repo.get_wallet(&wallet_id).ok().flatten();
+    repo.set_wallet(&wallet_id, &limits)
+        .context("Failed to save spending limits")?;
+    log_limits_change(
+        &state,
+        &admin.user_id,
+        "wallet_spending_limits",
+        &wallet_id,
+        old.as_ref(),
+        Some(&limits),
+    );
+    Ok(Json(limits))
+}
+
+/// Remove a wallet's spending limits (admin only).
+#[utoipa::path(
+    delete,
+    path = "/v1/admin/limits/wallets/{wallet_id}",
+    tag = "Admin",
+    params(("wallet_id" = String, Path, description = "Wallet ID")),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 204, description = "Limits removed"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not authorized (admin required)"),
+        (status = 404, description = "Wallet not found or no limits set")
+    )
+)]
+pub async fn delete_wallet_limits(
+    AdminOnly(admin): AdminOnly,
+    State(state): State<AppState>,
+    Path(wallet_id): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    admin_wallet(&state, &admin, &wallet_id)?;
+    let repo = SpendingLimitRepository::new(state.storage());
+    let old = repo
+        .get_wallet(&wallet_id)
+        .context("Failed to load spending limits")?
+        .ok_or_else(|| ApiError::not_found("No spending limits set"))?;
+    repo.delete_wallet(&wallet_id)
+        .context("Failed to delete spending limits")?;
+    log_limits_change(
+        &state,
+        &admin.user_id,
+        "wallet_spending_limits",
+        &wallet_id,
+        Some(&old),
+        None,
+    );
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Spending allowances that apply to a wallet.
+#[utoipa::path(
+    get,
+    path = "/v1/wallets/{wallet_id}/limits",
+    tag = "Transactions",
+    params(("wallet_id" = String, Path, description = "Wallet ID")),
+    security(("bearer" = [])),
+    responses(
+        (status = 200, description = "Allowances", body = SpendingAllowanceResponse),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not wallet owner"),
+        (status = 404, description = "Wallet not found")
+    )
+)]
+pub async fn get_spending_allowance(
+    Auth(user): Auth,
+    State(state): State<AppState>,
+    Path(wallet_id): Path<String>,
+) -> Result<Json<SpendingAllowanceResponse>, ApiError> {
+    let wallet = WalletRepository::new(state.storage()).get(&wallet_id)?;
+    if !wallet.is_owned_by(&user) {
+        return Err(ApiError::forbidden("You do not own this wallet").with_code("wallet_not_owned"));
+    }
+    if wallet.status == WalletStatus::Deleted {
+        return Err(ApiError::not_found("Wallet not found").with_code("wallet_not_found"));
+    }
+    let in_flight = state
+        .spending_reservations
+        .lock(&wallet.owner_user_id)
+        .await;
+    let allowances = allowances(
+        &state,
+        &wallet,
+        &load_prices(&state),
+        Utc::now(),
+        &in_flight,
+    )?
+    .iter()
+    .map(Allowance::to_response)
+    .collect();
+    Ok(Json(SpendingAllowanceResponse {
+        wallet_id,
+        allowances,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::Role;
+    use crate::blockchain::REUR_TOKEN;
+    use crate::storage::StoredTransaction;
+
+    fn user(user_id: &str, role: Role) -> AuthenticatedUser {
+        AuthenticatedUser {
+            user_id: user_id.to_string(),
+            role,
+            session_id: None,
+            issuer: "https://test.clerk.dev".into(),
+            expires_at: Utc::now().timestamp() + 3600,
+            tenant_id: None,
+        }
+    }
+
+    fn wallet(state: &AppState, wallet_id: &str, address: &str) -> WalletMetadata {
+        let wallet = WalletMetadata {
+            wallet_id: wallet_id.to_string(),
+            owner_user_id: "user_1".to_string(),
+            public_address: address.to_string(),
+            created_at: Utc::now(),
+            status: WalletStatus::Active,
+            label: None,
+            email_lookup_key: None,
+            email_sha256: None,
+            account_type: Default::default(),
+            smart_account: None,
+            lock: None,
+            deleted_at: None,
+            purged_at: None,
+            funded_at: None,
+            tenant_id: None,
+        };
+        WalletRepository::new(state.storage())
+            .create(&wallet, b"test_key")
+            .unwrap();
+        wallet
+    }
+
+    fn sent_reur(state: &AppState, hash: &str, from: &str, amount: &str, age: Duration) {
+        let mut tx = StoredTransaction::new_pending(
+            hash.to_string(),
+            "w".to_string(),
+            None,
+            from.to_string(),
+            "0x2222222222222222222222222222222222222222".to_string(),
+            amount.to_string(),
+            TokenType::Erc20(REUR_TOKEN.fuji_address.unwrap().to_string()),
+            NETWORK_FUJI.to_string(),
+            String::new(),
+        );
+        tx.created_at = Utc::now() - age;
+        tx.status = TxStatus::Confirmed;
+        state
+            .tx_db
+            .as_ref()
+            .unwrap()
+            .upsert_transaction(&tx, &[(from.to_string(), "sent")])
+            .unwrap();
+    }
+
+    #[test]
+    fn months_reset_on_the_first() {
+        let now = Utc.with_ymd_and_hms(2026, 12, 31, 23, 0, 0).unwrap();
+        let (start, next) = period_bounds(LimitPeriod::Monthly, now);
+        assert_eq!(start, Utc.with_ymd_and_hms(2026, 12, 1, 0, 0, 0).unwrap());
+        assert_eq!(next, Utc.with_ymd_and_hms(2027, 1, 1, 0, 0, 0).unwrap());
+        let (start, next) = period_bounds(LimitPeriod::Daily, now);
+        assert_eq!(start, Utc.with_ymd_and_hms(2026, 12, 31, 0, 0, 0).unwrap());
+        assert_eq!(next, Utc.with_ymd_and_hms(2027, 1, 1, 0, 0, 0).unwrap());
+    }
+
+    #[tokio::test]
+    async fn sends_past_a_limit_are_refused_with_the_allowance() {
+        let state = AppState::default();
+        let reur = REUR_TOKEN.fuji_address.unwrap();
+        let addr_a = "0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
+        let addr_b = "0xbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb";
+        let wallet_a = wallet(&state, "w_a", addr_a);
+        wallet(&state, "w_b", addr_b);
+        sent_reur(&state, "0x01", addr_a, "30.00", Duration::seconds(1));
+        sent_reur(&state, "0x02", addr_b, "50.00", Duration::seconds(1));
+        sent_reur(&state, "0x03", addr_a, "999.00", Duration::days(40));
+
+        // No limits: anything goes.
+        check_spending_limits(&state, &wallet_a, &[(reur, "10000")], NETWORK_FUJI)
+            .await
+            .unwrap();
+
+        let admin = user("admin_1", Role::Admin);
+        let _ = put_user_limits(
+            AdminOnly(admin.clone()),
+            State(state.clone()),
+            Path("user_1".to_string()),
+            Json(SpendingLimitsRequest {
+                daily_eur: Some("100".into()),
+                monthly_eur: None,
+            }),
+        )
+        .await
+        .unwrap();
+        let _ = put_wallet_limits(
+            AdminOnly(admin),
+            State(state.clone()),
+            Path("w_a".to_string()),
+            Json(SpendingLimitsRequest {
+                daily_eur: None,
+                monthly_eur: Some("1000.00".into()),
+            }),
+        )
+        .await
+        .unwrap();
+
+        // 80.00 of the user's 100.00 is spent across both wallets.
+        check_spending_limits(&state, &wallet_a, &[(reur, "20")], NETWORK_FUJI)
+            .await
+            .unwrap();
+        let err = check_spending_limits(&state, &wallet_a, &[(reur, "20.01")], NETWORK_FUJI)
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(err.code, Some("spending_limit_exceeded"));
+        let details = err.details.unwrap();
+        assert_eq!(details["scope"], "user");
+        assert_eq!(details["remaining_eur"], "20.00");
+        assert_eq!(details["requested_eur"], "20.01");
+        // Unpriced tokens are not counted.
+        check_spending_limits(&state, &wallet_a, &[("0x1234", "500")], NETWORK_FUJI)
+            .await
+            .unwrap();
+
+        let Json(response) = get_spending_allowance(
+            Auth(user("user_1", Role::Client)),
+            State(state.clone()),
+            Path("w_a".to_string()),
+        )
+        .await
+        .unwrap();
+        let spent: Vec<_> = response
+            .allowances
+            .iter()
+            .map(|a| (a.scope, a.spent_eur.as_str()))
+            .collect();
+        assert_eq!(
+            spent,
+            [(LimitScope::User, "80.00"), (LimitScope::Wallet, "30.00")]
+        );
+    }
+
+    #[tokio::test]
+    async fn reservations_count_until_indexed() {
+        let state = AppState::default();
+        let reur = REUR_TOKEN.fuji_address.unwrap();
+        let addr_a = "0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
+        let wallet_a = wallet(&state, "w_a", addr_a);
+        let _ = put_wallet_limits(
+            AdminOnly(user("admin_1", Role::Admin)),
+            State(state.clone()),
+            Path("w_a".to_string()),
+            Json(SpendingLimitsRequest {
+                daily_eur: Some("100".into()),
+                monthly_eur: None,
+            }),
+        )
+        .await
+        .unwrap();
+
+        // A second send waits for the first reservation.
+        let first = reserve_spending(&state, &wallet_a, &[(reur, "60")], NETWORK_FUJI)
+            .await
+            .unwrap();
+        let second = {
+            let (state, wallet) = (state.clone(), wallet_a.clone());
+            tokio::spawn(async move {
+                reserve_spending(&state, &wallet, &[(reur, "60")], NETWORK_FUJI)
+                    .await
+                    .map(drop)
+            })
+        };
+        tokio::task::yield_now().await;
+        assert!(!second.is_finished());
+        first.commit("0x0a");
+        let err = second.await.unwrap().unwrap_err();
+        assert_eq!(err.code, Some("spending_limit_exceeded"));
+
+        // Approvals of unlimited amounts are refused under a limit.
+        let err = check_spending_limits(&state, &wallet_a, &[(reur, "unlimited")], NETWORK_FUJI)
+            .await
+            .unwrap_err();
+        assert_eq!(err.code, Some("spending_limit_exceeded"));
+
+        // Once indexed, the send counts once.
+        sent_reur(&state, "0x0a", addr_a, "60", Duration::seconds(1));
+        check_spending_limits(&state, &wallet_a, &[(reur, "40")], NETWORK_FUJI)
+            .await
+            .unwrap();
+        let err = check_spending_limits(&state, &wallet_a, &[(reur, "40.01")], NETWORK_FUJI)
+            .await
+            .unwrap_err();
+        assert_eq!(err.code, Some("spending_limit_exceeded"));
+    }
+
+    #[tokio::test]
+    async fn unvalued_sends_on_other_networks_are_refused_under_a_limit() {
+        let state = AppState::default();
+        let wallet_a = wallet(&state, "w_a", "0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa");
+        let sends = [("native", "1000"), ("0x1234", "500")];
+
+        // No limits: nothing to value against.
+        check_spending_limits(&state, &wallet_a, &sends, "base-sepolia")
+            .await
+            .unwrap();
+
+        let _ = put_wallet_limits(
+            AdminOnly(user("admin_1", Role::Admin)),
+            State(state.clone()),
+            Path("w_a".to_string()),
+            Json(SpendingLimitsRequest {
+                daily_eur: Some("100".into()),
+                monthly_eur: None,
+            }),
+        )
+        .await
+        .unwrap();
+        for send in sends {
+            let err = check_spending_limits(&state, &wallet_a, &[send], "base-sepolia")
+                .await
+                .unwrap_err();
+            assert_eq!(err.status, StatusCode::UNPROCESSABLE_ENTITY);
+            assert_eq!(err.code, Some("spending_limit_unvalued"));
+        }
+        // Unpriced tokens on Fuji are still not counted.
+        check_spending_limits(&state, &wallet_a, &[("0x1234", "500")], NETWORK_FUJI)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn limits_must_be_positive_amounts() {
+        let state = AppState::default();
+        let err = put_user_limits(
+            AdminOnly(user("admin_1", Role::Admin)),
+            State(state),
+            Path("user_1".to_string()),
+            Json(SpendingLimitsRequest {
+                daily_eur: Some("0".into()),
+                monthly_eur: None,
+            }),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+    }
+}
@@ -6,11 +6,10 @@ use axum::{
     extract::Path,
     http::{header, StatusCode},
     response::{IntoResponse, Response},
-    routing::{delete, get, post},
+    routing::{delete, get, post, put},
     Json, Router,
 };
 use std::sync::Arc;
-use tower_http::cors::{AllowOrigin, CorsLayer};
 use utoipa::OpenApi;
 
 use crate::{
@@ -22,27 +21,171 @@ use crate::{
     },
     state::AppState,
     storage::{
-        FiatDirection, FiatRequestStatus, StoredFiatRequest, StoredTransaction, TokenType, TxStatus,
+        ClaimStatus, EscrowActor, EscrowPaymentStatus, EscrowTransition, FiatDirection,
+        FiatRequestStatus, StoredFiatRequest, StoredTransaction, TokenType, TxStatus,
     },
 };
 
 use crate::discovery;
 
+pub mod activity_stream;
+pub mod address_format;
+pub mod address_map;
 pub mod admin;
+pub mod admin_activity;
+pub mod admin_bootstrap;
+pub mod admin_integrity;
+pub mod admin_journal;
+pub mod admin_key_rotation;
+pub mod admin_overview;
+pub mod admin_reports;
+pub mod alerts;
+pub mod allowances;
+pub mod api_keys;
+pub mod auto_topup;
 pub mod balance;
 pub mod bookmarks;
+pub mod bridge;
+pub mod canary;
+pub mod categories;
+pub mod claims;
+#[cfg(feature = "dev")]
+pub mod dev_faucet;
+pub mod dr;
+pub mod escrow;
+pub mod events;
+pub mod feature_flags;
 pub mod fiat;
+pub mod fiat_beneficiaries;
+pub mod fiat_card;
+pub mod fiat_destination_kyc;
+pub mod fiat_mandates;
+pub mod fiat_name_check;
+pub mod fiat_return;
 pub mod health;
+pub(crate) mod iban;
+pub mod insights;
+pub mod key_ceremony;
+pub mod limits;
+pub mod nfts;
+pub mod orphans;
 pub mod payment_links;
+pub mod permits;
+pub mod portfolio;
+pub mod rate_limit;
+pub mod recipients;
+pub mod reserve_queue;
 pub mod resolve;
+pub mod response_shaping;
+pub mod schema_bundle;
+pub mod security_headers;
+pub mod send_holds;
+pub mod soft_quotas;
+pub mod tax_report;
+pub mod tenants;
+pub mod tokens;
 pub mod transactions;
+pub mod tx_descriptions;
+pub mod tx_proofs;
+pub mod tx_replacement;
+pub mod usage;
 pub mod users;
 pub mod wallets;
+pub mod watch_only;
+pub mod webhooks;
+
+/// Where the Swagger UI and OpenAPI document are served.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DocsListener {
+    /// On the API listener (default).
+    Api,
+    /// On their own port, keeping them off the API listener.
+    Separate(u16),
+    /// Not served.
+    Disabled,
+}
+
+impl DocsListener {
+    /// From `DOCS_ENABLED` and `DOCS_PORT`.
+    pub fn from_env() -> Result<Self, String> {
+        Self::from_lookup(|name| std::env::var(name).ok())
+    }
+
+    pub fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Result<Self, String> {
+        let enabled = lookup("DOCS_ENABLED")
+            .map(|v| !matches!(v.to_ascii_lowercase().as_str(), "0" | "false" | "no"))
+            .unwrap_or(true);
+        if !enabled {
+            return Ok(Self::Disabled);
+        }
+        match lookup("DOCS_PORT").filter(|v| !v.trim().is_empty()) {
+            Some(port) => port
+                .trim()
+                .parse()
+                .map(Self::Separate)
+                .map_err(|e| format!("DOCS_PORT: {e}")),
+            None => Ok(Self::Api),
+        }
+    }
+}
+
+/// API router; the docs are included only for [`DocsListener::Api`].
+pub fn router(state: AppState, docs: DocsListener) -> Router {
+    // Admin operations that move reserve funds, replace keys or override
+    // decisions also require an operator signature (see
+    // `auth::request_signing`).
+    let signed_admin_routes = Router::new()
+        .route(
+            "/admin/tx-database/rebuild",
+            post(admin::rebuild_tx_database),
+        )
+        .route(
+            "/admin/escrows/{escrow_id}/resolve",
+            post(escrow::resolve_escrow),
+        )
+        .route(
+            "/admin/fiat/reserve/topup",
+            post(fiat::topup_fiat_reserve_admin),
+        )
+        .route(
+            "/admin/fiat/reserve/queue/{job_id}/resolve",
+            post(reserve_queue::resolve_reserve_job),
+        )
+        .route(
+            "/admin/fiat/requests/{request_id}/name-review",
+            post(fiat_name_check::decide_name_review),
+        )
+        .route(
+            "/admin/fiat/service-wallet/ceremony/activate",
+            post(key_ceremony::activate_key_ceremony),
+        )
+        .route(
+            "/admin/wallets/{wallet_id}/restore",
+            post(admin::restore_wallet),
+        )
+        .route(
+            "/admin/wallets/{wallet_id}/rotate-key",
+            post(admin_key_rotation::rotate_wallet_key),
+        )
+        .route(
+            "/admin/indexer/address-map",
+            put(address_map::update_address_map),
+        )
+        .route_layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            crate::auth::request_signing::require_admin_signature,
+        ));
 
-pub fn router(state: AppState) -> Router {
     let v1_routes = Router::new()
         // User endpoints (auth required)
         .route("/users/me", get(users::get_current_user))
+        .route("/users/me/sessions", get(users::list_my_sessions))
+        .route("/users/me/features", get(feature_flags::list_my_features))
+        .route("/users/me/usage", get(usage::get_my_usage))
+        .route(
+            "/users/me/send-hold-settings",
+            get(send_holds::get_send_hold_settings).put(send_holds::put_send_hold_settings),
+        )
         // Wallet lifecycle endpoints (auth required)
         .route(
             "/wallets",
@@ -52,20 +195,58 @@ pub fn router(state: AppState) -> Router {
             "/wallets/{wallet_id}",
             get(wallets::get_wallet).delete(wallets::delete_wallet),
         )
+        .route("/wallets/{wallet_id}/lock", post(wallets::lock_wallet))
+        .route("/wallets/{wallet_id}/unlock", post(wallets::unlock_wallet))
         // Wallet balance endpoints
         .route(
             "/wallets/{wallet_id}/balance",
             get(balance::get_wallet_balance),
         )
+        .route(
+            "/wallets/{wallet_id}/balance/at",
+            get(balance::get_wallet_balance_at),
+        )
         // Transaction endpoints
         .route(
             "/wallets/{wallet_id}/estimate",
             post(transactions::estimate_gas),
         )
+        .route(
+            "/network/{network}/fees",
+            get(transactions::get_network_fees),
+        )
         .route(
             "/wallets/{wallet_id}/send",
             post(transactions::send_transaction),
         )
+        .route("/wallets/{wallet_id}/batch", post(transactions::send_batch))
+        .route(
+            "/wallets/{wallet_id}/send-holds",
+            get(send_holds::list_send_holds),
+        )
+        .route(
+            "/wallets/{wallet_id}/send-holds/{hold_id}/confirm",
+            post(send_holds::confirm_send_hold),
+        )
+        .route(
+            "/wallets/{wallet_id}/send-holds/{hold_id}/cancel",
+            post(send_holds::cancel_send_hold),
+        )
+        .route("/wallets/{wallet_id}/permits", post(permits::create_permit))
+        .route(
+            "/wallets/{wallet_id}/permits/permit2-approval",
+            post(permits::approve_permit2),
+        )
+        .route(
+            "/wallets/{wallet_id}/approve",
+            post(allowances::approve_token),
+        )
+        .route(
+            "/wallets/{wallet_id}/allowances",
+            get(allowances::get_allowances),
+        )
+        .route("/wallets/{wallet_id}/nfts", get(nfts::list_nfts))
+        .route("/wallets/{wallet_id}/nfts/send", post(nfts::send_nft))
         .route(
             "/wallets/{wallet_id}/transactions",
             get(transactions::list_transactions),
@@ -74,6 +255,89 @@ pub fn router(state: AppState) -> Router {
             "/wallets/{wallet_id}/transactions/{tx_hash}",
             get(transactions::get_transaction_status),
         )
+        .route(
+            "/wallets/{wallet_id}/transactions/{tx_hash}/wait",
+            get(transactions::wait_for_transaction_status),
+        )
+        .route(
+            "/wallets/{wallet_id}/transactions/{tx_hash}/proof",
+            get(tx_proofs::get_transaction_proof),
+        )
+        .route(
+            "/wallets/{wallet_id}/transactions/{tx_hash}/speedup",
+            post(tx_replacement::speed_up_transaction),
+        )
+        .route(
+            "/wallets/{wallet_id}/transactions/{tx_hash}/cancel",
+            post(tx_replacement::cancel_transaction),
+        )
+        .route("/stream/activity", get(activity_stream::stream_activity))
+        .route("/ws", get(activity_stream::activity_socket))
+        .route(
+            "/wallets/{wallet_id}/recipients/recent",
+            get(recipients::list_recent_recipients),
+        )
+        .route(
+            "/wallets/{wallet_id}/transactions/{tx_hash}/category",
+            put(categories::assign_transaction_category),
+        )
+        .route(
+            "/wallets/{wallet_id}/tax-report",
+            get(tax_report::get_tax_report),
+        )
+        .route(
+            "/wallets/{wallet_id}/insights",
+            get(insights::get_wallet_insights),
+        )
+        // Transaction category endpoints
+        .route(
+            "/categories",
+            get(categories::list_categories).post(categories::create_category),
+        )
+        .route("/categories/spending", get(categories::get_spending))
+        .route(
+            "/categories/{category_id}",
+            put(categories::update_category).delete(categories::delete_category),
+        )
+        .route("/portfolio", get(portfolio::get_portfolio))
+        // Claimable transfer endpoints
+        .route("/wallets/{wallet_id}/claims", post(claims::create_claim))
+        .route("/claims", get(claims::list_claims))
+        .route("/claims/{claim_id}/reclaim", post(claims::reclaim_claim))
+        // Claim link preview (no JWT — the signed token is the capability)
+        .route("/claim-links/{token}", get(claims::preview_claim))
+        .route("/claim-links/{token}/claim", post(claims::redeem_claim))
+        // Escrowed payment endpoints
+        .route("/wallets/{wallet_id}/escrows", post(escrow::create_escrow))
+        .route("/escrows", get(escrow::list_escrows))
+        .route("/escrows/{escrow_id}", get(escrow::get_escrow))
+        .route(
+            "/escrows/{escrow_id}/request-release",
+            post(escrow::request_release),
+        )
+        .route(
+            "/escrows/{escrow_id}/approve",
+            post(escrow::approve_release),
+        )
+        .route(
+            "/escrows/{escrow_id}/dispute",
+            post(escrow::dispute_release),
+        )
+        .route("/escrows/{escrow_id}/refund", post(escrow::refund_escrow))
+        // Cross-chain bridge endpoints
+        .route(
+            "/wallets/{wallet_id}/bridge/quote",
+            post(bridge::quote_bridge),
+        )
+        .route(
+            "/wallets/{wallet_id}/bridge/transfers",
+            post(bridge::initiate_bridge),
+        )
+        .route("/bridge/transfers", get(bridge::list_bridge_transfers))
+        .route(
+            "/bridge/transfers/{transfer_id}",
+            get(bridge::get_bridge_transfer),
+        )
         // Bookmark endpoints
         .route(
             "/bookmarks",
@@ -101,16 +365,208 @@ pub fn router(state: AppState) -> Router {
             "/fiat/providers/truelayer/webhook",
             post(fiat::truelayer_webhook),
         )
+        // Card PSP webhook (no JWT — authenticates via signed payload)
+        .route(
+            "/fiat/providers/card/webhook",
+            post(fiat_card::card_webhook),
+        )
         .route("/fiat/onramp/requests", post(fiat::create_onramp_request))
         .route("/fiat/offramp/requests", post(fiat::create_offramp_request))
         .route("/fiat/requests", get(fiat::list_fiat_requests))
         .route("/fiat/requests/{request_id}", get(fiat::get_fiat_request))
+        .route(
+            "/fiat/requests/{request_id}/kyc-check",
+            post(fiat_destination_kyc::recheck_destination_kyc),
+        )
+        .route("/fiat/return", get(fiat_return::verify_fiat_return))
+        .route(
+            "/fiat/beneficiaries",
+            get(fiat_beneficiaries::list_fiat_beneficiaries)
+                .post(fiat_beneficiaries::create_fiat_beneficiary),
+        )
+        .route(
+            "/fiat/beneficiaries/{beneficiary_id}",
+            get(fiat_beneficiaries::get_fiat_beneficiary)
+                .delete(fiat_beneficiaries::delete_fiat_beneficiary),
+        )
+        .route(
+            "/fiat/mandates",
+            get(fiat_mandates::list_fiat_mandates).post(fiat_mandates::create_fiat_mandate),
+        )
+        .route(
+            "/fiat/mandates/{mandate_id}",
+            get(fiat_mandates::get_fiat_mandate),
+        )
+        .route(
+            "/fiat/mandates/{mandate_id}/revoke",
+            post(fiat_mandates::revoke_fiat_mandate),
+        )
+        .route(
+            "/fiat/mandates/{mandate_id}/onramp",
+            post(fiat_mandates::create_mandate_onramp),
+        )
+        .route(
+            "/wallets/{wallet_id}/auto-topup",
+            get(auto_topup::get_auto_topup)
+                .put(auto_topup::put_auto_topup)
+                .delete(auto_topup::delete_auto_topup),
+        )
+        // Wallet alert rules
+        .route(
+            "/wallets/{wallet_id}/alerts",
+            get(alerts::get_wallet_alerts).put(alerts::put_wallet_alerts),
+        )
+        // Spending allowances left under admin-set limits
+        .route(
+            "/wallets/{wallet_id}/limits",
+            get(limits::get_spending_allowance),
+        )
+        // Watch-only external addresses
+        .route(
+            "/watch-only",
+            get(watch_only::list_watch_only).post(watch_only::create_watch_only),
+        )
+        .route(
+            "/watch-only/{watch_id}",
+            delete(watch_only::delete_watch_only),
+        )
+        .route(
+            "/watch-only/{watch_id}/balance",
+            get(watch_only::get_watch_only_balance),
+        )
+        .route(
+            "/watch-only/{watch_id}/transactions",
+            get(watch_only::list_watch_only_transactions),
+        )
+        // Event catalog (no JWT — public contract for integrators)
+        .route("/events/catalog", get(events::get_event_catalog))
+        // Tenant branding (no JWT — read before sign-in)
+        .route(
+            "/tenants/{tenant_id}/branding",
+            get(tenants::get_tenant_branding),
+        )
+        // Webhook signing keys (no JWT — public keys for partners)
+        .route(
+            "/webhooks/signing-key",
+            get(webhooks::get_webhook_signing_key),
+        )
+        // Webhook subscriptions (JWT required)
+        .route(
+            "/webhooks",
+            get(webhooks::list_webhooks).post(webhooks::create_webhook),
+        )
+        .route(
+            "/webhooks/{subscription_id}",
+            delete(webhooks::delete_webhook),
+        )
+        .route(
+            "/webhooks/{subscription_id}/deliveries",
+            get(webhooks::list_webhook_deliveries),
+        )
+        .route(
+            "/wallets/{wallet_id}/deposit-webhook",
+            get(webhooks::get_deposit_webhook)
+                .put(webhooks::put_deposit_webhook)
+                .delete(webhooks::delete_deposit_webhook),
+        )
+        // Initial admin designation (setup token, any authenticated user)
+        .route(
+            "/admin/bootstrap",
+            post(admin_bootstrap::bootstrap_admin).delete(admin_bootstrap::revoke_admin_bootstrap),
+        )
         // Admin endpoints (admin role required)
+        .route("/admin/overview", get(admin_overview::get_admin_overview))
+        .route(
+            "/admin/integrity",
+            get(admin_integrity::get_integrity_report),
+        )
+        .route(
+            "/admin/reports/dormant",
+            get(admin_reports::get_dormant_report),
+        )
+        .route(
+            "/admin/reports/proof-of-reserve",
+            get(admin_reports::get_proof_of_reserve),
+        )
+        .route(
+            "/admin/journal/entries",
+            get(admin_journal::list_journal_entries),
+        )
+        .route(
+            "/admin/journal/trial-balance",
+            get(admin_journal::get_trial_balance),
+        )
+        .route(
+            "/admin/wallets/{wallet_id}/heatmap",
+            get(admin_reports::get_wallet_heatmap),
+        )
+        .route(
+            "/admin/feature-flags",
+            get(feature_flags::list_feature_flags),
+        )
+        .route(
+            "/admin/api-keys",
+            get(api_keys::list_api_keys).post(api_keys::create_api_key),
+        )
+        .route("/admin/api-keys/{key_id}", delete(api_keys::revoke_api_key))
+        .route(
+            "/admin/feature-flags/{key}",
+            put(feature_flags::put_feature_flag).delete(feature_flags::delete_feature_flag),
+        )
+        .route("/admin/tokens", get(tokens::list_tokens))
+        .route(
+            "/admin/tokens/{network}/{address}",
+            put(tokens::put_token).delete(tokens::delete_token),
+        )
+        .route(
+            "/admin/tx-description-templates",
+            get(tx_descriptions::get_description_templates)
+                .put(tx_descriptions::put_description_templates),
+        )
+        .route(
+            "/admin/limits/users/{user_id}",
+            get(limits::get_user_limits)
+                .put(limits::put_user_limits)
+                .delete(limits::delete_user_limits),
+        )
+        .route(
+            "/admin/limits/wallets/{wallet_id}",
+            get(limits::get_wallet_limits)
+                .put(limits::put_wallet_limits)
+                .delete(limits::delete_wallet_limits),
+        )
+        .route("/admin/tenants", get(tenants::list_tenant_configs))
+        .route(
+            "/admin/tenants/{tenant_id}/config",
+            get(tenants::get_tenant_config)
+                .put(tenants::put_tenant_config)
+                .delete(tenants::delete_tenant_config),
+        )
         .route("/admin/stats", get(admin::get_system_stats))
         .route("/admin/wallets", get(admin::list_all_wallets))
         .route("/admin/users", get(admin::list_all_users))
         .route("/admin/audit/events", get(admin::query_audit_logs))
+        .route(
+            "/admin/audit/admin-activity",
+            get(admin_activity::get_admin_activity),
+        )
         .route("/admin/health", get(admin::get_detailed_health))
+        .route("/admin/storage/orphans", get(orphans::list_orphans))
+        .route(
+            "/admin/indexer/address-map",
+            get(address_map::get_address_map),
+        )
+        .route("/admin/dr/verify", post(dr::verify_dr))
+        .route("/admin/canary", get(canary::get_canary_report))
+        .route(
+            "/admin/webhooks/signing-key/rotate",
+            post(webhooks::rotate_webhook_signing_key),
+        )
+        .route(
+            "/admin/webhooks/deliveries",
+            get(webhooks::admin_list_webhook_deliveries),
+        )
+        .route("/admin/escrows", get(escrow::admin_list_escrows))
         .route(
             "/admin/wallets/{wallet_id}/suspend",
             post(admin::suspend_wallet),
@@ -123,10 +579,32 @@ pub fn router(state: AppState) -> Router {
             "/admin/fiat/service-wallet",
             get(fiat::get_fiat_service_wallet),
         )
+        .route(
+            "/admin/fiat/reserve/queue",
+            get(reserve_queue::list_reserve_queue),
+        )
+        .route(
+            "/admin/fiat/reconciliation",
+            get(fiat::get_fiat_reconciliation_report),
+        )
+        .route(
+            "/admin/fiat/service-wallet/ceremony",
+            get(key_ceremony::get_key_ceremony)
+                .post(key_ceremony::start_key_ceremony)
+                .delete(key_ceremony::abort_key_ceremony),
+        )
+        .route(
+            "/admin/fiat/service-wallet/ceremony/shares",
+            post(key_ceremony::submit_key_share),
+        )
         .route(
             "/admin/fiat/requests/{request_id}/sync",
             post(fiat::sync_fiat_request_admin),
         )
+        .route(
+            "/admin/fiat/name-reviews",
+            get(fiat_name_check::list_name_reviews),
+        )
         // Admin discovery peer management
         .route("/admin/peers/self", get(admin::get_self_node_info))
         .route("/admin/peers/self/test", post(admin::test_self_ratls))
@@ -136,7 +614,11 @@ pub fn router(state: AppState) -> Router {
             axum::routing::put(admin::update_peer).delete(admin::remove_peer),
         )
         .route("/admin/peers/{node_id}/test", post(admin::test_peer_ratls))
-        .with_state(state.clone());
+        .merge(signed_admin_routes);
+    // Test funds for Fuji (dev builds only)
+    #[cfg(feature = "dev")]
+    let v1_routes = v1_routes.route("/dev/faucet", post(dev_faucet::request_faucet));
+    let v1_routes = v1_routes.with_state(state.clone());
 
     // Internal discovery routes (Phase 2): peer-to-peer VOPRF evaluate/lookup.
     // These live outside /v1 because they use RA-TLS mutual authentication
@@ -148,24 +630,94 @@ pub fn router(state: AppState) -> Router {
             .route("/internal/discovery/lookup", p(discovery::api::lookup))
     };
 
-    Router::new()
+    let security = security_headers::SecurityConfig::from_env();
+    let routes = Router::new()
         // Health endpoints (no auth required, but need state for JWKS check)
         .route("/health", get(health::health))
         .route("/health/live", get(health::liveness))
         .route("/health/ready", get(health::readiness))
+        // Public status page, rate limited like the sensitive /v1 routes
+        .route(
+            "/status",
+            get(health::status).layer(axum::middleware::from_fn_with_state(
+                state.clone(),
+                rate_limit::rate_limit,
+            )),
+        )
         // API v1 routes
-        .nest("/v1", v1_routes)
-        // Swagger/OpenAPI docs
+        .nest(
+            "/v1",
+            v1_routes
+                .layer(axum::middleware::from_fn_with_state(
+                    state.clone(),
+                    crate::auth::replay::reject_replays,
+                ))
+                .layer(axum::middleware::from_fn_with_state(
+                    state.clone(),
+                    rate_limit::rate_limit,
+                ))
+                .layer(axum::middleware::from_fn(soft_quotas::soft_quota_headers))
+                .layer(axum::middleware::from_fn_with_state(
+                    state.clone(),
+                    response_shaping::shape_experimental_fields,
+                ))
+                .layer(axum::middleware::from_fn(
+                    address_format::checksum_addresses,
+                )),
+        );
+    let routes = if docs == DocsListener::Api {
+        routes.merge(docs_routes())
+    } else {
+        routes
+    };
+    routes
+        .layer(axum::middleware::from_fn(crate::i18n::localize_errors))
+        .layer(axum::middleware::from_fn_with_state(
+            Arc::new(security.clone()),
+            security_headers::set_security_headers,
+        ))
+        .layer(security_headers::cors_layer(
+            &security,
+            state.storage().clone(),
+        ))
+        .with_state(state)
+}
+
+/// Swagger UI and OpenAPI document alone, for [`DocsListener::Separate`].
+pub fn docs_router() -> Router {
+    let security = security_headers::SecurityConfig::from_env();
+    docs_routes().layer(axum::middleware::from_fn_with_state(
+        Arc::new(security),
+        security_headers::set_security_headers,
+    ))
+}
+
+/// Swagger/OpenAPI docs.
+fn docs_routes<S: Clone + Send + Sync + 'static>() -> Router<S> {
+    Router::new()
         .route("/api-doc/openapi.json", get(openapi_json))
+        .route(schema_bundle::SCHEMA_BUNDLE_PATH, get(schemas_json))
         .route("/docs", get(swagger_ui_index))
         .route("/docs/", get(swagger_ui_index))
         .route("/docs/{*rest}", get(swagger_ui_asset))
-        .layer(build_cors_layer())
-        .with_state(state)
+}
+
+/// The OpenAPI document, with dev-only endpoints when built with `dev`.
+fn api_doc() -> utoipa::openapi::OpenApi {
+    #[allow(unused_mut)]
+    let mut doc = ApiDoc::openapi();
+    #[cfg(feature = "dev")]
+    doc.merge(dev_faucet::FaucetApiDoc::openapi());
+    doc
 }
 
 async fn openapi_json() -> Json<utoipa::openapi::OpenApi> {
-    Json(ApiDoc::openapi())
+    Json(api_doc())
+}
+
+/// Request and response models as JSON Schema, for frontend codegen.
+async fn schemas_json() -> Json<serde_json::Value> {
+    Json(schema_bundle::schema_bundle(&api_doc()))
 }
 
 async fn swagger_ui_index() -> Response {
@@ -219,45 +771,77 @@ fn serve_swagger_ui(path: &str) -> Response {
     }
 }
 
-/// Build CORS layer from environment configuration.
-///
-/// - If `CORS_ALLOWED_ORIGINS` is set, only those origins are allowed.
-///   Multiple origins can be comma-separated (e.g., `https://app.example.com,https://staging.example.com`).
-/// - If not set, falls back to permissive CORS (development only).
-fn build_cors_layer() -> CorsLayer {
-    if let Ok(origins) = std::env::var("CORS_ALLOWED_ORIGINS") {
-        let allowed: Vec<_> = origins
-            .split(',')
-            .map(|s| s.trim().parse().expect("Invalid CORS origin"))
-            .collect();
-        tracing::info!(origins = %origins, "CORS: restricting to configured origins");
-        CorsLayer::new()
-            .allow_origin(AllowOrigin::list(allowed))
-            .allow_methods(tower_http::cors::Any)
-            .allow_headers(tower_http::cors::Any)
-    } else {
-        tracing::warn!("CORS_ALLOWED_ORIGINS not set - using permissive CORS (development only)");
-        CorsLayer::permissive()
-    }
-}
-
 #[derive(OpenApi)]
 #[openapi(
     paths(
         // User endpoints
         users::get_current_user,
+        users::list_my_sessions,
+        feature_flags::list_my_features,
+        usage::get_my_usage,
+        send_holds::get_send_hold_settings,
+        send_holds::put_send_hold_settings,
         // Wallet lifecycle endpoints
         wallets::create_wallet,
         wallets::list_wallets,
         wallets::get_wallet,
         wallets::delete_wallet,
+        wallets::lock_wallet,
+        wallets::unlock_wallet,
         // Wallet balance endpoints
         balance::get_wallet_balance,
+        balance::get_wallet_balance_at,
         // Transaction endpoints
         transactions::estimate_gas,
+        transactions::get_network_fees,
         transactions::send_transaction,
+        transactions::send_batch,
+        send_holds::list_send_holds,
+        send_holds::confirm_send_hold,
+        send_holds::cancel_send_hold,
+        permits::create_permit,
+        permits::approve_permit2,
+        allowances::approve_token,
+        allowances::get_allowances,
+        nfts::list_nfts,
+        nfts::send_nft,
         transactions::list_transactions,
         transactions::get_transaction_status,
+        transactions::wait_for_transaction_status,
+        tx_proofs::get_transaction_proof,
+        tx_replacement::speed_up_transaction,
+        tx_replacement::cancel_transaction,
+        activity_stream::stream_activity,
+        activity_stream::activity_socket,
+        recipients::list_recent_recipients,
+        tax_report::get_tax_report,
+        // Transaction category endpoints
+        categories::list_categories,
+        categories::create_category,
+        categories::update_category,
+        categories::delete_category,
+        categories::assign_transaction_category,
+        categories::get_spending,
+        insights::get_wallet_insights,
+        // Claimable transfer endpoints
+        claims::create_claim,
+        claims::list_claims,
+        claims::preview_claim,
+        claims::redeem_claim,
+        claims::reclaim_claim,
+        // Escrowed payment endpoints
+        escrow::create_escrow,
+        escrow::list_escrows,
+        escrow::get_escrow,
+        escrow::request_release,
+        escrow::approve_release,
+        escrow::dispute_release,
+        escrow::refund_escrow,
+        // Cross-chain bridge endpoints
+        bridge::quote_bridge,
+        bridge::initiate_bridge,
+        bridge::list_bridge_transfers,
+        bridge::get_bridge_transfer,
         // Bookmark endpoints
         bookmarks::list_bookmarks,
         bookmarks::create_bookmark,
@@ -270,74 +854,414 @@ fn build_cors_layer() -> CorsLayer {
         // Fiat endpoints
         fiat::list_fiat_providers,
         fiat::truelayer_webhook,
+        fiat_card::card_webhook,
         fiat::create_onramp_request,
         fiat::create_offramp_request,
         fiat::list_fiat_requests,
         fiat::get_fiat_request,
+        fiat_destination_kyc::recheck_destination_kyc,
+        fiat_return::verify_fiat_return,
+        fiat_beneficiaries::create_fiat_beneficiary,
+        fiat_beneficiaries::list_fiat_beneficiaries,
+        fiat_beneficiaries::get_fiat_beneficiary,
+        fiat_beneficiaries::delete_fiat_beneficiary,
+        fiat_mandates::create_fiat_mandate,
+        fiat_mandates::list_fiat_mandates,
+        fiat_mandates::get_fiat_mandate,
+        fiat_mandates::revoke_fiat_mandate,
+        fiat_mandates::create_mandate_onramp,
+        auto_topup::put_auto_topup,
+        auto_topup::get_auto_topup,
+        auto_topup::delete_auto_topup,
+        alerts::put_wallet_alerts,
+        alerts::get_wallet_alerts,
+        limits::get_spending_allowance,
+        portfolio::get_portfolio,
+        watch_only::create_watch_only,
+        watch_only::list_watch_only,
+        watch_only::delete_watch_only,
+        watch_only::get_watch_only_balance,
+        watch_only::list_watch_only_transactions,
         fiat::get_fiat_service_wallet,
         fiat::sync_fiat_request_admin,
+        fiat_name_check::list_name_reviews,
+        fiat_name_check::decide_name_review,
+        fiat::topup_fiat_reserve_admin,
+        fiat::get_fiat_reconciliation_report,
+        reserve_queue::list_reserve_queue,
+        reserve_queue::resolve_reserve_job,
+        // Reserve key ceremony endpoints
+        key_ceremony::start_key_ceremony,
+        key_ceremony::get_key_ceremony,
+        key_ceremony::submit_key_share,
+        key_ceremony::activate_key_ceremony,
+        key_ceremony::abort_key_ceremony,
+        // Event catalog
+        events::get_event_catalog,
+        // Tenant branding
+        tenants::get_tenant_branding,
+        // Webhook signing key endpoints
+        webhooks::get_webhook_signing_key,
+        webhooks::rotate_webhook_signing_key,
+        webhooks::create_webhook,
+        webhooks::list_webhooks,
+        webhooks::delete_webhook,
+        webhooks::list_webhook_deliveries,
+        webhooks::put_deposit_webhook,
+        webhooks::get_deposit_webhook,
+        webhooks::delete_deposit_webhook,
+        webhooks::admin_list_webhook_deliveries,
+        escrow::admin_list_escrows,
+        escrow::resolve_escrow,
         // Admin endpoints
+        admin_bootstrap::bootstrap_admin,
+        admin_bootstrap::revoke_admin_bootstrap,
+        admin_overview::get_admin_overview,
+        admin_integrity::get_integrity_report,
+        admin_reports::get_dormant_report,
+        admin_reports::get_wallet_heatmap,
+        admin_reports::get_proof_of_reserve,
+        admin_journal::list_journal_entries,
+        admin_journal::get_trial_balance,
+        feature_flags::list_feature_flags,
+        feature_flags::put_feature_flag,
+        feature_flags::delete_feature_flag,
+        tokens::list_tokens,
+        tokens::put_token,
+        tokens::delete_token,
+        tx_descriptions::get_description_templates,
+        tx_descriptions::put_description_templates,
+        api_keys::list_api_keys,
+        api_keys::create_api_key,
+        api_keys::revoke_api_key,
+        limits::get_user_limits,
+        limits::put_user_limits,
+        limits::delete_user_limits,
+        limits::get_wallet_limits,
+        limits::put_wallet_limits,
+        limits::delete_wallet_limits,
+        tenants::list_tenant_configs,
+        tenants::get_tenant_config,
+        tenants::put_tenant_config,
+        tenants::delete_tenant_config,
+        orphans::list_orphans,
         admin::get_system_stats,
         admin::list_all_wallets,
         admin::list_all_users,
         admin::query_audit_logs,
+        admin_activity::get_admin_activity,
         admin::get_detailed_health,
+        admin::rebuild_tx_database,
+        address_map::get_address_map,
+        address_map::update_address_map,
+        dr::verify_dr,
+        canary::get_canary_report,
         admin::suspend_wallet,
         admin::activate_wallet,
+        admin::restore_wallet,
+        admin_key_rotation::rotate_wallet_key,
         admin::test_self_ratls,
         admin::test_peer_ratls,
         // Health endpoints
         health::health,
         health::liveness,
-        health::readiness
+        health::readiness,
+        health::status
     ),
     components(
         schemas(
             // Auth schemas
             Role,
             users::UserMeResponse,
+            users::UserSessionResponse,
+            users::UserSessionsResponse,
+            crate::storage::SessionRecord,
             // Wallet lifecycle schemas
             wallets::CreateWalletRequest,
             wallets::CreateWalletResponse,
             wallets::WalletListResponse,
             wallets::DeleteWalletResponse,
             crate::storage::WalletResponse,
+            crate::storage::WalletLock,
+            crate::storage::WalletAccountType,
+            crate::storage::SmartAccountInfo,
             crate::storage::WalletStatus,
             // Wallet balance schemas
             balance::BalanceResponse,
+            balance::HistoricalBalanceResponse,
             TokenBalance,
             WalletBalanceResponse,
             // Transaction schemas
             transactions::EstimateGasRequest,
             transactions::EstimateGasResponse,
+            transactions::NetworkFeesResponse,
+            transactions::FeeSuggestionResponse,
+            crate::blockchain::FeeSpeed,
             transactions::SendTransactionRequest,
             transactions::SendTransactionResponse,
+            transactions::BatchSendRequest,
+            transactions::BatchTransfer,
+            send_holds::SendHoldResponse,
+            crate::storage::SendHoldSettings,
+            crate::storage::SendHoldStatus,
+            crate::storage::SessionAnomaly,
+            permits::PermitStandard,
+            permits::CreatePermitRequest,
+            permits::PermitResponse,
+            permits::Permit2ApprovalRequest,
+            allowances::ApproveRequest,
+            allowances::Allowance,
+            allowances::AllowancesResponse,
+            crate::blockchain::nft::NftStandard,
+            nfts::NftItem,
+            nfts::NftListResponse,
+            nfts::SendNftRequest,
             transactions::TransactionListResponse,
             transactions::TransactionSummary,
             transactions::TransactionStatusResponse,
+            tx_proofs::ProofStatement,
+            tx_proofs::TransactionProofResponse,
+            tx_replacement::ReplacementResponse,
+            crate::blockchain::BlockReference,
+            recipients::RecentRecipient,
+            recipients::RecentRecipientsResponse,
+            tax_report::TaxReportResponse,
+            tax_report::TaxSummary,
+            tax_report::TaxDisposal,
+            tax_report::TaxIncomeItem,
+            // Transaction category schemas
+            categories::TxCategory,
+            categories::CategoryRequest,
+            categories::AssignCategoryRequest,
+            categories::TransactionCategoryResponse,
+            categories::CategorySpending,
+            categories::SpendingResponse,
+            crate::storage::UserCategory,
+            insights::CategoryInsight,
+            insights::CounterpartyInsight,
+            insights::MonthComparison,
+            insights::WalletInsightsResponse,
+            // Claimable transfer schemas
+            claims::CreateClaimRequest,
+            claims::ClaimResponse,
+            claims::ClaimListResponse,
+            claims::ClaimPreviewResponse,
+            claims::RedeemClaimRequest,
+            ClaimStatus,
+            // Escrowed payment schemas
+            escrow::CreateEscrowRequest,
+            escrow::EscrowActionRequest,
+            escrow::ResolveEscrowRequest,
+            escrow::EscrowOutcome,
+            escrow::EscrowResponse,
+            escrow::EscrowListResponse,
+            EscrowPaymentStatus,
+            EscrowActor,
+            EscrowTransition,
+            // Cross-chain bridge schemas
+            bridge::BridgeQuoteRequest,
+            bridge::BridgeQuoteResponse,
+            bridge::InitiateBridgeRequest,
+            bridge::BridgeTransferResponse,
+            bridge::BridgeTransferListResponse,
+            crate::storage::BridgeDirection,
+            crate::storage::BridgeStatus,
             StoredTransaction,
             TokenType,
             TxStatus,
             // Fiat schemas
             fiat::CreateFiatRequest,
+            fiat_return::FiatReturnClient,
+            fiat_beneficiaries::CreateFiatBeneficiaryRequest,
+            fiat_beneficiaries::FiatBeneficiaryResponse,
+            fiat_beneficiaries::FiatBeneficiaryListResponse,
+            crate::storage::FiatBeneficiaryStatus,
+            fiat_name_check::NameReviewRequest,
+            fiat_name_check::NameReviewItem,
+            fiat_name_check::NameReviewListResponse,
+            crate::storage::BeneficiaryNameCheck,
+            crate::storage::NameReview,
+            crate::storage::NameReviewDecision,
+            fiat_mandates::CreateFiatMandateRequest,
+            fiat_mandates::MandateOnRampRequest,
+            fiat_mandates::FiatMandateResponse,
+            fiat_mandates::FiatMandateListResponse,
+            crate::storage::FiatMandateStatus,
+            auto_topup::AutoTopUpRequest,
+            auto_topup::AutoTopUpResponse,
+            auto_topup::DeleteAutoTopUpResponse,
+            portfolio::PortfolioResponse,
+            portfolio::PortfolioHolding,
+            portfolio::PortfolioSource,
+            portfolio::PortfolioSourceKind,
+            watch_only::CreateWatchOnlyRequest,
+            watch_only::WatchOnlyResponse,
+            watch_only::WatchOnlyBalanceResponse,
+            crate::storage::AutoTopUpEvent,
+            crate::storage::AutoTopUpEventKind,
+            alerts::WalletAlertsRequest,
+            alerts::WalletAlertsResponse,
+            crate::storage::AlertRule,
+            crate::storage::AlertRuleKind,
+            crate::storage::AlertEvent,
+            crate::storage::AlertEventKind,
             fiat::FiatProviderSummary,
             fiat::FiatProviderListResponse,
             fiat::FiatRequestResponse,
+            fiat::PayoutArrivalEstimate,
+            fiat::FiatTimelineStep,
+            fiat::FiatTimelineEntry,
             fiat::FiatRequestListResponse,
             fiat::FiatServiceWalletStatusResponse,
             fiat::FiatSyncResponse,
+            fiat::FiatReserveTopUpRequest,
+            fiat::FiatReserveTopUpResponse,
+            fiat::FiatReconciliationDay,
+            fiat::FiatReconciliationReport,
+            crate::storage::GasSpendEntry,
+            reserve_queue::ReserveQueueResponse,
+            reserve_queue::ResolveReserveJobRequest,
+            crate::storage::StoredReserveSendJob,
+            crate::storage::FiatChargeback,
+            crate::storage::ClawbackStatus,
+            crate::storage::ReserveSendKind,
+            crate::storage::ReserveSendStatus,
             FiatDirection,
             FiatRequestStatus,
             StoredFiatRequest,
+            crate::storage::FiatStatusTransition,
+            crate::storage::DestinationKycCheck,
+            crate::storage::ReserveKeySource,
+            // Admin bootstrap schemas
+            admin_bootstrap::AdminBootstrapRequest,
+            admin_bootstrap::AdminBootstrapResponse,
+            admin_bootstrap::AdminBootstrapRevocation,
+            crate::storage::SetupTokenSource,
+            // Reserve key ceremony schemas
+            key_ceremony::StartKeyCeremonyRequest,
+            key_ceremony::SubmitKeyShareRequest,
+            key_ceremony::ActivateKeyCeremonyRequest,
+            key_ceremony::KeyShareSummary,
+            key_ceremony::KeyCeremonyResponse,
+            crate::storage::KeyCeremonyStatus,
+            // Activity stream schemas
+            activity_stream::ActivityEvent,
+            // Event catalog schemas
+            events::EventCatalogResponse,
+            events::EventChannel,
+            events::CatalogEvent,
+            // Webhook signing key schemas
+            webhooks::WebhookSigningKeyResponse,
+            webhooks::WebhookKeyInfo,
+            webhooks::RotateWebhookKeyRequest,
+            webhooks::CreateWebhookRequest,
+            webhooks::DepositWebhookRequest,
+            webhooks::WebhookSubscriptionResponse,
+            webhooks::WebhookListResponse,
+            webhooks::WebhookDeliveryListResponse,
+            crate::storage::StoredWebhookDelivery,
+            crate::storage::WebhookDeliveryStatus,
+            crate::storage::WebhookEventType,
             // Admin schemas
+            admin_overview::AdminOverviewResponse,
+            admin_overview::OverviewStatus,
+            admin_overview::FiatOverview,
+            admin_overview::StuckTransaction,
+            admin_overview::StuckTransactionsOverview,
+            admin_overview::IndexerOverview,
+            admin_overview::ReserveOverview,
+            admin_overview::WorkerStatus,
+            admin_overview::WorkerOverview,
+            admin_overview::ErrorOverview,
+            admin_integrity::IntegrityReportResponse,
+            admin_integrity::IntegrityCheckResult,
+            admin_integrity::IntegrityDiscrepancy,
+            admin_integrity::IntegrityCheck,
+            admin_key_rotation::KeyRotationResponse,
+            admin_key_rotation::SweptAsset,
+            admin_reports::DormantReportResponse,
+            admin_reports::DormantWallet,
+            admin_reports::OwnerContactState,
+            admin_reports::HeatmapDay,
+            admin_reports::WalletHeatmapResponse,
+            admin_reports::ReserveTokenTotal,
+            admin_reports::ReserveStatement,
+            admin_reports::ProofOfReserveResponse,
+            admin_journal::JournalEntriesResponse,
+            admin_journal::TrialBalanceResponse,
+            crate::storage::JournalEntry,
+            crate::storage::JournalPosting,
+            crate::storage::JournalAccount,
+            crate::storage::JournalSource,
+            crate::storage::PostingSide,
+            crate::storage::TrialBalance,
+            crate::storage::AssetTrialBalance,
+            crate::storage::AccountBalance,
+            feature_flags::UpsertFeatureFlagRequest,
+            feature_flags::FeatureFlagListResponse,
+            feature_flags::MyFeaturesResponse,
+            tokens::UpsertTokenRequest,
+            tokens::TokenListResponse,
+            crate::blockchain::ListedToken,
+            crate::blockchain::TokenSource,
+            crate::storage::StoredToken,
+            tx_descriptions::DescriptionTemplateRequest,
+            tx_descriptions::PutDescriptionTemplatesRequest,
+            tx_descriptions::DescriptionTemplatesResponse,
+            crate::storage::DescriptionTemplate,
+            limits::SpendingLimitsRequest,
+            limits::LimitScope,
+            limits::LimitPeriod,
+            limits::SpendingAllowance,
+            limits::SpendingAllowanceResponse,
+            crate::storage::StoredSpendingLimits,
+            tenants::UpsertTenantConfigRequest,
+            tenants::TenantProviderSummary,
+            tenants::TenantConfigResponse,
+            tenants::TenantConfigListResponse,
+            tenants::TenantBrandingResponse,
+            crate::storage::repository::TenantBranding,
+            crate::storage::repository::FeeSchedule,
+            crate::storage::repository::ProviderCredentials,
+            crate::storage::repository::TrueLayerCredentials,
+            crate::storage::repository::CardCredentials,
+            soft_quotas::QuotaWarning,
+            usage::ObjectUsage,
+            usage::UsageResponse,
+            orphans::OrphanListResponse,
+            crate::storage::StoredOrphan,
+            crate::storage::OrphanKind,
+            crate::storage::StoredFeatureFlag,
+            api_keys::CreateApiKeyRequest,
+            api_keys::CreateApiKeyResponse,
+            api_keys::ApiKeySummary,
+            api_keys::ApiKeyListResponse,
+            crate::worker_health::Worker,
             admin::SystemStatsResponse,
             admin::AdminWalletItem,
             admin::AdminWalletListResponse,
             admin::AdminUserSummary,
             admin::AdminUserListResponse,
             admin::AuditLogResponse,
+            admin_activity::AdminActivitySummary,
+            admin_activity::AdminActivityResponse,
             admin::DetailedHealthResponse,
+            admin::RebuildTxDatabaseRequest,
+            crate::indexer::rebuild::RebuildReport,
+            address_map::AddressMapEntry,
+            address_map::AddressMapResponse,
+            address_map::AddressMapEdit,
+            address_map::UpdateAddressMapRequest,
+            crate::indexer::address_map::AddressMapIssue,
+            crate::indexer::address_map::AddressMapIssueKind,
+            dr::DrStepStatus,
+            dr::DrStep,
+            dr::DrVerifyResponse,
+            canary::CanaryReportResponse,
+            crate::canary::CanaryRun,
+            crate::canary::CanaryOutcome,
             admin::StorageHealth,
+            crate::auth::jwks::JwksStats,
             admin::DiagnosticStep,
             admin::RaTlsTestResponse,
             crate::discovery::ffi::ObservedMeasurements,
@@ -357,6 +1281,9 @@ fn build_cors_layer() -> CorsLayer {
             // Health schemas
             health::HealthResponse,
             health::HealthChecks,
+            health::ComponentStatus,
+            health::StatusComponents,
+            health::StatusPageResponse,
             health::ReadyResponse
         )
     ),
@@ -364,14 +1291,26 @@ fn build_cors_layer() -> CorsLayer {
         (name = "Users", description = "User identity and authentication"),
         (name = "Wallets", description = "Wallet lifecycle management"),
         (name = "Transactions", description = "Transaction signing and sending"),
+        (name = "Claims", description = "Claimable transfers to email addresses and links"),
+        (name = "Escrow", description = "Conditional payments held in escrow between users"),
+        (name = "Permits", description = "Gasless token approvals via EIP-2612 and Permit2"),
+        (name = "Allowances", description = "On-chain ERC-20 approvals and allowance lookups"),
+        (name = "NFTs", description = "ERC-721 and ERC-1155 holdings and sends for listed collections"),
+        (name = "Bridge", description = "Cross-chain USDC transfers via CCTP burn and mint"),
+        (name = "Categories", description = "Transaction categories and monthly budgets"),
         (name = "Bookmarks", description = "Bookmark management"),
+        (name = "Watch-Only", description = "Read-only tracking of external addresses"),
         (name = "resolve", description = "Email resolution"),
         (name = "payment_links", description = "Payment link generation and resolution"),
         (name = "Fiat", description = "Fiat on-ramp/off-ramp provider integrations"),
+        (name = "Activity", description = "Server-Sent Events stream of the caller's activity"),
+        (name = "Events", description = "Catalog of emitted event types and payload schemas"),
+        (name = "Webhooks", description = "User webhook subscriptions and keys for verifying outbound webhook signatures"),
+        (name = "Tenants", description = "Public branding of white-label tenants"),
         (name = "Admin", description = "Admin-only system management"),
         (name = "Health", description = "Liveness and readiness checks")
     ),
-    modifiers(&SecurityAddon)
+    modifiers(&SecurityAddon, &ExperimentalFieldsAddon)
 )]
 struct ApiDoc;
 
@@ -381,17 +1320,35 @@ struct SecurityAddon;
 impl utoipa::Modify for SecurityAddon {
     fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
         if let Some(components) = openapi.components.as_mut() {
-            use utoipa::openapi::security::{Http, HttpAuthScheme, SecurityScheme};
+            use utoipa::openapi::security::{
+                ApiKey, ApiKeyValue, Http, HttpAuthScheme, SecurityScheme,
+            };
             let scheme = Http::builder()
                 .scheme(HttpAuthScheme::Bearer)
                 .bearer_format("JWT")
                 .description(Some("Clerk JWT token"))
                 .build();
             components.add_security_scheme("bearer", SecurityScheme::Http(scheme));
+            components.add_security_scheme(
+                "api_key",
+                SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::with_description(
+                    "X-Api-Key",
+                    "Scoped API key for service integrations",
+                ))),
+            );
         }
     }
 }
 
+/// Marks experimental response fields (see [`response_shaping`]).
+struct ExperimentalFieldsAddon;
+
+impl utoipa::Modify for ExperimentalFieldsAddon {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        response_shaping::mark_experimental_fields(openapi, response_shaping::EXPERIMENTAL_FIELDS);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -400,14 +1357,14 @@ mod tests {
 
     #[tokio::test]
     async fn router_builds_with_all_routes() {
-        let app = router(AppState::default());
+        let app = router(AppState::default(), DocsListener::Api);
         // Ensure the router can be converted into a service without panicking.
         let _ = app.into_make_service();
     }
 
     #[tokio::test]
     async fn docs_route_serves_without_redirect() {
-        let app = router(AppState::default());
+        let app = router(AppState::default(), DocsListener::Api);
         let response = app
             .clone()
             .oneshot(Request::builder().uri("/docs").body(Body::empty()).unwrap())
@@ -427,6 +1384,65 @@ mod tests {
         assert_eq!(response.status(), StatusCode::OK);
     }
 
+    #[tokio::test]
+    async fn docs_move_to_their_own_listener_or_off() {
+        let lookup = |vars: &'static [(&'static str, &'static str)]| {
+            move |name: &str| {
+                vars.iter()
+                    .find(|(key, _)| *key == name)
+                    .map(|(_, v)| v.to_string())
+            }
+        };
+        assert_eq!(
+            DocsListener::from_lookup(lookup(&[])),
+            Ok(DocsListener::Api)
+        );
+        assert_eq!(
+            DocsListener::from_lookup(lookup(&[("DOCS_PORT", "8081")])),
+            Ok(DocsListener::Separate(8081))
+        );
+        assert_eq!(
+            DocsListener::from_lookup(lookup(&[("DOCS_ENABLED", "false"), ("DOCS_PORT", "8081")])),
+            Ok(DocsListener::Disabled)
+        );
+        assert!(DocsListener::from_lookup(lookup(&[("DOCS_PORT", "docs")])).is_err());
+
+        let docs = |uri: &str| Request::builder().uri(uri).body(Body::empty()).unwrap();
+        let api = router(AppState::default(), DocsListener::Separate(8081));
+        for uri in ["/docs", "/api-doc/openapi.json"] {
+            let response = api.clone().oneshot(docs(uri)).await.unwrap();
+            assert_eq!(response.status(), StatusCode::NOT_FOUND, "{uri}");
+        }
+        let response = api.oneshot(docs("/health/live")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = docs_router()
+            .oneshot(docs("/api-doc/openapi.json"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn schema_bundle_is_served_with_the_docs() {
+        let response = docs_router()
+            .oneshot(
+                Request::builder()
+                    .uri("/api-doc/schemas.json")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let bundle = schema_bundle::schema_bundle(&api_doc());
+        assert!(bundle["$defs"]["WalletResponse"].is_object());
+        let text = bundle.to_string();
+        assert!(!text.contains("#/components/schemas/"));
+        assert!(text.contains("#/$defs/"));
+    }
+
     #[test]
     fn generate_openapi_json() {
         use std::fs;
@@ -0,0 +1,337 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! NFT holdings and sends.
+//!
+//! Only collections listed in `NFT_COLLECTIONS` are supported (see
+//! [`crate::blockchain::nft`]). Holdings come from the indexer, so a token
+//! shows up once its transfer has been indexed; ownership is re-checked on
+//! chain before a send.
+
+use alloy::primitives::U256;
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::{
+    api::{
+        claims::active_wallet,
+        limits,
+        permits::parse_address,
+        transactions::{
+            send_error, send_network, send_user_operation, sending_wallet, SendTransactionResponse,
+        },
+    },
+    auth::Auth,
+    blockchain::{
+        address_key,
+        nft::{nft_collections, transfer_calldata, NftStandard},
+        resolve_network,
+        smart_account::Call,
+        wallet_from_pem, TxBuilder,
+    },
+    error::ApiError,
+    state::AppState,
+    storage::{AuditEvent, AuditEventType, AuditRepository, WalletAccountType, WalletRepository},
+};
+
+// =============================================================================
+// Request/Response Types
+// =============================================================================
+
+/// Query parameters for listing NFTs.
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct NftListQuery {
+    /// Only list NFTs on this network.
+    #[serde(default)]
+    pub network: Option<String>,
+}
+
+/// One NFT held by a wallet.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct NftItem {
+    pub network: String,
+    /// Collection contract address (lowercase).
+    pub contract: String,
+    /// Collection name from the deployment's list.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub collection: Option<String>,
+    pub standard: NftStandard,
+    /// Token ID, decimal.
+    pub token_id: String,
+    /// Tokens held, decimal; always "1" for ERC-721.
+    pub amount: String,
+    /// Transaction that last changed the holding.
+    pub last_tx_hash: String,
+    pub last_block: u64,
+}
+
+/// NFTs held by a wallet.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct NftListResponse {
+    pub nfts: Vec<NftItem>,
+}
+
+/// Request to send an NFT.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct SendNftRequest {
+    /// Collection contract address.
+    pub contract: String,
+    /// Token ID, decimal.
+    pub token_id: String,
+    /// Recipient address.
+    pub to: String,
+    /// Tokens to send, decimal. Defaults to 1, the only value ERC-721
+    /// accepts.
+    #[serde(default)]
+    pub amount: Option<String>,
+    /// Network ID from the server's network registry.
+    #[serde(default = "default_fuji")]
+    pub network: String,
+    /// Optional gas limit override
+    #[serde(default)]
+    pub gas_limit: Option<String>,
+    /// Optional max priority fee per gas override (in wei)
+    #[serde(default)]
+    pub max_priority_fee_per_gas: Option<String>,
+}
+
+fn default_fuji() -> String {
+    "fuji".to_string()
+}
+
+fn parse_decimal(value: &str, field: &str) -> Result<U256, ApiError> {
+    U256::from_str_radix(value.trim(), 10)
+        .map_err(|_| ApiError::bad_request(format!("`{field}` must be a decimal integer")))
+}
+
+/// Number of tokens to send: 1 by default, exactly 1 for ERC-721.
+fn send_amount(standard: NftStandard, raw: Option<&str>) -> Result<U256, ApiError> {
+    let amount = raw
+        .map(|v| parse_decimal(v, "amount"))
+        .transpose()?
+        .unwrap_or(U256::from(1));
+    match standard {
+        NftStandard::Erc721 if amount != U256::from(1) => Err(ApiError::bad_request(
+            "An ERC-721 token is sent whole; `amount` must be 1",
+        )),
+        _ if amount.is_zero() => Err(ApiError::bad_request("`amount` must be positive")),
+        _ => Ok(amount),
+    }
+}
+
+// =============================================================================
+// Handlers
+// =============================================================================
+
+/// List a wallet's NFTs.
+///
+/// Returns the indexed holdings of listed collections.
+#[utoipa::path(
+    get,
+    path = "/v1/wallets/{wallet_id}/nfts",
+    tag = "NFTs",
+    params(
+        ("wallet_id" = String, Path, description = "Wallet ID"),
+        NftListQuery
+    ),
+    security(("bearer" = [])),
+    responses(
+        (status = 200, description = "NFTs held", body = NftListResponse),
+        (status = 400, description = "Unknown network"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - not wallet owner, or suspended"),
+        (status = 404, description = "Wallet not found")
+    )
+)]
+pub async fn list_nfts(
+    Auth(user): Auth,
+    State(state): State<AppState>,
+    Path(wallet_id): Path<String>,
+    Query(query): Query<NftListQuery>,
+) -> Result<Json<NftListResponse>, ApiError> {
+    active_wallet(state.storage(), &user, &wallet_id)?;
+    let network = query
+        .network
+        .as_deref()
+        .map(|raw| resolve_network(Some(raw)).map_err(ApiError::bad_request))
+        .transpose()?;
+    let tx_db = state
+        .tx_db
+        .as_ref()
+        .expect("transaction database must be configured");
+    let holdings = tx_db
+        .list_nft_holdings(&wallet_id)
+        .map_err(|e| ApiError::internal(format!("Failed to read NFTs: {e}")))?;
+
+    let nfts = holdings
+        .into_iter()
+        .filter(|h| network.as_ref().is_none_or(|n| h.network == n.id))
+        .map(|h| NftItem {
+            collection: nft_collections()
+                .get(&h.network, &h.contract)
+                .map(|c| c.name.clone()),
+            network: h.network,
+            contract: h.contract,
+            standard: h.standard,
+            token_id: h.token_id,
+            amount: h.amount,
+            last_tx_hash: h.last_tx_hash,
+            last_block: h.last_block,
+        })
+        .collect();
+    Ok(Json(NftListResponse { nfts }))
+}
+
+/// Send an NFT.
+///
+/// Broadcasts `safeTransferFrom` on the collection after checking on chain
+/// that the wallet holds the tokens.
+#[utoipa::path(
+    post,
+    path = "/v1/wallets/{wallet_id}/nfts/send",
+    tag = "NFTs",
+    params(("wallet_id" = String, Path, description = "Wallet ID")),
+    request_body = SendNftRequest,
+    security(("bearer" = [])),
+    responses(
+        (status = 200, description = "Transfer submitted", body = SendTransactionResponse),
+        (status = 400, description = "Invalid request, or collection not listed"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - not wallet owner, or suspended"),
+        (status = 404, description = "Wallet not found"),
+        (status = 422, description = "Wallet does not hold the tokens, or insufficient gas balance"),
+        (status = 503, description = "Blockchain network unavailable")
+    )
+)]
+pub async fn send_nft(
+    Auth(user): Auth,
+    State(state): State<AppState>,
+    Path(wallet_id): Path<String>,
+    Json(request): Json<SendNftRequest>,
+) -> Result<Json<SendTransactionResponse>, ApiError> {
+    let storage = state.storage();
+    let wallet = sending_wallet(storage, &user, &wallet_id)?;
+    let contract = parse_address(&request.contract, "contract")?;
+    let to = parse_address(&request.to, "to")?;
+    let network = send_network(&wallet, &request.network)?;
+    let collection = nft_collections()
+        .get(network.id, &request.contract)
+        .ok_or_else(|| {
+            ApiError::bad_request(format!(
+                "Collection {} is not listed on `{}`",
+                request.contract, network.id
+            ))
+            .with_code("nft_collection_not_listed")
+        })?;
+    let standard = collection.standard;
+    let token_id = parse_decimal(&request.token_id, "token_id")?;
+    let amount = send_amount(standard, request.amount.as_deref())?;
+    let gas_limit = request
+        .gas_limit
+        .as_deref()
+        .map(str::parse::<u64>)
+        .transpose()
+        .map_err(|_| ApiError::bad_request("Invalid gas_limit"))?;
+    let max_priority_fee = request
+        .max_priority_fee_per_gas
+        .as_deref()
+        .map(str::parse::<u128>)
+        .transpose()
+        .map_err(|_| ApiError::bad_request("Invalid max_priority_fee_per_gas"))?;
+
+    let client = state
+        .chain_client(&network)
+        .await
+        .map_err(|e| ApiError::service_unavailable(format!("Failed to connect: {e}")))?;
+    let held = client
+        .get_nft_balance(
+            standard,
+            &request.contract,
+            &wallet.public_address,
+            token_id,
+        )
+        .await
+        .map_err(|e| ApiError::service_unavailable(format!("Failed to read NFT: {e}")))?;
+    if held < amount {
+        return Err(
+            ApiError::unprocessable("Wallet does not hold the tokens to send")
+                .with_code("nft_not_owned"),
+        );
+    }
+
+    let from = parse_address(&wallet.public_address, "wallet")?;
+    let calldata = transfer_calldata(standard, from, to, token_id, amount);
+    let _signing = limits::hold_for_signing(&state, &wallet).await?;
+    let result = if wallet.account_type == WalletAccountType::SmartAccount {
+        let call = Call {
+            to: contract,
+            value: U256::ZERO,
+            data: calldata.into(),
+        };
+        send_user_operation(storage, &wallet, &[call], gas_limit, max_priority_fee).await?
+    } else {
+        let key = WalletRepository::new(storage)
+            .read_private_key(&wallet_id)
+            .map_err(|e| ApiError::internal(format!("Failed to read private key: {e}")))?;
+        let eth_wallet = wallet_from_pem(&key)
+            .map_err(|e| ApiError::internal(format!("Failed to create signer: {e}")))?;
+        TxBuilder::new(network.clone(), eth_wallet)
+            .await
+            .map_err(|e| ApiError::service_unavailable(format!("Failed to connect: {e}")))?
+            .with_nonce_manager(state.nonce_manager.clone())
+            .send_contract_call(
+                &request.contract,
+                calldata,
+                None,
+                gas_limit,
+                max_priority_fee,
+            )
+            .await
+            .map_err(send_error)?
+    };
+
+    let event = AuditEvent::new(AuditEventType::TransactionBroadcast)
+        .with_user(&user.user_id)
+        .with_resource(&wallet_id, "wallet")
+        .with_details(serde_json::json!({
+            "tx_hash": result.tx_hash,
+            "kind": "nft_transfer",
+            "contract": address_key(&request.contract),
+            "token_id": token_id.to_string(),
+            "amount": amount.to_string(),
+            "to": request.to,
+            "network": network.id,
+        }));
+    let _ = AuditRepository::new(storage).log(&event);
+
+    Ok(Json(SendTransactionResponse {
+        tx_hash: result.tx_hash,
+        status: "pending".to_string(),
+        explorer_url: result.explorer_url,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn erc721_sends_move_exactly_one_token() {
+        assert_eq!(
+            send_amount(NftStandard::Erc721, None).unwrap(),
+            U256::from(1)
+        );
+        assert!(send_amount(NftStandard::Erc721, Some("2")).is_err());
+        assert_eq!(
+            send_amount(NftStandard::Erc1155, Some("25")).unwrap(),
+            U256::from(25)
+        );
+        assert!(send_amount(NftStandard::Erc1155, Some("0")).is_err());
+        assert!(send_amount(NftStandard::Erc1155, Some("1.5")).is_err());
+    }
+}
@@ -0,0 +1,64 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Orphaned storage artifacts found by the
+//! [`orphan_sweeper`](crate::orphan_sweeper), listed for admins before they
+//! are removed.
+
+use axum::{extract::State, Json};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::{
+    api::admin::require_platform_admin,
+    auth::AdminOnly,
+    error::{ApiError, StorageContext},
+    orphan_sweeper::SweepConfig,
+    state::AppState,
+    storage::{OrphanRepository, StoredOrphan},
+};
+
+/// Orphaned artifacts awaiting removal.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct OrphanListResponse {
+    /// Oldest first.
+    pub orphans: Vec<StoredOrphan>,
+    pub total: usize,
+    /// How long an artifact stays listed before it is removed.
+    pub confirm_window_hours: i64,
+    /// How long a deleted wallet's records are kept.
+    pub retention_days: i64,
+}
+
+/// List orphaned storage artifacts (admin only).
+///
+/// Each is removed by the first sweep after its `remove_after` that still
+/// finds it orphaned.
+#[utoipa::path(
+    get,
+    path = "/v1/admin/storage/orphans",
+    tag = "Admin",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Orphaned artifacts", body = OrphanListResponse),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not authorized (platform admin required)")
+    )
+)]
+pub async fn list_orphans(
+    AdminOnly(admin): AdminOnly,
+    State(state): State<AppState>,
+) -> Result<Json<OrphanListResponse>, ApiError> {
+    require_platform_admin(&admin)?;
+    let orphans = OrphanRepository::new(state.storage())
+        .list_all()
+        .context("Failed to list orphaned artifacts")?;
+    let config = SweepConfig::from_env();
+    Ok(Json(OrphanListResponse {
+        total: orphans.len(),
+        orphans,
+        confirm_window_hours: config.confirm_window.num_hours(),
+        retention_days: config.retention.num_days(),
+    }))
+}
@@ -58,7 +58,7 @@ pub async fn create_payment_link(
     // Get and verify wallet ownership
     let wallet = wallet_repo
         .get(&wallet_id)
-        .map_err(|_| ApiError::not_found("Wallet not found"))?;
+        .map_err(|_| ApiError::not_found("Wallet not found").with_code("wallet_not_found"))?;
 
     wallet
         .verify_ownership(&user)
@@ -0,0 +1,542 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Gasless token approvals.
+//!
+//! Instead of broadcasting an `approve` transaction, the wallet signs a
+//! permit inside the enclave and hands it to the caller, who passes it to
+//! the spender (a swap router, payment contract, ...). The spender submits
+//! it with its own call, so approving costs the wallet no gas and no extra
+//! round-trip.
+//!
+//! EIP-2612 tokens get a native `permit`. Other tokens use Permit2, which
+//! needs the Permit2 contract approved once per token; the
+//! `permit2-approval` endpoint sends that transaction. See
+//! [`crate::blockchain::permit`].
+//!
+//! Permits are signed by the wallet key, so smart-account wallets (whose
+//! funds sit at the account address) cannot use them; they batch `approve`
+//! with the spending call instead.
+//!
+//! Like approvals, permits are checked against the spending limits: a permit
+//! may not grant more than the limits still allow, and the unlimited Permit2
+//! approval is refused while a limit applies to the token.
+
+use std::str::FromStr;
+
+use alloy::{
+    primitives::{Address, U256},
+    sol_types::SolCall,
+};
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use chrono::{Duration, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::{
+    api::{
+        claims::active_wallet, limits, transactions::SendTransactionResponse,
+        wallets::ensure_unlocked,
+    },
+    auth::{Auth, AuthenticatedUser},
+    blockchain::{
+        avax_fuji,
+        erc20::IERC20,
+        parse_amount,
+        permit::{permit2_address, permit2_domain, sign_eip2612, sign_permit2},
+        signing::signer_from_pem,
+        wallet_from_pem, AvaxClient, TxBuilder,
+    },
+    error::ApiError,
+    state::AppState,
+    storage::{
+        AuditEvent, AuditEventType, AuditRepository, WalletAccountType, WalletMetadata,
+        WalletRepository,
+    },
+};
+
+const DEFAULT_PERMIT_TTL_SECS: u64 = 3600;
+const MAX_PERMIT_TTL_SECS: u64 = 30 * 24 * 3600;
+
+// =============================================================================
+// Request/Response Types
+// =============================================================================
+
+/// Permit standard used for a signature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PermitStandard {
+    /// The token's own `permit` (EIP-2612).
+    Eip2612,
+    /// Uniswap Permit2 `AllowanceTransfer.permit`.
+    Permit2,
+}
+
+/// Request to sign a permit.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct CreatePermitRequest {
+    /// ERC-20 contract address.
+    pub token: String,
+    /// Contract allowed to spend the tokens.
+    pub spender: String,
+    /// Allowance in human-readable units (e.g. "25.00").
+    pub amount: String,
+    /// Seconds the permit stays valid (default 3600, max 30 days).
+    #[serde(default)]
+    pub valid_for_secs: Option<u64>,
+    /// Force a standard. By default EIP-2612 is used when the token
+    /// supports it, Permit2 otherwise.
+    #[serde(default)]
+    pub standard: Option<PermitStandard>,
+}
+
+/// A signed permit, ready to be passed to the spender.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PermitResponse {
+    pub standard: PermitStandard,
+    pub token: String,
+    /// Address that signed the permit and holds the tokens.
+    pub owner: String,
+    pub spender: String,
+    /// Allowance in the token's smallest unit.
+    pub value: String,
+    pub nonce: String,
+    /// Unix time after which the signature is rejected. For Permit2 this is
+    /// both `sigDeadline` and the allowance `expiration`.
+    pub deadline: i64,
+    /// 65-byte `r || s || v` signature, hex-encoded.
+    pub signature: String,
+    pub v: u8,
+    pub r: String,
+    pub s: String,
+    /// Permit2 contract to submit the permit to; Permit2 only.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub permit2: Option<String>,
+}
+
+/// Request to approve the Permit2 contract for a token.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct Permit2ApprovalRequest {
+    /// ERC-20 contract address.
+    pub token: String,
+}
+
+// =============================================================================
+// Helpers
+// =============================================================================
+
+pub(crate) fn parse_address(value: &str, field: &str) -> Result<Address, ApiError> {
+    let address = Address::from_str(value)
+        .map_err(|_| ApiError::bad_request(format!("`{field}` is not a valid address")))?;
+    if address.is_zero() {
+        return Err(ApiError::bad_request(format!(
+            "`{field}` must not be the zero address"
+        )));
+    }
+    Ok(address)
+}
+
+/// Load a wallet that can sign permits: active, owned, unlocked, and an EOA.
+fn permit_wallet(
+    state: &AppState,
+    user: &AuthenticatedUser,
+    wallet_id: &str,
+) -> Result<WalletMetadata, ApiError> {
+    let wallet = active_wallet(state.storage(), user, wallet_id)?;
+    ensure_unlocked(&wallet)?;
+    if wallet.account_type == WalletAccountType::SmartAccount {
+        return Err(ApiError::unprocessable(
+            "Smart-account wallets cannot sign permits; batch the approval with the spending call",
+        ));
+    }
+    Ok(wallet)
+}
+
+// =============================================================================
+// Handlers
+// =============================================================================
+
+/// Sign a gasless token approval.
+///
+/// Signs an EIP-2612 or Permit2 permit granting `spender` an allowance of
+/// `amount`. Nothing is broadcast; the signature is returned for the caller
+/// to pass to the spender.
+#[utoipa::path(
+    post,
+    path = "/v1/wallets/{wallet_id}/permits",
+    tag = "Permits",
+    params(("wallet_id" = String, Path, description = "Wallet ID")),
+    request_body = CreatePermitRequest,
+    security(("bearer" = [])),
+    responses(
+        (status = 200, description = "Permit signed", body = PermitResponse),
+        (status = 400, description = "Invalid request"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - not wallet owner, or suspended"),
+        (status = 404, description = "Wallet not found"),
+        (status = 409, description = "Permit2 is not approved for this token"),
+        (status = 422, description = "Smart-account wallet, spending limit exceeded, or the token does not support EIP-2612"),
+        (status = 503, description = "Blockchain network unavailable")
+    )
+)]
+pub async fn create_permit(
+    Auth(user): Auth,
+    State(state): State<AppState>,
+    Path(wallet_id): Path<String>,
+    Json(request): Json<CreatePermitRequest>,
+) -> Result<Json<PermitResponse>, ApiError> {
+    let wallet = permit_wallet(&state, &user, &wallet_id)?;
+    let token = parse_address(&request.token, "token")?;
+    let spender = parse_address(&request.spender, "spender")?;
+    let ttl = request.valid_for_secs.unwrap_or(DEFAULT_PERMIT_TTL_SECS);
+    if ttl == 0 || ttl > MAX_PERMIT_TTL_SECS {
+        return Err(ApiError::bad_request(format!(
+            "valid_for_secs must be between 1 and {MAX_PERMIT_TTL_SECS}"
+        )));
+    }
+    let deadline = (Utc::now() + Duration::seconds(ttl as i64)).timestamp();
+
+    let network = avax_fuji();
+    let client = AvaxClient::new(network.clone())
+        .await
+        .map_err(|e| ApiError::service_unavailable(format!("Failed to connect: {e}")))?;
+    let decimals = client
+        .get_token_decimals(&request.token)
+        .await
+        .map_err(|e| ApiError::service_unavailable(format!("Failed to read token: {e}")))?;
+    let value = parse_amount(&request.amount, decimals)
+        .map_err(|e| ApiError::bad_request(format!("Invalid amount: {e}")))?;
+    let _reservation = limits::reserve_spending(
+        &state,
+        &wallet,
+        &[(&request.token, &request.amount)],
+        network.id,
+    )
+    .await?;
+
+    let storage = state.storage();
+    let key = WalletRepository::new(storage)
+        .read_private_key(&wallet_id)
+        .map_err(|e| ApiError::internal(format!("Failed to read private key: {e}")))?;
+    let signer = signer_from_pem(&key)
+        .map_err(|e| ApiError::internal(format!("Failed to create signer: {e}")))?;
+    let owner = signer.address();
+
+    let domain = client
+        .get_permit_domain(token)
+        .await
+        .map_err(|e| ApiError::service_unavailable(format!("Failed to read token: {e}")))?;
+    let standard = match (request.standard, &domain) {
+        (Some(standard), _) => standard,
+        (None, Some(_)) => PermitStandard::Eip2612,
+        (None, None) => PermitStandard::Permit2,
+    };
+
+    let (nonce, signature, permit2) = match standard {
+        PermitStandard::Eip2612 => {
+            let domain = domain.ok_or_else(|| {
+                ApiError::unprocessable("This token does not support EIP-2612 permits")
+            })?;
+            let nonce = client
+                .get_permit_nonce(token, owner)
+                .await
+                .map_err(|e| ApiError::service_unavailable(format!("Failed to read nonce: {e}")))?;
+            let (_, signature) = sign_eip2612(
+                &signer,
+                &domain,
+                spender,
+                value,
+                nonce,
+                U256::from(deadline),
+            )
+            .map_err(|e| ApiError::internal(e.to_string()))?;
+            (nonce, signature, None)
+        }
+        PermitStandard::Permit2 => {
+            let permit2 = permit2_address();
+            let approved = client
+                .get_token_allowance(&request.token, &wallet.public_address, &permit2.to_string())
+                .await
+                .map_err(|e| {
+                    ApiError::service_unavailable(format!("Failed to read allowance: {e}"))
+                })?;
+            if approved < value {
+                return Err(ApiError::conflict(
+                    "Permit2 is not approved for this token; call permit2-approval once first",
+                ));
+            }
+            let nonce = client
+                .get_permit2_nonce(permit2, owner, token, spender)
+                .await
+                .map_err(|e| ApiError::service_unavailable(format!("Failed to read nonce: {e}")))?;
+            let (_, signature) = sign_permit2(
+                &signer,
+                &permit2_domain(network.chain_id, permit2),
+                token,
+                spender,
+                value,
+                deadline as u64,
+                nonce,
+                U256::from(deadline),
+            )
+            .map_err(|e| ApiError::bad_request(e.to_string()))?;
+            (U256::from(nonce), signature, Some(permit2))
+        }
+    };
+
+    let event = AuditEvent::new(AuditEventType::PermitSigned)
+        .with_user(&user.user_id)
+        .with_resource(&wallet_id, "wallet")
+        .with_details(serde_json::json!({
+            "standard": standard,
+            "token": request.token,
+            "spender": request.spender,
+            "value": value.to_string(),
+            "deadline": deadline,
+        }));
+    let _ = AuditRepository::new(storage).log(&event);
+
+    let bytes = signature.as_bytes();
+    Ok(Json(PermitResponse {
+        standard,
+        token: request.token,
+        owner: wallet.public_address,
+        spender: request.spender,
+        value: value.to_string(),
+        nonce: nonce.to_string(),
+        deadline,
+        signature: alloy::hex::encode_prefixed(bytes),
+        v: bytes[64],
+        r: alloy::hex::encode_prefixed(&bytes[..32]),
+        s: alloy::hex::encode_prefixed(&bytes[32..64]),
+        permit2: permit2.map(|address| address.to_string()),
+    }))
+}
+
+/// Approve the Permit2 contract for a token.
+///
+/// One-time `approve(Permit2, max)` transaction for tokens without EIP-2612.
+/// Afterwards every allowance for the token is a Permit2 signature.
+#[utoipa::path(
+    post,
+    path = "/v1/wallets/{wallet_id}/permits/permit2-approval",
+    tag = "Permits",
+    params(("wallet_id" = String, Path, description = "Wallet ID")),
+    request_body = Permit2ApprovalRequest,
+    security(("bearer" = [])),
+    responses(
+        (status = 200, description = "Approval submitted", body = SendTransactionResponse),
+        (status = 400, description = "Invalid request"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - not wallet owner, or suspended"),
+        (status = 404, description = "Wallet not found"),
+        (status = 422, description = "Smart-account wallet, insufficient gas balance, or spending limit exceeded"),
+        (status = 503, description = "Blockchain network unavailable")
+    )
+)]
+pub async fn approve_permit2(
+    Auth(user): Auth,
+    State(state): State<AppState>,
+    Path(wallet_id): Path<String>,
+    Json(request): Json<Permit2ApprovalRequest>,
+) -> Result<Json<SendTransactionResponse>, ApiError> {
+    let wallet = permit_wallet(&state, &user, &wallet_id)?;
+    parse_address(&request.token, "token")?;
+    let permit2 = permit2_address();
+    let network = avax_fuji();
+    let _reservation = limits::reserve_spending(
+        &state,
+        &wallet,
+        &[(&request.token, "unlimited")],
+        network.id,
+    )
+    .await?;
+
+    let storage = state.storage();
+    let key = WalletRepository::new(storage)
+        .read_private_key(&wallet_id)
+        .map_err(|e| ApiError::internal(format!("Failed to read private key: {e}")))?;
+    let eth_wallet = wallet_from_pem(&key)
+        .map_err(|e| ApiError::internal(format!("Failed to create signer: {e}")))?;
+    let builder = TxBuilder::new(network, eth_wallet)
+        .await
+        .map_err(|e| ApiError::service_unavailable(format!("Failed to connect: {e}")))?
+        .with_nonce_manager(state.nonce_manager.clone());
+    let calldata = IERC20::approveCall {
+        spender: permit2,
+        amount: U256::MAX,
+    }
+    .abi_encode();
+    let result = builder
+        .send_contract_call(&request.token, calldata, None, None, None)
+        .await
+        .map_err(|e| {
+            if e.to_string().contains("insufficient funds") {
+                ApiError::unprocessable("Insufficient balance for transaction")
+                    .with_code("insufficient_balance")
+            } else {
+                ApiError::service_unavailable(format!("Transaction failed: {e}"))
+            }
+        })?;
+
+    let event = AuditEvent::new(AuditEventType::TransactionBroadcast)
+        .with_user(&user.user_id)
+        .with_resource(&wallet_id, "wallet")
+        .with_details(serde_json::json!({
+            "tx_hash": result.tx_hash,
+            "kind": "permit2_approval",
+            "token": request.token,
+            "spender": permit2.to_string(),
+        }));
+    let _ = AuditRepository::new(storage).log(&event);
+
+    Ok(Json(SendTransactionResponse {
+        tx_hash: result.tx_hash,
+        status: "pending".to_string(),
+        explorer_url: result.explorer_url,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::{AdminOnly, AuthenticatedUser, Role};
+    use crate::blockchain::REUR_TOKEN;
+    use crate::storage::WalletStatus;
+
+    fn auth(user_id: &str) -> Auth {
+        Auth(AuthenticatedUser {
+            user_id: user_id.to_string(),
+            role: Role::Client,
+            session_id: None,
+            issuer: "https://test.clerk.dev".to_string(),
+            expires_at: Utc::now().timestamp() + 3600,
+            tenant_id: None,
+        })
+    }
+
+    #[tokio::test]
+    async fn smart_accounts_and_bad_addresses_are_rejected_before_signing() {
+        let state = AppState::default();
+        let mut wallet = WalletMetadata {
+            wallet_id: "w-smart".to_string(),
+            owner_user_id: "user-a".to_string(),
+            public_address: "0x1111111111111111111111111111111111111111".to_string(),
+            created_at: Utc::now(),
+            status: WalletStatus::Active,
+            label: None,
+            email_lookup_key: None,
+            email_sha256: None,
+            account_type: WalletAccountType::SmartAccount,
+            smart_account: None,
+            lock: None,
+            deleted_at: None,
+            purged_at: None,
+            funded_at: None,
+            tenant_id: None,
+        };
+        let repo = WalletRepository::new(state.storage());
+        repo.create(&wallet, b"test_key").unwrap();
+        wallet.wallet_id = "w-eoa".to_string();
+        wallet.account_type = WalletAccountType::Eoa;
+        repo.create(&wallet, b"test_key").unwrap();
+
+        let request = |spender: &str| CreatePermitRequest {
+            token: "0x5425890298aed601595a70AB815c96711a31Bc65".to_string(),
+            spender: spender.to_string(),
+            amount: "1".to_string(),
+            valid_for_secs: None,
+            standard: None,
+        };
+        let spender = "0x2222222222222222222222222222222222222222";
+
+        let err = create_permit(
+            auth("user-a"),
+            State(state.clone()),
+            Path("w-smart".to_string()),
+            Json(request(spender)),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, axum::http::StatusCode::UNPROCESSABLE_ENTITY);
+
+        let err = create_permit(
+            auth("user-a"),
+            State(state.clone()),
+            Path("w-eoa".to_string()),
+            Json(request("0x0000000000000000000000000000000000000000")),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, axum::http::StatusCode::BAD_REQUEST);
+
+        let mut long = request(spender);
+        long.valid_for_secs = Some(MAX_PERMIT_TTL_SECS + 1);
+        let err = create_permit(
+            auth("user-a"),
+            State(state),
+            Path("w-eoa".to_string()),
+            Json(long),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, axum::http::StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn permit2_approval_is_refused_under_a_spending_limit() {
+        let state = AppState::default();
+        let wallet = WalletMetadata {
+            wallet_id: "w-eoa".to_string(),
+            owner_user_id: "user-a".to_string(),
+            public_address: "0x1111111111111111111111111111111111111111".to_string(),
+            created_at: Utc::now(),
+            status: WalletStatus::Active,
+            label: None,
+            email_lookup_key: None,
+            email_sha256: None,
+            account_type: WalletAccountType::Eoa,
+            smart_account: None,
+            lock: None,
+            deleted_at: None,
+            purged_at: None,
+            funded_at: None,
+            tenant_id: None,
+        };
+        WalletRepository::new(state.storage())
+            .create(&wallet, b"test_key")
+            .unwrap();
+        let Auth(admin) = auth("admin-1");
+        let _ = limits::put_wallet_limits(
+            AdminOnly(AuthenticatedUser {
+                role: Role::Admin,
+                ..admin
+            }),
+            State(state.clone()),
+            Path("w-eoa".to_string()),
+            Json(limits::SpendingLimitsRequest {
+                daily_eur: Some("100".into()),
+                monthly_eur: None,
+            }),
+        )
+        .await
+        .unwrap();
+
+        // An unlimited approval of rEUR exceeds any limit.
+        let err = approve_permit2(
+            auth("user-a"),
+            State(state),
+            Path("w-eoa".to_string()),
+            Json(Permit2ApprovalRequest {
+                token: REUR_TOKEN.fuji_address.unwrap().to_string(),
+            }),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.code, Some("spending_limit_exceeded"));
+    }
+}
@@ -0,0 +1,393 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Portfolio view for the app's home screen.
+//!
+//! Balances of every custodial wallet and watch-only address a user has are
+//! read in parallel and summed per token, then valued in EUR with the
+//! pricing feed. A source whose balance cannot be read within the timeout
+//! is reported as unavailable instead of failing the whole response, and
+//! holdings are left unvalued when prices are unavailable.
+
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use std::time::Duration;
+
+use alloy::primitives::U256;
+use axum::{extract::State, Json};
+use chrono::Utc;
+use serde::Serialize;
+use tokio::task::JoinSet;
+use utoipa::ToSchema;
+
+use crate::{
+    api::balance::fetch_address_balance,
+    auth::Auth,
+    blockchain::{avax_fuji, format_amount, TokenBalance, WalletBalanceResponse},
+    error::ApiError,
+    providers::pricing::{PriceFeedClient, PriceQuotes},
+    state::AppState,
+    storage::{WalletRepository, WalletStatus, WatchOnlyRepository},
+};
+
+/// Per-source limit on the balance read.
+const BALANCE_READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Where a portfolio balance comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PortfolioSourceKind {
+    /// Wallet whose key is held in the enclave.
+    Custodial,
+    /// External address registered as watch-only.
+    WatchOnly,
+}
+
+/// A wallet or watch-only address included in the portfolio.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PortfolioSource {
+    pub kind: PortfolioSourceKind,
+    /// `wallet_id` or `watch_id`.
+    pub id: String,
+    pub address: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// `false` when the balance could not be read; its holdings are missing
+    /// from the totals.
+    pub available: bool,
+}
+
+/// Aggregate holding of one token across all sources.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PortfolioHolding {
+    pub symbol: String,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contract_address: Option<String>,
+    pub decimals: u8,
+    /// Total balance, formatted with decimals.
+    pub balance: String,
+    pub balance_raw: String,
+    /// Part of `balance` held at watch-only addresses (not spendable here).
+    pub read_only_balance: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub price_eur: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value_eur: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub change_24h_pct: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value_change_24h_eur: Option<String>,
+    /// Share of `total_value_eur`, in percent. Absent for unpriced tokens.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allocation_pct: Option<f64>,
+}
+
+/// Response for `GET /v1/portfolio`.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PortfolioResponse {
+    /// Value of all priced holdings.
+    pub total_value_eur: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_change_24h_eur: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_change_24h_pct: Option<f64>,
+    /// Holdings, highest value first.
+    pub holdings: Vec<PortfolioHolding>,
+    pub sources: Vec<PortfolioSource>,
+    /// `false` when the price feed failed and no holding is valued.
+    pub prices_available: bool,
+    pub generated_at: String,
+}
+
+/// Running per-token sum while aggregating.
+struct TokenTotal {
+    meta: TokenBalance,
+    total: U256,
+    read_only: U256,
+}
+
+/// Sum balances per token and value them.
+///
+/// Tokens are keyed by contract address (native AVAX has none). `quotes`
+/// is `None` when the price feed was unavailable. Returns the holdings, the
+/// total EUR value and, when a day-old value can be derived, the 24h change
+/// in EUR and percent.
+fn aggregate(
+    balances: &[(PortfolioSourceKind, WalletBalanceResponse)],
+    quotes: Option<&PriceQuotes>,
+) -> (Vec<PortfolioHolding>, f64, Option<(f64, f64)>) {
+    let mut totals: BTreeMap<String, TokenTotal> = BTreeMap::new();
+    for (kind, balance) in balances {
+        for token in std::iter::once(&balance.native_balance).chain(&balance.token_balances) {
+            let key = token
+                .contract_address
+                .as_deref()
+                .map(str::to_lowercase)
+                .unwrap_or_default();
+            let raw = U256::from_str(&token.balance_raw).unwrap_or(U256::ZERO);
+            let entry = totals.entry(key).or_insert_with(|| TokenTotal {
+                meta: token.clone(),
+                total: U256::ZERO,
+                read_only: U256::ZERO,
+            });
+            entry.total = entry.total.saturating_add(raw);
+            if *kind == PortfolioSourceKind::WatchOnly {
+                entry.read_only = entry.read_only.saturating_add(raw);
+            }
+        }
+    }
+
+    let mut holdings = Vec::with_capacity(totals.len());
+    let mut total_value = 0.0;
+    let mut value_24h_ago: Option<f64> = None;
+    for TokenTotal {
+        meta,
+        total,
+        read_only,
+    } in totals.into_values()
+    {
+        let balance = format_amount(total, meta.decimals);
+        let quote = quotes.and_then(|q| q.get(&meta.symbol));
+        let value = quote.map(|q| balance.parse::<f64>().unwrap_or(0.0) * q.price_eur);
+        let change = quote.and_then(|q| q.change_24h_pct);
+        let value_change = value
+            .zip(change)
+            .map(|(value, pct)| value - value / (1.0 + pct / 100.0));
+        if let Some(value) = value {
+            total_value += value;
+            *value_24h_ago.get_or_insert(0.0) += value - value_change.unwrap_or(0.0);
+        }
+        let holding = PortfolioHolding {
+            symbol: meta.symbol,
+            name: meta.name,
+            contract_address: meta.contract_address,
+            decimals: meta.decimals,
+            balance,
+            balance_raw: total.to_string(),
+            read_only_balance: format_amount(read_only, meta.decimals),
+            price_eur: quote.map(|q| q.price_eur),
+            value_eur: value.map(format_eur),
+            change_24h_pct: change,
+            value_change_24h_eur: value_change.map(format_eur),
+            // Needs the final total; filled in below.
+            allocation_pct: None,
+        };
+        holdings.push((holding, value));
+    }
+
+    // Unpriced tokens sort last.
+    holdings.sort_by(|(_, a), (_, b)| b.unwrap_or(-1.0).total_cmp(&a.unwrap_or(-1.0)));
+    let holdings = holdings
+        .into_iter()
+        .map(|(mut holding, value)| {
+            holding.allocation_pct = value.map(|v| percent_of(v, total_value));
+            holding
+        })
+        .collect();
+
+    let total_change = value_24h_ago.filter(|before| *before > 0.0).map(|before| {
+        let change = total_value - before;
+        (change, round2(change / before * 100.0))
+    });
+    (holdings, total_value, total_change)
+}
+
+fn percent_of(value: f64, total: f64) -> f64 {
+    if total > 0.0 {
+        round2(value / total * 100.0)
+    } else {
+        0.0
+    }
+}
+
+fn round2(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+fn format_eur(value: f64) -> String {
+    format!("{value:.2}")
+}
+
+/// Aggregate holdings across all of the caller's wallets and watch-only
+/// addresses, valued in EUR.
+#[utoipa::path(
+    get,
+    path = "/v1/portfolio",
+    tag = "Wallets",
+    security(("bearer" = [])),
+    responses(
+        (status = 200, description = "Portfolio summary", body = PortfolioResponse),
+        (status = 401, description = "Unauthorized")
+    )
+)]
+pub async fn get_portfolio(
+    Auth(user): Auth,
+    State(state): State<AppState>,
+) -> Result<Json<PortfolioResponse>, ApiError> {
+    let storage = state.storage();
+    let wallets = WalletRepository::new(storage)
+        .list_for_user(&user)
+        .map_err(|e| ApiError::internal(format!("Failed to list wallets: {e}")))?;
+    let watched = WatchOnlyRepository::new(storage)
+        .list_by_owner(&user.user_id)
+        .map_err(|e| ApiError::internal(format!("Failed to list watch-only addresses: {e}")))?;
+
+    // Suspended wallets still hold funds, so they stay in the totals.
+    let mut sources: Vec<PortfolioSource> = wallets
+        .into_iter()
+        .filter(|w| w.status != WalletStatus::Deleted)
+        .map(|w| PortfolioSource {
+            kind: PortfolioSourceKind::Custodial,
+            id: w.wallet_id,
+            address: w.public_address,
+            label: w.label,
+            available: false,
+        })
+        .chain(watched.into_iter().map(|entry| PortfolioSource {
+            kind: PortfolioSourceKind::WatchOnly,
+            id: entry.watch_id,
+            address: entry.address,
+            label: entry.label,
+            available: false,
+        }))
+        .collect();
+
+    let mut reads = JoinSet::new();
+    for (index, source) in sources.iter().enumerate() {
+        let state = state.clone();
+        let address = source.address.clone();
+        reads.spawn(async move {
+            let network = avax_fuji();
+            let read = fetch_address_balance(&state, &network, &address, None);
+            (
+                index,
+                tokio::time::timeout(BALANCE_READ_TIMEOUT, read).await,
+            )
+        });
+    }
+    let mut balances = Vec::with_capacity(sources.len());
+    while let Some(joined) = reads.join_next().await {
+        let Ok((index, result)) = joined else {
+            continue;
+        };
+        match result {
+            Ok(Ok(balance)) => {
+                sources[index].available = true;
+                balances.push((sources[index].kind, balance));
+            }
+            Ok(Err(e)) => {
+                tracing::warn!(source = %sources[index].id, error = %e.message, "Portfolio balance read failed");
+            }
+            Err(_) => {
+                tracing::warn!(source = %sources[index].id, "Portfolio balance read timed out");
+            }
+        }
+    }
+
+    let quotes = match PriceFeedClient::from_env() {
+        Ok(client) => client.current_quotes().await,
+        Err(e) => Err(e),
+    }
+    .inspect_err(|e| tracing::warn!(error = %e, "Price feed unavailable for portfolio"))
+    .ok();
+
+    let (holdings, total_value, total_change) = aggregate(&balances, quotes.as_ref());
+
+    Ok(Json(PortfolioResponse {
+        total_value_eur: format_eur(total_value),
+        total_change_24h_eur: total_change.map(|(eur, _)| format_eur(eur)),
+        total_change_24h_pct: total_change.map(|(_, pct)| pct),
+        holdings,
+        sources,
+        prices_available: quotes.is_some(),
+        generated_at: Utc::now().to_rfc3339(),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::pricing::PriceQuote;
+
+    fn token(symbol: &str, contract: Option<&str>, raw: &str, decimals: u8) -> TokenBalance {
+        TokenBalance {
+            symbol: symbol.to_string(),
+            name: symbol.to_string(),
+            balance_raw: raw.to_string(),
+            balance_formatted: String::new(),
+            decimals,
+            contract_address: contract.map(str::to_string),
+        }
+    }
+
+    fn balance(avax_raw: &str, reur_raw: &str) -> WalletBalanceResponse {
+        WalletBalanceResponse {
+            address: "0x0".to_string(),
+            network: "fuji".to_string(),
+            chain_id: 43113,
+            native_balance: token("AVAX", None, avax_raw, 18),
+            token_balances: vec![token("rEUR", Some("0xReur"), reur_raw, 6)],
+        }
+    }
+
+    fn quote(symbol: &str, price_eur: f64, change_24h_pct: Option<f64>) -> (String, PriceQuote) {
+        (
+            symbol.to_string(),
+            PriceQuote {
+                symbol: symbol.to_string(),
+                price_eur,
+                change_24h_pct,
+                as_of: Utc::now(),
+            },
+        )
+    }
+
+    #[test]
+    fn holdings_are_summed_valued_and_allocated() {
+        let balances = vec![
+            // 1 AVAX + 50 rEUR custodial
+            (
+                PortfolioSourceKind::Custodial,
+                balance("1000000000000000000", "50000000"),
+            ),
+            // 1 AVAX + 0 rEUR watched
+            (
+                PortfolioSourceKind::WatchOnly,
+                balance("1000000000000000000", "0"),
+            ),
+        ];
+        let quotes = PriceQuotes::from([
+            quote("AVAX", 25.0, Some(25.0)),
+            quote("rEUR", 1.0, Some(0.0)),
+        ]);
+
+        let (holdings, total, change) = aggregate(&balances, Some(&quotes));
+
+        assert_eq!(total, 100.0);
+        let reur = holdings.iter().find(|h| h.symbol == "rEUR").unwrap();
+        assert_eq!(reur.value_eur.as_deref(), Some("50.00"));
+        let avax = holdings.iter().find(|h| h.symbol == "AVAX").unwrap();
+        assert_eq!(avax.balance, "2");
+        assert_eq!(avax.read_only_balance, "1");
+        assert_eq!(avax.allocation_pct, Some(50.0));
+        // AVAX was worth 40 EUR a day ago: 90 → 100.
+        assert_eq!(avax.value_change_24h_eur.as_deref(), Some("10.00"));
+        let (change_eur, change_pct) = change.unwrap();
+        assert_eq!(format_eur(change_eur), "10.00");
+        assert_eq!(change_pct, 11.11);
+    }
+
+    #[test]
+    fn holdings_are_unvalued_without_prices() {
+        let balances = vec![(
+            PortfolioSourceKind::Custodial,
+            balance("1000000000000000000", "50000000"),
+        )];
+        let (holdings, total, change) = aggregate(&balances, None);
+        assert_eq!(total, 0.0);
+        assert!(change.is_none());
+        assert!(holdings.iter().all(|h| h.value_eur.is_none()));
+    }
+}
@@ -0,0 +1,407 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Request rate limits for sensitive endpoints.
+//!
+//! Sending, creating wallets, redeeming claim links and the public status
+//! page are limited per [`RateLimitPolicy`] with a token bucket refilled
+//! over a minute. Buckets
+//! are keyed by the authenticated user, or by client IP for requests that
+//! do not authenticate (see [`crate::auth::client_ip`]). A limited request
+//! gets `429` with `Retry-After`; every `RATE_LIMIT_AUDIT_AFTER`th
+//! rejection in a row is recorded as a `rate_limit_exceeded` audit event.
+//! Buckets are kept in memory, per instance.
+
+use std::collections::HashMap;
+use std::env;
+use std::sync::Mutex;
+
+use axum::{
+    extract::{FromRequestParts, Request, State},
+    http::{header, HeaderValue, Method},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use chrono::Utc;
+
+use crate::{
+    auth::{client_ip::client_ip, Auth, AuthenticatedUser},
+    error::ApiError,
+    events::DomainEvent,
+    state::AppState,
+    storage::{AuditEvent, AuditEventType, AuditRepository},
+};
+
+/// Window over which a bucket refills completely.
+const WINDOW_MS: i64 = 60_000;
+
+/// Buckets tracked at once. Beyond this, full buckets are dropped.
+const MAX_TRACKED_BUCKETS: usize = 100_000;
+
+const DEFAULT_AUDIT_AFTER: u32 = 5;
+
+/// A limited group of endpoints.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RateLimitPolicy {
+    /// `POST /v1/wallets/{wallet_id}/send`
+    Send,
+    /// `POST /v1/wallets`
+    WalletCreate,
+    /// `POST /v1/claim-links/{token}/claim`
+    ClaimRedeem,
+    /// `GET /status`
+    StatusPage,
+}
+
+impl RateLimitPolicy {
+    pub const ALL: [RateLimitPolicy; 4] = [
+        Self::Send,
+        Self::WalletCreate,
+        Self::ClaimRedeem,
+        Self::StatusPage,
+    ];
+
+    /// Name in errors and audit events.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Send => "send",
+            Self::WalletCreate => "wallet_create",
+            Self::ClaimRedeem => "claim_redeem",
+            Self::StatusPage => "status_page",
+        }
+    }
+
+    fn env_var(self) -> &'static str {
+        match self {
+            Self::Send => "RATE_LIMIT_SEND_PER_MINUTE",
+            Self::WalletCreate => "RATE_LIMIT_WALLET_CREATE_PER_MINUTE",
+            Self::ClaimRedeem => "RATE_LIMIT_CLAIM_REDEEM_PER_MINUTE",
+            Self::StatusPage => "RATE_LIMIT_STATUS_PAGE_PER_MINUTE",
+        }
+    }
+
+    fn default_per_minute(self) -> u32 {
+        match self {
+            Self::Send => 10,
+            Self::WalletCreate => 3,
+            Self::ClaimRedeem => 5,
+            Self::StatusPage => 6,
+        }
+    }
+
+    /// The policy covering a request, if any. Accepts paths with or without
+    /// the `/v1` prefix.
+    pub fn for_request(method: &Method, path: &str) -> Option<Self> {
+        let path = path.strip_prefix("/v1").unwrap_or(path);
+        let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+        match (method, segments.as_slice()) {
+            (&Method::POST, ["wallets"]) => Some(Self::WalletCreate),
+            (&Method::POST, ["wallets", _, "send"]) => Some(Self::Send),
+            (&Method::POST, ["claim-links", _, "claim"]) => Some(Self::ClaimRedeem),
+            (&Method::GET, ["status"]) => Some(Self::StatusPage),
+            _ => None,
+        }
+    }
+}
+
+/// Outcome of a rate limit check.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RateDecision {
+    Allowed,
+    Limited {
+        /// Seconds until the next request is accepted.
+        retry_after_secs: u64,
+        /// Rejections in a row, when this one should be audited.
+        audit_violations: Option<u32>,
+    },
+}
+
+#[derive(Debug, Clone)]
+struct Bucket {
+    tokens: f64,
+    updated_ms: i64,
+    violations: u32,
+}
+
+/// Per-policy limits and the buckets of recent callers.
+#[derive(Debug, Default)]
+pub struct RateLimiter {
+    /// Requests per minute; policies without an entry are unlimited.
+    limits: HashMap<RateLimitPolicy, u32>,
+    audit_after: u32,
+    buckets: Mutex<HashMap<(RateLimitPolicy, String), Bucket>>,
+}
+
+impl RateLimiter {
+    /// Limiter configured from `RATE_LIMIT_*_PER_MINUTE` (`0` disables a
+    /// policy) and `RATE_LIMIT_AUDIT_AFTER`.
+    pub fn from_env() -> Self {
+        Self::from_lookup(|name| env::var(name).ok())
+    }
+
+    pub fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Self {
+        let limits = RateLimitPolicy::ALL
+            .into_iter()
+            .map(|policy| {
+                let limit = lookup(policy.env_var())
+                    .and_then(|v| v.trim().parse().ok())
+                    .unwrap_or(policy.default_per_minute());
+                (policy, limit)
+            })
+            .collect();
+        let audit_after = lookup("RATE_LIMIT_AUDIT_AFTER")
+            .and_then(|v| v.trim().parse().ok())
+            .unwrap_or(DEFAULT_AUDIT_AFTER);
+        Self::new(limits, audit_after)
+    }
+
+    pub fn new(limits: HashMap<RateLimitPolicy, u32>, audit_after: u32) -> Self {
+        Self {
+            limits: limits.into_iter().filter(|(_, limit)| *limit > 0).collect(),
+            audit_after: audit_after.max(1),
+            buckets: Mutex::default(),
+        }
+    }
+
+    /// Requests per minute allowed under `policy`, if it is limited.
+    pub fn limit(&self, policy: RateLimitPolicy) -> Option<u32> {
+        self.limits.get(&policy).copied()
+    }
+
+    /// Take a token from `key`'s bucket for `policy` at `now_ms`.
+    pub fn check(&self, policy: RateLimitPolicy, key: &str, now_ms: i64) -> RateDecision {
+        let Some(limit) = self.limit(policy) else {
+            return RateDecision::Allowed;
+        };
+        let capacity = f64::from(limit);
+        let window = WINDOW_MS as f64;
+
+        let mut buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+        if buckets.len() >= MAX_TRACKED_BUCKETS {
+            buckets.retain(|(p, _), b| {
+                let cap = self.limit(*p).map_or(0.0, f64::from);
+                b.tokens + (now_ms - b.updated_ms) as f64 * cap / window < cap
+            });
+        }
+        let bucket = buckets.entry((policy, key.to_string())).or_insert(Bucket {
+            tokens: capacity,
+            updated_ms: now_ms,
+            violations: 0,
+        });
+        let elapsed = (now_ms - bucket.updated_ms).max(0) as f64;
+        bucket.tokens = (bucket.tokens + elapsed * capacity / window).min(capacity);
+        bucket.updated_ms = now_ms;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            bucket.violations = 0;
+            return RateDecision::Allowed;
+        }
+        bucket.violations += 1;
+        let wait_ms = (1.0 - bucket.tokens) * window / capacity;
+        RateDecision::Limited {
+            retry_after_secs: (wait_ms / 1000.0).ceil().max(1.0) as u64,
+            audit_violations: bucket
+                .violations
+                .is_multiple_of(self.audit_after)
+                .then_some(bucket.violations),
+        }
+    }
+}
+
+fn log_violation(
+    state: &AppState,
+    policy: RateLimitPolicy,
+    user: Option<&AuthenticatedUser>,
+    ip: Option<&str>,
+    path: &str,
+    violations: u32,
+) {
+    let mut event = AuditEvent::new(AuditEventType::RateLimitExceeded)
+        .with_details(serde_json::json!({
+            "policy": policy.name(),
+            "path": path,
+            "violations": violations,
+        }))
+        .failed("Rate limit exceeded");
+    if let Some(user) = user {
+        event = event.with_user(&user.user_id);
+    }
+    if let Some(ip) = ip {
+        event = event.with_ip(ip);
+    }
+    let _ = AuditRepository::new(state.storage()).log(&event);
+}
+
+/// Layer enforcing [`RateLimitPolicy`] limits.
+///
+/// Authenticates limited requests itself to key them by user; the handler
+/// reuses that user instead of verifying the credentials again.
+pub async fn rate_limit(State(state): State<AppState>, request: Request, next: Next) -> Response {
+    let Some(policy) = RateLimitPolicy::for_request(request.method(), request.uri().path())
+        .filter(|p| state.rate_limiter.limit(*p).is_some())
+    else {
+        return next.run(request).await;
+    };
+
+    let (mut parts, body) = request.into_parts();
+    let user = Auth::from_request_parts(&mut parts, &state)
+        .await
+        .ok()
+        .map(|Auth(user)| user);
+    let ip = client_ip(&parts, &state);
+    let key = match (&user, &ip) {
+        (Some(user), _) => format!("user:{}", user.user_id),
+        (None, Some(ip)) => format!("ip:{ip}"),
+        (None, None) => "ip:unknown".to_string(),
+    };
+
+    let now_ms = Utc::now().timestamp_millis();
+    match state.rate_limiter.check(policy, &key, now_ms) {
+        RateDecision::Allowed => {
+            if let Some(user) = user {
+                parts.extensions.insert(user);
+            }
+            next.run(Request::from_parts(parts, body)).await
+        }
+        RateDecision::Limited {
+            retry_after_secs,
+            audit_violations,
+        } => {
+            state.events.publish(DomainEvent::PolicyViolation {
+                user_id: user.as_ref().map(|u| u.user_id.clone()),
+                policy: policy.name(),
+                detail: format!("rate limit exceeded on {}", parts.uri.path()),
+            });
+            if let Some(violations) = audit_violations {
+                log_violation(
+                    &state,
+                    policy,
+                    user.as_ref(),
+                    ip.as_deref(),
+                    parts.uri.path(),
+                    violations,
+                );
+            }
+            let mut response = ApiError::too_many_requests(format!(
+                "Too many {} requests; retry in {retry_after_secs}s",
+                policy.name()
+            ))
+            .with_code("rate_limited")
+            .into_response();
+            response
+                .headers_mut()
+                .insert(header::RETRY_AFTER, HeaderValue::from(retry_after_secs));
+            response
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::StatusCode, routing::post, Router};
+    use tower::ServiceExt;
+
+    fn limiter(send_per_minute: u32, audit_after: u32) -> RateLimiter {
+        RateLimiter::new(
+            HashMap::from([(RateLimitPolicy::Send, send_per_minute)]),
+            audit_after,
+        )
+    }
+
+    #[test]
+    fn policies_cover_sensitive_endpoints_only() {
+        let post = Method::POST;
+        assert_eq!(
+            RateLimitPolicy::for_request(&post, "/v1/wallets/w-1/send"),
+            Some(RateLimitPolicy::Send)
+        );
+        assert_eq!(
+            RateLimitPolicy::for_request(&post, "/wallets"),
+            Some(RateLimitPolicy::WalletCreate)
+        );
+        assert_eq!(
+            RateLimitPolicy::for_request(&post, "/claim-links/abc/claim"),
+            Some(RateLimitPolicy::ClaimRedeem)
+        );
+        assert_eq!(
+            RateLimitPolicy::for_request(&Method::GET, "/status"),
+            Some(RateLimitPolicy::StatusPage)
+        );
+        assert_eq!(RateLimitPolicy::for_request(&Method::GET, "/wallets"), None);
+        assert_eq!(
+            RateLimitPolicy::for_request(&post, "/wallets/w-1/escrows"),
+            None
+        );
+    }
+
+    #[test]
+    fn buckets_refill_over_the_window() {
+        let limiter = limiter(2, 2);
+        let now = 1_800_000_000_000;
+        let send = RateLimitPolicy::Send;
+        assert_eq!(limiter.check(send, "user:a", now), RateDecision::Allowed);
+        assert_eq!(limiter.check(send, "user:a", now), RateDecision::Allowed);
+        assert_eq!(
+            limiter.check(send, "user:a", now),
+            RateDecision::Limited {
+                retry_after_secs: 30,
+                audit_violations: None
+            }
+        );
+        assert_eq!(
+            limiter.check(send, "user:a", now + 1_500),
+            RateDecision::Limited {
+                retry_after_secs: 29,
+                audit_violations: Some(2)
+            }
+        );
+        // Other callers and unlimited policies are unaffected.
+        assert_eq!(limiter.check(send, "user:b", now), RateDecision::Allowed);
+        assert_eq!(
+            limiter.check(RateLimitPolicy::WalletCreate, "user:a", now),
+            RateDecision::Allowed
+        );
+        assert_eq!(
+            limiter.check(send, "user:a", now + 31_000),
+            RateDecision::Allowed
+        );
+    }
+
+    #[test]
+    fn limits_come_from_env_and_zero_disables() {
+        let limiter = RateLimiter::from_lookup(|name| {
+            (name == "RATE_LIMIT_SEND_PER_MINUTE").then(|| "0".to_string())
+        });
+        assert_eq!(limiter.limit(RateLimitPolicy::Send), None);
+        assert_eq!(limiter.limit(RateLimitPolicy::WalletCreate), Some(3));
+        assert_eq!(RateLimiter::default().limit(RateLimitPolicy::Send), None);
+    }
+
+    #[tokio::test]
+    async fn limited_requests_get_429_with_retry_after() {
+        let state = AppState::default().with_rate_limiter(limiter(1, 5));
+        let app = Router::new()
+            .route("/wallets/{wallet_id}/send", post(|| async { "sent" }))
+            .layer(axum::middleware::from_fn_with_state(
+                state.clone(),
+                rate_limit,
+            ))
+            .with_state(state);
+        let request = || {
+            axum::http::Request::builder()
+                .method("POST")
+                .uri("/wallets/w-1/send")
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        let response = app.clone().oneshot(request()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let response = app.oneshot(request()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers()[header::RETRY_AFTER], "60");
+    }
+}
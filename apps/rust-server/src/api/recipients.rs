@@ -0,0 +1,315 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Recent recipients for the send screen.
+//!
+//! `GET /v1/wallets/{wallet_id}/recipients/recent` suggests addresses the
+//! wallet paid before, ranked by how often and how recently. Suggestions are
+//! matched against the user's own wallets and the wallet's bookmarks so the
+//! app can show a name instead of a bare address. Addresses seen in
+//! transfers flagged as [address poisoning](crate::indexer::poisoning) are
+//! never suggested.
+
+use std::collections::{HashMap, HashSet};
+
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::{
+    auth::Auth,
+    blockchain::address_key,
+    error::{ApiError, StorageContext},
+    indexer::poisoning,
+    state::AppState,
+    storage::{
+        BookmarkRepository, OwnershipEnforcer, RecipientType, StoredBookmark, StoredTransaction,
+        TxStatus, WalletMetadata, WalletRepository,
+    },
+};
+
+/// Suggestions returned.
+const RECENT_RECIPIENTS_LIMIT: usize = 10;
+
+/// Age at which a transfer counts half as much towards the ranking.
+const RECENCY_HALF_LIFE_DAYS: f64 = 30.0;
+
+/// A recently paid address.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct RecentRecipient {
+    /// Recipient address (lowercase)
+    pub address: String,
+    /// Latest transfer to this address
+    pub last_sent_at: DateTime<Utc>,
+    /// Outgoing transfers to this address among the recent history
+    pub transactions: u32,
+    /// The address is a wallet hosted by this server.
+    pub internal: bool,
+    /// Your own wallet at this address.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wallet_id: Option<String>,
+    /// Label of your own wallet at this address.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wallet_label: Option<String>,
+    /// Bookmark of this wallet for the address.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bookmark_id: Option<String>,
+    /// Name of the bookmark.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bookmark_name: Option<String>,
+}
+
+/// Response for `GET /v1/wallets/{wallet_id}/recipients/recent`.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct RecentRecipientsResponse {
+    /// Best suggestion first
+    pub recipients: Vec<RecentRecipient>,
+}
+
+/// Weight of a transfer sent at `at`.
+fn recency_weight(at: DateTime<Utc>, now: DateTime<Utc>) -> f64 {
+    let age_days = (now - at).num_seconds().max(0) as f64 / 86_400.0;
+    0.5_f64.powf(age_days / RECENCY_HALF_LIFE_DAYS)
+}
+
+/// Rank the addresses the wallet sent to in `history` (newest first).
+///
+/// Each transfer adds its recency weight to its recipient's score, so
+/// frequent and recent payees come first. Counterparties of transactions
+/// flagged as address poisoning are left out entirely, including transfers
+/// sent to them, so a lookalike copied by mistake is not suggested again.
+fn rank_recipients(
+    history: &[(StoredTransaction, String)],
+    own_wallets: &[WalletMetadata],
+    bookmarks: &[StoredBookmark],
+    now: DateTime<Utc>,
+    limit: usize,
+) -> Vec<RecentRecipient> {
+    let flagged: HashSet<String> = history
+        .iter()
+        .filter(|(tx, _)| tx.suspected_poisoning)
+        .flat_map(|(tx, _)| [address_key(&tx.from), address_key(&tx.to)])
+        .collect();
+
+    let mut scores: HashMap<String, f64> = HashMap::new();
+    let mut recipients: Vec<RecentRecipient> = Vec::new();
+    for (tx, direction) in history {
+        if direction != "sent" || tx.status == TxStatus::Failed {
+            continue;
+        }
+        let address = address_key(&tx.to);
+        if flagged.contains(&address) {
+            continue;
+        }
+        *scores.entry(address.clone()).or_default() += recency_weight(tx.created_at, now);
+        match recipients.iter_mut().find(|r| r.address == address) {
+            Some(existing) => {
+                existing.transactions += 1;
+                existing.last_sent_at = existing.last_sent_at.max(tx.created_at);
+                existing.internal |= tx.counterparty_wallet_id.is_some();
+            }
+            None => recipients.push(RecentRecipient {
+                address,
+                last_sent_at: tx.created_at,
+                transactions: 1,
+                internal: tx.counterparty_wallet_id.is_some(),
+                wallet_id: None,
+                wallet_label: None,
+                bookmark_id: None,
+                bookmark_name: None,
+            }),
+        }
+    }
+
+    recipients.sort_by(|a, b| {
+        scores[&b.address]
+            .total_cmp(&scores[&a.address])
+            .then(b.last_sent_at.cmp(&a.last_sent_at))
+    });
+    recipients.truncate(limit);
+
+    for recipient in &mut recipients {
+        if let Some(wallet) = own_wallets
+            .iter()
+            .find(|w| w.public_address.eq_ignore_ascii_case(&recipient.address))
+        {
+            recipient.internal = true;
+            recipient.wallet_id = Some(wallet.wallet_id.clone());
+            recipient.wallet_label = wallet.label.clone();
+        }
+        if let Some(bookmark) = bookmarks.iter().find(|b| {
+            b.recipient_type == RecipientType::Address
+                && b.address.eq_ignore_ascii_case(&recipient.address)
+        }) {
+            recipient.bookmark_id = Some(bookmark.id.clone());
+            recipient.bookmark_name = Some(bookmark.name.clone());
+        }
+    }
+    recipients
+}
+
+/// Recent recipients of a wallet, for send-screen suggestions.
+///
+/// Deduplicated payees from the latest indexed transactions, ranked by
+/// frequency and recency, with matching own wallets and bookmarks.
+/// Addresses involved in transfers flagged as address poisoning are never
+/// suggested.
+#[utoipa::path(
+    get,
+    path = "/v1/wallets/{wallet_id}/recipients/recent",
+    tag = "Transactions",
+    params(("wallet_id" = String, Path, description = "Wallet ID")),
+    security(("bearer" = [])),
+    responses(
+        (status = 200, description = "Recent recipients", body = RecentRecipientsResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - not wallet owner"),
+        (status = 404, description = "Wallet not found")
+    )
+)]
+pub async fn list_recent_recipients(
+    Auth(user): Auth,
+    State(state): State<AppState>,
+    Path(wallet_id): Path<String>,
+) -> Result<Json<RecentRecipientsResponse>, ApiError> {
+    let storage = state.storage();
+    let wallet_repo = WalletRepository::new(storage);
+    let wallet = wallet_repo.get(&wallet_id)?;
+    if !wallet.is_owned_by(&user) {
+        return Err(ApiError::forbidden("You do not own this wallet").with_code("wallet_not_owned"));
+    }
+
+    let tx_db = state
+        .tx_db
+        .as_ref()
+        .expect("transaction database must be configured");
+    let (history, _) = tx_db
+        .list_by_wallet(
+            &address_key(&wallet.public_address),
+            None,
+            poisoning::HISTORY_WINDOW,
+        )
+        .map_err(|e| ApiError::internal(format!("Failed to list transactions: {e}")))?;
+    let own_wallets = wallet_repo
+        .list_for_user(&user)
+        .context("Failed to list wallets")?;
+    let bookmarks = BookmarkRepository::new(storage)
+        .list_by_wallet(&wallet.wallet_id, &user.user_id)
+        .context("Failed to list bookmarks")?;
+
+    Ok(Json(RecentRecipientsResponse {
+        recipients: rank_recipients(
+            &history,
+            &own_wallets,
+            &bookmarks,
+            Utc::now(),
+            RECENT_RECIPIENTS_LIMIT,
+        ),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::{TokenType, WalletStatus};
+    use chrono::Duration;
+
+    const ME: &str = "0x1111111111111111111111111111111111111111";
+    const FRIEND: &str = "0xabcd000000000000000000000000000000001234";
+    const LOOKALIKE: &str = "0xabcd999999999999999999999999999999991234";
+    const SHOP: &str = "0x2222222222222222222222222222222222222222";
+    const SAVINGS: &str = "0x3333333333333333333333333333333333333333";
+
+    fn tx(from: &str, to: &str, amount: &str, days_ago: i64) -> StoredTransaction {
+        let mut tx = StoredTransaction::new_pending(
+            format!("0x{days_ago:x}"),
+            "wallet-1".to_string(),
+            None,
+            from.to_string(),
+            to.to_string(),
+            amount.to_string(),
+            TokenType::Native,
+            "fuji".to_string(),
+            String::new(),
+        );
+        tx.created_at = Utc::now() - Duration::days(days_ago);
+        tx
+    }
+
+    fn sent(to: &str, days_ago: i64) -> (StoredTransaction, String) {
+        (tx(ME, to, "1", days_ago), "sent".to_string())
+    }
+
+    #[test]
+    fn recipients_are_ranked_matched_and_poison_free() {
+        let mut poison = tx(LOOKALIKE, ME, "0", 0);
+        poison.suspected_poisoning = true;
+        // Newest first, as returned by the transaction database.
+        let history = vec![
+            (poison, "received".to_string()),
+            sent(LOOKALIKE, 1),
+            sent(SHOP, 2),
+            sent(SAVINGS, 5),
+            sent(FRIEND, 20),
+            sent(FRIEND, 25),
+            sent(FRIEND, 30),
+        ];
+        let savings = WalletMetadata {
+            wallet_id: "wallet-2".to_string(),
+            owner_user_id: "user-1".to_string(),
+            public_address: "0x3333333333333333333333333333333333333333".to_string(),
+            created_at: Utc::now(),
+            status: WalletStatus::Active,
+            label: Some("Savings".to_string()),
+            email_lookup_key: None,
+            email_sha256: None,
+            account_type: Default::default(),
+            smart_account: None,
+            lock: None,
+            deleted_at: None,
+            purged_at: None,
+            funded_at: None,
+            tenant_id: None,
+        };
+        let bookmark = StoredBookmark {
+            id: "bm-1".to_string(),
+            wallet_id: "wallet-1".to_string(),
+            owner_user_id: "user-1".to_string(),
+            name: "Alice".to_string(),
+            recipient_type: RecipientType::Address,
+            address: FRIEND.to_uppercase().replace("0X", "0x"),
+            email_hash: None,
+            email_display: None,
+            created_at: Utc::now(),
+        };
+
+        let recipients = rank_recipients(&history, &[savings], &[bookmark], Utc::now(), 10);
+        let addresses: Vec<&str> = recipients.iter().map(|r| r.address.as_str()).collect();
+        assert_eq!(addresses, vec![FRIEND, SHOP, SAVINGS]);
+        assert_eq!(recipients[0].transactions, 3);
+        assert_eq!(recipients[0].bookmark_name.as_deref(), Some("Alice"));
+        assert!(!recipients[0].internal);
+        assert!(recipients[2].internal);
+        assert_eq!(recipients[2].wallet_label.as_deref(), Some("Savings"));
+
+        let top = rank_recipients(&history, &[], &[], Utc::now(), 1);
+        assert_eq!(top.len(), 1);
+        assert_eq!(top[0].address, FRIEND);
+    }
+
+    #[test]
+    fn recent_transfers_outweigh_old_ones() {
+        let now = Utc::now();
+        assert!((recency_weight(now, now) - 1.0).abs() < 1e-9);
+        assert!((recency_weight(now - Duration::days(30), now) - 0.5).abs() < 1e-6);
+
+        let history = vec![sent(SHOP, 0), sent(FRIEND, 200), sent(FRIEND, 210)];
+        let recipients = rank_recipients(&history, &[], &[], now, 10);
+        assert_eq!(recipients[0].address, SHOP);
+    }
+}
@@ -0,0 +1,424 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Serialized send queue for the fiat reserve wallet.
+//!
+//! The reserve wallet is a single EOA, so two concurrent sends would read the
+//! same pending nonce and one would be dropped or replace the other. Every
+//! reserve-signed transaction goes through [`submit`], which persists the job,
+//! waits for all earlier jobs to finish, and only then builds, signs and
+//! broadcasts. Waiters are woken in arrival order.
+//!
+//! Admin endpoints:
+//! - `GET  /v1/admin/fiat/reserve/queue` — current and recent jobs.
+//! - `POST /v1/admin/fiat/reserve/queue/{job_id}/resolve` — settle an
+//!   interrupted job once an operator has checked the chain.
+
+use std::{
+    future::Future,
+    sync::{Mutex, MutexGuard},
+};
+
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+use utoipa::ToSchema;
+
+use crate::{
+    api::admin::require_platform_admin,
+    auth::AdminOnly,
+    blockchain::transactions::SendResult,
+    error::ApiError,
+    state::AppState,
+    storage::{
+        repository::reserve_queue::StoredReserveSendQueue, AuditEvent, AuditEventType,
+        AuditRepository, EncryptedStorage, ReserveSendKind, ReserveSendQueueRepository,
+        ReserveSendStatus, StoredReserveSendJob,
+    },
+};
+
+/// Held for the whole build-sign-broadcast of one reserve send. Tokio's
+/// mutex queues waiters fairly, which gives FIFO ordering.
+static SEND_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+/// Guards read-modify-write of the persisted queue file.
+static QUEUE_FILE_LOCK: Mutex<()> = Mutex::new(());
+
+fn lock_queue_file() -> MutexGuard<'static, ()> {
+    QUEUE_FILE_LOCK
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn update_queue<R>(
+    storage: &EncryptedStorage,
+    f: impl FnOnce(&mut StoredReserveSendQueue) -> R,
+) -> Result<R, ApiError> {
+    let _guard = lock_queue_file();
+    let repo = ReserveSendQueueRepository::new(storage);
+    let mut queue = repo
+        .load()
+        .map_err(|e| ApiError::internal(format!("Failed to load reserve send queue: {e}")))?;
+    let result = f(&mut queue);
+    repo.save(&queue)
+        .map_err(|e| ApiError::internal(format!("Failed to persist reserve send queue: {e}")))?;
+    Ok(result)
+}
+
+fn set_job_status(
+    storage: &EncryptedStorage,
+    job_id: &str,
+    status: ReserveSendStatus,
+    tx_hash: Option<String>,
+    error: Option<String>,
+) -> Result<(), ApiError> {
+    update_queue(storage, |queue| {
+        if let Some(job) = queue.job_mut(job_id) {
+            job.status = status;
+            job.tx_hash = tx_hash;
+            job.error = error;
+            job.updated_at = chrono::Utc::now();
+        }
+    })
+}
+
+/// Run a reserve-wallet send through the queue.
+///
+/// `send` must do all nonce-sensitive work (building the signer and
+/// provider, estimating, broadcasting) so that it happens under the queue
+/// lock.
+pub(crate) async fn submit<F, Fut>(
+    storage: &EncryptedStorage,
+    kind: ReserveSendKind,
+    request_ids: &[String],
+    to: &str,
+    amount_eur: &str,
+    send: F,
+) -> Result<SendResult, ApiError>
+where
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = Result<SendResult, ApiError>>,
+{
+    let job = update_queue(storage, |queue| {
+        queue.enqueue(kind, request_ids, to, amount_eur)
+    })?;
+
+    let _send_guard = SEND_LOCK.lock().await;
+    set_job_status(storage, &job.job_id, ReserveSendStatus::Sending, None, None)?;
+
+    let result = send().await;
+    let (status, tx_hash, error) = match &result {
+        Ok(sent) => (
+            ReserveSendStatus::Submitted,
+            Some(sent.tx_hash.clone()),
+            None,
+        ),
+        Err(e) => (ReserveSendStatus::Failed, None, Some(e.message.clone())),
+    };
+    if let Err(e) = set_job_status(storage, &job.job_id, status, tx_hash, error) {
+        // The send itself already happened; surface its result regardless.
+        warn!(job_id = %job.job_id, error = %e.message, "Failed to record reserve send outcome");
+    }
+    result
+}
+
+/// Most recent queue job for a fiat request's settlement, if any.
+pub(crate) fn latest_settlement_job(
+    storage: &EncryptedStorage,
+    request_id: &str,
+) -> Option<StoredReserveSendJob> {
+    let _guard = lock_queue_file();
+    ReserveSendQueueRepository::new(storage)
+        .load()
+        .ok()?
+        .latest_for_request(request_id)
+        .cloned()
+}
+
+/// Reconcile jobs left unfinished by the previous process. Call once at
+/// startup, before any reserve send.
+pub fn recover_unfinished(storage: &EncryptedStorage) -> Result<usize, ApiError> {
+    let interrupted = update_queue(storage, StoredReserveSendQueue::recover_unfinished)?;
+    if interrupted > 0 {
+        warn!(
+            interrupted,
+            "Reserve send queue has interrupted jobs; check the chain and resolve them \
+             via /v1/admin/fiat/reserve/queue before affected settlements can retry"
+        );
+    }
+    Ok(interrupted)
+}
+
+/// Reserve send queue listing.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ReserveQueueResponse {
+    /// Jobs in queue order, oldest first.
+    pub jobs: Vec<StoredReserveSendJob>,
+}
+
+/// Outcome of an interrupted job, as established by an operator.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct ResolveReserveJobRequest {
+    /// Hash of the transaction the job broadcast, or omitted if it never
+    /// reached the chain.
+    #[serde(default)]
+    pub tx_hash: Option<String>,
+}
+
+/// List reserve send queue jobs.
+#[utoipa::path(
+    get,
+    path = "/v1/admin/fiat/reserve/queue",
+    tag = "Admin",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Reserve send queue", body = ReserveQueueResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Not authorized (platform admin required)")
+    )
+)]
+pub async fn list_reserve_queue(
+    AdminOnly(admin): AdminOnly,
+    State(state): State<AppState>,
+) -> Result<Json<ReserveQueueResponse>, ApiError> {
+    require_platform_admin(&admin)?;
+    let _guard = lock_queue_file();
+    let queue = ReserveSendQueueRepository::new(state.storage())
+        .load()
+        .map_err(|e| ApiError::internal(format!("Failed to load reserve send queue: {e}")))?;
+    Ok(Json(ReserveQueueResponse { jobs: queue.jobs }))
+}
+
+/// Resolve an interrupted reserve send.
+///
+/// With `tx_hash`, the job is recorded as submitted and a settlement job's
+/// fiat requests complete with that transaction. Without it, the job is
+/// recorded as failed and the settlement retries.
+#[utoipa::path(
+    post,
+    path = "/v1/admin/fiat/reserve/queue/{job_id}/resolve",
+    tag = "Admin",
+    params(
+        ("job_id" = String, Path, description = "Queue job ID")
+    ),
+    request_body = ResolveReserveJobRequest,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Job resolved", body = StoredReserveSendJob),
+        (status = 400, description = "Invalid transaction hash"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Not authorized (platform admin required)"),
+        (status = 404, description = "Job not found"),
+        (status = 409, description = "Job is not interrupted")
+    )
+)]
+pub async fn resolve_reserve_job(
+    AdminOnly(admin): AdminOnly,
+    State(state): State<AppState>,
+    Path(job_id): Path<String>,
+    Json(request): Json<ResolveReserveJobRequest>,
+) -> Result<Json<StoredReserveSendJob>, ApiError> {
+    require_platform_admin(&admin)?;
+    let tx_hash = request
+        .tx_hash
+        .map(|hash| hash.trim().to_ascii_lowercase())
+        .filter(|hash| !hash.is_empty());
+    if let Some(hash) = &tx_hash {
+        let valid = hash
+            .strip_prefix("0x")
+            .is_some_and(|hex| hex.len() == 64 && hex.chars().all(|c| c.is_ascii_hexdigit()));
+        if !valid {
+            return Err(ApiError::bad_request(
+                "tx_hash must be a 0x-prefixed 32-byte hex hash",
+            ));
+        }
+    }
+
+    let storage = state.storage();
+    let resolved = update_queue(storage, |queue| {
+        let job = queue
+            .job_mut(&job_id)
+            .ok_or_else(|| ApiError::not_found("Reserve send job not found"))?;
+        if job.status != ReserveSendStatus::Interrupted {
+            return Err(ApiError::conflict("Only interrupted jobs can be resolved"));
+        }
+        match &tx_hash {
+            Some(hash) => {
+                job.status = ReserveSendStatus::Submitted;
+                job.tx_hash = Some(hash.clone());
+                job.error = None;
+            }
+            None => {
+                job.status = ReserveSendStatus::Failed;
+                job.error = Some("Resolved by admin: not broadcast".to_string());
+            }
+        }
+        job.updated_at = chrono::Utc::now();
+        Ok(job.clone())
+    })??;
+
+    info!(
+        job_id = %resolved.job_id,
+        status = ?resolved.status,
+        "Reserve send job resolved"
+    );
+    let event = AuditEvent::new(AuditEventType::AdminAccess)
+        .with_user(&admin.user_id)
+        .with_resource("reserve_send_job", &resolved.job_id)
+        .with_details(serde_json::json!({
+            "action": "reserve_queue_resolve",
+            "request_ids": resolved.request_ids,
+            "tx_hash": resolved.tx_hash,
+        }));
+    let _ = AuditRepository::new(storage).log(&event);
+
+    Ok(Json(resolved))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::{AuthenticatedUser, Role};
+    use chrono::Utc;
+    use std::{sync::Arc, time::Duration};
+
+    fn admin() -> AdminOnly {
+        AdminOnly(AuthenticatedUser {
+            user_id: "admin-1".to_string(),
+            role: Role::Admin,
+            session_id: None,
+            issuer: "https://test.clerk.dev".to_string(),
+            expires_at: Utc::now().timestamp() + 3600,
+            tenant_id: None,
+        })
+    }
+
+    fn sent(tx_hash: &str) -> SendResult {
+        SendResult {
+            tx_hash: tx_hash.to_string(),
+            explorer_url: String::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn sends_never_overlap_and_run_in_arrival_order() {
+        let state = AppState::default();
+        let log = Arc::new(Mutex::new(Vec::new()));
+
+        let mut handles = Vec::new();
+        for i in 0..3 {
+            let storage = state.storage().clone();
+            let log = log.clone();
+            handles.push(tokio::spawn(async move {
+                submit(
+                    &storage,
+                    ReserveSendKind::Mint,
+                    &[],
+                    "0x1",
+                    "1.00",
+                    || async move {
+                        log.lock().unwrap().push(format!("start-{i}"));
+                        tokio::time::sleep(Duration::from_millis(20)).await;
+                        log.lock().unwrap().push(format!("end-{i}"));
+                        Ok(sent(&format!("0x{i}")))
+                    },
+                )
+                .await
+            }));
+            // Make arrival order deterministic.
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        for handle in handles {
+            handle.await.unwrap().unwrap();
+        }
+
+        assert_eq!(
+            *log.lock().unwrap(),
+            vec!["start-0", "end-0", "start-1", "end-1", "start-2", "end-2"]
+        );
+        let queue = ReserveSendQueueRepository::new(state.storage())
+            .load()
+            .unwrap();
+        assert!(queue
+            .jobs
+            .iter()
+            .all(|job| job.status == ReserveSendStatus::Submitted));
+    }
+
+    #[tokio::test]
+    async fn failed_send_is_recorded_on_the_job() {
+        let state = AppState::default();
+        let result = submit(
+            state.storage(),
+            ReserveSendKind::Settlement,
+            &["req-1".to_string()],
+            "0x1",
+            "1.00",
+            || async { Err(ApiError::service_unavailable("rpc down")) },
+        )
+        .await;
+        assert!(result.is_err());
+
+        let job = latest_settlement_job(state.storage(), "req-1").unwrap();
+        assert_eq!(job.status, ReserveSendStatus::Failed);
+        assert_eq!(job.error.as_deref(), Some("rpc down"));
+    }
+
+    #[tokio::test]
+    async fn interrupted_job_can_be_resolved_once() {
+        let state = AppState::default();
+        let storage = state.storage();
+        let job = update_queue(storage, |queue| {
+            let job = queue.enqueue(
+                ReserveSendKind::Settlement,
+                &["req-1".to_string()],
+                "0x1",
+                "1.00",
+            );
+            queue.job_mut(&job.job_id).unwrap().status = ReserveSendStatus::Sending;
+            job
+        })
+        .unwrap();
+        assert_eq!(recover_unfinished(storage).unwrap(), 1);
+
+        let bad = resolve_reserve_job(
+            admin(),
+            State(state.clone()),
+            Path(job.job_id.clone()),
+            Json(ResolveReserveJobRequest {
+                tx_hash: Some("0x1234".to_string()),
+            }),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(bad.status, axum::http::StatusCode::BAD_REQUEST);
+
+        let hash = format!("0x{}", "AB".repeat(32));
+        let Json(resolved) = resolve_reserve_job(
+            admin(),
+            State(state.clone()),
+            Path(job.job_id.clone()),
+            Json(ResolveReserveJobRequest {
+                tx_hash: Some(hash.clone()),
+            }),
+        )
+        .await
+        .unwrap();
+        assert_eq!(resolved.status, ReserveSendStatus::Submitted);
+        assert_eq!(resolved.tx_hash, Some(hash.to_ascii_lowercase()));
+
+        let again = resolve_reserve_job(
+            admin(),
+            State(state.clone()),
+            Path(job.job_id),
+            Json(ResolveReserveJobRequest { tx_hash: None }),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(again.status, axum::http::StatusCode::CONFLICT);
+    }
+}
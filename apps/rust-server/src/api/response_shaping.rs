@@ -0,0 +1,267 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Experimental response fields.
+//!
+//! A field listed in [`EXPERIMENTAL_FIELDS`] is only returned to callers for
+//! whom its feature flag is on; for everyone else, including unauthenticated
+//! callers, the [`shape_experimental_fields`] layer removes it from JSON
+//! responses. Clients can trial a field on a rollout cohort without a
+//! separate build, and drop it again by deleting the flag.
+//!
+//! Fields are matched by name anywhere in a response body, so an
+//! experimental field name must not be used by any other schema. The
+//! OpenAPI document marks each field under its schema's `x-experimental`
+//! extension, mapping field name to flag key.
+
+use axum::{
+    body::{to_bytes, Body},
+    extract::{Request, State},
+    http::{header, HeaderValue},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use serde_json::Value;
+use utoipa::openapi::{schema::Schema, OpenApi, RefOr};
+
+use crate::{
+    auth::{extractor::CallerCell, AuthenticatedUser},
+    state::AppState,
+};
+
+/// OpenAPI extension listing a schema's experimental fields.
+pub const EXPERIMENTAL_EXTENSION: &str = "x-experimental";
+
+/// A response field gated by a feature flag.
+#[derive(Debug, Clone, Copy)]
+pub struct ExperimentalField {
+    /// Feature flag that must be on for the caller.
+    pub flag: &'static str,
+    /// OpenAPI schema that declares the field.
+    pub schema: &'static str,
+    /// Field name.
+    pub field: &'static str,
+}
+
+/// Experimental fields, e.g.
+///
+/// ```rust,ignore
+/// ExperimentalField { flag: "staking", schema: "WalletResponse", field: "staking_apy" },
+/// ```
+pub const EXPERIMENTAL_FIELDS: &[ExperimentalField] = &[];
+
+/// Remove `fields` from every object in `value`.
+fn strip_fields(value: &mut Value, fields: &[&str]) {
+    match value {
+        Value::Object(map) => {
+            map.retain(|key, _| !fields.contains(&key.as_str()));
+            map.values_mut().for_each(|v| strip_fields(v, fields));
+        }
+        Value::Array(items) => items.iter_mut().for_each(|v| strip_fields(v, fields)),
+        _ => {}
+    }
+}
+
+/// Field names the caller may not see.
+fn hidden_fields(
+    state: &AppState,
+    registry: &[ExperimentalField],
+    user: Option<&AuthenticatedUser>,
+) -> Vec<&'static str> {
+    registry
+        .iter()
+        .filter(|f| {
+            !user.is_some_and(|u| state.feature_enabled(f.flag, &u.user_id, u.tenant_id.as_deref()))
+        })
+        .map(|f| f.field)
+        .collect()
+}
+
+/// Layer removing experimental fields the caller's cohort should not see.
+///
+/// Only JSON responses are rewritten. Shaped responses carry
+/// `Vary: Authorization` so shared caches keep cohorts apart.
+pub async fn shape_experimental_fields(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    shape_response(&state, EXPERIMENTAL_FIELDS, request, next).await
+}
+
+async fn shape_response(
+    state: &AppState,
+    registry: &[ExperimentalField],
+    mut request: Request,
+    next: Next,
+) -> Response {
+    if registry.is_empty() {
+        return next.run(request).await;
+    }
+    let caller = CallerCell::default();
+    request.extensions_mut().insert(caller.clone());
+    let response = next.run(request).await;
+
+    let is_json = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|ct| ct.starts_with("application/json"));
+    if !is_json {
+        return response;
+    }
+    let hidden = hidden_fields(state, registry, caller.get());
+    if hidden.is_empty() {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let bytes = match to_bytes(body, usize::MAX).await {
+        Ok(bytes) => bytes,
+        Err(error) => {
+            tracing::warn!(error = %error, "Failed to buffer response for shaping");
+            return axum::http::StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+    let Ok(mut value) = serde_json::from_slice::<Value>(&bytes) else {
+        return Response::from_parts(parts, Body::from(bytes));
+    };
+    strip_fields(&mut value, &hidden);
+    let shaped = serde_json::to_vec(&value).unwrap_or_else(|_| bytes.to_vec());
+    parts.headers.remove(header::CONTENT_LENGTH);
+    parts
+        .headers
+        .append(header::VARY, HeaderValue::from_static("authorization"));
+    Response::from_parts(parts, Body::from(shaped))
+}
+
+/// Mark each registered field under its schema's `x-experimental`
+/// extension as `{ "<field>": "<flag>" }`.
+pub(crate) fn mark_experimental_fields(openapi: &mut OpenApi, registry: &[ExperimentalField]) {
+    let Some(components) = openapi.components.as_mut() else {
+        return;
+    };
+    for field in registry {
+        let Some(RefOr::T(Schema::Object(object))) = components.schemas.get_mut(field.schema)
+        else {
+            continue;
+        };
+        let extensions = object.extensions.get_or_insert_with(Default::default);
+        let marked = extensions
+            .entry(EXPERIMENTAL_EXTENSION.to_string())
+            .or_insert_with(|| Value::Object(Default::default()));
+        marked[field.field] = Value::from(field.flag);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        api::ApiDoc,
+        auth::{extractor::OptionalAuth, AuthenticatedUser, Role},
+        storage::{FeatureFlagRepository, StoredFeatureFlag},
+    };
+    use axum::{middleware::from_fn_with_state, routing::get, Json, Router};
+    use chrono::Utc;
+    use serde_json::json;
+    use tower::ServiceExt;
+    use utoipa::OpenApi as _;
+
+    const TRIAL: &[ExperimentalField] = &[ExperimentalField {
+        flag: "staking",
+        schema: "HealthResponse",
+        field: "staking_apy",
+    }];
+
+    async fn trial_layer(State(state): State<AppState>, request: Request, next: Next) -> Response {
+        shape_response(&state, TRIAL, request, next).await
+    }
+
+    async fn wallet(_auth: OptionalAuth) -> Json<Value> {
+        Json(json!({
+            "wallets": [{ "wallet_id": "w-1", "staking_apy": "4.1" }],
+            "total": 1
+        }))
+    }
+
+    async fn call(state: &AppState, user_id: Option<&str>) -> Value {
+        let app = Router::new()
+            .route("/wallets", get(wallet))
+            .layer(from_fn_with_state(state.clone(), trial_layer))
+            .with_state(state.clone());
+        let mut request = axum::http::Request::builder()
+            .uri("/wallets")
+            .body(Body::empty())
+            .unwrap();
+        if let Some(user_id) = user_id {
+            request.extensions_mut().insert(AuthenticatedUser {
+                user_id: user_id.to_string(),
+                role: Role::Client,
+                session_id: None,
+                issuer: "test".to_string(),
+                expires_at: 0,
+                tenant_id: None,
+            });
+        }
+        let response = app.oneshot(request).await.unwrap();
+        let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        serde_json::from_slice(&bytes).unwrap()
+    }
+
+    #[tokio::test]
+    async fn experimental_fields_reach_only_the_flag_cohort() {
+        let state = AppState::default();
+        FeatureFlagRepository::new(state.storage())
+            .save(&StoredFeatureFlag {
+                key: "staking".to_string(),
+                description: None,
+                enabled: true,
+                rollout_percent: 0,
+                allowed_users: vec!["user_beta".to_string()],
+                tenants: Vec::new(),
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+                updated_by: "admin".to_string(),
+            })
+            .unwrap();
+
+        let beta = call(&state, Some("user_beta")).await;
+        assert_eq!(beta["wallets"][0]["staking_apy"], "4.1");
+        let other = call(&state, Some("user_other")).await;
+        assert!(other["wallets"][0].get("staking_apy").is_none());
+        assert_eq!(other["wallets"][0]["wallet_id"], "w-1");
+        let anonymous = call(&state, None).await;
+        assert!(anonymous["wallets"][0].get("staking_apy").is_none());
+    }
+
+    #[test]
+    fn openapi_marks_experimental_fields() {
+        let mut openapi = ApiDoc::openapi();
+        mark_experimental_fields(&mut openapi, TRIAL);
+        let doc = serde_json::to_value(&openapi).unwrap();
+        assert_eq!(
+            doc["components"]["schemas"]["HealthResponse"][EXPERIMENTAL_EXTENSION]["staking_apy"],
+            "staking"
+        );
+    }
+
+    #[test]
+    fn registered_fields_exist_and_are_unique_to_their_schema() {
+        let doc = serde_json::to_value(ApiDoc::openapi()).unwrap();
+        let schemas = doc["components"]["schemas"].as_object().unwrap();
+        for field in EXPERIMENTAL_FIELDS {
+            for (name, schema) in schemas {
+                let declared = schema["properties"].get(field.field).is_some();
+                assert_eq!(
+                    declared,
+                    name == field.schema,
+                    "experimental field {} must be declared only by {}",
+                    field.field,
+                    field.schema
+                );
+            }
+        }
+    }
+}
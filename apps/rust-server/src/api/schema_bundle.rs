@@ -0,0 +1,84 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Request and response models as one JSON Schema document.
+//!
+//! `GET /api-doc/schemas.json` serves the OpenAPI component schemas as a
+//! JSON Schema (draft 2020-12) bundle, each model under `$defs`, so the
+//! frontend can generate its TypeScript types from the running server
+//! (for example with `json-schema-to-typescript`) and stay in step with
+//! it. OpenAPI 3.1 schemas are JSON Schema already; only their
+//! `#/components/schemas/` references are rewritten to `#/$defs/`.
+
+use serde_json::{json, Map, Value};
+use utoipa::openapi::OpenApi;
+
+/// Path the bundle is served at; also its `$id`.
+pub const SCHEMA_BUNDLE_PATH: &str = "/api-doc/schemas.json";
+
+const COMPONENT_REF_PREFIX: &str = "#/components/schemas/";
+const DEFS_REF_PREFIX: &str = "#/$defs/";
+
+/// Point component references at `$defs`.
+fn rewrite_refs(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, child) in map.iter_mut() {
+                match child {
+                    Value::String(target) if key == "$ref" => {
+                        if let Some(name) = target.strip_prefix(COMPONENT_REF_PREFIX) {
+                            *target = format!("{DEFS_REF_PREFIX}{name}");
+                        }
+                    }
+                    _ => rewrite_refs(child),
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(rewrite_refs),
+        _ => {}
+    }
+}
+
+/// The component schemas of `doc` as a JSON Schema bundle.
+pub fn schema_bundle(doc: &OpenApi) -> Value {
+    let mut defs = doc
+        .components
+        .as_ref()
+        .and_then(|components| serde_json::to_value(&components.schemas).ok())
+        .unwrap_or_else(|| Value::Object(Map::new()));
+    rewrite_refs(&mut defs);
+    json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "$id": SCHEMA_BUNDLE_PATH,
+        "title": format!("{} models", doc.info.title),
+        "x-api-version": doc.info.version,
+        "$defs": defs,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn references_point_into_the_bundle() {
+        let mut value = json!({
+            "properties": {
+                "wallet": { "$ref": "#/components/schemas/WalletResponse" },
+                "items": { "type": "array", "items": { "$ref": "#/components/schemas/Tx" } },
+                "other": { "$ref": "https://example.org/schema.json" }
+            }
+        });
+        rewrite_refs(&mut value);
+        assert_eq!(
+            value["properties"]["wallet"]["$ref"],
+            "#/$defs/WalletResponse"
+        );
+        assert_eq!(value["properties"]["items"]["items"]["$ref"], "#/$defs/Tx");
+        assert_eq!(
+            value["properties"]["other"]["$ref"],
+            "https://example.org/schema.json"
+        );
+    }
+}
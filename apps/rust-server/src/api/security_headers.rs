@@ -0,0 +1,376 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! CORS policy and security response headers.
+//!
+//! Browsers may call the API from `CORS_ALLOWED_ORIGINS` plus each tenant's
+//! configured `cors_origins`, which are read at request time so changes apply
+//! without a restart. Without `CORS_ALLOWED_ORIGINS` any origin is allowed
+//! (development only). Either way only the configured methods and request
+//! headers are allowed, and some routes narrow that further:
+//!
+//! - provider webhooks and peer discovery are server-to-server and never
+//!   answer cross-origin requests;
+//! - health checks, the OpenAPI document and the Swagger UI are read-only.
+//!
+//! Every response carries `X-Content-Type-Options: nosniff` and, unless
+//! disabled, `Strict-Transport-Security`; Swagger UI pages also get a
+//! `Content-Security-Policy`.
+
+use std::{env, sync::Arc, time::Duration};
+
+use axum::{
+    extract::{Request, State},
+    http::{
+        header::{
+            ACCEPT, ACCEPT_LANGUAGE, ACCESS_CONTROL_REQUEST_METHOD, AUTHORIZATION,
+            CONTENT_LANGUAGE, CONTENT_SECURITY_POLICY, CONTENT_TYPE, STRICT_TRANSPORT_SECURITY,
+            X_CONTENT_TYPE_OPTIONS,
+        },
+        request::Parts,
+        HeaderName, HeaderValue, Method,
+    },
+    middleware::Next,
+    response::Response,
+};
+use tower_http::cors::{AllowOrigin, CorsLayer};
+
+use super::soft_quotas::{
+    X_POLICY_WARNING, X_RATELIMIT_LIMIT, X_RATELIMIT_POLICY, X_RATELIMIT_REMAINING,
+    X_RATELIMIT_RESET,
+};
+use crate::{
+    auth::{replay, request_signing::ADMIN_SIGNATURE_HEADER},
+    storage::{EncryptedStorage, TenantConfigRepository},
+};
+
+/// One year, the usual HSTS lifetime.
+const DEFAULT_HSTS_MAX_AGE_SECS: u64 = 31_536_000;
+
+/// Swagger UI loads its scripts and styles from `/docs/`, sets inline
+/// styles and uses `data:` images.
+const DEFAULT_DOCS_CSP: &str = "default-src 'self'; script-src 'self'; \
+    style-src 'self' 'unsafe-inline'; img-src 'self' data:; connect-src 'self'; \
+    object-src 'none'; base-uri 'self'; frame-ancestors 'none'";
+
+/// How long browsers may cache a preflight answer.
+const PREFLIGHT_MAX_AGE: Duration = Duration::from_secs(600);
+
+const X_REQUEST_ID: &str = "x-request-id";
+
+/// Methods browsers may use, unless `CORS_ALLOWED_METHODS` says otherwise.
+fn default_methods() -> Vec<Method> {
+    vec![Method::GET, Method::POST, Method::PUT, Method::DELETE]
+}
+
+/// Request headers browsers may send, unless `CORS_ALLOWED_HEADERS` says
+/// otherwise.
+fn default_headers() -> Vec<HeaderName> {
+    vec![
+        AUTHORIZATION,
+        CONTENT_TYPE,
+        ACCEPT,
+        ACCEPT_LANGUAGE,
+        HeaderName::from_static(X_REQUEST_ID),
+        HeaderName::from_static(replay::TIMESTAMP_HEADER),
+        HeaderName::from_static(replay::NONCE_HEADER),
+        HeaderName::from_static(replay::SIGNATURE_HEADER),
+        HeaderName::from_static(ADMIN_SIGNATURE_HEADER),
+    ]
+}
+
+/// Response headers browser clients may read.
+fn exposed_headers() -> Vec<HeaderName> {
+    [
+        X_REQUEST_ID,
+        X_RATELIMIT_LIMIT,
+        X_RATELIMIT_REMAINING,
+        X_RATELIMIT_RESET,
+        X_RATELIMIT_POLICY,
+        X_POLICY_WARNING,
+    ]
+    .into_iter()
+    .map(HeaderName::from_static)
+    .chain([CONTENT_LANGUAGE])
+    .collect()
+}
+
+/// CORS and security header settings.
+#[derive(Debug, Clone)]
+pub struct SecurityConfig {
+    /// Allowed origins besides the tenants'; `None` allows any origin.
+    pub cors_origins: Option<Vec<HeaderValue>>,
+    pub cors_methods: Vec<Method>,
+    pub cors_headers: Vec<HeaderName>,
+    /// `Strict-Transport-Security` value, or `None` to omit it.
+    pub hsts: Option<HeaderValue>,
+    /// `Content-Security-Policy` of the Swagger UI.
+    pub docs_csp: HeaderValue,
+}
+
+impl Default for SecurityConfig {
+    fn default() -> Self {
+        Self {
+            cors_origins: None,
+            cors_methods: default_methods(),
+            cors_headers: default_headers(),
+            hsts: Some(hsts_value(DEFAULT_HSTS_MAX_AGE_SECS)),
+            docs_csp: HeaderValue::from_static(DEFAULT_DOCS_CSP),
+        }
+    }
+}
+
+fn hsts_value(max_age_secs: u64) -> HeaderValue {
+    HeaderValue::from_str(&format!("max-age={max_age_secs}; includeSubDomains"))
+        .expect("HSTS value is ASCII")
+}
+
+/// Comma-separated entries of `value`, skipping blanks and ones `parse`
+/// rejects.
+fn parse_list<T>(name: &str, value: &str, parse: impl Fn(&str) -> Option<T>) -> Vec<T> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .filter_map(|entry| {
+            let parsed = parse(entry);
+            if parsed.is_none() {
+                tracing::warn!(entry, "Ignoring malformed {name} entry");
+            }
+            parsed
+        })
+        .collect()
+}
+
+impl SecurityConfig {
+    /// Settings from `CORS_ALLOWED_ORIGINS`, `CORS_ALLOWED_METHODS`,
+    /// `CORS_ALLOWED_HEADERS`, `HSTS_MAX_AGE_SECS` (0 disables HSTS) and
+    /// `DOCS_CONTENT_SECURITY_POLICY`.
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+        if let Ok(origins) = env::var("CORS_ALLOWED_ORIGINS") {
+            tracing::info!(origins = %origins, "CORS: restricting to configured origins");
+            config.cors_origins = Some(parse_list("CORS_ALLOWED_ORIGINS", &origins, |o| {
+                HeaderValue::from_str(o).ok()
+            }));
+        } else {
+            tracing::warn!("CORS_ALLOWED_ORIGINS not set - allowing any origin (development only)");
+        }
+        if let Ok(methods) = env::var("CORS_ALLOWED_METHODS") {
+            config.cors_methods = parse_list("CORS_ALLOWED_METHODS", &methods, |m| {
+                Method::from_bytes(m.to_ascii_uppercase().as_bytes()).ok()
+            });
+        }
+        if let Ok(headers) = env::var("CORS_ALLOWED_HEADERS") {
+            config.cors_headers = parse_list("CORS_ALLOWED_HEADERS", &headers, |h| {
+                HeaderName::from_bytes(h.as_bytes()).ok()
+            });
+        }
+        if let Ok(max_age) = env::var("HSTS_MAX_AGE_SECS") {
+            match max_age.trim().parse::<u64>() {
+                Ok(0) => config.hsts = None,
+                Ok(secs) => config.hsts = Some(hsts_value(secs)),
+                Err(_) => tracing::warn!(value = %max_age, "Ignoring malformed HSTS_MAX_AGE_SECS"),
+            }
+        }
+        if let Ok(csp) = env::var("DOCS_CONTENT_SECURITY_POLICY") {
+            match HeaderValue::from_str(csp.trim()) {
+                Ok(csp) => config.docs_csp = csp,
+                Err(_) => tracing::warn!("Ignoring malformed DOCS_CONTENT_SECURITY_POLICY"),
+            }
+        }
+        config
+    }
+}
+
+fn is_docs_path(path: &str) -> bool {
+    path == "/docs" || path.starts_with("/docs/")
+}
+
+/// Whether a cross-origin `method` request to `path` may be allowed.
+pub fn route_allows(path: &str, method: &Method, allowed: &[Method]) -> bool {
+    if path.starts_with("/v1/internal/") || path.ends_with("/webhook") {
+        return false;
+    }
+    let read_only = path == "/health"
+        || path.starts_with("/health/")
+        || path.starts_with("/api-doc/")
+        || is_docs_path(path);
+    if read_only {
+        return *method == Method::GET;
+    }
+    allowed.contains(method)
+}
+
+/// Method of the actual request, also for preflights.
+fn requested_method(parts: &Parts) -> Method {
+    parts
+        .headers
+        .get(ACCESS_CONTROL_REQUEST_METHOD)
+        .and_then(|m| Method::from_bytes(m.as_bytes()).ok())
+        .unwrap_or_else(|| parts.method.clone())
+}
+
+/// CORS layer for `config`.
+pub fn cors_layer(config: &SecurityConfig, storage: Arc<EncryptedStorage>) -> CorsLayer {
+    let origins = config.cors_origins.clone();
+    let methods = config.cors_methods.clone();
+    CorsLayer::new()
+        .allow_origin(AllowOrigin::predicate(move |origin, parts| {
+            if !route_allows(parts.uri.path(), &requested_method(parts), &methods) {
+                return false;
+            }
+            let Some(allowed) = &origins else {
+                return true;
+            };
+            allowed.contains(origin)
+                || origin.to_str().is_ok_and(|origin| {
+                    TenantConfigRepository::new(&storage)
+                        .list_all()
+                        .unwrap_or_default()
+                        .iter()
+                        .any(|config| config.cors_origins.iter().any(|o| o == origin))
+                })
+        }))
+        .allow_methods(config.cors_methods.clone())
+        .allow_headers(config.cors_headers.clone())
+        .expose_headers(exposed_headers())
+        .max_age(PREFLIGHT_MAX_AGE)
+}
+
+/// Layer adding security headers to every response.
+pub async fn set_security_headers(
+    State(config): State<Arc<SecurityConfig>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let docs = is_docs_path(request.uri().path());
+    let mut response = next.run(request).await;
+    let headers = response.headers_mut();
+    headers.insert(X_CONTENT_TYPE_OPTIONS, HeaderValue::from_static("nosniff"));
+    if let Some(hsts) = &config.hsts {
+        headers.insert(STRICT_TRANSPORT_SECURITY, hsts.clone());
+    }
+    if docs {
+        headers.insert(CONTENT_SECURITY_POLICY, config.docs_csp.clone());
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        api::{router, DocsListener},
+        state::AppState,
+    };
+    use axum::{body::Body, http::StatusCode};
+    use tower::ServiceExt;
+
+    fn preflight(path: &str, method: &str) -> axum::http::Request<Body> {
+        axum::http::Request::builder()
+            .method("OPTIONS")
+            .uri(path)
+            .header("origin", "https://app.example")
+            .header(ACCESS_CONTROL_REQUEST_METHOD, method)
+            .body(Body::empty())
+            .unwrap()
+    }
+
+    #[test]
+    fn routes_narrow_cross_origin_methods() {
+        let allowed = default_methods();
+        assert!(route_allows("/v1/wallets", &Method::POST, &allowed));
+        assert!(!route_allows("/v1/wallets", &Method::PATCH, &allowed));
+        assert!(route_allows("/health/ready", &Method::GET, &allowed));
+        assert!(!route_allows("/docs/index.html", &Method::POST, &allowed));
+        assert!(!route_allows(
+            "/v1/fiat/providers/truelayer/webhook",
+            &Method::POST,
+            &allowed
+        ));
+        assert!(!route_allows(
+            "/v1/internal/discovery/lookup",
+            &Method::POST,
+            &allowed
+        ));
+    }
+
+    #[tokio::test]
+    async fn preflights_follow_the_route_policy() {
+        let state = AppState::default();
+        let app = router(state.clone(), DocsListener::Api).layer(cors_layer(
+            &SecurityConfig {
+                cors_origins: Some(vec![HeaderValue::from_static("https://app.example")]),
+                ..Default::default()
+            },
+            state.storage().clone(),
+        ));
+
+        let response = app
+            .clone()
+            .oneshot(preflight("/v1/wallets", "POST"))
+            .await
+            .unwrap();
+        let headers = response.headers();
+        assert_eq!(
+            headers.get("access-control-allow-origin").unwrap(),
+            "https://app.example"
+        );
+        let allowed_headers = headers
+            .get("access-control-allow-headers")
+            .unwrap()
+            .to_str()
+            .unwrap();
+        assert!(allowed_headers.contains("authorization"));
+        assert!(!allowed_headers.contains('*'));
+
+        let response = app
+            .oneshot(preflight("/v1/fiat/providers/card/webhook", "POST"))
+            .await
+            .unwrap();
+        assert!(response
+            .headers()
+            .get("access-control-allow-origin")
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn responses_carry_security_headers() {
+        let app = router(AppState::default(), DocsListener::Api);
+
+        let response = app
+            .clone()
+            .oneshot(
+                axum::http::Request::builder()
+                    .uri("/health/live")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let headers = response.headers();
+        assert_eq!(headers.get(X_CONTENT_TYPE_OPTIONS).unwrap(), "nosniff");
+        assert!(headers.get(STRICT_TRANSPORT_SECURITY).is_some());
+        assert!(headers.get(CONTENT_SECURITY_POLICY).is_none());
+
+        let response = app
+            .oneshot(
+                axum::http::Request::builder()
+                    .uri("/docs/")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(response
+            .headers()
+            .get(CONTENT_SECURITY_POLICY)
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .contains("frame-ancestors 'none'"));
+    }
+}
@@ -0,0 +1,678 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Holds on sends from unfamiliar sessions.
+//!
+//! When a send comes from a session whose network and device both match
+//! none of the user's recent sessions, it is stored as a hold instead of
+//! being broadcast, and `POST /v1/wallets/{id}/send` answers 202. The user
+//! confirms it as a step-up from another session they were already using
+//! before the hold, or cancels it from any session. Unconfirmed holds expire
+//! after the user's chosen window and are never sent.
+
+use std::sync::Mutex;
+
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::{
+    api::{
+        limits::check_spending_limits,
+        transactions::{
+            execute_send, sending_wallet, SendTransactionRequest, SendTransactionResponse,
+        },
+    },
+    auth::{Auth, AuthenticatedUser},
+    error::{ApiError, StorageContext},
+    state::AppState,
+    storage::{
+        repository::send_holds::MAX_HOLD_MINUTES, AuditEvent, AuditEventType, AuditRepository,
+        EncryptedStorage, OwnershipEnforcer, SendHoldRepository, SendHoldSettings, SendHoldStatus,
+        SessionAnomaly, SessionLogRepository, StoredSendHold, WalletRepository,
+    },
+};
+
+/// Serializes hold status transitions so a hold is broadcast at most once.
+static HOLD_LOCK: Mutex<()> = Mutex::new(());
+
+/// A held send as shown to its owner.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct SendHoldResponse {
+    pub hold_id: String,
+    pub wallet_id: String,
+    pub status: SendHoldStatus,
+    pub to: String,
+    pub amount: String,
+    pub token: String,
+    pub network: String,
+    /// Why the send was held.
+    pub anomalies: Vec<SessionAnomaly>,
+    /// Client IP of the session that initiated the send.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ip_address: Option<String>,
+    /// `User-Agent` of the session that initiated the send.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_agent: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tx_hash: Option<String>,
+    pub created_at: DateTime<Utc>,
+    /// The send is dropped if not confirmed by then.
+    pub expires_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+impl From<&StoredSendHold> for SendHoldResponse {
+    fn from(hold: &StoredSendHold) -> Self {
+        Self {
+            hold_id: hold.hold_id.clone(),
+            wallet_id: hold.wallet_id.clone(),
+            status: hold.status_at(Utc::now()),
+            to: hold.to.clone(),
+            amount: hold.amount.clone(),
+            token: hold.token.clone(),
+            network: hold.network.clone(),
+            anomalies: hold.anomalies.clone(),
+            ip_address: hold.ip_address.clone(),
+            user_agent: hold.user_agent.clone(),
+            tx_hash: hold.tx_hash.clone(),
+            created_at: hold.created_at,
+            expires_at: hold.expires_at,
+            resolved_at: hold.resolved_at,
+        }
+    }
+}
+
+/// Hold a send if the caller's session is unlike all of their recent ones.
+///
+/// Returns `None` when the send may go ahead: holds are disabled for the
+/// user, the token carries no session ID, or the session resembles the
+/// user's history in network or device.
+pub(crate) fn hold_if_unfamiliar(
+    storage: &EncryptedStorage,
+    user: &AuthenticatedUser,
+    wallet_id: &str,
+    to_address: &str,
+    request: &SendTransactionRequest,
+) -> Result<Option<StoredSendHold>, ApiError> {
+    let Some(session_id) = user.session_id.as_deref() else {
+        return Ok(None);
+    };
+    let repo = SendHoldRepository::new(storage);
+    let settings = repo
+        .settings(&user.user_id)
+        .context("Failed to read send-hold settings")?;
+    if !settings.enabled {
+        return Ok(None);
+    }
+    let log = SessionLogRepository::new(storage)
+        .get(&user.user_id)
+        .context("Failed to read session history")?;
+    let Some(session) = log.find(session_id) else {
+        return Ok(None);
+    };
+    let now = Utc::now();
+    let anomalies = log.anomalies(session, now);
+    let sharp = anomalies.contains(&SessionAnomaly::UnfamiliarNetwork)
+        && anomalies.contains(&SessionAnomaly::UnfamiliarDevice);
+    if !sharp {
+        return Ok(None);
+    }
+
+    let hold = StoredSendHold {
+        hold_id: uuid::Uuid::new_v4().to_string(),
+        wallet_id: wallet_id.to_string(),
+        owner_user_id: user.user_id.clone(),
+        to: to_address.to_string(),
+        amount: request.amount.clone(),
+        token: request.token.clone(),
+        network: request.network.clone(),
+        gas_limit: request.gas_limit.clone(),
+        max_priority_fee_per_gas: request.max_priority_fee_per_gas.clone(),
+        fee_speed: request.fee_speed,
+        session_id: session.session_id.clone(),
+        ip_address: session.ip_address.clone(),
+        user_agent: session.user_agent.clone(),
+        anomalies,
+        status: SendHoldStatus::Held,
+        tx_hash: None,
+        created_at: now,
+        expires_at: now + Duration::minutes(settings.hold_minutes as i64),
+        resolved_at: None,
+    };
+    repo.create(&hold).context("Failed to store send hold")?;
+
+    tracing::warn!(
+        hold_id = %hold.hold_id,
+        wallet_id = %wallet_id,
+        "Send from an unfamiliar session held for confirmation"
+    );
+    let mut event = AuditEvent::new(AuditEventType::SendHeld)
+        .with_user(&user.user_id)
+        .with_resource("wallet", wallet_id)
+        .with_details(serde_json::json!({
+            "hold_id": hold.hold_id,
+            "to": hold.to,
+            "amount": hold.amount,
+            "token": hold.token,
+            "anomalies": hold.anomalies,
+            "expires_at": hold.expires_at,
+        }));
+    if let Some(ip) = &hold.ip_address {
+        event = event.with_ip(ip);
+    }
+    let _ = AuditRepository::new(storage).log(&event);
+
+    Ok(Some(hold))
+}
+
+/// Check that `user` owns the wallet.
+fn ensure_wallet_owner(
+    storage: &EncryptedStorage,
+    user: &AuthenticatedUser,
+    wallet_id: &str,
+) -> Result<(), ApiError> {
+    let wallet = WalletRepository::new(storage).get(wallet_id)?;
+    if !wallet.is_owned_by(user) {
+        return Err(ApiError::forbidden("You do not own this wallet").with_code("wallet_not_owned"));
+    }
+    Ok(())
+}
+
+/// Load a hold of the wallet, checking that `user` owns both.
+fn owned_hold(
+    storage: &EncryptedStorage,
+    user: &AuthenticatedUser,
+    wallet_id: &str,
+    hold_id: &str,
+) -> Result<StoredSendHold, ApiError> {
+    ensure_wallet_owner(storage, user, wallet_id)?;
+    let hold = SendHoldRepository::new(storage).get(hold_id)?;
+    if hold.wallet_id != wallet_id || hold.owner_user_id != user.user_id {
+        return Err(ApiError::not_found("Send hold not found"));
+    }
+    Ok(hold)
+}
+
+/// Check that the caller may confirm `hold`: from a different session that
+/// predates the hold and is itself familiar once the holding session is
+/// left out of the history.
+fn ensure_step_up(
+    storage: &EncryptedStorage,
+    user: &AuthenticatedUser,
+    hold: &StoredSendHold,
+) -> Result<(), ApiError> {
+    let refused = || {
+        ApiError::forbidden(
+            "Confirm this send from a device you were already signed in on before it was held",
+        )
+    };
+    let session_id = user.session_id.as_deref().ok_or_else(refused)?;
+    if hold.session_id.as_deref() == Some(session_id) {
+        return Err(refused());
+    }
+    let mut log = SessionLogRepository::new(storage)
+        .get(&user.user_id)
+        .context("Failed to read session history")?;
+    log.sessions.retain(|s| s.session_id != hold.session_id);
+    let session = log
+        .find(session_id)
+        .filter(|s| s.first_seen < hold.created_at)
+        .ok_or_else(refused)?;
+    if !log.anomalies(session, Utc::now()).is_empty() {
+        return Err(refused());
+    }
+    Ok(())
+}
+
+/// Move a held send to `status`, failing if it is no longer held.
+fn transition(
+    storage: &EncryptedStorage,
+    hold_id: &str,
+    status: SendHoldStatus,
+) -> Result<StoredSendHold, ApiError> {
+    let _guard = HOLD_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let repo = SendHoldRepository::new(storage);
+    let mut hold = repo.get(hold_id)?;
+    let now = Utc::now();
+    match hold.status_at(now) {
+        SendHoldStatus::Held => {}
+        SendHoldStatus::Expired => {
+            return Err(ApiError::conflict("Send hold has expired"));
+        }
+        _ => {
+            return Err(ApiError::conflict("Send hold is already resolved"));
+        }
+    }
+    hold.status = status;
+    hold.resolved_at = Some(now);
+    repo.update(&hold).context("Failed to update send hold")?;
+    Ok(hold)
+}
+
+/// List a wallet's held sends.
+#[utoipa::path(
+    get,
+    path = "/v1/wallets/{wallet_id}/send-holds",
+    tag = "Transactions",
+    params(("wallet_id" = String, Path, description = "Wallet ID")),
+    security(("bearer" = [])),
+    responses(
+        (status = 200, description = "Held sends, newest first", body = [SendHoldResponse]),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - not wallet owner"),
+        (status = 404, description = "Wallet not found")
+    )
+)]
+pub async fn list_send_holds(
+    Auth(user): Auth,
+    State(state): State<AppState>,
+    Path(wallet_id): Path<String>,
+) -> Result<Json<Vec<SendHoldResponse>>, ApiError> {
+    let storage = state.storage();
+    ensure_wallet_owner(storage, &user, &wallet_id)?;
+    let holds = SendHoldRepository::new(storage)
+        .list_by_wallet(&wallet_id)
+        .context("Failed to list send holds")?;
+    Ok(Json(holds.iter().map(Into::into).collect()))
+}
+
+/// Confirm and broadcast a held send.
+///
+/// Must come from another session the user was signed in on before the
+/// send was held, whose network and device match their history.
+#[utoipa::path(
+    post,
+    path = "/v1/wallets/{wallet_id}/send-holds/{hold_id}/confirm",
+    tag = "Transactions",
+    params(
+        ("wallet_id" = String, Path, description = "Wallet ID"),
+        ("hold_id" = String, Path, description = "Send hold ID")
+    ),
+    security(("bearer" = [])),
+    responses(
+        (status = 200, description = "Transaction submitted", body = SendTransactionResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Not wallet owner, or the session may not confirm"),
+        (status = 404, description = "Wallet or hold not found"),
+        (status = 409, description = "Hold expired or already resolved"),
+        (status = 422, description = "Insufficient balance or spending limit exceeded"),
+        (status = 503, description = "Blockchain network unavailable")
+    )
+)]
+pub async fn confirm_send_hold(
+    Auth(user): Auth,
+    State(state): State<AppState>,
+    Path((wallet_id, hold_id)): Path<(String, String)>,
+) -> Result<Json<SendTransactionResponse>, ApiError> {
+    let storage = state.storage();
+    let hold = owned_hold(storage, &user, &wallet_id, &hold_id)?;
+    ensure_step_up(storage, &user, &hold)?;
+    let wallet = sending_wallet(storage, &user, &wallet_id)?;
+    // Spending since the hold was placed counts too.
+    check_spending_limits(
+        &state,
+        &wallet,
+        &[(&hold.token, &hold.amount)],
+        &hold.network,
+    )
+    .await?;
+
+    let mut hold = transition(storage, &hold_id, SendHoldStatus::Confirmed)?;
+    let request = SendTransactionRequest {
+        to: Some(hold.to.clone()),
+        to_email_hash: None,
+        amount: hold.amount.clone(),
+        token: hold.token.clone(),
+        network: hold.network.clone(),
+        gas_limit: hold.gas_limit.clone(),
+        max_priority_fee_per_gas: hold.max_priority_fee_per_gas.clone(),
+        fee_speed: hold.fee_speed,
+    };
+    let repo = SendHoldRepository::new(storage);
+    let response = match execute_send(&state, &user.user_id, &wallet, &hold.to, &request).await {
+        Ok(response) => response,
+        Err(error) => {
+            // Nothing was broadcast; let the user retry within the window.
+            hold.status = SendHoldStatus::Held;
+            hold.resolved_at = None;
+            if let Err(e) = repo.update(&hold) {
+                tracing::warn!(hold_id = %hold_id, error = %e, "Failed to reopen send hold");
+            }
+            return Err(error);
+        }
+    };
+    hold.tx_hash = Some(response.tx_hash.clone());
+    if let Err(e) = repo.update(&hold) {
+        tracing::warn!(hold_id = %hold_id, error = %e, "Failed to record held send hash");
+    }
+
+    let event = AuditEvent::new(AuditEventType::SendHoldConfirmed)
+        .with_user(&user.user_id)
+        .with_resource("wallet", &wallet_id)
+        .with_details(serde_json::json!({
+            "hold_id": hold_id,
+            "tx_hash": response.tx_hash,
+        }));
+    let _ = AuditRepository::new(storage).log(&event);
+
+    Ok(Json(response))
+}
+
+/// Cancel a held send. Allowed from any of the user's sessions.
+#[utoipa::path(
+    post,
+    path = "/v1/wallets/{wallet_id}/send-holds/{hold_id}/cancel",
+    tag = "Transactions",
+    params(
+        ("wallet_id" = String, Path, description = "Wallet ID"),
+        ("hold_id" = String, Path, description = "Send hold ID")
+    ),
+    security(("bearer" = [])),
+    responses(
+        (status = 200, description = "Hold cancelled", body = SendHoldResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - not wallet owner"),
+        (status = 404, description = "Wallet or hold not found"),
+        (status = 409, description = "Hold expired or already resolved")
+    )
+)]
+pub async fn cancel_send_hold(
+    Auth(user): Auth,
+    State(state): State<AppState>,
+    Path((wallet_id, hold_id)): Path<(String, String)>,
+) -> Result<Json<SendHoldResponse>, ApiError> {
+    let storage = state.storage();
+    owned_hold(storage, &user, &wallet_id, &hold_id)?;
+    let hold = transition(storage, &hold_id, SendHoldStatus::Cancelled)?;
+
+    let event = AuditEvent::new(AuditEventType::SendHoldCancelled)
+        .with_user(&user.user_id)
+        .with_resource("wallet", &wallet_id)
+        .with_details(serde_json::json!({ "hold_id": hold_id }));
+    let _ = AuditRepository::new(storage).log(&event);
+
+    Ok(Json((&hold).into()))
+}
+
+/// The caller's send-hold settings.
+#[utoipa::path(
+    get,
+    path = "/v1/users/me/send-hold-settings",
+    tag = "Users",
+    security(("bearer" = [])),
+    responses(
+        (status = 200, description = "Send-hold settings", body = SendHoldSettings),
+        (status = 401, description = "Unauthorized")
+    )
+)]
+pub async fn get_send_hold_settings(
+    Auth(user): Auth,
+    State(state): State<AppState>,
+) -> Result<Json<SendHoldSettings>, ApiError> {
+    let settings = SendHoldRepository::new(state.storage())
+        .settings(&user.user_id)
+        .context("Failed to read send-hold settings")?;
+    Ok(Json(settings))
+}
+
+/// Turn send holds on or off, or change how long a hold waits.
+#[utoipa::path(
+    put,
+    path = "/v1/users/me/send-hold-settings",
+    tag = "Users",
+    request_body = SendHoldSettings,
+    security(("bearer" = [])),
+    responses(
+        (status = 200, description = "Settings saved", body = SendHoldSettings),
+        (status = 400, description = "hold_minutes out of range"),
+        (status = 401, description = "Unauthorized")
+    )
+)]
+pub async fn put_send_hold_settings(
+    Auth(user): Auth,
+    State(state): State<AppState>,
+    Json(settings): Json<SendHoldSettings>,
+) -> Result<Json<SendHoldSettings>, ApiError> {
+    if !(1..=MAX_HOLD_MINUTES).contains(&settings.hold_minutes) {
+        return Err(ApiError::bad_request(format!(
+            "hold_minutes must be between 1 and {MAX_HOLD_MINUTES}"
+        )));
+    }
+    let storage = state.storage();
+    let repo = SendHoldRepository::new(storage);
+    let old = repo
+        .settings(&user.user_id)
+        .context("Failed to read send-hold settings")?;
+    repo.set_settings(&user.user_id, &settings)
+        .context("Failed to save send-hold settings")?;
+
+    let event = AuditEvent::config_changed(
+        serde_json::to_value(&old).ok(),
+        serde_json::to_value(&settings).ok(),
+    )
+    .with_user(&user.user_id)
+    .with_resource("send_hold_settings", &user.user_id);
+    let _ = AuditRepository::new(storage).log(&event);
+
+    Ok(Json(settings))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        api::transactions::send_transaction,
+        auth::Role,
+        storage::{
+            repository::send_holds::DEFAULT_HOLD_MINUTES, SessionObservation, WalletMetadata,
+            WalletStatus,
+        },
+    };
+    use axum::http::StatusCode;
+
+    const MAC_CHROME: &str = "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/126.0 Safari/537.36";
+    const ANDROID_FIREFOX: &str =
+        "Mozilla/5.0 (Android 14; Mobile; rv:128.0) Gecko/128.0 Firefox/128.0";
+
+    fn auth(session_id: &str) -> Auth {
+        Auth(AuthenticatedUser {
+            user_id: "user-a".to_string(),
+            role: Role::Client,
+            session_id: Some(session_id.to_string()),
+            issuer: "https://test.clerk.dev".to_string(),
+            expires_at: Utc::now().timestamp() + 3600,
+            tenant_id: None,
+        })
+    }
+
+    fn seen(state: &AppState, session_id: &str, ip: &str, ua: &str, at: DateTime<Utc>) {
+        let observation = SessionObservation {
+            session_id: Some(session_id.to_string()),
+            issuer: "https://test.clerk.dev".to_string(),
+            ip_address: Some(ip.to_string()),
+            user_agent: Some(ua.to_string()),
+        };
+        SessionLogRepository::new(state.storage())
+            .record("user-a", &observation, at)
+            .unwrap();
+    }
+
+    fn setup() -> AppState {
+        let state = AppState::default();
+        WalletRepository::new(state.storage())
+            .create(
+                &WalletMetadata {
+                    wallet_id: "wallet-1".to_string(),
+                    owner_user_id: "user-a".to_string(),
+                    public_address: "0x1111111111111111111111111111111111111111".to_string(),
+                    created_at: Utc::now(),
+                    status: WalletStatus::Active,
+                    label: None,
+                    email_lookup_key: None,
+                    email_sha256: None,
+                    account_type: Default::default(),
+                    smart_account: None,
+                    lock: None,
+                    deleted_at: None,
+                    purged_at: None,
+                    funded_at: None,
+                    tenant_id: None,
+                },
+                b"test_key",
+            )
+            .unwrap();
+        let now = Utc::now();
+        seen(
+            &state,
+            "sess_home",
+            "203.0.113.7",
+            MAC_CHROME,
+            now - Duration::days(2),
+        );
+        seen(&state, "sess_far", "198.51.100.9", ANDROID_FIREFOX, now);
+        state
+    }
+
+    fn send_request() -> SendTransactionRequest {
+        SendTransactionRequest {
+            to: Some("0x2222222222222222222222222222222222222222".to_string()),
+            to_email_hash: None,
+            amount: "1".to_string(),
+            token: "native".to_string(),
+            network: "fuji".to_string(),
+            gas_limit: None,
+            max_priority_fee_per_gas: None,
+            fee_speed: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn sends_from_unfamiliar_sessions_are_held() {
+        let state = setup();
+        let storage = state.storage();
+        let Auth(home) = auth("sess_home");
+        assert!(
+            hold_if_unfamiliar(storage, &home, "wallet-1", "0x22", &send_request())
+                .unwrap()
+                .is_none()
+        );
+
+        let response = send_transaction(
+            auth("sess_far"),
+            State(state.clone()),
+            Path("wallet-1".to_string()),
+            Json(send_request()),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+
+        let Json(holds) = list_send_holds(
+            auth("sess_home"),
+            State(state.clone()),
+            Path("wallet-1".to_string()),
+        )
+        .await
+        .unwrap();
+        assert_eq!(holds.len(), 1);
+        let hold = &holds[0];
+        assert_eq!(hold.status, SendHoldStatus::Held);
+        assert_eq!(
+            hold.anomalies,
+            vec![
+                SessionAnomaly::UnfamiliarNetwork,
+                SessionAnomaly::UnfamiliarDevice
+            ]
+        );
+
+        // The holding session cannot vouch for itself.
+        let err = confirm_send_hold(
+            auth("sess_far"),
+            State(state.clone()),
+            Path(("wallet-1".to_string(), hold.hold_id.clone())),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::FORBIDDEN);
+
+        // Nor can a session opened after the hold from the same device.
+        seen(
+            &state,
+            "sess_far2",
+            "198.51.100.9",
+            ANDROID_FIREFOX,
+            Utc::now(),
+        );
+        let err = confirm_send_hold(
+            auth("sess_far2"),
+            State(state.clone()),
+            Path(("wallet-1".to_string(), hold.hold_id.clone())),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::FORBIDDEN);
+
+        let Json(cancelled) = cancel_send_hold(
+            auth("sess_far"),
+            State(state.clone()),
+            Path(("wallet-1".to_string(), hold.hold_id.clone())),
+        )
+        .await
+        .unwrap();
+        assert_eq!(cancelled.status, SendHoldStatus::Cancelled);
+        let err = cancel_send_hold(
+            auth("sess_home"),
+            State(state),
+            Path(("wallet-1".to_string(), hold.hold_id.clone())),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::CONFLICT);
+    }
+
+    #[tokio::test]
+    async fn settings_are_validated_and_disable_holds() {
+        let state = setup();
+        let Json(defaults) = get_send_hold_settings(auth("sess_home"), State(state.clone()))
+            .await
+            .unwrap();
+        assert!(defaults.enabled);
+        assert_eq!(defaults.hold_minutes, DEFAULT_HOLD_MINUTES);
+
+        let err = put_send_hold_settings(
+            auth("sess_home"),
+            State(state.clone()),
+            Json(SendHoldSettings {
+                enabled: true,
+                hold_minutes: MAX_HOLD_MINUTES + 1,
+            }),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+
+        let _ = put_send_hold_settings(
+            auth("sess_home"),
+            State(state.clone()),
+            Json(SendHoldSettings {
+                enabled: false,
+                hold_minutes: 10,
+            }),
+        )
+        .await
+        .unwrap();
+        let Auth(far) = auth("sess_far");
+        assert!(
+            hold_if_unfamiliar(state.storage(), &far, "wallet-1", "0x22", &send_request())
+                .unwrap()
+                .is_none()
+        );
+    }
+}
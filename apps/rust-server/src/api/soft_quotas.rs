@@ -0,0 +1,291 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Soft quota warnings.
+//!
+//! Limit checks call [`record`] with how much of a quota a request uses.
+//! The [`soft_quota_headers`] layer reports the tightest quota in
+//! `X-RateLimit-Limit`, `X-RateLimit-Remaining`, `X-RateLimit-Reset` and
+//! `X-RateLimit-Policy`. Once a quota is at least
+//! `SOFT_QUOTA_WARN_PERCENT` (default 80) percent used, the response also
+//! carries an `X-Policy-Warning` header and a `warnings` entry in its JSON
+//! body, so clients can tell users before requests start failing.
+//!
+//! Usage is collected per request in a task-local, so checks deep in a
+//! handler need no extra plumbing, and checks outside a request (e.g. the
+//! fiat poller) are ignored.
+
+use std::{
+    env,
+    sync::{Arc, Mutex},
+};
+
+use axum::{
+    body::{to_bytes, Body},
+    extract::Request,
+    http::{header, HeaderValue, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use chrono::{DateTime, Datelike, TimeZone, Utc};
+use serde::Serialize;
+use serde_json::Value;
+use utoipa::ToSchema;
+
+const SOFT_QUOTA_WARN_PERCENT_ENV: &str = "SOFT_QUOTA_WARN_PERCENT";
+const DEFAULT_WARN_PERCENT: u64 = 80;
+
+pub(crate) const X_RATELIMIT_LIMIT: &str = "x-ratelimit-limit";
+pub(crate) const X_RATELIMIT_REMAINING: &str = "x-ratelimit-remaining";
+pub(crate) const X_RATELIMIT_RESET: &str = "x-ratelimit-reset";
+pub(crate) const X_RATELIMIT_POLICY: &str = "x-ratelimit-policy";
+pub(crate) const X_POLICY_WARNING: &str = "x-policy-warning";
+
+tokio::task_local! {
+    static USAGE: Arc<Mutex<Vec<QuotaUsage>>>;
+}
+
+/// How much of a quota a request uses, counting the request itself.
+#[derive(Debug, Clone)]
+pub struct QuotaUsage {
+    /// Quota name, e.g. `beneficiary_monthly`.
+    pub policy: &'static str,
+    pub limit: u64,
+    pub used: u64,
+    /// When the quota starts over, if it does.
+    pub resets_at: Option<DateTime<Utc>>,
+    /// Warning shown once the quota is nearly used up.
+    pub message: String,
+}
+
+impl QuotaUsage {
+    fn remaining(&self) -> u64 {
+        self.limit.saturating_sub(self.used)
+    }
+
+    fn used_percent(&self) -> u64 {
+        if self.limit == 0 {
+            return 100;
+        }
+        self.used.saturating_mul(100) / self.limit
+    }
+}
+
+/// A quota that is nearly used up.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct QuotaWarning {
+    pub policy: String,
+    pub message: String,
+    pub limit: u64,
+    pub remaining: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resets_at: Option<String>,
+}
+
+impl From<&QuotaUsage> for QuotaWarning {
+    fn from(usage: &QuotaUsage) -> Self {
+        Self {
+            policy: usage.policy.to_string(),
+            message: usage.message.clone(),
+            limit: usage.limit,
+            remaining: usage.remaining(),
+            resets_at: usage.resets_at.map(|at| at.to_rfc3339()),
+        }
+    }
+}
+
+fn warn_percent() -> u64 {
+    env::var(SOFT_QUOTA_WARN_PERCENT_ENV)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|p| (1..=100).contains(p))
+        .unwrap_or(DEFAULT_WARN_PERCENT)
+}
+
+/// Start of the next calendar month (UTC), when monthly quotas reset.
+pub(crate) fn next_month_start(now: DateTime<Utc>) -> DateTime<Utc> {
+    let (year, month) = if now.month() == 12 {
+        (now.year() + 1, 1)
+    } else {
+        (now.year(), now.month() + 1)
+    };
+    Utc.with_ymd_and_hms(year, month, 1, 0, 0, 0)
+        .single()
+        .unwrap_or(now)
+}
+
+/// Note quota usage for the current request. Does nothing outside a
+/// request.
+pub(crate) fn record(usage: QuotaUsage) {
+    let _ = USAGE.try_with(|recorded| {
+        if let Ok(mut recorded) = recorded.lock() {
+            recorded.push(usage);
+        }
+    });
+}
+
+fn header_value(value: impl ToString) -> Option<HeaderValue> {
+    HeaderValue::from_str(&value.to_string()).ok()
+}
+
+/// Layer adding quota headers and warnings to successful responses.
+pub async fn soft_quota_headers(request: Request, next: Next) -> Response {
+    let recorded = Arc::new(Mutex::new(Vec::new()));
+    let response = USAGE.scope(recorded.clone(), next.run(request)).await;
+    let usage = recorded
+        .lock()
+        .map(|mut recorded| std::mem::take(&mut *recorded))
+        .unwrap_or_default();
+    if usage.is_empty() || !response.status().is_success() {
+        return response;
+    }
+    let Some(tightest) = usage.iter().max_by_key(|u| u.used_percent()) else {
+        return response;
+    };
+
+    let (mut parts, body) = response.into_parts();
+    let headers = &mut parts.headers;
+    let quota_headers = [
+        (X_RATELIMIT_LIMIT, header_value(tightest.limit)),
+        (X_RATELIMIT_REMAINING, header_value(tightest.remaining())),
+        (X_RATELIMIT_POLICY, header_value(tightest.policy)),
+        (
+            X_RATELIMIT_RESET,
+            tightest
+                .resets_at
+                .and_then(|at| header_value(at.timestamp())),
+        ),
+    ];
+    for (name, value) in quota_headers {
+        if let Some(value) = value {
+            headers.insert(name, value);
+        }
+    }
+
+    let threshold = warn_percent();
+    let warnings: Vec<QuotaWarning> = usage
+        .iter()
+        .filter(|u| u.used_percent() >= threshold)
+        .map(QuotaWarning::from)
+        .collect();
+    for warning in &warnings {
+        // Header values must be visible ASCII; the JSON body has the full text.
+        let text: String = warning
+            .message
+            .chars()
+            .map(|c| {
+                if c.is_ascii_graphic() || c == ' ' {
+                    c
+                } else {
+                    '?'
+                }
+            })
+            .collect();
+        if let Some(value) = header_value(format!("{}; {}", warning.policy, text)) {
+            headers.append(X_POLICY_WARNING, value);
+        }
+    }
+
+    let is_json = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|ct| ct.starts_with("application/json"));
+    if warnings.is_empty() || !is_json {
+        return Response::from_parts(parts, body);
+    }
+    let bytes = match to_bytes(body, usize::MAX).await {
+        Ok(bytes) => bytes,
+        Err(error) => {
+            tracing::warn!(error = %error, "Failed to buffer response for quota warnings");
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+    let with_warnings = serde_json::from_slice::<Value>(&bytes)
+        .ok()
+        .and_then(|mut value| {
+            value.as_object_mut()?.insert(
+                "warnings".to_string(),
+                serde_json::to_value(&warnings).ok()?,
+            );
+            serde_json::to_vec(&value).ok()
+        });
+    match with_warnings {
+        Some(shaped) => {
+            parts.headers.remove(header::CONTENT_LENGTH);
+            Response::from_parts(parts, Body::from(shaped))
+        }
+        None => Response::from_parts(parts, Body::from(bytes)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{middleware::from_fn, routing::get, Json, Router};
+    use serde_json::json;
+    use tower::ServiceExt;
+
+    fn usage(used: u64) -> QuotaUsage {
+        QuotaUsage {
+            policy: "beneficiary_monthly",
+            limit: 100_000,
+            used,
+            resets_at: Some(Utc.with_ymd_and_hms(2026, 11, 1, 0, 0, 0).unwrap()),
+            message: format!("{used} of 100000 used this month"),
+        }
+    }
+
+    async fn call(used: u64) -> Response {
+        let app = Router::new()
+            .route(
+                "/payout",
+                get(move || async move {
+                    record(usage(used));
+                    Json(json!({ "request_id": "fr-1" }))
+                }),
+            )
+            .layer(from_fn(soft_quota_headers));
+        let request = axum::http::Request::builder()
+            .uri("/payout")
+            .body(Body::empty())
+            .unwrap();
+        app.oneshot(request).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn headers_report_usage_and_warn_near_the_limit() {
+        let response = call(50_000).await;
+        assert_eq!(response.headers()[X_RATELIMIT_REMAINING], "50000");
+        assert_eq!(response.headers()[X_RATELIMIT_RESET], "1793491200");
+        assert!(response.headers().get(X_POLICY_WARNING).is_none());
+        let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: Value = serde_json::from_slice(&bytes).unwrap();
+        assert!(body.get("warnings").is_none());
+
+        let response = call(90_000).await;
+        assert_eq!(response.headers()[X_RATELIMIT_REMAINING], "10000");
+        assert_eq!(
+            response.headers()[X_POLICY_WARNING],
+            "beneficiary_monthly; 90000 of 100000 used this month"
+        );
+        let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(body["request_id"], "fr-1");
+        assert_eq!(body["warnings"][0]["remaining"], 10_000);
+    }
+
+    #[test]
+    fn recording_outside_a_request_is_ignored() {
+        record(usage(1));
+    }
+
+    #[test]
+    fn monthly_quotas_reset_at_the_next_month() {
+        let december = Utc.with_ymd_and_hms(2026, 12, 31, 23, 0, 0).unwrap();
+        assert_eq!(
+            next_month_start(december),
+            Utc.with_ymd_and_hms(2027, 1, 1, 0, 0, 0).unwrap()
+        );
+    }
+}
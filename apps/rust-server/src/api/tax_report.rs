@@ -0,0 +1,644 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Yearly tax summary for a wallet.
+//!
+//! Every confirmed incoming transfer opens a lot at the EUR price of its UTC
+//! day; outgoing transfers consume lots first-in first-out, and the
+//! difference between proceeds and consumed cost basis is the realized gain.
+//! All history before the year is replayed so lots carry over.
+//!
+//! Transfers received from third parties count as income at their value on
+//! receipt. The server cannot tell e.g. a gift from a salary, so transfers
+//! from the user's own wallets and on-ramp deliveries from the fiat reserve
+//! are the only receipts excluded; each line item carries its
+//! [categories](crate::api::categories) so the user can sort the rest out.
+//! Network fees are not included. The report is informational, not tax
+//! advice.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use alloy::primitives::U256;
+use axum::{
+    extract::{Path, Query, State},
+    http::header,
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::{
+    api::{
+        categories::{categorize, load_history, CategoryContext, TxCategory},
+        tx_descriptions::{TxDescriber, TxFacts},
+    },
+    auth::Auth,
+    blockchain::{address_key, format_amount, parse_amount},
+    error::{ApiError, StorageContext},
+    providers::pricing::{token_symbol, PriceFeedClient},
+    state::AppState,
+    storage::{CategoryRepository, OwnershipEnforcer, TxStatus, WalletRepository, WalletStatus},
+};
+
+/// Disclaimer carried by every report.
+pub const INFORMATIONAL_NOTICE: &str = "Informational only, not tax advice. Figures use FIFO \
+     cost basis and daily EUR prices, exclude network fees, and treat transfers from third \
+     parties as income. Check them with a tax professional.";
+
+/// Query parameters for the tax report.
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct TaxReportQuery {
+    /// Calendar year (UTC).
+    pub year: i32,
+    /// `json` (default) or `csv`.
+    pub format: Option<String>,
+}
+
+/// A disposal matched against earlier acquisitions.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct TaxDisposal {
+    pub tx_hash: String,
+    pub date: NaiveDate,
+    pub token: String,
+    pub quantity: String,
+    pub proceeds_eur: String,
+    pub cost_basis_eur: String,
+    pub gain_eur: String,
+    /// Quantity with no earlier acquisition on record; counted at zero cost.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unmatched_quantity: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category: Option<TxCategory>,
+    /// Name of the category the user assigned.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_category: Option<String>,
+    /// Description from the admin-configured templates.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// A receipt counted as income.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct TaxIncomeItem {
+    pub tx_hash: String,
+    pub date: NaiveDate,
+    pub token: String,
+    pub quantity: String,
+    pub value_eur: String,
+    /// Sending address.
+    pub from: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category: Option<TxCategory>,
+    /// Name of the category the user assigned.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_category: Option<String>,
+    /// Description from the admin-configured templates.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// Year totals.
+#[derive(Debug, Clone, Default, Serialize, ToSchema)]
+pub struct TaxSummary {
+    pub proceeds_eur: String,
+    pub cost_basis_eur: String,
+    pub realized_gains_eur: String,
+    pub realized_losses_eur: String,
+    pub net_gain_eur: String,
+    pub income_eur: String,
+}
+
+/// Response for `GET /v1/wallets/{wallet_id}/tax-report`.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct TaxReportResponse {
+    pub wallet_id: String,
+    pub year: i32,
+    /// Always `true`.
+    pub informational: bool,
+    pub notice: String,
+    /// Cost-basis method (`fifo`).
+    pub method: String,
+    pub currency: String,
+    pub summary: TaxSummary,
+    pub disposals: Vec<TaxDisposal>,
+    pub income: Vec<TaxIncomeItem>,
+    /// Transactions that could not be valued and are left out of the totals.
+    pub warnings: Vec<String>,
+    pub generated_at: String,
+}
+
+/// A confirmed transfer of a priced token, ready to be replayed.
+#[derive(Debug, Clone)]
+struct TaxEvent {
+    tx_hash: String,
+    at: DateTime<Utc>,
+    symbol: &'static str,
+    decimals: u8,
+    quantity: U256,
+    /// EUR price per whole token on the event's day.
+    price_eur: Option<f64>,
+    kind: TaxEventKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum TaxEventKind {
+    /// Incoming transfer. `income` when the sender is a third party.
+    Acquisition {
+        income: bool,
+        from: String,
+    },
+    Disposal,
+}
+
+/// Remaining quantity of an acquisition and its cost.
+struct Lot {
+    quantity: U256,
+    cost_eur: f64,
+}
+
+fn token_decimals(symbol: &str) -> u8 {
+    if symbol == "rEUR" {
+        6
+    } else {
+        18
+    }
+}
+
+fn eur(value: f64) -> String {
+    format!("{value:.2}")
+}
+
+/// `part / whole` as a float, for pro-rating a lot's cost.
+fn ratio(part: U256, whole: U256) -> f64 {
+    if whole.is_zero() {
+        return 0.0;
+    }
+    let as_f64 = |v: U256| v.to_string().parse::<f64>().unwrap_or(0.0);
+    as_f64(part) / as_f64(whole)
+}
+
+fn to_token_units(quantity: U256, decimals: u8) -> f64 {
+    format_amount(quantity, decimals).parse().unwrap_or(0.0)
+}
+
+/// Replay `events` (oldest first) and report on those in `year`.
+fn build_report(
+    year: i32,
+    events: &[TaxEvent],
+) -> (
+    TaxSummary,
+    Vec<TaxDisposal>,
+    Vec<TaxIncomeItem>,
+    Vec<String>,
+) {
+    let mut lots: HashMap<&str, VecDeque<Lot>> = HashMap::new();
+    let mut disposals = Vec::new();
+    let mut income = Vec::new();
+    let mut warnings = Vec::new();
+    let (mut proceeds, mut cost, mut gains, mut losses, mut income_total) =
+        (0.0, 0.0, 0.0, 0.0, 0.0);
+
+    for event in events {
+        if event.at.year() > year {
+            break;
+        }
+        let in_year = event.at.year() == year;
+        let Some(price) = event.price_eur else {
+            if in_year {
+                warnings.push(format!(
+                    "No {} price for {}; transaction {} is not valued",
+                    event.symbol,
+                    event.at.date_naive(),
+                    event.tx_hash
+                ));
+            }
+            continue;
+        };
+        let value = to_token_units(event.quantity, event.decimals) * price;
+        let queue = lots.entry(event.symbol).or_default();
+
+        match &event.kind {
+            TaxEventKind::Acquisition {
+                income: is_income,
+                from,
+            } => {
+                queue.push_back(Lot {
+                    quantity: event.quantity,
+                    cost_eur: value,
+                });
+                if *is_income && in_year {
+                    income_total += value;
+                    income.push(TaxIncomeItem {
+                        tx_hash: event.tx_hash.clone(),
+                        date: event.at.date_naive(),
+                        token: event.symbol.to_string(),
+                        quantity: format_amount(event.quantity, event.decimals),
+                        value_eur: eur(value),
+                        from: from.clone(),
+                        category: None,
+                        user_category: None,
+                        description: None,
+                    });
+                }
+            }
+            TaxEventKind::Disposal => {
+                let mut remaining = event.quantity;
+                let mut basis = 0.0;
+                while !remaining.is_zero() {
+                    let Some(lot) = queue.front_mut() else {
+                        break;
+                    };
+                    if lot.quantity <= remaining {
+                        remaining -= lot.quantity;
+                        basis += lot.cost_eur;
+                        queue.pop_front();
+                    } else {
+                        let share = lot.cost_eur * ratio(remaining, lot.quantity);
+                        basis += share;
+                        lot.cost_eur -= share;
+                        lot.quantity -= remaining;
+                        remaining = U256::ZERO;
+                    }
+                }
+                if !in_year {
+                    continue;
+                }
+                let gain = value - basis;
+                proceeds += value;
+                cost += basis;
+                if gain >= 0.0 {
+                    gains += gain;
+                } else {
+                    losses -= gain;
+                }
+                disposals.push(TaxDisposal {
+                    tx_hash: event.tx_hash.clone(),
+                    date: event.at.date_naive(),
+                    token: event.symbol.to_string(),
+                    quantity: format_amount(event.quantity, event.decimals),
+                    proceeds_eur: eur(value),
+                    cost_basis_eur: eur(basis),
+                    gain_eur: eur(gain),
+                    unmatched_quantity: (!remaining.is_zero())
+                        .then(|| format_amount(remaining, event.decimals)),
+                    category: None,
+                    user_category: None,
+                    description: None,
+                });
+            }
+        }
+    }
+
+    let summary = TaxSummary {
+        proceeds_eur: eur(proceeds),
+        cost_basis_eur: eur(cost),
+        realized_gains_eur: eur(gains),
+        realized_losses_eur: eur(losses),
+        net_gain_eur: eur(gains - losses),
+        income_eur: eur(income_total),
+    };
+    (summary, disposals, income, warnings)
+}
+
+/// Quote a CSV field when needed.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Render a report as CSV, one row per disposal and income item, led by the
+/// notice as a comment line.
+fn to_csv(report: &TaxReportResponse) -> String {
+    let mut out = format!("# {}\n", report.notice);
+    out.push_str(
+        "type,date,tx_hash,token,quantity,proceeds_eur,cost_basis_eur,gain_eur,income_eur,from,\
+         category,user_category,description\n",
+    );
+    for d in &report.disposals {
+        let row = [
+            "disposal",
+            &d.date.to_string(),
+            &d.tx_hash,
+            &d.token,
+            &d.quantity,
+            &d.proceeds_eur,
+            &d.cost_basis_eur,
+            &d.gain_eur,
+            "",
+            "",
+            d.category.map_or("", TxCategory::as_str),
+            d.user_category.as_deref().unwrap_or_default(),
+            d.description.as_deref().unwrap_or_default(),
+        ];
+        out.push_str(&row.map(csv_field).join(","));
+        out.push('\n');
+    }
+    for i in &report.income {
+        let row = [
+            "income",
+            &i.date.to_string(),
+            &i.tx_hash,
+            &i.token,
+            &i.quantity,
+            "",
+            "",
+            "",
+            &i.value_eur,
+            &i.from,
+            i.category.map_or("", TxCategory::as_str),
+            i.user_category.as_deref().unwrap_or_default(),
+            i.description.as_deref().unwrap_or_default(),
+        ];
+        out.push_str(&row.map(csv_field).join(","));
+        out.push('\n');
+    }
+    out
+}
+
+/// Yearly FIFO gains and income summary for a wallet.
+///
+/// Informational only. `format=csv` returns the line items as a CSV file.
+#[utoipa::path(
+    get,
+    path = "/v1/wallets/{wallet_id}/tax-report",
+    tag = "Transactions",
+    params(
+        ("wallet_id" = String, Path, description = "Wallet ID"),
+        TaxReportQuery
+    ),
+    security(("bearer" = [])),
+    responses(
+        (status = 200, description = "Tax report (JSON, or CSV when format=csv)", body = TaxReportResponse),
+        (status = 400, description = "Invalid year or format"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - not wallet owner"),
+        (status = 404, description = "Wallet not found")
+    )
+)]
+pub async fn get_tax_report(
+    Auth(user): Auth,
+    State(state): State<AppState>,
+    Path(wallet_id): Path<String>,
+    Query(query): Query<TaxReportQuery>,
+) -> Result<Response, ApiError> {
+    let csv = match query.format.as_deref() {
+        None | Some("json") => false,
+        Some("csv") => true,
+        Some(other) => {
+            return Err(ApiError::bad_request(format!(
+                "Unsupported format '{other}' (use json or csv)"
+            )))
+        }
+    };
+    let current_year = Utc::now().year();
+    if !(2020..=current_year).contains(&query.year) {
+        return Err(ApiError::bad_request(format!(
+            "year must be between 2020 and {current_year}"
+        )));
+    }
+
+    let storage = state.storage();
+    let wallet_repo = WalletRepository::new(storage);
+    let wallet = wallet_repo.get(&wallet_id)?;
+    if !wallet.is_owned_by(&user) {
+        return Err(ApiError::forbidden("You do not own this wallet").with_code("wallet_not_owned"));
+    }
+    if wallet.status == WalletStatus::Deleted {
+        return Err(ApiError::not_found("Wallet has been deleted").with_code("wallet_deleted"));
+    }
+
+    // Receipts from these addresses are not income.
+    let context = CategoryContext::load(storage, &user.user_id)?;
+    let mut own_addresses: HashSet<String> = context.own_addresses.clone();
+    own_addresses.extend(context.reserve_address.clone());
+
+    let mut history = load_history(&state, &wallet.public_address)?;
+    let categories = categorize(&history, &context);
+    let user_categories = CategoryRepository::new(storage)
+        .get(&user.user_id)
+        .context("Failed to load categories")?;
+    history.retain(|(tx, _)| tx.status == TxStatus::Confirmed && tx.network == "fuji");
+    history.sort_by_key(|(tx, _)| tx.created_at);
+
+    let prices = PriceFeedClient::from_env()
+        .inspect_err(|e| tracing::warn!(error = %e, "Price feed unavailable for tax report"))
+        .ok();
+    let mut price_cache: HashMap<(&str, NaiveDate), Option<f64>> = HashMap::new();
+    let mut events = Vec::with_capacity(history.len());
+    for (tx, direction) in &history {
+        if tx.created_at.year() > query.year {
+            break;
+        }
+        let Some(symbol) = token_symbol(&tx.token) else {
+            continue;
+        };
+        let decimals = token_decimals(symbol);
+        let Ok(quantity) = parse_amount(&tx.amount, decimals) else {
+            continue;
+        };
+        let kind = match direction.as_str() {
+            "received" => TaxEventKind::Acquisition {
+                income: !own_addresses.contains(&address_key(&tx.from)),
+                from: tx.from.clone(),
+            },
+            "sent" => TaxEventKind::Disposal,
+            _ => continue,
+        };
+        let date = tx.created_at.date_naive();
+        let price_eur = match price_cache.get(&(symbol, date)) {
+            Some(price) => *price,
+            None => {
+                let price = match &prices {
+                    Some(client) => client
+                        .price_on(storage, symbol, date)
+                        .await
+                        .inspect_err(
+                            |e| tracing::warn!(symbol, %date, error = %e, "Price lookup failed"),
+                        )
+                        .ok()
+                        .flatten(),
+                    None => None,
+                };
+                price_cache.insert((symbol, date), price);
+                price
+            }
+        };
+        events.push(TaxEvent {
+            tx_hash: tx.tx_hash.clone(),
+            at: tx.created_at,
+            symbol,
+            decimals,
+            quantity,
+            price_eur,
+            kind,
+        });
+    }
+
+    let (summary, mut disposals, mut income, warnings) = build_report(query.year, &events);
+    let describer = TxDescriber::load(storage);
+    let descriptions: HashMap<String, String> = history
+        .iter()
+        .filter(|(tx, _)| tx.created_at.year() == query.year)
+        .filter_map(|(tx, direction)| {
+            let key = tx.tx_hash.to_lowercase();
+            let category = categories.get(&key).copied();
+            describer
+                .describe(&TxFacts::of_stored(tx, direction, category))
+                .map(|description| (key, description))
+        })
+        .collect();
+    let category_of = |tx_hash: &str| {
+        (
+            categories.get(&tx_hash.to_lowercase()).copied(),
+            user_categories.category_of(tx_hash).map(|c| c.name.clone()),
+            descriptions.get(&tx_hash.to_lowercase()).cloned(),
+        )
+    };
+    for d in &mut disposals {
+        (d.category, d.user_category, d.description) = category_of(&d.tx_hash);
+    }
+    for i in &mut income {
+        (i.category, i.user_category, i.description) = category_of(&i.tx_hash);
+    }
+    let report = TaxReportResponse {
+        wallet_id: wallet.wallet_id,
+        year: query.year,
+        informational: true,
+        notice: INFORMATIONAL_NOTICE.to_string(),
+        method: "fifo".to_string(),
+        currency: "EUR".to_string(),
+        summary,
+        disposals,
+        income,
+        warnings,
+        generated_at: Utc::now().to_rfc3339(),
+    };
+
+    if csv {
+        let filename = format!("tax-report-{}-{}.csv", report.wallet_id, report.year);
+        return Ok((
+            [
+                (header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+                (
+                    header::CONTENT_DISPOSITION,
+                    format!("attachment; filename=\"{filename}\""),
+                ),
+            ],
+            to_csv(&report),
+        )
+            .into_response());
+    }
+    Ok(Json(report).into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn event(
+        day: u32,
+        month: u32,
+        year: i32,
+        avax: &str,
+        price: f64,
+        kind: TaxEventKind,
+    ) -> TaxEvent {
+        TaxEvent {
+            tx_hash: format!("0x{year}{month:02}{day:02}"),
+            at: Utc.with_ymd_and_hms(year, month, day, 12, 0, 0).unwrap(),
+            symbol: "AVAX",
+            decimals: 18,
+            quantity: parse_amount(avax, 18).unwrap(),
+            price_eur: Some(price),
+            kind,
+        }
+    }
+
+    fn bought() -> TaxEventKind {
+        TaxEventKind::Acquisition {
+            income: false,
+            from: "0xown".to_string(),
+        }
+    }
+
+    #[test]
+    fn disposals_consume_lots_first_in_first_out_across_years() {
+        let events = vec![
+            event(1, 6, 2025, "2", 10.0, bought()),
+            event(1, 2, 2026, "2", 20.0, bought()),
+            // 3 AVAX at 30: 2 from the 2025 lot (20) + 1 from the 2026 lot (20).
+            event(1, 3, 2026, "3", 30.0, TaxEventKind::Disposal),
+            // 1 AVAX at 15 against the remaining half of the 2026 lot (20).
+            event(1, 4, 2026, "1", 15.0, TaxEventKind::Disposal),
+        ];
+
+        let (summary, disposals, income, warnings) = build_report(2026, &events);
+
+        assert_eq!(disposals.len(), 2);
+        assert_eq!(disposals[0].cost_basis_eur, "40.00");
+        assert_eq!(disposals[0].gain_eur, "50.00");
+        assert_eq!(disposals[1].gain_eur, "-5.00");
+        assert_eq!(summary.realized_gains_eur, "50.00");
+        assert_eq!(summary.realized_losses_eur, "5.00");
+        assert_eq!(summary.net_gain_eur, "45.00");
+        assert!(income.is_empty() && warnings.is_empty());
+    }
+
+    #[test]
+    fn third_party_receipts_are_income_and_unmatched_disposals_are_flagged() {
+        let mut unpriced = event(3, 1, 2026, "1", 0.0, bought());
+        unpriced.price_eur = None;
+        let events = vec![
+            event(
+                1,
+                1,
+                2026,
+                "1",
+                25.0,
+                TaxEventKind::Acquisition {
+                    income: true,
+                    from: "0xemployer".to_string(),
+                },
+            ),
+            event(2, 1, 2026, "3", 30.0, TaxEventKind::Disposal),
+            unpriced,
+        ];
+
+        let (summary, disposals, income, warnings) = build_report(2026, &events);
+
+        assert_eq!(summary.income_eur, "25.00");
+        assert_eq!(income[0].from, "0xemployer");
+        assert_eq!(disposals[0].unmatched_quantity.as_deref(), Some("2"));
+        assert_eq!(disposals[0].gain_eur, "65.00");
+        assert_eq!(warnings.len(), 1);
+    }
+
+    #[test]
+    fn csv_leads_with_the_notice_and_quotes_fields() {
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        let report = TaxReportResponse {
+            wallet_id: "wallet-1".to_string(),
+            year: 2026,
+            informational: true,
+            notice: INFORMATIONAL_NOTICE.to_string(),
+            method: "fifo".to_string(),
+            currency: "EUR".to_string(),
+            summary: TaxSummary::default(),
+            disposals: Vec::new(),
+            income: Vec::new(),
+            warnings: Vec::new(),
+            generated_at: String::new(),
+        };
+        let csv = to_csv(&report);
+        assert!(csv.starts_with("# Informational only"));
+        assert_eq!(csv.lines().count(), 2);
+        assert!(csv.lines().nth(1).unwrap().ends_with(",description"));
+    }
+}
@@ -0,0 +1,614 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Per-tenant configuration.
+//!
+//! Platform admins set a tenant's branding, fiat provider accounts, fee
+//! schedule, CORS origins and webhook endpoints under
+//! `/v1/admin/tenants/{tenant_id}/config`. Fiat requests pick up the
+//! credentials and fees of their wallet's tenant when they are created and
+//! synced. Provider secrets are write-only: responses only say which
+//! providers are configured.
+//!
+//! Tenant frontends read their branding before sign-in from the
+//! unauthenticated `GET /v1/tenants/{tenant_id}/branding`.
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use url::Url;
+use utoipa::ToSchema;
+
+use crate::{
+    api::admin::require_platform_admin,
+    auth::{tenant::is_valid_tenant_id, AdminOnly, AuthenticatedUser},
+    error::{ApiError, StorageContext},
+    providers::email::is_valid_email,
+    state::AppState,
+    storage::{
+        repository::{tenant_config::MAX_FEE_BPS, TenantBranding},
+        AuditEvent, AuditRepository, FeeSchedule, ProviderCredentials, StoredTenantConfig,
+        TenantConfigRepository,
+    },
+};
+
+/// Longest display name.
+const MAX_DISPLAY_NAME_LEN: usize = 64;
+/// Most CORS origins or webhook endpoints a tenant may list.
+const MAX_URLS: usize = 10;
+
+/// Request body for creating or replacing a tenant's configuration.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct UpsertTenantConfigRequest {
+    #[serde(default)]
+    pub branding: TenantBranding,
+    /// The tenant's own provider accounts. Omit to keep the stored ones;
+    /// send `{}` to fall back to the deployment's.
+    #[serde(default)]
+    pub provider_credentials: Option<ProviderCredentials>,
+    #[serde(default)]
+    pub fees: FeeSchedule,
+    /// Browser origins of the tenant's frontend, e.g. `https://pay.acme.com`.
+    #[serde(default)]
+    pub cors_origins: Vec<String>,
+    /// HTTPS endpoints for the tenant's outbound webhooks.
+    #[serde(default)]
+    pub webhook_endpoints: Vec<String>,
+}
+
+/// Which provider accounts a tenant brings. Secrets are never returned.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct TenantProviderSummary {
+    /// TrueLayer requests use the tenant's account.
+    pub truelayer: bool,
+    /// Card payments use the tenant's account.
+    pub card: bool,
+    /// Card webhooks are verified with the tenant's secret.
+    pub card_webhook: bool,
+}
+
+/// A tenant's configuration, without provider secrets.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct TenantConfigResponse {
+    pub tenant_id: String,
+    pub branding: TenantBranding,
+    pub providers: TenantProviderSummary,
+    pub fees: FeeSchedule,
+    pub cors_origins: Vec<String>,
+    pub webhook_endpoints: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub updated_by: String,
+}
+
+/// Configured tenants.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TenantConfigListResponse {
+    pub tenants: Vec<TenantConfigResponse>,
+    pub total: usize,
+}
+
+/// Public branding of a tenant.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TenantBrandingResponse {
+    pub tenant_id: String,
+    pub branding: TenantBranding,
+}
+
+fn to_response(config: &StoredTenantConfig) -> TenantConfigResponse {
+    let credentials = &config.provider_credentials;
+    TenantConfigResponse {
+        tenant_id: config.tenant_id.clone(),
+        branding: config.branding.clone(),
+        providers: TenantProviderSummary {
+            truelayer: credentials.truelayer.is_some(),
+            card: credentials.card.is_some(),
+            card_webhook: credentials
+                .card
+                .as_ref()
+                .is_some_and(|card| card.webhook_secret.is_some()),
+        },
+        fees: config.fees,
+        cors_origins: config.cors_origins.clone(),
+        webhook_endpoints: config.webhook_endpoints.clone(),
+        created_at: config.created_at,
+        updated_at: config.updated_at,
+        updated_by: config.updated_by.clone(),
+    }
+}
+
+fn validate_tenant_id(tenant_id: &str) -> Result<(), ApiError> {
+    if !is_valid_tenant_id(tenant_id) {
+        return Err(ApiError::bad_request(format!(
+            "Invalid tenant ID: {tenant_id}"
+        )));
+    }
+    Ok(())
+}
+
+/// Tenant admins may only read their own tenant's configuration; others
+/// look missing.
+fn ensure_visible(admin: &AuthenticatedUser, tenant_id: &str) -> Result<(), ApiError> {
+    if admin.sees_tenant(Some(tenant_id)) {
+        Ok(())
+    } else {
+        Err(ApiError::not_found("Tenant config not found"))
+    }
+}
+
+fn trimmed(value: Option<String>) -> Option<String> {
+    value
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+fn validate_branding(branding: TenantBranding) -> Result<TenantBranding, ApiError> {
+    let branding = TenantBranding {
+        display_name: trimmed(branding.display_name),
+        logo_url: trimmed(branding.logo_url),
+        primary_color: trimmed(branding.primary_color).map(|c| c.to_ascii_lowercase()),
+        support_email: trimmed(branding.support_email),
+    };
+    if branding
+        .display_name
+        .as_ref()
+        .is_some_and(|name| name.chars().count() > MAX_DISPLAY_NAME_LEN)
+    {
+        return Err(ApiError::bad_request(format!(
+            "display_name may be at most {MAX_DISPLAY_NAME_LEN} characters"
+        )));
+    }
+    if let Some(logo_url) = &branding.logo_url {
+        https_url(logo_url, "logo_url")?;
+    }
+    if let Some(color) = &branding.primary_color {
+        let valid = color.len() == 7
+            && color.starts_with('#')
+            && color[1..].chars().all(|c| c.is_ascii_hexdigit());
+        if !valid {
+            return Err(ApiError::bad_request(
+                "primary_color must be a hex color like #1a2b3c",
+            ));
+        }
+    }
+    if let Some(email) = &branding.support_email {
+        if !is_valid_email(email) {
+            return Err(ApiError::bad_request("support_email is not a valid email"));
+        }
+    }
+    Ok(branding)
+}
+
+fn validate_credentials(credentials: &ProviderCredentials) -> Result<(), ApiError> {
+    if let Some(truelayer) = &credentials.truelayer {
+        let fields = [
+            &truelayer.client_id,
+            &truelayer.client_secret,
+            &truelayer.signing_key_id,
+            &truelayer.signing_private_key,
+            &truelayer.merchant_account_id,
+        ];
+        if fields.iter().any(|field| field.trim().is_empty()) {
+            return Err(ApiError::bad_request(
+                "All TrueLayer credential fields are required",
+            ));
+        }
+    }
+    if let Some(card) = &credentials.card {
+        if card.api_secret.trim().is_empty()
+            || card
+                .webhook_secret
+                .as_ref()
+                .is_some_and(|secret| secret.trim().is_empty())
+        {
+            return Err(ApiError::bad_request("Card secrets must not be empty"));
+        }
+    }
+    Ok(())
+}
+
+fn https_url(raw: &str, field: &str) -> Result<Url, ApiError> {
+    match Url::parse(raw) {
+        Ok(url) if url.scheme() == "https" && url.host_str().is_some() => Ok(url),
+        _ => Err(ApiError::bad_request(format!(
+            "{field} must be an https URL: {raw}"
+        ))),
+    }
+}
+
+/// Origins are `scheme://host[:port]` exactly as browsers send them.
+/// Plain http is allowed for local development only.
+fn normalize_origin(raw: &str) -> Result<String, ApiError> {
+    let invalid = || ApiError::bad_request(format!("Invalid CORS origin: {raw}"));
+    let url = Url::parse(raw).map_err(|_| invalid())?;
+    let host = url.host_str().ok_or_else(invalid)?;
+    let local = matches!(host, "localhost" | "127.0.0.1");
+    if !(url.scheme() == "https" || (url.scheme() == "http" && local))
+        || url.path() != "/"
+        || url.query().is_some()
+        || url.fragment().is_some()
+    {
+        return Err(invalid());
+    }
+    Ok(url.origin().ascii_serialization())
+}
+
+fn normalize_urls(
+    raw: &[String],
+    field: &str,
+    normalize: impl Fn(&str) -> Result<String, ApiError>,
+) -> Result<Vec<String>, ApiError> {
+    let mut urls = raw
+        .iter()
+        .map(|u| normalize(u.trim()))
+        .collect::<Result<Vec<_>, _>>()?;
+    urls.sort();
+    urls.dedup();
+    if urls.len() > MAX_URLS {
+        return Err(ApiError::bad_request(format!(
+            "{field} may list at most {MAX_URLS} entries"
+        )));
+    }
+    Ok(urls)
+}
+
+fn log_config_change(
+    state: &AppState,
+    admin_user_id: &str,
+    tenant_id: &str,
+    old: Option<&StoredTenantConfig>,
+    new: Option<&StoredTenantConfig>,
+) {
+    // `provider_credentials` is redacted by the audit log.
+    let event = AuditEvent::config_changed(
+        old.and_then(|c| serde_json::to_value(c).ok()),
+        new.and_then(|c| serde_json::to_value(c).ok()),
+    )
+    .with_user(admin_user_id)
+    .with_resource("tenant_config", tenant_id);
+    let _ = AuditRepository::new(state.storage()).log(&event);
+}
+
+/// List configured tenants (admin only). Tenant admins see only their own.
+#[utoipa::path(
+    get,
+    path = "/v1/admin/tenants",
+    tag = "Admin",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Tenant configurations", body = TenantConfigListResponse),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not authorized (admin required)")
+    )
+)]
+pub async fn list_tenant_configs(
+    AdminOnly(admin): AdminOnly,
+    State(state): State<AppState>,
+) -> Result<Json<TenantConfigListResponse>, ApiError> {
+    let tenants: Vec<_> = TenantConfigRepository::new(state.storage())
+        .list_all()
+        .context("Failed to list tenant configs")?
+        .iter()
+        .filter(|config| admin.sees_tenant(Some(config.tenant_id.as_str())))
+        .map(to_response)
+        .collect();
+    Ok(Json(TenantConfigListResponse {
+        total: tenants.len(),
+        tenants,
+    }))
+}
+
+/// Get a tenant's configuration (admin only).
+#[utoipa::path(
+    get,
+    path = "/v1/admin/tenants/{tenant_id}/config",
+    tag = "Admin",
+    params(("tenant_id" = String, Path, description = "Tenant (organization) ID")),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Tenant configuration", body = TenantConfigResponse),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not authorized (admin required)"),
+        (status = 404, description = "Tenant has no configuration")
+    )
+)]
+pub async fn get_tenant_config(
+    AdminOnly(admin): AdminOnly,
+    State(state): State<AppState>,
+    Path(tenant_id): Path<String>,
+) -> Result<Json<TenantConfigResponse>, ApiError> {
+    validate_tenant_id(&tenant_id)?;
+    ensure_visible(&admin, &tenant_id)?;
+    let config = TenantConfigRepository::new(state.storage())
+        .get(&tenant_id)
+        .map_err(|_| ApiError::not_found("Tenant config not found"))?;
+    Ok(Json(to_response(&config)))
+}
+
+/// Create or replace a tenant's configuration (platform admins only).
+///
+/// Takes effect on the next request. Fees apply to fiat requests created
+/// afterwards.
+#[utoipa::path(
+    put,
+    path = "/v1/admin/tenants/{tenant_id}/config",
+    tag = "Admin",
+    params(("tenant_id" = String, Path, description = "Tenant (organization) ID")),
+    request_body = UpsertTenantConfigRequest,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Configuration saved", body = TenantConfigResponse),
+        (status = 400, description = "Invalid tenant ID, branding, credentials, fee or URL"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not authorized (platform admin required)")
+    )
+)]
+pub async fn put_tenant_config(
+    AdminOnly(admin): AdminOnly,
+    State(state): State<AppState>,
+    Path(tenant_id): Path<String>,
+    Json(body): Json<UpsertTenantConfigRequest>,
+) -> Result<Json<TenantConfigResponse>, ApiError> {
+    require_platform_admin(&admin)?;
+    validate_tenant_id(&tenant_id)?;
+    let branding = validate_branding(body.branding)?;
+    if body.fees.on_ramp_bps > MAX_FEE_BPS || body.fees.off_ramp_bps > MAX_FEE_BPS {
+        return Err(ApiError::bad_request(format!(
+            "Fees may be at most {MAX_FEE_BPS} basis points"
+        )));
+    }
+    let cors_origins = normalize_urls(&body.cors_origins, "cors_origins", normalize_origin)?;
+    let webhook_endpoints = normalize_urls(&body.webhook_endpoints, "webhook_endpoints", |raw| {
+        https_url(raw, "webhook_endpoints").map(String::from)
+    })?;
+
+    let repo = TenantConfigRepository::new(state.storage());
+    let old = repo.get(&tenant_id).ok();
+    let provider_credentials = match body.provider_credentials {
+        Some(credentials) => {
+            validate_credentials(&credentials)?;
+            credentials
+        }
+        None => old
+            .as_ref()
+            .map(|config| config.provider_credentials.clone())
+            .unwrap_or_default(),
+    };
+    let now = Utc::now();
+    let config = StoredTenantConfig {
+        tenant_id: tenant_id.clone(),
+        branding,
+        provider_credentials,
+        fees: body.fees,
+        cors_origins,
+        webhook_endpoints,
+        created_at: old.as_ref().map_or(now, |c| c.created_at),
+        updated_at: now,
+        updated_by: admin.user_id.clone(),
+    };
+    repo.save(&config).context("Failed to save tenant config")?;
+
+    log_config_change(
+        &state,
+        &admin.user_id,
+        &tenant_id,
+        old.as_ref(),
+        Some(&config),
+    );
+    Ok(Json(to_response(&config)))
+}
+
+/// Delete a tenant's configuration (platform admins only). The tenant falls
+/// back to the deployment's providers, no fees and default branding.
+#[utoipa::path(
+    delete,
+    path = "/v1/admin/tenants/{tenant_id}/config",
+    tag = "Admin",
+    params(("tenant_id" = String, Path, description = "Tenant (organization) ID")),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 204, description = "Configuration deleted"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not authorized (platform admin required)"),
+        (status = 404, description = "Tenant has no configuration")
+    )
+)]
+pub async fn delete_tenant_config(
+    AdminOnly(admin): AdminOnly,
+    State(state): State<AppState>,
+    Path(tenant_id): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    require_platform_admin(&admin)?;
+    validate_tenant_id(&tenant_id)?;
+    let repo = TenantConfigRepository::new(state.storage());
+    let old = repo
+        .get(&tenant_id)
+        .map_err(|_| ApiError::not_found("Tenant config not found"))?;
+    repo.delete(&tenant_id)
+        .context("Failed to delete tenant config")?;
+
+    log_config_change(&state, &admin.user_id, &tenant_id, Some(&old), None);
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Branding of a tenant's frontend (no authentication).
+#[utoipa::path(
+    get,
+    path = "/v1/tenants/{tenant_id}/branding",
+    tag = "Tenants",
+    params(("tenant_id" = String, Path, description = "Tenant (organization) ID")),
+    responses(
+        (status = 200, description = "Tenant branding", body = TenantBrandingResponse),
+        (status = 404, description = "Tenant has no configuration")
+    )
+)]
+pub async fn get_tenant_branding(
+    State(state): State<AppState>,
+    Path(tenant_id): Path<String>,
+) -> Result<Json<TenantBrandingResponse>, ApiError> {
+    if !is_valid_tenant_id(&tenant_id) {
+        return Err(ApiError::not_found("Tenant not found"));
+    }
+    let config = TenantConfigRepository::new(state.storage())
+        .get(&tenant_id)
+        .map_err(|_| ApiError::not_found("Tenant not found"))?;
+    Ok(Json(TenantBrandingResponse {
+        tenant_id,
+        branding: config.branding,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::Role;
+    use crate::storage::repository::CardCredentials;
+
+    fn admin(tenant_id: Option<&str>) -> AuthenticatedUser {
+        AuthenticatedUser {
+            user_id: "admin_1".to_string(),
+            role: Role::Admin,
+            session_id: None,
+            issuer: "https://test.clerk.dev".into(),
+            expires_at: Utc::now().timestamp() + 3600,
+            tenant_id: tenant_id.map(str::to_string),
+        }
+    }
+
+    fn body() -> UpsertTenantConfigRequest {
+        UpsertTenantConfigRequest {
+            branding: TenantBranding {
+                display_name: Some(" Acme Pay ".to_string()),
+                primary_color: Some("#1A2B3C".to_string()),
+                ..Default::default()
+            },
+            provider_credentials: Some(ProviderCredentials {
+                truelayer: None,
+                card: Some(CardCredentials {
+                    api_secret: "sk_test_acme".to_string(),
+                    webhook_secret: Some("whsec_acme".to_string()),
+                }),
+            }),
+            fees: FeeSchedule {
+                on_ramp_bps: 150,
+                off_ramp_bps: 50,
+            },
+            cors_origins: vec!["https://pay.acme.example/".to_string()],
+            webhook_endpoints: vec!["https://hooks.acme.example/wallet".to_string()],
+        }
+    }
+
+    #[test]
+    fn origins_must_be_bare_https_origins() {
+        assert_eq!(
+            normalize_origin("https://Pay.Acme.example/").unwrap(),
+            "https://pay.acme.example"
+        );
+        assert!(normalize_origin("http://localhost:3000").is_ok());
+        for raw in [
+            "http://pay.acme.example",
+            "https://pay.acme.example/app",
+            "pay.acme.example",
+            "*",
+        ] {
+            assert!(normalize_origin(raw).is_err(), "{raw}");
+        }
+    }
+
+    #[tokio::test]
+    async fn platform_admins_configure_tenants_without_exposing_secrets() {
+        let state = AppState::default();
+
+        let Json(config) = put_tenant_config(
+            AdminOnly(admin(None)),
+            State(state.clone()),
+            Path("org_acme".to_string()),
+            Json(body()),
+        )
+        .await
+        .unwrap();
+        assert_eq!(config.branding.display_name.as_deref(), Some("Acme Pay"));
+        assert_eq!(config.branding.primary_color.as_deref(), Some("#1a2b3c"));
+        assert_eq!(config.cors_origins, ["https://pay.acme.example"]);
+        assert!(config.providers.card && config.providers.card_webhook);
+        let json = serde_json::to_string(&config).unwrap();
+        assert!(!json.contains("sk_test_acme"));
+
+        // Leaving credentials out keeps the stored ones.
+        let mut update = body();
+        update.provider_credentials = None;
+        update.fees.on_ramp_bps = 100;
+        let Json(config) = put_tenant_config(
+            AdminOnly(admin(None)),
+            State(state.clone()),
+            Path("org_acme".to_string()),
+            Json(update),
+        )
+        .await
+        .unwrap();
+        assert!(config.providers.card);
+        assert_eq!(config.fees.on_ramp_bps, 100);
+
+        let mut too_expensive = body();
+        too_expensive.fees.off_ramp_bps = MAX_FEE_BPS + 1;
+        let err = put_tenant_config(
+            AdminOnly(admin(None)),
+            State(state.clone()),
+            Path("org_acme".to_string()),
+            Json(too_expensive),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+
+        // Tenant admins read their own tenant only and cannot change it.
+        let err = put_tenant_config(
+            AdminOnly(admin(Some("org_acme"))),
+            State(state.clone()),
+            Path("org_acme".to_string()),
+            Json(body()),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::FORBIDDEN);
+        assert!(get_tenant_config(
+            AdminOnly(admin(Some("org_acme"))),
+            State(state.clone()),
+            Path("org_acme".to_string()),
+        )
+        .await
+        .is_ok());
+        let err = get_tenant_config(
+            AdminOnly(admin(Some("org_other"))),
+            State(state.clone()),
+            Path("org_acme".to_string()),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::NOT_FOUND);
+
+        let Json(branding) =
+            get_tenant_branding(State(state.clone()), Path("org_acme".to_string()))
+                .await
+                .unwrap();
+        assert_eq!(branding.branding.display_name.as_deref(), Some("Acme Pay"));
+
+        let status = delete_tenant_config(
+            AdminOnly(admin(None)),
+            State(state.clone()),
+            Path("org_acme".to_string()),
+        )
+        .await
+        .unwrap();
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        let err = get_tenant_branding(State(state), Path("org_acme".to_string()))
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, StatusCode::NOT_FOUND);
+    }
+}
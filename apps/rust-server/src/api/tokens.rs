@@ -0,0 +1,335 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Token listings.
+//!
+//! Admins list further ERC-20 tokens under `/v1/admin/tokens`. A listing
+//! takes effect in the [token registry](crate::blockchain::tokens) at once:
+//! balances include the token, amounts use its decimals and, when it is
+//! indexed, the indexer picks up its transfers on the next poll. Listings
+//! are kept in encrypted storage and reloaded at startup.
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::{
+    api::admin::require_platform_admin,
+    auth::AdminOnly,
+    blockchain::{
+        address::is_address, checksum_address, resolve_network, tokens, ListedToken, TokenSource,
+    },
+    error::{ApiError, StorageContext},
+    state::AppState,
+    storage::{AuditEvent, AuditRepository, StoredToken, TokenRepository},
+};
+
+/// Request body for listing or relisting a token.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct UpsertTokenRequest {
+    pub symbol: String,
+    /// Display name; defaults to the symbol.
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Token decimals; read from the contract when omitted.
+    #[serde(default)]
+    pub decimals: Option<u8>,
+    /// Whether the indexer records transfers of this token.
+    #[serde(default = "default_indexed")]
+    pub indexed: bool,
+}
+
+fn default_indexed() -> bool {
+    true
+}
+
+/// All listed tokens.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TokenListResponse {
+    pub tokens: Vec<ListedToken>,
+    pub total: usize,
+}
+
+/// Resolve the `{network}/{address}` path of a listing.
+fn listing_key(network: &str, address: &str) -> Result<(String, String), ApiError> {
+    let network = resolve_network(Some(network)).map_err(ApiError::bad_request)?;
+    if !is_address(address.trim()) {
+        return Err(ApiError::bad_request(format!(
+            "Invalid token address: {address}"
+        )));
+    }
+    Ok((network.id.to_string(), checksum_address(address.trim())))
+}
+
+/// Refuse changes to tokens listed at deployment.
+fn ensure_admin_listing(network: &str, address: &str) -> Result<(), ApiError> {
+    match tokens().get(network, address) {
+        Some(token) if token.source != TokenSource::Admin => Err(ApiError::conflict(format!(
+            "Token {address} on `{network}` is configured at deployment and cannot be changed here"
+        ))),
+        _ => Ok(()),
+    }
+}
+
+fn log_token_change(
+    state: &AppState,
+    admin_user_id: &str,
+    network: &str,
+    address: &str,
+    old: Option<&StoredToken>,
+    new: Option<&StoredToken>,
+) {
+    let event = AuditEvent::config_changed(
+        old.and_then(|t| serde_json::to_value(t).ok()),
+        new.and_then(|t| serde_json::to_value(t).ok()),
+    )
+    .with_user(admin_user_id)
+    .with_resource("token", format!("{network}/{address}"));
+    let _ = AuditRepository::new(state.storage()).log(&event);
+}
+
+/// List all tokens in the registry (admin only).
+#[utoipa::path(
+    get,
+    path = "/v1/admin/tokens",
+    tag = "Admin",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Listed tokens", body = TokenListResponse),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not authorized (admin required)")
+    )
+)]
+pub async fn list_tokens(
+    AdminOnly(_admin): AdminOnly,
+    State(_state): State<AppState>,
+) -> Result<Json<TokenListResponse>, ApiError> {
+    let tokens = tokens().all();
+    Ok(Json(TokenListResponse {
+        total: tokens.len(),
+        tokens,
+    }))
+}
+
+/// List or relist a token (admin only).
+///
+/// Takes effect at once; no rebuild or redeploy is needed. Tokens listed
+/// in the build or in `TOKEN_REGISTRY` cannot be changed here.
+#[utoipa::path(
+    put,
+    path = "/v1/admin/tokens/{network}/{address}",
+    tag = "Admin",
+    params(
+        ("network" = String, Path, description = "Network ID, e.g. fuji"),
+        ("address" = String, Path, description = "Token contract address")
+    ),
+    request_body = UpsertTokenRequest,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Token listed", body = StoredToken),
+        (status = 400, description = "Unknown network, invalid address, symbol or decimals"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not authorized (platform admin required)"),
+        (status = 409, description = "Token is configured at deployment"),
+        (status = 503, description = "Decimals omitted and the contract could not be read")
+    )
+)]
+pub async fn put_token(
+    AdminOnly(admin): AdminOnly,
+    State(state): State<AppState>,
+    Path((network, address)): Path<(String, String)>,
+    Json(body): Json<UpsertTokenRequest>,
+) -> Result<Json<StoredToken>, ApiError> {
+    require_platform_admin(&admin)?;
+    let (network_id, address) = listing_key(&network, &address)?;
+    ensure_admin_listing(&network_id, &address)?;
+
+    let symbol = body.symbol.trim().to_string();
+    let decimals = match body.decimals {
+        Some(decimals) => decimals,
+        None => {
+            let network = resolve_network(Some(&network_id)).map_err(ApiError::bad_request)?;
+            let client = state.chain_client(&network).await.map_err(|e| {
+                ApiError::service_unavailable(format!("Failed to connect to blockchain: {e}"))
+            })?;
+            client.get_token_decimals(&address).await.map_err(|e| {
+                ApiError::service_unavailable(format!("Failed to read token decimals: {e}"))
+            })?
+        }
+    };
+
+    let repo = TokenRepository::new(state.storage());
+    let old = repo.get(&network_id, &address).ok();
+    let now = Utc::now();
+    let token = StoredToken {
+        network: network_id.clone(),
+        address: address.clone(),
+        name: body
+            .name
+            .map(|n| n.trim().to_string())
+            .filter(|n| !n.is_empty())
+            .unwrap_or_else(|| symbol.clone()),
+        symbol,
+        decimals,
+        indexed: body.indexed,
+        created_at: old.as_ref().map_or(now, |t| t.created_at),
+        updated_at: now,
+        updated_by: admin.user_id.clone(),
+    };
+    tokens()
+        .upsert(token.to_listed())
+        .map_err(ApiError::bad_request)?;
+    repo.save(&token).context("Failed to save token listing")?;
+
+    log_token_change(
+        &state,
+        &admin.user_id,
+        &network_id,
+        &address,
+        old.as_ref(),
+        Some(&token),
+    );
+    Ok(Json(token))
+}
+
+/// Unlist a token (admin only). Its transfers stop being indexed; those
+/// already recorded are kept.
+#[utoipa::path(
+    delete,
+    path = "/v1/admin/tokens/{network}/{address}",
+    tag = "Admin",
+    params(
+        ("network" = String, Path, description = "Network ID, e.g. fuji"),
+        ("address" = String, Path, description = "Token contract address")
+    ),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 204, description = "Token unlisted"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not authorized (platform admin required)"),
+        (status = 404, description = "Token not listed by an admin"),
+        (status = 409, description = "Token is configured at deployment")
+    )
+)]
+pub async fn delete_token(
+    AdminOnly(admin): AdminOnly,
+    State(state): State<AppState>,
+    Path((network, address)): Path<(String, String)>,
+) -> Result<StatusCode, ApiError> {
+    require_platform_admin(&admin)?;
+    let (network_id, address) = listing_key(&network, &address)?;
+    ensure_admin_listing(&network_id, &address)?;
+
+    let repo = TokenRepository::new(state.storage());
+    let old = repo
+        .get(&network_id, &address)
+        .map_err(|_| ApiError::not_found("Token not listed"))?;
+    repo.delete(&network_id, &address)
+        .context("Failed to delete token listing")?;
+    let _ = tokens().remove(&network_id, &address);
+
+    log_token_change(
+        &state,
+        &admin.user_id,
+        &network_id,
+        &address,
+        Some(&old),
+        None,
+    );
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        auth::{AuthenticatedUser, Role},
+        blockchain::{AVAX_FUJI, REUR_TOKEN},
+    };
+
+    fn admin() -> AuthenticatedUser {
+        AuthenticatedUser {
+            user_id: "admin_1".to_string(),
+            role: Role::Admin,
+            session_id: None,
+            issuer: "https://test.clerk.dev".into(),
+            expires_at: Utc::now().timestamp() + 3600,
+            tenant_id: None,
+        }
+    }
+
+    fn body(decimals: u8) -> UpsertTokenRequest {
+        UpsertTokenRequest {
+            symbol: "TST".to_string(),
+            name: None,
+            decimals: Some(decimals),
+            indexed: true,
+        }
+    }
+
+    #[tokio::test]
+    async fn admins_list_tokens_that_the_registry_then_uses() {
+        let state = AppState::default();
+        // Unique per run: the registry is process-wide.
+        let address = format!("0x{:040x}", uuid::Uuid::new_v4().as_u128());
+        let path = || Path(("fuji".to_string(), address.clone()));
+
+        let Json(token) = put_token(
+            AdminOnly(admin()),
+            State(state.clone()),
+            path(),
+            Json(body(8)),
+        )
+        .await
+        .unwrap();
+        assert_eq!(token.name, "TST");
+        assert_eq!(tokens().decimals(&AVAX_FUJI, &address), 8);
+        assert!(tokens()
+            .indexed_contracts("fuji")
+            .contains(&address.parse().unwrap()));
+
+        let Json(list) = list_tokens(AdminOnly(admin()), State(state.clone()))
+            .await
+            .unwrap();
+        assert!(list.tokens.iter().any(|t| t.address == token.address));
+
+        let status = delete_token(AdminOnly(admin()), State(state.clone()), path())
+            .await
+            .unwrap();
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        assert_eq!(tokens().decimals(&AVAX_FUJI, &address), 18);
+        let err = delete_token(AdminOnly(admin()), State(state.clone()), path())
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, StatusCode::NOT_FOUND);
+
+        let reur = Path((
+            "fuji".to_string(),
+            REUR_TOKEN.fuji_address.unwrap().to_string(),
+        ));
+        let err = put_token(
+            AdminOnly(admin()),
+            State(state.clone()),
+            reur,
+            Json(body(2)),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::CONFLICT);
+        let err = put_token(
+            AdminOnly(admin()),
+            State(state),
+            Path(("mainnet".to_string(), address.clone())),
+            Json(body(6)),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+    }
+}
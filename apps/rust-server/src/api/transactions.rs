@@ -4,25 +4,52 @@
 
 //! Transaction endpoints for signing and sending transactions.
 
+use std::sync::Arc;
+
+use alloy::primitives::U256;
 use axum::{
     extract::{Path, Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
     Json,
 };
 use serde::{Deserialize, Serialize};
 use utoipa::{IntoParams, ToSchema};
 
 use crate::{
-    auth::Auth,
+    api::{
+        allowances,
+        categories::{self, TxCategory},
+        limits,
+        send_holds::{self, SendHoldResponse},
+        tx_descriptions::TxDescriber,
+        wallets::ensure_unlocked,
+    },
+    auth::{Auth, AuthenticatedUser},
     blockchain::{
-        avax_fuji, ensure_fuji_network, format_amount, parse_amount, wallet_from_pem, AvaxClient,
-        TxBuilder, REUR_TOKEN,
+        address_key, avax_fuji,
+        client::AvaxClientError,
+        ensure_fuji_network,
+        fees::{FeeSpeed, FeeSuggestion, FeeSuggestions},
+        format_amount, networks, parse_amount, resolve_network, same_address,
+        signing::signer_from_pem,
+        smart_account::{Call, SmartAccountClient, SmartAccountConfig},
+        tokens,
+        transactions::{NonceManager, SendResult},
+        wallet_from_pem, NetworkConfig, TxBuilder, NETWORK_FUJI,
     },
     error::ApiError,
-    providers::email,
+    events::DomainEvent,
+    providers::{
+        email,
+        pricing::{value_at_tx_time, PRICED_SYMBOLS},
+    },
     state::AppState,
     storage::{
-        AuditEvent, AuditEventType, AuditRepository, EmailIndexRepository, StoredTransaction,
-        TokenType, TxStatus, WalletRepository, WalletStatus,
+        repository::watch_only::is_tracking_id, tx_database::TxDbError, AuditEvent, AuditEventType,
+        AuditRepository, CacheResource, EmailIndexRepository, EncryptedStorage, OwnershipEnforcer,
+        PriceHistories, PriceHistoryRepository, StoredTransaction, TokenType, TxStatus,
+        WalletAccountType, WalletMetadata, WalletRepository, WalletStatus, WatchOnlyRepository,
     },
 };
 
@@ -44,9 +71,14 @@ pub struct EstimateGasRequest {
     /// Token type: "native" for AVAX or contract address for ERC-20
     #[serde(default = "default_native")]
     pub token: String,
-    /// Network: "fuji" only.
+    /// Network ID from the server's network registry.
     #[serde(default = "default_fuji")]
     pub network: String,
+    /// Estimate an ERC-20 `approve` of `amount` to this spender instead of a
+    /// transfer; `to` and `to_email_hash` are then ignored and `amount` may
+    /// be `"unlimited"`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub spender: Option<String>,
 }
 
 fn default_native() -> String {
@@ -72,6 +104,38 @@ pub struct EstimateGasResponse {
     pub estimated_cost: String,
 }
 
+/// Fee fields for one speed.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct FeeSuggestionResponse {
+    /// Max fee per gas in wei
+    pub max_fee_per_gas: String,
+    /// Max priority fee per gas in wei
+    pub max_priority_fee_per_gas: String,
+}
+
+impl From<FeeSuggestion> for FeeSuggestionResponse {
+    fn from(fee: FeeSuggestion) -> Self {
+        Self {
+            max_fee_per_gas: fee.max_fee_per_gas.to_string(),
+            max_priority_fee_per_gas: fee.max_priority_fee_per_gas.to_string(),
+        }
+    }
+}
+
+/// EIP-1559 fee suggestions for a network.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct NetworkFeesResponse {
+    pub network: String,
+    pub chain_id: u64,
+    /// Newest block sampled
+    pub block_number: u64,
+    /// Base fee of the next block in wei
+    pub base_fee_per_gas: String,
+    pub slow: FeeSuggestionResponse,
+    pub normal: FeeSuggestionResponse,
+    pub fast: FeeSuggestionResponse,
+}
+
 /// Request to send a transaction.
 #[derive(Debug, Clone, Deserialize, ToSchema)]
 pub struct SendTransactionRequest {
@@ -86,15 +150,20 @@ pub struct SendTransactionRequest {
     /// Token type: "native" for AVAX or contract address for ERC-20
     #[serde(default = "default_native")]
     pub token: String,
-    /// Network: "fuji" only.
+    /// Network ID from the server's network registry.
     #[serde(default = "default_fuji")]
     pub network: String,
     /// Optional gas limit override
     #[serde(skip_serializing_if = "Option::is_none")]
     pub gas_limit: Option<String>,
-    /// Optional max priority fee per gas override (in wei)
+    /// Optional max priority fee per gas override (in wei). Prefer
+    /// `fee_speed`; the two cannot be combined.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub max_priority_fee_per_gas: Option<String>,
+    /// Pick the priority fee from `GET /v1/network/{network}/fees` at send
+    /// time instead of passing wei.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fee_speed: Option<FeeSpeed>,
 }
 
 /// Transaction send response.
@@ -108,26 +177,61 @@ pub struct SendTransactionResponse {
     pub explorer_url: String,
 }
 
+/// One transfer in a batched send.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct BatchTransfer {
+    /// Recipient address (0x + 40 hex chars)
+    pub to: String,
+    /// Amount to send in human-readable format (e.g., "1.5")
+    pub amount: String,
+    /// Token type: "native" for AVAX or contract address for ERC-20
+    #[serde(default = "default_native")]
+    pub token: String,
+}
+
+/// Request to send several transfers in one UserOperation.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct BatchSendRequest {
+    /// Transfers to make, in order (at most 16)
+    pub transfers: Vec<BatchTransfer>,
+    /// Network: "fuji" only.
+    #[serde(default = "default_fuji")]
+    pub network: String,
+}
+
 /// Query parameters for transaction list.
 #[derive(Debug, Deserialize, IntoParams)]
 pub struct TransactionListQuery {
-    /// Network filter (must be "fuji" if provided).
+    /// Network filter, by registry ID.
     pub network: Option<String>,
-    /// Maximum number of results (default: 50)
-    #[param(default = 50)]
+    /// Maximum number of results (1-200, default: 50)
+    #[param(default = 50, minimum = 1, maximum = 200)]
     pub limit: Option<usize>,
-    /// Cursor for pagination (returned as `next_cursor` in previous response).
+    /// Opaque cursor from a previous page's `next_cursor`; the page starts
+    /// after the last transaction of that page. A hex string, only valid
+    /// for the wallet that returned it.
     pub cursor: Option<String>,
     /// Direction filter: "sent" or "received". If omitted, returns both.
     pub direction: Option<String>,
 }
 
+/// Query parameters for waiting on a transaction status change.
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct WaitQuery {
+    /// Seconds to wait before returning the current status (1-60, default: 30)
+    #[param(default = 30)]
+    pub timeout: Option<u64>,
+}
+
 /// Transaction list response.
 #[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct TransactionListResponse {
     /// List of transactions
     pub transactions: Vec<TransactionSummary>,
-    /// Cursor for the next page (null if no more pages).
+    /// Cursor for the next page, passed back as `cursor`. Absent on the
+    /// last page. Pages are newest first; with a `network` or `direction`
+    /// filter a page may hold fewer than `limit` transactions even when
+    /// more follow.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub next_cursor: Option<String>,
 }
@@ -158,6 +262,32 @@ pub struct TransactionSummary {
     pub explorer_url: String,
     /// Timestamp
     pub timestamp: String,
+    /// EUR value at the recorded price of the transaction's UTC day.
+    /// Absent for unpriced tokens or days without a recorded price.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value_eur: Option<String>,
+    /// Automatic category. Absent for watch-only addresses.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category: Option<TxCategory>,
+    /// Category the user assigned, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_category_id: Option<String>,
+    /// Description from the admin-configured templates, if one matches.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Zero-value or dust transfer with a lookalike of a known counterparty
+    /// (address poisoning). Do not copy addresses from such transactions.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub suspected_poisoning: bool,
+    /// Fiat request this transaction settled, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub related_fiat_request_id: Option<String>,
+    /// Transaction this one sped up or cancelled.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replaces: Option<String>,
+    /// Speed-up or cancellation that replaced this transaction.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replaced_by: Option<String>,
 }
 
 /// Transaction status response.
@@ -179,6 +309,12 @@ pub struct TransactionStatusResponse {
     /// Timestamp
     #[serde(skip_serializing_if = "Option::is_none")]
     pub timestamp: Option<String>,
+    /// Fiat request this transaction settled, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub related_fiat_request_id: Option<String>,
+    /// Speed-up or cancellation that replaced this transaction.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replaced_by: Option<String>,
 }
 
 // =============================================================================
@@ -203,19 +339,199 @@ fn validate_address(address: &str) -> Result<(), ApiError> {
     Ok(())
 }
 
-/// Get decimals for a token.
-fn get_token_decimals(token: &str) -> u8 {
-    if token == "native" {
-        18 // AVAX
-    } else if token.eq_ignore_ascii_case(REUR_TOKEN.fuji_address.unwrap_or("")) {
-        6 // rEUR
+/// Error for a send or estimate against an ID that is not a custodial wallet.
+///
+/// Watch-only entries are addressed by their own ID in the app, so a signing
+/// request naming one gets an explicit rejection instead of a bare 404.
+fn wallet_not_found(storage: &EncryptedStorage, user_id: &str, wallet_id: &str) -> ApiError {
+    match WatchOnlyRepository::new(storage).get(wallet_id) {
+        Ok(entry) if entry.owner_user_id == user_id => ApiError::unprocessable(
+            "This is a watch-only address: the server holds no key for it and cannot send from it",
+        ),
+        _ => ApiError::not_found("Wallet not found").with_code("wallet_not_found"),
+    }
+}
+
+/// Load a wallet the user may send from: owned, not deleted, not suspended,
+/// not locked by its owner.
+pub(crate) fn sending_wallet(
+    storage: &EncryptedStorage,
+    user: &AuthenticatedUser,
+    wallet_id: &str,
+) -> Result<WalletMetadata, ApiError> {
+    let wallet = WalletRepository::new(storage)
+        .get(wallet_id)
+        .map_err(|e| match e {
+            crate::storage::StorageError::NotFound(_) => {
+                wallet_not_found(storage, &user.user_id, wallet_id)
+            }
+            other => other.into(),
+        })?;
+
+    // Verify ownership
+    if !wallet.is_owned_by(user) {
+        return Err(ApiError::forbidden("You do not own this wallet").with_code("wallet_not_owned"));
+    }
+
+    // Check wallet status
+    if wallet.status == WalletStatus::Deleted {
+        return Err(ApiError::not_found("Wallet has been deleted").with_code("wallet_deleted"));
+    }
+    if wallet.status == WalletStatus::Suspended {
+        return Err(ApiError::forbidden("Wallet is suspended").with_code("wallet_suspended"));
+    }
+    ensure_unlocked(&wallet)?;
+    Ok(wallet)
+}
+
+/// Map a failed send to an API error.
+pub(crate) fn send_error(e: AvaxClientError) -> ApiError {
+    let msg = e.to_string();
+    // EOA nodes report "insufficient funds"; bundlers reject unfunded
+    // smart accounts with EntryPoint error AA21.
+    if msg.contains("insufficient funds") || msg.contains("AA21") {
+        ApiError::unprocessable("Insufficient balance for transaction")
+            .with_code("insufficient_balance")
     } else {
-        18 // Default to 18 for unknown tokens
+        ApiError::service_unavailable(format!("Transaction failed: {}", e))
+    }
+}
+
+/// Send `amount` of `token` from a custodial wallet.
+///
+/// EOA wallets sign and broadcast a plain transaction on `network`.
+/// Smart-account wallets submit a UserOperation through the bundler; `gas_limit`
+/// then overrides the call gas limit. EOA nonces come from `nonces`.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn send_from_wallet(
+    storage: &EncryptedStorage,
+    nonces: &Arc<NonceManager>,
+    wallet: &WalletMetadata,
+    network: &NetworkConfig,
+    to: &str,
+    token: &TokenType,
+    amount: U256,
+    gas_limit: Option<u64>,
+    max_priority_fee: Option<u128>,
+) -> Result<SendResult, ApiError> {
+    if wallet.account_type == WalletAccountType::SmartAccount {
+        if network.id != NETWORK_FUJI {
+            return Err(smart_account_network_error());
+        }
+        let call = match token {
+            TokenType::Native => Call::native(to, amount),
+            TokenType::Erc20(contract) => Call::token(contract, to, amount),
+        }
+        .map_err(|e| ApiError::bad_request(e.to_string()))?;
+        return send_user_operation(storage, wallet, &[call], gas_limit, max_priority_fee).await;
+    }
+
+    let private_key_pem = WalletRepository::new(storage)
+        .read_private_key(&wallet.wallet_id)
+        .map_err(|e| ApiError::internal(format!("Failed to read private key: {}", e)))?;
+    let eth_wallet = wallet_from_pem(&private_key_pem)
+        .map_err(|e| ApiError::internal(format!("Failed to create signer: {}", e)))?;
+    let tx_builder = TxBuilder::new(network.clone(), eth_wallet)
+        .await
+        .map_err(|e| ApiError::service_unavailable(format!("Failed to connect: {}", e)))?
+        .with_nonce_manager(nonces.clone());
+    match token {
+        TokenType::Native => {
+            tx_builder
+                .send_native(to, amount, gas_limit, max_priority_fee)
+                .await
+        }
+        TokenType::Erc20(contract) => {
+            tx_builder
+                .send_token(to, contract, amount, gas_limit, max_priority_fee)
+                .await
+        }
+    }
+    .map_err(send_error)
+}
+
+/// Submit `calls` from a smart-account wallet as one UserOperation, and
+/// record the deployment the first time one succeeds.
+pub(crate) async fn send_user_operation(
+    storage: &EncryptedStorage,
+    wallet: &WalletMetadata,
+    calls: &[Call],
+    call_gas_limit: Option<u64>,
+    max_priority_fee: Option<u128>,
+) -> Result<SendResult, ApiError> {
+    let info = wallet
+        .smart_account
+        .as_ref()
+        .ok_or_else(|| ApiError::internal("Smart-account wallet has no account details"))?;
+    let config = SmartAccountConfig::from_env().ok_or_else(|| {
+        ApiError::service_unavailable("Smart-account wallets are not enabled on this server")
+    })?;
+    let account = wallet
+        .public_address
+        .parse()
+        .map_err(|e| ApiError::internal(format!("Invalid account address: {e}")))?;
+
+    let repo = WalletRepository::new(storage);
+    let private_key_pem = repo
+        .read_private_key(&wallet.wallet_id)
+        .map_err(|e| ApiError::internal(format!("Failed to read private key: {}", e)))?;
+    let owner = signer_from_pem(&private_key_pem)
+        .map_err(|e| ApiError::internal(format!("Failed to create signer: {}", e)))?;
+    let client = SmartAccountClient::new(config, avax_fuji())
+        .map_err(|e| ApiError::service_unavailable(format!("Failed to connect: {}", e)))?;
+
+    let result = client
+        .send(
+            &owner,
+            account,
+            info.deployed,
+            calls,
+            call_gas_limit,
+            max_priority_fee,
+        )
+        .await
+        .map_err(send_error)?;
+
+    if !info.deployed {
+        let mut updated = wallet.clone();
+        if let Some(info) = updated.smart_account.as_mut() {
+            info.deployed = true;
+            info.deployed_at = Some(chrono::Utc::now());
+        }
+        if let Err(e) = repo.update(&updated) {
+            tracing::warn!(wallet_id = %wallet.wallet_id, error = %e, "Failed to record smart-account deployment");
+        }
     }
+    Ok(result)
+}
+
+fn smart_account_network_error() -> ApiError {
+    ApiError::bad_request(format!(
+        "Smart-account wallets only send on the `{NETWORK_FUJI}` network"
+    ))
+    .with_code("network_not_supported")
+}
+
+/// Resolve the network of a send request.
+pub(crate) fn send_network(wallet: &WalletMetadata, raw: &str) -> Result<NetworkConfig, ApiError> {
+    let network = resolve_network(Some(raw)).map_err(ApiError::bad_request)?;
+    if wallet.account_type == WalletAccountType::SmartAccount && network.id != NETWORK_FUJI {
+        return Err(smart_account_network_error());
+    }
+    Ok(network)
+}
+
+/// Get decimals for a token on `network`, from the token registry.
+fn get_token_decimals(token: &str, network: &NetworkConfig) -> u8 {
+    tokens().decimals(network, token)
 }
 
 /// Convert StoredTransaction to TransactionSummary with direction.
-fn to_summary_with_direction(tx: &StoredTransaction, direction: &str) -> TransactionSummary {
+fn to_summary_with_direction(
+    tx: &StoredTransaction,
+    direction: &str,
+    prices: &PriceHistories,
+) -> TransactionSummary {
     let token_str = match &tx.token {
         TokenType::Native => "native".to_string(),
         TokenType::Erc20(addr) => addr.clone(),
@@ -237,6 +553,14 @@ fn to_summary_with_direction(tx: &StoredTransaction, direction: &str) -> Transac
         block_number: tx.block_number,
         explorer_url: tx.explorer_url.clone(),
         timestamp: tx.created_at.to_rfc3339(),
+        value_eur: value_at_tx_time(prices, tx).map(|v| format!("{v:.2}")),
+        category: None,
+        user_category_id: None,
+        description: None,
+        suspected_poisoning: tx.suspected_poisoning,
+        related_fiat_request_id: tx.related_fiat_request_id.clone(),
+        replaces: tx.replaces.clone(),
+        replaced_by: tx.replaced_by.clone(),
     }
 }
 
@@ -326,7 +650,8 @@ async fn resolve_recipient(
 
 /// Estimate gas for a transaction.
 ///
-/// Returns estimated gas limit and cost before sending.
+/// Returns estimated gas limit and cost before sending. With `spender` set,
+/// estimates an ERC-20 approval instead of a transfer.
 #[utoipa::path(
     post,
     path = "/v1/wallets/{wallet_id}/estimate",
@@ -342,6 +667,7 @@ async fn resolve_recipient(
         (status = 401, description = "Unauthorized"),
         (status = 403, description = "Forbidden - not wallet owner"),
         (status = 404, description = "Wallet not found"),
+        (status = 422, description = "The ID is a watch-only address or a smart-account wallet"),
         (status = 503, description = "Blockchain network unavailable")
     )
 )]
@@ -351,67 +677,70 @@ pub async fn estimate_gas(
     Path(wallet_id): Path<String>,
     Json(request): Json<EstimateGasRequest>,
 ) -> Result<Json<EstimateGasResponse>, ApiError> {
-    // Resolve recipient: either direct address or email hash → address
-    let to_address = resolve_recipient(&request.to, &request.to_email_hash, &state).await?;
+    // Resolve recipient: either direct address or email hash → address.
+    // Approvals name a spender instead.
+    let to_address = match &request.spender {
+        Some(spender) => {
+            if request.token == "native" {
+                return Err(ApiError::bad_request("Only ERC-20 tokens can be approved"));
+            }
+            validate_address(spender)?;
+            String::new()
+        }
+        None => resolve_recipient(&request.to, &request.to_email_hash, &state).await?,
+    };
 
     // Get wallet from storage
     let storage = state.storage();
-    let wallet_repo = WalletRepository::new(storage);
-    let wallet = wallet_repo.get(&wallet_id).map_err(|e| match e {
-        crate::storage::StorageError::NotFound(_) => ApiError::not_found("Wallet not found"),
-        _ => ApiError::internal(format!("Failed to access storage: {}", e)),
-    })?;
+    let wallet = sending_wallet(storage, &user, &wallet_id)?;
 
-    // Verify ownership
-    if wallet.owner_user_id != user.user_id {
-        return Err(ApiError::forbidden("You do not own this wallet"));
-    }
+    let network = resolve_network(Some(request.network.as_str())).map_err(ApiError::bad_request)?;
 
-    // Check wallet status
-    if wallet.status == WalletStatus::Deleted {
-        return Err(ApiError::not_found("Wallet has been deleted"));
-    }
-    if wallet.status == WalletStatus::Suspended {
-        return Err(ApiError::forbidden("Wallet is suspended"));
+    if wallet.account_type == WalletAccountType::SmartAccount {
+        return Err(ApiError::unprocessable(
+            "Smart-account gas is estimated by the bundler when the UserOperation is sent",
+        ));
     }
 
-    ensure_fuji_network(Some(request.network.as_str())).map_err(ApiError::bad_request)?;
-
     // Get private key and create wallet
-    let private_key_pem = wallet_repo
+    let private_key_pem = WalletRepository::new(storage)
         .read_private_key(&wallet_id)
         .map_err(|e| ApiError::internal(format!("Failed to read private key: {}", e)))?;
 
     let eth_wallet = wallet_from_pem(&private_key_pem)
         .map_err(|e| ApiError::internal(format!("Failed to create signer: {}", e)))?;
 
-    // Determine network (Fuji-only).
-    let network_config = avax_fuji();
-
     // Create transaction builder
-    let tx_builder = TxBuilder::new(network_config, eth_wallet)
+    let tx_builder = TxBuilder::new(network.clone(), eth_wallet)
         .await
         .map_err(|e| ApiError::service_unavailable(format!("Failed to connect: {}", e)))?;
 
     // Parse amount
-    let decimals = get_token_decimals(&request.token);
-    let amount_wei = parse_amount(&request.amount, decimals)
-        .map_err(|e| ApiError::bad_request(format!("Invalid amount: {}", e)))?;
+    let decimals = get_token_decimals(&request.token, &network);
 
     // Estimate gas
-    let estimate = if request.token == "native" {
+    let estimate = if let Some(spender) = &request.spender {
+        let amount = allowances::parse_allowance_amount(&request.amount, decimals)?;
         tx_builder
-            .estimate_native_transfer(&wallet.public_address, &to_address, amount_wei)
+            .estimate_token_approval(&wallet.public_address, &request.token, spender, amount)
             .await
     } else {
-        tx_builder
-            .estimate_token_transfer(
-                &wallet.public_address,
-                &to_address,
-                &request.token,
-                amount_wei,
-            )
-            .await
+        let amount_wei = parse_amount(&request.amount, decimals)
+            .map_err(|e| ApiError::bad_request(format!("Invalid amount: {}", e)))?;
+        if request.token == "native" {
+            tx_builder
+                .estimate_native_transfer(&wallet.public_address, &to_address, amount_wei)
+                .await
+        } else {
+            tx_builder
+                .estimate_token_transfer(
+                    &wallet.public_address,
+                    &to_address,
+                    &request.token,
+                    amount_wei,
+                )
+                .await
+        }
     }
     .map_err(|e| ApiError::service_unavailable(format!("Gas estimation failed: {}", e)))?;
 
@@ -420,13 +749,67 @@ pub async fn estimate_gas(
         max_fee_per_gas: estimate.max_fee_per_gas.to_string(),
         max_priority_fee_per_gas: estimate.max_priority_fee_per_gas.to_string(),
         estimated_cost_wei: estimate.estimated_cost_wei.to_string(),
-        estimated_cost: format_amount(estimate.estimated_cost_wei, 18),
+        estimated_cost: format_amount(estimate.estimated_cost_wei, network.native_decimals),
+    }))
+}
+
+/// Sample recent blocks of `network` for fee suggestions.
+async fn network_fees(
+    state: &AppState,
+    network: &NetworkConfig,
+) -> Result<FeeSuggestions, ApiError> {
+    let client = state
+        .chain_client(network)
+        .await
+        .map_err(|e| ApiError::service_unavailable(format!("Failed to connect: {e}")))?;
+    client
+        .suggest_fees()
+        .await
+        .map_err(|e| ApiError::service_unavailable(format!("Fee suggestion failed: {e}")))
+}
+
+/// Get fee suggestions for a network.
+///
+/// Samples recent blocks and returns slow, normal and fast EIP-1559 fees.
+/// Sends take the same tiers through `fee_speed`.
+#[utoipa::path(
+    get,
+    path = "/v1/network/{network}/fees",
+    tag = "Transactions",
+    params(
+        ("network" = String, Path, description = "Network ID from the server's network registry")
+    ),
+    security(("bearer" = [])),
+    responses(
+        (status = 200, description = "Fee suggestions", body = NetworkFeesResponse),
+        (status = 400, description = "Unknown network"),
+        (status = 401, description = "Unauthorized"),
+        (status = 503, description = "Blockchain network unavailable")
+    )
+)]
+pub async fn get_network_fees(
+    Auth(_user): Auth,
+    State(state): State<AppState>,
+    Path(network): Path<String>,
+) -> Result<Json<NetworkFeesResponse>, ApiError> {
+    let network = resolve_network(Some(network.as_str())).map_err(ApiError::bad_request)?;
+    let fees = network_fees(&state, &network).await?;
+    Ok(Json(NetworkFeesResponse {
+        network: network.id.to_string(),
+        chain_id: network.chain_id,
+        block_number: fees.block_number,
+        base_fee_per_gas: fees.base_fee_per_gas.to_string(),
+        slow: fees.slow.into(),
+        normal: fees.normal.into(),
+        fast: fees.fast.into(),
     }))
 }
 
 /// Send a transaction from a wallet.
 ///
 /// Signs the transaction inside the SGX enclave and broadcasts to the network.
+/// A send from a session unlike the user's recent ones is held for
+/// confirmation instead (202).
 #[utoipa::path(
     post,
     path = "/v1/wallets/{wallet_id}/send",
@@ -438,11 +821,12 @@ pub async fn estimate_gas(
     security(("bearer" = [])),
     responses(
         (status = 200, description = "Transaction submitted", body = SendTransactionResponse),
+        (status = 202, description = "Held for confirmation from a familiar session", body = SendHoldResponse),
         (status = 400, description = "Invalid request"),
         (status = 401, description = "Unauthorized"),
         (status = 403, description = "Forbidden - not wallet owner"),
         (status = 404, description = "Wallet not found"),
-        (status = 422, description = "Insufficient balance"),
+        (status = 422, description = "Insufficient balance, spending limit exceeded, or the ID is a watch-only address"),
         (status = 503, description = "Blockchain network unavailable")
     )
 )]
@@ -451,51 +835,48 @@ pub async fn send_transaction(
     State(state): State<AppState>,
     Path(wallet_id): Path<String>,
     Json(request): Json<SendTransactionRequest>,
-) -> Result<Json<SendTransactionResponse>, ApiError> {
+) -> Result<Response, ApiError> {
     // Resolve recipient: either direct address or email hash → address
     let to_address = resolve_recipient(&request.to, &request.to_email_hash, &state).await?;
 
     // Get wallet from storage
     let storage = state.storage();
-    let wallet_repo = WalletRepository::new(storage);
-    let wallet = wallet_repo.get(&wallet_id).map_err(|e| match e {
-        crate::storage::StorageError::NotFound(_) => ApiError::not_found("Wallet not found"),
-        _ => ApiError::internal(format!("Failed to access storage: {}", e)),
-    })?;
-
-    // Verify ownership
-    if wallet.owner_user_id != user.user_id {
-        return Err(ApiError::forbidden("You do not own this wallet"));
-    }
+    let wallet = sending_wallet(storage, &user, &wallet_id)?;
+
+    let network = send_network(&wallet, &request.network)?;
+    parse_send_params(&request, &network)?;
+    limits::check_spending_limits(
+        &state,
+        &wallet,
+        &[(&request.token, &request.amount)],
+        &request.network,
+    )
+    .await?;
 
-    // Check wallet status
-    if wallet.status == WalletStatus::Deleted {
-        return Err(ApiError::not_found("Wallet has been deleted"));
-    }
-    if wallet.status == WalletStatus::Suspended {
-        return Err(ApiError::forbidden("Wallet is suspended"));
+    if let Some(hold) =
+        send_holds::hold_if_unfamiliar(storage, &user, &wallet_id, &to_address, &request)?
+    {
+        return Ok((StatusCode::ACCEPTED, Json(SendHoldResponse::from(&hold))).into_response());
     }
 
-    ensure_fuji_network(Some(request.network.as_str())).map_err(ApiError::bad_request)?;
-
-    // Get private key and create wallet
-    let private_key_pem = wallet_repo
-        .read_private_key(&wallet_id)
-        .map_err(|e| ApiError::internal(format!("Failed to read private key: {}", e)))?;
-
-    let eth_wallet = wallet_from_pem(&private_key_pem)
-        .map_err(|e| ApiError::internal(format!("Failed to create signer: {}", e)))?;
-
-    // Determine network (Fuji-only).
-    let network_config = avax_fuji();
+    let response = execute_send(&state, &user.user_id, &wallet, &to_address, &request).await?;
+    Ok(Json(response).into_response())
+}
 
-    // Create transaction builder
-    let tx_builder = TxBuilder::new(network_config.clone(), eth_wallet)
-        .await
-        .map_err(|e| ApiError::service_unavailable(format!("Failed to connect: {}", e)))?;
+/// Parsed amount, token and fee overrides of a send request.
+struct SendParams {
+    amount_wei: U256,
+    token_type: TokenType,
+    gas_limit: Option<u64>,
+    max_priority_fee: Option<u128>,
+}
 
+fn parse_send_params(
+    request: &SendTransactionRequest,
+    network: &NetworkConfig,
+) -> Result<SendParams, ApiError> {
     // Parse amount
-    let decimals = get_token_decimals(&request.token);
+    let decimals = get_token_decimals(&request.token, network);
     let amount_wei = parse_amount(&request.amount, decimals)
         .map_err(|e| ApiError::bad_request(format!("Invalid amount: {}", e)))?;
 
@@ -513,39 +894,77 @@ pub async fn send_transaction(
         .map(|s| s.parse::<u128>())
         .transpose()
         .map_err(|_| ApiError::bad_request("Invalid max_priority_fee_per_gas"))?;
-
-    // Send transaction
-    let result = if request.token == "native" {
-        tx_builder
-            .send_native(&to_address, amount_wei, gas_limit, max_priority_fee)
-            .await
-    } else {
-        tx_builder
-            .send_token(
-                &to_address,
-                &request.token,
-                amount_wei,
-                gas_limit,
-                max_priority_fee,
-            )
-            .await
+    if max_priority_fee.is_some() && request.fee_speed.is_some() {
+        return Err(ApiError::bad_request(
+            "Set either fee_speed or max_priority_fee_per_gas, not both",
+        ));
     }
-    .map_err(|e| {
-        let msg = e.to_string();
-        if msg.contains("insufficient funds") {
-            ApiError::unprocessable("Insufficient balance for transaction")
-        } else {
-            ApiError::service_unavailable(format!("Transaction failed: {}", e))
-        }
-    })?;
 
-    // Store transaction record
     let token_type = if request.token == "native" {
         TokenType::Native
     } else {
         TokenType::Erc20(request.token.clone())
     };
+    Ok(SendParams {
+        amount_wei,
+        token_type,
+        gas_limit,
+        max_priority_fee,
+    })
+}
+
+/// Broadcast a send to an already resolved recipient, then index and audit
+/// it. Shared with the confirmation of held sends.
+pub(crate) async fn execute_send(
+    state: &AppState,
+    user_id: &str,
+    wallet: &WalletMetadata,
+    to_address: &str,
+    request: &SendTransactionRequest,
+) -> Result<SendTransactionResponse, ApiError> {
+    let storage = state.storage();
+    let wallet_id = wallet.wallet_id.clone();
+    let to_address = to_address.to_string();
+    let network = send_network(wallet, &request.network)?;
+    let SendParams {
+        amount_wei,
+        token_type,
+        gas_limit,
+        max_priority_fee,
+    } = parse_send_params(request, &network)?;
+    let max_priority_fee = match request.fee_speed {
+        Some(speed) => Some(
+            network_fees(state, &network)
+                .await?
+                .for_speed(speed)
+                .max_priority_fee_per_gas,
+        ),
+        None => max_priority_fee,
+    };
 
+    // Send transaction
+    let reservation = limits::reserve_spending(
+        state,
+        wallet,
+        &[(&request.token, &request.amount)],
+        &request.network,
+    )
+    .await?;
+    let result = send_from_wallet(
+        storage,
+        &state.nonce_manager,
+        wallet,
+        &network,
+        &to_address,
+        &token_type,
+        amount_wei,
+        gas_limit,
+        max_priority_fee,
+    )
+    .await?;
+    reservation.commit(&result.tx_hash);
+
+    // Store transaction record
     // O(1) lookup: check if recipient belongs to an internal wallet via address_wallet_map.
     let tx_db = state
         .tx_db
@@ -556,12 +975,14 @@ pub async fn send_transaction(
     let stored_tx = StoredTransaction::new_pending(
         result.tx_hash.clone(),
         wallet_id.clone(),
-        recipient_wallet_id.clone().filter(|id| id != &wallet_id),
+        recipient_wallet_id
+            .clone()
+            .filter(|id| id != &wallet_id && !is_tracking_id(id)),
         wallet.public_address.clone(),
         to_address.clone(),
         request.amount.clone(),
         token_type.clone(),
-        request.network.clone(),
+        network.id.to_string(),
         result.explorer_url.clone(),
     );
 
@@ -572,10 +993,12 @@ pub async fn send_transaction(
     if let Err(e) = tx_db.upsert_transaction(&stored_tx, &directions) {
         tracing::warn!(error = %e, "Failed to store transaction in tx database");
     }
-    if let Some(tx_cache) = &state.tx_cache {
-        tx_cache.invalidate(&wallet.public_address);
-        tx_cache.invalidate(&to_address);
-    }
+    state
+        .cache_bus
+        .invalidate(&wallet.public_address, CacheResource::TransactionHistory);
+    state
+        .cache_bus
+        .invalidate(&to_address, CacheResource::TransactionHistory);
 
     // Mirror recipient-side transaction record for internal transfers.
     if let Some(recipient_id) = recipient_wallet_id {
@@ -588,33 +1011,134 @@ pub async fn send_transaction(
                 to_address.clone(),
                 request.amount.clone(),
                 token_type,
-                request.network.clone(),
+                network.id.to_string(),
                 result.explorer_url.clone(),
             );
             let directions = vec![(to_address.clone(), "received")];
             if let Err(e) = tx_db.upsert_transaction(&mirrored_tx, &directions) {
                 tracing::warn!(error = %e, "Failed to store mirrored tx in tx database");
             }
-            if let Some(tx_cache) = &state.tx_cache {
-                tx_cache.invalidate(&to_address);
-            }
+            state
+                .cache_bus
+                .invalidate(&to_address, CacheResource::TransactionHistory);
         }
     }
 
     // Log audit event
     let audit_repo = AuditRepository::new(storage);
     let event = AuditEvent::new(AuditEventType::TransactionBroadcast)
-        .with_user(&user.user_id)
+        .with_user(user_id)
         .with_resource(&wallet_id, "wallet")
         .with_details(serde_json::json!({
             "tx_hash": result.tx_hash,
             "to": to_address,
             "amount": request.amount,
             "token": request.token,
-            "network": request.network,
+            "network": network.id,
         }));
     let _ = audit_repo.log(&event);
 
+    Ok(SendTransactionResponse {
+        tx_hash: result.tx_hash,
+        status: "pending".to_string(),
+        explorer_url: result.explorer_url,
+    })
+}
+
+/// Most transfers in one batched send.
+const MAX_BATCH_TRANSFERS: usize = 16;
+
+/// Send several transfers from a smart-account wallet atomically.
+///
+/// All transfers are executed by one UserOperation through the account's
+/// `executeBatch`; if any of them reverts, none take effect.
+#[utoipa::path(
+    post,
+    path = "/v1/wallets/{wallet_id}/batch",
+    tag = "Transactions",
+    params(
+        ("wallet_id" = String, Path, description = "Wallet ID")
+    ),
+    request_body = BatchSendRequest,
+    security(("bearer" = [])),
+    responses(
+        (status = 200, description = "UserOperation included", body = SendTransactionResponse),
+        (status = 400, description = "Invalid request"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - not wallet owner"),
+        (status = 404, description = "Wallet not found"),
+        (status = 422, description = "Not a smart-account wallet, insufficient balance, or spending limit exceeded"),
+        (status = 503, description = "Bundler or blockchain network unavailable")
+    )
+)]
+pub async fn send_batch(
+    Auth(user): Auth,
+    State(state): State<AppState>,
+    Path(wallet_id): Path<String>,
+    Json(request): Json<BatchSendRequest>,
+) -> Result<Json<SendTransactionResponse>, ApiError> {
+    let storage = state.storage();
+    let wallet = sending_wallet(storage, &user, &wallet_id)?;
+    if wallet.account_type != WalletAccountType::SmartAccount {
+        return Err(ApiError::unprocessable(
+            "Batched sends require a smart-account wallet",
+        ));
+    }
+    ensure_fuji_network(Some(request.network.as_str())).map_err(ApiError::bad_request)?;
+    if request.transfers.is_empty() || request.transfers.len() > MAX_BATCH_TRANSFERS {
+        return Err(ApiError::bad_request(format!(
+            "A batch must contain between 1 and {MAX_BATCH_TRANSFERS} transfers"
+        )));
+    }
+
+    let mut calls = Vec::with_capacity(request.transfers.len());
+    for transfer in &request.transfers {
+        validate_address(&transfer.to)?;
+        let amount = parse_amount(
+            &transfer.amount,
+            get_token_decimals(&transfer.token, &avax_fuji()),
+        )
+        .map_err(|e| ApiError::bad_request(format!("Invalid amount: {}", e)))?;
+        let call = if transfer.token == "native" {
+            Call::native(&transfer.to, amount)
+        } else {
+            Call::token(&transfer.token, &transfer.to, amount)
+        }
+        .map_err(|e| ApiError::bad_request(e.to_string()))?;
+        calls.push(call);
+    }
+    let sends: Vec<_> = request
+        .transfers
+        .iter()
+        .map(|t| (t.token.as_str(), t.amount.as_str()))
+        .collect();
+    let reservation = limits::reserve_spending(&state, &wallet, &sends, &request.network).await?;
+
+    let result = send_user_operation(storage, &wallet, &calls, None, None).await?;
+    reservation.commit(&result.tx_hash);
+    state
+        .cache_bus
+        .invalidate(&wallet.public_address, CacheResource::TransactionHistory);
+    for transfer in &request.transfers {
+        state
+            .cache_bus
+            .invalidate(&transfer.to, CacheResource::TransactionHistory);
+    }
+
+    let event = AuditEvent::new(AuditEventType::TransactionBroadcast)
+        .with_user(&user.user_id)
+        .with_resource(&wallet_id, "wallet")
+        .with_details(serde_json::json!({
+            "tx_hash": result.tx_hash,
+            "batch": request
+                .transfers
+                .iter()
+                .map(|t| serde_json::json!({ "to": t.to, "amount": t.amount, "token": t.token }))
+                .collect::<Vec<_>>(),
+            "network": request.network,
+        }));
+    let _ = AuditRepository::new(storage).log(&event);
+
     Ok(Json(SendTransactionResponse {
         tx_hash: result.tx_hash,
         status: "pending".to_string(),
@@ -623,6 +1147,10 @@ pub async fn send_transaction(
 }
 
 /// List transactions for a wallet.
+///
+/// Each transaction carries its automatic `category`, the
+/// `user_category_id` the user assigned and a `description` from the
+/// admin-configured templates, if any.
 #[utoipa::path(
     get,
     path = "/v1/wallets/{wallet_id}/transactions",
@@ -634,6 +1162,7 @@ pub async fn send_transaction(
     security(("bearer" = [])),
     responses(
         (status = 200, description = "Transaction list", body = TransactionListResponse),
+        (status = 400, description = "Invalid network or cursor"),
         (status = 401, description = "Unauthorized"),
         (status = 403, description = "Forbidden - not wallet owner"),
         (status = 404, description = "Wallet not found")
@@ -648,23 +1177,52 @@ pub async fn list_transactions(
     // Get wallet from storage
     let storage = state.storage();
     let wallet_repo = WalletRepository::new(storage);
-    let wallet = wallet_repo.get(&wallet_id).map_err(|e| match e {
-        crate::storage::StorageError::NotFound(_) => ApiError::not_found("Wallet not found"),
-        _ => ApiError::internal(format!("Failed to access storage: {}", e)),
-    })?;
+    let wallet = wallet_repo.get(&wallet_id)?;
 
     // Verify ownership
-    if wallet.owner_user_id != user.user_id {
-        return Err(ApiError::forbidden("You do not own this wallet"));
+    if !wallet.is_owned_by(&user) {
+        return Err(ApiError::forbidden("You do not own this wallet").with_code("wallet_not_owned"));
     }
 
+    let mut response = list_address_transactions(&state, &wallet.public_address, &query).await?;
+    categories::annotate(
+        &state,
+        &user.user_id,
+        &wallet.public_address,
+        &mut response.transactions,
+    )?;
+    TxDescriber::load(storage).annotate(&mut response.transactions);
+    Ok(Json(response))
+}
+
+/// List indexed transactions touching an address, reconciling pending ones.
+///
+/// Shared with the watch-only transaction endpoint.
+pub(crate) async fn list_address_transactions(
+    state: &AppState,
+    address: &str,
+    query: &TransactionListQuery,
+) -> Result<TransactionListResponse, ApiError> {
     // Filter by network if specified
-    if let Some(network) = &query.network {
-        ensure_fuji_network(Some(network.as_str())).map_err(ApiError::bad_request)?;
-    }
+    let network_filter = query
+        .network
+        .as_deref()
+        .map(|raw| resolve_network(Some(raw)).map_err(ApiError::bad_request))
+        .transpose()?;
+    // Transactions on networks no longer registered are hidden.
+    let listed = |tx: &TransactionSummary| match &network_filter {
+        Some(network) => tx.network == network.id,
+        None => networks().get(&tx.network).is_some(),
+    };
 
-    let limit = query.limit.unwrap_or(50).min(200);
-    let wallet_address = wallet.public_address.to_lowercase();
+    let limit = query.limit.unwrap_or(50).clamp(1, 200);
+    let wallet_address = address_key(address);
+    let prices = PriceHistoryRepository::new(state.storage())
+        .get_many(&PRICED_SYMBOLS)
+        .unwrap_or_else(|e| {
+            tracing::warn!(error = %e, "Failed to load price history");
+            PriceHistories::new()
+        });
 
     let tx_db = state
         .tx_db
@@ -680,97 +1238,110 @@ pub async fn list_transactions(
                     let summaries: Vec<TransactionSummary> = cached
                         .iter()
                         .take(limit)
-                        .map(|(tx, dir)| to_summary_with_direction(tx, dir))
+                        .map(|(tx, dir)| to_summary_with_direction(tx, dir, &prices))
+                        .filter(listed)
                         .collect();
-                    return Ok(Json(TransactionListResponse {
+                    return Ok(TransactionListResponse {
                         transactions: summaries,
                         next_cursor,
-                    }));
+                    });
                 }
             }
         }
     }
 
+    // Taken before reading: a page read before a concurrent invalidation
+    // must not be cached after it.
+    let epoch = state
+        .cache_bus
+        .epoch(&wallet_address, CacheResource::TransactionHistory);
     let (results, next_cursor) = tx_db
         .list_by_wallet(&wallet_address, query.cursor.as_deref(), limit)
-        .map_err(|e| ApiError::internal(format!("Failed to list transactions: {}", e)))?;
+        .map_err(|e| match e {
+            TxDbError::InvalidCursor(_) => {
+                ApiError::bad_request("Invalid pagination cursor").with_code("invalid_cursor")
+            }
+            e => ApiError::internal(format!("Failed to list transactions: {}", e)),
+        })?;
 
     // ── Reconcile pending transactions with on-chain status ─────────
     // If there are any pending transactions in the result set, check
     // their receipt on-chain and promote them to confirmed/failed.
     // This is lightweight: typically 0-1 pending txs per page, and each
-    // receipt RPC call is ~100ms.  We only create the AvaxClient if we
-    // actually have pending items.
-    let pending_hashes: Vec<(usize, String)> = results
+    // receipt RPC call is ~100ms.  We only create a client for the
+    // transaction's network if we actually have pending items.
+    let pending_hashes: Vec<(usize, String, &'static NetworkConfig)> = results
         .iter()
         .enumerate()
         .filter(|(_, (tx, _))| tx.status == TxStatus::Pending)
-        .map(|(i, (tx, _))| (i, tx.tx_hash.clone()))
+        .filter_map(|(i, (tx, _))| {
+            let network = networks().get(&tx.network)?;
+            Some((i, tx.tx_hash.clone(), network))
+        })
         .collect();
 
     let mut updated_results = results;
 
     if !pending_hashes.is_empty() {
-        if let Ok(client) = AvaxClient::fuji().await {
-            for (idx, hash) in &pending_hashes {
-                if let Ok(Some(receipt)) = client.get_transaction_receipt_status(hash).await {
-                    let new_status = if receipt.success {
-                        TxStatus::Confirmed
-                    } else {
-                        TxStatus::Failed
-                    };
-
-                    // Update redb so future reads are correct
-                    let _ = tx_db.update_status(
-                        hash,
-                        new_status,
-                        Some(receipt.block_number),
-                        Some(receipt.gas_used),
-                    );
-
-                    // Update the in-memory copy for this response
-                    updated_results[*idx].0.status = new_status;
-                    updated_results[*idx].0.block_number = Some(receipt.block_number);
-                    updated_results[*idx].0.gas_used = Some(receipt.gas_used);
-
-                    tracing::debug!(
-                        tx_hash = %hash,
-                        status = ?new_status,
-                        "list_transactions: reconciled pending tx"
-                    );
-                }
-            }
-
-            // Invalidate cache if we updated anything
-            if !pending_hashes.is_empty() {
-                if let Some(tx_cache) = &state.tx_cache {
-                    tx_cache.invalidate(&wallet_address);
-                }
+        for (idx, hash, network) in &pending_hashes {
+            let Ok(client) = state.chain_client(network).await else {
+                continue;
+            };
+            if let Ok(Some(receipt)) = client.get_transaction_receipt_status(hash).await {
+                let new_status = if receipt.success {
+                    TxStatus::Confirmed
+                } else {
+                    TxStatus::Failed
+                };
+
+                // Update redb so future reads are correct
+                let _ = tx_db.update_status(
+                    hash,
+                    new_status,
+                    Some(receipt.block_number),
+                    Some(receipt.gas_used),
+                );
+
+                // Update the in-memory copy for this response
+                updated_results[*idx].0.status = new_status;
+                updated_results[*idx].0.block_number = Some(receipt.block_number);
+                updated_results[*idx].0.gas_used = Some(receipt.gas_used);
+
+                tracing::debug!(
+                    tx_hash = %hash,
+                    status = ?new_status,
+                    "list_transactions: reconciled pending tx"
+                );
             }
         }
+
+        // Invalidate cache if we updated anything
+        state
+            .cache_bus
+            .invalidate(&wallet_address, CacheResource::TransactionHistory);
     }
 
     let mut summaries: Vec<TransactionSummary> = updated_results
         .iter()
-        .map(|(tx, dir)| to_summary_with_direction(tx, dir))
+        .map(|(tx, dir)| to_summary_with_direction(tx, dir, &prices))
         .collect();
 
-    summaries.retain(|tx| tx.network == "fuji");
+    summaries.retain(listed);
 
     if let Some(ref direction) = query.direction {
         summaries.retain(|s| s.direction == *direction);
     }
 
-    if query.cursor.is_none() && query.direction.is_none() {
+    if query.cursor.is_none() && query.direction.is_none() && state.cache_bus.is_current(epoch) {
         if let Some(tx_cache) = &state.tx_cache {
             tx_cache.put_first_page(&wallet_address, updated_results, next_cursor.clone());
         }
     }
 
-    Ok(Json(TransactionListResponse {
+    Ok(TransactionListResponse {
         transactions: summaries,
         next_cursor,
-    }))
+    })
 }
 
 /// Get the status of a specific transaction.
@@ -797,50 +1368,26 @@ pub async fn get_transaction_status(
     State(state): State<AppState>,
     Path((wallet_id, tx_hash)): Path<(String, String)>,
 ) -> Result<Json<TransactionStatusResponse>, ApiError> {
-    // Get wallet from storage
-    let storage = state.storage();
-    let wallet_repo = WalletRepository::new(storage);
-    let wallet = wallet_repo.get(&wallet_id).map_err(|e| match e {
-        crate::storage::StorageError::NotFound(_) => ApiError::not_found("Wallet not found"),
-        _ => ApiError::internal(format!("Failed to access storage: {}", e)),
-    })?;
-
-    // Verify ownership
-    if wallet.owner_user_id != user.user_id {
-        return Err(ApiError::forbidden("You do not own this wallet"));
-    }
-
+    let (wallet, tx) = owned_transaction(&state, &user, &wallet_id, &tx_hash)?;
     let tx_db = state
         .tx_db
         .as_ref()
         .expect("transaction database must be configured");
-    let tx = tx_db
-        .get_transaction(&tx_hash)
-        .map_err(|e| ApiError::internal(format!("Failed to get transaction: {}", e)))?
-        .ok_or_else(|| ApiError::not_found("Transaction not found"))?;
-    if !tx.from.eq_ignore_ascii_case(&wallet.public_address)
-        && !tx.to.eq_ignore_ascii_case(&wallet.public_address)
-    {
-        return Err(ApiError::not_found("Transaction not found"));
-    }
+
+    let network = networks().get(&tx.network).ok_or_else(|| {
+        ApiError::bad_request(format!(
+            "Network `{}` is not supported in this deployment.",
+            tx.network
+        ))
+    })?;
 
     // If pending, check blockchain for updates
     if tx.status == TxStatus::Pending {
-        if tx.network != "fuji" {
-            return Err(ApiError::bad_request(
-                "Only `fuji` network is supported in this deployment.",
-            ));
-        }
-        // Use shared client from AppState, fall back to per-request
-        let owned_client;
-        let client: &AvaxClient = if let Some(ref shared) = state.avax_client {
-            shared.as_ref()
-        } else {
-            owned_client = AvaxClient::fuji()
-                .await
-                .map_err(|e| ApiError::service_unavailable(format!("Failed to connect: {}", e)))?;
-            &owned_client
-        };
+        // Shared client on Fuji, per-request elsewhere
+        let client = state
+            .chain_client(network)
+            .await
+            .map_err(|e| ApiError::service_unavailable(format!("Failed to connect: {}", e)))?;
 
         // Get current block for confirmations
         let current_block = client.get_block_number().await.unwrap_or(0);
@@ -858,9 +1405,9 @@ pub async fn get_transaction_status(
                 Some(receipt.block_number),
                 Some(receipt.gas_used),
             );
-            if let Some(tx_cache) = &state.tx_cache {
-                tx_cache.invalidate(&wallet.public_address);
-            }
+            state
+                .cache_bus
+                .invalidate(&wallet.public_address, CacheResource::TransactionHistory);
 
             let confirmations = current_block.saturating_sub(receipt.block_number);
 
@@ -875,25 +1422,19 @@ pub async fn get_transaction_status(
                 confirmations: Some(confirmations),
                 gas_used: Some(receipt.gas_used.to_string()),
                 timestamp: Some(tx.updated_at.to_rfc3339()),
+                related_fiat_request_id: tx.related_fiat_request_id,
+                replaced_by: tx.replaced_by,
             }));
         }
     }
 
     // Return stored status
     let confirmations = if tx.status == TxStatus::Confirmed {
-        if tx.network != "fuji" {
-            return Err(ApiError::bad_request(
-                "Only `fuji` network is supported in this deployment.",
-            ));
-        }
         // Try to get current block for confirmations (shared client preferred)
         if let Some(block) = tx.block_number {
-            let result = if let Some(ref shared) = state.avax_client {
-                shared.get_block_number().await.ok()
-            } else if let Ok(client) = AvaxClient::fuji().await {
-                client.get_block_number().await.ok()
-            } else {
-                None
+            let result = match state.chain_client(network).await {
+                Ok(client) => client.get_block_number().await.ok(),
+                Err(_) => None,
             };
             result.map(|current| current.saturating_sub(block))
         } else {
@@ -914,9 +1455,159 @@ pub async fn get_transaction_status(
         confirmations,
         gas_used: tx.gas_used.map(|g| g.to_string()),
         timestamp: Some(tx.updated_at.to_rfc3339()),
+        related_fiat_request_id: tx.related_fiat_request_id,
+        replaced_by: tx.replaced_by,
     }))
 }
 
+/// Default and maximum long-poll duration for [`wait_for_transaction_status`].
+const DEFAULT_WAIT_SECS: u64 = 30;
+const MAX_WAIT_SECS: u64 = 60;
+/// How often a long poll asks the chain for a receipt of a pending transaction.
+const WAIT_RECEIPT_CHECK_SECS: u64 = 5;
+
+/// Load a transaction of a wallet owned by `user`.
+///
+/// Transactions the wallet is not a party to are reported as not found.
+pub(crate) fn owned_transaction(
+    state: &AppState,
+    user: &AuthenticatedUser,
+    wallet_id: &str,
+    tx_hash: &str,
+) -> Result<(WalletMetadata, StoredTransaction), ApiError> {
+    let wallet = WalletRepository::new(state.storage()).get(wallet_id)?;
+    if !wallet.is_owned_by(user) {
+        return Err(ApiError::forbidden("You do not own this wallet").with_code("wallet_not_owned"));
+    }
+
+    let tx_db = state
+        .tx_db
+        .as_ref()
+        .expect("transaction database must be configured");
+    let tx = tx_db
+        .get_transaction(tx_hash)
+        .map_err(|e| ApiError::internal(format!("Failed to get transaction: {}", e)))?
+        .ok_or_else(|| ApiError::not_found("Transaction not found"))?;
+    if !same_address(&tx.from, &wallet.public_address)
+        && !same_address(&tx.to, &wallet.public_address)
+    {
+        return Err(ApiError::not_found("Transaction not found"));
+    }
+    Ok((wallet, tx))
+}
+
+/// Wait for a transaction to leave `pending`.
+///
+/// Long-polling alternative to the WebSocket stream for clients that cannot
+/// hold a socket open. Returns as soon as the transaction is confirmed or
+/// failed, or with the current status once `timeout` seconds have passed.
+#[utoipa::path(
+    get,
+    path = "/v1/wallets/{wallet_id}/transactions/{tx_hash}/wait",
+    tag = "Transactions",
+    params(
+        ("wallet_id" = String, Path, description = "Wallet ID"),
+        ("tx_hash" = String, Path, description = "Transaction hash"),
+        WaitQuery
+    ),
+    security(("bearer" = [])),
+    responses(
+        (status = 200, description = "Transaction status after the change or timeout", body = TransactionStatusResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - not wallet owner"),
+        (status = 404, description = "Wallet or transaction not found")
+    )
+)]
+pub async fn wait_for_transaction_status(
+    Auth(user): Auth,
+    State(state): State<AppState>,
+    Path((wallet_id, tx_hash)): Path<(String, String)>,
+    Query(query): Query<WaitQuery>,
+) -> Result<Json<TransactionStatusResponse>, ApiError> {
+    use tokio::sync::broadcast::error::RecvError;
+    use tokio::time::{sleep_until, Duration, Instant};
+
+    let tx_db = state
+        .tx_db
+        .as_ref()
+        .expect("transaction database must be configured");
+    // Subscribe before reading so a change landing in between is not missed.
+    let mut changes = state.events.subscribe();
+    let (wallet, tx) = owned_transaction(&state, &user, &wallet_id, &tx_hash)?;
+
+    if tx.status == TxStatus::Pending {
+        let timeout = query
+            .timeout
+            .unwrap_or(DEFAULT_WAIT_SECS)
+            .clamp(1, MAX_WAIT_SECS);
+        let deadline = Instant::now() + Duration::from_secs(timeout);
+        let check_interval = Duration::from_secs(WAIT_RECEIPT_CHECK_SECS);
+        let mut next_check = Instant::now() + check_interval;
+
+        loop {
+            tokio::select! {
+                change = changes.recv() => match change {
+                    Ok(DomainEvent::TxStatusChanged(change)) => {
+                        if change.tx_hash.eq_ignore_ascii_case(&tx.tx_hash)
+                            && change.status != TxStatus::Pending
+                        {
+                            break;
+                        }
+                    }
+                    Ok(_) => {}
+                    Err(RecvError::Lagged(_)) => {
+                        let settled = tx_db
+                            .get_transaction(&tx.tx_hash)
+                            .ok()
+                            .flatten()
+                            .is_some_and(|t| t.status != TxStatus::Pending);
+                        if settled {
+                            break;
+                        }
+                    }
+                    Err(RecvError::Closed) => break,
+                },
+                _ = sleep_until(deadline.min(next_check)) => {
+                    if Instant::now() >= deadline {
+                        break;
+                    }
+                    next_check = Instant::now() + check_interval;
+                    // Only with the shared Fuji client; the final status read
+                    // below falls back to a per-request one.
+                    let Some(client) = state
+                        .avax_client
+                        .as_ref()
+                        .filter(|_| tx.network == NETWORK_FUJI)
+                    else {
+                        continue;
+                    };
+                    if let Ok(Some(receipt)) =
+                        client.get_transaction_receipt_status(&tx.tx_hash).await
+                    {
+                        let new_status = if receipt.success {
+                            TxStatus::Confirmed
+                        } else {
+                            TxStatus::Failed
+                        };
+                        let _ = tx_db.update_status(
+                            &tx.tx_hash,
+                            new_status,
+                            Some(receipt.block_number),
+                            Some(receipt.gas_used),
+                        );
+                        state
+                            .cache_bus
+                            .invalidate(&wallet.public_address, CacheResource::TransactionHistory);
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    get_transaction_status(Auth(user), State(state), Path((wallet_id, tx_hash))).await
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -932,6 +1623,7 @@ mod tests {
             session_id: None,
             issuer: "https://test.clerk.dev".to_string(),
             expires_at: Utc::now().timestamp() + 3600,
+            tenant_id: None,
         })
     }
 
@@ -945,6 +1637,13 @@ mod tests {
             label: None,
             email_lookup_key: None,
             email_sha256: None,
+            account_type: Default::default(),
+            smart_account: None,
+            lock: None,
+            deleted_at: None,
+            purged_at: None,
+            funded_at: None,
+            tenant_id: None,
         }
     }
 
@@ -1020,6 +1719,91 @@ mod tests {
         assert_eq!(response.transactions[0].direction, "received");
         assert_eq!(response.transactions[0].from.to_lowercase(), sender_addr);
         assert_eq!(response.transactions[0].to.to_lowercase(), receiver_addr);
+        assert!(response.next_cursor.is_none());
+
+        // A key from the sender's index is not a cursor for the receiver
+        let foreign = alloy::hex::encode(format!("{sender_addr}|x"));
+        let err = list_transactions(
+            mock_auth("user-b"),
+            State(state.clone()),
+            Path(receiver_wallet_id.to_string()),
+            Query(TransactionListQuery {
+                network: None,
+                limit: Some(10),
+                cursor: Some(foreign),
+                direction: None,
+            }),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn cached_first_pages_are_filtered_by_network() {
+        let state = AppState::default().with_tx_cache(Arc::new(
+            crate::storage::tx_cache::TxCache::new(16, std::time::Duration::from_secs(60)),
+        ));
+        let tx_db = state.tx_db.as_ref().unwrap();
+        let wallet_id = "wallet-1";
+        let address = "0x1111111111111111111111111111111111111111";
+        WalletRepository::new(state.storage())
+            .create(&wallet_meta(wallet_id, "user-a", address), b"test-key")
+            .unwrap();
+        tx_db.register_address(address, wallet_id).unwrap();
+        for (hash, network) in [("0xf0", "fuji"), ("0xb0", "base-sepolia")] {
+            let mut tx = StoredTransaction::new_pending(
+                hash.to_string(),
+                wallet_id.to_string(),
+                None,
+                address.to_string(),
+                "0x2222222222222222222222222222222222222222".to_string(),
+                "1".to_string(),
+                TokenType::Native,
+                network.to_string(),
+                String::new(),
+            );
+            tx.status = TxStatus::Confirmed;
+            tx_db
+                .upsert_transaction(&tx, &[(address.to_string(), "sent")])
+                .unwrap();
+        }
+
+        let list = |network: Option<&str>| {
+            let state = state.clone();
+            let network = network.map(str::to_string);
+            async move {
+                list_transactions(
+                    mock_auth("user-a"),
+                    State(state),
+                    Path(wallet_id.to_string()),
+                    Query(TransactionListQuery {
+                        network,
+                        limit: None,
+                        cursor: None,
+                        direction: None,
+                    }),
+                )
+                .await
+                .unwrap()
+                .0
+                .transactions
+                .into_iter()
+                .map(|tx| tx.tx_hash)
+                .collect::<Vec<_>>()
+            }
+        };
+        // The first read fills the cache; later ones are served from it.
+        // Base Sepolia is not registered here, so its transaction is hidden.
+        assert_eq!(list(None).await, ["0xf0"]);
+        assert!(state
+            .tx_cache
+            .as_ref()
+            .unwrap()
+            .get_first_page(&address_key(address), 50)
+            .is_some());
+        assert_eq!(list(None).await, ["0xf0"]);
+        assert_eq!(list(Some("fuji")).await, ["0xf0"]);
     }
 
     #[tokio::test]
@@ -1084,4 +1868,197 @@ mod tests {
         assert_eq!(response.tx_hash, tx_hash);
         assert!(response.block_number.is_none());
     }
+
+    #[tokio::test]
+    async fn wait_returns_when_a_pending_transaction_settles() {
+        let state = AppState::default();
+        let sender_addr = "0x5555555555555555555555555555555555555555";
+        let tx_hash = "0xabc333";
+        WalletRepository::new(state.storage())
+            .create(
+                &wallet_meta("wait-wallet", "user-a", sender_addr),
+                b"test-key",
+            )
+            .unwrap();
+
+        let tx = StoredTransaction::new_pending(
+            tx_hash.to_string(),
+            "wait-wallet".to_string(),
+            None,
+            sender_addr.to_string(),
+            "0x6666666666666666666666666666666666666666".to_string(),
+            "1".to_string(),
+            TokenType::Native,
+            "fuji".to_string(),
+            "https://testnet.snowtrace.io/tx/0xabc333".to_string(),
+        );
+        let tx_db = state.tx_db.clone().unwrap();
+        tx_db
+            .upsert_transaction(&tx, &[(sender_addr.to_string(), "sent")])
+            .unwrap();
+
+        let settle = tokio::spawn(async move {
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+            tx_db
+                .update_status(tx_hash, TxStatus::Failed, None, None)
+                .unwrap();
+        });
+
+        let response = wait_for_transaction_status(
+            mock_auth("user-a"),
+            State(state.clone()),
+            Path(("wait-wallet".to_string(), tx_hash.to_string())),
+            Query(WaitQuery { timeout: Some(5) }),
+        )
+        .await
+        .unwrap();
+        settle.await.unwrap();
+
+        assert_eq!(response.status, "failed");
+
+        let err = wait_for_transaction_status(
+            mock_auth("user-b"),
+            State(state),
+            Path(("wait-wallet".to_string(), tx_hash.to_string())),
+            Query(WaitQuery { timeout: Some(1) }),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn batched_sends_and_smart_account_estimates_are_mode_specific() {
+        let state = AppState::default();
+        let repo = WalletRepository::new(state.storage());
+        repo.create(
+            &wallet_meta(
+                "eoa-1",
+                "user-a",
+                "0x1111111111111111111111111111111111111111",
+            ),
+            b"test_key",
+        )
+        .unwrap();
+        let mut smart = wallet_meta(
+            "smart-1",
+            "user-a",
+            "0x2222222222222222222222222222222222222222",
+        );
+        smart.account_type = WalletAccountType::SmartAccount;
+        repo.create(&smart, b"test_key").unwrap();
+
+        let batch = BatchSendRequest {
+            transfers: vec![BatchTransfer {
+                to: "0x3333333333333333333333333333333333333333".to_string(),
+                amount: "1".to_string(),
+                token: default_native(),
+            }],
+            network: default_fuji(),
+        };
+        let err = send_batch(
+            mock_auth("user-a"),
+            State(state.clone()),
+            Path("eoa-1".to_string()),
+            Json(batch),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, axum::http::StatusCode::UNPROCESSABLE_ENTITY);
+
+        let err = send_batch(
+            mock_auth("user-a"),
+            State(state.clone()),
+            Path("smart-1".to_string()),
+            Json(BatchSendRequest {
+                transfers: Vec::new(),
+                network: default_fuji(),
+            }),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, axum::http::StatusCode::BAD_REQUEST);
+
+        let err = estimate_gas(
+            mock_auth("user-a"),
+            State(state),
+            Path("smart-1".to_string()),
+            Json(EstimateGasRequest {
+                to: Some("0x3333333333333333333333333333333333333333".to_string()),
+                to_email_hash: None,
+                amount: "1".to_string(),
+                token: default_native(),
+                network: default_fuji(),
+                spender: None,
+            }),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, axum::http::StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn send_from_watch_only_entry_is_rejected() {
+        let state = AppState::default();
+        crate::storage::WatchOnlyRepository::new(state.storage())
+            .create(&crate::storage::StoredWatchOnlyAddress {
+                watch_id: "watch-1".to_string(),
+                owner_user_id: "user-a".to_string(),
+                address: "0x5555555555555555555555555555555555555555".to_string(),
+                label: None,
+                created_at: Utc::now(),
+            })
+            .unwrap();
+        let request = || SendTransactionRequest {
+            to: Some("0x6666666666666666666666666666666666666666".to_string()),
+            to_email_hash: None,
+            amount: "1".to_string(),
+            token: default_native(),
+            network: default_fuji(),
+            gas_limit: None,
+            max_priority_fee_per_gas: None,
+            fee_speed: None,
+        };
+
+        let err = send_transaction(
+            mock_auth("user-a"),
+            State(state.clone()),
+            Path("watch-1".to_string()),
+            Json(request()),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, axum::http::StatusCode::UNPROCESSABLE_ENTITY);
+
+        // Other users must not learn that the ID exists.
+        let err = send_transaction(
+            mock_auth("user-b"),
+            State(state),
+            Path("watch-1".to_string()),
+            Json(request()),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, axum::http::StatusCode::NOT_FOUND);
+    }
+
+    #[test]
+    fn fee_speed_and_raw_priority_fee_are_exclusive() {
+        let mut request = SendTransactionRequest {
+            to: Some("0x6666666666666666666666666666666666666666".to_string()),
+            to_email_hash: None,
+            amount: "1".to_string(),
+            token: default_native(),
+            network: default_fuji(),
+            gas_limit: None,
+            max_priority_fee_per_gas: Some("1000000000".to_string()),
+            fee_speed: Some(FeeSpeed::Fast),
+        };
+        let err = parse_send_params(&request, &avax_fuji()).err().unwrap();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+
+        request.max_priority_fee_per_gas = None;
+        let params = parse_send_params(&request, &avax_fuji()).ok().unwrap();
+        assert_eq!(params.max_priority_fee, None);
+    }
 }
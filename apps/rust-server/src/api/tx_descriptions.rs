@@ -0,0 +1,443 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Transaction descriptions.
+//!
+//! Platform admins configure description templates per automatic
+//! [category](crate::api::categories) and token at
+//! `/v1/admin/tx-description-templates`. The most specific matching
+//! template describes each transaction in:
+//!
+//! - wallet transaction lists (`description`);
+//! - the tax report and its CSV export;
+//! - transfer alert notifications, which have no category, so only
+//!   templates without one apply there.
+//!
+//! Transactions no template matches have no description, and alerts keep
+//! their default message.
+
+use axum::{extract::State, Json};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::{
+    api::{
+        admin::require_platform_admin, categories::TxCategory, transactions::TransactionSummary,
+    },
+    auth::AdminOnly,
+    blockchain::{networks, tokens},
+    error::{ApiError, StorageContext},
+    providers::fiat::{CARD_PROVIDER_ID, TRUELAYER_PROVIDER_ID},
+    state::AppState,
+    storage::{
+        repository::tx_descriptions::{template_placeholders, TEMPLATE_PLACEHOLDERS},
+        AuditEvent, AuditRepository, DescriptionTemplate, DescriptionTemplateRepository,
+        EncryptedStorage, FiatRequestRepository, StoredDescriptionTemplates, StoredFiatRequest,
+        StoredTransaction, TokenType,
+    },
+};
+
+/// Most templates one configuration may hold.
+const MAX_TEMPLATES: usize = 100;
+/// Longest accepted template.
+const MAX_TEMPLATE_LEN: usize = 200;
+
+/// One template in a request.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct DescriptionTemplateRequest {
+    /// Automatic category the template applies to; omit for any.
+    #[serde(default)]
+    pub category: Option<TxCategory>,
+    /// Token symbol the template applies to, e.g. `rEUR`; omit for any.
+    #[serde(default)]
+    pub token: Option<String>,
+    /// Text with `{placeholder}`s.
+    pub template: String,
+}
+
+/// Request body replacing all description templates.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct PutDescriptionTemplatesRequest {
+    pub templates: Vec<DescriptionTemplateRequest>,
+}
+
+/// The configured description templates.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DescriptionTemplatesResponse {
+    pub templates: Vec<DescriptionTemplate>,
+    /// Placeholders templates may use.
+    pub placeholders: Vec<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated_by: Option<String>,
+}
+
+impl From<Option<StoredDescriptionTemplates>> for DescriptionTemplatesResponse {
+    fn from(stored: Option<StoredDescriptionTemplates>) -> Self {
+        Self {
+            placeholders: TEMPLATE_PLACEHOLDERS.to_vec(),
+            updated_at: stored.as_ref().map(|s| s.updated_at),
+            updated_by: stored.as_ref().map(|s| s.updated_by.clone()),
+            templates: stored.map(|s| s.templates).unwrap_or_default(),
+        }
+    }
+}
+
+/// What a template can say about one transaction.
+pub(crate) struct TxFacts<'t> {
+    pub tx_hash: &'t str,
+    /// `sent` or `received`.
+    pub direction: &'t str,
+    pub from: &'t str,
+    pub to: &'t str,
+    pub amount: &'t str,
+    /// `native` or the token contract address.
+    pub token: &'t str,
+    pub network: &'t str,
+    pub date: Option<NaiveDate>,
+    pub category: Option<TxCategory>,
+    pub fiat_request_id: Option<&'t str>,
+}
+
+impl<'t> TxFacts<'t> {
+    pub(crate) fn of_summary(tx: &'t TransactionSummary) -> Self {
+        Self {
+            tx_hash: &tx.tx_hash,
+            direction: &tx.direction,
+            from: &tx.from,
+            to: &tx.to,
+            amount: &tx.amount,
+            token: &tx.token,
+            network: &tx.network,
+            date: DateTime::parse_from_rfc3339(&tx.timestamp)
+                .ok()
+                .map(|at| at.date_naive()),
+            category: tx.category,
+            fiat_request_id: tx.related_fiat_request_id.as_deref(),
+        }
+    }
+
+    pub(crate) fn of_stored(
+        tx: &'t StoredTransaction,
+        direction: &'t str,
+        category: Option<TxCategory>,
+    ) -> Self {
+        Self {
+            tx_hash: &tx.tx_hash,
+            direction,
+            from: &tx.from,
+            to: &tx.to,
+            amount: &tx.amount,
+            token: match &tx.token {
+                TokenType::Native => "native",
+                TokenType::Erc20(address) => address,
+            },
+            network: &tx.network,
+            date: Some(tx.created_at.date_naive()),
+            category,
+            fiat_request_id: tx.related_fiat_request_id.as_deref(),
+        }
+    }
+}
+
+/// Symbol of `token` on `network`, or the token as given when unlisted.
+fn token_symbol(network: &str, token: &str) -> String {
+    let symbol = if token == "native" {
+        networks().get(network).map(|n| n.native_symbol.to_string())
+    } else {
+        tokens().get(network, token).map(|t| t.symbol)
+    };
+    symbol.unwrap_or_else(|| token.to_string())
+}
+
+fn provider_name(provider_id: &str) -> &str {
+    match provider_id {
+        TRUELAYER_PROVIDER_ID => "TrueLayer",
+        CARD_PROVIDER_ID => "card",
+        other => other,
+    }
+}
+
+/// Renders descriptions from the configured templates.
+pub(crate) struct TxDescriber<'a> {
+    storage: &'a EncryptedStorage,
+    templates: Option<StoredDescriptionTemplates>,
+}
+
+impl<'a> TxDescriber<'a> {
+    /// Load the templates. Transactions go undescribed if they cannot be
+    /// read.
+    pub(crate) fn load(storage: &'a EncryptedStorage) -> Self {
+        let templates = DescriptionTemplateRepository::new(storage)
+            .get()
+            .unwrap_or_else(|e| {
+                tracing::warn!(error = %e, "Failed to load description templates");
+                None
+            });
+        Self { storage, templates }
+    }
+
+    /// Description of a transaction, if a template matches it.
+    pub(crate) fn describe(&self, tx: &TxFacts<'_>) -> Option<String> {
+        let symbol = token_symbol(tx.network, tx.token);
+        let template = self
+            .templates
+            .as_ref()?
+            .select(tx.category.map(TxCategory::as_str), &symbol)?;
+        let needs_fiat = template_placeholders(&template.template).is_ok_and(|names| {
+            names
+                .iter()
+                .any(|n| matches!(*n, "provider" | "provider_reference" | "fiat_amount_eur"))
+        });
+        let fiat: Option<StoredFiatRequest> = tx
+            .fiat_request_id
+            .filter(|_| needs_fiat)
+            .and_then(|id| FiatRequestRepository::new(self.storage).get(id).ok());
+
+        Some(template.render(|name| {
+            match name {
+                "amount" => Some(tx.amount.to_string()),
+                "token" => Some(symbol.clone()),
+                "direction" => Some(tx.direction.to_string()),
+                "counterparty" => Some(
+                    if tx.direction == "sent" {
+                        tx.to
+                    } else {
+                        tx.from
+                    }
+                    .to_string(),
+                ),
+                "network" => Some(tx.network.to_string()),
+                "date" => tx.date.map(|d| d.to_string()),
+                "tx_hash" => Some(tx.tx_hash.to_string()),
+                "category" => tx.category.map(|c| c.as_str().to_string()),
+                "provider" => fiat
+                    .as_ref()
+                    .map(|f| provider_name(&f.provider).to_string()),
+                "provider_reference" => fiat.as_ref().and_then(|f| f.provider_reference.clone()),
+                "fiat_amount_eur" => fiat.as_ref().map(|f| f.amount_eur.clone()),
+                _ => None,
+            }
+        }))
+    }
+
+    /// Fill in the description of each listed transaction.
+    pub(crate) fn annotate(&self, transactions: &mut [TransactionSummary]) {
+        if self.templates.is_none() {
+            return;
+        }
+        for tx in transactions {
+            tx.description = self.describe(&TxFacts::of_summary(tx));
+        }
+    }
+}
+
+fn validate(body: PutDescriptionTemplatesRequest) -> Result<Vec<DescriptionTemplate>, ApiError> {
+    if body.templates.len() > MAX_TEMPLATES {
+        return Err(ApiError::bad_request(format!(
+            "At most {MAX_TEMPLATES} templates may be configured"
+        )));
+    }
+    let mut templates: Vec<DescriptionTemplate> = Vec::with_capacity(body.templates.len());
+    for request in body.templates {
+        let text = request.template.trim();
+        if text.is_empty() || text.chars().count() > MAX_TEMPLATE_LEN {
+            return Err(ApiError::bad_request(format!(
+                "template must be 1 to {MAX_TEMPLATE_LEN} characters"
+            )));
+        }
+        template_placeholders(text)
+            .map_err(|e| ApiError::bad_request(format!("Invalid template '{text}': {e}")))?;
+        let template = DescriptionTemplate {
+            category: request.category.map(|c| c.as_str().to_string()),
+            token: request
+                .token
+                .map(|t| t.trim().to_string())
+                .filter(|t| !t.is_empty()),
+            template: text.to_string(),
+        };
+        let duplicate = templates.iter().any(|t| {
+            t.category == template.category
+                && t.token.as_deref().map(str::to_ascii_lowercase)
+                    == template.token.as_deref().map(str::to_ascii_lowercase)
+        });
+        if duplicate {
+            return Err(ApiError::bad_request(
+                "Only one template may target each category and token",
+            ));
+        }
+        templates.push(template);
+    }
+    Ok(templates)
+}
+
+/// Get the transaction description templates (admin only).
+#[utoipa::path(
+    get,
+    path = "/v1/admin/tx-description-templates",
+    tag = "Admin",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Description templates", body = DescriptionTemplatesResponse),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not authorized (platform admin required)")
+    )
+)]
+pub async fn get_description_templates(
+    AdminOnly(admin): AdminOnly,
+    State(state): State<AppState>,
+) -> Result<Json<DescriptionTemplatesResponse>, ApiError> {
+    require_platform_admin(&admin)?;
+    let stored = DescriptionTemplateRepository::new(state.storage())
+        .get()
+        .context("Failed to load description templates")?;
+    Ok(Json(stored.into()))
+}
+
+/// Replace the transaction description templates (admin only).
+///
+/// Takes effect on the next read; an empty list turns descriptions off.
+/// Templates are shared by all tenants, so tenant admins cannot change
+/// them.
+#[utoipa::path(
+    put,
+    path = "/v1/admin/tx-description-templates",
+    tag = "Admin",
+    request_body = PutDescriptionTemplatesRequest,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Templates saved", body = DescriptionTemplatesResponse),
+        (status = 400, description = "Invalid or duplicate template"),
+        (status = 401, description = "Not authenticated"),
+        (status = 403, description = "Not authorized (platform admin required)")
+    )
+)]
+pub async fn put_description_templates(
+    AdminOnly(admin): AdminOnly,
+    State(state): State<AppState>,
+    Json(body): Json<PutDescriptionTemplatesRequest>,
+) -> Result<Json<DescriptionTemplatesResponse>, ApiError> {
+    require_platform_admin(&admin)?;
+    let templates = validate(body)?;
+
+    let repo = DescriptionTemplateRepository::new(state.storage());
+    let old = repo.get().context("Failed to load description templates")?;
+    let stored = StoredDescriptionTemplates {
+        templates,
+        updated_at: Utc::now(),
+        updated_by: admin.user_id.clone(),
+    };
+    repo.save(&stored)
+        .context("Failed to save description templates")?;
+
+    let event = AuditEvent::config_changed(
+        old.and_then(|t| serde_json::to_value(t.templates).ok()),
+        serde_json::to_value(&stored.templates).ok(),
+    )
+    .with_user(&admin.user_id)
+    .with_resource("settings", "tx_description_templates");
+    let _ = AuditRepository::new(state.storage()).log(&event);
+
+    Ok(Json(Some(stored).into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::StoragePaths;
+
+    fn request(
+        category: Option<TxCategory>,
+        token: Option<&str>,
+        text: &str,
+    ) -> DescriptionTemplateRequest {
+        DescriptionTemplateRequest {
+            category,
+            token: token.map(str::to_string),
+            template: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn templates_are_validated() {
+        let ok = validate(PutDescriptionTemplatesRequest {
+            templates: vec![
+                request(None, None, "  {direction} {amount} {token} "),
+                request(Some(TxCategory::Fee), Some("rEUR"), "Fee"),
+            ],
+        })
+        .unwrap();
+        assert_eq!(ok[0].template, "{direction} {amount} {token}");
+        assert_eq!(ok[1].category.as_deref(), Some("fee"));
+
+        for templates in [
+            vec![request(None, None, "Hello {name}")],
+            vec![request(None, None, "   ")],
+            vec![
+                request(Some(TxCategory::Fee), Some("rEUR"), "a"),
+                request(Some(TxCategory::Fee), Some("reur"), "b"),
+            ],
+        ] {
+            assert!(validate(PutDescriptionTemplatesRequest { templates }).is_err());
+        }
+    }
+
+    #[test]
+    fn describes_fiat_settlements_with_their_provider_reference() {
+        let test_dir =
+            std::env::temp_dir().join(format!("test-tx-describer-{}", uuid::Uuid::new_v4()));
+        let mut storage = EncryptedStorage::new(StoragePaths::new(&test_dir));
+        storage.initialize().unwrap();
+        DescriptionTemplateRepository::new(&storage)
+            .save(&StoredDescriptionTemplates {
+                templates: vec![DescriptionTemplate {
+                    category: Some("fiat_settlement".to_string()),
+                    token: None,
+                    template: "Top-up via {provider} – ref {provider_reference} ({amount} {token})"
+                        .to_string(),
+                }],
+                updated_at: Utc::now(),
+                updated_by: "admin".to_string(),
+            })
+            .unwrap();
+        let mut fiat = StoredFiatRequest::new_queued(
+            "fr-1".to_string(),
+            "wallet-1".to_string(),
+            "user-1".to_string(),
+            crate::storage::FiatDirection::OnRamp,
+            "25.00".to_string(),
+            TRUELAYER_PROVIDER_ID.to_string(),
+            None,
+        );
+        fiat.provider_reference = Some("PAY-42".to_string());
+        FiatRequestRepository::new(&storage).create(&fiat).unwrap();
+
+        let describer = TxDescriber::load(&storage);
+        let facts = TxFacts {
+            tx_hash: "0xabc",
+            direction: "received",
+            from: "0x1111111111111111111111111111111111111111",
+            to: "0x2222222222222222222222222222222222222222",
+            amount: "25.0",
+            token: "native",
+            network: "fuji",
+            date: None,
+            category: Some(TxCategory::FiatSettlement),
+            fiat_request_id: Some("fr-1"),
+        };
+        assert_eq!(
+            describer.describe(&facts).as_deref(),
+            Some("Top-up via TrueLayer – ref PAY-42 (25.0 AVAX)")
+        );
+        let transfer = TxFacts {
+            category: Some(TxCategory::Transfer),
+            ..facts
+        };
+        assert!(describer.describe(&transfer).is_none());
+
+        let _ = std::fs::remove_dir_all(&test_dir);
+    }
+}
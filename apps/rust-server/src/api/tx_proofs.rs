@@ -0,0 +1,263 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Transaction proof bundles.
+//!
+//! `GET /v1/wallets/{wallet_id}/transactions/{tx_hash}/proof` gathers what
+//! a counterparty needs to check a payment without trusting a block
+//! explorer:
+//!
+//! - the raw signed transaction; its keccak256 is the transaction hash;
+//! - the receipt, whose `blockHash` and `status` show where and whether it
+//!   executed;
+//! - the header fields of that block, to compare against any node;
+//! - a statement of the payment signed inside the enclave.
+//!
+//! The statement is [`crate::canonical_json`] and signed with the server's
+//! published Ed25519 key, the one that signs webhook deliveries: the
+//! `signature` has the webhook header form `t=<unix>,kid=<key id>,v1=<sig>`
+//! over `"{t}.{signed_statement}"`, and the keys are served by
+//! `GET /v1/webhooks/signing-key`. Its `type` keeps it from being mistaken
+//! for a webhook body. Keys are destroyed once their rotation overlap ends,
+//! so a proof should be verified when received, not archived for later.
+
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use chrono::Utc;
+use serde::Serialize;
+use serde_json::Value;
+use utoipa::ToSchema;
+
+use crate::{
+    api::transactions::owned_transaction,
+    auth::Auth,
+    blockchain::{networks, BlockReference},
+    canonical_json,
+    error::ApiError,
+    state::AppState,
+    storage::{StorageError, TokenType},
+    webhooks::{self, WebhookSignatureError},
+};
+
+/// `type` of a transaction proof statement.
+pub const PROOF_STATEMENT_TYPE: &str = "relational.transaction_proof.v1";
+
+/// Facts the server attests to about a mined transaction.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ProofStatement {
+    /// Always `relational.transaction_proof.v1`.
+    #[serde(rename = "type")]
+    pub statement_type: String,
+    pub chain_id: u64,
+    pub network: String,
+    pub tx_hash: String,
+    pub from: String,
+    /// Recipient of the payment (for tokens, not the contract).
+    pub to: String,
+    /// Amount in human-readable units.
+    pub amount: String,
+    pub token: TokenType,
+    /// Whether the transaction executed without reverting.
+    pub success: bool,
+    pub block_number: u64,
+    pub block_hash: String,
+    /// Unix seconds.
+    pub block_timestamp: u64,
+    pub confirmations: u64,
+    /// Unix seconds.
+    pub issued_at: i64,
+}
+
+/// Proof that a transaction was mined, for verification without an explorer.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct TransactionProofResponse {
+    /// EIP-2718 encoded signed transaction, hex.
+    pub raw_transaction: String,
+    /// Receipt as returned by `eth_getTransactionReceipt`.
+    #[schema(value_type = Object)]
+    pub receipt: Value,
+    pub block: BlockReference,
+    pub statement: ProofStatement,
+    /// `statement` exactly as signed (canonical JSON).
+    pub signed_statement: String,
+    /// `t=<unix>,kid=<key id>,v1=<signature>` over `"{t}.{signed_statement}"`.
+    pub signature: String,
+}
+
+/// Serialize and sign a statement issued at `issued_at`; returns the signed
+/// text and the signature header. Statements carry a `type` so one kind is
+/// never mistaken for another.
+pub(crate) async fn sign_statement<T: Serialize>(
+    state: &AppState,
+    statement: &T,
+    issued_at: i64,
+) -> Result<(String, String), ApiError> {
+    let signed_statement = canonical_json::to_string(statement)
+        .map_err(|e| ApiError::internal(format!("Failed to serialize statement: {e}")))?;
+    let signature = webhooks::sign_payload(
+        state.storage(),
+        &state.secret_signer,
+        signed_statement.as_bytes(),
+        issued_at,
+    )
+    .await
+    .map_err(|e| match e {
+        WebhookSignatureError::Signer(e) => ApiError::from(e),
+        WebhookSignatureError::Storage(StorageError::NotFound(_)) => {
+            ApiError::service_unavailable("Signing key not initialized")
+        }
+        other => ApiError::internal(format!("Failed to sign statement: {other}")),
+    })?;
+    Ok((signed_statement, signature))
+}
+
+/// Get a proof bundle for a transaction.
+///
+/// Returns the raw signed transaction, its receipt, the block header it was
+/// mined in and a statement of the payment signed by the enclave.
+#[utoipa::path(
+    get,
+    path = "/v1/wallets/{wallet_id}/transactions/{tx_hash}/proof",
+    tag = "Transactions",
+    params(
+        ("wallet_id" = String, Path, description = "Wallet ID"),
+        ("tx_hash" = String, Path, description = "Transaction hash")
+    ),
+    security(("bearer" = [])),
+    responses(
+        (status = 200, description = "Proof bundle", body = TransactionProofResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - not wallet owner"),
+        (status = 404, description = "Wallet or transaction not found"),
+        (status = 409, description = "Transaction not mined yet"),
+        (status = 503, description = "Blockchain network or signing key unavailable")
+    )
+)]
+pub async fn get_transaction_proof(
+    Auth(user): Auth,
+    State(state): State<AppState>,
+    Path((wallet_id, tx_hash)): Path<(String, String)>,
+) -> Result<Json<TransactionProofResponse>, ApiError> {
+    let (_, tx) = owned_transaction(&state, &user, &wallet_id, &tx_hash)?;
+    let network = networks().get(&tx.network).ok_or_else(|| {
+        ApiError::bad_request(format!(
+            "Network `{}` is not supported in this deployment.",
+            tx.network
+        ))
+    })?;
+
+    let client = state
+        .chain_client(network)
+        .await
+        .map_err(|e| ApiError::service_unavailable(format!("Failed to connect: {e}")))?;
+    let inclusion = client
+        .get_transaction_inclusion(&tx.tx_hash)
+        .await
+        .map_err(|e| ApiError::service_unavailable(format!("Failed to read transaction: {e}")))?
+        .ok_or_else(|| ApiError::conflict("Transaction is not mined yet"))?;
+    let current_block = client
+        .get_block_number()
+        .await
+        .map_err(|e| ApiError::service_unavailable(format!("Failed to read block: {e}")))?;
+
+    let statement = ProofStatement {
+        statement_type: PROOF_STATEMENT_TYPE.to_string(),
+        chain_id: network.chain_id,
+        network: network.id.to_string(),
+        tx_hash: tx.tx_hash,
+        from: tx.from,
+        to: tx.to,
+        amount: tx.amount,
+        token: tx.token,
+        success: inclusion.success,
+        block_number: inclusion.block.number,
+        block_hash: inclusion.block.hash.clone(),
+        block_timestamp: inclusion.block.timestamp,
+        confirmations: current_block.saturating_sub(inclusion.block.number),
+        issued_at: Utc::now().timestamp(),
+    };
+    let (signed_statement, signature) =
+        sign_statement(&state, &statement, statement.issued_at).await?;
+
+    Ok(Json(TransactionProofResponse {
+        raw_transaction: alloy::hex::encode_prefixed(&inclusion.raw_transaction),
+        receipt: inclusion.receipt,
+        block: inclusion.block,
+        statement,
+        signed_statement,
+        signature,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::WebhookKeyRepository;
+    use crate::webhooks::{verify_signature, WebhookPublicKey, DEFAULT_TOLERANCE_SECS};
+
+    #[tokio::test]
+    async fn statements_verify_against_the_published_keys() {
+        let state = AppState::default();
+        let statement = ProofStatement {
+            statement_type: PROOF_STATEMENT_TYPE.to_string(),
+            chain_id: 43113,
+            network: "fuji".to_string(),
+            tx_hash: "0xabc".to_string(),
+            from: "0x1111111111111111111111111111111111111111".to_string(),
+            to: "0x2222222222222222222222222222222222222222".to_string(),
+            amount: "5".to_string(),
+            token: TokenType::Native,
+            success: true,
+            block_number: 7,
+            block_hash: "0xdef".to_string(),
+            block_timestamp: 1_700_000_000,
+            confirmations: 3,
+            issued_at: Utc::now().timestamp(),
+        };
+
+        let err = sign_statement(&state, &statement, statement.issued_at)
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, axum::http::StatusCode::SERVICE_UNAVAILABLE);
+
+        webhooks::bootstrap_signing_key(state.storage(), &state.secret_signer)
+            .await
+            .unwrap();
+        let (signed, signature) = sign_statement(&state, &statement, statement.issued_at)
+            .await
+            .unwrap();
+        assert!(signed.starts_with("{\"amount\":\"5\",\"block_hash\""));
+        assert!(signed.contains("\"type\":\"relational.transaction_proof.v1\""));
+
+        let keys: Vec<_> = WebhookKeyRepository::new(state.storage())
+            .get()
+            .unwrap()
+            .keys
+            .into_iter()
+            .map(|k| WebhookPublicKey {
+                key_id: k.key_id,
+                public_key: k.public_key,
+            })
+            .collect();
+        let now = statement.issued_at;
+        verify_signature(
+            &signature,
+            signed.as_bytes(),
+            &keys,
+            now,
+            DEFAULT_TOLERANCE_SECS,
+        )
+        .unwrap();
+        assert!(verify_signature(
+            &signature,
+            signed.replace("\"5\"", "\"50\"").as_bytes(),
+            &keys,
+            now,
+            DEFAULT_TOLERANCE_SECS
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,316 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Speeding up and cancelling pending transactions.
+//!
+//! `POST /v1/wallets/{wallet_id}/transactions/{tx_hash}/speedup` and
+//! `/cancel` rebroadcast a stuck send with the same nonce and higher fees:
+//! a speed-up repeats the transaction, a cancel replaces it by a zero-value
+//! transfer to the wallet itself. See
+//! [`crate::blockchain::transactions::TxBuilder::replace_transaction`].
+//!
+//! The replacement is stored as a new pending transaction with `replaces`
+//! set; the original gets `replaced_by` and is marked failed. Should the
+//! original still be mined first, the replacement fails instead and the
+//! indexer confirms the original.
+
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::{
+    api::{
+        limits,
+        transactions::{owned_transaction, send_error, sending_wallet},
+    },
+    auth::{Auth, AuthenticatedUser},
+    blockchain::{networks, same_address, wallet_from_pem, ReplacementKind, TxBuilder},
+    error::ApiError,
+    state::AppState,
+    storage::{
+        AuditEvent, AuditEventType, AuditRepository, CacheResource, StoredTransaction, TokenType,
+        TxStatus, WalletAccountType, WalletMetadata, WalletRepository,
+    },
+};
+
+/// Response for a replaced transaction.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ReplacementResponse {
+    /// Hash of the replacement transaction
+    pub tx_hash: String,
+    /// Hash of the transaction it replaces
+    pub replaces: String,
+    /// Status of the replacement: "pending"
+    pub status: String,
+    /// Block explorer URL of the replacement
+    pub explorer_url: String,
+}
+
+/// Check that `tx` is a pending transaction `wallet` can replace.
+fn ensure_replaceable(wallet: &WalletMetadata, tx: &StoredTransaction) -> Result<(), ApiError> {
+    if wallet.account_type == WalletAccountType::SmartAccount {
+        return Err(ApiError::unprocessable(
+            "Smart-account UserOperations cannot be replaced",
+        ));
+    }
+    if !same_address(&tx.from, &wallet.public_address) {
+        return Err(ApiError::unprocessable(
+            "Only transactions sent by this wallet can be replaced",
+        ));
+    }
+    if tx.status != TxStatus::Pending {
+        return Err(ApiError::conflict("Transaction is no longer pending"));
+    }
+    Ok(())
+}
+
+/// Replace a pending transaction, then store, link and audit the replacement.
+async fn replace(
+    state: &AppState,
+    user: &AuthenticatedUser,
+    wallet_id: &str,
+    tx_hash: &str,
+    kind: ReplacementKind,
+) -> Result<ReplacementResponse, ApiError> {
+    let storage = state.storage();
+    let wallet = sending_wallet(storage, user, wallet_id)?;
+    let (_, tx) = owned_transaction(state, user, wallet_id, tx_hash)?;
+    ensure_replaceable(&wallet, &tx)?;
+    let network = networks().get(&tx.network).ok_or_else(|| {
+        ApiError::bad_request(format!(
+            "Network `{}` is not supported in this deployment.",
+            tx.network
+        ))
+    })?;
+
+    let _signing = limits::hold_for_signing(state, &wallet).await?;
+    let key = WalletRepository::new(storage)
+        .read_private_key(wallet_id)
+        .map_err(|e| ApiError::internal(format!("Failed to read private key: {e}")))?;
+    let eth_wallet = wallet_from_pem(&key)
+        .map_err(|e| ApiError::internal(format!("Failed to create signer: {e}")))?;
+    let result = TxBuilder::new(network.clone(), eth_wallet)
+        .await
+        .map_err(|e| ApiError::service_unavailable(format!("Failed to connect: {e}")))?
+        .with_nonce_manager(state.nonce_manager.clone())
+        .replace_transaction(&tx.tx_hash, kind)
+        .await
+        .map_err(send_error)?
+        .ok_or_else(|| ApiError::conflict("Transaction is no longer pending"))?;
+
+    let tx_db = state
+        .tx_db
+        .as_ref()
+        .expect("transaction database must be configured");
+    let mut directions = vec![(wallet.public_address.clone(), "sent")];
+    let mut replacement = match kind {
+        ReplacementKind::SpeedUp => {
+            let recipient_wallet_id = tx_db.get_wallet_id_for_address(&tx.to).ok().flatten();
+            if recipient_wallet_id.is_some() {
+                directions.push((tx.to.clone(), "received"));
+            }
+            let mut replacement = StoredTransaction::new_pending(
+                result.tx_hash.clone(),
+                wallet.wallet_id.clone(),
+                [
+                    Some(tx.wallet_id.clone()),
+                    tx.counterparty_wallet_id.clone(),
+                ]
+                .into_iter()
+                .flatten()
+                .find(|id| id != &wallet.wallet_id),
+                tx.from.clone(),
+                tx.to.clone(),
+                tx.amount.clone(),
+                tx.token.clone(),
+                tx.network.clone(),
+                result.explorer_url.clone(),
+            );
+            replacement.related_fiat_request_id = tx.related_fiat_request_id.clone();
+            replacement
+        }
+        ReplacementKind::Cancel => StoredTransaction::new_pending(
+            result.tx_hash.clone(),
+            wallet.wallet_id.clone(),
+            None,
+            tx.from.clone(),
+            tx.from.clone(),
+            "0".to_string(),
+            TokenType::Native,
+            tx.network.clone(),
+            result.explorer_url.clone(),
+        ),
+    };
+    replacement.replaces = Some(tx.tx_hash.clone());
+    if let Err(e) = tx_db.upsert_transaction(&replacement, &directions) {
+        tracing::warn!(error = %e, "Failed to store replacement transaction");
+    }
+    if let Err(e) = tx_db.mark_replaced(&tx.tx_hash, &result.tx_hash) {
+        tracing::warn!(error = %e, "Failed to mark transaction as replaced");
+    }
+    state
+        .cache_bus
+        .invalidate(&tx.from, CacheResource::TransactionHistory);
+    state
+        .cache_bus
+        .invalidate(&tx.to, CacheResource::TransactionHistory);
+
+    let event = AuditEvent::new(AuditEventType::TransactionBroadcast)
+        .with_user(&user.user_id)
+        .with_resource(wallet_id, "wallet")
+        .with_details(serde_json::json!({
+            "tx_hash": result.tx_hash,
+            "replaces": tx.tx_hash,
+            "kind": match kind {
+                ReplacementKind::SpeedUp => "speedup",
+                ReplacementKind::Cancel => "cancel",
+            },
+            "network": tx.network,
+        }));
+    let _ = AuditRepository::new(storage).log(&event);
+
+    Ok(ReplacementResponse {
+        tx_hash: result.tx_hash,
+        replaces: tx.tx_hash,
+        status: "pending".to_string(),
+        explorer_url: result.explorer_url,
+    })
+}
+
+/// Speed up a pending transaction.
+///
+/// Rebroadcasts the transaction with the same nonce and higher fees.
+#[utoipa::path(
+    post,
+    path = "/v1/wallets/{wallet_id}/transactions/{tx_hash}/speedup",
+    tag = "Transactions",
+    params(
+        ("wallet_id" = String, Path, description = "Wallet ID"),
+        ("tx_hash" = String, Path, description = "Hash of the pending transaction")
+    ),
+    security(("bearer" = [])),
+    responses(
+        (status = 200, description = "Replacement broadcast", body = ReplacementResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - not wallet owner"),
+        (status = 404, description = "Wallet or transaction not found"),
+        (status = 409, description = "Transaction is no longer pending"),
+        (status = 422, description = "Not sent by this wallet, smart-account wallet, or insufficient balance"),
+        (status = 503, description = "Blockchain network unavailable")
+    )
+)]
+pub async fn speed_up_transaction(
+    Auth(user): Auth,
+    State(state): State<AppState>,
+    Path((wallet_id, tx_hash)): Path<(String, String)>,
+) -> Result<Json<ReplacementResponse>, ApiError> {
+    replace(
+        &state,
+        &user,
+        &wallet_id,
+        &tx_hash,
+        ReplacementKind::SpeedUp,
+    )
+    .await
+    .map(Json)
+}
+
+/// Cancel a pending transaction.
+///
+/// Replaces the transaction by a zero-value transfer to the wallet itself
+/// with the same nonce and higher fees, so the original never executes.
+#[utoipa::path(
+    post,
+    path = "/v1/wallets/{wallet_id}/transactions/{tx_hash}/cancel",
+    tag = "Transactions",
+    params(
+        ("wallet_id" = String, Path, description = "Wallet ID"),
+        ("tx_hash" = String, Path, description = "Hash of the pending transaction")
+    ),
+    security(("bearer" = [])),
+    responses(
+        (status = 200, description = "Cancellation broadcast", body = ReplacementResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - not wallet owner"),
+        (status = 404, description = "Wallet or transaction not found"),
+        (status = 409, description = "Transaction is no longer pending"),
+        (status = 422, description = "Not sent by this wallet, smart-account wallet, or insufficient balance"),
+        (status = 503, description = "Blockchain network unavailable")
+    )
+)]
+pub async fn cancel_transaction(
+    Auth(user): Auth,
+    State(state): State<AppState>,
+    Path((wallet_id, tx_hash)): Path<(String, String)>,
+) -> Result<Json<ReplacementResponse>, ApiError> {
+    replace(&state, &user, &wallet_id, &tx_hash, ReplacementKind::Cancel)
+        .await
+        .map(Json)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::StatusCode;
+    use chrono::Utc;
+
+    const ADDRESS: &str = "0x1111111111111111111111111111111111111111";
+
+    fn wallet() -> WalletMetadata {
+        WalletMetadata {
+            wallet_id: "wallet-1".to_string(),
+            owner_user_id: "user-1".to_string(),
+            public_address: ADDRESS.to_string(),
+            created_at: Utc::now(),
+            status: crate::storage::WalletStatus::Active,
+            label: None,
+            email_lookup_key: None,
+            email_sha256: None,
+            account_type: Default::default(),
+            smart_account: None,
+            lock: None,
+            deleted_at: None,
+            purged_at: None,
+            funded_at: None,
+            tenant_id: None,
+        }
+    }
+
+    fn sent(from: &str) -> StoredTransaction {
+        StoredTransaction::new_pending(
+            "0xabc".to_string(),
+            "wallet-1".to_string(),
+            None,
+            from.to_string(),
+            "0x2222222222222222222222222222222222222222".to_string(),
+            "1".to_string(),
+            TokenType::Native,
+            "fuji".to_string(),
+            String::new(),
+        )
+    }
+
+    #[test]
+    fn only_pending_sends_of_the_wallet_are_replaceable() {
+        let wallet = wallet();
+        ensure_replaceable(&wallet, &sent(ADDRESS)).unwrap();
+
+        let received = sent("0x3333333333333333333333333333333333333333");
+        let err = ensure_replaceable(&wallet, &received).unwrap_err();
+        assert_eq!(err.status, StatusCode::UNPROCESSABLE_ENTITY);
+
+        let mut mined = sent(ADDRESS);
+        mined.mark_confirmed(7, 21_000);
+        let err = ensure_replaceable(&wallet, &mined).unwrap_err();
+        assert_eq!(err.status, StatusCode::CONFLICT);
+
+        let mut smart = wallet.clone();
+        smart.account_type = WalletAccountType::SmartAccount;
+        let err = ensure_replaceable(&smart, &sent(ADDRESS)).unwrap_err();
+        assert_eq!(err.status, StatusCode::UNPROCESSABLE_ENTITY);
+    }
+}
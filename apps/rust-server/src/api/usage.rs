@@ -0,0 +1,354 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Per-user storage quotas.
+//!
+//! Saved objects (bookmarks, fiat beneficiaries and fiat mandates) are
+//! capped per user, both by count per kind and by their total stored size.
+//! Create handlers call [`check_quota`] before saving, and users see their
+//! consumption at `GET /v1/users/me/usage`. Removed beneficiaries and
+//! revoked or failed mandates do not count.
+//!
+//! Limits come from `USER_QUOTA_BOOKMARKS`, `USER_QUOTA_FIAT_BENEFICIARIES`,
+//! `USER_QUOTA_FIAT_MANDATES` and `USER_QUOTA_STORAGE_BYTES`.
+
+use std::env;
+
+use axum::{extract::State, Json};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use super::soft_quotas::{self, QuotaUsage};
+use crate::{
+    auth::Auth,
+    error::{ApiError, StorageContext},
+    state::AppState,
+    storage::{
+        BookmarkRepository, EncryptedStorage, FiatBeneficiaryRepository, FiatMandateRepository,
+        FiatMandateStatus, StorageResult,
+    },
+};
+
+const DEFAULT_MAX_BOOKMARKS: u64 = 500;
+const DEFAULT_MAX_FIAT_BENEFICIARIES: u64 = 50;
+const DEFAULT_MAX_FIAT_MANDATES: u64 = 20;
+const DEFAULT_MAX_STORAGE_BYTES: u64 = 1024 * 1024;
+
+/// Kinds of saved objects with a per-user quota.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuotaKind {
+    Bookmarks,
+    FiatBeneficiaries,
+    FiatMandates,
+}
+
+impl QuotaKind {
+    const ALL: [QuotaKind; 3] = [
+        QuotaKind::Bookmarks,
+        QuotaKind::FiatBeneficiaries,
+        QuotaKind::FiatMandates,
+    ];
+
+    /// Name used in responses and quota headers.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            QuotaKind::Bookmarks => "bookmarks",
+            QuotaKind::FiatBeneficiaries => "fiat_beneficiaries",
+            QuotaKind::FiatMandates => "fiat_mandates",
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            QuotaKind::Bookmarks => "bookmarks",
+            QuotaKind::FiatBeneficiaries => "beneficiaries",
+            QuotaKind::FiatMandates => "mandates",
+        }
+    }
+}
+
+/// Per-user limits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StorageQuotas {
+    pub max_bookmarks: u64,
+    pub max_fiat_beneficiaries: u64,
+    pub max_fiat_mandates: u64,
+    /// Total size of a user's saved objects, in bytes of JSON.
+    pub max_bytes: u64,
+}
+
+impl Default for StorageQuotas {
+    fn default() -> Self {
+        Self {
+            max_bookmarks: DEFAULT_MAX_BOOKMARKS,
+            max_fiat_beneficiaries: DEFAULT_MAX_FIAT_BENEFICIARIES,
+            max_fiat_mandates: DEFAULT_MAX_FIAT_MANDATES,
+            max_bytes: DEFAULT_MAX_STORAGE_BYTES,
+        }
+    }
+}
+
+impl StorageQuotas {
+    /// Limits from the environment, falling back to the defaults.
+    pub fn from_env() -> Self {
+        let read = |name: &str, default: u64| {
+            env::var(name)
+                .ok()
+                .and_then(|v| v.trim().parse().ok())
+                .unwrap_or(default)
+        };
+        Self {
+            max_bookmarks: read("USER_QUOTA_BOOKMARKS", DEFAULT_MAX_BOOKMARKS),
+            max_fiat_beneficiaries: read(
+                "USER_QUOTA_FIAT_BENEFICIARIES",
+                DEFAULT_MAX_FIAT_BENEFICIARIES,
+            ),
+            max_fiat_mandates: read("USER_QUOTA_FIAT_MANDATES", DEFAULT_MAX_FIAT_MANDATES),
+            max_bytes: read("USER_QUOTA_STORAGE_BYTES", DEFAULT_MAX_STORAGE_BYTES),
+        }
+    }
+
+    fn max_count(&self, kind: QuotaKind) -> u64 {
+        match kind {
+            QuotaKind::Bookmarks => self.max_bookmarks,
+            QuotaKind::FiatBeneficiaries => self.max_fiat_beneficiaries,
+            QuotaKind::FiatMandates => self.max_fiat_mandates,
+        }
+    }
+}
+
+/// Consumption of one kind of saved object.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ObjectUsage {
+    /// `bookmarks`, `fiat_beneficiaries` or `fiat_mandates`.
+    pub kind: String,
+    pub count: u64,
+    pub max_count: u64,
+    /// Stored size in bytes.
+    pub bytes: u64,
+}
+
+/// The caller's storage consumption against their quotas.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct UsageResponse {
+    pub objects: Vec<ObjectUsage>,
+    pub total_bytes: u64,
+    pub max_bytes: u64,
+}
+
+fn json_bytes<T: Serialize>(items: &[T]) -> u64 {
+    items
+        .iter()
+        .map(|item| serde_json::to_vec(item).map_or(0, |bytes| bytes.len() as u64))
+        .sum()
+}
+
+/// Count and size of a user's saved objects of `kind`.
+fn measure_kind(
+    storage: &EncryptedStorage,
+    user_id: &str,
+    kind: QuotaKind,
+) -> StorageResult<(u64, u64)> {
+    let (count, bytes) = match kind {
+        QuotaKind::Bookmarks => {
+            let bookmarks = BookmarkRepository::new(storage).list_by_owner(user_id)?;
+            (bookmarks.len(), json_bytes(&bookmarks))
+        }
+        QuotaKind::FiatBeneficiaries => {
+            let beneficiaries = FiatBeneficiaryRepository::new(storage).list_by_owner(user_id)?;
+            (beneficiaries.len(), json_bytes(&beneficiaries))
+        }
+        QuotaKind::FiatMandates => {
+            let mandates: Vec<_> = FiatMandateRepository::new(storage)
+                .list_by_owner(user_id)?
+                .into_iter()
+                .filter(|m| {
+                    !matches!(
+                        m.status,
+                        FiatMandateStatus::Revoked | FiatMandateStatus::Failed
+                    )
+                })
+                .collect();
+            (mandates.len(), json_bytes(&mandates))
+        }
+    };
+    Ok((count as u64, bytes))
+}
+
+/// A user's consumption of every quota.
+pub(crate) fn measure(
+    storage: &EncryptedStorage,
+    user_id: &str,
+    quotas: &StorageQuotas,
+) -> StorageResult<UsageResponse> {
+    let mut objects = Vec::with_capacity(QuotaKind::ALL.len());
+    for kind in QuotaKind::ALL {
+        let (count, bytes) = measure_kind(storage, user_id, kind)?;
+        objects.push(ObjectUsage {
+            kind: kind.as_str().to_string(),
+            count,
+            max_count: quotas.max_count(kind),
+            bytes,
+        });
+    }
+    Ok(UsageResponse {
+        total_bytes: objects.iter().map(|o| o.bytes).sum(),
+        max_bytes: quotas.max_bytes,
+        objects,
+    })
+}
+
+/// Reject saving one more object of `kind`, about `new_bytes` in size, if
+/// it would take the user over a quota. Otherwise the new usage is
+/// recorded for the soft quota headers.
+pub(crate) fn check_quota(
+    storage: &EncryptedStorage,
+    user_id: &str,
+    kind: QuotaKind,
+    new_bytes: u64,
+    quotas: &StorageQuotas,
+) -> Result<(), ApiError> {
+    let usage = measure(storage, user_id, quotas).context("Failed to measure storage usage")?;
+    let count = usage
+        .objects
+        .iter()
+        .find(|o| o.kind == kind.as_str())
+        .map_or(0, |o| o.count);
+    let max_count = quotas.max_count(kind);
+    if count >= max_count {
+        return Err(ApiError::unprocessable(format!(
+            "At most {max_count} {} can be saved",
+            kind.label()
+        ))
+        .with_code("storage_quota_exceeded"));
+    }
+    let total_bytes = usage.total_bytes.saturating_add(new_bytes);
+    if total_bytes > quotas.max_bytes {
+        return Err(ApiError::unprocessable(format!(
+            "Saved objects are limited to {} bytes",
+            quotas.max_bytes
+        ))
+        .with_code("storage_quota_exceeded"));
+    }
+
+    soft_quotas::record(QuotaUsage {
+        policy: kind.as_str(),
+        limit: max_count,
+        used: count + 1,
+        resets_at: None,
+        message: format!("{} of {max_count} {} saved", count + 1, kind.label()),
+    });
+    soft_quotas::record(QuotaUsage {
+        policy: "storage_bytes",
+        limit: quotas.max_bytes,
+        used: total_bytes,
+        resets_at: None,
+        message: format!(
+            "{total_bytes} of {} bytes of storage used",
+            quotas.max_bytes
+        ),
+    });
+    Ok(())
+}
+
+/// Storage used by the current user's saved objects, against their quotas.
+#[utoipa::path(
+    get,
+    path = "/v1/users/me/usage",
+    tag = "Users",
+    security(("bearer" = [])),
+    responses(
+        (status = 200, description = "Storage usage", body = UsageResponse),
+        (status = 401, description = "Not authenticated")
+    )
+)]
+pub async fn get_my_usage(
+    Auth(user): Auth,
+    State(state): State<AppState>,
+) -> Result<Json<UsageResponse>, ApiError> {
+    let usage = measure(state.storage(), &user.user_id, &StorageQuotas::from_env())
+        .context("Failed to measure storage usage")?;
+    Ok(Json(usage))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        auth::{AuthenticatedUser, Role},
+        storage::{RecipientType, StoredBookmark},
+    };
+    use axum::http::StatusCode;
+    use chrono::Utc;
+
+    fn bookmark(owner: &str) -> StoredBookmark {
+        StoredBookmark {
+            id: uuid::Uuid::new_v4().to_string(),
+            wallet_id: "wallet-1".to_string(),
+            owner_user_id: owner.to_string(),
+            name: "Rent".to_string(),
+            recipient_type: RecipientType::Address,
+            address: "0x0000000000000000000000000000000000000001".to_string(),
+            email_hash: None,
+            email_display: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn quotas_reject_objects_past_the_count_or_size_limit() {
+        let state = AppState::default();
+        let storage = state.storage();
+        let quotas = StorageQuotas {
+            max_bookmarks: 2,
+            ..StorageQuotas::default()
+        };
+        let repo = BookmarkRepository::new(storage);
+        for _ in 0..2 {
+            check_quota(storage, "user_1", QuotaKind::Bookmarks, 200, &quotas).unwrap();
+            repo.create(&bookmark("user_1")).unwrap();
+        }
+        repo.create(&bookmark("user_2")).unwrap();
+
+        let err = check_quota(storage, "user_1", QuotaKind::Bookmarks, 200, &quotas).unwrap_err();
+        assert_eq!(err.status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(err.code, Some("storage_quota_exceeded"));
+        check_quota(storage, "user_2", QuotaKind::Bookmarks, 200, &quotas).unwrap();
+
+        let tiny = StorageQuotas {
+            max_bytes: 100,
+            ..StorageQuotas::default()
+        };
+        let err = check_quota(storage, "user_2", QuotaKind::FiatMandates, 0, &tiny).unwrap_err();
+        assert_eq!(err.code, Some("storage_quota_exceeded"));
+    }
+
+    #[tokio::test]
+    async fn usage_reports_only_the_callers_objects() {
+        let state = AppState::default();
+        let repo = BookmarkRepository::new(state.storage());
+        repo.create(&bookmark("user_1")).unwrap();
+        repo.create(&bookmark("user_2")).unwrap();
+
+        let Json(usage) = get_my_usage(
+            Auth(AuthenticatedUser {
+                user_id: "user_1".to_string(),
+                role: Role::Client,
+                session_id: None,
+                issuer: "https://test.clerk.dev".into(),
+                expires_at: Utc::now().timestamp() + 3600,
+                tenant_id: None,
+            }),
+            State(state),
+        )
+        .await
+        .unwrap();
+        let bookmarks = &usage.objects[0];
+        assert_eq!(bookmarks.kind, "bookmarks");
+        assert_eq!(bookmarks.count, 1);
+        assert!(bookmarks.bytes > 0);
+        assert_eq!(usage.total_bytes, bookmarks.bytes);
+        assert_eq!(usage.objects[2].count, 0);
+    }
+}
@@ -4,11 +4,16 @@
 
 //! User endpoints.
 
-use axum::Json;
+use axum::{extract::State, Json};
 use serde::Serialize;
 use utoipa::ToSchema;
 
-use crate::auth::{Auth, AuthenticatedUser, Role};
+use crate::{
+    auth::{Auth, AuthenticatedUser, Role},
+    error::ApiError,
+    state::AppState,
+    storage::{SessionLogRepository, SessionRecord},
+};
 
 /// Response for GET /v1/users/me
 #[derive(Debug, Serialize, ToSchema)]
@@ -49,9 +54,57 @@ pub async fn get_current_user(Auth(user): Auth) -> Json<UserMeResponse> {
     Json(user.into())
 }
 
+/// A session in the user's login history.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct UserSessionResponse {
+    #[serde(flatten)]
+    pub session: SessionRecord,
+    /// Whether this is the session making the request.
+    pub current: bool,
+}
+
+/// Response for GET /v1/users/me/sessions
+#[derive(Debug, Serialize, ToSchema)]
+pub struct UserSessionsResponse {
+    /// Newest first.
+    pub sessions: Vec<UserSessionResponse>,
+}
+
+/// List the sessions that accessed the current user's account.
+///
+/// Each entry shows when a session was first and last seen, with the IP
+/// address and user agent of its latest request, so users can spot
+/// unfamiliar access.
+#[utoipa::path(
+    get,
+    path = "/v1/users/me/sessions",
+    tag = "Users",
+    security(("bearer" = [])),
+    responses(
+        (status = 200, description = "Session history", body = UserSessionsResponse),
+        (status = 401, description = "Unauthorized - invalid or missing token"),
+    )
+)]
+pub async fn list_my_sessions(
+    Auth(user): Auth,
+    State(state): State<AppState>,
+) -> Result<Json<UserSessionsResponse>, ApiError> {
+    let log = SessionLogRepository::new(state.storage()).get(&user.user_id)?;
+    let sessions = log
+        .sessions
+        .into_iter()
+        .map(|session| UserSessionResponse {
+            current: user.session_id.is_some() && session.session_id == user.session_id,
+            session,
+        })
+        .collect();
+    Ok(Json(UserSessionsResponse { sessions }))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::storage::SessionObservation;
 
     #[test]
     fn user_me_response_from_authenticated_user() {
@@ -61,6 +114,7 @@ mod tests {
             session_id: Some("sess_abc".to_string()),
             issuer: "test".to_string(),
             expires_at: 0,
+            tenant_id: None,
         };
 
         let response: UserMeResponse = user.into();
@@ -68,4 +122,34 @@ mod tests {
         assert_eq!(response.role, Role::Client);
         assert_eq!(response.session_id, Some("sess_abc".to_string()));
     }
+
+    #[tokio::test]
+    async fn sessions_mark_the_current_one() {
+        let state = AppState::default();
+        let repo = SessionLogRepository::new(state.storage());
+        for sid in ["sess_old", "sess_abc"] {
+            let observation = SessionObservation {
+                session_id: Some(sid.to_string()),
+                issuer: "test".to_string(),
+                ip_address: Some("10.0.0.1".to_string()),
+                user_agent: None,
+            };
+            repo.record("user_123", &observation, chrono::Utc::now())
+                .unwrap();
+        }
+        let user = AuthenticatedUser {
+            user_id: "user_123".to_string(),
+            role: Role::Client,
+            session_id: Some("sess_abc".to_string()),
+            issuer: "test".to_string(),
+            expires_at: 0,
+            tenant_id: None,
+        };
+
+        let Json(response) = list_my_sessions(Auth(user), State(state)).await.unwrap();
+        assert_eq!(response.sessions.len(), 2);
+        let current: Vec<_> = response.sessions.iter().filter(|s| s.current).collect();
+        assert_eq!(current.len(), 1);
+        assert_eq!(current[0].session.session_id.as_deref(), Some("sess_abc"));
+    }
 }
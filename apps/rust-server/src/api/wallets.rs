@@ -4,36 +4,51 @@
 
 //! Wallet management API endpoints.
 //!
-//! These endpoints handle wallet creation, listing, retrieval, and deletion.
-//! All operations require authentication and enforce ownership.
+//! These endpoints handle wallet creation, listing, retrieval, and deletion,
+//! and the owner's send lock. All operations require authentication and
+//! enforce ownership.
 
 use axum::{
     extract::{Path, State},
     http::StatusCode,
     Json,
 };
-use chrono::Utc;
+use chrono::{Duration, Utc};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
 use crate::{
+    api::admin_key_rotation::is_rotating,
     audit_log,
-    auth::Auth,
-    error::ApiError,
+    auth::{Auth, AuthenticatedUser},
+    blockchain::{
+        avax_fuji,
+        smart_account::{SmartAccountClient, SmartAccountConfig},
+    },
+    error::{ApiError, StorageContext},
+    events::DomainEvent,
     providers::{clerk::ClerkError, email},
     state::AppState,
     storage::{
-        AuditEventType, AuditRepository, EmailIndexRepository, OwnershipEnforcer, WalletMetadata,
+        AuditEvent, AuditEventType, AuditRepository, EmailIndexRepository, OwnershipEnforcer,
+        SmartAccountInfo, WalletAccountType, WalletLock, WalletMetadata, WalletPoolRepository,
         WalletRepository, WalletResponse, WalletStatus,
     },
 };
 
+/// Delay between requesting an unlock and sends being allowed again.
+pub const UNLOCK_COOLDOWN_HOURS: i64 = 24;
+
 /// Request to create a new wallet.
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct CreateWalletRequest {
     /// Optional human-readable label for the wallet.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub label: Option<String>,
+    /// Account model: `eoa` (default) or `smart_account`. Smart accounts
+    /// require the server to have a bundler configured.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub account_type: Option<WalletAccountType>,
 }
 
 /// Response after creating a wallet.
@@ -67,6 +82,10 @@ pub struct DeleteWalletResponse {
 ///
 /// Generates a new secp256k1 keypair inside the SGX enclave and stores it
 /// encrypted on disk. Returns the wallet metadata (never the private key).
+///
+/// With `account_type: smart_account` the key becomes the owner of an
+/// ERC-4337 smart account, whose counterfactual address is the wallet's
+/// public address. The account is deployed by its first send.
 #[utoipa::path(
     post,
     path = "/v1/wallets",
@@ -76,7 +95,8 @@ pub struct DeleteWalletResponse {
     responses(
         (status = 201, description = "Wallet created successfully", body = CreateWalletResponse),
         (status = 401, description = "Unauthorized"),
-        (status = 500, description = "Internal server error")
+        (status = 500, description = "Internal server error"),
+        (status = 503, description = "Smart-account wallets are not enabled, or the chain is unavailable")
     )
 )]
 pub async fn create_wallet(
@@ -95,6 +115,18 @@ pub async fn create_wallet(
         return Err(ApiError::conflict("You already have a wallet"));
     }
 
+    let account_type = request.account_type.unwrap_or_default();
+    let smart_account_config = match account_type {
+        WalletAccountType::Eoa => None,
+        WalletAccountType::SmartAccount => {
+            Some(SmartAccountConfig::from_env().ok_or_else(|| {
+                ApiError::service_unavailable(
+                    "Smart-account wallets are not enabled on this server",
+                )
+            })?)
+        }
+    };
+
     // ── Fetch email from Clerk and enforce 1-wallet-per-email ──
     let mut email_lookup_key: Option<String> = None;
     let mut email_sha256_hex: Option<String> = None;
@@ -131,9 +163,29 @@ pub async fn create_wallet(
     // Generate wallet ID
     let wallet_id = uuid::Uuid::new_v4().to_string();
 
-    // Generate secp256k1 keypair (Ethereum/Avalanche compatible)
-    let (private_key_pem, public_address) = generate_secp256k1_keypair()
-        .map_err(|e| ApiError::internal(format!("Key generation failed: {}", e)))?;
+    // Claim a pre-generated key if the pool has one; otherwise generate a
+    // secp256k1 keypair (Ethereum/Avalanche compatible). A claimed key is
+    // removed from the pool, so it is only ever bound to this wallet.
+    let pooled = WalletPoolRepository::new(storage)
+        .take()
+        .unwrap_or_else(|e| {
+            tracing::warn!(error = %e, "Failed to claim pooled wallet key");
+            None
+        });
+    let (private_key_pem, key_address) = match pooled {
+        Some(key) => (key.private_key_pem, key.key_address),
+        None => generate_secp256k1_keypair()
+            .map_err(|e| ApiError::internal(format!("Key generation failed: {}", e)))?,
+    };
+
+    // Smart-account wallets are addressed by the account, not the key.
+    let (public_address, smart_account) = match smart_account_config {
+        Some(config) => {
+            let (address, info) = derive_smart_account(config, &key_address).await?;
+            (address, Some(info))
+        }
+        None => (key_address, None),
+    };
 
     // Create wallet metadata
     let metadata = WalletMetadata {
@@ -145,6 +197,13 @@ pub async fn create_wallet(
         label: request.label,
         email_lookup_key: email_lookup_key.clone(),
         email_sha256: email_sha256_hex.clone(),
+        account_type,
+        smart_account,
+        lock: None,
+        deleted_at: None,
+        purged_at: None,
+        funded_at: None,
+        tenant_id: user.tenant_id.clone(),
     };
 
     // Store wallet
@@ -231,6 +290,10 @@ pub async fn create_wallet(
         "wallet",
         &wallet_id
     );
+    state.events.publish(DomainEvent::WalletCreated {
+        wallet_id: wallet_id.clone(),
+        owner_user_id: user.user_id.clone(),
+    });
 
     let response = CreateWalletResponse {
         wallet: WalletResponse::from(metadata),
@@ -263,7 +326,9 @@ pub async fn list_wallets(
     let wallet_responses: Vec<WalletResponse> = if let Some(db) = tx_db {
         if let Ok(Some(wallet_id)) = db.get_user_wallet(&user.user_id) {
             match repo.get(&wallet_id) {
-                Ok(meta) if meta.status != WalletStatus::Deleted => vec![meta.into()],
+                Ok(meta) if meta.status != WalletStatus::Deleted && meta.is_owned_by(&user) => {
+                    vec![meta.into()]
+                }
                 _ => Vec::new(),
             }
         } else {
@@ -272,7 +337,7 @@ pub async fn list_wallets(
     } else {
         // Fallback: O(N) filesystem scan (no tx_db configured)
         let wallets = repo
-            .list_by_owner(&user.user_id)
+            .list_for_user(&user)
             .map_err(|e| ApiError::internal(format!("Failed to list wallets: {}", e)))?;
         wallets.into_iter().map(Into::into).collect()
     };
@@ -333,8 +398,9 @@ pub async fn get_wallet(
 
 /// Delete (soft-delete) a wallet.
 ///
-/// Marks the wallet as deleted. The private key is retained for potential
-/// recovery but the wallet cannot be used for new transactions.
+/// Marks the wallet as deleted. The wallet cannot be used for new
+/// transactions, but an admin can restore it until the retention period
+/// (`DELETED_WALLET_RETENTION_DAYS`) ends and its key material is purged.
 #[utoipa::path(
     delete,
     path = "/v1/wallets/{wallet_id}",
@@ -412,6 +478,171 @@ pub async fn delete_wallet(
     }))
 }
 
+/// Reject sends from a wallet its owner has locked.
+pub(crate) fn ensure_unlocked(wallet: &WalletMetadata) -> Result<(), ApiError> {
+    if is_rotating(&wallet.wallet_id) {
+        return Err(ApiError::conflict(
+            "The wallet's key is being rotated; retry when it finishes",
+        )
+        .with_code("key_rotation_in_progress"));
+    }
+    if wallet.is_locked(Utc::now()) {
+        return Err(ApiError::forbidden(
+            "Wallet is locked by its owner; unlock it to send",
+        ));
+    }
+    Ok(())
+}
+
+/// Load a wallet for locking or unlocking: owned and not deleted.
+fn lockable_wallet(
+    repo: &WalletRepository<'_>,
+    user: &AuthenticatedUser,
+    wallet_id: &str,
+) -> Result<WalletMetadata, ApiError> {
+    let wallet = repo.get(wallet_id)?;
+    if !wallet.is_owned_by(user) {
+        return Err(ApiError::forbidden("You do not own this wallet").with_code("wallet_not_owned"));
+    }
+    if wallet.status == WalletStatus::Deleted {
+        return Err(ApiError::not_found("Wallet has been deleted").with_code("wallet_deleted"));
+    }
+    Ok(wallet)
+}
+
+/// Lock a wallet against sends.
+///
+/// Takes effect immediately and blocks every way of moving funds out, while
+/// deposits and reads keep working. Locking an already locked wallet
+/// cancels a pending unlock.
+#[utoipa::path(
+    post,
+    path = "/v1/wallets/{wallet_id}/lock",
+    tag = "Wallets",
+    security(("bearer_auth" = [])),
+    params(
+        ("wallet_id" = String, Path, description = "Wallet ID")
+    ),
+    responses(
+        (status = 200, description = "Wallet locked", body = WalletResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - not your wallet"),
+        (status = 404, description = "Wallet not found")
+    )
+)]
+pub async fn lock_wallet(
+    Auth(user): Auth,
+    State(state): State<AppState>,
+    Path(wallet_id): Path<String>,
+) -> Result<Json<WalletResponse>, ApiError> {
+    let storage = state.storage();
+    let repo = WalletRepository::new(storage);
+    let mut wallet = lockable_wallet(&repo, &user, &wallet_id)?;
+
+    let now = Utc::now();
+    let lock = match wallet.lock.take() {
+        Some(lock) if lock.in_force(now) => WalletLock {
+            unlocks_at: None,
+            ..lock
+        },
+        _ => WalletLock {
+            locked_at: now,
+            unlocks_at: None,
+        },
+    };
+    wallet.lock = Some(lock);
+    repo.update(&wallet).context("Failed to lock wallet")?;
+
+    audit_log!(
+        &storage,
+        AuditEventType::WalletLocked,
+        &user,
+        "wallet",
+        &wallet_id
+    );
+
+    Ok(Json(wallet.into()))
+}
+
+/// Request that a locked wallet be unlocked.
+///
+/// Sends stay blocked for a cool-down of [`UNLOCK_COOLDOWN_HOURS`], so a
+/// thief who takes over the session cannot lift the lock and drain the
+/// wallet at once. Locking again during the cool-down cancels it.
+#[utoipa::path(
+    post,
+    path = "/v1/wallets/{wallet_id}/unlock",
+    tag = "Wallets",
+    security(("bearer_auth" = [])),
+    params(
+        ("wallet_id" = String, Path, description = "Wallet ID")
+    ),
+    responses(
+        (status = 200, description = "Unlock scheduled", body = WalletResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - not your wallet"),
+        (status = 404, description = "Wallet not found"),
+        (status = 409, description = "Wallet is not locked")
+    )
+)]
+pub async fn unlock_wallet(
+    Auth(user): Auth,
+    State(state): State<AppState>,
+    Path(wallet_id): Path<String>,
+) -> Result<Json<WalletResponse>, ApiError> {
+    let storage = state.storage();
+    let repo = WalletRepository::new(storage);
+    let mut wallet = lockable_wallet(&repo, &user, &wallet_id)?;
+
+    let now = Utc::now();
+    let lock = wallet
+        .lock
+        .as_mut()
+        .filter(|lock| lock.in_force(now))
+        .ok_or_else(|| ApiError::conflict("Wallet is not locked"))?;
+    // Repeated requests do not push the unlock further out.
+    let unlocks_at = *lock
+        .unlocks_at
+        .get_or_insert(now + Duration::hours(UNLOCK_COOLDOWN_HOURS));
+    repo.update(&wallet)
+        .context("Failed to schedule wallet unlock")?;
+
+    let event = AuditEvent::new(AuditEventType::WalletUnlockRequested)
+        .with_user(&user.user_id)
+        .with_resource("wallet", &wallet_id)
+        .with_details(serde_json::json!({ "unlocks_at": unlocks_at }));
+    let _ = AuditRepository::new(storage).log(&event);
+
+    Ok(Json(wallet.into()))
+}
+
+/// Derive the smart-account address owned by `owner_address` through the
+/// configured factory.
+async fn derive_smart_account(
+    config: SmartAccountConfig,
+    owner_address: &str,
+) -> Result<(String, SmartAccountInfo), ApiError> {
+    let owner = owner_address
+        .parse()
+        .map_err(|e| ApiError::internal(format!("Invalid owner address: {e}")))?;
+    let client = SmartAccountClient::new(config, avax_fuji())
+        .map_err(|e| ApiError::service_unavailable(format!("Failed to connect: {e}")))?;
+    let account = client.account_address(owner).await.map_err(|e| {
+        ApiError::service_unavailable(format!("Failed to derive smart account: {e}"))
+    })?;
+    let config = client.config();
+    Ok((
+        alloy::hex::encode_prefixed(account),
+        SmartAccountInfo {
+            owner_address: owner_address.to_string(),
+            entry_point: alloy::hex::encode_prefixed(config.entry_point),
+            factory: alloy::hex::encode_prefixed(config.factory),
+            deployed: false,
+            deployed_at: None,
+        },
+    ))
+}
+
 /// Generate a secp256k1 keypair and derive Ethereum/Avalanche address.
 ///
 /// Ethereum addresses are derived by:
@@ -520,6 +751,13 @@ mod tests {
             label: Some("My Wallet".to_string()),
             email_lookup_key: None,
             email_sha256: None,
+            account_type: Default::default(),
+            smart_account: None,
+            lock: None,
+            deleted_at: None,
+            purged_at: None,
+            funded_at: None,
+            tenant_id: None,
         };
 
         let response: WalletResponse = metadata.into();
@@ -527,4 +765,85 @@ mod tests {
         assert_eq!(response.public_address, "0xabc");
         assert_eq!(response.label, Some("My Wallet".to_string()));
     }
+
+    #[tokio::test]
+    async fn lock_blocks_sends_until_the_unlock_cool_down_passes() {
+        use crate::{
+            api::transactions::sending_wallet,
+            auth::{AuthenticatedUser, Role},
+        };
+
+        let state = AppState::default();
+        let repo = WalletRepository::new(state.storage());
+        repo.create(
+            &WalletMetadata {
+                wallet_id: "w1".to_string(),
+                owner_user_id: "user-a".to_string(),
+                public_address: "0xabc".to_string(),
+                created_at: Utc::now(),
+                status: WalletStatus::Active,
+                label: None,
+                email_lookup_key: None,
+                email_sha256: None,
+                account_type: Default::default(),
+                smart_account: None,
+                lock: None,
+                deleted_at: None,
+                purged_at: None,
+                funded_at: None,
+                tenant_id: None,
+            },
+            b"test_key",
+        )
+        .unwrap();
+        let auth = || {
+            Auth(AuthenticatedUser {
+                user_id: "user-a".to_string(),
+                role: Role::Client,
+                session_id: None,
+                issuer: "https://test.clerk.dev".to_string(),
+                expires_at: Utc::now().timestamp() + 3600,
+                tenant_id: None,
+            })
+        };
+
+        let err = unlock_wallet(auth(), State(state.clone()), Path("w1".to_string()))
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, StatusCode::CONFLICT);
+
+        let Json(locked) = lock_wallet(auth(), State(state.clone()), Path("w1".to_string()))
+            .await
+            .unwrap();
+        assert!(locked.lock.is_some());
+        let err = sending_wallet(state.storage(), &auth().0, "w1").unwrap_err();
+        assert_eq!(err.status, StatusCode::FORBIDDEN);
+
+        // Unlocking only starts the cool-down; the wallet stays locked.
+        let Json(pending) = unlock_wallet(auth(), State(state.clone()), Path("w1".to_string()))
+            .await
+            .unwrap();
+        let unlocks_at = pending.lock.unwrap().unlocks_at.unwrap();
+        assert!(unlocks_at > Utc::now() + Duration::hours(UNLOCK_COOLDOWN_HOURS - 1));
+        assert!(sending_wallet(state.storage(), &auth().0, "w1").is_err());
+
+        // Locking again cancels the pending unlock.
+        let Json(relocked) = lock_wallet(auth(), State(state.clone()), Path("w1".to_string()))
+            .await
+            .unwrap();
+        assert_eq!(relocked.lock.unwrap().unlocks_at, None);
+
+        // Once the cool-down has passed, sends are allowed again.
+        let mut wallet = repo.get("w1").unwrap();
+        wallet.lock = Some(WalletLock {
+            locked_at: Utc::now() - Duration::hours(48),
+            unlocks_at: Some(Utc::now() - Duration::hours(1)),
+        });
+        repo.update(&wallet).unwrap();
+        assert!(sending_wallet(state.storage(), &auth().0, "w1").is_ok());
+        let Json(response) = get_wallet(auth(), State(state), Path("w1".to_string()))
+            .await
+            .unwrap();
+        assert!(response.lock.is_none());
+    }
 }
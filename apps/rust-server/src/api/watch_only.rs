@@ -0,0 +1,451 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Watch-only external addresses.
+//!
+//! Users register addresses they control outside the enclave so the app can
+//! show one portfolio. The indexer tracks ERC-20 transfers for a watched
+//! address from the moment it is registered; balances are read live from
+//! the chain. No key is held, so send and estimate requests naming a
+//! watch-only ID are rejected.
+
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::{
+    api::{
+        balance::{fetch_address_balance, BalanceQuery},
+        transactions::{list_address_transactions, TransactionListQuery, TransactionListResponse},
+    },
+    auth::{Auth, AuthenticatedUser},
+    blockchain::{address_key, resolve_network, WalletBalanceResponse},
+    error::{ApiError, StorageContext},
+    models::WalletAddress,
+    state::AppState,
+    storage::{
+        repository::watch_only::{is_tracking_id, tracking_id},
+        AuditEvent, AuditEventType, AuditRepository, EncryptedStorage, StoredWatchOnlyAddress,
+        WatchOnlyRepository,
+    },
+};
+
+/// Watch-only entries a single user may register.
+const MAX_WATCH_ONLY_PER_USER: usize = 20;
+
+/// Longest accepted label.
+const MAX_LABEL_LEN: usize = 64;
+
+/// Request body for registering a watch-only address.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct CreateWatchOnlyRequest {
+    /// External address to watch (`0x` + 40 hex).
+    pub address: String,
+    /// Optional display label.
+    #[serde(default)]
+    pub label: Option<String>,
+}
+
+/// Watch-only entry returned to clients.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct WatchOnlyResponse {
+    pub watch_id: String,
+    pub address: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    pub created_at: String,
+    /// Always `true`: the server holds no key for this address.
+    pub read_only: bool,
+}
+
+impl From<StoredWatchOnlyAddress> for WatchOnlyResponse {
+    fn from(entry: StoredWatchOnlyAddress) -> Self {
+        Self {
+            watch_id: entry.watch_id,
+            address: entry.address,
+            label: entry.label,
+            created_at: entry.created_at.to_rfc3339(),
+            read_only: true,
+        }
+    }
+}
+
+/// Balance of a watch-only address.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct WatchOnlyBalanceResponse {
+    pub watch_id: String,
+    /// Always `true`: the balance cannot be spent through this server.
+    pub read_only: bool,
+    #[serde(flatten)]
+    pub balance: WalletBalanceResponse,
+}
+
+/// Load an entry and check that `user` registered it.
+fn owned_entry(
+    storage: &EncryptedStorage,
+    user: &AuthenticatedUser,
+    watch_id: &str,
+) -> Result<StoredWatchOnlyAddress, ApiError> {
+    let entry = WatchOnlyRepository::new(storage).get(watch_id)?;
+    if entry.owner_user_id != user.user_id {
+        return Err(ApiError::forbidden(
+            "You do not own this watch-only address",
+        ));
+    }
+    Ok(entry)
+}
+
+/// Register an external address as watch-only.
+///
+/// The indexer starts tracking its transfers from the next block range it
+/// scans; earlier history is not backfilled.
+#[utoipa::path(
+    post,
+    path = "/v1/watch-only",
+    tag = "Watch-Only",
+    request_body = CreateWatchOnlyRequest,
+    security(("bearer" = [])),
+    responses(
+        (status = 201, description = "Address registered", body = WatchOnlyResponse),
+        (status = 400, description = "Invalid address or label"),
+        (status = 401, description = "Unauthorized"),
+        (status = 409, description = "Already watched, or a custodial wallet address"),
+        (status = 422, description = "Watch-only limit reached")
+    )
+)]
+pub async fn create_watch_only(
+    Auth(user): Auth,
+    State(state): State<AppState>,
+    Json(request): Json<CreateWatchOnlyRequest>,
+) -> Result<(StatusCode, Json<WatchOnlyResponse>), ApiError> {
+    WalletAddress::from(request.address.clone())
+        .validate_eth_address()
+        .map_err(ApiError::bad_request)?;
+    let address = address_key(&request.address);
+
+    let label = request
+        .label
+        .map(|l| l.trim().to_string())
+        .filter(|l| !l.is_empty());
+    if label
+        .as_ref()
+        .is_some_and(|l| l.chars().count() > MAX_LABEL_LEN)
+    {
+        return Err(ApiError::bad_request(format!(
+            "label must be at most {MAX_LABEL_LEN} characters"
+        )));
+    }
+
+    let storage = state.storage();
+    let repo = WatchOnlyRepository::new(storage);
+    let existing = repo
+        .list_by_owner(&user.user_id)
+        .map_err(|e| ApiError::internal(format!("Failed to list watch-only addresses: {e}")))?;
+    if existing.iter().any(|entry| entry.address == address) {
+        return Err(ApiError::conflict("Address is already watched"));
+    }
+    if existing.len() >= MAX_WATCH_ONLY_PER_USER {
+        return Err(ApiError::unprocessable(format!(
+            "At most {MAX_WATCH_ONLY_PER_USER} watch-only addresses are allowed"
+        )));
+    }
+
+    let tx_db = state
+        .tx_db
+        .as_ref()
+        .expect("transaction database must be configured");
+    let mapped = tx_db
+        .get_wallet_id_for_address(&address)
+        .map_err(|e| ApiError::internal(format!("Failed to look up address: {e}")))?;
+    if mapped.as_deref().is_some_and(|id| !is_tracking_id(id)) {
+        return Err(ApiError::conflict(
+            "Address belongs to a wallet managed by this server",
+        ));
+    }
+
+    let entry = StoredWatchOnlyAddress {
+        watch_id: uuid::Uuid::new_v4().to_string(),
+        owner_user_id: user.user_id.clone(),
+        address: address.clone(),
+        label,
+        created_at: Utc::now(),
+    };
+    repo.create(&entry)
+        .map_err(|e| ApiError::internal(format!("Failed to store watch-only address: {e}")))?;
+    tx_db
+        .register_address(&address, &tracking_id(&address))
+        .map_err(|e| ApiError::internal(format!("Failed to register address: {e}")))?;
+
+    let event = AuditEvent::new(AuditEventType::WatchOnlyAdded)
+        .with_user(&user.user_id)
+        .with_resource("watch_only", &entry.watch_id)
+        .with_details(serde_json::json!({ "address": address }));
+    let _ = AuditRepository::new(storage).log(&event);
+
+    Ok((StatusCode::CREATED, Json(entry.into())))
+}
+
+/// List the caller's watch-only addresses.
+#[utoipa::path(
+    get,
+    path = "/v1/watch-only",
+    tag = "Watch-Only",
+    security(("bearer" = [])),
+    responses(
+        (status = 200, description = "Watch-only addresses", body = [WatchOnlyResponse]),
+        (status = 401, description = "Unauthorized")
+    )
+)]
+pub async fn list_watch_only(
+    Auth(user): Auth,
+    State(state): State<AppState>,
+) -> Result<Json<Vec<WatchOnlyResponse>>, ApiError> {
+    let entries = WatchOnlyRepository::new(state.storage())
+        .list_by_owner(&user.user_id)
+        .map_err(|e| ApiError::internal(format!("Failed to list watch-only addresses: {e}")))?;
+    Ok(Json(entries.into_iter().map(Into::into).collect()))
+}
+
+/// Stop watching an address.
+///
+/// Indexed history is kept while another user still watches the address.
+#[utoipa::path(
+    delete,
+    path = "/v1/watch-only/{watch_id}",
+    tag = "Watch-Only",
+    params(("watch_id" = String, Path, description = "Watch-only entry ID")),
+    security(("bearer" = [])),
+    responses(
+        (status = 204, description = "Entry removed"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - not your entry"),
+        (status = 404, description = "Entry not found")
+    )
+)]
+pub async fn delete_watch_only(
+    Auth(user): Auth,
+    State(state): State<AppState>,
+    Path(watch_id): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    let storage = state.storage();
+    let entry = owned_entry(storage, &user, &watch_id)?;
+    let repo = WatchOnlyRepository::new(storage);
+    repo.delete(&watch_id)
+        .context("Failed to delete watch-only address")?;
+
+    let still_watched = repo
+        .is_watched(&entry.address)
+        .map_err(|e| ApiError::internal(format!("Failed to list watch-only addresses: {e}")))?;
+    if !still_watched {
+        if let Some(tx_db) = &state.tx_db {
+            // Only drop the mapping if it is ours; never a custodial wallet's.
+            if tx_db
+                .get_wallet_id_for_address(&entry.address)
+                .ok()
+                .flatten()
+                .is_some_and(|id| is_tracking_id(&id))
+            {
+                if let Err(e) = tx_db.remove_wallet_address(&entry.address) {
+                    tracing::warn!(error = %e, "Failed to unregister watch-only address");
+                }
+            }
+        }
+    }
+
+    let event = AuditEvent::new(AuditEventType::WatchOnlyRemoved)
+        .with_user(&user.user_id)
+        .with_resource("watch_only", &watch_id)
+        .with_details(serde_json::json!({ "address": entry.address }));
+    let _ = AuditRepository::new(storage).log(&event);
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Read-only balance of a watch-only address.
+#[utoipa::path(
+    get,
+    path = "/v1/watch-only/{watch_id}/balance",
+    tag = "Watch-Only",
+    params(
+        ("watch_id" = String, Path, description = "Watch-only entry ID"),
+        BalanceQuery
+    ),
+    security(("bearer" = [])),
+    responses(
+        (status = 200, description = "Balance retrieved", body = WatchOnlyBalanceResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - not your entry"),
+        (status = 404, description = "Entry not found"),
+        (status = 503, description = "Blockchain network unavailable")
+    )
+)]
+pub async fn get_watch_only_balance(
+    Auth(user): Auth,
+    State(state): State<AppState>,
+    Path(watch_id): Path<String>,
+    Query(query): Query<BalanceQuery>,
+) -> Result<Json<WatchOnlyBalanceResponse>, ApiError> {
+    let entry = owned_entry(state.storage(), &user, &watch_id)?;
+    let network = resolve_network(query.network.as_deref()).map_err(ApiError::bad_request)?;
+    let balance =
+        fetch_address_balance(&state, &network, &entry.address, query.tokens.as_deref()).await?;
+    Ok(Json(WatchOnlyBalanceResponse {
+        watch_id: entry.watch_id,
+        read_only: true,
+        balance,
+    }))
+}
+
+/// Indexed transfers of a watch-only address.
+#[utoipa::path(
+    get,
+    path = "/v1/watch-only/{watch_id}/transactions",
+    tag = "Watch-Only",
+    params(
+        ("watch_id" = String, Path, description = "Watch-only entry ID"),
+        TransactionListQuery
+    ),
+    security(("bearer" = [])),
+    responses(
+        (status = 200, description = "Transaction list", body = TransactionListResponse),
+        (status = 400, description = "Invalid network or cursor"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Forbidden - not your entry"),
+        (status = 404, description = "Entry not found")
+    )
+)]
+pub async fn list_watch_only_transactions(
+    Auth(user): Auth,
+    State(state): State<AppState>,
+    Path(watch_id): Path<String>,
+    Query(query): Query<TransactionListQuery>,
+) -> Result<Json<TransactionListResponse>, ApiError> {
+    let entry = owned_entry(state.storage(), &user, &watch_id)?;
+    list_address_transactions(&state, &entry.address, &query)
+        .await
+        .map(Json)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::Role;
+    use crate::storage::{WalletMetadata, WalletRepository, WalletStatus};
+
+    const WATCHED: &str = "0xAbCdEf0000000000000000000000000000000001";
+
+    fn user(user_id: &str) -> Auth {
+        Auth(AuthenticatedUser {
+            user_id: user_id.to_string(),
+            role: Role::Client,
+            session_id: None,
+            issuer: "https://test.clerk.dev".into(),
+            expires_at: Utc::now().timestamp() + 3600,
+            tenant_id: None,
+        })
+    }
+
+    fn body(address: &str) -> Json<CreateWatchOnlyRequest> {
+        Json(CreateWatchOnlyRequest {
+            address: address.to_string(),
+            label: Some("  Cold storage ".to_string()),
+        })
+    }
+
+    #[tokio::test]
+    async fn shared_address_stays_tracked_until_last_watcher_leaves() {
+        let state = AppState::default();
+        let tx_db = state.tx_db.clone().unwrap();
+
+        let (status, Json(first)) =
+            create_watch_only(user("user-1"), State(state.clone()), body(WATCHED))
+                .await
+                .unwrap();
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(first.address, WATCHED.to_lowercase());
+        assert_eq!(first.label.as_deref(), Some("Cold storage"));
+        assert!(first.read_only);
+
+        let duplicate = create_watch_only(user("user-1"), State(state.clone()), body(WATCHED))
+            .await
+            .unwrap_err();
+        assert_eq!(duplicate.status, StatusCode::CONFLICT);
+
+        let (_, Json(second)) =
+            create_watch_only(user("user-2"), State(state.clone()), body(WATCHED))
+                .await
+                .unwrap();
+
+        let forbidden = delete_watch_only(
+            user("user-2"),
+            State(state.clone()),
+            Path(first.watch_id.clone()),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(forbidden.status, StatusCode::FORBIDDEN);
+
+        delete_watch_only(user("user-1"), State(state.clone()), Path(first.watch_id))
+            .await
+            .unwrap();
+        assert_eq!(
+            tx_db.get_wallet_id_for_address(WATCHED).unwrap(),
+            Some(tracking_id(WATCHED))
+        );
+
+        delete_watch_only(user("user-2"), State(state.clone()), Path(second.watch_id))
+            .await
+            .unwrap();
+        assert_eq!(tx_db.get_wallet_id_for_address(WATCHED).unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn custodial_addresses_cannot_be_watched() {
+        let state = AppState::default();
+        let custodial = "0x0000000000000000000000000000000000000002";
+        WalletRepository::new(state.storage())
+            .create(
+                &WalletMetadata {
+                    wallet_id: "wallet-1".to_string(),
+                    owner_user_id: "user-1".to_string(),
+                    public_address: custodial.to_string(),
+                    created_at: Utc::now(),
+                    status: WalletStatus::Active,
+                    label: None,
+                    email_lookup_key: None,
+                    email_sha256: None,
+                    account_type: Default::default(),
+                    smart_account: None,
+                    lock: None,
+                    deleted_at: None,
+                    purged_at: None,
+                    funded_at: None,
+                    tenant_id: None,
+                },
+                b"test_key",
+            )
+            .unwrap();
+        state
+            .tx_db
+            .as_ref()
+            .unwrap()
+            .register_address(custodial, "wallet-1")
+            .unwrap();
+
+        let err = create_watch_only(user("user-2"), State(state.clone()), body(custodial))
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, StatusCode::CONFLICT);
+
+        let err = create_watch_only(user("user-2"), State(state), body("0x1234"))
+            .await
+            .unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+    }
+}
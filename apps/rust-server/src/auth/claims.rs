@@ -8,6 +8,7 @@ use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
 use super::roles::Role;
+use super::tenant::{self, OrgClaim};
 
 /// Claims extracted from a Clerk JWT.
 ///
@@ -56,6 +57,14 @@ pub struct ClerkClaims {
     /// Organization memberships (if using Clerk organizations)
     #[serde(default)]
     pub org_memberships: Option<Vec<OrgMembership>>,
+
+    /// Active organization of the session (Clerk v1 session tokens)
+    #[serde(default)]
+    pub org_id: Option<String>,
+
+    /// Active organization of the session (Clerk v2 session tokens)
+    #[serde(default)]
+    pub o: Option<OrgClaim>,
 }
 
 /// User metadata from Clerk.
@@ -113,10 +122,18 @@ pub struct AuthenticatedUser {
     /// Token expiration (Unix timestamp, used for validation, not serialized)
     #[serde(skip)]
     pub expires_at: i64,
+
+    /// Tenant (Clerk organization) of the session; `None` for the default
+    /// tenant.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tenant_id: Option<String>,
 }
 
 impl AuthenticatedUser {
     /// Create from Clerk claims.
+    ///
+    /// A malformed organization ID yields the default tenant here; token
+    /// validation rejects such tokens before this is called.
     pub fn from_claims(claims: ClerkClaims) -> Self {
         // Extract role from metadata or default to Client
         let role = claims
@@ -125,6 +142,9 @@ impl AuthenticatedUser {
             .and_then(|m| m.role.as_ref())
             .and_then(|r| Role::from_str(r))
             .unwrap_or(Role::Client);
+        let tenant_id = tenant::tenant_from_claims(claims.org_id.as_deref(), claims.o.as_ref())
+            .ok()
+            .flatten();
 
         Self {
             user_id: claims.sub,
@@ -132,6 +152,7 @@ impl AuthenticatedUser {
             session_id: claims.sid,
             issuer: claims.iss,
             expires_at: claims.exp,
+            tenant_id,
         }
     }
 
@@ -146,6 +167,13 @@ impl AuthenticatedUser {
     pub fn is_admin(&self) -> bool {
         self.role == Role::Admin
     }
+
+    /// Whether this user's admin views include records of `tenant_id`.
+    ///
+    /// Admins without a tenant operate the deployment and see every tenant.
+    pub fn sees_tenant(&self, tenant_id: Option<&str>) -> bool {
+        self.tenant_id.is_none() || self.tenant_id.as_deref() == tenant_id
+    }
 }
 
 #[cfg(test)]
@@ -167,6 +195,8 @@ mod tests {
                 extra: Default::default(),
             }),
             org_memberships: None,
+            org_id: None,
+            o: None,
         }
     }
 
@@ -202,4 +232,18 @@ mod tests {
         assert!(user.has_role(Role::Client));
         assert!(user.has_role(Role::Support));
     }
+
+    #[test]
+    fn from_claims_extracts_tenant() {
+        let mut claims = sample_claims();
+        claims.org_id = Some("org_acme".to_string());
+        let user = AuthenticatedUser::from_claims(claims);
+        assert_eq!(user.tenant_id.as_deref(), Some("org_acme"));
+        assert!(user.sees_tenant(Some("org_acme")));
+        assert!(!user.sees_tenant(None));
+
+        let operator = AuthenticatedUser::from_claims(sample_claims());
+        assert!(operator.tenant_id.is_none());
+        assert!(operator.sees_tenant(Some("org_acme")));
+    }
 }
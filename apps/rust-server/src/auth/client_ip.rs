@@ -0,0 +1,225 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! # Client IP Resolution
+//!
+//! The enclave normally sits behind the nginx proxy in `apps/proxy`, so the
+//! TCP peer of every request is the proxy. The client's address is taken
+//! from `X-Forwarded-For`, but only hops added by a proxy listed in
+//! `TRUSTED_PROXIES` are believed: the header is walked from the right and
+//! the first address not in the list is the client. Anything to its left
+//! was written by the client and is ignored. With no trusted proxy, the
+//! header is ignored and the peer address is used.
+
+use std::env;
+use std::net::{IpAddr, SocketAddr};
+
+use axum::{
+    extract::ConnectInfo,
+    http::{request::Parts, HeaderMap},
+};
+
+use crate::state::AppState;
+
+/// Header appended to by each proxy hop.
+pub const FORWARDED_FOR_HEADER: &str = "x-forwarded-for";
+
+/// An address range, as a network address and prefix length.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct IpRange {
+    network: IpAddr,
+    prefix: u8,
+}
+
+impl IpRange {
+    /// `<ip>` or `<ip>/<prefix>`.
+    fn parse(value: &str) -> Option<Self> {
+        let (ip, prefix) = match value.split_once('/') {
+            Some((ip, prefix)) => (ip.trim(), Some(prefix.trim().parse::<u8>().ok()?)),
+            None => (value, None),
+        };
+        let network = ip.parse::<IpAddr>().ok()?.to_canonical();
+        let max = if network.is_ipv4() { 32 } else { 128 };
+        let prefix = prefix.unwrap_or(max);
+        (prefix <= max).then_some(Self { network, prefix })
+    }
+
+    fn contains(&self, ip: IpAddr) -> bool {
+        match (self.network, ip.to_canonical()) {
+            (IpAddr::V4(network), IpAddr::V4(ip)) => {
+                let mask = u32::MAX
+                    .checked_shl(32 - u32::from(self.prefix))
+                    .unwrap_or(0);
+                u32::from(network) & mask == u32::from(ip) & mask
+            }
+            (IpAddr::V6(network), IpAddr::V6(ip)) => {
+                let mask = u128::MAX
+                    .checked_shl(128 - u32::from(self.prefix))
+                    .unwrap_or(0);
+                u128::from(network) & mask == u128::from(ip) & mask
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Proxies whose `X-Forwarded-For` entries are trusted.
+#[derive(Debug, Default)]
+pub struct TrustedProxies {
+    ranges: Vec<IpRange>,
+}
+
+impl TrustedProxies {
+    /// Proxies from `TRUSTED_PROXIES`; malformed entries are skipped with a
+    /// warning.
+    pub fn from_env() -> Self {
+        env::var("TRUSTED_PROXIES")
+            .map(|value| Self::parse(&value))
+            .unwrap_or_default()
+    }
+
+    /// Proxies from comma-separated IP addresses or CIDR ranges.
+    pub fn parse(value: &str) -> Self {
+        let mut ranges = Vec::new();
+        for entry in value.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            match IpRange::parse(entry) {
+                Some(range) => ranges.push(range),
+                None => tracing::warn!(entry, "Ignoring malformed TRUSTED_PROXIES entry"),
+            }
+        }
+        Self { ranges }
+    }
+
+    /// Whether no proxy is trusted.
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Number of configured entries.
+    pub fn len(&self) -> usize {
+        self.ranges.len()
+    }
+
+    /// Whether `ip` is a trusted proxy.
+    pub fn contains(&self, ip: IpAddr) -> bool {
+        self.ranges.iter().any(|range| range.contains(ip))
+    }
+
+    /// The client address of a request from `peer` with `headers`.
+    ///
+    /// Returns `peer` unless it is a trusted proxy. A malformed hop stops the
+    /// walk at the last address known to be good.
+    pub fn client_ip(&self, peer: IpAddr, headers: &HeaderMap) -> IpAddr {
+        let mut client = peer.to_canonical();
+        if !self.contains(client) {
+            return client;
+        }
+        let hops: Vec<&str> = headers
+            .get_all(FORWARDED_FOR_HEADER)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .map(str::trim)
+            .collect();
+        for hop in hops.into_iter().rev() {
+            let Ok(ip) = hop.parse::<IpAddr>() else {
+                break;
+            };
+            client = ip.to_canonical();
+            if !self.contains(client) {
+                break;
+            }
+        }
+        client
+    }
+}
+
+/// The client address of a request, when the peer address is known.
+pub fn client_ip(parts: &Parts, state: &AppState) -> Option<String> {
+    let ConnectInfo(peer) = parts.extensions.get::<ConnectInfo<SocketAddr>>()?;
+    Some(
+        state
+            .trusted_proxies
+            .client_ip(peer.ip(), &parts.headers)
+            .to_string(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    fn ip(value: &str) -> IpAddr {
+        value.parse().unwrap()
+    }
+
+    fn forwarded(values: &[&str]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for value in values {
+            headers.append(FORWARDED_FOR_HEADER, HeaderValue::from_str(value).unwrap());
+        }
+        headers
+    }
+
+    #[test]
+    fn parses_addresses_and_ranges() {
+        let proxies = TrustedProxies::parse("127.0.0.1, 10.0.0.0/8,fd00::/8, nope, 1.2.3.4/33");
+        assert_eq!(proxies.len(), 3);
+        assert!(proxies.contains(ip("127.0.0.1")));
+        assert!(!proxies.contains(ip("127.0.0.2")));
+        assert!(proxies.contains(ip("10.20.30.40")));
+        assert!(!proxies.contains(ip("11.0.0.1")));
+        assert!(proxies.contains(ip("fd12::1")));
+        assert!(proxies.contains(ip("::ffff:10.1.2.3")));
+        assert!(TrustedProxies::parse("0.0.0.0/0").contains(ip("203.0.113.9")));
+    }
+
+    #[test]
+    fn ignores_the_header_from_untrusted_peers() {
+        let proxies = TrustedProxies::parse("127.0.0.1");
+        let headers = forwarded(&["198.51.100.7"]);
+
+        assert_eq!(
+            proxies.client_ip(ip("203.0.113.9"), &headers),
+            ip("203.0.113.9")
+        );
+        assert_eq!(
+            TrustedProxies::default().client_ip(ip("127.0.0.1"), &headers),
+            ip("127.0.0.1")
+        );
+    }
+
+    #[test]
+    fn takes_the_first_untrusted_hop_from_the_right() {
+        let proxies = TrustedProxies::parse("127.0.0.1,10.0.0.0/8");
+
+        // The client prepended a spoofed address; nginx appended the real one.
+        let headers = forwarded(&["1.1.1.1, 198.51.100.7"]);
+        assert_eq!(
+            proxies.client_ip(ip("127.0.0.1"), &headers),
+            ip("198.51.100.7")
+        );
+
+        // Chained proxies, across repeated header lines.
+        let headers = forwarded(&["1.1.1.1, 198.51.100.7", "10.0.0.5"]);
+        assert_eq!(
+            proxies.client_ip(ip("127.0.0.1"), &headers),
+            ip("198.51.100.7")
+        );
+    }
+
+    #[test]
+    fn stops_at_malformed_or_missing_hops() {
+        let proxies = TrustedProxies::parse("127.0.0.1,10.0.0.0/8");
+
+        let headers = forwarded(&["198.51.100.7, garbage, 10.0.0.5"]);
+        assert_eq!(proxies.client_ip(ip("127.0.0.1"), &headers), ip("10.0.0.5"));
+
+        assert_eq!(
+            proxies.client_ip(ip("127.0.0.1"), &HeaderMap::new()),
+            ip("127.0.0.1")
+        );
+    }
+}
@@ -38,6 +38,8 @@ pub enum AuthError {
     JwksFetchError(String),
     /// No matching key in JWKS
     NoMatchingKey,
+    /// API key is unknown, expired or revoked
+    InvalidApiKey,
     /// Internal error
     InternalError(String),
     /// Insufficient permissions
@@ -64,6 +66,7 @@ impl AuthError {
             AuthError::TokenNotYetValid => "token_not_yet_valid",
             AuthError::JwksFetchError(_) => "jwks_fetch_error",
             AuthError::NoMatchingKey => "no_matching_key",
+            AuthError::InvalidApiKey => "invalid_api_key",
             AuthError::InternalError(_) => "internal_error",
             AuthError::InsufficientPermissions => "insufficient_permissions",
         }
@@ -80,7 +83,8 @@ impl AuthError {
             | AuthError::InvalidIssuer
             | AuthError::InvalidAudience
             | AuthError::TokenNotYetValid
-            | AuthError::NoMatchingKey => StatusCode::UNAUTHORIZED,
+            | AuthError::NoMatchingKey
+            | AuthError::InvalidApiKey => StatusCode::UNAUTHORIZED,
             AuthError::InsufficientPermissions => StatusCode::FORBIDDEN,
             AuthError::JwksFetchError(_) | AuthError::InternalError(_) => {
                 StatusCode::INTERNAL_SERVER_ERROR
@@ -107,6 +111,7 @@ impl std::fmt::Display for AuthError {
             AuthError::TokenNotYetValid => write!(f, "Token is not yet valid"),
             AuthError::JwksFetchError(msg) => write!(f, "Failed to fetch JWKS: {msg}"),
             AuthError::NoMatchingKey => write!(f, "No matching key found in JWKS"),
+            AuthError::InvalidApiKey => write!(f, "API key is invalid, expired or revoked"),
             AuthError::InternalError(msg) => write!(f, "Internal authentication error: {msg}"),
             AuthError::InsufficientPermissions => {
                 write!(f, "Insufficient permissions for this operation")
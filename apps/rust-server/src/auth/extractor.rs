@@ -12,19 +12,39 @@
 //! }
 //! ```
 
+use std::sync::{Arc, OnceLock};
+
 use axum::{
     extract::FromRequestParts,
-    http::{header::AUTHORIZATION, request::Parts},
+    http::{
+        header::{AUTHORIZATION, USER_AGENT},
+        request::Parts,
+        Method,
+    },
 };
 use jsonwebtoken::{decode, decode_header, Validation};
 use serde::Deserialize;
 
+use super::client_ip::client_ip;
+use super::tenant::{tenant_from_claims, OrgClaim};
 use super::{AuthError, AuthenticatedUser, Role};
 use crate::state::AppState;
+use crate::storage::{
+    AdminBootstrapRepository, ApiKeyRepository, SessionLogRepository, SessionObservation,
+};
 
 /// Clock skew tolerance (60 seconds).
 const CLOCK_SKEW_LEEWAY: u64 = 60;
 
+/// Longest `User-Agent` kept in a user's session history.
+const MAX_USER_AGENT_LEN: usize = 256;
+
+/// Header carrying a machine-to-machine API key.
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+/// Issuer recorded for users authenticated with an API key.
+pub const API_KEY_ISSUER: &str = "api_key";
+
 /// Minimal JWT claims for decoding Clerk tokens.
 #[derive(Debug, Deserialize)]
 struct JwtClaims {
@@ -57,6 +77,12 @@ struct JwtClaims {
     /// Optional top-level role claim used by custom templates.
     #[serde(default)]
     role: Option<String>,
+    /// Active organization (Clerk v1 session tokens).
+    #[serde(default)]
+    org_id: Option<String>,
+    /// Active organization (Clerk v2 session tokens).
+    #[serde(default)]
+    o: Option<OrgClaim>,
 }
 
 /// Clerk public metadata structure.
@@ -69,8 +95,9 @@ struct PublicMetadata {
 
 /// Extractor for authenticated users.
 ///
-/// This extractor validates the JWT from the Authorization header
-/// and provides the authenticated user information.
+/// This extractor validates the JWT from the Authorization header, or the
+/// API key from the [`API_KEY_HEADER`] header, and provides the
+/// authenticated user information.
 ///
 /// ## Authentication Modes
 ///
@@ -90,6 +117,25 @@ struct PublicMetadata {
 /// ```
 pub struct Auth(pub AuthenticatedUser);
 
+/// Records which user a request was authenticated as, for layers that shape
+/// the response after the handler has run.
+///
+/// A layer inserts an empty cell into the request extensions; `Auth` fills
+/// it in. It stays empty for requests that never authenticate.
+#[derive(Clone, Default)]
+pub struct CallerCell(Arc<OnceLock<AuthenticatedUser>>);
+
+impl CallerCell {
+    /// The authenticated caller, if any.
+    pub fn get(&self) -> Option<&AuthenticatedUser> {
+        self.0.get()
+    }
+
+    fn set(&self, user: &AuthenticatedUser) {
+        let _ = self.0.set(user.clone());
+    }
+}
+
 impl FromRequestParts<AppState> for Auth {
     type Rejection = AuthError;
 
@@ -99,6 +145,19 @@ impl FromRequestParts<AppState> for Auth {
     ) -> Result<Self, Self::Rejection> {
         // First check if middleware already set the user
         if let Some(user) = parts.extensions.get::<AuthenticatedUser>().cloned() {
+            if let Some(cell) = parts.extensions.get::<CallerCell>() {
+                cell.set(&user);
+            }
+            return Ok(Auth(user));
+        }
+
+        // Machine clients send an API key instead of a JWT
+        if let Some(key) = parts.headers.get(API_KEY_HEADER) {
+            let key = key.to_str().map_err(|_| AuthError::InvalidApiKey)?;
+            let user = verify_api_key(key, &parts.method, parts.uri.path(), state)?;
+            if let Some(cell) = parts.extensions.get::<CallerCell>() {
+                cell.set(&user);
+            }
             return Ok(Auth(user));
         }
 
@@ -116,12 +175,96 @@ impl FromRequestParts<AppState> for Auth {
             .ok_or(AuthError::InvalidAuthHeader)?;
 
         // Decode and verify the JWT
-        let user = verify_jwt(token, &state.auth_config).await?;
+        let mut user = verify_jwt(token, &state.auth_config).await?;
+        apply_bootstrap_admin(state, &mut user);
+        record_session(parts, state, &user);
+        if let Some(cell) = parts.extensions.get::<CallerCell>() {
+            cell.set(&user);
+        }
 
         Ok(Auth(user))
     }
 }
 
+/// Authenticate an API key and check that its scopes cover the request.
+///
+/// The key acts as the user [`StoredApiKey::principal`] with the key's role
+/// and tenant. Keys skip the admin bootstrap and session history.
+///
+/// [`StoredApiKey::principal`]: crate::storage::StoredApiKey::principal
+fn verify_api_key(
+    key: &str,
+    method: &Method,
+    path: &str,
+    state: &AppState,
+) -> Result<AuthenticatedUser, AuthError> {
+    let repo = ApiKeyRepository::new(state.storage());
+    let now = chrono::Utc::now();
+    let stored = repo
+        .authenticate(key, now)
+        .map_err(|e| AuthError::InternalError(e.to_string()))?
+        .ok_or(AuthError::InvalidApiKey)?;
+    let write = !matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS);
+    if !stored.allows(request_area(path), write) {
+        return Err(AuthError::InsufficientPermissions);
+    }
+    if let Err(error) = repo.touch(&stored.key_id, now) {
+        tracing::warn!(error = %error, "Failed to record API key use");
+    }
+
+    Ok(AuthenticatedUser {
+        user_id: stored.principal(),
+        role: stored.role,
+        session_id: None,
+        issuer: API_KEY_ISSUER.to_string(),
+        expires_at: stored.expires_at.map_or(0, |at| at.timestamp()),
+        tenant_id: stored.tenant_id,
+    })
+}
+
+/// First path segment under `/v1`, which API key scopes refer to.
+///
+/// Handlers nested under `/v1` see the path without the prefix.
+fn request_area(path: &str) -> &str {
+    let path = path.strip_prefix("/v1/").unwrap_or(path);
+    path.trim_start_matches('/').split('/').next().unwrap_or("")
+}
+
+/// Grant the admin role to the user designated through the one-time admin
+/// bootstrap, whose JWT may not carry it.
+fn apply_bootstrap_admin(state: &AppState, user: &mut AuthenticatedUser) {
+    if user.role == Role::Admin {
+        return;
+    }
+    match AdminBootstrapRepository::new(state.storage()).admin_user_id() {
+        Ok(Some(admin_user_id)) if admin_user_id == user.user_id => user.role = Role::Admin,
+        Ok(_) => {}
+        Err(error) => tracing::warn!(error = %error, "Failed to read admin bootstrap state"),
+    }
+}
+
+/// Add the request to the user's session history. Failures are logged and
+/// never reject the request.
+fn record_session(parts: &Parts, state: &AppState, user: &AuthenticatedUser) {
+    let observation = SessionObservation {
+        session_id: user.session_id.clone(),
+        issuer: user.issuer.clone(),
+        ip_address: client_ip(parts, state),
+        user_agent: parts
+            .headers
+            .get(USER_AGENT)
+            .and_then(|v| v.to_str().ok())
+            .map(|ua| ua.chars().take(MAX_USER_AGENT_LEN).collect()),
+    };
+    if let Err(error) = SessionLogRepository::new(state.storage()).record(
+        &user.user_id,
+        &observation,
+        chrono::Utc::now(),
+    ) {
+        tracing::warn!(error = %error, "Failed to record session history");
+    }
+}
+
 /// Verify JWT and extract user information.
 ///
 /// In production mode (JWKS configured), verifies signature against Clerk JWKS.
@@ -198,6 +341,7 @@ async fn verify_jwt_production(
         .or(claims.role.as_deref())
         .and_then(Role::from_str)
         .unwrap_or(Role::Client);
+    let tenant_id = tenant_from_claims(claims.org_id.as_deref(), claims.o.as_ref())?;
 
     Ok(AuthenticatedUser {
         user_id: claims.sub,
@@ -205,6 +349,7 @@ async fn verify_jwt_production(
         session_id: claims.sid,
         issuer: claims.iss,
         expires_at: claims.exp,
+        tenant_id,
     })
 }
 
@@ -240,6 +385,7 @@ fn verify_jwt_development(token: &str) -> Result<AuthenticatedUser, AuthError> {
         .or(claims.role.as_deref())
         .and_then(Role::from_str)
         .unwrap_or(Role::Client);
+    let tenant_id = tenant_from_claims(claims.org_id.as_deref(), claims.o.as_ref())?;
 
     Ok(AuthenticatedUser {
         user_id: claims.sub,
@@ -247,6 +393,7 @@ fn verify_jwt_development(token: &str) -> Result<AuthenticatedUser, AuthError> {
         session_id: claims.sid,
         issuer: claims.iss,
         expires_at: claims.exp,
+        tenant_id,
     })
 }
 
@@ -365,6 +512,7 @@ mod tests {
     }
 
     /// Helper to create a test JWT token (unsigned, for testing only)
+    #[cfg(feature = "dev")]
     fn create_test_jwt(user_id: &str) -> String {
         use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
 
@@ -431,6 +579,7 @@ mod tests {
             session_id: None,
             issuer: "middleware".to_string(),
             expires_at: 0,
+            tenant_id: None,
         };
         parts.extensions.insert(user.clone());
 
@@ -439,6 +588,59 @@ mod tests {
         assert_eq!(result.unwrap().0.user_id, "user_from_middleware");
     }
 
+    #[tokio::test]
+    async fn api_key_authenticates_within_its_scopes() {
+        use crate::storage::repository::api_keys::{api_key_sha256, generate_api_key};
+        use crate::storage::StoredApiKey;
+
+        let (state, _temp_dir) = create_test_state();
+        let (key_id, key) = generate_api_key();
+        ApiKeyRepository::new(state.storage())
+            .save(&StoredApiKey {
+                key_id: key_id.clone(),
+                name: "reports".to_string(),
+                key_sha256: api_key_sha256(&key),
+                role: Role::Auditor,
+                scopes: vec!["admin:read".to_string()],
+                tenant_id: None,
+                created_by: "admin".to_string(),
+                created_at: chrono::Utc::now(),
+                expires_at: None,
+                last_used_at: None,
+                revoked_at: None,
+            })
+            .unwrap();
+        let parts = |method: &str, uri: &str, key: &str| {
+            Request::builder()
+                .method(method)
+                .uri(uri)
+                .header(API_KEY_HEADER, key)
+                .body(())
+                .unwrap()
+                .into_parts()
+                .0
+        };
+
+        let Auth(user) = Auth::from_request_parts(&mut parts("GET", "/admin/audit", &key), &state)
+            .await
+            .unwrap();
+        assert_eq!(user.user_id, format!("api_key:{key_id}"));
+        assert_eq!(user.role, Role::Auditor);
+        assert_eq!(user.issuer, API_KEY_ISSUER);
+
+        let result =
+            Auth::from_request_parts(&mut parts("POST", "/admin/audit", &key), &state).await;
+        assert!(matches!(result, Err(AuthError::InsufficientPermissions)));
+        let result = Auth::from_request_parts(&mut parts("GET", "/wallets", &key), &state).await;
+        assert!(matches!(result, Err(AuthError::InsufficientPermissions)));
+        let result =
+            Auth::from_request_parts(&mut parts("GET", "/admin/audit", "rwk_00_00"), &state).await;
+        assert!(matches!(result, Err(AuthError::InvalidApiKey)));
+
+        assert_eq!(request_area("/v1/wallets/w1"), "wallets");
+        assert_eq!(request_area("/admin/api-keys"), "admin");
+    }
+
     #[tokio::test]
     async fn admin_only_rejects_non_admin() {
         let (state, _temp_dir) = create_test_state();
@@ -455,6 +657,7 @@ mod tests {
             session_id: None,
             issuer: "test".to_string(),
             expires_at: 0,
+            tenant_id: None,
         };
         parts.extensions.insert(user);
 
@@ -7,21 +7,37 @@
 //! ## Security
 //!
 //! - JWKS is fetched via HTTPS only
-//! - Keys are cached with a configurable TTL (60s default)
+//! - Keys are cached with a configurable TTL (60s default), shortened by
+//!   the endpoint's `Cache-Control: max-age`
 //! - On fetch failure: stale cache used within 2x TTL grace period
 //! - Beyond grace period: fail-closed (reject all auth requests)
 //!
+//! ## Key Rotation
+//!
+//! [`JwksManager::run_refresh`] re-fetches the keys in the background every
+//! `JWKS_REFRESH_INTERVAL_SECS` (or sooner when `max-age` says so), so
+//! requests keep hitting a warm cache. A token signed with a `kid` the cache
+//! does not know triggers an immediate re-fetch, at most once per
+//! [`UNKNOWN_KID_REFETCH_INTERVAL`], so a key Clerk has just rotated in is
+//! accepted without waiting for the next refresh. Added and removed key IDs
+//! are logged and counted in [`JwksStats`].
+//!
 //! ## Usage
 //!
 //! Initialize JwksManager with CLERK_JWKS_URL in main.rs and store in AppState.
 //! The Auth extractor uses it for production JWT verification.
 
-use std::sync::Arc;
+use std::env;
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
+use chrono::{DateTime, Utc};
 use jsonwebtoken::jwk::{AlgorithmParameters, Jwk, JwkSet};
 use jsonwebtoken::{Algorithm, DecodingKey};
+use serde::Serialize;
 use tokio::sync::RwLock;
+use tokio_util::sync::CancellationToken;
+use utoipa::ToSchema;
 
 use super::error::AuthError;
 
@@ -31,10 +47,86 @@ use super::error::AuthError;
 /// Stale cache is used with a 2x grace period if fetch fails.
 const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(60);
 
+/// Default background refresh interval, shorter than the cache TTL so
+/// requests rarely have to fetch.
+pub const DEFAULT_REFRESH_INTERVAL: Duration = Duration::from_secs(50);
+
+/// Shortest refresh interval or cache lifetime, whatever `max-age` says.
+const MIN_REFRESH_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Retry delay after a failed background refresh.
+const REFRESH_RETRY: Duration = Duration::from_secs(5);
+
+/// Minimum time between re-fetches triggered by unknown key IDs, so
+/// tokens with made-up `kid`s cannot hammer the endpoint.
+pub const UNKNOWN_KID_REFETCH_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Background refresh interval from `JWKS_REFRESH_INTERVAL_SECS`.
+pub fn refresh_interval_from_env() -> Duration {
+    env::var("JWKS_REFRESH_INTERVAL_SECS")
+        .ok()
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .map(Duration::from_secs)
+        .map_or(DEFAULT_REFRESH_INTERVAL, |d| d.max(MIN_REFRESH_INTERVAL))
+}
+
+/// `max-age` of a `Cache-Control` header value.
+fn parse_max_age(cache_control: &str) -> Option<Duration> {
+    cache_control.split(',').find_map(|directive| {
+        let (name, value) = directive.trim().split_once('=')?;
+        if !name.trim().eq_ignore_ascii_case("max-age") {
+            return None;
+        }
+        value
+            .trim()
+            .trim_matches('"')
+            .parse::<u64>()
+            .ok()
+            .map(Duration::from_secs)
+    })
+}
+
+/// Key IDs of a key set, sorted.
+fn key_ids(jwks: &JwkSet) -> Vec<String> {
+    let mut ids: Vec<String> = jwks
+        .keys
+        .iter()
+        .filter_map(|k| k.common.key_id.clone())
+        .collect();
+    ids.sort();
+    ids
+}
+
 /// JWKS cache entry.
 struct CacheEntry {
     jwks: JwkSet,
     fetched_at: Instant,
+    /// `Cache-Control: max-age` of the response, if any.
+    max_age: Option<Duration>,
+}
+
+/// A fetched key set.
+struct Fetched {
+    jwks: JwkSet,
+    max_age: Option<Duration>,
+}
+
+/// JWKS refresh counters, reported by the admin health endpoint.
+#[derive(Debug, Clone, Default, Serialize, ToSchema)]
+pub struct JwksStats {
+    /// Key IDs currently cached.
+    pub key_ids: Vec<String>,
+    /// Last successful fetch.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_refresh_at: Option<DateTime<Utc>>,
+    /// Successful fetches since startup.
+    pub refreshes: u64,
+    /// Failed fetches since startup.
+    pub refresh_failures: u64,
+    /// Fetches that changed the set of key IDs.
+    pub rotations: u64,
+    /// Re-fetches triggered by tokens with an unknown key ID.
+    pub unknown_kid_refetches: u64,
 }
 
 /// JWKS manager with caching.
@@ -48,6 +140,10 @@ pub struct JwksManager {
     cache_ttl: Duration,
     /// Cached JWKS
     cache: Arc<RwLock<Option<CacheEntry>>>,
+    /// Last re-fetch for an unknown key ID
+    last_kid_refetch: Arc<Mutex<Option<Instant>>>,
+    /// Refresh counters
+    stats: Arc<Mutex<JwksStats>>,
     /// HTTP client
     client: reqwest::Client,
 }
@@ -62,6 +158,8 @@ impl JwksManager {
             jwks_url: jwks_url.into(),
             cache_ttl: DEFAULT_CACHE_TTL,
             cache: Arc::new(RwLock::new(None)),
+            last_kid_refetch: Arc::new(Mutex::new(None)),
+            stats: Arc::new(Mutex::new(JwksStats::default())),
             client: reqwest::Client::builder()
                 .timeout(Duration::from_secs(10))
                 .build()
@@ -94,7 +192,7 @@ impl JwksManager {
         {
             let cache = self.cache.read().await;
             if let Some(entry) = &*cache {
-                if entry.fetched_at.elapsed() < self.cache_ttl {
+                if entry.fetched_at.elapsed() < self.entry_ttl(entry) {
                     return Ok(entry.jwks.clone());
                 }
             }
@@ -102,21 +200,13 @@ impl JwksManager {
 
         // Cache is missing or stale — fetch fresh JWKS
         match self.fetch_jwks().await {
-            Ok(jwks) => {
-                // Update cache with fresh keys
-                let mut cache = self.cache.write().await;
-                *cache = Some(CacheEntry {
-                    jwks: jwks.clone(),
-                    fetched_at: Instant::now(),
-                });
-                Ok(jwks)
-            }
+            Ok(fetched) => Ok(self.store(fetched).await),
             Err(fetch_err) => {
                 // Fetch failed — check if we have a stale cache to fall back on
                 let cache = self.cache.read().await;
                 if let Some(entry) = &*cache {
                     // Allow stale cache for a grace period (2x TTL)
-                    if entry.fetched_at.elapsed() < self.cache_ttl * 2 {
+                    if entry.fetched_at.elapsed() < self.entry_ttl(entry) * 2 {
                         tracing::warn!(
                             "JWKS fetch failed, using stale cache (age: {:?}): {}",
                             entry.fetched_at.elapsed(),
@@ -135,8 +225,60 @@ impl JwksManager {
         }
     }
 
+    /// Lifetime of a cache entry: the TTL, or the response's `max-age` if
+    /// shorter.
+    fn entry_ttl(&self, entry: &CacheEntry) -> Duration {
+        entry.max_age.map_or(self.cache_ttl, |max_age| {
+            max_age.max(MIN_REFRESH_INTERVAL).min(self.cache_ttl)
+        })
+    }
+
+    /// Cache a fetched key set, logging and counting key rotations.
+    async fn store(&self, fetched: Fetched) -> JwkSet {
+        let ids = key_ids(&fetched.jwks);
+        let mut cache = self.cache.write().await;
+        if let Some(previous) = cache.as_ref().map(|entry| key_ids(&entry.jwks)) {
+            if previous != ids {
+                let added: Vec<&String> = ids.iter().filter(|id| !previous.contains(id)).collect();
+                let removed: Vec<&String> =
+                    previous.iter().filter(|id| !ids.contains(id)).collect();
+                tracing::info!(?added, ?removed, "JWKS key rotation detected");
+                self.stats().rotations += 1;
+            }
+        }
+        *cache = Some(CacheEntry {
+            jwks: fetched.jwks.clone(),
+            fetched_at: Instant::now(),
+            max_age: fetched.max_age,
+        });
+        drop(cache);
+
+        let mut stats = self.stats();
+        stats.key_ids = ids;
+        stats.last_refresh_at = Some(Utc::now());
+        stats.refreshes += 1;
+        fetched.jwks
+    }
+
+    fn stats(&self) -> std::sync::MutexGuard<'_, JwksStats> {
+        self.stats.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Refresh counters and the cached key IDs.
+    pub fn stats_snapshot(&self) -> JwksStats {
+        self.stats().clone()
+    }
+
     /// Fetch JWKS from the endpoint.
-    async fn fetch_jwks(&self) -> Result<JwkSet, AuthError> {
+    async fn fetch_jwks(&self) -> Result<Fetched, AuthError> {
+        let result = self.fetch_jwks_inner().await;
+        if result.is_err() {
+            self.stats().refresh_failures += 1;
+        }
+        result
+    }
+
+    async fn fetch_jwks_inner(&self) -> Result<Fetched, AuthError> {
         let response = self
             .client
             .get(&self.jwks_url)
@@ -151,17 +293,31 @@ impl JwksManager {
             )));
         }
 
+        let max_age = response
+            .headers()
+            .get(reqwest::header::CACHE_CONTROL)
+            .and_then(|v| v.to_str().ok())
+            .and_then(parse_max_age);
         let jwks: JwkSet = response
             .json()
             .await
             .map_err(|e| AuthError::JwksFetchError(e.to_string()))?;
 
-        Ok(jwks)
+        Ok(Fetched { jwks, max_age })
     }
 
     /// Get a decoding key for the given key ID.
     pub async fn get_decoding_key(&self, kid: &str) -> Result<(DecodingKey, Algorithm), AuthError> {
-        let jwks = self.get_jwks().await?;
+        let mut jwks = self.get_jwks().await?;
+
+        // A key ID we have not seen may have just been rotated in
+        if !jwks
+            .keys
+            .iter()
+            .any(|k| k.common.key_id.as_deref() == Some(kid))
+        {
+            jwks = self.refetch_for_unknown_kid(kid).await?;
+        }
 
         // Find the key with matching kid
         let jwk = jwks
@@ -175,6 +331,26 @@ impl JwksManager {
         Ok((decoding_key, algorithm))
     }
 
+    /// Re-fetch the keys for a token with an unknown `kid`, unless that
+    /// happened within [`UNKNOWN_KID_REFETCH_INTERVAL`].
+    async fn refetch_for_unknown_kid(&self, kid: &str) -> Result<JwkSet, AuthError> {
+        {
+            let mut last = self
+                .last_kid_refetch
+                .lock()
+                .unwrap_or_else(|e| e.into_inner());
+            if last.is_some_and(|at| at.elapsed() < UNKNOWN_KID_REFETCH_INTERVAL) {
+                return Err(AuthError::NoMatchingKey);
+            }
+            *last = Some(Instant::now());
+        }
+
+        tracing::info!(kid, "Unknown JWKS key ID, re-fetching keys");
+        self.stats().unknown_kid_refetches += 1;
+        let fetched = self.fetch_jwks().await?;
+        Ok(self.store(fetched).await)
+    }
+
     /// Get any valid decoding key (for tokens without kid).
     pub async fn get_any_decoding_key(&self) -> Result<(DecodingKey, Algorithm), AuthError> {
         let jwks = self.get_jwks().await?;
@@ -192,14 +368,49 @@ impl JwksManager {
     /// Force refresh the JWKS cache.
     #[cfg_attr(test, allow(dead_code))]
     pub async fn refresh(&self) -> Result<(), AuthError> {
-        let jwks = self.fetch_jwks().await?;
-        let mut cache = self.cache.write().await;
-        *cache = Some(CacheEntry {
-            jwks,
-            fetched_at: Instant::now(),
-        });
+        let fetched = self.fetch_jwks().await?;
+        self.store(fetched).await;
         Ok(())
     }
+
+    /// Delay until the next background refresh: `interval`, or the cached
+    /// response's `max-age` if shorter.
+    async fn next_refresh_in(&self, interval: Duration) -> Duration {
+        let max_age = self.cache.read().await.as_ref().and_then(|e| e.max_age);
+        max_age
+            .map_or(interval, |max_age| max_age.min(interval))
+            .max(MIN_REFRESH_INTERVAL)
+    }
+
+    /// Refresh the keys every `interval` until the cancellation token is
+    /// triggered. Failed refreshes are retried after a few seconds; the
+    /// cache keeps serving within its grace period meanwhile.
+    #[cfg_attr(test, allow(dead_code))]
+    pub async fn run_refresh(&self, interval: Duration, shutdown: CancellationToken) {
+        tracing::info!(
+            interval_secs = interval.as_secs(),
+            "JWKS refresh task starting"
+        );
+
+        loop {
+            let delay = match self.refresh().await {
+                Ok(()) => self.next_refresh_in(interval).await,
+                Err(e) => {
+                    tracing::warn!(error = %e, "JWKS background refresh failed");
+                    REFRESH_RETRY
+                }
+            };
+            crate::worker_health::record_heartbeat(crate::worker_health::Worker::JwksRefresh);
+
+            tokio::select! {
+                _ = tokio::time::sleep(delay) => {},
+                _ = shutdown.cancelled() => {
+                    tracing::info!("JWKS refresh task shutting down");
+                    return;
+                }
+            }
+        }
+    }
 }
 
 /// Convert a JWK to a DecodingKey.
@@ -264,4 +475,66 @@ mod tests {
             .with_cache_ttl(Duration::from_secs(60));
         assert_eq!(manager.cache_ttl, Duration::from_secs(60));
     }
+
+    #[test]
+    fn max_age_is_read_from_cache_control() {
+        assert_eq!(
+            parse_max_age("public, max-age=300, must-revalidate"),
+            Some(Duration::from_secs(300))
+        );
+        assert_eq!(
+            parse_max_age("Max-Age=\"30\""),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(parse_max_age("no-cache"), None);
+        assert_eq!(parse_max_age("s-maxage=10"), None);
+    }
+
+    fn key_set(kids: &[&str]) -> Fetched {
+        let keys = kids
+            .iter()
+            .map(|kid| {
+                serde_json::json!({
+                    "kty": "RSA",
+                    "kid": kid,
+                    "n": "AQAB",
+                    "e": "AQAB",
+                })
+            })
+            .collect::<Vec<_>>();
+        Fetched {
+            jwks: serde_json::from_value(serde_json::json!({ "keys": keys })).unwrap(),
+            max_age: Some(Duration::from_secs(1)),
+        }
+    }
+
+    #[tokio::test]
+    async fn rotations_are_counted_and_max_age_bounds_refresh() {
+        let manager = JwksManager::new("https://example.com/.well-known/jwks.json");
+        manager.store(key_set(&["a"])).await;
+        manager.store(key_set(&["a"])).await;
+        manager.store(key_set(&["a", "b"])).await;
+
+        let stats = manager.stats_snapshot();
+        assert_eq!(stats.key_ids, ["a", "b"]);
+        assert_eq!(stats.refreshes, 3);
+        assert_eq!(stats.rotations, 1);
+
+        // max-age of 1s is floored at the minimum interval.
+        assert_eq!(
+            manager.next_refresh_in(Duration::from_secs(300)).await,
+            MIN_REFRESH_INTERVAL
+        );
+    }
+
+    #[tokio::test]
+    async fn unknown_kid_refetches_are_rate_limited() {
+        let manager = JwksManager::new("https://example.com/.well-known/jwks.json");
+        *manager.last_kid_refetch.lock().unwrap() = Some(Instant::now());
+        assert!(matches!(
+            manager.refetch_for_unknown_kid("new").await,
+            Err(AuthError::NoMatchingKey)
+        ));
+        assert_eq!(manager.stats_snapshot().unknown_kid_refetches, 0);
+    }
 }
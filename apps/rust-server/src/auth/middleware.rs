@@ -173,6 +173,10 @@ async fn validate_token(token: &str, config: &AuthConfig) -> Result<Authenticate
             _ => AuthError::MalformedToken,
         })?;
 
+    super::tenant::tenant_from_claims(
+        token_data.claims.org_id.as_deref(),
+        token_data.claims.o.as_ref(),
+    )?;
     Ok(AuthenticatedUser::from_claims(token_data.claims))
 }
 
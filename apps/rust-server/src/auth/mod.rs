@@ -16,6 +16,10 @@
 //!    - Extracts:
 //!      - `sub` → canonical `user_id`
 //!      - role claims (custom or group claims)
+//!      - organization → tenant ([`tenant`])
+//!
+//! Service integrations that cannot obtain a Clerk JWT send a scoped API key
+//! in `X-Api-Key` instead (see [`crate::storage::repository::api_keys`]).
 //!
 //! ## Security
 //!
@@ -23,14 +27,23 @@
 //! - JWT verification uses HTTPS-only JWKS fetching
 //! - JWKS is cached with TTL for performance
 //! - Clock skew tolerance is 60 seconds
+//! - The most dangerous admin routes also require a signature from the
+//!   operator's registered key ([`request_signing`])
+//! - State-changing requests can carry a single-use nonce ([`replay`])
+//! - Client addresses come from `X-Forwarded-For` only behind a trusted
+//!   proxy ([`client_ip`])
 
 pub mod claims;
+pub mod client_ip;
 pub mod error;
 pub mod extractor;
 pub mod jwks;
 pub mod jwt_crypto;
 pub mod middleware;
+pub mod replay;
+pub mod request_signing;
 pub mod roles;
+pub mod tenant;
 
 pub use claims::AuthenticatedUser;
 pub use error::AuthError;
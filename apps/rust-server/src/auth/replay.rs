@@ -0,0 +1,551 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! # Request Replay Protection
+//!
+//! Clients may send [`TIMESTAMP_HEADER`] (unix seconds), [`NONCE_HEADER`]
+//! (16 to 128 characters of `[A-Za-z0-9_-]`) and [`SIGNATURE_HEADER`] on
+//! state-changing requests. The signature is an HMAC-SHA256, keyed with the
+//! request's bearer token or API key, over
+//! `"{timestamp}.{nonce}.{METHOD}.{path and query}.{raw body}"` (base64url,
+//! unpadded; the path is the full request path, e.g. `/v1/wallets/w/send`).
+//! A request whose timestamp is outside [`WINDOW_SECS`], whose signature
+//! does not match, or whose nonce the caller already used within the window,
+//! is rejected, so a request captured from a log or a TLS-terminating proxy
+//! cannot be sent again or altered without the credential itself.
+//!
+//! The check runs after authentication and tracks nonces per principal.
+//! Requests that do not authenticate pass through unchecked and are
+//! rejected by their handler. The headers are optional unless
+//! `REQUIRE_REQUEST_NONCE` is set, in which case every state-changing
+//! request carrying a bearer token or API key needs them.
+//! Provider webhooks and peer discovery calls carry no bearer token and are
+//! never required to. Used nonces are kept in memory, per instance, until
+//! they leave the window.
+
+use std::collections::HashMap;
+use std::env;
+use std::sync::Mutex;
+
+use axum::{
+    body::{to_bytes, Body},
+    extract::{FromRequestParts, OriginalUri, Request, State},
+    http::{header::AUTHORIZATION, request::Parts, Method, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use base64ct::{Base64UrlUnpadded, Encoding};
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use thiserror::Error;
+
+use super::extractor::{Auth, API_KEY_HEADER};
+use crate::{error::ApiError, state::AppState};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Header carrying the client's unix timestamp.
+pub const TIMESTAMP_HEADER: &str = "x-request-timestamp";
+
+/// Header carrying the client's single-use nonce.
+pub const NONCE_HEADER: &str = "x-request-nonce";
+
+/// Header carrying the client's request signature.
+pub const SIGNATURE_HEADER: &str = "x-request-signature";
+
+/// Accepted clock difference, and how long a nonce stays used.
+pub const WINDOW_SECS: i64 = 300;
+
+/// Used nonces tracked per principal. Beyond this, the principal's oldest
+/// nonces are forgotten early.
+const MAX_NONCES_PER_PRINCIPAL: usize = 10_000;
+
+/// Largest request body buffered for the signature check.
+const MAX_BODY_BYTES: usize = 2 * 1024 * 1024;
+
+const MIN_NONCE_LEN: usize = 16;
+const MAX_NONCE_LEN: usize = 128;
+
+/// Why a request failed the replay check.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ReplayError {
+    #[error("Missing {TIMESTAMP_HEADER}, {NONCE_HEADER} or {SIGNATURE_HEADER} header")]
+    Missing,
+    #[error("Malformed {0} header")]
+    Malformed(&'static str),
+    #[error("Request timestamp outside the accepted window")]
+    Expired,
+    #[error("Request signature does not match the request")]
+    Invalid,
+    #[error("Request nonce was already used")]
+    Replayed,
+}
+
+impl ReplayError {
+    /// Error code for the response body.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Missing => "request_nonce_required",
+            Self::Malformed(_) | Self::Expired | Self::Invalid => "request_nonce_invalid",
+            Self::Replayed => "request_replayed",
+        }
+    }
+
+    fn status(&self) -> StatusCode {
+        match self {
+            Self::Missing | Self::Malformed(_) => StatusCode::BAD_REQUEST,
+            Self::Invalid => StatusCode::UNAUTHORIZED,
+            Self::Expired | Self::Replayed => StatusCode::CONFLICT,
+        }
+    }
+}
+
+/// What a request presents to the replay check.
+#[derive(Debug, Clone, Copy)]
+pub struct ReplayProof<'a> {
+    pub timestamp: Option<&'a str>,
+    pub nonce: Option<&'a str>,
+    pub signature: Option<&'a str>,
+    pub method: &'a str,
+    /// Full path and query.
+    pub path: &'a str,
+    pub body: &'a [u8],
+}
+
+/// Nonces seen within the window, per principal.
+#[derive(Debug, Default)]
+pub struct ReplayGuard {
+    /// Whether bearer-authenticated writes must carry the headers.
+    required: bool,
+    seen: Mutex<SeenNonces>,
+}
+
+#[derive(Debug, Default)]
+struct SeenNonces {
+    /// Used nonces with their timestamps, by principal.
+    by_principal: HashMap<String, HashMap<String, i64>>,
+    /// When every principal's expired nonces were last dropped.
+    swept_at: i64,
+}
+
+impl ReplayGuard {
+    /// Guard configured from `REQUIRE_REQUEST_NONCE`.
+    pub fn from_env() -> Self {
+        let required = env::var("REQUIRE_REQUEST_NONCE")
+            .map(|v| matches!(v.to_ascii_lowercase().as_str(), "1" | "true" | "yes"))
+            .unwrap_or(false);
+        Self::new(required)
+    }
+
+    pub fn new(required: bool) -> Self {
+        Self {
+            required,
+            seen: Mutex::default(),
+        }
+    }
+
+    /// Whether the headers are mandatory.
+    pub fn is_required(&self) -> bool {
+        self.required
+    }
+
+    /// Check and record a request by `principal`, authenticated with
+    /// `credential`, at unix time `now`.
+    pub fn check(
+        &self,
+        principal: &str,
+        credential: &str,
+        proof: &ReplayProof<'_>,
+        now: i64,
+    ) -> Result<(), ReplayError> {
+        let (Some(timestamp), Some(nonce), Some(signature)) =
+            (proof.timestamp, proof.nonce, proof.signature)
+        else {
+            return Err(ReplayError::Missing);
+        };
+        let timestamp = timestamp
+            .trim()
+            .parse::<i64>()
+            .map_err(|_| ReplayError::Malformed(TIMESTAMP_HEADER))?;
+        if !is_valid_nonce(nonce) {
+            return Err(ReplayError::Malformed(NONCE_HEADER));
+        }
+        let signature = Base64UrlUnpadded::decode_vec(signature.trim())
+            .map_err(|_| ReplayError::Malformed(SIGNATURE_HEADER))?;
+        if (now - timestamp).abs() > WINDOW_SECS {
+            return Err(ReplayError::Expired);
+        }
+        request_mac(
+            credential,
+            timestamp,
+            nonce,
+            proof.method,
+            proof.path,
+            proof.body,
+        )
+        .verify_slice(&signature)
+        .map_err(|_| ReplayError::Invalid)?;
+
+        let mut seen = self.seen.lock().unwrap_or_else(|e| e.into_inner());
+        let live = |at: &i64| (now - *at).abs() <= WINDOW_SECS;
+        if now - seen.swept_at > WINDOW_SECS {
+            seen.by_principal.retain(|_, nonces| {
+                nonces.retain(|_, at| live(at));
+                !nonces.is_empty()
+            });
+            seen.swept_at = now;
+        }
+        let nonces = seen.by_principal.entry(principal.to_string()).or_default();
+        if nonces.contains_key(nonce) {
+            return Err(ReplayError::Replayed);
+        }
+        if nonces.len() >= MAX_NONCES_PER_PRINCIPAL {
+            nonces.retain(|_, at| live(at));
+        }
+        if nonces.len() >= MAX_NONCES_PER_PRINCIPAL {
+            let oldest = nonces
+                .iter()
+                .min_by_key(|(_, at)| **at)
+                .map(|(n, _)| n.clone());
+            if let Some(oldest) = oldest {
+                nonces.remove(&oldest);
+            }
+        }
+        nonces.insert(nonce.to_string(), timestamp);
+        Ok(())
+    }
+}
+
+/// HMAC of a request, keyed with its credential.
+fn request_mac(
+    credential: &str,
+    timestamp: i64,
+    nonce: &str,
+    method: &str,
+    path: &str,
+    body: &[u8],
+) -> HmacSha256 {
+    let mut mac =
+        HmacSha256::new_from_slice(credential.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(format!("{timestamp}.{nonce}.{method}.{path}.").as_bytes());
+    mac.update(body);
+    mac
+}
+
+fn is_valid_nonce(nonce: &str) -> bool {
+    (MIN_NONCE_LEN..=MAX_NONCE_LEN).contains(&nonce.len())
+        && nonce
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_')
+}
+
+/// Bearer token or API key the request authenticates with, in the order
+/// [`Auth`] tries them.
+fn credential(parts: &Parts) -> Option<&str> {
+    if let Some(key) = parts.headers.get(API_KEY_HEADER) {
+        return key.to_str().ok();
+    }
+    parts
+        .headers
+        .get(AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+}
+
+/// Layer rejecting replayed state-changing requests.
+///
+/// Authenticates the caller first and leaves the user in the request
+/// extensions, so the handler does not verify the credential again.
+pub async fn reject_replays(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if matches!(
+        *request.method(),
+        Method::GET | Method::HEAD | Method::OPTIONS
+    ) {
+        return next.run(request).await;
+    }
+    let headers = request.headers();
+    let must_check = [TIMESTAMP_HEADER, NONCE_HEADER, SIGNATURE_HEADER]
+        .iter()
+        .any(|name| headers.contains_key(*name))
+        || (state.replay_guard.is_required()
+            && (headers.contains_key(AUTHORIZATION) || headers.contains_key(API_KEY_HEADER)));
+    if !must_check {
+        return next.run(request).await;
+    }
+
+    let (mut parts, body) = request.into_parts();
+    let (Ok(Auth(user)), Some(credential)) = (
+        Auth::from_request_parts(&mut parts, &state).await,
+        credential(&parts).map(str::to_string),
+    ) else {
+        return next.run(Request::from_parts(parts, body)).await;
+    };
+    parts.extensions.insert(user.clone());
+
+    let Ok(body) = to_bytes(body, MAX_BODY_BYTES).await else {
+        return ApiError::new(StatusCode::PAYLOAD_TOO_LARGE, "Request body too large")
+            .into_response();
+    };
+    let uri = parts
+        .extensions
+        .get::<OriginalUri>()
+        .map_or(&parts.uri, |original| &original.0);
+    let header = |name: &str| parts.headers.get(name).and_then(|v| v.to_str().ok());
+    let proof = ReplayProof {
+        timestamp: header(TIMESTAMP_HEADER),
+        nonce: header(NONCE_HEADER),
+        signature: header(SIGNATURE_HEADER),
+        method: parts.method.as_str(),
+        path: uri.path_and_query().map_or(uri.path(), |pq| pq.as_str()),
+        body: &body,
+    };
+    if let Err(e) =
+        state
+            .replay_guard
+            .check(&user.user_id, &credential, &proof, Utc::now().timestamp())
+    {
+        return ApiError::new(e.status(), e.to_string())
+            .with_code(e.code())
+            .into_response();
+    }
+    next.run(Request::from_parts(parts, Body::from(body))).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::Role;
+    use crate::storage::repository::api_keys::{api_key_sha256, generate_api_key};
+    use crate::storage::{ApiKeyRepository, StoredApiKey};
+    use axum::{routing::post, Router};
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    const NONCE: &str = "c1d2e3f4a5b6c7d8";
+    const PATH: &str = "/v1/wallets/w_1/send";
+
+    fn sign(credential: &str, t: i64, nonce: &str, path: &str, body: &[u8]) -> String {
+        let tag = request_mac(credential, t, nonce, "POST", path, body)
+            .finalize()
+            .into_bytes();
+        Base64UrlUnpadded::encode_string(&tag)
+    }
+
+    fn proof<'a>(
+        t: &'a str,
+        nonce: &'a str,
+        signature: &'a str,
+        body: &'a [u8],
+    ) -> ReplayProof<'a> {
+        ReplayProof {
+            timestamp: Some(t),
+            nonce: Some(nonce),
+            signature: Some(signature),
+            method: "POST",
+            path: PATH,
+            body,
+        }
+    }
+
+    #[test]
+    fn nonces_are_single_use_and_bound_to_the_request() {
+        let guard = ReplayGuard::new(false);
+        let now = 1_800_000_000;
+        let t = now.to_string();
+        let body = br#"{"amount":"1"}"#;
+        let signature = sign("token", now, NONCE, PATH, body);
+
+        let missing = ReplayProof {
+            signature: None,
+            ..proof(&t, NONCE, &signature, body)
+        };
+        assert_eq!(
+            guard.check("user_1", "token", &missing, now),
+            Err(ReplayError::Missing)
+        );
+        assert_eq!(
+            guard.check(
+                "user_1",
+                "token",
+                &proof("soon", NONCE, &signature, body),
+                now
+            ),
+            Err(ReplayError::Malformed(TIMESTAMP_HEADER))
+        );
+        assert_eq!(
+            guard.check(
+                "user_1",
+                "token",
+                &proof(&t, "short", &signature, body),
+                now
+            ),
+            Err(ReplayError::Malformed(NONCE_HEADER))
+        );
+        assert_eq!(
+            guard.check(
+                "user_1",
+                "token",
+                &proof(&t, NONCE, &signature, body),
+                now + WINDOW_SECS + 1
+            ),
+            Err(ReplayError::Expired)
+        );
+        // A fresh nonce, another body or another credential breaks the MAC.
+        let fresh = "f1e2d3c4b5a69788";
+        assert_eq!(
+            guard.check("user_1", "token", &proof(&t, fresh, &signature, body), now),
+            Err(ReplayError::Invalid)
+        );
+        assert_eq!(
+            guard.check("user_1", "token", &proof(&t, NONCE, &signature, b"{}"), now),
+            Err(ReplayError::Invalid)
+        );
+        assert_eq!(
+            guard.check("user_1", "other", &proof(&t, NONCE, &signature, body), now),
+            Err(ReplayError::Invalid)
+        );
+
+        assert_eq!(
+            guard.check("user_1", "token", &proof(&t, NONCE, &signature, body), now),
+            Ok(())
+        );
+        assert_eq!(
+            guard.check(
+                "user_1",
+                "token",
+                &proof(&t, NONCE, &signature, body),
+                now + 1
+            ),
+            Err(ReplayError::Replayed)
+        );
+        // Nonces are tracked per principal.
+        let other = sign("token_2", now, NONCE, PATH, body);
+        assert_eq!(
+            guard.check("user_2", "token_2", &proof(&t, NONCE, &other, body), now),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn expired_nonces_are_evicted_instead_of_refusing() {
+        let guard = ReplayGuard::new(false);
+        let now = 1_800_000_000;
+        let t = now.to_string();
+        for i in 0..MAX_NONCES_PER_PRINCIPAL {
+            let nonce = format!("nonce{i:011}");
+            let signature = sign("token", now, &nonce, PATH, b"");
+            guard
+                .check("user_1", "token", &proof(&t, &nonce, &signature, b""), now)
+                .unwrap();
+        }
+        let later = now + WINDOW_SECS + 1;
+        let t = later.to_string();
+        let signature = sign("token", later, NONCE, PATH, b"");
+        assert_eq!(
+            guard.check("user_1", "token", &proof(&t, NONCE, &signature, b""), later),
+            Ok(())
+        );
+        let seen = guard.seen.lock().unwrap();
+        assert_eq!(seen.by_principal["user_1"].len(), 1);
+    }
+
+    #[tokio::test]
+    async fn layer_checks_authenticated_writes_that_opt_in_or_are_required() {
+        let state = AppState::default();
+        let (key_id, key) = generate_api_key();
+        ApiKeyRepository::new(state.storage())
+            .save(&StoredApiKey {
+                key_id,
+                name: "payouts".to_string(),
+                key_sha256: api_key_sha256(&key),
+                role: Role::Client,
+                scopes: vec!["wallets:write".to_string()],
+                tenant_id: None,
+                created_by: "admin".to_string(),
+                created_at: Utc::now(),
+                expires_at: None,
+                last_used_at: None,
+                revoked_at: None,
+            })
+            .unwrap();
+        let app = |required: bool| {
+            let mut state = state.clone();
+            state.replay_guard = Arc::new(ReplayGuard::new(required));
+            Router::new()
+                .route(PATH, post(|| async { "ok" }).get(|| async { "ok" }))
+                .layer(axum::middleware::from_fn_with_state(
+                    state.clone(),
+                    reject_replays,
+                ))
+                .with_state(state)
+        };
+        let request = |method: &str, nonce: Option<&str>, key: Option<&str>| {
+            let mut builder = axum::http::Request::builder().method(method).uri(PATH);
+            if let Some(nonce) = nonce {
+                let now = Utc::now().timestamp();
+                builder = builder
+                    .header(TIMESTAMP_HEADER, now.to_string())
+                    .header(NONCE_HEADER, nonce)
+                    .header(
+                        SIGNATURE_HEADER,
+                        sign(key.unwrap_or(""), now, nonce, PATH, b""),
+                    );
+            }
+            if let Some(key) = key {
+                builder = builder.header(API_KEY_HEADER, key);
+            }
+            builder.body(Body::empty()).unwrap()
+        };
+
+        let optional = app(false);
+        let response = optional
+            .clone()
+            .oneshot(request("POST", None, Some(&key)))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let response = optional
+            .clone()
+            .oneshot(request("POST", Some(NONCE), Some(&key)))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let response = optional
+            .clone()
+            .oneshot(request("POST", Some(NONCE), Some(&key)))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+        // Unauthenticated requests are left to the handler.
+        let response = optional
+            .oneshot(request("POST", Some(NONCE), None))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let required = app(true);
+        let response = required
+            .clone()
+            .oneshot(request("POST", None, Some(&key)))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let response = required
+            .clone()
+            .oneshot(request("POST", None, None))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let response = required
+            .oneshot(request("GET", None, Some(&key)))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+}
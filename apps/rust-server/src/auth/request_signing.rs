@@ -0,0 +1,373 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! # Signed Admin Requests
+//!
+//! The most dangerous admin calls (reserve transfers, overrides of escrow,
+//! reserve-queue and name-review decisions, applying a transaction database
+//! rebuild) also require a detached signature from the calling operator's
+//! registered Ed25519 key, so a stolen admin JWT is not enough on its own.
+//!
+//! Keys are registered out of band in `ADMIN_SIGNING_KEYS`, as
+//! comma-separated `<user_id>=<public key>` pairs with raw Ed25519 public
+//! keys in base64url without padding. No API can add one. While no key is
+//! registered, the check is off; once any is, every operator calling a
+//! signed route needs one.
+//!
+//! Requests carry an [`ADMIN_SIGNATURE_HEADER`] of the form
+//! `t=<unix seconds>,v1=<signature>`, where the signature (base64url,
+//! unpadded) covers `"{t}.{METHOD}.{path and query}.{raw body}"`. The path is
+//! the full request path, e.g. `/v1/admin/fiat/reserve/topup`. Timestamps
+//! outside [`TOLERANCE_SECS`] are rejected, and each signature is accepted
+//! once.
+
+use std::collections::HashMap;
+use std::env;
+use std::sync::Mutex;
+
+use axum::{
+    body::{to_bytes, Body},
+    extract::{FromRequestParts, OriginalUri, Request, State},
+    http::StatusCode,
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use base64ct::{Base64UrlUnpadded, Encoding};
+use chrono::Utc;
+use ring::signature::{UnparsedPublicKey, ED25519};
+use thiserror::Error;
+
+use super::AdminOnly;
+use crate::{
+    error::ApiError,
+    state::AppState,
+    storage::{AuditEvent, AuditEventType, AuditRepository},
+};
+
+/// Header carrying the operator's request signature.
+pub const ADMIN_SIGNATURE_HEADER: &str = "x-admin-signature";
+
+/// Accepted clock difference between signing and verification.
+pub const TOLERANCE_SECS: i64 = 300;
+
+/// Largest request body a signed route buffers for verification.
+const MAX_SIGNED_BODY_BYTES: usize = 1024 * 1024;
+
+/// Why a signed admin request was rejected.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum RequestSignatureError {
+    #[error("No request signing key is registered for this operator")]
+    NoKey,
+    #[error("Missing {ADMIN_SIGNATURE_HEADER} header")]
+    Missing,
+    #[error("Malformed {ADMIN_SIGNATURE_HEADER} header: {0}")]
+    Malformed(String),
+    #[error("Signature timestamp outside tolerance")]
+    Expired,
+    #[error("Signature does not match request")]
+    Invalid,
+    #[error("Signature was already used")]
+    Replayed,
+}
+
+impl RequestSignatureError {
+    /// Error code for the response body.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::NoKey | Self::Missing => "admin_signature_required",
+            _ => "admin_signature_invalid",
+        }
+    }
+}
+
+/// Operators' registered request signing keys.
+#[derive(Debug, Default)]
+pub struct AdminSigningKeys {
+    /// Raw Ed25519 public keys by user ID.
+    keys: HashMap<String, Vec<u8>>,
+    /// Signatures accepted within the tolerance, with their timestamps.
+    seen: Mutex<HashMap<String, i64>>,
+}
+
+impl AdminSigningKeys {
+    /// Keys from `ADMIN_SIGNING_KEYS`; malformed entries are skipped with a
+    /// warning.
+    pub fn from_env() -> Self {
+        env::var("ADMIN_SIGNING_KEYS")
+            .map(|value| Self::parse(&value))
+            .unwrap_or_default()
+    }
+
+    /// Keys from comma-separated `<user_id>=<public key>` pairs.
+    pub fn parse(value: &str) -> Self {
+        let mut keys = HashMap::new();
+        for entry in value.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            let parsed = entry.split_once('=').and_then(|(user_id, key)| {
+                let key = Base64UrlUnpadded::decode_vec(key.trim()).ok()?;
+                (key.len() == 32).then(|| (user_id.trim().to_string(), key))
+            });
+            match parsed {
+                Some((user_id, key)) => {
+                    keys.insert(user_id, key);
+                }
+                None => tracing::warn!(
+                    entry = entry.split('=').next().unwrap_or_default(),
+                    "Ignoring malformed ADMIN_SIGNING_KEYS entry"
+                ),
+            }
+        }
+        Self {
+            keys,
+            seen: Mutex::default(),
+        }
+    }
+
+    /// Whether request signing is enforced.
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Number of registered operators.
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Verify `header` for a request by `user_id` at unix time `now`.
+    pub fn verify(
+        &self,
+        user_id: &str,
+        header: Option<&str>,
+        method: &str,
+        path: &str,
+        body: &[u8],
+        now: i64,
+    ) -> Result<(), RequestSignatureError> {
+        let key = self.keys.get(user_id).ok_or(RequestSignatureError::NoKey)?;
+        let header = header.ok_or(RequestSignatureError::Missing)?;
+
+        let mut timestamp = None;
+        let mut signature = None;
+        for part in header.split(',') {
+            match part.trim().split_once('=') {
+                Some(("t", value)) => timestamp = value.parse::<i64>().ok(),
+                Some(("v1", value)) => signature = Some(value),
+                _ => {}
+            }
+        }
+        let timestamp =
+            timestamp.ok_or_else(|| RequestSignatureError::Malformed("missing t".to_string()))?;
+        let signature =
+            signature.ok_or_else(|| RequestSignatureError::Malformed("missing v1".to_string()))?;
+        if (now - timestamp).abs() > TOLERANCE_SECS {
+            return Err(RequestSignatureError::Expired);
+        }
+        let raw = Base64UrlUnpadded::decode_vec(signature)
+            .map_err(|_| RequestSignatureError::Malformed("v1 is not base64url".to_string()))?;
+        UnparsedPublicKey::new(&ED25519, key)
+            .verify(&signed_payload(timestamp, method, path, body), &raw)
+            .map_err(|_| RequestSignatureError::Invalid)?;
+
+        let mut seen = self.seen.lock().unwrap_or_else(|e| e.into_inner());
+        seen.retain(|_, at| (now - *at).abs() <= TOLERANCE_SECS);
+        if seen.insert(signature.to_string(), timestamp).is_some() {
+            return Err(RequestSignatureError::Replayed);
+        }
+        Ok(())
+    }
+}
+
+/// Bytes covered by the signature.
+pub fn signed_payload(timestamp: i64, method: &str, path: &str, body: &[u8]) -> Vec<u8> {
+    let mut payload = format!("{timestamp}.{method}.{path}.").into_bytes();
+    payload.extend_from_slice(body);
+    payload
+}
+
+/// Layer requiring a valid operator signature on admin routes.
+///
+/// Authenticates the caller as an admin first and leaves the user in the
+/// request extensions, so the handler does not verify the JWT again.
+pub async fn require_admin_signature(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if state.admin_signing_keys.is_empty() {
+        return next.run(request).await;
+    }
+    let (mut parts, body) = request.into_parts();
+    let admin = match AdminOnly::from_request_parts(&mut parts, &state).await {
+        Ok(AdminOnly(user)) => user,
+        Err(e) => return e.into_response(),
+    };
+    parts.extensions.insert(admin.clone());
+
+    let Ok(body) = to_bytes(body, MAX_SIGNED_BODY_BYTES).await else {
+        return ApiError::new(StatusCode::PAYLOAD_TOO_LARGE, "Request body too large")
+            .into_response();
+    };
+    let uri = parts
+        .extensions
+        .get::<OriginalUri>()
+        .map_or(&parts.uri, |original| &original.0);
+    let path = uri.path_and_query().map_or(uri.path(), |pq| pq.as_str());
+    let header = parts
+        .headers
+        .get(ADMIN_SIGNATURE_HEADER)
+        .and_then(|v| v.to_str().ok());
+
+    if let Err(e) = state.admin_signing_keys.verify(
+        &admin.user_id,
+        header,
+        parts.method.as_str(),
+        path,
+        &body,
+        Utc::now().timestamp(),
+    ) {
+        let event = AuditEvent::new(AuditEventType::PermissionDenied)
+            .with_user(&admin.user_id)
+            .with_details(serde_json::json!({
+                "action": "admin_request_signature",
+                "method": parts.method.as_str(),
+                "path": path,
+            }))
+            .failed(e.to_string());
+        let _ = AuditRepository::new(state.storage()).log(&event);
+        return ApiError::forbidden(e.to_string())
+            .with_code(e.code())
+            .into_response();
+    }
+    next.run(Request::from_parts(parts, Body::from(body))).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::{AuthenticatedUser, Role};
+    use axum::{routing::post, Router};
+    use ring::rand::SystemRandom;
+    use ring::signature::{Ed25519KeyPair, KeyPair};
+    use tower::ServiceExt;
+
+    fn key_pair() -> Ed25519KeyPair {
+        let pkcs8 = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new()).unwrap();
+        Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap()
+    }
+
+    fn registry(user_id: &str, pair: &Ed25519KeyPair) -> AdminSigningKeys {
+        AdminSigningKeys::parse(&format!(
+            "{user_id}={}",
+            Base64UrlUnpadded::encode_string(pair.public_key().as_ref())
+        ))
+    }
+
+    fn sign(pair: &Ed25519KeyPair, t: i64, method: &str, path: &str, body: &[u8]) -> String {
+        let signature = pair.sign(&signed_payload(t, method, path, body));
+        format!(
+            "t={t},v1={}",
+            Base64UrlUnpadded::encode_string(signature.as_ref())
+        )
+    }
+
+    #[test]
+    fn parse_skips_malformed_entries() {
+        let pair = key_pair();
+        let key = Base64UrlUnpadded::encode_string(pair.public_key().as_ref());
+        let keys = AdminSigningKeys::parse(&format!("user_a={key}, user_b=short,garbage,"));
+        assert_eq!(keys.len(), 1);
+        assert!(AdminSigningKeys::parse("").is_empty());
+    }
+
+    #[test]
+    fn signatures_bind_operator_request_and_time() {
+        let pair = key_pair();
+        let keys = registry("admin_1", &pair);
+        let path = "/v1/admin/fiat/reserve/topup";
+        let body = br#"{"amount_eur":"100.00"}"#;
+        let now = 1_800_000_000;
+        let header = sign(&pair, now, "POST", path, body);
+
+        assert_eq!(
+            keys.verify("admin_2", Some(&header), "POST", path, body, now),
+            Err(RequestSignatureError::NoKey)
+        );
+        assert_eq!(
+            keys.verify("admin_1", None, "POST", path, body, now),
+            Err(RequestSignatureError::Missing)
+        );
+        assert_eq!(
+            keys.verify("admin_1", Some(&header), "POST", path, b"{}", now),
+            Err(RequestSignatureError::Invalid)
+        );
+        assert_eq!(
+            keys.verify("admin_1", Some(&header), "POST", "/v1/admin/x", body, now),
+            Err(RequestSignatureError::Invalid)
+        );
+        assert_eq!(
+            keys.verify("admin_1", Some(&header), "POST", path, body, now + 301),
+            Err(RequestSignatureError::Expired)
+        );
+        assert_eq!(
+            keys.verify("admin_1", Some(&header), "POST", path, body, now),
+            Ok(())
+        );
+        assert_eq!(
+            keys.verify("admin_1", Some(&header), "POST", path, body, now),
+            Err(RequestSignatureError::Replayed)
+        );
+    }
+
+    #[tokio::test]
+    async fn layer_rejects_unsigned_admin_requests() {
+        let pair = key_pair();
+        let state = AppState::default().with_admin_signing_keys(registry("admin_1", &pair));
+        let app = Router::new()
+            .route(
+                "/admin/op",
+                post(|_admin: AdminOnly, body: String| async move { body }),
+            )
+            .route_layer(axum::middleware::from_fn_with_state(
+                state.clone(),
+                require_admin_signature,
+            ))
+            .with_state(state);
+
+        let request = |signature: Option<String>| {
+            let mut request = axum::http::Request::builder()
+                .method("POST")
+                .uri("/admin/op")
+                .body(Body::from("payload"))
+                .unwrap();
+            if let Some(signature) = signature {
+                request
+                    .headers_mut()
+                    .insert(ADMIN_SIGNATURE_HEADER, signature.parse().unwrap());
+            }
+            request.extensions_mut().insert(AuthenticatedUser {
+                user_id: "admin_1".to_string(),
+                role: Role::Admin,
+                session_id: None,
+                issuer: "test".to_string(),
+                expires_at: 0,
+                tenant_id: None,
+            });
+            request
+        };
+
+        let response = app.clone().oneshot(request(None)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        let signed = sign(
+            &pair,
+            Utc::now().timestamp(),
+            "POST",
+            "/admin/op",
+            b"payload",
+        );
+        let response = app.oneshot(request(Some(signed))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(&body[..], b"payload");
+    }
+}
@@ -0,0 +1,88 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Tenant namespaces.
+//!
+//! One deployment can serve several white-label frontends. Every request
+//! belongs to a tenant, taken from the Clerk organization of the session
+//! (`org_id`, or `o.id` in v2 session tokens). Requests without an
+//! organization belong to the default tenant (`None`), so single-tenant
+//! deployments are unaffected.
+//!
+//! Wallets and fiat requests record their tenant at creation, and ownership
+//! checks ([`OwnershipEnforcer`](crate::storage::OwnershipEnforcer)) require
+//! the caller's tenant to match. Admins of a tenant only see that tenant's
+//! data; admins without an organization operate the deployment and see all
+//! tenants.
+
+use serde::Deserialize;
+
+use super::AuthError;
+
+/// Longest accepted tenant ID.
+pub const MAX_TENANT_ID_LEN: usize = 64;
+
+/// Organization claim of Clerk v2 session tokens (`o`).
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct OrgClaim {
+    /// Organization ID
+    #[serde(default)]
+    pub id: Option<String>,
+}
+
+/// Whether `id` is a usable tenant ID: ASCII letters, digits, `_` and `-`.
+pub fn is_valid_tenant_id(id: &str) -> bool {
+    !id.is_empty()
+        && id.len() <= MAX_TENANT_ID_LEN
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// Tenant of a token: `org_id`, else `o.id`; `None` for the default tenant.
+///
+/// A malformed organization ID rejects the token rather than silently
+/// falling back to the default tenant.
+pub fn tenant_from_claims(
+    org_id: Option<&str>,
+    org: Option<&OrgClaim>,
+) -> Result<Option<String>, AuthError> {
+    let id = org_id.or_else(|| org.and_then(|o| o.id.as_deref()));
+    match id {
+        None | Some("") => Ok(None),
+        Some(id) if is_valid_tenant_id(id) => Ok(Some(id.to_string())),
+        Some(_) => Err(AuthError::MalformedToken),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tenant_comes_from_org_claims() {
+        assert_eq!(tenant_from_claims(None, None).unwrap(), None);
+        assert_eq!(
+            tenant_from_claims(Some("org_2abc"), None)
+                .unwrap()
+                .as_deref(),
+            Some("org_2abc")
+        );
+        let v2 = OrgClaim {
+            id: Some("org_v2".to_string()),
+        };
+        assert_eq!(
+            tenant_from_claims(None, Some(&v2)).unwrap().as_deref(),
+            Some("org_v2")
+        );
+        assert_eq!(
+            tenant_from_claims(Some("org_v1"), Some(&v2))
+                .unwrap()
+                .as_deref(),
+            Some("org_v1")
+        );
+        assert!(tenant_from_claims(Some("../other"), None).is_err());
+        assert!(tenant_from_claims(Some(&"a".repeat(MAX_TENANT_ID_LEN + 1)), None).is_err());
+    }
+}
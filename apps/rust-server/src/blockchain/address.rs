@@ -0,0 +1,77 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! EVM address formatting and comparison.
+//!
+//! Addresses reach the server in whatever case clients, RPC nodes and
+//! configuration use. Compare them with [`same_address`], key maps and
+//! indexes by [`address_key`], and format them for output with
+//! [`checksum_address`] (EIP-55). API responses are checksummed by
+//! [`crate::api::address_format`].
+
+use std::str::FromStr;
+
+use alloy::primitives::Address;
+
+/// Whether `value` is `0x` followed by 40 hex digits.
+pub fn is_address(value: &str) -> bool {
+    value.len() == 42
+        && value.starts_with("0x")
+        && value[2..].bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+/// Whether two addresses are the same, whatever their case.
+pub fn same_address(a: &str, b: &str) -> bool {
+    a.trim().eq_ignore_ascii_case(b.trim())
+}
+
+/// Case-independent form of an address, for map keys and indexes.
+pub fn address_key(address: &str) -> String {
+    address.trim().to_ascii_lowercase()
+}
+
+/// EIP-55 checksummed form of an address. Values that are not addresses
+/// are returned unchanged.
+pub fn checksum_address(address: &str) -> String {
+    let trimmed = address.trim();
+    if !is_address(trimmed) {
+        return address.to_string();
+    }
+    Address::from_str(trimmed)
+        .map(|a| a.to_checksum(None))
+        .unwrap_or_else(|_| address.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOWER: &str = "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed";
+    const CHECKSUMMED: &str = "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed";
+
+    #[test]
+    fn addresses_are_checksummed_per_eip55() {
+        assert_eq!(checksum_address(LOWER), CHECKSUMMED);
+        assert_eq!(
+            checksum_address(&LOWER.to_uppercase().replace("0X", "0x")),
+            CHECKSUMMED
+        );
+        assert_eq!(checksum_address("native"), "native");
+        let tx_hash = format!("0x{}", "ab".repeat(32));
+        assert_eq!(checksum_address(&tx_hash), tx_hash);
+    }
+
+    #[test]
+    fn comparison_ignores_case() {
+        assert!(same_address(LOWER, CHECKSUMMED));
+        assert!(same_address(&format!(" {LOWER}"), CHECKSUMMED));
+        assert!(!same_address(
+            LOWER,
+            "0x0000000000000000000000000000000000000000"
+        ));
+        assert_eq!(address_key(CHECKSUMMED), LOWER);
+        assert!(is_address(CHECKSUMMED));
+        assert!(!is_address("0x1234"));
+    }
+}
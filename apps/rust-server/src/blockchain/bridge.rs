@@ -0,0 +1,283 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! USDC bridging with Circle's Cross-Chain Transfer Protocol (CCTP).
+//!
+//! A transfer burns USDC on the source chain with
+//! `TokenMessenger.depositForBurn`, waits for Circle to attest the emitted
+//! message, then mints on the destination chain with
+//! `MessageTransmitter.receiveMessage(message, attestation)`. There is no
+//! liquidity pool and no bridge fee; the recipient gets exactly the amount
+//! burned.
+//!
+//! One side is always Avalanche (Fuji). The other side is a single remote
+//! chain configured with `BRIDGE_DEST_*` variables; bridging is disabled
+//! until `BRIDGE_DEST_RPC_URL` is set. Wallet keys are plain secp256k1 keys,
+//! so a wallet has the same address on both chains and signs both legs.
+
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+use alloy::{
+    primitives::{Address, Bytes, B256, U256},
+    sol,
+    sol_types::SolCall,
+};
+
+use super::client::AvaxClientError;
+use super::types::{avax_fuji, NetworkConfig};
+
+sol! {
+    interface ITokenMessenger {
+        function depositForBurn(
+            uint256 amount,
+            uint32 destinationDomain,
+            bytes32 mintRecipient,
+            address burnToken
+        ) external returns (uint64 nonce);
+    }
+
+    interface IMessageTransmitter {
+        function receiveMessage(bytes message, bytes attestation) external returns (bool success);
+    }
+}
+
+/// CCTP domain of Avalanche.
+pub const AVALANCHE_DOMAIN: u32 = 1;
+/// Circle's USDC on Fuji.
+pub const FUJI_USDC: &str = "0x5425890298aed601595a70AB815c96711a31Bc65";
+/// CCTP `TokenMessenger` on Fuji.
+pub const FUJI_TOKEN_MESSENGER: &str = "0xeb08f243E5d3FCFF26A9E38Ae5520A669f4019d0";
+/// CCTP `MessageTransmitter` on Fuji.
+pub const FUJI_MESSAGE_TRANSMITTER: &str = "0xa9fB1b3009DCb79E2fe346c16a604B8Fa8aE0a79";
+/// USDC has 6 decimals on every CCTP chain.
+pub const USDC_DECIMALS: u8 = 6;
+
+/// One chain taking part in CCTP transfers.
+#[derive(Debug, Clone)]
+pub struct ChainEndpoint {
+    pub network: NetworkConfig,
+    /// CCTP domain ID (not the EVM chain ID).
+    pub domain: u32,
+    pub usdc: Address,
+    pub token_messenger: Address,
+    pub message_transmitter: Address,
+}
+
+/// Avalanche and the configured remote chain.
+#[derive(Debug, Clone)]
+pub struct BridgeConfig {
+    pub avalanche: ChainEndpoint,
+    pub remote: ChainEndpoint,
+}
+
+static BRIDGE_CONFIG: OnceLock<Option<BridgeConfig>> = OnceLock::new();
+
+impl BridgeConfig {
+    /// The process-wide configuration, or `None` when bridging is disabled.
+    pub fn get() -> Option<&'static BridgeConfig> {
+        BRIDGE_CONFIG
+            .get_or_init(|| {
+                Self::from_lookup(|name| {
+                    std::env::var(name)
+                        .ok()
+                        .map(|v| v.trim().to_string())
+                        .filter(|v| !v.is_empty())
+                })
+                .unwrap_or_else(|e| {
+                    tracing::warn!(error = %e, "Bridge configuration invalid; bridging disabled");
+                    None
+                })
+            })
+            .as_ref()
+    }
+
+    /// Build the configuration from a variable lookup. `Ok(None)` when
+    /// `BRIDGE_DEST_RPC_URL` is unset.
+    pub fn from_lookup(
+        lookup: impl Fn(&str) -> Option<String>,
+    ) -> Result<Option<Self>, AvaxClientError> {
+        let Some(rpc_url) = lookup("BRIDGE_DEST_RPC_URL") else {
+            return Ok(None);
+        };
+        let required = |name: &str| {
+            lookup(name).ok_or_else(|| AvaxClientError::ContractError(format!("{name} is not set")))
+        };
+        let parse = |name: &str, value: String| {
+            Address::from_str(&value)
+                .map_err(|e| AvaxClientError::InvalidAddress(format!("{name}: {e}")))
+        };
+        let fuji_address = |name: &str, default: &str| {
+            parse(name, lookup(name).unwrap_or_else(|| default.to_string()))
+        };
+        let remote_address = |name: &str| parse(name, required(name)?);
+        let number = |name: &str| -> Result<u64, AvaxClientError> {
+            required(name)?
+                .parse()
+                .map_err(|e| AvaxClientError::ContractError(format!("{name}: {e}")))
+        };
+        let leak = |value: String| -> &'static str { Box::leak(value.into_boxed_str()) };
+
+        let avalanche = ChainEndpoint {
+            network: avax_fuji(),
+            domain: AVALANCHE_DOMAIN,
+            usdc: fuji_address("BRIDGE_FUJI_USDC", FUJI_USDC)?,
+            token_messenger: fuji_address("BRIDGE_FUJI_TOKEN_MESSENGER", FUJI_TOKEN_MESSENGER)?,
+            message_transmitter: fuji_address(
+                "BRIDGE_FUJI_MESSAGE_TRANSMITTER",
+                FUJI_MESSAGE_TRANSMITTER,
+            )?,
+        };
+        let remote = ChainEndpoint {
+            network: NetworkConfig {
+                id: "bridge-destination",
+                name: leak(
+                    lookup("BRIDGE_DEST_CHAIN_NAME").unwrap_or_else(|| "Remote chain".to_string()),
+                ),
+                chain_id: number("BRIDGE_DEST_CHAIN_ID")?,
+                rpc_url: leak(rpc_url),
+                explorer_url: leak(
+                    lookup("BRIDGE_DEST_EXPLORER_URL")
+                        .map(|url| url.trim_end_matches('/').to_string())
+                        .unwrap_or_default(),
+                ),
+                native_symbol: "ETH",
+                native_name: "Ether",
+                native_decimals: 18,
+            },
+            domain: number("BRIDGE_DEST_DOMAIN")?.try_into().map_err(|_| {
+                AvaxClientError::ContractError("BRIDGE_DEST_DOMAIN is too large".into())
+            })?,
+            usdc: remote_address("BRIDGE_DEST_USDC")?,
+            token_messenger: remote_address("BRIDGE_DEST_TOKEN_MESSENGER")?,
+            message_transmitter: remote_address("BRIDGE_DEST_MESSAGE_TRANSMITTER")?,
+        };
+        if remote.domain == AVALANCHE_DOMAIN {
+            return Err(AvaxClientError::ContractError(
+                "BRIDGE_DEST_DOMAIN must differ from Avalanche's domain".into(),
+            ));
+        }
+        Ok(Some(Self { avalanche, remote }))
+    }
+
+    /// `(source, destination)` for a transfer leaving Avalanche when
+    /// `outbound`, or arriving on it otherwise.
+    pub fn route(&self, outbound: bool) -> (&ChainEndpoint, &ChainEndpoint) {
+        if outbound {
+            (&self.avalanche, &self.remote)
+        } else {
+            (&self.remote, &self.avalanche)
+        }
+    }
+}
+
+/// CCTP encodes recipients as 32 bytes, left-padded for EVM addresses.
+pub fn mint_recipient(address: Address) -> B256 {
+    address.into_word()
+}
+
+/// Calldata for `TokenMessenger.depositForBurn`.
+pub fn encode_deposit_for_burn(
+    amount: U256,
+    destination_domain: u32,
+    recipient: Address,
+    burn_token: Address,
+) -> Vec<u8> {
+    ITokenMessenger::depositForBurnCall {
+        amount,
+        destinationDomain: destination_domain,
+        mintRecipient: mint_recipient(recipient),
+        burnToken: burn_token,
+    }
+    .abi_encode()
+}
+
+/// Calldata for `MessageTransmitter.receiveMessage`.
+pub fn encode_receive_message(
+    message: &str,
+    attestation: &str,
+) -> Result<Vec<u8>, AvaxClientError> {
+    let decode = |field: &str, value: &str| {
+        Bytes::from_str(value)
+            .map_err(|e| AvaxClientError::ContractError(format!("Invalid CCTP {field}: {e}")))
+    };
+    Ok(IMessageTransmitter::receiveMessageCall {
+        message: decode("message", message)?,
+        attestation: decode("attestation", attestation)?,
+    }
+    .abi_encode())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::address;
+    use std::collections::HashMap;
+
+    fn remote_vars() -> HashMap<&'static str, &'static str> {
+        HashMap::from([
+            ("BRIDGE_DEST_RPC_URL", "https://sepolia.example"),
+            ("BRIDGE_DEST_CHAIN_NAME", "Ethereum Sepolia"),
+            ("BRIDGE_DEST_CHAIN_ID", "11155111"),
+            ("BRIDGE_DEST_DOMAIN", "0"),
+            (
+                "BRIDGE_DEST_USDC",
+                "0x1c7D4B196Cb0C7B01d743Fbc6116a902379C7238",
+            ),
+            (
+                "BRIDGE_DEST_TOKEN_MESSENGER",
+                "0x9f3B8679c73C2Fef8b59B4f3444d4e156fb70AA5",
+            ),
+            (
+                "BRIDGE_DEST_MESSAGE_TRANSMITTER",
+                "0x7865fAfC2db2093669d92c0F33AeEF291086BEFD",
+            ),
+            ("BRIDGE_DEST_EXPLORER_URL", "https://sepolia.etherscan.io/"),
+        ])
+    }
+
+    #[test]
+    fn config_requires_the_remote_rpc_and_contracts() {
+        assert!(BridgeConfig::from_lookup(|_| None).unwrap().is_none());
+
+        let vars = remote_vars();
+        let config = BridgeConfig::from_lookup(|name| vars.get(name).map(|v| v.to_string()))
+            .unwrap()
+            .unwrap();
+        assert_eq!(config.avalanche.domain, AVALANCHE_DOMAIN);
+        assert_eq!(config.remote.domain, 0);
+        assert_eq!(config.remote.network.chain_id, 11155111);
+        assert_eq!(
+            config.remote.network.explorer_url,
+            "https://sepolia.etherscan.io"
+        );
+        let (source, destination) = config.route(false);
+        assert_eq!(source.domain, 0);
+        assert_eq!(destination.domain, AVALANCHE_DOMAIN);
+
+        let mut missing = remote_vars();
+        missing.remove("BRIDGE_DEST_MESSAGE_TRANSMITTER");
+        assert!(
+            BridgeConfig::from_lookup(|name| missing.get(name).map(|v| v.to_string())).is_err()
+        );
+    }
+
+    #[test]
+    fn burn_calldata_pads_the_recipient() {
+        let recipient = address!("0x1111111111111111111111111111111111111111");
+        let calldata = encode_deposit_for_burn(
+            U256::from(1_500_000u64),
+            0,
+            recipient,
+            Address::from_str(FUJI_USDC).unwrap(),
+        );
+        let decoded = ITokenMessenger::depositForBurnCall::abi_decode(&calldata).unwrap();
+        assert_eq!(decoded.amount, U256::from(1_500_000u64));
+        assert_eq!(&decoded.mintRecipient[..12], &[0u8; 12]);
+        assert_eq!(&decoded.mintRecipient[12..], recipient.as_slice());
+
+        assert!(encode_receive_message("0x01", "0x02").is_ok());
+        assert!(encode_receive_message("PENDING", "0x02").is_err());
+    }
+}
@@ -7,16 +7,20 @@
 use std::str::FromStr;
 
 use alloy::{
+    eips::{BlockId, BlockNumberOrTag},
     network::{Ethereum, EthereumWallet},
-    primitives::{Address, U256},
+    primitives::{Address, Bytes, U256},
     providers::{
         fillers::{BlobGasFiller, ChainIdFiller, FillProvider, GasFiller, JoinFill, NonceFiller},
         Identity, Provider, ProviderBuilder, RootProvider,
     },
     signers::local::PrivateKeySigner,
+    sol_types::Eip712Domain,
 };
 
 use super::erc20::Erc20Contract;
+use super::fees::{self, FeeSuggestions};
+use super::nft::{self, NftStandard};
 use super::types::*;
 
 /// HTTP provider type for Avalanche C-Chain (with all fillers).
@@ -41,9 +45,23 @@ pub struct AvaxClient {
 pub struct ReceiptStatus {
     pub block_number: u64,
     pub gas_used: u64,
+    /// Price paid per gas unit, in wei.
+    pub effective_gas_price: u128,
     pub success: bool,
 }
 
+/// On-chain evidence that a transaction was mined.
+#[derive(Debug, Clone)]
+pub struct TransactionInclusion {
+    /// EIP-2718 encoding of the signed transaction; its keccak256 is the
+    /// transaction hash.
+    pub raw_transaction: Bytes,
+    /// Receipt as returned by `eth_getTransactionReceipt`.
+    pub receipt: serde_json::Value,
+    pub success: bool,
+    pub block: BlockReference,
+}
+
 impl AvaxClient {
     /// Create a new client for the specified network.
     pub async fn new(network: NetworkConfig) -> Result<Self, AvaxClientError> {
@@ -62,7 +80,7 @@ impl AvaxClient {
         Self::new(avax_fuji()).await
     }
 
-    /// Get the native AVAX balance for an address.
+    /// Get the native token balance for an address.
     pub async fn get_native_balance(&self, address: &str) -> Result<TokenBalance, AvaxClientError> {
         let addr = Address::from_str(address)
             .map_err(|e| AvaxClientError::InvalidAddress(e.to_string()))?;
@@ -74,11 +92,11 @@ impl AvaxClient {
             .map_err(|e| AvaxClientError::RpcError(e.to_string()))?;
 
         Ok(TokenBalance {
-            symbol: "AVAX".to_string(),
-            name: "Avalanche".to_string(),
+            symbol: self.network.native_symbol.to_string(),
+            name: self.network.native_name.to_string(),
             balance_raw: balance.to_string(),
-            balance_formatted: format_balance(balance, 18),
-            decimals: 18,
+            balance_formatted: format_balance(balance, self.network.native_decimals),
+            decimals: self.network.native_decimals,
             contract_address: None,
         })
     }
@@ -140,6 +158,160 @@ impl AvaxClient {
         })
     }
 
+    /// Get the ERC-20 allowance `owner` has granted `spender`, in raw units.
+    pub async fn get_token_allowance(
+        &self,
+        token_address: &str,
+        owner: &str,
+        spender: &str,
+    ) -> Result<U256, AvaxClientError> {
+        Erc20Contract::new(&self.provider, token_address)?
+            .allowance(owner, spender)
+            .await
+    }
+
+    /// How many of NFT `token_id` of `contract` `owner` holds.
+    pub async fn get_nft_balance(
+        &self,
+        standard: NftStandard,
+        contract: &str,
+        owner: &str,
+        token_id: U256,
+    ) -> Result<U256, AvaxClientError> {
+        let contract = Address::from_str(contract)
+            .map_err(|e| AvaxClientError::InvalidAddress(e.to_string()))?;
+        let owner =
+            Address::from_str(owner).map_err(|e| AvaxClientError::InvalidAddress(e.to_string()))?;
+        nft::balance_of(&self.provider, standard, contract, owner, token_id).await
+    }
+
+    /// Get the decimals of an ERC-20 token.
+    pub async fn get_token_decimals(&self, token_address: &str) -> Result<u8, AvaxClientError> {
+        Erc20Contract::new(&self.provider, token_address)?
+            .decimals()
+            .await
+    }
+
+    /// EIP-712 domain of an EIP-2612 token, or `None` if it has no `permit`.
+    pub async fn get_permit_domain(
+        &self,
+        token: Address,
+    ) -> Result<Option<Eip712Domain>, AvaxClientError> {
+        super::permit::eip2612_domain(&self.provider, token, self.network.chain_id).await
+    }
+
+    /// Current EIP-2612 nonce of `owner` on `token`.
+    pub async fn get_permit_nonce(
+        &self,
+        token: Address,
+        owner: Address,
+    ) -> Result<U256, AvaxClientError> {
+        super::permit::eip2612_nonce(&self.provider, token, owner).await
+    }
+
+    /// Current Permit2 nonce for the (owner, token, spender) allowance.
+    pub async fn get_permit2_nonce(
+        &self,
+        permit2: Address,
+        owner: Address,
+        token: Address,
+        spender: Address,
+    ) -> Result<u64, AvaxClientError> {
+        super::permit::permit2_nonce(&self.provider, permit2, owner, token, spender).await
+    }
+
+    /// Check whether `account` holds `MINTER_ROLE` on the rEUR contract.
+    pub async fn has_minter_role(
+        &self,
+        contract_address: &str,
+        account: &str,
+    ) -> Result<bool, AvaxClientError> {
+        super::minter::has_role(
+            &self.provider,
+            contract_address,
+            super::minter::minter_role(),
+            account,
+        )
+        .await
+    }
+
+    /// Native and token balances of a wallet as of `block`.
+    ///
+    /// Token names and decimals come from the
+    /// [token registry](super::tokens), or from the contract for unlisted
+    /// tokens. Unlike [`Self::get_wallet_balances`], a token whose balance
+    /// cannot be read fails the query: a partial historical balance would
+    /// be misleading.
+    pub async fn get_wallet_balances_at(
+        &self,
+        wallet_address: &str,
+        token_addresses: &[&str],
+        block: u64,
+    ) -> Result<WalletBalanceResponse, AvaxClientError> {
+        let addr = Address::from_str(wallet_address)
+            .map_err(|e| AvaxClientError::InvalidAddress(e.to_string()))?;
+        let native = self
+            .provider
+            .get_balance(addr)
+            .block_id(BlockId::number(block))
+            .await
+            .map_err(|e| AvaxClientError::RpcError(e.to_string()))?;
+
+        let mut token_balances = Vec::with_capacity(token_addresses.len());
+        for &token_address in token_addresses {
+            let contract = Erc20Contract::new(&self.provider, token_address)?;
+            let balance = contract.balance_of_at(wallet_address, block).await?;
+            let (symbol, name, decimals) =
+                match super::tokens::tokens().get(self.network.id, token_address) {
+                    Some(listed) => (listed.symbol, listed.name, listed.decimals),
+                    None => (
+                        contract
+                            .symbol()
+                            .await
+                            .unwrap_or_else(|_| "???".to_string()),
+                        contract
+                            .name()
+                            .await
+                            .unwrap_or_else(|_| "Unknown".to_string()),
+                        contract.decimals().await?,
+                    ),
+                };
+            token_balances.push(TokenBalance {
+                symbol,
+                name,
+                balance_raw: balance.to_string(),
+                balance_formatted: format_balance(balance, decimals),
+                decimals,
+                contract_address: Some(token_address.to_string()),
+            });
+        }
+
+        Ok(WalletBalanceResponse {
+            address: wallet_address.to_string(),
+            network: self.network.name.to_string(),
+            chain_id: self.network.chain_id,
+            native_balance: TokenBalance {
+                symbol: self.network.native_symbol.to_string(),
+                name: self.network.native_name.to_string(),
+                balance_raw: native.to_string(),
+                balance_formatted: format_balance(native, self.network.native_decimals),
+                decimals: self.network.native_decimals,
+                contract_address: None,
+            },
+            token_balances,
+        })
+    }
+
+    /// Unix timestamp of block `number`, or `None` if it does not exist yet.
+    pub async fn get_block_timestamp(&self, number: u64) -> Result<Option<u64>, AvaxClientError> {
+        let block = self
+            .provider
+            .get_block_by_number(BlockNumberOrTag::Number(number))
+            .await
+            .map_err(|e| AvaxClientError::RpcError(e.to_string()))?;
+        Ok(block.map(|block| block.header.timestamp))
+    }
+
     /// Get the current block number.
     pub async fn get_block_number(&self) -> Result<u64, AvaxClientError> {
         self.provider
@@ -148,6 +320,11 @@ impl AvaxClient {
             .map_err(|e| AvaxClientError::RpcError(e.to_string()))
     }
 
+    /// Suggest EIP-1559 fees from recent blocks.
+    pub async fn suggest_fees(&self) -> Result<FeeSuggestions, AvaxClientError> {
+        fees::suggest_fees(&self.provider).await
+    }
+
     /// Query transaction receipt status.
     pub async fn get_transaction_receipt_status(
         &self,
@@ -166,10 +343,64 @@ impl AvaxClient {
         Ok(receipt.map(|value| ReceiptStatus {
             block_number: value.block_number.unwrap_or(0),
             gas_used: value.gas_used,
+            effective_gas_price: value.effective_gas_price,
             success: value.status(),
         }))
     }
 
+    /// Fetch the signed transaction, its receipt and its block header.
+    ///
+    /// Returns `None` while the transaction is not mined.
+    pub async fn get_transaction_inclusion(
+        &self,
+        tx_hash: &str,
+    ) -> Result<Option<TransactionInclusion>, AvaxClientError> {
+        let hash = tx_hash
+            .parse()
+            .map_err(|e| AvaxClientError::InvalidAddress(format!("Invalid tx hash: {e}")))?;
+
+        let Some(receipt) = self
+            .provider
+            .get_transaction_receipt(hash)
+            .await
+            .map_err(|e| AvaxClientError::RpcError(format!("Failed to get receipt: {e}")))?
+        else {
+            return Ok(None);
+        };
+        let Some(block_hash) = receipt.block_hash else {
+            return Ok(None);
+        };
+        let raw_transaction = self
+            .provider
+            .get_raw_transaction_by_hash(hash)
+            .await
+            .map_err(|e| AvaxClientError::RpcError(format!("Failed to get transaction: {e}")))?
+            .ok_or_else(|| AvaxClientError::RpcError("Node has no raw transaction".to_string()))?;
+        let block = self
+            .provider
+            .get_block_by_hash(block_hash)
+            .await
+            .map_err(|e| AvaxClientError::RpcError(format!("Failed to get block: {e}")))?
+            .ok_or_else(|| AvaxClientError::RpcError("Block not found".to_string()))?;
+        let header = &block.header;
+
+        Ok(Some(TransactionInclusion {
+            raw_transaction,
+            success: receipt.status(),
+            receipt: serde_json::to_value(&receipt)
+                .map_err(|e| AvaxClientError::RpcError(format!("Invalid receipt: {e}")))?,
+            block: BlockReference {
+                number: header.number,
+                hash: format!("{:?}", header.hash),
+                parent_hash: format!("{:?}", header.parent_hash),
+                timestamp: header.timestamp,
+                state_root: format!("{:?}", header.state_root),
+                transactions_root: format!("{:?}", header.transactions_root),
+                receipts_root: format!("{:?}", header.receipts_root),
+            },
+        }))
+    }
+
     /// Create a signer from a private key (hex string without 0x prefix).
     ///
     /// # Arguments
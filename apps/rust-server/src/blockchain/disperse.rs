@@ -0,0 +1,84 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Calldata for the Disperse contract (disperse.app).
+//!
+//! `disperseToken` pulls the total from the caller with `transferFrom` and
+//! pays each recipient in one transaction, so the sender must first approve
+//! the disperse contract for at least the batch total.
+
+use std::str::FromStr;
+
+use alloy::{
+    primitives::{Address, U256},
+    sol,
+    sol_types::SolCall,
+};
+
+use super::client::AvaxClientError;
+
+sol! {
+    interface IDisperse {
+        function disperseToken(address token, address[] recipients, uint256[] values) external;
+    }
+}
+
+/// ABI-encode `disperseToken(token, recipients, values)` calldata.
+pub fn encode_disperse_token_call(
+    token: &str,
+    payouts: &[(String, U256)],
+) -> Result<Vec<u8>, AvaxClientError> {
+    let token = Address::from_str(token)
+        .map_err(|e| AvaxClientError::InvalidAddress(format!("Invalid token address: {e}")))?;
+    let recipients = payouts
+        .iter()
+        .map(|(to, _)| Address::from_str(to))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| AvaxClientError::InvalidAddress(e.to_string()))?;
+    let values = payouts.iter().map(|(_, value)| *value).collect();
+
+    Ok(IDisperse::disperseTokenCall {
+        token,
+        recipients,
+        values,
+    }
+    .abi_encode())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::keccak256;
+
+    #[test]
+    fn disperse_call_uses_disperse_token_selector() {
+        let data = encode_disperse_token_call(
+            "0x1111111111111111111111111111111111111111",
+            &[
+                (
+                    "0x2222222222222222222222222222222222222222".to_string(),
+                    U256::from(1u64),
+                ),
+                (
+                    "0x3333333333333333333333333333333333333333".to_string(),
+                    U256::from(2u64),
+                ),
+            ],
+        )
+        .unwrap();
+        let selector = keccak256("disperseToken(address,address[],uint256[])");
+        assert_eq!(&data[..4], &selector[..4]);
+        // token + two offsets + (length + 2 items) for each array.
+        assert_eq!(data.len(), 4 + 32 * 3 + 32 * 3 * 2);
+    }
+
+    #[test]
+    fn disperse_call_rejects_bad_recipient() {
+        let result = encode_disperse_token_call(
+            "0x1111111111111111111111111111111111111111",
+            &[("nope".to_string(), U256::from(1u64))],
+        );
+        assert!(result.is_err());
+    }
+}
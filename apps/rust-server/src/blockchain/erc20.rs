@@ -7,9 +7,11 @@
 use std::str::FromStr;
 
 use alloy::{
+    eips::BlockId,
     primitives::{Address, U256},
     providers::Provider,
     sol,
+    sol_types::SolCall,
 };
 
 use super::client::AvaxClientError;
@@ -81,6 +83,19 @@ impl<P: Provider + Clone> Erc20Contract<P> {
         Ok(result)
     }
 
+    /// Get how much `spender` may transfer on behalf of `owner`, in raw units.
+    pub async fn allowance(&self, owner: &str, spender: &str) -> Result<U256, AvaxClientError> {
+        let owner =
+            Address::from_str(owner).map_err(|e| AvaxClientError::InvalidAddress(e.to_string()))?;
+        let spender = Address::from_str(spender)
+            .map_err(|e| AvaxClientError::InvalidAddress(e.to_string()))?;
+        self.contract
+            .allowance(owner, spender)
+            .call()
+            .await
+            .map_err(|e| AvaxClientError::ContractError(e.to_string()))
+    }
+
     /// Get the balance of an address.
     pub async fn balance_of(&self, wallet_address: &str) -> Result<TokenBalance, AvaxClientError> {
         let addr = Address::from_str(wallet_address)
@@ -109,6 +124,42 @@ impl<P: Provider + Clone> Erc20Contract<P> {
     }
 }
 
+impl<P: Provider + Clone> Erc20Contract<P> {
+    /// Raw balance of an address as of `block`. Needs an archive node for
+    /// blocks the RPC has pruned.
+    pub async fn balance_of_at(
+        &self,
+        wallet_address: &str,
+        block: u64,
+    ) -> Result<U256, AvaxClientError> {
+        let addr = Address::from_str(wallet_address)
+            .map_err(|e| AvaxClientError::InvalidAddress(e.to_string()))?;
+        self.contract
+            .balanceOf(addr)
+            .call()
+            .block(BlockId::number(block))
+            .await
+            .map_err(|e| AvaxClientError::ContractError(e.to_string()))
+    }
+}
+
+/// Encode `approve(spender, amount)` call data.
+///
+/// An `amount` of zero revokes the allowance.
+pub fn approve_calldata(spender: &str, amount: U256) -> Result<Vec<u8>, AvaxClientError> {
+    let spender = Address::from_str(spender)
+        .map_err(|e| AvaxClientError::InvalidAddress(format!("Invalid spender address: {}", e)))?;
+    Ok(IERC20::approveCall { spender, amount }.abi_encode())
+}
+
+/// Whether an allowance is effectively unlimited.
+///
+/// Wallets approve `U256::MAX` for "unlimited"; some tokens decrement it on
+/// every `transferFrom`, so anything at or above half of it counts.
+pub fn is_unlimited_allowance(amount: U256) -> bool {
+    amount >= U256::MAX >> 1
+}
+
 /// Format a token balance with the specified decimals.
 fn format_token_balance(balance: U256, decimals: u8) -> String {
     if balance.is_zero() {
@@ -131,3 +182,28 @@ fn format_token_balance(balance: U256, decimals: u8) -> String {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn approve_calldata_encodes_selector_and_arguments() {
+        let data =
+            approve_calldata("0x2222222222222222222222222222222222222222", U256::from(5)).unwrap();
+        assert_eq!(data.len(), 4 + 32 + 32);
+        assert_eq!(&data[..4], &[0x09, 0x5e, 0xa7, 0xb3]);
+        assert_eq!(&data[16..36], &[0x22; 20]);
+        assert_eq!(data[67], 5);
+        assert!(approve_calldata("0xnope", U256::ZERO).is_err());
+    }
+
+    #[test]
+    fn decremented_max_allowance_is_still_unlimited() {
+        assert!(is_unlimited_allowance(U256::MAX));
+        assert!(is_unlimited_allowance(
+            U256::MAX - U256::from(10u64).pow(U256::from(24))
+        ));
+        assert!(!is_unlimited_allowance(U256::from(1_000_000u64)));
+    }
+}
@@ -0,0 +1,179 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! EIP-1559 fee suggestions from recent blocks.
+//!
+//! [`suggest_fees`] reads `eth_feeHistory` for the last
+//! [`FEE_HISTORY_BLOCKS`] blocks and turns it into three [`FeeSpeed`]
+//! tiers. The tip for each tier is the median across blocks of the 25th,
+//! 50th and 75th percentile tip paid within each block. The max fee leaves
+//! room for the base fee to double before inclusion, the same headroom
+//! [`super::TxBuilder`] uses.
+
+use alloy::{eips::BlockNumberOrTag, providers::Provider};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::client::AvaxClientError;
+
+/// Number of recent blocks sampled for a suggestion.
+pub const FEE_HISTORY_BLOCKS: u64 = 20;
+
+/// Tip percentiles requested per block: slow, normal, fast.
+pub const REWARD_PERCENTILES: [f64; 3] = [25.0, 50.0, 75.0];
+
+/// Tip used when the node reports no tip history (2.5 gwei).
+pub const DEFAULT_PRIORITY_FEE: u128 = 2_500_000_000;
+
+/// Base fee assumed when the node reports none (25 gwei).
+pub const DEFAULT_BASE_FEE: u128 = 25_000_000_000;
+
+/// How quickly a transaction should be included.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum FeeSpeed {
+    Slow,
+    #[default]
+    Normal,
+    Fast,
+}
+
+/// Fee fields for one speed, in wei.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FeeSuggestion {
+    pub max_fee_per_gas: u128,
+    pub max_priority_fee_per_gas: u128,
+}
+
+/// Suggestions for every speed, derived from one fee history sample.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeeSuggestions {
+    /// Newest block in the sample.
+    pub block_number: u64,
+    /// Base fee of the next block, in wei.
+    pub base_fee_per_gas: u128,
+    pub slow: FeeSuggestion,
+    pub normal: FeeSuggestion,
+    pub fast: FeeSuggestion,
+}
+
+impl FeeSuggestions {
+    /// Suggestion for `speed`.
+    pub fn for_speed(&self, speed: FeeSpeed) -> FeeSuggestion {
+        match speed {
+            FeeSpeed::Slow => self.slow,
+            FeeSpeed::Normal => self.normal,
+            FeeSpeed::Fast => self.fast,
+        }
+    }
+}
+
+/// Max fee for a tip, allowing the base fee to double.
+pub fn max_fee_for(base_fee: u128, priority_fee: u128) -> u128 {
+    base_fee.saturating_mul(2).saturating_add(priority_fee)
+}
+
+fn median(values: &mut [u128]) -> Option<u128> {
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_unstable();
+    Some(values[values.len() / 2])
+}
+
+/// Build suggestions from fee history.
+///
+/// `base_fees` has one entry per sampled block plus the next block's base
+/// fee last, as `eth_feeHistory` returns it. `rewards` has one row per
+/// block with the tips at [`REWARD_PERCENTILES`]. Tips never decrease from
+/// slow to fast.
+pub fn suggest_from_history(
+    newest_block: u64,
+    base_fees: &[u128],
+    rewards: &[Vec<u128>],
+) -> FeeSuggestions {
+    let base_fee = base_fees.last().copied().unwrap_or(DEFAULT_BASE_FEE);
+    let mut tips = [0u128; 3];
+    for (i, tip) in tips.iter_mut().enumerate() {
+        let mut column: Vec<u128> = rewards
+            .iter()
+            .filter_map(|row| row.get(i).copied())
+            .collect();
+        *tip = median(&mut column).unwrap_or(DEFAULT_PRIORITY_FEE);
+    }
+    tips[1] = tips[1].max(tips[0]);
+    tips[2] = tips[2].max(tips[1]);
+
+    let suggestion = |tip: u128| FeeSuggestion {
+        max_fee_per_gas: max_fee_for(base_fee, tip),
+        max_priority_fee_per_gas: tip,
+    };
+    FeeSuggestions {
+        block_number: newest_block,
+        base_fee_per_gas: base_fee,
+        slow: suggestion(tips[0]),
+        normal: suggestion(tips[1]),
+        fast: suggestion(tips[2]),
+    }
+}
+
+/// Sample recent blocks from `provider` and suggest fees.
+pub async fn suggest_fees<P: Provider>(provider: &P) -> Result<FeeSuggestions, AvaxClientError> {
+    let history = provider
+        .get_fee_history(
+            FEE_HISTORY_BLOCKS,
+            BlockNumberOrTag::Latest,
+            &REWARD_PERCENTILES,
+        )
+        .await
+        .map_err(|e| AvaxClientError::RpcError(format!("Failed to get fee history: {}", e)))?;
+
+    let sampled = history.base_fee_per_gas.len().saturating_sub(1) as u64;
+    let newest_block = (history.oldest_block + sampled).saturating_sub(1);
+    Ok(suggest_from_history(
+        newest_block,
+        &history.base_fee_per_gas,
+        history.reward.as_deref().unwrap_or_default(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GWEI: u128 = 1_000_000_000;
+
+    #[test]
+    fn suggestions_take_the_median_tip_per_percentile() {
+        let base_fees = [30 * GWEI, 28 * GWEI, 26 * GWEI, 27 * GWEI];
+        let rewards = vec![
+            vec![GWEI, 2 * GWEI, 3 * GWEI],
+            vec![0, 3 * GWEI, 9 * GWEI],
+            vec![GWEI, GWEI, 4 * GWEI],
+        ];
+        let fees = suggest_from_history(100, &base_fees, &rewards);
+
+        assert_eq!(fees.block_number, 100);
+        assert_eq!(fees.base_fee_per_gas, 27 * GWEI);
+        assert_eq!(fees.slow.max_priority_fee_per_gas, GWEI);
+        assert_eq!(fees.normal.max_priority_fee_per_gas, 2 * GWEI);
+        assert_eq!(fees.fast.max_priority_fee_per_gas, 4 * GWEI);
+        assert_eq!(fees.fast.max_fee_per_gas, 58 * GWEI);
+        assert_eq!(fees.for_speed(FeeSpeed::Normal), fees.normal);
+    }
+
+    #[test]
+    fn empty_history_falls_back_to_defaults() {
+        let fees = suggest_from_history(5, &[], &[]);
+        assert_eq!(fees.base_fee_per_gas, DEFAULT_BASE_FEE);
+        for speed in [FeeSpeed::Slow, FeeSpeed::Normal, FeeSpeed::Fast] {
+            let fee = fees.for_speed(speed);
+            assert_eq!(fee.max_priority_fee_per_gas, DEFAULT_PRIORITY_FEE);
+            assert_eq!(
+                fee.max_fee_per_gas,
+                max_fee_for(DEFAULT_BASE_FEE, DEFAULT_PRIORITY_FEE)
+            );
+        }
+    }
+}
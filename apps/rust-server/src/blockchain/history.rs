@@ -0,0 +1,185 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Balances as of a past block.
+//!
+//! Reading state at an old block needs an archive RPC; nodes that prune
+//! state answer with an error for anything but recent blocks. A timestamp
+//! resolves to the last block mined at or before it by binary search over
+//! block timestamps, about 25 header reads on Fuji.
+//!
+//! Blocks at least [`FINAL_DEPTH`] below the head cannot change, so their
+//! timestamps and the balances read at them are cached in-process. The
+//! caches are bounded and simply cleared when full. [`HistoricalBalances`]
+//! subscribes the balance cache to the [`CacheBus`](crate::storage::CacheBus),
+//! so balances re-read after the indexer stores a transfer for the address.
+
+use std::{
+    collections::HashMap,
+    future::Future,
+    sync::{Mutex, OnceLock},
+};
+
+use super::{client::AvaxClientError, AvaxClient, WalletBalanceResponse};
+use crate::storage::{CacheResource, CacheSubscriber};
+
+/// Blocks this far below the head are treated as final.
+pub const FINAL_DEPTH: u64 = 12;
+
+/// Entries kept per cache before it is cleared.
+const MAX_CACHED: usize = 4096;
+
+/// (network, block number)
+type BlockKey = (String, u64);
+/// (network, lowercase address, block number, sorted lowercase tokens)
+type BalanceKey = (String, String, u64, Vec<String>);
+
+static BLOCK_TIMES: OnceLock<Mutex<HashMap<BlockKey, u64>>> = OnceLock::new();
+static BALANCES: OnceLock<Mutex<HashMap<BalanceKey, WalletBalanceResponse>>> = OnceLock::new();
+
+fn lookup<K: Eq + std::hash::Hash, V: Clone>(
+    cache: &'static OnceLock<Mutex<HashMap<K, V>>>,
+    key: &K,
+) -> Option<V> {
+    let cache = cache.get_or_init(Default::default);
+    let cache = cache.lock().unwrap_or_else(|e| e.into_inner());
+    cache.get(key).cloned()
+}
+
+fn store<K: Eq + std::hash::Hash, V>(
+    cache: &'static OnceLock<Mutex<HashMap<K, V>>>,
+    key: K,
+    value: V,
+) {
+    let cache = cache.get_or_init(Default::default);
+    let mut cache = cache.lock().unwrap_or_else(|e| e.into_inner());
+    if cache.len() >= MAX_CACHED {
+        cache.clear();
+    }
+    cache.insert(key, value);
+}
+
+/// The historical balance cache, as a [`CacheSubscriber`].
+pub struct HistoricalBalances;
+
+impl CacheSubscriber for HistoricalBalances {
+    fn invalidate(&self, address: &str, resource: CacheResource) {
+        if resource != CacheResource::Balance {
+            return;
+        }
+        let address = address.to_lowercase();
+        if let Some(cache) = BALANCES.get() {
+            cache
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .retain(|(_, cached, _, _), _| *cached != address);
+        }
+    }
+}
+
+fn is_final(block: u64, head: u64) -> bool {
+    head.saturating_sub(block) >= FINAL_DEPTH
+}
+
+/// Unix timestamp of block `number` on `network`.
+pub async fn block_timestamp(
+    client: &AvaxClient,
+    network: &str,
+    number: u64,
+    head: u64,
+) -> Result<u64, AvaxClientError> {
+    let key = (network.to_string(), number);
+    if let Some(timestamp) = lookup(&BLOCK_TIMES, &key) {
+        return Ok(timestamp);
+    }
+    let timestamp = client
+        .get_block_timestamp(number)
+        .await?
+        .ok_or_else(|| AvaxClientError::RpcError(format!("block {number} not found")))?;
+    if is_final(number, head) {
+        store(&BLOCK_TIMES, key, timestamp);
+    }
+    Ok(timestamp)
+}
+
+/// Last block in `0..=head` whose timestamp is at or before `timestamp`,
+/// or `None` if the chain started later.
+pub async fn last_block_at<F, Fut, E>(
+    timestamp: u64,
+    head: u64,
+    mut block_time: F,
+) -> Result<Option<u64>, E>
+where
+    F: FnMut(u64) -> Fut,
+    Fut: Future<Output = Result<u64, E>>,
+{
+    if block_time(0).await? > timestamp {
+        return Ok(None);
+    }
+    let (mut low, mut high) = (0, head);
+    while low < high {
+        let mid = low + (high - low).div_ceil(2);
+        if block_time(mid).await? <= timestamp {
+            low = mid;
+        } else {
+            high = mid - 1;
+        }
+    }
+    Ok(Some(low))
+}
+
+/// Balances of `address` as of `block`, from cache when the block is final.
+pub async fn balances_at(
+    client: &AvaxClient,
+    network: &str,
+    address: &str,
+    tokens: &[&str],
+    block: u64,
+    head: u64,
+) -> Result<WalletBalanceResponse, AvaxClientError> {
+    let mut token_key: Vec<String> = tokens.iter().map(|t| t.to_lowercase()).collect();
+    token_key.sort();
+    let key = (
+        network.to_string(),
+        address.to_lowercase(),
+        block,
+        token_key,
+    );
+    if let Some(balance) = lookup(&BALANCES, &key) {
+        return Ok(balance);
+    }
+    let balance = client
+        .get_wallet_balances_at(address, tokens, block)
+        .await?;
+    if is_final(block, head) {
+        store(&BALANCES, key, balance.clone());
+    }
+    Ok(balance)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::convert::Infallible;
+
+    #[tokio::test]
+    async fn timestamps_resolve_to_the_last_block_at_or_before_them() {
+        // Blocks 0..=9 mined every 2 seconds from t=100, with two in the
+        // same second at the end.
+        let times = [100, 102, 104, 106, 108, 110, 112, 114, 116, 116];
+        let find = |timestamp| {
+            last_block_at(timestamp, 9, |n| async move {
+                Ok::<_, Infallible>(times[n as usize])
+            })
+        };
+
+        assert_eq!(find(99).await.unwrap(), None);
+        assert_eq!(find(100).await.unwrap(), Some(0));
+        assert_eq!(find(105).await.unwrap(), Some(2));
+        assert_eq!(find(106).await.unwrap(), Some(3));
+        assert_eq!(find(116).await.unwrap(), Some(9));
+        assert_eq!(find(1_000).await.unwrap(), Some(9));
+        assert!(is_final(10, 22) && !is_final(11, 22));
+    }
+}
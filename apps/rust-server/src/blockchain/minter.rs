@@ -0,0 +1,89 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! rEUR minting and `AccessControl` role checks.
+//!
+//! `RelationalEuro.mint` is guarded by `onlyRole(MINTER_ROLE)`. Sending a mint
+//! from an account without the role reverts with an undecoded custom error, so
+//! callers should check the role with a static `hasRole` call first.
+
+use std::str::FromStr;
+
+use alloy::{
+    primitives::{keccak256, Address, B256, U256},
+    providers::Provider,
+    sol,
+    sol_types::SolCall,
+};
+
+use super::client::AvaxClientError;
+
+sol! {
+    #[sol(rpc)]
+    interface IRelationalEuro {
+        function hasRole(bytes32 role, address account) external view returns (bool);
+        function mint(address to, uint256 amount) external;
+    }
+}
+
+/// `keccak256("MINTER_ROLE")`, as defined in `RelationalEuro.sol`.
+pub fn minter_role() -> B256 {
+    keccak256("MINTER_ROLE")
+}
+
+/// Static call to `hasRole(role, account)` on an `AccessControl` contract.
+pub async fn has_role<P: Provider + Clone>(
+    provider: &P,
+    contract_address: &str,
+    role: B256,
+    account: &str,
+) -> Result<bool, AvaxClientError> {
+    let contract = Address::from_str(contract_address)
+        .map_err(|e| AvaxClientError::InvalidAddress(format!("Invalid contract address: {e}")))?;
+    let account =
+        Address::from_str(account).map_err(|e| AvaxClientError::InvalidAddress(e.to_string()))?;
+
+    IRelationalEuro::new(contract, provider.clone())
+        .hasRole(role, account)
+        .call()
+        .await
+        .map_err(|e| AvaxClientError::ContractError(format!("hasRole call failed: {e}")))
+}
+
+/// ABI-encode `mint(to, amount)` calldata.
+pub fn encode_mint_call(to: &str, amount: U256) -> Result<Vec<u8>, AvaxClientError> {
+    let to = Address::from_str(to).map_err(|e| AvaxClientError::InvalidAddress(e.to_string()))?;
+    Ok(IRelationalEuro::mintCall { to, amount }.abi_encode())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn minter_role_matches_solidity_constant() {
+        // cast keccak "MINTER_ROLE"
+        assert_eq!(
+            format!("{:#x}", minter_role()),
+            "0x9f2df0fed2c77648de5860a4cc508cd0818c85b8b8a1ab4ceeef8d981c8956a6"
+        );
+    }
+
+    #[test]
+    fn mint_call_uses_mint_selector() {
+        let data = encode_mint_call(
+            "0x1111111111111111111111111111111111111111",
+            U256::from(1_000_000u64),
+        )
+        .unwrap();
+        // bytes4(keccak256("mint(address,uint256)"))
+        assert_eq!(&data[..4], &[0x40, 0xc1, 0x0f, 0x19]);
+        assert_eq!(data.len(), 4 + 32 + 32);
+    }
+
+    #[test]
+    fn mint_call_rejects_bad_address() {
+        assert!(encode_mint_call("not-an-address", U256::ZERO).is_err());
+    }
+}
@@ -6,17 +6,38 @@
 //!
 //! This module provides functionality for:
 //! - Querying native AVAX balances
-//! - Querying ERC-20 token balances (rEUR)
+//! - Querying ERC-20 token balances (rEUR), also as of a past block
 //! - Transaction signing and broadcasting
-//! - Gas estimation
+//! - Gas estimation and EIP-1559 fee suggestions
+//! - ERC-4337 smart accounts (UserOperations via a bundler)
+//! - EIP-2612 / Permit2 signed token approvals
+//! - CCTP USDC bridging to one remote chain
+//! - A runtime registry of further EVM networks for plain transfers
+//! - A runtime registry of listed ERC-20 tokens
+//! - ERC-721 / ERC-1155 NFT holdings and sends for listed collections
 
+pub mod address;
+pub mod bridge;
 pub mod client;
+pub mod disperse;
 pub mod erc20;
+pub mod fees;
+pub mod history;
+pub mod minter;
+pub mod network;
+pub mod nft;
+pub mod permit;
 pub mod signing;
+pub mod smart_account;
+pub mod tokens;
 pub mod transactions;
 pub mod types;
 
+pub use address::{address_key, checksum_address, same_address};
 pub use client::AvaxClient;
+pub use fees::FeeSpeed;
+pub use network::{networks, resolve_network};
 pub use signing::wallet_from_pem;
-pub use transactions::{format_amount, parse_amount, TxBuilder};
+pub use tokens::{tokens, ListedToken, TokenSource};
+pub use transactions::{format_amount, parse_amount, ReplacementKind, TxBuilder};
 pub use types::*;
@@ -0,0 +1,296 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Runtime registry of the EVM networks the server can use.
+//!
+//! Fuji is always registered. Further chains come from `EVM_NETWORKS`, a
+//! JSON array, or the JSON file named by `EVM_NETWORKS_FILE`:
+//!
+//! ```json
+//! [{
+//!   "id": "base-sepolia",
+//!   "name": "Base Sepolia",
+//!   "chain_id": 84532,
+//!   "rpc_url": "https://sepolia.base.org",
+//!   "explorer_url": "https://sepolia.basescan.org",
+//!   "native_symbol": "ETH",
+//!   "native_decimals": 18,
+//!   "index_tokens": []
+//! }]
+//! ```
+//!
+//! Handlers look networks up by `id`, the value clients send as `network`.
+//! ERC-20 transfers of the `index_tokens` contracts are indexed per network.
+
+use std::sync::OnceLock;
+
+use alloy::primitives::Address;
+use serde::Deserialize;
+
+use super::types::{avax_fuji, NetworkConfig, NETWORK_FUJI, REUR_TOKEN};
+
+/// One network as configured.
+#[derive(Debug, Deserialize)]
+struct NetworkEntry {
+    id: String,
+    name: String,
+    chain_id: u64,
+    rpc_url: String,
+    #[serde(default)]
+    explorer_url: String,
+    native_symbol: String,
+    #[serde(default)]
+    native_name: Option<String>,
+    #[serde(default = "default_native_decimals")]
+    native_decimals: u8,
+    /// ERC-20 contracts whose transfers are indexed.
+    #[serde(default)]
+    index_tokens: Vec<String>,
+}
+
+fn default_native_decimals() -> u8 {
+    18
+}
+
+/// A registered network and the token contracts indexed on it.
+#[derive(Debug, Clone)]
+pub struct RegisteredNetwork {
+    pub config: NetworkConfig,
+    pub index_tokens: Vec<Address>,
+}
+
+/// Networks available to this deployment.
+#[derive(Debug, Clone)]
+pub struct NetworkRegistry {
+    networks: Vec<RegisteredNetwork>,
+}
+
+fn is_valid_network_id(id: &str) -> bool {
+    !id.is_empty()
+        && id.len() <= 32
+        && id
+            .bytes()
+            .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-')
+}
+
+impl NetworkRegistry {
+    /// Fuji only.
+    pub fn fuji_only() -> Self {
+        let index_tokens = REUR_TOKEN
+            .fuji_address
+            .and_then(|addr| addr.parse().ok())
+            .into_iter()
+            .collect();
+        Self {
+            networks: vec![RegisteredNetwork {
+                config: avax_fuji(),
+                index_tokens,
+            }],
+        }
+    }
+
+    /// Build the registry from a variable lookup and a file reader.
+    pub fn from_lookup(
+        lookup: impl Fn(&str) -> Option<String>,
+        read_file: impl Fn(&str) -> std::io::Result<String>,
+    ) -> Result<Self, String> {
+        let mut registry = Self::fuji_only();
+        let mut sources = Vec::new();
+        if let Some(inline) = lookup("EVM_NETWORKS").filter(|v| !v.trim().is_empty()) {
+            sources.push(("EVM_NETWORKS".to_string(), inline));
+        }
+        if let Some(path) = lookup("EVM_NETWORKS_FILE").filter(|v| !v.trim().is_empty()) {
+            let contents =
+                read_file(&path).map_err(|e| format!("EVM_NETWORKS_FILE {path}: {e}"))?;
+            sources.push((format!("EVM_NETWORKS_FILE {path}"), contents));
+        }
+        for (source, json) in sources {
+            let entries: Vec<NetworkEntry> =
+                serde_json::from_str(&json).map_err(|e| format!("{source}: {e}"))?;
+            for entry in entries {
+                registry.add(entry).map_err(|e| format!("{source}: {e}"))?;
+            }
+        }
+        Ok(registry)
+    }
+
+    /// Registry from the environment.
+    pub fn from_env() -> Result<Self, String> {
+        Self::from_lookup(
+            |name| std::env::var(name).ok(),
+            |p| std::fs::read_to_string(p),
+        )
+    }
+
+    fn add(&mut self, entry: NetworkEntry) -> Result<(), String> {
+        let id = entry.id.trim().to_ascii_lowercase();
+        if !is_valid_network_id(&id) {
+            return Err(format!(
+                "network id `{}` must be 1-32 characters of a-z, 0-9 and -",
+                entry.id
+            ));
+        }
+        if self.get(&id).is_some() {
+            return Err(format!("network `{id}` is defined twice"));
+        }
+        if let Some(other) = self
+            .networks
+            .iter()
+            .find(|n| n.config.chain_id == entry.chain_id)
+        {
+            return Err(format!(
+                "network `{id}` reuses chain ID {} of `{}`",
+                entry.chain_id, other.config.id
+            ));
+        }
+        let rpc_url: url::Url = entry
+            .rpc_url
+            .parse()
+            .map_err(|e| format!("network `{id}` rpc_url: {e}"))?;
+        if !matches!(rpc_url.scheme(), "http" | "https") {
+            return Err(format!("network `{id}` rpc_url must be http(s)"));
+        }
+        let index_tokens = entry
+            .index_tokens
+            .iter()
+            .map(|token| {
+                token
+                    .parse::<Address>()
+                    .map_err(|e| format!("network `{id}` index token {token}: {e}"))
+            })
+            .collect::<Result<_, _>>()?;
+
+        let leak = |value: String| -> &'static str { Box::leak(value.into_boxed_str()) };
+        let native_name = entry
+            .native_name
+            .unwrap_or_else(|| entry.native_symbol.clone());
+        self.networks.push(RegisteredNetwork {
+            config: NetworkConfig {
+                id: leak(id),
+                name: leak(entry.name),
+                chain_id: entry.chain_id,
+                rpc_url: leak(entry.rpc_url),
+                explorer_url: leak(entry.explorer_url.trim_end_matches('/').to_string()),
+                native_symbol: leak(entry.native_symbol),
+                native_name: leak(native_name),
+                native_decimals: entry.native_decimals,
+            },
+            index_tokens,
+        });
+        Ok(())
+    }
+
+    /// Network registered as `id` (case-insensitive).
+    pub fn get(&self, id: &str) -> Option<&NetworkConfig> {
+        self.networks
+            .iter()
+            .map(|n| &n.config)
+            .find(|config| config.id.eq_ignore_ascii_case(id.trim()))
+    }
+
+    /// Network named by request input, defaulting to Fuji.
+    pub fn resolve(&self, raw: Option<&str>) -> Result<&NetworkConfig, String> {
+        let id = raw.unwrap_or(NETWORK_FUJI);
+        self.get(id).ok_or_else(|| {
+            let known: Vec<_> = self.networks.iter().map(|n| n.config.id).collect();
+            format!(
+                "Unknown network `{}`. Supported networks: {}.",
+                id.trim(),
+                known.join(", ")
+            )
+        })
+    }
+
+    /// All registered networks, Fuji first.
+    pub fn all(&self) -> &[RegisteredNetwork] {
+        &self.networks
+    }
+}
+
+static NETWORKS: OnceLock<NetworkRegistry> = OnceLock::new();
+
+/// Install the registry built at startup. Fails if one is already in use.
+pub fn install_networks(registry: NetworkRegistry) -> Result<(), NetworkRegistry> {
+    NETWORKS.set(registry)
+}
+
+/// The deployment's networks; Fuji only until [`install_networks`] runs.
+pub fn networks() -> &'static NetworkRegistry {
+    NETWORKS.get_or_init(NetworkRegistry::fuji_only)
+}
+
+/// Look up the network named by request input, defaulting to Fuji.
+pub fn resolve_network(raw: Option<&str>) -> Result<NetworkConfig, String> {
+    networks().resolve(raw).cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    const BASE_SEPOLIA: &str = r#"[{
+        "id": "base-sepolia",
+        "name": "Base Sepolia",
+        "chain_id": 84532,
+        "rpc_url": "https://sepolia.base.org",
+        "explorer_url": "https://sepolia.basescan.org/",
+        "native_symbol": "ETH"
+    }]"#;
+
+    fn registry(vars: &[(&str, &str)]) -> Result<NetworkRegistry, String> {
+        let vars: HashMap<_, _> = vars.iter().copied().collect();
+        NetworkRegistry::from_lookup(
+            |name| vars.get(name).map(|v| v.to_string()),
+            |_| Ok(BASE_SEPOLIA.to_string()),
+        )
+    }
+
+    #[test]
+    fn configured_networks_are_added_after_fuji() {
+        let registry = registry(&[("EVM_NETWORKS", BASE_SEPOLIA)]).unwrap();
+        assert_eq!(registry.all().len(), 2);
+        assert_eq!(registry.resolve(None).unwrap().id, NETWORK_FUJI);
+        assert_eq!(registry.all()[0].index_tokens.len(), 1);
+
+        let base = registry.resolve(Some("Base-Sepolia")).unwrap();
+        assert_eq!(base.chain_id, 84532);
+        assert_eq!(base.native_symbol, "ETH");
+        assert_eq!(base.native_name, "ETH");
+        assert_eq!(base.native_decimals, 18);
+        assert_eq!(base.explorer_url, "https://sepolia.basescan.org");
+
+        let err = registry.resolve(Some("mainnet")).unwrap_err();
+        assert!(err.contains("fuji, base-sepolia"), "{err}");
+    }
+
+    #[test]
+    fn config_file_is_read_and_conflicts_are_rejected() {
+        let registry = registry(&[("EVM_NETWORKS_FILE", "/etc/networks.json")]).unwrap();
+        assert!(registry.get("base-sepolia").is_some());
+
+        let twice = registry_with_both();
+        assert!(twice.unwrap_err().contains("defined twice"));
+
+        let fuji_chain = r#"[{"id": "fuji-2", "name": "Fuji", "chain_id": 43113,
+            "rpc_url": "https://example.org", "native_symbol": "AVAX"}]"#;
+        assert!(registry_inline(fuji_chain)
+            .unwrap_err()
+            .contains("reuses chain ID"));
+        let bad_id = r#"[{"id": "Base Sepolia", "name": "x", "chain_id": 1,
+            "rpc_url": "https://example.org", "native_symbol": "ETH"}]"#;
+        assert!(registry_inline(bad_id).is_err());
+    }
+
+    fn registry_inline(json: &str) -> Result<NetworkRegistry, String> {
+        registry(&[("EVM_NETWORKS", json)])
+    }
+
+    fn registry_with_both() -> Result<NetworkRegistry, String> {
+        registry(&[
+            ("EVM_NETWORKS", BASE_SEPOLIA),
+            ("EVM_NETWORKS_FILE", "/etc/networks.json"),
+        ])
+    }
+}
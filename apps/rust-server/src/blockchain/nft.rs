@@ -0,0 +1,387 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! ERC-721 and ERC-1155 NFTs.
+//!
+//! Collections are listed per deployment in `NFT_COLLECTIONS`, a JSON
+//! array:
+//!
+//! ```json
+//! [{
+//!   "network": "fuji",
+//!   "address": "0x6B175474E89094C44Da98b954EedeAC495271d0F",
+//!   "standard": "erc721",
+//!   "name": "Relational Badges"
+//! }]
+//! ```
+//!
+//! The indexer reads the `Transfer` (ERC-721) and `TransferSingle` /
+//! `TransferBatch` (ERC-1155) events of listed collections and keeps the
+//! wallets' holdings in the transaction database. Sends use
+//! `safeTransferFrom`, after checking ownership with `ownerOf` (ERC-721) or
+//! `balanceOf` (ERC-1155).
+
+use std::{str::FromStr, sync::OnceLock};
+
+use alloy::{
+    primitives::{Address, Bytes, Log, U256},
+    providers::Provider,
+    sol,
+    sol_types::{SolCall, SolEvent},
+};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::{address::same_address, client::AvaxClientError, network::NetworkRegistry};
+
+sol! {
+    #[sol(rpc)]
+    interface IERC721 {
+        event Transfer(address indexed from, address indexed to, uint256 indexed tokenId);
+
+        function balanceOf(address owner) external view returns (uint256);
+        function ownerOf(uint256 tokenId) external view returns (address);
+        function safeTransferFrom(address from, address to, uint256 tokenId) external;
+    }
+
+    #[sol(rpc)]
+    interface IERC1155 {
+        event TransferSingle(address indexed operator, address indexed from, address indexed to, uint256 id, uint256 value);
+        event TransferBatch(address indexed operator, address indexed from, address indexed to, uint256[] ids, uint256[] values);
+
+        function balanceOf(address account, uint256 id) external view returns (uint256);
+        function safeTransferFrom(address from, address to, uint256 id, uint256 value, bytes data) external;
+    }
+}
+
+/// Token standard of a collection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum NftStandard {
+    Erc721,
+    Erc1155,
+}
+
+/// A listed NFT collection.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct NftCollection {
+    /// Registry ID of the network, e.g. `fuji`.
+    pub network: String,
+    /// Contract address, as configured.
+    pub address: String,
+    pub standard: NftStandard,
+    pub name: String,
+}
+
+/// Collections listed for this deployment.
+#[derive(Debug, Default)]
+pub struct NftCollections {
+    collections: Vec<NftCollection>,
+}
+
+impl NftCollections {
+    /// Build the list from a variable lookup. Collections must be on a
+    /// network in `networks`.
+    pub fn from_lookup(
+        lookup: impl Fn(&str) -> Option<String>,
+        networks: &NetworkRegistry,
+    ) -> Result<Self, String> {
+        let Some(json) = lookup("NFT_COLLECTIONS").filter(|v| !v.trim().is_empty()) else {
+            return Ok(Self::default());
+        };
+        let entries: Vec<NftCollection> =
+            serde_json::from_str(&json).map_err(|e| format!("NFT_COLLECTIONS: {e}"))?;
+        let mut collections: Vec<NftCollection> = Vec::with_capacity(entries.len());
+        for mut entry in entries {
+            let network = networks
+                .get(&entry.network)
+                .ok_or_else(|| format!("NFT_COLLECTIONS: unknown network `{}`", entry.network))?;
+            entry.network = network.id.to_string();
+            entry.address = entry.address.trim().to_string();
+            if Address::from_str(&entry.address).is_err() {
+                return Err(format!(
+                    "NFT_COLLECTIONS: address {} is not valid",
+                    entry.address
+                ));
+            }
+            if collections
+                .iter()
+                .any(|c| c.network == entry.network && same_address(&c.address, &entry.address))
+            {
+                return Err(format!(
+                    "NFT_COLLECTIONS: collection {} on `{}` is listed twice",
+                    entry.address, entry.network
+                ));
+            }
+            collections.push(entry);
+        }
+        Ok(Self { collections })
+    }
+
+    /// Collections from the environment.
+    pub fn from_env(networks: &NetworkRegistry) -> Result<Self, String> {
+        Self::from_lookup(|name| std::env::var(name).ok(), networks)
+    }
+
+    /// Collection listed as `address` on `network`.
+    pub fn get(&self, network: &str, address: &str) -> Option<&NftCollection> {
+        self.collections
+            .iter()
+            .find(|c| c.network.eq_ignore_ascii_case(network) && same_address(&c.address, address))
+    }
+
+    /// Contracts of the collections listed on `network`.
+    pub fn contracts(&self, network: &str) -> Vec<Address> {
+        self.collections
+            .iter()
+            .filter(|c| c.network.eq_ignore_ascii_case(network))
+            .filter_map(|c| c.address.parse().ok())
+            .collect()
+    }
+}
+
+static COLLECTIONS: OnceLock<NftCollections> = OnceLock::new();
+
+/// Install the collections listed at startup. Fails if a list is already
+/// in use.
+pub fn install_nft_collections(collections: NftCollections) -> Result<(), NftCollections> {
+    COLLECTIONS.set(collections)
+}
+
+/// The deployment's NFT collections; none until
+/// [`install_nft_collections`] runs.
+pub fn nft_collections() -> &'static NftCollections {
+    COLLECTIONS.get_or_init(NftCollections::default)
+}
+
+/// One token moved by an NFT transfer event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NftTransfer {
+    pub standard: NftStandard,
+    /// Lowercase address.
+    pub from: String,
+    /// Lowercase address.
+    pub to: String,
+    pub token_id: U256,
+    /// Always 1 for ERC-721.
+    pub amount: U256,
+}
+
+fn lower(address: Address) -> String {
+    format!("{address:#x}")
+}
+
+/// Tokens moved by `log`, if it is an ERC-721 or ERC-1155 transfer event.
+///
+/// ERC-20 `Transfer` shares the ERC-721 signature; it has no indexed value
+/// and is skipped.
+pub fn decode_transfers(log: &Log) -> Vec<NftTransfer> {
+    let topics = log.topics();
+    let Some(&signature) = topics.first() else {
+        return Vec::new();
+    };
+    if signature == IERC721::Transfer::SIGNATURE_HASH && topics.len() == 4 {
+        IERC721::Transfer::decode_log_data(&log.data)
+            .map(|event| {
+                vec![NftTransfer {
+                    standard: NftStandard::Erc721,
+                    from: lower(event.from),
+                    to: lower(event.to),
+                    token_id: event.tokenId,
+                    amount: U256::from(1),
+                }]
+            })
+            .unwrap_or_default()
+    } else if signature == IERC1155::TransferSingle::SIGNATURE_HASH {
+        IERC1155::TransferSingle::decode_log_data(&log.data)
+            .map(|event| {
+                vec![NftTransfer {
+                    standard: NftStandard::Erc1155,
+                    from: lower(event.from),
+                    to: lower(event.to),
+                    token_id: event.id,
+                    amount: event.value,
+                }]
+            })
+            .unwrap_or_default()
+    } else if signature == IERC1155::TransferBatch::SIGNATURE_HASH {
+        IERC1155::TransferBatch::decode_log_data(&log.data)
+            .map(|event| {
+                event
+                    .ids
+                    .iter()
+                    .zip(&event.values)
+                    .map(|(id, value)| NftTransfer {
+                        standard: NftStandard::Erc1155,
+                        from: lower(event.from),
+                        to: lower(event.to),
+                        token_id: *id,
+                        amount: *value,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    } else {
+        Vec::new()
+    }
+}
+
+/// Event signatures the indexer asks for.
+pub fn transfer_event_signatures() -> Vec<alloy::primitives::B256> {
+    vec![
+        IERC721::Transfer::SIGNATURE_HASH,
+        IERC1155::TransferSingle::SIGNATURE_HASH,
+        IERC1155::TransferBatch::SIGNATURE_HASH,
+    ]
+}
+
+/// Encode `safeTransferFrom` moving `amount` of `token_id` from `from` to
+/// `to`. ERC-721 moves the token whole and ignores `amount`.
+pub fn transfer_calldata(
+    standard: NftStandard,
+    from: Address,
+    to: Address,
+    token_id: U256,
+    amount: U256,
+) -> Vec<u8> {
+    match standard {
+        NftStandard::Erc721 => IERC721::safeTransferFromCall {
+            from,
+            to,
+            tokenId: token_id,
+        }
+        .abi_encode(),
+        NftStandard::Erc1155 => IERC1155::safeTransferFromCall {
+            from,
+            to,
+            id: token_id,
+            value: amount,
+            data: Bytes::new(),
+        }
+        .abi_encode(),
+    }
+}
+
+/// How many of `token_id` `owner` holds: 0 or 1 from `ownerOf` for
+/// ERC-721, `balanceOf` for ERC-1155.
+pub async fn balance_of<P: Provider + Clone>(
+    provider: &P,
+    standard: NftStandard,
+    contract: Address,
+    owner: Address,
+    token_id: U256,
+) -> Result<U256, AvaxClientError> {
+    match standard {
+        NftStandard::Erc721 => {
+            let holder = IERC721::new(contract, provider.clone())
+                .ownerOf(token_id)
+                .call()
+                .await
+                .map_err(|e| AvaxClientError::ContractError(e.to_string()))?;
+            Ok(U256::from(u8::from(holder == owner)))
+        }
+        NftStandard::Erc1155 => IERC1155::new(contract, provider.clone())
+            .balanceOf(owner, token_id)
+            .call()
+            .await
+            .map_err(|e| AvaxClientError::ContractError(e.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::{address, LogData};
+
+    const WALLET: Address = address!("1111111111111111111111111111111111111111");
+    const OTHER: Address = address!("2222222222222222222222222222222222222222");
+    const CONTRACT: Address = address!("3333333333333333333333333333333333333333");
+
+    fn log(data: LogData) -> Log {
+        Log {
+            address: CONTRACT,
+            data,
+        }
+    }
+
+    #[test]
+    fn transfer_events_of_both_standards_decode() {
+        let erc721 = IERC721::Transfer {
+            from: OTHER,
+            to: WALLET,
+            tokenId: U256::from(7),
+        };
+        let transfers = decode_transfers(&log(erc721.encode_log_data()));
+        assert_eq!(transfers.len(), 1);
+        assert_eq!(transfers[0].standard, NftStandard::Erc721);
+        assert_eq!(transfers[0].to, lower(WALLET));
+        assert_eq!(transfers[0].amount, U256::from(1));
+
+        let batch = IERC1155::TransferBatch {
+            operator: OTHER,
+            from: WALLET,
+            to: OTHER,
+            ids: vec![U256::from(1), U256::from(2)],
+            values: vec![U256::from(5), U256::from(6)],
+        };
+        let transfers = decode_transfers(&log(batch.encode_log_data()));
+        assert_eq!(transfers.len(), 2);
+        assert_eq!(transfers[1].token_id, U256::from(2));
+        assert_eq!(transfers[1].amount, U256::from(6));
+        assert_eq!(transfers[1].from, lower(WALLET));
+
+        // An ERC-20 Transfer has the same signature but three topics.
+        let mut erc20 = erc721.encode_log_data();
+        let topics = erc20.topics()[..3].to_vec();
+        erc20.set_topics_unchecked(topics);
+        assert!(decode_transfers(&log(erc20)).is_empty());
+    }
+
+    #[test]
+    fn sends_use_the_standards_safe_transfer() {
+        let erc721 = transfer_calldata(
+            NftStandard::Erc721,
+            WALLET,
+            OTHER,
+            U256::from(7),
+            U256::from(1),
+        );
+        assert_eq!(&erc721[..4], IERC721::safeTransferFromCall::SELECTOR);
+        assert_eq!(erc721.len(), 4 + 3 * 32);
+        let erc1155 = transfer_calldata(
+            NftStandard::Erc1155,
+            WALLET,
+            OTHER,
+            U256::from(7),
+            U256::from(2),
+        );
+        assert_eq!(&erc1155[..4], IERC1155::safeTransferFromCall::SELECTOR);
+    }
+
+    #[test]
+    fn collections_are_read_from_the_environment() {
+        let lookup = |json: &'static str| {
+            move |name: &str| (name == "NFT_COLLECTIONS").then(|| json.to_string())
+        };
+        let collections = NftCollections::from_lookup(
+            lookup(
+                r#"[{"network": "FUJI", "address": "0x3333333333333333333333333333333333333333", "standard": "erc1155", "name": "Tickets"}]"#,
+            ),
+            &NetworkRegistry::fuji_only(),
+        )
+        .unwrap();
+        assert_eq!(collections.contracts("fuji"), vec![CONTRACT]);
+        assert_eq!(
+            collections.get("fuji", &lower(CONTRACT)).unwrap().standard,
+            NftStandard::Erc1155
+        );
+        assert!(NftCollections::from_lookup(
+            lookup(
+                r#"[{"network": "mainnet", "address": "0x3333333333333333333333333333333333333333", "standard": "erc721", "name": "X"}]"#,
+            ),
+            &NetworkRegistry::fuji_only(),
+        )
+        .is_err());
+    }
+}
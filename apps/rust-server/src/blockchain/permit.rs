@@ -0,0 +1,307 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Signed token approvals: EIP-2612 `permit` and Uniswap Permit2.
+//!
+//! A permit is an EIP-712 signature that grants a spender an allowance. The
+//! spender submits it together with its own call, so the wallet never sends
+//! an `approve` transaction.
+//!
+//! Tokens that implement EIP-2612 (USDC, and rEUR deployments with
+//! `ERC20Permit`) are permitted directly. Other tokens go through Permit2,
+//! which needs a one-time `approve` of the Permit2 contract per token; after
+//! that every allowance is a signature.
+//!
+//! EIP-2612 support is detected by recomputing the token's
+//! `DOMAIN_SEPARATOR()` from its name and the common versions `"1"` and
+//! `"2"` (USDC uses `"2"`); a token whose separator matches neither is
+//! treated as not supporting permits.
+
+use std::str::FromStr;
+
+use alloy::{
+    primitives::{
+        aliases::{U160, U48},
+        Address, B256, U256,
+    },
+    providers::Provider,
+    signers::{local::PrivateKeySigner, Signature, SignerSync},
+    sol,
+    sol_types::{Eip712Domain, SolStruct},
+};
+
+use super::client::AvaxClientError;
+
+sol! {
+    #[sol(rpc)]
+    interface IERC20Permit {
+        function name() external view returns (string);
+        function nonces(address owner) external view returns (uint256);
+        function DOMAIN_SEPARATOR() external view returns (bytes32);
+    }
+
+    #[sol(rpc)]
+    interface IPermit2 {
+        function allowance(address user, address token, address spender)
+            external view returns (uint160 amount, uint48 expiration, uint48 nonce);
+    }
+
+    /// EIP-2612 permit message.
+    struct Permit {
+        address owner;
+        address spender;
+        uint256 value;
+        uint256 nonce;
+        uint256 deadline;
+    }
+
+    /// Permit2 `AllowanceTransfer` allowance details.
+    struct PermitDetails {
+        address token;
+        uint160 amount;
+        uint48 expiration;
+        uint48 nonce;
+    }
+
+    /// Permit2 `AllowanceTransfer` single-token permit message.
+    struct PermitSingle {
+        PermitDetails details;
+        address spender;
+        uint256 sigDeadline;
+    }
+}
+
+/// Canonical Permit2 deployment (same address on every chain).
+pub const DEFAULT_PERMIT2: &str = "0x000000000022D473030F116dDEE9F6B43aC78BA3";
+
+/// EIP-712 versions tried when matching a token's domain separator.
+const EIP2612_VERSIONS: [&str; 2] = ["1", "2"];
+
+/// Permit2 contract address, overridable with `PERMIT2_ADDRESS`.
+pub fn permit2_address() -> Address {
+    std::env::var("PERMIT2_ADDRESS")
+        .ok()
+        .and_then(|v| Address::from_str(&v).ok())
+        .unwrap_or_else(|| Address::from_str(DEFAULT_PERMIT2).expect("valid Permit2 address"))
+}
+
+/// Find the EIP-712 domain of an EIP-2612 token, or `None` if the token does
+/// not support permits.
+pub async fn eip2612_domain<P: Provider + Clone>(
+    provider: &P,
+    token: Address,
+    chain_id: u64,
+) -> Result<Option<Eip712Domain>, AvaxClientError> {
+    let contract = IERC20Permit::new(token, provider.clone());
+    let Ok(separator) = contract.DOMAIN_SEPARATOR().call().await else {
+        return Ok(None);
+    };
+    let name = contract
+        .name()
+        .call()
+        .await
+        .map_err(|e| AvaxClientError::ContractError(format!("name call failed: {e}")))?;
+    Ok(EIP2612_VERSIONS
+        .iter()
+        .map(|version| eip2612_domain_for(&name, version, chain_id, token))
+        .find(|domain| domain.separator() == separator))
+}
+
+fn eip2612_domain_for(name: &str, version: &str, chain_id: u64, token: Address) -> Eip712Domain {
+    Eip712Domain::new(
+        Some(name.to_string().into()),
+        Some(version.to_string().into()),
+        Some(U256::from(chain_id)),
+        Some(token),
+        None,
+    )
+}
+
+/// Current EIP-2612 nonce of `owner` on `token`.
+pub async fn eip2612_nonce<P: Provider + Clone>(
+    provider: &P,
+    token: Address,
+    owner: Address,
+) -> Result<U256, AvaxClientError> {
+    IERC20Permit::new(token, provider.clone())
+        .nonces(owner)
+        .call()
+        .await
+        .map_err(|e| AvaxClientError::ContractError(format!("nonces call failed: {e}")))
+}
+
+/// Sign an EIP-2612 permit; returns the signing hash and the signature.
+pub fn sign_eip2612(
+    signer: &PrivateKeySigner,
+    domain: &Eip712Domain,
+    spender: Address,
+    value: U256,
+    nonce: U256,
+    deadline: U256,
+) -> Result<(B256, Signature), AvaxClientError> {
+    let permit = Permit {
+        owner: signer.address(),
+        spender,
+        value,
+        nonce,
+        deadline,
+    };
+    let hash = permit.eip712_signing_hash(domain);
+    let signature = signer
+        .sign_hash_sync(&hash)
+        .map_err(|e| AvaxClientError::TransactionFailed(format!("Signing failed: {e}")))?;
+    Ok((hash, signature))
+}
+
+/// EIP-712 domain of the Permit2 contract.
+pub fn permit2_domain(chain_id: u64, permit2: Address) -> Eip712Domain {
+    Eip712Domain::new(
+        Some("Permit2".into()),
+        None,
+        Some(U256::from(chain_id)),
+        Some(permit2),
+        None,
+    )
+}
+
+/// Current Permit2 nonce for the (owner, token, spender) allowance.
+pub async fn permit2_nonce<P: Provider + Clone>(
+    provider: &P,
+    permit2: Address,
+    owner: Address,
+    token: Address,
+    spender: Address,
+) -> Result<u64, AvaxClientError> {
+    let allowance = IPermit2::new(permit2, provider.clone())
+        .allowance(owner, token, spender)
+        .call()
+        .await
+        .map_err(|e| AvaxClientError::ContractError(format!("Permit2 allowance failed: {e}")))?;
+    Ok(allowance.nonce.to())
+}
+
+/// Sign a Permit2 `PermitSingle`; returns the signing hash and the
+/// signature. `amount` must fit in 160 bits.
+#[allow(clippy::too_many_arguments)]
+pub fn sign_permit2(
+    signer: &PrivateKeySigner,
+    domain: &Eip712Domain,
+    token: Address,
+    spender: Address,
+    amount: U256,
+    expiration: u64,
+    nonce: u64,
+    sig_deadline: U256,
+) -> Result<(B256, Signature), AvaxClientError> {
+    let amount = U160::checked_from_limbs_slice(amount.as_limbs()).ok_or_else(|| {
+        AvaxClientError::TransactionFailed("Permit2 amounts are limited to 160 bits".into())
+    })?;
+    let permit = PermitSingle {
+        details: PermitDetails {
+            token,
+            amount,
+            expiration: U48::from(expiration),
+            nonce: U48::from(nonce),
+        },
+        spender,
+        sigDeadline: sig_deadline,
+    };
+    let hash = permit.eip712_signing_hash(domain);
+    let signature = signer
+        .sign_hash_sync(&hash)
+        .map_err(|e| AvaxClientError::TransactionFailed(format!("Signing failed: {e}")))?;
+    Ok((hash, signature))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::{address, keccak256};
+
+    #[test]
+    fn type_hashes_match_the_standards() {
+        let permit = Permit {
+            owner: Address::ZERO,
+            spender: Address::ZERO,
+            value: U256::ZERO,
+            nonce: U256::ZERO,
+            deadline: U256::ZERO,
+        };
+        assert_eq!(
+            permit.eip712_type_hash(),
+            keccak256(
+                "Permit(address owner,address spender,uint256 value,uint256 nonce,uint256 deadline)"
+            )
+        );
+
+        let single = PermitSingle {
+            details: PermitDetails {
+                token: Address::ZERO,
+                amount: U160::ZERO,
+                expiration: U48::ZERO,
+                nonce: U48::ZERO,
+            },
+            spender: Address::ZERO,
+            sigDeadline: U256::ZERO,
+        };
+        assert_eq!(
+            single.eip712_type_hash(),
+            keccak256(
+                "PermitSingle(PermitDetails details,address spender,uint256 sigDeadline)\
+                 PermitDetails(address token,uint160 amount,uint48 expiration,uint48 nonce)"
+            )
+        );
+    }
+
+    #[test]
+    fn signatures_recover_to_the_wallet_key() {
+        let signer = PrivateKeySigner::random();
+        let token = address!("0x5425890298aed601595a70AB815c96711a31Bc65");
+        let spender = address!("0x1111111111111111111111111111111111111111");
+
+        let domain = eip2612_domain_for("USD Coin", "2", 43113, token);
+        let (hash, signature) = sign_eip2612(
+            &signer,
+            &domain,
+            spender,
+            U256::from(1_000_000u64),
+            U256::ZERO,
+            U256::from(1_900_000_000u64),
+        )
+        .unwrap();
+        assert_eq!(
+            signature.recover_address_from_prehash(&hash).unwrap(),
+            signer.address()
+        );
+
+        let domain = permit2_domain(43113, permit2_address());
+        let (hash, signature) = sign_permit2(
+            &signer,
+            &domain,
+            token,
+            spender,
+            U256::from(5u64),
+            1_900_000_000,
+            0,
+            U256::from(1_900_000_000u64),
+        )
+        .unwrap();
+        assert_eq!(
+            signature.recover_address_from_prehash(&hash).unwrap(),
+            signer.address()
+        );
+
+        assert!(sign_permit2(
+            &signer,
+            &domain,
+            token,
+            spender,
+            U256::MAX,
+            0,
+            0,
+            U256::ZERO
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,621 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! ERC-4337 smart accounts owned by an enclave key.
+//!
+//! A smart-account wallet keeps its secp256k1 key in the enclave like any
+//! other wallet, but the key only signs UserOperations: funds live in a
+//! `SimpleAccount` contract whose address is derived counterfactually from
+//! the owner key through the factory. The account is deployed by its first
+//! UserOperation, which carries the factory call.
+//!
+//! UserOperations use the EntryPoint v0.7 format and are submitted to the
+//! bundler named by `BUNDLER_URL`. When `PAYMASTER_URL` is set, gas is
+//! sponsored through the ERC-7677 `pm_getPaymasterStubData` /
+//! `pm_getPaymasterData` methods; otherwise the account pays its own gas.
+
+use std::str::FromStr;
+use std::time::Duration;
+
+use alloy::{
+    hex,
+    primitives::{aliases::U192, keccak256, Address, Bytes, B256, U256},
+    providers::{Provider, RootProvider},
+    signers::{local::PrivateKeySigner, SignerSync},
+    sol,
+    sol_types::{SolCall, SolValue},
+};
+use serde_json::{json, Value};
+
+use super::client::AvaxClientError;
+use super::erc20::{self, IERC20};
+use super::transactions::SendResult;
+use super::types::NetworkConfig;
+
+sol! {
+    #[sol(rpc)]
+    interface ISimpleAccountFactory {
+        function getAddress(address owner, uint256 salt) external view returns (address);
+        function createAccount(address owner, uint256 salt) external returns (address);
+    }
+
+    interface ISimpleAccount {
+        function execute(address dest, uint256 value, bytes calldata func) external;
+        function executeBatch(address[] calldata dest, uint256[] calldata value, bytes[] calldata func) external;
+    }
+
+    #[sol(rpc)]
+    interface IEntryPoint {
+        function getNonce(address sender, uint192 key) external view returns (uint256 nonce);
+    }
+}
+
+/// Canonical EntryPoint v0.7 deployment.
+pub const DEFAULT_ENTRY_POINT: &str = "0x0000000071727De22E5E9d8BAf0edAc6f37da032";
+
+/// `SimpleAccountFactory` deployed alongside EntryPoint v0.7.
+pub const DEFAULT_FACTORY: &str = "0x91E60e0613810449d098b0b5Ec8b51A0FE8c8985";
+
+/// Placeholder signature used while estimating gas. It has the length and
+/// shape of a real ECDSA signature so `validateUserOp` costs the same.
+const DUMMY_SIGNATURE: &str = "0xfffffffffffffffffffffffffffffff0000000000000000000000000000000007aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa1c";
+
+/// How long to wait for the bundler to include a UserOperation.
+const RECEIPT_TIMEOUT_SECS: u64 = 60;
+const RECEIPT_POLL_SECS: u64 = 2;
+
+/// Smart-account settings, resolved from the environment.
+#[derive(Debug, Clone)]
+pub struct SmartAccountConfig {
+    pub bundler_url: String,
+    pub paymaster_url: Option<String>,
+    pub entry_point: Address,
+    pub factory: Address,
+}
+
+impl SmartAccountConfig {
+    /// Read the configuration; `None` when `BUNDLER_URL` is unset, which
+    /// disables smart-account wallets.
+    pub fn from_env() -> Option<Self> {
+        let bundler_url = std::env::var("BUNDLER_URL")
+            .ok()
+            .filter(|url| !url.is_empty())?;
+        let address = |var: &str, default: &str| {
+            std::env::var(var)
+                .ok()
+                .filter(|v| !v.is_empty())
+                .and_then(|v| Address::from_str(&v).ok())
+                .unwrap_or_else(|| Address::from_str(default).expect("valid default address"))
+        };
+        Some(Self {
+            bundler_url,
+            paymaster_url: std::env::var("PAYMASTER_URL")
+                .ok()
+                .filter(|url| !url.is_empty()),
+            entry_point: address("ENTRY_POINT_ADDRESS", DEFAULT_ENTRY_POINT),
+            factory: address("SMART_ACCOUNT_FACTORY_ADDRESS", DEFAULT_FACTORY),
+        })
+    }
+}
+
+/// One call made by the smart account.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Call {
+    pub to: Address,
+    pub value: U256,
+    pub data: Bytes,
+}
+
+impl Call {
+    /// A native AVAX transfer.
+    pub fn native(to: &str, amount_wei: U256) -> Result<Self, AvaxClientError> {
+        Ok(Self {
+            to: parse_address(to, "to")?,
+            value: amount_wei,
+            data: Bytes::new(),
+        })
+    }
+
+    /// An ERC-20 `transfer(to, amount)`.
+    pub fn token(token_address: &str, to: &str, amount: U256) -> Result<Self, AvaxClientError> {
+        let call = IERC20::transferCall {
+            to: parse_address(to, "to")?,
+            amount,
+        };
+        Ok(Self {
+            to: parse_address(token_address, "token")?,
+            value: U256::ZERO,
+            data: call.abi_encode().into(),
+        })
+    }
+
+    /// An ERC-20 `approve(spender, amount)`.
+    pub fn approve(
+        token_address: &str,
+        spender: &str,
+        amount: U256,
+    ) -> Result<Self, AvaxClientError> {
+        Ok(Self {
+            to: parse_address(token_address, "token")?,
+            value: U256::ZERO,
+            data: erc20::approve_calldata(spender, amount)?.into(),
+        })
+    }
+}
+
+/// Encode the account call data: `execute` for a single call,
+/// `executeBatch` for several.
+pub fn encode_calls(calls: &[Call]) -> Bytes {
+    match calls {
+        [call] => ISimpleAccount::executeCall {
+            dest: call.to,
+            value: call.value,
+            func: call.data.clone(),
+        }
+        .abi_encode()
+        .into(),
+        _ => ISimpleAccount::executeBatchCall {
+            dest: calls.iter().map(|c| c.to).collect(),
+            value: calls.iter().map(|c| c.value).collect(),
+            func: calls.iter().map(|c| c.data.clone()).collect(),
+        }
+        .abi_encode()
+        .into(),
+    }
+}
+
+/// An EntryPoint v0.7 UserOperation, in its unpacked RPC form.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UserOperation {
+    pub sender: Address,
+    pub nonce: U256,
+    pub factory: Option<Address>,
+    pub factory_data: Bytes,
+    pub call_data: Bytes,
+    pub call_gas_limit: u128,
+    pub verification_gas_limit: u128,
+    pub pre_verification_gas: U256,
+    pub max_fee_per_gas: u128,
+    pub max_priority_fee_per_gas: u128,
+    pub paymaster: Option<Address>,
+    pub paymaster_verification_gas_limit: u128,
+    pub paymaster_post_op_gas_limit: u128,
+    pub paymaster_data: Bytes,
+    pub signature: Bytes,
+}
+
+/// Pack two 128-bit values into one word, `high` first, as the EntryPoint
+/// does for `accountGasLimits` and `gasFees`.
+fn pack_u128_pair(high: u128, low: u128) -> B256 {
+    let mut word = [0u8; 32];
+    word[..16].copy_from_slice(&high.to_be_bytes());
+    word[16..].copy_from_slice(&low.to_be_bytes());
+    B256::from(word)
+}
+
+fn quantity<T: std::fmt::LowerHex>(value: T) -> String {
+    format!("{value:#x}")
+}
+
+impl UserOperation {
+    /// `factory ++ factoryData`, empty for deployed accounts.
+    fn init_code(&self) -> Vec<u8> {
+        match self.factory {
+            Some(factory) => [factory.as_slice(), &self.factory_data].concat(),
+            None => Vec::new(),
+        }
+    }
+
+    /// `paymaster ++ verificationGas ++ postOpGas ++ paymasterData`, empty
+    /// when unsponsored.
+    fn paymaster_and_data(&self) -> Vec<u8> {
+        match self.paymaster {
+            Some(paymaster) => [
+                paymaster.as_slice(),
+                &self.paymaster_verification_gas_limit.to_be_bytes(),
+                &self.paymaster_post_op_gas_limit.to_be_bytes(),
+                &self.paymaster_data,
+            ]
+            .concat(),
+            None => Vec::new(),
+        }
+    }
+
+    /// The hash the owner signs, as computed by `EntryPoint.getUserOpHash`.
+    pub fn hash(&self, entry_point: Address, chain_id: u64) -> B256 {
+        let packed = (
+            self.sender,
+            self.nonce,
+            keccak256(self.init_code()),
+            keccak256(&self.call_data),
+            pack_u128_pair(self.verification_gas_limit, self.call_gas_limit),
+            self.pre_verification_gas,
+            pack_u128_pair(self.max_priority_fee_per_gas, self.max_fee_per_gas),
+            keccak256(self.paymaster_and_data()),
+        )
+            .abi_encode();
+        keccak256((keccak256(packed), entry_point, U256::from(chain_id)).abi_encode())
+    }
+
+    /// JSON-RPC representation for `eth_*UserOperation*` and `pm_*` calls.
+    pub fn to_rpc(&self) -> Value {
+        let mut op = json!({
+            "sender": self.sender.to_checksum(None),
+            "nonce": quantity(self.nonce),
+            "callData": hex::encode_prefixed(&self.call_data),
+            "callGasLimit": quantity(self.call_gas_limit),
+            "verificationGasLimit": quantity(self.verification_gas_limit),
+            "preVerificationGas": quantity(self.pre_verification_gas),
+            "maxFeePerGas": quantity(self.max_fee_per_gas),
+            "maxPriorityFeePerGas": quantity(self.max_priority_fee_per_gas),
+            "signature": hex::encode_prefixed(&self.signature),
+        });
+        if let Some(factory) = self.factory {
+            op["factory"] = json!(factory.to_checksum(None));
+            op["factoryData"] = json!(hex::encode_prefixed(&self.factory_data));
+        }
+        if let Some(paymaster) = self.paymaster {
+            op["paymaster"] = json!(paymaster.to_checksum(None));
+            op["paymasterVerificationGasLimit"] =
+                json!(quantity(self.paymaster_verification_gas_limit));
+            op["paymasterPostOpGasLimit"] = json!(quantity(self.paymaster_post_op_gas_limit));
+            op["paymasterData"] = json!(hex::encode_prefixed(&self.paymaster_data));
+        }
+        op
+    }
+
+    /// Apply the paymaster fields of a `pm_getPaymaster*` response.
+    fn apply_paymaster(&mut self, response: &Value) -> Result<(), AvaxClientError> {
+        let paymaster = response["paymaster"]
+            .as_str()
+            .ok_or_else(|| AvaxClientError::RpcError("Paymaster returned no address".into()))?;
+        self.paymaster = Some(parse_address(paymaster, "paymaster")?);
+        self.paymaster_data = hex_field(response, "paymasterData")?.unwrap_or_default();
+        if let Some(gas) = quantity_field(response, "paymasterVerificationGasLimit")? {
+            self.paymaster_verification_gas_limit = gas.saturating_to();
+        }
+        if let Some(gas) = quantity_field(response, "paymasterPostOpGasLimit")? {
+            self.paymaster_post_op_gas_limit = gas.saturating_to();
+        }
+        Ok(())
+    }
+}
+
+fn parse_address(value: &str, what: &str) -> Result<Address, AvaxClientError> {
+    Address::from_str(value)
+        .map_err(|e| AvaxClientError::InvalidAddress(format!("Invalid {what} address: {e}")))
+}
+
+fn quantity_field(value: &Value, field: &str) -> Result<Option<U256>, AvaxClientError> {
+    value[field]
+        .as_str()
+        .map(|v| {
+            U256::from_str(v).map_err(|e| {
+                AvaxClientError::RpcError(format!("Invalid {field} from bundler: {e}"))
+            })
+        })
+        .transpose()
+}
+
+fn hex_field(value: &Value, field: &str) -> Result<Option<Bytes>, AvaxClientError> {
+    value[field]
+        .as_str()
+        .map(|v| {
+            Bytes::from_str(v).map_err(|e| {
+                AvaxClientError::RpcError(format!("Invalid {field} from bundler: {e}"))
+            })
+        })
+        .transpose()
+}
+
+fn http_provider(url: &str) -> Result<RootProvider, AvaxClientError> {
+    let url: url::Url = url
+        .parse()
+        .map_err(|e: url::ParseError| AvaxClientError::InvalidRpcUrl(e.to_string()))?;
+    Ok(RootProvider::new_http(url))
+}
+
+/// Client for deriving, deploying and operating smart accounts.
+pub struct SmartAccountClient {
+    config: SmartAccountConfig,
+    network: NetworkConfig,
+    chain: RootProvider,
+    bundler: RootProvider,
+    paymaster: Option<RootProvider>,
+}
+
+impl SmartAccountClient {
+    /// Connect to the chain, the bundler and (if configured) the paymaster.
+    pub fn new(
+        config: SmartAccountConfig,
+        network: NetworkConfig,
+    ) -> Result<Self, AvaxClientError> {
+        Ok(Self {
+            chain: http_provider(network.rpc_url)?,
+            bundler: http_provider(&config.bundler_url)?,
+            paymaster: config
+                .paymaster_url
+                .as_deref()
+                .map(http_provider)
+                .transpose()?,
+            config,
+            network,
+        })
+    }
+
+    pub fn config(&self) -> &SmartAccountConfig {
+        &self.config
+    }
+
+    /// Counterfactual account address for `owner` (salt 0).
+    pub async fn account_address(&self, owner: Address) -> Result<Address, AvaxClientError> {
+        ISimpleAccountFactory::new(self.config.factory, self.chain.clone())
+            .getAddress(owner, U256::ZERO)
+            .call()
+            .await
+            .map_err(|e| AvaxClientError::ContractError(format!("getAddress call failed: {e}")))
+    }
+
+    /// Submit `calls` as one UserOperation signed by `owner` and wait for the
+    /// bundler to include it.
+    ///
+    /// `deployed` is the stored deployment flag; if it is false the operation
+    /// carries the factory call, unless the chain shows the account already
+    /// exists. `call_gas_limit` overrides the bundler's estimate.
+    pub async fn send(
+        &self,
+        owner: &PrivateKeySigner,
+        account: Address,
+        deployed: bool,
+        calls: &[Call],
+        call_gas_limit: Option<u64>,
+        max_priority_fee: Option<u128>,
+    ) -> Result<SendResult, AvaxClientError> {
+        let entry_point = self.config.entry_point;
+        let chain_id = self.network.chain_id;
+
+        let nonce = IEntryPoint::new(entry_point, self.chain.clone())
+            .getNonce(account, U192::ZERO)
+            .call()
+            .await
+            .map_err(|e| AvaxClientError::ContractError(format!("getNonce call failed: {e}")))?;
+
+        let needs_deploy = !deployed
+            && self
+                .chain
+                .get_code_at(account)
+                .await
+                .map_err(|e| AvaxClientError::RpcError(format!("Failed to get code: {e}")))?
+                .is_empty();
+
+        let (max_fee_per_gas, priority_fee) = self.gas_prices(max_priority_fee).await?;
+        let mut op = UserOperation {
+            sender: account,
+            nonce,
+            call_data: encode_calls(calls),
+            max_fee_per_gas,
+            max_priority_fee_per_gas: priority_fee,
+            signature: Bytes::from_str(DUMMY_SIGNATURE).expect("valid dummy signature"),
+            ..Default::default()
+        };
+        if needs_deploy {
+            op.factory = Some(self.config.factory);
+            op.factory_data = ISimpleAccountFactory::createAccountCall {
+                owner: owner.address(),
+                salt: U256::ZERO,
+            }
+            .abi_encode()
+            .into();
+        }
+
+        if self.paymaster.is_some() {
+            let stub = self
+                .paymaster_request("pm_getPaymasterStubData", &op)
+                .await?;
+            op.apply_paymaster(&stub)?;
+        }
+
+        let estimate: Value = self
+            .bundler
+            .raw_request(
+                "eth_estimateUserOperationGas".into(),
+                (op.to_rpc(), entry_point),
+            )
+            .await
+            .map_err(|e| {
+                AvaxClientError::RpcError(format!("UserOperation estimation failed: {e}"))
+            })?;
+        let gas = |field: &str| -> Result<U256, AvaxClientError> {
+            quantity_field(&estimate, field)?.ok_or_else(|| {
+                AvaxClientError::RpcError(format!("Bundler estimate is missing {field}"))
+            })
+        };
+        op.pre_verification_gas = gas("preVerificationGas")?;
+        op.verification_gas_limit = gas("verificationGasLimit")?.saturating_to();
+        op.call_gas_limit = match call_gas_limit {
+            Some(limit) => limit as u128,
+            None => gas("callGasLimit")?.saturating_to(),
+        };
+        if let Some(limit) = quantity_field(&estimate, "paymasterVerificationGasLimit")? {
+            op.paymaster_verification_gas_limit = limit.saturating_to();
+        }
+        if let Some(limit) = quantity_field(&estimate, "paymasterPostOpGasLimit")? {
+            op.paymaster_post_op_gas_limit = limit.saturating_to();
+        }
+
+        if self.paymaster.is_some() {
+            let sponsored = self.paymaster_request("pm_getPaymasterData", &op).await?;
+            op.apply_paymaster(&sponsored)?;
+        }
+
+        let signature = owner
+            .sign_message_sync(op.hash(entry_point, chain_id).as_slice())
+            .map_err(|e| AvaxClientError::TransactionFailed(format!("Signing failed: {e}")))?;
+        op.signature = signature.as_bytes().to_vec().into();
+
+        let user_op_hash: String = self
+            .bundler
+            .raw_request("eth_sendUserOperation".into(), (op.to_rpc(), entry_point))
+            .await
+            .map_err(|e| AvaxClientError::TransactionFailed(format!("Failed to send: {e}")))?;
+
+        let tx_hash = self.wait_for_inclusion(&user_op_hash).await?;
+        let explorer_url = format!("{}/tx/{}", self.network.explorer_url, tx_hash);
+        Ok(SendResult {
+            tx_hash,
+            explorer_url,
+        })
+    }
+
+    /// Max fee and priority fee, using the same policy as `TxBuilder`.
+    async fn gas_prices(
+        &self,
+        max_priority_fee: Option<u128>,
+    ) -> Result<(u128, u128), AvaxClientError> {
+        let block = self
+            .chain
+            .get_block_by_number(alloy::eips::BlockNumberOrTag::Latest)
+            .await
+            .map_err(|e| AvaxClientError::RpcError(format!("Failed to get block: {e}")))?
+            .ok_or_else(|| AvaxClientError::RpcError("No latest block".to_string()))?;
+        let base_fee = block
+            .header
+            .base_fee_per_gas
+            .map(|f| f as u128)
+            .unwrap_or(25_000_000_000u128);
+        let priority_fee = max_priority_fee.unwrap_or(2_500_000_000);
+        Ok((
+            base_fee.saturating_mul(2).saturating_add(priority_fee),
+            priority_fee,
+        ))
+    }
+
+    async fn paymaster_request(
+        &self,
+        method: &'static str,
+        op: &UserOperation,
+    ) -> Result<Value, AvaxClientError> {
+        let paymaster = self.paymaster.as_ref().expect("paymaster configured");
+        paymaster
+            .raw_request(
+                method.into(),
+                (
+                    op.to_rpc(),
+                    self.config.entry_point,
+                    quantity(self.network.chain_id),
+                    json!({}),
+                ),
+            )
+            .await
+            .map_err(|e| AvaxClientError::RpcError(format!("{method} failed: {e}")))
+    }
+
+    /// Poll the bundler until the operation lands and return the bundle
+    /// transaction hash.
+    async fn wait_for_inclusion(&self, user_op_hash: &str) -> Result<String, AvaxClientError> {
+        for _ in 0..RECEIPT_TIMEOUT_SECS / RECEIPT_POLL_SECS {
+            let receipt: Value = self
+                .bundler
+                .raw_request("eth_getUserOperationReceipt".into(), (user_op_hash,))
+                .await
+                .map_err(|e| AvaxClientError::RpcError(format!("Receipt lookup failed: {e}")))?;
+            if !receipt.is_null() {
+                if receipt["success"] == Value::Bool(false) {
+                    return Err(AvaxClientError::TransactionFailed(format!(
+                        "UserOperation {user_op_hash} reverted"
+                    )));
+                }
+                return receipt["receipt"]["transactionHash"]
+                    .as_str()
+                    .map(str::to_string)
+                    .ok_or_else(|| {
+                        AvaxClientError::RpcError("Receipt has no transaction hash".into())
+                    });
+            }
+            tokio::time::sleep(Duration::from_secs(RECEIPT_POLL_SECS)).await;
+        }
+        Err(AvaxClientError::RpcError(format!(
+            "UserOperation {user_op_hash} was not included within {RECEIPT_TIMEOUT_SECS}s"
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn selector(signature: &str) -> [u8; 4] {
+        keccak256(signature)[..4].try_into().unwrap()
+    }
+
+    #[test]
+    fn single_call_uses_execute_and_several_use_execute_batch() {
+        let to = "0x0000000000000000000000000000000000000001";
+        let one = Call::native(to, U256::from(5)).unwrap();
+        let two = Call::token(to, to, U256::from(7)).unwrap();
+
+        let single = encode_calls(std::slice::from_ref(&one));
+        assert_eq!(single[..4], selector("execute(address,uint256,bytes)"));
+
+        let batch = encode_calls(&[one, two]);
+        assert_eq!(
+            batch[..4],
+            selector("executeBatch(address[],uint256[],bytes[])")
+        );
+    }
+
+    #[test]
+    fn gas_values_pack_high_word_first() {
+        let word = pack_u128_pair(1, 2);
+        assert_eq!(word[15], 1);
+        assert_eq!(word[31], 2);
+        assert!(word[..15].iter().chain(&word[16..31]).all(|b| *b == 0));
+    }
+
+    #[test]
+    fn user_op_hash_covers_chain_and_deployment() {
+        let entry_point = Address::from_str(DEFAULT_ENTRY_POINT).unwrap();
+        let op = UserOperation {
+            sender: Address::repeat_byte(0x11),
+            nonce: U256::from(3),
+            call_data: Bytes::from(vec![1, 2, 3]),
+            call_gas_limit: 100_000,
+            verification_gas_limit: 200_000,
+            pre_verification_gas: U256::from(50_000),
+            max_fee_per_gas: 60_000_000_000,
+            max_priority_fee_per_gas: 2_500_000_000,
+            ..Default::default()
+        };
+        let hash = op.hash(entry_point, 43113);
+        assert_eq!(hash, op.clone().hash(entry_point, 43113));
+        assert_ne!(hash, op.hash(entry_point, 43114));
+
+        // The signature is not part of the hash.
+        let mut signed = op.clone();
+        signed.signature = Bytes::from(vec![9; 65]);
+        assert_eq!(signed.hash(entry_point, 43113), hash);
+
+        let mut deploying = op.clone();
+        deploying.factory = Some(Address::from_str(DEFAULT_FACTORY).unwrap());
+        assert_ne!(deploying.hash(entry_point, 43113), hash);
+    }
+
+    #[test]
+    fn rpc_form_only_includes_factory_and_paymaster_when_set() {
+        let mut op = UserOperation {
+            nonce: U256::from(16),
+            ..Default::default()
+        };
+        let rpc = op.to_rpc();
+        assert_eq!(rpc["nonce"], "0x10");
+        assert!(rpc.get("factory").is_none());
+        assert!(rpc.get("paymaster").is_none());
+
+        op.factory = Some(Address::repeat_byte(0x22));
+        op.paymaster = Some(Address::repeat_byte(0x33));
+        let rpc = op.to_rpc();
+        assert_eq!(rpc["factoryData"], "0x");
+        assert_eq!(rpc["paymasterPostOpGasLimit"], "0x0");
+    }
+}
@@ -0,0 +1,370 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Runtime registry of the ERC-20 tokens the server knows about.
+//!
+//! rEUR on Fuji is always listed. Further tokens come from `TOKEN_REGISTRY`,
+//! a JSON array, and from admins through `/v1/admin/tokens`, whose entries
+//! are kept in encrypted storage and loaded at startup:
+//!
+//! ```json
+//! [{
+//!   "network": "fuji",
+//!   "address": "0x5425890298aed601595a70AB815c96711a31Bc65",
+//!   "symbol": "USDC",
+//!   "name": "USD Coin",
+//!   "decimals": 6,
+//!   "indexed": true
+//! }]
+//! ```
+//!
+//! The indexer watches transfers of `indexed` tokens, balance queries
+//! include every token listed on the network, and amounts are parsed and
+//! formatted with the listed decimals. Tokens that are not listed are
+//! assumed to have 18 decimals.
+
+use std::sync::{OnceLock, RwLock};
+
+use alloy::primitives::Address;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::{
+    address::same_address,
+    network::NetworkRegistry,
+    types::{NetworkConfig, NETWORK_FUJI, REUR_TOKEN},
+};
+
+/// Decimals assumed for tokens that are not listed.
+pub const DEFAULT_TOKEN_DECIMALS: u8 = 18;
+
+/// Where a listing comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenSource {
+    /// Compiled into the server.
+    Builtin,
+    /// Listed in `TOKEN_REGISTRY`.
+    Env,
+    /// Listed by an admin; can be changed at runtime.
+    Admin,
+}
+
+/// A listed ERC-20 token.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct ListedToken {
+    /// Registry ID of the network, e.g. `fuji`.
+    pub network: String,
+    /// Contract address, checksummed as configured.
+    pub address: String,
+    pub symbol: String,
+    pub name: String,
+    pub decimals: u8,
+    /// Whether the indexer records transfers of this token.
+    pub indexed: bool,
+    pub source: TokenSource,
+}
+
+impl ListedToken {
+    fn is(&self, network: &str, address: &str) -> bool {
+        self.network.eq_ignore_ascii_case(network) && same_address(&self.address, address)
+    }
+}
+
+/// One token as configured in `TOKEN_REGISTRY`.
+#[derive(Debug, Deserialize)]
+struct TokenEntry {
+    network: String,
+    address: String,
+    symbol: String,
+    #[serde(default)]
+    name: Option<String>,
+    decimals: u8,
+    #[serde(default = "default_indexed")]
+    indexed: bool,
+}
+
+fn default_indexed() -> bool {
+    true
+}
+
+/// Tokens listed for this deployment.
+#[derive(Debug, Default)]
+pub struct TokenRegistry {
+    tokens: RwLock<Vec<ListedToken>>,
+}
+
+impl TokenRegistry {
+    /// rEUR on Fuji only.
+    pub fn builtin() -> Self {
+        let tokens = REUR_TOKEN
+            .fuji_address
+            .map(|address| ListedToken {
+                network: NETWORK_FUJI.to_string(),
+                address: address.to_string(),
+                symbol: REUR_TOKEN.symbol.to_string(),
+                name: REUR_TOKEN.name.to_string(),
+                decimals: REUR_TOKEN.decimals,
+                indexed: true,
+                source: TokenSource::Builtin,
+            })
+            .into_iter()
+            .collect();
+        Self {
+            tokens: RwLock::new(tokens),
+        }
+    }
+
+    /// Build the registry from a variable lookup. Tokens must be on a
+    /// network in `networks`.
+    pub fn from_lookup(
+        lookup: impl Fn(&str) -> Option<String>,
+        networks: &NetworkRegistry,
+    ) -> Result<Self, String> {
+        let registry = Self::builtin();
+        let Some(json) = lookup("TOKEN_REGISTRY").filter(|v| !v.trim().is_empty()) else {
+            return Ok(registry);
+        };
+        let entries: Vec<TokenEntry> =
+            serde_json::from_str(&json).map_err(|e| format!("TOKEN_REGISTRY: {e}"))?;
+        for entry in entries {
+            let network = networks
+                .get(&entry.network)
+                .ok_or_else(|| format!("TOKEN_REGISTRY: unknown network `{}`", entry.network))?;
+            let token = ListedToken {
+                network: network.id.to_string(),
+                address: entry.address.trim().to_string(),
+                name: entry.name.unwrap_or_else(|| entry.symbol.clone()),
+                symbol: entry.symbol,
+                decimals: entry.decimals,
+                indexed: entry.indexed,
+                source: TokenSource::Env,
+            };
+            validate(&token).map_err(|e| format!("TOKEN_REGISTRY: {e}"))?;
+            if registry.get(&token.network, &token.address).is_some() {
+                return Err(format!(
+                    "TOKEN_REGISTRY: token {} on `{}` is listed twice",
+                    token.address, token.network
+                ));
+            }
+            registry.write().push(token);
+        }
+        Ok(registry)
+    }
+
+    /// Registry from the environment.
+    pub fn from_env(networks: &NetworkRegistry) -> Result<Self, String> {
+        Self::from_lookup(|name| std::env::var(name).ok(), networks)
+    }
+
+    fn read(&self) -> std::sync::RwLockReadGuard<'_, Vec<ListedToken>> {
+        self.tokens.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn write(&self) -> std::sync::RwLockWriteGuard<'_, Vec<ListedToken>> {
+        self.tokens.write().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Token listed as `address` on `network`.
+    pub fn get(&self, network: &str, address: &str) -> Option<ListedToken> {
+        self.read().iter().find(|t| t.is(network, address)).cloned()
+    }
+
+    /// First token listed as `symbol` on `network`. Built-in listings come
+    /// first, so an admin listing cannot take over rEUR's symbol.
+    pub fn by_symbol(&self, network: &str, symbol: &str) -> Option<ListedToken> {
+        self.read()
+            .iter()
+            .find(|t| t.network.eq_ignore_ascii_case(network) && t.symbol == symbol)
+            .cloned()
+    }
+
+    /// All listed tokens, by network then symbol.
+    pub fn all(&self) -> Vec<ListedToken> {
+        let mut tokens = self.read().clone();
+        tokens.sort_by(|a, b| {
+            (a.network.as_str(), a.symbol.as_str()).cmp(&(b.network.as_str(), b.symbol.as_str()))
+        });
+        tokens
+    }
+
+    /// Tokens listed on `network`.
+    pub fn on_network(&self, network: &str) -> Vec<ListedToken> {
+        self.read()
+            .iter()
+            .filter(|t| t.network.eq_ignore_ascii_case(network))
+            .cloned()
+            .collect()
+    }
+
+    /// Contracts whose transfers are indexed on `network`.
+    pub fn indexed_contracts(&self, network: &str) -> Vec<Address> {
+        self.read()
+            .iter()
+            .filter(|t| t.indexed && t.network.eq_ignore_ascii_case(network))
+            .filter_map(|t| t.address.parse().ok())
+            .collect()
+    }
+
+    /// Decimals of `token` (`native` or a contract address) on `network`.
+    pub fn decimals(&self, network: &NetworkConfig, token: &str) -> u8 {
+        if token == "native" {
+            return network.native_decimals;
+        }
+        self.get(network.id, token)
+            .map_or(DEFAULT_TOKEN_DECIMALS, |t| t.decimals)
+    }
+
+    /// List or replace an admin token. Built-in and `TOKEN_REGISTRY`
+    /// listings cannot be changed at runtime.
+    pub fn upsert(&self, token: ListedToken) -> Result<(), String> {
+        validate(&token)?;
+        let mut tokens = self.write();
+        match tokens
+            .iter_mut()
+            .find(|t| t.is(&token.network, &token.address))
+        {
+            Some(existing) if existing.source != TokenSource::Admin => Err(format!(
+                "token {} on `{}` is configured at deployment and cannot be changed",
+                token.address, token.network
+            )),
+            Some(existing) => {
+                *existing = token;
+                Ok(())
+            }
+            None => {
+                tokens.push(token);
+                Ok(())
+            }
+        }
+    }
+
+    /// Unlist an admin token. Returns whether it was listed.
+    pub fn remove(&self, network: &str, address: &str) -> Result<bool, String> {
+        let mut tokens = self.write();
+        let Some(index) = tokens.iter().position(|t| t.is(network, address)) else {
+            return Ok(false);
+        };
+        if tokens[index].source != TokenSource::Admin {
+            return Err(format!(
+                "token {address} on `{network}` is configured at deployment and cannot be removed"
+            ));
+        }
+        tokens.remove(index);
+        Ok(true)
+    }
+}
+
+fn validate(token: &ListedToken) -> Result<(), String> {
+    if token.address.parse::<Address>().is_err() {
+        return Err(format!("token address {} is not valid", token.address));
+    }
+    if token.symbol.trim().is_empty() || token.symbol.len() > 16 {
+        return Err(format!(
+            "token {} symbol must be 1-16 characters",
+            token.address
+        ));
+    }
+    if token.decimals > 36 {
+        return Err(format!(
+            "token {} decimals must be at most 36",
+            token.address
+        ));
+    }
+    Ok(())
+}
+
+static TOKENS: OnceLock<TokenRegistry> = OnceLock::new();
+
+/// Install the registry built at startup. Fails if one is already in use.
+pub fn install_tokens(registry: TokenRegistry) -> Result<(), TokenRegistry> {
+    TOKENS.set(registry)
+}
+
+/// The deployment's tokens; rEUR only until [`install_tokens`] runs.
+pub fn tokens() -> &'static TokenRegistry {
+    TOKENS.get_or_init(TokenRegistry::builtin)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blockchain::AVAX_FUJI;
+
+    const USDC_FUJI: &str = r#"[{
+        "network": "FUJI",
+        "address": "0x5425890298aed601595a70AB815c96711a31Bc65",
+        "symbol": "USDC",
+        "decimals": 6
+    }]"#;
+
+    fn registry(json: &str) -> Result<TokenRegistry, String> {
+        TokenRegistry::from_lookup(
+            |name| (name == "TOKEN_REGISTRY").then(|| json.to_string()),
+            &NetworkRegistry::fuji_only(),
+        )
+    }
+
+    #[test]
+    fn configured_tokens_are_listed_after_reur() {
+        let listed = registry(USDC_FUJI).unwrap();
+        assert_eq!(listed.on_network(NETWORK_FUJI).len(), 2);
+        assert_eq!(listed.indexed_contracts("fuji").len(), 2);
+
+        let usdc = listed
+            .get("fuji", "0x5425890298AED601595A70AB815C96711A31BC65")
+            .unwrap();
+        assert_eq!(usdc.name, "USDC");
+        assert_eq!(usdc.source, TokenSource::Env);
+        assert_eq!(
+            listed.decimals(&AVAX_FUJI, REUR_TOKEN.fuji_address.unwrap()),
+            6
+        );
+        assert_eq!(listed.decimals(&AVAX_FUJI, "native"), 18);
+        assert_eq!(listed.decimals(&AVAX_FUJI, "0x1234"), 18);
+
+        assert!(registry(r#"[{"network": "mainnet", "address": "0x5425890298aed601595a70AB815c96711a31Bc65", "symbol": "USDC", "decimals": 6}]"#)
+            .unwrap_err()
+            .contains("unknown network"));
+        let reur_again = format!(
+            r#"[{{"network": "fuji", "address": "{}", "symbol": "X", "decimals": 6}}]"#,
+            REUR_TOKEN.fuji_address.unwrap()
+        );
+        assert!(registry(&reur_again).unwrap_err().contains("twice"));
+    }
+
+    #[test]
+    fn only_admin_listings_change_at_runtime() {
+        let registry = TokenRegistry::builtin();
+        let mut token = ListedToken {
+            network: NETWORK_FUJI.to_string(),
+            address: "0x5425890298aed601595a70AB815c96711a31Bc65".to_string(),
+            symbol: "USDC".to_string(),
+            name: "USD Coin".to_string(),
+            decimals: 6,
+            indexed: false,
+            source: TokenSource::Admin,
+        };
+        registry.upsert(token.clone()).unwrap();
+        assert_eq!(registry.indexed_contracts(NETWORK_FUJI).len(), 1);
+
+        token.indexed = true;
+        registry.upsert(token.clone()).unwrap();
+        assert_eq!(registry.indexed_contracts(NETWORK_FUJI).len(), 2);
+        assert!(registry.remove(NETWORK_FUJI, &token.address).unwrap());
+        assert!(!registry.remove(NETWORK_FUJI, &token.address).unwrap());
+
+        // An admin listing under rEUR's symbol does not replace it.
+        token.symbol = REUR_TOKEN.symbol.to_string();
+        registry.upsert(token.clone()).unwrap();
+        let listed = registry.by_symbol("FUJI", REUR_TOKEN.symbol).unwrap();
+        assert_eq!(listed.source, TokenSource::Builtin);
+        assert!(registry.remove(NETWORK_FUJI, &token.address).unwrap());
+
+        let reur = REUR_TOKEN.fuji_address.unwrap();
+        assert!(registry.remove(NETWORK_FUJI, reur).is_err());
+        token.address = reur.to_string();
+        assert!(registry.upsert(token).is_err());
+    }
+}
@@ -5,21 +5,36 @@
 //! Transaction building and broadcasting for Avalanche C-Chain.
 //!
 //! This module provides EIP-1559 transaction building, gas estimation,
-//! and broadcasting capabilities for both native AVAX and ERC-20 transfers.
+//! and broadcasting capabilities for native AVAX and ERC-20 transfers and
+//! ERC-20 approvals. A [`NonceManager`] hands out nonces so concurrent sends
+//! from one address do not collide. Pending transactions can be replaced
+//! with the same nonce at a higher fee, see [`TxBuilder::replace_transaction`].
 
+use std::collections::HashMap;
 use std::str::FromStr;
+use std::sync::{Arc, Mutex};
 
 use alloy::{
-    network::EthereumWallet,
-    primitives::{Address, U256},
+    consensus::Transaction as _,
+    network::{Ethereum, EthereumWallet, NetworkWallet, TransactionBuilder},
+    primitives::{Address, B256, U256},
     providers::{Provider, ProviderBuilder},
     rpc::types::TransactionRequest,
     sol_types::SolCall,
 };
 
+use chrono::{DateTime, Duration, Utc};
+use tokio::sync::OwnedMutexGuard;
+
 use super::client::AvaxClientError;
-use super::erc20::IERC20;
+use super::erc20::{self, IERC20};
+use super::fees;
 use super::types::NetworkConfig;
+use crate::storage::{EncryptedStorage, PendingNonceRepository, StoredPendingNonce};
+
+/// How long a nonce the node does not report as pending stays reserved.
+/// After that its transaction is assumed dropped and the nonce is reused.
+const STALE_NONCE_SECS: i64 = 600;
 
 /// Gas estimation result.
 #[derive(Debug, Clone)]
@@ -43,10 +58,113 @@ pub struct SendResult {
     pub explorer_url: String,
 }
 
+/// How a pending transaction is replaced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplacementKind {
+    /// Same transaction, higher fee.
+    SpeedUp,
+    /// Zero-value transfer to the sender, so the original never executes.
+    Cancel,
+}
+
+/// Gas limit of a plain transfer, used by cancellations.
+const TRANSFER_GAS: u64 = 21_000;
+
+/// Raise a fee by 12.5%, rounded up. Nodes only accept a replacement whose
+/// fees are at least 10% above the original's.
+fn bump_fee(fee: u128) -> u128 {
+    fee.saturating_add(fee.div_ceil(8))
+}
+
+/// Serializes nonce selection for one address on one chain.
+type AddressLock = Arc<tokio::sync::Mutex<()>>;
+
+/// Hands out nonces per sending address.
+///
+/// Each builder fills in its own nonce from `eth_getTransactionCount`, so two
+/// sends from one address racing through separate builders can pick the
+/// same nonce and one of them is rejected or replaced. Builders given a
+/// manager hold the address's lock from choosing the nonce until the node
+/// accepts the transaction, and nonces of accepted transactions are
+/// persisted until mined so a restart does not reuse them.
+pub struct NonceManager {
+    storage: Arc<EncryptedStorage>,
+    locks: Mutex<HashMap<(u64, Address), AddressLock>>,
+}
+
+impl NonceManager {
+    /// Create a manager persisting pending nonces in `storage`.
+    pub fn new(storage: Arc<EncryptedStorage>) -> Self {
+        Self {
+            storage,
+            locks: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Wait for exclusive use of `address` on `chain_id`.
+    async fn lock(&self, chain_id: u64, address: Address) -> OwnedMutexGuard<()> {
+        let lock = self
+            .locks
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .entry((chain_id, address))
+            .or_default()
+            .clone();
+        lock.lock_owned().await
+    }
+
+    fn pending(&self, chain_id: u64, address: Address) -> Vec<StoredPendingNonce> {
+        PendingNonceRepository::new(&self.storage)
+            .get(chain_id, &address.to_string())
+            .unwrap_or_else(|e| {
+                tracing::warn!(%address, error = %e, "Failed to read pending nonces");
+                Vec::new()
+            })
+    }
+
+    fn save(&self, chain_id: u64, address: Address, pending: &[StoredPendingNonce]) {
+        if let Err(e) =
+            PendingNonceRepository::new(&self.storage).set(chain_id, &address.to_string(), pending)
+        {
+            tracing::warn!(%address, error = %e, "Failed to persist pending nonces");
+        }
+    }
+}
+
+/// Pick the next nonce.
+///
+/// `mined` and `node_pending` are the address's transaction counts at the
+/// latest and pending block. Entries already mined are dropped from
+/// `pending`; so are entries the node has not reported for longer than
+/// [`STALE_NONCE_SECS`], whose nonces are then handed out again.
+fn next_nonce(
+    pending: &mut Vec<StoredPendingNonce>,
+    mined: u64,
+    node_pending: u64,
+    now: DateTime<Utc>,
+) -> u64 {
+    pending.retain(|p| p.nonce >= mined);
+    let dropped = pending.iter().any(|p| {
+        p.nonce >= node_pending && now - p.assigned_at > Duration::seconds(STALE_NONCE_SECS)
+    });
+    if dropped {
+        pending.retain(|p| p.nonce < node_pending);
+    }
+    pending
+        .iter()
+        .map(|p| p.nonce + 1)
+        .max()
+        .unwrap_or(0)
+        .max(node_pending)
+}
+
 /// Transaction builder for Avalanche C-Chain.
 #[allow(clippy::type_complexity)]
 pub struct TxBuilder {
     network: NetworkConfig,
+    /// Address of the signing wallet.
+    from: Address,
+    nonces: Option<Arc<NonceManager>>,
     provider: alloy::providers::fillers::FillProvider<
         alloy::providers::fillers::JoinFill<
             alloy::providers::fillers::JoinFill<
@@ -79,9 +197,22 @@ impl TxBuilder {
             .parse()
             .map_err(|e: url::ParseError| AvaxClientError::InvalidRpcUrl(e.to_string()))?;
 
+        let from = NetworkWallet::<Ethereum>::default_signer_address(&wallet);
         let provider = ProviderBuilder::new().wallet(wallet).connect_http(url);
 
-        Ok(Self { network, provider })
+        Ok(Self {
+            network,
+            from,
+            nonces: None,
+            provider,
+        })
+    }
+
+    /// Take nonces from `nonces` instead of the node, so sends sharing the
+    /// manager never reuse one.
+    pub fn with_nonce_manager(mut self, nonces: Arc<NonceManager>) -> Self {
+        self.nonces = Some(nonces);
+        self
     }
 
     /// Estimate gas for a native AVAX transfer.
@@ -135,6 +266,29 @@ impl TxBuilder {
         self.estimate_gas_for_tx(tx).await
     }
 
+    /// Estimate gas for an ERC-20 `approve(spender, amount)`.
+    pub async fn estimate_token_approval(
+        &self,
+        from: &str,
+        token_address: &str,
+        spender: &str,
+        amount: U256,
+    ) -> Result<GasEstimate, AvaxClientError> {
+        let from_addr = Address::from_str(from)
+            .map_err(|e| AvaxClientError::InvalidAddress(format!("Invalid from address: {}", e)))?;
+        let token_addr = Address::from_str(token_address).map_err(|e| {
+            AvaxClientError::InvalidAddress(format!("Invalid token address: {}", e))
+        })?;
+        let data = erc20::approve_calldata(spender, amount)?;
+
+        let tx = TransactionRequest::default()
+            .from(from_addr)
+            .to(token_addr)
+            .input(data.into());
+
+        self.estimate_gas_for_tx(tx).await
+    }
+
     /// Internal gas estimation helper.
     async fn estimate_gas_for_tx(
         &self,
@@ -148,7 +302,7 @@ impl TxBuilder {
             .map_err(|e| AvaxClientError::RpcError(format!("Gas estimation failed: {}", e)))?;
 
         // Get current gas prices
-        let (max_fee_per_gas, max_priority_fee_per_gas) = self.get_gas_prices().await?;
+        let (max_fee_per_gas, max_priority_fee_per_gas) = self.get_gas_prices(None).await?;
 
         // Calculate estimated cost
         let estimated_cost_wei = U256::from(gas_limit) * U256::from(max_fee_per_gas);
@@ -162,7 +316,13 @@ impl TxBuilder {
     }
 
     /// Get current gas prices from the network.
-    async fn get_gas_prices(&self) -> Result<(u128, u128), AvaxClientError> {
+    ///
+    /// Uses `priority_fee` as the tip when given; the max fee always leaves
+    /// room for that tip on top of a doubled base fee.
+    async fn get_gas_prices(
+        &self,
+        priority_fee: Option<u128>,
+    ) -> Result<(u128, u128), AvaxClientError> {
         // Get base fee from latest block
         let block = self
             .provider
@@ -175,13 +335,13 @@ impl TxBuilder {
             .header
             .base_fee_per_gas
             .map(|f| f as u128)
-            .unwrap_or(25_000_000_000u128); // 25 gwei default
+            .unwrap_or(fees::DEFAULT_BASE_FEE);
 
         // Aggressive priority fee for fast Avalanche C-Chain inclusion (~2s blocks)
-        let priority_fee: u128 = 2_500_000_000; // 2.5 gwei
+        let priority_fee = priority_fee.unwrap_or(fees::DEFAULT_PRIORITY_FEE);
 
         // Max fee = 2 * base_fee + priority_fee (allows for base fee increase)
-        let max_fee = base_fee.saturating_mul(2).saturating_add(priority_fee);
+        let max_fee = fees::max_fee_for(base_fee, priority_fee);
 
         Ok((max_fee, priority_fee))
     }
@@ -203,8 +363,7 @@ impl TxBuilder {
         let to_addr = Address::from_str(to)
             .map_err(|e| AvaxClientError::InvalidAddress(format!("Invalid to address: {}", e)))?;
 
-        let (max_fee_per_gas, default_priority_fee) = self.get_gas_prices().await?;
-        let priority_fee = max_priority_fee.unwrap_or(default_priority_fee);
+        let (max_fee_per_gas, priority_fee) = self.get_gas_prices(max_priority_fee).await?;
 
         let mut tx = TransactionRequest::default()
             .to(to_addr)
@@ -219,6 +378,42 @@ impl TxBuilder {
         self.send_transaction(tx).await
     }
 
+    /// Send the whole native balance, less the gas of a plain transfer, to
+    /// `to`, returning the transfer and the amount sent. Returns `None` if
+    /// the balance does not cover the gas.
+    ///
+    /// The gas is reserved at the max fee, so the difference to the fee
+    /// actually charged stays behind.
+    pub async fn sweep_native(
+        &self,
+        to: &str,
+    ) -> Result<Option<(SendResult, U256)>, AvaxClientError> {
+        let to_addr = Address::from_str(to)
+            .map_err(|e| AvaxClientError::InvalidAddress(format!("Invalid to address: {}", e)))?;
+
+        let balance = self
+            .provider
+            .get_balance(self.from)
+            .await
+            .map_err(|e| AvaxClientError::RpcError(format!("Failed to get balance: {}", e)))?;
+        let (max_fee_per_gas, priority_fee) = self.get_gas_prices(None).await?;
+        let gas_cost = U256::from(TRANSFER_GAS) * U256::from(max_fee_per_gas);
+        if balance <= gas_cost {
+            return Ok(None);
+        }
+
+        let amount = balance - gas_cost;
+        let tx = TransactionRequest::default()
+            .to(to_addr)
+            .value(amount)
+            .gas_limit(TRANSFER_GAS)
+            .max_fee_per_gas(max_fee_per_gas)
+            .max_priority_fee_per_gas(priority_fee);
+
+        let sent = self.send_transaction(tx).await?;
+        Ok(Some((sent, amount)))
+    }
+
     /// Send an ERC-20 token transfer.
     ///
     /// # Arguments
@@ -248,8 +443,7 @@ impl TxBuilder {
         };
         let data = call.abi_encode();
 
-        let (max_fee_per_gas, default_priority_fee) = self.get_gas_prices().await?;
-        let priority_fee = max_priority_fee.unwrap_or(default_priority_fee);
+        let (max_fee_per_gas, priority_fee) = self.get_gas_prices(max_priority_fee).await?;
 
         let mut tx = TransactionRequest::default()
             .to(token_addr)
@@ -278,8 +472,7 @@ impl TxBuilder {
             AvaxClientError::InvalidAddress(format!("Invalid contract address: {}", e))
         })?;
 
-        let (max_fee_per_gas, default_priority_fee) = self.get_gas_prices().await?;
-        let priority_fee = max_priority_fee.unwrap_or(default_priority_fee);
+        let (max_fee_per_gas, priority_fee) = self.get_gas_prices(max_priority_fee).await?;
 
         let mut tx = TransactionRequest::default()
             .to(contract_addr)
@@ -302,6 +495,115 @@ impl TxBuilder {
         &self,
         tx: TransactionRequest,
     ) -> Result<SendResult, AvaxClientError> {
+        let Some(nonces) = &self.nonces else {
+            return self.broadcast(tx).await;
+        };
+        let chain_id = self.network.chain_id;
+        let _guard = nonces.lock(chain_id, self.from).await;
+
+        let count = |e| AvaxClientError::RpcError(format!("Failed to get nonce: {}", e));
+        let mined = self
+            .provider
+            .get_transaction_count(self.from)
+            .latest()
+            .await
+            .map_err(count)?;
+        let node_pending = self
+            .provider
+            .get_transaction_count(self.from)
+            .pending()
+            .await
+            .map_err(count)?;
+        let mut pending = nonces.pending(chain_id, self.from);
+        let nonce = next_nonce(&mut pending, mined, node_pending, Utc::now());
+
+        let result = self.broadcast(tx.nonce(nonce)).await;
+        if let Ok(sent) = &result {
+            pending.push(StoredPendingNonce {
+                nonce,
+                tx_hash: sent.tx_hash.clone(),
+                assigned_at: Utc::now(),
+            });
+        }
+        nonces.save(chain_id, self.from, &pending);
+        result
+    }
+
+    /// Replace the pending transaction `tx_hash` of this builder's address by
+    /// one with the same nonce and higher fees.
+    ///
+    /// Fees are raised by 12.5% or to the current network price, whichever
+    /// is higher. Returns `None` if the node does not know the transaction
+    /// or it is already mined.
+    pub async fn replace_transaction(
+        &self,
+        tx_hash: &str,
+        kind: ReplacementKind,
+    ) -> Result<Option<SendResult>, AvaxClientError> {
+        let hash = B256::from_str(tx_hash)
+            .map_err(|e| AvaxClientError::TransactionFailed(format!("Invalid hash: {}", e)))?;
+        let chain_id = self.network.chain_id;
+        let _guard = match &self.nonces {
+            Some(nonces) => Some(nonces.lock(chain_id, self.from).await),
+            None => None,
+        };
+
+        let original = self
+            .provider
+            .get_transaction_by_hash(hash)
+            .await
+            .map_err(|e| AvaxClientError::RpcError(format!("Failed to get transaction: {}", e)))?;
+        let Some(original) = original.filter(|tx| tx.block_number.is_none()) else {
+            return Ok(None);
+        };
+        if original.inner.signer() != self.from {
+            return Err(AvaxClientError::TransactionFailed(
+                "Transaction was not sent by this wallet".to_string(),
+            ));
+        }
+
+        let (current_max_fee, current_priority_fee) = self.get_gas_prices(None).await?;
+        let old_priority_fee = original
+            .max_priority_fee_per_gas()
+            .unwrap_or_else(|| original.max_fee_per_gas());
+        let priority_fee = bump_fee(old_priority_fee).max(current_priority_fee);
+        let max_fee_per_gas = bump_fee(original.max_fee_per_gas())
+            .max(current_max_fee)
+            .max(priority_fee);
+
+        let nonce = original.nonce();
+        let tx = TransactionRequest::default()
+            .nonce(nonce)
+            .max_fee_per_gas(max_fee_per_gas)
+            .max_priority_fee_per_gas(priority_fee);
+        let tx = match kind {
+            ReplacementKind::SpeedUp => {
+                let tx = tx
+                    .value(original.value())
+                    .input(original.input().clone().into())
+                    .gas_limit(original.gas_limit());
+                match original.to() {
+                    Some(to) => tx.to(to),
+                    None => tx.into_create(),
+                }
+            }
+            ReplacementKind::Cancel => tx.to(self.from).value(U256::ZERO).gas_limit(TRANSFER_GAS),
+        };
+
+        let sent = self.broadcast(tx).await?;
+        if let Some(nonces) = &self.nonces {
+            let mut pending = nonces.pending(chain_id, self.from);
+            if let Some(entry) = pending.iter_mut().find(|p| p.nonce == nonce) {
+                entry.tx_hash = sent.tx_hash.clone();
+                entry.assigned_at = Utc::now();
+                nonces.save(chain_id, self.from, &pending);
+            }
+        }
+        Ok(Some(sent))
+    }
+
+    /// Broadcast a transaction and return the hash.
+    async fn broadcast(&self, tx: TransactionRequest) -> Result<SendResult, AvaxClientError> {
         let pending =
             self.provider.send_transaction(tx).await.map_err(|e| {
                 AvaxClientError::TransactionFailed(format!("Failed to send: {}", e))
@@ -392,6 +694,52 @@ pub fn format_amount(amount: U256, decimals: u8) -> String {
 mod tests {
     use super::*;
 
+    fn reserved(nonce: u64, age_secs: i64, now: DateTime<Utc>) -> StoredPendingNonce {
+        StoredPendingNonce {
+            nonce,
+            tx_hash: format!("0x{nonce}"),
+            assigned_at: now - Duration::seconds(age_secs),
+        }
+    }
+
+    #[test]
+    fn next_nonce_skips_reserved_nonces_the_node_has_not_reported() {
+        let now = Utc::now();
+        // Two sends broadcast a moment ago; the node only reports one.
+        let mut pending = vec![reserved(5, 1, now), reserved(6, 1, now)];
+        assert_eq!(next_nonce(&mut pending, 5, 6, now), 7);
+        assert_eq!(pending.len(), 2);
+
+        // Nonce 5 was mined meanwhile.
+        assert_eq!(next_nonce(&mut pending, 6, 7, now), 7);
+        assert_eq!(pending.len(), 1);
+
+        // Nothing reserved: the node's pending count is used.
+        assert_eq!(next_nonce(&mut Vec::new(), 3, 4, now), 4);
+    }
+
+    #[test]
+    fn next_nonce_reuses_nonces_of_dropped_transactions() {
+        let now = Utc::now();
+        let mut pending = vec![
+            reserved(5, STALE_NONCE_SECS + 60, now),
+            reserved(6, STALE_NONCE_SECS + 30, now),
+        ];
+        // The node knows neither transaction any more.
+        assert_eq!(next_nonce(&mut pending, 5, 5, now), 5);
+        assert!(pending.is_empty());
+    }
+
+    #[test]
+    fn bumped_fees_clear_the_replacement_minimum() {
+        assert_eq!(bump_fee(2_500_000_000), 2_812_500_000);
+        assert_eq!(bump_fee(1), 2);
+        assert_eq!(bump_fee(u128::MAX), u128::MAX);
+        for fee in [7, 1_000, 25_000_000_001] {
+            assert!(bump_fee(fee) * 10 >= fee * 11);
+        }
+    }
+
     #[test]
     fn test_parse_amount_whole() {
         let result = parse_amount("1", 18).unwrap();
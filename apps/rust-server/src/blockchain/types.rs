@@ -9,9 +9,11 @@ use std::sync::OnceLock;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
-/// Avalanche network configuration.
+/// EVM network configuration.
 #[derive(Debug, Clone)]
 pub struct NetworkConfig {
+    /// Short name clients pass as `network` (e.g. "fuji")
+    pub id: &'static str,
     /// Network name for display
     pub name: &'static str,
     /// Chain ID
@@ -20,6 +22,12 @@ pub struct NetworkConfig {
     pub rpc_url: &'static str,
     /// Block explorer URL
     pub explorer_url: &'static str,
+    /// Native token symbol (e.g. "AVAX")
+    pub native_symbol: &'static str,
+    /// Native token name (e.g. "Avalanche")
+    pub native_name: &'static str,
+    /// Native token decimals
+    pub native_decimals: u8,
 }
 
 /// Default Fuji RPC endpoint.
@@ -45,26 +53,30 @@ fn fuji_rpc_url() -> &'static str {
 /// Call this function (not a const) so that the env override is resolved.
 pub fn avax_fuji() -> NetworkConfig {
     NetworkConfig {
-        name: "Avalanche Fuji Testnet",
-        chain_id: 43113,
         rpc_url: fuji_rpc_url(),
-        explorer_url: "https://testnet.snowtrace.io",
+        ..AVAX_FUJI
     }
 }
 
 /// Legacy constant for backwards compatibility in tests.
 /// Prefer `avax_fuji()` which supports env var override.
 pub const AVAX_FUJI: NetworkConfig = NetworkConfig {
+    id: NETWORK_FUJI,
     name: "Avalanche Fuji Testnet",
     chain_id: 43113,
     rpc_url: DEFAULT_FUJI_RPC,
     explorer_url: "https://testnet.snowtrace.io",
+    native_symbol: "AVAX",
+    native_name: "Avalanche",
+    native_decimals: 18,
 };
 
-/// Supported network identifier for this build.
+/// Identifier of the network every deployment supports. Fiat settlement,
+/// claims, bridging and smart accounts only run here; other registered
+/// networks (see [`super::network`]) support plain transfers.
 pub const NETWORK_FUJI: &str = "fuji";
 
-/// Validate network input for Fuji-only runtime.
+/// Validate network input for Fuji-only features.
 pub fn ensure_fuji_network(raw: Option<&str>) -> Result<(), String> {
     let value = raw.unwrap_or(NETWORK_FUJI).trim().to_ascii_lowercase();
     if value == NETWORK_FUJI {
@@ -109,6 +121,19 @@ pub struct WalletBalanceResponse {
     pub token_balances: Vec<TokenBalance>,
 }
 
+/// Header fields of the block a transaction was mined in.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct BlockReference {
+    pub number: u64,
+    pub hash: String,
+    pub parent_hash: String,
+    /// Unix seconds.
+    pub timestamp: u64,
+    pub state_root: String,
+    pub transactions_root: String,
+    pub receipts_root: String,
+}
+
 /// Known ERC-20 tokens on Avalanche.
 /// TODO: Configure metadata and addresses for actual euro stablecoins when available.
 #[allow(dead_code)]
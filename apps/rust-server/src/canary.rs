@@ -0,0 +1,476 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! # Canary Transfers
+//!
+//! Opt-in background task that checks the transfer pipeline end to end.
+//! Every `CANARY_INTERVAL_SECS` (default 900) it sends `CANARY_AMOUNT`
+//! rEUR (default 0.01) between the two internal wallets named in
+//! `CANARY_WALLET_IDS`, alternating direction so the balance shuttles back
+//! and forth, and measures:
+//!
+//! - **confirmation latency** — until the transaction has a receipt;
+//! - **indexing latency** — until the event indexer has confirmed the
+//!   pending record in the transaction database.
+//!
+//! A run is `degraded` when either latency exceeds `CANARY_CONFIRM_ALERT_SECS`
+//! (default 60) or `CANARY_INDEX_ALERT_SECS` (default 180), and `failed` when
+//! the send fails, reverts, or a stage misses `CANARY_TIMEOUT_SECS` (default
+//! 600). Recent runs are kept in memory and reported at
+//! `GET /v1/admin/canary`; the admin overview raises an alert when the latest
+//! run was not healthy.
+//!
+//! Both wallets need AVAX for gas and one of them the rEUR being shuttled.
+//! Without `CANARY_WALLET_IDS` the worker is not started.
+//!
+//! ## Shutdown
+//!
+//! Uses `tokio_util::sync::CancellationToken` for graceful shutdown, following
+//! the same pattern as the `FiatPoller`.
+
+use std::collections::VecDeque;
+use std::env;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+use utoipa::ToSchema;
+
+use crate::api::claims::record_transfer;
+use crate::api::transactions::send_from_wallet;
+use crate::blockchain::{
+    avax_fuji, parse_amount, tokens, transactions::NonceManager, AvaxClient, NETWORK_FUJI,
+    REUR_TOKEN,
+};
+use crate::storage::{
+    EncryptedStorage, TokenType, TxCache, TxDatabase, TxStatus, WalletRepository,
+};
+
+/// Runs kept for `GET /v1/admin/canary` (one day at the default interval).
+pub const HISTORY_LEN: usize = 96;
+
+/// Delay between receipt and index checks while a run is in flight.
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+const DEFAULT_INTERVAL_SECS: u64 = 900;
+const DEFAULT_AMOUNT: &str = "0.01";
+const DEFAULT_CONFIRM_ALERT_SECS: u64 = 60;
+const DEFAULT_INDEX_ALERT_SECS: u64 = 180;
+const DEFAULT_TIMEOUT_SECS: u64 = 600;
+
+/// Canary settings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CanaryConfig {
+    /// The two internal wallets transfers alternate between.
+    pub wallet_ids: [String; 2],
+    /// rEUR sent per run.
+    pub amount: String,
+    pub interval_secs: u64,
+    /// Confirmation latency above which a run is degraded.
+    pub confirm_alert_secs: u64,
+    /// Indexing latency above which a run is degraded.
+    pub index_alert_secs: u64,
+    /// Time each stage may take before the run fails.
+    pub timeout_secs: u64,
+}
+
+fn env_secs(name: &str, default: u64) -> u64 {
+    env::var(name)
+        .ok()
+        .and_then(|v| v.trim().parse().ok())
+        .filter(|v| *v > 0)
+        .unwrap_or(default)
+}
+
+impl CanaryConfig {
+    /// Settings from the `CANARY_*` variables, or `None` when
+    /// `CANARY_WALLET_IDS` does not name two distinct wallets.
+    pub fn from_env() -> Option<Self> {
+        let ids = env::var("CANARY_WALLET_IDS").ok()?;
+        let ids: Vec<&str> = ids
+            .split(',')
+            .map(str::trim)
+            .filter(|id| !id.is_empty())
+            .collect();
+        let [a, b] = ids.as_slice() else {
+            return None;
+        };
+        if a == b {
+            return None;
+        }
+        Some(Self {
+            wallet_ids: [a.to_string(), b.to_string()],
+            amount: env::var("CANARY_AMOUNT")
+                .ok()
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+                .unwrap_or_else(|| DEFAULT_AMOUNT.to_string()),
+            interval_secs: env_secs("CANARY_INTERVAL_SECS", DEFAULT_INTERVAL_SECS),
+            confirm_alert_secs: env_secs("CANARY_CONFIRM_ALERT_SECS", DEFAULT_CONFIRM_ALERT_SECS),
+            index_alert_secs: env_secs("CANARY_INDEX_ALERT_SECS", DEFAULT_INDEX_ALERT_SECS),
+            timeout_secs: env_secs("CANARY_TIMEOUT_SECS", DEFAULT_TIMEOUT_SECS),
+        })
+    }
+
+    /// Heartbeat age after which the worker counts as stale: one missed run,
+    /// including a run that waits out both stage timeouts.
+    pub fn stale_after_secs(&self) -> i64 {
+        (2 * self.interval_secs + 2 * self.timeout_secs) as i64
+    }
+}
+
+/// Health of one canary run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CanaryOutcome {
+    Healthy,
+    /// Completed, but slower than an alert threshold.
+    Degraded,
+    /// Send failed, reverted, or a stage timed out.
+    Failed,
+}
+
+/// One canary transfer.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct CanaryRun {
+    pub started_at: DateTime<Utc>,
+    pub from_wallet_id: String,
+    pub to_wallet_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tx_hash: Option<String>,
+    pub outcome: CanaryOutcome,
+    /// Send to receipt.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confirmation_ms: Option<u64>,
+    /// Send to the indexer confirming the record.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub indexing_ms: Option<u64>,
+    /// Why the run was not healthy.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+static RUNS: OnceLock<Mutex<VecDeque<CanaryRun>>> = OnceLock::new();
+
+fn runs() -> &'static Mutex<VecDeque<CanaryRun>> {
+    RUNS.get_or_init(|| Mutex::new(VecDeque::with_capacity(HISTORY_LEN)))
+}
+
+fn record_run(run: CanaryRun) {
+    let mut runs = runs().lock().unwrap_or_else(|e| e.into_inner());
+    if runs.len() == HISTORY_LEN {
+        runs.pop_back();
+    }
+    runs.push_front(run);
+}
+
+/// Recent runs, newest first.
+pub fn recent_runs() -> Vec<CanaryRun> {
+    runs()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .iter()
+        .cloned()
+        .collect()
+}
+
+/// The most recent run, if any.
+pub fn latest_run() -> Option<CanaryRun> {
+    runs()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .front()
+        .cloned()
+}
+
+/// Compare a completed run's latencies with the alert thresholds.
+fn classify(
+    confirmation_ms: u64,
+    indexing_ms: u64,
+    config: &CanaryConfig,
+) -> (CanaryOutcome, Option<String>) {
+    let mut slow = Vec::new();
+    if confirmation_ms > config.confirm_alert_secs * 1000 {
+        slow.push(format!(
+            "confirmation took {}s (limit {}s)",
+            confirmation_ms / 1000,
+            config.confirm_alert_secs
+        ));
+    }
+    if indexing_ms > config.index_alert_secs * 1000 {
+        slow.push(format!(
+            "indexing took {}s (limit {}s)",
+            indexing_ms / 1000,
+            config.index_alert_secs
+        ));
+    }
+    if slow.is_empty() {
+        (CanaryOutcome::Healthy, None)
+    } else {
+        (CanaryOutcome::Degraded, Some(slow.join("; ")))
+    }
+}
+
+/// Background task sending canary transfers.
+pub struct CanaryWorker {
+    storage: Arc<EncryptedStorage>,
+    tx_db: Arc<TxDatabase>,
+    tx_cache: Arc<TxCache>,
+    nonces: Arc<NonceManager>,
+    config: CanaryConfig,
+}
+
+impl CanaryWorker {
+    /// Create a worker over the given storage and transaction database.
+    pub fn new(
+        storage: Arc<EncryptedStorage>,
+        tx_db: Arc<TxDatabase>,
+        tx_cache: Arc<TxCache>,
+        config: CanaryConfig,
+    ) -> Self {
+        Self {
+            nonces: Arc::new(NonceManager::new(storage.clone())),
+            storage,
+            tx_db,
+            tx_cache,
+            config,
+        }
+    }
+
+    /// Share the server's nonce manager so worker sends do not race user sends.
+    pub fn with_nonce_manager(mut self, nonces: Arc<NonceManager>) -> Self {
+        self.nonces = nonces;
+        self
+    }
+
+    /// Run the worker loop until the cancellation token is triggered.
+    pub async fn run(self, shutdown: CancellationToken) {
+        info!(
+            interval_secs = self.config.interval_secs,
+            from = %self.config.wallet_ids[0],
+            to = %self.config.wallet_ids[1],
+            "Canary worker starting"
+        );
+
+        let mut forward = true;
+        loop {
+            if shutdown.is_cancelled() {
+                info!("Canary worker shutting down");
+                return;
+            }
+
+            let [a, b] = &self.config.wallet_ids;
+            let (from, to) = if forward { (a, b) } else { (b, a) };
+            let run = tokio::select! {
+                run = self.probe(from, to) => run,
+                _ = shutdown.cancelled() => {
+                    info!("Canary worker shutting down");
+                    return;
+                }
+            };
+            match run.outcome {
+                CanaryOutcome::Healthy => info!(
+                    tx_hash = ?run.tx_hash,
+                    confirmation_ms = ?run.confirmation_ms,
+                    indexing_ms = ?run.indexing_ms,
+                    "Canary transfer healthy"
+                ),
+                outcome => warn!(
+                    ?outcome,
+                    tx_hash = ?run.tx_hash,
+                    confirmation_ms = ?run.confirmation_ms,
+                    indexing_ms = ?run.indexing_ms,
+                    detail = ?run.detail,
+                    "Canary transfer not healthy"
+                ),
+            }
+            // Only flip once funds actually moved, so a failed send is
+            // retried in the same direction.
+            if run.tx_hash.is_some() {
+                forward = !forward;
+            }
+            record_run(run);
+            crate::worker_health::record_heartbeat(crate::worker_health::Worker::Canary);
+
+            tokio::select! {
+                _ = tokio::time::sleep(Duration::from_secs(self.config.interval_secs)) => {},
+                _ = shutdown.cancelled() => {
+                    info!("Canary worker shutting down");
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Send one transfer and wait for its receipt and its indexed record.
+    async fn probe(&self, from_id: &str, to_id: &str) -> CanaryRun {
+        let mut run = CanaryRun {
+            started_at: Utc::now(),
+            from_wallet_id: from_id.to_string(),
+            to_wallet_id: to_id.to_string(),
+            tx_hash: None,
+            outcome: CanaryOutcome::Failed,
+            confirmation_ms: None,
+            indexing_ms: None,
+            detail: None,
+        };
+        let fail = |mut run: CanaryRun, detail: String| {
+            run.detail = Some(detail);
+            run
+        };
+
+        let wallets = WalletRepository::new(&self.storage);
+        let (from, to) = match (wallets.get(from_id), wallets.get(to_id)) {
+            (Ok(from), Ok(to)) => (from, to),
+            (Err(e), _) | (_, Err(e)) => {
+                return fail(run, format!("Failed to load canary wallet: {e}"))
+            }
+        };
+        let Some(reur) = tokens().by_symbol(NETWORK_FUJI, REUR_TOKEN.symbol) else {
+            return fail(run, "rEUR is not listed on Fuji".to_string());
+        };
+        let contract = reur.address;
+        let amount = match parse_amount(&self.config.amount, reur.decimals) {
+            Ok(amount) => amount,
+            Err(e) => return fail(run, format!("Invalid CANARY_AMOUNT: {e}")),
+        };
+        let client = match AvaxClient::fuji().await {
+            Ok(client) => client,
+            Err(e) => return fail(run, format!("Failed to connect: {e}")),
+        };
+
+        let sent_at = Instant::now();
+        let token = TokenType::Erc20(contract);
+        let result = match send_from_wallet(
+            &self.storage,
+            &self.nonces,
+            &from,
+            &avax_fuji(),
+            &to.public_address,
+            &token,
+            amount,
+            None,
+            None,
+        )
+        .await
+        {
+            Ok(result) => result,
+            Err(e) => return fail(run, format!("Send failed: {}", e.message)),
+        };
+        record_transfer(
+            &self.tx_db,
+            Some(self.tx_cache.as_ref()),
+            &result,
+            from_id,
+            "sent",
+            &from.public_address,
+            &to.public_address,
+            &self.config.amount,
+            token,
+        );
+        run.tx_hash = Some(result.tx_hash.clone());
+        let deadline = Duration::from_secs(self.config.timeout_secs);
+
+        loop {
+            match client.get_transaction_receipt_status(&result.tx_hash).await {
+                Ok(Some(receipt)) if !receipt.success => {
+                    return fail(run, "Transaction reverted".to_string())
+                }
+                Ok(Some(_)) => break,
+                Ok(None) => {}
+                Err(e) => warn!(error = %e, "Canary receipt check failed"),
+            }
+            if sent_at.elapsed() >= deadline {
+                return fail(
+                    run,
+                    format!("Not confirmed within {}s", self.config.timeout_secs),
+                );
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+        let confirmation_ms = sent_at.elapsed().as_millis() as u64;
+        run.confirmation_ms = Some(confirmation_ms);
+
+        loop {
+            match self.tx_db.get_transaction(&result.tx_hash) {
+                Ok(Some(tx)) if tx.status == TxStatus::Confirmed => break,
+                Ok(_) => {}
+                Err(e) => warn!(error = %e, "Canary index check failed"),
+            }
+            if sent_at.elapsed() >= deadline + Duration::from_millis(confirmation_ms) {
+                return fail(
+                    run,
+                    format!(
+                        "Not indexed within {}s of confirmation",
+                        self.config.timeout_secs
+                    ),
+                );
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+        let indexing_ms = sent_at.elapsed().as_millis() as u64;
+        run.indexing_ms = Some(indexing_ms);
+
+        let (outcome, detail) = classify(confirmation_ms, indexing_ms, &self.config);
+        run.outcome = outcome;
+        run.detail = detail;
+        run
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> CanaryConfig {
+        CanaryConfig {
+            wallet_ids: ["a".to_string(), "b".to_string()],
+            amount: DEFAULT_AMOUNT.to_string(),
+            interval_secs: DEFAULT_INTERVAL_SECS,
+            confirm_alert_secs: 60,
+            index_alert_secs: 180,
+            timeout_secs: DEFAULT_TIMEOUT_SECS,
+        }
+    }
+
+    #[test]
+    fn runs_are_degraded_past_either_threshold() {
+        let config = config();
+        assert_eq!(
+            classify(5_000, 30_000, &config),
+            (CanaryOutcome::Healthy, None)
+        );
+
+        let (outcome, detail) = classify(61_000, 30_000, &config);
+        assert_eq!(outcome, CanaryOutcome::Degraded);
+        assert_eq!(detail.unwrap(), "confirmation took 61s (limit 60s)");
+
+        let (outcome, detail) = classify(61_000, 200_000, &config);
+        assert_eq!(outcome, CanaryOutcome::Degraded);
+        assert!(detail.unwrap().contains("indexing took 200s"));
+    }
+
+    #[test]
+    fn history_keeps_the_newest_runs() {
+        for i in 0..HISTORY_LEN + 3 {
+            record_run(CanaryRun {
+                started_at: Utc::now(),
+                from_wallet_id: format!("w-{i}"),
+                to_wallet_id: "b".to_string(),
+                tx_hash: None,
+                outcome: CanaryOutcome::Failed,
+                confirmation_ms: None,
+                indexing_ms: None,
+                detail: None,
+            });
+        }
+        let runs = recent_runs();
+        assert_eq!(runs.len(), HISTORY_LEN);
+        assert_eq!(runs[0].from_wallet_id, format!("w-{}", HISTORY_LEN + 2));
+        assert_eq!(latest_run().unwrap().from_wallet_id, runs[0].from_wallet_id);
+    }
+}
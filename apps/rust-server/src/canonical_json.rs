@@ -0,0 +1,211 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! # Canonical JSON
+//!
+//! Deterministic serialization for payloads that are signed or MACed, so
+//! that the signer and any verifier agree on the exact bytes. The output
+//! follows the JSON Canonicalization Scheme (RFC 8785):
+//!
+//! - no insignificant whitespace;
+//! - object members sorted by the UTF-16 code units of their names;
+//! - strings escape only `"`, `\` and control characters, using the short
+//!   forms (`\n`, `\t`, ...) where they exist and lowercase `\u00xx`
+//!   otherwise;
+//! - numbers in their shortest round-trip form, formatted like
+//!   ECMAScript's `Number.prototype.toString`.
+//!
+//! Sign the bytes from [`to_vec`] or [`to_string`] and send those same
+//! bytes; never re-serialize a payload between signing and sending.
+
+use std::cmp::Ordering;
+
+use serde::Serialize;
+use serde_json::{Number, Value};
+
+/// Serialize `value` to canonical JSON.
+pub fn to_string<T: Serialize + ?Sized>(value: &T) -> Result<String, serde_json::Error> {
+    let value = serde_json::to_value(value)?;
+    let mut out = String::new();
+    write_value(&mut out, &value);
+    Ok(out)
+}
+
+/// Serialize `value` to canonical JSON bytes.
+pub fn to_vec<T: Serialize + ?Sized>(value: &T) -> Result<Vec<u8>, serde_json::Error> {
+    to_string(value).map(String::into_bytes)
+}
+
+fn write_value(out: &mut String, value: &Value) {
+    match value {
+        Value::Null => out.push_str("null"),
+        Value::Bool(b) => out.push_str(if *b { "true" } else { "false" }),
+        Value::Number(n) => write_number(out, n),
+        Value::String(s) => write_string(out, s),
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_value(out, item);
+            }
+            out.push(']');
+        }
+        Value::Object(map) => {
+            let mut members: Vec<_> = map.iter().collect();
+            members.sort_by(|(a, _), (b, _)| utf16_cmp(a, b));
+            out.push('{');
+            for (i, (key, item)) in members.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_string(out, key);
+                out.push(':');
+                write_value(out, item);
+            }
+            out.push('}');
+        }
+    }
+}
+
+fn utf16_cmp(a: &str, b: &str) -> Ordering {
+    a.encode_utf16().cmp(b.encode_utf16())
+}
+
+fn write_string(out: &mut String, s: &str) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\u{08}' => out.push_str("\\b"),
+            '\t' => out.push_str("\\t"),
+            '\n' => out.push_str("\\n"),
+            '\u{0c}' => out.push_str("\\f"),
+            '\r' => out.push_str("\\r"),
+            c if c < ' ' => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+fn write_number(out: &mut String, n: &Number) {
+    if let Some(i) = n.as_i64() {
+        out.push_str(&i.to_string());
+    } else if let Some(u) = n.as_u64() {
+        out.push_str(&u.to_string());
+    } else if let Some(f) = n.as_f64() {
+        // serde_json never holds NaN or infinities.
+        out.push_str(&format_f64(f));
+    }
+}
+
+/// ECMAScript `Number.prototype.toString` for a finite `f64`.
+fn format_f64(f: f64) -> String {
+    if f == 0.0 {
+        return "0".to_string();
+    }
+    // Rust's `{:e}` gives the shortest round-trip digits, e.g. `1.25e-7`.
+    let sci = format!("{:e}", f.abs());
+    let (mantissa, exponent) = sci.split_once('e').expect("`{:e}` has an exponent");
+    let digits: String = mantissa.chars().filter(|c| *c != '.').collect();
+    let k = digits.len() as i32;
+    // Decimal point position: value = 0.digits × 10^n.
+    let n = exponent
+        .parse::<i32>()
+        .expect("`{:e}` exponent is an integer")
+        + 1;
+
+    let body = if k <= n && n <= 21 {
+        format!("{digits}{}", "0".repeat((n - k) as usize))
+    } else if 0 < n && n <= 21 {
+        format!("{}.{}", &digits[..n as usize], &digits[n as usize..])
+    } else if -6 < n && n <= 0 {
+        format!("0.{}{digits}", "0".repeat(-n as usize))
+    } else {
+        let sign = if n - 1 < 0 { '-' } else { '+' };
+        let fraction = if k > 1 {
+            format!(".{}", &digits[1..])
+        } else {
+            String::new()
+        };
+        format!("{}{fraction}e{sign}{}", &digits[..1], (n - 1).abs())
+    };
+    if f < 0.0 {
+        format!("-{body}")
+    } else {
+        body
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn members_are_sorted_and_whitespace_dropped() {
+        let value = json!({
+            "b": [1, {"z": null, "a": true}],
+            "a": "x",
+            "\u{ff61}": 1,
+            "\u{1f600}": 2,
+        });
+        // U+1F600 encodes as the surrogate pair D83D DE00, which sorts
+        // before U+FF61 in UTF-16 though not in UTF-8.
+        assert_eq!(
+            to_string(&value).unwrap(),
+            "{\"a\":\"x\",\"b\":[1,{\"a\":true,\"z\":null}],\"\u{1f600}\":2,\"\u{ff61}\":1}"
+        );
+    }
+
+    #[test]
+    fn output_does_not_depend_on_field_order() {
+        #[derive(Serialize)]
+        struct Forward {
+            amount: u64,
+            id: &'static str,
+        }
+        #[derive(Serialize)]
+        struct Backward {
+            id: &'static str,
+            amount: u64,
+        }
+        assert_eq!(
+            to_vec(&Forward { amount: 5, id: "r" }).unwrap(),
+            to_vec(&Backward { id: "r", amount: 5 }).unwrap()
+        );
+    }
+
+    #[test]
+    fn strings_use_minimal_escapes() {
+        assert_eq!(
+            to_string("é\"\\\n\u{1f}/").unwrap(),
+            "\"é\\\"\\\\\\n\\u001f/\""
+        );
+    }
+
+    #[test]
+    fn numbers_match_ecmascript_formatting() {
+        let cases = [
+            (json!(0.0), "0"),
+            (json!(-0.0), "0"),
+            (json!(1.0), "1"),
+            (json!(-1.5), "-1.5"),
+            (json!(100.0), "100"),
+            (json!(0.000001), "0.000001"),
+            (json!(1e-7), "1e-7"),
+            (json!(123e18), "123000000000000000000"),
+            (json!(1e21), "1e+21"),
+            (json!(4.5e-10), "4.5e-10"),
+            (json!(u64::MAX), "18446744073709551615"),
+            (json!(-42), "-42"),
+        ];
+        for (value, expected) in cases {
+            assert_eq!(to_string(&value).unwrap(), expected, "{value}");
+        }
+    }
+}
@@ -0,0 +1,245 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! # Claim Expiry Worker
+//!
+//! Background task that settles claimable transfers nobody claimed. Every
+//! [`EXPIRY_INTERVAL_SECS`] it returns expired pending transfers from their
+//! escrow sub-wallet to the sender's wallet, and retries the gas stipend for
+//! transfers whose stipend send failed at creation.
+//!
+//! The same run applies the timeouts of escrowed payments between users:
+//! payments whose payee never requested release are refunded after
+//! `expires_at`, and release requests the payer left unanswered are released
+//! after their `review_deadline`.
+//!
+//! A transfer that fails to settle keeps its status with `last_error` set and
+//! is retried on the next run.
+//!
+//! ## Shutdown
+//!
+//! Uses `tokio_util::sync::CancellationToken` for graceful shutdown, following
+//! the same pattern as the `FiatPoller`.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::Utc;
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+
+use crate::api::claims::{fund_gas, send_stipend, settle_claim};
+use crate::api::escrow::{apply_action, EscrowAction};
+use crate::blockchain::transactions::NonceManager;
+use crate::storage::{
+    AuditEvent, AuditEventType, AuditRepository, ClaimStatus, EncryptedStorage, EscrowActor,
+    EscrowPaymentStatus, EscrowRepository, TxCache, TxDatabase,
+};
+
+/// Interval between expiry runs.
+pub const EXPIRY_INTERVAL_SECS: u64 = 300;
+
+/// Background task returning expired claimable transfers.
+pub struct ClaimExpiryWorker {
+    storage: Arc<EncryptedStorage>,
+    tx_db: Arc<TxDatabase>,
+    tx_cache: Arc<TxCache>,
+    nonces: Arc<NonceManager>,
+}
+
+impl ClaimExpiryWorker {
+    /// Create a worker over the given storage and transaction database.
+    pub fn new(
+        storage: Arc<EncryptedStorage>,
+        tx_db: Arc<TxDatabase>,
+        tx_cache: Arc<TxCache>,
+    ) -> Self {
+        Self {
+            nonces: Arc::new(NonceManager::new(storage.clone())),
+            storage,
+            tx_db,
+            tx_cache,
+        }
+    }
+
+    /// Share the server's nonce manager so worker sends do not race user sends.
+    pub fn with_nonce_manager(mut self, nonces: Arc<NonceManager>) -> Self {
+        self.nonces = nonces;
+        self
+    }
+
+    /// Run the worker loop until the cancellation token is triggered.
+    pub async fn run(self, shutdown: CancellationToken) {
+        info!(
+            interval_secs = EXPIRY_INTERVAL_SECS,
+            "Claim expiry worker starting"
+        );
+
+        loop {
+            if shutdown.is_cancelled() {
+                info!("Claim expiry worker shutting down");
+                return;
+            }
+
+            self.expiry_step().await;
+            self.escrow_step().await;
+            crate::worker_health::record_heartbeat(crate::worker_health::Worker::ClaimExpiry);
+
+            tokio::select! {
+                _ = tokio::time::sleep(Duration::from_secs(EXPIRY_INTERVAL_SECS)) => {},
+                _ = shutdown.cancelled() => {
+                    info!("Claim expiry worker shutting down");
+                    return;
+                }
+            }
+        }
+    }
+
+    async fn expiry_step(&self) {
+        let repo = EscrowRepository::new(&self.storage);
+        let claims = match repo.list_claims() {
+            Ok(claims) => claims,
+            Err(e) => {
+                warn!(error = %e, "Claim expiry: failed to list claims");
+                return;
+            }
+        };
+
+        let now = Utc::now();
+        for mut claim in claims {
+            if claim.status != ClaimStatus::Pending {
+                continue;
+            }
+
+            if !claim.gas_funded {
+                let funded = fund_gas(
+                    &self.storage,
+                    &self.nonces,
+                    &self.tx_db,
+                    Some(&self.tx_cache),
+                    &mut claim,
+                )
+                .await;
+                if let Err(e) = repo.update_claim(&claim) {
+                    warn!(claim_id = %claim.claim_id, error = %e, "Claim expiry: failed to save claim");
+                }
+                match funded {
+                    Ok(()) => info!(claim_id = %claim.claim_id, "Claim expiry: gas stipend sent"),
+                    Err(e) => {
+                        warn!(claim_id = %claim.claim_id, error = %e.message, "Claim expiry: gas stipend failed")
+                    }
+                }
+                // A fresh stipend has to confirm before the escrow can pay out.
+                continue;
+            }
+
+            if claim.expires_at > now {
+                continue;
+            }
+            match settle_claim(
+                &self.storage,
+                &self.tx_db,
+                Some(&self.tx_cache),
+                &claim.claim_id,
+                &claim.sender_wallet_id,
+                &claim.sender_address,
+                ClaimStatus::Reclaimed,
+                None,
+            )
+            .await
+            {
+                Ok(settled) => {
+                    info!(claim_id = %settled.claim_id, "Claim expiry: returned to sender");
+                    let event = AuditEvent::new(AuditEventType::ClaimReclaimed)
+                        .with_resource("claim", &settled.claim_id)
+                        .with_details(serde_json::json!({
+                            "reason": "expired",
+                            "tx_hash": settled.payout_tx_hash,
+                        }));
+                    let _ = AuditRepository::new(&self.storage).log(&event);
+                }
+                Err(e) => {
+                    warn!(claim_id = %claim.claim_id, error = %e.message, "Claim expiry: return failed")
+                }
+            }
+        }
+    }
+
+    async fn escrow_step(&self) {
+        let repo = EscrowRepository::new(&self.storage);
+        let payments = match repo.list_payments() {
+            Ok(payments) => payments,
+            Err(e) => {
+                warn!(error = %e, "Claim expiry: failed to list escrows");
+                return;
+            }
+        };
+
+        let now = Utc::now();
+        for mut payment in payments {
+            if payment.status.is_final() || payment.status == EscrowPaymentStatus::Settling {
+                continue;
+            }
+
+            if !payment.gas_funded {
+                match send_stipend(
+                    &self.storage,
+                    &self.nonces,
+                    &self.tx_db,
+                    Some(&self.tx_cache),
+                    &payment.payer_wallet_id,
+                    &payment.payer_address,
+                    &payment.escrow_address,
+                )
+                .await
+                {
+                    Ok(tx_hash) => {
+                        info!(escrow_id = %payment.escrow_id, "Claim expiry: escrow gas stipend sent");
+                        payment.funding_tx_hashes.push(tx_hash);
+                        payment.gas_funded = true;
+                        payment.last_error = None;
+                    }
+                    Err(e) => {
+                        warn!(escrow_id = %payment.escrow_id, error = %e.message, "Claim expiry: escrow gas stipend failed");
+                        payment.last_error =
+                            Some(format!("Gas stipend transfer failed: {}", e.message));
+                    }
+                }
+                if let Err(e) = repo.update_payment(&payment) {
+                    warn!(escrow_id = %payment.escrow_id, error = %e, "Claim expiry: failed to save escrow");
+                }
+                continue;
+            }
+
+            let action = match payment.status {
+                EscrowPaymentStatus::Funded if payment.expires_at <= now => EscrowAction::Refund,
+                EscrowPaymentStatus::ReleaseRequested
+                    if payment.review_deadline.is_some_and(|at| at <= now) =>
+                {
+                    EscrowAction::Release
+                }
+                _ => continue,
+            };
+            match apply_action(
+                &self.storage,
+                &self.tx_db,
+                Some(&self.tx_cache),
+                &payment.escrow_id,
+                EscrowActor::System,
+                None,
+                action,
+                Some("timeout".to_string()),
+            )
+            .await
+            {
+                Ok(settled) => {
+                    info!(escrow_id = %settled.escrow_id, status = ?settled.status, "Claim expiry: escrow timeout applied")
+                }
+                Err(e) => {
+                    warn!(escrow_id = %payment.escrow_id, error = %e.message, "Claim expiry: escrow timeout failed")
+                }
+            }
+        }
+    }
+}
@@ -20,6 +20,10 @@
 //! | `CLERK_AUDIENCE` | Expected JWT audience claim | Optional |
 //! | `LOG_FORMAT` | Logging format (`json` or `pretty`) | `pretty` |
 //! | `RUST_LOG` | Log level filter | `info,tower_http=debug` |
+//! | `FIAT_RESERVE_KEY_SOURCE` | `enclave` or `ceremony` (reserve wallet key origin) | `enclave` |
+//! | `SECRET_SIGNER` | `enclave` or `kms` (where non-wallet signing keys live) | `enclave` |
+//! | `SECRET_KMS_URL` | Base URL of the external KMS | Required for `kms` |
+//! | `SECRET_KMS_TOKEN` | Bearer token for the external KMS | Required for `kms` |
 
 /// Environment variable name for the encrypted data directory path.
 ///
@@ -30,6 +34,33 @@
 /// `/data` (set in Gramine manifest as encrypted mount point)
 pub const DATA_DIR_ENV: &str = "DATA_DIR";
 
+/// Environment variable selecting how the fiat reserve wallet key is created.
+///
+/// - `enclave` (default): generated from enclave randomness at startup.
+/// - `ceremony`: startup bootstrap is skipped and the key must be assembled
+///   from operator Shamir shares via the admin key-ceremony endpoints.
+pub const FIAT_RESERVE_KEY_SOURCE_ENV: &str = "FIAT_RESERVE_KEY_SOURCE";
+
+/// Environment variable holding the sealed admin bootstrap setup token.
+///
+/// When unset and no admin was designated yet, a token is generated at
+/// startup and printed once to the enclave's stderr instead.
+pub const ADMIN_BOOTSTRAP_TOKEN_ENV: &str = "ADMIN_BOOTSTRAP_TOKEN";
+
+/// Environment variable selecting where webhook, claim-link and return-state
+/// keys are kept.
+///
+/// - `enclave` (default): files in encrypted storage.
+/// - `kms`: an external KMS at [`SECRET_KMS_URL_ENV`]; see
+///   [`crate::secret_signer`].
+pub const SECRET_SIGNER_ENV: &str = "SECRET_SIGNER";
+
+/// Environment variable holding the external KMS base URL.
+pub const SECRET_KMS_URL_ENV: &str = "SECRET_KMS_URL";
+
+/// Environment variable holding the external KMS bearer token.
+pub const SECRET_KMS_TOKEN_ENV: &str = "SECRET_KMS_TOKEN";
+
 // =============================================================================
 // Discovery Configuration (Phase 2)
 // =============================================================================
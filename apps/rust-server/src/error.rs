@@ -14,19 +14,31 @@
 //! use crate::error::ApiError;
 //!
 //! // Return a 404 error
-//! return Err(ApiError::not_found("Wallet not found"));
+//! return Err(ApiError::not_found("Wallet not found").with_code("wallet_not_found"));
 //!
 //! // Return a 403 error
 //! return Err(ApiError::forbidden("Not your wallet"));
+//!
+//! // Storage errors convert with `?`; NotFound becomes 404, and so on
+//! let wallet = WalletRepository::new(storage).get(&wallet_id)?;
+//!
+//! // Name the operation so unexpected failures are traceable
+//! repo.update(&wallet).context("Failed to update wallet")?;
 //! ```
 //!
 //! ## JSON Response Format
 //!
-//! All errors are returned as JSON with a single `error` field:
+//! All errors are returned as JSON with an `error` field. Errors given a
+//! stable code with [`ApiError::with_code`] also carry `error_code`, which
+//! clients should match on instead of the message; [`crate::i18n`]
+//! translates the message for these codes.
 //!
 //! ```json
-//! { "error": "Wallet not found" }
+//! { "error": "Wallet not found", "error_code": "wallet_not_found" }
 //! ```
+//!
+//! Errors a client can recover from may add a `details` object with the
+//! figures it needs (see [`ApiError::with_details`]).
 
 use axum::{
     http::StatusCode,
@@ -35,6 +47,9 @@ use axum::{
 };
 use serde::Serialize;
 
+use crate::secret_signer::SecretSignerError;
+use crate::storage::StorageError;
+
 /// API error with HTTP status and message.
 ///
 /// This type implements `IntoResponse`, allowing it to be returned directly
@@ -45,6 +60,10 @@ pub struct ApiError {
     pub status: StatusCode,
     /// Human-readable error message (included in JSON response).
     pub message: String,
+    /// Stable machine-readable code, if the error has one.
+    pub code: Option<&'static str>,
+    /// Structured context for the client, if any.
+    pub details: Option<serde_json::Value>,
 }
 
 /// JSON body structure for error responses.
@@ -52,6 +71,12 @@ pub struct ApiError {
 struct ErrorBody {
     /// The error message.
     error: String,
+    /// Stable error code.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error_code: Option<&'static str>,
+    /// Structured context.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    details: Option<serde_json::Value>,
 }
 
 impl ApiError {
@@ -64,9 +89,26 @@ impl ApiError {
         Self {
             status,
             message: message.into(),
+            code: None,
+            details: None,
         }
     }
 
+    /// Attach a stable error code (e.g. `wallet_not_found`).
+    ///
+    /// Codes never change once published; add the code to the message
+    /// catalog in [`crate::i18n`] so the message can be translated.
+    pub fn with_code(mut self, code: &'static str) -> Self {
+        self.code = Some(code);
+        self
+    }
+
+    /// Attach structured context, returned as `details`.
+    pub fn with_details(mut self, details: impl Serialize) -> Self {
+        self.details = serde_json::to_value(details).ok();
+        self
+    }
+
     /// Create a 404 Not Found error.
     ///
     /// Use when a requested resource does not exist.
@@ -117,12 +159,67 @@ impl ApiError {
     }
 
     /// Create a 429 Too Many Requests error.
-    #[allow(dead_code)] // Prepared for rate-limiting middleware
     pub fn too_many_requests(message: impl Into<String>) -> Self {
         Self::new(StatusCode::TOO_MANY_REQUESTS, message)
     }
 }
 
+impl ApiError {
+    /// Map a storage error to its API status. `action` prefixes the message
+    /// of failures the client cannot act on.
+    fn from_storage(error: StorageError, action: &str) -> Self {
+        match error {
+            StorageError::NotFound(entity) => Self::not_found(format!("{entity} not found")),
+            StorageError::NotFoundResource { resource, id } => {
+                Self::not_found(format!("{resource} {id} not found"))
+            }
+            StorageError::AlreadyExists(entity) => {
+                Self::conflict(format!("{entity} already exists"))
+            }
+            StorageError::PermissionDenied { .. } => {
+                Self::forbidden("You don't have permission to access this resource")
+                    .with_code("permission_denied")
+            }
+            StorageError::NotInitialized => Self::service_unavailable("Storage is not available")
+                .with_code("storage_unavailable"),
+            other => {
+                tracing::error!(error = %other, action, "Storage operation failed");
+                Self::internal(format!("{action}: {other}"))
+            }
+        }
+    }
+}
+
+impl From<StorageError> for ApiError {
+    fn from(error: StorageError) -> Self {
+        Self::from_storage(error, "Failed to access storage")
+    }
+}
+
+impl From<SecretSignerError> for ApiError {
+    fn from(error: SecretSignerError) -> Self {
+        tracing::error!(error = %error, "Secret signer failed");
+        match error {
+            SecretSignerError::Kms(_) => {
+                Self::service_unavailable("Key management service unavailable")
+            }
+            _ => Self::internal("Signing key unavailable"),
+        }
+    }
+}
+
+/// Attach the failed operation to a storage error as it becomes an
+/// [`ApiError`]. Statuses are the same as the `From` conversion.
+pub trait StorageContext<T> {
+    fn context(self, action: &str) -> Result<T, ApiError>;
+}
+
+impl<T> StorageContext<T> for Result<T, StorageError> {
+    fn context(self, action: &str) -> Result<T, ApiError> {
+        self.map_err(|error| ApiError::from_storage(error, action))
+    }
+}
+
 impl IntoResponse for ApiError {
     /// Convert the error into an Axum HTTP response.
     ///
@@ -131,6 +228,8 @@ impl IntoResponse for ApiError {
     fn into_response(self) -> Response {
         let body = Json(ErrorBody {
             error: self.message,
+            error_code: self.code,
+            details: self.details,
         });
         (self.status, body).into_response()
     }
@@ -156,6 +255,53 @@ mod tests {
         assert_eq!(unp.message, "oops");
     }
 
+    #[test]
+    fn storage_errors_map_to_their_status() {
+        let cases = [
+            (
+                StorageError::NotFound("Wallet w-1".into()),
+                StatusCode::NOT_FOUND,
+            ),
+            (
+                StorageError::AlreadyExists("Wallet w-1".into()),
+                StatusCode::CONFLICT,
+            ),
+            (
+                StorageError::PermissionDenied {
+                    user_id: "u-1".into(),
+                    resource: "wallet".into(),
+                },
+                StatusCode::FORBIDDEN,
+            ),
+            (
+                StorageError::NotInitialized,
+                StatusCode::SERVICE_UNAVAILABLE,
+            ),
+            (
+                StorageError::SerializationError("bad".into()),
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ),
+        ];
+        for (error, status) in cases {
+            assert_eq!(ApiError::from(error).status, status);
+        }
+
+        let not_found = ApiError::from(StorageError::NotFound("Wallet w-1".into()));
+        assert_eq!(not_found.message, "Wallet w-1 not found");
+
+        let failed: Result<(), StorageError> = Err(StorageError::IntegrityViolation("x".into()));
+        let error = failed.context("Failed to delete bookmark").unwrap_err();
+        assert_eq!(error.status, StatusCode::INTERNAL_SERVER_ERROR);
+        assert!(error.message.starts_with("Failed to delete bookmark: "));
+
+        // The permission message does not leak the caller's ID.
+        let denied = ApiError::from(StorageError::PermissionDenied {
+            user_id: "u-1".into(),
+            resource: "wallet".into(),
+        });
+        assert!(!denied.message.contains("u-1"));
+    }
+
     #[tokio::test]
     async fn into_response_returns_json_body() {
         let response = ApiError::bad_request("bad data").into_response();
@@ -164,5 +310,21 @@ mod tests {
         let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
         let body = String::from_utf8(body_bytes.to_vec()).unwrap();
         assert_eq!(body, r#"{"error":"bad data"}"#);
+
+        let response = ApiError::not_found("Wallet not found")
+            .with_code("wallet_not_found")
+            .with_code("wallet_not_found")
+            .into_response();
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body_bytes).unwrap();
+        assert_eq!(body["error_code"], "wallet_not_found");
+        assert!(body.get("details").is_none());
+
+        let response = ApiError::unprocessable("Daily limit reached")
+            .with_details(serde_json::json!({ "remaining_eur": "0.00" }))
+            .into_response();
+        let body_bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body_bytes).unwrap();
+        assert_eq!(body["details"]["remaining_eur"], "0.00");
     }
 }
@@ -0,0 +1,128 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! # Domain Events
+//!
+//! One in-process bus for what happens in the service. Handlers, workers
+//! and storage publish a [`DomainEvent`] when they change something others
+//! care about; features that react to changes (live activity feeds, status
+//! waits, notifications) subscribe to the bus instead of instrumenting
+//! each handler.
+//!
+//! The bus is process-wide, since storage layers such as
+//! [`TxDatabase`](crate::storage::TxDatabase) and repositories created per
+//! call publish without access to the application state;
+//! [`AppState::events`](crate::state::AppState::events) is a handle to the
+//! same bus. Events carry identifiers only; subscribers read details from
+//! storage. A subscriber that falls more than [`EVENT_CAPACITY`] events
+//! behind misses events and should re-read state.
+
+use std::sync::OnceLock;
+
+use tokio::sync::broadcast;
+
+use crate::storage::{tx_database::TxStatusChange, FiatStatusChange};
+
+/// Events buffered per subscriber.
+pub const EVENT_CAPACITY: usize = 1024;
+
+/// Something that happened in the service.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DomainEvent {
+    /// A wallet was created.
+    WalletCreated {
+        wallet_id: String,
+        owner_user_id: String,
+    },
+    /// A transaction was stored or its status changed. Confirmations and
+    /// failures are status changes to `confirmed` and `failed`.
+    TxStatusChanged(TxStatusChange),
+    /// A fiat request was created or changed status.
+    FiatStatusChanged(FiatStatusChange),
+    /// A request was refused by a limit or policy.
+    PolicyViolation {
+        /// Caller, when the request was authenticated.
+        user_id: Option<String>,
+        /// Policy name, e.g. `send` or `spending_limit`.
+        policy: &'static str,
+        detail: String,
+    },
+}
+
+impl DomainEvent {
+    /// Stable name for logs and metrics.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::WalletCreated { .. } => "wallet_created",
+            Self::TxStatusChanged(_) => "tx_status_changed",
+            Self::FiatStatusChanged(_) => "fiat_status_changed",
+            Self::PolicyViolation { .. } => "policy_violation",
+        }
+    }
+}
+
+/// Handle to the event bus. Cheap to clone.
+#[derive(Debug, Clone)]
+pub struct EventBus {
+    sender: broadcast::Sender<DomainEvent>,
+}
+
+static GLOBAL: OnceLock<EventBus> = OnceLock::new();
+
+impl EventBus {
+    /// A bus of its own, for tests.
+    pub fn new() -> Self {
+        Self {
+            sender: broadcast::channel(EVENT_CAPACITY).0,
+        }
+    }
+
+    /// The process-wide bus.
+    pub fn global() -> Self {
+        GLOBAL.get_or_init(Self::new).clone()
+    }
+
+    /// Publish an event to every current subscriber.
+    pub fn publish(&self, event: DomainEvent) {
+        tracing::trace!(event = event.name(), "Domain event");
+        // Fails only when nobody is listening.
+        let _ = self.sender.send(event);
+    }
+
+    /// Receive every event published from now on.
+    pub fn subscribe(&self) -> broadcast::Receiver<DomainEvent> {
+        self.sender.subscribe()
+    }
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::global()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::TxStatus;
+
+    #[test]
+    fn subscribers_receive_events_published_after_subscribing() {
+        let bus = EventBus::new();
+        bus.publish(DomainEvent::WalletCreated {
+            wallet_id: "w-0".to_string(),
+            owner_user_id: "u".to_string(),
+        });
+        let mut events = bus.subscribe();
+        let change = DomainEvent::TxStatusChanged(TxStatusChange {
+            tx_hash: "0xabc".to_string(),
+            status: TxStatus::Confirmed,
+        });
+        bus.clone().publish(change.clone());
+
+        assert_eq!(events.try_recv().unwrap(), change);
+        assert!(events.try_recv().is_err());
+        assert_eq!(change.name(), "tx_status_changed");
+    }
+}
@@ -18,17 +18,29 @@
 //! 3. Skips requests whose `last_provider_sync_at` is less than the minimum
 //!    sync interval, avoiding duplicate work when the frontend is also polling.
 //!
+//! Before the per-request pass, small settlement-pending on-ramps are settled
+//! together in one disperse transaction when a disperse contract is configured.
+//!
+//! Card chargebacks on settled on-ramps are clawed back from the user's
+//! wallet in the same sweep, with a growing delay between failed attempts.
+//!
+//! Wallet auto top-up rules are evaluated at most once per
+//! `AUTO_TOPUP_INTERVAL`, and wallet alert rules once per `ALERTS_INTERVAL`,
+//! since each rule costs a balance RPC.
+//!
 //! ## Shutdown
 //!
 //! Uses `tokio_util::sync::CancellationToken` for graceful shutdown, following
 //! the same pattern as the `EventIndexer`.
 
-use std::sync::Arc;
-use std::time::Duration;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use tokio_util::sync::CancellationToken;
 use tracing::{info, warn};
 
+use crate::blockchain::transactions::NonceManager;
+use crate::providers::clerk::ClerkClient;
 use crate::storage::{EncryptedStorage, TxCache, TxDatabase};
 
 /// Environment variable to override the default poll interval (in seconds).
@@ -37,12 +49,22 @@ const POLL_INTERVAL_ENV: &str = "FIAT_POLL_INTERVAL_SECS";
 /// Default interval between polling sweeps (5 seconds — Avalanche confirms in ~2s).
 const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(5);
 
+/// Minimum interval between auto top-up sweeps.
+const AUTO_TOPUP_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Minimum interval between wallet alert sweeps.
+const ALERTS_INTERVAL: Duration = Duration::from_secs(60);
+
 /// Background fiat request poller that syncs pending requests with TrueLayer.
 pub struct FiatPoller {
     storage: Arc<EncryptedStorage>,
     tx_db: Arc<TxDatabase>,
     tx_cache: Arc<TxCache>,
+    nonces: Arc<NonceManager>,
+    clerk_client: Option<ClerkClient>,
     poll_interval: Duration,
+    last_auto_topup: Mutex<Option<Instant>>,
+    last_alerts: Mutex<Option<Instant>>,
 }
 
 impl FiatPoller {
@@ -62,13 +84,29 @@ impl FiatPoller {
             .unwrap_or(DEFAULT_POLL_INTERVAL);
 
         Self {
+            nonces: Arc::new(NonceManager::new(storage.clone())),
             storage,
             tx_db,
             tx_cache,
+            clerk_client: None,
             poll_interval,
+            last_auto_topup: Mutex::new(None),
+            last_alerts: Mutex::new(None),
         }
     }
 
+    /// Use a Clerk client for the KYC checks of auto top-up on-ramps.
+    pub fn with_clerk_client(mut self, clerk_client: Option<ClerkClient>) -> Self {
+        self.clerk_client = clerk_client;
+        self
+    }
+
+    /// Share the server's nonce manager so clawbacks do not race user sends.
+    pub fn with_nonce_manager(mut self, nonces: Arc<NonceManager>) -> Self {
+        self.nonces = nonces;
+        self
+    }
+
     /// Run the poller loop until the cancellation token is triggered.
     ///
     /// Should be spawned as a background task:
@@ -88,6 +126,7 @@ impl FiatPoller {
             }
 
             self.poll_step().await;
+            crate::worker_health::record_heartbeat(crate::worker_health::Worker::FiatPoller);
 
             tokio::select! {
                 _ = tokio::time::sleep(self.poll_interval) => {},
@@ -101,6 +140,49 @@ impl FiatPoller {
 
     /// Execute one polling sweep: find pending requests and sync each.
     async fn poll_step(&self) {
+        let batched = crate::api::fiat::settle_onramp_batch(
+            &self.storage,
+            self.tx_db.as_ref(),
+            Some(self.tx_cache.as_ref()),
+        )
+        .await;
+        if batched > 0 {
+            info!(
+                count = batched,
+                "Fiat poller: settled on-ramps in one batch"
+            );
+        }
+
+        if self.auto_topup_due() {
+            let created =
+                crate::api::auto_topup::run_auto_topups(&self.storage, self.clerk_client.as_ref())
+                    .await;
+            if created > 0 {
+                info!(count = created, "Fiat poller: created auto top-ups");
+            }
+        }
+
+        if sweep_due(&self.last_alerts, ALERTS_INTERVAL) {
+            let raised = crate::api::alerts::run_alerts(&self.storage, self.tx_db.as_ref()).await;
+            if raised > 0 {
+                info!(count = raised, "Fiat poller: raised wallet alerts");
+            }
+        }
+
+        let clawbacks = crate::api::fiat_card::run_pending_clawbacks(
+            &self.storage,
+            &self.nonces,
+            self.tx_db.as_ref(),
+            Some(self.tx_cache.as_ref()),
+        )
+        .await;
+        if clawbacks > 0 {
+            info!(
+                count = clawbacks,
+                "Fiat poller: attempted chargeback clawbacks"
+            );
+        }
+
         let pending_ids = crate::api::fiat::list_pending_request_ids(&self.storage);
 
         if pending_ids.is_empty() {
@@ -141,4 +223,19 @@ impl FiatPoller {
             }
         }
     }
+
+    /// Whether an auto top-up sweep is due; marks it as run if so.
+    fn auto_topup_due(&self) -> bool {
+        sweep_due(&self.last_auto_topup, AUTO_TOPUP_INTERVAL)
+    }
+}
+
+/// Whether a sweep last run at `last` is due again; marks it as run if so.
+fn sweep_due(last: &Mutex<Option<Instant>>, interval: Duration) -> bool {
+    let mut last = last.lock().unwrap_or_else(|e| e.into_inner());
+    if last.is_some_and(|at| at.elapsed() < interval) {
+        return false;
+    }
+    *last = Some(Instant::now());
+    true
 }
@@ -0,0 +1,397 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! # Localization
+//!
+//! User-facing messages are looked up in a catalog keyed by the stable
+//! error codes that [`ApiError`](crate::error::ApiError) and
+//! [`AuthError`](crate::auth::AuthError) put in `error_code`. The language
+//! is negotiated from `Accept-Language`; English is the default.
+//!
+//! The [`localize_errors`] layer rewrites the `error` message of JSON error
+//! responses whose code is in the catalog. Codes are never translated, so
+//! clients keep matching on `error_code`. English responses are left as
+//! they are, since handlers often add detail to the English message that
+//! the catalog entry does not carry.
+
+use axum::{
+    body::{to_bytes, Body},
+    extract::Request,
+    http::{header, HeaderValue, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use serde_json::Value;
+
+/// A supported language.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Locale {
+    En,
+    De,
+    Fr,
+}
+
+impl Locale {
+    /// BCP 47 tag, as sent in `Content-Language`.
+    pub fn tag(self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::De => "de",
+            Locale::Fr => "fr",
+        }
+    }
+
+    fn from_primary_tag(tag: &str) -> Option<Self> {
+        match tag {
+            "en" => Some(Locale::En),
+            "de" => Some(Locale::De),
+            "fr" => Some(Locale::Fr),
+            _ => None,
+        }
+    }
+
+    /// Pick the best supported language for an `Accept-Language` header.
+    ///
+    /// Regional variants match their language (`de-CH` is German). Among
+    /// equal quality values the first listed wins; `q=0` excludes a
+    /// language. Falls back to English.
+    pub fn negotiate(accept_language: Option<&str>) -> Self {
+        let Some(header) = accept_language else {
+            return Locale::En;
+        };
+        let mut best: Option<(Locale, f32)> = None;
+        for item in header.split(',') {
+            let mut params = item.split(';');
+            let tag = params.next().unwrap_or("").trim().to_ascii_lowercase();
+            let quality = params
+                .filter_map(|p| p.trim().strip_prefix("q="))
+                .find_map(|q| q.trim().parse::<f32>().ok())
+                .unwrap_or(1.0);
+            let primary = tag.split('-').next().unwrap_or("");
+            let locale = match primary {
+                "*" => Some(Locale::En),
+                other => Locale::from_primary_tag(other),
+            };
+            if let Some(locale) = locale {
+                if quality > 0.0 && best.is_none_or(|(_, q)| quality > q) {
+                    best = Some((locale, quality));
+                }
+            }
+        }
+        best.map_or(Locale::En, |(locale, _)| locale)
+    }
+}
+
+const EN: &[(&str, &str)] = &[
+    ("missing_auth_header", "Authorization header is required"),
+    (
+        "invalid_auth_header",
+        "Invalid authorization header format (expected 'Bearer <token>')",
+    ),
+    ("malformed_token", "Token is malformed"),
+    ("invalid_signature", "Token signature is invalid"),
+    ("token_expired", "Token has expired"),
+    ("invalid_issuer", "Token issuer is invalid"),
+    ("invalid_audience", "Token audience is invalid"),
+    ("token_not_yet_valid", "Token is not yet valid"),
+    ("jwks_fetch_error", "Failed to fetch signing keys"),
+    ("no_matching_key", "No matching signing key found"),
+    ("invalid_api_key", "API key is invalid, expired or revoked"),
+    ("internal_error", "Internal authentication error"),
+    (
+        "insufficient_permissions",
+        "Insufficient permissions for this operation",
+    ),
+    (
+        "permission_denied",
+        "You don't have permission to access this resource",
+    ),
+    ("storage_unavailable", "Storage is not available"),
+    ("wallet_not_found", "Wallet not found"),
+    ("wallet_not_owned", "You do not own this wallet"),
+    ("wallet_deleted", "Wallet has been deleted"),
+    ("wallet_suspended", "Wallet is suspended"),
+    ("fiat_request_not_found", "Fiat request not found"),
+    (
+        "insufficient_balance",
+        "Insufficient balance for transaction",
+    ),
+    (
+        "insufficient_gas",
+        "Insufficient gas balance for transaction",
+    ),
+    ("invalid_amount", "Amount must be greater than zero"),
+    ("storage_quota_exceeded", "Storage quota exceeded"),
+    ("spending_limit_exceeded", "Spending limit exceeded"),
+    ("admin_signature_required", "Request signature required"),
+    ("admin_signature_invalid", "Invalid request signature"),
+    ("rate_limited", "Too many requests, please retry later"),
+];
+
+const DE: &[(&str, &str)] = &[
+    ("missing_auth_header", "Authorization-Header fehlt"),
+    (
+        "invalid_auth_header",
+        "Ungültiges Format des Authorization-Headers (erwartet 'Bearer <token>')",
+    ),
+    ("malformed_token", "Token ist fehlerhaft"),
+    ("invalid_signature", "Token-Signatur ist ungültig"),
+    ("token_expired", "Token ist abgelaufen"),
+    ("invalid_issuer", "Token-Aussteller ist ungültig"),
+    ("invalid_audience", "Token-Zielgruppe ist ungültig"),
+    ("token_not_yet_valid", "Token ist noch nicht gültig"),
+    (
+        "jwks_fetch_error",
+        "Signaturschlüssel konnten nicht abgerufen werden",
+    ),
+    (
+        "no_matching_key",
+        "Kein passender Signaturschlüssel gefunden",
+    ),
+    (
+        "invalid_api_key",
+        "API-Schlüssel ist ungültig, abgelaufen oder widerrufen",
+    ),
+    ("internal_error", "Interner Authentifizierungsfehler"),
+    (
+        "insufficient_permissions",
+        "Unzureichende Berechtigungen für diesen Vorgang",
+    ),
+    (
+        "permission_denied",
+        "Sie haben keine Berechtigung für diese Ressource",
+    ),
+    ("storage_unavailable", "Speicher ist nicht verfügbar"),
+    ("wallet_not_found", "Wallet nicht gefunden"),
+    ("wallet_not_owned", "Diese Wallet gehört Ihnen nicht"),
+    ("wallet_deleted", "Wallet wurde gelöscht"),
+    ("wallet_suspended", "Wallet ist gesperrt"),
+    ("fiat_request_not_found", "Fiat-Auftrag nicht gefunden"),
+    (
+        "insufficient_balance",
+        "Unzureichendes Guthaben für die Transaktion",
+    ),
+    (
+        "insufficient_gas",
+        "Unzureichendes Gas-Guthaben für die Transaktion",
+    ),
+    ("invalid_amount", "Der Betrag muss größer als null sein"),
+    ("storage_quota_exceeded", "Speicherkontingent überschritten"),
+    ("spending_limit_exceeded", "Ausgabelimit überschritten"),
+    ("admin_signature_required", "Anfragesignatur erforderlich"),
+    ("admin_signature_invalid", "Ungültige Anfragesignatur"),
+    (
+        "rate_limited",
+        "Zu viele Anfragen, bitte später erneut versuchen",
+    ),
+];
+
+const FR: &[(&str, &str)] = &[
+    ("missing_auth_header", "L'en-tête Authorization est requis"),
+    (
+        "invalid_auth_header",
+        "Format de l'en-tête Authorization invalide (attendu : 'Bearer <token>')",
+    ),
+    ("malformed_token", "Le jeton est mal formé"),
+    ("invalid_signature", "La signature du jeton est invalide"),
+    ("token_expired", "Le jeton a expiré"),
+    ("invalid_issuer", "L'émetteur du jeton est invalide"),
+    ("invalid_audience", "L'audience du jeton est invalide"),
+    ("token_not_yet_valid", "Le jeton n'est pas encore valide"),
+    (
+        "jwks_fetch_error",
+        "Impossible de récupérer les clés de signature",
+    ),
+    ("no_matching_key", "Aucune clé de signature correspondante"),
+    (
+        "invalid_api_key",
+        "La clé d'API est invalide, expirée ou révoquée",
+    ),
+    ("internal_error", "Erreur interne d'authentification"),
+    (
+        "insufficient_permissions",
+        "Permissions insuffisantes pour cette opération",
+    ),
+    (
+        "permission_denied",
+        "Vous n'avez pas l'autorisation d'accéder à cette ressource",
+    ),
+    ("storage_unavailable", "Le stockage n'est pas disponible"),
+    ("wallet_not_found", "Portefeuille introuvable"),
+    ("wallet_not_owned", "Ce portefeuille ne vous appartient pas"),
+    ("wallet_deleted", "Le portefeuille a été supprimé"),
+    ("wallet_suspended", "Le portefeuille est suspendu"),
+    ("fiat_request_not_found", "Demande fiat introuvable"),
+    (
+        "insufficient_balance",
+        "Solde insuffisant pour la transaction",
+    ),
+    (
+        "insufficient_gas",
+        "Solde de gas insuffisant pour la transaction",
+    ),
+    ("invalid_amount", "Le montant doit être supérieur à zéro"),
+    ("storage_quota_exceeded", "Quota de stockage dépassé"),
+    ("spending_limit_exceeded", "Plafond de dépenses dépassé"),
+    (
+        "admin_signature_required",
+        "Signature de la requête requise",
+    ),
+    (
+        "admin_signature_invalid",
+        "Signature de la requête invalide",
+    ),
+    (
+        "rate_limited",
+        "Trop de requêtes, veuillez réessayer plus tard",
+    ),
+];
+
+/// Message for `code` in `locale`, if the catalog has one.
+pub fn message(locale: Locale, code: &str) -> Option<&'static str> {
+    let catalog = match locale {
+        Locale::En => EN,
+        Locale::De => DE,
+        Locale::Fr => FR,
+    };
+    catalog
+        .iter()
+        .find(|(key, _)| *key == code)
+        .map(|(_, text)| *text)
+}
+
+/// Layer translating error messages into the caller's language.
+pub async fn localize_errors(request: Request, next: Next) -> Response {
+    let locale = Locale::negotiate(
+        request
+            .headers()
+            .get(header::ACCEPT_LANGUAGE)
+            .and_then(|v| v.to_str().ok()),
+    );
+    let response = next.run(request).await;
+    let is_json = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|ct| ct.starts_with("application/json"));
+    if locale == Locale::En || !is_json || response.status().is_success() {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let bytes = match to_bytes(body, usize::MAX).await {
+        Ok(bytes) => bytes,
+        Err(error) => {
+            tracing::warn!(error = %error, "Failed to buffer error response for localization");
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+    let translated = serde_json::from_slice::<Value>(&bytes)
+        .ok()
+        .and_then(|mut value| {
+            let text = message(locale, value.get("error_code")?.as_str()?)?;
+            value["error"] = Value::from(text);
+            serde_json::to_vec(&value).ok()
+        });
+    let Some(translated) = translated else {
+        return Response::from_parts(parts, Body::from(bytes));
+    };
+    parts.headers.remove(header::CONTENT_LENGTH);
+    parts.headers.insert(
+        header::CONTENT_LANGUAGE,
+        HeaderValue::from_static(locale.tag()),
+    );
+    parts
+        .headers
+        .append(header::VARY, HeaderValue::from_static("accept-language"));
+    Response::from_parts(parts, Body::from(translated))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{auth::AuthError, error::ApiError};
+    use axum::{middleware::from_fn, routing::get, Router};
+    use tower::ServiceExt;
+
+    #[test]
+    fn negotiates_by_quality_and_region() {
+        assert_eq!(Locale::negotiate(None), Locale::En);
+        assert_eq!(
+            Locale::negotiate(Some("de-CH,de;q=0.9,en;q=0.8")),
+            Locale::De
+        );
+        assert_eq!(
+            Locale::negotiate(Some("it, fr;q=0.5, en;q=0.4")),
+            Locale::Fr
+        );
+        assert_eq!(Locale::negotiate(Some("en;q=0.5, de;q=0.7")), Locale::De);
+        assert_eq!(Locale::negotiate(Some("fr;q=0, *;q=0.1")), Locale::En);
+        assert_eq!(Locale::negotiate(Some("ja")), Locale::En);
+    }
+
+    #[test]
+    fn every_code_is_translated() {
+        for catalog in [DE, FR] {
+            assert_eq!(catalog.len(), EN.len());
+            for (code, _) in EN {
+                assert!(catalog.iter().any(|(key, _)| key == code), "{code}");
+            }
+        }
+        let auth_codes = [
+            AuthError::MissingAuthHeader,
+            AuthError::TokenExpired,
+            AuthError::InsufficientPermissions,
+        ];
+        for error in auth_codes {
+            assert!(message(Locale::En, error.error_code()).is_some());
+        }
+    }
+
+    async fn call(path: &str, accept_language: &str) -> (Option<String>, Value) {
+        let app = Router::new()
+            .route(
+                "/wallet",
+                get(|| async {
+                    ApiError::not_found("Wallet w-1 not found")
+                        .with_code("wallet_not_found")
+                        .into_response()
+                }),
+            )
+            .route(
+                "/plain",
+                get(|| async { ApiError::bad_request("bad data").into_response() }),
+            )
+            .layer(from_fn(localize_errors));
+        let request = axum::http::Request::builder()
+            .uri(path)
+            .header(header::ACCEPT_LANGUAGE, accept_language)
+            .body(Body::empty())
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_ne!(response.status(), StatusCode::OK);
+        let content_language = response
+            .headers()
+            .get(header::CONTENT_LANGUAGE)
+            .map(|v| v.to_str().unwrap().to_string());
+        let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (content_language, serde_json::from_slice(&bytes).unwrap())
+    }
+
+    #[tokio::test]
+    async fn error_messages_follow_accept_language_and_codes_stay() {
+        let (language, body) = call("/wallet", "de-DE").await;
+        assert_eq!(language.as_deref(), Some("de"));
+        assert_eq!(body["error"], "Wallet nicht gefunden");
+        assert_eq!(body["error_code"], "wallet_not_found");
+
+        let (language, body) = call("/wallet", "en").await;
+        assert_eq!(language, None);
+        assert_eq!(body["error"], "Wallet w-1 not found");
+
+        // Errors without a code keep their message.
+        let (_, body) = call("/plain", "fr").await;
+        assert_eq!(body["error"], "bad data");
+    }
+}
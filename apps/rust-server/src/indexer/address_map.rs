@@ -0,0 +1,151 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! # Address Map Consistency
+//!
+//! The transaction database maps every known on-chain address to the wallet
+//! owning it; the indexer and internal-transfer detection look senders and
+//! recipients up there. The map is derived from encrypted storage and can
+//! drift from it (imports, rotations, a write that failed after the wallet
+//! was saved). [`expected_address_map`] derives what it should hold and
+//! [`find_issues`] lists the differences.
+
+use std::collections::{BTreeMap, HashSet};
+
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::blockchain::address_key;
+use crate::storage::repository::watch_only::tracking_id;
+use crate::storage::{
+    EncryptedStorage, FiatServiceWalletRepository, StorageError, StorageResult, WalletRepository,
+    WalletStatus, WatchOnlyRepository,
+};
+
+/// How a mapping differs from encrypted storage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AddressMapIssueKind {
+    /// A known address is not mapped.
+    Missing,
+    /// The address is mapped to another wallet than the one owning it.
+    Mismatched,
+    /// The address is mapped but no live wallet or watch-only entry owns it.
+    Stale,
+}
+
+/// A mapping that differs from encrypted storage.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct AddressMapIssue {
+    /// Lowercase address.
+    pub address: String,
+    pub kind: AddressMapIssueKind,
+    /// What the map holds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mapped_wallet_id: Option<String>,
+    /// What encrypted storage says; `None` means the entry should go.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expected_wallet_id: Option<String>,
+}
+
+/// Address (lowercase) → owner ID, as encrypted storage describes it:
+/// watch-only addresses map to their tracking ID, overridden by live
+/// wallets and the fiat service wallet. Deleted wallets are left out, as
+/// deleting a wallet unregisters its address.
+pub fn expected_address_map(storage: &EncryptedStorage) -> StorageResult<BTreeMap<String, String>> {
+    let mut expected = BTreeMap::new();
+    for entry in WatchOnlyRepository::new(storage).list_all()? {
+        expected.insert(address_key(&entry.address), tracking_id(&entry.address));
+    }
+    for wallet in WalletRepository::new(storage).list_all_wallets()? {
+        if wallet.status != WalletStatus::Deleted {
+            expected.insert(address_key(&wallet.public_address), wallet.wallet_id);
+        }
+    }
+    match FiatServiceWalletRepository::new(storage).get() {
+        Ok(meta) => {
+            expected.insert(address_key(&meta.public_address), meta.wallet_id);
+        }
+        Err(StorageError::NotFound(_)) => {}
+        Err(e) => return Err(e),
+    }
+    Ok(expected)
+}
+
+/// Differences between the mappings in the database and the expected map,
+/// ordered by address.
+pub fn find_issues(
+    mapped: &[(String, String)],
+    expected: &BTreeMap<String, String>,
+) -> Vec<AddressMapIssue> {
+    let mapped_addresses: HashSet<&str> = mapped.iter().map(|(a, _)| a.as_str()).collect();
+    let mut issues = Vec::new();
+    for (address, wallet_id) in mapped {
+        let kind = match expected.get(address) {
+            Some(owner) if owner == wallet_id => continue,
+            Some(_) => AddressMapIssueKind::Mismatched,
+            None => AddressMapIssueKind::Stale,
+        };
+        issues.push(AddressMapIssue {
+            address: address.clone(),
+            kind,
+            mapped_wallet_id: Some(wallet_id.clone()),
+            expected_wallet_id: expected.get(address).cloned(),
+        });
+    }
+    for (address, owner) in expected {
+        if !mapped_addresses.contains(address.as_str()) {
+            issues.push(AddressMapIssue {
+                address: address.clone(),
+                kind: AddressMapIssueKind::Missing,
+                mapped_wallet_id: None,
+                expected_wallet_id: Some(owner.clone()),
+            });
+        }
+    }
+    issues.sort_by(|a, b| a.address.cmp(&b.address));
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn issues_classify_each_difference() {
+        let pair = |a: &str, w: &str| (a.to_string(), w.to_string());
+        let mapped = vec![pair("0xa", "w1"), pair("0xb", "w9"), pair("0xc", "w3")];
+        let expected: BTreeMap<String, String> =
+            [pair("0xa", "w1"), pair("0xb", "w2"), pair("0xd", "w4")]
+                .into_iter()
+                .collect();
+
+        let issues = find_issues(&mapped, &expected);
+        let summary: Vec<_> = issues
+            .iter()
+            .map(|i| {
+                (
+                    i.address.as_str(),
+                    i.kind,
+                    i.mapped_wallet_id.as_deref(),
+                    i.expected_wallet_id.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    "0xb",
+                    AddressMapIssueKind::Mismatched,
+                    Some("w9"),
+                    Some("w2")
+                ),
+                ("0xc", AddressMapIssueKind::Stale, Some("w3"), None),
+                ("0xd", AddressMapIssueKind::Missing, None, Some("w4")),
+            ]
+        );
+        assert!(find_issues(&mapped[..1], &BTreeMap::from([pair("0xa", "w1")])).is_empty());
+    }
+}
@@ -0,0 +1,235 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! # First Deposit Detection
+//!
+//! The first time the indexer stores an incoming transfer for a wallet, the
+//! wallet's `funded_at` is set, a `wallet_first_funded` audit event is
+//! logged and a `first_deposit` alert is raised for the owner (see
+//! [`AlertEventKind::FirstDeposit`]).
+//!
+//! Wallets funded before the indexer tracked this have no `funded_at`
+//! either. For them the earliest deposit in the transaction database sets
+//! `funded_at` quietly, without an event, so a later deposit is not
+//! mistaken for the first.
+
+use chrono::Utc;
+use tracing::{info, warn};
+
+use crate::storage::repository::transactions::{StoredTransaction, TxStatus};
+use crate::storage::tx_database::{TxDatabase, TxDbResult};
+use crate::storage::{
+    AlertEventKind, AlertRepository, AuditEvent, AuditEventType, AuditRepository, EncryptedStorage,
+    StorageError, StoredWalletAlerts, WalletRepository,
+};
+
+/// History page size when looking for a wallet's earliest deposit.
+const HISTORY_PAGE: usize = 200;
+
+/// Earliest confirmed incoming transfer of a history page.
+fn earliest_deposit(history: &[(StoredTransaction, String)]) -> Option<&StoredTransaction> {
+    history
+        .iter()
+        .filter(|(tx, direction)| direction == "received" && tx.status == TxStatus::Confirmed)
+        .map(|(tx, _)| tx)
+        .min_by_key(|tx| tx.created_at)
+}
+
+/// Earliest confirmed incoming transfer to `address` in the database.
+fn first_deposit(db: &TxDatabase, address: &str) -> TxDbResult<Option<StoredTransaction>> {
+    let mut earliest: Option<StoredTransaction> = None;
+    let mut cursor: Option<String> = None;
+    loop {
+        let (page, next) = db.list_by_wallet(address, cursor.as_deref(), HISTORY_PAGE)?;
+        if let Some(tx) = earliest_deposit(&page) {
+            if earliest
+                .as_ref()
+                .is_none_or(|current| tx.created_at < current.created_at)
+            {
+                earliest = Some(tx.clone());
+            }
+        }
+        match next {
+            Some(next) => cursor = Some(next),
+            None => return Ok(earliest),
+        }
+    }
+}
+
+/// Note a confirmed deposit of `tx` to `wallet_id` at `address`, which is
+/// already stored in `db`. Returns whether it was the wallet's first.
+pub fn record_deposit(
+    storage: &EncryptedStorage,
+    db: &TxDatabase,
+    wallet_id: &str,
+    address: &str,
+    tx: &StoredTransaction,
+) -> bool {
+    let wallets = WalletRepository::new(storage);
+    let Ok(mut wallet) = wallets.get(wallet_id) else {
+        return false;
+    };
+    if wallet.funded_at.is_some() {
+        return false;
+    }
+
+    let first = match first_deposit(db, address) {
+        Ok(first) => first.unwrap_or_else(|| tx.clone()),
+        Err(e) => {
+            warn!(wallet_id, error = %e, "Failed to look up earlier deposits");
+            return false;
+        }
+    };
+    wallet.funded_at = Some(first.created_at);
+    if let Err(e) = wallets.update(&wallet) {
+        warn!(wallet_id, error = %e, "Failed to record wallet funding");
+        return false;
+    }
+    if first.tx_hash != tx.tx_hash {
+        return false;
+    }
+
+    let event = AuditEvent::new(AuditEventType::WalletFirstFunded)
+        .with_user(&wallet.owner_user_id)
+        .with_resource("wallet", wallet_id)
+        .with_details(serde_json::json!({
+            "tx_hash": tx.tx_hash,
+            "from": tx.from,
+            "amount": tx.amount,
+            "token": tx.token,
+            "network": tx.network,
+        }));
+    let _ = AuditRepository::new(storage).log(&event);
+
+    let alerts = AlertRepository::new(storage);
+    let now = Utc::now();
+    let mut stored = match alerts.get(wallet_id) {
+        Ok(stored) => stored,
+        Err(StorageError::NotFound(_)) => StoredWalletAlerts {
+            wallet_id: wallet_id.to_string(),
+            owner_user_id: wallet.owner_user_id.clone(),
+            rules: Vec::new(),
+            armed_at: now,
+            low_tokens: Vec::new(),
+            seen_tx: Vec::new(),
+            events: Vec::new(),
+            created_at: now,
+            updated_at: now,
+        },
+        Err(e) => {
+            warn!(wallet_id, error = %e, "Failed to load alerts for first deposit");
+            return true;
+        }
+    };
+    stored.record_event(
+        AlertEventKind::FirstDeposit,
+        Some(tx.tx_hash.clone()),
+        format!("Your wallet received its first deposit: {}", tx.amount),
+        now,
+    );
+    stored.updated_at = now;
+    if let Err(e) = alerts.save(&stored) {
+        warn!(wallet_id, error = %e, "Failed to store first deposit alert");
+    }
+
+    info!(wallet_id, tx_hash = %tx.tx_hash, "Indexer: wallet received its first deposit");
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::{StoragePaths, TokenType, WalletMetadata, WalletStatus};
+    use chrono::Duration;
+    use tempfile::TempDir;
+
+    const ADDRESS: &str = "0x1111111111111111111111111111111111111111";
+
+    fn deposit(tx_hash: &str, age_mins: i64) -> StoredTransaction {
+        let mut tx = StoredTransaction::new_pending(
+            tx_hash.to_string(),
+            "wallet-1".to_string(),
+            None,
+            "0x2222222222222222222222222222222222222222".to_string(),
+            ADDRESS.to_string(),
+            "5".to_string(),
+            TokenType::Native,
+            "fuji".to_string(),
+            String::new(),
+        );
+        tx.status = TxStatus::Confirmed;
+        tx.created_at = Utc::now() - Duration::minutes(age_mins);
+        tx
+    }
+
+    fn setup(dir: &TempDir) -> (EncryptedStorage, TxDatabase) {
+        let mut storage = EncryptedStorage::new(StoragePaths::new(dir.path()));
+        storage.initialize().unwrap();
+        WalletRepository::new(&storage)
+            .create(
+                &WalletMetadata {
+                    wallet_id: "wallet-1".to_string(),
+                    owner_user_id: "user-1".to_string(),
+                    public_address: ADDRESS.to_string(),
+                    created_at: Utc::now(),
+                    status: WalletStatus::Active,
+                    label: None,
+                    email_lookup_key: None,
+                    email_sha256: None,
+                    account_type: Default::default(),
+                    smart_account: None,
+                    lock: None,
+                    deleted_at: None,
+                    purged_at: None,
+                    funded_at: None,
+                    tenant_id: None,
+                },
+                b"test_key",
+            )
+            .unwrap();
+        let db = TxDatabase::open(&dir.path().join("tx.redb")).unwrap();
+        (storage, db)
+    }
+
+    #[test]
+    fn only_the_first_deposit_raises_an_event() {
+        let dir = TempDir::new().unwrap();
+        let (storage, db) = setup(&dir);
+
+        let first = deposit("0xa", 2);
+        db.upsert_transaction(&first, &[(ADDRESS.to_string(), "received")])
+            .unwrap();
+        assert!(record_deposit(&storage, &db, "wallet-1", ADDRESS, &first));
+
+        let second = deposit("0xb", 1);
+        db.upsert_transaction(&second, &[(ADDRESS.to_string(), "received")])
+            .unwrap();
+        assert!(!record_deposit(&storage, &db, "wallet-1", ADDRESS, &second));
+
+        let wallet = WalletRepository::new(&storage).get("wallet-1").unwrap();
+        assert_eq!(wallet.funded_at, Some(first.created_at));
+        let alerts = AlertRepository::new(&storage).get("wallet-1").unwrap();
+        assert_eq!(alerts.events.len(), 1);
+        assert_eq!(alerts.events[0].kind, AlertEventKind::FirstDeposit);
+        assert!(alerts.rules.is_empty());
+    }
+
+    #[test]
+    fn earlier_deposits_are_backfilled_quietly() {
+        let dir = TempDir::new().unwrap();
+        let (storage, db) = setup(&dir);
+
+        let old = deposit("0xa", 60);
+        let new = deposit("0xb", 1);
+        for tx in [&old, &new] {
+            db.upsert_transaction(tx, &[(ADDRESS.to_string(), "received")])
+                .unwrap();
+        }
+        assert!(!record_deposit(&storage, &db, "wallet-1", ADDRESS, &new));
+
+        let wallet = WalletRepository::new(&storage).get("wallet-1").unwrap();
+        assert_eq!(wallet.funded_at, Some(old.created_at));
+        assert!(AlertRepository::new(&storage).get("wallet-1").is_err());
+    }
+}
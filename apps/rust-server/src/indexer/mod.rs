@@ -4,33 +4,75 @@
 
 //! # Event Indexer
 //!
-//! Background task that indexes ERC-20 Transfer events from the Avalanche
-//! C-Chain into the embedded redb transaction database.
+//! Background task that indexes ERC-20 Transfer events and native transfers
+//! from the Avalanche C-Chain into the embedded redb transaction database.
 //!
 //! ## Strategy
 //!
 //! 1. **ERC-20 Transfers**: Uses `eth_getLogs` with the Transfer(address,address,uint256)
 //!    event topic, filtered to known token contract addresses.
 //! 2. **Native AVAX Transfers**: Outgoing native sends are recorded at `send_transaction`
-//!    time. Incoming native discovery runs on a slower poll by checking balance diffs.
+//!    time. Native value moves in transactions rather than logs, so a second pass reads
+//!    whole blocks, at most [`NATIVE_BLOCKS_PER_STEP`] per poll, and stores transfers
+//!    with value that touch a registered address once their receipt shows success.
+//!    Only top-level transfers are seen: value a contract forwards (an internal
+//!    transaction) needs call tracing, which public RPC endpoints do not offer.
+//! 3. **NFTs**: ERC-721 `Transfer` and ERC-1155 `TransferSingle` / `TransferBatch`
+//!    logs of the [listed collections](crate::blockchain::nft) are read with the
+//!    ERC-20 logs and kept as per-wallet holdings rather than transaction records.
 //!
 //! ## Checkpointing
 //!
-//! The indexer persists the last processed block in redb (`INDEXER_STATE` table).
-//! On restart, it resumes from the checkpoint, avoiding full rescans.
+//! The indexer persists the last processed block in redb (`INDEXER_STATE` table),
+//! separately for each pass (see [`checkpoint_key`] and [`native_checkpoint_key`]).
+//! On restart, it resumes from the checkpoints, avoiding full rescans.
+//!
+//! ## Rebuild
+//!
+//! [`rebuild`] replays a block range into a scratch database to verify or
+//! restore the live one, e.g. after the redb file was lost.
+//!
+//! ## Address Map
+//!
+//! Transfers are attributed through the address→wallet map, which
+//! [`address_map`] checks against encrypted storage.
+//!
+//! ## Address Poisoning
+//!
+//! New zero-value and dust transfers are checked against the wallet's
+//! recent counterparties and flagged when they involve a lookalike address
+//! (see [`poisoning`]).
+//!
+//! ## First Deposits
+//!
+//! With storage attached ([`EventIndexer::with_storage`]), the first
+//! incoming transfer stored for a wallet marks it funded (see [`funding`]).
+
+pub mod address_map;
+pub mod funding;
+pub mod poisoning;
+pub mod rebuild;
 
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
 
+use alloy::consensus::Transaction as _;
+use alloy::eips::BlockNumberOrTag;
+use alloy::network::TransactionResponse as _;
 use alloy::primitives::{Address, FixedBytes, U256};
 use alloy::providers::{Provider, ProviderBuilder};
 use alloy::rpc::types::Filter;
+use chrono::{DateTime, Utc};
 use tokio_util::sync::CancellationToken;
 
-use crate::blockchain::{format_amount, NetworkConfig, AVAX_FUJI, REUR_TOKEN};
+use crate::blockchain::nft::{decode_transfers, nft_collections, transfer_event_signatures};
+use crate::blockchain::{address_key, format_amount, tokens, NetworkConfig, NETWORK_FUJI};
 use crate::storage::repository::transactions::{StoredTransaction, TokenType, TxStatus};
-use crate::storage::tx_cache::TxCache;
+use crate::storage::repository::watch_only::is_tracking_id;
 use crate::storage::tx_database::TxDatabase;
+use crate::storage::CacheBus;
+use crate::storage::EncryptedStorage;
 
 /// keccak256("Transfer(address,address,uint256)")
 const TRANSFER_TOPIC: FixedBytes<32> = FixedBytes::new([
@@ -47,34 +89,126 @@ const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(5);
 /// How far back to look when starting fresh (no checkpoint).
 const INITIAL_LOOKBACK_BLOCKS: u64 = 10_000;
 
-/// ERC-20 event indexer that runs as a background tokio task.
+/// Most blocks the native-transfer pass reads per poll. Each costs a full
+/// block fetch, so catching up is spread over several polls.
+pub const NATIVE_BLOCKS_PER_STEP: u64 = 100;
+
+/// A transfer seen on chain, native or ERC-20.
+struct ObservedTransfer {
+    tx_hash: String,
+    /// Lowercase address.
+    from: String,
+    /// Lowercase address.
+    to: String,
+    value: U256,
+    token: TokenType,
+    decimals: u8,
+    block_number: Option<u64>,
+    /// Unix seconds, when already known.
+    block_timestamp: Option<u64>,
+}
+
+/// Transfer indexer that runs as a background tokio task.
 pub struct EventIndexer {
     db: Arc<TxDatabase>,
-    cache: Arc<TxCache>,
+    /// Caches told about addresses whose transfers were stored.
+    caches: CacheBus,
     network: NetworkConfig,
     poll_interval: Duration,
     chunk_size: u64,
     token_contracts: Vec<Address>,
+    /// Also index the tokens the registry lists as indexed on the network.
+    listed_tokens: bool,
+    /// Date records by their block instead of by when they were indexed.
+    block_timestamps: bool,
+    /// Wallet storage for first-deposit detection.
+    storage: Option<Arc<EncryptedStorage>>,
 }
 
 impl EventIndexer {
     /// Create a new indexer for the given network and token contracts.
     pub fn new(
         db: Arc<TxDatabase>,
-        cache: Arc<TxCache>,
+        caches: CacheBus,
         network: NetworkConfig,
         token_contracts: Vec<Address>,
     ) -> Self {
         Self {
             db,
-            cache,
+            caches,
             network,
             poll_interval: DEFAULT_POLL_INTERVAL,
             chunk_size: DEFAULT_CHUNK_SIZE,
             token_contracts,
+            listed_tokens: false,
+            block_timestamps: false,
+            storage: None,
         }
     }
 
+    /// Detect wallets' first deposits, recording them in `storage`.
+    pub fn with_storage(mut self, storage: Arc<EncryptedStorage>) -> Self {
+        self.storage = Some(storage);
+        self
+    }
+
+    /// Follow the [token registry](crate::blockchain::tokens): tokens
+    /// admins list as indexed are picked up on the next poll, without a
+    /// restart.
+    pub fn with_listed_tokens(mut self) -> Self {
+        self.listed_tokens = true;
+        self
+    }
+
+    /// Contracts whose transfers are indexed this step.
+    fn contracts(&self) -> Vec<Address> {
+        let mut contracts = self.token_contracts.clone();
+        if self.listed_tokens {
+            for contract in tokens().indexed_contracts(self.network.id) {
+                if !contracts.contains(&contract) {
+                    contracts.push(contract);
+                }
+            }
+        }
+        contracts
+    }
+
+    /// Date records by their block's timestamp. Costs a block lookup per
+    /// block with transfers, so it is only used when replaying history.
+    pub fn with_block_timestamps(mut self) -> Self {
+        self.block_timestamps = true;
+        self
+    }
+
+    /// Index `from_block..=to_block` without touching the checkpoint.
+    pub async fn index_range(&self, from_block: u64, to_block: u64) -> Result<usize, IndexerError> {
+        let url = self
+            .network
+            .rpc_url
+            .parse()
+            .map_err(|e| IndexerError::Rpc(format!("invalid RPC URL: {e}")))?;
+        let provider = ProviderBuilder::new().connect_http(url);
+
+        let contracts = self.contracts();
+        let collections = nft_collections().contracts(self.network.id);
+        let mut indexed = 0;
+        let mut from = from_block;
+        while from <= to_block && !(contracts.is_empty() && collections.is_empty()) {
+            let to = (from + self.chunk_size - 1).min(to_block);
+            if !contracts.is_empty() {
+                indexed += self
+                    .fetch_and_store_logs(&provider, &contracts, from, to)
+                    .await?;
+            }
+            if !collections.is_empty() {
+                self.fetch_and_store_nft_logs(&provider, &collections, from, to)
+                    .await?;
+            }
+            from = to + 1;
+        }
+        Ok(indexed)
+    }
+
     /// Run the indexer loop until the cancellation token is triggered.
     ///
     /// This should be spawned as a background task:
@@ -84,7 +218,7 @@ impl EventIndexer {
     pub async fn run(self, shutdown: CancellationToken) {
         tracing::info!(
             network = %self.network.name,
-            contracts = self.token_contracts.len(),
+            contracts = self.contracts().len(),
             "Event indexer starting"
         );
 
@@ -101,6 +235,7 @@ impl EventIndexer {
             if let Err(e) = self.index_step(&provider).await {
                 tracing::warn!(error = %e, "Indexer step failed, will retry");
             }
+            crate::worker_health::record_heartbeat(crate::worker_health::Worker::EventIndexer);
 
             tokio::select! {
                 _ = tokio::time::sleep(self.poll_interval) => {},
@@ -114,7 +249,7 @@ impl EventIndexer {
 
     /// Execute one indexing step: fetch logs from checkpoint to head.
     async fn index_step<P: Provider + Clone>(&self, provider: &P) -> Result<(), IndexerError> {
-        let network_key = self.network.name.to_lowercase().replace(' ', "_");
+        let network_key = checkpoint_key(&self.network);
         let checkpoint = self.db.get_last_indexed_block(&network_key)?;
 
         let head = provider
@@ -136,8 +271,8 @@ impl EventIndexer {
         };
 
         if start > head {
-            // Already caught up
-            return Ok(());
+            // Logs caught up; native transfers may still be behind.
+            return self.native_step(provider, head).await;
         }
 
         let blocks_behind = head - start;
@@ -151,16 +286,24 @@ impl EventIndexer {
         }
 
         // Process in chunks
+        let contracts = self.contracts();
+        let collections = nft_collections().contracts(self.network.id);
+        let nothing_to_index = contracts.is_empty() && collections.is_empty();
         let mut from = start;
         while from <= head {
-            if self.token_contracts.is_empty() {
+            if nothing_to_index {
                 // No contracts to index, just update checkpoint
                 break;
             }
 
             let to = (from + self.chunk_size - 1).min(head);
 
-            let indexed = self.fetch_and_store_logs(provider, from, to).await?;
+            let indexed = if contracts.is_empty() {
+                0
+            } else {
+                self.fetch_and_store_logs(provider, &contracts, from, to)
+                    .await?
+            };
             if indexed > 0 {
                 tracing::info!(
                     from_block = from,
@@ -169,31 +312,139 @@ impl EventIndexer {
                     "Indexed ERC-20 transfer events"
                 );
             }
+            if !collections.is_empty() {
+                let moved = self
+                    .fetch_and_store_nft_logs(provider, &collections, from, to)
+                    .await?;
+                if moved > 0 {
+                    tracing::info!(
+                        from_block = from,
+                        to_block = to,
+                        transfers = moved,
+                        "Indexed NFT transfers"
+                    );
+                }
+            }
 
             self.db.set_last_indexed_block(&network_key, to)?;
             from = to + 1;
         }
 
         // If no contracts, still update checkpoint to head
-        if self.token_contracts.is_empty() {
+        if nothing_to_index {
             self.db.set_last_indexed_block(&network_key, head)?;
         }
 
+        self.native_step(provider, head).await
+    }
+
+    /// Read the next blocks for native transfers, up to
+    /// [`NATIVE_BLOCKS_PER_STEP`] of them.
+    async fn native_step<P: Provider + Clone>(
+        &self,
+        provider: &P,
+        head: u64,
+    ) -> Result<(), IndexerError> {
+        let native_key = native_checkpoint_key(&self.network);
+        let checkpoint = self.db.get_last_indexed_block(&native_key)?;
+        let start = if checkpoint == 0 {
+            head.saturating_sub(INITIAL_LOOKBACK_BLOCKS)
+        } else {
+            checkpoint + 1
+        };
+        if start > head {
+            return Ok(());
+        }
+
+        let to = (start + NATIVE_BLOCKS_PER_STEP - 1).min(head);
+        let indexed = self.fetch_and_store_native(provider, start, to).await?;
+        if indexed > 0 {
+            tracing::info!(
+                from_block = start,
+                to_block = to,
+                transfers = indexed,
+                "Indexed native transfers"
+            );
+        }
+        self.db.set_last_indexed_block(&native_key, to)?;
         Ok(())
     }
 
+    /// Read blocks in full and store native transfers with value that touch
+    /// a registered address.
+    async fn fetch_and_store_native<P: Provider + Clone>(
+        &self,
+        provider: &P,
+        from_block: u64,
+        to_block: u64,
+    ) -> Result<usize, IndexerError> {
+        let mut count = 0;
+        let mut block_times: HashMap<u64, DateTime<Utc>> = HashMap::new();
+
+        for number in from_block..=to_block {
+            let Some(block) = provider
+                .get_block_by_number(BlockNumberOrTag::Number(number))
+                .full()
+                .await
+                .map_err(|e| IndexerError::Rpc(e.to_string()))?
+            else {
+                continue;
+            };
+
+            for tx in block.transactions.txns() {
+                let (Some(to), false) = (tx.to(), tx.value().is_zero()) else {
+                    continue;
+                };
+                let from_addr = address_key(&tx.from().to_string());
+                let to_addr = address_key(&to.to_string());
+                if self.db.get_wallet_id_for_address(&from_addr)?.is_none()
+                    && self.db.get_wallet_id_for_address(&to_addr)?.is_none()
+                {
+                    continue;
+                }
+
+                // A reverted call to a contract moved no value.
+                let receipt = provider
+                    .get_transaction_receipt(tx.tx_hash())
+                    .await
+                    .map_err(|e| IndexerError::Rpc(e.to_string()))?;
+                if !receipt.is_some_and(|r| r.status()) {
+                    continue;
+                }
+
+                let transfer = ObservedTransfer {
+                    tx_hash: format!("{:#x}", tx.tx_hash()),
+                    from: from_addr,
+                    to: to_addr,
+                    value: tx.value(),
+                    token: TokenType::Native,
+                    decimals: self.network.native_decimals,
+                    block_number: Some(number),
+                    block_timestamp: Some(block.header.timestamp),
+                };
+                if self
+                    .store_transfer(provider, transfer, &mut block_times)
+                    .await?
+                {
+                    count += 1;
+                }
+            }
+        }
+
+        Ok(count)
+    }
+
     /// Fetch logs for a block range and store matching transfers.
     async fn fetch_and_store_logs<P: Provider + Clone>(
         &self,
         provider: &P,
+        contracts: &[Address],
         from_block: u64,
         to_block: u64,
     ) -> Result<usize, IndexerError> {
         // Build filter: Transfer events from our watched contracts
-        let addresses: Vec<Address> = self.token_contracts.clone();
-
         let filter = Filter::new()
-            .address(addresses)
+            .address(contracts.to_vec())
             .event_signature(TRANSFER_TOPIC)
             .from_block(from_block)
             .to_block(to_block);
@@ -204,6 +455,7 @@ impl EventIndexer {
             .map_err(|e| IndexerError::Rpc(e.to_string()))?;
 
         let mut count = 0;
+        let mut block_times: HashMap<u64, DateTime<Utc>> = HashMap::new();
 
         for log in &logs {
             // Transfer event has 3 topics: [event_sig, from, to] and data = value
@@ -225,7 +477,7 @@ impl EventIndexer {
                 U256::ZERO
             };
 
-            let contract_addr = log.address().to_string().to_lowercase();
+            let contract_addr = address_key(&log.address().to_string());
 
             let tx_hash = log
                 .transaction_hash
@@ -238,148 +490,324 @@ impl EventIndexer {
                 continue;
             }
 
-            // Check if from or to is a registered wallet address
-            let from_wallet = self.db.get_wallet_id_for_address(&from_addr)?;
-            let to_wallet = self.db.get_wallet_id_for_address(&to_addr)?;
+            let transfer = ObservedTransfer {
+                tx_hash,
+                from: from_addr,
+                to: to_addr,
+                value,
+                token: TokenType::Erc20(contract_addr.clone()),
+                decimals: self.identify_token(&contract_addr).1,
+                block_number,
+                block_timestamp: log.block_timestamp,
+            };
+            if self
+                .store_transfer(provider, transfer, &mut block_times)
+                .await?
+            {
+                count += 1;
+            }
+        }
+
+        Ok(count)
+    }
 
-            if from_wallet.is_none() && to_wallet.is_none() {
-                // Neither side belongs to us
+    /// Apply NFT transfers of the listed `collections` to wallet holdings.
+    /// Returns how many token movements were newly applied.
+    async fn fetch_and_store_nft_logs<P: Provider + Clone>(
+        &self,
+        provider: &P,
+        collections: &[Address],
+        from_block: u64,
+        to_block: u64,
+    ) -> Result<usize, IndexerError> {
+        let filter = Filter::new()
+            .address(collections.to_vec())
+            .event_signature(transfer_event_signatures())
+            .from_block(from_block)
+            .to_block(to_block);
+
+        let logs = provider
+            .get_logs(&filter)
+            .await
+            .map_err(|e| IndexerError::Rpc(e.to_string()))?;
+
+        let mut count = 0;
+        for log in &logs {
+            let (Some(tx_hash), Some(log_index), Some(block_number)) =
+                (log.transaction_hash, log.log_index, log.block_number)
+            else {
                 continue;
+            };
+            let tx_hash = format!("{tx_hash:#x}");
+            let contract = address_key(&log.address().to_string());
+            for transfer in decode_transfers(&log.inner) {
+                if self.db.record_nft_transfer(
+                    self.network.id,
+                    &contract,
+                    &transfer,
+                    &tx_hash,
+                    log_index,
+                    block_number,
+                )? {
+                    count += 1;
+                }
             }
+        }
 
-            // Determine token metadata
-            let (_symbol, decimals) = self.identify_token(&contract_addr);
-            let amount_formatted = format_amount(value, decimals);
+        Ok(count)
+    }
 
-            let explorer_url = format!("{}/tx/{}", self.network.explorer_url, tx_hash);
+    /// Store a transfer touching a registered address, or confirm the
+    /// record already stored for it. Returns whether a new record was
+    /// stored.
+    async fn store_transfer<P: Provider + Clone>(
+        &self,
+        provider: &P,
+        transfer: ObservedTransfer,
+        block_times: &mut HashMap<u64, DateTime<Utc>>,
+    ) -> Result<bool, IndexerError> {
+        let ObservedTransfer {
+            tx_hash,
+            from: from_addr,
+            to: to_addr,
+            value,
+            token,
+            decimals,
+            block_number,
+            block_timestamp,
+        } = transfer;
+
+        // Check if from or to is a registered wallet address
+        let from_wallet = self.db.get_wallet_id_for_address(&from_addr)?;
+        let to_wallet = self.db.get_wallet_id_for_address(&to_addr)?;
+
+        if from_wallet.is_none() && to_wallet.is_none() {
+            // Neither side belongs to us
+            return Ok(false);
+        }
 
-            // Determine the wallet_id this record belongs to and the direction(s)
-            let mut directions: Vec<(String, &str)> = Vec::new();
+        let amount_formatted = format_amount(value, decimals);
 
-            if from_wallet.is_some() {
-                directions.push((from_addr.clone(), "sent"));
-            }
-            if to_wallet.is_some() {
-                directions.push((to_addr.clone(), "received"));
+        let explorer_url = format!("{}/tx/{}", self.network.explorer_url, tx_hash);
+
+        // Determine the wallet_id this record belongs to and the direction(s)
+        let mut directions: Vec<(String, &str)> = Vec::new();
+
+        if from_wallet.is_some() {
+            directions.push((from_addr.clone(), "sent"));
+        }
+        if to_wallet.is_some() {
+            directions.push((to_addr.clone(), "received"));
+        }
+
+        // Check if this tx already exists
+        if let Some(existing) = self.db.get_transaction(&tx_hash)? {
+            // If the existing record is still pending, promote it to
+            // confirmed — we are reading from finalised on-chain logs so
+            // this transaction has definitely landed. The same holds for
+            // a transaction marked failed when it was replaced but mined
+            // before its replacement.
+            if existing.status == crate::storage::repository::transactions::TxStatus::Pending
+                || (existing.replaced_by.is_some()
+                    && existing.status
+                        == crate::storage::repository::transactions::TxStatus::Failed)
+            {
+                if let Err(e) = self.db.update_status(
+                    &tx_hash,
+                    crate::storage::repository::transactions::TxStatus::Confirmed,
+                    block_number,
+                    None,
+                ) {
+                    tracing::warn!(
+                        tx_hash = %tx_hash,
+                        error = %e,
+                        "Failed to confirm pending indexed transaction"
+                    );
+                } else {
+                    tracing::info!(
+                        tx_hash = %tx_hash,
+                        block_number = ?block_number,
+                        "Indexer: promoted pending tx to confirmed"
+                    );
+                    // Invalidate cached data of affected wallets
+                    for (addr, _) in &directions {
+                        self.caches.invalidate_address(addr);
+                    }
+                    if let Some(wallet_id) = &to_wallet {
+                        self.note_deposit(wallet_id, &to_addr, &existing);
+                    }
+                }
             }
+            return Ok(false);
+        }
+
+        // Build StoredTransaction
+        // Use the first matching wallet_id for the record
+        let primary_wallet_id = from_wallet
+            .as_deref()
+            .or(to_wallet.as_deref())
+            .unwrap_or("unknown")
+            .to_string();
+
+        // Watched addresses are tracked under a shared ID, not a wallet.
+        let counterparty_wallet_id = if from_wallet.is_some() && to_wallet.is_some() {
+            to_wallet.clone().filter(|id| !is_tracking_id(id))
+        } else {
+            None
+        };
 
-            // Check if this tx already exists
-            if let Some(existing) = self.db.get_transaction(&tx_hash)? {
-                // If the existing record is still pending, promote it to
-                // confirmed — we are reading from finalised on-chain logs so
-                // this transaction has definitely landed.
-                if existing.status == crate::storage::repository::transactions::TxStatus::Pending {
-                    if let Err(e) = self.db.update_status(
-                        &tx_hash,
-                        crate::storage::repository::transactions::TxStatus::Confirmed,
-                        block_number,
-                        None,
-                    ) {
+        let mut stored_tx = StoredTransaction::new_pending(
+            tx_hash.clone(),
+            primary_wallet_id,
+            counterparty_wallet_id,
+            from_addr.clone(),
+            to_addr.clone(),
+            amount_formatted,
+            token,
+            self.network_name_short(),
+            explorer_url,
+        );
+
+        // Mark as confirmed since we're reading from finalized logs
+        stored_tx.status = TxStatus::Confirmed;
+        stored_tx.block_number = block_number;
+        if poisoning::is_dust(&stored_tx.amount) {
+            stored_tx.suspected_poisoning = directions.iter().any(|(addr, direction)| {
+                let other = if *direction == "sent" {
+                    &to_addr
+                } else {
+                    &from_addr
+                };
+                match self
+                    .db
+                    .list_by_wallet(addr, None, poisoning::HISTORY_WINDOW)
+                {
+                    Ok((history, _)) => {
+                        poisoning::is_suspected_poisoning(&stored_tx.amount, other, &history)
+                    }
+                    Err(e) => {
                         tracing::warn!(
                             tx_hash = %tx_hash,
                             error = %e,
-                            "Failed to confirm pending indexed transaction"
-                        );
-                    } else {
-                        tracing::info!(
-                            tx_hash = %tx_hash,
-                            block_number = ?block_number,
-                            "Indexer: promoted pending tx to confirmed"
+                            "Failed to load history for poisoning check"
                         );
-                        // Invalidate cache for affected wallets
-                        for (addr, _) in &directions {
-                            self.cache.invalidate(addr);
+                        false
+                    }
+                }
+            });
+            if stored_tx.suspected_poisoning {
+                tracing::info!(
+                    tx_hash = %tx_hash,
+                    from = %from_addr,
+                    to = %to_addr,
+                    "Indexer: flagged suspected address poisoning"
+                );
+            }
+        }
+        if self.block_timestamps {
+            if let Some(number) = block_number {
+                let time = match block_times.get(&number) {
+                    Some(time) => Some(*time),
+                    None => {
+                        let time = block_time(provider, number, block_timestamp).await?;
+                        if let Some(time) = time {
+                            block_times.insert(number, time);
                         }
+                        time
                     }
+                };
+                if let Some(time) = time {
+                    stored_tx.created_at = time;
+                    stored_tx.updated_at = time;
                 }
-                continue;
             }
+        }
 
-            // Build StoredTransaction
-            // Use the first matching wallet_id for the record
-            let primary_wallet_id = from_wallet
-                .as_deref()
-                .or(to_wallet.as_deref())
-                .unwrap_or("unknown")
-                .to_string();
-
-            let counterparty_wallet_id = if from_wallet.is_some() && to_wallet.is_some() {
-                to_wallet.clone()
-            } else {
-                None
-            };
-
-            let mut stored_tx = StoredTransaction::new_pending(
-                tx_hash.clone(),
-                primary_wallet_id,
-                counterparty_wallet_id,
-                from_addr.clone(),
-                to_addr.clone(),
-                amount_formatted,
-                TokenType::Erc20(contract_addr.clone()),
-                self.network_name_short(),
-                explorer_url,
+        if let Err(e) = self.db.upsert_transaction(&stored_tx, &directions) {
+            tracing::warn!(
+                tx_hash = %tx_hash,
+                error = %e,
+                "Failed to store indexed transaction"
             );
+            return Ok(false);
+        }
 
-            // Mark as confirmed since we're reading from finalized logs
-            stored_tx.status = TxStatus::Confirmed;
-            stored_tx.block_number = block_number;
-
-            if let Err(e) = self.db.upsert_transaction(&stored_tx, &directions) {
-                tracing::warn!(
-                    tx_hash = %tx_hash,
-                    error = %e,
-                    "Failed to store indexed transaction"
-                );
-                continue;
-            }
+        // Invalidate cached data of affected wallets
+        for (addr, _) in &directions {
+            self.caches.invalidate_address(addr);
+        }
+        if let Some(wallet_id) = &to_wallet {
+            self.note_deposit(wallet_id, &to_addr, &stored_tx);
+        }
 
-            // Invalidate cache for affected wallets
-            for (addr, _) in &directions {
-                self.cache.invalidate(addr);
-            }
+        Ok(true)
+    }
 
-            count += 1;
+    /// Check whether a confirmed deposit is the wallet's first.
+    fn note_deposit(&self, wallet_id: &str, address: &str, tx: &StoredTransaction) {
+        let Some(storage) = &self.storage else {
+            return;
+        };
+        if is_tracking_id(wallet_id) {
+            return;
         }
-
-        Ok(count)
+        funding::record_deposit(storage, &self.db, wallet_id, address, tx);
     }
 
     /// Identify token symbol and decimals from contract address.
-    fn identify_token(&self, contract_addr: &str) -> (&str, u8) {
-        let addr_lower = contract_addr.to_lowercase();
-
-        if REUR_TOKEN
-            .fuji_address
-            .map(|a| a.to_lowercase() == addr_lower)
-            .unwrap_or(false)
-        {
-            return (REUR_TOKEN.symbol, REUR_TOKEN.decimals);
+    fn identify_token(&self, contract_addr: &str) -> (String, u8) {
+        match tokens().get(self.network.id, contract_addr) {
+            Some(token) => (token.symbol, token.decimals),
+            // Unknown token — default to 18 decimals
+            None => ("ERC20".to_string(), 18),
         }
-
-        // Unknown token — default to 18 decimals
-        ("ERC20", 18)
     }
 
-    /// Short network name for storage (e.g. "fuji").
+    /// Registry ID of the network, stored on each record (e.g. "fuji").
     fn network_name_short(&self) -> String {
-        if self.network.chain_id == AVAX_FUJI.chain_id {
-            "fuji".to_string()
-        } else {
-            "mainnet".to_string()
-        }
+        self.network.id.to_string()
     }
 }
 
-/// Build the list of token contract addresses to monitor on Fuji.
-pub fn fuji_token_contracts() -> Vec<Address> {
-    let mut addrs = Vec::new();
-    if let Some(addr_str) = REUR_TOKEN.fuji_address {
-        if let Ok(addr) = addr_str.parse::<Address>() {
-            addrs.push(addr);
+/// Timestamp of a block, from the log when the node includes it.
+async fn block_time<P: Provider + Clone>(
+    provider: &P,
+    number: u64,
+    log_timestamp: Option<u64>,
+) -> Result<Option<DateTime<Utc>>, IndexerError> {
+    let seconds = match log_timestamp {
+        Some(seconds) => seconds,
+        None => {
+            let block = provider
+                .get_block_by_number(BlockNumberOrTag::Number(number))
+                .await
+                .map_err(|e| IndexerError::Rpc(e.to_string()))?;
+            match block {
+                Some(block) => block.header.timestamp,
+                None => return Ok(None),
+            }
         }
-    }
-    addrs
+    };
+    Ok(i64::try_from(seconds)
+        .ok()
+        .and_then(|s| DateTime::from_timestamp(s, 0)))
+}
+
+/// Key under which the indexer checkpoints its progress on `network`.
+pub fn checkpoint_key(network: &NetworkConfig) -> String {
+    network.name.to_lowercase().replace(' ', "_")
+}
+
+/// Key under which the native-transfer pass checkpoints its progress on
+/// `network`.
+pub fn native_checkpoint_key(network: &NetworkConfig) -> String {
+    format!("{}_native", checkpoint_key(network))
+}
+
+/// Build the list of token contract addresses to monitor on Fuji: the
+/// tokens the registry lists as indexed there.
+pub fn fuji_token_contracts() -> Vec<Address> {
+    tokens().indexed_contracts(NETWORK_FUJI)
 }
 
 // =============================================================================
@@ -393,11 +821,15 @@ pub enum IndexerError {
 
     #[error("Database error: {0}")]
     Db(#[from] crate::storage::tx_database::TxDbError),
+
+    #[error("Invalid block range: {0}")]
+    InvalidRange(String),
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::blockchain::AVAX_FUJI;
 
     #[test]
     fn transfer_topic_is_correct() {
@@ -421,12 +853,39 @@ mod tests {
             )
             .unwrap(),
         );
-        let cache = Arc::new(TxCache::new(10, Duration::from_secs(60)));
-        let indexer = EventIndexer::new(db, cache, AVAX_FUJI, fuji_token_contracts());
+        let indexer = EventIndexer::new(db, CacheBus::new(), AVAX_FUJI, fuji_token_contracts());
 
         let (symbol, decimals) =
             indexer.identify_token("0x76568BEd5Acf1A5Cd888773C8cAe9ea2a9131A63");
         assert_eq!(symbol, "rEUR");
         assert_eq!(decimals, 6);
     }
+
+    #[test]
+    fn listed_tokens_join_configured_contracts() {
+        let db = Arc::new(
+            TxDatabase::open(
+                &std::env::temp_dir().join(format!("test-listed-{}.redb", uuid::Uuid::new_v4())),
+            )
+            .unwrap(),
+        );
+        let caches = CacheBus::new();
+        let indexer = EventIndexer::new(db.clone(), caches.clone(), AVAX_FUJI, Vec::new());
+        assert!(indexer.contracts().is_empty());
+
+        let indexer = indexer.with_listed_tokens();
+        assert_eq!(indexer.contracts(), fuji_token_contracts());
+        let indexer =
+            EventIndexer::new(db, caches, AVAX_FUJI, fuji_token_contracts()).with_listed_tokens();
+        assert_eq!(indexer.contracts(), fuji_token_contracts());
+    }
+
+    #[test]
+    fn native_pass_checkpoints_separately() {
+        assert_eq!(checkpoint_key(&AVAX_FUJI), "avalanche_fuji_testnet");
+        assert_eq!(
+            native_checkpoint_key(&AVAX_FUJI),
+            "avalanche_fuji_testnet_native"
+        );
+    }
 }
@@ -0,0 +1,121 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! # Address Poisoning Detection
+//!
+//! Address poisoning seeds a wallet's history with zero-value or dust
+//! transfers involving an address that starts and ends like one of the
+//! wallet's real counterparties, hoping the user later copies the lookalike
+//! from their history. The indexer flags such transfers
+//! ([`StoredTransaction::suspected_poisoning`]) and recent-recipient
+//! suggestions leave their counterparties out.
+
+use crate::blockchain::address_key;
+use crate::storage::repository::transactions::StoredTransaction;
+
+/// Leading hex characters (after `0x`) a lookalike shares with the original.
+pub const MATCH_PREFIX_LEN: usize = 4;
+
+/// Trailing hex characters a lookalike shares with the original.
+pub const MATCH_SUFFIX_LEN: usize = 4;
+
+/// Largest amount, in whole tokens, that counts as dust.
+pub const DUST_MAX_AMOUNT: f64 = 0.01;
+
+/// Most recent transactions searched for the original counterparty.
+pub const HISTORY_WINDOW: usize = 200;
+
+/// Whether an amount (whole tokens) is zero or dust.
+pub fn is_dust(amount: &str) -> bool {
+    amount
+        .trim()
+        .parse::<f64>()
+        .is_ok_and(|value| value <= DUST_MAX_AMOUNT)
+}
+
+/// Whether two different addresses share their first and last characters.
+pub fn is_lookalike(a: &str, b: &str) -> bool {
+    let a = a.trim_start_matches("0x").to_lowercase();
+    let b = b.trim_start_matches("0x").to_lowercase();
+    if a == b || a.len() != b.len() || a.len() < MATCH_PREFIX_LEN + MATCH_SUFFIX_LEN {
+        return false;
+    }
+    a[..MATCH_PREFIX_LEN] == b[..MATCH_PREFIX_LEN]
+        && a[a.len() - MATCH_SUFFIX_LEN..] == b[b.len() - MATCH_SUFFIX_LEN..]
+}
+
+/// The other side of a transaction, lowercase.
+pub fn counterparty(tx: &StoredTransaction, direction: &str) -> String {
+    if direction == "sent" {
+        address_key(&tx.to)
+    } else {
+        address_key(&tx.from)
+    }
+}
+
+/// Whether a transfer of `amount` with `other` looks like address poisoning,
+/// given the wallet's history (newest first).
+pub fn is_suspected_poisoning(
+    amount: &str,
+    other: &str,
+    history: &[(StoredTransaction, String)],
+) -> bool {
+    is_dust(amount)
+        && history
+            .iter()
+            .filter(|(tx, _)| !tx.suspected_poisoning)
+            .any(|(tx, direction)| is_lookalike(&counterparty(tx, direction), other))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::repository::transactions::TokenType;
+
+    const ME: &str = "0x1111111111111111111111111111111111111111";
+    const FRIEND: &str = "0xabcd000000000000000000000000000000001234";
+    const LOOKALIKE: &str = "0xABCD999999999999999999999999999999991234";
+
+    fn sent(to: &str, amount: &str) -> (StoredTransaction, String) {
+        let tx = StoredTransaction::new_pending(
+            "0xaa".to_string(),
+            "wallet-1".to_string(),
+            None,
+            ME.to_string(),
+            to.to_string(),
+            amount.to_string(),
+            TokenType::Native,
+            "fuji".to_string(),
+            String::new(),
+        );
+        (tx, "sent".to_string())
+    }
+
+    #[test]
+    fn lookalikes_share_prefix_and_suffix_only() {
+        assert!(is_lookalike(FRIEND, LOOKALIKE));
+        assert!(!is_lookalike(FRIEND, FRIEND));
+        assert!(!is_lookalike(
+            FRIEND,
+            &FRIEND.to_uppercase().replace("0X", "0x")
+        ));
+        assert!(!is_lookalike(FRIEND, ME));
+        assert!(!is_lookalike("0xabcd", "0xabce"));
+    }
+
+    #[test]
+    fn dust_from_a_lookalike_is_flagged() {
+        let history = vec![sent(FRIEND, "250")];
+        assert!(is_dust("0"));
+        assert!(is_dust("0.005"));
+        assert!(!is_dust("1"));
+        assert!(is_suspected_poisoning("0", LOOKALIKE, &history));
+        assert!(!is_suspected_poisoning("5", LOOKALIKE, &history));
+        assert!(!is_suspected_poisoning("0", FRIEND, &history));
+
+        let mut flagged = sent(FRIEND, "0");
+        flagged.0.suspected_poisoning = true;
+        assert!(!is_suspected_poisoning("0", LOOKALIKE, &[flagged]));
+    }
+}
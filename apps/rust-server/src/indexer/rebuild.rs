@@ -0,0 +1,461 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Rebuilding the transaction database from chain data.
+//!
+//! The redb file is a cache of on-chain history plus lookup maps derived
+//! from encrypted storage, so it can be reconstructed while the wallet
+//! records survive. A rebuild:
+//!
+//! 1. registers every known address (wallets, the fiat service wallet,
+//!    watch-only addresses) in a scratch database,
+//! 2. replays ERC-20 `Transfer` logs of the token registry over a block
+//!    range into it, dated by block,
+//! 3. compares the result with the live database, and
+//! 4. when applying, re-registers the addresses in the live database and
+//!    copies in transfers it is missing or still has as pending.
+//!
+//! Native AVAX transfers are not visible in logs and are never restored.
+//! Ranges are capped at [`MAX_REBUILD_BLOCKS`]; the report's
+//! `next_from_block` continues a longer replay.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use alloy::primitives::Address;
+use alloy::providers::{Provider, ProviderBuilder};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use super::{EventIndexer, IndexerError};
+use crate::blockchain::{address_key, NetworkConfig};
+use crate::storage::repository::transactions::{StoredTransaction, TokenType, TxStatus};
+use crate::storage::repository::watch_only::tracking_id;
+use crate::storage::tx_database::TxDatabase;
+use crate::storage::{
+    CacheBus, EncryptedStorage, FiatServiceWalletRepository, WalletRepository, WalletStatus,
+    WatchOnlyRepository,
+};
+
+/// Most blocks replayed by one rebuild call.
+pub const MAX_REBUILD_BLOCKS: u64 = 200_000;
+/// Most transaction hashes listed per mismatch category in a report.
+const MAX_LISTED_HASHES: usize = 50;
+
+/// What to replay.
+#[derive(Debug, Clone)]
+pub struct RebuildOptions {
+    pub from_block: u64,
+    /// Defaults to the chain head, capped at [`MAX_REBUILD_BLOCKS`].
+    pub to_block: Option<u64>,
+    pub token_contracts: Vec<Address>,
+    /// Write restored records into the live database; otherwise only verify.
+    pub apply: bool,
+}
+
+/// Outcome of a rebuild.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct RebuildReport {
+    pub from_block: u64,
+    pub to_block: u64,
+    /// Start of the next range when the chain head was not reached.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_from_block: Option<u64>,
+    pub token_contracts: Vec<String>,
+    /// Addresses known from encrypted storage.
+    pub registered_addresses: usize,
+    /// Token transfers touching known addresses found on chain.
+    pub onchain_transactions: usize,
+    /// Confirmed token transfers of the same tokens and range in the live
+    /// database.
+    pub database_transactions: usize,
+    /// On chain and in the live database.
+    pub matching: usize,
+    /// On chain but absent from the live database.
+    pub missing_from_database: Vec<String>,
+    pub missing_from_database_count: usize,
+    /// On chain but still pending in the live database.
+    pub pending_in_database: Vec<String>,
+    pub pending_in_database_count: usize,
+    /// In the live database for this range but not found on chain.
+    pub missing_on_chain: Vec<String>,
+    pub missing_on_chain_count: usize,
+    /// Whether restored records were written.
+    pub applied: bool,
+    /// Records inserted or confirmed in the live database.
+    pub restored: usize,
+}
+
+/// Register every address known from encrypted storage in `db`: wallet
+/// addresses, user and email lookups for live wallets, the fiat service
+/// wallet, and watch-only addresses. Idempotent; returns the number of
+/// addresses registered.
+pub fn register_known_addresses(storage: &EncryptedStorage, db: &TxDatabase) -> usize {
+    let mut registered = 0;
+    if let Ok(wallets) = WalletRepository::new(storage).list_all_wallets() {
+        for w in &wallets {
+            // address → wallet_id
+            if let Err(e) = db.register_address(&w.public_address, &w.wallet_id) {
+                tracing::warn!(wallet_id = %w.wallet_id, error = %e, "Failed to register wallet address");
+            } else {
+                registered += 1;
+            }
+            // user_id → wallet_id (only for non-deleted wallets)
+            if w.status != WalletStatus::Deleted {
+                if let Err(e) = db.register_user_wallet(&w.owner_user_id, &w.wallet_id) {
+                    tracing::warn!(wallet_id = %w.wallet_id, error = %e, "Failed to register user→wallet mapping");
+                }
+                // email_lookup_key → { wallet_id, public_address }
+                if let Some(ref lookup_key) = w.email_lookup_key {
+                    if let Err(e) =
+                        db.register_email_lookup(lookup_key, &w.wallet_id, &w.public_address)
+                    {
+                        tracing::warn!(wallet_id = %w.wallet_id, error = %e, "Failed to register email lookup");
+                    }
+                }
+            }
+        }
+    }
+    // The fiat service wallet, so off-ramp deposits are detected.
+    if let Ok(meta) = FiatServiceWalletRepository::new(storage).get() {
+        if let Err(e) = db.register_address(&meta.public_address, &meta.wallet_id) {
+            tracing::warn!(error = %e, "Failed to register service wallet address");
+        } else {
+            registered += 1;
+        }
+    }
+    // Watched external addresses share one tracking ID per address.
+    if let Ok(entries) = WatchOnlyRepository::new(storage).list_all() {
+        for entry in &entries {
+            if let Err(e) = db.register_address(&entry.address, &tracking_id(&entry.address)) {
+                tracing::warn!(watch_id = %entry.watch_id, error = %e, "Failed to register watch-only address");
+            } else {
+                registered += 1;
+            }
+        }
+    }
+    registered
+}
+
+/// Replay `options` and compare with (or restore into) `live`.
+pub async fn rebuild(
+    storage: &EncryptedStorage,
+    live: &TxDatabase,
+    caches: Option<&CacheBus>,
+    network: NetworkConfig,
+    options: RebuildOptions,
+) -> Result<RebuildReport, IndexerError> {
+    let url = network
+        .rpc_url
+        .parse()
+        .map_err(|e| IndexerError::Rpc(format!("invalid RPC URL: {e}")))?;
+    let head = ProviderBuilder::new()
+        .connect_http(url)
+        .get_block_number()
+        .await
+        .map_err(|e| IndexerError::Rpc(e.to_string()))?;
+    let (from_block, to_block, next_from_block) =
+        plan_range(options.from_block, options.to_block, head)?;
+
+    let scratch = ScratchDatabase::open(storage)?;
+    let registered_addresses = register_known_addresses(storage, &scratch.db);
+    EventIndexer::new(
+        scratch.db.clone(),
+        CacheBus::new(),
+        network,
+        options.token_contracts.clone(),
+    )
+    .with_block_timestamps()
+    .index_range(from_block, to_block)
+    .await?;
+
+    let onchain = scratch.db.list_all_transactions()?;
+    let existing = live.list_all_transactions()?;
+    let contracts: HashSet<String> = options
+        .token_contracts
+        .iter()
+        .map(|a| address_key(&a.to_string()))
+        .collect();
+    let comparison = compare(&onchain, &existing, &contracts, from_block, to_block);
+
+    let mut restored = 0;
+    if options.apply {
+        register_known_addresses(storage, live);
+        for tx in &onchain {
+            let hash = tx.tx_hash.as_str();
+            if comparison.missing_from_database.contains(hash) {
+                let directions = directions(&scratch.db, tx)?;
+                live.upsert_transaction(tx, &directions)?;
+            } else if comparison.pending_in_database.contains(hash) {
+                live.update_status(hash, TxStatus::Confirmed, tx.block_number, None)?;
+            } else {
+                continue;
+            }
+            if let Some(caches) = caches {
+                caches.invalidate_address(&tx.from);
+                caches.invalidate_address(&tx.to);
+            }
+            restored += 1;
+        }
+    }
+
+    let listed = |hashes: &HashSet<String>| {
+        let mut hashes: Vec<String> = hashes.iter().cloned().collect();
+        hashes.sort();
+        hashes.truncate(MAX_LISTED_HASHES);
+        hashes
+    };
+    Ok(RebuildReport {
+        from_block,
+        to_block,
+        next_from_block,
+        token_contracts: options
+            .token_contracts
+            .iter()
+            .map(ToString::to_string)
+            .collect(),
+        registered_addresses,
+        onchain_transactions: onchain.len(),
+        database_transactions: comparison.database_transactions,
+        matching: comparison.matching,
+        missing_from_database: listed(&comparison.missing_from_database),
+        missing_from_database_count: comparison.missing_from_database.len(),
+        pending_in_database: listed(&comparison.pending_in_database),
+        pending_in_database_count: comparison.pending_in_database.len(),
+        missing_on_chain: listed(&comparison.missing_on_chain),
+        missing_on_chain_count: comparison.missing_on_chain.len(),
+        applied: options.apply,
+        restored,
+    })
+}
+
+/// Clamp the requested range to the head and [`MAX_REBUILD_BLOCKS`].
+/// Returns `(from, to, next_from)`.
+fn plan_range(
+    from_block: u64,
+    to_block: Option<u64>,
+    head: u64,
+) -> Result<(u64, u64, Option<u64>), IndexerError> {
+    if from_block > head {
+        return Err(IndexerError::InvalidRange(format!(
+            "from_block {from_block} is past the chain head {head}"
+        )));
+    }
+    let requested = to_block.unwrap_or(head).min(head);
+    if requested < from_block {
+        return Err(IndexerError::InvalidRange(
+            "to_block is before from_block".into(),
+        ));
+    }
+    let to = requested.min(from_block + MAX_REBUILD_BLOCKS - 1);
+    let next = (to < head && to_block.is_none_or(|t| to < t)).then_some(to + 1);
+    Ok((from_block, to, next))
+}
+
+#[derive(Debug, Default)]
+struct Comparison {
+    database_transactions: usize,
+    matching: usize,
+    missing_from_database: HashSet<String>,
+    pending_in_database: HashSet<String>,
+    missing_on_chain: HashSet<String>,
+}
+
+/// Match replayed transfers against the live records of the same tokens.
+fn compare(
+    onchain: &[StoredTransaction],
+    existing: &[StoredTransaction],
+    contracts: &HashSet<String>,
+    from_block: u64,
+    to_block: u64,
+) -> Comparison {
+    let onchain_hashes: HashSet<&str> = onchain.iter().map(|tx| tx.tx_hash.as_str()).collect();
+    let mut comparison = Comparison::default();
+
+    let mut seen = HashSet::new();
+    for tx in existing {
+        if !onchain_hashes.contains(tx.tx_hash.as_str()) {
+            let in_range = tx
+                .block_number
+                .is_some_and(|block| (from_block..=to_block).contains(&block));
+            if tx.status == TxStatus::Confirmed && in_range && is_watched_token(tx, contracts) {
+                comparison.database_transactions += 1;
+                comparison.missing_on_chain.insert(tx.tx_hash.clone());
+            }
+            continue;
+        }
+        seen.insert(tx.tx_hash.as_str());
+        if tx.status == TxStatus::Pending {
+            comparison.pending_in_database.insert(tx.tx_hash.clone());
+        } else {
+            comparison.database_transactions += 1;
+            comparison.matching += 1;
+        }
+    }
+    comparison.missing_from_database = onchain_hashes
+        .difference(&seen)
+        .map(|hash| hash.to_string())
+        .collect();
+    comparison
+}
+
+fn is_watched_token(tx: &StoredTransaction, contracts: &HashSet<String>) -> bool {
+    matches!(&tx.token, TokenType::Erc20(address) if contracts.contains(&address_key(address)))
+}
+
+/// History index entries for a replayed transfer, from the scratch
+/// database's address map.
+fn directions(
+    db: &TxDatabase,
+    tx: &StoredTransaction,
+) -> Result<Vec<(String, &'static str)>, IndexerError> {
+    let mut directions = Vec::new();
+    if db.get_wallet_id_for_address(&tx.from)?.is_some() {
+        directions.push((tx.from.clone(), "sent"));
+    }
+    if db.get_wallet_id_for_address(&tx.to)?.is_some() {
+        directions.push((tx.to.clone(), "received"));
+    }
+    Ok(directions)
+}
+
+/// A throwaway database next to the live one, deleted on drop.
+struct ScratchDatabase {
+    db: Arc<TxDatabase>,
+    path: std::path::PathBuf,
+}
+
+impl ScratchDatabase {
+    fn open(storage: &EncryptedStorage) -> Result<Self, IndexerError> {
+        let path = storage
+            .paths()
+            .root()
+            .join(format!("tx-rebuild-{}.redb", uuid::Uuid::new_v4()));
+        Ok(Self {
+            db: Arc::new(TxDatabase::open(&path)?),
+            path,
+        })
+    }
+}
+
+impl Drop for ScratchDatabase {
+    fn drop(&mut self) {
+        if let Err(e) = std::fs::remove_file(&self.path) {
+            tracing::warn!(path = %self.path.display(), error = %e, "Failed to remove scratch database");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REUR: &str = "0x76568bed5acf1a5cd888773c8cae9ea2a9131a63";
+
+    fn tx(hash: &str, status: TxStatus, block: Option<u64>) -> StoredTransaction {
+        let mut tx = StoredTransaction::new_pending(
+            hash.to_string(),
+            "wallet-1".to_string(),
+            None,
+            "0x1111111111111111111111111111111111111111".to_string(),
+            "0x2222222222222222222222222222222222222222".to_string(),
+            "1.0".to_string(),
+            TokenType::Erc20(REUR.to_string()),
+            "fuji".to_string(),
+            String::new(),
+        );
+        tx.status = status;
+        tx.block_number = block;
+        tx
+    }
+
+    #[test]
+    fn comparison_classifies_each_record() {
+        let onchain = vec![
+            tx("0xmatch", TxStatus::Confirmed, Some(10)),
+            tx("0xlost", TxStatus::Confirmed, Some(11)),
+            tx("0xpending", TxStatus::Confirmed, Some(12)),
+        ];
+        let mut native = tx("0xnative", TxStatus::Confirmed, Some(12));
+        native.token = TokenType::Native;
+        let existing = vec![
+            tx("0xmatch", TxStatus::Confirmed, Some(10)),
+            tx("0xpending", TxStatus::Pending, None),
+            tx("0xreorged", TxStatus::Confirmed, Some(13)),
+            tx("0xoutside", TxStatus::Confirmed, Some(500)),
+            native,
+        ];
+        let contracts = HashSet::from([REUR.to_string()]);
+
+        let comparison = compare(&onchain, &existing, &contracts, 0, 100);
+        assert_eq!(comparison.matching, 1);
+        assert_eq!(comparison.database_transactions, 2);
+        assert_eq!(
+            comparison.missing_from_database,
+            HashSet::from(["0xlost".to_string()])
+        );
+        assert_eq!(
+            comparison.pending_in_database,
+            HashSet::from(["0xpending".to_string()])
+        );
+        assert_eq!(
+            comparison.missing_on_chain,
+            HashSet::from(["0xreorged".to_string()])
+        );
+    }
+
+    #[test]
+    fn ranges_are_capped_and_continued() {
+        assert_eq!(plan_range(100, None, 150).unwrap(), (100, 150, None));
+        assert_eq!(
+            plan_range(0, None, MAX_REBUILD_BLOCKS * 2).unwrap(),
+            (0, MAX_REBUILD_BLOCKS - 1, Some(MAX_REBUILD_BLOCKS))
+        );
+        assert_eq!(plan_range(10, Some(20), 1_000).unwrap(), (10, 20, None));
+        assert!(plan_range(200, None, 150).is_err());
+        assert!(plan_range(20, Some(10), 150).is_err());
+    }
+
+    #[test]
+    fn known_addresses_are_registered() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let mut storage = EncryptedStorage::new(crate::storage::StoragePaths::new(dir.path()));
+        storage.initialize().unwrap();
+        WalletRepository::new(&storage)
+            .create(
+                &crate::storage::WalletMetadata {
+                    wallet_id: "wallet-1".to_string(),
+                    owner_user_id: "user-a".to_string(),
+                    public_address: "0x1111111111111111111111111111111111111111".to_string(),
+                    created_at: chrono::Utc::now(),
+                    status: WalletStatus::Active,
+                    label: None,
+                    email_lookup_key: None,
+                    email_sha256: None,
+                    account_type: Default::default(),
+                    smart_account: None,
+                    lock: None,
+                    deleted_at: None,
+                    purged_at: None,
+                    funded_at: None,
+                    tenant_id: None,
+                },
+                b"test_key",
+            )
+            .unwrap();
+        let db = TxDatabase::open(&dir.path().join("scratch.redb")).unwrap();
+
+        assert_eq!(register_known_addresses(&storage, &db), 1);
+        assert_eq!(
+            db.get_wallet_id_for_address("0x1111111111111111111111111111111111111111")
+                .unwrap()
+                .as_deref(),
+            Some("wallet-1")
+        );
+        assert_eq!(
+            db.get_user_wallet("user-a").unwrap().as_deref(),
+            Some("wallet-1")
+        );
+    }
+}
@@ -0,0 +1,408 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! # Wallet Insights Aggregator
+//!
+//! Background task that keeps per-wallet monthly spending aggregates up to
+//! date. Every [`INSIGHTS_INTERVAL_SECS`] it walks the non-deleted wallets,
+//! categorizes their history and stores one [`MonthlyInsights`] per month in
+//! the wallet's [`InsightsRepository`] record. `GET
+//! /v1/wallets/{wallet_id}/insights` only reads these records.
+//!
+//! Work is incremental: the current and previous month are always
+//! recomputed, older months only when they are missing, were invalidated
+//! (a user category assignment changed), their confirmed transaction count
+//! changed or some of their transactions had no price yet. Otherwise a
+//! closed month keeps its stored totals, including the automatic categories
+//! it was computed with.
+//!
+//! ## Shutdown
+//!
+//! Uses `tokio_util::sync::CancellationToken` for graceful shutdown, following
+//! the same pattern as the `FiatPoller`.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Datelike, Months, Utc};
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+
+use crate::api::categories::{categorize, wallet_history, CategoryContext, TxCategory};
+use crate::providers::pricing::{value_at_tx_time, PRICED_SYMBOLS};
+use crate::storage::{
+    CategoryRepository, CounterpartyTotals, EncryptedStorage, InsightsRepository, MonthlyInsights,
+    PriceHistories, PriceHistoryRepository, StoredTransaction, StoredUserCategories, TxDatabase,
+    TxStatus, WalletMetadata, WalletRepository, WalletStatus,
+};
+
+/// Interval between aggregation runs.
+pub const INSIGHTS_INTERVAL_SECS: u64 = 900;
+
+/// Counterparties kept per month, largest volume first.
+pub const MAX_COUNTERPARTIES: usize = 50;
+
+/// Background task aggregating monthly wallet insights.
+pub struct InsightsAggregator {
+    storage: Arc<EncryptedStorage>,
+    tx_db: Arc<TxDatabase>,
+}
+
+impl InsightsAggregator {
+    /// Create an aggregator over the given storage and transaction database.
+    pub fn new(storage: Arc<EncryptedStorage>, tx_db: Arc<TxDatabase>) -> Self {
+        Self { storage, tx_db }
+    }
+
+    /// Run the aggregation loop until the cancellation token is triggered.
+    pub async fn run(self, shutdown: CancellationToken) {
+        info!(
+            interval_secs = INSIGHTS_INTERVAL_SECS,
+            "Insights aggregator starting"
+        );
+
+        loop {
+            if shutdown.is_cancelled() {
+                info!("Insights aggregator shutting down");
+                return;
+            }
+
+            let storage = self.storage.clone();
+            let tx_db = self.tx_db.clone();
+            let result =
+                tokio::task::spawn_blocking(move || aggregate_all(&storage, &tx_db, Utc::now()))
+                    .await;
+            match result {
+                Ok(0) => {}
+                Ok(months) => info!(months, "Insights aggregation finished"),
+                Err(e) => warn!(error = %e, "Insights aggregation panicked"),
+            }
+            crate::worker_health::record_heartbeat(crate::worker_health::Worker::Insights);
+
+            tokio::select! {
+                _ = tokio::time::sleep(Duration::from_secs(INSIGHTS_INTERVAL_SECS)) => {},
+                _ = shutdown.cancelled() => {
+                    info!("Insights aggregator shutting down");
+                    return;
+                }
+            }
+        }
+    }
+}
+
+fn month_key(at: DateTime<Utc>) -> String {
+    at.format("%Y-%m").to_string()
+}
+
+/// Aggregate every non-deleted wallet. Returns the number of months
+/// recomputed.
+pub fn aggregate_all(storage: &EncryptedStorage, tx_db: &TxDatabase, now: DateTime<Utc>) -> usize {
+    let wallets = match WalletRepository::new(storage).list_all_wallets() {
+        Ok(wallets) => wallets,
+        Err(e) => {
+            warn!(error = %e, "Insights aggregator: failed to list wallets");
+            return 0;
+        }
+    };
+    let prices = PriceHistoryRepository::new(storage)
+        .get_many(&PRICED_SYMBOLS)
+        .unwrap_or_else(|e| {
+            warn!(error = %e, "Insights aggregator: failed to load price history");
+            PriceHistories::new()
+        });
+
+    let mut contexts: HashMap<&str, CategoryContext> = HashMap::new();
+    let mut recomputed = 0;
+    for wallet in wallets.iter().filter(|w| w.status != WalletStatus::Deleted) {
+        let context = contexts
+            .entry(wallet.owner_user_id.as_str())
+            .or_insert_with(|| {
+                CategoryContext::from_wallets(storage, &wallets, &wallet.owner_user_id)
+            });
+        match aggregate_wallet(storage, tx_db, wallet, context, &prices, now) {
+            Ok(months) => recomputed += months,
+            Err(e) => warn!(
+                wallet_id = %wallet.wallet_id,
+                error = %e,
+                "Insights aggregator: failed to aggregate wallet"
+            ),
+        }
+    }
+    recomputed
+}
+
+fn aggregate_wallet(
+    storage: &EncryptedStorage,
+    tx_db: &TxDatabase,
+    wallet: &WalletMetadata,
+    context: &CategoryContext,
+    prices: &PriceHistories,
+    now: DateTime<Utc>,
+) -> Result<usize, String> {
+    let history = wallet_history(tx_db, &wallet.public_address).map_err(|e| e.to_string())?;
+    let categories = categorize(&history, context);
+    let user_categories = CategoryRepository::new(storage)
+        .get(&wallet.owner_user_id)
+        .map_err(|e| e.to_string())?;
+    let repo = InsightsRepository::new(storage);
+    let mut stored = repo.get(&wallet.wallet_id).map_err(|e| e.to_string())?;
+
+    let mut confirmed: HashMap<String, u32> = HashMap::new();
+    for (tx, _) in &history {
+        if tx.status == TxStatus::Confirmed {
+            *confirmed.entry(month_key(tx.created_at)).or_default() += 1;
+        }
+    }
+    let mut open: HashSet<String> = HashSet::from([month_key(now)]);
+    if let Some(previous) = now.checked_sub_months(Months::new(1)) {
+        open.insert(month_key(previous));
+    }
+    let mut due: Vec<String> = confirmed
+        .iter()
+        .filter(|(month, count)| match stored.months.get(*month) {
+            Some(existing) => existing.transactions != **count || existing.unpriced > 0,
+            None => true,
+        })
+        .map(|(month, _)| month.clone())
+        .chain(open)
+        .collect();
+    due.sort();
+    due.dedup();
+
+    for month in &due {
+        let insights = aggregate_month(&history, &categories, &user_categories, prices, month, now);
+        stored.months.insert(month.clone(), insights);
+    }
+    stored.updated_at = Some(now);
+    repo.save(&stored).map_err(|e| e.to_string())?;
+    Ok(due.len())
+}
+
+/// One month of a wallet's confirmed activity. Transfers between the
+/// owner's wallets are counted in `transactions` but in no total.
+pub fn aggregate_month(
+    history: &[(StoredTransaction, String)],
+    categories: &HashMap<String, TxCategory>,
+    user_categories: &StoredUserCategories,
+    prices: &PriceHistories,
+    month: &str,
+    now: DateTime<Utc>,
+) -> MonthlyInsights {
+    let mut insights = MonthlyInsights {
+        computed_at: now,
+        ..Default::default()
+    };
+    for (tx, direction) in history {
+        if tx.status != TxStatus::Confirmed || month_key(tx.created_at) != month {
+            continue;
+        }
+        insights.transactions += 1;
+        let hash = tx.tx_hash.to_lowercase();
+        let category = categories
+            .get(&hash)
+            .copied()
+            .unwrap_or(TxCategory::Transfer);
+        if category == TxCategory::Internal {
+            continue;
+        }
+        let Some(value) = value_at_tx_time(prices, tx) else {
+            insights.unpriced += 1;
+            continue;
+        };
+
+        let sent = direction == "sent";
+        let other = if sent { &tx.to } else { &tx.from };
+        let counterparty = insights
+            .counterparties
+            .entry(other.to_lowercase())
+            .or_default();
+        counterparty.transactions += 1;
+        if !sent {
+            counterparty.received_eur += value;
+            insights.received_eur += value;
+            continue;
+        }
+        counterparty.sent_eur += value;
+        insights.spent_eur += value;
+        *insights
+            .by_category
+            .entry(category.as_str().to_string())
+            .or_default() += value;
+        match user_categories.category_of(&hash) {
+            Some(c) => {
+                *insights
+                    .by_user_category
+                    .entry(c.category_id.clone())
+                    .or_default() += value
+            }
+            None => insights.uncategorized_eur += value,
+        }
+    }
+
+    if insights.counterparties.len() > MAX_COUNTERPARTIES {
+        let mut ranked: Vec<(String, CounterpartyTotals)> =
+            std::mem::take(&mut insights.counterparties)
+                .into_iter()
+                .collect();
+        ranked.sort_by(|a, b| volume(&b.1).total_cmp(&volume(&a.1)));
+        ranked.truncate(MAX_COUNTERPARTIES);
+        insights.counterparties = ranked.into_iter().collect();
+    }
+    insights
+}
+
+/// Total value exchanged with a counterparty.
+pub fn volume(totals: &CounterpartyTotals) -> f64 {
+    totals.sent_eur + totals.received_eur
+}
+
+/// Month before a `YYYY-MM` month.
+pub fn previous_month(month: &str) -> Option<String> {
+    let first = chrono::NaiveDate::parse_from_str(&format!("{month}-01"), "%Y-%m-%d").ok()?;
+    let previous = first.checked_sub_months(Months::new(1))?;
+    Some(format!("{:04}-{:02}", previous.year(), previous.month()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::repository::price_history::StoredPriceHistory;
+    use crate::storage::{StoragePaths, TokenType, UserCategory};
+    use chrono::{NaiveDate, TimeZone};
+
+    const ME: &str = "0x1111111111111111111111111111111111111111";
+    const SAVINGS: &str = "0x3333333333333333333333333333333333333333";
+    const SHOP: &str = "0x4444444444444444444444444444444444444444";
+    const EMPLOYER: &str = "0x5555555555555555555555555555555555555555";
+
+    fn tx(hash: &str, from: &str, to: &str, amount: &str, day: u32) -> StoredTransaction {
+        let mut tx = StoredTransaction::new_pending(
+            hash.to_string(),
+            "wallet-1".to_string(),
+            None,
+            from.to_string(),
+            to.to_string(),
+            amount.to_string(),
+            TokenType::Native,
+            "fuji".to_string(),
+            String::new(),
+        );
+        tx.status = TxStatus::Confirmed;
+        tx.created_at = Utc.with_ymd_and_hms(2026, 3, day, 12, 0, 0).unwrap();
+        tx
+    }
+
+    fn prices() -> PriceHistories {
+        let avax = StoredPriceHistory {
+            symbol: "AVAX".to_string(),
+            daily_eur: (1..=31)
+                .map(|day| (NaiveDate::from_ymd_opt(2026, 3, day).unwrap(), 10.0))
+                .collect(),
+        };
+        PriceHistories::from([("AVAX".to_string(), avax)])
+    }
+
+    #[test]
+    fn month_totals_skip_internal_transfers() {
+        let history = vec![
+            (tx("0xa1", ME, SHOP, "2", 3), "sent".to_string()),
+            (tx("0xa2", ME, SHOP, "1", 4), "sent".to_string()),
+            (tx("0xa3", EMPLOYER, ME, "5", 5), "received".to_string()),
+            (tx("0xa4", ME, SAVINGS, "3", 6), "sent".to_string()),
+        ];
+        let context = CategoryContext {
+            own_addresses: HashSet::from([ME.to_string(), SAVINGS.to_string()]),
+            ..Default::default()
+        };
+        let categories = categorize(&history, &context);
+        let mut user_categories = StoredUserCategories::default();
+        user_categories.categories.push(UserCategory {
+            category_id: "groceries".to_string(),
+            name: "Groceries".to_string(),
+            monthly_budget_eur: Some("25".to_string()),
+            created_at: Utc::now(),
+        });
+        user_categories
+            .assignments
+            .insert("0xa1".to_string(), "groceries".to_string());
+
+        let insights = aggregate_month(
+            &history,
+            &categories,
+            &user_categories,
+            &prices(),
+            "2026-03",
+            Utc::now(),
+        );
+        assert_eq!(insights.transactions, 4);
+        assert_eq!(insights.spent_eur, 30.0);
+        assert_eq!(insights.received_eur, 50.0);
+        assert_eq!(insights.by_category["transfer"], 30.0);
+        assert_eq!(insights.by_user_category["groceries"], 20.0);
+        assert_eq!(insights.uncategorized_eur, 10.0);
+        assert_eq!(insights.counterparties.len(), 2);
+        assert_eq!(insights.counterparties[SHOP].transactions, 2);
+        assert!(!insights.counterparties.contains_key(SAVINGS));
+
+        let empty = aggregate_month(
+            &history,
+            &categories,
+            &user_categories,
+            &PriceHistories::new(),
+            "2026-03",
+            Utc::now(),
+        );
+        assert_eq!(empty.unpriced, 3);
+        assert_eq!(empty.spent_eur, 0.0);
+    }
+
+    #[test]
+    fn previous_month_wraps_the_year() {
+        assert_eq!(previous_month("2026-01").as_deref(), Some("2025-12"));
+        assert_eq!(previous_month("2026-07").as_deref(), Some("2026-06"));
+        assert!(previous_month("2026-13").is_none());
+    }
+
+    #[test]
+    fn aggregation_stores_open_months_for_every_wallet() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let mut storage = EncryptedStorage::new(StoragePaths::new(temp.path()));
+        storage.initialize().unwrap();
+        let tx_db = TxDatabase::open(&temp.path().join("tx.redb")).unwrap();
+        let wallet = WalletMetadata {
+            wallet_id: "wallet-1".to_string(),
+            owner_user_id: "user_1".to_string(),
+            public_address: ME.to_string(),
+            created_at: Utc::now(),
+            status: WalletStatus::Active,
+            label: None,
+            email_lookup_key: None,
+            email_sha256: None,
+            account_type: Default::default(),
+            smart_account: None,
+            lock: None,
+            deleted_at: None,
+            purged_at: None,
+            funded_at: None,
+            tenant_id: None,
+        };
+        WalletRepository::new(&storage)
+            .create(&wallet, b"test_key")
+            .unwrap();
+
+        let now = Utc.with_ymd_and_hms(2026, 3, 15, 0, 0, 0).unwrap();
+        assert_eq!(aggregate_all(&storage, &tx_db, now), 2);
+        let stored = InsightsRepository::new(&storage).get("wallet-1").unwrap();
+        assert_eq!(stored.updated_at, Some(now));
+        assert!(stored.months.contains_key("2026-03"));
+        assert!(stored.months.contains_key("2026-02"));
+
+        InsightsRepository::new(&storage)
+            .invalidate_month("wallet-1", "2026-03")
+            .unwrap();
+        let stored = InsightsRepository::new(&storage).get("wallet-1").unwrap();
+        assert!(!stored.months.contains_key("2026-03"));
+    }
+}
@@ -0,0 +1,418 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! # Accounting Journal Poster
+//!
+//! Background task keeping the double-entry
+//! [journal](crate::storage::repository::journal) in step with the records
+//! it is derived from. A transaction is posted as soon as the
+//! [domain event bus](crate::events) reports it confirmed, and a fiat
+//! request once it completes. Every [`JOURNAL_SYNC_INTERVAL_SECS`] the
+//! poster also walks all confirmed transactions, completed fiat requests and
+//! the reserve gas ledger and posts whatever is missing: history from
+//! before the journal existed, events missed while the poster lagged, and
+//! gas recorded after its request completed.
+//!
+//! Addresses are mapped to accounts when an entry is posted: registered
+//! wallet addresses to their wallet, the reserve wallet to `reserve`,
+//! escrow sub-wallets to their escrow, anything else to `external`.
+//!
+//! ## Shutdown
+//!
+//! Uses `tokio_util::sync::CancellationToken` for graceful shutdown, following
+//! the same pattern as the `FiatPoller`.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use alloy::primitives::U256;
+use tokio::sync::broadcast::error::RecvError;
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+
+use crate::blockchain::{address_key, format_amount, NETWORK_FUJI};
+use crate::events::{DomainEvent, EventBus};
+use crate::storage::repository::journal::{journal_units, FIAT_ASSET, JOURNAL_DECIMALS};
+use crate::storage::{
+    EncryptedStorage, EscrowRepository, FiatDirection, FiatRequestRepository, FiatRequestStatus,
+    FiatServiceWalletRepository, GasSpendEntry, JournalAccount, JournalEntry, JournalRepository,
+    JournalSource, ReserveGasLedgerRepository, StoredFiatRequest, StoredTransaction, TokenType,
+    TxDatabase, TxStatus,
+};
+
+/// Interval between full journal syncs.
+pub const JOURNAL_SYNC_INTERVAL_SECS: u64 = 600;
+
+/// Maps on-chain addresses to journal accounts.
+pub struct AccountResolver {
+    wallets: HashMap<String, String>,
+    reserve: Option<String>,
+    escrows: HashMap<String, String>,
+}
+
+impl AccountResolver {
+    /// Resolver over the current address map, reserve wallet and escrows.
+    pub fn load(storage: &EncryptedStorage, tx_db: &TxDatabase) -> Self {
+        let wallets = tx_db
+            .list_address_map()
+            .unwrap_or_else(|e| {
+                warn!(error = %e, "Journal: failed to read the address map");
+                Vec::new()
+            })
+            .into_iter()
+            .map(|(address, wallet_id)| (address_key(&address), wallet_id))
+            .collect();
+        let reserve = FiatServiceWalletRepository::new(storage)
+            .get()
+            .ok()
+            .map(|wallet| address_key(&wallet.public_address));
+        let escrow_repo = EscrowRepository::new(storage);
+        let claims = escrow_repo.list_claims().unwrap_or_default();
+        let payments = escrow_repo.list_payments().unwrap_or_default();
+        let escrows = claims
+            .into_iter()
+            .map(|c| (address_key(&c.escrow_address), c.claim_id))
+            .chain(
+                payments
+                    .into_iter()
+                    .map(|p| (address_key(&p.escrow_address), p.escrow_id)),
+            )
+            .collect();
+        Self {
+            wallets,
+            reserve,
+            escrows,
+        }
+    }
+
+    /// Account holding `address`.
+    pub fn account(&self, address: &str) -> JournalAccount {
+        let key = address_key(address);
+        if let Some(wallet_id) = self.wallets.get(&key) {
+            JournalAccount::Wallet {
+                wallet_id: wallet_id.clone(),
+            }
+        } else if self.reserve.as_deref() == Some(key.as_str()) {
+            JournalAccount::Reserve
+        } else if let Some(escrow_id) = self.escrows.get(&key) {
+            JournalAccount::Escrow {
+                escrow_id: escrow_id.clone(),
+            }
+        } else {
+            JournalAccount::External
+        }
+    }
+}
+
+/// Asset key of a transfer: `{network}:native` or `{network}:{contract}`.
+fn transfer_asset(tx: &StoredTransaction) -> String {
+    match &tx.token {
+        TokenType::Native => format!("{}:native", tx.network),
+        TokenType::Erc20(contract) => format!("{}:{}", tx.network, address_key(contract)),
+    }
+}
+
+/// Entry for a confirmed transfer; `None` for other statuses, zero-value
+/// transfers and transfers between two outside addresses.
+pub fn transaction_entry(
+    resolver: &AccountResolver,
+    tx: &StoredTransaction,
+) -> Option<JournalEntry> {
+    if tx.status != TxStatus::Confirmed {
+        return None;
+    }
+    let from = resolver.account(&tx.from);
+    let to = resolver.account(&tx.to);
+    if from == JournalAccount::External && to == JournalAccount::External {
+        return None;
+    }
+    JournalEntry::transfer(
+        JournalSource::OnChainTx,
+        &tx.tx_hash,
+        &transfer_asset(tx),
+        from,
+        to,
+        &tx.amount,
+        tx.updated_at,
+    )
+}
+
+/// Settlement and fee entries of a completed fiat request.
+///
+/// An on-ramp brings the full amount into the reserve; an off-ramp pays
+/// the amount less the fee out of it. The fee moves from the reserve to
+/// `fees` in both directions.
+pub fn fiat_entries(request: &StoredFiatRequest) -> Vec<JournalEntry> {
+    if request.status != FiatRequestStatus::Completed {
+        return Vec::new();
+    }
+    let occurred_at = request.executed_at.unwrap_or(request.updated_at);
+    let amount = journal_units(&request.amount_eur).unwrap_or(U256::ZERO);
+    let fee = request
+        .fee_eur
+        .as_deref()
+        .and_then(journal_units)
+        .unwrap_or(U256::ZERO)
+        .min(amount);
+    let (from, to, settled) = match request.direction {
+        FiatDirection::OnRamp => (JournalAccount::External, JournalAccount::Reserve, amount),
+        FiatDirection::OffRamp => (
+            JournalAccount::Reserve,
+            JournalAccount::External,
+            amount - fee,
+        ),
+    };
+    let settlement = JournalEntry::transfer(
+        JournalSource::FiatSettlement,
+        &request.request_id,
+        FIAT_ASSET,
+        from,
+        to,
+        &format_amount(settled, JOURNAL_DECIMALS),
+        occurred_at,
+    );
+    let fee = JournalEntry::transfer(
+        JournalSource::Fee,
+        &request.request_id,
+        FIAT_ASSET,
+        JournalAccount::Reserve,
+        JournalAccount::Fees,
+        &format_amount(fee, JOURNAL_DECIMALS),
+        occurred_at,
+    );
+    settlement.into_iter().chain(fee).collect()
+}
+
+/// Entry for gas the reserve wallet paid on Fuji.
+pub fn gas_entry(entry: &GasSpendEntry) -> Option<JournalEntry> {
+    JournalEntry::transfer(
+        JournalSource::NetworkFee,
+        &entry.tx_hash,
+        &format!("{NETWORK_FUJI}:native"),
+        JournalAccount::Reserve,
+        JournalAccount::NetworkFees,
+        &format_amount(U256::from(entry.cost()), JOURNAL_DECIMALS),
+        entry.recorded_at,
+    )
+}
+
+fn post_all(repo: &JournalRepository, entries: impl IntoIterator<Item = JournalEntry>) -> usize {
+    let mut posted = 0;
+    for entry in entries {
+        match repo.post(&entry) {
+            Ok(true) => posted += 1,
+            Ok(false) => {}
+            Err(e) => {
+                warn!(entry_id = %entry.entry_id, error = %e, "Journal: failed to post entry")
+            }
+        }
+    }
+    posted
+}
+
+/// Post every missing entry. Returns the number posted.
+pub fn sync_all(storage: &EncryptedStorage, tx_db: &TxDatabase) -> usize {
+    let repo = JournalRepository::new(storage);
+    let resolver = AccountResolver::load(storage, tx_db);
+    let mut posted = 0;
+
+    match tx_db.list_all_transactions() {
+        Ok(transactions) => {
+            posted += post_all(
+                &repo,
+                transactions
+                    .iter()
+                    .filter_map(|tx| transaction_entry(&resolver, tx)),
+            );
+        }
+        Err(e) => warn!(error = %e, "Journal: failed to list transactions"),
+    }
+    match FiatRequestRepository::new(storage).list_all() {
+        Ok(requests) => posted += post_all(&repo, requests.iter().flat_map(fiat_entries)),
+        Err(e) => warn!(error = %e, "Journal: failed to list fiat requests"),
+    }
+    let gas = ReserveGasLedgerRepository::new(storage);
+    match gas.list_days() {
+        Ok(days) => {
+            for day in days {
+                match gas.get_day(&day) {
+                    Ok(ledger) => {
+                        posted += post_all(&repo, ledger.entries.iter().filter_map(gas_entry))
+                    }
+                    Err(e) => warn!(day = %day, error = %e, "Journal: failed to read gas ledger"),
+                }
+            }
+        }
+        Err(e) => warn!(error = %e, "Journal: failed to list gas ledgers"),
+    }
+    posted
+}
+
+/// Background task posting journal entries.
+pub struct JournalPoster {
+    storage: Arc<EncryptedStorage>,
+    tx_db: Arc<TxDatabase>,
+    events: EventBus,
+}
+
+impl JournalPoster {
+    /// Create a poster over the given storage and transaction database.
+    pub fn new(storage: Arc<EncryptedStorage>, tx_db: Arc<TxDatabase>) -> Self {
+        Self {
+            storage,
+            tx_db,
+            events: EventBus::global(),
+        }
+    }
+
+    /// Run the posting loop until the cancellation token is triggered.
+    pub async fn run(self, shutdown: CancellationToken) {
+        info!(
+            interval_secs = JOURNAL_SYNC_INTERVAL_SECS,
+            "Journal poster starting"
+        );
+        let mut events = self.events.subscribe();
+        let mut interval = tokio::time::interval(Duration::from_secs(JOURNAL_SYNC_INTERVAL_SECS));
+
+        loop {
+            tokio::select! {
+                _ = shutdown.cancelled() => {
+                    info!("Journal poster shutting down");
+                    return;
+                }
+                event = events.recv() => match event {
+                    Ok(event) => self.post_event(&event),
+                    Err(RecvError::Lagged(skipped)) => {
+                        warn!(skipped, "Journal poster fell behind; the next sync posts the missed events");
+                    }
+                    Err(RecvError::Closed) => return,
+                },
+                _ = interval.tick() => {
+                    let storage = self.storage.clone();
+                    let tx_db = self.tx_db.clone();
+                    match tokio::task::spawn_blocking(move || sync_all(&storage, &tx_db)).await {
+                        Ok(0) => {}
+                        Ok(posted) => info!(posted, "Journal sync finished"),
+                        Err(e) => warn!(error = %e, "Journal sync panicked"),
+                    }
+                    crate::worker_health::record_heartbeat(crate::worker_health::Worker::Journal);
+                }
+            }
+        }
+    }
+
+    fn post_event(&self, event: &DomainEvent) {
+        let repo = JournalRepository::new(&self.storage);
+        match event {
+            DomainEvent::TxStatusChanged(change) if change.status == TxStatus::Confirmed => {
+                if repo.exists(&JournalEntry::entry_id(
+                    JournalSource::OnChainTx,
+                    &change.tx_hash,
+                )) {
+                    return;
+                }
+                let Ok(Some(tx)) = self.tx_db.get_transaction(&change.tx_hash) else {
+                    return;
+                };
+                let resolver = AccountResolver::load(&self.storage, &self.tx_db);
+                post_all(&repo, transaction_entry(&resolver, &tx));
+            }
+            DomainEvent::FiatStatusChanged(change)
+                if change.status == FiatRequestStatus::Completed =>
+            {
+                if let Ok(request) =
+                    FiatRequestRepository::new(&self.storage).get(&change.request_id)
+                {
+                    post_all(&repo, fiat_entries(&request));
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn resolver() -> AccountResolver {
+        AccountResolver {
+            wallets: HashMap::from([("0xaaaa".to_string(), "w-1".to_string())]),
+            reserve: Some("0xbbbb".to_string()),
+            escrows: HashMap::from([("0xcccc".to_string(), "claim-1".to_string())]),
+        }
+    }
+
+    fn confirmed(from: &str, to: &str, amount: &str) -> StoredTransaction {
+        let mut tx = StoredTransaction::new_pending(
+            "0xHASH".to_string(),
+            "w-1".to_string(),
+            None,
+            from.to_string(),
+            to.to_string(),
+            amount.to_string(),
+            TokenType::Erc20("0xREUR".to_string()),
+            "fuji".to_string(),
+            String::new(),
+        );
+        tx.status = TxStatus::Confirmed;
+        tx
+    }
+
+    #[test]
+    fn transfers_are_posted_between_resolved_accounts() {
+        let resolver = resolver();
+        let entry = transaction_entry(&resolver, &confirmed("0xBBBB", "0xAAAA", "25")).unwrap();
+        assert_eq!(entry.entry_id, "tx-0xhash");
+        assert_eq!(entry.asset, "fuji:0xreur");
+        assert_eq!(entry.postings[0].account, JournalAccount::Reserve);
+        assert_eq!(
+            entry.postings[1].account,
+            JournalAccount::Wallet {
+                wallet_id: "w-1".to_string()
+            }
+        );
+
+        let entry = transaction_entry(&resolver, &confirmed("0xaaaa", "0xcccc", "1")).unwrap();
+        assert_eq!(
+            entry.postings[1].account,
+            JournalAccount::Escrow {
+                escrow_id: "claim-1".to_string()
+            }
+        );
+
+        assert!(transaction_entry(&resolver, &confirmed("0xdddd", "0xeeee", "1")).is_none());
+        let mut pending = confirmed("0xaaaa", "0xdddd", "1");
+        pending.status = TxStatus::Pending;
+        assert!(transaction_entry(&resolver, &pending).is_none());
+    }
+
+    #[test]
+    fn off_ramps_pay_out_the_amount_less_the_fee() {
+        let mut request = StoredFiatRequest::new_queued(
+            "req-1".to_string(),
+            "w-1".to_string(),
+            "user-1".to_string(),
+            FiatDirection::OffRamp,
+            "100.00".to_string(),
+            "truelayer_sandbox".to_string(),
+            None,
+        );
+        request.fee_eur = Some("1.50".to_string());
+        assert!(fiat_entries(&request).is_empty());
+
+        request.status = FiatRequestStatus::Completed;
+        request.executed_at = Some(Utc::now());
+        let entries = fiat_entries(&request);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].entry_id, "fiat-req-1");
+        assert_eq!(entries[0].postings[0].account, JournalAccount::Reserve);
+        assert_eq!(entries[0].postings[0].amount, "98.5");
+        assert_eq!(entries[1].entry_id, "fee-req-1");
+        assert_eq!(entries[1].postings[1].account, JournalAccount::Fees);
+        assert_eq!(entries[1].postings[1].amount, "1.5");
+    }
+}
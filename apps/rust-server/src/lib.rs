@@ -28,12 +28,23 @@
 //! - [`api`] - HTTP API handlers built on Axum with OpenAPI documentation
 //! - [`auth`] - Clerk JWT authentication with JWKS verification
 //! - [`blockchain`] - Avalanche C-Chain client for balance queries
+//! - [`canary`] - Opt-in canary transfers monitoring the send pipeline
+//! - [`canonical_json`] - Deterministic JSON for signed payloads
+//! - [`claim_expiry`] - Background return of expired claimable transfers
 //! - [`config`] - Runtime configuration constants
 //! - [`error`] - API error types with HTTP status mapping
+//! - [`events`] - In-process bus of typed domain events
+//! - [`insights`] - Background aggregation of monthly wallet insights
 //! - [`models`] - Request/response data structures
+//! - [`orphan_sweeper`] - Background removal of orphaned storage artifacts
+//! - [`price_recorder`] - Background recording of daily token prices
+//! - [`secret_signer`] - Enclave-file or external KMS signing with non-wallet keys
 //! - [`state`] - Application state shared across handlers
 //! - [`storage`] - Gramine encrypted filesystem repositories
 //! - [`tls`] - RA-TLS certificate loading utilities
+//! - [`webhook_dispatcher`] - Background delivery of users' webhook subscriptions
+//! - [`webhooks`] - Outbound webhook signing and verification
+//! - [`worker_health`] - Background worker heartbeats
 //!
 //! ## Security Model
 //!
@@ -59,13 +70,27 @@
 pub mod api;
 pub mod auth;
 pub mod blockchain;
+pub mod canary;
+pub mod canonical_json;
+pub mod claim_expiry;
 pub mod config;
 pub mod discovery;
 pub mod error;
+pub mod events;
 pub mod fiat_poller;
+pub mod i18n;
 pub mod indexer;
+pub mod insights;
+pub mod journal;
 pub mod models;
+pub mod orphan_sweeper;
+pub mod price_recorder;
 pub mod providers;
+pub mod secret_signer;
 pub mod state;
 pub mod storage;
 pub mod tls;
+pub mod wallet_pool;
+pub mod webhook_dispatcher;
+pub mod webhooks;
+pub mod worker_health;
@@ -7,22 +7,52 @@
 // those false positives so `cargo test` compiles warning-free.
 #[cfg_attr(test, allow(unused_imports, dead_code))]
 mod api;
+#[cfg_attr(test, allow(dead_code))]
 mod auth;
+#[cfg_attr(test, allow(dead_code))]
 mod blockchain;
+#[cfg_attr(test, allow(dead_code))]
+mod canary;
+mod canonical_json;
+#[cfg_attr(test, allow(dead_code))]
+mod claim_expiry;
+#[cfg_attr(test, allow(dead_code))]
 mod config;
 mod discovery;
 mod error;
 #[cfg_attr(test, allow(dead_code))]
+mod events;
+#[cfg_attr(test, allow(dead_code))]
 mod fiat_poller;
+mod i18n;
 #[cfg_attr(test, allow(dead_code))]
 mod indexer;
+#[cfg_attr(test, allow(dead_code))]
+mod insights;
+#[cfg_attr(test, allow(dead_code))]
+mod journal;
 mod models;
+#[cfg_attr(test, allow(dead_code))]
+mod orphan_sweeper;
+#[cfg_attr(test, allow(dead_code))]
+mod price_recorder;
+#[cfg_attr(test, allow(dead_code))]
 mod providers;
 #[cfg_attr(test, allow(dead_code))]
+mod secret_signer;
+#[cfg_attr(test, allow(dead_code))]
 mod state;
 #[cfg_attr(test, allow(unused_imports))]
 mod storage;
 mod tls;
+#[cfg_attr(test, allow(dead_code))]
+mod wallet_pool;
+#[cfg_attr(test, allow(dead_code))]
+mod webhook_dispatcher;
+// Verification helpers are for receivers; the binary only signs.
+#[allow(dead_code)]
+mod webhooks;
+mod worker_health;
 
 #[cfg(not(test))]
 use axum_server::Handle;
@@ -30,7 +60,7 @@ use axum_server::Handle;
 use std::{env, net::SocketAddr, sync::Arc, time::Duration};
 
 #[cfg(not(test))]
-use api::router;
+use api::{docs_router, router, DocsListener};
 #[cfg(not(test))]
 use auth::JwksManager;
 #[cfg(not(test))]
@@ -96,8 +126,66 @@ async fn main() {
         .expect("Encrypted storage health check failed");
     info!("Encrypted storage initialized and verified");
 
-    // Bootstrap enclave-managed fiat reserve service wallet (idempotent).
+    // Refuses to start on data written by a newer release.
+    let outcome = storage::migrations::migrate_storage(&encrypted_storage)
+        .unwrap_or_else(|error| panic!("Storage schema migration failed: {error}"));
+    info!(
+        from = outcome.from,
+        to = outcome.to,
+        "Storage schema is current"
+    );
+
+    // Arm the one-time admin bootstrap until an initial admin is designated.
+    let sealed_bootstrap_token = env::var(config::ADMIN_BOOTSTRAP_TOKEN_ENV).ok();
+    match storage::AdminBootstrapRepository::new(&encrypted_storage)
+        .arm(sealed_bootstrap_token.as_deref(), chrono::Utc::now())
     {
+        Ok(storage::BootstrapArming::Completed { admin_user_id }) => {
+            info!(admin_user_id = %admin_user_id, "Admin bootstrap already completed")
+        }
+        Ok(storage::BootstrapArming::Revoked) => info!("Admin bootstrap revoked"),
+        Ok(storage::BootstrapArming::Kept { source, expires_at }) => warn!(
+            ?source,
+            %expires_at,
+            "No admin designated yet; the setup token armed earlier is still valid"
+        ),
+        Ok(storage::BootstrapArming::Sealed { expires_at }) => warn!(
+            %expires_at,
+            "No admin designated yet; sealed admin bootstrap token armed"
+        ),
+        Ok(storage::BootstrapArming::SealedExpired) => warn!(
+            "No admin designated yet; the sealed admin bootstrap token has expired, seal a new one"
+        ),
+        Ok(storage::BootstrapArming::Generated { token, expires_at }) => {
+            // Shown once on the console, never through the log pipeline.
+            warn!(%expires_at, "No admin designated yet; setup token printed to stderr");
+            eprintln!(
+                "One-time admin setup token for POST /v1/admin/bootstrap \
+                 (valid until {expires_at}): {token}"
+            );
+        }
+        Err(e) => warn!(error = %e, "Failed to arm admin bootstrap"),
+    }
+
+    // Bootstrap enclave-managed fiat reserve service wallet (idempotent).
+    // In ceremony mode the key is assembled from operator shares via the admin
+    // API instead, so an existing wallet is only reported.
+    let reserve_key_source =
+        env::var(config::FIAT_RESERVE_KEY_SOURCE_ENV).unwrap_or_else(|_| "enclave".to_string());
+    if reserve_key_source.eq_ignore_ascii_case("ceremony") {
+        let repo = storage::FiatServiceWalletRepository::new(&encrypted_storage);
+        match repo.get() {
+            Ok(metadata) => info!(
+                public_address = %metadata.public_address,
+                key_source = ?metadata.key_source,
+                "Fiat reserve service wallet ready"
+            ),
+            Err(_) => warn!(
+                "Fiat reserve wallet not initialized; awaiting admin key ceremony \
+                 (fiat settlement is unavailable until activation)"
+            ),
+        }
+    } else {
         let repo = storage::FiatServiceWalletRepository::new(&encrypted_storage);
         match repo.bootstrap() {
             Ok(metadata) => {
@@ -113,6 +201,12 @@ async fn main() {
         }
     }
 
+    // Jobs that were mid-send when the previous process stopped are marked
+    // interrupted so their settlements are not blindly re-sent.
+    if let Err(error) = api::reserve_queue::recover_unfinished(&encrypted_storage) {
+        warn!(error = %error.message, "Failed to recover reserve send queue");
+    }
+
     // ========== Initialize Transaction Database (redb) ==========
     info!("Opening transaction database...");
     let tx_db_path = encrypted_storage.paths().root().join("tx.redb");
@@ -127,52 +221,20 @@ async fn main() {
     );
     info!(path = %tx_db_path.display(), "Transaction database opened");
 
+    let outcome = storage::migrations::migrate_tx_database(&encrypted_storage, &tx_db)
+        .unwrap_or_else(|error| panic!("Transaction database migration failed: {error}"));
+    info!(
+        from = outcome.from,
+        to = outcome.to,
+        "Transaction database schema is current"
+    );
+
     // ========== Register wallet addresses in tx_db ==========
     // Ensures the address→wallet_id, user→wallet, and email_lookup maps
     // are always consistent, even after redb recreation.
     // This is idempotent and very cheap.
-    {
-        use storage::repository::WalletRepository;
-        let repo = WalletRepository::new(&encrypted_storage);
-        let mut registered = 0u32;
-        if let Ok(wallets) = repo.list_all_wallets() {
-            for w in &wallets {
-                // address → wallet_id
-                if let Err(e) = tx_db.register_address(&w.public_address, &w.wallet_id) {
-                    warn!(wallet_id = %w.wallet_id, error = %e, "Failed to register wallet address");
-                } else {
-                    registered += 1;
-                }
-                // user_id → wallet_id (only for non-deleted wallets)
-                if w.status != storage::WalletStatus::Deleted {
-                    if let Err(e) = tx_db.register_user_wallet(&w.owner_user_id, &w.wallet_id) {
-                        warn!(wallet_id = %w.wallet_id, error = %e, "Failed to register user→wallet mapping");
-                    }
-                    // email_lookup_key → { wallet_id, public_address }
-                    if let Some(ref lookup_key) = w.email_lookup_key {
-                        if let Err(e) =
-                            tx_db.register_email_lookup(lookup_key, &w.wallet_id, &w.public_address)
-                        {
-                            warn!(wallet_id = %w.wallet_id, error = %e, "Failed to register email lookup");
-                        }
-                    }
-                }
-            }
-        }
-        // Also register the fiat service wallet so the indexer can
-        // detect incoming transfers to it (off-ramp deposit detection).
-        {
-            let svc_repo = storage::FiatServiceWalletRepository::new(&encrypted_storage);
-            if let Ok(meta) = svc_repo.get() {
-                if let Err(e) = tx_db.register_address(&meta.public_address, &meta.wallet_id) {
-                    warn!(error = %e, "Failed to register service wallet address");
-                } else {
-                    registered += 1;
-                }
-            }
-        }
-        info!(count = registered, "Wallet addresses registered in tx_db");
-    }
+    let registered = indexer::rebuild::register_known_addresses(&encrypted_storage, &tx_db);
+    info!(count = registered, "Wallet addresses registered in tx_db");
 
     // ========== Cleanup Expired Payment Links ==========
     {
@@ -264,6 +326,27 @@ async fn main() {
     // Create discovery client
     let discovery_client = Arc::new(discovery::DiscoveryClient::new(peer_registry.clone()));
 
+    // ========== Load EVM Network Registry ==========
+    let network_registry = blockchain::network::NetworkRegistry::from_env()
+        .unwrap_or_else(|e| panic!("Invalid EVM network configuration: {e}"));
+    let network_ids: Vec<_> = network_registry.all().iter().map(|n| n.config.id).collect();
+    info!(networks = ?network_ids, "EVM network registry loaded");
+    if blockchain::network::install_networks(network_registry).is_err() {
+        warn!("EVM network registry was already initialized");
+    }
+
+    // ========== Load Token Registry ==========
+    let token_registry = blockchain::tokens::TokenRegistry::from_env(blockchain::networks())
+        .unwrap_or_else(|e| panic!("Invalid token registry configuration: {e}"));
+    if blockchain::tokens::install_tokens(token_registry).is_err() {
+        warn!("Token registry was already initialized");
+    }
+    let nft_collections = blockchain::nft::NftCollections::from_env(blockchain::networks())
+        .unwrap_or_else(|e| panic!("Invalid NFT collection configuration: {e}"));
+    if blockchain::nft::install_nft_collections(nft_collections).is_err() {
+        warn!("NFT collections were already initialized");
+    }
+
     // ========== Build Application State ==========
     // Initialize shared Avalanche C-Chain client (connection pool reuse)
     let avax_client = match blockchain::AvaxClient::fuji().await {
@@ -288,6 +371,34 @@ async fn main() {
     .with_tx_db(tx_db.clone())
     .with_email_hmac_key(email_hmac_key);
 
+    // ========== Load Admin Request Signing Keys ==========
+    let admin_signing_keys = auth::request_signing::AdminSigningKeys::from_env();
+    if admin_signing_keys.is_empty() {
+        warn!("ADMIN_SIGNING_KEYS not set — signed admin routes accept admin JWTs alone");
+    } else {
+        info!(
+            operators = admin_signing_keys.len(),
+            "Admin request signing enforced"
+        );
+    }
+    state = state.with_admin_signing_keys(admin_signing_keys);
+
+    let replay_guard = auth::replay::ReplayGuard::from_env();
+    if replay_guard.is_required() {
+        info!("Request nonces required on authenticated writes");
+    }
+    state = state.with_replay_guard(replay_guard);
+    state = state.with_rate_limiter(api::rate_limit::RateLimiter::from_env());
+
+    let trusted_proxies = auth::client_ip::TrustedProxies::from_env();
+    if !trusted_proxies.is_empty() {
+        info!(
+            proxies = trusted_proxies.len(),
+            "Client addresses taken from X-Forwarded-For behind trusted proxies"
+        );
+    }
+    state = state.with_trusted_proxies(trusted_proxies);
+
     if let Some(client) = avax_client {
         state = state.with_avax_client(client);
     }
@@ -296,6 +407,38 @@ async fn main() {
         state = state.with_clerk_client(clerk);
     }
 
+    // ========== Secret Signer ==========
+    // Webhook, claim-link and return-state keys stay in encrypted storage
+    // unless an external KMS is configured.
+    let secret_signer = secret_signer::SecretSigner::from_env(state.storage().clone())
+        .unwrap_or_else(|error| panic!("Invalid secret signer configuration: {error}"));
+    info!(backend = secret_signer.backend(), "Secret signer ready");
+    state = state.with_secret_signer(secret_signer);
+
+    match webhooks::bootstrap_signing_key(state.storage(), &state.secret_signer).await {
+        Ok(keyring) => info!(
+            key_id = %keyring.active_key_id,
+            "Webhook signing key ready"
+        ),
+        Err(error) => warn!(error = %error, "Failed to bootstrap webhook signing key"),
+    }
+
+    // Tokens listed by admins at runtime
+    match storage::TokenRepository::new(state.storage()).list_all() {
+        Ok(listings) => {
+            for listing in &listings {
+                if let Err(e) = blockchain::tokens().upsert(listing.to_listed()) {
+                    warn!(error = %e, "Skipping stored token listing");
+                }
+            }
+            info!(
+                tokens = blockchain::tokens().all().len(),
+                "Token registry loaded"
+            );
+        }
+        Err(e) => warn!(error = %e, "Failed to load stored token listings"),
+    }
+
     // Register VOPRF tokens for all existing wallets
     {
         use storage::repository::WalletRepository;
@@ -363,32 +506,46 @@ async fn main() {
     // Create LRU cache
     let tx_cache = Arc::new(storage::TxCache::new(1000, Duration::from_secs(300)));
 
-    // Wire tx_db and tx_cache into state
+    // Wire tx_db and tx_cache into state; the cache and the historical
+    // balance cache are invalidated through the cache bus.
     let state = state.with_tx_cache(tx_cache.clone());
+    state
+        .cache_bus
+        .subscribe(Arc::new(blockchain::history::HistoricalBalances));
 
-    // ========== Spawn Event Indexer ==========
+    // ========== Spawn Event Indexers ==========
+    // One per registered network: ERC-20 transfers of its token contracts
+    // and of the tokens listed as indexed on it, and native transfers.
     let shutdown = CancellationToken::new();
-    let token_contracts = indexer::fuji_token_contracts();
-    if !token_contracts.is_empty() {
+    for network in blockchain::networks().all() {
+        if network.index_tokens.is_empty()
+            && blockchain::tokens()
+                .indexed_contracts(network.config.id)
+                .is_empty()
+        {
+            info!(network = %network.config.id, "No token contracts listed yet — indexing native transfers only");
+        }
         let event_indexer = indexer::EventIndexer::new(
             tx_db.clone(),
-            tx_cache.clone(),
-            blockchain::avax_fuji(),
-            token_contracts,
-        );
+            state.cache_bus.clone(),
+            network.config.clone(),
+            network.index_tokens.clone(),
+        )
+        .with_listed_tokens()
+        .with_storage(state.storage().clone());
         let shutdown_clone = shutdown.clone();
         tokio::spawn(async move {
             event_indexer.run(shutdown_clone).await;
         });
-        info!("ERC-20 event indexer spawned");
-    } else {
-        info!("No token contracts configured — event indexer not started");
+        info!(network = %network.config.id, "Event indexer spawned");
     }
 
     // ========== Spawn Fiat Request Poller ==========
     {
         let fiat_poller =
-            fiat_poller::FiatPoller::new(state.storage().clone(), tx_db.clone(), tx_cache.clone());
+            fiat_poller::FiatPoller::new(state.storage().clone(), tx_db.clone(), tx_cache.clone())
+                .with_clerk_client(state.clerk_client.clone())
+                .with_nonce_manager(state.nonce_manager.clone());
         let shutdown_clone = shutdown.clone();
         tokio::spawn(async move {
             fiat_poller.run(shutdown_clone).await;
@@ -396,8 +553,124 @@ async fn main() {
         info!("Fiat request poller spawned");
     }
 
+    // ========== Spawn Daily Price Recorder ==========
+    {
+        let price_recorder = price_recorder::PriceRecorder::new(state.storage().clone());
+        let shutdown_clone = shutdown.clone();
+        tokio::spawn(async move {
+            price_recorder.run(shutdown_clone).await;
+        });
+        info!("Daily price recorder spawned");
+    }
+
+    // ========== Spawn Claim Expiry Worker ==========
+    {
+        let claim_expiry = claim_expiry::ClaimExpiryWorker::new(
+            state.storage().clone(),
+            tx_db.clone(),
+            tx_cache.clone(),
+        )
+        .with_nonce_manager(state.nonce_manager.clone());
+        let shutdown_clone = shutdown.clone();
+        tokio::spawn(async move {
+            claim_expiry.run(shutdown_clone).await;
+        });
+        info!("Claim expiry worker spawned");
+    }
+
+    // ========== Spawn Insights Aggregator ==========
+    {
+        let insights_aggregator =
+            insights::InsightsAggregator::new(state.storage().clone(), tx_db.clone());
+        let shutdown_clone = shutdown.clone();
+        tokio::spawn(async move {
+            insights_aggregator.run(shutdown_clone).await;
+        });
+        info!("Insights aggregator spawned");
+    }
+
+    // ========== Spawn Accounting Journal Poster ==========
+    {
+        let journal_poster = journal::JournalPoster::new(state.storage().clone(), tx_db.clone());
+        let shutdown_clone = shutdown.clone();
+        tokio::spawn(async move {
+            journal_poster.run(shutdown_clone).await;
+        });
+        info!("Journal poster spawned");
+    }
+
+    // ========== Spawn Orphan Sweeper ==========
+    {
+        let orphan_sweeper =
+            orphan_sweeper::OrphanSweeper::new(state.storage().clone(), tx_db.clone());
+        let shutdown_clone = shutdown.clone();
+        tokio::spawn(async move {
+            orphan_sweeper.run(shutdown_clone).await;
+        });
+        info!("Orphan sweeper spawned");
+    }
+
+    // ========== Spawn JWKS Refresh ==========
+    if let Some(jwks) = state.auth_config().jwks.clone() {
+        let interval = auth::jwks::refresh_interval_from_env();
+        let shutdown_clone = shutdown.clone();
+        tokio::spawn(async move {
+            jwks.run_refresh(interval, shutdown_clone).await;
+        });
+        info!(
+            interval_secs = interval.as_secs(),
+            "JWKS refresh task spawned"
+        );
+    }
+
+    // ========== Spawn Wallet Pool Worker (opt-in) ==========
+    if let Some(size) = wallet_pool::pool_size_from_env() {
+        let wallet_pool = wallet_pool::WalletPoolWorker::new(state.storage().clone(), size);
+        let shutdown_clone = shutdown.clone();
+        tokio::spawn(async move {
+            wallet_pool.run(shutdown_clone).await;
+        });
+        info!(size, "Wallet pool worker spawned");
+    }
+
+    // ========== Spawn Canary Worker (opt-in) ==========
+    if let Some(config) = canary::CanaryConfig::from_env() {
+        let canary = canary::CanaryWorker::new(
+            state.storage().clone(),
+            tx_db.clone(),
+            tx_cache.clone(),
+            config,
+        )
+        .with_nonce_manager(state.nonce_manager.clone());
+        let shutdown_clone = shutdown.clone();
+        tokio::spawn(async move {
+            canary.run(shutdown_clone).await;
+        });
+        info!("Canary worker spawned");
+    } else {
+        info!("CANARY_WALLET_IDS not set — canary worker not started");
+    }
+
+    // ========== Spawn Webhook Dispatcher ==========
+    {
+        let webhook_dispatcher = webhook_dispatcher::WebhookDispatcher::new(
+            state.storage().clone(),
+            tx_db.clone(),
+            state.secret_signer.clone(),
+        );
+        let shutdown_clone = shutdown.clone();
+        tokio::spawn(async move {
+            webhook_dispatcher.run(shutdown_clone).await;
+        });
+        info!("Webhook dispatcher spawned");
+    }
+
+    // Swagger UI placement: API listener, own port, or off
+    let docs_listener =
+        DocsListener::from_env().unwrap_or_else(|e| panic!("Invalid docs configuration: {e}"));
+
     // Build router with tracing middleware for request IDs
-    let app = router(state)
+    let app = router(state, docs_listener)
         .layer(PropagateRequestIdLayer::x_request_id())
         .layer(SetRequestIdLayer::x_request_id(MakeRequestUuid))
         .layer(
@@ -439,9 +712,17 @@ async fn main() {
         .parse()
         .expect("Failed to parse bind address");
 
+    let docs_addr = match docs_listener {
+        DocsListener::Separate(docs_port) => {
+            assert_ne!(docs_port, port, "DOCS_PORT must differ from PORT");
+            Some(SocketAddr::new(addr.ip(), docs_port))
+        }
+        DocsListener::Api | DocsListener::Disabled => None,
+    };
+
     info!(
         address = %addr,
-        docs = "/docs",
+        docs = ?docs_listener,
         "Relational Wallet server starting"
     );
     info!("Running with DCAP RA-TLS attestation");
@@ -452,11 +733,13 @@ async fn main() {
 
     // ========== Graceful Shutdown ==========
     // Install a Ctrl+C (SIGINT) handler that:
-    //   1. Cancels background tasks (indexer, fiat poller) via the CancellationToken
+    //   1. Cancels background tasks (indexer, fiat poller, price recorder) via the CancellationToken
     //   2. Gracefully drains in-flight HTTP connections
     //   3. Lets the Database drop normally so redb can flush & close cleanly
     let handle = Handle::new();
     let server_handle = handle.clone();
+    let docs_handle = Handle::new();
+    let docs_server_handle = docs_handle.clone();
     let shutdown_token = shutdown.clone();
     tokio::spawn(async move {
         // Wait for Ctrl+C (SIGINT)
@@ -472,12 +755,28 @@ async fn main() {
         // 2. Tell axum-server to stop accepting new connections and drain
         //    existing ones within 5 seconds
         server_handle.graceful_shutdown(Some(Duration::from_secs(5)));
+        docs_server_handle.graceful_shutdown(Some(Duration::from_secs(5)));
     });
 
+    // Docs listener: same RA-TLS certificate, no API routes
+    if let Some(docs_addr) = docs_addr {
+        let docs_tls = tls_config.clone();
+        tokio::spawn(async move {
+            info!(address = %docs_addr, "Docs listener starting");
+            if let Err(e) = axum_server::bind_rustls(docs_addr, docs_tls)
+                .handle(docs_handle)
+                .serve(docs_router().into_make_service())
+                .await
+            {
+                tracing::error!(error = %e, "Docs listener failed");
+            }
+        });
+    }
+
     // Start HTTPS server (TLS is mandatory - no HTTP fallback)
     axum_server::bind_rustls(addr, tls_config)
         .handle(handle)
-        .serve(app.into_make_service())
+        .serve(app.into_make_service_with_connect_info::<SocketAddr>())
         .await
         .expect("HTTPS server failed");
 
@@ -563,7 +862,7 @@ async fn initialize_auth_config() -> AuthConfig {
                         warn!(
                             error = %e,
                             "JWKS fetch failed after {max_retries} attempts — \
-                             JWT verification will fail until next background refresh"
+                             JWT verification will fail until the background refresh succeeds"
                         );
                     }
                 }
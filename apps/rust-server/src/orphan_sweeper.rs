@@ -0,0 +1,858 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! # Orphan Sweeper
+//!
+//! Background task that keeps storage consistent. Every
+//! [`SWEEP_INTERVAL_SECS`] it looks for artifacts nothing refers to any
+//! more:
+//!
+//! - temp files left behind by interrupted atomic writes, once they are
+//!   older than [`TEMP_FILE_MIN_AGE_SECS`];
+//! - transaction records none of whose wallets still exist. A wallet counts
+//!   as gone once it was soft-deleted more than `DELETED_WALLET_RETENTION_DAYS`
+//!   (default 90) ago, or when its files are missing. Records mirrored to a
+//!   counterparty are kept while either side still exists;
+//! - bookmarks whose wallet is gone.
+//!
+//! Wallets deleted past retention also have their key material destroyed
+//! (logged as `wallet_purged`); until then an admin can restore them. A
+//! wallet that still holds native coins, listed tokens or NFTs on any
+//! registered network, or has open escrows or claims, keeps its key and is
+//! recorded as a held wallet for an admin instead. Balances are read before
+//! each sweep; a wallet whose balances cannot be read is held too.
+//!
+//! Each finding is recorded with an [`OrphanRepository`] record that admins
+//! can list at `GET /v1/admin/storage/orphans`. An artifact is removed by
+//! the first sweep that still finds it orphaned after `ORPHAN_CONFIRM_HOURS`
+//! (default 72); if it stops being orphaned in the meantime, its record is
+//! dropped instead.
+//!
+//! ## Shutdown
+//!
+//! Uses `tokio_util::sync::CancellationToken` for graceful shutdown, following
+//! the same pattern as the `FiatPoller`.
+
+use std::collections::{HashMap, HashSet};
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration as StdDuration, SystemTime};
+
+use chrono::{DateTime, Duration, Utc};
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+
+use crate::blockchain::{networks, tokens, AvaxClient};
+use crate::storage::repository::orphans::orphan_id;
+use crate::storage::{
+    AuditEvent, AuditEventType, AuditRepository, BookmarkRepository, ClaimStatus, EncryptedStorage,
+    EscrowRepository, OrphanKind, OrphanRepository, StorageError, StorageResult, StoredOrphan,
+    TxDatabase, WalletMetadata, WalletRepository, WalletStatus,
+};
+
+/// Interval between sweeps.
+pub const SWEEP_INTERVAL_SECS: u64 = 3600;
+
+/// Temp files younger than this may belong to a write in progress.
+pub const TEMP_FILE_MIN_AGE_SECS: u64 = 3600;
+
+const DEFAULT_RETENTION_DAYS: i64 = 90;
+const DEFAULT_CONFIRM_HOURS: i64 = 72;
+
+/// How long artifacts are kept.
+#[derive(Debug, Clone, Copy)]
+pub struct SweepConfig {
+    /// How long a soft-deleted wallet's records are kept.
+    pub retention: Duration,
+    /// How long an artifact must stay orphaned before it is removed.
+    pub confirm_window: Duration,
+}
+
+impl SweepConfig {
+    /// Settings from `DELETED_WALLET_RETENTION_DAYS` and
+    /// `ORPHAN_CONFIRM_HOURS`.
+    pub fn from_env() -> Self {
+        let read = |name: &str, default: i64| {
+            env::var(name)
+                .ok()
+                .and_then(|v| v.trim().parse::<i64>().ok())
+                .filter(|v| *v >= 0)
+                .unwrap_or(default)
+        };
+        Self {
+            retention: Duration::days(read(
+                "DELETED_WALLET_RETENTION_DAYS",
+                DEFAULT_RETENTION_DAYS,
+            )),
+            confirm_window: Duration::hours(read("ORPHAN_CONFIRM_HOURS", DEFAULT_CONFIRM_HOURS)),
+        }
+    }
+}
+
+/// Why wallets due for purging keep their keys, by wallet ID.
+pub type HeldWallets = HashMap<String, String>;
+
+/// An artifact found orphaned by one sweep.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Finding {
+    pub kind: OrphanKind,
+    pub target: String,
+    pub reason: String,
+}
+
+/// Outcome of one sweep.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SweepSummary {
+    /// Orphans seen for the first time.
+    pub found: usize,
+    /// Orphans removed after their confirmation window.
+    pub removed: usize,
+    /// Earlier orphans that are referenced again or already gone.
+    pub resolved: usize,
+    /// Deleted wallets whose keys were destroyed.
+    pub purged: usize,
+}
+
+/// Whether wallets are gone, memoized for one sweep.
+struct WalletCheck<'a> {
+    wallets: WalletRepository<'a>,
+    cutoff: DateTime<Utc>,
+    held: &'a HeldWallets,
+    seen: HashMap<String, Option<String>>,
+}
+
+impl<'a> WalletCheck<'a> {
+    fn new(storage: &'a EncryptedStorage, cutoff: DateTime<Utc>, held: &'a HeldWallets) -> Self {
+        Self {
+            wallets: WalletRepository::new(storage),
+            cutoff,
+            held,
+            seen: HashMap::new(),
+        }
+    }
+
+    /// Why `wallet_id` is gone, or `None` while it is still kept. Only
+    /// custodial wallet IDs (UUIDs) are checked; other IDs, such as the
+    /// tracking IDs of watched addresses, and held wallets are never gone.
+    fn gone(&mut self, wallet_id: &str) -> Option<String> {
+        if uuid::Uuid::parse_str(wallet_id).is_err() || self.held.contains_key(wallet_id) {
+            return None;
+        }
+        if let Some(reason) = self.seen.get(wallet_id) {
+            return reason.clone();
+        }
+        let reason = match self.wallets.get(wallet_id) {
+            Err(StorageError::NotFound(_)) => Some(format!("wallet {wallet_id} no longer exists")),
+            Ok(meta)
+                if meta.status == WalletStatus::Deleted
+                    && meta.deleted_at.is_some_and(|at| at <= self.cutoff) =>
+            {
+                Some(format!("wallet {wallet_id} deleted past retention"))
+            }
+            _ => None,
+        };
+        self.seen.insert(wallet_id.to_string(), reason.clone());
+        reason
+    }
+}
+
+/// Temp files under `dir` last modified before `cutoff`, relative to `root`.
+fn stale_temp_files(root: &Path, dir: &Path, cutoff: SystemTime, found: &mut Vec<Finding>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            stale_temp_files(root, &path, cutoff, found);
+            continue;
+        }
+        if path.extension().is_none_or(|ext| ext != "tmp") {
+            continue;
+        }
+        let stale = entry
+            .metadata()
+            .and_then(|m| m.modified())
+            .is_ok_and(|modified| modified < cutoff);
+        if let (true, Ok(relative)) = (stale, path.strip_prefix(root)) {
+            found.push(Finding {
+                kind: OrphanKind::TempFile,
+                target: relative.to_string_lossy().into_owned(),
+                reason: "temp file left by an interrupted write".to_string(),
+            });
+        }
+    }
+}
+
+/// Every artifact that is orphaned at `now`. Records of `held` wallets are
+/// kept with them.
+pub fn find_orphans(
+    storage: &EncryptedStorage,
+    tx_db: &TxDatabase,
+    now: DateTime<Utc>,
+    config: &SweepConfig,
+    held: &HeldWallets,
+) -> StorageResult<Vec<Finding>> {
+    let mut found = Vec::new();
+    let root = storage.paths().root();
+    let temp_cutoff = SystemTime::now()
+        .checked_sub(StdDuration::from_secs(TEMP_FILE_MIN_AGE_SECS))
+        .unwrap_or(SystemTime::UNIX_EPOCH);
+    stale_temp_files(root, root, temp_cutoff, &mut found);
+
+    let mut wallets = WalletCheck::new(storage, now - config.retention, held);
+    let transactions = tx_db
+        .list_all_transactions()
+        .map_err(|e| StorageError::Io(std::io::Error::other(e)))?;
+    for tx in transactions {
+        let Some(reason) = wallets.gone(&tx.wallet_id) else {
+            continue;
+        };
+        let reason = match &tx.counterparty_wallet_id {
+            Some(counterparty) => match wallets.gone(counterparty) {
+                Some(counterparty_reason) => {
+                    format!("mirrored record; {reason}, counterparty {counterparty_reason}")
+                }
+                None => continue,
+            },
+            None => reason,
+        };
+        found.push(Finding {
+            kind: OrphanKind::Transaction,
+            target: tx.tx_hash,
+            reason,
+        });
+    }
+
+    for bookmark in BookmarkRepository::new(storage).list_all()? {
+        if let Some(reason) = wallets.gone(&bookmark.wallet_id) {
+            found.push(Finding {
+                kind: OrphanKind::Bookmark,
+                target: bookmark.id,
+                reason,
+            });
+        }
+    }
+    Ok(found)
+}
+
+fn remove_artifact(
+    storage: &EncryptedStorage,
+    tx_db: &TxDatabase,
+    kind: OrphanKind,
+    target: &str,
+) -> Result<(), String> {
+    match kind {
+        OrphanKind::TempFile => {
+            let relative = Path::new(target);
+            if relative.is_absolute() || target.split(['/', '\\']).any(|part| part == "..") {
+                return Err(format!("refusing to remove {target}"));
+            }
+            let path = storage.paths().root().join(relative);
+            if !path.exists() {
+                return Ok(());
+            }
+            storage.delete(path).map_err(|e| e.to_string())
+        }
+        OrphanKind::Transaction => tx_db
+            .delete_transaction(target)
+            .map(|_| ())
+            .map_err(|e| e.to_string()),
+        OrphanKind::Bookmark => match BookmarkRepository::new(storage).delete(target) {
+            Ok(()) | Err(StorageError::NotFound(_)) => Ok(()),
+            Err(e) => Err(e.to_string()),
+        },
+        OrphanKind::HeldWallet => Err(format!("wallet {target} is held for an admin")),
+    }
+}
+
+/// Wallets deleted before the retention cutoff whose keys still exist.
+/// Wallets without a deletion time are kept.
+fn expired_wallets(
+    storage: &EncryptedStorage,
+    now: DateTime<Utc>,
+    config: &SweepConfig,
+) -> StorageResult<Vec<WalletMetadata>> {
+    let cutoff = now - config.retention;
+    Ok(WalletRepository::new(storage)
+        .list_all_wallets()?
+        .into_iter()
+        .filter(|w| {
+            w.status == WalletStatus::Deleted
+                && w.purged_at.is_none()
+                && w.deleted_at.is_some_and(|at| at <= cutoff)
+        })
+        .collect())
+}
+
+/// Why `wallet` must keep its key according to storage: escrows or claims
+/// it is party to are still open, or it holds indexed NFTs.
+fn stored_holdings(
+    storage: &EncryptedStorage,
+    tx_db: &TxDatabase,
+    wallet: &WalletMetadata,
+) -> StorageResult<Option<String>> {
+    let id = wallet.wallet_id.as_str();
+    let escrows = EscrowRepository::new(storage);
+    let open_escrows = escrows
+        .list_payments()?
+        .iter()
+        .filter(|p| !p.status.is_final() && (p.payer_wallet_id == id || p.payee_wallet_id == id))
+        .count();
+    if open_escrows > 0 {
+        return Ok(Some(format!("{open_escrows} open escrowed payment(s)")));
+    }
+    let open_claims = escrows
+        .list_claims()?
+        .iter()
+        .filter(|c| {
+            matches!(c.status, ClaimStatus::Pending | ClaimStatus::Settling)
+                && (c.sender_wallet_id == id || c.payout_wallet_id.as_deref() == Some(id))
+        })
+        .count();
+    if open_claims > 0 {
+        return Ok(Some(format!("{open_claims} open claimable transfer(s)")));
+    }
+    let nfts = tx_db
+        .list_nft_holdings(id)
+        .map_err(|e| StorageError::Io(std::io::Error::other(e)))?
+        .len();
+    Ok((nfts > 0).then(|| format!("holds {nfts} NFT(s)")))
+}
+
+/// Why `address` must keep its key according to the chain: it holds
+/// native coins or listed tokens on a registered network, or a balance
+/// could not be read.
+async fn chain_holdings(address: &str) -> Option<String> {
+    for registered in networks().all() {
+        let network = &registered.config;
+        let client = match AvaxClient::new(network.clone()).await {
+            Ok(client) => client,
+            Err(e) => return Some(format!("balances on `{}` unreadable: {e}", network.id)),
+        };
+        let native = client.get_native_balance(address).await;
+        let mut balances = vec![(network.native_symbol.to_string(), native)];
+        for token in tokens().on_network(network.id) {
+            let balance = client.get_token_balance(address, &token.address).await;
+            balances.push((token.symbol, balance));
+        }
+        for (symbol, balance) in balances {
+            match balance {
+                Ok(balance) if balance.balance_raw.trim_start_matches('0').is_empty() => {}
+                Ok(_) => return Some(format!("holds {symbol} on `{}`", network.id)),
+                Err(e) => {
+                    return Some(format!(
+                        "{symbol} balance on `{}` unreadable: {e}",
+                        network.id
+                    ))
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Wallets due for purging that must keep their keys, and why.
+pub async fn held_wallets(
+    storage: &EncryptedStorage,
+    tx_db: &TxDatabase,
+    now: DateTime<Utc>,
+    config: &SweepConfig,
+) -> StorageResult<HeldWallets> {
+    let mut held = HeldWallets::new();
+    for wallet in expired_wallets(storage, now, config)? {
+        let reason = match stored_holdings(storage, tx_db, &wallet)? {
+            Some(reason) => Some(reason),
+            None => chain_holdings(&wallet.public_address).await,
+        };
+        if let Some(reason) = reason {
+            held.insert(wallet.wallet_id, reason);
+        }
+    }
+    Ok(held)
+}
+
+/// Destroy the key material of wallets deleted before the retention
+/// cutoff, except those in `held`. Wallets without a deletion time are
+/// kept.
+pub fn purge_expired_wallets(
+    storage: &EncryptedStorage,
+    now: DateTime<Utc>,
+    config: &SweepConfig,
+    held: &HeldWallets,
+) -> StorageResult<usize> {
+    let repo = WalletRepository::new(storage);
+    let mut purged = 0;
+    for wallet in expired_wallets(storage, now, config)? {
+        if held.contains_key(&wallet.wallet_id) {
+            continue;
+        }
+        let deleted_at = wallet.deleted_at;
+        match repo.purge_keys(&wallet.wallet_id) {
+            Ok(_) => {
+                purged += 1;
+                let event = AuditEvent::new(AuditEventType::WalletPurged)
+                    .with_user(&wallet.owner_user_id)
+                    .with_resource("wallet", &wallet.wallet_id)
+                    .with_details(serde_json::json!({
+                        "deleted_at": deleted_at,
+                        "retention_days": config.retention.num_days(),
+                    }));
+                let _ = AuditRepository::new(storage).log(&event);
+            }
+            Err(e) => {
+                warn!(wallet_id = %wallet.wallet_id, error = %e, "Orphan sweep: wallet purge failed");
+                let event = AuditEvent::new(AuditEventType::WalletPurgeFailed)
+                    .with_user(&wallet.owner_user_id)
+                    .with_resource("wallet", &wallet.wallet_id)
+                    .with_details(serde_json::json!({
+                        "deleted_at": deleted_at,
+                        "error": e.to_string(),
+                    }));
+                let _ = AuditRepository::new(storage).log(&event);
+            }
+        }
+    }
+    Ok(purged)
+}
+
+/// Purge expired wallets not in `held`, record new orphans and held
+/// wallets, drop resolved ones and remove those whose confirmation window
+/// has passed.
+pub fn sweep(
+    storage: &EncryptedStorage,
+    tx_db: &TxDatabase,
+    now: DateTime<Utc>,
+    config: &SweepConfig,
+    held: &HeldWallets,
+) -> StorageResult<SweepSummary> {
+    let purged = purge_expired_wallets(storage, now, config, held)?;
+    let mut findings = find_orphans(storage, tx_db, now, config, held)?;
+    findings.extend(held.iter().map(|(wallet_id, reason)| Finding {
+        kind: OrphanKind::HeldWallet,
+        target: wallet_id.clone(),
+        reason: format!("key kept: {reason}"),
+    }));
+    let repo = OrphanRepository::new(storage);
+    let mut known: HashMap<String, StoredOrphan> = repo
+        .list_all()?
+        .into_iter()
+        .map(|o| (o.orphan_id.clone(), o))
+        .collect();
+    let mut summary = SweepSummary {
+        purged,
+        ..SweepSummary::default()
+    };
+    let mut current = HashSet::new();
+
+    for finding in findings {
+        let id = orphan_id(finding.kind, &finding.target);
+        if !current.insert(id.clone()) {
+            continue;
+        }
+        let Some(mut orphan) = known.remove(&id) else {
+            repo.save(&StoredOrphan {
+                orphan_id: id,
+                kind: finding.kind,
+                target: finding.target,
+                reason: finding.reason,
+                first_seen_at: now,
+                last_seen_at: now,
+                remove_after: now + config.confirm_window,
+            })?;
+            summary.found += 1;
+            continue;
+        };
+        if orphan.remove_after > now || orphan.kind == OrphanKind::HeldWallet {
+            orphan.last_seen_at = now;
+            orphan.reason = finding.reason;
+            repo.save(&orphan)?;
+            continue;
+        }
+        match remove_artifact(storage, tx_db, orphan.kind, &orphan.target) {
+            Ok(()) => {
+                repo.delete(&orphan.orphan_id)?;
+                summary.removed += 1;
+                let event = AuditEvent::new(AuditEventType::OrphanRemoved)
+                    .with_resource("orphan", &orphan.orphan_id)
+                    .with_details(serde_json::json!({
+                        "kind": orphan.kind,
+                        "target": orphan.target,
+                        "reason": orphan.reason,
+                    }));
+                let _ = AuditRepository::new(storage).log(&event);
+            }
+            Err(e) => {
+                warn!(orphan_id = %orphan.orphan_id, error = %e, "Orphan sweep: removal failed")
+            }
+        }
+    }
+
+    for orphan_id in known.into_keys() {
+        repo.delete(&orphan_id)?;
+        summary.resolved += 1;
+    }
+    Ok(summary)
+}
+
+/// Background task removing orphaned storage artifacts.
+pub struct OrphanSweeper {
+    storage: Arc<EncryptedStorage>,
+    tx_db: Arc<TxDatabase>,
+}
+
+impl OrphanSweeper {
+    /// Create a sweeper over the given storage and transaction database.
+    pub fn new(storage: Arc<EncryptedStorage>, tx_db: Arc<TxDatabase>) -> Self {
+        Self { storage, tx_db }
+    }
+
+    /// Run the sweep loop until the cancellation token is triggered.
+    pub async fn run(self, shutdown: CancellationToken) {
+        let config = SweepConfig::from_env();
+        info!(
+            interval_secs = SWEEP_INTERVAL_SECS,
+            retention_days = config.retention.num_days(),
+            confirm_hours = config.confirm_window.num_hours(),
+            "Orphan sweeper starting"
+        );
+
+        loop {
+            if shutdown.is_cancelled() {
+                info!("Orphan sweeper shutting down");
+                return;
+            }
+
+            let now = Utc::now();
+            let result = match held_wallets(&self.storage, &self.tx_db, now, &config).await {
+                Ok(held) => {
+                    let storage = self.storage.clone();
+                    let tx_db = self.tx_db.clone();
+                    tokio::task::spawn_blocking(move || {
+                        sweep(&storage, &tx_db, now, &config, &held)
+                    })
+                    .await
+                }
+                Err(e) => Ok(Err(e)),
+            };
+            match result {
+                Ok(Ok(summary)) => {
+                    if summary != SweepSummary::default() {
+                        info!(
+                            found = summary.found,
+                            removed = summary.removed,
+                            resolved = summary.resolved,
+                            purged = summary.purged,
+                            "Orphan sweep finished"
+                        );
+                    }
+                }
+                Ok(Err(e)) => warn!(error = %e, "Orphan sweep failed"),
+                Err(e) => warn!(error = %e, "Orphan sweep panicked"),
+            }
+            crate::worker_health::record_heartbeat(crate::worker_health::Worker::OrphanSweeper);
+
+            tokio::select! {
+                _ = tokio::time::sleep(StdDuration::from_secs(SWEEP_INTERVAL_SECS)) => {},
+                _ = shutdown.cancelled() => {
+                    info!("Orphan sweeper shutting down");
+                    return;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::{
+        RecipientType, StoragePaths, StoredBookmark, StoredClaim, StoredTransaction, TokenType,
+    };
+
+    struct Fixture {
+        storage: EncryptedStorage,
+        tx_db: TxDatabase,
+    }
+
+    impl Drop for Fixture {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(self.storage.paths().root());
+        }
+    }
+
+    fn fixture() -> Fixture {
+        let dir = env::temp_dir().join(format!("test-orphan-sweep-{}", uuid::Uuid::new_v4()));
+        let mut storage = EncryptedStorage::new(StoragePaths::new(&dir));
+        storage.initialize().unwrap();
+        let tx_db = TxDatabase::open(&dir.join("tx.redb")).unwrap();
+        Fixture { storage, tx_db }
+    }
+
+    fn wallet(storage: &EncryptedStorage, deleted_days_ago: Option<i64>) -> String {
+        let wallet_id = uuid::Uuid::new_v4().to_string();
+        let meta = WalletMetadata {
+            wallet_id: wallet_id.clone(),
+            owner_user_id: "user_1".to_string(),
+            public_address: format!("0x{}", &wallet_id.replace('-', "")[..32]),
+            created_at: Utc::now(),
+            status: if deleted_days_ago.is_some() {
+                WalletStatus::Deleted
+            } else {
+                WalletStatus::Active
+            },
+            label: None,
+            email_lookup_key: None,
+            email_sha256: None,
+            account_type: Default::default(),
+            smart_account: None,
+            lock: None,
+            deleted_at: deleted_days_ago.map(|days| Utc::now() - Duration::days(days)),
+            purged_at: None,
+            funded_at: None,
+            tenant_id: None,
+        };
+        WalletRepository::new(storage)
+            .create(&meta, b"test_key")
+            .unwrap();
+        wallet_id
+    }
+
+    fn record(tx_db: &TxDatabase, hash: &str, wallet_id: &str, counterparty: Option<&str>) {
+        let tx = StoredTransaction::new_pending(
+            hash.to_string(),
+            wallet_id.to_string(),
+            counterparty.map(str::to_string),
+            "0x1111111111111111111111111111111111111111".to_string(),
+            "0x2222222222222222222222222222222222222222".to_string(),
+            "1.0".to_string(),
+            TokenType::Native,
+            "fuji".to_string(),
+            String::new(),
+        );
+        tx_db
+            .upsert_transaction(&tx, &[(tx.from.clone(), "sent")])
+            .unwrap();
+    }
+
+    fn config() -> SweepConfig {
+        SweepConfig {
+            retention: Duration::days(90),
+            confirm_window: Duration::hours(72),
+        }
+    }
+
+    #[test]
+    fn finds_records_only_once_every_wallet_is_gone() {
+        let f = fixture();
+        let active = wallet(&f.storage, None);
+        let recent = wallet(&f.storage, Some(10));
+        let expired = wallet(&f.storage, Some(100));
+        let missing = uuid::Uuid::new_v4().to_string();
+        record(&f.tx_db, "0xactive", &active, None);
+        record(&f.tx_db, "0xrecent", &recent, None);
+        record(&f.tx_db, "0xexpired", &expired, None);
+        record(&f.tx_db, "0xmirror-live", &missing, Some(&active));
+        record(&f.tx_db, "0xmirror-gone", &missing, Some(&expired));
+        record(&f.tx_db, "0xwatched", "watch:0xabc", None);
+        BookmarkRepository::new(&f.storage)
+            .create(&StoredBookmark {
+                id: "bm-1".to_string(),
+                wallet_id: missing.clone(),
+                owner_user_id: "user_1".to_string(),
+                name: "Rent".to_string(),
+                recipient_type: RecipientType::Address,
+                address: "0x2222222222222222222222222222222222222222".to_string(),
+                email_hash: None,
+                email_display: None,
+                created_at: Utc::now(),
+            })
+            .unwrap();
+
+        let mut targets: Vec<_> = find_orphans(
+            &f.storage,
+            &f.tx_db,
+            Utc::now(),
+            &config(),
+            &HeldWallets::new(),
+        )
+        .unwrap()
+        .into_iter()
+        .map(|finding| finding.target)
+        .collect();
+        targets.sort();
+        assert_eq!(targets, ["0xexpired", "0xmirror-gone", "bm-1"]);
+    }
+
+    #[test]
+    fn orphans_are_removed_only_after_the_confirmation_window() {
+        let f = fixture();
+        let expired = wallet(&f.storage, Some(100));
+        record(&f.tx_db, "0xexpired", &expired, None);
+        let now = Utc::now();
+
+        let first = sweep(&f.storage, &f.tx_db, now, &config(), &HeldWallets::new()).unwrap();
+        assert_eq!(first.found, 1);
+        assert_eq!(first.purged, 1);
+        let listed = OrphanRepository::new(&f.storage).list_all().unwrap();
+        assert_eq!(listed[0].remove_after, now + Duration::hours(72));
+
+        let early = sweep(
+            &f.storage,
+            &f.tx_db,
+            now + Duration::hours(1),
+            &config(),
+            &HeldWallets::new(),
+        )
+        .unwrap();
+        assert_eq!(early, SweepSummary::default());
+        assert!(f.tx_db.get_transaction("0xexpired").unwrap().is_some());
+
+        let late = sweep(
+            &f.storage,
+            &f.tx_db,
+            now + Duration::hours(73),
+            &config(),
+            &HeldWallets::new(),
+        )
+        .unwrap();
+        assert_eq!(late.removed, 1);
+        assert!(f.tx_db.get_transaction("0xexpired").unwrap().is_none());
+        assert!(OrphanRepository::new(&f.storage)
+            .list_all()
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn orphans_that_are_referenced_again_are_dropped() {
+        let f = fixture();
+        let missing = uuid::Uuid::new_v4().to_string();
+        record(&f.tx_db, "0xlost", &missing, None);
+        let now = Utc::now();
+        assert_eq!(
+            sweep(&f.storage, &f.tx_db, now, &config(), &HeldWallets::new())
+                .unwrap()
+                .found,
+            1
+        );
+
+        record(&f.tx_db, "0xlost", "watch:0xabc", None);
+        let summary = sweep(
+            &f.storage,
+            &f.tx_db,
+            now + Duration::hours(73),
+            &config(),
+            &HeldWallets::new(),
+        )
+        .unwrap();
+        assert_eq!(summary.resolved, 1);
+        assert!(f.tx_db.get_transaction("0xlost").unwrap().is_some());
+    }
+
+    #[test]
+    fn purges_keys_of_wallets_deleted_past_retention() {
+        let f = fixture();
+        let active = wallet(&f.storage, None);
+        let recent = wallet(&f.storage, Some(10));
+        let expired = wallet(&f.storage, Some(100));
+        let now = Utc::now();
+
+        assert_eq!(
+            purge_expired_wallets(&f.storage, now, &config(), &HeldWallets::new()).unwrap(),
+            1
+        );
+        let repo = WalletRepository::new(&f.storage);
+        assert!(repo.get(&expired).unwrap().purged_at.is_some());
+        assert!(repo.read_private_key(&expired).is_err());
+        for kept in [&active, &recent] {
+            assert!(repo.get(kept).unwrap().purged_at.is_none());
+            assert!(repo.read_private_key(kept).is_ok());
+        }
+        let events = AuditRepository::new(&f.storage)
+            .read_events(&now.format("%Y-%m-%d").to_string())
+            .unwrap();
+        assert!(events
+            .iter()
+            .any(|e| e.event_type == AuditEventType::WalletPurged
+                && e.resource_id.as_deref() == Some(expired.as_str())));
+
+        // Already purged wallets are skipped.
+        assert_eq!(
+            purge_expired_wallets(&f.storage, now, &config(), &HeldWallets::new()).unwrap(),
+            0
+        );
+    }
+
+    #[test]
+    fn wallets_with_open_claims_keep_their_keys_until_settled() {
+        let f = fixture();
+        let expired = wallet(&f.storage, Some(100));
+        record(&f.tx_db, "0xheld", &expired, None);
+        let now = Utc::now();
+        let mut claim = StoredClaim {
+            claim_id: "claim-1".to_string(),
+            sender_user_id: "user_1".to_string(),
+            sender_wallet_id: expired.clone(),
+            sender_address: "0x1111111111111111111111111111111111111111".to_string(),
+            escrow_address: "0x3333333333333333333333333333333333333333".to_string(),
+            token: "native".to_string(),
+            amount: "1".to_string(),
+            recipient_email_hash: None,
+            note: None,
+            funding_tx_hashes: Vec::new(),
+            gas_funded: false,
+            status: ClaimStatus::Pending,
+            expires_at: now,
+            claimed_by_user_id: None,
+            payout_wallet_id: None,
+            payout_tx_hash: None,
+            last_error: None,
+            created_at: now,
+            updated_at: now,
+        };
+        let escrows = EscrowRepository::new(&f.storage);
+        escrows.create_claim(&claim).unwrap();
+        let meta = WalletRepository::new(&f.storage).get(&expired).unwrap();
+        let reason = stored_holdings(&f.storage, &f.tx_db, &meta)
+            .unwrap()
+            .unwrap();
+        assert!(reason.contains("claimable"));
+
+        // Held: the key, the wallet's records and a finding for an admin
+        // stay, however long it is held.
+        let held = HeldWallets::from([(expired.clone(), reason)]);
+        for at in [now, now + Duration::hours(73)] {
+            let summary = sweep(&f.storage, &f.tx_db, at, &config(), &held).unwrap();
+            assert_eq!(summary.purged, 0);
+            assert_eq!(summary.removed, 0);
+        }
+        assert!(WalletRepository::new(&f.storage)
+            .read_private_key(&expired)
+            .is_ok());
+        let listed = OrphanRepository::new(&f.storage).list_all().unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].kind, OrphanKind::HeldWallet);
+        assert_eq!(listed[0].target, expired);
+
+        // Once reclaimed, the wallet is purged and the finding resolved.
+        claim.status = ClaimStatus::Reclaimed;
+        escrows.update_claim(&claim).unwrap();
+        assert!(stored_holdings(&f.storage, &f.tx_db, &meta)
+            .unwrap()
+            .is_none());
+        let summary = sweep(
+            &f.storage,
+            &f.tx_db,
+            now + Duration::hours(74),
+            &config(),
+            &HeldWallets::new(),
+        )
+        .unwrap();
+        assert_eq!(summary.purged, 1);
+        assert_eq!(summary.resolved, 1);
+    }
+}
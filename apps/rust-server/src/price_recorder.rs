@@ -0,0 +1,99 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! # Daily Price Recorder
+//!
+//! Background task that writes the current EUR price of every priced token
+//! into the price history once per [`RECORD_INTERVAL_SECS`]. Each run
+//! overwrites the current day's entry, so a finished day keeps the last
+//! price recorded for it.
+//!
+//! Transactions are valued with these daily prices, so recording keeps
+//! valuations available without a feed call per transaction. Days of the
+//! last [`BACKFILL_DAYS`] the recorder missed (downtime, feed outage) are
+//! backfilled from the feed's history on the next run.
+//!
+//! ## Shutdown
+//!
+//! Uses `tokio_util::sync::CancellationToken` for graceful shutdown, following
+//! the same pattern as the `FiatPoller`.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{Days, Utc};
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+
+use crate::providers::pricing::{PriceFeedClient, PRICED_SYMBOLS};
+use crate::storage::EncryptedStorage;
+
+/// Interval between recordings.
+pub const RECORD_INTERVAL_SECS: u64 = 3600;
+
+/// How many past days are checked for gaps on each run.
+const BACKFILL_DAYS: u64 = 7;
+
+/// Background task recording daily token prices.
+pub struct PriceRecorder {
+    storage: Arc<EncryptedStorage>,
+}
+
+impl PriceRecorder {
+    /// Create a recorder writing to the given encrypted storage.
+    pub fn new(storage: Arc<EncryptedStorage>) -> Self {
+        Self { storage }
+    }
+
+    /// Run the recorder loop until the cancellation token is triggered.
+    pub async fn run(self, shutdown: CancellationToken) {
+        info!(
+            interval_secs = RECORD_INTERVAL_SECS,
+            "Price recorder starting"
+        );
+
+        loop {
+            if shutdown.is_cancelled() {
+                info!("Price recorder shutting down");
+                return;
+            }
+
+            self.record_step().await;
+            crate::worker_health::record_heartbeat(crate::worker_health::Worker::PriceRecorder);
+
+            tokio::select! {
+                _ = tokio::time::sleep(Duration::from_secs(RECORD_INTERVAL_SECS)) => {},
+                _ = shutdown.cancelled() => {
+                    info!("Price recorder shutting down");
+                    return;
+                }
+            }
+        }
+    }
+
+    async fn record_step(&self) {
+        let client = match PriceFeedClient::from_env() {
+            Ok(client) => client,
+            Err(e) => {
+                warn!(error = %e, "Price recorder: price feed not configured");
+                return;
+            }
+        };
+        match client.record_daily_prices(&self.storage).await {
+            Ok(count) => info!(count, "Price recorder: daily prices recorded"),
+            Err(e) => warn!(error = %e, "Price recorder: failed to record prices"),
+        }
+
+        // `price_on` only calls the feed for days missing from the history.
+        let today = Utc::now().date_naive();
+        for days_ago in 1..=BACKFILL_DAYS {
+            let date = today - Days::new(days_ago);
+            for symbol in PRICED_SYMBOLS {
+                if let Err(e) = client.price_on(&self.storage, symbol, date).await {
+                    warn!(symbol, %date, error = %e, "Price recorder: backfill failed");
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,502 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Card payments through a Stripe-compatible PSP.
+//!
+//! On-ramps use hosted Checkout Sessions, so card entry and any 3-D Secure
+//! challenge happen on the PSP's page, which then redirects back to the
+//! signed return URI. Webhooks carry checkout results and chargebacks
+//! (disputes) and are authenticated with the PSP's HMAC signature scheme.
+
+use std::time::Duration;
+
+use hmac::{Hmac, Mac};
+use reqwest::Client;
+use serde::Deserialize;
+use serde_json::Value;
+use sha2::Sha256;
+
+use super::fiat::{
+    CreateOnRampRequest, FiatProvider, FiatProviderError, ProviderExecutionResult,
+    ProviderExecutionStatus,
+};
+use crate::storage::repository::CardCredentials;
+
+type HmacSha256 = Hmac<Sha256>;
+
+const DEFAULT_API_BASE_URL: &str = "https://api.stripe.com";
+const DEFAULT_CURRENCY: &str = "eur";
+/// Header carrying the webhook signature (`t=<unix>,v1=<hex hmac>`).
+pub const WEBHOOK_SIGNATURE_HEADER: &str = "stripe-signature";
+/// Webhooks signed longer ago than this are rejected as replays.
+const WEBHOOK_TOLERANCE_SECS: i64 = 300;
+
+#[derive(Debug, thiserror::Error)]
+pub enum CardProviderError {
+    #[error("Card provider configuration missing: {0}")]
+    MissingConfig(String),
+
+    #[error("Card provider request failed: {0}")]
+    Request(String),
+
+    #[error("Card provider response was invalid: {0}")]
+    InvalidResponse(String),
+
+    #[error("Card webhook signature invalid: {0}")]
+    Signature(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct CardPaymentClient {
+    api_base_url: String,
+    secret_key: String,
+    currency: String,
+    /// Ask the issuer for a 3-D Secure challenge on every payment instead of
+    /// leaving it to the PSP's risk engine.
+    require_three_d_secure: bool,
+    http: Client,
+}
+
+/// A PSP webhook event.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CardWebhookEvent {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub event_type: String,
+    pub data: CardWebhookData,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CardWebhookData {
+    pub object: Value,
+}
+
+/// What a webhook event means for our fiat requests.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CardWebhookAction {
+    /// A checkout session (the on-ramp's provider reference) changed state.
+    Checkout {
+        session_id: String,
+        status: ProviderExecutionStatus,
+    },
+    /// The cardholder disputed a payment.
+    Dispute {
+        dispute_id: String,
+        payment_intent_id: String,
+        reason: Option<String>,
+    },
+    /// Not relevant to on-ramps.
+    Ignored,
+}
+
+impl CardPaymentClient {
+    pub fn is_configured() -> bool {
+        env_optional("CARD_PSP_SECRET_KEY").is_some()
+    }
+
+    /// Whether `credentials`, or the environment when there are none, hold a
+    /// secret key.
+    pub fn is_configured_for(credentials: Option<&CardCredentials>) -> bool {
+        credentials.is_some() || Self::is_configured()
+    }
+
+    /// Client for a tenant's own account, falling back to the environment's.
+    pub fn for_tenant(credentials: Option<&CardCredentials>) -> Result<Self, CardProviderError> {
+        let secret_key = match credentials {
+            Some(credentials) => credentials.api_secret.clone(),
+            None => env_optional("CARD_PSP_SECRET_KEY").ok_or_else(|| {
+                CardProviderError::MissingConfig("CARD_PSP_SECRET_KEY".to_string())
+            })?,
+        };
+        let api_base_url =
+            env_optional("CARD_PSP_API_BASE_URL").unwrap_or_else(|| DEFAULT_API_BASE_URL.into());
+        let currency = env_optional("CARD_PSP_CURRENCY")
+            .unwrap_or_else(|| DEFAULT_CURRENCY.into())
+            .to_ascii_lowercase();
+        let require_three_d_secure = env_optional("CARD_PSP_REQUIRE_3DS")
+            .map(|v| matches!(v.to_ascii_lowercase().as_str(), "1" | "true" | "yes"))
+            .unwrap_or(false);
+
+        let http = Client::builder()
+            .timeout(Duration::from_secs(15))
+            .build()
+            .map_err(|e| CardProviderError::Request(format!("failed to build HTTP client: {e}")))?;
+
+        Ok(Self {
+            api_base_url,
+            secret_key,
+            currency,
+            require_three_d_secure,
+            http,
+        })
+    }
+
+    /// Create a hosted checkout session for an on-ramp.
+    pub async fn create_checkout(
+        &self,
+        request: CreateOnRampRequest<'_>,
+    ) -> Result<ProviderExecutionResult, CardProviderError> {
+        let three_d_secure = if self.require_three_d_secure {
+            "any"
+        } else {
+            "automatic"
+        };
+        let mut form = vec![
+            ("mode", "payment".to_string()),
+            ("payment_method_types[0]", "card".to_string()),
+            (
+                "payment_method_options[card][request_three_d_secure]",
+                three_d_secure.to_string(),
+            ),
+            ("line_items[0][quantity]", "1".to_string()),
+            ("line_items[0][price_data][currency]", self.currency.clone()),
+            (
+                "line_items[0][price_data][unit_amount]",
+                request.amount_in_minor.to_string(),
+            ),
+            (
+                "line_items[0][price_data][product_data][name]",
+                format!("rEUR top-up ({} EUR)", request.amount_eur),
+            ),
+            ("client_reference_id", request.request_id.to_string()),
+            ("success_url", request.return_uri.to_string()),
+            ("cancel_url", request.return_uri.to_string()),
+            ("metadata[request_id]", request.request_id.to_string()),
+            ("metadata[wallet_id]", request.wallet_id.to_string()),
+            ("metadata[user_id]", request.user_id.to_string()),
+            (
+                "payment_intent_data[metadata][request_id]",
+                request.request_id.to_string(),
+            ),
+        ];
+        if let Some(note) = request.note {
+            form.push(("metadata[note]", note.to_string()));
+        }
+
+        let response = self
+            .post_form("/v1/checkout/sessions", &form, request.request_id)
+            .await?;
+        let session_id = response
+            .get("id")
+            .and_then(Value::as_str)
+            .ok_or_else(|| {
+                CardProviderError::InvalidResponse("missing checkout session id".to_string())
+            })?
+            .to_string();
+
+        Ok(ProviderExecutionResult {
+            provider_reference: session_id,
+            provider_action_url: response
+                .get("url")
+                .and_then(Value::as_str)
+                .map(str::to_string),
+            status: checkout_status_of(&response),
+        })
+    }
+
+    /// Current status of a checkout session.
+    pub async fn fetch_checkout_status(
+        &self,
+        session_id: &str,
+    ) -> Result<ProviderExecutionStatus, CardProviderError> {
+        let response = self
+            .get_json(&format!("/v1/checkout/sessions/{session_id}"))
+            .await?;
+        Ok(checkout_status_of(&response))
+    }
+
+    /// Fiat request ID recorded on a payment intent, used to match disputes,
+    /// which only reference the payment intent.
+    pub async fn fetch_payment_request_id(
+        &self,
+        payment_intent_id: &str,
+    ) -> Result<Option<String>, CardProviderError> {
+        let response = self
+            .get_json(&format!("/v1/payment_intents/{payment_intent_id}"))
+            .await?;
+        Ok(response
+            .pointer("/metadata/request_id")
+            .and_then(Value::as_str)
+            .map(str::to_string))
+    }
+
+    async fn get_json(&self, path: &str) -> Result<Value, CardProviderError> {
+        let response = self
+            .http
+            .get(format!(
+                "{}{}",
+                self.api_base_url.trim_end_matches('/'),
+                path
+            ))
+            .bearer_auth(&self.secret_key)
+            .send()
+            .await
+            .map_err(|e| CardProviderError::Request(format!("GET {path} failed: {e}")))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(CardProviderError::Request(format!(
+                "GET {path} returned {status}: {body}"
+            )));
+        }
+
+        response.json().await.map_err(|e| {
+            CardProviderError::InvalidResponse(format!("GET {path} invalid JSON: {e}"))
+        })
+    }
+
+    async fn post_form(
+        &self,
+        path: &str,
+        form: &[(&str, String)],
+        idempotency_key: &str,
+    ) -> Result<Value, CardProviderError> {
+        let response = self
+            .http
+            .post(format!(
+                "{}{}",
+                self.api_base_url.trim_end_matches('/'),
+                path
+            ))
+            .bearer_auth(&self.secret_key)
+            .header("Idempotency-Key", idempotency_key)
+            .form(form)
+            .send()
+            .await
+            .map_err(|e| CardProviderError::Request(format!("POST {path} failed: {e}")))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(CardProviderError::Request(format!(
+                "POST {path} returned {status}: {body}"
+            )));
+        }
+
+        response.json().await.map_err(|e| {
+            CardProviderError::InvalidResponse(format!("POST {path} invalid JSON: {e}"))
+        })
+    }
+}
+
+impl FiatProvider for CardPaymentClient {
+    async fn create_onramp(
+        &self,
+        request: CreateOnRampRequest<'_>,
+    ) -> Result<ProviderExecutionResult, FiatProviderError> {
+        Ok(self.create_checkout(request).await?)
+    }
+
+    async fn fetch_onramp_status(
+        &self,
+        provider_reference: &str,
+    ) -> Result<ProviderExecutionStatus, FiatProviderError> {
+        Ok(self.fetch_checkout_status(provider_reference).await?)
+    }
+}
+
+/// Map a checkout session's `status` and `payment_status`.
+pub fn map_checkout_status(status: &str, payment_status: &str) -> ProviderExecutionStatus {
+    match (status, payment_status) {
+        ("complete", "paid" | "no_payment_required") => ProviderExecutionStatus::Completed,
+        ("expired", _) => ProviderExecutionStatus::Failed,
+        _ => ProviderExecutionStatus::Pending,
+    }
+}
+
+fn checkout_status_of(session: &Value) -> ProviderExecutionStatus {
+    let field = |name: &str| {
+        session
+            .get(name)
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+    };
+    map_checkout_status(field("status"), field("payment_status"))
+}
+
+/// Work out what a webhook event asks us to do.
+pub fn classify_webhook_event(event: &CardWebhookEvent) -> CardWebhookAction {
+    let object = &event.data.object;
+    let field = |name: &str| object.get(name).and_then(Value::as_str);
+    match event.event_type.as_str() {
+        "checkout.session.completed"
+        | "checkout.session.async_payment_succeeded"
+        | "checkout.session.async_payment_failed"
+        | "checkout.session.expired" => {
+            let Some(session_id) = field("id") else {
+                return CardWebhookAction::Ignored;
+            };
+            let status = if event.event_type == "checkout.session.async_payment_failed" {
+                ProviderExecutionStatus::Failed
+            } else {
+                checkout_status_of(object)
+            };
+            CardWebhookAction::Checkout {
+                session_id: session_id.to_string(),
+                status,
+            }
+        }
+        "charge.dispute.created" => match (field("id"), field("payment_intent")) {
+            (Some(dispute_id), Some(payment_intent_id)) => CardWebhookAction::Dispute {
+                dispute_id: dispute_id.to_string(),
+                payment_intent_id: payment_intent_id.to_string(),
+                reason: field("reason").map(str::to_string),
+            },
+            _ => CardWebhookAction::Ignored,
+        },
+        _ => CardWebhookAction::Ignored,
+    }
+}
+
+/// Webhook signing secret, if configured.
+pub fn webhook_secret() -> Option<String> {
+    env_optional("CARD_PSP_WEBHOOK_SECRET")
+}
+
+/// Check a `t=<unix>,v1=<hex>` signature header against the raw body.
+///
+/// The signed payload is `"{t}.{body}"`; any `v1` entry may match, which
+/// lets the PSP roll its secret.
+pub fn verify_webhook_signature(
+    secret: &str,
+    header: &str,
+    body: &[u8],
+    now_unix: i64,
+) -> Result<(), CardProviderError> {
+    let mut timestamp = None;
+    let mut signatures = Vec::new();
+    for part in header.split(',') {
+        match part.trim().split_once('=') {
+            Some(("t", value)) => timestamp = value.parse::<i64>().ok(),
+            Some(("v1", value)) => signatures.push(value),
+            _ => {}
+        }
+    }
+    let timestamp =
+        timestamp.ok_or_else(|| CardProviderError::Signature("missing timestamp".into()))?;
+    if (now_unix - timestamp).abs() > WEBHOOK_TOLERANCE_SECS {
+        return Err(CardProviderError::Signature(
+            "timestamp outside tolerance".into(),
+        ));
+    }
+
+    let mut mac =
+        HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC can take key of any size");
+    mac.update(format!("{timestamp}.").as_bytes());
+    mac.update(body);
+    let matched = signatures.iter().any(|candidate| {
+        alloy::hex::decode(candidate)
+            .map(|bytes| mac.clone().verify_slice(&bytes).is_ok())
+            .unwrap_or(false)
+    });
+    if matched {
+        Ok(())
+    } else {
+        Err(CardProviderError::Signature("no matching signature".into()))
+    }
+}
+
+fn env_optional(name: &str) -> Option<String> {
+    std::env::var(name)
+        .ok()
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn sign(secret: &str, timestamp: i64, body: &[u8]) -> String {
+        let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).unwrap();
+        mac.update(format!("{timestamp}.").as_bytes());
+        mac.update(body);
+        alloy::hex::encode(mac.finalize().into_bytes())
+    }
+
+    fn event(event_type: &str, object: Value) -> CardWebhookEvent {
+        CardWebhookEvent {
+            id: "evt_1".to_string(),
+            event_type: event_type.to_string(),
+            data: CardWebhookData { object },
+        }
+    }
+
+    #[test]
+    fn checkout_is_completed_only_once_paid() {
+        assert_eq!(
+            map_checkout_status("complete", "paid"),
+            ProviderExecutionStatus::Completed
+        );
+        assert_eq!(
+            map_checkout_status("complete", "unpaid"),
+            ProviderExecutionStatus::Pending
+        );
+        assert_eq!(
+            map_checkout_status("open", "unpaid"),
+            ProviderExecutionStatus::Pending
+        );
+        assert_eq!(
+            map_checkout_status("expired", "unpaid"),
+            ProviderExecutionStatus::Failed
+        );
+    }
+
+    #[test]
+    fn classifies_checkout_and_dispute_events() {
+        assert_eq!(
+            classify_webhook_event(&event(
+                "checkout.session.completed",
+                json!({"id": "cs_1", "status": "complete", "payment_status": "paid"})
+            )),
+            CardWebhookAction::Checkout {
+                session_id: "cs_1".into(),
+                status: ProviderExecutionStatus::Completed
+            }
+        );
+        assert_eq!(
+            classify_webhook_event(&event(
+                "checkout.session.async_payment_failed",
+                json!({"id": "cs_1", "status": "complete", "payment_status": "unpaid"})
+            )),
+            CardWebhookAction::Checkout {
+                session_id: "cs_1".into(),
+                status: ProviderExecutionStatus::Failed
+            }
+        );
+        assert_eq!(
+            classify_webhook_event(&event(
+                "charge.dispute.created",
+                json!({"id": "dp_1", "payment_intent": "pi_1", "reason": "fraudulent"})
+            )),
+            CardWebhookAction::Dispute {
+                dispute_id: "dp_1".into(),
+                payment_intent_id: "pi_1".into(),
+                reason: Some("fraudulent".into())
+            }
+        );
+        assert_eq!(
+            classify_webhook_event(&event("customer.created", json!({"id": "cus_1"}))),
+            CardWebhookAction::Ignored
+        );
+    }
+
+    #[test]
+    fn webhook_signature_checks_hmac_and_timestamp() {
+        let body = br#"{"id":"evt_1"}"#;
+        let now = 1_800_000_000;
+        let good = format!("t={now},v1={}", sign("whsec", now, body));
+        assert!(verify_webhook_signature("whsec", &good, body, now + 10).is_ok());
+
+        let rolled = format!("t={now},v1={},v1={}", "00", sign("whsec", now, body));
+        assert!(verify_webhook_signature("whsec", &rolled, body, now).is_ok());
+
+        assert!(verify_webhook_signature("other", &good, body, now).is_err());
+        assert!(verify_webhook_signature("whsec", &good, b"{}", now).is_err());
+        assert!(verify_webhook_signature("whsec", &good, body, now + 301).is_err());
+        assert!(verify_webhook_signature("whsec", "v1=abcd", body, now).is_err());
+    }
+}
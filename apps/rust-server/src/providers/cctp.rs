@@ -0,0 +1,142 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Circle attestation service (Iris) for CCTP transfers.
+//!
+//! After a burn is finalized on the source chain, Iris signs the emitted
+//! message. `GET /v1/messages/{sourceDomain}/{txHash}` returns the message
+//! and its attestation; the attestation reads `PENDING` until Circle has
+//! signed it. Set `CCTP_ATTESTATION_URL` to point at the mainnet service;
+//! the default is the sandbox used with testnets.
+
+use std::time::Duration;
+
+use reqwest::{Client, StatusCode};
+use serde_json::Value;
+
+const DEFAULT_API_BASE_URL: &str = "https://iris-api-sandbox.circle.com";
+
+#[derive(Debug, thiserror::Error)]
+pub enum CctpError {
+    #[error("Attestation request failed: {0}")]
+    Request(String),
+
+    #[error("Attestation response was invalid: {0}")]
+    InvalidResponse(String),
+}
+
+/// A signed CCTP message, ready for `receiveMessage`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attestation {
+    pub message: String,
+    pub attestation: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct AttestationClient {
+    api_base_url: String,
+    http: Client,
+}
+
+impl AttestationClient {
+    pub fn from_env() -> Result<Self, CctpError> {
+        let api_base_url = env_optional("CCTP_ATTESTATION_URL")
+            .unwrap_or_else(|| DEFAULT_API_BASE_URL.into())
+            .trim_end_matches('/')
+            .to_string();
+        let http = Client::builder()
+            .timeout(Duration::from_secs(15))
+            .build()
+            .map_err(|e| CctpError::Request(format!("failed to build HTTP client: {e}")))?;
+        Ok(Self { api_base_url, http })
+    }
+
+    /// The attested message of a burn, or `None` while Circle has not seen
+    /// or not yet signed it.
+    pub async fn fetch(
+        &self,
+        source_domain: u32,
+        burn_tx_hash: &str,
+    ) -> Result<Option<Attestation>, CctpError> {
+        let url = format!(
+            "{}/v1/messages/{source_domain}/{burn_tx_hash}",
+            self.api_base_url
+        );
+        let response = self
+            .http
+            .get(url)
+            .send()
+            .await
+            .map_err(|e| CctpError::Request(e.to_string()))?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        if !response.status().is_success() {
+            return Err(CctpError::Request(format!(
+                "unexpected status {}",
+                response.status()
+            )));
+        }
+        let body: Value = response
+            .json()
+            .await
+            .map_err(|e| CctpError::InvalidResponse(e.to_string()))?;
+        parse_messages(&body)
+    }
+}
+
+/// Extract the first attested message from an Iris `messages` response.
+fn parse_messages(body: &Value) -> Result<Option<Attestation>, CctpError> {
+    let message = body
+        .get("messages")
+        .and_then(Value::as_array)
+        .and_then(|messages| messages.first())
+        .ok_or_else(|| CctpError::InvalidResponse("missing messages".into()))?;
+    let field = |name: &str| message.get(name).and_then(Value::as_str);
+    match (field("message"), field("attestation")) {
+        (Some(message), Some(attestation))
+            if message.starts_with("0x") && attestation.starts_with("0x") =>
+        {
+            Ok(Some(Attestation {
+                message: message.to_string(),
+                attestation: attestation.to_string(),
+            }))
+        }
+        _ => Ok(None),
+    }
+}
+
+fn env_optional(name: &str) -> Option<String> {
+    std::env::var(name)
+        .ok()
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn pending_attestations_are_not_ready() {
+        let pending = json!({
+            "messages": [{ "message": "0x", "attestation": "PENDING", "eventNonce": "9" }]
+        });
+        assert_eq!(parse_messages(&pending).unwrap(), None);
+
+        let ready = json!({
+            "messages": [{ "message": "0xabcd", "attestation": "0x1234" }]
+        });
+        assert_eq!(
+            parse_messages(&ready).unwrap(),
+            Some(Attestation {
+                message: "0xabcd".into(),
+                attestation: "0x1234".into(),
+            })
+        );
+
+        assert!(parse_messages(&json!({ "error": "bad" })).is_err());
+    }
+}
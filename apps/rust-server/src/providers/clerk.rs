@@ -2,11 +2,17 @@
 //
 // Copyright (C) 2026 Relational Network
 
-//! Clerk Backend API client for fetching user email addresses.
+//! Clerk Backend API client for fetching user email addresses and verified
+//! names.
 //!
 //! Uses the Clerk Backend API (`GET /v1/users/{user_id}`) with
 //! `CLERK_SECRET_KEY` to retrieve the user's primary email address.
 //! The email is normalized per the frozen spec in `providers::email`.
+//!
+//! The KYC integration writes the user's verified legal name to
+//! `private_metadata.kyc_verified_name` and the tier they reached to
+//! `private_metadata.kyc_tier`; users who have not completed KYC have no
+//! name and tier 0.
 
 use super::email::{normalize_email, EmailError};
 
@@ -55,6 +61,23 @@ fn extract_single_verified_primary_email(
     Ok(primary_email.to_string())
 }
 
+/// Verified legal name from the user's private metadata, if set.
+fn extract_verified_name(body: &serde_json::Value) -> Option<String> {
+    body["private_metadata"]["kyc_verified_name"]
+        .as_str()
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(str::to_string)
+}
+
+/// KYC tier from the user's private metadata; 0 if unset.
+fn extract_kyc_tier(body: &serde_json::Value) -> u8 {
+    body["private_metadata"]["kyc_tier"]
+        .as_u64()
+        .map(|tier| tier.min(u64::from(u8::MAX)) as u8)
+        .unwrap_or(0)
+}
+
 /// Errors from Clerk API operations.
 #[derive(Debug, thiserror::Error)]
 pub enum ClerkError {
@@ -100,6 +123,28 @@ impl ClerkClient {
     ///
     /// Returns the normalized email string.
     pub async fn get_user_email(&self, user_id: &str) -> Result<String, ClerkError> {
+        let body = self.fetch_user(user_id).await?;
+
+        let primary_email = extract_single_verified_primary_email(&body, user_id)?;
+
+        // Normalize per frozen spec
+        let normalized = normalize_email(&primary_email)?;
+        Ok(normalized)
+    }
+
+    /// Fetch the KYC-verified legal name of a Clerk user, if they have one.
+    pub async fn get_verified_name(&self, user_id: &str) -> Result<Option<String>, ClerkError> {
+        let body = self.fetch_user(user_id).await?;
+        Ok(extract_verified_name(&body))
+    }
+
+    /// Fetch the KYC tier a Clerk user has reached (0 if none).
+    pub async fn get_kyc_tier(&self, user_id: &str) -> Result<u8, ClerkError> {
+        let body = self.fetch_user(user_id).await?;
+        Ok(extract_kyc_tier(&body))
+    }
+
+    async fn fetch_user(&self, user_id: &str) -> Result<serde_json::Value, ClerkError> {
         let url = format!("https://api.clerk.com/v1/users/{user_id}");
 
         let response = self
@@ -118,13 +163,7 @@ impl ClerkClient {
             });
         }
 
-        let body: serde_json::Value = response.json().await?;
-
-        let primary_email = extract_single_verified_primary_email(&body, user_id)?;
-
-        // Normalize per frozen spec
-        let normalized = normalize_email(&primary_email)?;
-        Ok(normalized)
+        Ok(response.json().await?)
     }
 }
 
@@ -185,4 +224,26 @@ mod tests {
         let err = extract_single_verified_primary_email(&body, "user_123").unwrap_err();
         assert!(matches!(err, ClerkError::InvalidEmailConfiguration { .. }));
     }
+
+    #[test]
+    fn extracts_verified_name_from_private_metadata() {
+        let body = json!({ "private_metadata": { "kyc_verified_name": " Jane Doe " } });
+        assert_eq!(extract_verified_name(&body).as_deref(), Some("Jane Doe"));
+
+        assert_eq!(
+            extract_verified_name(&json!({ "private_metadata": {} })),
+            None
+        );
+        let blank = json!({ "private_metadata": { "kyc_verified_name": "" } });
+        assert_eq!(extract_verified_name(&blank), None);
+    }
+
+    #[test]
+    fn extracts_kyc_tier_defaulting_to_zero() {
+        let body = json!({ "private_metadata": { "kyc_tier": 2 } });
+        assert_eq!(extract_kyc_tier(&body), 2);
+        assert_eq!(extract_kyc_tier(&json!({ "private_metadata": {} })), 0);
+        let text = json!({ "private_metadata": { "kyc_tier": "2" } });
+        assert_eq!(extract_kyc_tier(&text), 0);
+    }
 }
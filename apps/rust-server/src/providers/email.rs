@@ -78,7 +78,7 @@ pub fn validate_email_hash(hash: &str) -> bool {
 }
 
 /// Basic RFC 5322 email validation.
-fn is_valid_email(email: &str) -> bool {
+pub(crate) fn is_valid_email(email: &str) -> bool {
     // Must contain exactly one @
     let parts: Vec<&str> = email.split('@').collect();
     if parts.len() != 2 {
@@ -0,0 +1,148 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Provider-neutral interface for fiat on-ramp payments.
+//!
+//! Each payment provider implements [`FiatProvider`]; [`OnRampProvider`]
+//! picks the implementation from the provider ID stored on a fiat request.
+
+use std::future::Future;
+
+use super::card::{CardPaymentClient, CardProviderError};
+use super::truelayer::{TrueLayerClient, TrueLayerError};
+use crate::storage::repository::ProviderCredentials;
+
+/// Provider ID of the TrueLayer open-banking integration.
+pub const TRUELAYER_PROVIDER_ID: &str = "truelayer_sandbox";
+/// Provider ID of the card payment integration.
+pub const CARD_PROVIDER_ID: &str = "card_sandbox";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProviderExecutionStatus {
+    Pending,
+    Completed,
+    Failed,
+}
+
+pub struct CreateOnRampRequest<'a> {
+    pub request_id: &'a str,
+    pub wallet_id: &'a str,
+    pub user_id: &'a str,
+    pub amount_in_minor: u64,
+    pub amount_eur: &'a str,
+    pub note: Option<&'a str>,
+    /// Where the hosted payment page sends the user afterwards.
+    pub return_uri: &'a str,
+}
+
+#[derive(Debug, Clone)]
+pub struct ProviderExecutionResult {
+    pub provider_reference: String,
+    pub provider_action_url: Option<String>,
+    pub status: ProviderExecutionStatus,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum FiatProviderError {
+    #[error(transparent)]
+    TrueLayer(#[from] TrueLayerError),
+
+    #[error(transparent)]
+    Card(#[from] CardProviderError),
+
+    #[error("Unknown fiat provider: {0}")]
+    Unknown(String),
+}
+
+/// A payment provider that can collect EUR for an on-ramp.
+pub trait FiatProvider {
+    /// Start a payment. The user completes it at `provider_action_url`.
+    fn create_onramp(
+        &self,
+        request: CreateOnRampRequest<'_>,
+    ) -> impl Future<Output = Result<ProviderExecutionResult, FiatProviderError>> + Send;
+
+    /// Current status of a payment started by `create_onramp`.
+    fn fetch_onramp_status(
+        &self,
+        provider_reference: &str,
+    ) -> impl Future<Output = Result<ProviderExecutionStatus, FiatProviderError>> + Send;
+}
+
+/// The on-ramp provider configured for a provider ID.
+pub enum OnRampProvider {
+    TrueLayer(TrueLayerClient),
+    Card(CardPaymentClient),
+}
+
+impl OnRampProvider {
+    /// Whether the provider is configured for a tenant, through its own
+    /// credentials or the environment's.
+    pub fn is_configured_for(provider_id: &str, credentials: &ProviderCredentials) -> bool {
+        match provider_id {
+            TRUELAYER_PROVIDER_ID => {
+                TrueLayerClient::is_configured_for(credentials.truelayer.as_ref())
+            }
+            CARD_PROVIDER_ID => CardPaymentClient::is_configured_for(credentials.card.as_ref()),
+            _ => false,
+        }
+    }
+
+    /// The provider using a tenant's credentials where it has them.
+    pub fn for_tenant(
+        provider_id: &str,
+        credentials: &ProviderCredentials,
+    ) -> Result<Self, FiatProviderError> {
+        match provider_id {
+            TRUELAYER_PROVIDER_ID => Ok(Self::TrueLayer(TrueLayerClient::for_tenant(
+                credentials.truelayer.as_ref(),
+            )?)),
+            CARD_PROVIDER_ID => Ok(Self::Card(CardPaymentClient::for_tenant(
+                credentials.card.as_ref(),
+            )?)),
+            other => Err(FiatProviderError::Unknown(other.to_string())),
+        }
+    }
+}
+
+impl FiatProvider for OnRampProvider {
+    async fn create_onramp(
+        &self,
+        request: CreateOnRampRequest<'_>,
+    ) -> Result<ProviderExecutionResult, FiatProviderError> {
+        match self {
+            Self::TrueLayer(client) => FiatProvider::create_onramp(client, request).await,
+            Self::Card(client) => FiatProvider::create_onramp(client, request).await,
+        }
+    }
+
+    async fn fetch_onramp_status(
+        &self,
+        provider_reference: &str,
+    ) -> Result<ProviderExecutionStatus, FiatProviderError> {
+        match self {
+            Self::TrueLayer(client) => {
+                FiatProvider::fetch_onramp_status(client, provider_reference).await
+            }
+            Self::Card(client) => {
+                FiatProvider::fetch_onramp_status(client, provider_reference).await
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unknown_provider_ids_are_rejected() {
+        let credentials = ProviderCredentials::default();
+        assert!(!OnRampProvider::is_configured_for("paypal", &credentials));
+        assert!(matches!(
+            OnRampProvider::for_tenant("paypal", &credentials),
+            Err(FiatProviderError::Unknown(_))
+        ));
+    }
+}
@@ -4,6 +4,10 @@
 
 //! External provider integrations.
 
+pub mod card;
+pub mod cctp;
 pub mod clerk;
 pub mod email;
+pub mod fiat;
+pub mod pricing;
 pub mod truelayer;
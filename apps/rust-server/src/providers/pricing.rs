@@ -0,0 +1,355 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! EUR token prices.
+//!
+//! rEUR is pegged 1:1 to the euro and never queried. AVAX comes from a
+//! CoinGecko-compatible `simple/price` endpoint, which also reports the
+//! 24-hour change from its price history. Quotes are cached in-process for
+//! [`QUOTE_TTL_SECS`] so the home screen does not hit the feed's rate limit.
+//!
+//! Set `PRICE_AVAX_EUR` to pin the AVAX price (no network calls), e.g. for
+//! local development or when the feed is unreachable from the enclave.
+//!
+//! Daily prices are persisted in the price history so transactions can be
+//! valued at the price of the day they happened rather than today's. Days
+//! missing from the history are backfilled from the feed's `coins/{id}/history`
+//! endpoint on demand.
+
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use reqwest::Client;
+use serde::Serialize;
+use serde_json::Value;
+use utoipa::ToSchema;
+
+use crate::blockchain::{same_address, tokens, NETWORK_FUJI, REUR_TOKEN};
+use crate::storage::{
+    EncryptedStorage, PriceHistories, PriceHistoryRepository, StoredTransaction, TokenType,
+};
+
+const DEFAULT_API_BASE_URL: &str = "https://api.coingecko.com/api/v3";
+/// CoinGecko coin ID for AVAX.
+const AVAX_COIN_ID: &str = "avalanche-2";
+/// How long a fetched quote set is reused.
+pub const QUOTE_TTL_SECS: i64 = 60;
+
+#[derive(Debug, thiserror::Error)]
+pub enum PriceFeedError {
+    #[error("Price feed configuration invalid: {0}")]
+    InvalidConfig(String),
+
+    #[error("Price feed request failed: {0}")]
+    Request(String),
+
+    #[error("Price feed response was invalid: {0}")]
+    InvalidResponse(String),
+
+    #[error("Price history storage failed: {0}")]
+    Storage(String),
+}
+
+/// Symbols with a EUR price, in reporting order.
+pub const PRICED_SYMBOLS: [&str; 2] = ["AVAX", "rEUR"];
+
+/// Price symbol of a transaction's token, if it is priced.
+pub fn token_symbol(token: &TokenType) -> Option<&'static str> {
+    match token {
+        TokenType::Native => Some("AVAX"),
+        TokenType::Erc20(address)
+            if tokens()
+                .by_symbol(NETWORK_FUJI, REUR_TOKEN.symbol)
+                .is_some_and(|reur| same_address(&reur.address, address)) =>
+        {
+            Some("rEUR")
+        }
+        TokenType::Erc20(_) => None,
+    }
+}
+
+/// EUR value of a transaction at the price of its UTC day, from recorded
+/// history only. `None` when the token is unpriced or the day is missing.
+pub fn value_at_tx_time(histories: &PriceHistories, tx: &StoredTransaction) -> Option<f64> {
+    let symbol = token_symbol(&tx.token)?;
+    let price = histories
+        .get(symbol)?
+        .price_on(tx.created_at.date_naive())?;
+    Some(tx.amount.parse::<f64>().ok()? * price)
+}
+
+/// EUR price of one whole token.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct PriceQuote {
+    /// Token symbol (`AVAX`, `rEUR`).
+    pub symbol: String,
+    pub price_eur: f64,
+    /// Percentage change over the last 24 hours, when the feed reports it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub change_24h_pct: Option<f64>,
+    pub as_of: DateTime<Utc>,
+}
+
+/// Quotes keyed by token symbol.
+pub type PriceQuotes = HashMap<String, PriceQuote>;
+
+#[derive(Debug, Clone)]
+pub struct PriceFeedClient {
+    api_base_url: String,
+    /// Fixed AVAX price from `PRICE_AVAX_EUR`.
+    avax_override: Option<f64>,
+    http: Client,
+}
+
+impl PriceFeedClient {
+    pub fn from_env() -> Result<Self, PriceFeedError> {
+        let api_base_url =
+            env_optional("PRICE_FEED_API_BASE_URL").unwrap_or_else(|| DEFAULT_API_BASE_URL.into());
+        let avax_override = env_optional("PRICE_AVAX_EUR")
+            .map(|v| {
+                v.parse::<f64>()
+                    .ok()
+                    .filter(|p| p.is_finite() && *p >= 0.0)
+                    .ok_or_else(|| {
+                        PriceFeedError::InvalidConfig(format!("PRICE_AVAX_EUR is not a price: {v}"))
+                    })
+            })
+            .transpose()?;
+        let http = Client::builder()
+            .timeout(Duration::from_secs(5))
+            .build()
+            .map_err(|e| PriceFeedError::Request(format!("failed to build HTTP client: {e}")))?;
+        Ok(Self {
+            api_base_url: api_base_url.trim_end_matches('/').to_string(),
+            avax_override,
+            http,
+        })
+    }
+
+    /// Current quotes for every priced token, served from cache when fresh.
+    pub async fn current_quotes(&self) -> Result<PriceQuotes, PriceFeedError> {
+        let now = Utc::now();
+        if let Some(quotes) = cached_quotes(now) {
+            return Ok(quotes);
+        }
+        let avax = match self.avax_override {
+            Some(price_eur) => PriceQuote {
+                symbol: "AVAX".to_string(),
+                price_eur,
+                change_24h_pct: None,
+                as_of: now,
+            },
+            None => self.fetch_avax(now).await?,
+        };
+        let quotes = PriceQuotes::from([
+            ("AVAX".to_string(), avax),
+            ("rEUR".to_string(), reur_quote(now)),
+        ]);
+        store_quotes(now, &quotes);
+        Ok(quotes)
+    }
+
+    /// Record today's price of every priced token in the history.
+    pub async fn record_daily_prices(
+        &self,
+        storage: &EncryptedStorage,
+    ) -> Result<usize, PriceFeedError> {
+        let quotes = self.current_quotes().await?;
+        let repo = PriceHistoryRepository::new(storage);
+        for quote in quotes.values() {
+            repo.record(&quote.symbol, quote.as_of.date_naive(), quote.price_eur)
+                .map_err(|e| PriceFeedError::Storage(e.to_string()))?;
+        }
+        Ok(quotes.len())
+    }
+
+    /// EUR price of `symbol` on `date`, from the history or, when missing,
+    /// from the feed (and then recorded). `None` for unpriced symbols.
+    pub async fn price_on(
+        &self,
+        storage: &EncryptedStorage,
+        symbol: &str,
+        date: NaiveDate,
+    ) -> Result<Option<f64>, PriceFeedError> {
+        let repo = PriceHistoryRepository::new(storage);
+        let history = repo
+            .get(symbol)
+            .map_err(|e| PriceFeedError::Storage(e.to_string()))?;
+        if let Some(price) = history.price_on(date) {
+            return Ok(Some(price));
+        }
+        let price = match symbol {
+            "rEUR" => 1.0,
+            "AVAX" => match self.avax_override {
+                Some(price) => price,
+                None if date >= Utc::now().date_naive() => {
+                    self.fetch_avax(Utc::now()).await?.price_eur
+                }
+                None => self.fetch_avax_on(date).await?,
+            },
+            _ => return Ok(None),
+        };
+        repo.record(symbol, date, price)
+            .map_err(|e| PriceFeedError::Storage(e.to_string()))?;
+        Ok(Some(price))
+    }
+
+    async fn fetch_avax_on(&self, date: NaiveDate) -> Result<f64, PriceFeedError> {
+        let url = format!("{}/coins/{AVAX_COIN_ID}/history", self.api_base_url);
+        let body: Value = self
+            .http
+            .get(&url)
+            .query(&[
+                ("date", date.format("%d-%m-%Y").to_string()),
+                ("localization", "false".to_string()),
+            ])
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| PriceFeedError::Request(e.to_string()))?
+            .json()
+            .await
+            .map_err(|e| PriceFeedError::InvalidResponse(e.to_string()))?;
+        parse_history_price(&body, date)
+    }
+
+    async fn fetch_avax(&self, now: DateTime<Utc>) -> Result<PriceQuote, PriceFeedError> {
+        let url = format!("{}/simple/price", self.api_base_url);
+        let body: Value = self
+            .http
+            .get(&url)
+            .query(&[
+                ("ids", AVAX_COIN_ID),
+                ("vs_currencies", "eur"),
+                ("include_24hr_change", "true"),
+            ])
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| PriceFeedError::Request(e.to_string()))?
+            .json()
+            .await
+            .map_err(|e| PriceFeedError::InvalidResponse(e.to_string()))?;
+        parse_simple_price(&body, AVAX_COIN_ID, "AVAX", now)
+    }
+}
+
+/// The pegged rEUR quote.
+fn reur_quote(now: DateTime<Utc>) -> PriceQuote {
+    PriceQuote {
+        symbol: "rEUR".to_string(),
+        price_eur: 1.0,
+        change_24h_pct: Some(0.0),
+        as_of: now,
+    }
+}
+
+/// Parse `{"<coin>": {"eur": 31.2, "eur_24h_change": -1.4}}`.
+fn parse_simple_price(
+    body: &Value,
+    coin_id: &str,
+    symbol: &str,
+    now: DateTime<Utc>,
+) -> Result<PriceQuote, PriceFeedError> {
+    let coin = body
+        .get(coin_id)
+        .ok_or_else(|| PriceFeedError::InvalidResponse(format!("no entry for {coin_id}")))?;
+    let price_eur = coin
+        .get("eur")
+        .and_then(Value::as_f64)
+        .ok_or_else(|| PriceFeedError::InvalidResponse(format!("no EUR price for {coin_id}")))?;
+    Ok(PriceQuote {
+        symbol: symbol.to_string(),
+        price_eur,
+        change_24h_pct: coin.get("eur_24h_change").and_then(Value::as_f64),
+        as_of: now,
+    })
+}
+
+/// Parse `{"market_data": {"current_price": {"eur": 31.2}}}`.
+fn parse_history_price(body: &Value, date: NaiveDate) -> Result<f64, PriceFeedError> {
+    body.pointer("/market_data/current_price/eur")
+        .and_then(Value::as_f64)
+        .ok_or_else(|| PriceFeedError::InvalidResponse(format!("no EUR price for {date}")))
+}
+
+/// Quotes with the time they were fetched.
+type CachedQuotes = (DateTime<Utc>, PriceQuotes);
+
+static QUOTE_CACHE: OnceLock<Mutex<Option<CachedQuotes>>> = OnceLock::new();
+
+fn quote_cache() -> &'static Mutex<Option<CachedQuotes>> {
+    QUOTE_CACHE.get_or_init(|| Mutex::new(None))
+}
+
+fn cached_quotes(now: DateTime<Utc>) -> Option<PriceQuotes> {
+    let cache = quote_cache().lock().unwrap_or_else(|e| e.into_inner());
+    cache
+        .as_ref()
+        .filter(|(fetched_at, _)| (now - *fetched_at).num_seconds() < QUOTE_TTL_SECS)
+        .map(|(_, quotes)| quotes.clone())
+}
+
+fn store_quotes(now: DateTime<Utc>, quotes: &PriceQuotes) {
+    *quote_cache().lock().unwrap_or_else(|e| e.into_inner()) = Some((now, quotes.clone()));
+}
+
+fn env_optional(name: &str) -> Option<String> {
+    std::env::var(name)
+        .ok()
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn simple_price_response_is_parsed() {
+        let body = serde_json::json!({
+            "avalanche-2": { "eur": 31.25, "eur_24h_change": -2.5 }
+        });
+        let quote = parse_simple_price(&body, AVAX_COIN_ID, "AVAX", Utc::now()).unwrap();
+        assert_eq!(quote.price_eur, 31.25);
+        assert_eq!(quote.change_24h_pct, Some(-2.5));
+
+        let missing = serde_json::json!({ "avalanche-2": {} });
+        assert!(parse_simple_price(&missing, AVAX_COIN_ID, "AVAX", Utc::now()).is_err());
+    }
+
+    #[test]
+    fn transactions_are_valued_at_the_price_of_their_day() {
+        let day = NaiveDate::from_ymd_opt(2026, 1, 10).unwrap();
+        let mut tx = StoredTransaction::new_pending(
+            "0xabc".to_string(),
+            "wallet-1".to_string(),
+            None,
+            "0x1".to_string(),
+            "0x2".to_string(),
+            "2".to_string(),
+            TokenType::Native,
+            "fuji".to_string(),
+            String::new(),
+        );
+        tx.created_at = day.and_hms_opt(12, 0, 0).unwrap().and_utc();
+        let mut histories = PriceHistories::new();
+        histories.insert(
+            "AVAX".to_string(),
+            crate::storage::repository::price_history::StoredPriceHistory {
+                symbol: "AVAX".to_string(),
+                daily_eur: [(day, 20.0), (day.succ_opt().unwrap(), 40.0)].into(),
+            },
+        );
+
+        assert_eq!(value_at_tx_time(&histories, &tx), Some(40.0));
+        tx.created_at += chrono::Duration::days(5);
+        assert_eq!(value_at_tx_time(&histories, &tx), None);
+
+        let body = serde_json::json!({ "market_data": { "current_price": { "eur": 18.5 } } });
+        assert_eq!(parse_history_price(&body, day).unwrap(), 18.5);
+    }
+}
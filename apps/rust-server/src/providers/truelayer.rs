@@ -10,6 +10,7 @@ use std::{
     time::{Duration, Instant},
 };
 
+use chrono::{DateTime, Utc};
 use reqwest::Client;
 use serde::Deserialize;
 use serde_json::{json, Value};
@@ -17,47 +18,64 @@ use tracing::info;
 use truelayer_signing::{sign_with_pem, Method};
 use uuid::Uuid;
 
+pub use super::fiat::{CreateOnRampRequest, ProviderExecutionResult, ProviderExecutionStatus};
+use super::fiat::{FiatProvider, FiatProviderError};
+use crate::canonical_json;
+use crate::storage::repository::TrueLayerCredentials;
+
 const DEFAULT_API_BASE_URL: &str = "https://api.truelayer-sandbox.com";
 const DEFAULT_AUTH_BASE_URL: &str = "https://auth.truelayer-sandbox.com";
 const DEFAULT_HOSTED_PAYMENTS_BASE_URL: &str = "https://payment.truelayer-sandbox.com";
 const DEFAULT_CURRENCY: &str = "EUR";
-const DEFAULT_HOSTED_PAYMENTS_RETURN_URI: &str = "http://localhost:3000/callback";
 const PAYMENTS_SCOPE: &str = "payments";
 /// Scope that includes merchant-accounts read access.
 const MERCHANT_ACCOUNTS_SCOPE: &str = "payments";
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum ProviderExecutionStatus {
-    Pending,
-    Completed,
-    Failed,
-}
-
-pub struct CreateOnRampRequest<'a> {
+pub struct CreateOffRampRequest<'a> {
     pub request_id: &'a str,
     pub wallet_id: &'a str,
     pub user_id: &'a str,
     pub amount_in_minor: u64,
     pub amount_eur: &'a str,
+    pub beneficiary_account_holder_name: &'a str,
+    pub beneficiary_iban: &'a str,
     pub note: Option<&'a str>,
 }
 
-pub struct CreateOffRampRequest<'a> {
+/// Payment for an on-ramp funded by an authorized mandate.
+pub struct CreateMandatePaymentRequest<'a> {
     pub request_id: &'a str,
     pub wallet_id: &'a str,
     pub user_id: &'a str,
+    pub provider_mandate_id: &'a str,
     pub amount_in_minor: u64,
     pub amount_eur: &'a str,
-    pub beneficiary_account_holder_name: &'a str,
-    pub beneficiary_iban: &'a str,
-    pub note: Option<&'a str>,
+}
+
+/// Variable recurring payment mandate to create at the provider.
+pub struct CreateMandateRequest<'a> {
+    pub mandate_id: &'a str,
+    pub wallet_id: &'a str,
+    pub user_id: &'a str,
+    pub max_single_payment_minor: u64,
+    pub max_monthly_minor: u64,
+    /// Where the hosted authorization page sends the user afterwards.
+    pub return_uri: &'a str,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProviderMandateStatus {
+    AuthorizationRequired,
+    Authorized,
+    Revoked,
+    Failed,
 }
 
 #[derive(Debug, Clone)]
-pub struct ProviderExecutionResult {
-    pub provider_reference: String,
+pub struct MandateCreationResult {
+    pub provider_mandate_id: String,
     pub provider_action_url: Option<String>,
-    pub status: ProviderExecutionStatus,
+    pub status: ProviderMandateStatus,
 }
 
 #[derive(Debug, Clone)]
@@ -65,6 +83,8 @@ pub struct OffRampStatusDetails {
     pub status: ProviderExecutionStatus,
     pub raw_status: String,
     pub scheme_id: Option<String>,
+    /// When the payout was executed, once it has been.
+    pub executed_at: Option<DateTime<Utc>>,
     pub failure_reason: Option<String>,
 }
 
@@ -134,18 +154,47 @@ impl TrueLayerClient {
             && required_env_present("TRUELAYER_MERCHANT_ACCOUNT_ID")
     }
 
+    /// Whether `credentials`, or the environment when there are none, hold a
+    /// complete account.
+    pub fn is_configured_for(credentials: Option<&TrueLayerCredentials>) -> bool {
+        credentials.is_some() || Self::is_configured()
+    }
+
     pub fn from_env() -> Result<Self, TrueLayerError> {
+        Self::for_tenant(None)
+    }
+
+    /// Client for a tenant's own account, falling back to the environment's.
+    /// Base URLs and currency always come from the environment.
+    pub fn for_tenant(credentials: Option<&TrueLayerCredentials>) -> Result<Self, TrueLayerError> {
         let api_base_url = env_or_default("TRUELAYER_API_BASE_URL", DEFAULT_API_BASE_URL);
         let auth_base_url = env_or_default("TRUELAYER_AUTH_BASE_URL", DEFAULT_AUTH_BASE_URL);
         let hosted_payments_base_url = env_or_default(
             "TRUELAYER_HOSTED_PAYMENTS_BASE_URL",
             DEFAULT_HOSTED_PAYMENTS_BASE_URL,
         );
-        let client_id = env_required("TRUELAYER_CLIENT_ID")?;
-        let client_secret = env_required("TRUELAYER_CLIENT_SECRET")?;
-        let signing_key_id = env_required("TRUELAYER_SIGNING_KEY_ID")?;
-        let signing_private_key_pem = load_signing_key_pem()?;
-        let merchant_account_id = env_required("TRUELAYER_MERCHANT_ACCOUNT_ID")?;
+        let (
+            client_id,
+            client_secret,
+            signing_key_id,
+            signing_private_key_pem,
+            merchant_account_id,
+        ) = match credentials {
+            Some(credentials) => (
+                credentials.client_id.clone(),
+                credentials.client_secret.clone(),
+                credentials.signing_key_id.clone(),
+                credentials.signing_private_key.replace("\\n", "\n"),
+                credentials.merchant_account_id.clone(),
+            ),
+            None => (
+                env_required("TRUELAYER_CLIENT_ID")?,
+                env_required("TRUELAYER_CLIENT_SECRET")?,
+                env_required("TRUELAYER_SIGNING_KEY_ID")?,
+                load_signing_key_pem()?,
+                env_required("TRUELAYER_MERCHANT_ACCOUNT_ID")?,
+            ),
+        };
         let currency = env_or_default("TRUELAYER_CURRENCY", DEFAULT_CURRENCY).to_ascii_uppercase();
 
         let http = Client::builder()
@@ -292,7 +341,7 @@ impl TrueLayerClient {
         request: CreateOnRampRequest<'_>,
     ) -> Result<ProviderExecutionResult, TrueLayerError> {
         let provider_user = build_provider_user(request.user_id);
-        let return_uri = self.resolve_return_uri();
+        let return_uri = request.return_uri;
         let merchant_account_id = self.merchant_account_id().await;
 
         let mut metadata = serde_json::Map::new();
@@ -383,12 +432,12 @@ impl TrueLayerClient {
                         &self.hosted_payments_base_url,
                         &payment_id,
                         token,
-                        Some(return_uri.as_str()),
+                        Some(return_uri),
                     )
                 })
             });
 
-        let action_url = ensure_hpp_return_uri(action_url, Some(return_uri.as_str()));
+        let action_url = ensure_hpp_return_uri(action_url, Some(return_uri));
 
         Ok(ProviderExecutionResult {
             provider_reference: payment_id,
@@ -537,6 +586,11 @@ impl TrueLayerClient {
                 .get("scheme_id")
                 .and_then(Value::as_str)
                 .map(str::to_string),
+            executed_at: response
+                .get("executed_at")
+                .and_then(Value::as_str)
+                .and_then(|raw| DateTime::parse_from_rfc3339(raw).ok())
+                .map(|ts| ts.with_timezone(&Utc)),
             failure_reason: response
                 .get("failure_reason")
                 .and_then(Value::as_str)
@@ -544,8 +598,159 @@ impl TrueLayerClient {
         })
     }
 
-    fn resolve_return_uri(&self) -> String {
-        DEFAULT_HOSTED_PAYMENTS_RETURN_URI.to_string()
+    /// Create a sweeping VRP mandate limited to the request's per-payment
+    /// and calendar-month amounts. The user authorizes it on the hosted
+    /// payment page.
+    pub async fn create_mandate(
+        &self,
+        request: CreateMandateRequest<'_>,
+    ) -> Result<MandateCreationResult, TrueLayerError> {
+        let merchant_account_id = self.merchant_account_id().await;
+        let payload = json!({
+            "mandate": {
+                "type": "sweeping",
+                "provider_selection": {
+                    "type": "user_selected"
+                },
+                "beneficiary": {
+                    "type": "merchant_account",
+                    "merchant_account_id": merchant_account_id
+                },
+                "reference": short_reference("rw-mandate", request.mandate_id)
+            },
+            "currency": self.currency,
+            "user": build_provider_user(request.user_id),
+            "constraints": {
+                "maximum_individual_amount": request.max_single_payment_minor,
+                "periodic_limits": {
+                    "month": {
+                        "maximum_amount": request.max_monthly_minor,
+                        "period_alignment": "calendar"
+                    }
+                }
+            },
+            "metadata": {
+                "mandate_id": request.mandate_id,
+                "wallet_id": request.wallet_id,
+                "user_id": request.user_id
+            }
+        });
+
+        let response = self
+            .signed_post_json("/v3/mandates", PAYMENTS_SCOPE, &payload, request.mandate_id)
+            .await?;
+
+        let provider_mandate_id = response
+            .get("id")
+            .and_then(Value::as_str)
+            .ok_or_else(|| {
+                TrueLayerError::InvalidResponse("missing mandate id in response".to_string())
+            })?
+            .to_string();
+        let status = response
+            .get("status")
+            .and_then(Value::as_str)
+            .map(map_mandate_status)
+            .unwrap_or(ProviderMandateStatus::AuthorizationRequired);
+        let provider_action_url =
+            response
+                .get("resource_token")
+                .and_then(Value::as_str)
+                .map(|token| {
+                    build_hpp_mandate_url(
+                        &self.hosted_payments_base_url,
+                        &provider_mandate_id,
+                        token,
+                        request.return_uri,
+                    )
+                });
+
+        Ok(MandateCreationResult {
+            provider_mandate_id,
+            provider_action_url,
+            status,
+        })
+    }
+
+    pub async fn fetch_mandate_status(
+        &self,
+        provider_mandate_id: &str,
+    ) -> Result<ProviderMandateStatus, TrueLayerError> {
+        let response = self
+            .get_json(
+                &format!("/v3/mandates/{provider_mandate_id}"),
+                PAYMENTS_SCOPE,
+            )
+            .await?;
+        let status = response
+            .get("status")
+            .and_then(Value::as_str)
+            .ok_or_else(|| {
+                TrueLayerError::InvalidResponse("missing mandate status in response".to_string())
+            })?;
+        Ok(map_mandate_status(status))
+    }
+
+    pub async fn revoke_mandate(&self, provider_mandate_id: &str) -> Result<(), TrueLayerError> {
+        self.signed_post_json(
+            &format!("/v3/mandates/{provider_mandate_id}/revoke"),
+            PAYMENTS_SCOPE,
+            &json!({}),
+            &Uuid::new_v4().to_string(),
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Pay an on-ramp from an authorized mandate. No user redirect is
+    /// involved; the payment then moves through the usual payment statuses.
+    pub async fn create_mandate_payment(
+        &self,
+        request: CreateMandatePaymentRequest<'_>,
+    ) -> Result<ProviderExecutionResult, TrueLayerError> {
+        let payload = json!({
+            "amount_in_minor": request.amount_in_minor,
+            "currency": self.currency,
+            "payment_method": {
+                "type": "mandate",
+                "mandate_id": request.provider_mandate_id,
+                "reference": short_reference("rw-onramp", request.request_id)
+            },
+            "metadata": {
+                "wallet_id": request.wallet_id,
+                "request_id": request.request_id,
+                "amount_eur": request.amount_eur,
+                "user_id": request.user_id
+            }
+        });
+
+        let response = self
+            .signed_post_json("/v3/payments", PAYMENTS_SCOPE, &payload, request.request_id)
+            .await?;
+
+        let payment_id = response
+            .get("id")
+            .and_then(Value::as_str)
+            .ok_or_else(|| {
+                TrueLayerError::InvalidResponse("missing payment id in response".to_string())
+            })?
+            .to_string();
+        let status = response
+            .get("status")
+            .and_then(Value::as_str)
+            .unwrap_or("authorized");
+
+        Ok(ProviderExecutionResult {
+            provider_reference: payment_id,
+            provider_action_url: None,
+            status: map_payment_status(status),
+        })
+    }
+
+    /// Check that the auth server accepts this account's credentials. A
+    /// cached access token counts as a recent success.
+    pub async fn check_reachable(&self) -> Result<(), TrueLayerError> {
+        self.access_token(PAYMENTS_SCOPE).await.map(|_| ())
     }
 
     async fn access_token(&self, scope: &str) -> Result<String, TrueLayerError> {
@@ -635,7 +840,7 @@ impl TrueLayerClient {
         idempotency_key: &str,
     ) -> Result<Value, TrueLayerError> {
         let token = self.access_token(scope).await?;
-        let body = serde_json::to_string(payload)
+        let body = canonical_json::to_string(payload)
             .map_err(|e| TrueLayerError::InvalidResponse(format!("serialize body failed: {e}")))?;
 
         let signature = sign_with_pem(
@@ -681,6 +886,22 @@ impl TrueLayerClient {
     }
 }
 
+impl FiatProvider for TrueLayerClient {
+    async fn create_onramp(
+        &self,
+        request: CreateOnRampRequest<'_>,
+    ) -> Result<ProviderExecutionResult, FiatProviderError> {
+        Ok(TrueLayerClient::create_onramp(self, request).await?)
+    }
+
+    async fn fetch_onramp_status(
+        &self,
+        provider_reference: &str,
+    ) -> Result<ProviderExecutionStatus, FiatProviderError> {
+        Ok(TrueLayerClient::fetch_onramp_status(self, provider_reference).await?)
+    }
+}
+
 pub fn map_payment_status(raw_status: &str) -> ProviderExecutionStatus {
     let status = raw_status.trim().to_ascii_lowercase();
     match status.as_str() {
@@ -699,6 +920,22 @@ pub fn map_payout_status(raw_status: &str) -> ProviderExecutionStatus {
     }
 }
 
+pub fn map_mandate_status(raw_status: &str) -> ProviderMandateStatus {
+    let status = raw_status.trim().to_ascii_lowercase();
+    match status.as_str() {
+        "authorized" => ProviderMandateStatus::Authorized,
+        "revoked" => ProviderMandateStatus::Revoked,
+        "failed" => ProviderMandateStatus::Failed,
+        _ => ProviderMandateStatus::AuthorizationRequired,
+    }
+}
+
+/// `{prefix}-{first 8 chars of id}`, short enough for scheme reference limits.
+fn short_reference(prefix: &str, id: &str) -> String {
+    let short: String = id.chars().take(8).collect();
+    format!("{prefix}-{short}")
+}
+
 /// Returns `Some(scheme_selection)` for production or `None` for sandbox.
 ///
 /// TrueLayer sandbox uses an `internal_transfer` scheme and does not
@@ -804,6 +1041,22 @@ fn build_hpp_url(
     url
 }
 
+fn build_hpp_mandate_url(
+    hosted_payments_base_url: &str,
+    mandate_id: &str,
+    resource_token: &str,
+    return_uri: &str,
+) -> String {
+    let encoded_uri: String = url::form_urlencoded::byte_serialize(return_uri.as_bytes()).collect();
+    format!(
+        "{}/mandates#mandate_id={}&resource_token={}&return_uri={}",
+        hosted_payments_base_url.trim_end_matches('/'),
+        mandate_id,
+        resource_token,
+        encoded_uri
+    )
+}
+
 fn ensure_hpp_return_uri(url: Option<String>, return_uri: Option<&str>) -> Option<String> {
     let mut url = url?;
     let Some(return_uri) = return_uri else {
@@ -873,6 +1126,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn mandate_status_mapping_is_stable() {
+        assert_eq!(
+            map_mandate_status("Authorized"),
+            ProviderMandateStatus::Authorized
+        );
+        assert_eq!(
+            map_mandate_status("revoked"),
+            ProviderMandateStatus::Revoked
+        );
+        assert_eq!(
+            map_mandate_status("authorizing"),
+            ProviderMandateStatus::AuthorizationRequired
+        );
+    }
+
+    #[test]
+    fn hpp_mandate_url_targets_mandate_page() {
+        let url = build_hpp_mandate_url(
+            "https://payment.truelayer-sandbox.com/",
+            "mandate-id",
+            "token",
+            "relationalwallet://fiat/return",
+        );
+        assert!(
+            url.starts_with("https://payment.truelayer-sandbox.com/mandates#mandate_id=mandate-id")
+        );
+        assert!(url.ends_with("return_uri=relationalwallet%3A%2F%2Ffiat%2Freturn"));
+    }
+
     #[test]
     fn normalize_user_id_passes_through_valid_uuid() {
         let user_id = "3f4d6542-b8ce-4226-93d3-80d6f14d6db2";
@@ -947,24 +1230,4 @@ mod tests {
         let payload = json!({ "id": "payout_123" });
         assert_eq!(extract_provider_status(&payload), None);
     }
-
-    #[test]
-    fn resolve_return_uri_defaults_to_callback_route() {
-        let client = TrueLayerClient {
-            api_base_url: DEFAULT_API_BASE_URL.to_string(),
-            auth_base_url: DEFAULT_AUTH_BASE_URL.to_string(),
-            hosted_payments_base_url: DEFAULT_HOSTED_PAYMENTS_BASE_URL.to_string(),
-            client_id: "id".to_string(),
-            client_secret: "secret".to_string(),
-            signing_key_id: "kid".to_string(),
-            signing_private_key_pem: "pem".to_string(),
-            merchant_account_id: "merchant".to_string(),
-            currency: "EUR".to_string(),
-            http: Client::new(),
-        };
-        assert_eq!(
-            client.resolve_return_uri(),
-            "http://localhost:3000/callback"
-        );
-    }
 }
@@ -0,0 +1,485 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! # Secret Signer
+//!
+//! Signing with the server's non-wallet secrets: the HMAC keys behind claim
+//! links and fiat return states, and the Ed25519 keys that sign outbound
+//! webhooks. Callers name a key and get a MAC or signature back; the key
+//! material stays with the backend.
+//!
+//! | Backend | `SECRET_SIGNER` | Key material |
+//! |---------|-----------------|--------------|
+//! | Enclave file | `enclave` (default) | Files in encrypted storage, created on first use |
+//! | External KMS | `kms` | Held by the KMS at `SECRET_KMS_URL` |
+//!
+//! The KMS backend speaks a small JSON-over-HTTPS protocol, authenticated
+//! with `Authorization: Bearer $SECRET_KMS_TOKEN`. Data, MACs, signatures
+//! and public keys are standard base64:
+//!
+//! | Request | Body | Response |
+//! |---------|------|----------|
+//! | `POST /keys/{name}/mac` | `{"data"}` | `{"mac"}` (HMAC-SHA256) |
+//! | `POST /keys/{name}` | `{"algorithm": "ed25519"}` | `{"public_key"}` (raw 32 bytes) |
+//! | `POST /keys/{name}/sign` | `{"data"}` | `{"signature"}` |
+//! | `DELETE /keys/{name}` | | any 2xx, or 404 |
+//!
+//! HMAC keys ([`SecretKey`]) must be provisioned in the KMS before use;
+//! Ed25519 keys are created and deleted by the server as webhook keys
+//! rotate. Keys do not move between backends: switching backends
+//! invalidates outstanding claim links and return states, and needs a
+//! webhook key rotation.
+
+use std::{sync::Arc, sync::Mutex, time::Duration};
+
+use base64ct::{Base64, Encoding};
+use hmac::{Hmac, Mac};
+use reqwest::{Client, StatusCode};
+use ring::{
+    rand::SystemRandom,
+    signature::{Ed25519KeyPair, KeyPair},
+};
+use serde::Deserialize;
+use serde_json::json;
+use sha2::Sha256;
+use thiserror::Error;
+
+use crate::config::{SECRET_KMS_TOKEN_ENV, SECRET_KMS_URL_ENV, SECRET_SIGNER_ENV};
+use crate::storage::{EncryptedStorage, StorageError};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Serializes creation of HMAC key files.
+static HMAC_KEY_LOCK: Mutex<()> = Mutex::new(());
+
+/// Errors from the secret signer.
+#[derive(Debug, Error)]
+pub enum SecretSignerError {
+    #[error("Secret signer configuration missing: {0}")]
+    MissingConfig(String),
+    #[error("Unknown secret signer backend: {0}")]
+    UnknownBackend(String),
+    #[error("Secret key not found: {0}")]
+    NotFound(String),
+    #[error("Invalid key material: {0}")]
+    InvalidKey(String),
+    #[error("Secret storage failed: {0}")]
+    Storage(#[from] StorageError),
+    #[error("KMS request failed: {0}")]
+    Kms(String),
+}
+
+/// HMAC-SHA256 keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SecretKey {
+    /// Signs claim link tokens.
+    ClaimLink,
+    /// Signs the `state` of fiat return URIs.
+    FiatReturnState,
+}
+
+impl SecretKey {
+    /// Name of the key in the KMS.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::ClaimLink => "claim-link",
+            Self::FiatReturnState => "fiat-return-state",
+        }
+    }
+}
+
+/// Where secrets are kept.
+pub enum SecretSigner {
+    EnclaveFile(EnclaveFileSigner),
+    Kms(KmsSigner),
+}
+
+impl SecretSigner {
+    /// The backend selected by `SECRET_SIGNER`.
+    pub fn from_env(storage: Arc<EncryptedStorage>) -> Result<Self, SecretSignerError> {
+        match env_optional(SECRET_SIGNER_ENV).as_deref() {
+            None | Some("enclave") => Ok(Self::enclave(storage)),
+            Some("kms") => KmsSigner::from_env().map(Self::Kms),
+            Some(other) => Err(SecretSignerError::UnknownBackend(other.to_string())),
+        }
+    }
+
+    /// Keys in files in encrypted storage.
+    pub fn enclave(storage: Arc<EncryptedStorage>) -> Self {
+        Self::EnclaveFile(EnclaveFileSigner { storage })
+    }
+
+    /// Backend name for logs.
+    pub fn backend(&self) -> &'static str {
+        match self {
+            Self::EnclaveFile(_) => "enclave",
+            Self::Kms(_) => "kms",
+        }
+    }
+
+    /// HMAC-SHA256 of `data` under `key`.
+    pub async fn mac(&self, key: SecretKey, data: &[u8]) -> Result<[u8; 32], SecretSignerError> {
+        match self {
+            Self::EnclaveFile(signer) => signer.mac(key, data),
+            Self::Kms(signer) => signer.mac(key, data).await,
+        }
+    }
+
+    /// Whether `tag` is the HMAC-SHA256 of `data` under `key`.
+    pub async fn verify_mac(
+        &self,
+        key: SecretKey,
+        data: &[u8],
+        tag: &[u8],
+    ) -> Result<bool, SecretSignerError> {
+        let expected = self.mac(key, data).await?;
+        Ok(constant_time_eq(&expected, tag))
+    }
+
+    /// Create the Ed25519 key `key_id` and return its raw public key.
+    pub async fn create_signing_key(&self, key_id: &str) -> Result<Vec<u8>, SecretSignerError> {
+        match self {
+            Self::EnclaveFile(signer) => signer.create_signing_key(key_id),
+            Self::Kms(signer) => signer.create_signing_key(key_id).await,
+        }
+    }
+
+    /// Ed25519 signature of `data` with `key_id`.
+    pub async fn sign(&self, key_id: &str, data: &[u8]) -> Result<Vec<u8>, SecretSignerError> {
+        match self {
+            Self::EnclaveFile(signer) => signer.sign(key_id, data),
+            Self::Kms(signer) => signer.sign(key_id, data).await,
+        }
+    }
+
+    /// Destroy the Ed25519 key `key_id`. Missing keys are not an error.
+    pub async fn delete_signing_key(&self, key_id: &str) -> Result<(), SecretSignerError> {
+        match self {
+            Self::EnclaveFile(signer) => signer.delete_signing_key(key_id),
+            Self::Kms(signer) => signer.delete_signing_key(key_id).await,
+        }
+    }
+}
+
+/// Secrets as files in encrypted storage. HMAC keys live under
+/// `/data/system/`; Ed25519 keys next to the webhook keyring.
+pub struct EnclaveFileSigner {
+    storage: Arc<EncryptedStorage>,
+}
+
+impl EnclaveFileSigner {
+    fn hmac_key(&self, key: SecretKey) -> Result<[u8; 32], SecretSignerError> {
+        let _guard = HMAC_KEY_LOCK
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let path = match key {
+            SecretKey::ClaimLink => self.storage.paths().claim_link_key(),
+            SecretKey::FiatReturnState => self.storage.paths().fiat_return_state_key(),
+        };
+        let mut bytes = [0u8; 32];
+        if self.storage.exists(&path) {
+            let stored = self.storage.read_raw(&path)?;
+            if stored.len() != bytes.len() {
+                return Err(SecretSignerError::InvalidKey(format!(
+                    "{} key has the wrong length",
+                    key.name()
+                )));
+            }
+            bytes.copy_from_slice(&stored);
+        } else {
+            use k256::elliptic_curve::rand_core::{OsRng, RngCore};
+            OsRng.fill_bytes(&mut bytes);
+            self.storage.write_raw(&path, &bytes)?;
+        }
+        Ok(bytes)
+    }
+
+    fn mac(&self, key: SecretKey, data: &[u8]) -> Result<[u8; 32], SecretSignerError> {
+        let mut mac = HmacSha256::new_from_slice(&self.hmac_key(key)?)
+            .expect("HMAC can take key of any size");
+        mac.update(data);
+        let mut tag = [0u8; 32];
+        tag.copy_from_slice(&mac.finalize().into_bytes());
+        Ok(tag)
+    }
+
+    fn create_signing_key(&self, key_id: &str) -> Result<Vec<u8>, SecretSignerError> {
+        let pkcs8 = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new())
+            .map_err(|e| SecretSignerError::InvalidKey(format!("key generation failed: {e}")))?;
+        let key_pair = Ed25519KeyPair::from_pkcs8(pkcs8.as_ref())
+            .map_err(|e| SecretSignerError::InvalidKey(format!("key generation failed: {e}")))?;
+        self.storage.write_raw(
+            self.storage.paths().webhook_private_key(key_id),
+            pkcs8.as_ref(),
+        )?;
+        Ok(key_pair.public_key().as_ref().to_vec())
+    }
+
+    fn sign(&self, key_id: &str, data: &[u8]) -> Result<Vec<u8>, SecretSignerError> {
+        let path = self.storage.paths().webhook_private_key(key_id);
+        if !self.storage.exists(&path) {
+            return Err(SecretSignerError::NotFound(key_id.to_string()));
+        }
+        let pkcs8 = self.storage.read_raw(path)?;
+        let key_pair = Ed25519KeyPair::from_pkcs8(&pkcs8)
+            .map_err(|e| SecretSignerError::InvalidKey(format!("{key_id}: {e}")))?;
+        Ok(key_pair.sign(data).as_ref().to_vec())
+    }
+
+    fn delete_signing_key(&self, key_id: &str) -> Result<(), SecretSignerError> {
+        let path = self.storage.paths().webhook_private_key(key_id);
+        if self.storage.exists(&path) {
+            self.storage.delete(path)?;
+        }
+        Ok(())
+    }
+}
+
+/// Secrets held by an external KMS.
+pub struct KmsSigner {
+    base_url: String,
+    token: String,
+    http: Client,
+}
+
+#[derive(Deserialize)]
+struct MacResponse {
+    mac: String,
+}
+
+#[derive(Deserialize)]
+struct PublicKeyResponse {
+    public_key: String,
+}
+
+#[derive(Deserialize)]
+struct SignatureResponse {
+    signature: String,
+}
+
+impl KmsSigner {
+    fn from_env() -> Result<Self, SecretSignerError> {
+        let base_url = env_optional(SECRET_KMS_URL_ENV)
+            .ok_or_else(|| SecretSignerError::MissingConfig(SECRET_KMS_URL_ENV.to_string()))?;
+        let token = env_optional(SECRET_KMS_TOKEN_ENV)
+            .ok_or_else(|| SecretSignerError::MissingConfig(SECRET_KMS_TOKEN_ENV.to_string()))?;
+        Self::new(base_url, token)
+    }
+
+    /// Client for the KMS at `base_url`.
+    pub fn new(base_url: String, token: String) -> Result<Self, SecretSignerError> {
+        let http = Client::builder()
+            .timeout(Duration::from_secs(10))
+            .build()
+            .map_err(|e| SecretSignerError::Kms(format!("failed to build HTTP client: {e}")))?;
+        Ok(Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            token,
+            http,
+        })
+    }
+
+    fn url(&self, name: &str, action: Option<&str>) -> String {
+        match action {
+            Some(action) => format!("{}/keys/{name}/{action}", self.base_url),
+            None => format!("{}/keys/{name}", self.base_url),
+        }
+    }
+
+    async fn post<T: for<'de> Deserialize<'de>>(
+        &self,
+        name: &str,
+        url: String,
+        body: serde_json::Value,
+    ) -> Result<T, SecretSignerError> {
+        let response = self
+            .http
+            .post(url)
+            .bearer_auth(&self.token)
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| SecretSignerError::Kms(e.to_string()))?;
+        match response.status() {
+            StatusCode::NOT_FOUND => Err(SecretSignerError::NotFound(name.to_string())),
+            status if !status.is_success() => Err(SecretSignerError::Kms(format!(
+                "{name}: KMS returned {status}"
+            ))),
+            _ => response
+                .json()
+                .await
+                .map_err(|e| SecretSignerError::Kms(format!("{name}: invalid response: {e}"))),
+        }
+    }
+
+    async fn mac(&self, key: SecretKey, data: &[u8]) -> Result<[u8; 32], SecretSignerError> {
+        let name = key.name();
+        let response: MacResponse = self
+            .post(
+                name,
+                self.url(name, Some("mac")),
+                json!({ "data": Base64::encode_string(data) }),
+            )
+            .await?;
+        decode_base64(name, &response.mac)?
+            .try_into()
+            .map_err(|_| SecretSignerError::Kms(format!("{name}: MAC is not 32 bytes")))
+    }
+
+    async fn create_signing_key(&self, key_id: &str) -> Result<Vec<u8>, SecretSignerError> {
+        let response: PublicKeyResponse = self
+            .post(
+                key_id,
+                self.url(key_id, None),
+                json!({ "algorithm": "ed25519" }),
+            )
+            .await?;
+        decode_base64(key_id, &response.public_key)
+    }
+
+    async fn sign(&self, key_id: &str, data: &[u8]) -> Result<Vec<u8>, SecretSignerError> {
+        let response: SignatureResponse = self
+            .post(
+                key_id,
+                self.url(key_id, Some("sign")),
+                json!({ "data": Base64::encode_string(data) }),
+            )
+            .await?;
+        decode_base64(key_id, &response.signature)
+    }
+
+    async fn delete_signing_key(&self, key_id: &str) -> Result<(), SecretSignerError> {
+        let response = self
+            .http
+            .delete(self.url(key_id, None))
+            .bearer_auth(&self.token)
+            .send()
+            .await
+            .map_err(|e| SecretSignerError::Kms(e.to_string()))?;
+        let status = response.status();
+        if status.is_success() || status == StatusCode::NOT_FOUND {
+            Ok(())
+        } else {
+            Err(SecretSignerError::Kms(format!(
+                "{key_id}: KMS returned {status}"
+            )))
+        }
+    }
+}
+
+fn decode_base64(name: &str, value: &str) -> Result<Vec<u8>, SecretSignerError> {
+    Base64::decode_vec(value)
+        .map_err(|_| SecretSignerError::Kms(format!("{name}: response is not base64")))
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+fn env_optional(name: &str) -> Option<String> {
+    std::env::var(name)
+        .ok()
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::StoragePaths;
+    use axum::{
+        extract::Path,
+        http::HeaderMap,
+        routing::{delete, post},
+        Json, Router,
+    };
+    use ring::signature::{UnparsedPublicKey, ED25519};
+    use serde_json::Value;
+    use tempfile::TempDir;
+
+    fn enclave_signer(dir: &TempDir) -> SecretSigner {
+        let mut storage = EncryptedStorage::new(StoragePaths::new(dir.path()));
+        storage.initialize().unwrap();
+        SecretSigner::enclave(Arc::new(storage))
+    }
+
+    #[tokio::test]
+    async fn enclave_keys_persist_and_stay_apart() {
+        let dir = TempDir::new().unwrap();
+        let signer = enclave_signer(&dir);
+        let tag = signer.mac(SecretKey::ClaimLink, b"claim-1").await.unwrap();
+        assert!(signer
+            .verify_mac(SecretKey::ClaimLink, b"claim-1", &tag)
+            .await
+            .unwrap());
+        assert!(!signer
+            .verify_mac(SecretKey::FiatReturnState, b"claim-1", &tag)
+            .await
+            .unwrap());
+        // A second signer over the same storage uses the same keys.
+        let reopened = enclave_signer(&dir);
+        assert_eq!(
+            reopened
+                .mac(SecretKey::ClaimLink, b"claim-1")
+                .await
+                .unwrap(),
+            tag
+        );
+
+        let public_key = signer.create_signing_key("whk_1").await.unwrap();
+        let signature = signer.sign("whk_1", b"payload").await.unwrap();
+        UnparsedPublicKey::new(&ED25519, &public_key)
+            .verify(b"payload", &signature)
+            .unwrap();
+        signer.delete_signing_key("whk_1").await.unwrap();
+        assert!(matches!(
+            signer.sign("whk_1", b"payload").await,
+            Err(SecretSignerError::NotFound(_))
+        ));
+        signer.delete_signing_key("whk_1").await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn kms_backend_follows_the_protocol() {
+        async fn mac(
+            Path(name): Path<String>,
+            headers: HeaderMap,
+            Json(body): Json<Value>,
+        ) -> Result<Json<Value>, axum::http::StatusCode> {
+            if headers["authorization"] != "Bearer test-token" {
+                return Err(axum::http::StatusCode::UNAUTHORIZED);
+            }
+            if name != "claim-link" {
+                return Err(axum::http::StatusCode::NOT_FOUND);
+            }
+            let data = Base64::decode_vec(body["data"].as_str().unwrap()).unwrap();
+            let mut mac = HmacSha256::new_from_slice(b"kms-key").unwrap();
+            mac.update(&data);
+            let tag = mac.finalize().into_bytes();
+            Ok(Json(json!({ "mac": Base64::encode_string(&tag) })))
+        }
+        let app = Router::new().route("/keys/{name}/mac", post(mac)).route(
+            "/keys/{name}",
+            delete(|| async { axum::http::StatusCode::NOT_FOUND }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let signer = SecretSigner::Kms(
+            KmsSigner::new(format!("http://{addr}/"), "test-token".to_string()).unwrap(),
+        );
+        let mut expected = HmacSha256::new_from_slice(b"kms-key").unwrap();
+        expected.update(b"claim-1");
+        assert_eq!(
+            signer.mac(SecretKey::ClaimLink, b"claim-1").await.unwrap()[..],
+            expected.finalize().into_bytes()[..]
+        );
+        assert!(matches!(
+            signer.mac(SecretKey::FiatReturnState, b"x").await,
+            Err(SecretSignerError::NotFound(_))
+        ));
+        signer.delete_signing_key("whk_gone").await.unwrap();
+    }
+}
@@ -35,12 +35,21 @@
 
 use std::sync::Arc;
 
+use crate::api::limits::SpendingReservations;
+use crate::api::rate_limit::RateLimiter;
+use crate::auth::client_ip::TrustedProxies;
+use crate::auth::replay::ReplayGuard;
+use crate::auth::request_signing::AdminSigningKeys;
 use crate::auth::JwksManager;
-use crate::blockchain::AvaxClient;
+use crate::blockchain::client::AvaxClientError;
+use crate::blockchain::transactions::NonceManager;
+use crate::blockchain::{AvaxClient, NetworkConfig, NETWORK_FUJI};
+use crate::events::EventBus;
 use crate::providers::clerk::ClerkClient;
+use crate::secret_signer::SecretSigner;
 use crate::storage::tx_cache::TxCache;
 use crate::storage::tx_database::TxDatabase;
-use crate::storage::EncryptedStorage;
+use crate::storage::{CacheBus, EncryptedStorage, FeatureFlagRepository};
 
 use crate::discovery::{DiscoveryClient, PeerRegistry, VoprfServerWrapper, VoprfTokenStore};
 
@@ -124,6 +133,27 @@ pub struct AppState {
     /// In-process LRU cache for hot wallet transaction lookups.
     pub tx_cache: Option<Arc<TxCache>>,
 
+    /// Cache invalidation bus; a handle to the process-wide
+    /// [`CacheBus::global`]. Invalidate through it rather than a single
+    /// cache so every cache of the address drops its entry.
+    pub cache_bus: CacheBus,
+
+    /// Operators' keys for signed admin requests. Empty disables the check.
+    pub admin_signing_keys: Arc<AdminSigningKeys>,
+
+    /// Nonces of recent state-changing requests, for replay protection.
+    pub replay_guard: Arc<ReplayGuard>,
+
+    /// Request rate limits for sensitive endpoints.
+    pub rate_limiter: Arc<RateLimiter>,
+
+    /// Proxies whose `X-Forwarded-For` entries are trusted.
+    pub trusted_proxies: Arc<TrustedProxies>,
+
+    /// Domain event bus; a handle to the process-wide
+    /// [`EventBus::global`].
+    pub events: EventBus,
+
     /// Clerk Backend API client for fetching user emails.
     ///
     /// `None` when `CLERK_SECRET_KEY` is not set (dev mode: email
@@ -136,6 +166,17 @@ pub struct AppState {
     /// Loaded from `/data/system/email_hmac_key.bin` on startup.
     pub email_hmac_key: [u8; 32],
 
+    /// Signer for webhook, claim-link and return-state keys.
+    ///
+    /// Keeps keys in encrypted storage unless an external KMS is configured.
+    pub secret_signer: Arc<SecretSigner>,
+
+    /// Per-address nonce assignment shared by every send from this process.
+    pub nonce_manager: Arc<NonceManager>,
+
+    /// Sends being checked against spending limits, per owner.
+    pub spending_reservations: Arc<SpendingReservations>,
+
     /// Shared read-only Avalanche C-Chain client (Fuji testnet).
     ///
     /// Reuses a single HTTP connection pool across all requests instead
@@ -177,11 +218,21 @@ impl AppState {
         peer_registry: Arc<PeerRegistry>,
         voprf_store: Arc<VoprfTokenStore>,
     ) -> Self {
+        let storage = Arc::new(encrypted_storage);
         Self {
-            storage: Arc::new(encrypted_storage),
+            secret_signer: Arc::new(SecretSigner::enclave(storage.clone())),
+            nonce_manager: Arc::new(NonceManager::new(storage.clone())),
+            spending_reservations: Arc::new(SpendingReservations::default()),
+            storage,
             auth_config: AuthConfig::default(),
             tx_db: None,
             tx_cache: None,
+            cache_bus: CacheBus::global(),
+            admin_signing_keys: Arc::new(AdminSigningKeys::default()),
+            replay_guard: Arc::new(ReplayGuard::default()),
+            rate_limiter: Arc::new(RateLimiter::default()),
+            trusted_proxies: Arc::new(TrustedProxies::default()),
+            events: EventBus::global(),
             clerk_client: None,
             email_hmac_key: [0u8; 32],
             avax_client: None,
@@ -225,12 +276,37 @@ impl AppState {
         self
     }
 
-    /// Configure the transaction cache.
+    /// Configure the transaction cache, subscribing it to the cache bus.
     pub fn with_tx_cache(mut self, tx_cache: Arc<TxCache>) -> Self {
+        self.cache_bus.subscribe(tx_cache.clone());
         self.tx_cache = Some(tx_cache);
         self
     }
 
+    /// Configure the operators' request signing keys.
+    pub fn with_admin_signing_keys(mut self, keys: AdminSigningKeys) -> Self {
+        self.admin_signing_keys = Arc::new(keys);
+        self
+    }
+
+    /// Configure request replay protection.
+    pub fn with_replay_guard(mut self, guard: ReplayGuard) -> Self {
+        self.replay_guard = Arc::new(guard);
+        self
+    }
+
+    /// Configure request rate limits.
+    pub fn with_rate_limiter(mut self, limiter: RateLimiter) -> Self {
+        self.rate_limiter = Arc::new(limiter);
+        self
+    }
+
+    /// Configure the proxies trusted to report client addresses.
+    pub fn with_trusted_proxies(mut self, proxies: TrustedProxies) -> Self {
+        self.trusted_proxies = Arc::new(proxies);
+        self
+    }
+
     /// Configure the Clerk Backend API client.
     pub fn with_clerk_client(mut self, clerk_client: ClerkClient) -> Self {
         self.clerk_client = Some(clerk_client);
@@ -243,12 +319,30 @@ impl AppState {
         self
     }
 
+    /// Configure the secret signer.
+    pub fn with_secret_signer(mut self, signer: SecretSigner) -> Self {
+        self.secret_signer = Arc::new(signer);
+        self
+    }
+
     /// Configure the shared Avalanche C-Chain client.
     pub fn with_avax_client(mut self, client: Arc<AvaxClient>) -> Self {
         self.avax_client = Some(client);
         self
     }
 
+    /// Read client for `network`: the shared client on Fuji when one is
+    /// configured, otherwise a new one.
+    pub async fn chain_client(
+        &self,
+        network: &NetworkConfig,
+    ) -> Result<Arc<AvaxClient>, AvaxClientError> {
+        match &self.avax_client {
+            Some(shared) if network.id == NETWORK_FUJI => Ok(shared.clone()),
+            _ => AvaxClient::new(network.clone()).await.map(Arc::new),
+        }
+    }
+
     /// Get a reference to the encrypted storage.
     ///
     /// The returned `Arc` can be cloned for use in repository constructors.
@@ -256,6 +350,12 @@ impl AppState {
         &self.storage
     }
 
+    /// Whether the feature flag `key` is on for `user_id` in `tenant_id`.
+    /// Unknown flags and storage errors count as off.
+    pub fn feature_enabled(&self, key: &str, user_id: &str, tenant_id: Option<&str>) -> bool {
+        FeatureFlagRepository::new(&self.storage).is_enabled(key, user_id, tenant_id)
+    }
+
     /// Get the authentication configuration.
     #[allow(dead_code)]
     pub fn auth_config(&self) -> &AuthConfig {
@@ -6,12 +6,39 @@
 //!
 //! All wallet operations, authentication events, and administrative
 //! actions are logged to the encrypted audit store.
+//!
+//! Events are appended to one JSONL file per day, the append-only archive.
+//! For queries they are also indexed in the [`TxDatabase`] by user, event
+//! type and resource; [`AuditRepository::sync_index`] catches the index up
+//! with the archive before a query. The index is a cache: if the database
+//! is lost it is rebuilt from the archive on the next query.
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, NaiveDate, NaiveTime, Utc};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
-use super::{EncryptedStorage, StorageResult};
+use super::tx_database::{AuditDayState, TxDatabase, TxDbError};
+use super::{EncryptedStorage, StorageError, StorageResult};
+
+/// How long after midnight a day's archive may still receive events, e.g.
+/// from requests that started before midnight. Later the day is sealed and
+/// its file never read again for indexing.
+const AUDIT_SEAL_GRACE_MINUTES: i64 = 5;
+
+/// Placeholder stored in place of secret configuration values.
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Field-name suffixes whose values never reach the audit log.
+const SECRET_FIELD_SUFFIXES: &[&str] = &[
+    "secret",
+    "password",
+    "private_key",
+    "api_key",
+    "token",
+    "mnemonic",
+    "key_share",
+    "credentials",
+];
 
 /// Types of auditable events.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
@@ -21,27 +48,236 @@ pub enum AuditEventType {
     WalletCreated,
     WalletDeleted,
     WalletAccessed,
+    WalletLocked,
+    WalletUnlockRequested,
+    WalletFirstFunded,
+    /// An admin moved the wallet to a new key, sweeping its funds.
+    WalletKeyRotated,
+    /// An admin restored a soft-deleted wallet.
+    WalletRestored,
+    /// A deleted wallet's key material was destroyed after retention.
+    WalletPurged,
+    /// Destroying a deleted wallet's key material failed.
+    WalletPurgeFailed,
 
     // Transaction events
     TransactionSigned,
     TransactionBroadcast,
+    PermitSigned,
+    SendHeld,
+    SendHoldConfirmed,
+    SendHoldCancelled,
+
+    // Claimable transfer events
+    ClaimCreated,
+    ClaimRedeemed,
+    ClaimReclaimed,
+
+    // Escrowed payment events
+    EscrowCreated,
+    EscrowReleaseRequested,
+    EscrowDisputed,
+    EscrowReleased,
+    EscrowRefunded,
+
+    // Bridge events
+    BridgeInitiated,
+    BridgeCompleted,
+    BridgeFailed,
 
     // Bookmark events
     BookmarkCreated,
     BookmarkDeleted,
 
+    // Watch-only address events
+    WatchOnlyAdded,
+    WatchOnlyRemoved,
+
     // Auth events
     AuthSuccess,
     AuthFailure,
     PermissionDenied,
+    /// A caller kept exceeding a request rate limit.
+    RateLimitExceeded,
 
     // Admin events
     AdminAccess,
+    /// The initial admin was designated with the setup token.
+    AdminBootstrapped,
+    /// The admin bootstrap was revoked, with the bootstrap admin's grant.
+    AdminBootstrapRevoked,
+    /// An admin read sensitive data; `details.fields` lists what was returned.
+    AdminDataRead,
     ConfigChanged,
+    WebhookKeyRotated,
+    ApiKeyCreated,
+    ApiKeyRevoked,
+    WebhookSubscriptionCreated,
+    WebhookSubscriptionDeleted,
+    OrphanRemoved,
 
     // Fiat events
     FiatOnRampRequested,
     FiatOffRampRequested,
+    FiatMandateCreated,
+    FiatMandateRevoked,
+    FiatBeneficiaryAdded,
+    FiatBeneficiaryRemoved,
+    FiatNameReviewRequired,
+    FiatNameReviewDecided,
+    FiatAutoTopUpConfigured,
+    FiatAutoTopUpRemoved,
+    FiatChargebackReceived,
+    FiatClawback,
+    ReserveKeyCeremony,
+}
+
+impl AuditEventType {
+    /// Every event type, in declaration order.
+    pub const ALL: [AuditEventType; 59] = [
+        AuditEventType::WalletCreated,
+        AuditEventType::WalletDeleted,
+        AuditEventType::WalletAccessed,
+        AuditEventType::WalletLocked,
+        AuditEventType::WalletUnlockRequested,
+        AuditEventType::WalletFirstFunded,
+        AuditEventType::WalletKeyRotated,
+        AuditEventType::WalletRestored,
+        AuditEventType::WalletPurged,
+        AuditEventType::WalletPurgeFailed,
+        AuditEventType::TransactionSigned,
+        AuditEventType::TransactionBroadcast,
+        AuditEventType::PermitSigned,
+        AuditEventType::SendHeld,
+        AuditEventType::SendHoldConfirmed,
+        AuditEventType::SendHoldCancelled,
+        AuditEventType::ClaimCreated,
+        AuditEventType::ClaimRedeemed,
+        AuditEventType::ClaimReclaimed,
+        AuditEventType::EscrowCreated,
+        AuditEventType::EscrowReleaseRequested,
+        AuditEventType::EscrowDisputed,
+        AuditEventType::EscrowReleased,
+        AuditEventType::EscrowRefunded,
+        AuditEventType::BridgeInitiated,
+        AuditEventType::BridgeCompleted,
+        AuditEventType::BridgeFailed,
+        AuditEventType::BookmarkCreated,
+        AuditEventType::BookmarkDeleted,
+        AuditEventType::WatchOnlyAdded,
+        AuditEventType::WatchOnlyRemoved,
+        AuditEventType::AuthSuccess,
+        AuditEventType::AuthFailure,
+        AuditEventType::PermissionDenied,
+        AuditEventType::RateLimitExceeded,
+        AuditEventType::AdminAccess,
+        AuditEventType::AdminBootstrapped,
+        AuditEventType::AdminBootstrapRevoked,
+        AuditEventType::AdminDataRead,
+        AuditEventType::ConfigChanged,
+        AuditEventType::WebhookKeyRotated,
+        AuditEventType::ApiKeyCreated,
+        AuditEventType::ApiKeyRevoked,
+        AuditEventType::WebhookSubscriptionCreated,
+        AuditEventType::WebhookSubscriptionDeleted,
+        AuditEventType::OrphanRemoved,
+        AuditEventType::FiatOnRampRequested,
+        AuditEventType::FiatOffRampRequested,
+        AuditEventType::FiatMandateCreated,
+        AuditEventType::FiatMandateRevoked,
+        AuditEventType::FiatBeneficiaryAdded,
+        AuditEventType::FiatBeneficiaryRemoved,
+        AuditEventType::FiatNameReviewRequired,
+        AuditEventType::FiatNameReviewDecided,
+        AuditEventType::FiatAutoTopUpConfigured,
+        AuditEventType::FiatAutoTopUpRemoved,
+        AuditEventType::FiatChargebackReceived,
+        AuditEventType::FiatClawback,
+        AuditEventType::ReserveKeyCeremony,
+    ];
+
+    /// Wire name, e.g. `wallet_created`.
+    pub fn name(&self) -> String {
+        serde_json::to_value(self)
+            .ok()
+            .and_then(|v| v.as_str().map(str::to_string))
+            .unwrap_or_default()
+    }
+
+    /// One-line description, as published in the event catalog.
+    pub fn description(&self) -> &'static str {
+        match self {
+            AuditEventType::WalletCreated => "New wallet generated",
+            AuditEventType::WalletDeleted => "Wallet soft-deleted",
+            AuditEventType::WalletAccessed => "Wallet metadata read",
+            AuditEventType::WalletLocked => "Wallet locked by its owner",
+            AuditEventType::WalletUnlockRequested => "Owner asked to unlock a locked wallet",
+            AuditEventType::WalletFirstFunded => "Wallet received its first incoming transfer",
+            AuditEventType::WalletKeyRotated => {
+                "Wallet key rotated and funds swept to the new address"
+            }
+            AuditEventType::WalletRestored => "Soft-deleted wallet restored by an admin",
+            AuditEventType::WalletPurged => {
+                "Deleted wallet's key material destroyed after its retention period"
+            }
+            AuditEventType::WalletPurgeFailed => {
+                "Destroying a deleted wallet's key material failed; retried next sweep"
+            }
+            AuditEventType::TransactionSigned => "Transaction signed inside the enclave",
+            AuditEventType::TransactionBroadcast => "Transaction sent to chain",
+            AuditEventType::PermitSigned => "EIP-2612 or Permit2 approval signed for a spender",
+            AuditEventType::SendHeld => "Send from an unfamiliar session held for confirmation",
+            AuditEventType::SendHoldConfirmed => "Held send confirmed and broadcast",
+            AuditEventType::SendHoldCancelled => "Held send cancelled",
+            AuditEventType::ClaimCreated => "Claimable transfer funded into escrow",
+            AuditEventType::ClaimRedeemed => "Claimable transfer paid out to its recipient",
+            AuditEventType::ClaimReclaimed => "Expired claimable transfer returned to the sender",
+            AuditEventType::EscrowCreated => "Escrowed payment funded",
+            AuditEventType::EscrowReleaseRequested => "Payee asked for an escrowed payment",
+            AuditEventType::EscrowDisputed => "Payer contested a release request",
+            AuditEventType::EscrowReleased => "Escrowed payment paid to the payee",
+            AuditEventType::EscrowRefunded => "Escrowed payment returned to the payer",
+            AuditEventType::BridgeInitiated => {
+                "USDC burned on the source chain of a cross-chain transfer"
+            }
+            AuditEventType::BridgeCompleted => "Bridged USDC minted on the destination chain",
+            AuditEventType::BridgeFailed => "A leg of a cross-chain transfer reverted",
+            AuditEventType::BookmarkCreated => "Bookmark added",
+            AuditEventType::BookmarkDeleted => "Bookmark removed",
+            AuditEventType::WatchOnlyAdded => "Watch-only address added",
+            AuditEventType::WatchOnlyRemoved => "Watch-only address removed",
+            AuditEventType::AuthSuccess => "Successful authentication",
+            AuditEventType::AuthFailure => "Failed authentication attempt",
+            AuditEventType::PermissionDenied => "Unauthorized access attempt",
+            AuditEventType::RateLimitExceeded => "Request rate limit repeatedly exceeded",
+            AuditEventType::AdminAccess => "Admin endpoint accessed",
+            AuditEventType::AdminBootstrapped => "Initial admin designated with the setup token",
+            AuditEventType::AdminBootstrapRevoked => "Admin bootstrap and its admin grant revoked",
+            AuditEventType::AdminDataRead => "Admin read sensitive data",
+            AuditEventType::ConfigChanged => {
+                "Configuration modified, with old and new values (secrets redacted)"
+            }
+            AuditEventType::WebhookKeyRotated => "Webhook signing key rotated",
+            AuditEventType::ApiKeyCreated => "Machine-to-machine API key minted",
+            AuditEventType::ApiKeyRevoked => "Machine-to-machine API key revoked",
+            AuditEventType::WebhookSubscriptionCreated => "Webhook subscription registered",
+            AuditEventType::WebhookSubscriptionDeleted => "Webhook subscription removed",
+            AuditEventType::OrphanRemoved => "Orphaned storage artifact removed by the sweeper",
+            AuditEventType::FiatOnRampRequested => "Fiat deposit initiated",
+            AuditEventType::FiatOffRampRequested => "Fiat withdrawal initiated",
+            AuditEventType::FiatMandateCreated => "Payment mandate created",
+            AuditEventType::FiatMandateRevoked => "Payment mandate revoked",
+            AuditEventType::FiatBeneficiaryAdded => "Withdrawal beneficiary saved",
+            AuditEventType::FiatBeneficiaryRemoved => "Withdrawal beneficiary removed",
+            AuditEventType::FiatNameReviewRequired => "Withdrawal held for beneficiary name review",
+            AuditEventType::FiatNameReviewDecided => "Admin decided a beneficiary name review",
+            AuditEventType::FiatAutoTopUpConfigured => "Auto top-up rule created or changed",
+            AuditEventType::FiatAutoTopUpRemoved => "Auto top-up rule removed",
+            AuditEventType::FiatChargebackReceived => "Card payment charged back by the provider",
+            AuditEventType::FiatClawback => "rEUR clawed back after a chargeback",
+            AuditEventType::ReserveKeyCeremony => "Reserve key ceremony step",
+        }
+    }
 }
 
 /// An audit log entry.
@@ -112,6 +348,33 @@ impl AuditEvent {
         self
     }
 
+    /// A `ConfigChanged` event recording a setting before and after the
+    /// change. `None` marks a setting that was created or removed. Secret
+    /// fields are redacted; `changed` lists the top-level fields that differ,
+    /// secrets included, without their values.
+    pub fn config_changed(old: Option<serde_json::Value>, new: Option<serde_json::Value>) -> Self {
+        let changed: Vec<String> = match (&old, &new) {
+            (Some(serde_json::Value::Object(before)), Some(serde_json::Value::Object(after))) => {
+                let mut keys: Vec<String> = before
+                    .keys()
+                    .chain(after.keys())
+                    .filter(|key| before.get(*key) != after.get(*key))
+                    .cloned()
+                    .collect();
+                keys.sort();
+                keys.dedup();
+                keys
+            }
+            _ => Vec::new(),
+        };
+        let details = serde_json::json!({
+            "old": old.map(redact_secrets),
+            "new": new.map(redact_secrets),
+            "changed": changed,
+        });
+        Self::new(AuditEventType::ConfigChanged).with_details(details)
+    }
+
     /// Add details.
     pub fn with_details(mut self, details: serde_json::Value) -> Self {
         self.details = Some(details);
@@ -128,6 +391,57 @@ impl AuditEvent {
     }
 }
 
+/// A filtered, paginated query over indexed audit events.
+#[derive(Debug, Clone, Default)]
+pub struct AuditQuery {
+    /// Earliest event time (inclusive).
+    pub from: DateTime<Utc>,
+    /// Latest event time (exclusive).
+    pub to: DateTime<Utc>,
+    pub user_id: Option<String>,
+    /// Event type wire name, e.g. `wallet_created`.
+    pub event_type: Option<String>,
+    pub resource_type: Option<String>,
+    pub resource_id: Option<String>,
+    /// Cursor from the previous page's `next_cursor`.
+    pub cursor: Option<String>,
+    /// Matches to skip after the cursor.
+    pub offset: usize,
+    pub limit: usize,
+}
+
+/// One page of audit query results.
+#[derive(Debug, Clone)]
+pub struct AuditPage {
+    /// Matching events, oldest first.
+    pub events: Vec<AuditEvent>,
+    /// All matches in the time range.
+    pub total: usize,
+    /// Cursor for the next page; `None` on the last page.
+    pub next_cursor: Option<String>,
+}
+
+/// Replace the values of secret-looking fields, at any depth, with
+/// [`REDACTED`].
+pub fn redact_secrets(value: serde_json::Value) -> serde_json::Value {
+    match value {
+        serde_json::Value::Object(map) => map
+            .into_iter()
+            .map(|(key, value)| {
+                let lower = key.to_ascii_lowercase();
+                let value = if SECRET_FIELD_SUFFIXES.iter().any(|s| lower.ends_with(s)) {
+                    serde_json::Value::String(REDACTED.to_string())
+                } else {
+                    redact_secrets(value)
+                };
+                (key, value)
+            })
+            .collect(),
+        serde_json::Value::Array(items) => items.into_iter().map(redact_secrets).collect(),
+        other => other,
+    }
+}
+
 /// Repository for audit events.
 pub struct AuditRepository<'a> {
     storage: &'a EncryptedStorage,
@@ -224,6 +538,66 @@ impl<'a> AuditRepository<'a> {
         Ok(all_events)
     }
 
+    /// Index the archive days from `from` to `to` that are not indexed yet
+    /// or may have grown since, returning the number of events indexed.
+    ///
+    /// Only new lines are indexed. Days over for longer than the grace
+    /// period are sealed, so an indexed range is not re-read; days without
+    /// events or after `now` are skipped. Unreadable lines are logged and
+    /// skipped.
+    pub fn sync_index(
+        &self,
+        db: &TxDatabase,
+        from: NaiveDate,
+        to: NaiveDate,
+        now: DateTime<Utc>,
+    ) -> StorageResult<usize> {
+        let mut indexed = 0;
+        let last = to.min(now.date_naive());
+        for day in from.iter_days().take_while(|day| *day <= last) {
+            let date = day.format("%Y-%m-%d").to_string();
+            let lines = match db.audit_day_state(&date).map_err(index_error)? {
+                AuditDayState::Sealed => continue,
+                AuditDayState::Indexed { lines } => lines as usize,
+            };
+            let path = self.storage.paths().audit_events_file(&date);
+            if !self.storage.exists(&path) {
+                continue;
+            }
+            let day_end = (day + Duration::days(1)).and_time(NaiveTime::MIN).and_utc();
+            let sealed = now >= day_end + Duration::minutes(AUDIT_SEAL_GRACE_MINUTES);
+
+            let content = self.storage.read_raw(&path)?;
+            let content = String::from_utf8_lossy(&content);
+            let all: Vec<&str> = content.lines().filter(|l| !l.trim().is_empty()).collect();
+            if all.len() <= lines && !sealed {
+                continue;
+            }
+            let events: Vec<AuditEvent> = all
+                .iter()
+                .skip(lines)
+                .filter_map(|line| {
+                    serde_json::from_str(line)
+                        .map_err(|e| {
+                            tracing::warn!(date = %date, error = %e, "Skipping unreadable audit event")
+                        })
+                        .ok()
+                })
+                .collect();
+            let state = if sealed {
+                AuditDayState::Sealed
+            } else {
+                AuditDayState::Indexed {
+                    lines: all.len() as u64,
+                }
+            };
+            db.index_audit_events(&date, &events, state)
+                .map_err(index_error)?;
+            indexed += events.len();
+        }
+        Ok(indexed)
+    }
+
     /// Search events by user ID.
     /// TODO: Use when implementing user-specific audit views
     #[allow(dead_code)]
@@ -255,6 +629,10 @@ impl<'a> AuditRepository<'a> {
     }
 }
 
+fn index_error(e: TxDbError) -> StorageError {
+    StorageError::Io(std::io::Error::other(format!("audit index: {e}")))
+}
+
 /// Helper macro for logging audit events.
 #[macro_export]
 macro_rules! audit_log {
@@ -300,6 +678,36 @@ mod tests {
         assert!(event.success);
     }
 
+    #[test]
+    fn config_changes_redact_secrets() {
+        let event = AuditEvent::config_changed(
+            Some(serde_json::json!({
+                "url": "https://old.example",
+                "api_key": "sk-old",
+                "auth": { "client_secret": "s1", "client_id": "c1" },
+            })),
+            Some(serde_json::json!({
+                "url": "https://new.example",
+                "api_key": "sk-new",
+                "auth": { "client_secret": "s2", "client_id": "c1" },
+            })),
+        );
+        assert_eq!(event.event_type, AuditEventType::ConfigChanged);
+        let details = event.details.unwrap();
+        assert_eq!(details["old"]["url"], "https://old.example");
+        assert_eq!(details["new"]["api_key"], REDACTED);
+        assert_eq!(details["new"]["auth"]["client_secret"], REDACTED);
+        assert_eq!(details["new"]["auth"]["client_id"], "c1");
+        assert_eq!(
+            details["changed"],
+            serde_json::json!(["api_key", "auth", "url"])
+        );
+        assert!(!details.to_string().contains("sk-"));
+
+        let removed = AuditEvent::config_changed(Some(serde_json::json!({ "a": 1 })), None);
+        assert!(removed.details.unwrap()["new"].is_null());
+    }
+
     #[test]
     fn failed_event() {
         let event = AuditEvent::new(AuditEventType::PermissionDenied)
@@ -393,4 +801,49 @@ mod tests {
 
         assert_eq!(events.len(), 2);
     }
+
+    #[test]
+    fn sync_index_reads_only_new_lines_and_seals_past_days() {
+        let (temp, storage) = setup();
+        let db = TxDatabase::open(&temp.path().join("tx.redb")).unwrap();
+        let repo = AuditRepository::new(&storage);
+        let day = NaiveDate::from_ymd_opt(2026, 3, 1).unwrap();
+        let at = |hour| day.and_hms_opt(hour, 0, 0).unwrap().and_utc();
+        let log_at = |hour| {
+            let mut event = AuditEvent::new(AuditEventType::WalletCreated).with_user("user_1");
+            event.timestamp = at(hour);
+            repo.log(&event).unwrap();
+        };
+
+        log_at(9);
+        log_at(10);
+        assert_eq!(repo.sync_index(&db, day, day, at(12)).unwrap(), 2);
+        assert_eq!(repo.sync_index(&db, day, day, at(13)).unwrap(), 0);
+        log_at(14);
+        assert_eq!(repo.sync_index(&db, day, day, at(15)).unwrap(), 1);
+        assert_eq!(
+            db.audit_day_state("2026-03-01").unwrap(),
+            AuditDayState::Indexed { lines: 3 }
+        );
+
+        let next_day = day.succ_opt().unwrap();
+        let sealed_at = next_day.and_hms_opt(1, 0, 0).unwrap().and_utc();
+        assert_eq!(repo.sync_index(&db, day, next_day, sealed_at).unwrap(), 0);
+        assert_eq!(
+            db.audit_day_state("2026-03-01").unwrap(),
+            AuditDayState::Sealed
+        );
+
+        let page = db
+            .query_audit_events(&AuditQuery {
+                from: at(0),
+                to: at(23),
+                user_id: Some("user_1".to_string()),
+                limit: 10,
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(page.total, 3);
+        assert_eq!(page.events[2].timestamp, at(14));
+    }
 }
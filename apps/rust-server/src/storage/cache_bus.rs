@@ -0,0 +1,198 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Cache invalidation shared by the indexer and the API.
+//!
+//! In-process caches of per-address data ([`TxCache`](super::TxCache), the
+//! [historical balance cache](crate::blockchain::history)) subscribe to one
+//! [`CacheBus`]. Whoever changes an address's data (the indexer storing a
+//! transfer, a handler recording a send) invalidates it on the bus by
+//! address and resource kind, and every cache holding that resource drops
+//! its entry before the call returns.
+//!
+//! Invalidation alone leaves a race: a reader that loaded the old data
+//! just before the change can put it back into the cache just after. So
+//! the bus also counts invalidations per (address, resource): readers take
+//! an [`CacheEpoch`] before reading the underlying data and only cache the
+//! result if [`CacheBus::is_current`] still holds.
+
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, OnceLock, RwLock,
+    },
+};
+
+/// Epoch counters; keys sharing a stripe invalidate each other's epochs,
+/// which costs a cache miss, never a stale read.
+const EPOCH_STRIPES: usize = 256;
+
+/// Kind of per-address data a cache holds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CacheResource {
+    /// Transaction history pages.
+    TransactionHistory,
+    /// On-chain balances.
+    Balance,
+}
+
+impl CacheResource {
+    pub const ALL: [CacheResource; 2] = [Self::TransactionHistory, Self::Balance];
+}
+
+/// A cache that drops entries when the bus says they changed.
+pub trait CacheSubscriber: Send + Sync {
+    /// Drop what is cached for `address` of kind `resource`. Called
+    /// synchronously; must not block.
+    fn invalidate(&self, address: &str, resource: CacheResource);
+}
+
+/// Invalidation count of one (address, resource) when a read started.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CacheEpoch {
+    stripe: usize,
+    value: u64,
+}
+
+struct Inner {
+    subscribers: RwLock<Vec<Arc<dyn CacheSubscriber>>>,
+    epochs: [AtomicU64; EPOCH_STRIPES],
+}
+
+/// Handle to the invalidation bus. Cheap to clone.
+#[derive(Clone)]
+pub struct CacheBus {
+    inner: Arc<Inner>,
+}
+
+static GLOBAL: OnceLock<CacheBus> = OnceLock::new();
+
+impl CacheBus {
+    /// A bus of its own, for tests and scratch indexers.
+    pub fn new() -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                subscribers: RwLock::new(Vec::new()),
+                epochs: std::array::from_fn(|_| AtomicU64::new(0)),
+            }),
+        }
+    }
+
+    /// The process-wide bus.
+    pub fn global() -> Self {
+        GLOBAL.get_or_init(Self::new).clone()
+    }
+
+    /// Have `cache` invalidated from now on.
+    pub fn subscribe(&self, cache: Arc<dyn CacheSubscriber>) {
+        self.inner
+            .subscribers
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(cache);
+    }
+
+    fn stripe(address: &str, resource: CacheResource) -> usize {
+        let mut hasher = DefaultHasher::new();
+        address.to_lowercase().hash(&mut hasher);
+        resource.hash(&mut hasher);
+        (hasher.finish() % EPOCH_STRIPES as u64) as usize
+    }
+
+    /// Current epoch of `address`'s `resource`; take it before reading the
+    /// data to be cached.
+    pub fn epoch(&self, address: &str, resource: CacheResource) -> CacheEpoch {
+        let stripe = Self::stripe(address, resource);
+        CacheEpoch {
+            stripe,
+            value: self.inner.epochs[stripe].load(Ordering::Acquire),
+        }
+    }
+
+    /// Whether nothing was invalidated since `epoch` was taken, so data
+    /// read after it may be cached.
+    pub fn is_current(&self, epoch: CacheEpoch) -> bool {
+        self.inner.epochs[epoch.stripe].load(Ordering::Acquire) == epoch.value
+    }
+
+    /// `address`'s `resource` changed: drop it from every cache.
+    pub fn invalidate(&self, address: &str, resource: CacheResource) {
+        // Bump first, so readers that finish after this point do not cache.
+        self.inner.epochs[Self::stripe(address, resource)].fetch_add(1, Ordering::AcqRel);
+        let subscribers = self
+            .inner
+            .subscribers
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone();
+        for cache in subscribers {
+            cache.invalidate(address, resource);
+        }
+    }
+
+    /// Everything about `address` changed, e.g. a transfer was indexed.
+    pub fn invalidate_address(&self, address: &str) {
+        for resource in CacheResource::ALL {
+            self.invalidate(address, resource);
+        }
+    }
+}
+
+impl Default for CacheBus {
+    fn default() -> Self {
+        Self::global()
+    }
+}
+
+impl std::fmt::Debug for CacheBus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let subscribers = self
+            .inner
+            .subscribers
+            .read()
+            .map_or(0, |subscribers| subscribers.len());
+        f.debug_struct("CacheBus")
+            .field("subscribers", &subscribers)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct Recorder(Mutex<Vec<(String, CacheResource)>>);
+
+    impl CacheSubscriber for Recorder {
+        fn invalidate(&self, address: &str, resource: CacheResource) {
+            self.0.lock().unwrap().push((address.to_string(), resource));
+        }
+    }
+
+    #[test]
+    fn subscribers_are_invalidated_and_epochs_move() {
+        let bus = CacheBus::new();
+        let recorder = Arc::new(Recorder::default());
+        bus.subscribe(recorder.clone());
+
+        let history = bus.epoch("0xABCD", CacheResource::TransactionHistory);
+        let balance = bus.epoch("0xabcd", CacheResource::Balance);
+        bus.invalidate("0xAbCd", CacheResource::TransactionHistory);
+        assert!(!bus.is_current(history));
+        assert_eq!(
+            recorder.0.lock().unwrap().as_slice(),
+            &[("0xAbCd".to_string(), CacheResource::TransactionHistory)]
+        );
+
+        bus.clone().invalidate_address("0xabcd");
+        assert!(!bus.is_current(balance));
+        assert_eq!(recorder.0.lock().unwrap().len(), 3);
+        let fresh = bus.epoch("0xabcd", CacheResource::Balance);
+        assert!(bus.is_current(fresh));
+    }
+}
@@ -259,8 +259,6 @@ impl EncryptedStorage {
     }
 
     /// Delete a directory and all its contents.
-    /// TODO: Use for wallet hard delete (after retention period)
-    #[allow(dead_code)]
     pub fn delete_dir(&self, path: impl AsRef<Path>) -> StorageResult<()> {
         if !self.initialized {
             return Err(StorageError::NotInitialized);
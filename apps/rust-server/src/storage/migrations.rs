@@ -0,0 +1,312 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Schema versions and startup migrations.
+//!
+//! Each storage subsystem records the schema version its data was last
+//! written with in `/data/system/schema/{subsystem}.json`. At startup the
+//! registered migrations newer than that version run in order, and the
+//! version file is advanced after each one so an interrupted upgrade resumes
+//! where it stopped. An install without a version file predates versioning
+//! and is treated as version 0.
+//!
+//! Data written by a newer release is never touched: if the stored version
+//! is above the highest registered migration, startup is refused so a
+//! rollback cannot silently misread or overwrite it.
+//!
+//! To change a record shape or table layout, append a migration with the
+//! next version number to the subsystem's registry.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{EncryptedStorage, StorageError, TxDatabase};
+use crate::storage::tx_database::TxDbError;
+
+/// Subsystem name of the JSON records under `/data`.
+pub const STORAGE_SUBSYSTEM: &str = "storage";
+/// Subsystem name of the redb transaction database (`/data/tx.redb`).
+pub const TX_DATABASE_SUBSYSTEM: &str = "tx_database";
+
+#[derive(Debug, thiserror::Error)]
+pub enum MigrationError {
+    #[error(
+        "{subsystem} schema version {found} is newer than this build supports ({supported}); \
+         refusing to start"
+    )]
+    FutureVersion {
+        subsystem: String,
+        found: u32,
+        supported: u32,
+    },
+
+    #[error("{subsystem} migration {version} ({name}) failed: {reason}")]
+    Failed {
+        subsystem: String,
+        version: u32,
+        name: &'static str,
+        reason: String,
+    },
+
+    #[error("storage error: {0}")]
+    Storage(#[from] StorageError),
+
+    #[error("transaction database error: {0}")]
+    TxDatabase(#[from] TxDbError),
+}
+
+/// One ordered step of a subsystem's schema history.
+pub struct Migration<T: ?Sized> {
+    /// Version the data is at once this step has run.
+    pub version: u32,
+    pub name: &'static str,
+    pub run: fn(&T) -> Result<(), MigrationError>,
+}
+
+/// Contents of a schema version file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SchemaVersion {
+    pub subsystem: String,
+    pub version: u32,
+    pub updated_at: DateTime<Utc>,
+    #[serde(default)]
+    pub applied: Vec<AppliedMigration>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppliedMigration {
+    pub version: u32,
+    pub name: String,
+    pub applied_at: DateTime<Utc>,
+}
+
+/// Versions a subsystem moved between at startup.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MigrationOutcome {
+    pub from: u32,
+    pub to: u32,
+}
+
+fn baseline<T: ?Sized>(_: &T) -> Result<(), MigrationError> {
+    Ok(())
+}
+
+/// Migrations of the JSON records, in version order.
+pub const STORAGE_MIGRATIONS: &[Migration<EncryptedStorage>] = &[Migration {
+    version: 1,
+    name: "baseline",
+    run: baseline,
+}];
+
+/// Migrations of the redb table layout, in version order.
+pub const TX_DATABASE_MIGRATIONS: &[Migration<TxDatabase>] = &[Migration {
+    version: 1,
+    name: "baseline",
+    run: baseline,
+}];
+
+/// Bring the JSON records up to the current schema.
+pub fn migrate_storage(storage: &EncryptedStorage) -> Result<MigrationOutcome, MigrationError> {
+    apply(storage, STORAGE_SUBSYSTEM, storage, STORAGE_MIGRATIONS)
+}
+
+/// Bring the transaction database up to the current schema.
+pub fn migrate_tx_database(
+    storage: &EncryptedStorage,
+    tx_db: &TxDatabase,
+) -> Result<MigrationOutcome, MigrationError> {
+    apply(
+        storage,
+        TX_DATABASE_SUBSYSTEM,
+        tx_db,
+        TX_DATABASE_MIGRATIONS,
+    )
+}
+
+/// The stored schema version of a subsystem, or `None` before versioning.
+pub fn read_version(
+    storage: &EncryptedStorage,
+    subsystem: &str,
+) -> Result<Option<SchemaVersion>, StorageError> {
+    let path = storage.paths().schema_version(subsystem);
+    if !storage.exists(&path) {
+        return Ok(None);
+    }
+    storage.read_json(&path).map(Some)
+}
+
+fn apply<T: ?Sized>(
+    storage: &EncryptedStorage,
+    subsystem: &str,
+    target: &T,
+    migrations: &[Migration<T>],
+) -> Result<MigrationOutcome, MigrationError> {
+    let supported = migrations.last().map_or(0, |m| m.version);
+    let mut record = read_version(storage, subsystem)?.unwrap_or_else(|| SchemaVersion {
+        subsystem: subsystem.to_string(),
+        version: 0,
+        updated_at: Utc::now(),
+        applied: Vec::new(),
+    });
+    let from = record.version;
+    if from > supported {
+        return Err(MigrationError::FutureVersion {
+            subsystem: subsystem.to_string(),
+            found: from,
+            supported,
+        });
+    }
+
+    let path = storage.paths().schema_version(subsystem);
+    for migration in migrations.iter().filter(|m| m.version > from) {
+        (migration.run)(target).map_err(|e| MigrationError::Failed {
+            subsystem: subsystem.to_string(),
+            version: migration.version,
+            name: migration.name,
+            reason: e.to_string(),
+        })?;
+        let now = Utc::now();
+        record.version = migration.version;
+        record.updated_at = now;
+        record.applied.push(AppliedMigration {
+            version: migration.version,
+            name: migration.name.to_string(),
+            applied_at: now,
+        });
+        storage.write_json(&path, &record)?;
+        tracing::info!(
+            subsystem,
+            version = migration.version,
+            name = migration.name,
+            "Applied schema migration"
+        );
+    }
+
+    Ok(MigrationOutcome {
+        from,
+        to: record.version,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::StoragePaths;
+    use std::sync::Mutex;
+    use tempfile::TempDir;
+
+    fn test_storage() -> (TempDir, EncryptedStorage) {
+        let dir = TempDir::new().unwrap();
+        let mut storage = EncryptedStorage::new(StoragePaths::new(dir.path()));
+        storage.initialize().unwrap();
+        (dir, storage)
+    }
+
+    #[test]
+    fn registries_are_strictly_ordered_from_one() {
+        fn check<T: ?Sized>(migrations: &[Migration<T>]) {
+            for (index, migration) in migrations.iter().enumerate() {
+                assert_eq!(migration.version as usize, index + 1, "{}", migration.name);
+            }
+        }
+        check(STORAGE_MIGRATIONS);
+        check(TX_DATABASE_MIGRATIONS);
+    }
+
+    #[test]
+    fn pending_migrations_run_in_order_once() {
+        static RAN: Mutex<Vec<u32>> = Mutex::new(Vec::new());
+        let migrations: &[Migration<()>] = &[
+            Migration {
+                version: 1,
+                name: "first",
+                run: |_| {
+                    RAN.lock().unwrap().push(1);
+                    Ok(())
+                },
+            },
+            Migration {
+                version: 2,
+                name: "second",
+                run: |_| {
+                    RAN.lock().unwrap().push(2);
+                    Ok(())
+                },
+            },
+        ];
+        let (_dir, storage) = test_storage();
+
+        let outcome = apply(&storage, "test", &(), migrations).unwrap();
+        assert_eq!(outcome, MigrationOutcome { from: 0, to: 2 });
+        assert_eq!(*RAN.lock().unwrap(), vec![1, 2]);
+
+        let outcome = apply(&storage, "test", &(), migrations).unwrap();
+        assert_eq!(outcome, MigrationOutcome { from: 2, to: 2 });
+        assert_eq!(RAN.lock().unwrap().len(), 2);
+
+        let stored = read_version(&storage, "test").unwrap().unwrap();
+        assert_eq!(stored.version, 2);
+        assert_eq!(stored.applied.len(), 2);
+    }
+
+    #[test]
+    fn unversioned_installs_are_stamped_current() {
+        let (dir, storage) = test_storage();
+        let tx_db = TxDatabase::open(&dir.path().join("tx.redb")).unwrap();
+
+        let outcome = migrate_tx_database(&storage, &tx_db).unwrap();
+        assert_eq!(outcome.from, 0);
+        assert_eq!(outcome.to, TX_DATABASE_MIGRATIONS.last().unwrap().version);
+        let stored = read_version(&storage, TX_DATABASE_SUBSYSTEM)
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.subsystem, TX_DATABASE_SUBSYSTEM);
+        assert_eq!(stored.version, outcome.to);
+    }
+
+    #[test]
+    fn future_versions_refuse_to_start() {
+        let (_dir, storage) = test_storage();
+        storage
+            .write_json(
+                storage.paths().schema_version(STORAGE_SUBSYSTEM),
+                &SchemaVersion {
+                    subsystem: STORAGE_SUBSYSTEM.into(),
+                    version: 99,
+                    updated_at: Utc::now(),
+                    applied: Vec::new(),
+                },
+            )
+            .unwrap();
+
+        let error = migrate_storage(&storage).unwrap_err();
+        assert!(matches!(
+            error,
+            MigrationError::FutureVersion { found: 99, .. }
+        ));
+    }
+
+    #[test]
+    fn failed_migrations_keep_the_last_good_version() {
+        let migrations: &[Migration<()>] = &[
+            Migration {
+                version: 1,
+                name: "ok",
+                run: |_| Ok(()),
+            },
+            Migration {
+                version: 2,
+                name: "broken",
+                run: |_| Err(StorageError::NotInitialized.into()),
+            },
+        ];
+        let (_dir, storage) = test_storage();
+
+        assert!(matches!(
+            apply(&storage, "test", &(), migrations),
+            Err(MigrationError::Failed { version: 2, .. })
+        ));
+        assert_eq!(read_version(&storage, "test").unwrap().unwrap().version, 1);
+    }
+}
@@ -28,6 +28,8 @@
 //!     {bookmark_id}.json
 //!   audit/
 //!     {date}/events.jsonl  # Daily audit logs
+//!   system/schema/
+//!     {subsystem}.json     # Schema version per subsystem (see `migrations`)
 //! ```
 //!
 //! ## Important Notes
@@ -38,22 +40,51 @@
 //! - DO NOT access SGX key devices directly
 
 pub mod audit;
+pub mod cache_bus;
 pub mod encrypted_fs;
+pub mod migrations;
 pub mod ownership;
 pub mod paths;
 pub mod repository;
 pub mod tx_cache;
 pub mod tx_database;
 
-pub use audit::{AuditEvent, AuditEventType, AuditRepository};
+pub use audit::{AuditEvent, AuditEventType, AuditQuery, AuditRepository};
+pub use cache_bus::{CacheBus, CacheResource, CacheSubscriber};
 pub use encrypted_fs::{EncryptedStorage, StorageError, StorageResult};
 pub use ownership::{OwnedResource, OwnershipEnforcer};
 pub use paths::StoragePaths;
 pub use repository::{
-    BookmarkRepository, EmailIndexRepository, FiatDirection, FiatRequestRepository,
-    FiatRequestStatus, FiatServiceWalletMetadata, FiatServiceWalletRepository, PaymentLinkData,
-    PaymentLinkRepository, RecipientType, StoredBookmark, StoredFiatRequest, StoredTransaction,
-    TokenType, TxStatus, WalletMetadata, WalletRepository, WalletResponse, WalletStatus,
+    AccountBalance, AdminBootstrapRepository, AlertEvent, AlertEventKind, AlertRepository,
+    AlertRule, AlertRuleKind, ApiKeyRepository, AssetTrialBalance, AutoTopUpEvent,
+    AutoTopUpEventKind, AutoTopUpRepository, BeneficiaryNameCheck, BookmarkRepository,
+    BootstrapArming, BridgeDirection, BridgeRepository, BridgeStatus, CategoryRepository,
+    ClaimStatus, ClawbackStatus, CounterpartyTotals, DescriptionTemplate,
+    DescriptionTemplateRepository, DestinationKycCheck, EmailIndexRepository, EscrowActor,
+    EscrowPaymentStatus, EscrowRepository, EscrowTransition, FeatureFlagRepository, FeeSchedule,
+    FiatActor, FiatBeneficiaryRepository, FiatBeneficiaryStatus, FiatChargeback, FiatDirection,
+    FiatMandateRepository, FiatMandateStatus, FiatRequestRepository, FiatRequestStatus,
+    FiatServiceWalletMetadata, FiatServiceWalletRepository, FiatStatusChange, FiatStatusTransition,
+    GasSpendEntry, InsightsRepository, JournalAccount, JournalEntry, JournalPosting,
+    JournalRepository, JournalSource, KeyCeremonyRepository, KeyCeremonyStatus, MonthlyInsights,
+    NameReview, NameReviewDecision, OrphanKind, OrphanRepository, PaymentLinkData,
+    PaymentLinkRepository, PendingNonceRepository, PooledKey, PostingSide, PriceHistories,
+    PriceHistoryRepository, ProviderCredentials, RecipientType, ReserveGasLedgerRepository,
+    ReserveKeySource, ReserveSendKind, ReserveSendQueueRepository, ReserveSendStatus,
+    SendHoldRepository, SendHoldSettings, SendHoldStatus, SessionAnomaly, SessionLogRepository,
+    SessionObservation, SessionRecord, SetupTokenSource, SmartAccountInfo, SpendingLimitRepository,
+    StoredApiKey, StoredAutoTopUp, StoredBookmark, StoredBridgeTransfer, StoredClaim,
+    StoredDescriptionTemplates, StoredEscrowPayment, StoredFeatureFlag, StoredFiatBeneficiary,
+    StoredFiatMandate, StoredFiatRequest, StoredKeyCeremony, StoredOrphan, StoredPendingNonce,
+    StoredReserveSendJob, StoredSendHold, StoredSpendingLimits, StoredTenantConfig, StoredToken,
+    StoredTransaction, StoredUserCategories, StoredWalletAlerts, StoredWatchOnlyAddress,
+    StoredWebhookDelivery, StoredWebhookKey, StoredWebhookKeyring, StoredWebhookSubscription,
+    TenantConfigRepository, TokenRepository, TokenType, TrialBalance, TxStatus, UserCategory,
+    WalletAccountType, WalletLock, WalletMetadata, WalletPoolRepository, WalletRepository,
+    WalletResponse, WalletStatus, WatchOnlyRepository, WebhookDeliveryStatus, WebhookEventType,
+    WebhookKeyRepository, WebhookSubscriptionRepository,
 };
+#[cfg(feature = "dev")]
+pub use repository::{FaucetRepository, StoredFaucetUsage};
 pub use tx_cache::TxCache;
 pub use tx_database::TxDatabase;
@@ -15,10 +15,21 @@ use super::{StorageError, StorageResult};
 pub trait OwnedResource {
     /// Get the owner's user ID.
     fn owner_user_id(&self) -> &str;
+
+    /// Whether the resource belongs to `tenant_id` (see [`crate::auth::tenant`]).
+    ///
+    /// Resources only reached through an owned wallet are not partitioned
+    /// on their own.
+    fn in_tenant(&self, _tenant_id: Option<&str>) -> bool {
+        true
+    }
 }
 
 /// Trait for enforcing ownership on storage operations.
 pub trait OwnershipEnforcer {
+    /// Whether the user owns this resource, within their tenant.
+    fn is_owned_by(&self, user: &AuthenticatedUser) -> bool;
+
     /// Verify that the user owns this resource.
     ///
     /// # Errors
@@ -27,8 +38,12 @@ pub trait OwnershipEnforcer {
 }
 
 impl<T: OwnedResource> OwnershipEnforcer for T {
+    fn is_owned_by(&self, user: &AuthenticatedUser) -> bool {
+        self.owner_user_id() == user.user_id && self.in_tenant(user.tenant_id.as_deref())
+    }
+
     fn verify_ownership(&self, user: &AuthenticatedUser) -> StorageResult<()> {
-        if self.owner_user_id() == user.user_id {
+        if self.is_owned_by(user) {
             Ok(())
         } else {
             Err(StorageError::PermissionDenied {
@@ -116,6 +131,7 @@ mod tests {
             session_id: None,
             issuer: "test".to_string(),
             expires_at: 0,
+            tenant_id: None,
         }
     }
 
@@ -190,4 +206,37 @@ mod tests {
         let result = op.check_admin_access(&user);
         assert!(matches!(result, Err(StorageError::PermissionDenied { .. })));
     }
+
+    struct TenantResource {
+        owner: String,
+        tenant_id: Option<String>,
+    }
+
+    impl OwnedResource for TenantResource {
+        fn owner_user_id(&self) -> &str {
+            &self.owner
+        }
+
+        fn in_tenant(&self, tenant_id: Option<&str>) -> bool {
+            self.tenant_id.as_deref() == tenant_id
+        }
+    }
+
+    #[test]
+    fn ownership_requires_matching_tenant() {
+        let resource = TenantResource {
+            owner: "user_123".to_string(),
+            tenant_id: Some("org_a".to_string()),
+        };
+        let mut user = make_user("user_123", Role::Client);
+        assert!(!resource.is_owned_by(&user));
+        assert!(resource.verify_ownership(&user).is_err());
+
+        user.tenant_id = Some("org_b".to_string());
+        assert!(!resource.is_owned_by(&user));
+
+        user.tenant_id = Some("org_a".to_string());
+        assert!(resource.is_owned_by(&user));
+        assert!(resource.verify_ownership(&user).is_ok());
+    }
 }
@@ -57,6 +57,32 @@ impl StoragePaths {
         self.wallet_dir(wallet_id).join("key.pem")
     }
 
+    /// Key generated for a rotation that has not finished yet.
+    pub fn wallet_rotation_key(&self, wallet_id: &str) -> PathBuf {
+        self.wallet_dir(wallet_id).join("rotation_key.pem")
+    }
+
+    /// Directory of keys a wallet was rotated away from.
+    pub fn wallet_archived_keys_dir(&self, wallet_id: &str) -> PathBuf {
+        self.wallet_dir(wallet_id).join("archived_keys")
+    }
+
+    /// Archived key that controlled `address`.
+    pub fn wallet_archived_key(&self, wallet_id: &str, address: &str) -> PathBuf {
+        self.wallet_archived_keys_dir(wallet_id)
+            .join(format!("{}.pem", address.to_lowercase()))
+    }
+
+    /// Directory of pre-generated keys not yet bound to a wallet.
+    pub fn wallet_pool_dir(&self) -> PathBuf {
+        self.root.join("wallet_pool")
+    }
+
+    /// Path to a pre-generated key.
+    pub fn wallet_pool_entry(&self, entry_id: &str) -> PathBuf {
+        self.wallet_pool_dir().join(format!("{entry_id}.json"))
+    }
+
     // ========== Bookmark Paths ==========
 
     /// Directory containing all bookmarks.
@@ -69,6 +95,283 @@ impl StoragePaths {
         self.bookmarks_dir().join(format!("{bookmark_id}.json"))
     }
 
+    // ========== Watch-Only Address Paths ==========
+
+    /// Directory for watch-only address entries.
+    pub fn watch_only_dir(&self) -> PathBuf {
+        self.root.join("watch_only")
+    }
+
+    /// Path to a watch-only address entry.
+    pub fn watch_only(&self, watch_id: &str) -> PathBuf {
+        self.watch_only_dir().join(format!("{watch_id}.json"))
+    }
+
+    // ========== Session History Paths ==========
+
+    /// Directory for per-user login/session logs.
+    pub fn user_sessions_dir(&self) -> PathBuf {
+        self.root.join("sessions")
+    }
+
+    /// Path to a user's session log, keyed by a digest of the user ID.
+    pub fn user_sessions(&self, user_key: &str) -> PathBuf {
+        self.user_sessions_dir().join(format!("{user_key}.json"))
+    }
+
+    // ========== Transaction Category Paths ==========
+
+    /// Directory for users' transaction categories.
+    pub fn categories_dir(&self) -> PathBuf {
+        self.root.join("categories")
+    }
+
+    /// Path to a user's categories and assignments, keyed by a digest of the
+    /// user ID.
+    pub fn user_categories(&self, user_key: &str) -> PathBuf {
+        self.categories_dir().join(format!("{user_key}.json"))
+    }
+
+    // ========== Wallet Insights Paths ==========
+
+    /// Directory for per-wallet monthly spending aggregates.
+    pub fn insights_dir(&self) -> PathBuf {
+        self.root.join("insights")
+    }
+
+    /// Path to a wallet's monthly aggregates.
+    pub fn wallet_insights(&self, wallet_id: &str) -> PathBuf {
+        self.insights_dir().join(format!("{wallet_id}.json"))
+    }
+
+    // ========== Send Hold Paths ==========
+
+    /// Directory for sends held for confirmation.
+    pub fn send_holds_dir(&self) -> PathBuf {
+        self.root.join("send_holds")
+    }
+
+    /// Path to a held send.
+    pub fn send_hold(&self, hold_id: &str) -> PathBuf {
+        self.send_holds_dir().join(format!("{hold_id}.json"))
+    }
+
+    /// Path to a user's send-hold settings, keyed by a digest of the user ID.
+    pub fn send_hold_settings(&self, user_key: &str) -> PathBuf {
+        self.send_holds_dir()
+            .join("settings")
+            .join(format!("{user_key}.json"))
+    }
+
+    // ========== Spending Limit Paths ==========
+
+    /// Directory for admin-set spending limits.
+    pub fn spending_limits_dir(&self) -> PathBuf {
+        self.root.join("spending_limits")
+    }
+
+    /// Path to a user's spending limits, keyed by a digest of the user ID.
+    pub fn user_spending_limits(&self, user_key: &str) -> PathBuf {
+        self.spending_limits_dir()
+            .join("users")
+            .join(format!("{user_key}.json"))
+    }
+
+    /// Path to a wallet's spending limits.
+    pub fn wallet_spending_limits(&self, wallet_id: &str) -> PathBuf {
+        self.spending_limits_dir()
+            .join("wallets")
+            .join(format!("{wallet_id}.json"))
+    }
+
+    // ========== Faucet Paths ==========
+
+    /// Directory for dev faucet usage.
+    #[cfg(feature = "dev")]
+    pub fn faucet_dir(&self) -> PathBuf {
+        self.root.join("faucet")
+    }
+
+    /// Path to a user's faucet usage, keyed by a digest of the user ID.
+    #[cfg(feature = "dev")]
+    pub fn faucet_usage(&self, user_key: &str) -> PathBuf {
+        self.faucet_dir().join(format!("{user_key}.json"))
+    }
+
+    // ========== Nonce Paths ==========
+
+    /// Directory for nonces of sends not yet mined.
+    pub fn pending_nonces_dir(&self) -> PathBuf {
+        self.root.join("nonces")
+    }
+
+    /// Path to the pending nonces of an address on a chain.
+    pub fn pending_nonces(&self, chain_id: u64, address: &str) -> PathBuf {
+        self.pending_nonces_dir()
+            .join(chain_id.to_string())
+            .join(format!("{}.json", address.to_ascii_lowercase()))
+    }
+
+    // ========== Price History Paths ==========
+
+    /// Directory for daily token price histories.
+    pub fn prices_dir(&self) -> PathBuf {
+        self.root.join("prices")
+    }
+
+    /// Path to a token's price history.
+    pub fn price_history(&self, symbol: &str) -> PathBuf {
+        self.prices_dir()
+            .join(format!("{}.json", symbol.to_lowercase()))
+    }
+
+    // ========== Escrow Paths ==========
+
+    /// Directory containing escrow sub-wallets and the transfers they hold.
+    pub fn escrow_dir(&self) -> PathBuf {
+        self.root.join("escrow")
+    }
+
+    /// Directory containing claimable transfers.
+    pub fn escrow_claims_dir(&self) -> PathBuf {
+        self.escrow_dir().join("claims")
+    }
+
+    /// Path to a claimable transfer.
+    pub fn escrow_claim(&self, claim_id: &str) -> PathBuf {
+        self.escrow_claims_dir().join(format!("{claim_id}.json"))
+    }
+
+    /// Directory containing escrowed payments between users.
+    pub fn escrow_payments_dir(&self) -> PathBuf {
+        self.escrow_dir().join("payments")
+    }
+
+    /// Path to an escrowed payment.
+    pub fn escrow_payment(&self, escrow_id: &str) -> PathBuf {
+        self.escrow_payments_dir().join(format!("{escrow_id}.json"))
+    }
+
+    /// Path to an escrow sub-wallet's private key (PEM).
+    pub fn escrow_key(&self, escrow_id: &str) -> PathBuf {
+        self.escrow_dir()
+            .join("keys")
+            .join(format!("{escrow_id}.pem"))
+    }
+
+    // ========== Bridge Paths ==========
+
+    /// Directory containing cross-chain bridge transfers.
+    pub fn bridge_dir(&self) -> PathBuf {
+        self.root.join("bridge")
+    }
+
+    /// Path to a bridge transfer record.
+    pub fn bridge_transfer(&self, transfer_id: &str) -> PathBuf {
+        self.bridge_dir().join(format!("{transfer_id}.json"))
+    }
+
+    // ========== Webhook Signing Key Paths ==========
+
+    /// Directory containing the outbound webhook signing keys.
+    pub fn webhook_keys_dir(&self) -> PathBuf {
+        self.root.join("webhook_keys")
+    }
+
+    /// Path to the webhook keyring (public keys and rotation state).
+    pub fn webhook_keyring(&self) -> PathBuf {
+        self.webhook_keys_dir().join("keyring.json")
+    }
+
+    /// Path to a webhook signing key's private half (PKCS#8).
+    pub fn webhook_private_key(&self, key_id: &str) -> PathBuf {
+        self.webhook_keys_dir().join(format!("{key_id}.pk8"))
+    }
+
+    // ========== Webhook Subscription Paths ==========
+
+    /// Directory containing users' webhook subscriptions.
+    pub fn webhook_subscriptions_dir(&self) -> PathBuf {
+        self.root.join("webhooks").join("subscriptions")
+    }
+
+    /// Path to a webhook subscription.
+    pub fn webhook_subscription(&self, subscription_id: &str) -> PathBuf {
+        self.webhook_subscriptions_dir()
+            .join(format!("{subscription_id}.json"))
+    }
+
+    /// Directory containing webhook deliveries.
+    pub fn webhook_deliveries_dir(&self) -> PathBuf {
+        self.root.join("webhooks").join("deliveries")
+    }
+
+    /// Path to a webhook delivery.
+    pub fn webhook_delivery(&self, delivery_id: &str) -> PathBuf {
+        self.webhook_deliveries_dir()
+            .join(format!("{delivery_id}.json"))
+    }
+
+    // ========== Feature Flag Paths ==========
+
+    /// Directory containing feature flags.
+    pub fn feature_flags_dir(&self) -> PathBuf {
+        self.root.join("feature_flags")
+    }
+
+    /// Path to a feature flag.
+    pub fn feature_flag(&self, key: &str) -> PathBuf {
+        self.feature_flags_dir().join(format!("{key}.json"))
+    }
+
+    // ========== Token Listing Paths ==========
+
+    /// Directory containing admin token listings.
+    pub fn tokens_dir(&self) -> PathBuf {
+        self.root.join("tokens")
+    }
+
+    /// Path to the listing of a token contract on a network.
+    pub fn token(&self, network: &str, address: &str) -> PathBuf {
+        self.tokens_dir().join(format!("{network}_{address}.json"))
+    }
+
+    // ========== API Key Paths ==========
+
+    /// Directory containing machine-to-machine API keys.
+    pub fn api_keys_dir(&self) -> PathBuf {
+        self.root.join("api_keys")
+    }
+
+    /// Path to an API key record.
+    pub fn api_key(&self, key_id: &str) -> PathBuf {
+        self.api_keys_dir().join(format!("{key_id}.json"))
+    }
+
+    // ========== Tenant Paths ==========
+
+    /// Directory containing per-tenant configuration.
+    pub fn tenants_dir(&self) -> PathBuf {
+        self.root.join("tenants")
+    }
+
+    /// Path to a tenant's configuration.
+    pub fn tenant_config(&self, tenant_id: &str) -> PathBuf {
+        self.tenants_dir().join(format!("{tenant_id}.json"))
+    }
+
+    // ========== Orphan Paths ==========
+
+    /// Directory containing orphaned storage artifacts awaiting removal.
+    pub fn orphans_dir(&self) -> PathBuf {
+        self.root.join("orphans")
+    }
+
+    /// Path to an orphan record.
+    pub fn orphan(&self, orphan_id: &str) -> PathBuf {
+        self.orphans_dir().join(format!("{orphan_id}.json"))
+    }
+
     // ========== Fiat Request Paths ==========
 
     /// Directory containing all fiat requests.
@@ -88,6 +391,58 @@ impl StoragePaths {
         self.root.join("system")
     }
 
+    /// Directory containing per-subsystem schema version files.
+    pub fn schema_dir(&self) -> PathBuf {
+        self.system_dir().join("schema")
+    }
+
+    /// Path to the schema version file of a storage subsystem.
+    pub fn schema_version(&self, subsystem: &str) -> PathBuf {
+        self.schema_dir().join(format!("{subsystem}.json"))
+    }
+
+    /// Directory containing open-banking payment mandates.
+    pub fn fiat_mandates_dir(&self) -> PathBuf {
+        self.root.join("fiat_mandates")
+    }
+
+    /// Path to a specific payment mandate file.
+    pub fn fiat_mandate(&self, mandate_id: &str) -> PathBuf {
+        self.fiat_mandates_dir().join(format!("{mandate_id}.json"))
+    }
+
+    /// Directory containing saved off-ramp beneficiaries.
+    pub fn fiat_beneficiaries_dir(&self) -> PathBuf {
+        self.root.join("fiat_beneficiaries")
+    }
+
+    /// Path to a specific beneficiary file.
+    pub fn fiat_beneficiary(&self, beneficiary_id: &str) -> PathBuf {
+        self.fiat_beneficiaries_dir()
+            .join(format!("{beneficiary_id}.json"))
+    }
+
+    /// Directory containing per-wallet fiat auto top-up rules.
+    pub fn fiat_auto_topups_dir(&self) -> PathBuf {
+        self.root.join("fiat_auto_topups")
+    }
+
+    /// Path to a wallet's auto top-up rule.
+    pub fn fiat_auto_topup(&self, wallet_id: &str) -> PathBuf {
+        self.fiat_auto_topups_dir()
+            .join(format!("{wallet_id}.json"))
+    }
+
+    /// Directory containing per-wallet alert rules.
+    pub fn wallet_alerts_dir(&self) -> PathBuf {
+        self.root.join("wallet_alerts")
+    }
+
+    /// Path to a wallet's alert rules.
+    pub fn wallet_alerts(&self, wallet_id: &str) -> PathBuf {
+        self.wallet_alerts_dir().join(format!("{wallet_id}.json"))
+    }
+
     /// Directory for fiat reserve service-wallet metadata and key material.
     pub fn fiat_service_wallet_dir(&self) -> PathBuf {
         self.system_dir().join("fiat_service_wallet")
@@ -103,6 +458,56 @@ impl StoragePaths {
         self.fiat_service_wallet_dir().join("key.pem")
     }
 
+    /// Path to the reserve-wallet key ceremony record (collected shares and status).
+    pub fn fiat_key_ceremony(&self) -> PathBuf {
+        self.system_dir().join("fiat_key_ceremony.json")
+    }
+
+    /// Path to the one-time admin bootstrap record.
+    pub fn admin_bootstrap(&self) -> PathBuf {
+        self.system_dir().join("admin_bootstrap.json")
+    }
+
+    /// Path to the persisted reserve-wallet send queue.
+    pub fn reserve_send_queue(&self) -> PathBuf {
+        self.system_dir().join("reserve_send_queue.json")
+    }
+
+    /// HMAC key signing the `state` parameter of hosted-payment return URIs.
+    pub fn fiat_return_state_key(&self) -> PathBuf {
+        self.system_dir().join("fiat_return_state_key.bin")
+    }
+
+    /// HMAC key signing claim link tokens.
+    pub fn claim_link_key(&self) -> PathBuf {
+        self.system_dir().join("claim_link_key.bin")
+    }
+
+    /// Directory of per-day reserve-wallet gas spend ledgers.
+    pub fn reserve_gas_dir(&self) -> PathBuf {
+        self.system_dir().join("reserve_gas")
+    }
+
+    /// Path to the reserve-wallet gas ledger for a UTC date (`YYYY-MM-DD`).
+    pub fn reserve_gas_day(&self, date: &str) -> PathBuf {
+        self.reserve_gas_dir().join(format!("{date}.json"))
+    }
+
+    /// Directory of double-entry journal entries.
+    pub fn journal_dir(&self) -> PathBuf {
+        self.system_dir().join("journal")
+    }
+
+    /// Path to a journal entry.
+    pub fn journal_entry(&self, entry_id: &str) -> PathBuf {
+        self.journal_dir().join(format!("{entry_id}.json"))
+    }
+
+    /// Path to the admin-configured transaction description templates.
+    pub fn tx_description_templates(&self) -> PathBuf {
+        self.system_dir().join("tx_description_templates.json")
+    }
+
     // ========== Audit Log Paths ==========
 
     /// Directory containing audit logs.
@@ -174,6 +579,192 @@ mod tests {
         );
     }
 
+    #[test]
+    fn watch_only_paths_are_correct() {
+        let paths = StoragePaths::default();
+        assert_eq!(paths.watch_only_dir(), PathBuf::from("/data/watch_only"));
+        assert_eq!(
+            paths.watch_only("wo-1"),
+            PathBuf::from("/data/watch_only/wo-1.json")
+        );
+    }
+
+    #[test]
+    fn session_paths_are_correct() {
+        let paths = StoragePaths::default();
+        assert_eq!(paths.user_sessions_dir(), PathBuf::from("/data/sessions"));
+        assert_eq!(
+            paths.user_sessions("ab12"),
+            PathBuf::from("/data/sessions/ab12.json")
+        );
+    }
+
+    #[test]
+    fn send_hold_paths_are_correct() {
+        let paths = StoragePaths::default();
+        assert_eq!(paths.send_holds_dir(), PathBuf::from("/data/send_holds"));
+        assert_eq!(
+            paths.send_hold("h1"),
+            PathBuf::from("/data/send_holds/h1.json")
+        );
+        assert_eq!(
+            paths.send_hold_settings("ab12"),
+            PathBuf::from("/data/send_holds/settings/ab12.json")
+        );
+    }
+
+    #[test]
+    fn price_history_paths_are_correct() {
+        let paths = StoragePaths::default();
+        assert_eq!(paths.prices_dir(), PathBuf::from("/data/prices"));
+        assert_eq!(
+            paths.price_history("rEUR"),
+            PathBuf::from("/data/prices/reur.json")
+        );
+    }
+
+    #[test]
+    fn escrow_paths_are_correct() {
+        let paths = StoragePaths::default();
+        assert_eq!(
+            paths.escrow_claim("claim-1"),
+            PathBuf::from("/data/escrow/claims/claim-1.json")
+        );
+        assert_eq!(
+            paths.escrow_payment("esc-1"),
+            PathBuf::from("/data/escrow/payments/esc-1.json")
+        );
+        assert_eq!(
+            paths.escrow_key("claim-1"),
+            PathBuf::from("/data/escrow/keys/claim-1.pem")
+        );
+        assert_eq!(
+            paths.claim_link_key(),
+            PathBuf::from("/data/system/claim_link_key.bin")
+        );
+    }
+
+    #[test]
+    fn bridge_paths_are_correct() {
+        let paths = StoragePaths::default();
+        assert_eq!(
+            paths.bridge_transfer("br-1"),
+            PathBuf::from("/data/bridge/br-1.json")
+        );
+    }
+
+    #[test]
+    fn webhook_key_paths_are_correct() {
+        let paths = StoragePaths::default();
+        assert_eq!(
+            paths.webhook_keyring(),
+            PathBuf::from("/data/webhook_keys/keyring.json")
+        );
+        assert_eq!(
+            paths.webhook_private_key("whk-1"),
+            PathBuf::from("/data/webhook_keys/whk-1.pk8")
+        );
+    }
+
+    #[test]
+    fn webhook_subscription_paths_are_correct() {
+        let paths = StoragePaths::default();
+        assert_eq!(
+            paths.webhook_subscription("whs_1"),
+            PathBuf::from("/data/webhooks/subscriptions/whs_1.json")
+        );
+        assert_eq!(
+            paths.webhook_delivery("whd_1"),
+            PathBuf::from("/data/webhooks/deliveries/whd_1.json")
+        );
+    }
+
+    #[test]
+    fn wallet_pool_paths_are_correct() {
+        let paths = StoragePaths::default();
+        assert_eq!(
+            paths.wallet_pool_entry("e1"),
+            PathBuf::from("/data/wallet_pool/e1.json")
+        );
+    }
+
+    #[test]
+    fn pending_nonce_paths_are_lowercased() {
+        let paths = StoragePaths::default();
+        assert_eq!(
+            paths.pending_nonces(43113, "0xAbC0000000000000000000000000000000000001"),
+            PathBuf::from("/data/nonces/43113/0xabc0000000000000000000000000000000000001.json")
+        );
+    }
+
+    #[test]
+    fn spending_limit_paths_are_correct() {
+        let paths = StoragePaths::default();
+        assert_eq!(
+            paths.user_spending_limits("ab12"),
+            PathBuf::from("/data/spending_limits/users/ab12.json")
+        );
+        assert_eq!(
+            paths.wallet_spending_limits("w1"),
+            PathBuf::from("/data/spending_limits/wallets/w1.json")
+        );
+    }
+
+    #[cfg(feature = "dev")]
+    #[test]
+    fn faucet_paths_are_correct() {
+        let paths = StoragePaths::default();
+        assert_eq!(
+            paths.faucet_usage("ab12"),
+            PathBuf::from("/data/faucet/ab12.json")
+        );
+    }
+
+    #[test]
+    fn feature_flag_paths_are_correct() {
+        let paths = StoragePaths::default();
+        assert_eq!(
+            paths.feature_flag("swaps"),
+            PathBuf::from("/data/feature_flags/swaps.json")
+        );
+    }
+
+    #[test]
+    fn token_paths_are_correct() {
+        let paths = StoragePaths::default();
+        assert_eq!(
+            paths.token("fuji", "0xabc"),
+            PathBuf::from("/data/tokens/fuji_0xabc.json")
+        );
+    }
+
+    #[test]
+    fn api_key_paths_are_correct() {
+        let paths = StoragePaths::default();
+        assert_eq!(
+            paths.api_key("key_1"),
+            PathBuf::from("/data/api_keys/key_1.json")
+        );
+    }
+
+    #[test]
+    fn tenant_paths_are_correct() {
+        let paths = StoragePaths::default();
+        assert_eq!(
+            paths.tenant_config("org_acme"),
+            PathBuf::from("/data/tenants/org_acme.json")
+        );
+    }
+
+    #[test]
+    fn orphan_paths_are_correct() {
+        let paths = StoragePaths::default();
+        assert_eq!(
+            paths.orphan("0123abcd"),
+            PathBuf::from("/data/orphans/0123abcd.json")
+        );
+    }
+
     #[test]
     fn audit_paths_are_correct() {
         let paths = StoragePaths::default();
@@ -198,6 +789,22 @@ mod tests {
     fn system_paths_are_correct() {
         let paths = StoragePaths::default();
         assert_eq!(paths.system_dir(), PathBuf::from("/data/system"));
+        assert_eq!(
+            paths.schema_version("tx_database"),
+            PathBuf::from("/data/system/schema/tx_database.json")
+        );
+        assert_eq!(
+            paths.fiat_mandate("m-1"),
+            PathBuf::from("/data/fiat_mandates/m-1.json")
+        );
+        assert_eq!(
+            paths.fiat_beneficiary("b-1"),
+            PathBuf::from("/data/fiat_beneficiaries/b-1.json")
+        );
+        assert_eq!(
+            paths.fiat_auto_topup("w-1"),
+            PathBuf::from("/data/fiat_auto_topups/w-1.json")
+        );
         assert_eq!(
             paths.fiat_service_wallet_dir(),
             PathBuf::from("/data/system/fiat_service_wallet")
@@ -210,5 +817,25 @@ mod tests {
             paths.fiat_service_wallet_key(),
             PathBuf::from("/data/system/fiat_service_wallet/key.pem")
         );
+        assert_eq!(
+            paths.fiat_key_ceremony(),
+            PathBuf::from("/data/system/fiat_key_ceremony.json")
+        );
+        assert_eq!(
+            paths.reserve_send_queue(),
+            PathBuf::from("/data/system/reserve_send_queue.json")
+        );
+        assert_eq!(
+            paths.fiat_return_state_key(),
+            PathBuf::from("/data/system/fiat_return_state_key.bin")
+        );
+        assert_eq!(
+            paths.reserve_gas_day("2026-03-01"),
+            PathBuf::from("/data/system/reserve_gas/2026-03-01.json")
+        );
+        assert_eq!(
+            paths.tx_description_templates(),
+            PathBuf::from("/data/system/tx_description_templates.json")
+        );
     }
 }
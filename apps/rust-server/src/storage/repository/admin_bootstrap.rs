@@ -0,0 +1,333 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! One-time designation of the first admin.
+//!
+//! Until an admin exists, a setup token is armed: either taken from sealed
+//! configuration or generated inside the enclave and shown to the operator
+//! once. Only the token's SHA-256 is stored, and it expires
+//! [`SETUP_TOKEN_TTL`] after arming; restarts keep the armed token instead
+//! of generating another. The first authenticated user presenting it
+//! becomes the bootstrap admin, after which the token is discarded and
+//! never re-armed. Revoking the bootstrap discards the token and the
+//! admin's grant for good. The record lives under
+//! `/data/system/admin_bootstrap.json`.
+
+use alloy::hex;
+use chrono::{DateTime, TimeDelta, Utc};
+use k256::elliptic_curve::rand_core::{OsRng, RngCore};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use utoipa::ToSchema;
+
+use super::super::{EncryptedStorage, StorageResult};
+
+/// How long an armed setup token stays valid.
+pub const SETUP_TOKEN_TTL: TimeDelta = TimeDelta::hours(24);
+
+/// Where the armed setup token came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SetupTokenSource {
+    /// Provided through sealed configuration.
+    Sealed,
+    /// Generated at startup and shown to the operator once.
+    Generated,
+}
+
+/// Persisted bootstrap state.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StoredAdminBootstrap {
+    /// Hex SHA-256 of the armed setup token; cleared once used.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token_sha256: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token_source: Option<SetupTokenSource>,
+    /// When the current token was armed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub armed_at: Option<DateTime<Utc>>,
+    /// When the current token stops being accepted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
+    /// User designated as the initial admin.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub admin_user_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub completed_at: Option<DateTime<Utc>>,
+    /// When the bootstrap was revoked; no token is armed again afterwards.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revoked_at: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revoked_by: Option<String>,
+}
+
+impl StoredAdminBootstrap {
+    /// Whether the armed token is still accepted at `now`.
+    pub fn token_valid(&self, now: DateTime<Utc>) -> bool {
+        self.token_sha256.is_some() && self.expires_at.is_some_and(|at| now < at)
+    }
+}
+
+/// Outcome of arming the setup token at startup.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BootstrapArming {
+    /// An admin was already designated; no token is armed.
+    Completed { admin_user_id: String },
+    /// The bootstrap was revoked; no token is armed.
+    Revoked,
+    /// The token armed at an earlier start is still valid.
+    Kept {
+        source: SetupTokenSource,
+        expires_at: DateTime<Utc>,
+    },
+    /// The sealed token is armed.
+    Sealed { expires_at: DateTime<Utc> },
+    /// The sealed token was armed before and has expired; a new sealed
+    /// value is needed.
+    SealedExpired,
+    /// A fresh token was generated and must be shown to the operator.
+    Generated {
+        token: String,
+        expires_at: DateTime<Utc>,
+    },
+}
+
+/// The bootstrap state before a revocation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RevokedBootstrap {
+    /// The bootstrap admin whose grant was removed.
+    pub admin_user_id: Option<String>,
+    /// Whether an unused token was discarded.
+    pub token_discarded: bool,
+}
+
+/// Hex SHA-256 of a setup token.
+pub fn setup_token_sha256(token: &str) -> String {
+    hex::encode(Sha256::digest(token.trim().as_bytes()))
+}
+
+/// Repository for the admin bootstrap record.
+pub struct AdminBootstrapRepository<'a> {
+    storage: &'a EncryptedStorage,
+}
+
+impl<'a> AdminBootstrapRepository<'a> {
+    /// Create repository.
+    pub fn new(storage: &'a EncryptedStorage) -> Self {
+        Self { storage }
+    }
+
+    /// Get the bootstrap record, if one was ever written.
+    pub fn get(&self) -> StorageResult<Option<StoredAdminBootstrap>> {
+        let path = self.storage.paths().admin_bootstrap();
+        if !self.storage.exists(&path) {
+            return Ok(None);
+        }
+        self.storage.read_json(path).map(Some)
+    }
+
+    /// Persist the bootstrap record.
+    pub fn save(&self, record: &StoredAdminBootstrap) -> StorageResult<()> {
+        self.storage
+            .write_json(self.storage.paths().admin_bootstrap(), record)
+    }
+
+    /// The bootstrap admin, once designated and unless revoked.
+    pub fn admin_user_id(&self) -> StorageResult<Option<String>> {
+        Ok(self.get()?.and_then(|record| record.admin_user_id))
+    }
+
+    /// Arm a setup token at `now` unless an admin was already designated or
+    /// the bootstrap was revoked.
+    ///
+    /// A sealed token wins over generation. A token armed at an earlier
+    /// start is kept until it expires, so a restart neither invalidates it
+    /// nor produces another secret.
+    pub fn arm(
+        &self,
+        sealed_token: Option<&str>,
+        now: DateTime<Utc>,
+    ) -> StorageResult<BootstrapArming> {
+        let mut record = self.get()?.unwrap_or_default();
+        if record.revoked_at.is_some() {
+            return Ok(BootstrapArming::Revoked);
+        }
+        if let Some(admin_user_id) = record.admin_user_id.clone() {
+            return Ok(BootstrapArming::Completed { admin_user_id });
+        }
+
+        let sealed = sealed_token.map(str::trim).filter(|t| !t.is_empty());
+        let kept = match sealed {
+            Some(token) => {
+                if record.token_sha256.as_deref() == Some(setup_token_sha256(token).as_str()) {
+                    if !record.token_valid(now) {
+                        return Ok(BootstrapArming::SealedExpired);
+                    }
+                    true
+                } else {
+                    false
+                }
+            }
+            None => {
+                record.token_source == Some(SetupTokenSource::Generated) && record.token_valid(now)
+            }
+        };
+        if kept {
+            return Ok(BootstrapArming::Kept {
+                source: record.token_source.unwrap_or(SetupTokenSource::Generated),
+                expires_at: record.expires_at.unwrap_or(now),
+            });
+        }
+
+        let expires_at = now + SETUP_TOKEN_TTL;
+        let (token, source, arming) = match sealed {
+            Some(token) => (
+                token.to_string(),
+                SetupTokenSource::Sealed,
+                BootstrapArming::Sealed { expires_at },
+            ),
+            None => {
+                let mut bytes = [0u8; 32];
+                OsRng.fill_bytes(&mut bytes);
+                let token = hex::encode(bytes);
+                (
+                    token.clone(),
+                    SetupTokenSource::Generated,
+                    BootstrapArming::Generated { token, expires_at },
+                )
+            }
+        };
+        record.token_sha256 = Some(setup_token_sha256(&token));
+        record.token_source = Some(source);
+        record.armed_at = Some(now);
+        record.expires_at = Some(expires_at);
+        self.save(&record)?;
+        Ok(arming)
+    }
+
+    /// Discard the armed token and the bootstrap admin's grant, and stop
+    /// arming tokens. `None` when the bootstrap was already revoked.
+    pub fn revoke(
+        &self,
+        revoked_by: &str,
+        now: DateTime<Utc>,
+    ) -> StorageResult<Option<RevokedBootstrap>> {
+        let mut record = self.get()?.unwrap_or_default();
+        if record.revoked_at.is_some() {
+            return Ok(None);
+        }
+        let revoked = RevokedBootstrap {
+            admin_user_id: record.admin_user_id.take(),
+            token_discarded: record.token_sha256.take().is_some(),
+        };
+        record.expires_at = None;
+        record.revoked_at = Some(now);
+        record.revoked_by = Some(revoked_by.to_string());
+        self.save(&record)?;
+        Ok(Some(revoked))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::StoragePaths;
+    use tempfile::TempDir;
+
+    fn storage() -> (TempDir, EncryptedStorage) {
+        let temp = TempDir::new().unwrap();
+        let mut storage = EncryptedStorage::new(StoragePaths::new(temp.path()));
+        storage.initialize().unwrap();
+        (temp, storage)
+    }
+
+    #[test]
+    fn arming_keeps_one_token_until_it_expires() {
+        let (_temp, storage) = storage();
+        let repo = AdminBootstrapRepository::new(&storage);
+        assert!(repo.get().unwrap().is_none());
+        let now = Utc::now();
+
+        let BootstrapArming::Generated { token, expires_at } = repo.arm(None, now).unwrap() else {
+            panic!("expected a generated token");
+        };
+        assert_eq!(expires_at, now + SETUP_TOKEN_TTL);
+        let record = repo.get().unwrap().unwrap();
+        assert_eq!(record.token_sha256, Some(setup_token_sha256(&token)));
+        assert_eq!(record.token_source, Some(SetupTokenSource::Generated));
+
+        // A restart keeps the token instead of printing a new one.
+        let later = now + TimeDelta::hours(1);
+        assert_eq!(
+            repo.arm(None, later).unwrap(),
+            BootstrapArming::Kept {
+                source: SetupTokenSource::Generated,
+                expires_at
+            }
+        );
+        assert_eq!(
+            repo.get().unwrap().unwrap().token_sha256,
+            Some(setup_token_sha256(&token))
+        );
+        let expired = expires_at + TimeDelta::seconds(1);
+        assert!(!repo.get().unwrap().unwrap().token_valid(expired));
+        let BootstrapArming::Generated { token: next, .. } = repo.arm(None, expired).unwrap()
+        else {
+            panic!("expected a new token after expiry");
+        };
+        assert_ne!(next, token);
+
+        assert!(matches!(
+            repo.arm(Some(" sealed "), now).unwrap(),
+            BootstrapArming::Sealed { .. }
+        ));
+        assert!(matches!(
+            repo.arm(Some("sealed"), later).unwrap(),
+            BootstrapArming::Kept {
+                source: SetupTokenSource::Sealed,
+                ..
+            }
+        ));
+        assert_eq!(
+            repo.arm(Some("sealed"), expired).unwrap(),
+            BootstrapArming::SealedExpired
+        );
+
+        let mut record = repo.get().unwrap().unwrap();
+        record.token_sha256 = None;
+        record.admin_user_id = Some("user_1".to_string());
+        record.completed_at = Some(Utc::now());
+        repo.save(&record).unwrap();
+        assert_eq!(
+            repo.arm(Some("other"), now).unwrap(),
+            BootstrapArming::Completed {
+                admin_user_id: "user_1".to_string()
+            }
+        );
+        assert!(repo.get().unwrap().unwrap().token_sha256.is_none());
+        assert_eq!(repo.admin_user_id().unwrap().as_deref(), Some("user_1"));
+    }
+
+    #[test]
+    fn revoking_removes_the_grant_and_stops_arming() {
+        let (_temp, storage) = storage();
+        let repo = AdminBootstrapRepository::new(&storage);
+        let now = Utc::now();
+        repo.arm(None, now).unwrap();
+
+        let revoked = repo.revoke("admin_2", now).unwrap().unwrap();
+        assert_eq!(revoked.admin_user_id, None);
+        assert!(revoked.token_discarded);
+        let record = repo.get().unwrap().unwrap();
+        assert!(!record.token_valid(now));
+        assert_eq!(record.revoked_by.as_deref(), Some("admin_2"));
+
+        assert_eq!(
+            repo.arm(Some("sealed"), now).unwrap(),
+            BootstrapArming::Revoked
+        );
+        assert!(repo.revoke("admin_2", now).unwrap().is_none());
+        assert!(repo.admin_user_id().unwrap().is_none());
+    }
+}
@@ -0,0 +1,247 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Per-wallet alert rules.
+//!
+//! A wallet owner can be alerted when a balance drops below a threshold,
+//! when a large transfer comes in, or on any outgoing transfer. Rules live
+//! under `/data/wallet_alerts/{wallet_id}.json` together with the alerts the
+//! poller raised for them and the indexer's first-deposit notice.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::super::{EncryptedStorage, StorageError, StorageResult};
+
+/// Alerts kept per wallet; older ones are dropped.
+const RETAINED_EVENTS: usize = 50;
+
+/// Evaluated transaction hashes kept per wallet.
+const RETAINED_SEEN: usize = 200;
+
+/// Condition an alert rule watches for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertRuleKind {
+    /// The token balance is below `threshold`.
+    LowBalance,
+    /// A confirmed incoming transfer of `token` is above `threshold`.
+    LargeIncoming,
+    /// Any confirmed outgoing transfer.
+    AnyOutgoing,
+}
+
+/// A wallet alert rule.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct AlertRule {
+    pub kind: AlertRuleKind,
+    /// `AVAX` or `rEUR`; required for `low_balance` and `large_incoming`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+    /// Whole tokens; required for `low_balance` and `large_incoming`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub threshold: Option<String>,
+}
+
+/// Kind of a raised alert.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertEventKind {
+    /// A balance fell below its threshold.
+    LowBalance,
+    /// A large incoming transfer was confirmed.
+    LargeIncoming,
+    /// An outgoing transfer was confirmed.
+    OutgoingTransfer,
+    /// The wallet received its first incoming transfer. Raised whether or
+    /// not the wallet has alert rules.
+    FirstDeposit,
+}
+
+impl AlertEventKind {
+    /// Every kind, in declaration order.
+    pub const ALL: [AlertEventKind; 4] = [
+        AlertEventKind::LowBalance,
+        AlertEventKind::LargeIncoming,
+        AlertEventKind::OutgoingTransfer,
+        AlertEventKind::FirstDeposit,
+    ];
+
+    /// One-line description, as published in the event catalog.
+    pub fn description(self) -> &'static str {
+        match self {
+            AlertEventKind::LowBalance => "A wallet balance fell below the alert threshold",
+            AlertEventKind::LargeIncoming => {
+                "An incoming transfer above the alert threshold was confirmed"
+            }
+            AlertEventKind::OutgoingTransfer => "An outgoing transfer was confirmed",
+            AlertEventKind::FirstDeposit => "The wallet received its first incoming transfer",
+        }
+    }
+}
+
+/// An alert raised for a wallet, shown to the user as a notification.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AlertEvent {
+    pub at: DateTime<Utc>,
+    pub kind: AlertEventKind,
+    /// Transfer that raised a transfer alert.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tx_hash: Option<String>,
+    pub message: String,
+}
+
+/// Persisted alert rules of a wallet.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct StoredWalletAlerts {
+    pub wallet_id: String,
+    pub owner_user_id: String,
+    pub rules: Vec<AlertRule>,
+    /// Transfers confirmed before this are never alerted on.
+    pub armed_at: DateTime<Utc>,
+    /// Tokens currently below their low-balance threshold. A token alerts
+    /// again only after its balance recovered.
+    #[serde(default)]
+    pub low_tokens: Vec<String>,
+    /// Transactions already evaluated, oldest first.
+    #[serde(default)]
+    pub seen_tx: Vec<String>,
+    /// Raised alerts, oldest first.
+    #[serde(default)]
+    pub events: Vec<AlertEvent>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl StoredWalletAlerts {
+    /// Whether any rule watches transfers.
+    pub fn watches_transfers(&self) -> bool {
+        self.rules
+            .iter()
+            .any(|r| r.kind != AlertRuleKind::LowBalance)
+    }
+
+    /// Whether a transaction was already evaluated.
+    pub fn has_seen(&self, tx_hash: &str) -> bool {
+        let tx_hash = tx_hash.to_lowercase();
+        self.seen_tx.contains(&tx_hash)
+    }
+
+    /// Remember an evaluated transaction.
+    pub fn mark_seen(&mut self, tx_hash: &str) {
+        if self.has_seen(tx_hash) {
+            return;
+        }
+        self.seen_tx.push(tx_hash.to_lowercase());
+        if self.seen_tx.len() > RETAINED_SEEN {
+            let excess = self.seen_tx.len() - RETAINED_SEEN;
+            self.seen_tx.drain(..excess);
+        }
+    }
+
+    /// Append an alert.
+    pub fn record_event(
+        &mut self,
+        kind: AlertEventKind,
+        tx_hash: Option<String>,
+        message: String,
+        at: DateTime<Utc>,
+    ) {
+        self.events.push(AlertEvent {
+            at,
+            kind,
+            tx_hash,
+            message,
+        });
+        if self.events.len() > RETAINED_EVENTS {
+            let excess = self.events.len() - RETAINED_EVENTS;
+            self.events.drain(..excess);
+        }
+    }
+}
+
+/// Repository for wallet alert rules.
+pub struct AlertRepository<'a> {
+    storage: &'a EncryptedStorage,
+}
+
+impl<'a> AlertRepository<'a> {
+    /// Create repository.
+    pub fn new(storage: &'a EncryptedStorage) -> Self {
+        Self { storage }
+    }
+
+    /// Get a wallet's alert rules.
+    pub fn get(&self, wallet_id: &str) -> StorageResult<StoredWalletAlerts> {
+        let path = self.storage.paths().wallet_alerts(wallet_id);
+        if !self.storage.exists(&path) {
+            return Err(StorageError::NotFound(format!("Alerts for {wallet_id}")));
+        }
+        self.storage.read_json(path)
+    }
+
+    /// Create or replace a wallet's alert rules.
+    pub fn save(&self, alerts: &StoredWalletAlerts) -> StorageResult<()> {
+        self.storage.write_json(
+            self.storage.paths().wallet_alerts(&alerts.wallet_id),
+            alerts,
+        )
+    }
+
+    /// All wallets' alert rules (poller use).
+    pub fn list_all(&self) -> StorageResult<Vec<StoredWalletAlerts>> {
+        let ids = self
+            .storage
+            .list_files(self.storage.paths().wallet_alerts_dir(), "json")?;
+        Ok(ids.iter().filter_map(|id| self.get(id).ok()).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::StoragePaths;
+    use tempfile::TempDir;
+
+    #[test]
+    fn seen_transactions_are_bounded_and_case_insensitive() {
+        let temp = TempDir::new().unwrap();
+        let mut storage = EncryptedStorage::new(StoragePaths::new(temp.path()));
+        storage.initialize().unwrap();
+        let repo = AlertRepository::new(&storage);
+        assert!(repo.list_all().unwrap().is_empty());
+
+        let now = Utc::now();
+        let mut alerts = StoredWalletAlerts {
+            wallet_id: "wallet-1".to_string(),
+            owner_user_id: "user-1".to_string(),
+            rules: vec![AlertRule {
+                kind: AlertRuleKind::AnyOutgoing,
+                token: None,
+                threshold: None,
+            }],
+            armed_at: now,
+            low_tokens: Vec::new(),
+            seen_tx: Vec::new(),
+            events: Vec::new(),
+            created_at: now,
+            updated_at: now,
+        };
+        alerts.mark_seen("0xAB");
+        alerts.mark_seen("0xab");
+        assert!(alerts.has_seen("0xaB"));
+        assert_eq!(alerts.seen_tx.len(), 1);
+        for i in 0..RETAINED_SEEN {
+            alerts.mark_seen(&format!("0xfeed{i:x}"));
+        }
+        assert_eq!(alerts.seen_tx.len(), RETAINED_SEEN);
+        assert!(!alerts.has_seen("0xab"));
+        assert!(alerts.watches_transfers());
+
+        repo.save(&alerts).unwrap();
+        assert_eq!(repo.list_all().unwrap().len(), 1);
+        assert_eq!(repo.get("wallet-1").unwrap().seen_tx, alerts.seen_tx);
+    }
+}
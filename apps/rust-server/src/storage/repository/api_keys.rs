@@ -0,0 +1,289 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Machine-to-machine API keys.
+//!
+//! Keys look like `rwk_<key_id>_<secret>`. Only the SHA-256 of the whole
+//! key is stored, under `/data/api_keys/{key_id}.json`; the plaintext is
+//! shown once when the key is minted. Each key carries the role it acts
+//! with and the scopes it may call (see [`StoredApiKey::allows`]).
+
+use std::sync::Mutex;
+
+use alloy::hex;
+use chrono::{DateTime, Duration, Utc};
+use k256::elliptic_curve::rand_core::{OsRng, RngCore};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::super::{EncryptedStorage, StorageError, StorageResult};
+use crate::auth::Role;
+
+/// Prefix of every API key.
+pub const API_KEY_PREFIX: &str = "rwk_";
+
+/// Scope area matching every path.
+const ANY_AREA: &str = "*";
+
+/// Minimum time between `last_used_at` updates of a key.
+const TOUCH_INTERVAL_SECS: i64 = 60;
+
+/// Serializes read-modify-write updates, so recording a use cannot undo a
+/// concurrent revocation.
+static UPDATE_LOCK: Mutex<()> = Mutex::new(());
+
+/// A minted API key.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct StoredApiKey {
+    pub key_id: String,
+    /// Operator-facing label, e.g. the integration using the key.
+    pub name: String,
+    /// Hex SHA-256 of the full key.
+    pub key_sha256: String,
+    pub role: Role,
+    /// `{area}:{access}` entries, see [`StoredApiKey::allows`].
+    pub scopes: Vec<String>,
+    /// Tenant the key acts in; `None` for the default tenant.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tenant_id: Option<String>,
+    pub created_by: String,
+    pub created_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_used_at: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+impl StoredApiKey {
+    /// User ID requests made with this key are attributed to.
+    pub fn principal(&self) -> String {
+        format!("api_key:{}", self.key_id)
+    }
+
+    /// Whether the key can be used at `now`.
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        self.revoked_at.is_none() && self.expires_at.is_none_or(|at| at > now)
+    }
+
+    /// Whether a scope covers a request to `area` (the first path segment
+    /// under `/v1`, e.g. `wallets` or `admin`).
+    ///
+    /// A scope is `{area}:{access}`, where `area` may be `*` and `access` is
+    /// `read` (safe methods only) or `write` (any method).
+    pub fn allows(&self, area: &str, write: bool) -> bool {
+        self.scopes
+            .iter()
+            .filter_map(|s| parse_scope(s))
+            .any(|(scope_area, scope_write)| {
+                (scope_area == ANY_AREA || scope_area == area) && (scope_write || !write)
+            })
+    }
+}
+
+/// Split a scope into its area and whether it grants write access.
+pub fn parse_scope(scope: &str) -> Option<(&str, bool)> {
+    let (area, access) = scope.split_once(':')?;
+    let valid_area = area == ANY_AREA
+        || (!area.is_empty()
+            && area
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_'));
+    if !valid_area {
+        return None;
+    }
+    match access {
+        "read" => Some((area, false)),
+        "write" => Some((area, true)),
+        _ => None,
+    }
+}
+
+/// Hex SHA-256 of an API key.
+pub fn api_key_sha256(key: &str) -> String {
+    hex::encode(Sha256::digest(key.trim().as_bytes()))
+}
+
+/// Key ID embedded in a presented API key.
+pub fn api_key_id(key: &str) -> Option<&str> {
+    let (key_id, secret) = key.trim().strip_prefix(API_KEY_PREFIX)?.split_once('_')?;
+    let hex_part = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_hexdigit());
+    (hex_part(key_id) && hex_part(secret)).then_some(key_id)
+}
+
+/// Generate a fresh key ID and the full plaintext key.
+pub fn generate_api_key() -> (String, String) {
+    let mut id = [0u8; 8];
+    let mut secret = [0u8; 32];
+    OsRng.fill_bytes(&mut id);
+    OsRng.fill_bytes(&mut secret);
+    let key_id = hex::encode(id);
+    let key = format!("{API_KEY_PREFIX}{key_id}_{}", hex::encode(secret));
+    (key_id, key)
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Repository for API keys.
+pub struct ApiKeyRepository<'a> {
+    storage: &'a EncryptedStorage,
+}
+
+impl<'a> ApiKeyRepository<'a> {
+    /// Create repository.
+    pub fn new(storage: &'a EncryptedStorage) -> Self {
+        Self { storage }
+    }
+
+    /// Get a key by ID.
+    pub fn get(&self, key_id: &str) -> StorageResult<StoredApiKey> {
+        let path = self.storage.paths().api_key(key_id);
+        if !self.storage.exists(&path) {
+            return Err(StorageError::NotFound(format!("API key {key_id}")));
+        }
+        self.storage.read_json(path)
+    }
+
+    /// Create or replace a key.
+    pub fn save(&self, key: &StoredApiKey) -> StorageResult<()> {
+        self.storage
+            .write_json(self.storage.paths().api_key(&key.key_id), key)
+    }
+
+    /// Revoke a key. Revoking twice keeps the first revocation time.
+    pub fn revoke(&self, key_id: &str, now: DateTime<Utc>) -> StorageResult<StoredApiKey> {
+        let _guard = UPDATE_LOCK.lock().unwrap_or_else(|p| p.into_inner());
+        let mut key = self.get(key_id)?;
+        if key.revoked_at.is_none() {
+            key.revoked_at = Some(now);
+            self.save(&key)?;
+        }
+        Ok(key)
+    }
+
+    /// Record that a key was used at `now`. Updates at most once per
+    /// [`TOUCH_INTERVAL_SECS`].
+    pub fn touch(&self, key_id: &str, now: DateTime<Utc>) -> StorageResult<()> {
+        let _guard = UPDATE_LOCK.lock().unwrap_or_else(|p| p.into_inner());
+        let mut key = self.get(key_id)?;
+        if key
+            .last_used_at
+            .is_some_and(|at| now - at < Duration::seconds(TOUCH_INTERVAL_SECS))
+        {
+            return Ok(());
+        }
+        key.last_used_at = Some(now);
+        self.save(&key)
+    }
+
+    /// All keys, newest first.
+    pub fn list_all(&self) -> StorageResult<Vec<StoredApiKey>> {
+        let ids = self
+            .storage
+            .list_files(self.storage.paths().api_keys_dir(), "json")?;
+        let mut keys: Vec<_> = ids.iter().filter_map(|id| self.get(id).ok()).collect();
+        keys.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        Ok(keys)
+    }
+
+    /// The active key matching a presented plaintext key, if any.
+    pub fn authenticate(
+        &self,
+        presented: &str,
+        now: DateTime<Utc>,
+    ) -> StorageResult<Option<StoredApiKey>> {
+        let Some(key_id) = api_key_id(presented) else {
+            return Ok(None);
+        };
+        let key = match self.get(key_id) {
+            Ok(key) => key,
+            Err(StorageError::NotFound(_)) => return Ok(None),
+            Err(e) => return Err(e),
+        };
+        let matches = constant_time_eq(
+            api_key_sha256(presented).as_bytes(),
+            key.key_sha256.as_bytes(),
+        );
+        Ok((matches && key.is_active(now)).then_some(key))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::StoragePaths;
+    use tempfile::TempDir;
+
+    fn stored(key_id: &str, key: &str, scopes: &[&str]) -> StoredApiKey {
+        StoredApiKey {
+            key_id: key_id.to_string(),
+            name: "cron".to_string(),
+            key_sha256: api_key_sha256(key),
+            role: Role::Auditor,
+            scopes: scopes.iter().map(|s| s.to_string()).collect(),
+            tenant_id: None,
+            created_by: "admin".to_string(),
+            created_at: Utc::now(),
+            expires_at: None,
+            last_used_at: None,
+            revoked_at: None,
+        }
+    }
+
+    #[test]
+    fn scopes_limit_area_and_access() {
+        let key = stored("k", "", &["admin:read", "wallets:write"]);
+        assert!(key.allows("admin", false));
+        assert!(!key.allows("admin", true));
+        assert!(key.allows("wallets", true));
+        assert!(!key.allows("fiat", false));
+
+        let any = stored("k", "", &["*:read"]);
+        assert!(any.allows("fiat", false));
+        assert!(!any.allows("fiat", true));
+
+        assert_eq!(parse_scope("admin:read"), Some(("admin", false)));
+        assert_eq!(parse_scope("admin:delete"), None);
+        assert_eq!(parse_scope("Admin:read"), None);
+        assert_eq!(parse_scope("read"), None);
+    }
+
+    #[test]
+    fn only_matching_active_keys_authenticate() {
+        let temp = TempDir::new().unwrap();
+        let mut storage = EncryptedStorage::new(StoragePaths::new(temp.path()));
+        storage.initialize().unwrap();
+        let repo = ApiKeyRepository::new(&storage);
+        let now = Utc::now();
+
+        let (key_id, key) = generate_api_key();
+        assert_eq!(api_key_id(&key), Some(key_id.as_str()));
+        let mut record = stored(&key_id, &key, &["*:read"]);
+        repo.save(&record).unwrap();
+
+        assert_eq!(repo.authenticate(&key, now).unwrap(), Some(record.clone()));
+        let forged = format!("{API_KEY_PREFIX}{key_id}_{}", "0".repeat(64));
+        assert!(repo.authenticate(&forged, now).unwrap().is_none());
+        assert!(repo.authenticate("rwk_ffff_00", now).unwrap().is_none());
+        assert!(repo.authenticate("not-a-key", now).unwrap().is_none());
+
+        record.expires_at = Some(now - Duration::seconds(1));
+        repo.save(&record).unwrap();
+        assert!(repo.authenticate(&key, now).unwrap().is_none());
+
+        record.expires_at = None;
+        repo.save(&record).unwrap();
+        repo.touch(&key_id, now).unwrap();
+        repo.touch(&key_id, now + Duration::seconds(5)).unwrap();
+        assert_eq!(repo.get(&key_id).unwrap().last_used_at, Some(now));
+
+        let revoked = repo.revoke(&key_id, now).unwrap();
+        assert_eq!(revoked.revoked_at, Some(now));
+        assert!(repo.authenticate(&key, now).unwrap().is_none());
+        assert_eq!(repo.list_all().unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,252 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Per-wallet fiat auto top-up rules.
+//!
+//! A rule tops a wallet up from a payment mandate whenever its rEUR balance
+//! falls below a threshold. Rules live under
+//! `/data/fiat_auto_topups/{wallet_id}.json`, together with a short log of
+//! what the poller did with them.
+
+use chrono::{DateTime, Datelike, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::super::{EncryptedStorage, StorageError, StorageResult};
+
+/// Events kept per rule; older ones are dropped.
+const RETAINED_EVENTS: usize = 50;
+
+/// What happened when the poller evaluated a rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AutoTopUpEventKind {
+    /// An on-ramp was created.
+    Triggered,
+    /// The balance was low but this month's top-ups are used up.
+    MonthlyCapReached,
+    /// Creating the on-ramp failed.
+    Failed,
+}
+
+impl AutoTopUpEventKind {
+    /// Every kind, in declaration order.
+    pub const ALL: [AutoTopUpEventKind; 3] = [
+        AutoTopUpEventKind::Triggered,
+        AutoTopUpEventKind::MonthlyCapReached,
+        AutoTopUpEventKind::Failed,
+    ];
+
+    /// One-line description, as published in the event catalog.
+    pub fn description(self) -> &'static str {
+        match self {
+            AutoTopUpEventKind::Triggered => "An auto top-up created an on-ramp",
+            AutoTopUpEventKind::MonthlyCapReached => {
+                "The balance was low but this month's top-ups are used up"
+            }
+            AutoTopUpEventKind::Failed => "Creating the auto top-up on-ramp failed",
+        }
+    }
+}
+
+/// Entry of a rule's activity log, shown to the user as a notification.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AutoTopUpEvent {
+    pub at: DateTime<Utc>,
+    pub kind: AutoTopUpEventKind,
+    /// On-ramp created by a `triggered` event.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+    pub message: String,
+}
+
+/// Persisted auto top-up rule.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct StoredAutoTopUp {
+    pub wallet_id: String,
+    pub owner_user_id: String,
+    /// Mandate paying for the top-ups.
+    pub mandate_id: String,
+    /// Top up when the rEUR balance is below this (EUR).
+    pub threshold_eur: String,
+    /// On-ramp amount per top-up (EUR).
+    pub topup_eur: String,
+    /// Top-ups allowed per calendar month (UTC).
+    pub max_per_month: u32,
+    pub enabled: bool,
+    /// Most recent activity, oldest first.
+    #[serde(default)]
+    pub events: Vec<AutoTopUpEvent>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl StoredAutoTopUp {
+    /// Top-ups triggered in the calendar month of `now`.
+    pub fn triggered_in_month(&self, now: DateTime<Utc>) -> u32 {
+        self.events
+            .iter()
+            .filter(|e| e.kind == AutoTopUpEventKind::Triggered)
+            .filter(|e| e.at.year() == now.year() && e.at.month() == now.month())
+            .count() as u32
+    }
+
+    /// On-ramp created by the most recent top-up.
+    pub fn last_request_id(&self) -> Option<&str> {
+        self.events
+            .iter()
+            .rev()
+            .find(|e| e.kind == AutoTopUpEventKind::Triggered)
+            .and_then(|e| e.request_id.as_deref())
+    }
+
+    /// Append an event. A repeat of the previous non-trigger event only
+    /// refreshes its timestamp, so a persisting failure is not logged on
+    /// every poll.
+    pub fn record_event(
+        &mut self,
+        kind: AutoTopUpEventKind,
+        request_id: Option<String>,
+        message: String,
+        at: DateTime<Utc>,
+    ) {
+        if let Some(last) = self.events.last_mut() {
+            if kind != AutoTopUpEventKind::Triggered && last.kind == kind && last.message == message
+            {
+                last.at = at;
+                return;
+            }
+        }
+        self.events.push(AutoTopUpEvent {
+            at,
+            kind,
+            request_id,
+            message,
+        });
+        if self.events.len() > RETAINED_EVENTS {
+            let excess = self.events.len() - RETAINED_EVENTS;
+            self.events.drain(..excess);
+        }
+    }
+}
+
+/// Repository for auto top-up rules.
+pub struct AutoTopUpRepository<'a> {
+    storage: &'a EncryptedStorage,
+}
+
+impl<'a> AutoTopUpRepository<'a> {
+    /// Create repository.
+    pub fn new(storage: &'a EncryptedStorage) -> Self {
+        Self { storage }
+    }
+
+    /// Get the rule for a wallet.
+    pub fn get(&self, wallet_id: &str) -> StorageResult<StoredAutoTopUp> {
+        let path = self.storage.paths().fiat_auto_topup(wallet_id);
+        if !self.storage.exists(&path) {
+            return Err(StorageError::NotFound(format!(
+                "Auto top-up for {wallet_id}"
+            )));
+        }
+        self.storage.read_json(path)
+    }
+
+    /// Create or replace a wallet's rule.
+    pub fn save(&self, rule: &StoredAutoTopUp) -> StorageResult<()> {
+        self.storage
+            .write_json(self.storage.paths().fiat_auto_topup(&rule.wallet_id), rule)
+    }
+
+    /// Remove a wallet's rule.
+    pub fn delete(&self, wallet_id: &str) -> StorageResult<()> {
+        self.storage
+            .delete(self.storage.paths().fiat_auto_topup(wallet_id))
+    }
+
+    /// All rules (poller use).
+    pub fn list_all(&self) -> StorageResult<Vec<StoredAutoTopUp>> {
+        let ids = self
+            .storage
+            .list_files(self.storage.paths().fiat_auto_topups_dir(), "json")?;
+        Ok(ids.iter().filter_map(|id| self.get(id).ok()).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::StoragePaths;
+    use chrono::TimeDelta;
+    use std::env;
+    use std::fs;
+
+    fn test_storage() -> EncryptedStorage {
+        let test_dir =
+            env::temp_dir().join(format!("test-auto-topup-repo-{}", uuid::Uuid::new_v4()));
+        let paths = StoragePaths::new(&test_dir);
+        let mut storage = EncryptedStorage::new(paths);
+        storage.initialize().expect("initialize test storage");
+        storage
+    }
+
+    fn sample_rule() -> StoredAutoTopUp {
+        let now = Utc::now();
+        StoredAutoTopUp {
+            wallet_id: "wallet-1".to_string(),
+            owner_user_id: "user-1".to_string(),
+            mandate_id: "m-1".to_string(),
+            threshold_eur: "20.00".to_string(),
+            topup_eur: "50.00".to_string(),
+            max_per_month: 2,
+            enabled: true,
+            events: Vec::new(),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[test]
+    fn repeated_failures_collapse_and_triggers_count_per_month() {
+        let now = Utc::now();
+        let mut rule = sample_rule();
+        rule.record_event(
+            AutoTopUpEventKind::Triggered,
+            Some("old".into()),
+            "x".into(),
+            now - TimeDelta::days(40),
+        );
+        rule.record_event(AutoTopUpEventKind::Failed, None, "down".into(), now);
+        rule.record_event(AutoTopUpEventKind::Failed, None, "down".into(), now);
+        rule.record_event(
+            AutoTopUpEventKind::Triggered,
+            Some("req-1".into()),
+            "x".into(),
+            now,
+        );
+
+        assert_eq!(rule.events.len(), 3);
+        assert_eq!(rule.triggered_in_month(now), 1);
+        assert_eq!(rule.last_request_id(), Some("req-1"));
+    }
+
+    #[test]
+    fn save_list_and_delete() {
+        let storage = test_storage();
+        let repo = AutoTopUpRepository::new(&storage);
+        assert!(repo.list_all().unwrap().is_empty());
+
+        repo.save(&sample_rule()).unwrap();
+        assert_eq!(repo.get("wallet-1").unwrap().max_per_month, 2);
+        assert_eq!(repo.list_all().unwrap().len(), 1);
+
+        repo.delete("wallet-1").unwrap();
+        assert!(matches!(
+            repo.get("wallet-1"),
+            Err(StorageError::NotFound(_))
+        ));
+
+        let _ = fs::remove_dir_all(storage.paths().root());
+    }
+}
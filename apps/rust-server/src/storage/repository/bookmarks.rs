@@ -161,8 +161,6 @@ impl<'a> BookmarkRepository<'a> {
     }
 
     /// List all bookmarks owned by a user.
-    /// TODO: Use when implementing user-centric bookmark views
-    #[allow(dead_code)]
     pub fn list_by_owner(&self, owner_user_id: &str) -> StorageResult<Vec<StoredBookmark>> {
         let bookmark_ids = self
             .storage
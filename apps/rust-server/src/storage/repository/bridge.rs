@@ -0,0 +1,217 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Cross-chain USDC bridge transfers.
+//!
+//! A bridge transfer has two legs: a burn on the source chain and a mint on
+//! the destination chain, joined by the CCTP message and its attestation.
+//! Both legs are kept on one record at `/data/bridge/{transfer_id}.json`.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::super::{EncryptedStorage, StorageError, StorageResult};
+
+/// Which way a transfer crosses.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BridgeDirection {
+    /// From Avalanche to the configured remote chain.
+    #[default]
+    Outbound,
+    /// From the remote chain to Avalanche.
+    Inbound,
+}
+
+/// Lifecycle of a bridge transfer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BridgeStatus {
+    /// Burn transaction broadcast on the source chain.
+    Burning,
+    /// Burn confirmed; waiting for the attestation.
+    Attesting,
+    /// Mint transaction broadcast on the destination chain.
+    Minting,
+    /// Minted on the destination chain.
+    Completed,
+    /// A leg reverted; see `last_error`.
+    Failed,
+}
+
+impl BridgeStatus {
+    /// Whether the transfer can no longer change.
+    pub fn is_final(self) -> bool {
+        matches!(self, Self::Completed | Self::Failed)
+    }
+}
+
+/// A USDC transfer between Avalanche and the remote chain.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredBridgeTransfer {
+    pub transfer_id: String,
+    pub user_id: String,
+    pub wallet_id: String,
+    pub direction: BridgeDirection,
+    pub source_chain: String,
+    pub destination_chain: String,
+    /// CCTP domain IDs of both chains.
+    pub source_domain: u32,
+    pub destination_domain: u32,
+    /// Amount in human-readable units.
+    pub amount: String,
+    /// Amount in USDC base units.
+    pub amount_raw: String,
+    /// Address that burned on the source chain.
+    pub sender: String,
+    /// Address minted to on the destination chain.
+    pub recipient: String,
+    pub status: BridgeStatus,
+    /// `approve` of the token messenger, when one was needed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub approval_tx_hash: Option<String>,
+    /// Source leg.
+    pub burn_tx_hash: String,
+    pub burn_explorer_url: String,
+    /// CCTP message emitted by the burn, once known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub attestation: Option<String>,
+    /// Destination leg.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mint_tx_hash: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mint_explorer_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+/// Repository for bridge transfers.
+pub struct BridgeRepository<'a> {
+    storage: &'a EncryptedStorage,
+}
+
+impl<'a> BridgeRepository<'a> {
+    pub fn new(storage: &'a EncryptedStorage) -> Self {
+        Self { storage }
+    }
+
+    pub fn get(&self, transfer_id: &str) -> StorageResult<StoredBridgeTransfer> {
+        let path = self.storage.paths().bridge_transfer(transfer_id);
+        if !self.storage.exists(&path) {
+            return Err(StorageError::NotFound(format!(
+                "Bridge transfer {transfer_id}"
+            )));
+        }
+        self.storage.read_json(path)
+    }
+
+    pub fn create(&self, transfer: &StoredBridgeTransfer) -> StorageResult<()> {
+        let path = self.storage.paths().bridge_transfer(&transfer.transfer_id);
+        if self.storage.exists(&path) {
+            return Err(StorageError::AlreadyExists(format!(
+                "Bridge transfer {}",
+                transfer.transfer_id
+            )));
+        }
+        self.storage.write_json(path, transfer)
+    }
+
+    pub fn update(&self, transfer: &StoredBridgeTransfer) -> StorageResult<()> {
+        self.storage.write_json(
+            self.storage.paths().bridge_transfer(&transfer.transfer_id),
+            transfer,
+        )
+    }
+
+    /// A user's transfers, newest first.
+    pub fn list_for_user(&self, user_id: &str) -> StorageResult<Vec<StoredBridgeTransfer>> {
+        let ids = self
+            .storage
+            .list_files(self.storage.paths().bridge_dir(), "json")?;
+        let mut transfers: Vec<_> = ids
+            .iter()
+            .filter_map(|id| self.get(id).ok())
+            .filter(|transfer| transfer.user_id == user_id)
+            .collect();
+        transfers.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        Ok(transfers)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::StoragePaths;
+    use chrono::Duration;
+    use tempfile::TempDir;
+
+    fn transfer(
+        transfer_id: &str,
+        user_id: &str,
+        created_at: DateTime<Utc>,
+    ) -> StoredBridgeTransfer {
+        StoredBridgeTransfer {
+            transfer_id: transfer_id.to_string(),
+            user_id: user_id.to_string(),
+            wallet_id: "wallet-1".to_string(),
+            direction: BridgeDirection::Outbound,
+            source_chain: "avalanche-fuji".to_string(),
+            destination_chain: "ethereum-sepolia".to_string(),
+            source_domain: 1,
+            destination_domain: 0,
+            amount: "1.5".to_string(),
+            amount_raw: "1500000".to_string(),
+            sender: "0x1111111111111111111111111111111111111111".to_string(),
+            recipient: "0x1111111111111111111111111111111111111111".to_string(),
+            status: BridgeStatus::Burning,
+            approval_tx_hash: None,
+            burn_tx_hash: "0xburn".to_string(),
+            burn_explorer_url: "https://testnet.snowtrace.io/tx/0xburn".to_string(),
+            message: None,
+            attestation: None,
+            mint_tx_hash: None,
+            mint_explorer_url: None,
+            last_error: None,
+            created_at,
+            updated_at: created_at,
+            completed_at: None,
+        }
+    }
+
+    #[test]
+    fn transfers_are_listed_per_user_newest_first() {
+        let dir = TempDir::new().unwrap();
+        let mut storage = EncryptedStorage::new(StoragePaths::new(dir.path()));
+        storage.initialize().unwrap();
+        let repo = BridgeRepository::new(&storage);
+        let now = Utc::now();
+
+        repo.create(&transfer("br-1", "user-a", now - Duration::hours(1)))
+            .unwrap();
+        repo.create(&transfer("br-2", "user-a", now)).unwrap();
+        repo.create(&transfer("br-3", "user-b", now)).unwrap();
+        assert!(matches!(
+            repo.create(&transfer("br-1", "user-a", now)),
+            Err(StorageError::AlreadyExists(_))
+        ));
+
+        let mut second = repo.get("br-2").unwrap();
+        second.status = BridgeStatus::Completed;
+        second.mint_tx_hash = Some("0xmint".to_string());
+        repo.update(&second).unwrap();
+
+        let listed = repo.list_for_user("user-a").unwrap();
+        let ids: Vec<_> = listed.iter().map(|t| t.transfer_id.as_str()).collect();
+        assert_eq!(ids, ["br-2", "br-1"]);
+        assert_eq!(listed[0].mint_tx_hash.as_deref(), Some("0xmint"));
+        assert!(listed[0].status.is_final());
+    }
+}
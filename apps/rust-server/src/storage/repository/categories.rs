@@ -0,0 +1,127 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! User-defined transaction categories.
+//!
+//! A user's categories, their optional monthly budgets and the transactions
+//! assigned to them live in one file, `/data/categories/{user_key}.json`.
+//! Automatic categories are derived on read and never stored.
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::super::{EncryptedStorage, StorageResult};
+use super::sessions::user_key;
+
+/// A category created by a user.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, PartialEq, Eq)]
+pub struct UserCategory {
+    pub category_id: String,
+    pub name: String,
+    /// Monthly spending budget in EUR, if set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub monthly_budget_eur: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Everything a user stored about categories.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StoredUserCategories {
+    /// Oldest first.
+    #[serde(default)]
+    pub categories: Vec<UserCategory>,
+    /// Lowercase transaction hash to category ID.
+    #[serde(default)]
+    pub assignments: BTreeMap<String, String>,
+}
+
+impl StoredUserCategories {
+    pub fn get(&self, category_id: &str) -> Option<&UserCategory> {
+        self.categories
+            .iter()
+            .find(|c| c.category_id == category_id)
+    }
+
+    /// Category assigned to a transaction.
+    pub fn category_of(&self, tx_hash: &str) -> Option<&UserCategory> {
+        self.assignments
+            .get(&tx_hash.to_lowercase())
+            .and_then(|id| self.get(id))
+    }
+
+    /// Remove a category and its assignments. Returns whether it existed.
+    pub fn remove(&mut self, category_id: &str) -> bool {
+        let before = self.categories.len();
+        self.categories.retain(|c| c.category_id != category_id);
+        self.assignments.retain(|_, id| id != category_id);
+        self.categories.len() != before
+    }
+}
+
+/// Repository for user-defined categories.
+pub struct CategoryRepository<'a> {
+    storage: &'a EncryptedStorage,
+}
+
+impl<'a> CategoryRepository<'a> {
+    /// Create repository.
+    pub fn new(storage: &'a EncryptedStorage) -> Self {
+        Self { storage }
+    }
+
+    /// A user's categories; empty if they never created one.
+    pub fn get(&self, user_id: &str) -> StorageResult<StoredUserCategories> {
+        let path = self.storage.paths().user_categories(&user_key(user_id));
+        if !self.storage.exists(&path) {
+            return Ok(StoredUserCategories::default());
+        }
+        self.storage.read_json(path)
+    }
+
+    /// Replace a user's categories.
+    pub fn save(&self, user_id: &str, categories: &StoredUserCategories) -> StorageResult<()> {
+        let path = self.storage.paths().user_categories(&user_key(user_id));
+        self.storage.write_json(path, categories)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::StoragePaths;
+    use tempfile::TempDir;
+
+    #[test]
+    fn removing_a_category_drops_its_assignments() {
+        let temp = TempDir::new().unwrap();
+        let mut storage = EncryptedStorage::new(StoragePaths::new(temp.path()));
+        storage.initialize().unwrap();
+        let repo = CategoryRepository::new(&storage);
+        assert!(repo.get("user_1").unwrap().categories.is_empty());
+
+        let mut stored = StoredUserCategories::default();
+        for id in ["cat-a", "cat-b"] {
+            stored.categories.push(UserCategory {
+                category_id: id.to_string(),
+                name: id.to_string(),
+                monthly_budget_eur: None,
+                created_at: Utc::now(),
+            });
+        }
+        stored.assignments.insert("0xaa".into(), "cat-a".into());
+        stored.assignments.insert("0xbb".into(), "cat-b".into());
+        repo.save("user_1", &stored).unwrap();
+
+        let mut stored = repo.get("user_1").unwrap();
+        assert_eq!(stored.category_of("0xAA").unwrap().category_id, "cat-a");
+        assert!(stored.remove("cat-a"));
+        assert!(!stored.remove("cat-a"));
+        assert!(stored.category_of("0xaa").is_none());
+        assert_eq!(stored.assignments.len(), 1);
+        assert!(repo.get("user_2").unwrap().assignments.is_empty());
+    }
+}
@@ -0,0 +1,458 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Escrow sub-wallets, claimable transfers and escrowed payments.
+//!
+//! An escrow sub-wallet is an enclave-held key that belongs to no user. It
+//! holds funds on behalf of a pending transfer until the transfer settles,
+//! and is never reused. Keys live under `/data/escrow/keys/`, claimable
+//! transfers under `/data/escrow/claims/{claim_id}.json` and escrowed
+//! payments between users under `/data/escrow/payments/{escrow_id}.json`.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::super::{EncryptedStorage, StorageError, StorageResult};
+use super::service_wallet::generate_secp256k1_keypair;
+
+/// Lifecycle of a claimable transfer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ClaimStatus {
+    /// Funded and waiting for the recipient.
+    Pending,
+    /// A payout from the escrow is being broadcast.
+    Settling,
+    /// Paid out to the recipient.
+    Claimed,
+    /// Returned to the sender after expiry.
+    Reclaimed,
+}
+
+/// A transfer parked in an escrow sub-wallet until claimed via its link.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredClaim {
+    pub claim_id: String,
+    pub sender_user_id: String,
+    pub sender_wallet_id: String,
+    pub sender_address: String,
+    /// Address of the escrow sub-wallet (keyed by `claim_id`).
+    pub escrow_address: String,
+    /// `native` or an ERC-20 contract address.
+    pub token: String,
+    /// Amount in human-readable units.
+    pub amount: String,
+    /// When set, only the user whose wallet is registered for this email
+    /// hash can claim.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recipient_email_hash: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    /// Transfers from the sender into the escrow.
+    pub funding_tx_hashes: Vec<String>,
+    /// Whether the AVAX stipend paying the payout's gas was sent.
+    pub gas_funded: bool,
+    pub status: ClaimStatus,
+    pub expires_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub claimed_by_user_id: Option<String>,
+    /// Wallet the funds went to (the recipient's, or the sender's on reclaim).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payout_wallet_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payout_tx_hash: Option<String>,
+    /// Most recent funding or payout failure.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl super::super::OwnedResource for StoredClaim {
+    fn owner_user_id(&self) -> &str {
+        &self.sender_user_id
+    }
+}
+
+/// Lifecycle of an escrowed payment between two users.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum EscrowPaymentStatus {
+    /// Funds are in escrow; the payee has not asked for them yet.
+    Funded,
+    /// The payee asked for release; waiting for the payer.
+    ReleaseRequested,
+    /// The payer contested the release request; waiting for an arbiter.
+    Disputed,
+    /// A payout from the escrow is being broadcast.
+    Settling,
+    /// Paid out to the payee.
+    Released,
+    /// Returned to the payer.
+    Refunded,
+}
+
+impl EscrowPaymentStatus {
+    /// Whether the funds have left the escrow.
+    pub fn is_final(self) -> bool {
+        matches!(self, Self::Released | Self::Refunded)
+    }
+}
+
+/// Who moved an escrowed payment to a new status.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum EscrowActor {
+    Payer,
+    Payee,
+    /// An admin resolving a dispute.
+    Arbiter,
+    /// A timeout applied by the background worker.
+    System,
+}
+
+/// One entry in an escrowed payment's status history.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct EscrowTransition {
+    /// Status entered.
+    pub status: EscrowPaymentStatus,
+    pub actor: EscrowActor,
+    /// User acting; `None` for timeouts.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<String>,
+    pub at: DateTime<Utc>,
+    /// Reason given by the actor, or the failure that reverted a payout.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+/// A payment held in an escrow sub-wallet until the payer approves it, an
+/// arbiter decides, or a timeout applies.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredEscrowPayment {
+    pub escrow_id: String,
+    pub payer_user_id: String,
+    pub payer_wallet_id: String,
+    pub payer_address: String,
+    pub payee_user_id: String,
+    pub payee_wallet_id: String,
+    pub payee_address: String,
+    /// Address of the escrow sub-wallet (keyed by `escrow_id`).
+    pub escrow_address: String,
+    /// `native` or an ERC-20 contract address.
+    pub token: String,
+    /// Amount in human-readable units.
+    pub amount: String,
+    /// What the payment is for, as agreed between the parties.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Transfers from the payer into the escrow.
+    pub funding_tx_hashes: Vec<String>,
+    /// Whether the AVAX stipend paying the payout's gas was sent.
+    pub gas_funded: bool,
+    pub status: EscrowPaymentStatus,
+    /// The payee must request release before this; afterwards the payment
+    /// is refunded.
+    pub expires_at: DateTime<Utc>,
+    /// Set on a release request; the payer must approve or dispute before
+    /// this, or the payment is released.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub review_deadline: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub settlement_tx_hash: Option<String>,
+    /// Most recent funding or payout failure.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+    pub history: Vec<EscrowTransition>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl StoredEscrowPayment {
+    /// Whether `user_id` is the payer or the payee.
+    pub fn is_party(&self, user_id: &str) -> bool {
+        self.payer_user_id == user_id || self.payee_user_id == user_id
+    }
+
+    /// Move to `status` and append it to the history.
+    pub fn transition(
+        &mut self,
+        status: EscrowPaymentStatus,
+        actor: EscrowActor,
+        user_id: Option<&str>,
+        note: Option<String>,
+        now: DateTime<Utc>,
+    ) {
+        self.status = status;
+        self.updated_at = now;
+        self.history.push(EscrowTransition {
+            status,
+            actor,
+            user_id: user_id.map(str::to_string),
+            at: now,
+            note,
+        });
+    }
+}
+
+/// Repository for escrow keys, claimable transfers and escrowed payments.
+pub struct EscrowRepository<'a> {
+    storage: &'a EncryptedStorage,
+}
+
+impl<'a> EscrowRepository<'a> {
+    /// Create repository.
+    pub fn new(storage: &'a EncryptedStorage) -> Self {
+        Self { storage }
+    }
+
+    /// Generate the sub-wallet for `escrow_id` and return its address.
+    pub fn create_key(&self, escrow_id: &str) -> StorageResult<String> {
+        let path = self.storage.paths().escrow_key(escrow_id);
+        if self.storage.exists(&path) {
+            return Err(StorageError::AlreadyExists(format!(
+                "Escrow key {escrow_id}"
+            )));
+        }
+        let (private_key_pem, address) = generate_secp256k1_keypair()
+            .map_err(|e| StorageError::SerializationError(format!("key generation failed: {e}")))?;
+        self.storage.write_raw(path, private_key_pem.as_bytes())?;
+        Ok(address)
+    }
+
+    /// Read a sub-wallet's private key (PEM).
+    pub fn read_key(&self, escrow_id: &str) -> StorageResult<Vec<u8>> {
+        let path = self.storage.paths().escrow_key(escrow_id);
+        if !self.storage.exists(&path) {
+            return Err(StorageError::NotFound(format!("Escrow key {escrow_id}")));
+        }
+        self.storage.read_raw(path)
+    }
+
+    /// Remove a sub-wallet whose funding never happened.
+    pub fn delete_key(&self, escrow_id: &str) -> StorageResult<()> {
+        self.storage
+            .delete(self.storage.paths().escrow_key(escrow_id))
+    }
+
+    /// Get a claimable transfer.
+    pub fn get_claim(&self, claim_id: &str) -> StorageResult<StoredClaim> {
+        let path = self.storage.paths().escrow_claim(claim_id);
+        if !self.storage.exists(&path) {
+            return Err(StorageError::NotFound(format!("Claim {claim_id}")));
+        }
+        self.storage.read_json(path)
+    }
+
+    /// Store a new claimable transfer.
+    pub fn create_claim(&self, claim: &StoredClaim) -> StorageResult<()> {
+        let path = self.storage.paths().escrow_claim(&claim.claim_id);
+        if self.storage.exists(&path) {
+            return Err(StorageError::AlreadyExists(format!(
+                "Claim {}",
+                claim.claim_id
+            )));
+        }
+        self.storage.write_json(path, claim)
+    }
+
+    /// Overwrite a claimable transfer.
+    pub fn update_claim(&self, claim: &StoredClaim) -> StorageResult<()> {
+        self.storage
+            .write_json(self.storage.paths().escrow_claim(&claim.claim_id), claim)
+    }
+
+    /// All claimable transfers.
+    pub fn list_claims(&self) -> StorageResult<Vec<StoredClaim>> {
+        let ids = self
+            .storage
+            .list_files(self.storage.paths().escrow_claims_dir(), "json")?;
+        Ok(ids
+            .iter()
+            .filter_map(|id| self.get_claim(id).ok())
+            .collect())
+    }
+
+    /// A sender's claimable transfers, newest first.
+    pub fn list_claims_by_sender(&self, sender_user_id: &str) -> StorageResult<Vec<StoredClaim>> {
+        let mut claims: Vec<_> = self
+            .list_claims()?
+            .into_iter()
+            .filter(|claim| claim.sender_user_id == sender_user_id)
+            .collect();
+        claims.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        Ok(claims)
+    }
+
+    /// Get an escrowed payment.
+    pub fn get_payment(&self, escrow_id: &str) -> StorageResult<StoredEscrowPayment> {
+        let path = self.storage.paths().escrow_payment(escrow_id);
+        if !self.storage.exists(&path) {
+            return Err(StorageError::NotFound(format!("Escrow {escrow_id}")));
+        }
+        self.storage.read_json(path)
+    }
+
+    /// Store a new escrowed payment.
+    pub fn create_payment(&self, payment: &StoredEscrowPayment) -> StorageResult<()> {
+        let path = self.storage.paths().escrow_payment(&payment.escrow_id);
+        if self.storage.exists(&path) {
+            return Err(StorageError::AlreadyExists(format!(
+                "Escrow {}",
+                payment.escrow_id
+            )));
+        }
+        self.storage.write_json(path, payment)
+    }
+
+    /// Overwrite an escrowed payment.
+    pub fn update_payment(&self, payment: &StoredEscrowPayment) -> StorageResult<()> {
+        self.storage.write_json(
+            self.storage.paths().escrow_payment(&payment.escrow_id),
+            payment,
+        )
+    }
+
+    /// All escrowed payments.
+    pub fn list_payments(&self) -> StorageResult<Vec<StoredEscrowPayment>> {
+        let ids = self
+            .storage
+            .list_files(self.storage.paths().escrow_payments_dir(), "json")?;
+        Ok(ids
+            .iter()
+            .filter_map(|id| self.get_payment(id).ok())
+            .collect())
+    }
+
+    /// Escrowed payments where the user is payer or payee, newest first.
+    pub fn list_payments_for_user(&self, user_id: &str) -> StorageResult<Vec<StoredEscrowPayment>> {
+        let mut payments: Vec<_> = self
+            .list_payments()?
+            .into_iter()
+            .filter(|payment| payment.is_party(user_id))
+            .collect();
+        payments.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        Ok(payments)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::StoragePaths;
+    use chrono::Duration;
+    use tempfile::TempDir;
+
+    fn claim(claim_id: &str, sender: &str, created_at: DateTime<Utc>) -> StoredClaim {
+        StoredClaim {
+            claim_id: claim_id.to_string(),
+            sender_user_id: sender.to_string(),
+            sender_wallet_id: "wallet-1".to_string(),
+            sender_address: "0x1111111111111111111111111111111111111111".to_string(),
+            escrow_address: "0x2222222222222222222222222222222222222222".to_string(),
+            token: "native".to_string(),
+            amount: "1".to_string(),
+            recipient_email_hash: None,
+            note: None,
+            funding_tx_hashes: vec!["0xabc".to_string()],
+            gas_funded: true,
+            status: ClaimStatus::Pending,
+            expires_at: created_at + Duration::days(7),
+            claimed_by_user_id: None,
+            payout_wallet_id: None,
+            payout_tx_hash: None,
+            last_error: None,
+            created_at,
+            updated_at: created_at,
+        }
+    }
+
+    #[test]
+    fn escrow_keys_are_unique_per_claim_and_claims_list_per_sender() {
+        let temp = TempDir::new().unwrap();
+        let mut storage = EncryptedStorage::new(StoragePaths::new(temp.path()));
+        storage.initialize().unwrap();
+        let repo = EscrowRepository::new(&storage);
+
+        let address = repo.create_key("claim-1").unwrap();
+        assert!(address.starts_with("0x") && address.len() == 42);
+        assert!(repo.create_key("claim-1").is_err());
+        assert_ne!(repo.create_key("claim-2").unwrap(), address);
+        assert!(repo.read_key("claim-1").is_ok());
+        repo.delete_key("claim-2").unwrap();
+        assert!(repo.read_key("claim-2").is_err());
+
+        let now = Utc::now();
+        repo.create_claim(&claim("claim-1", "user-1", now - Duration::hours(1)))
+            .unwrap();
+        repo.create_claim(&claim("claim-3", "user-1", now)).unwrap();
+        repo.create_claim(&claim("claim-4", "user-2", now)).unwrap();
+        assert!(repo.create_claim(&claim("claim-1", "user-1", now)).is_err());
+
+        let mine = repo.list_claims_by_sender("user-1").unwrap();
+        assert_eq!(
+            mine.iter().map(|c| c.claim_id.as_str()).collect::<Vec<_>>(),
+            ["claim-3", "claim-1"]
+        );
+    }
+
+    #[test]
+    fn payments_list_for_both_parties_and_record_history() {
+        let temp = TempDir::new().unwrap();
+        let mut storage = EncryptedStorage::new(StoragePaths::new(temp.path()));
+        storage.initialize().unwrap();
+        let repo = EscrowRepository::new(&storage);
+
+        let now = Utc::now();
+        let mut payment = StoredEscrowPayment {
+            escrow_id: "esc-1".to_string(),
+            payer_user_id: "payer".to_string(),
+            payer_wallet_id: "wallet-1".to_string(),
+            payer_address: "0x1111111111111111111111111111111111111111".to_string(),
+            payee_user_id: "payee".to_string(),
+            payee_wallet_id: "wallet-2".to_string(),
+            payee_address: "0x3333333333333333333333333333333333333333".to_string(),
+            escrow_address: "0x2222222222222222222222222222222222222222".to_string(),
+            token: "native".to_string(),
+            amount: "1".to_string(),
+            description: None,
+            funding_tx_hashes: vec!["0xabc".to_string()],
+            gas_funded: true,
+            status: EscrowPaymentStatus::Funded,
+            expires_at: now + Duration::days(14),
+            review_deadline: None,
+            settlement_tx_hash: None,
+            last_error: None,
+            history: Vec::new(),
+            created_at: now,
+            updated_at: now,
+        };
+        payment.transition(
+            EscrowPaymentStatus::Funded,
+            EscrowActor::Payer,
+            Some("payer"),
+            None,
+            now,
+        );
+        repo.create_payment(&payment).unwrap();
+        assert!(repo.create_payment(&payment).is_err());
+
+        payment.transition(
+            EscrowPaymentStatus::ReleaseRequested,
+            EscrowActor::Payee,
+            Some("payee"),
+            Some("done".to_string()),
+            now,
+        );
+        repo.update_payment(&payment).unwrap();
+
+        let stored = repo.get_payment("esc-1").unwrap();
+        assert_eq!(stored.status, EscrowPaymentStatus::ReleaseRequested);
+        assert_eq!(stored.history.len(), 2);
+        assert_eq!(repo.list_payments_for_user("payee").unwrap().len(), 1);
+        assert_eq!(repo.list_payments_for_user("payer").unwrap().len(), 1);
+        assert!(repo.list_payments_for_user("other").unwrap().is_empty());
+    }
+}
@@ -0,0 +1,96 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Dev faucet usage per user.
+//!
+//! Counts the faucet drips a user received on the current UTC day, under
+//! `/data/faucet/{user_key}.json` keyed by a digest of the user ID. Only
+//! compiled with the `dev` feature.
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::super::{EncryptedStorage, StorageResult};
+use super::sessions::user_key;
+
+/// Drips a user received on one UTC day.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct StoredFaucetUsage {
+    pub day: NaiveDate,
+    pub drips: u32,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl StoredFaucetUsage {
+    /// Drips received on `day`; earlier days count as none.
+    pub fn drips_on(&self, day: NaiveDate) -> u32 {
+        if self.day == day {
+            self.drips
+        } else {
+            0
+        }
+    }
+}
+
+/// Repository for faucet usage.
+pub struct FaucetRepository<'a> {
+    storage: &'a EncryptedStorage,
+}
+
+impl<'a> FaucetRepository<'a> {
+    /// Create repository.
+    pub fn new(storage: &'a EncryptedStorage) -> Self {
+        Self { storage }
+    }
+
+    /// Usage of a user, if they ever used the faucet.
+    pub fn get(&self, user_id: &str) -> StorageResult<Option<StoredFaucetUsage>> {
+        let path = self.storage.paths().faucet_usage(&user_key(user_id));
+        if !self.storage.exists(&path) {
+            return Ok(None);
+        }
+        self.storage.read_json(path).map(Some)
+    }
+
+    /// Count one more drip for a user today. Returns today's total.
+    pub fn record_drip(&self, user_id: &str) -> StorageResult<u32> {
+        let now = Utc::now();
+        let today = now.date_naive();
+        let drips = self.get(user_id)?.map_or(0, |u| u.drips_on(today)) + 1;
+        let usage = StoredFaucetUsage {
+            day: today,
+            drips,
+            updated_at: now,
+        };
+        let path = self.storage.paths().faucet_usage(&user_key(user_id));
+        self.storage.write_json(path, &usage)?;
+        Ok(drips)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::StoragePaths;
+    use chrono::Duration;
+    use tempfile::TempDir;
+
+    #[test]
+    fn drips_are_counted_per_day() {
+        let dir = TempDir::new().unwrap();
+        let mut storage = EncryptedStorage::new(StoragePaths::new(dir.path()));
+        storage.initialize().unwrap();
+        let repo = FaucetRepository::new(&storage);
+        assert!(repo.get("user_1").unwrap().is_none());
+
+        assert_eq!(repo.record_drip("user_1").unwrap(), 1);
+        assert_eq!(repo.record_drip("user_1").unwrap(), 2);
+        assert_eq!(repo.record_drip("user_2").unwrap(), 1);
+
+        let usage = repo.get("user_1").unwrap().unwrap();
+        let today = Utc::now().date_naive();
+        assert_eq!(usage.drips_on(today), 2);
+        assert_eq!(usage.drips_on(today + Duration::days(1)), 0);
+    }
+}
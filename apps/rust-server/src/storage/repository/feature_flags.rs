@@ -0,0 +1,216 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Feature flag repository.
+//!
+//! Flags gate new capabilities (swaps, staking, ...) so they can be rolled
+//! out gradually without a redeploy. Each flag is stored under
+//! `/data/feature_flags/{key}.json`.
+//!
+//! A flag that is enabled applies to the users on its allow-list and to a
+//! percentage of everyone else. Users are assigned to a bucket from a hash
+//! of the flag key and their user ID, so raising the percentage only ever
+//! adds users, and each flag picks a different slice of the user base.
+//! A flag limited to some [tenants](crate::auth::tenant) is off for every
+//! other tenant, allow-list included.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use utoipa::ToSchema;
+
+use super::super::{EncryptedStorage, StorageError, StorageResult};
+
+/// A feature flag and its rollout.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, PartialEq, Eq)]
+pub struct StoredFeatureFlag {
+    /// Flag key, e.g. `swaps`.
+    pub key: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Master switch. A disabled flag is off for everyone.
+    pub enabled: bool,
+    /// Share of users (0-100) the flag is on for.
+    #[serde(default)]
+    pub rollout_percent: u8,
+    /// Users the flag is always on for while enabled.
+    #[serde(default)]
+    pub allowed_users: Vec<String>,
+    /// Tenants the flag is limited to; empty for all tenants.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tenants: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    /// Admin who last changed the flag.
+    pub updated_by: String,
+}
+
+impl StoredFeatureFlag {
+    /// Whether the flag is on for `user_id` in `tenant_id`.
+    pub fn is_enabled_for(&self, user_id: &str, tenant_id: Option<&str>) -> bool {
+        if !self.enabled {
+            return false;
+        }
+        if !self.tenants.is_empty()
+            && !tenant_id.is_some_and(|t| self.tenants.iter().any(|x| x == t))
+        {
+            return false;
+        }
+        if self.allowed_users.iter().any(|u| u == user_id) {
+            return true;
+        }
+        rollout_bucket(&self.key, user_id) < self.rollout_percent
+    }
+}
+
+/// Stable bucket in `0..100` for a user under a flag.
+fn rollout_bucket(key: &str, user_id: &str) -> u8 {
+    let digest = Sha256::digest(format!("{key}:{user_id}").as_bytes());
+    let n = u32::from_be_bytes([digest[0], digest[1], digest[2], digest[3]]);
+    (n % 100) as u8
+}
+
+/// Repository for feature flags.
+pub struct FeatureFlagRepository<'a> {
+    storage: &'a EncryptedStorage,
+}
+
+impl<'a> FeatureFlagRepository<'a> {
+    /// Create repository.
+    pub fn new(storage: &'a EncryptedStorage) -> Self {
+        Self { storage }
+    }
+
+    /// Get a flag by key.
+    pub fn get(&self, key: &str) -> StorageResult<StoredFeatureFlag> {
+        let path = self.storage.paths().feature_flag(key);
+        if !self.storage.exists(&path) {
+            return Err(StorageError::NotFound(format!("Feature flag {key}")));
+        }
+        self.storage.read_json(path)
+    }
+
+    /// Create or replace a flag.
+    pub fn save(&self, flag: &StoredFeatureFlag) -> StorageResult<()> {
+        self.storage
+            .write_json(self.storage.paths().feature_flag(&flag.key), flag)
+    }
+
+    /// Remove a flag.
+    pub fn delete(&self, key: &str) -> StorageResult<()> {
+        self.get(key)?;
+        self.storage.delete(self.storage.paths().feature_flag(key))
+    }
+
+    /// All flags, by key.
+    pub fn list_all(&self) -> StorageResult<Vec<StoredFeatureFlag>> {
+        let keys = self
+            .storage
+            .list_files(self.storage.paths().feature_flags_dir(), "json")?;
+        let mut flags: Vec<_> = keys.iter().filter_map(|k| self.get(k).ok()).collect();
+        flags.sort_by(|a, b| a.key.cmp(&b.key));
+        Ok(flags)
+    }
+
+    /// Whether `key` is on for `user_id` in `tenant_id`. Unknown flags are off.
+    pub fn is_enabled(&self, key: &str, user_id: &str, tenant_id: Option<&str>) -> bool {
+        self.get(key)
+            .map(|flag| flag.is_enabled_for(user_id, tenant_id))
+            .unwrap_or(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::StoragePaths;
+    use std::env;
+    use std::fs;
+
+    fn test_storage() -> EncryptedStorage {
+        let test_dir = env::temp_dir().join(format!("test-feature-flags-{}", uuid::Uuid::new_v4()));
+        let paths = StoragePaths::new(&test_dir);
+        let mut storage = EncryptedStorage::new(paths);
+        storage.initialize().expect("initialize test storage");
+        storage
+    }
+
+    fn flag(key: &str, enabled: bool, rollout_percent: u8) -> StoredFeatureFlag {
+        StoredFeatureFlag {
+            key: key.to_string(),
+            description: None,
+            enabled,
+            rollout_percent,
+            allowed_users: Vec::new(),
+            tenants: Vec::new(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            updated_by: "admin".to_string(),
+        }
+    }
+
+    #[test]
+    fn rollout_percentage_grows_the_same_user_set() {
+        let users: Vec<String> = (0..1000).map(|i| format!("user_{i}")).collect();
+        let on_at = |percent| {
+            let flag = flag("swaps", true, percent);
+            users
+                .iter()
+                .filter(|u| flag.is_enabled_for(u, None))
+                .cloned()
+                .collect::<Vec<_>>()
+        };
+        let ten = on_at(10);
+        let fifty = on_at(50);
+        assert!((50..150).contains(&ten.len()), "{}", ten.len());
+        assert!(ten.iter().all(|u| fifty.contains(u)));
+        assert_eq!(on_at(100).len(), users.len());
+        assert!(on_at(0).is_empty());
+    }
+
+    #[test]
+    fn allow_list_applies_only_while_enabled() {
+        let mut f = flag("staking", true, 0);
+        f.allowed_users = vec!["user_beta".to_string()];
+        assert!(f.is_enabled_for("user_beta", None));
+        assert!(!f.is_enabled_for("user_other", None));
+        f.enabled = false;
+        assert!(!f.is_enabled_for("user_beta", None));
+    }
+
+    #[test]
+    fn tenant_limited_flags_are_off_elsewhere() {
+        let mut f = flag("swaps", true, 100);
+        f.allowed_users = vec!["user_beta".to_string()];
+        f.tenants = vec!["org_a".to_string()];
+        assert!(f.is_enabled_for("user_1", Some("org_a")));
+        assert!(!f.is_enabled_for("user_1", Some("org_b")));
+        assert!(!f.is_enabled_for("user_beta", None));
+    }
+
+    #[test]
+    fn flags_are_saved_listed_and_deleted() {
+        let storage = test_storage();
+        let repo = FeatureFlagRepository::new(&storage);
+        repo.save(&flag("swaps", true, 100)).unwrap();
+        repo.save(&flag("staking", false, 100)).unwrap();
+
+        let keys: Vec<_> = repo
+            .list_all()
+            .unwrap()
+            .into_iter()
+            .map(|f| f.key)
+            .collect();
+        assert_eq!(keys, ["staking", "swaps"]);
+        assert!(repo.is_enabled("swaps", "user_1", None));
+        assert!(!repo.is_enabled("staking", "user_1", None));
+        assert!(!repo.is_enabled("unknown", "user_1", None));
+
+        repo.delete("swaps").unwrap();
+        assert!(repo.delete("swaps").is_err());
+        assert!(!repo.is_enabled("swaps", "user_1", None));
+
+        let _ = fs::remove_dir_all(storage.paths().root());
+    }
+}
@@ -8,7 +8,9 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
-use super::super::{EncryptedStorage, StorageError, StorageResult};
+use super::super::{EncryptedStorage, OwnershipEnforcer, StorageError, StorageResult};
+use crate::auth::AuthenticatedUser;
+use crate::events::{DomainEvent, EventBus};
 
 /// Fiat request direction.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
@@ -30,6 +32,12 @@ pub enum FiatRequestStatus {
     AwaitingProvider,
     /// Off-ramp request is waiting for user to deposit rEUR to service wallet.
     AwaitingUserDeposit,
+    /// Off-ramp beneficiary name did not match the user's verified name and
+    /// is waiting for an admin decision.
+    ReviewRequired,
+    /// On-ramp paid, but settlement waits for the destination wallet's owner
+    /// to reach the required KYC tier.
+    KycRequired,
     /// Request is in on-chain settlement step.
     SettlementPending,
     /// Provider flow started and waiting for completion.
@@ -40,6 +48,186 @@ pub enum FiatRequestStatus {
     Failed,
 }
 
+/// Who moves a fiat request to a new status.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FiatActor {
+    /// The owner, creating a request or re-checking KYC.
+    User,
+    /// A provider webhook (TrueLayer or card checkout).
+    Provider,
+    /// The background sync: provider polls, deposit detection and settlement.
+    Poller,
+    /// An admin deciding a review.
+    Admin,
+}
+
+impl FiatRequestStatus {
+    /// Whether the request can no longer change status.
+    pub fn is_final(self) -> bool {
+        matches!(self, Self::Completed | Self::Failed)
+    }
+
+    /// Whether `actor` may move a request from `from` to `to`. Staying in the
+    /// same status is always allowed.
+    pub fn can_transition(from: Self, to: Self, actor: FiatActor) -> bool {
+        use FiatActor::*;
+        use FiatRequestStatus::*;
+
+        if from == to {
+            return true;
+        }
+        match (from, to) {
+            (Completed | Failed, _) => false,
+
+            // Creation: the provider's first answer, or waiting for the
+            // off-ramp deposit.
+            (Queued, AwaitingProvider | SettlementPending | Failed) => {
+                matches!(actor, User | Provider | Poller)
+            }
+            (Queued, AwaitingUserDeposit) => actor == User,
+
+            (AwaitingProvider, SettlementPending | ProviderPending | Completed | Failed) => {
+                matches!(actor, Provider | Poller)
+            }
+
+            // The name check runs while the off-ramp is created; the deposit
+            // is detected, and the payout started, by the sync.
+            (AwaitingUserDeposit, ReviewRequired) => actor == User,
+            (AwaitingUserDeposit, ProviderPending | Completed | Failed) => actor == Poller,
+
+            (ReviewRequired, AwaitingUserDeposit | Failed) => actor == Admin,
+
+            (KycRequired, SettlementPending) => actor == User,
+            // Charged back before any rEUR was delivered.
+            (KycRequired, Failed) => actor == Provider,
+
+            (SettlementPending, KycRequired | Completed) => actor == Poller,
+            (SettlementPending, Failed) => matches!(actor, Provider | Poller),
+
+            (ProviderPending, Completed | Failed) => matches!(actor, Provider | Poller),
+
+            _ => false,
+        }
+    }
+}
+
+/// One entry of a fiat request's status transition log.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct FiatStatusTransition {
+    /// Status entered.
+    pub status: FiatRequestStatus,
+    /// When the status was first persisted.
+    pub at: DateTime<Utc>,
+    /// Failure reason, for transitions into `failed`, or the destination
+    /// KYC outcome for on-ramps that were checked.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+/// Recovery state of rEUR delivered for a charged-back card payment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ClawbackStatus {
+    /// Waiting for settlement to finish, or for the recovery transfer.
+    Pending,
+    /// The full settled amount was moved back to the reserve wallet.
+    Recovered,
+    /// Only part of the amount was still in the wallet and was recovered.
+    Partial,
+    /// No rEUR had been delivered, so nothing needed recovering.
+    NotRequired,
+    /// Recovery gave up; an admin must follow up.
+    Failed,
+}
+
+/// Chargeback (dispute) raised against an on-ramp's card payment.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct FiatChargeback {
+    /// Dispute ID at the provider.
+    pub dispute_id: String,
+    /// Reason given by the card network, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    pub received_at: DateTime<Utc>,
+    pub clawback_status: ClawbackStatus,
+    /// Recovery transfer attempts so far.
+    #[serde(default)]
+    pub clawback_attempts: u32,
+    /// EUR amount moved back to the reserve wallet.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub clawed_back_eur: Option<String>,
+    /// Recovery transfer tx hash.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub clawback_tx_hash: Option<String>,
+    /// Last error or shortfall explanation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+/// Admin decision on an off-ramp held for name review.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum NameReviewDecision {
+    /// Payout may proceed.
+    Approved,
+    /// Payout refused; the request fails.
+    Rejected,
+}
+
+/// Admin review of a beneficiary name mismatch.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct NameReview {
+    pub decision: NameReviewDecision,
+    /// Admin who decided.
+    pub reviewed_by: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    pub decided_at: DateTime<Utc>,
+}
+
+/// Comparison of an off-ramp's beneficiary name with the owner's verified
+/// name.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct BeneficiaryNameCheck {
+    /// Verified name compared against; absent if the user has none.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verified_name: Option<String>,
+    /// Similarity between the names, from 0 to 1.
+    pub score: f64,
+    /// Whether the score reached the match threshold.
+    pub matched: bool,
+    pub checked_at: DateTime<Utc>,
+    /// Admin decision, for mismatches.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub review: Option<NameReview>,
+}
+
+/// KYC check of a large on-ramp's destination wallet owner.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct DestinationKycCheck {
+    /// Owner of the destination wallet.
+    pub wallet_owner_user_id: String,
+    /// KYC tier the owner had when checked.
+    pub tier: u8,
+    /// Tier needed to settle this amount.
+    pub required_tier: u8,
+    pub passed: bool,
+    pub checked_at: DateTime<Utc>,
+}
+
+impl DestinationKycCheck {
+    /// One-line outcome for the transition log.
+    pub fn summary(&self) -> String {
+        let outcome = if self.passed { "passed" } else { "not passed" };
+        format!(
+            "Destination KYC {outcome}: tier {} of {} required",
+            self.tier, self.required_tier
+        )
+    }
+}
+
 /// Persisted fiat request record.
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct StoredFiatRequest {
@@ -49,11 +237,17 @@ pub struct StoredFiatRequest {
     pub wallet_id: String,
     /// Owner user ID.
     pub owner_user_id: String,
+    /// Tenant of the wallet; `None` for the default tenant.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tenant_id: Option<String>,
     /// On-ramp vs off-ramp direction.
     pub direction: FiatDirection,
     /// Requested fiat amount in EUR (human-readable decimal string).
     pub amount_eur: String,
-    /// Selected provider identifier (stub: `truelayer_sandbox`).
+    /// Tenant fee withheld from the amount, in EUR; `None` when no fee applies.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fee_eur: Option<String>,
+    /// Selected provider identifier (`truelayer_sandbox` or `card_sandbox`).
     pub provider: String,
     /// Optional user note.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -64,6 +258,13 @@ pub struct StoredFiatRequest {
     /// Beneficiary IBAN for off-ramp payout.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub beneficiary_iban: Option<String>,
+    /// Payment scheme the provider used for the off-ramp payout
+    /// (e.g. `sepa_credit_transfer_instant`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scheme_id: Option<String>,
+    /// When the provider executed the off-ramp payout.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub executed_at: Option<DateTime<Utc>>,
     /// Optional provider reference/session ID.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub provider_reference: Option<String>,
@@ -87,6 +288,29 @@ pub struct StoredFiatRequest {
     /// Reserve transfer tx hash for on-ramp settlement.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub reserve_transfer_tx_hash: Option<String>,
+    /// AVAX fee (wei, decimal string) the reserve wallet paid for the
+    /// settlement transfer, once its receipt has been observed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reserve_gas_spent_wei: Option<String>,
+    /// Number of on-ramps settled by `reserve_transfer_tx_hash` when it was a
+    /// batched disperse transfer. Requests sharing a hash form one batch.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub settlement_batch_size: Option<u32>,
+    /// Payment mandate that funded this on-ramp, instead of a hosted payment.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mandate_id: Option<String>,
+    /// Saved beneficiary this off-ramp pays out to, when one was used.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub beneficiary_id: Option<String>,
+    /// Beneficiary name check, for off-ramps above the check threshold.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name_check: Option<BeneficiaryNameCheck>,
+    /// Destination wallet KYC check, for on-ramps above the check threshold.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub destination_kyc: Option<DestinationKycCheck>,
+    /// Card chargeback raised against this on-ramp, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chargeback: Option<FiatChargeback>,
     /// Last provider webhook event id processed.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub provider_event_id: Option<String>,
@@ -99,9 +323,17 @@ pub struct StoredFiatRequest {
     /// Number of settlement transfer attempts (for on-ramp).
     #[serde(default)]
     pub settlement_attempts: u32,
+    /// Hashes of the on-chain transactions that settled this request, in
+    /// the order they were seen.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub settlement_tx_ids: Vec<String>,
     /// Failure reason for terminal failed state.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub failure_reason: Option<String>,
+    /// Status transitions in the order they were persisted. Records created
+    /// before the log existed start with an empty log.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub transitions: Vec<FiatStatusTransition>,
     /// Creation timestamp.
     pub created_at: DateTime<Utc>,
     /// Last update timestamp.
@@ -116,6 +348,10 @@ impl super::super::OwnedResource for StoredFiatRequest {
     fn owner_user_id(&self) -> &str {
         &self.owner_user_id
     }
+
+    fn in_tenant(&self, tenant_id: Option<&str>) -> bool {
+        self.tenant_id.as_deref() == tenant_id
+    }
 }
 
 impl StoredFiatRequest {
@@ -134,12 +370,16 @@ impl StoredFiatRequest {
             request_id,
             wallet_id,
             owner_user_id,
+            tenant_id: None,
             direction,
             amount_eur,
+            fee_eur: None,
             provider,
             note,
             beneficiary_account_holder_name: None,
             beneficiary_iban: None,
+            scheme_id: None,
+            executed_at: None,
             provider_reference: None,
             provider_action_url: None,
             status: FiatRequestStatus::Queued,
@@ -148,15 +388,108 @@ impl StoredFiatRequest {
             expected_amount_minor: None,
             deposit_tx_hash: None,
             reserve_transfer_tx_hash: None,
+            reserve_gas_spent_wei: None,
+            settlement_batch_size: None,
+            mandate_id: None,
+            beneficiary_id: None,
+            name_check: None,
+            destination_kyc: None,
+            chargeback: None,
             provider_event_id: None,
             last_provider_sync_at: None,
             last_chain_sync_at: None,
             settlement_attempts: 0,
+            settlement_tx_ids: Vec::new(),
             failure_reason: None,
+            transitions: vec![FiatStatusTransition {
+                status: FiatRequestStatus::Queued,
+                at: now,
+                detail: None,
+            }],
             created_at: now,
             updated_at: now,
         }
     }
+
+    /// Append the current status to the transition log if it differs from
+    /// the last logged one.
+    pub fn note_transition(&mut self) {
+        if self.transitions.last().map(|t| t.status) == Some(self.status) {
+            return;
+        }
+        let detail = match self.status {
+            FiatRequestStatus::Failed => self.failure_reason.clone(),
+            FiatRequestStatus::KycRequired | FiatRequestStatus::SettlementPending => self
+                .destination_kyc
+                .as_ref()
+                .map(DestinationKycCheck::summary),
+            _ => None,
+        };
+        self.transitions.push(FiatStatusTransition {
+            status: self.status,
+            at: self.updated_at,
+            detail,
+        });
+    }
+
+    /// Move to `to` on behalf of `actor`. Returns `false`, leaving the status
+    /// unchanged, if the transition is not allowed.
+    pub fn transition(&mut self, to: FiatRequestStatus, actor: FiatActor) -> bool {
+        if !FiatRequestStatus::can_transition(self.status, to, actor) {
+            tracing::warn!(
+                request_id = %self.request_id,
+                from = ?self.status,
+                to = ?to,
+                actor = ?actor,
+                "Rejected illegal fiat request status transition"
+            );
+            return false;
+        }
+        if self.status != to {
+            self.status = to;
+            self.updated_at = Utc::now();
+        }
+        true
+    }
+
+    /// Move to `failed` with `reason`, if `actor` may.
+    pub fn fail(&mut self, reason: impl Into<String>, actor: FiatActor) -> bool {
+        if !self.transition(FiatRequestStatus::Failed, actor) {
+            return false;
+        }
+        self.failure_reason = Some(reason.into());
+        true
+    }
+
+    /// Record a transaction that settled this request. Returns `false` if
+    /// it was already linked.
+    pub fn link_settlement_tx(&mut self, tx_hash: &str) -> bool {
+        if self
+            .settlement_tx_ids
+            .iter()
+            .any(|id| id.eq_ignore_ascii_case(tx_hash))
+        {
+            return false;
+        }
+        self.settlement_tx_ids.push(tx_hash.to_string());
+        true
+    }
+}
+
+/// A fiat request was created or changed status.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FiatStatusChange {
+    pub request_id: String,
+    pub status: FiatRequestStatus,
+}
+
+/// Published on the domain event bus, since repositories are created per
+/// call.
+fn publish_status(request: &StoredFiatRequest) {
+    EventBus::global().publish(DomainEvent::FiatStatusChanged(FiatStatusChange {
+        request_id: request.request_id.clone(),
+        status: request.status,
+    }));
 }
 
 /// Repository for fiat request storage.
@@ -242,6 +575,9 @@ impl<'a> FiatRequestRepository<'a> {
     }
 
     /// Persist new request.
+    ///
+    /// Callers that changed the status after `new_queued` should call
+    /// [`StoredFiatRequest::note_transition`] first.
     pub fn create(&self, request: &StoredFiatRequest) -> StorageResult<()> {
         if self.exists(&request.request_id) {
             return Err(StorageError::AlreadyExists(format!(
@@ -252,21 +588,28 @@ impl<'a> FiatRequestRepository<'a> {
         self.storage.write_json(
             self.storage.paths().fiat_request(&request.request_id),
             request,
-        )
+        )?;
+        publish_status(request);
+        Ok(())
     }
 
     /// Update existing request.
-    pub fn update(&self, request: &StoredFiatRequest) -> StorageResult<()> {
-        if !self.exists(&request.request_id) {
-            return Err(StorageError::NotFound(format!(
-                "Fiat request {}",
-                request.request_id
-            )));
-        }
+    ///
+    /// The stored transition log is authoritative: it replaces the caller's
+    /// copy, which may be stale, and the current status is appended to it if
+    /// it changed.
+    pub fn update(&self, request: &mut StoredFiatRequest) -> StorageResult<()> {
+        let stored = self.get(&request.request_id)?;
+        request.transitions = stored.transitions;
+        request.note_transition();
         self.storage.write_json(
             self.storage.paths().fiat_request(&request.request_id),
             request,
-        )
+        )?;
+        if request.status != stored.status {
+            publish_status(request);
+        }
+        Ok(())
     }
 
     /// List all requests for user.
@@ -285,25 +628,28 @@ impl<'a> FiatRequestRepository<'a> {
     ) -> StorageResult<Vec<StoredFiatRequest>> {
         self.list_matching(
             |record| {
-                if record.owner_user_id != owner_user_id {
-                    return false;
-                }
-                if let Some(wallet_id) = wallet_id {
-                    if record.wallet_id != wallet_id {
-                        return false;
-                    }
-                }
-                if let Some(statuses) = statuses {
-                    if !statuses.contains(&record.status) {
-                        return false;
-                    }
-                }
-                true
+                record.owner_user_id == owner_user_id
+                    && matches_filters(record, wallet_id, statuses)
             },
             limit,
         )
     }
 
+    /// List a user's requests within their tenant, with optional
+    /// wallet/status filters and limit.
+    pub fn list_filtered_for_user(
+        &self,
+        user: &AuthenticatedUser,
+        wallet_id: Option<&str>,
+        statuses: Option<&[FiatRequestStatus]>,
+        limit: Option<usize>,
+    ) -> StorageResult<Vec<StoredFiatRequest>> {
+        self.list_matching(
+            |record| record.is_owned_by(user) && matches_filters(record, wallet_id, statuses),
+            limit,
+        )
+    }
+
     /// List all requests (admin/system use).
     pub fn list_all(&self) -> StorageResult<Vec<StoredFiatRequest>> {
         self.list_matching(|_| true, None)
@@ -320,6 +666,15 @@ impl<'a> FiatRequestRepository<'a> {
     }
 }
 
+fn matches_filters(
+    record: &StoredFiatRequest,
+    wallet_id: Option<&str>,
+    statuses: Option<&[FiatRequestStatus]>,
+) -> bool {
+    wallet_id.is_none_or(|wallet_id| record.wallet_id == wallet_id)
+        && statuses.is_none_or(|statuses| statuses.contains(&record.status))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -327,6 +682,27 @@ mod tests {
     use std::env;
     use std::fs;
 
+    /// Every status, in declaration order.
+    const STATUSES: [FiatRequestStatus; 9] = [
+        FiatRequestStatus::Queued,
+        FiatRequestStatus::AwaitingProvider,
+        FiatRequestStatus::AwaitingUserDeposit,
+        FiatRequestStatus::ReviewRequired,
+        FiatRequestStatus::KycRequired,
+        FiatRequestStatus::SettlementPending,
+        FiatRequestStatus::ProviderPending,
+        FiatRequestStatus::Completed,
+        FiatRequestStatus::Failed,
+    ];
+
+    /// Every actor, in declaration order.
+    const ACTORS: [FiatActor; 4] = [
+        FiatActor::User,
+        FiatActor::Provider,
+        FiatActor::Poller,
+        FiatActor::Admin,
+    ];
+
     fn test_storage() -> EncryptedStorage {
         let test_dir = env::temp_dir().join(format!("test-fiat-repo-{}", uuid::Uuid::new_v4()));
         let paths = StoragePaths::new(&test_dir);
@@ -383,4 +759,161 @@ mod tests {
 
         cleanup(&storage);
     }
+
+    #[test]
+    fn update_appends_status_changes_to_stored_log() {
+        let storage = test_storage();
+        let repo = FiatRequestRepository::new(&storage);
+        let req = sample_request("req-1");
+        repo.create(&req).expect("create request");
+
+        // Stale copy without the log still extends the stored one.
+        let mut stale = req.clone();
+        stale.transitions.clear();
+        stale.status = FiatRequestStatus::AwaitingProvider;
+        repo.update(&mut stale).expect("update");
+        repo.update(&mut stale).expect("update unchanged status");
+
+        stale.status = FiatRequestStatus::Failed;
+        stale.failure_reason = Some("declined".to_string());
+        repo.update(&mut stale).expect("update to failed");
+
+        let loaded = repo.get("req-1").expect("get request");
+        let statuses: Vec<_> = loaded.transitions.iter().map(|t| t.status).collect();
+        assert_eq!(
+            statuses,
+            vec![
+                FiatRequestStatus::Queued,
+                FiatRequestStatus::AwaitingProvider,
+                FiatRequestStatus::Failed,
+            ]
+        );
+        assert_eq!(loaded.transitions[2].detail.as_deref(), Some("declined"));
+
+        cleanup(&storage);
+    }
+    #[test]
+    fn status_changes_are_published() {
+        let storage = test_storage();
+        let repo = FiatRequestRepository::new(&storage);
+        let mut events = EventBus::global().subscribe();
+        let id = format!("req-{}", uuid::Uuid::new_v4());
+        let mut req = sample_request(&id);
+        repo.create(&req).expect("create request");
+        repo.update(&mut req).expect("update unchanged status");
+        req.status = FiatRequestStatus::Completed;
+        repo.update(&mut req).expect("update to completed");
+
+        // Other tests publish on the same bus.
+        let mut statuses = Vec::new();
+        while let Ok(event) = events.try_recv() {
+            if let DomainEvent::FiatStatusChanged(change) = event {
+                if change.request_id == id {
+                    statuses.push(change.status);
+                }
+            }
+        }
+        assert_eq!(
+            statuses,
+            vec![FiatRequestStatus::Queued, FiatRequestStatus::Completed]
+        );
+
+        cleanup(&storage);
+    }
+
+    #[test]
+    fn only_listed_status_transitions_are_allowed() {
+        use FiatActor::*;
+        use FiatRequestStatus::*;
+
+        let allowed = [
+            (Queued, AwaitingProvider, vec![User, Provider, Poller]),
+            (Queued, SettlementPending, vec![User, Provider, Poller]),
+            (Queued, Failed, vec![User, Provider, Poller]),
+            (Queued, AwaitingUserDeposit, vec![User]),
+            (AwaitingProvider, SettlementPending, vec![Provider, Poller]),
+            (AwaitingProvider, ProviderPending, vec![Provider, Poller]),
+            (AwaitingProvider, Completed, vec![Provider, Poller]),
+            (AwaitingProvider, Failed, vec![Provider, Poller]),
+            (AwaitingUserDeposit, ReviewRequired, vec![User]),
+            (AwaitingUserDeposit, ProviderPending, vec![Poller]),
+            (AwaitingUserDeposit, Completed, vec![Poller]),
+            (AwaitingUserDeposit, Failed, vec![Poller]),
+            (ReviewRequired, AwaitingUserDeposit, vec![Admin]),
+            (ReviewRequired, Failed, vec![Admin]),
+            (KycRequired, SettlementPending, vec![User]),
+            (KycRequired, Failed, vec![Provider]),
+            (SettlementPending, KycRequired, vec![Poller]),
+            (SettlementPending, Completed, vec![Poller]),
+            (SettlementPending, Failed, vec![Provider, Poller]),
+            (ProviderPending, Completed, vec![Provider, Poller]),
+            (ProviderPending, Failed, vec![Provider, Poller]),
+        ];
+
+        for from in STATUSES {
+            for to in STATUSES {
+                for actor in ACTORS {
+                    let expected = from == to
+                        || allowed.iter().any(|(f, t, actors)| {
+                            *f == from && *t == to && actors.contains(&actor)
+                        });
+                    assert_eq!(
+                        FiatRequestStatus::can_transition(from, to, actor),
+                        expected,
+                        "{from:?} -> {to:?} by {actor:?}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn final_statuses_are_final_and_reachable_from_every_status() {
+        for from in STATUSES.into_iter().filter(|s| s.is_final()) {
+            for to in STATUSES.into_iter().filter(|to| *to != from) {
+                for actor in ACTORS {
+                    assert!(!FiatRequestStatus::can_transition(from, to, actor));
+                }
+            }
+        }
+
+        for start in STATUSES {
+            let mut reached = vec![start];
+            let mut i = 0;
+            while i < reached.len() {
+                for to in STATUSES {
+                    let step = ACTORS
+                        .into_iter()
+                        .any(|actor| FiatRequestStatus::can_transition(reached[i], to, actor));
+                    if step && !reached.contains(&to) {
+                        reached.push(to);
+                    }
+                }
+                i += 1;
+            }
+            assert!(
+                reached.iter().any(|s| s.is_final()),
+                "{start:?} cannot finish"
+            );
+        }
+    }
+
+    #[test]
+    fn illegal_transitions_leave_the_request_unchanged() {
+        let mut req = sample_request("req-1");
+        req.status = FiatRequestStatus::Completed;
+        let updated_at = req.updated_at;
+
+        assert!(!req.transition(FiatRequestStatus::AwaitingProvider, FiatActor::Provider));
+        assert!(!req.fail("late failure", FiatActor::Poller));
+        assert_eq!(req.status, FiatRequestStatus::Completed);
+        assert_eq!(req.failure_reason, None);
+        assert_eq!(req.updated_at, updated_at);
+
+        req.status = FiatRequestStatus::ReviewRequired;
+        assert!(!req.transition(FiatRequestStatus::AwaitingUserDeposit, FiatActor::User));
+        assert!(req.fail("name mismatch", FiatActor::Admin));
+        assert_eq!(req.status, FiatRequestStatus::Failed);
+        assert_eq!(req.failure_reason.as_deref(), Some("name mismatch"));
+    }
 }
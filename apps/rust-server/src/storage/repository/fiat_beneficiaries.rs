@@ -0,0 +1,171 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Saved off-ramp beneficiaries.
+//!
+//! A beneficiary is a bank account a user pays out to, stored once and then
+//! referenced by ID from off-ramp requests. Records live under
+//! `/data/fiat_beneficiaries/{beneficiary_id}.json`. Removed beneficiaries
+//! are kept so past off-ramps still resolve, but are no longer listed.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::super::{EncryptedStorage, StorageError, StorageResult};
+
+/// Whether a beneficiary's account has been shown to work.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum FiatBeneficiaryStatus {
+    /// No payout to this account has completed yet.
+    Unverified,
+    /// A payout to this account has completed.
+    Verified,
+}
+
+/// Persisted beneficiary record.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct StoredFiatBeneficiary {
+    pub beneficiary_id: String,
+    pub owner_user_id: String,
+    /// User-chosen display name.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    pub account_holder_name: String,
+    /// Compact, upper-case IBAN.
+    pub iban: String,
+    pub status: FiatBeneficiaryStatus,
+    /// Largest single payout, in euro cents.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_single_payout_minor: Option<u64>,
+    /// Largest total of payouts per calendar month (UTC), in euro cents.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_monthly_minor: Option<u64>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verified_at: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub removed_at: Option<DateTime<Utc>>,
+}
+
+impl super::super::OwnedResource for StoredFiatBeneficiary {
+    fn owner_user_id(&self) -> &str {
+        &self.owner_user_id
+    }
+}
+
+/// Repository for beneficiary storage.
+pub struct FiatBeneficiaryRepository<'a> {
+    storage: &'a EncryptedStorage,
+}
+
+impl<'a> FiatBeneficiaryRepository<'a> {
+    /// Create repository.
+    pub fn new(storage: &'a EncryptedStorage) -> Self {
+        Self { storage }
+    }
+
+    /// Get beneficiary by ID, including removed ones.
+    pub fn get(&self, beneficiary_id: &str) -> StorageResult<StoredFiatBeneficiary> {
+        let path = self.storage.paths().fiat_beneficiary(beneficiary_id);
+        if !self.storage.exists(&path) {
+            return Err(StorageError::NotFound(format!(
+                "Fiat beneficiary {beneficiary_id}"
+            )));
+        }
+        self.storage.read_json(path)
+    }
+
+    /// Persist a new beneficiary.
+    pub fn create(&self, beneficiary: &StoredFiatBeneficiary) -> StorageResult<()> {
+        let path = self
+            .storage
+            .paths()
+            .fiat_beneficiary(&beneficiary.beneficiary_id);
+        if self.storage.exists(&path) {
+            return Err(StorageError::AlreadyExists(format!(
+                "Fiat beneficiary {}",
+                beneficiary.beneficiary_id
+            )));
+        }
+        self.storage.write_json(path, beneficiary)
+    }
+
+    /// Update an existing beneficiary.
+    pub fn update(&self, beneficiary: &StoredFiatBeneficiary) -> StorageResult<()> {
+        self.get(&beneficiary.beneficiary_id)?;
+        self.storage.write_json(
+            self.storage
+                .paths()
+                .fiat_beneficiary(&beneficiary.beneficiary_id),
+            beneficiary,
+        )
+    }
+
+    /// List a user's beneficiaries that have not been removed, newest first.
+    pub fn list_by_owner(&self, owner_user_id: &str) -> StorageResult<Vec<StoredFiatBeneficiary>> {
+        let ids = self
+            .storage
+            .list_files(self.storage.paths().fiat_beneficiaries_dir(), "json")?;
+        let mut beneficiaries: Vec<StoredFiatBeneficiary> = ids
+            .iter()
+            .filter_map(|id| self.get(id).ok())
+            .filter(|b| b.owner_user_id == owner_user_id && b.removed_at.is_none())
+            .collect();
+        beneficiaries.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        Ok(beneficiaries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::StoragePaths;
+    use tempfile::TempDir;
+
+    fn sample(id: &str, owner: &str) -> StoredFiatBeneficiary {
+        let now = Utc::now();
+        StoredFiatBeneficiary {
+            beneficiary_id: id.to_string(),
+            owner_user_id: owner.to_string(),
+            label: None,
+            account_holder_name: "Jane Doe".to_string(),
+            iban: "GB33BUKB20201555555555".to_string(),
+            status: FiatBeneficiaryStatus::Unverified,
+            max_single_payout_minor: None,
+            max_monthly_minor: None,
+            created_at: now,
+            updated_at: now,
+            verified_at: None,
+            removed_at: None,
+        }
+    }
+
+    #[test]
+    fn create_update_and_list_skips_removed() {
+        let dir = TempDir::new().unwrap();
+        let mut storage = EncryptedStorage::new(StoragePaths::new(dir.path()));
+        storage.initialize().unwrap();
+        let repo = FiatBeneficiaryRepository::new(&storage);
+        assert!(repo.list_by_owner("user-1").unwrap().is_empty());
+
+        let mut first = sample("b-1", "user-1");
+        repo.create(&first).unwrap();
+        repo.create(&sample("b-2", "user-1")).unwrap();
+        repo.create(&sample("b-3", "user-2")).unwrap();
+        assert!(repo.create(&first).is_err());
+        assert_eq!(repo.list_by_owner("user-1").unwrap().len(), 2);
+
+        first.removed_at = Some(Utc::now());
+        repo.update(&first).unwrap();
+        let listed = repo.list_by_owner("user-1").unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].beneficiary_id, "b-2");
+        // Removed beneficiaries still resolve by ID.
+        assert!(repo.get("b-1").unwrap().removed_at.is_some());
+        assert!(matches!(repo.get("b-9"), Err(StorageError::NotFound(_))));
+    }
+}
@@ -0,0 +1,183 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Open-banking payment mandates (variable recurring payments).
+//!
+//! A mandate lets a user authorize their bank once and then fund on-ramps
+//! without going through the hosted payment page again. Records live under
+//! `/data/fiat_mandates/{mandate_id}.json`.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::super::{EncryptedStorage, StorageError, StorageResult};
+
+/// Mandate lifecycle status.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum FiatMandateStatus {
+    /// Waiting for the user to authorize the mandate with their bank.
+    AuthorizationRequired,
+    /// Usable for on-ramps.
+    Authorized,
+    /// Revoked by the user or their bank.
+    Revoked,
+    /// Authorization failed or was abandoned.
+    Failed,
+}
+
+/// Persisted mandate record.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct StoredFiatMandate {
+    pub mandate_id: String,
+    pub owner_user_id: String,
+    /// Wallet credited by on-ramps funded through this mandate.
+    pub wallet_id: String,
+    pub provider: String,
+    /// Mandate ID at the provider, once created there.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provider_mandate_id: Option<String>,
+    /// Hosted page where the user authorizes the mandate.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provider_action_url: Option<String>,
+    pub status: FiatMandateStatus,
+    /// Largest single on-ramp, in euro cents.
+    pub max_single_payment_minor: u64,
+    /// Largest total of on-ramps per calendar month (UTC), in euro cents.
+    pub max_monthly_minor: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failure_reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+impl super::super::OwnedResource for StoredFiatMandate {
+    fn owner_user_id(&self) -> &str {
+        &self.owner_user_id
+    }
+}
+
+/// Repository for mandate storage.
+pub struct FiatMandateRepository<'a> {
+    storage: &'a EncryptedStorage,
+}
+
+impl<'a> FiatMandateRepository<'a> {
+    /// Create repository.
+    pub fn new(storage: &'a EncryptedStorage) -> Self {
+        Self { storage }
+    }
+
+    /// Get mandate by ID.
+    pub fn get(&self, mandate_id: &str) -> StorageResult<StoredFiatMandate> {
+        let path = self.storage.paths().fiat_mandate(mandate_id);
+        if !self.storage.exists(&path) {
+            return Err(StorageError::NotFound(format!("Fiat mandate {mandate_id}")));
+        }
+        self.storage.read_json(path)
+    }
+
+    /// Persist a new mandate.
+    pub fn create(&self, mandate: &StoredFiatMandate) -> StorageResult<()> {
+        let path = self.storage.paths().fiat_mandate(&mandate.mandate_id);
+        if self.storage.exists(&path) {
+            return Err(StorageError::AlreadyExists(format!(
+                "Fiat mandate {}",
+                mandate.mandate_id
+            )));
+        }
+        self.storage.write_json(path, mandate)
+    }
+
+    /// Update an existing mandate.
+    pub fn update(&self, mandate: &StoredFiatMandate) -> StorageResult<()> {
+        let path = self.storage.paths().fiat_mandate(&mandate.mandate_id);
+        if !self.storage.exists(&path) {
+            return Err(StorageError::NotFound(format!(
+                "Fiat mandate {}",
+                mandate.mandate_id
+            )));
+        }
+        self.storage.write_json(path, mandate)
+    }
+
+    /// List a user's mandates, newest first.
+    pub fn list_by_owner(&self, owner_user_id: &str) -> StorageResult<Vec<StoredFiatMandate>> {
+        let ids = self
+            .storage
+            .list_files(self.storage.paths().fiat_mandates_dir(), "json")?;
+        let mut mandates: Vec<StoredFiatMandate> = ids
+            .iter()
+            .filter_map(|id| self.get(id).ok())
+            .filter(|mandate| mandate.owner_user_id == owner_user_id)
+            .collect();
+        mandates.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        Ok(mandates)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::StoragePaths;
+    use std::env;
+    use std::fs;
+
+    fn test_storage() -> EncryptedStorage {
+        let test_dir =
+            env::temp_dir().join(format!("test-fiat-mandate-repo-{}", uuid::Uuid::new_v4()));
+        let paths = StoragePaths::new(&test_dir);
+        let mut storage = EncryptedStorage::new(paths);
+        storage.initialize().expect("initialize test storage");
+        storage
+    }
+
+    fn cleanup(storage: &EncryptedStorage) {
+        let _ = fs::remove_dir_all(storage.paths().root());
+    }
+
+    fn sample_mandate(id: &str, owner: &str) -> StoredFiatMandate {
+        let now = Utc::now();
+        StoredFiatMandate {
+            mandate_id: id.to_string(),
+            owner_user_id: owner.to_string(),
+            wallet_id: "wallet-1".to_string(),
+            provider: "truelayer_sandbox".to_string(),
+            provider_mandate_id: None,
+            provider_action_url: None,
+            status: FiatMandateStatus::AuthorizationRequired,
+            max_single_payment_minor: 10_000,
+            max_monthly_minor: 50_000,
+            failure_reason: None,
+            created_at: now,
+            updated_at: now,
+            revoked_at: None,
+        }
+    }
+
+    #[test]
+    fn create_update_and_list_by_owner() {
+        let storage = test_storage();
+        let repo = FiatMandateRepository::new(&storage);
+        assert!(repo.list_by_owner("user-1").unwrap().is_empty());
+
+        let mut mandate = sample_mandate("m-1", "user-1");
+        repo.create(&mandate).unwrap();
+        repo.create(&sample_mandate("m-2", "user-2")).unwrap();
+        assert!(repo.create(&mandate).is_err());
+
+        mandate.status = FiatMandateStatus::Authorized;
+        repo.update(&mandate).unwrap();
+
+        let owned = repo.list_by_owner("user-1").unwrap();
+        assert_eq!(owned.len(), 1);
+        assert_eq!(owned[0].status, FiatMandateStatus::Authorized);
+        assert!(matches!(repo.get("m-3"), Err(StorageError::NotFound(_))));
+
+        cleanup(&storage);
+    }
+}
@@ -0,0 +1,100 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Per-wallet monthly spending aggregates.
+//!
+//! Written by the [insights aggregator](crate::insights) under
+//! `/data/insights/{wallet_id}.json`, one entry per `YYYY-MM` month with
+//! activity. EUR amounts are kept as floats; responses round them.
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::super::{EncryptedStorage, StorageResult};
+
+/// Totals exchanged with one counterparty in a month.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct CounterpartyTotals {
+    pub sent_eur: f64,
+    pub received_eur: f64,
+    pub transactions: u32,
+}
+
+/// One month of a wallet's confirmed activity, valued in EUR.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct MonthlyInsights {
+    /// Outgoing value, excluding transfers between the owner's wallets.
+    pub spent_eur: f64,
+    /// Incoming value, excluding transfers between the owner's wallets.
+    pub received_eur: f64,
+    /// Outgoing value per automatic category.
+    #[serde(default)]
+    pub by_category: BTreeMap<String, f64>,
+    /// Outgoing value per user category ID.
+    #[serde(default)]
+    pub by_user_category: BTreeMap<String, f64>,
+    /// Outgoing value not assigned to a user category.
+    pub uncategorized_eur: f64,
+    /// Largest counterparties by volume, keyed by lowercase address.
+    #[serde(default)]
+    pub counterparties: BTreeMap<String, CounterpartyTotals>,
+    /// Confirmed transactions counted.
+    pub transactions: u32,
+    /// Transactions without a recorded price, left out of the totals.
+    pub unpriced: u32,
+    pub computed_at: DateTime<Utc>,
+}
+
+/// A wallet's monthly aggregates.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StoredWalletInsights {
+    pub wallet_id: String,
+    /// Keyed by `YYYY-MM`.
+    #[serde(default)]
+    pub months: BTreeMap<String, MonthlyInsights>,
+    /// Last aggregator run over this wallet.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// Repository for wallet insights.
+pub struct InsightsRepository<'a> {
+    storage: &'a EncryptedStorage,
+}
+
+impl<'a> InsightsRepository<'a> {
+    /// Create repository.
+    pub fn new(storage: &'a EncryptedStorage) -> Self {
+        Self { storage }
+    }
+
+    /// A wallet's aggregates; empty before the aggregator first covers it.
+    pub fn get(&self, wallet_id: &str) -> StorageResult<StoredWalletInsights> {
+        let path = self.storage.paths().wallet_insights(wallet_id);
+        if !self.storage.exists(&path) {
+            return Ok(StoredWalletInsights {
+                wallet_id: wallet_id.to_string(),
+                ..Default::default()
+            });
+        }
+        self.storage.read_json(path)
+    }
+
+    /// Replace a wallet's aggregates.
+    pub fn save(&self, insights: &StoredWalletInsights) -> StorageResult<()> {
+        let path = self.storage.paths().wallet_insights(&insights.wallet_id);
+        self.storage.write_json(path, insights)
+    }
+
+    /// Drop a month so the next aggregator run recomputes it.
+    pub fn invalidate_month(&self, wallet_id: &str, month: &str) -> StorageResult<()> {
+        let mut insights = self.get(wallet_id)?;
+        if insights.months.remove(month).is_some() {
+            self.save(&insights)?;
+        }
+        Ok(())
+    }
+}
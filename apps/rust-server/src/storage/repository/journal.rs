@@ -0,0 +1,475 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Double-entry accounting journal.
+//!
+//! Every value movement the service knows about is posted as a balanced
+//! [`JournalEntry`] between [`JournalAccount`]s: confirmed on-chain
+//! transfers, fiat settlements, tenant fees and the reserve wallet's network
+//! fees. Entries are kept next to the records they are derived from, under
+//! `/data/system/journal/{entry_id}.json`, and are never rewritten: the
+//! entry ID is derived from the source record, so posting the same movement
+//! twice is a no-op.
+//!
+//! Amounts are human-readable decimal strings in the entry's asset, summed
+//! at [`JOURNAL_DECIMALS`] so tokens with up to 18 decimals and EUR share
+//! one arithmetic.
+
+use std::collections::BTreeMap;
+
+use alloy::primitives::{I256, U256};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::super::{EncryptedStorage, StorageResult};
+use crate::blockchain::{format_amount, parse_amount};
+
+/// Fixed-point precision used to sum journal amounts.
+pub const JOURNAL_DECIMALS: u8 = 18;
+
+/// Asset of fiat settlements and fees.
+pub const FIAT_ASSET: &str = "EUR";
+
+/// A ledger account.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum JournalAccount {
+    /// A custodial user wallet.
+    Wallet { wallet_id: String },
+    /// The fiat reserve: the reserve wallet on chain, the provider account
+    /// for EUR.
+    Reserve,
+    /// Tenant fees withheld from fiat requests.
+    Fees,
+    /// An escrow sub-wallet of a claimable transfer or escrowed payment.
+    Escrow { escrow_id: String },
+    /// Gas paid by the reserve wallet.
+    NetworkFees,
+    /// Everything outside the service: external addresses and bank
+    /// accounts.
+    External,
+}
+
+impl JournalAccount {
+    /// Stable key, e.g. `wallet:{wallet_id}`, `escrow:{escrow_id}` or
+    /// `reserve`.
+    pub fn key(&self) -> String {
+        match self {
+            Self::Wallet { wallet_id } => format!("wallet:{wallet_id}"),
+            Self::Reserve => "reserve".to_string(),
+            Self::Fees => "fees".to_string(),
+            Self::Escrow { escrow_id } => format!("escrow:{escrow_id}"),
+            Self::NetworkFees => "network_fees".to_string(),
+            Self::External => "external".to_string(),
+        }
+    }
+}
+
+/// What an entry was derived from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum JournalSource {
+    /// A confirmed on-chain transfer.
+    OnChainTx,
+    /// The fiat side of a completed on-ramp or off-ramp.
+    FiatSettlement,
+    /// A tenant fee withheld from a fiat request.
+    Fee,
+    /// Gas the reserve wallet paid for a settlement transfer.
+    NetworkFee,
+}
+
+impl JournalSource {
+    fn id_prefix(self) -> &'static str {
+        match self {
+            Self::OnChainTx => "tx",
+            Self::FiatSettlement => "fiat",
+            Self::Fee => "fee",
+            Self::NetworkFee => "gas",
+        }
+    }
+}
+
+/// Side of a posting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PostingSide {
+    /// Value into the account.
+    Debit,
+    /// Value out of the account.
+    Credit,
+}
+
+/// One line of an entry.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct JournalPosting {
+    pub account: JournalAccount,
+    pub side: PostingSide,
+    /// Amount in the entry's asset, decimal.
+    pub amount: String,
+}
+
+/// A balanced movement of one asset.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct JournalEntry {
+    /// `{source}-{reference}`, e.g. `tx-0xabc…` or `fee-{request_id}`.
+    pub entry_id: String,
+    pub source: JournalSource,
+    /// Transaction hash or fiat request ID the entry was derived from.
+    pub reference: String,
+    /// `{network}:native`, `{network}:{contract}` or `EUR`.
+    pub asset: String,
+    pub postings: Vec<JournalPosting>,
+    /// When the movement happened.
+    pub occurred_at: DateTime<Utc>,
+    pub recorded_at: DateTime<Utc>,
+}
+
+/// Amount at [`JOURNAL_DECIMALS`]; `None` when unreadable.
+pub fn journal_units(amount: &str) -> Option<U256> {
+    parse_amount(amount.trim(), JOURNAL_DECIMALS).ok()
+}
+
+fn format_signed(value: I256) -> String {
+    let formatted = format_amount(value.unsigned_abs(), JOURNAL_DECIMALS);
+    if value.is_negative() {
+        format!("-{formatted}")
+    } else {
+        formatted
+    }
+}
+
+impl JournalEntry {
+    /// Entry moving `amount` of `asset` from one account to another: a
+    /// credit to `from` and a debit to `to`. `None` when the amount is zero
+    /// or unreadable.
+    pub fn transfer(
+        source: JournalSource,
+        reference: &str,
+        asset: &str,
+        from: JournalAccount,
+        to: JournalAccount,
+        amount: &str,
+        occurred_at: DateTime<Utc>,
+    ) -> Option<Self> {
+        let units = journal_units(amount).filter(|units| !units.is_zero())?;
+        let amount = format_amount(units, JOURNAL_DECIMALS);
+        Some(Self {
+            entry_id: Self::entry_id(source, reference),
+            source,
+            reference: reference.to_string(),
+            asset: asset.to_string(),
+            postings: vec![
+                JournalPosting {
+                    account: from,
+                    side: PostingSide::Credit,
+                    amount: amount.clone(),
+                },
+                JournalPosting {
+                    account: to,
+                    side: PostingSide::Debit,
+                    amount,
+                },
+            ],
+            occurred_at,
+            recorded_at: Utc::now(),
+        })
+    }
+
+    /// ID of the entry derived from `reference`.
+    pub fn entry_id(source: JournalSource, reference: &str) -> String {
+        format!("{}-{}", source.id_prefix(), reference.to_ascii_lowercase())
+    }
+
+    /// Whether debits and credits are equal and every amount is readable.
+    pub fn is_balanced(&self) -> bool {
+        let mut net = I256::ZERO;
+        for posting in &self.postings {
+            let Some(units) = journal_units(&posting.amount).and_then(|u| I256::try_from(u).ok())
+            else {
+                return false;
+            };
+            net = match posting.side {
+                PostingSide::Debit => net.saturating_add(units),
+                PostingSide::Credit => net.saturating_sub(units),
+            };
+        }
+        !self.postings.is_empty() && net.is_zero()
+    }
+
+    /// Whether any posting is to `account_key`.
+    pub fn touches(&self, account_key: &str) -> bool {
+        self.postings.iter().any(|p| p.account.key() == account_key)
+    }
+}
+
+/// Totals of one account in one asset.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct AccountBalance {
+    /// Account key, e.g. `wallet:{wallet_id}`.
+    pub account: String,
+    pub debits: String,
+    pub credits: String,
+    /// Debits minus credits; negative for accounts value left, such as
+    /// `external` on a net inflow.
+    pub balance: String,
+}
+
+/// Trial balance of one asset.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct AssetTrialBalance {
+    pub asset: String,
+    pub total_debits: String,
+    pub total_credits: String,
+    /// Whether total debits equal total credits.
+    pub balanced: bool,
+    pub accounts: Vec<AccountBalance>,
+}
+
+/// Trial balance over a set of entries.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct TrialBalance {
+    /// Whether every entry and every asset balances.
+    pub balanced: bool,
+    pub entry_count: usize,
+    pub assets: Vec<AssetTrialBalance>,
+    /// Entries whose own postings do not balance.
+    pub unbalanced_entries: Vec<String>,
+}
+
+impl TrialBalance {
+    /// Sum `entries` per asset and account.
+    pub fn compute(entries: &[JournalEntry]) -> Self {
+        #[derive(Default)]
+        struct Totals {
+            debits: U256,
+            credits: U256,
+        }
+        let mut by_asset: BTreeMap<&str, BTreeMap<String, Totals>> = BTreeMap::new();
+        let mut unbalanced_entries = Vec::new();
+        for entry in entries {
+            if !entry.is_balanced() {
+                unbalanced_entries.push(entry.entry_id.clone());
+            }
+            let accounts = by_asset.entry(entry.asset.as_str()).or_default();
+            for posting in &entry.postings {
+                let units = journal_units(&posting.amount).unwrap_or(U256::ZERO);
+                let totals = accounts.entry(posting.account.key()).or_default();
+                match posting.side {
+                    PostingSide::Debit => totals.debits = totals.debits.saturating_add(units),
+                    PostingSide::Credit => totals.credits = totals.credits.saturating_add(units),
+                }
+            }
+        }
+
+        let assets: Vec<AssetTrialBalance> = by_asset
+            .into_iter()
+            .map(|(asset, accounts)| {
+                let total_debits = accounts
+                    .values()
+                    .fold(U256::ZERO, |acc, t| acc.saturating_add(t.debits));
+                let total_credits = accounts
+                    .values()
+                    .fold(U256::ZERO, |acc, t| acc.saturating_add(t.credits));
+                AssetTrialBalance {
+                    asset: asset.to_string(),
+                    total_debits: format_amount(total_debits, JOURNAL_DECIMALS),
+                    total_credits: format_amount(total_credits, JOURNAL_DECIMALS),
+                    balanced: total_debits == total_credits,
+                    accounts: accounts
+                        .into_iter()
+                        .map(|(account, t)| {
+                            let balance = I256::try_from(t.debits)
+                                .unwrap_or(I256::MAX)
+                                .saturating_sub(I256::try_from(t.credits).unwrap_or(I256::MAX));
+                            AccountBalance {
+                                account,
+                                debits: format_amount(t.debits, JOURNAL_DECIMALS),
+                                credits: format_amount(t.credits, JOURNAL_DECIMALS),
+                                balance: format_signed(balance),
+                            }
+                        })
+                        .collect(),
+                }
+            })
+            .collect();
+        Self {
+            balanced: unbalanced_entries.is_empty() && assets.iter().all(|a| a.balanced),
+            entry_count: entries.len(),
+            assets,
+            unbalanced_entries,
+        }
+    }
+}
+
+/// Repository for journal entries.
+pub struct JournalRepository<'a> {
+    storage: &'a EncryptedStorage,
+}
+
+impl<'a> JournalRepository<'a> {
+    /// Create repository.
+    pub fn new(storage: &'a EncryptedStorage) -> Self {
+        Self { storage }
+    }
+
+    /// Whether the entry with this ID was posted.
+    pub fn exists(&self, entry_id: &str) -> bool {
+        self.storage
+            .exists(self.storage.paths().journal_entry(entry_id))
+    }
+
+    /// Post an entry unless one with its ID exists. Returns whether it was
+    /// written.
+    pub fn post(&self, entry: &JournalEntry) -> StorageResult<bool> {
+        if self.exists(&entry.entry_id) {
+            return Ok(false);
+        }
+        self.storage
+            .write_json(self.storage.paths().journal_entry(&entry.entry_id), entry)?;
+        Ok(true)
+    }
+
+    /// All entries, oldest first.
+    pub fn list(&self) -> StorageResult<Vec<JournalEntry>> {
+        let ids = self
+            .storage
+            .list_files(self.storage.paths().journal_dir(), "json")?;
+        let mut entries: Vec<JournalEntry> = ids
+            .iter()
+            .filter_map(|id| {
+                self.storage
+                    .read_json(self.storage.paths().journal_entry(id))
+                    .ok()
+            })
+            .collect();
+        entries.sort_by(|a, b| {
+            a.occurred_at
+                .cmp(&b.occurred_at)
+                .then_with(|| a.entry_id.cmp(&b.entry_id))
+        });
+        Ok(entries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::StoragePaths;
+    use std::env;
+    use std::fs;
+
+    fn test_storage() -> EncryptedStorage {
+        let test_dir = env::temp_dir().join(format!("test-journal-repo-{}", uuid::Uuid::new_v4()));
+        let paths = StoragePaths::new(&test_dir);
+        let mut storage = EncryptedStorage::new(paths);
+        storage.initialize().expect("initialize test storage");
+        storage
+    }
+
+    fn wallet(id: &str) -> JournalAccount {
+        JournalAccount::Wallet {
+            wallet_id: id.to_string(),
+        }
+    }
+
+    #[test]
+    fn posting_is_idempotent_per_source() {
+        let storage = test_storage();
+        let repo = JournalRepository::new(&storage);
+        let entry = JournalEntry::transfer(
+            JournalSource::OnChainTx,
+            "0xABC",
+            "fuji:native",
+            wallet("w-1"),
+            JournalAccount::External,
+            "1.50",
+            Utc::now(),
+        )
+        .unwrap();
+        assert_eq!(entry.entry_id, "tx-0xabc");
+        assert_eq!(entry.postings[0].amount, "1.5");
+        assert!(entry.is_balanced());
+
+        assert!(repo.post(&entry).unwrap());
+        assert!(!repo.post(&entry).unwrap());
+        assert_eq!(repo.list().unwrap(), vec![entry]);
+
+        let _ = fs::remove_dir_all(storage.paths().root());
+    }
+
+    #[test]
+    fn trial_balance_sums_accounts_per_asset() {
+        let now = Utc::now();
+        let deposit = JournalEntry::transfer(
+            JournalSource::FiatSettlement,
+            "req-1",
+            FIAT_ASSET,
+            JournalAccount::External,
+            JournalAccount::Reserve,
+            "100",
+            now,
+        )
+        .unwrap();
+        let fee = JournalEntry::transfer(
+            JournalSource::Fee,
+            "req-1",
+            FIAT_ASSET,
+            JournalAccount::Reserve,
+            JournalAccount::Fees,
+            "1.25",
+            now,
+        )
+        .unwrap();
+        let send = JournalEntry::transfer(
+            JournalSource::OnChainTx,
+            "0x01",
+            "fuji:native",
+            wallet("w-1"),
+            wallet("w-2"),
+            "0.000000000000000001",
+            now,
+        )
+        .unwrap();
+        let mut broken = send.clone();
+        broken.entry_id = "tx-0x02".to_string();
+        broken.postings[1].amount = "2".to_string();
+
+        let balance = TrialBalance::compute(&[deposit.clone(), fee.clone(), send.clone()]);
+        assert!(balance.balanced);
+        assert_eq!(balance.entry_count, 3);
+        let eur = &balance.assets[0];
+        assert_eq!(eur.asset, "EUR");
+        assert_eq!(eur.total_debits, "101.25");
+        let reserve = eur
+            .accounts
+            .iter()
+            .find(|a| a.account == "reserve")
+            .unwrap();
+        assert_eq!(reserve.balance, "98.75");
+        let external = eur
+            .accounts
+            .iter()
+            .find(|a| a.account == "external")
+            .unwrap();
+        assert_eq!(external.balance, "-100");
+
+        let balance = TrialBalance::compute(&[deposit, fee, send, broken]);
+        assert!(!balance.balanced);
+        assert_eq!(balance.unbalanced_entries, vec!["tx-0x02".to_string()]);
+        assert!(!balance.assets[1].balanced);
+
+        assert!(JournalEntry::transfer(
+            JournalSource::Fee,
+            "req-2",
+            FIAT_ASSET,
+            JournalAccount::Reserve,
+            JournalAccount::Fees,
+            "0",
+            now,
+        )
+        .is_none());
+    }
+}
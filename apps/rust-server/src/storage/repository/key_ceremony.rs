@@ -0,0 +1,330 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Reserve-wallet key ceremony: Shamir share collection and reconstruction.
+//!
+//! Instead of generating the fiat reserve key from enclave randomness, an
+//! operator group can split a secp256k1 secret offline into `n` Shamir shares
+//! over GF(2^8) and submit any `threshold` of them. The enclave reconstructs
+//! the secret, shows the derived address for out-of-band verification, and
+//! only writes the service wallet once an admin confirms that address.
+//!
+//! Shares are byte-wise: share `i` is the 32-byte evaluation of 32 random
+//! polynomials (one per secret byte) at `x = i`, using the AES field
+//! polynomial `x^8 + x^4 + x^3 + x + 1`.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::super::{EncryptedStorage, StorageError, StorageResult};
+
+/// Length of the reconstructed secret (a secp256k1 scalar).
+pub const SECRET_LEN: usize = 32;
+
+/// Lifecycle of a reserve key ceremony.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum KeyCeremonyStatus {
+    /// Waiting for more shares.
+    Collecting,
+    /// Threshold reached; derived address awaits admin confirmation.
+    AwaitingActivation,
+    /// Service wallet written from the reconstructed key. Shares are discarded.
+    Activated,
+}
+
+/// A submitted share. The value is wiped on activation; index and submitter
+/// are kept as provenance.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredKeyShare {
+    /// Share x-coordinate (1..=255).
+    pub index: u8,
+    /// Hex-encoded 32-byte share value.
+    pub value: String,
+    /// Admin who submitted this share.
+    pub submitted_by: String,
+    /// Submission timestamp.
+    pub submitted_at: DateTime<Utc>,
+}
+
+/// Persisted ceremony record under `/data/system/fiat_key_ceremony.json`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredKeyCeremony {
+    pub ceremony_id: String,
+    /// Number of shares required to reconstruct the secret.
+    pub threshold: u8,
+    pub status: KeyCeremonyStatus,
+    pub shares: Vec<StoredKeyShare>,
+    /// Address derived from the reconstructed secret, once available.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub derived_address: Option<String>,
+    pub created_by: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub activated_by: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub activated_at: Option<DateTime<Utc>>,
+}
+
+impl StoredKeyCeremony {
+    /// Start a new ceremony in the collecting state.
+    pub fn new(threshold: u8, created_by: &str) -> Self {
+        let now = Utc::now();
+        Self {
+            ceremony_id: uuid::Uuid::new_v4().to_string(),
+            threshold,
+            status: KeyCeremonyStatus::Collecting,
+            shares: Vec::new(),
+            derived_address: None,
+            created_by: created_by.to_string(),
+            created_at: now,
+            updated_at: now,
+            activated_by: None,
+            activated_at: None,
+        }
+    }
+
+    /// Drop share values while keeping who contributed which index.
+    pub fn wipe_share_values(&mut self) {
+        for share in &mut self.shares {
+            share.value.clear();
+        }
+    }
+
+    /// Decode stored shares into `(index, bytes)` pairs for reconstruction.
+    pub fn decoded_shares(&self) -> StorageResult<Vec<(u8, [u8; SECRET_LEN])>> {
+        self.shares
+            .iter()
+            .map(|share| {
+                decode_share_value(&share.value)
+                    .map(|value| (share.index, value))
+                    .map_err(StorageError::SerializationError)
+            })
+            .collect()
+    }
+}
+
+/// Repository for the single in-flight reserve key ceremony.
+pub struct KeyCeremonyRepository<'a> {
+    storage: &'a EncryptedStorage,
+}
+
+impl<'a> KeyCeremonyRepository<'a> {
+    /// Create repository.
+    pub fn new(storage: &'a EncryptedStorage) -> Self {
+        Self { storage }
+    }
+
+    /// Load the current ceremony record.
+    pub fn get(&self) -> StorageResult<StoredKeyCeremony> {
+        let path = self.storage.paths().fiat_key_ceremony();
+        if !self.storage.exists(&path) {
+            return Err(StorageError::NotFound("Key ceremony".to_string()));
+        }
+        self.storage.read_json(path)
+    }
+
+    /// Persist the ceremony record (create or replace).
+    pub fn save(&self, ceremony: &StoredKeyCeremony) -> StorageResult<()> {
+        self.storage
+            .write_json(self.storage.paths().fiat_key_ceremony(), ceremony)
+    }
+
+    /// Remove the ceremony record, discarding any collected shares.
+    pub fn delete(&self) -> StorageResult<()> {
+        self.storage
+            .delete(self.storage.paths().fiat_key_ceremony())
+    }
+}
+
+/// Parse a hex-encoded share value (optional `0x` prefix) into 32 bytes.
+pub fn decode_share_value(value: &str) -> Result<[u8; SECRET_LEN], String> {
+    let trimmed = value.trim();
+    let hex = trimmed.strip_prefix("0x").unwrap_or(trimmed);
+    let bytes = alloy::hex::decode(hex).map_err(|_| "share must be hex-encoded".to_string())?;
+    bytes
+        .try_into()
+        .map_err(|_| format!("share must be exactly {SECRET_LEN} bytes"))
+}
+
+// =============================================================================
+// Shamir secret sharing over GF(2^8)
+// =============================================================================
+
+fn gf_mul(mut a: u8, mut b: u8) -> u8 {
+    let mut product = 0u8;
+    while b != 0 {
+        if b & 1 != 0 {
+            product ^= a;
+        }
+        let carry = a & 0x80;
+        a <<= 1;
+        if carry != 0 {
+            a ^= 0x1b;
+        }
+        b >>= 1;
+    }
+    product
+}
+
+/// Multiplicative inverse via `a^254` (every non-zero element has order dividing 255).
+fn gf_inv(a: u8) -> u8 {
+    let mut result = 1u8;
+    let mut base = a;
+    let mut exp = 254u8;
+    while exp != 0 {
+        if exp & 1 != 0 {
+            result = gf_mul(result, base);
+        }
+        base = gf_mul(base, base);
+        exp >>= 1;
+    }
+    result
+}
+
+/// Reconstruct the secret from `(index, share)` pairs by Lagrange
+/// interpolation at `x = 0`.
+///
+/// The caller is responsible for supplying at least `threshold` shares; with
+/// fewer, interpolation succeeds but yields an unrelated value.
+pub fn combine_shares(shares: &[(u8, [u8; SECRET_LEN])]) -> Result<[u8; SECRET_LEN], String> {
+    if shares.is_empty() {
+        return Err("no shares supplied".to_string());
+    }
+    for (i, (x, _)) in shares.iter().enumerate() {
+        if *x == 0 {
+            return Err("share index 0 is reserved for the secret".to_string());
+        }
+        if shares[..i].iter().any(|(other, _)| other == x) {
+            return Err(format!("duplicate share index {x}"));
+        }
+    }
+
+    let mut secret = [0u8; SECRET_LEN];
+    for (i, (xi, yi)) in shares.iter().enumerate() {
+        // Lagrange basis l_i(0) = prod_{j != i} x_j / (x_j - x_i); subtraction is XOR.
+        let mut basis = 1u8;
+        for (j, (xj, _)) in shares.iter().enumerate() {
+            if i != j {
+                basis = gf_mul(basis, gf_mul(*xj, gf_inv(xj ^ xi)));
+            }
+        }
+        for (out, y) in secret.iter_mut().zip(yi.iter()) {
+            *out ^= gf_mul(*y, basis);
+        }
+    }
+    Ok(secret)
+}
+
+/// Split a secret into `count` shares, any `threshold` of which reconstruct it.
+#[cfg(test)]
+pub fn split_secret(
+    secret: &[u8; SECRET_LEN],
+    threshold: u8,
+    count: u8,
+) -> Vec<(u8, [u8; SECRET_LEN])> {
+    use k256::elliptic_curve::rand_core::{OsRng, RngCore};
+
+    let mut coefficients = vec![[0u8; SECRET_LEN]; threshold as usize];
+    coefficients[0] = *secret;
+    for coefficient in coefficients.iter_mut().skip(1) {
+        OsRng.fill_bytes(coefficient);
+    }
+
+    (1..=count)
+        .map(|x| {
+            let mut share = [0u8; SECRET_LEN];
+            for (byte, out) in share.iter_mut().enumerate() {
+                // Horner evaluation from the highest-degree coefficient down.
+                *out = coefficients
+                    .iter()
+                    .rev()
+                    .fold(0u8, |acc, c| gf_mul(acc, x) ^ c[byte]);
+            }
+            (x, share)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::StoragePaths;
+    use std::env;
+    use std::fs;
+
+    fn test_storage() -> EncryptedStorage {
+        let test_dir =
+            env::temp_dir().join(format!("test-key-ceremony-repo-{}", uuid::Uuid::new_v4()));
+        let paths = StoragePaths::new(&test_dir);
+        let mut storage = EncryptedStorage::new(paths);
+        storage.initialize().expect("initialize test storage");
+        storage
+    }
+
+    fn cleanup(storage: &EncryptedStorage) {
+        let _ = fs::remove_dir_all(storage.paths().root());
+    }
+
+    #[test]
+    fn gf_inverse_round_trips() {
+        for a in 1..=255u8 {
+            assert_eq!(gf_mul(a, gf_inv(a)), 1, "inverse failed for {a}");
+        }
+    }
+
+    #[test]
+    fn any_threshold_subset_reconstructs_secret() {
+        let secret = [0x42u8; SECRET_LEN];
+        let shares = split_secret(&secret, 3, 5);
+
+        assert_eq!(combine_shares(&shares[0..3]).unwrap(), secret);
+        assert_eq!(combine_shares(&shares[2..5]).unwrap(), secret);
+        let mixed = vec![shares[4], shares[0], shares[3]];
+        assert_eq!(combine_shares(&mixed).unwrap(), secret);
+    }
+
+    #[test]
+    fn below_threshold_does_not_reconstruct_secret() {
+        let secret = [0x42u8; SECRET_LEN];
+        let shares = split_secret(&secret, 3, 5);
+        assert_ne!(combine_shares(&shares[0..2]).unwrap(), secret);
+    }
+
+    #[test]
+    fn combine_rejects_duplicate_and_zero_indexes() {
+        let share = [1u8; SECRET_LEN];
+        assert!(combine_shares(&[(1, share), (1, share)]).is_err());
+        assert!(combine_shares(&[(0, share)]).is_err());
+        assert!(combine_shares(&[]).is_err());
+    }
+
+    #[test]
+    fn decode_share_value_accepts_prefixed_hex() {
+        let hex = format!("0x{}", "ab".repeat(SECRET_LEN));
+        assert_eq!(decode_share_value(&hex).unwrap(), [0xab; SECRET_LEN]);
+        assert!(decode_share_value("abcd").is_err());
+        assert!(decode_share_value(&"zz".repeat(SECRET_LEN)).is_err());
+    }
+
+    #[test]
+    fn ceremony_record_round_trips() {
+        let storage = test_storage();
+        let repo = KeyCeremonyRepository::new(&storage);
+        assert!(matches!(repo.get(), Err(StorageError::NotFound(_))));
+
+        let ceremony = StoredKeyCeremony::new(2, "admin-1");
+        repo.save(&ceremony).expect("save");
+        let loaded = repo.get().expect("get");
+        assert_eq!(loaded.ceremony_id, ceremony.ceremony_id);
+        assert_eq!(loaded.status, KeyCeremonyStatus::Collecting);
+
+        repo.delete().expect("delete");
+        assert!(repo.get().is_err());
+
+        cleanup(&storage);
+    }
+}
@@ -7,18 +7,107 @@
 //! Each repository provides CRUD operations for a specific entity type,
 //! using the EncryptedStorage for all file operations.
 
+pub mod admin_bootstrap;
+pub mod alerts;
+pub mod api_keys;
+pub mod auto_topup;
 pub mod bookmarks;
+pub mod bridge;
+pub mod categories;
 pub mod email_index;
+pub mod escrow;
+#[cfg(feature = "dev")]
+pub mod faucet;
+pub mod feature_flags;
 pub mod fiat;
+pub mod fiat_beneficiaries;
+pub mod fiat_mandates;
+pub mod insights;
+pub mod journal;
+pub mod key_ceremony;
+pub mod orphans;
 pub mod payment_links;
+pub mod pending_nonces;
+pub mod price_history;
+pub mod reserve_gas;
+pub mod reserve_queue;
+pub mod send_holds;
 pub mod service_wallet;
+pub mod sessions;
+pub mod spending_limits;
+pub mod tenant_config;
+pub mod tokens;
 pub mod transactions;
+pub mod tx_descriptions;
+pub mod wallet_pool;
 pub mod wallets;
+pub mod watch_only;
+pub mod webhook_keys;
+pub mod webhook_subscriptions;
 
+pub use admin_bootstrap::{AdminBootstrapRepository, BootstrapArming, SetupTokenSource};
+pub use alerts::{
+    AlertEvent, AlertEventKind, AlertRepository, AlertRule, AlertRuleKind, StoredWalletAlerts,
+};
+pub use api_keys::{ApiKeyRepository, StoredApiKey};
+pub use auto_topup::{AutoTopUpEvent, AutoTopUpEventKind, AutoTopUpRepository, StoredAutoTopUp};
 pub use bookmarks::{BookmarkRepository, RecipientType, StoredBookmark};
+pub use bridge::{BridgeDirection, BridgeRepository, BridgeStatus, StoredBridgeTransfer};
+pub use categories::{CategoryRepository, StoredUserCategories, UserCategory};
 pub use email_index::EmailIndexRepository;
-pub use fiat::{FiatDirection, FiatRequestRepository, FiatRequestStatus, StoredFiatRequest};
+pub use escrow::{
+    ClaimStatus, EscrowActor, EscrowPaymentStatus, EscrowRepository, EscrowTransition, StoredClaim,
+    StoredEscrowPayment,
+};
+#[cfg(feature = "dev")]
+pub use faucet::{FaucetRepository, StoredFaucetUsage};
+pub use feature_flags::{FeatureFlagRepository, StoredFeatureFlag};
+pub use fiat::{
+    BeneficiaryNameCheck, ClawbackStatus, DestinationKycCheck, FiatActor, FiatChargeback,
+    FiatDirection, FiatRequestRepository, FiatRequestStatus, FiatStatusChange,
+    FiatStatusTransition, NameReview, NameReviewDecision, StoredFiatRequest,
+};
+pub use fiat_beneficiaries::{
+    FiatBeneficiaryRepository, FiatBeneficiaryStatus, StoredFiatBeneficiary,
+};
+pub use fiat_mandates::{FiatMandateRepository, FiatMandateStatus, StoredFiatMandate};
+pub use insights::{CounterpartyTotals, InsightsRepository, MonthlyInsights};
+pub use journal::{
+    AccountBalance, AssetTrialBalance, JournalAccount, JournalEntry, JournalPosting,
+    JournalRepository, JournalSource, PostingSide, TrialBalance,
+};
+pub use key_ceremony::{KeyCeremonyRepository, KeyCeremonyStatus, StoredKeyCeremony};
+pub use orphans::{OrphanKind, OrphanRepository, StoredOrphan};
 pub use payment_links::{PaymentLinkData, PaymentLinkRepository};
-pub use service_wallet::{FiatServiceWalletMetadata, FiatServiceWalletRepository};
+pub use pending_nonces::{PendingNonceRepository, StoredPendingNonce};
+pub use price_history::{PriceHistories, PriceHistoryRepository};
+pub use reserve_gas::{GasSpendEntry, ReserveGasLedgerRepository};
+pub use reserve_queue::{
+    ReserveSendKind, ReserveSendQueueRepository, ReserveSendStatus, StoredReserveSendJob,
+};
+pub use send_holds::{SendHoldRepository, SendHoldSettings, SendHoldStatus, StoredSendHold};
+pub use service_wallet::{
+    FiatServiceWalletMetadata, FiatServiceWalletRepository, ReserveKeySource,
+};
+pub use sessions::{SessionAnomaly, SessionLogRepository, SessionObservation, SessionRecord};
+pub use spending_limits::{SpendingLimitRepository, StoredSpendingLimits};
+pub use tenant_config::{
+    CardCredentials, FeeSchedule, ProviderCredentials, StoredTenantConfig, TenantBranding,
+    TenantConfigRepository, TrueLayerCredentials,
+};
+pub use tokens::{StoredToken, TokenRepository};
 pub use transactions::{StoredTransaction, TokenType, TxStatus};
-pub use wallets::{WalletMetadata, WalletRepository, WalletResponse, WalletStatus};
+pub use tx_descriptions::{
+    DescriptionTemplate, DescriptionTemplateRepository, StoredDescriptionTemplates,
+};
+pub use wallet_pool::{PooledKey, WalletPoolRepository};
+pub use wallets::{
+    SmartAccountInfo, WalletAccountType, WalletLock, WalletMetadata, WalletRepository,
+    WalletResponse, WalletStatus,
+};
+pub use watch_only::{StoredWatchOnlyAddress, WatchOnlyRepository};
+pub use webhook_keys::{StoredWebhookKey, StoredWebhookKeyring, WebhookKeyRepository};
+pub use webhook_subscriptions::{
+    StoredWebhookDelivery, StoredWebhookSubscription, WebhookDeliveryStatus, WebhookEventType,
+    WebhookSubscriptionRepository,
+};
@@ -0,0 +1,160 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Orphaned storage artifact repository.
+//!
+//! The orphan sweeper records every artifact it finds orphaned under
+//! `/data/orphans/{orphan_id}.json`, so admins can review it before it is
+//! removed. The ID is derived from the artifact's kind and target, so the
+//! same artifact keeps its record across sweeps.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use utoipa::ToSchema;
+
+use super::super::{EncryptedStorage, StorageError, StorageResult};
+
+/// What kind of artifact is orphaned.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum OrphanKind {
+    /// Leftover temp file from an interrupted atomic write. Target is the
+    /// path relative to the storage root.
+    TempFile,
+    /// Transaction record whose wallets are gone. Target is the tx hash.
+    Transaction,
+    /// Bookmark whose wallet is gone. Target is the bookmark ID.
+    Bookmark,
+    /// Wallet deleted past retention whose key is kept because it still
+    /// holds assets or has open escrows or claims. Target is the wallet ID.
+    /// Never removed by the sweeper; it is purged once it holds nothing.
+    HeldWallet,
+}
+
+/// An orphaned artifact awaiting removal.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, PartialEq, Eq)]
+pub struct StoredOrphan {
+    pub orphan_id: String,
+    pub kind: OrphanKind,
+    pub target: String,
+    /// Why the artifact counts as orphaned.
+    pub reason: String,
+    pub first_seen_at: DateTime<Utc>,
+    pub last_seen_at: DateTime<Utc>,
+    /// Removed by the first sweep after this time that still finds it
+    /// orphaned.
+    pub remove_after: DateTime<Utc>,
+}
+
+/// Stable ID for an artifact.
+pub fn orphan_id(kind: OrphanKind, target: &str) -> String {
+    let digest = Sha256::digest(format!("{kind:?}:{target}").as_bytes());
+    alloy::hex::encode(&digest[..16])
+}
+
+/// Repository for orphan records.
+pub struct OrphanRepository<'a> {
+    storage: &'a EncryptedStorage,
+}
+
+impl<'a> OrphanRepository<'a> {
+    /// Create repository.
+    pub fn new(storage: &'a EncryptedStorage) -> Self {
+        Self { storage }
+    }
+
+    /// Get an orphan record by ID.
+    pub fn get(&self, orphan_id: &str) -> StorageResult<StoredOrphan> {
+        let path = self.storage.paths().orphan(orphan_id);
+        if !self.storage.exists(&path) {
+            return Err(StorageError::NotFound(format!("Orphan {orphan_id}")));
+        }
+        self.storage.read_json(path)
+    }
+
+    /// Create or replace an orphan record.
+    pub fn save(&self, orphan: &StoredOrphan) -> StorageResult<()> {
+        self.storage
+            .write_json(self.storage.paths().orphan(&orphan.orphan_id), orphan)
+    }
+
+    /// Remove an orphan record.
+    pub fn delete(&self, orphan_id: &str) -> StorageResult<()> {
+        self.get(orphan_id)?;
+        self.storage.delete(self.storage.paths().orphan(orphan_id))
+    }
+
+    /// All orphan records, oldest first.
+    pub fn list_all(&self) -> StorageResult<Vec<StoredOrphan>> {
+        let ids = self
+            .storage
+            .list_files(self.storage.paths().orphans_dir(), "json")?;
+        let mut orphans: Vec<_> = ids.iter().filter_map(|id| self.get(id).ok()).collect();
+        orphans.sort_by(|a, b| {
+            a.first_seen_at
+                .cmp(&b.first_seen_at)
+                .then_with(|| a.orphan_id.cmp(&b.orphan_id))
+        });
+        Ok(orphans)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::StoragePaths;
+    use chrono::Duration;
+    use std::env;
+    use std::fs;
+
+    fn test_storage() -> EncryptedStorage {
+        let test_dir = env::temp_dir().join(format!("test-orphans-{}", uuid::Uuid::new_v4()));
+        let paths = StoragePaths::new(&test_dir);
+        let mut storage = EncryptedStorage::new(paths);
+        storage.initialize().expect("initialize test storage");
+        storage
+    }
+
+    #[test]
+    fn orphan_ids_are_stable_per_kind_and_target() {
+        let id = orphan_id(OrphanKind::Transaction, "0xabc");
+        assert_eq!(id, orphan_id(OrphanKind::Transaction, "0xabc"));
+        assert_ne!(id, orphan_id(OrphanKind::Bookmark, "0xabc"));
+        assert_eq!(id.len(), 32);
+    }
+
+    #[test]
+    fn orphans_are_saved_listed_and_deleted() {
+        let storage = test_storage();
+        let repo = OrphanRepository::new(&storage);
+        let now = Utc::now();
+        for (kind, target, age) in [
+            (OrphanKind::Bookmark, "bm-1", 1),
+            (OrphanKind::TempFile, "wallets/w-1/meta.tmp", 2),
+        ] {
+            let seen = now - Duration::hours(age);
+            repo.save(&StoredOrphan {
+                orphan_id: orphan_id(kind, target),
+                kind,
+                target: target.to_string(),
+                reason: "test".to_string(),
+                first_seen_at: seen,
+                last_seen_at: seen,
+                remove_after: seen + Duration::hours(72),
+            })
+            .unwrap();
+        }
+
+        let listed = repo.list_all().unwrap();
+        assert_eq!(listed.len(), 2);
+        assert_eq!(listed[0].kind, OrphanKind::TempFile);
+
+        repo.delete(&listed[0].orphan_id).unwrap();
+        assert!(repo.delete(&listed[0].orphan_id).is_err());
+        assert_eq!(repo.list_all().unwrap().len(), 1);
+
+        let _ = fs::remove_dir_all(storage.paths().root());
+    }
+}
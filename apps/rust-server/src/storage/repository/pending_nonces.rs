@@ -0,0 +1,92 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Nonces of sends that were broadcast but not yet mined.
+//!
+//! Kept per sending address under `/data/nonces/{chain_id}/{address}.json`
+//! so that nonces stay reserved across restarts even while the node does
+//! not report the transactions as pending. See
+//! [`crate::blockchain::transactions::NonceManager`].
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::super::{EncryptedStorage, StorageResult};
+
+/// A nonce used by a broadcast transaction.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct StoredPendingNonce {
+    pub nonce: u64,
+    pub tx_hash: String,
+    pub assigned_at: DateTime<Utc>,
+}
+
+/// Repository for pending nonces.
+pub struct PendingNonceRepository<'a> {
+    storage: &'a EncryptedStorage,
+}
+
+impl<'a> PendingNonceRepository<'a> {
+    /// Create repository.
+    pub fn new(storage: &'a EncryptedStorage) -> Self {
+        Self { storage }
+    }
+
+    /// Pending nonces of `address`, lowest first.
+    pub fn get(&self, chain_id: u64, address: &str) -> StorageResult<Vec<StoredPendingNonce>> {
+        let path = self.storage.paths().pending_nonces(chain_id, address);
+        if !self.storage.exists(&path) {
+            return Ok(Vec::new());
+        }
+        self.storage.read_json(path)
+    }
+
+    /// Replace the pending nonces of `address`; an empty list removes the file.
+    pub fn set(
+        &self,
+        chain_id: u64,
+        address: &str,
+        pending: &[StoredPendingNonce],
+    ) -> StorageResult<()> {
+        let path = self.storage.paths().pending_nonces(chain_id, address);
+        if pending.is_empty() {
+            if self.storage.exists(&path) {
+                self.storage.delete(path)?;
+            }
+            return Ok(());
+        }
+        let mut pending = pending.to_vec();
+        pending.sort_by_key(|p| p.nonce);
+        self.storage.write_json(path, &pending)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::StoragePaths;
+    use tempfile::TempDir;
+
+    #[test]
+    fn nonces_are_kept_per_chain_and_sorted() {
+        let dir = TempDir::new().unwrap();
+        let mut storage = EncryptedStorage::new(StoragePaths::new(dir.path()));
+        storage.initialize().unwrap();
+        let repo = PendingNonceRepository::new(&storage);
+        let address = "0x1111111111111111111111111111111111111111";
+        let pending = |nonce| StoredPendingNonce {
+            nonce,
+            tx_hash: format!("0x{nonce}"),
+            assigned_at: Utc::now(),
+        };
+
+        repo.set(43113, address, &[pending(4), pending(3)]).unwrap();
+        let stored = repo.get(43113, address).unwrap();
+        assert_eq!(stored.iter().map(|p| p.nonce).collect::<Vec<_>>(), [3, 4]);
+        assert!(repo.get(1, address).unwrap().is_empty());
+
+        repo.set(43113, address, &[]).unwrap();
+        assert!(repo.get(43113, address).unwrap().is_empty());
+    }
+}
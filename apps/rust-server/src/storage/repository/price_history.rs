@@ -0,0 +1,109 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Daily EUR price history per token.
+//!
+//! One file per token symbol under `/data/prices/{symbol}.json` maps UTC
+//! dates to the EUR price of one whole token. A day's entry is overwritten
+//! by later observations on the same day, so past days hold the last price
+//! seen that day.
+
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use super::super::{EncryptedStorage, StorageResult};
+
+/// Price history of one token.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct StoredPriceHistory {
+    pub symbol: String,
+    /// EUR price per UTC day.
+    pub daily_eur: BTreeMap<NaiveDate, f64>,
+}
+
+impl StoredPriceHistory {
+    /// Price recorded for `date`, if any.
+    pub fn price_on(&self, date: NaiveDate) -> Option<f64> {
+        self.daily_eur.get(&date).copied()
+    }
+
+    /// Price of `date`, or of the latest earlier day with one.
+    pub fn price_on_or_before(&self, date: NaiveDate) -> Option<f64> {
+        self.daily_eur.range(..=date).next_back().map(|(_, p)| *p)
+    }
+}
+
+/// Price histories keyed by token symbol.
+pub type PriceHistories = HashMap<String, StoredPriceHistory>;
+
+/// Repository for daily token prices.
+pub struct PriceHistoryRepository<'a> {
+    storage: &'a EncryptedStorage,
+}
+
+impl<'a> PriceHistoryRepository<'a> {
+    /// Create repository.
+    pub fn new(storage: &'a EncryptedStorage) -> Self {
+        Self { storage }
+    }
+
+    /// History for `symbol`; empty when nothing was recorded yet.
+    pub fn get(&self, symbol: &str) -> StorageResult<StoredPriceHistory> {
+        let path = self.storage.paths().price_history(symbol);
+        if !self.storage.exists(&path) {
+            return Ok(StoredPriceHistory {
+                symbol: symbol.to_string(),
+                daily_eur: BTreeMap::new(),
+            });
+        }
+        self.storage.read_json(path)
+    }
+
+    /// Histories for the given symbols.
+    pub fn get_many(&self, symbols: &[&str]) -> StorageResult<PriceHistories> {
+        symbols
+            .iter()
+            .map(|symbol| Ok((symbol.to_string(), self.get(symbol)?)))
+            .collect()
+    }
+
+    /// Record the price of `symbol` on `date`, replacing an earlier entry
+    /// for the same day.
+    pub fn record(&self, symbol: &str, date: NaiveDate, price_eur: f64) -> StorageResult<()> {
+        let mut history = self.get(symbol)?;
+        history.daily_eur.insert(date, price_eur);
+        self.storage
+            .write_json(self.storage.paths().price_history(symbol), &history)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::StoragePaths;
+    use tempfile::TempDir;
+
+    #[test]
+    fn daily_prices_are_recorded_and_overwritten_per_day() {
+        let temp = TempDir::new().unwrap();
+        let mut storage = EncryptedStorage::new(StoragePaths::new(temp.path()));
+        storage.initialize().unwrap();
+        let repo = PriceHistoryRepository::new(&storage);
+        let day = NaiveDate::from_ymd_opt(2026, 3, 1).unwrap();
+
+        assert!(repo.get("AVAX").unwrap().daily_eur.is_empty());
+        repo.record("AVAX", day, 30.0).unwrap();
+        repo.record("AVAX", day, 31.5).unwrap();
+        repo.record("AVAX", day.succ_opt().unwrap(), 29.0).unwrap();
+
+        let history = repo.get("AVAX").unwrap();
+        assert_eq!(history.price_on(day), Some(31.5));
+        assert_eq!(history.daily_eur.len(), 2);
+        assert!(repo.get_many(&["AVAX", "rEUR"]).unwrap()["rEUR"]
+            .daily_eur
+            .is_empty());
+    }
+}
@@ -0,0 +1,191 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Reserve-wallet gas ledger.
+//!
+//! Every AVAX fee paid by the fiat reserve wallet for a settlement transfer
+//! is recorded against the UTC day its receipt was observed, under
+//! `/data/system/reserve_gas/{YYYY-MM-DD}.json`. The daily total backs the
+//! settlement gas budget and the reconciliation report.
+//!
+//! Wei amounts are stored as decimal strings: a day's total can exceed
+//! `u64::MAX` (~18.4 AVAX) and JSON consumers should not lose precision.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::super::{EncryptedStorage, StorageResult};
+
+/// Gas paid for a single reserve-wallet transaction.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct GasSpendEntry {
+    /// Fiat request the transaction settled (the first one accounted, for a
+    /// batched transfer).
+    pub request_id: String,
+    /// Number of requests the transaction settled, when batched.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub batch_size: Option<u32>,
+    pub tx_hash: String,
+    pub gas_used: u64,
+    /// Effective gas price in wei (decimal string).
+    pub effective_gas_price_wei: String,
+    /// `gas_used * effective_gas_price` in wei (decimal string).
+    pub cost_wei: String,
+    pub recorded_at: DateTime<Utc>,
+}
+
+impl GasSpendEntry {
+    /// Build an entry from receipt values.
+    pub fn new(request_id: &str, tx_hash: &str, gas_used: u64, effective_gas_price: u128) -> Self {
+        Self {
+            request_id: request_id.to_string(),
+            batch_size: None,
+            tx_hash: tx_hash.to_string(),
+            gas_used,
+            effective_gas_price_wei: effective_gas_price.to_string(),
+            cost_wei: (gas_used as u128)
+                .saturating_mul(effective_gas_price)
+                .to_string(),
+            recorded_at: Utc::now(),
+        }
+    }
+
+    /// Fee in wei. Unparseable values count as zero.
+    pub fn cost(&self) -> u128 {
+        self.cost_wei.parse().unwrap_or(0)
+    }
+}
+
+/// One UTC day of reserve-wallet gas spend.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredDailyGasSpend {
+    /// UTC date (`YYYY-MM-DD`).
+    pub date: String,
+    pub entries: Vec<GasSpendEntry>,
+}
+
+impl StoredDailyGasSpend {
+    /// Sum of all entry costs in wei.
+    pub fn total_wei(&self) -> u128 {
+        self.entries
+            .iter()
+            .fold(0u128, |acc, entry| acc.saturating_add(entry.cost()))
+    }
+}
+
+/// Repository for the per-day reserve gas ledgers.
+pub struct ReserveGasLedgerRepository<'a> {
+    storage: &'a EncryptedStorage,
+}
+
+impl<'a> ReserveGasLedgerRepository<'a> {
+    /// Create repository.
+    pub fn new(storage: &'a EncryptedStorage) -> Self {
+        Self { storage }
+    }
+
+    /// Load a day's ledger. Days without spend yield an empty ledger.
+    pub fn get_day(&self, date: &str) -> StorageResult<StoredDailyGasSpend> {
+        let path = self.storage.paths().reserve_gas_day(date);
+        if !self.storage.exists(&path) {
+            return Ok(StoredDailyGasSpend {
+                date: date.to_string(),
+                entries: Vec::new(),
+            });
+        }
+        self.storage.read_json(path)
+    }
+
+    /// UTC dates with a ledger, oldest first.
+    pub fn list_days(&self) -> StorageResult<Vec<String>> {
+        let mut days = self
+            .storage
+            .list_files(self.storage.paths().reserve_gas_dir(), "json")?;
+        days.sort();
+        Ok(days)
+    }
+
+    /// Total wei spent on a day.
+    pub fn spent_on(&self, date: &str) -> StorageResult<u128> {
+        Ok(self.get_day(date)?.total_wei())
+    }
+
+    /// Append an entry to a day's ledger and return the new daily total.
+    ///
+    /// Entries are keyed by transaction hash, so recording the same receipt
+    /// twice does not double-count it.
+    pub fn record(&self, date: &str, entry: GasSpendEntry) -> StorageResult<u128> {
+        let mut day = self.get_day(date)?;
+        if !day.entries.iter().any(|e| e.tx_hash == entry.tx_hash) {
+            day.entries.push(entry);
+            self.storage
+                .write_json(self.storage.paths().reserve_gas_day(date), &day)?;
+        }
+        Ok(day.total_wei())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::StoragePaths;
+    use std::env;
+    use std::fs;
+
+    fn test_storage() -> EncryptedStorage {
+        let test_dir =
+            env::temp_dir().join(format!("test-reserve-gas-repo-{}", uuid::Uuid::new_v4()));
+        let paths = StoragePaths::new(&test_dir);
+        let mut storage = EncryptedStorage::new(paths);
+        storage.initialize().expect("initialize test storage");
+        storage
+    }
+
+    fn cleanup(storage: &EncryptedStorage) {
+        let _ = fs::remove_dir_all(storage.paths().root());
+    }
+
+    #[test]
+    fn entry_cost_is_gas_times_price() {
+        let entry = GasSpendEntry::new("req-1", "0xaa", 21_000, 25_000_000_000);
+        assert_eq!(entry.cost_wei, "525000000000000");
+        assert_eq!(entry.cost(), 525_000_000_000_000);
+    }
+
+    #[test]
+    fn record_accumulates_daily_total_and_dedupes_by_tx_hash() {
+        let storage = test_storage();
+        let repo = ReserveGasLedgerRepository::new(&storage);
+        assert_eq!(repo.spent_on("2026-03-01").unwrap(), 0);
+
+        let total = repo
+            .record(
+                "2026-03-01",
+                GasSpendEntry::new("req-1", "0xaa", 50_000, 10),
+            )
+            .unwrap();
+        assert_eq!(total, 500_000);
+        let total = repo
+            .record(
+                "2026-03-01",
+                GasSpendEntry::new("req-2", "0xbb", 60_000, 10),
+            )
+            .unwrap();
+        assert_eq!(total, 1_100_000);
+        let total = repo
+            .record(
+                "2026-03-01",
+                GasSpendEntry::new("req-1", "0xaa", 50_000, 10),
+            )
+            .unwrap();
+        assert_eq!(total, 1_100_000);
+
+        assert_eq!(repo.get_day("2026-03-01").unwrap().entries.len(), 2);
+        assert_eq!(repo.spent_on("2026-03-02").unwrap(), 0);
+        assert_eq!(repo.list_days().unwrap(), vec!["2026-03-01".to_string()]);
+
+        cleanup(&storage);
+    }
+}
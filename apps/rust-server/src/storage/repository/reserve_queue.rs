@@ -0,0 +1,311 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Persisted state of the reserve-wallet send queue.
+//!
+//! Every transaction signed by the fiat reserve wallet (settlement transfers,
+//! rEUR mints and dev faucet drips) is recorded here before it is sent, so a crash mid-send
+//! leaves an `interrupted` job behind instead of silently allowing a retry
+//! that could pay twice. The whole queue lives in one file,
+//! `/data/system/reserve_send_queue.json`.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::super::{EncryptedStorage, StorageResult};
+
+/// Finished jobs kept for inspection; older ones are pruned.
+const RETAINED_FINISHED_JOBS: usize = 500;
+
+/// What a reserve send does.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReserveSendKind {
+    /// rEUR transfer settling an on-ramp request.
+    Settlement,
+    /// Disperse-contract call settling several small on-ramps at once.
+    BatchSettlement,
+    /// rEUR mint into the reserve wallet.
+    Mint,
+    /// Test AVAX sent by the dev faucet; `amount_eur` holds the AVAX amount.
+    FaucetDrip,
+    /// Test rEUR minted to a wallet by the dev faucet.
+    FaucetMint,
+}
+
+/// Lifecycle of a queued reserve send.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReserveSendStatus {
+    /// Waiting for earlier sends to finish.
+    Queued,
+    /// Being signed and broadcast.
+    Sending,
+    /// Broadcast; `tx_hash` is set.
+    Submitted,
+    /// Rejected before broadcast; `error` is set.
+    Failed,
+    /// The server stopped while the job was sending. Whether it reached the
+    /// chain is unknown until an admin resolves it.
+    Interrupted,
+}
+
+impl ReserveSendStatus {
+    /// Whether the job will not change state on its own.
+    pub fn is_finished(self) -> bool {
+        matches!(self, Self::Submitted | Self::Failed)
+    }
+}
+
+/// A single reserve-wallet send.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct StoredReserveSendJob {
+    pub job_id: String,
+    /// Position in the queue; jobs are sent in ascending order.
+    pub seq: u64,
+    pub kind: ReserveSendKind,
+    /// Fiat requests being settled (settlement jobs only).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub request_ids: Vec<String>,
+    /// Recipient address.
+    pub to: String,
+    /// EUR amount (AVAX for faucet drips).
+    pub amount_eur: String,
+    pub status: ReserveSendStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tx_hash: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub enqueued_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// The persisted queue.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StoredReserveSendQueue {
+    /// Sequence number for the next enqueued job.
+    pub next_seq: u64,
+    /// Jobs in enqueue order.
+    pub jobs: Vec<StoredReserveSendJob>,
+}
+
+impl StoredReserveSendQueue {
+    /// Append a queued job and return a copy of it.
+    pub fn enqueue(
+        &mut self,
+        kind: ReserveSendKind,
+        request_ids: &[String],
+        to: &str,
+        amount_eur: &str,
+    ) -> StoredReserveSendJob {
+        let now = Utc::now();
+        let job = StoredReserveSendJob {
+            job_id: uuid::Uuid::new_v4().to_string(),
+            seq: self.next_seq,
+            kind,
+            request_ids: request_ids.to_vec(),
+            to: to.to_string(),
+            amount_eur: amount_eur.to_string(),
+            status: ReserveSendStatus::Queued,
+            tx_hash: None,
+            error: None,
+            enqueued_at: now,
+            updated_at: now,
+        };
+        self.next_seq += 1;
+        self.jobs.push(job.clone());
+        self.prune();
+        job
+    }
+
+    /// Look up a job by id.
+    pub fn job_mut(&mut self, job_id: &str) -> Option<&mut StoredReserveSendJob> {
+        self.jobs.iter_mut().find(|job| job.job_id == job_id)
+    }
+
+    /// Most recent job settling the given fiat request, alone or in a batch.
+    pub fn latest_for_request(&self, request_id: &str) -> Option<&StoredReserveSendJob> {
+        self.jobs
+            .iter()
+            .rev()
+            .find(|job| job.request_ids.iter().any(|id| id == request_id))
+    }
+
+    /// Reconcile jobs left unfinished by a previous process.
+    ///
+    /// Queued jobs never reached the signer and are failed; sending jobs may
+    /// or may not have been broadcast and become interrupted. Returns the
+    /// number of interrupted jobs.
+    pub fn recover_unfinished(&mut self) -> usize {
+        let now = Utc::now();
+        let mut interrupted = 0;
+        for job in &mut self.jobs {
+            match job.status {
+                ReserveSendStatus::Queued => {
+                    job.status = ReserveSendStatus::Failed;
+                    job.error = Some("Server restarted before the job was sent".to_string());
+                    job.updated_at = now;
+                }
+                ReserveSendStatus::Sending => {
+                    job.status = ReserveSendStatus::Interrupted;
+                    job.updated_at = now;
+                    interrupted += 1;
+                }
+                _ => {}
+            }
+        }
+        interrupted
+    }
+
+    fn prune(&mut self) {
+        let finished = self
+            .jobs
+            .iter()
+            .filter(|job| job.status.is_finished())
+            .count();
+        let mut excess = finished.saturating_sub(RETAINED_FINISHED_JOBS);
+        self.jobs.retain(|job| {
+            if excess > 0 && job.status.is_finished() {
+                excess -= 1;
+                false
+            } else {
+                true
+            }
+        });
+    }
+}
+
+/// Repository for the reserve send queue file.
+pub struct ReserveSendQueueRepository<'a> {
+    storage: &'a EncryptedStorage,
+}
+
+impl<'a> ReserveSendQueueRepository<'a> {
+    /// Create repository.
+    pub fn new(storage: &'a EncryptedStorage) -> Self {
+        Self { storage }
+    }
+
+    /// Load the queue. A missing file is an empty queue.
+    pub fn load(&self) -> StorageResult<StoredReserveSendQueue> {
+        let path = self.storage.paths().reserve_send_queue();
+        if !self.storage.exists(&path) {
+            return Ok(StoredReserveSendQueue::default());
+        }
+        self.storage.read_json(path)
+    }
+
+    /// Persist the queue.
+    pub fn save(&self, queue: &StoredReserveSendQueue) -> StorageResult<()> {
+        self.storage
+            .write_json(self.storage.paths().reserve_send_queue(), queue)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::StoragePaths;
+    use std::env;
+    use std::fs;
+
+    fn test_storage() -> EncryptedStorage {
+        let test_dir =
+            env::temp_dir().join(format!("test-reserve-queue-repo-{}", uuid::Uuid::new_v4()));
+        let paths = StoragePaths::new(&test_dir);
+        let mut storage = EncryptedStorage::new(paths);
+        storage.initialize().expect("initialize test storage");
+        storage
+    }
+
+    fn cleanup(storage: &EncryptedStorage) {
+        let _ = fs::remove_dir_all(storage.paths().root());
+    }
+
+    #[test]
+    fn enqueue_assigns_increasing_sequence_numbers() {
+        let mut queue = StoredReserveSendQueue::default();
+        let first = queue.enqueue(
+            ReserveSendKind::Settlement,
+            &["req-1".to_string()],
+            "0x1",
+            "1.00",
+        );
+        let second = queue.enqueue(ReserveSendKind::Mint, &[], "0x2", "2.00");
+        assert_eq!(first.seq, 0);
+        assert_eq!(second.seq, 1);
+        assert_eq!(queue.next_seq, 2);
+        assert_eq!(
+            queue.latest_for_request("req-1").map(|j| j.job_id.as_str()),
+            Some(first.job_id.as_str())
+        );
+    }
+
+    #[test]
+    fn recovery_fails_queued_and_interrupts_sending_jobs() {
+        let mut queue = StoredReserveSendQueue::default();
+        let queued = queue.enqueue(ReserveSendKind::Mint, &[], "0x1", "1.00");
+        let sending = queue.enqueue(
+            ReserveSendKind::Settlement,
+            &["req-1".to_string()],
+            "0x2",
+            "2.00",
+        );
+        let submitted = queue.enqueue(ReserveSendKind::Mint, &[], "0x3", "3.00");
+        queue.job_mut(&sending.job_id).unwrap().status = ReserveSendStatus::Sending;
+        queue.job_mut(&submitted.job_id).unwrap().status = ReserveSendStatus::Submitted;
+
+        assert_eq!(queue.recover_unfinished(), 1);
+        assert_eq!(
+            queue.job_mut(&queued.job_id).unwrap().status,
+            ReserveSendStatus::Failed
+        );
+        assert_eq!(
+            queue.job_mut(&sending.job_id).unwrap().status,
+            ReserveSendStatus::Interrupted
+        );
+        assert_eq!(
+            queue.job_mut(&submitted.job_id).unwrap().status,
+            ReserveSendStatus::Submitted
+        );
+    }
+
+    #[test]
+    fn prune_keeps_unfinished_jobs() {
+        let mut queue = StoredReserveSendQueue::default();
+        let interrupted = queue.enqueue(ReserveSendKind::Mint, &[], "0x1", "1.00");
+        queue.job_mut(&interrupted.job_id).unwrap().status = ReserveSendStatus::Interrupted;
+        for _ in 0..RETAINED_FINISHED_JOBS + 5 {
+            let job = queue.enqueue(ReserveSendKind::Mint, &[], "0x1", "1.00");
+            queue.job_mut(&job.job_id).unwrap().status = ReserveSendStatus::Submitted;
+        }
+        queue.enqueue(ReserveSendKind::Mint, &[], "0x1", "1.00");
+
+        assert_eq!(queue.jobs.len(), RETAINED_FINISHED_JOBS + 2);
+        assert_eq!(queue.jobs[0].job_id, interrupted.job_id);
+    }
+
+    #[test]
+    fn queue_round_trips_through_storage() {
+        let storage = test_storage();
+        let repo = ReserveSendQueueRepository::new(&storage);
+        assert!(repo.load().unwrap().jobs.is_empty());
+
+        let mut queue = repo.load().unwrap();
+        queue.enqueue(
+            ReserveSendKind::Settlement,
+            &["req-1".to_string()],
+            "0x1",
+            "1.00",
+        );
+        repo.save(&queue).unwrap();
+
+        let loaded = repo.load().unwrap();
+        assert_eq!(loaded.next_seq, 1);
+        assert_eq!(loaded.jobs[0].request_ids, vec!["req-1".to_string()]);
+
+        cleanup(&storage);
+    }
+}
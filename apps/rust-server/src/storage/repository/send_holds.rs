@@ -0,0 +1,255 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Sends held for confirmation.
+//!
+//! A send initiated from a session that looks nothing like the user's recent
+//! history is not broadcast right away. It is stored under
+//! `/data/send_holds/{hold_id}.json` until the user confirms it from a
+//! familiar session or cancels it. A hold that is not confirmed within its
+//! window expires and is never sent.
+//!
+//! Users can turn holds off or change the window; their settings live in
+//! `/data/send_holds/settings/{user_key}.json`.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::super::{EncryptedStorage, StorageError, StorageResult};
+use super::sessions::{user_key, SessionAnomaly};
+use crate::blockchain::FeeSpeed;
+
+/// Confirmation window when the user has not chosen one.
+pub const DEFAULT_HOLD_MINUTES: u32 = 30;
+
+/// Longest confirmation window a user may choose (24 hours).
+pub const MAX_HOLD_MINUTES: u32 = 1440;
+
+/// A user's send-hold preferences.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, PartialEq, Eq)]
+pub struct SendHoldSettings {
+    /// Whether sends from unfamiliar sessions are held.
+    pub enabled: bool,
+    /// Minutes a held send waits for confirmation.
+    pub hold_minutes: u32,
+}
+
+impl Default for SendHoldSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            hold_minutes: DEFAULT_HOLD_MINUTES,
+        }
+    }
+}
+
+/// Lifecycle of a held send.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SendHoldStatus {
+    /// Waiting for confirmation.
+    Held,
+    /// Confirmed and broadcast.
+    Confirmed,
+    /// Cancelled by the user.
+    Cancelled,
+    /// Not confirmed in time.
+    Expired,
+}
+
+/// A send waiting for confirmation.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct StoredSendHold {
+    pub hold_id: String,
+    pub wallet_id: String,
+    pub owner_user_id: String,
+    /// Resolved recipient address.
+    pub to: String,
+    pub amount: String,
+    pub token: String,
+    pub network: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gas_limit: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_priority_fee_per_gas: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fee_speed: Option<FeeSpeed>,
+    /// Clerk session that initiated the send.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ip_address: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_agent: Option<String>,
+    /// Why the send was held.
+    pub anomalies: Vec<SessionAnomaly>,
+    pub status: SendHoldStatus,
+    /// Hash of the transaction once confirmed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tx_hash: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+impl StoredSendHold {
+    /// Status as of `now`; a held send past its window reads as expired.
+    pub fn status_at(&self, now: DateTime<Utc>) -> SendHoldStatus {
+        if self.status == SendHoldStatus::Held && now >= self.expires_at {
+            SendHoldStatus::Expired
+        } else {
+            self.status
+        }
+    }
+}
+
+impl super::super::OwnedResource for StoredSendHold {
+    fn owner_user_id(&self) -> &str {
+        &self.owner_user_id
+    }
+}
+
+/// Repository for held sends and send-hold settings.
+pub struct SendHoldRepository<'a> {
+    storage: &'a EncryptedStorage,
+}
+
+impl<'a> SendHoldRepository<'a> {
+    /// Create repository.
+    pub fn new(storage: &'a EncryptedStorage) -> Self {
+        Self { storage }
+    }
+
+    /// Get a held send by ID.
+    pub fn get(&self, hold_id: &str) -> StorageResult<StoredSendHold> {
+        let path = self.storage.paths().send_hold(hold_id);
+        if !self.storage.exists(&path) {
+            return Err(StorageError::NotFound(format!("Send hold {hold_id}")));
+        }
+        self.storage.read_json(path)
+    }
+
+    /// Store a new held send.
+    pub fn create(&self, hold: &StoredSendHold) -> StorageResult<()> {
+        let path = self.storage.paths().send_hold(&hold.hold_id);
+        if self.storage.exists(&path) {
+            return Err(StorageError::AlreadyExists(format!(
+                "Send hold {}",
+                hold.hold_id
+            )));
+        }
+        self.storage.write_json(path, hold)
+    }
+
+    /// Overwrite an existing held send.
+    pub fn update(&self, hold: &StoredSendHold) -> StorageResult<()> {
+        self.get(&hold.hold_id)?;
+        self.storage
+            .write_json(self.storage.paths().send_hold(&hold.hold_id), hold)
+    }
+
+    /// Held sends of a wallet, newest first.
+    pub fn list_by_wallet(&self, wallet_id: &str) -> StorageResult<Vec<StoredSendHold>> {
+        let ids = self
+            .storage
+            .list_files(self.storage.paths().send_holds_dir(), "json")?;
+        let mut holds: Vec<_> = ids
+            .iter()
+            .filter_map(|id| self.get(id).ok())
+            .filter(|hold| hold.wallet_id == wallet_id)
+            .collect();
+        holds.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        Ok(holds)
+    }
+
+    /// A user's settings; the defaults if they never changed them.
+    pub fn settings(&self, user_id: &str) -> StorageResult<SendHoldSettings> {
+        let path = self.storage.paths().send_hold_settings(&user_key(user_id));
+        if !self.storage.exists(&path) {
+            return Ok(SendHoldSettings::default());
+        }
+        self.storage.read_json(path)
+    }
+
+    /// Replace a user's settings.
+    pub fn set_settings(&self, user_id: &str, settings: &SendHoldSettings) -> StorageResult<()> {
+        let path = self.storage.paths().send_hold_settings(&user_key(user_id));
+        self.storage.write_json(path, settings)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::StoragePaths;
+    use chrono::Duration;
+    use tempfile::TempDir;
+
+    fn hold(hold_id: &str, wallet_id: &str, created_at: DateTime<Utc>) -> StoredSendHold {
+        StoredSendHold {
+            hold_id: hold_id.into(),
+            wallet_id: wallet_id.into(),
+            owner_user_id: "user_1".into(),
+            to: "0x1111111111111111111111111111111111111111".into(),
+            amount: "1.5".into(),
+            token: "native".into(),
+            network: "fuji".into(),
+            gas_limit: None,
+            max_priority_fee_per_gas: None,
+            fee_speed: None,
+            session_id: Some("sess_far".into()),
+            ip_address: Some("198.51.100.9".into()),
+            user_agent: None,
+            anomalies: vec![SessionAnomaly::UnfamiliarNetwork],
+            status: SendHoldStatus::Held,
+            tx_hash: None,
+            created_at,
+            expires_at: created_at + Duration::minutes(DEFAULT_HOLD_MINUTES as i64),
+            resolved_at: None,
+        }
+    }
+
+    #[test]
+    fn holds_and_settings_round_trip() {
+        let dir = TempDir::new().unwrap();
+        let mut storage = EncryptedStorage::new(StoragePaths::new(dir.path()));
+        storage.initialize().unwrap();
+        let repo = SendHoldRepository::new(&storage);
+        let now = Utc::now();
+
+        repo.create(&hold("h1", "w1", now - Duration::minutes(5)))
+            .unwrap();
+        repo.create(&hold("h2", "w1", now)).unwrap();
+        repo.create(&hold("h3", "w2", now)).unwrap();
+        assert!(repo.create(&hold("h1", "w1", now)).is_err());
+
+        let held = repo.list_by_wallet("w1").unwrap();
+        assert_eq!(
+            held.iter().map(|h| h.hold_id.as_str()).collect::<Vec<_>>(),
+            ["h2", "h1"]
+        );
+
+        let h1 = repo.get("h1").unwrap();
+        assert_eq!(h1.status_at(now), SendHoldStatus::Held);
+        assert_eq!(
+            h1.status_at(now + Duration::minutes(DEFAULT_HOLD_MINUTES as i64)),
+            SendHoldStatus::Expired
+        );
+
+        // Settings live beside the holds without being listed as one.
+        assert_eq!(
+            repo.settings("user_1").unwrap(),
+            SendHoldSettings::default()
+        );
+        let custom = SendHoldSettings {
+            enabled: false,
+            hold_minutes: 60,
+        };
+        repo.set_settings("user_1", &custom).unwrap();
+        assert_eq!(repo.settings("user_1").unwrap(), custom);
+        assert_eq!(repo.list_by_wallet("w1").unwrap().len(), 2);
+    }
+}
@@ -12,6 +12,17 @@ use super::super::{EncryptedStorage, StorageError, StorageResult};
 
 const SERVICE_WALLET_ID: &str = "fiat_service_wallet";
 
+/// How the service-wallet key material came into existence.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReserveKeySource {
+    /// Generated from enclave randomness at bootstrap.
+    #[default]
+    Enclave,
+    /// Reconstructed from operator-supplied Shamir shares.
+    Ceremony,
+}
+
 /// Persisted metadata for the enclave-managed fiat reserve service wallet.
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct FiatServiceWalletMetadata {
@@ -23,6 +34,12 @@ pub struct FiatServiceWalletMetadata {
     pub created_at: DateTime<Utc>,
     /// Last update timestamp.
     pub updated_at: DateTime<Utc>,
+    /// Provenance of the key material.
+    #[serde(default)]
+    pub key_source: ReserveKeySource,
+    /// Key ceremony that produced the key (ceremony-sourced wallets only).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ceremony_id: Option<String>,
 }
 
 /// Repository for fiat service-wallet lifecycle and key access.
@@ -70,18 +87,62 @@ impl<'a> FiatServiceWalletRepository<'a> {
             public_address,
             created_at: now,
             updated_at: now,
+            key_source: ReserveKeySource::Enclave,
+            ceremony_id: None,
+        };
+
+        self.persist(&metadata, &private_key_pem)?;
+        Ok(metadata)
+    }
+
+    /// Create the service wallet from a raw secp256k1 secret reconstructed
+    /// during a key ceremony.
+    ///
+    /// Fails with `AlreadyExists` if a service wallet is already present; the
+    /// reserve key is never silently replaced.
+    pub fn import_from_ceremony(
+        &self,
+        secret: &[u8],
+        ceremony_id: &str,
+    ) -> StorageResult<FiatServiceWalletMetadata> {
+        if self.exists() {
+            return Err(StorageError::AlreadyExists(
+                "Fiat service wallet".to_string(),
+            ));
+        }
+
+        let signing_key = k256::ecdsa::SigningKey::from_slice(secret)
+            .map_err(|e| StorageError::SerializationError(format!("invalid secret key: {e}")))?;
+        let (private_key_pem, public_address) = encode_signing_key(&signing_key)
+            .map_err(|e| StorageError::SerializationError(format!("key encoding failed: {e}")))?;
+
+        let now = Utc::now();
+        let metadata = FiatServiceWalletMetadata {
+            wallet_id: SERVICE_WALLET_ID.to_string(),
+            public_address,
+            created_at: now,
+            updated_at: now,
+            key_source: ReserveKeySource::Ceremony,
+            ceremony_id: Some(ceremony_id.to_string()),
         };
 
+        self.persist(&metadata, &private_key_pem)?;
+        Ok(metadata)
+    }
+
+    fn persist(
+        &self,
+        metadata: &FiatServiceWalletMetadata,
+        private_key_pem: &str,
+    ) -> StorageResult<()> {
         self.storage
             .create_dir(self.storage.paths().fiat_service_wallet_dir())?;
         self.storage
-            .write_json(self.storage.paths().fiat_service_wallet_meta(), &metadata)?;
+            .write_json(self.storage.paths().fiat_service_wallet_meta(), metadata)?;
         self.storage.write_raw(
             self.storage.paths().fiat_service_wallet_key(),
             private_key_pem.as_bytes(),
-        )?;
-
-        Ok(metadata)
+        )
     }
 
     /// Read service-wallet private key bytes (PEM).
@@ -96,27 +157,45 @@ impl<'a> FiatServiceWalletRepository<'a> {
     }
 }
 
+/// Derive the EVM address for a raw 32-byte secp256k1 secret.
+///
+/// Used to show operators the address a key ceremony will produce before the
+/// key is committed to storage.
+pub fn address_from_secret(secret: &[u8]) -> Result<String, String> {
+    let signing_key = k256::ecdsa::SigningKey::from_slice(secret)
+        .map_err(|_| "secret is not a valid secp256k1 private key".to_string())?;
+    Ok(evm_address(&signing_key))
+}
+
 /// Generate secp256k1 keypair and derive EVM address.
-fn generate_secp256k1_keypair() -> Result<(String, String), Box<dyn std::error::Error + Send + Sync>>
-{
-    use alloy::primitives::keccak256;
+pub(crate) fn generate_secp256k1_keypair(
+) -> Result<(String, String), Box<dyn std::error::Error + Send + Sync>> {
     use k256::ecdsa::SigningKey;
     use k256::elliptic_curve::rand_core::OsRng;
+
+    encode_signing_key(&SigningKey::random(&mut OsRng))
+}
+
+/// Encode a signing key as PKCS#8 PEM and derive its EVM address.
+fn encode_signing_key(
+    signing_key: &k256::ecdsa::SigningKey,
+) -> Result<(String, String), Box<dyn std::error::Error + Send + Sync>> {
     use k256::pkcs8::EncodePrivateKey;
 
-    let signing_key = SigningKey::random(&mut OsRng);
-    let verifying_key = signing_key.verifying_key();
     let private_key_pem = signing_key
         .to_pkcs8_pem(k256::pkcs8::LineEnding::LF)
         .map_err(|e| format!("failed to encode private key: {e}"))?;
 
-    let public_key_uncompressed = verifying_key.to_encoded_point(false);
+    Ok((private_key_pem.to_string(), evm_address(signing_key)))
+}
+
+fn evm_address(signing_key: &k256::ecdsa::SigningKey) -> String {
+    use alloy::primitives::keccak256;
+
+    let public_key_uncompressed = signing_key.verifying_key().to_encoded_point(false);
     let public_key_bytes = public_key_uncompressed.as_bytes();
     let hash = keccak256(&public_key_bytes[1..]);
-    let address_bytes = &hash[12..];
-    let public_address = format!("0x{}", alloy::hex::encode(address_bytes));
-
-    Ok((private_key_pem.to_string(), public_address))
+    format!("0x{}", alloy::hex::encode(&hash[12..]))
 }
 
 #[cfg(test)]
@@ -166,4 +245,48 @@ mod tests {
 
         cleanup(&storage);
     }
+
+    #[test]
+    fn import_from_ceremony_records_provenance() {
+        let storage = test_storage();
+        let repo = FiatServiceWalletRepository::new(&storage);
+        let secret = [0x11u8; 32];
+
+        let metadata = repo
+            .import_from_ceremony(&secret, "ceremony-1")
+            .expect("import");
+        assert_eq!(metadata.key_source, ReserveKeySource::Ceremony);
+        assert_eq!(metadata.ceremony_id.as_deref(), Some("ceremony-1"));
+        assert_eq!(
+            metadata.public_address,
+            address_from_secret(&secret).unwrap()
+        );
+
+        // Bootstrap must keep the imported key rather than generating a new one.
+        let again = repo.bootstrap().expect("bootstrap");
+        assert_eq!(again.public_address, metadata.public_address);
+
+        let err = repo.import_from_ceremony(&secret, "ceremony-2");
+        assert!(matches!(err, Err(StorageError::AlreadyExists(_))));
+
+        cleanup(&storage);
+    }
+
+    #[test]
+    fn address_from_secret_rejects_zero_scalar() {
+        assert!(address_from_secret(&[0u8; 32]).is_err());
+    }
+
+    #[test]
+    fn legacy_metadata_defaults_to_enclave_source() {
+        let json = r#"{
+            "wallet_id": "fiat_service_wallet",
+            "public_address": "0xabc",
+            "created_at": "2026-01-01T00:00:00Z",
+            "updated_at": "2026-01-01T00:00:00Z"
+        }"#;
+        let metadata: FiatServiceWalletMetadata = serde_json::from_str(json).unwrap();
+        assert_eq!(metadata.key_source, ReserveKeySource::Enclave);
+        assert!(metadata.ceremony_id.is_none());
+    }
 }
@@ -0,0 +1,375 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Per-user login and device history.
+//!
+//! Every authenticated request is attributed to a session: the Clerk `sid`
+//! claim when the token carries one, otherwise the issuer, client IP and
+//! user agent together. Each user's sessions are kept newest-first in
+//! `/data/sessions/{user_key}.json`, where the key is a digest of the user ID
+//! so token subjects never become file names.
+//!
+//! The log is compact: a known session is only rewritten when its IP or user
+//! agent changes or [`TOUCH_INTERVAL_SECS`] have passed, and only the newest
+//! [`MAX_SESSIONS_PER_USER`] sessions are kept.
+//!
+//! [`StoredSessionLog::anomalies`] compares a session against the user's
+//! other recent sessions. There is no geolocation database in the enclave,
+//! so the client network (IPv4 /16, IPv6 /48) stands in for location and the
+//! browser and OS named by the user agent stand in for the device.
+
+use std::net::IpAddr;
+
+use alloy::primitives::keccak256;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::super::{EncryptedStorage, StorageResult};
+
+/// Sessions kept per user; older ones are dropped.
+pub const MAX_SESSIONS_PER_USER: usize = 50;
+
+/// Minimum age of `last_seen` before an unchanged session is rewritten.
+pub const TOUCH_INTERVAL_SECS: i64 = 300;
+
+/// Sessions last seen within this many days form a user's recent history.
+pub const RECENT_HISTORY_DAYS: i64 = 30;
+
+/// One session seen for a user.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, PartialEq, Eq)]
+pub struct SessionRecord {
+    /// Clerk session ID (`sid`), when the token carries one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+    /// Token issuer.
+    pub issuer: String,
+    /// Client IP of the most recent request.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ip_address: Option<String>,
+    /// `User-Agent` of the most recent request.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_agent: Option<String>,
+    pub first_seen: DateTime<Utc>,
+    pub last_seen: DateTime<Utc>,
+}
+
+/// A user's session log.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StoredSessionLog {
+    pub user_id: String,
+    /// Newest first.
+    #[serde(default)]
+    pub sessions: Vec<SessionRecord>,
+}
+
+/// Way in which a session differs from the user's recent history.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionAnomaly {
+    /// The client network matches none of the recent sessions.
+    UnfamiliarNetwork,
+    /// The browser and OS match none of the recent sessions.
+    UnfamiliarDevice,
+}
+
+impl StoredSessionLog {
+    /// The session for a Clerk session ID.
+    pub fn find(&self, session_id: &str) -> Option<&SessionRecord> {
+        self.sessions
+            .iter()
+            .find(|s| s.session_id.as_deref() == Some(session_id))
+    }
+
+    /// How `current` differs from the sessions that started before it and
+    /// were seen in the last [`RECENT_HISTORY_DAYS`]. Empty when there is no
+    /// such history; a missing IP or user agent is never reported.
+    pub fn anomalies(&self, current: &SessionRecord, now: DateTime<Utc>) -> Vec<SessionAnomaly> {
+        let cutoff = now - Duration::days(RECENT_HISTORY_DAYS);
+        let history: Vec<_> = self
+            .sessions
+            .iter()
+            .filter(|s| *s != current && s.first_seen < current.first_seen)
+            .filter(|s| s.last_seen >= cutoff)
+            .collect();
+        if history.is_empty() {
+            return Vec::new();
+        }
+
+        let mut anomalies = Vec::new();
+        if let Some(network) = current.ip_address.as_deref().and_then(network_of) {
+            let known = history.iter().any(|s| {
+                s.ip_address.as_deref().and_then(network_of).as_deref() == Some(network.as_str())
+            });
+            if !known {
+                anomalies.push(SessionAnomaly::UnfamiliarNetwork);
+            }
+        }
+        if let Some(device) = current.user_agent.as_deref().map(device_of) {
+            let known = history
+                .iter()
+                .any(|s| s.user_agent.as_deref().map(device_of) == Some(device));
+            if !known {
+                anomalies.push(SessionAnomaly::UnfamiliarDevice);
+            }
+        }
+        anomalies
+    }
+}
+
+/// Network prefix of an IP address: /16 for IPv4, /48 for IPv6.
+fn network_of(ip: &str) -> Option<String> {
+    match ip.parse::<IpAddr>().ok()? {
+        IpAddr::V4(v4) => {
+            let [a, b, ..] = v4.octets();
+            Some(format!("{a}.{b}.0.0/16"))
+        }
+        IpAddr::V6(v6) => {
+            let [a, b, c, ..] = v6.segments();
+            Some(format!("{a:x}:{b:x}:{c:x}::/48"))
+        }
+    }
+}
+
+/// Browser and OS family named by a user agent.
+fn device_of(user_agent: &str) -> (&'static str, &'static str) {
+    const OSES: &[(&str, &str)] = &[
+        ("iPhone", "ios"),
+        ("iPad", "ios"),
+        ("Android", "android"),
+        ("CrOS", "chromeos"),
+        ("Windows", "windows"),
+        ("Macintosh", "macos"),
+        ("Linux", "linux"),
+    ];
+    // Order matters: Edge and Opera also claim Chrome, Chrome claims Safari.
+    const BROWSERS: &[(&str, &str)] = &[
+        ("Edg/", "edge"),
+        ("OPR/", "opera"),
+        ("Firefox/", "firefox"),
+        ("Chrome/", "chrome"),
+        ("CriOS/", "chrome"),
+        ("Safari/", "safari"),
+    ];
+    let pick = |table: &[(&str, &'static str)]| {
+        table
+            .iter()
+            .find(|(marker, _)| user_agent.contains(marker))
+            .map_or("other", |(_, name)| *name)
+    };
+    (pick(BROWSERS), pick(OSES))
+}
+
+/// What an authenticated request revealed about its session.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionObservation {
+    pub session_id: Option<String>,
+    pub issuer: String,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+}
+
+impl SessionObservation {
+    fn matches(&self, record: &SessionRecord) -> bool {
+        if record.session_id != self.session_id || record.issuer != self.issuer {
+            return false;
+        }
+        // Without a session ID, each client is its own session.
+        self.session_id.is_some()
+            || (record.ip_address == self.ip_address && record.user_agent == self.user_agent)
+    }
+}
+
+/// Repository for session logs.
+pub struct SessionLogRepository<'a> {
+    storage: &'a EncryptedStorage,
+}
+
+impl<'a> SessionLogRepository<'a> {
+    /// Create repository.
+    pub fn new(storage: &'a EncryptedStorage) -> Self {
+        Self { storage }
+    }
+
+    /// A user's session log; empty if nothing was recorded yet.
+    pub fn get(&self, user_id: &str) -> StorageResult<StoredSessionLog> {
+        let path = self.storage.paths().user_sessions(&user_key(user_id));
+        if !self.storage.exists(&path) {
+            return Ok(StoredSessionLog {
+                user_id: user_id.to_string(),
+                sessions: Vec::new(),
+            });
+        }
+        self.storage.read_json(path)
+    }
+
+    /// Record a request. Returns whether the log was written.
+    pub fn record(
+        &self,
+        user_id: &str,
+        observation: &SessionObservation,
+        now: DateTime<Utc>,
+    ) -> StorageResult<bool> {
+        let mut log = self.get(user_id)?;
+        match log.sessions.iter_mut().find(|s| observation.matches(s)) {
+            Some(session) => {
+                let unchanged = session.ip_address == observation.ip_address
+                    && session.user_agent == observation.user_agent;
+                if unchanged && now - session.last_seen < Duration::seconds(TOUCH_INTERVAL_SECS) {
+                    return Ok(false);
+                }
+                session.ip_address = observation.ip_address.clone();
+                session.user_agent = observation.user_agent.clone();
+                session.last_seen = now;
+            }
+            None => log.sessions.push(SessionRecord {
+                session_id: observation.session_id.clone(),
+                issuer: observation.issuer.clone(),
+                ip_address: observation.ip_address.clone(),
+                user_agent: observation.user_agent.clone(),
+                first_seen: now,
+                last_seen: now,
+            }),
+        }
+        log.sessions.sort_by(|a, b| b.last_seen.cmp(&a.last_seen));
+        log.sessions.truncate(MAX_SESSIONS_PER_USER);
+
+        let path = self.storage.paths().user_sessions(&user_key(user_id));
+        self.storage.write_json(path, &log)?;
+        Ok(true)
+    }
+}
+
+/// File-name-safe key of a user ID.
+pub(crate) fn user_key(user_id: &str) -> String {
+    alloy::hex::encode(&keccak256(user_id.as_bytes())[..16])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::StoragePaths;
+    use tempfile::TempDir;
+
+    fn observation(session_id: Option<&str>, ip: &str) -> SessionObservation {
+        SessionObservation {
+            session_id: session_id.map(str::to_string),
+            issuer: "https://test.clerk.dev".into(),
+            ip_address: Some(ip.into()),
+            user_agent: Some("Mozilla/5.0".into()),
+        }
+    }
+
+    #[test]
+    fn sessions_are_recorded_compactly() {
+        let dir = TempDir::new().unwrap();
+        let mut storage = EncryptedStorage::new(StoragePaths::new(dir.path()));
+        storage.initialize().unwrap();
+        let repo = SessionLogRepository::new(&storage);
+        let start = Utc::now();
+
+        assert!(repo
+            .record("user_1", &observation(Some("sess_a"), "10.0.0.1"), start)
+            .unwrap());
+        // Same client within the touch interval: no write.
+        assert!(!repo
+            .record(
+                "user_1",
+                &observation(Some("sess_a"), "10.0.0.1"),
+                start + Duration::seconds(10)
+            )
+            .unwrap());
+        // The same session from a new IP updates it in place.
+        assert!(repo
+            .record(
+                "user_1",
+                &observation(Some("sess_a"), "10.0.0.2"),
+                start + Duration::seconds(20)
+            )
+            .unwrap());
+        // Tokens without a session ID are told apart by client.
+        repo.record("user_1", &observation(None, "10.0.0.3"), start)
+            .unwrap();
+        repo.record("user_1", &observation(None, "10.0.0.4"), start)
+            .unwrap();
+
+        let log = repo.get("user_1").unwrap();
+        assert_eq!(log.sessions.len(), 3);
+        assert_eq!(log.sessions[0].session_id.as_deref(), Some("sess_a"));
+        assert_eq!(log.sessions[0].ip_address.as_deref(), Some("10.0.0.2"));
+        assert_eq!(log.sessions[0].first_seen, start);
+        assert!(repo.get("user_2").unwrap().sessions.is_empty());
+
+        for i in 0..MAX_SESSIONS_PER_USER + 5 {
+            let id = format!("sess_{i}");
+            repo.record(
+                "user_1",
+                &observation(Some(&id), "10.0.0.9"),
+                start + Duration::seconds(100 + i as i64),
+            )
+            .unwrap();
+        }
+        let log = repo.get("user_1").unwrap();
+        assert_eq!(log.sessions.len(), MAX_SESSIONS_PER_USER);
+        assert_eq!(
+            log.sessions[0].session_id.as_deref(),
+            Some(format!("sess_{}", MAX_SESSIONS_PER_USER + 4).as_str())
+        );
+    }
+
+    #[test]
+    fn anomalies_compare_against_recent_history() {
+        let now = Utc::now();
+        let record = |sid: &str, ip: &str, ua: &str, days_ago: i64| SessionRecord {
+            session_id: Some(sid.to_string()),
+            issuer: "https://test.clerk.dev".into(),
+            ip_address: Some(ip.into()),
+            user_agent: Some(ua.into()),
+            first_seen: now - Duration::days(days_ago),
+            last_seen: now - Duration::days(days_ago),
+        };
+        let mac_chrome = "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/126.0 Safari/537.36";
+        let android_firefox =
+            "Mozilla/5.0 (Android 14; Mobile; rv:128.0) Gecko/128.0 Firefox/128.0";
+
+        let mut log = StoredSessionLog {
+            user_id: "user_1".into(),
+            sessions: vec![record("sess_home", "203.0.113.7", mac_chrome, 2)],
+        };
+        // Nothing to compare against yet.
+        let first = log.sessions[0].clone();
+        assert!(log.anomalies(&first, now).is_empty());
+
+        // Same /16 and device family: familiar.
+        let nearby = record("sess_new", "203.0.44.1", mac_chrome, 0);
+        log.sessions.insert(0, nearby.clone());
+        assert!(log.anomalies(&nearby, now).is_empty());
+
+        let foreign = record("sess_far", "198.51.100.9", android_firefox, 0);
+        log.sessions.insert(0, foreign.clone());
+        assert_eq!(
+            log.anomalies(&foreign, now),
+            vec![
+                SessionAnomaly::UnfamiliarNetwork,
+                SessionAnomaly::UnfamiliarDevice
+            ]
+        );
+        assert_eq!(log.find("sess_far"), Some(&foreign));
+
+        // History older than the window does not count.
+        let stale = StoredSessionLog {
+            user_id: "user_1".into(),
+            sessions: vec![
+                foreign.clone(),
+                record(
+                    "sess_old",
+                    "203.0.113.7",
+                    mac_chrome,
+                    RECENT_HISTORY_DAYS + 1,
+                ),
+            ],
+        };
+        assert!(stale.anomalies(&foreign, now).is_empty());
+    }
+}
@@ -0,0 +1,132 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Spending limits set by admins.
+//!
+//! A limit caps the EUR value a user, or a single wallet, may send per UTC
+//! day and per calendar month. User limits live under
+//! `/data/spending_limits/users/{user_key}.json`, keyed by a digest of the
+//! user ID; wallet limits under `/data/spending_limits/wallets/{wallet_id}.json`.
+//! A user with both is held to each.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::super::{EncryptedStorage, StorageError, StorageResult};
+use super::sessions::user_key;
+
+/// Daily and monthly caps, in EUR with two decimals.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, PartialEq, Eq)]
+pub struct StoredSpendingLimits {
+    /// Most that may be sent per UTC day.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub daily_eur: Option<String>,
+    /// Most that may be sent per calendar month (UTC).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub monthly_eur: Option<String>,
+    pub updated_at: DateTime<Utc>,
+    /// Admin who last changed the limits.
+    pub updated_by: String,
+}
+
+/// Repository for spending limits.
+pub struct SpendingLimitRepository<'a> {
+    storage: &'a EncryptedStorage,
+}
+
+impl<'a> SpendingLimitRepository<'a> {
+    /// Create repository.
+    pub fn new(storage: &'a EncryptedStorage) -> Self {
+        Self { storage }
+    }
+
+    fn read(&self, path: std::path::PathBuf) -> StorageResult<Option<StoredSpendingLimits>> {
+        if !self.storage.exists(&path) {
+            return Ok(None);
+        }
+        self.storage.read_json(path).map(Some)
+    }
+
+    /// Limits of a user across all their wallets, if any.
+    pub fn get_user(&self, user_id: &str) -> StorageResult<Option<StoredSpendingLimits>> {
+        self.read(
+            self.storage
+                .paths()
+                .user_spending_limits(&user_key(user_id)),
+        )
+    }
+
+    /// Replace a user's limits.
+    pub fn set_user(&self, user_id: &str, limits: &StoredSpendingLimits) -> StorageResult<()> {
+        let path = self
+            .storage
+            .paths()
+            .user_spending_limits(&user_key(user_id));
+        self.storage.write_json(path, limits)
+    }
+
+    /// Remove a user's limits.
+    pub fn delete_user(&self, user_id: &str) -> StorageResult<()> {
+        let path = self
+            .storage
+            .paths()
+            .user_spending_limits(&user_key(user_id));
+        if !self.storage.exists(&path) {
+            return Err(StorageError::NotFound("Spending limits".to_string()));
+        }
+        self.storage.delete(path)
+    }
+
+    /// Limits of a single wallet, if any.
+    pub fn get_wallet(&self, wallet_id: &str) -> StorageResult<Option<StoredSpendingLimits>> {
+        self.read(self.storage.paths().wallet_spending_limits(wallet_id))
+    }
+
+    /// Replace a wallet's limits.
+    pub fn set_wallet(&self, wallet_id: &str, limits: &StoredSpendingLimits) -> StorageResult<()> {
+        let path = self.storage.paths().wallet_spending_limits(wallet_id);
+        self.storage.write_json(path, limits)
+    }
+
+    /// Remove a wallet's limits.
+    pub fn delete_wallet(&self, wallet_id: &str) -> StorageResult<()> {
+        let path = self.storage.paths().wallet_spending_limits(wallet_id);
+        if !self.storage.exists(&path) {
+            return Err(StorageError::NotFound("Spending limits".to_string()));
+        }
+        self.storage.delete(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::StoragePaths;
+    use tempfile::TempDir;
+
+    #[test]
+    fn user_and_wallet_limits_are_kept_apart() {
+        let dir = TempDir::new().unwrap();
+        let mut storage = EncryptedStorage::new(StoragePaths::new(dir.path()));
+        storage.initialize().unwrap();
+        let repo = SpendingLimitRepository::new(&storage);
+        let limits = StoredSpendingLimits {
+            daily_eur: Some("100.00".into()),
+            monthly_eur: None,
+            updated_at: Utc::now(),
+            updated_by: "admin_1".into(),
+        };
+
+        assert_eq!(repo.get_user("user_1").unwrap(), None);
+        repo.set_user("user_1", &limits).unwrap();
+        assert_eq!(repo.get_user("user_1").unwrap(), Some(limits.clone()));
+        assert_eq!(repo.get_wallet("user_1").unwrap(), None);
+
+        repo.set_wallet("w1", &limits).unwrap();
+        repo.delete_user("user_1").unwrap();
+        assert!(repo.delete_user("user_1").is_err());
+        assert_eq!(repo.get_wallet("w1").unwrap(), Some(limits));
+    }
+}
@@ -0,0 +1,213 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Per-tenant configuration.
+//!
+//! White-label frontends sharing a deployment (see [`crate::auth::tenant`])
+//! can bring their own branding, fiat provider accounts, fee schedule, CORS
+//! origins and partner webhook endpoints. Each tenant's settings are stored
+//! under `/data/tenants/{tenant_id}.json`; anything a tenant leaves unset
+//! falls back to the deployment's environment configuration. The default
+//! tenant has no record.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::super::{EncryptedStorage, StorageError, StorageResult};
+
+/// Largest fee a schedule may charge, in basis points (10%).
+pub const MAX_FEE_BPS: u32 = 1_000;
+
+/// How a tenant's frontend presents itself.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema, PartialEq, Eq)]
+pub struct TenantBranding {
+    /// Product name shown to users.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+    /// HTTPS URL of the logo.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub logo_url: Option<String>,
+    /// Primary color as `#rrggbb`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub primary_color: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub support_email: Option<String>,
+}
+
+/// A tenant's own TrueLayer account.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, PartialEq, Eq)]
+pub struct TrueLayerCredentials {
+    pub client_id: String,
+    pub client_secret: String,
+    pub signing_key_id: String,
+    /// PEM-encoded request signing key.
+    pub signing_private_key: String,
+    pub merchant_account_id: String,
+}
+
+/// A tenant's own card PSP account.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, PartialEq, Eq)]
+pub struct CardCredentials {
+    pub api_secret: String,
+    /// Secret the PSP signs this account's webhooks with.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub webhook_secret: Option<String>,
+}
+
+/// Fiat provider accounts overriding the deployment's.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema, PartialEq, Eq)]
+pub struct ProviderCredentials {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub truelayer: Option<TrueLayerCredentials>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub card: Option<CardCredentials>,
+}
+
+/// Fees charged on fiat requests, in basis points of the amount.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, ToSchema, PartialEq, Eq)]
+pub struct FeeSchedule {
+    #[serde(default)]
+    pub on_ramp_bps: u32,
+    #[serde(default)]
+    pub off_ramp_bps: u32,
+}
+
+impl FeeSchedule {
+    /// Fee in cents on `amount_minor` cents, rounded down.
+    pub fn fee_minor(bps: u32, amount_minor: u64) -> u64 {
+        (u128::from(amount_minor) * u128::from(bps) / 10_000) as u64
+    }
+}
+
+/// Stored configuration of one tenant.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredTenantConfig {
+    pub tenant_id: String,
+    #[serde(default)]
+    pub branding: TenantBranding,
+    #[serde(default)]
+    pub provider_credentials: ProviderCredentials,
+    #[serde(default)]
+    pub fees: FeeSchedule,
+    /// Browser origins of the tenant's frontend.
+    #[serde(default)]
+    pub cors_origins: Vec<String>,
+    /// HTTPS endpoints that receive the tenant's outbound webhooks.
+    #[serde(default)]
+    pub webhook_endpoints: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    /// Admin who last changed the configuration.
+    pub updated_by: String,
+}
+
+/// Repository for tenant configuration.
+pub struct TenantConfigRepository<'a> {
+    storage: &'a EncryptedStorage,
+}
+
+impl<'a> TenantConfigRepository<'a> {
+    /// Create repository.
+    pub fn new(storage: &'a EncryptedStorage) -> Self {
+        Self { storage }
+    }
+
+    /// Get a tenant's configuration.
+    pub fn get(&self, tenant_id: &str) -> StorageResult<StoredTenantConfig> {
+        let path = self.storage.paths().tenant_config(tenant_id);
+        if !self.storage.exists(&path) {
+            return Err(StorageError::NotFound(format!("Tenant config {tenant_id}")));
+        }
+        self.storage.read_json(path)
+    }
+
+    /// Configuration for requests of `tenant_id`, if the tenant has any.
+    /// Unreadable records count as unconfigured.
+    pub fn for_tenant(&self, tenant_id: Option<&str>) -> Option<StoredTenantConfig> {
+        tenant_id.and_then(|tenant_id| self.get(tenant_id).ok())
+    }
+
+    /// Create or replace a tenant's configuration.
+    pub fn save(&self, config: &StoredTenantConfig) -> StorageResult<()> {
+        self.storage.write_json(
+            self.storage.paths().tenant_config(&config.tenant_id),
+            config,
+        )
+    }
+
+    /// Remove a tenant's configuration.
+    pub fn delete(&self, tenant_id: &str) -> StorageResult<()> {
+        self.get(tenant_id)?;
+        self.storage
+            .delete(self.storage.paths().tenant_config(tenant_id))
+    }
+
+    /// All tenant configurations, by tenant ID.
+    pub fn list_all(&self) -> StorageResult<Vec<StoredTenantConfig>> {
+        let ids = self
+            .storage
+            .list_files(self.storage.paths().tenants_dir(), "json")?;
+        let mut configs: Vec<_> = ids.iter().filter_map(|id| self.get(id).ok()).collect();
+        configs.sort_by(|a, b| a.tenant_id.cmp(&b.tenant_id));
+        Ok(configs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::StoragePaths;
+    use std::env;
+
+    fn test_storage() -> EncryptedStorage {
+        let test_dir = env::temp_dir().join(format!("test-tenant-config-{}", uuid::Uuid::new_v4()));
+        let paths = StoragePaths::new(&test_dir);
+        let mut storage = EncryptedStorage::new(paths);
+        storage.initialize().expect("initialize test storage");
+        storage
+    }
+
+    #[test]
+    fn configs_are_saved_and_selected_by_tenant() {
+        let storage = test_storage();
+        let repo = TenantConfigRepository::new(&storage);
+
+        let config = StoredTenantConfig {
+            tenant_id: "org_a".to_string(),
+            branding: TenantBranding {
+                display_name: Some("Acme Pay".to_string()),
+                ..Default::default()
+            },
+            provider_credentials: ProviderCredentials::default(),
+            fees: FeeSchedule {
+                on_ramp_bps: 150,
+                off_ramp_bps: 0,
+            },
+            cors_origins: vec!["https://pay.acme.example".to_string()],
+            webhook_endpoints: Vec::new(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            updated_by: "admin".to_string(),
+        };
+        repo.save(&config).unwrap();
+
+        assert!(repo.for_tenant(None).is_none());
+        assert!(repo.for_tenant(Some("org_b")).is_none());
+        let loaded = repo.for_tenant(Some("org_a")).unwrap();
+        assert_eq!(loaded.fees.on_ramp_bps, 150);
+        assert_eq!(repo.list_all().unwrap().len(), 1);
+
+        repo.delete("org_a").unwrap();
+        assert!(repo.delete("org_a").is_err());
+        assert!(repo.list_all().unwrap().is_empty());
+    }
+
+    #[test]
+    fn fees_round_down_to_the_cent() {
+        assert_eq!(FeeSchedule::fee_minor(150, 10_000), 150);
+        assert_eq!(FeeSchedule::fee_minor(150, 99), 1);
+        assert_eq!(FeeSchedule::fee_minor(0, 10_000), 0);
+    }
+}
@@ -0,0 +1,147 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Token listing repository.
+//!
+//! Tokens listed by admins are stored under
+//! `/data/tokens/{network}_{address}.json` and loaded into the
+//! [token registry](crate::blockchain::tokens) at startup. Built-in and
+//! `TOKEN_REGISTRY` listings are not stored.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::super::{EncryptedStorage, StorageError, StorageResult};
+use crate::blockchain::{address_key, ListedToken, TokenSource};
+
+/// A token listed by an admin.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, PartialEq, Eq)]
+pub struct StoredToken {
+    /// Registry ID of the network, e.g. `fuji`.
+    pub network: String,
+    /// Contract address.
+    pub address: String,
+    pub symbol: String,
+    pub name: String,
+    pub decimals: u8,
+    /// Whether the indexer records transfers of this token.
+    pub indexed: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    /// Admin who last changed the listing.
+    pub updated_by: String,
+}
+
+impl StoredToken {
+    /// The registry entry for this listing.
+    pub fn to_listed(&self) -> ListedToken {
+        ListedToken {
+            network: self.network.clone(),
+            address: self.address.clone(),
+            symbol: self.symbol.clone(),
+            name: self.name.clone(),
+            decimals: self.decimals,
+            indexed: self.indexed,
+            source: TokenSource::Admin,
+        }
+    }
+}
+
+/// Repository for admin token listings.
+pub struct TokenRepository<'a> {
+    storage: &'a EncryptedStorage,
+}
+
+impl<'a> TokenRepository<'a> {
+    /// Create repository.
+    pub fn new(storage: &'a EncryptedStorage) -> Self {
+        Self { storage }
+    }
+
+    /// Get the listing of `address` on `network`.
+    pub fn get(&self, network: &str, address: &str) -> StorageResult<StoredToken> {
+        let path = self.storage.paths().token(network, &address_key(address));
+        if !self.storage.exists(&path) {
+            return Err(StorageError::NotFound(format!(
+                "Token {address} on {network}"
+            )));
+        }
+        self.storage.read_json(path)
+    }
+
+    /// Create or replace a listing.
+    pub fn save(&self, token: &StoredToken) -> StorageResult<()> {
+        let path = self
+            .storage
+            .paths()
+            .token(&token.network, &address_key(&token.address));
+        self.storage.write_json(path, token)
+    }
+
+    /// Remove a listing.
+    pub fn delete(&self, network: &str, address: &str) -> StorageResult<()> {
+        self.get(network, address)?;
+        self.storage
+            .delete(self.storage.paths().token(network, &address_key(address)))
+    }
+
+    /// All listings, by network then symbol.
+    pub fn list_all(&self) -> StorageResult<Vec<StoredToken>> {
+        let dir = self.storage.paths().tokens_dir();
+        let ids = self.storage.list_files(&dir, "json")?;
+        let mut tokens: Vec<StoredToken> = ids
+            .iter()
+            .filter_map(|id| self.storage.read_json(dir.join(format!("{id}.json"))).ok())
+            .collect();
+        tokens.sort_by(|a, b| (&a.network, &a.symbol).cmp(&(&b.network, &b.symbol)));
+        Ok(tokens)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::StoragePaths;
+    use std::env;
+
+    fn test_storage() -> EncryptedStorage {
+        let test_dir = env::temp_dir().join(format!("test-tokens-{}", uuid::Uuid::new_v4()));
+        let paths = StoragePaths::new(&test_dir);
+        let mut storage = EncryptedStorage::new(paths);
+        storage.initialize().expect("initialize test storage");
+        storage
+    }
+
+    #[test]
+    fn listings_round_trip_by_address_in_any_case() {
+        let storage = test_storage();
+        let repo = TokenRepository::new(&storage);
+        let token = StoredToken {
+            network: "fuji".to_string(),
+            address: "0x5425890298aed601595a70AB815c96711a31Bc65".to_string(),
+            symbol: "USDC".to_string(),
+            name: "USD Coin".to_string(),
+            decimals: 6,
+            indexed: true,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            updated_by: "admin".to_string(),
+        };
+        repo.save(&token).unwrap();
+
+        let upper = "0x5425890298AED601595A70AB815C96711A31BC65";
+        assert_eq!(repo.get("fuji", upper).unwrap(), token);
+        assert_eq!(repo.list_all().unwrap(), std::slice::from_ref(&token));
+        assert_eq!(token.to_listed().source, TokenSource::Admin);
+
+        repo.delete("fuji", upper).unwrap();
+        assert!(repo.list_all().unwrap().is_empty());
+        assert!(matches!(
+            repo.delete("fuji", upper),
+            Err(StorageError::NotFound(_))
+        ));
+        let _ = std::fs::remove_dir_all(storage.paths().root());
+    }
+}
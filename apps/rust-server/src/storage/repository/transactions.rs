@@ -23,7 +23,7 @@ pub enum TxStatus {
 }
 
 /// Token type for a transaction.
-#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum TokenType {
     /// Native AVAX transfer
@@ -67,6 +67,20 @@ pub struct StoredTransaction {
     pub created_at: DateTime<Utc>,
     /// When the status was last updated
     pub updated_at: DateTime<Utc>,
+    /// Zero-value or dust transfer with a lookalike of a previous
+    /// counterparty (address poisoning). Set by the indexer.
+    #[serde(default)]
+    pub suspected_poisoning: bool,
+    /// Fiat request this transaction settled, when it was part of an
+    /// on-ramp, off-ramp or card clawback.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub related_fiat_request_id: Option<String>,
+    /// Hash of the transaction this one replaced (speed-up or cancel).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub replaces: Option<String>,
+    /// Hash of the transaction that replaced this one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub replaced_by: Option<String>,
 }
 
 impl StoredTransaction {
@@ -99,6 +113,10 @@ impl StoredTransaction {
             explorer_url,
             created_at: now,
             updated_at: now,
+            suspected_poisoning: false,
+            related_fiat_request_id: None,
+            replaces: None,
+            replaced_by: None,
         }
     }
 
@@ -0,0 +1,248 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Transaction description templates.
+//!
+//! Admins configure human-readable descriptions per transaction category
+//! and token, e.g. `Card top-up via {provider} – ref {provider_reference}`.
+//! All templates are stored together in
+//! `/data/system/tx_description_templates.json`; the
+//! [rendering layer](crate::api::tx_descriptions) fills them in for
+//! transaction lists, exports and alert notifications.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::super::{EncryptedStorage, StorageResult};
+
+/// Placeholders a template may use, as `{name}`.
+pub const TEMPLATE_PLACEHOLDERS: [&str; 11] = [
+    "amount",
+    "token",
+    "direction",
+    "counterparty",
+    "network",
+    "date",
+    "tx_hash",
+    "category",
+    "provider",
+    "provider_reference",
+    "fiat_amount_eur",
+];
+
+/// One description template.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, PartialEq, Eq)]
+pub struct DescriptionTemplate {
+    /// Automatic category the template applies to (`transfer`,
+    /// `fiat_settlement`, ...); `None` for any category.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    /// Token symbol the template applies to (`rEUR`, `AVAX`, ...); `None`
+    /// for any token.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+    /// Text with `{placeholder}`s.
+    pub template: String,
+}
+
+impl DescriptionTemplate {
+    /// Whether the template applies to a transaction, and how specifically:
+    /// `Some(2)` for a category and token match, `Some(1)` for one of them,
+    /// `Some(0)` for a catch-all.
+    fn specificity(&self, category: Option<&str>, token: &str) -> Option<u8> {
+        let category_match = match self.category.as_deref() {
+            None => 0,
+            Some(c) if Some(c) == category => 1,
+            Some(_) => return None,
+        };
+        let token_match = match self.token.as_deref() {
+            None => 0,
+            Some(t) if t.eq_ignore_ascii_case(token) => 1,
+            Some(_) => return None,
+        };
+        Some(category_match + token_match)
+    }
+
+    /// Fill in the template, taking each placeholder's value from `value`.
+    /// Placeholders without a value render empty.
+    pub fn render(&self, value: impl Fn(&str) -> Option<String>) -> String {
+        let mut out = String::with_capacity(self.template.len());
+        let mut rest = self.template.as_str();
+        while let Some(start) = rest.find('{') {
+            out.push_str(&rest[..start]);
+            let Some(len) = rest[start..].find('}') else {
+                break;
+            };
+            let name = &rest[start + 1..start + len];
+            out.push_str(&value(name).unwrap_or_default());
+            rest = &rest[start + len + 1..];
+        }
+        out.push_str(rest);
+        out
+    }
+}
+
+/// Placeholders in `template`, or the first thing that is not one: an
+/// unknown name or an unmatched brace.
+pub fn template_placeholders(template: &str) -> Result<Vec<&str>, String> {
+    let mut names = Vec::new();
+    let mut rest = template;
+    loop {
+        let open = rest.find('{');
+        let close = rest.find('}');
+        match (open, close) {
+            (None, None) => return Ok(names),
+            (Some(open), Some(close)) if open < close => {
+                let name = &rest[open + 1..close];
+                if !TEMPLATE_PLACEHOLDERS.contains(&name) {
+                    return Err(format!("unknown placeholder {{{name}}}"));
+                }
+                names.push(name);
+                rest = &rest[close + 1..];
+            }
+            _ => return Err("unmatched brace".to_string()),
+        }
+    }
+}
+
+/// The configured templates.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct StoredDescriptionTemplates {
+    pub templates: Vec<DescriptionTemplate>,
+    pub updated_at: DateTime<Utc>,
+    /// Admin who last changed the templates.
+    pub updated_by: String,
+}
+
+impl StoredDescriptionTemplates {
+    /// The most specific template for a transaction of `category` in
+    /// `token`. Among equally specific ones the first listed wins.
+    pub fn select(&self, category: Option<&str>, token: &str) -> Option<&DescriptionTemplate> {
+        let mut best: Option<(u8, &DescriptionTemplate)> = None;
+        for t in &self.templates {
+            if let Some(score) = t.specificity(category, token) {
+                if best.is_none_or(|(b, _)| score > b) {
+                    best = Some((score, t));
+                }
+            }
+        }
+        best.map(|(_, t)| t)
+    }
+}
+
+/// Repository for description templates.
+pub struct DescriptionTemplateRepository<'a> {
+    storage: &'a EncryptedStorage,
+}
+
+impl<'a> DescriptionTemplateRepository<'a> {
+    /// Create repository.
+    pub fn new(storage: &'a EncryptedStorage) -> Self {
+        Self { storage }
+    }
+
+    /// The configured templates; `None` until an admin saved some.
+    pub fn get(&self) -> StorageResult<Option<StoredDescriptionTemplates>> {
+        let path = self.storage.paths().tx_description_templates();
+        if !self.storage.exists(&path) {
+            return Ok(None);
+        }
+        self.storage.read_json(path).map(Some)
+    }
+
+    /// Replace the templates.
+    pub fn save(&self, templates: &StoredDescriptionTemplates) -> StorageResult<()> {
+        self.storage
+            .write_json(self.storage.paths().tx_description_templates(), templates)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::StoragePaths;
+    use std::env;
+
+    fn template(category: Option<&str>, token: Option<&str>, text: &str) -> DescriptionTemplate {
+        DescriptionTemplate {
+            category: category.map(str::to_string),
+            token: token.map(str::to_string),
+            template: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn most_specific_template_wins_and_renders() {
+        let stored = StoredDescriptionTemplates {
+            templates: vec![
+                template(None, None, "Transfer of {amount} {token}"),
+                template(Some("fiat_settlement"), None, "Fiat settlement"),
+                template(
+                    Some("fiat_settlement"),
+                    Some("rEUR"),
+                    "Card top-up via {provider} – ref {provider_reference}",
+                ),
+            ],
+            updated_at: Utc::now(),
+            updated_by: "admin".to_string(),
+        };
+
+        let chosen = stored.select(Some("fiat_settlement"), "reur").unwrap();
+        let text = chosen.render(|name| match name {
+            "provider" => Some("TrueLayer".to_string()),
+            "provider_reference" => Some("PAY-42".to_string()),
+            _ => None,
+        });
+        assert_eq!(text, "Card top-up via TrueLayer – ref PAY-42");
+        assert_eq!(
+            stored
+                .select(Some("fiat_settlement"), "AVAX")
+                .unwrap()
+                .template,
+            "Fiat settlement"
+        );
+        assert_eq!(
+            stored.select(Some("swap"), "AVAX").unwrap().template,
+            "Transfer of {amount} {token}"
+        );
+        assert!(StoredDescriptionTemplates {
+            templates: vec![template(Some("fee"), None, "Fee")],
+            ..stored
+        }
+        .select(None, "AVAX")
+        .is_none());
+    }
+
+    #[test]
+    fn placeholders_are_validated() {
+        assert_eq!(
+            template_placeholders("Top-up {amount} {token}").unwrap(),
+            ["amount", "token"]
+        );
+        assert!(template_placeholders("Hi {name}").is_err());
+        assert!(template_placeholders("Broken {amount").is_err());
+        assert!(template_placeholders("Broken } brace").is_err());
+    }
+
+    #[test]
+    fn templates_round_trip() {
+        let test_dir =
+            env::temp_dir().join(format!("test-tx-descriptions-{}", uuid::Uuid::new_v4()));
+        let mut storage = EncryptedStorage::new(StoragePaths::new(&test_dir));
+        storage.initialize().expect("initialize test storage");
+        let repo = DescriptionTemplateRepository::new(&storage);
+
+        assert!(repo.get().unwrap().is_none());
+        let stored = StoredDescriptionTemplates {
+            templates: vec![template(None, Some("AVAX"), "{direction} {amount} AVAX")],
+            updated_at: Utc::now(),
+            updated_by: "admin".to_string(),
+        };
+        repo.save(&stored).unwrap();
+        assert_eq!(repo.get().unwrap().unwrap().templates, stored.templates);
+
+        let _ = std::fs::remove_dir_all(&test_dir);
+    }
+}
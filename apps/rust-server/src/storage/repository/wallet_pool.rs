@@ -0,0 +1,117 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Pre-generated wallet keys.
+//!
+//! Keys are generated ahead of demand by the [`crate::wallet_pool`] worker
+//! and stored unassigned under `/data/wallet_pool/{entry_id}.json` until
+//! `create_wallet` claims one.
+//!
+//! A key is claimed by deleting its entry: only the caller whose delete
+//! succeeds gets it, so two signups can never be handed the same key.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use super::super::{EncryptedStorage, StorageResult};
+
+/// A key generated ahead of time, not yet bound to a wallet.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PooledKey {
+    pub entry_id: String,
+    /// Private key (PKCS#8 PEM format)
+    pub private_key_pem: String,
+    /// EVM address of the key
+    pub key_address: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Repository for pre-generated keys.
+pub struct WalletPoolRepository<'a> {
+    storage: &'a EncryptedStorage,
+}
+
+impl<'a> WalletPoolRepository<'a> {
+    /// Create repository.
+    pub fn new(storage: &'a EncryptedStorage) -> Self {
+        Self { storage }
+    }
+
+    /// Store a new unassigned key.
+    pub fn add(&self, key: &PooledKey) -> StorageResult<()> {
+        self.storage
+            .write_json(self.storage.paths().wallet_pool_entry(&key.entry_id), key)
+    }
+
+    /// Number of unassigned keys.
+    pub fn count(&self) -> StorageResult<usize> {
+        Ok(self
+            .storage
+            .list_files(self.storage.paths().wallet_pool_dir(), "json")?
+            .len())
+    }
+
+    /// Claim an unassigned key, oldest first. Returns `None` when the pool is
+    /// empty.
+    pub fn take(&self) -> StorageResult<Option<PooledKey>> {
+        let mut entries: Vec<PooledKey> = Vec::new();
+        for entry_id in self
+            .storage
+            .list_files(self.storage.paths().wallet_pool_dir(), "json")?
+        {
+            match self
+                .storage
+                .read_json(self.storage.paths().wallet_pool_entry(&entry_id))
+            {
+                Ok(key) => entries.push(key),
+                // Claimed meanwhile, or unreadable; skip it either way.
+                Err(e) => warn!(entry_id = %entry_id, error = %e, "Skipping pooled key"),
+            }
+        }
+        entries.sort_by_key(|k| k.created_at);
+
+        for key in entries {
+            let path = self.storage.paths().wallet_pool_entry(&key.entry_id);
+            if self.storage.delete(path).is_ok() {
+                return Ok(Some(key));
+            }
+        }
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::StoragePaths;
+    use chrono::Duration;
+    use tempfile::TempDir;
+
+    #[test]
+    fn keys_are_taken_once_oldest_first() {
+        let dir = TempDir::new().unwrap();
+        let mut storage = EncryptedStorage::new(StoragePaths::new(dir.path()));
+        storage.initialize().unwrap();
+        let repo = WalletPoolRepository::new(&storage);
+        assert!(repo.take().unwrap().is_none());
+
+        let now = Utc::now();
+        for (entry_id, age) in [("newer", 1), ("older", 5)] {
+            repo.add(&PooledKey {
+                entry_id: entry_id.to_string(),
+                private_key_pem: format!("pem-{entry_id}"),
+                key_address: format!("0x{entry_id}"),
+                created_at: now - Duration::minutes(age),
+            })
+            .unwrap();
+        }
+        assert_eq!(repo.count().unwrap(), 2);
+
+        assert_eq!(repo.take().unwrap().unwrap().entry_id, "older");
+        assert_eq!(repo.take().unwrap().unwrap().entry_id, "newer");
+        assert!(repo.take().unwrap().is_none());
+        assert_eq!(repo.count().unwrap(), 0);
+    }
+}
@@ -24,7 +24,8 @@ use serde::{Deserialize, Serialize};
 use tracing::warn;
 use utoipa::ToSchema;
 
-use super::super::{EncryptedStorage, StorageError, StorageResult};
+use super::super::{EncryptedStorage, OwnedResource, StorageError, StorageResult};
+use crate::auth::AuthenticatedUser;
 
 /// Wallet status.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
@@ -40,6 +41,52 @@ pub enum WalletStatus {
     Deleted,
 }
 
+/// How a wallet's transactions are authorized on-chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum WalletAccountType {
+    /// The enclave key's own address sends plain transactions.
+    #[default]
+    Eoa,
+    /// An ERC-4337 smart account owned by the enclave key; sends are
+    /// submitted as UserOperations through the configured bundler.
+    SmartAccount,
+}
+
+/// On-chain details of an ERC-4337 smart account.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct SmartAccountInfo {
+    /// Address of the enclave key that owns and signs for the account
+    pub owner_address: String,
+    /// EntryPoint contract the account was created against
+    pub entry_point: String,
+    /// Factory that deploys the account on its first UserOperation
+    pub factory: String,
+    /// Whether the account contract has been deployed
+    pub deployed: bool,
+    /// When the first UserOperation deployed the account
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deployed_at: Option<DateTime<Utc>>,
+}
+
+/// A lock the owner put on their wallet. Sends are blocked while it holds;
+/// deposits and reads are not.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct WalletLock {
+    /// When the owner locked the wallet
+    pub locked_at: DateTime<Utc>,
+    /// When a requested unlock takes effect
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unlocks_at: Option<DateTime<Utc>>,
+}
+
+impl WalletLock {
+    /// Whether the lock still holds at `now`.
+    pub fn in_force(&self, now: DateTime<Utc>) -> bool {
+        self.unlocks_at.is_none_or(|at| now < at)
+    }
+}
+
 /// Wallet metadata stored in meta.json.
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct WalletMetadata {
@@ -72,6 +119,37 @@ pub struct WalletMetadata {
     /// without needing the raw email.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub email_sha256: Option<String>,
+
+    /// Account model; wallets created before smart accounts are EOAs.
+    #[serde(default)]
+    pub account_type: WalletAccountType,
+    /// Smart account details. `public_address` is then the account
+    /// address, not the key's.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub smart_account: Option<SmartAccountInfo>,
+    /// Owner-imposed send lock.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lock: Option<WalletLock>,
+    /// When the wallet was soft-deleted; starts its retention period.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<DateTime<Utc>>,
+    /// When the deleted wallet's key material was destroyed. A purged
+    /// wallet can no longer be restored.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub purged_at: Option<DateTime<Utc>>,
+    /// When the wallet's first incoming transfer was indexed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub funded_at: Option<DateTime<Utc>>,
+    /// Tenant the wallet was created in; `None` for the default tenant.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tenant_id: Option<String>,
+}
+
+impl WalletMetadata {
+    /// Whether the owner's lock blocks sends at `now`.
+    pub fn is_locked(&self, now: DateTime<Utc>) -> bool {
+        self.lock.as_ref().is_some_and(|lock| lock.in_force(now))
+    }
 }
 
 /// Response returned to API clients (never includes private key).
@@ -88,24 +166,44 @@ pub struct WalletResponse {
     /// Optional human-readable label
     #[serde(skip_serializing_if = "Option::is_none")]
     pub label: Option<String>,
+    /// Account model
+    pub account_type: WalletAccountType,
+    /// Smart account details, for `smart_account` wallets
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub smart_account: Option<SmartAccountInfo>,
+    /// Owner-imposed send lock, while it is in force
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lock: Option<WalletLock>,
+    /// When the first incoming transfer was indexed; absent until funded
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub funded_at: Option<DateTime<Utc>>,
 }
 
 impl From<WalletMetadata> for WalletResponse {
     fn from(meta: WalletMetadata) -> Self {
+        let locked = meta.is_locked(Utc::now());
         Self {
             wallet_id: meta.wallet_id,
             public_address: meta.public_address,
             created_at: meta.created_at,
             status: meta.status,
             label: meta.label,
+            account_type: meta.account_type,
+            smart_account: meta.smart_account,
+            lock: meta.lock.filter(|_| locked),
+            funded_at: meta.funded_at,
         }
     }
 }
 
-impl super::super::OwnedResource for WalletMetadata {
+impl OwnedResource for WalletMetadata {
     fn owner_user_id(&self) -> &str {
         &self.owner_user_id
     }
+
+    fn in_tenant(&self, tenant_id: Option<&str>) -> bool {
+        self.tenant_id.as_deref() == tenant_id
+    }
 }
 
 /// Repository for wallet operations on encrypted storage.
@@ -187,7 +285,8 @@ impl<'a> WalletRepository<'a> {
     /// Delete a wallet and all its data.
     ///
     /// **Warning**: This permanently deletes the wallet including the private key.
-    /// TODO: Use after retention period expires for soft-deleted wallets
+    /// Soft-deleted wallets past retention are [purged](Self::purge_keys)
+    /// instead, which keeps their metadata.
     #[allow(dead_code)]
     pub fn delete(&self, wallet_id: &str) -> StorageResult<()> {
         let wallet_dir = self.storage.paths().wallet_dir(wallet_id);
@@ -201,9 +300,42 @@ impl<'a> WalletRepository<'a> {
     pub fn soft_delete(&self, wallet_id: &str) -> StorageResult<()> {
         let mut metadata = self.get(wallet_id)?;
         metadata.status = WalletStatus::Deleted;
+        metadata.deleted_at = Some(Utc::now());
         self.update(&metadata)
     }
 
+    /// Undo a soft delete. Callers check the wallet is deleted and not
+    /// purged first.
+    pub fn restore(&self, wallet_id: &str) -> StorageResult<WalletMetadata> {
+        let mut metadata = self.get(wallet_id)?;
+        metadata.status = WalletStatus::Active;
+        metadata.deleted_at = None;
+        self.update(&metadata)?;
+        Ok(metadata)
+    }
+
+    /// Destroy a wallet's current, staged and archived keys and record
+    /// when. Metadata is kept so history still resolves the wallet.
+    pub fn purge_keys(&self, wallet_id: &str) -> StorageResult<WalletMetadata> {
+        let mut metadata = self.get(wallet_id)?;
+        let paths = self.storage.paths();
+        for key in [
+            paths.wallet_key(wallet_id),
+            paths.wallet_rotation_key(wallet_id),
+        ] {
+            if self.storage.exists(&key) {
+                self.storage.delete(key)?;
+            }
+        }
+        let archived = paths.wallet_archived_keys_dir(wallet_id);
+        if archived.exists() {
+            self.storage.delete_dir(archived)?;
+        }
+        metadata.purged_at = Some(Utc::now());
+        self.update(&metadata)?;
+        Ok(metadata)
+    }
+
     /// List all wallet IDs.
     pub fn list_all_ids(&self) -> StorageResult<Vec<String>> {
         self.storage.list_dirs(self.storage.paths().wallets_dir())
@@ -254,6 +386,13 @@ impl<'a> WalletRepository<'a> {
         Ok(wallets)
     }
 
+    /// List a user's wallets within their tenant (excluding deleted).
+    pub fn list_for_user(&self, user: &AuthenticatedUser) -> StorageResult<Vec<WalletMetadata>> {
+        let mut wallets = self.list_by_owner(&user.user_id)?;
+        wallets.retain(|meta| meta.in_tenant(user.tenant_id.as_deref()));
+        Ok(wallets)
+    }
+
     /// Read the private key for a wallet.
     ///
     /// **Internal use only** - for signing operations.
@@ -271,6 +410,64 @@ impl<'a> WalletRepository<'a> {
             })
     }
 
+    /// Key staged for an unfinished rotation, if any.
+    pub(crate) fn rotation_key(&self, wallet_id: &str) -> StorageResult<Option<Vec<u8>>> {
+        let path = self.storage.paths().wallet_rotation_key(wallet_id);
+        if !self.storage.exists(&path) {
+            return Ok(None);
+        }
+        self.storage.read_raw(path).map(Some)
+    }
+
+    /// Stage the key a rotation moves the wallet to. It is kept until the
+    /// rotation completes, so funds swept to it before a failure stay
+    /// reachable and a retry reuses it.
+    pub(crate) fn stage_rotation_key(
+        &self,
+        wallet_id: &str,
+        private_key_pem: &[u8],
+    ) -> StorageResult<()> {
+        if !self.exists(wallet_id) {
+            return Err(StorageError::NotFound(format!("Wallet {wallet_id}")));
+        }
+        self.storage.write_raw(
+            self.storage.paths().wallet_rotation_key(wallet_id),
+            private_key_pem,
+        )
+    }
+
+    /// Finish a rotation: archive the current key under its address, make
+    /// the staged key current and store `metadata` with the new address.
+    pub(crate) fn complete_rotation(
+        &self,
+        metadata: &WalletMetadata,
+        old_address: &str,
+    ) -> StorageResult<()> {
+        let wallet_id = &metadata.wallet_id;
+        let paths = self.storage.paths();
+        let new_key = self
+            .rotation_key(wallet_id)?
+            .ok_or_else(|| StorageError::NotFound(format!("Wallet {wallet_id} rotation key")))?;
+        let old_key = self.read_private_key(wallet_id)?;
+
+        self.storage
+            .create_dir(paths.wallet_archived_keys_dir(wallet_id))?;
+        self.storage
+            .write_raw(paths.wallet_archived_key(wallet_id, old_address), &old_key)?;
+        self.storage
+            .write_raw(paths.wallet_key(wallet_id), &new_key)?;
+        self.update(metadata)?;
+        self.storage.delete(paths.wallet_rotation_key(wallet_id))
+    }
+
+    /// Addresses of the keys a wallet was rotated away from.
+    pub fn archived_key_addresses(&self, wallet_id: &str) -> StorageResult<Vec<String>> {
+        self.storage.list_files(
+            self.storage.paths().wallet_archived_keys_dir(wallet_id),
+            "pem",
+        )
+    }
+
     /// Verify wallet ownership.
     ///
     /// Returns the wallet metadata if the user owns it, otherwise returns Forbidden.
@@ -322,6 +519,13 @@ mod tests {
             label: Some("My Wallet".to_string()),
             email_lookup_key: None,
             email_sha256: None,
+            account_type: Default::default(),
+            smart_account: None,
+            lock: None,
+            deleted_at: None,
+            purged_at: None,
+            funded_at: None,
+            tenant_id: None,
         }
     }
 
@@ -343,6 +547,61 @@ mod tests {
         cleanup(&storage);
     }
 
+    #[test]
+    fn wallets_stored_before_smart_accounts_are_eoas() {
+        let json = r#"{
+            "wallet_id": "w1",
+            "owner_user_id": "u1",
+            "public_address": "0xabc",
+            "created_at": "2026-01-01T00:00:00Z",
+            "status": "active"
+        }"#;
+        let meta: WalletMetadata = serde_json::from_str(json).unwrap();
+        assert_eq!(meta.account_type, WalletAccountType::Eoa);
+        assert!(meta.smart_account.is_none());
+
+        let response = WalletResponse::from(meta);
+        let value = serde_json::to_value(&response).unwrap();
+        assert_eq!(value["account_type"], "eoa");
+        assert!(value.get("smart_account").is_none());
+    }
+
+    #[test]
+    fn rotation_archives_the_old_key() {
+        let storage = test_storage();
+        let repo = WalletRepository::new(&storage);
+        let mut meta = test_metadata();
+        repo.create(&meta, b"old-key").unwrap();
+
+        assert!(repo.rotation_key(&meta.wallet_id).unwrap().is_none());
+        repo.stage_rotation_key(&meta.wallet_id, b"new-key")
+            .unwrap();
+        assert_eq!(
+            repo.rotation_key(&meta.wallet_id).unwrap().as_deref(),
+            Some(&b"new-key"[..])
+        );
+
+        meta.public_address = "0xnew".to_string();
+        repo.complete_rotation(&meta, "0xOLD").unwrap();
+        assert_eq!(repo.read_private_key(&meta.wallet_id).unwrap(), b"new-key");
+        assert_eq!(repo.get(&meta.wallet_id).unwrap().public_address, "0xnew");
+        assert!(repo.rotation_key(&meta.wallet_id).unwrap().is_none());
+        assert_eq!(
+            repo.archived_key_addresses(&meta.wallet_id).unwrap(),
+            vec!["0xold".to_string()]
+        );
+        let archived = storage
+            .read_raw(
+                storage
+                    .paths()
+                    .wallet_archived_key(&meta.wallet_id, "0xold"),
+            )
+            .unwrap();
+        assert_eq!(archived, b"old-key");
+
+        cleanup(&storage);
+    }
+
     #[test]
     fn create_duplicate_fails() {
         let storage = test_storage();
@@ -422,6 +681,7 @@ mod tests {
 
         let loaded = repo.get(&meta.wallet_id).unwrap();
         assert_eq!(loaded.status, WalletStatus::Deleted);
+        assert!(loaded.deleted_at.is_some());
 
         // Soft-deleted wallets don't show in list_by_owner
         let wallets = repo.list_by_owner(&meta.owner_user_id).unwrap();
@@ -430,6 +690,35 @@ mod tests {
         cleanup(&storage);
     }
 
+    #[test]
+    fn restore_and_purge_keys() {
+        let storage = test_storage();
+        let repo = WalletRepository::new(&storage);
+
+        let meta = test_metadata();
+        repo.create(&meta, b"key").unwrap();
+        repo.soft_delete(&meta.wallet_id).unwrap();
+
+        let restored = repo.restore(&meta.wallet_id).unwrap();
+        assert_eq!(restored.status, WalletStatus::Active);
+        assert!(restored.deleted_at.is_none());
+        assert_eq!(repo.list_by_owner(&meta.owner_user_id).unwrap().len(), 1);
+
+        repo.soft_delete(&meta.wallet_id).unwrap();
+        repo.stage_rotation_key(&meta.wallet_id, b"staged").unwrap();
+        let purged = repo.purge_keys(&meta.wallet_id).unwrap();
+        assert!(purged.purged_at.is_some());
+        assert_eq!(purged.status, WalletStatus::Deleted);
+        assert!(matches!(
+            repo.read_private_key(&meta.wallet_id),
+            Err(StorageError::NotFound(_))
+        ));
+        assert!(repo.rotation_key(&meta.wallet_id).unwrap().is_none());
+        assert!(repo.get(&meta.wallet_id).unwrap().purged_at.is_some());
+
+        cleanup(&storage);
+    }
+
     #[test]
     fn wallet_response_from_metadata() {
         let meta = test_metadata();
@@ -0,0 +1,177 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Watch-only address repository.
+//!
+//! Users can register external addresses whose keys they hold elsewhere.
+//! The server tracks their balances and transfers but never signs for them.
+//! Each entry is stored under `/data/watch_only/{watch_id}.json`.
+//!
+//! Several users may watch the same address. The indexer keys its address
+//! map by a single tracking ID per address ([`tracking_id`]), so transfers
+//! are indexed once and shown to every watcher.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::super::{EncryptedStorage, StorageError, StorageResult};
+
+/// Prefix of the tracking IDs registered for watched addresses in the
+/// transaction database, in place of a wallet ID.
+pub const TRACKING_ID_PREFIX: &str = "watch:";
+
+/// ID under which the transaction database tracks a watched address.
+pub fn tracking_id(address: &str) -> String {
+    format!("{TRACKING_ID_PREFIX}{}", address.to_lowercase())
+}
+
+/// Whether an address-map entry belongs to a watched address rather than a
+/// custodial wallet.
+pub fn is_tracking_id(id: &str) -> bool {
+    id.starts_with(TRACKING_ID_PREFIX)
+}
+
+/// A watch-only address registered by a user.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, PartialEq, Eq)]
+pub struct StoredWatchOnlyAddress {
+    /// Unique entry identifier (UUID).
+    pub watch_id: String,
+    /// User who registered the address.
+    pub owner_user_id: String,
+    /// Watched address (lowercase, `0x`-prefixed).
+    pub address: String,
+    /// Optional user label.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl super::super::OwnedResource for StoredWatchOnlyAddress {
+    fn owner_user_id(&self) -> &str {
+        &self.owner_user_id
+    }
+}
+
+/// Repository for watch-only addresses.
+pub struct WatchOnlyRepository<'a> {
+    storage: &'a EncryptedStorage,
+}
+
+impl<'a> WatchOnlyRepository<'a> {
+    /// Create repository.
+    pub fn new(storage: &'a EncryptedStorage) -> Self {
+        Self { storage }
+    }
+
+    /// Get an entry by ID.
+    pub fn get(&self, watch_id: &str) -> StorageResult<StoredWatchOnlyAddress> {
+        let path = self.storage.paths().watch_only(watch_id);
+        if !self.storage.exists(&path) {
+            return Err(StorageError::NotFound(format!(
+                "Watch-only address {watch_id}"
+            )));
+        }
+        self.storage.read_json(path)
+    }
+
+    /// Store a new entry.
+    pub fn create(&self, entry: &StoredWatchOnlyAddress) -> StorageResult<()> {
+        let path = self.storage.paths().watch_only(&entry.watch_id);
+        if self.storage.exists(&path) {
+            return Err(StorageError::AlreadyExists(format!(
+                "Watch-only address {}",
+                entry.watch_id
+            )));
+        }
+        self.storage.write_json(path, entry)
+    }
+
+    /// Remove an entry.
+    pub fn delete(&self, watch_id: &str) -> StorageResult<()> {
+        self.get(watch_id)?;
+        self.storage
+            .delete(self.storage.paths().watch_only(watch_id))
+    }
+
+    /// All entries, across users.
+    pub fn list_all(&self) -> StorageResult<Vec<StoredWatchOnlyAddress>> {
+        let ids = self
+            .storage
+            .list_files(self.storage.paths().watch_only_dir(), "json")?;
+        Ok(ids.iter().filter_map(|id| self.get(id).ok()).collect())
+    }
+
+    /// A user's entries, oldest first.
+    pub fn list_by_owner(&self, owner_user_id: &str) -> StorageResult<Vec<StoredWatchOnlyAddress>> {
+        let mut entries: Vec<_> = self
+            .list_all()?
+            .into_iter()
+            .filter(|entry| entry.owner_user_id == owner_user_id)
+            .collect();
+        entries.sort_by_key(|entry| entry.created_at);
+        Ok(entries)
+    }
+
+    /// Whether any user still watches `address`.
+    pub fn is_watched(&self, address: &str) -> StorageResult<bool> {
+        let address = address.to_lowercase();
+        Ok(self
+            .list_all()?
+            .iter()
+            .any(|entry| entry.address == address))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::StoragePaths;
+    use std::env;
+    use std::fs;
+
+    fn test_storage() -> EncryptedStorage {
+        let test_dir = env::temp_dir().join(format!("test-watch-only-{}", uuid::Uuid::new_v4()));
+        let paths = StoragePaths::new(&test_dir);
+        let mut storage = EncryptedStorage::new(paths);
+        storage.initialize().expect("initialize test storage");
+        storage
+    }
+
+    fn entry(watch_id: &str, owner: &str, address: &str) -> StoredWatchOnlyAddress {
+        StoredWatchOnlyAddress {
+            watch_id: watch_id.to_string(),
+            owner_user_id: owner.to_string(),
+            address: address.to_string(),
+            label: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn entries_are_listed_per_owner_and_shared_addresses_stay_watched() {
+        let storage = test_storage();
+        let repo = WatchOnlyRepository::new(&storage);
+        let address = "0x3333333333333333333333333333333333333333";
+        repo.create(&entry("a", "user-1", address)).unwrap();
+        repo.create(&entry("b", "user-2", address)).unwrap();
+        assert!(repo.create(&entry("a", "user-1", address)).is_err());
+
+        assert_eq!(repo.list_by_owner("user-1").unwrap().len(), 1);
+        repo.delete("a").unwrap();
+        assert!(repo.is_watched(&address.to_uppercase()).unwrap());
+        repo.delete("b").unwrap();
+        assert!(!repo.is_watched(address).unwrap());
+
+        let _ = fs::remove_dir_all(storage.paths().root());
+    }
+
+    #[test]
+    fn tracking_ids_are_distinguishable_from_wallet_ids() {
+        let id = tracking_id("0xABCD");
+        assert_eq!(id, "watch:0xabcd");
+        assert!(is_tracking_id(&id));
+        assert!(!is_tracking_id("6f1c1c0e-0000-4000-8000-000000000000"));
+    }
+}
@@ -0,0 +1,162 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Repository for the Ed25519 keys that sign outbound webhooks.
+//!
+//! The keyring (`/data/webhook_keys/keyring.json`) lists public keys and
+//! which one is active. Private halves are held by the
+//! [secret signer](crate::secret_signer): next to the keyring as PKCS#8
+//! files, or in an external KMS. Rotation retires the previous key after an
+//! overlap period rather than immediately, so partners can pick up the new
+//! key while deliveries signed with the old one are still accepted.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::super::{EncryptedStorage, StorageError, StorageResult};
+
+/// Public half of a webhook signing key.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct StoredWebhookKey {
+    pub key_id: String,
+    /// Raw Ed25519 public key, base64url without padding.
+    pub public_key: String,
+    pub created_at: DateTime<Utc>,
+    /// When a rotated-out key stops being valid. `None` for the active key.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retires_at: Option<DateTime<Utc>>,
+}
+
+/// Webhook signing keys and rotation state.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct StoredWebhookKeyring {
+    pub active_key_id: String,
+    pub keys: Vec<StoredWebhookKey>,
+    /// Time of the last rotation; `None` until the first one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rotated_at: Option<DateTime<Utc>>,
+}
+
+impl StoredWebhookKeyring {
+    /// The key new deliveries are signed with.
+    pub fn active(&self) -> Option<&StoredWebhookKey> {
+        self.keys.iter().find(|k| k.key_id == self.active_key_id)
+    }
+
+    /// Rotated-out keys still inside their overlap period at `now`.
+    pub fn retiring(&self, now: DateTime<Utc>) -> Vec<&StoredWebhookKey> {
+        self.keys
+            .iter()
+            .filter(|k| k.key_id != self.active_key_id)
+            .filter(|k| k.retires_at.is_some_and(|at| at > now))
+            .collect()
+    }
+}
+
+/// Repository for webhook signing keys.
+pub struct WebhookKeyRepository<'a> {
+    storage: &'a EncryptedStorage,
+}
+
+impl<'a> WebhookKeyRepository<'a> {
+    /// Create repository.
+    pub fn new(storage: &'a EncryptedStorage) -> Self {
+        Self { storage }
+    }
+
+    /// Load the keyring.
+    pub fn get(&self) -> StorageResult<StoredWebhookKeyring> {
+        let path = self.storage.paths().webhook_keyring();
+        if !self.storage.exists(&path) {
+            return Err(StorageError::NotFound("Webhook keyring".to_string()));
+        }
+        self.storage.read_json(path)
+    }
+
+    /// Write the first keyring, with `key` active.
+    pub fn initialize(&self, key: StoredWebhookKey) -> StorageResult<StoredWebhookKeyring> {
+        let keyring = StoredWebhookKeyring {
+            active_key_id: key.key_id.clone(),
+            keys: vec![key],
+            rotated_at: None,
+        };
+        self.storage
+            .write_json(self.storage.paths().webhook_keyring(), &keyring)?;
+        Ok(keyring)
+    }
+
+    /// Make `new_key` active. The previous active key stays valid for
+    /// `overlap`; keys whose overlap already ended are removed from the
+    /// keyring and returned so their private halves can be destroyed.
+    pub fn rotate(
+        &self,
+        new_key: StoredWebhookKey,
+        overlap: Duration,
+        now: DateTime<Utc>,
+    ) -> StorageResult<(StoredWebhookKeyring, Vec<StoredWebhookKey>)> {
+        let mut keyring = self.get()?;
+        for key in &mut keyring.keys {
+            if key.key_id == keyring.active_key_id {
+                key.retires_at = Some(now + overlap);
+            }
+        }
+        let (expired, kept): (Vec<_>, Vec<_>) = keyring
+            .keys
+            .into_iter()
+            .partition(|k| k.retires_at.is_some_and(|at| at <= now));
+        keyring.keys = kept;
+        keyring.active_key_id = new_key.key_id.clone();
+        keyring.keys.push(new_key);
+        keyring.rotated_at = Some(now);
+
+        self.storage
+            .write_json(self.storage.paths().webhook_keyring(), &keyring)?;
+        Ok((keyring, expired))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::StoragePaths;
+    use tempfile::TempDir;
+
+    fn key(key_id: &str, now: DateTime<Utc>) -> StoredWebhookKey {
+        StoredWebhookKey {
+            key_id: key_id.to_string(),
+            public_key: "pk".to_string(),
+            created_at: now,
+            retires_at: None,
+        }
+    }
+
+    #[test]
+    fn rotation_keeps_the_previous_key_for_the_overlap_then_drops_it() {
+        let temp = TempDir::new().unwrap();
+        let mut storage = EncryptedStorage::new(StoragePaths::new(temp.path()));
+        storage.initialize().unwrap();
+        let repo = WebhookKeyRepository::new(&storage);
+        let now = Utc::now();
+
+        assert!(matches!(repo.get(), Err(StorageError::NotFound(_))));
+        let first = repo.initialize(key("whk_1", now)).unwrap();
+        assert_eq!(repo.get().unwrap(), first);
+
+        let (rotated, expired) = repo
+            .rotate(key("whk_2", now), Duration::hours(24), now)
+            .unwrap();
+        assert!(expired.is_empty());
+        assert_eq!(rotated.active_key_id, "whk_2");
+        assert_eq!(rotated.retiring(now)[0].key_id, "whk_1");
+        assert!(rotated.retiring(now + Duration::hours(25)).is_empty());
+
+        let later = now + Duration::hours(25);
+        let (again, expired) = repo
+            .rotate(key("whk_3", later), Duration::hours(24), later)
+            .unwrap();
+        assert_eq!(again.keys.len(), 2);
+        assert!(again.keys.iter().all(|k| k.key_id != "whk_1"));
+        assert_eq!(expired[0].key_id, "whk_1");
+    }
+}
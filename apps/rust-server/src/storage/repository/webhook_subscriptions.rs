@@ -0,0 +1,307 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! Users' webhook subscriptions and their deliveries.
+//!
+//! A subscription (`/data/webhooks/subscriptions/{subscription_id}.json`)
+//! names a URL, the secret deliveries to it are signed with, and the event
+//! types it wants. A subscription limited to one wallet is that wallet's
+//! deposit webhook, used by merchants to learn that a payment arrived. Every event queued for a subscription is a delivery
+//! (`/data/webhooks/deliveries/{delivery_id}.json`) that the
+//! [dispatcher](crate::webhook_dispatcher) attempts until it is accepted or
+//! runs out of attempts; finished deliveries double as the delivery log.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::super::{EncryptedStorage, OwnedResource, StorageError, StorageResult};
+
+/// Event a webhook can subscribe to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+pub enum WebhookEventType {
+    /// A transaction sent from one of the user's wallets was confirmed.
+    #[serde(rename = "transaction.confirmed")]
+    TransactionConfirmed,
+    /// An incoming transfer to one of the user's wallets was indexed.
+    #[serde(rename = "deposit.indexed")]
+    DepositIndexed,
+    /// One of the user's fiat requests completed.
+    #[serde(rename = "fiat.completed")]
+    FiatCompleted,
+}
+
+impl WebhookEventType {
+    /// Every event type, in declaration order.
+    pub const ALL: [WebhookEventType; 3] = [
+        WebhookEventType::TransactionConfirmed,
+        WebhookEventType::DepositIndexed,
+        WebhookEventType::FiatCompleted,
+    ];
+
+    /// Name in delivery bodies and headers.
+    pub fn name(self) -> &'static str {
+        match self {
+            WebhookEventType::TransactionConfirmed => "transaction.confirmed",
+            WebhookEventType::DepositIndexed => "deposit.indexed",
+            WebhookEventType::FiatCompleted => "fiat.completed",
+        }
+    }
+
+    /// One-line description, as published in the event catalog.
+    pub fn description(self) -> &'static str {
+        match self {
+            WebhookEventType::TransactionConfirmed => {
+                "A transaction sent from one of the user's wallets was confirmed"
+            }
+            WebhookEventType::DepositIndexed => {
+                "An incoming transfer to one of the user's wallets was indexed"
+            }
+            WebhookEventType::FiatCompleted => "One of the user's fiat requests completed",
+        }
+    }
+}
+
+/// A user's webhook subscription.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct StoredWebhookSubscription {
+    pub subscription_id: String,
+    pub owner_user_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tenant_id: Option<String>,
+    /// HTTPS endpoint receiving deliveries.
+    pub url: String,
+    /// Key of the HMAC-SHA256 delivery signature.
+    pub secret: String,
+    /// Subscribed event types; empty means all.
+    #[serde(default)]
+    pub events: Vec<WebhookEventType>,
+    /// Wallet the subscription is limited to, for a wallet's deposit
+    /// webhook; `None` covers all the owner's wallets.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wallet_id: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl StoredWebhookSubscription {
+    /// Whether the subscription wants `event_type`.
+    pub fn wants(&self, event_type: WebhookEventType) -> bool {
+        self.events.is_empty() || self.events.contains(&event_type)
+    }
+}
+
+impl OwnedResource for StoredWebhookSubscription {
+    fn owner_user_id(&self) -> &str {
+        &self.owner_user_id
+    }
+
+    fn in_tenant(&self, tenant_id: Option<&str>) -> bool {
+        self.tenant_id.as_deref() == tenant_id
+    }
+}
+
+/// Lifecycle of a delivery.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookDeliveryStatus {
+    /// Waiting for its next attempt at `next_attempt_at`.
+    Pending,
+    /// The endpoint answered with a 2xx status.
+    Delivered,
+    /// Every attempt failed, or the subscription was deleted.
+    Failed,
+}
+
+/// One event queued for one subscription.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct StoredWebhookDelivery {
+    pub delivery_id: String,
+    pub subscription_id: String,
+    pub owner_user_id: String,
+    pub event_type: WebhookEventType,
+    /// Delivery body; sent as canonical JSON.
+    pub payload: serde_json::Value,
+    pub status: WebhookDeliveryStatus,
+    pub attempts: u32,
+    /// When a pending delivery is attempted next.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_attempt_at: Option<DateTime<Utc>>,
+    /// HTTP status of the last attempt, if the endpoint answered.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_status_code: Option<u16>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl OwnedResource for StoredWebhookDelivery {
+    fn owner_user_id(&self) -> &str {
+        &self.owner_user_id
+    }
+}
+
+/// Repository for webhook subscriptions and deliveries.
+pub struct WebhookSubscriptionRepository<'a> {
+    storage: &'a EncryptedStorage,
+}
+
+impl<'a> WebhookSubscriptionRepository<'a> {
+    /// Create repository.
+    pub fn new(storage: &'a EncryptedStorage) -> Self {
+        Self { storage }
+    }
+
+    /// Get a subscription by ID.
+    pub fn get(&self, subscription_id: &str) -> StorageResult<StoredWebhookSubscription> {
+        let path = self.storage.paths().webhook_subscription(subscription_id);
+        if !self.storage.exists(&path) {
+            return Err(StorageError::NotFound(format!(
+                "Webhook subscription {subscription_id}"
+            )));
+        }
+        self.storage.read_json(path)
+    }
+
+    /// Create or replace a subscription.
+    pub fn save(&self, subscription: &StoredWebhookSubscription) -> StorageResult<()> {
+        self.storage.write_json(
+            self.storage
+                .paths()
+                .webhook_subscription(&subscription.subscription_id),
+            subscription,
+        )
+    }
+
+    /// Delete a subscription. Its deliveries stay in the log.
+    pub fn delete(&self, subscription_id: &str) -> StorageResult<()> {
+        self.get(subscription_id)?;
+        self.storage
+            .delete(self.storage.paths().webhook_subscription(subscription_id))
+    }
+
+    /// All subscriptions, oldest first.
+    pub fn list_all(&self) -> StorageResult<Vec<StoredWebhookSubscription>> {
+        let ids = self
+            .storage
+            .list_files(self.storage.paths().webhook_subscriptions_dir(), "json")?;
+        let mut subscriptions: Vec<_> = ids.iter().filter_map(|id| self.get(id).ok()).collect();
+        subscriptions.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+        Ok(subscriptions)
+    }
+
+    /// The deposit webhook of `wallet_id`, if it has one.
+    pub fn get_for_wallet(
+        &self,
+        wallet_id: &str,
+    ) -> StorageResult<Option<StoredWebhookSubscription>> {
+        Ok(self
+            .list_all()?
+            .into_iter()
+            .find(|s| s.wallet_id.as_deref() == Some(wallet_id)))
+    }
+
+    /// A user's subscriptions, oldest first.
+    pub fn list_for_owner(&self, user_id: &str) -> StorageResult<Vec<StoredWebhookSubscription>> {
+        Ok(self
+            .list_all()?
+            .into_iter()
+            .filter(|s| s.owner_user_id == user_id)
+            .collect())
+    }
+
+    /// Get a delivery by ID.
+    pub fn get_delivery(&self, delivery_id: &str) -> StorageResult<StoredWebhookDelivery> {
+        let path = self.storage.paths().webhook_delivery(delivery_id);
+        if !self.storage.exists(&path) {
+            return Err(StorageError::NotFound(format!(
+                "Webhook delivery {delivery_id}"
+            )));
+        }
+        self.storage.read_json(path)
+    }
+
+    /// Create or replace a delivery.
+    pub fn save_delivery(&self, delivery: &StoredWebhookDelivery) -> StorageResult<()> {
+        self.storage.write_json(
+            self.storage.paths().webhook_delivery(&delivery.delivery_id),
+            delivery,
+        )
+    }
+
+    /// Remove a delivery from the log.
+    pub fn delete_delivery(&self, delivery_id: &str) -> StorageResult<()> {
+        self.storage
+            .delete(self.storage.paths().webhook_delivery(delivery_id))
+    }
+
+    /// All deliveries, newest first.
+    pub fn list_deliveries(&self) -> StorageResult<Vec<StoredWebhookDelivery>> {
+        let ids = self
+            .storage
+            .list_files(self.storage.paths().webhook_deliveries_dir(), "json")?;
+        let mut deliveries: Vec<_> = ids
+            .iter()
+            .filter_map(|id| self.get_delivery(id).ok())
+            .collect();
+        deliveries.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        Ok(deliveries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::StoragePaths;
+    use tempfile::TempDir;
+
+    fn subscription(id: &str, owner: &str, minutes: i64) -> StoredWebhookSubscription {
+        StoredWebhookSubscription {
+            subscription_id: id.to_string(),
+            owner_user_id: owner.to_string(),
+            tenant_id: None,
+            url: "https://hooks.example.com/relational".to_string(),
+            secret: "s3cret-s3cret-s3cret".to_string(),
+            events: vec![WebhookEventType::FiatCompleted],
+            wallet_id: None,
+            created_at: Utc::now() + chrono::Duration::minutes(minutes),
+        }
+    }
+
+    #[test]
+    fn subscriptions_are_listed_per_owner_and_deleted() {
+        let temp = TempDir::new().unwrap();
+        let mut storage = EncryptedStorage::new(StoragePaths::new(temp.path()));
+        storage.initialize().unwrap();
+        let repo = WebhookSubscriptionRepository::new(&storage);
+
+        repo.save(&subscription("whs_b", "user-1", 1)).unwrap();
+        repo.save(&subscription("whs_a", "user-1", 0)).unwrap();
+        repo.save(&subscription("whs_c", "user-2", 2)).unwrap();
+
+        let ids: Vec<_> = repo
+            .list_for_owner("user-1")
+            .unwrap()
+            .into_iter()
+            .map(|s| s.subscription_id)
+            .collect();
+        assert_eq!(ids, vec!["whs_a", "whs_b"]);
+        assert!(repo
+            .get("whs_a")
+            .unwrap()
+            .wants(WebhookEventType::FiatCompleted));
+        assert!(!repo
+            .get("whs_a")
+            .unwrap()
+            .wants(WebhookEventType::DepositIndexed));
+
+        repo.delete("whs_a").unwrap();
+        assert!(matches!(repo.get("whs_a"), Err(StorageError::NotFound(_))));
+        assert!(matches!(
+            repo.delete("whs_a"),
+            Err(StorageError::NotFound(_))
+        ));
+        assert_eq!(repo.list_all().unwrap().len(), 2);
+    }
+}
@@ -5,7 +5,8 @@
 //! LRU cache for transaction history first-page lookups.
 //!
 //! Caches the first page of transactions per wallet address to avoid
-//! repeated redb reads for the most common query pattern.
+//! repeated redb reads for the most common query pattern. Subscribes to the
+//! [`CacheBus`](super::CacheBus) for transaction history changes.
 
 use std::num::NonZeroUsize;
 use std::sync::Mutex;
@@ -13,6 +14,7 @@ use std::time::{Duration, Instant};
 
 use lru::LruCache;
 
+use super::cache_bus::{CacheResource, CacheSubscriber};
 use super::repository::transactions::StoredTransaction;
 
 /// Cached entry: list of transactions + insertion timestamp.
@@ -98,6 +100,14 @@ impl TxCache {
     }
 }
 
+impl CacheSubscriber for TxCache {
+    fn invalidate(&self, address: &str, resource: CacheResource) {
+        if resource == CacheResource::TransactionHistory {
+            TxCache::invalidate(self, address);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -145,6 +155,19 @@ mod tests {
         assert!(cache.get_first_page(addr, 1).is_none());
     }
 
+    #[test]
+    fn cache_is_invalidated_through_the_bus() {
+        let cache = std::sync::Arc::new(TxCache::new(10, Duration::from_secs(300)));
+        let bus = crate::storage::CacheBus::new();
+        bus.subscribe(cache.clone());
+        cache.put_first_page("0xABCD", vec![sample_tx()], None);
+
+        bus.invalidate("0xabcd", CacheResource::Balance);
+        assert!(cache.get_first_page("0xABCD", 1).is_some());
+        bus.invalidate_address("0xabcd");
+        assert!(cache.get_first_page("0xABCD", 1).is_none());
+    }
+
     #[test]
     fn cache_ttl_expiry() {
         let cache = TxCache::new(10, Duration::from_millis(1));
@@ -10,12 +10,26 @@
 //! - `wallet_tx_index`: composite key (address|!timestamp|tx_hash) → direction
 //! - `address_wallet_map`: on-chain address → wallet_id
 //! - `indexer_state`: key → value (checkpoint state)
+//! - `nft_holdings`: (wallet_id|network|contract|token_id) → serialized NftHolding
+//! - `nft_transfers_seen`: (network|tx_hash|log_index) → block number
+//! - `audit_events`: (timestamp_ms|event_id) → serialized AuditEvent
+//! - `audit_index`: (field\0value\0timestamp_ms|event_id) → ()
+//! - `audit_days`: date → archive lines indexed
+//!
+//! Every transaction write is also published on the domain event bus as
+//! [`DomainEvent::TxStatusChanged`] so request handlers can wait for a
+//! status change instead of polling.
 
 use std::path::Path;
 
-use redb::{Database, ReadableDatabase, ReadableTable, TableDefinition};
+use alloy::primitives::U256;
+use serde::{Deserialize, Serialize};
 
+use super::audit::{AuditEvent, AuditPage, AuditQuery};
 use super::repository::transactions::{StoredTransaction, TxStatus};
+use crate::blockchain::nft::{NftStandard, NftTransfer};
+use crate::events::{DomainEvent, EventBus};
+use redb::{Database, ReadableDatabase, ReadableTable, TableDefinition};
 
 // =============================================================================
 // Table Definitions
@@ -34,6 +48,30 @@ const ADDRESS_WALLET_MAP: TableDefinition<&str, &str> = TableDefinition::new("ad
 /// Indexer state: key → value bytes (e.g., "last_block_fuji" → u64 big-endian).
 const INDEXER_STATE: TableDefinition<&str, &[u8]> = TableDefinition::new("indexer_state");
 
+/// NFT holdings: `wallet_id|network|contract|token_id` → JSON NftHolding.
+/// Contract is lowercase, token ID decimal; rows at zero are removed.
+const NFT_HOLDINGS: TableDefinition<&str, &[u8]> = TableDefinition::new("nft_holdings");
+
+/// NFT transfer logs already applied: `network|tx_hash|log_index` → block.
+/// Keeps re-scanned blocks from counting a transfer twice.
+const NFT_TRANSFERS_SEEN: TableDefinition<&str, u64> = TableDefinition::new("nft_transfers_seen");
+
+/// Audit events: `timestamp_ms_be | event_id` → JSON AuditEvent. A cache
+/// of the daily JSONL audit archive, ordered by time.
+const AUDIT_EVENTS: TableDefinition<&[u8], &[u8]> = TableDefinition::new("audit_events");
+
+/// Audit filter index: `field \0 value \0 timestamp_ms_be | event_id` → ().
+/// Fields are `user`, `type`, `resource_type` and `resource_id`; the key
+/// ends with the event's `audit_events` key.
+const AUDIT_INDEX: TableDefinition<&[u8], ()> = TableDefinition::new("audit_index");
+
+/// Audit archive progress: `YYYY-MM-DD` → JSONL lines indexed, or
+/// [`AUDIT_DAY_SEALED`] once the day is over and fully indexed.
+const AUDIT_DAYS: TableDefinition<&str, u64> = TableDefinition::new("audit_days");
+
+/// `audit_days` value of a day whose archive will not change any more.
+const AUDIT_DAY_SEALED: u64 = u64::MAX;
+
 /// Email lookup: HMAC(node_secret, SHA-256(email)) → JSON { wallet_id, public_address }.
 const EMAIL_LOOKUP: TableDefinition<&str, &str> = TableDefinition::new("email_lookup");
 
@@ -76,6 +114,9 @@ pub enum TxDbError {
 
     #[error("not found: {0}")]
     NotFound(String),
+
+    #[error("invalid cursor: {0}")]
+    InvalidCursor(String),
 }
 
 pub type TxDbResult<T> = Result<T, TxDbError>;
@@ -125,6 +166,77 @@ fn make_prefix_end(wallet_address: &str) -> Vec<u8> {
 // TxDatabase
 // =============================================================================
 
+/// A transaction was stored or its status changed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TxStatusChange {
+    pub tx_hash: String,
+    pub status: TxStatus,
+}
+
+/// Tokens of one NFT held by a wallet, as built by the indexer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NftHolding {
+    pub network: String,
+    /// Lowercase contract address.
+    pub contract: String,
+    /// Decimal token ID.
+    pub token_id: String,
+    pub standard: NftStandard,
+    /// Decimal number of tokens held; always 1 for ERC-721.
+    pub amount: String,
+    /// Transaction that last changed the holding.
+    pub last_tx_hash: String,
+    pub last_block: u64,
+}
+
+/// How far a day of the audit archive has been indexed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuditDayState {
+    /// The first `lines` lines of the day's JSONL file are indexed.
+    Indexed { lines: u64 },
+    /// The day is over and its file fully indexed.
+    Sealed,
+}
+
+/// `audit_events` key: millisecond timestamp (big-endian) then event ID.
+fn audit_event_key(event: &AuditEvent) -> Vec<u8> {
+    let millis = event.timestamp.timestamp_millis().max(0) as u64;
+    let mut key = Vec::with_capacity(8 + 1 + event.event_id.len());
+    key.extend_from_slice(&millis.to_be_bytes());
+    key.push(b'|');
+    key.extend_from_slice(event.event_id.as_bytes());
+    key
+}
+
+/// `audit_index` prefix of all events whose `field` equals `value`.
+fn audit_index_prefix(field: &str, value: &str) -> Vec<u8> {
+    let mut prefix = Vec::with_capacity(field.len() + value.len() + 2);
+    prefix.extend_from_slice(field.as_bytes());
+    prefix.push(0);
+    prefix.extend_from_slice(value.as_bytes());
+    prefix.push(0);
+    prefix
+}
+
+/// The `(field, value)` index entries of an event.
+fn audit_index_fields(event: &AuditEvent) -> Vec<(&'static str, String)> {
+    let mut fields = vec![("type", event.event_type.name())];
+    if let Some(user_id) = &event.user_id {
+        fields.push(("user", user_id.clone()));
+    }
+    if let Some(resource_type) = &event.resource_type {
+        fields.push(("resource_type", resource_type.clone()));
+    }
+    if let Some(resource_id) = &event.resource_id {
+        fields.push(("resource_id", resource_id.clone()));
+    }
+    fields
+}
+
+fn nft_holding_key(wallet_id: &str, network: &str, contract: &str, token_id: &str) -> String {
+    format!("{wallet_id}|{network}|{contract}|{token_id}")
+}
+
 /// Embedded ACID transaction database.
 pub struct TxDatabase {
     db: Database,
@@ -169,9 +281,14 @@ impl TxDatabase {
             let _ = write_txn.open_table(WALLET_TX_INDEX)?;
             let _ = write_txn.open_table(ADDRESS_WALLET_MAP)?;
             let _ = write_txn.open_table(INDEXER_STATE)?;
+            let _ = write_txn.open_table(NFT_HOLDINGS)?;
+            let _ = write_txn.open_table(NFT_TRANSFERS_SEEN)?;
             let _ = write_txn.open_table(EMAIL_LOOKUP)?;
             let _ = write_txn.open_table(USER_WALLET_MAP)?;
             let _ = write_txn.open_table(PAYMENT_LINKS)?;
+            let _ = write_txn.open_table(AUDIT_EVENTS)?;
+            let _ = write_txn.open_table(AUDIT_INDEX)?;
+            let _ = write_txn.open_table(AUDIT_DAYS)?;
             // Phase 2 discovery: always create table for schema compatibility
             let _ = write_txn.open_table(VOPRF_TOKENS)?;
         }
@@ -180,6 +297,13 @@ impl TxDatabase {
         Ok(Self { db })
     }
 
+    fn publish(&self, tx_hash: &str, status: TxStatus) {
+        EventBus::global().publish(DomainEvent::TxStatusChanged(TxStatusChange {
+            tx_hash: tx_hash.to_string(),
+            status,
+        }));
+    }
+
     // =========================================================================
     // Transaction CRUD
     // =========================================================================
@@ -208,6 +332,7 @@ impl TxDatabase {
             }
         }
         write_txn.commit()?;
+        self.publish(&tx.tx_hash, tx.status);
         Ok(())
     }
 
@@ -224,10 +349,28 @@ impl TxDatabase {
         }
     }
 
+    /// All stored transactions, in hash order.
+    pub fn list_all_transactions(&self) -> TxDbResult<Vec<StoredTransaction>> {
+        let read_txn = self.db.begin_read()?;
+        let table = read_txn.open_table(TRANSACTIONS)?;
+        let mut results = Vec::new();
+        for entry in table.iter()? {
+            let entry = entry?;
+            results.push(serde_json::from_slice(entry.1.value())?);
+        }
+        Ok(results)
+    }
+
     /// Paginated listing of transactions for a wallet address.
     ///
     /// Returns `(transactions_with_direction, next_cursor)`.
     /// Each item is `(StoredTransaction, direction_string)`.
+    ///
+    /// The cursor is the hex-encoded index key of the last item returned,
+    /// which orders the wallet's transactions by creation time; the next
+    /// page starts after it. `next_cursor` is only set when more
+    /// transactions follow. A cursor that does not belong to
+    /// `wallet_address` is rejected with [`TxDbError::InvalidCursor`].
     #[allow(clippy::type_complexity)]
     pub fn list_by_wallet(
         &self,
@@ -242,47 +385,50 @@ impl TxDatabase {
         let prefix = make_prefix(wallet_address);
         let prefix_end = make_prefix_end(wallet_address);
 
-        // Determine scan start: either after cursor or from prefix start
-        let start: Vec<u8> = if let Some(cursor_str) = cursor {
-            // Cursor is base64(last_index_key) — decode it
-            decode_cursor(cursor_str).unwrap_or_else(|| prefix.clone())
-        } else {
-            prefix.clone()
-        };
-
-        let mut results = Vec::with_capacity(limit + 1);
-        let range = idx_table.range(start.as_slice()..prefix_end.as_slice())?;
-
-        let mut skip_first = cursor.is_some();
+        // Determine scan start: either the cursor key or the prefix start
+        let after: Option<Vec<u8>> = cursor
+            .map(|cursor_str| {
+                decode_cursor(cursor_str)
+                    .filter(|key| {
+                        key.starts_with(&prefix) && key.as_slice() < prefix_end.as_slice()
+                    })
+                    .ok_or_else(|| TxDbError::InvalidCursor(cursor_str.to_string()))
+            })
+            .transpose()?;
+        let start = after.clone().unwrap_or_else(|| prefix.clone());
+
+        let mut results = Vec::with_capacity(limit);
         let mut last_key: Option<Vec<u8>> = None;
+        let mut has_more = false;
+        let range = idx_table.range(start.as_slice()..prefix_end.as_slice())?;
 
         for entry in range {
             let entry = entry?;
             let key_bytes = entry.0.value().to_vec();
-            let direction = entry.1.value().to_string();
 
             // Skip the cursor entry itself
-            if skip_first {
-                skip_first = false;
+            if after.as_deref() == Some(key_bytes.as_slice()) {
                 continue;
             }
 
             // Extract tx_hash from the composite key
-            if let Some(tx_hash) = extract_tx_hash_from_key(&key_bytes) {
-                if let Some(value) = tx_table.get(tx_hash.as_str())? {
-                    let tx: StoredTransaction = serde_json::from_slice(value.value())?;
-                    results.push((tx, direction));
-                    last_key = Some(key_bytes);
-                }
-            }
-
+            let Some(tx_hash) = extract_tx_hash_from_key(&key_bytes) else {
+                continue;
+            };
+            let Some(value) = tx_table.get(tx_hash.as_str())? else {
+                continue;
+            };
             if results.len() >= limit {
+                has_more = true;
                 break;
             }
+            let tx: StoredTransaction = serde_json::from_slice(value.value())?;
+            results.push((tx, entry.1.value().to_string()));
+            last_key = Some(key_bytes);
         }
 
-        // Build next cursor if we hit the limit
-        let next_cursor = if results.len() >= limit {
+        // Build next cursor only if another transaction follows
+        let next_cursor = if has_more {
             last_key.map(|k| encode_cursor(&k))
         } else {
             None
@@ -336,9 +482,110 @@ impl TxDatabase {
             table.insert(tx_hash, json.as_slice())?;
         }
         write_txn.commit()?;
+        self.publish(tx_hash, status);
         Ok(())
     }
 
+    /// Link a stored transaction to the fiat request it settled. Returns
+    /// `false` if the transaction is not stored.
+    pub fn set_related_fiat_request(&self, tx_hash: &str, request_id: &str) -> TxDbResult<bool> {
+        let write_txn = self.db.begin_write()?;
+        {
+            let mut table = write_txn.open_table(TRANSACTIONS)?;
+            let existing_bytes = match table.get(tx_hash)? {
+                Some(existing) => existing.value().to_vec(),
+                None => return Ok(false),
+            };
+            let mut tx: StoredTransaction = serde_json::from_slice(&existing_bytes)?;
+            if tx.related_fiat_request_id.as_deref() == Some(request_id) {
+                return Ok(true);
+            }
+            tx.related_fiat_request_id = Some(request_id.to_string());
+            let json = serde_json::to_vec(&tx)?;
+            table.insert(tx_hash, json.as_slice())?;
+        }
+        write_txn.commit()?;
+        Ok(true)
+    }
+
+    /// Record that a pending transaction was replaced by `replacement` (a
+    /// speed-up or cancel with the same nonce) and mark it failed, since at
+    /// most one of the two can be mined. Returns `false` if the transaction
+    /// is not stored.
+    pub fn mark_replaced(&self, tx_hash: &str, replacement: &str) -> TxDbResult<bool> {
+        let write_txn = self.db.begin_write()?;
+        {
+            let mut table = write_txn.open_table(TRANSACTIONS)?;
+            let existing_bytes = match table.get(tx_hash)? {
+                Some(existing) => existing.value().to_vec(),
+                None => return Ok(false),
+            };
+            let mut tx: StoredTransaction = serde_json::from_slice(&existing_bytes)?;
+            tx.replaced_by = Some(replacement.to_string());
+            tx.mark_failed();
+            let json = serde_json::to_vec(&tx)?;
+            table.insert(tx_hash, json.as_slice())?;
+        }
+        write_txn.commit()?;
+        self.publish(tx_hash, TxStatus::Failed);
+        Ok(true)
+    }
+
+    /// Remove a transaction and its index entries for its sender and
+    /// recipient. Returns whether it existed.
+    pub fn delete_transaction(&self, tx_hash: &str) -> TxDbResult<bool> {
+        let write_txn = self.db.begin_write()?;
+        let existed = {
+            let mut tx_table = write_txn.open_table(TRANSACTIONS)?;
+            let removed = tx_table.remove(tx_hash)?.map(|v| v.value().to_vec());
+            let Some(bytes) = removed else {
+                return Ok(false);
+            };
+            let tx: StoredTransaction = serde_json::from_slice(&bytes)?;
+
+            // Index keys carry the timestamp of whichever write created
+            // them, so match on the hash rather than rebuilding the key.
+            let mut idx_table = write_txn.open_table(WALLET_TX_INDEX)?;
+            let mut stale = Vec::new();
+            for address in [&tx.from, &tx.to] {
+                let prefix = make_prefix(address);
+                let prefix_end = make_prefix_end(address);
+                for entry in idx_table.range(prefix.as_slice()..prefix_end.as_slice())? {
+                    let key = entry?.0.value().to_vec();
+                    if extract_tx_hash_from_key(&key).as_deref() == Some(tx_hash) {
+                        stale.push(key);
+                    }
+                }
+            }
+            for key in stale {
+                idx_table.remove(key.as_slice())?;
+            }
+            true
+        };
+        write_txn.commit()?;
+        Ok(existed)
+    }
+
+    /// Transactions still pending that were created before `cutoff`, oldest
+    /// first. Scans the whole table; meant for admin diagnostics.
+    pub fn list_pending_before(
+        &self,
+        cutoff: chrono::DateTime<chrono::Utc>,
+    ) -> TxDbResult<Vec<StoredTransaction>> {
+        let read_txn = self.db.begin_read()?;
+        let table = read_txn.open_table(TRANSACTIONS)?;
+        let mut pending = Vec::new();
+        for entry in table.iter()? {
+            let entry = entry?;
+            let tx: StoredTransaction = serde_json::from_slice(entry.1.value())?;
+            if tx.status == TxStatus::Pending && tx.created_at < cutoff {
+                pending.push(tx);
+            }
+        }
+        pending.sort_by_key(|tx| tx.created_at);
+        Ok(pending)
+    }
+
     // =========================================================================
     // Address ↔ Wallet mapping
     // =========================================================================
@@ -366,6 +613,33 @@ impl TxDatabase {
         }
     }
 
+    /// All address→wallet mappings, ordered by address.
+    pub fn list_address_map(&self) -> TxDbResult<Vec<(String, String)>> {
+        let read_txn = self.db.begin_read()?;
+        let table = read_txn.open_table(ADDRESS_WALLET_MAP)?;
+        let mut entries = Vec::new();
+        for entry in table.iter()? {
+            let (address, wallet_id) = entry?;
+            entries.push((address.value().to_string(), wallet_id.value().to_string()));
+        }
+        Ok(entries)
+    }
+
+    /// Every wallet index entry as `(address, tx_hash, direction)`, whether
+    /// or not its transaction still exists. Meant for admin diagnostics.
+    pub fn list_index_entries(&self) -> TxDbResult<Vec<(String, String, String)>> {
+        let read_txn = self.db.begin_read()?;
+        let table = read_txn.open_table(WALLET_TX_INDEX)?;
+        let mut entries = Vec::new();
+        for entry in table.iter()? {
+            let (key, direction) = entry?;
+            if let Some((address, tx_hash)) = split_index_key(key.value()) {
+                entries.push((address, tx_hash, direction.value().to_string()));
+            }
+        }
+        Ok(entries)
+    }
+
     /// Remove an address→wallet mapping (used on wallet deletion).
     pub fn remove_wallet_address(&self, address: &str) -> TxDbResult<()> {
         let addr = address.to_lowercase();
@@ -378,6 +652,98 @@ impl TxDatabase {
         Ok(())
     }
 
+    // =========================================================================
+    // NFT holdings
+    // =========================================================================
+
+    /// Apply one NFT transfer to the holdings of the wallets on either side.
+    ///
+    /// Returns `false` without changes if the log was already applied.
+    /// Addresses without a wallet are ignored; a sender's holding never
+    /// drops below zero, since the indexer may have started after it was
+    /// received.
+    pub fn record_nft_transfer(
+        &self,
+        network: &str,
+        contract: &str,
+        transfer: &NftTransfer,
+        tx_hash: &str,
+        log_index: u64,
+        block: u64,
+    ) -> TxDbResult<bool> {
+        let contract = contract.to_lowercase();
+        let token_id = transfer.token_id.to_string();
+        let seen_key = format!("{network}|{}|{log_index}", tx_hash.to_lowercase());
+        let write_txn = self.db.begin_write()?;
+        {
+            let mut seen = write_txn.open_table(NFT_TRANSFERS_SEEN)?;
+            if seen.get(seen_key.as_str())?.is_some() {
+                return Ok(false);
+            }
+            seen.insert(seen_key.as_str(), block)?;
+
+            let wallets = write_txn.open_table(ADDRESS_WALLET_MAP)?;
+            let sender = wallets
+                .get(transfer.from.as_str())?
+                .map(|v| v.value().to_string());
+            let receiver = wallets
+                .get(transfer.to.as_str())?
+                .map(|v| v.value().to_string());
+
+            let mut holdings = write_txn.open_table(NFT_HOLDINGS)?;
+            for (wallet_id, incoming) in [(sender, false), (receiver, true)] {
+                let Some(wallet_id) = wallet_id else {
+                    continue;
+                };
+                let key = nft_holding_key(&wallet_id, network, &contract, &token_id);
+                let held = match holdings.get(key.as_str())? {
+                    Some(v) => {
+                        let holding: NftHolding = serde_json::from_slice(v.value())?;
+                        U256::from_str_radix(&holding.amount, 10).unwrap_or_default()
+                    }
+                    None => U256::ZERO,
+                };
+                let amount = if incoming {
+                    held.saturating_add(transfer.amount)
+                } else {
+                    held.saturating_sub(transfer.amount)
+                };
+                if amount.is_zero() {
+                    holdings.remove(key.as_str())?;
+                    continue;
+                }
+                let holding = NftHolding {
+                    network: network.to_string(),
+                    contract: contract.clone(),
+                    token_id: token_id.clone(),
+                    standard: transfer.standard,
+                    amount: amount.to_string(),
+                    last_tx_hash: tx_hash.to_string(),
+                    last_block: block,
+                };
+                holdings.insert(key.as_str(), serde_json::to_vec(&holding)?.as_slice())?;
+            }
+        }
+        write_txn.commit()?;
+        Ok(true)
+    }
+
+    /// NFTs held by a wallet, ordered by network, contract and token ID.
+    pub fn list_nft_holdings(&self, wallet_id: &str) -> TxDbResult<Vec<NftHolding>> {
+        let prefix = format!("{wallet_id}|");
+        let read_txn = self.db.begin_read()?;
+        let table = read_txn.open_table(NFT_HOLDINGS)?;
+        let mut holdings = Vec::new();
+        for entry in table.range(prefix.as_str()..)? {
+            let (key, value) = entry?;
+            if !key.value().starts_with(&prefix) {
+                break;
+            }
+            holdings.push(serde_json::from_slice(value.value())?);
+        }
+        Ok(holdings)
+    }
+
     // =========================================================================
     // Indexer checkpoint
     // =========================================================================
@@ -587,6 +953,152 @@ impl TxDatabase {
         Ok(results)
     }
 
+    // =========================================================================
+    // Audit index
+    // =========================================================================
+
+    /// How far the audit archive of `date` (`YYYY-MM-DD`) is indexed.
+    pub fn audit_day_state(&self, date: &str) -> TxDbResult<AuditDayState> {
+        let read_txn = self.db.begin_read()?;
+        let table = read_txn.open_table(AUDIT_DAYS)?;
+        Ok(match table.get(date)?.map(|v| v.value()) {
+            Some(AUDIT_DAY_SEALED) => AuditDayState::Sealed,
+            Some(lines) => AuditDayState::Indexed { lines },
+            None => AuditDayState::Indexed { lines: 0 },
+        })
+    }
+
+    /// Index audit events read from the archive of `date` and record the
+    /// day's progress, in one write transaction. Re-indexing an event is a
+    /// no-op.
+    pub fn index_audit_events(
+        &self,
+        date: &str,
+        events: &[AuditEvent],
+        state: AuditDayState,
+    ) -> TxDbResult<()> {
+        let write_txn = self.db.begin_write()?;
+        {
+            let mut event_table = write_txn.open_table(AUDIT_EVENTS)?;
+            let mut index_table = write_txn.open_table(AUDIT_INDEX)?;
+            for event in events {
+                let key = audit_event_key(event);
+                event_table.insert(key.as_slice(), serde_json::to_vec(event)?.as_slice())?;
+                for (field, value) in audit_index_fields(event) {
+                    let mut index_key = audit_index_prefix(field, &value);
+                    index_key.extend_from_slice(&key);
+                    index_table.insert(index_key.as_slice(), ())?;
+                }
+            }
+            let mut days = write_txn.open_table(AUDIT_DAYS)?;
+            let value = match state {
+                AuditDayState::Indexed { lines } => lines,
+                AuditDayState::Sealed => AUDIT_DAY_SEALED,
+            };
+            days.insert(date, value)?;
+        }
+        write_txn.commit()?;
+        Ok(())
+    }
+
+    /// Indexed audit events matching `query`, oldest first.
+    ///
+    /// The most selective filter (resource ID, user, resource type, event
+    /// type) is range-scanned by time; the others are checked against the
+    /// index without reading events, so only the returned page is
+    /// deserialized. The cursor is the hex-encoded key of the last event
+    /// returned; `offset` skips further matches after it. `total` counts
+    /// all matches in the time range.
+    pub fn query_audit_events(&self, query: &AuditQuery) -> TxDbResult<AuditPage> {
+        let read_txn = self.db.begin_read()?;
+        let event_table = read_txn.open_table(AUDIT_EVENTS)?;
+        let index_table = read_txn.open_table(AUDIT_INDEX)?;
+
+        let mut filters: Vec<Vec<u8>> = [
+            ("resource_id", &query.resource_id),
+            ("user", &query.user_id),
+            ("resource_type", &query.resource_type),
+            ("type", &query.event_type),
+        ]
+        .into_iter()
+        .filter_map(|(field, value)| value.as_deref().map(|v| audit_index_prefix(field, v)))
+        .collect();
+        let scan_prefix = if filters.is_empty() {
+            Vec::new()
+        } else {
+            filters.remove(0)
+        };
+
+        let bound = |time: chrono::DateTime<chrono::Utc>| {
+            let mut key = scan_prefix.clone();
+            key.extend_from_slice(&(time.timestamp_millis().max(0) as u64).to_be_bytes());
+            key
+        };
+        let (start, end) = (bound(query.from), bound(query.to));
+        let after: Option<Vec<u8>> = query
+            .cursor
+            .as_deref()
+            .map(|cursor| {
+                decode_cursor(cursor)
+                    .filter(|key| key.len() > 9 && key[8] == b'|')
+                    .ok_or_else(|| TxDbError::InvalidCursor(cursor.to_string()))
+            })
+            .transpose()?;
+
+        let mut total = 0;
+        let mut skipped = 0;
+        let mut keys: Vec<Vec<u8>> = Vec::with_capacity(query.limit);
+        let mut has_more = false;
+        let mut scan = |event_key: &[u8]| -> TxDbResult<()> {
+            for prefix in &filters {
+                let mut index_key = prefix.clone();
+                index_key.extend_from_slice(event_key);
+                if index_table.get(index_key.as_slice())?.is_none() {
+                    return Ok(());
+                }
+            }
+            total += 1;
+            if after.as_deref().is_some_and(|after| event_key <= after) {
+                return Ok(());
+            }
+            if skipped < query.offset {
+                skipped += 1;
+            } else if keys.len() < query.limit {
+                keys.push(event_key.to_vec());
+            } else {
+                has_more = true;
+            }
+            Ok(())
+        };
+        if scan_prefix.is_empty() {
+            for entry in event_table.range(start.as_slice()..end.as_slice())? {
+                scan(entry?.0.value())?;
+            }
+        } else {
+            for entry in index_table.range(start.as_slice()..end.as_slice())? {
+                let entry = entry?;
+                scan(&entry.0.value()[scan_prefix.len()..])?;
+            }
+        }
+
+        let mut events = Vec::with_capacity(keys.len());
+        for key in &keys {
+            if let Some(value) = event_table.get(key.as_slice())? {
+                events.push(serde_json::from_slice(value.value())?);
+            }
+        }
+        let next_cursor = if has_more {
+            keys.last().map(|key| encode_cursor(key))
+        } else {
+            None
+        };
+        Ok(AuditPage {
+            events,
+            total,
+            next_cursor,
+        })
+    }
+
     // =========================================================================
     // VOPRF Token Store (Phase 2 Discovery)
     // =========================================================================
@@ -637,21 +1149,25 @@ fn decode_cursor(cursor: &str) -> Option<Vec<u8>> {
     alloy::hex::decode(cursor).ok()
 }
 
-/// Extract the tx_hash portion from a composite index key.
+/// Split a composite index key into its address and tx_hash.
 ///
-/// Key format: `address|timestamp_bytes|tx_hash`
-fn extract_tx_hash_from_key(key: &[u8]) -> Option<String> {
-    // Find the second '|' separator
-    let mut pipe_count = 0;
-    for (i, &b) in key.iter().enumerate() {
-        if b == b'|' {
-            pipe_count += 1;
-            if pipe_count == 2 {
-                return String::from_utf8(key[i + 1..].to_vec()).ok();
-            }
-        }
+/// Key format: `address|timestamp_bytes|tx_hash`. The timestamp is a fixed
+/// 8 bytes and may itself contain `|`, so only the first separator is
+/// searched for.
+fn split_index_key(key: &[u8]) -> Option<(String, String)> {
+    let sep = key.iter().position(|&b| b == b'|')?;
+    let hash_start = sep + 1 + 8 + 1;
+    if key.len() <= hash_start || key[hash_start - 1] != b'|' {
+        return None;
     }
-    None
+    let address = String::from_utf8(key[..sep].to_vec()).ok()?;
+    let tx_hash = String::from_utf8(key[hash_start..].to_vec()).ok()?;
+    Some((address, tx_hash))
+}
+
+/// Extract the tx_hash portion from a composite index key.
+fn extract_tx_hash_from_key(key: &[u8]) -> Option<String> {
+    split_index_key(key).map(|(_, tx_hash)| tx_hash)
 }
 
 // =============================================================================
@@ -699,6 +1215,107 @@ mod tests {
         assert_eq!(retrieved.amount, "10.0");
     }
 
+    #[test]
+    fn delete_transaction_removes_record_and_index_entries() {
+        let (db, _dir) = temp_db();
+        let tx = sample_tx("0xdel");
+        let dirs = vec![(tx.from.clone(), "sent"), (tx.to.clone(), "received")];
+        db.upsert_transaction(&tx, &dirs).unwrap();
+        db.upsert_transaction(&sample_tx("0xkeep"), &dirs).unwrap();
+
+        assert!(db.delete_transaction("0xdel").unwrap());
+        assert!(!db.delete_transaction("0xdel").unwrap());
+        assert!(db.get_transaction("0xdel").unwrap().is_none());
+        for address in [&tx.from, &tx.to] {
+            let (listed, _) = db.list_by_wallet(address, None, 10).unwrap();
+            let hashes: Vec<_> = listed.iter().map(|(t, _)| t.tx_hash.as_str()).collect();
+            assert_eq!(hashes, ["0xkeep"]);
+        }
+    }
+
+    #[test]
+    fn fiat_request_link_survives_status_updates() {
+        let (db, _dir) = temp_db();
+        let dirs = vec![(
+            "0x1111111111111111111111111111111111111111".to_string(),
+            "sent",
+        )];
+        db.upsert_transaction(&sample_tx("0xfiat"), &dirs).unwrap();
+
+        assert!(db.set_related_fiat_request("0xfiat", "req-1").unwrap());
+        assert!(!db.set_related_fiat_request("0xmissing", "req-1").unwrap());
+        db.update_status("0xfiat", TxStatus::Confirmed, Some(7), Some(21_000))
+            .unwrap();
+
+        let stored = db.get_transaction("0xfiat").unwrap().unwrap();
+        assert_eq!(stored.related_fiat_request_id.as_deref(), Some("req-1"));
+        assert_eq!(stored.status, TxStatus::Confirmed);
+    }
+
+    #[test]
+    fn replaced_transactions_are_linked_and_failed() {
+        let (db, _dir) = temp_db();
+        let dirs = vec![(
+            "0x1111111111111111111111111111111111111111".to_string(),
+            "sent",
+        )];
+        db.upsert_transaction(&sample_tx("0xslow"), &dirs).unwrap();
+
+        assert!(db.mark_replaced("0xslow", "0xfast").unwrap());
+        assert!(!db.mark_replaced("0xmissing", "0xfast").unwrap());
+
+        let stored = db.get_transaction("0xslow").unwrap().unwrap();
+        assert_eq!(stored.replaced_by.as_deref(), Some("0xfast"));
+        assert_eq!(stored.status, TxStatus::Failed);
+    }
+
+    #[test]
+    fn writes_are_published_to_subscribers() {
+        let (db, _dir) = temp_db();
+        // The bus is shared with tests running in parallel.
+        let mut events = EventBus::global().subscribe();
+        let dirs = vec![(
+            "0x1111111111111111111111111111111111111111".to_string(),
+            "sent",
+        )];
+        db.upsert_transaction(&sample_tx("0xpublished"), &dirs)
+            .unwrap();
+        db.update_status("0xpublished", TxStatus::Failed, None, None)
+            .unwrap();
+
+        let mut statuses = Vec::new();
+        while let Ok(event) = events.try_recv() {
+            if let DomainEvent::TxStatusChanged(change) = event {
+                if change.tx_hash == "0xpublished" {
+                    statuses.push(change.status);
+                }
+            }
+        }
+        assert_eq!(statuses, vec![TxStatus::Pending, TxStatus::Failed]);
+    }
+
+    #[test]
+    fn list_pending_before_skips_recent_and_settled() {
+        let (db, _dir) = temp_db();
+        let dirs = vec![(
+            "0x1111111111111111111111111111111111111111".to_string(),
+            "sent",
+        )];
+        for (hash, age_secs) in [("0xold", 900), ("0xnew", 10), ("0xdone", 900)] {
+            let mut tx = sample_tx(hash);
+            tx.created_at = Utc::now() - chrono::Duration::seconds(age_secs);
+            db.upsert_transaction(&tx, &dirs).unwrap();
+        }
+        db.update_status("0xdone", TxStatus::Confirmed, Some(1), Some(21_000))
+            .unwrap();
+
+        let stuck = db
+            .list_pending_before(Utc::now() - chrono::Duration::seconds(600))
+            .unwrap();
+        assert_eq!(stuck.len(), 1);
+        assert_eq!(stuck[0].tx_hash, "0xold");
+    }
+
     #[test]
     fn list_by_wallet_with_pagination() {
         let (db, _dir) = temp_db();
@@ -726,6 +1343,37 @@ mod tests {
         let (page3, cursor3) = db.list_by_wallet(addr, cursor2.as_deref(), 2).unwrap();
         assert_eq!(page3.len(), 1);
         assert!(cursor3.is_none());
+
+        // An exact last page has no next cursor
+        let (all, cursor) = db.list_by_wallet(addr, None, 5).unwrap();
+        assert_eq!(all.len(), 5);
+        assert!(cursor.is_none());
+    }
+
+    #[test]
+    fn list_by_wallet_rejects_foreign_cursors() {
+        let (db, _dir) = temp_db();
+        let alice = "0x1111111111111111111111111111111111111111";
+        let bob = "0x2222222222222222222222222222222222222222";
+        for (i, addr) in [alice, alice, bob].into_iter().enumerate() {
+            let tx = sample_tx(&format!("0x{:04}", i));
+            db.upsert_transaction(&tx, &[(addr.to_string(), "sent")])
+                .unwrap();
+        }
+
+        let (_, cursor) = db.list_by_wallet(alice, None, 1).unwrap();
+        let cursor = cursor.unwrap();
+        assert!(matches!(
+            db.list_by_wallet(bob, Some(&cursor), 10),
+            Err(TxDbError::InvalidCursor(_))
+        ));
+        assert!(matches!(
+            db.list_by_wallet(alice, Some("not-hex"), 10),
+            Err(TxDbError::InvalidCursor(_))
+        ));
+        let (page, next) = db.list_by_wallet(alice, Some(&cursor), 10).unwrap();
+        assert_eq!(page.len(), 1);
+        assert!(next.is_none());
     }
 
     #[test]
@@ -759,6 +1407,51 @@ mod tests {
         // Case insensitive
         let result2 = db.get_wallet_id_for_address(&addr.to_lowercase()).unwrap();
         assert_eq!(result2, Some("wallet-42".to_string()));
+
+        assert_eq!(
+            db.list_address_map().unwrap(),
+            vec![(addr.to_lowercase(), "wallet-42".to_string())]
+        );
+        db.remove_wallet_address(addr).unwrap();
+        assert!(db.list_address_map().unwrap().is_empty());
+    }
+
+    #[test]
+    fn nft_transfers_move_holdings_once() {
+        let (db, _dir) = temp_db();
+        let alice = "0x1111111111111111111111111111111111111111";
+        let bob = "0x2222222222222222222222222222222222222222";
+        db.register_address(alice, "wallet-a").unwrap();
+        db.register_address(bob, "wallet-b").unwrap();
+        let contract = "0x3333333333333333333333333333333333333333";
+        let transfer = |from: &str, to: &str, amount: u64| NftTransfer {
+            standard: NftStandard::Erc1155,
+            from: from.to_string(),
+            to: to.to_string(),
+            token_id: U256::from(42),
+            amount: U256::from(amount),
+        };
+
+        let mint = transfer("0x0000000000000000000000000000000000000000", alice, 3);
+        assert!(db
+            .record_nft_transfer("fuji", contract, &mint, "0xmint", 0, 10)
+            .unwrap());
+        assert!(!db
+            .record_nft_transfer("fuji", contract, &mint, "0xmint", 0, 10)
+            .unwrap());
+        db.record_nft_transfer("fuji", contract, &transfer(alice, bob, 1), "0xsend", 1, 11)
+            .unwrap();
+
+        let held = db.list_nft_holdings("wallet-a").unwrap();
+        assert_eq!(held.len(), 1);
+        assert_eq!(held[0].amount, "2");
+        assert_eq!(held[0].token_id, "42");
+        assert_eq!(db.list_nft_holdings("wallet-b").unwrap()[0].amount, "1");
+
+        db.record_nft_transfer("fuji", contract, &transfer(alice, bob, 2), "0xsend2", 0, 12)
+            .unwrap();
+        assert!(db.list_nft_holdings("wallet-a").unwrap().is_empty());
+        assert_eq!(db.list_nft_holdings("wallet-b").unwrap()[0].amount, "3");
     }
 
     #[test]
@@ -857,4 +1550,101 @@ mod tests {
         db.remove_email_lookup(key).unwrap();
         assert!(!db.email_lookup_exists(key).unwrap());
     }
+
+    #[test]
+    fn index_keys_split_even_when_the_timestamp_holds_a_separator() {
+        let timestamp = !u64::from_be_bytes([0xff, 0xff, 0xff, 0xff, 0x7c, 0x7c, 0, 0]) as i64;
+        let key = make_index_key("0xABC", timestamp, "0xhash");
+        assert_eq!(
+            split_index_key(&key),
+            Some(("0xabc".to_string(), "0xhash".to_string()))
+        );
+        assert_eq!(split_index_key(b"0xabc|short"), None);
+
+        let (db, _dir) = temp_db();
+        let tx = sample_tx("0xidx");
+        db.upsert_transaction(&tx, &[(tx.from.clone(), "sent")])
+            .unwrap();
+        assert_eq!(
+            db.list_index_entries().unwrap(),
+            [(
+                tx.from.to_lowercase(),
+                "0xidx".to_string(),
+                "sent".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn audit_events_are_queried_by_filter_and_cursor() {
+        use super::super::audit::AuditEventType;
+
+        let (db, _dir) = temp_db();
+        let start = Utc::now() - chrono::Duration::hours(1);
+        let events: Vec<AuditEvent> = (0..5)
+            .map(|i| {
+                let mut event = AuditEvent::new(if i % 2 == 0 {
+                    AuditEventType::WalletCreated
+                } else {
+                    AuditEventType::WalletAccessed
+                })
+                .with_user(if i < 4 { "user_a" } else { "user_b" })
+                .with_resource("wallet", format!("w{i}"));
+                event.timestamp = start + chrono::Duration::minutes(i);
+                event
+            })
+            .collect();
+        db.index_audit_events("2026-01-01", &events, AuditDayState::Indexed { lines: 5 })
+            .unwrap();
+        // Re-indexing is a no-op.
+        db.index_audit_events("2026-01-01", &events[..2], AuditDayState::Sealed)
+            .unwrap();
+        assert_eq!(
+            db.audit_day_state("2026-01-01").unwrap(),
+            AuditDayState::Sealed
+        );
+
+        let query = AuditQuery {
+            from: start,
+            to: Utc::now(),
+            limit: 10,
+            ..Default::default()
+        };
+        let all = db.query_audit_events(&query).unwrap();
+        assert_eq!(all.total, 5);
+        assert!(all.next_cursor.is_none());
+
+        let user_a = AuditQuery {
+            user_id: Some("user_a".to_string()),
+            event_type: Some("wallet_created".to_string()),
+            limit: 1,
+            ..query.clone()
+        };
+        let first = db.query_audit_events(&user_a).unwrap();
+        assert_eq!(first.total, 2);
+        assert_eq!(first.events[0].resource_id.as_deref(), Some("w0"));
+        let second = db
+            .query_audit_events(&AuditQuery {
+                cursor: first.next_cursor,
+                ..user_a.clone()
+            })
+            .unwrap();
+        assert_eq!(second.events[0].resource_id.as_deref(), Some("w2"));
+        assert!(second.next_cursor.is_none());
+
+        let later = db
+            .query_audit_events(&AuditQuery {
+                from: start + chrono::Duration::minutes(3),
+                ..query.clone()
+            })
+            .unwrap();
+        assert_eq!(later.total, 2);
+        assert!(matches!(
+            db.query_audit_events(&AuditQuery {
+                cursor: Some("zz".to_string()),
+                ..query
+            }),
+            Err(TxDbError::InvalidCursor(_))
+        ));
+    }
 }
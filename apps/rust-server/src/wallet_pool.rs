@@ -0,0 +1,124 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! # Wallet Pool Worker
+//!
+//! Background task that keeps a pool of pre-generated wallet keys so
+//! `create_wallet` can skip key generation on signup. Every
+//! [`REFILL_INTERVAL_SECS`] it tops the pool up to `WALLET_POOL_SIZE` keys
+//! (see [`WalletPoolRepository`]). The worker only runs when
+//! `WALLET_POOL_SIZE` is set; with an empty pool `create_wallet` generates
+//! the key itself as before.
+//!
+//! ## Shutdown
+//!
+//! Uses `tokio_util::sync::CancellationToken` for graceful shutdown, following
+//! the same pattern as the `FiatPoller`.
+
+use std::env;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::Utc;
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+
+use crate::storage::repository::service_wallet::generate_secp256k1_keypair;
+use crate::storage::{
+    EncryptedStorage, PooledKey, StorageError, StorageResult, WalletPoolRepository,
+};
+
+/// Interval between refills.
+pub const REFILL_INTERVAL_SECS: u64 = 30;
+
+/// Largest accepted `WALLET_POOL_SIZE`.
+const MAX_POOL_SIZE: usize = 10_000;
+
+/// Pool size from `WALLET_POOL_SIZE`; `None` when unset or zero.
+pub fn pool_size_from_env() -> Option<usize> {
+    env::var("WALLET_POOL_SIZE")
+        .ok()
+        .and_then(|v| v.trim().parse::<usize>().ok())
+        .filter(|size| *size > 0)
+        .map(|size| size.min(MAX_POOL_SIZE))
+}
+
+/// Generate keys until the pool holds `size`. Returns the number added.
+pub fn refill(storage: &EncryptedStorage, size: usize) -> StorageResult<usize> {
+    let repo = WalletPoolRepository::new(storage);
+    let missing = size.saturating_sub(repo.count()?);
+    for _ in 0..missing {
+        let (private_key_pem, key_address) = generate_secp256k1_keypair()
+            .map_err(|e| StorageError::SerializationError(format!("Key generation failed: {e}")))?;
+        repo.add(&PooledKey {
+            entry_id: uuid::Uuid::new_v4().to_string(),
+            private_key_pem,
+            key_address,
+            created_at: Utc::now(),
+        })?;
+    }
+    Ok(missing)
+}
+
+/// Background task refilling the wallet pool.
+pub struct WalletPoolWorker {
+    storage: Arc<EncryptedStorage>,
+    size: usize,
+}
+
+impl WalletPoolWorker {
+    /// Create a worker keeping `size` keys in the pool.
+    pub fn new(storage: Arc<EncryptedStorage>, size: usize) -> Self {
+        Self { storage, size }
+    }
+
+    /// Run the worker loop until the cancellation token is triggered.
+    pub async fn run(self, shutdown: CancellationToken) {
+        info!(size = self.size, "Wallet pool worker starting");
+
+        loop {
+            if shutdown.is_cancelled() {
+                info!("Wallet pool worker shutting down");
+                return;
+            }
+
+            match refill(&self.storage, self.size) {
+                Ok(0) => {}
+                Ok(added) => info!(added, "Wallet pool: generated keys"),
+                Err(e) => warn!(error = %e, "Wallet pool: refill failed"),
+            }
+            crate::worker_health::record_heartbeat(crate::worker_health::Worker::WalletPool);
+
+            tokio::select! {
+                _ = tokio::time::sleep(Duration::from_secs(REFILL_INTERVAL_SECS)) => {},
+                _ = shutdown.cancelled() => {
+                    info!("Wallet pool worker shutting down");
+                    return;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::StoragePaths;
+    use tempfile::TempDir;
+
+    #[test]
+    fn refill_tops_up_to_size() {
+        let dir = TempDir::new().unwrap();
+        let mut storage = EncryptedStorage::new(StoragePaths::new(dir.path()));
+        storage.initialize().unwrap();
+
+        assert_eq!(refill(&storage, 3).unwrap(), 3);
+        let taken = WalletPoolRepository::new(&storage).take().unwrap().unwrap();
+        assert!(taken.private_key_pem.contains("PRIVATE KEY"));
+        assert_eq!(taken.key_address.len(), 42);
+
+        assert_eq!(refill(&storage, 3).unwrap(), 1);
+        assert_eq!(refill(&storage, 3).unwrap(), 0);
+    }
+}
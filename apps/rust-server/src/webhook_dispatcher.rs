@@ -0,0 +1,585 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! # Webhook Dispatcher
+//!
+//! Background task delivering users' webhook subscriptions. It listens on
+//! the [domain event bus](crate::events) and turns events into
+//! [`WebhookEventType`]s for the owners of the wallets and fiat requests
+//! involved, then queues one delivery per subscription that wants the
+//! event. A wallet's deposit webhook only receives events of that wallet.
+//!
+//! Due deliveries are sent right after queuing and every
+//! [`DISPATCH_INTERVAL_SECS`]. Each one is a `POST` of the canonical JSON
+//! body, signed with the service's Ed25519 key and with the subscription's
+//! secret (see [`crate::webhooks`]). A 2xx answer delivers it; otherwise
+//! attempt `n` is retried after [`retry_delay`] (30 s doubling, capped at
+//! six hours) until [`MAX_ATTEMPTS`] have failed. Redirects are not
+//! followed.
+//!
+//! Deliveries are files, so queued retries survive a restart; events
+//! published while the dispatcher was not listening are not delivered.
+//! Finished deliveries stay in the log for [`RETAINED_DELIVERY_DAYS`].
+//!
+//! ## Shutdown
+//!
+//! Uses `tokio_util::sync::CancellationToken` for graceful shutdown, following
+//! the same pattern as the `FiatPoller`.
+
+use std::sync::Arc;
+use std::time::Duration as StdDuration;
+
+use chrono::{DateTime, Duration, Utc};
+use tokio::sync::broadcast::error::RecvError;
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::blockchain::{networks, AvaxClient};
+use crate::canonical_json;
+use crate::events::{DomainEvent, EventBus};
+use crate::secret_signer::SecretSigner;
+use crate::storage::{
+    EncryptedStorage, FiatRequestRepository, FiatRequestStatus, StorageError,
+    StoredWebhookDelivery, StoredWebhookSubscription, TxDatabase, TxStatus, WalletMetadata,
+    WalletRepository, WebhookDeliveryStatus, WebhookEventType, WebhookSubscriptionRepository,
+};
+use crate::webhooks::{self, HMAC_SIGNATURE_HEADER, SIGNATURE_HEADER};
+
+/// Interval between delivery runs.
+pub const DISPATCH_INTERVAL_SECS: u64 = 15;
+
+/// Attempts before a delivery fails for good.
+pub const MAX_ATTEMPTS: u32 = 8;
+
+/// Days finished deliveries are kept in the log.
+pub const RETAINED_DELIVERY_DAYS: i64 = 30;
+
+/// Delay before the first retry; doubled after every further failure.
+const BASE_RETRY_SECS: i64 = 30;
+
+/// Longest delay between two attempts.
+const MAX_RETRY_SECS: i64 = 6 * 3600;
+
+/// Time an endpoint has to answer.
+const DELIVERY_TIMEOUT_SECS: u64 = 10;
+
+/// Longest error message kept on a delivery.
+const MAX_ERROR_LEN: usize = 200;
+
+/// Header naming the delivery, for receivers to deduplicate retries.
+pub const DELIVERY_ID_HEADER: &str = "relational-webhook-id";
+
+/// Header naming the event type.
+pub const EVENT_TYPE_HEADER: &str = "relational-webhook-event";
+
+/// An event for one user's subscriptions.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WebhookEvent {
+    pub owner_user_id: String,
+    pub tenant_id: Option<String>,
+    /// Wallet the event is about, matched against wallet subscriptions.
+    pub wallet_id: Option<String>,
+    pub event_type: WebhookEventType,
+    pub data: serde_json::Value,
+}
+
+impl WebhookEvent {
+    fn for_wallet(
+        wallet: &WalletMetadata,
+        event_type: WebhookEventType,
+        data: serde_json::Value,
+    ) -> Self {
+        Self {
+            owner_user_id: wallet.owner_user_id.clone(),
+            tenant_id: wallet.tenant_id.clone(),
+            wallet_id: Some(wallet.wallet_id.clone()),
+            event_type,
+            data,
+        }
+    }
+
+    /// Whether `subscription` should receive this event.
+    pub fn is_for(&self, subscription: &StoredWebhookSubscription) -> bool {
+        subscription.owner_user_id == self.owner_user_id
+            && subscription.tenant_id == self.tenant_id
+            && subscription.wants(self.event_type)
+            && subscription
+                .wallet_id
+                .as_ref()
+                .is_none_or(|wallet_id| self.wallet_id.as_ref() == Some(wallet_id))
+    }
+}
+
+/// Webhook events caused by a domain event.
+///
+/// A confirmed transaction is `transaction.confirmed` for the sending
+/// wallet's owner and `deposit.indexed` for the receiving wallet's owner; a
+/// completed fiat request is `fiat.completed` for its owner. `head_block`
+/// is the latest block of the transaction's network, from which deposits
+/// report their confirmations.
+pub fn webhook_events(
+    storage: &EncryptedStorage,
+    tx_db: &TxDatabase,
+    event: &DomainEvent,
+    head_block: Option<u64>,
+) -> Vec<WebhookEvent> {
+    match event {
+        DomainEvent::TxStatusChanged(change) if change.status == TxStatus::Confirmed => {
+            let Ok(Some(tx)) = tx_db.get_transaction(&change.tx_hash) else {
+                return Vec::new();
+            };
+            let wallets = WalletRepository::new(storage);
+            let wallet_for = |address: &str| {
+                tx_db
+                    .get_wallet_id_for_address(address)
+                    .ok()
+                    .flatten()
+                    .and_then(|wallet_id| wallets.get(&wallet_id).ok())
+            };
+
+            let mut events = Vec::new();
+            if let Some(wallet) = wallet_for(&tx.from) {
+                events.push(WebhookEvent::for_wallet(
+                    &wallet,
+                    WebhookEventType::TransactionConfirmed,
+                    serde_json::json!({
+                        "wallet_id": wallet.wallet_id,
+                        "tx_hash": tx.tx_hash,
+                        "to": tx.to,
+                        "amount": tx.amount,
+                        "token": tx.token,
+                        "network": tx.network,
+                    }),
+                ));
+            }
+            if let Some(wallet) = wallet_for(&tx.to) {
+                events.push(WebhookEvent::for_wallet(
+                    &wallet,
+                    WebhookEventType::DepositIndexed,
+                    serde_json::json!({
+                        "wallet_id": wallet.wallet_id,
+                        "tx_hash": tx.tx_hash,
+                        "from": tx.from,
+                        "amount": tx.amount,
+                        "token": tx.token,
+                        "network": tx.network,
+                        "block_number": tx.block_number,
+                        "confirmations": confirmations(tx.block_number, head_block),
+                    }),
+                ));
+            }
+            events
+        }
+        DomainEvent::FiatStatusChanged(change) if change.status == FiatRequestStatus::Completed => {
+            let Ok(request) = FiatRequestRepository::new(storage).get(&change.request_id) else {
+                return Vec::new();
+            };
+            vec![WebhookEvent {
+                owner_user_id: request.owner_user_id,
+                tenant_id: request.tenant_id,
+                wallet_id: Some(request.wallet_id.clone()),
+                event_type: WebhookEventType::FiatCompleted,
+                data: serde_json::json!({
+                    "request_id": request.request_id,
+                    "wallet_id": request.wallet_id,
+                    "direction": request.direction,
+                    "amount_eur": request.amount_eur,
+                }),
+            }]
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Blocks on top of `block`, counting it, when `head` has reached it.
+pub fn confirmations(block: Option<u64>, head: Option<u64>) -> Option<u64> {
+    head?.checked_sub(block?).map(|behind| behind + 1)
+}
+
+/// Delay before the attempt following `attempts` failed ones.
+pub fn retry_delay(attempts: u32) -> Duration {
+    let doublings = attempts.saturating_sub(1).min(20);
+    Duration::seconds((BASE_RETRY_SECS << doublings).min(MAX_RETRY_SECS))
+}
+
+/// Record the outcome of an attempt: delivered without an error, otherwise
+/// scheduled for a retry or failed once [`MAX_ATTEMPTS`] are used up.
+pub fn record_attempt(
+    delivery: &mut StoredWebhookDelivery,
+    status_code: Option<u16>,
+    error: Option<String>,
+    now: DateTime<Utc>,
+) {
+    delivery.attempts += 1;
+    delivery.last_status_code = status_code;
+    delivery.updated_at = now;
+    delivery.next_attempt_at = None;
+    match error {
+        None => {
+            delivery.status = WebhookDeliveryStatus::Delivered;
+            delivery.last_error = None;
+        }
+        Some(error) => {
+            delivery.last_error = Some(error.chars().take(MAX_ERROR_LEN).collect());
+            if delivery.attempts >= MAX_ATTEMPTS {
+                delivery.status = WebhookDeliveryStatus::Failed;
+            } else {
+                delivery.next_attempt_at = Some(now + retry_delay(delivery.attempts));
+            }
+        }
+    }
+}
+
+/// A pending delivery of `event` to `subscription`.
+pub fn new_delivery(
+    subscription: &StoredWebhookSubscription,
+    event: &WebhookEvent,
+    now: DateTime<Utc>,
+) -> StoredWebhookDelivery {
+    let delivery_id = format!("whd_{}", Uuid::new_v4().simple());
+    StoredWebhookDelivery {
+        payload: serde_json::json!({
+            "id": delivery_id,
+            "event": event.event_type.name(),
+            "created_at": now,
+            "data": event.data,
+        }),
+        delivery_id,
+        subscription_id: subscription.subscription_id.clone(),
+        owner_user_id: subscription.owner_user_id.clone(),
+        event_type: event.event_type,
+        status: WebhookDeliveryStatus::Pending,
+        attempts: 0,
+        next_attempt_at: Some(now),
+        last_status_code: None,
+        last_error: None,
+        created_at: now,
+        updated_at: now,
+    }
+}
+
+/// Background task delivering webhook subscriptions.
+pub struct WebhookDispatcher {
+    storage: Arc<EncryptedStorage>,
+    tx_db: Arc<TxDatabase>,
+    signer: Arc<SecretSigner>,
+    events: EventBus,
+    http: reqwest::Client,
+}
+
+impl WebhookDispatcher {
+    /// Create a dispatcher listening on the process-wide event bus.
+    pub fn new(
+        storage: Arc<EncryptedStorage>,
+        tx_db: Arc<TxDatabase>,
+        signer: Arc<SecretSigner>,
+    ) -> Self {
+        let http = reqwest::Client::builder()
+            .timeout(StdDuration::from_secs(DELIVERY_TIMEOUT_SECS))
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .expect("Failed to build webhook HTTP client");
+        Self {
+            storage,
+            tx_db,
+            signer,
+            events: EventBus::global(),
+            http,
+        }
+    }
+
+    /// Run the dispatcher loop until the cancellation token is triggered.
+    pub async fn run(self, shutdown: CancellationToken) {
+        info!(
+            interval_secs = DISPATCH_INTERVAL_SECS,
+            "Webhook dispatcher starting"
+        );
+        let mut events = self.events.subscribe();
+        let mut interval = tokio::time::interval(StdDuration::from_secs(DISPATCH_INTERVAL_SECS));
+
+        loop {
+            tokio::select! {
+                _ = shutdown.cancelled() => {
+                    info!("Webhook dispatcher shutting down");
+                    return;
+                }
+                event = events.recv() => match event {
+                    Ok(event) => {
+                        if self.queue(&event).await > 0 {
+                            self.deliver_due().await;
+                        }
+                    }
+                    Err(RecvError::Lagged(skipped)) => {
+                        warn!(skipped, "Webhook dispatcher fell behind; events were not queued");
+                    }
+                    Err(RecvError::Closed) => return,
+                },
+                _ = interval.tick() => {
+                    self.deliver_due().await;
+                    self.prune(Utc::now());
+                    crate::worker_health::record_heartbeat(
+                        crate::worker_health::Worker::WebhookDispatcher,
+                    );
+                }
+            }
+        }
+    }
+
+    /// Queue deliveries for `event`; returns how many were queued.
+    async fn queue(&self, event: &DomainEvent) -> usize {
+        let repo = WebhookSubscriptionRepository::new(&self.storage);
+        let subscriptions = match repo.list_all() {
+            Ok(subscriptions) => subscriptions,
+            Err(e) => {
+                warn!(error = %e, "Webhook dispatcher: failed to list subscriptions");
+                return 0;
+            }
+        };
+        if subscriptions.is_empty() {
+            return 0;
+        }
+        let head_block = self.head_block(event).await;
+        let webhook_events = webhook_events(&self.storage, &self.tx_db, event, head_block);
+
+        let now = Utc::now();
+        let mut queued = 0;
+        for webhook_event in &webhook_events {
+            for subscription in subscriptions.iter().filter(|s| webhook_event.is_for(s)) {
+                let delivery = new_delivery(subscription, webhook_event, now);
+                match repo.save_delivery(&delivery) {
+                    Ok(()) => queued += 1,
+                    Err(e) => warn!(
+                        subscription_id = %subscription.subscription_id,
+                        error = %e,
+                        "Webhook dispatcher: failed to queue delivery"
+                    ),
+                }
+            }
+        }
+        queued
+    }
+
+    /// Latest block of the network a confirmed transaction is on.
+    async fn head_block(&self, event: &DomainEvent) -> Option<u64> {
+        let DomainEvent::TxStatusChanged(change) = event else {
+            return None;
+        };
+        if change.status != TxStatus::Confirmed {
+            return None;
+        }
+        let tx = self.tx_db.get_transaction(&change.tx_hash).ok()??;
+        let network = networks().get(&tx.network)?.clone();
+        let client = AvaxClient::new(network).await.ok()?;
+        client.get_block_number().await.ok()
+    }
+
+    /// Attempt every pending delivery that is due.
+    async fn deliver_due(&self) {
+        let repo = WebhookSubscriptionRepository::new(&self.storage);
+        let deliveries = match repo.list_deliveries() {
+            Ok(deliveries) => deliveries,
+            Err(e) => {
+                warn!(error = %e, "Webhook dispatcher: failed to list deliveries");
+                return;
+            }
+        };
+
+        let now = Utc::now();
+        // Oldest first.
+        for mut delivery in deliveries.into_iter().rev() {
+            let due = delivery.status == WebhookDeliveryStatus::Pending
+                && delivery.next_attempt_at.is_some_and(|at| at <= now);
+            if !due {
+                continue;
+            }
+            match repo.get(&delivery.subscription_id) {
+                Ok(subscription) => {
+                    let (status_code, error) = self.attempt(&subscription, &delivery).await;
+                    record_attempt(&mut delivery, status_code, error, Utc::now());
+                }
+                Err(StorageError::NotFound(_)) => {
+                    delivery.status = WebhookDeliveryStatus::Failed;
+                    delivery.next_attempt_at = None;
+                    delivery.last_error = Some("Subscription was deleted".to_string());
+                    delivery.updated_at = now;
+                }
+                Err(e) => {
+                    warn!(error = %e, "Webhook dispatcher: failed to load subscription");
+                    continue;
+                }
+            }
+            if delivery.status == WebhookDeliveryStatus::Failed {
+                warn!(
+                    delivery_id = %delivery.delivery_id,
+                    subscription_id = %delivery.subscription_id,
+                    attempts = delivery.attempts,
+                    "Webhook delivery failed"
+                );
+            }
+            if let Err(e) = repo.save_delivery(&delivery) {
+                warn!(delivery_id = %delivery.delivery_id, error = %e, "Webhook dispatcher: failed to save delivery");
+            }
+        }
+    }
+
+    /// Send one delivery; returns the answer's status code and the error,
+    /// if the attempt failed.
+    async fn attempt(
+        &self,
+        subscription: &StoredWebhookSubscription,
+        delivery: &StoredWebhookDelivery,
+    ) -> (Option<u16>, Option<String>) {
+        let body = match canonical_json::to_vec(&delivery.payload) {
+            Ok(body) => body,
+            Err(e) => return (None, Some(format!("Failed to encode body: {e}"))),
+        };
+        let timestamp = Utc::now().timestamp();
+        let signature =
+            match webhooks::sign_payload(&self.storage, &self.signer, &body, timestamp).await {
+                Ok(signature) => signature,
+                Err(e) => return (None, Some(format!("Failed to sign delivery: {e}"))),
+            };
+
+        let response = self
+            .http
+            .post(&subscription.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(SIGNATURE_HEADER, signature)
+            .header(
+                HMAC_SIGNATURE_HEADER,
+                webhooks::hmac_signature(&subscription.secret, &body, timestamp),
+            )
+            .header(DELIVERY_ID_HEADER, &delivery.delivery_id)
+            .header(EVENT_TYPE_HEADER, delivery.event_type.name())
+            .body(body)
+            .send()
+            .await;
+        match response {
+            Ok(response) if response.status().is_success() => {
+                (Some(response.status().as_u16()), None)
+            }
+            Ok(response) => (
+                Some(response.status().as_u16()),
+                Some(format!("Endpoint answered {}", response.status())),
+            ),
+            Err(e) => (None, Some(format!("Request failed: {e}"))),
+        }
+    }
+
+    /// Remove finished deliveries older than [`RETAINED_DELIVERY_DAYS`].
+    fn prune(&self, now: DateTime<Utc>) {
+        let repo = WebhookSubscriptionRepository::new(&self.storage);
+        let Ok(deliveries) = repo.list_deliveries() else {
+            return;
+        };
+        let cutoff = now - Duration::days(RETAINED_DELIVERY_DAYS);
+        for delivery in deliveries {
+            if delivery.status != WebhookDeliveryStatus::Pending && delivery.updated_at < cutoff {
+                let _ = repo.delete_delivery(&delivery.delivery_id);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn subscription(events: Vec<WebhookEventType>) -> StoredWebhookSubscription {
+        StoredWebhookSubscription {
+            subscription_id: "whs_1".to_string(),
+            owner_user_id: "user-1".to_string(),
+            tenant_id: None,
+            url: "https://hooks.example.com/relational".to_string(),
+            secret: "s3cret-s3cret-s3cret".to_string(),
+            events,
+            wallet_id: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    fn event(event_type: WebhookEventType) -> WebhookEvent {
+        WebhookEvent {
+            owner_user_id: "user-1".to_string(),
+            tenant_id: None,
+            wallet_id: Some("wallet-1".to_string()),
+            event_type,
+            data: serde_json::json!({ "request_id": "req-1" }),
+        }
+    }
+
+    #[test]
+    fn events_match_owner_tenant_and_subscribed_types() {
+        let all = subscription(Vec::new());
+        let fiat_only = subscription(vec![WebhookEventType::FiatCompleted]);
+        let deposit = event(WebhookEventType::DepositIndexed);
+
+        assert!(deposit.is_for(&all));
+        assert!(!deposit.is_for(&fiat_only));
+        assert!(event(WebhookEventType::FiatCompleted).is_for(&fiat_only));
+
+        let mut other_tenant = all.clone();
+        other_tenant.tenant_id = Some("org_acme".to_string());
+        assert!(!deposit.is_for(&other_tenant));
+        let mut other_owner = all.clone();
+        other_owner.owner_user_id = "user-2".to_string();
+        assert!(!deposit.is_for(&other_owner));
+
+        let mut merchant = all;
+        merchant.wallet_id = Some("wallet-1".to_string());
+        assert!(deposit.is_for(&merchant));
+        merchant.wallet_id = Some("wallet-2".to_string());
+        assert!(!deposit.is_for(&merchant));
+    }
+
+    #[test]
+    fn confirmations_count_the_transfer_block() {
+        assert_eq!(confirmations(Some(100), Some(100)), Some(1));
+        assert_eq!(confirmations(Some(100), Some(111)), Some(12));
+        assert_eq!(confirmations(Some(100), Some(99)), None);
+        assert_eq!(confirmations(None, Some(100)), None);
+        assert_eq!(confirmations(Some(100), None), None);
+    }
+
+    #[test]
+    fn failed_attempts_back_off_until_the_delivery_fails() {
+        let now = Utc::now();
+        let mut delivery = new_delivery(
+            &subscription(Vec::new()),
+            &event(WebhookEventType::FiatCompleted),
+            now,
+        );
+        assert_eq!(delivery.payload["event"], "fiat.completed");
+        assert_eq!(delivery.payload["id"], delivery.delivery_id.as_str());
+
+        record_attempt(
+            &mut delivery,
+            Some(500),
+            Some("Endpoint answered 500".into()),
+            now,
+        );
+        assert_eq!(delivery.status, WebhookDeliveryStatus::Pending);
+        assert_eq!(delivery.next_attempt_at, Some(now + Duration::seconds(30)));
+        record_attempt(&mut delivery, None, Some("timeout".into()), now);
+        assert_eq!(delivery.next_attempt_at, Some(now + Duration::seconds(60)));
+        assert_eq!(retry_delay(MAX_ATTEMPTS + 5), Duration::hours(6));
+
+        while delivery.status == WebhookDeliveryStatus::Pending {
+            record_attempt(&mut delivery, None, Some("timeout".into()), now);
+        }
+        assert_eq!(delivery.status, WebhookDeliveryStatus::Failed);
+        assert_eq!(delivery.attempts, MAX_ATTEMPTS);
+        assert_eq!(delivery.next_attempt_at, None);
+
+        let mut delivered = new_delivery(
+            &subscription(Vec::new()),
+            &event(WebhookEventType::FiatCompleted),
+            now,
+        );
+        record_attempt(&mut delivered, Some(204), None, now);
+        assert_eq!(delivered.status, WebhookDeliveryStatus::Delivered);
+        assert_eq!(delivered.last_status_code, Some(204));
+    }
+}
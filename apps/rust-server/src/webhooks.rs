@@ -0,0 +1,335 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! # Outbound Webhook Signatures
+//!
+//! Deliveries to partners carry a [`SIGNATURE_HEADER`] of the form
+//! `t=<unix seconds>,kid=<key id>,v1=<signature>`, where the signature is an
+//! Ed25519 signature (base64url, unpadded) over `"{t}.{raw body}"`.
+//!
+//! Partners fetch the public keys from `GET /v1/webhooks/signing-key` and
+//! check deliveries with [`verify_signature`] or its equivalent in their own
+//! stack: pick the key named by `kid`, reject timestamps outside the
+//! tolerance, then verify. During a rotation both the new key and the
+//! retiring one are published, so either signature validates until the
+//! overlap ends.
+//!
+//! Delivery bodies are produced with [`crate::canonical_json`], so a
+//! receiver that re-serializes the parsed event the same way gets the
+//! signed bytes back.
+//!
+//! The same keys sign transaction proof statements (see
+//! [`crate::api::tx_proofs`]).
+//!
+//! Deliveries to users' own webhook subscriptions (see
+//! [`crate::webhook_dispatcher`]) additionally carry an
+//! [`HMAC_SIGNATURE_HEADER`] of the form `t=<unix seconds>,v1=<hex>`: an
+//! HMAC-SHA256 over the same `"{t}.{raw body}"`, keyed with the
+//! subscription's secret. Receivers check it with [`verify_hmac_signature`].
+//!
+//! Private keys are created, used and destroyed through the
+//! [`SecretSigner`]; only the public keyring is kept in storage.
+
+use alloy::hex;
+use base64ct::{Base64UrlUnpadded, Encoding};
+use chrono::{DateTime, Duration, Utc};
+use hmac::{Hmac, Mac};
+use ring::signature::{UnparsedPublicKey, ED25519};
+use sha2::Sha256;
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::secret_signer::{SecretSigner, SecretSignerError};
+use crate::storage::{
+    EncryptedStorage, StorageError, StoredWebhookKey, StoredWebhookKeyring, WebhookKeyRepository,
+};
+
+/// Header carrying the delivery signature.
+pub const SIGNATURE_HEADER: &str = "relational-signature";
+
+/// Header carrying the HMAC signature of a subscription delivery.
+pub const HMAC_SIGNATURE_HEADER: &str = "relational-webhook-signature";
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Default accepted clock difference between signing and verification.
+pub const DEFAULT_TOLERANCE_SECS: i64 = 300;
+
+/// Errors from signing or verifying a webhook delivery.
+#[derive(Debug, Error)]
+pub enum WebhookSignatureError {
+    #[error("Malformed signature header: {0}")]
+    MalformedHeader(String),
+    #[error("Unknown signing key: {0}")]
+    UnknownKey(String),
+    #[error("Signature timestamp outside tolerance")]
+    Expired,
+    #[error("Signature does not match payload")]
+    Invalid,
+    #[error("Signing key unavailable: {0}")]
+    Storage(#[from] StorageError),
+    #[error("Signer failed: {0}")]
+    Signer(#[from] SecretSignerError),
+}
+
+/// A public key a receiver accepts, as published by the signing-key endpoint.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebhookPublicKey {
+    pub key_id: String,
+    /// Raw Ed25519 public key, base64url without padding.
+    pub public_key: String,
+}
+
+/// Bytes covered by the signature.
+fn signed_payload(timestamp: i64, body: &[u8]) -> Vec<u8> {
+    let mut payload = format!("{timestamp}.").into_bytes();
+    payload.extend_from_slice(body);
+    payload
+}
+
+/// Generate a key pair with the signer and return its public half.
+async fn new_signing_key(
+    signer: &SecretSigner,
+    now: DateTime<Utc>,
+) -> Result<StoredWebhookKey, WebhookSignatureError> {
+    let key_id = format!("whk_{}", &Uuid::new_v4().simple().to_string()[..16]);
+    let public_key = signer.create_signing_key(&key_id).await?;
+    Ok(StoredWebhookKey {
+        key_id,
+        public_key: Base64UrlUnpadded::encode_string(&public_key),
+        created_at: now,
+        retires_at: None,
+    })
+}
+
+/// Create the first signing key if there is none, otherwise return the
+/// existing keyring.
+pub async fn bootstrap_signing_key(
+    storage: &EncryptedStorage,
+    signer: &SecretSigner,
+) -> Result<StoredWebhookKeyring, WebhookSignatureError> {
+    let repo = WebhookKeyRepository::new(storage);
+    match repo.get() {
+        Err(StorageError::NotFound(_)) => {}
+        existing => return Ok(existing?),
+    }
+    let key = new_signing_key(signer, Utc::now()).await?;
+    Ok(repo.initialize(key)?)
+}
+
+/// Make a fresh key active. The previous active key stays valid for
+/// `overlap`; keys whose overlap already ended are destroyed.
+pub async fn rotate_signing_key(
+    storage: &EncryptedStorage,
+    signer: &SecretSigner,
+    overlap: Duration,
+    now: DateTime<Utc>,
+) -> Result<StoredWebhookKeyring, WebhookSignatureError> {
+    bootstrap_signing_key(storage, signer).await?;
+    let key = new_signing_key(signer, now).await?;
+    let (keyring, expired) = WebhookKeyRepository::new(storage).rotate(key, overlap, now)?;
+    for key in expired {
+        if let Err(error) = signer.delete_signing_key(&key.key_id).await {
+            tracing::warn!(key_id = %key.key_id, error = %error, "Failed to destroy retired webhook key");
+        }
+    }
+    Ok(keyring)
+}
+
+/// Sign `body` with the active key and return the header value.
+pub async fn sign_payload(
+    storage: &EncryptedStorage,
+    signer: &SecretSigner,
+    body: &[u8],
+    timestamp: i64,
+) -> Result<String, WebhookSignatureError> {
+    let keyring = WebhookKeyRepository::new(storage).get()?;
+    let signature = signer
+        .sign(&keyring.active_key_id, &signed_payload(timestamp, body))
+        .await?;
+    Ok(format!(
+        "t={timestamp},kid={},v1={}",
+        keyring.active_key_id,
+        Base64UrlUnpadded::encode_string(&signature)
+    ))
+}
+
+/// Verify a delivery's signature header against the published keys.
+pub fn verify_signature(
+    header: &str,
+    body: &[u8],
+    keys: &[WebhookPublicKey],
+    now_unix: i64,
+    tolerance_secs: i64,
+) -> Result<(), WebhookSignatureError> {
+    let (mut timestamp, mut key_id, mut signature) = (None, None, None);
+    for part in header.split(',') {
+        match part.trim().split_once('=') {
+            Some(("t", value)) => timestamp = value.parse::<i64>().ok(),
+            Some(("kid", value)) => key_id = Some(value),
+            Some(("v1", value)) => signature = Some(value),
+            _ => {}
+        }
+    }
+    let malformed =
+        |field: &str| WebhookSignatureError::MalformedHeader(format!("missing {field}"));
+    let timestamp = timestamp.ok_or_else(|| malformed("t"))?;
+    let key_id = key_id.ok_or_else(|| malformed("kid"))?;
+    let signature = signature.ok_or_else(|| malformed("v1"))?;
+
+    if (now_unix - timestamp).abs() > tolerance_secs {
+        return Err(WebhookSignatureError::Expired);
+    }
+    let key = keys
+        .iter()
+        .find(|k| k.key_id == key_id)
+        .ok_or_else(|| WebhookSignatureError::UnknownKey(key_id.to_string()))?;
+    let public_key = Base64UrlUnpadded::decode_vec(&key.public_key)
+        .map_err(|_| WebhookSignatureError::UnknownKey(key_id.to_string()))?;
+    let signature = Base64UrlUnpadded::decode_vec(signature)
+        .map_err(|_| WebhookSignatureError::MalformedHeader("v1 is not base64url".into()))?;
+
+    UnparsedPublicKey::new(&ED25519, public_key)
+        .verify(&signed_payload(timestamp, body), &signature)
+        .map_err(|_| WebhookSignatureError::Invalid)
+}
+
+fn hmac_hex(secret: &str, timestamp: i64, body: &[u8]) -> String {
+    let mut mac =
+        HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC can take key of any size");
+    mac.update(&signed_payload(timestamp, body));
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// Sign `body` with a subscription secret and return the header value.
+pub fn hmac_signature(secret: &str, body: &[u8], timestamp: i64) -> String {
+    format!("t={timestamp},v1={}", hmac_hex(secret, timestamp, body))
+}
+
+/// Verify a subscription delivery's HMAC header.
+pub fn verify_hmac_signature(
+    header: &str,
+    body: &[u8],
+    secret: &str,
+    now_unix: i64,
+    tolerance_secs: i64,
+) -> Result<(), WebhookSignatureError> {
+    let (mut timestamp, mut signature) = (None, None);
+    for part in header.split(',') {
+        match part.trim().split_once('=') {
+            Some(("t", value)) => timestamp = value.parse::<i64>().ok(),
+            Some(("v1", value)) => signature = Some(value),
+            _ => {}
+        }
+    }
+    let timestamp =
+        timestamp.ok_or_else(|| WebhookSignatureError::MalformedHeader("missing t".into()))?;
+    let signature =
+        signature.ok_or_else(|| WebhookSignatureError::MalformedHeader("missing v1".into()))?;
+    if (now_unix - timestamp).abs() > tolerance_secs {
+        return Err(WebhookSignatureError::Expired);
+    }
+    let expected = hmac_hex(secret, timestamp, body);
+    let matches = expected.len() == signature.len()
+        && expected
+            .bytes()
+            .zip(signature.bytes())
+            .fold(0u8, |acc, (a, b)| acc | (a ^ b))
+            == 0;
+    if matches {
+        Ok(())
+    } else {
+        Err(WebhookSignatureError::Invalid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::StoragePaths;
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    fn published(storage: &EncryptedStorage) -> Vec<WebhookPublicKey> {
+        let keyring = WebhookKeyRepository::new(storage).get().unwrap();
+        keyring
+            .keys
+            .iter()
+            .map(|k| WebhookPublicKey {
+                key_id: k.key_id.clone(),
+                public_key: k.public_key.clone(),
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn signatures_verify_across_a_rotation_and_reject_tampering() {
+        let temp = TempDir::new().unwrap();
+        let mut storage = EncryptedStorage::new(StoragePaths::new(temp.path()));
+        storage.initialize().unwrap();
+        let storage = Arc::new(storage);
+        let signer = SecretSigner::enclave(storage.clone());
+        bootstrap_signing_key(&storage, &signer).await.unwrap();
+
+        let body = br#"{"event":"fiat.settled"}"#;
+        let now = 1_800_000_000;
+        let old_header = sign_payload(&storage, &signer, body, now).await.unwrap();
+
+        rotate_signing_key(&storage, &signer, Duration::hours(24), Utc::now())
+            .await
+            .unwrap();
+        let new_header = sign_payload(&storage, &signer, body, now).await.unwrap();
+        assert_ne!(old_header, new_header);
+
+        let keys = published(&storage);
+        for header in [&old_header, &new_header] {
+            verify_signature(header, body, &keys, now + 10, DEFAULT_TOLERANCE_SECS).unwrap();
+        }
+        assert!(matches!(
+            verify_signature(&new_header, b"{}", &keys, now, DEFAULT_TOLERANCE_SECS),
+            Err(WebhookSignatureError::Invalid)
+        ));
+        assert!(matches!(
+            verify_signature(&new_header, body, &keys, now + 600, DEFAULT_TOLERANCE_SECS),
+            Err(WebhookSignatureError::Expired)
+        ));
+        assert!(matches!(
+            verify_signature(&old_header, body, &keys[1..], now, DEFAULT_TOLERANCE_SECS),
+            Err(WebhookSignatureError::UnknownKey(_))
+        ));
+
+        // Once its overlap has ended, the first key is destroyed.
+        let later = Utc::now() + Duration::hours(25);
+        rotate_signing_key(&storage, &signer, Duration::hours(24), later)
+            .await
+            .unwrap();
+        assert!(signer.sign(&keys[0].key_id, body).await.is_err());
+    }
+
+    #[test]
+    fn hmac_signatures_verify_only_with_the_secret_and_in_time() {
+        let body = br#"{"type":"fiat.completed"}"#;
+        let now = 1_800_000_000;
+        let header = hmac_signature("secret-1", body, now);
+        assert!(header.starts_with("t=1800000000,v1="));
+
+        verify_hmac_signature(&header, body, "secret-1", now + 10, DEFAULT_TOLERANCE_SECS).unwrap();
+        assert!(matches!(
+            verify_hmac_signature(&header, body, "secret-2", now, DEFAULT_TOLERANCE_SECS),
+            Err(WebhookSignatureError::Invalid)
+        ));
+        assert!(matches!(
+            verify_hmac_signature(&header, b"{}", "secret-1", now, DEFAULT_TOLERANCE_SECS),
+            Err(WebhookSignatureError::Invalid)
+        ));
+        assert!(matches!(
+            verify_hmac_signature(&header, body, "secret-1", now + 600, DEFAULT_TOLERANCE_SECS),
+            Err(WebhookSignatureError::Expired)
+        ));
+        assert!(matches!(
+            verify_hmac_signature("v1=00", body, "secret-1", now, DEFAULT_TOLERANCE_SECS),
+            Err(WebhookSignatureError::MalformedHeader(_))
+        ));
+    }
+}
@@ -0,0 +1,143 @@
+// SPDX-License-Identifier: AGPL-3.0-or-later
+//
+// Copyright (C) 2026 Relational Network
+
+//! # Background Worker Heartbeats
+//!
+//! Each background loop records a heartbeat after every iteration. The admin
+//! overview reports a worker as stale once its last heartbeat is older than
+//! [`Worker::stale_after_secs`] ([`STALE_AFTER_SECS`] for the fast loops),
+//! which catches loops that hang on a stuck RPC call as well as tasks that
+//! exited.
+//!
+//! Heartbeats are process-local and start empty, so a worker that never ran
+//! (e.g. the indexer without token contracts) reports no heartbeat at all.
+
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+/// Heartbeat age after which a fast-looping worker counts as stale.
+pub const STALE_AFTER_SECS: i64 = 120;
+
+/// A background task that reports heartbeats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Worker {
+    /// Canary transfer worker (opt-in).
+    Canary,
+    /// Claimable transfer expiry worker.
+    ClaimExpiry,
+    /// ERC-20 transfer indexer.
+    EventIndexer,
+    /// Fiat request poller.
+    FiatPoller,
+    /// Monthly wallet insights aggregator.
+    Insights,
+    /// Accounting journal poster.
+    Journal,
+    /// Clerk JWKS refresh (when `CLERK_JWKS_URL` is set).
+    JwksRefresh,
+    /// Orphaned storage artifact sweeper.
+    OrphanSweeper,
+    /// Daily token price recorder.
+    PriceRecorder,
+    /// Pre-generated wallet key pool (opt-in).
+    WalletPool,
+    /// Webhook subscription delivery.
+    WebhookDispatcher,
+}
+
+impl Worker {
+    /// All workers, in reporting order.
+    pub const ALL: [Worker; 11] = [
+        Worker::Canary,
+        Worker::ClaimExpiry,
+        Worker::EventIndexer,
+        Worker::FiatPoller,
+        Worker::Insights,
+        Worker::Journal,
+        Worker::JwksRefresh,
+        Worker::OrphanSweeper,
+        Worker::PriceRecorder,
+        Worker::WalletPool,
+        Worker::WebhookDispatcher,
+    ];
+
+    /// Heartbeat age after which this worker counts as stale.
+    pub fn stale_after_secs(self) -> i64 {
+        match self {
+            Worker::EventIndexer | Worker::FiatPoller => STALE_AFTER_SECS,
+            // Allow one missed run.
+            Worker::PriceRecorder => {
+                2 * crate::price_recorder::RECORD_INTERVAL_SECS as i64 + STALE_AFTER_SECS
+            }
+            Worker::ClaimExpiry => {
+                2 * crate::claim_expiry::EXPIRY_INTERVAL_SECS as i64 + STALE_AFTER_SECS
+            }
+            Worker::Insights => {
+                2 * crate::insights::INSIGHTS_INTERVAL_SECS as i64 + STALE_AFTER_SECS
+            }
+            Worker::Journal => {
+                2 * crate::journal::JOURNAL_SYNC_INTERVAL_SECS as i64 + STALE_AFTER_SECS
+            }
+            Worker::OrphanSweeper => {
+                2 * crate::orphan_sweeper::SWEEP_INTERVAL_SECS as i64 + STALE_AFTER_SECS
+            }
+            Worker::WalletPool => {
+                2 * crate::wallet_pool::REFILL_INTERVAL_SECS as i64 + STALE_AFTER_SECS
+            }
+            Worker::WebhookDispatcher => {
+                2 * crate::webhook_dispatcher::DISPATCH_INTERVAL_SECS as i64 + STALE_AFTER_SECS
+            }
+            Worker::JwksRefresh => {
+                2 * crate::auth::jwks::refresh_interval_from_env().as_secs() as i64
+                    + STALE_AFTER_SECS
+            }
+            Worker::Canary => {
+                crate::canary::CanaryConfig::from_env()
+                    .map_or(STALE_AFTER_SECS, |config| config.stale_after_secs())
+                    + STALE_AFTER_SECS
+            }
+        }
+    }
+}
+
+static HEARTBEATS: OnceLock<Mutex<HashMap<Worker, DateTime<Utc>>>> = OnceLock::new();
+
+fn heartbeats() -> &'static Mutex<HashMap<Worker, DateTime<Utc>>> {
+    HEARTBEATS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Record that `worker` finished an iteration.
+pub fn record_heartbeat(worker: Worker) {
+    heartbeats()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(worker, Utc::now());
+}
+
+/// Time of the worker's most recent heartbeat, if it ever reported one.
+pub fn last_heartbeat(worker: Worker) -> Option<DateTime<Utc>> {
+    heartbeats()
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(&worker)
+        .copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn heartbeats_are_tracked_per_worker() {
+        record_heartbeat(Worker::FiatPoller);
+        let first = last_heartbeat(Worker::FiatPoller).unwrap();
+        record_heartbeat(Worker::FiatPoller);
+        assert!(last_heartbeat(Worker::FiatPoller).unwrap() >= first);
+    }
+}